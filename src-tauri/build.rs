@@ -1,3 +1,51 @@
+use std::fmt::Write;
+use std::fs;
+
+include!("src/command_groups.rs");
+
+const GROUPS_PERMISSION_FILE: &str = "permissions/command-groups.toml";
+
+/// Permission sets for `COMMAND_GROUPS`, built from the per-command
+/// `allow-*` permissions Tauri generates
+fn command_groups_toml() -> String {
+    let mut toml =
+        String::from("# Generated by build.rs from src/command_groups.rs; do not edit.\n");
+    for (identifier, description, commands) in COMMAND_GROUPS {
+        let permissions: Vec<String> = commands
+            .iter()
+            .map(|c| format!("  \"allow-{}\",", c.replace('_', "-")))
+            .collect();
+        write!(
+            toml,
+            "\n[[set]]\nidentifier = \"{}\"\ndescription = \"{}\"\npermissions = [\n{}\n]\n",
+            identifier,
+            description,
+            permissions.join("\n")
+        )
+        .unwrap();
+    }
+    toml
+}
+
 fn main() {
-    tauri_build::build()
+    println!("cargo:rerun-if-changed=src/command_groups.rs");
+
+    // Only write when the groups change, so the file doesn't retrigger builds
+    let toml = command_groups_toml();
+    if fs::read_to_string(GROUPS_PERMISSION_FILE).ok().as_deref() != Some(toml.as_str()) {
+        fs::create_dir_all("permissions").expect("failed to create permissions dir");
+        fs::write(GROUPS_PERMISSION_FILE, toml).expect("failed to write command groups");
+    }
+
+    let commands: Vec<&'static str> = READ_ONLY_COMMANDS
+        .iter()
+        .chain(MUTATING_COMMANDS)
+        .chain(AI_COMMANDS)
+        .copied()
+        .collect();
+    tauri_build::try_build(
+        tauri_build::Attributes::new()
+            .app_manifest(tauri_build::AppManifest::new().commands(commands.leak())),
+    )
+    .expect("failed to run tauri-build");
 }
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Attachments = { id: string, campaign_id: string, entity_type: string, entity_id: string, role: string, file_name: string, mime_type: string, size_bytes: bigint, 
+/**
+ * Hex SHA-256 of `data`
+ */
+content_hash: string | null, source: string | null, prompt: string | null, created_at: string, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type EncounterTables = { id: string, campaign_id: string, location_id: string, name: string, description: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type GoalAdvances = { id: string, campaign_id: string, goal_id: string, ticks: number, filled: number | null, segments: number | null, status: string | null, note: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type HandoutDeliveries = { id: string, campaign_id: string, handout_id: string, player_id: string, status: string, sent_at: string, read_at: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type MirrorState = { campaign_id: string, enabled: boolean, path: string | null, auto_commit: boolean, last_watermark: string | null, last_mirrored_at: string | null, updated_at: string, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Players = { id: string, campaign_id: string, name: string, preferences: string | null, boundaries: string | null, notes: string | null, contact_email: string | null, contact_handle: string | null, availability_json: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
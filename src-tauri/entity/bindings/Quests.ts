@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Quests = { id: string, campaign_id: string, name: string, status: string, plot_type: string, description: string | null, hook: string | null, objectives: string | null, complications: string | null, resolution: string | null, reward: string | null, color: string | null, icon: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
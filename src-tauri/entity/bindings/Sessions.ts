@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Sessions = { id: string, campaign_id: string, session_number: number, date: string | null, title: string | null, planned_content: string | null, notes: string | null, summary: string | null, highlights: string | null, started_at: string | null, ended_at: string | null, breaks_json: string | null, timeline_event_id: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -18,6 +18,11 @@ pub struct Model {
     pub settings_json: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub archived_at: Option<DateTimeUtc>,
+    pub archive_path: Option<String>,
+    pub locked_at: Option<DateTimeUtc>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -42,6 +47,8 @@ pub enum Relation {
     Secrets,
     #[sea_orm(has_many = "super::sessions::Entity")]
     Sessions,
+    #[sea_orm(has_one = "super::sync_state::Entity")]
+    SyncState,
     #[sea_orm(has_many = "super::tags::Entity")]
     Tags,
     #[sea_orm(has_many = "super::timeline_events::Entity")]
@@ -108,6 +115,12 @@ impl Related<super::sessions::Entity> for Entity {
     }
 }
 
+impl Related<super::sync_state::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SyncState.def()
+    }
+}
+
 impl Related<super::tags::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Tags.def()
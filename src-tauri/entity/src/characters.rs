@@ -27,8 +27,29 @@ pub struct Model {
     pub voice_notes: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub stat_block_json: Option<String>,
+    pub birth_year: Option<i32>,
+    pub birth_month: Option<i32>,
+    pub birth_day: Option<i32>,
+    pub death_year: Option<i32>,
+    pub death_month: Option<i32>,
+    pub death_day: Option<i32>,
+    pub death_event_id: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub aliases_json: Option<String>,
+    pub source_hero_id: Option<String>,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub accent: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub mannerisms_json: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub catchphrases_json: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub speech_sample: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -14,6 +14,9 @@ pub struct Model {
     pub entity_type: String,
     #[sea_orm(primary_key, auto_increment = false)]
     pub entity_id: String,
+    /// Absent from bundles exported before entity tags were synced
+    #[serde(default)]
+    pub created_at: DateTimeUtc,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
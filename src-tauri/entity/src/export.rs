@@ -13,18 +13,51 @@ mod tests {
     #[ignore] // Only run when explicitly called (e.g., by generate-entities.sh)
     fn export_bindings() {
         // Export all entity models to TypeScript
+        crate::annotations::Model::export_all().unwrap();
+        crate::app_meta::Model::export_all().unwrap();
         crate::campaigns::Model::export_all().unwrap();
         crate::characters::Model::export_all().unwrap();
+        crate::content_warnings::Model::export_all().unwrap();
+        crate::dispositions::Model::export_all().unwrap();
+        crate::drafts::Model::export_all().unwrap();
+        crate::encounter_entries::Model::export_all().unwrap();
+        crate::encounter_rolls::Model::export_all().unwrap();
+        crate::encounter_tables::Model::export_all().unwrap();
+        crate::entity_embeddings::Model::export_all().unwrap();
         crate::entity_tags::Model::export_all().unwrap();
+        crate::goal_advances::Model::export_all().unwrap();
+        crate::handout_deliveries::Model::export_all().unwrap();
+        crate::handouts::Model::export_all().unwrap();
+        crate::hero_items::Model::export_all().unwrap();
         crate::heroes::Model::export_all().unwrap();
+        crate::inbox_items::Model::export_all().unwrap();
+        crate::items::Model::export_all().unwrap();
         crate::locations::Model::export_all().unwrap();
+        crate::mirror_state::Model::export_all().unwrap();
+        crate::organization_goals::Model::export_all().unwrap();
+        crate::organization_holdings::Model::export_all().unwrap();
         crate::organizations::Model::export_all().unwrap();
         crate::players::Model::export_all().unwrap();
+        crate::price_list::Model::export_all().unwrap();
+        crate::price_modifiers::Model::export_all().unwrap();
+        crate::progress_clocks::Model::export_all().unwrap();
+        crate::prompt_templates::Model::export_all().unwrap();
         crate::quests::Model::export_all().unwrap();
+        crate::recall_cards::Model::export_all().unwrap();
         crate::relationships::Model::export_all().unwrap();
+        crate::search_history::Model::export_all().unwrap();
         crate::secrets::Model::export_all().unwrap();
+        crate::session_absences::Model::export_all().unwrap();
+        crate::session_transcripts::Model::export_all().unwrap();
         crate::sessions::Model::export_all().unwrap();
+        crate::snippets::Model::export_all().unwrap();
+        crate::srd_cache::Model::export_all().unwrap();
+        crate::strength_changes::Model::export_all().unwrap();
+        crate::sync_state::Model::export_all().unwrap();
         crate::tags::Model::export_all().unwrap();
+        crate::timeline_branches::Model::export_all().unwrap();
         crate::timeline_events::Model::export_all().unwrap();
+        crate::tombstones::Model::export_all().unwrap();
+        crate::whereabouts::Model::export_all().unwrap();
     }
 }
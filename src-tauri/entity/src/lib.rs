@@ -4,18 +4,53 @@ pub mod prelude;
 
 pub mod ai_conversations;
 pub mod ai_messages;
+pub mod ai_usage;
+pub mod annotations;
+pub mod app_meta;
+pub mod attachments;
 pub mod campaigns;
 pub mod characters;
+pub mod content_warnings;
+pub mod dispositions;
+pub mod drafts;
+pub mod encounter_entries;
+pub mod encounter_rolls;
+pub mod encounter_tables;
+pub mod entity_embeddings;
 pub mod entity_tags;
+pub mod goal_advances;
+pub mod handout_deliveries;
+pub mod handouts;
+pub mod hero_items;
 pub mod heroes;
+pub mod inbox_items;
+pub mod items;
 pub mod locations;
+pub mod mirror_state;
+pub mod organization_goals;
+pub mod organization_holdings;
 pub mod organizations;
 pub mod players;
+pub mod price_list;
+pub mod price_modifiers;
+pub mod progress_clocks;
+pub mod prompt_templates;
 pub mod quests;
+pub mod recall_cards;
 pub mod relationships;
+pub mod search_history;
 pub mod secrets;
+pub mod session_absences;
+pub mod session_transcripts;
 pub mod sessions;
+pub mod snippets;
+pub mod srd_cache;
+pub mod strength_changes;
+pub mod sync_state;
 pub mod tags;
+pub mod timeline_branches;
 pub mod timeline_events;
+pub mod tombstones;
+pub mod whereabouts;
 
 mod export;
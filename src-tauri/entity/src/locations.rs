@@ -18,8 +18,17 @@ pub struct Model {
     pub description: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub gm_notes: Option<String>,
+    pub color: Option<String>,
+    pub icon: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub population: Option<i64>,
+    pub government_type: Option<String>,
+    pub dominant_organization_id: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub economy_notes: Option<String>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
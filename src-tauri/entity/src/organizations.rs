@@ -24,8 +24,12 @@ pub struct Model {
     #[sea_orm(column_type = "Text", nullable)]
     pub secrets: Option<String>,
     pub is_active: bool,
+    pub color: Option<String>,
+    pub icon: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -18,8 +18,14 @@ pub struct Model {
     pub boundaries: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub notes: Option<String>,
+    pub contact_email: Option<String>,
+    pub contact_handle: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub availability_json: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -2,16 +2,51 @@
 
 pub use super::ai_conversations::Entity as AiConversations;
 pub use super::ai_messages::Entity as AiMessages;
+pub use super::ai_usage::Entity as AiUsage;
+pub use super::annotations::Entity as Annotations;
+pub use super::app_meta::Entity as AppMeta;
+pub use super::attachments::Entity as Attachments;
 pub use super::campaigns::Entity as Campaigns;
 pub use super::characters::Entity as Characters;
+pub use super::content_warnings::Entity as ContentWarnings;
+pub use super::dispositions::Entity as Dispositions;
+pub use super::drafts::Entity as Drafts;
+pub use super::encounter_entries::Entity as EncounterEntries;
+pub use super::encounter_rolls::Entity as EncounterRolls;
+pub use super::encounter_tables::Entity as EncounterTables;
+pub use super::entity_embeddings::Entity as EntityEmbeddings;
 pub use super::entity_tags::Entity as EntityTags;
+pub use super::goal_advances::Entity as GoalAdvances;
+pub use super::handout_deliveries::Entity as HandoutDeliveries;
+pub use super::handouts::Entity as Handouts;
+pub use super::hero_items::Entity as HeroItems;
 pub use super::heroes::Entity as Heroes;
+pub use super::inbox_items::Entity as InboxItems;
+pub use super::items::Entity as Items;
 pub use super::locations::Entity as Locations;
+pub use super::mirror_state::Entity as MirrorState;
+pub use super::organization_goals::Entity as OrganizationGoals;
+pub use super::organization_holdings::Entity as OrganizationHoldings;
 pub use super::organizations::Entity as Organizations;
 pub use super::players::Entity as Players;
+pub use super::price_list::Entity as PriceList;
+pub use super::price_modifiers::Entity as PriceModifiers;
+pub use super::progress_clocks::Entity as ProgressClocks;
+pub use super::prompt_templates::Entity as PromptTemplates;
 pub use super::quests::Entity as Quests;
+pub use super::recall_cards::Entity as RecallCards;
 pub use super::relationships::Entity as Relationships;
+pub use super::search_history::Entity as SearchHistory;
 pub use super::secrets::Entity as Secrets;
+pub use super::session_absences::Entity as SessionAbsences;
+pub use super::session_transcripts::Entity as SessionTranscripts;
 pub use super::sessions::Entity as Sessions;
+pub use super::snippets::Entity as Snippets;
+pub use super::srd_cache::Entity as SrdCache;
+pub use super::strength_changes::Entity as StrengthChanges;
+pub use super::sync_state::Entity as SyncState;
 pub use super::tags::Entity as Tags;
+pub use super::timeline_branches::Entity as TimelineBranches;
 pub use super::timeline_events::Entity as TimelineEvents;
+pub use super::tombstones::Entity as Tombstones;
+pub use super::whereabouts::Entity as Whereabouts;
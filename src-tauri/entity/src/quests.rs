@@ -26,8 +26,12 @@ pub struct Model {
     pub resolution: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub reward: Option<String>,
+    pub color: Option<String>,
+    pub icon: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -20,8 +20,12 @@ pub struct Model {
     pub known_by: Option<String>,
     pub revealed: bool,
     pub revealed_in_session: Option<i32>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub prerequisites_json: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
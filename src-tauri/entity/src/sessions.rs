@@ -22,8 +22,15 @@ pub struct Model {
     pub summary: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub highlights: Option<String>,
+    pub started_at: Option<DateTimeUtc>,
+    pub ended_at: Option<DateTimeUtc>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub breaks_json: Option<String>,
+    pub timeline_event_id: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -14,6 +14,8 @@ pub struct Model {
     pub name: String,
     pub color: Option<String>,
     pub created_at: DateTimeUtc,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -18,8 +18,11 @@ pub struct Model {
     pub description: Option<String>,
     pub significance: String,
     pub is_public: bool,
+    pub branch_id: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
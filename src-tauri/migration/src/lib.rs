@@ -18,6 +18,54 @@ mod m20251129_000001_create_ai_conversations;
 mod m20251129_000002_create_ai_messages;
 mod m20251129_000003_add_agent_messages_to_ai_conversations;
 mod m20251218_000001_drop_detail_level;
+mod m20260105_000001_create_tombstones;
+mod m20260112_000001_create_sync_state;
+mod m20260119_000001_add_character_life_dates;
+mod m20260120_000001_add_character_aliases;
+mod m20260121_000001_add_conversion_sources;
+mod m20260122_000001_create_items;
+mod m20260122_000002_create_hero_items;
+mod m20260123_000001_add_player_scheduling;
+mod m20260123_000002_create_session_absences;
+mod m20260124_000001_create_handouts;
+mod m20260124_000002_create_handout_deliveries;
+mod m20260125_000001_add_session_clock;
+mod m20260126_000001_add_session_timeline_event;
+mod m20260127_000001_create_timeline_branches;
+mod m20260127_000002_add_timeline_event_branch;
+mod m20260128_000001_create_snippets;
+mod m20260129_000001_create_dispositions;
+mod m20260129_000002_add_secret_prerequisites;
+mod m20260130_000001_create_srd_cache;
+mod m20260131_000001_create_session_transcripts;
+mod m20260201_000001_create_prompt_templates;
+mod m20260202_000001_create_ai_usage;
+mod m20260203_000001_create_attachments;
+mod m20260204_000001_add_campaign_archive;
+mod m20260205_000001_add_location_settlement_fields;
+mod m20260206_000001_create_organization_holdings;
+mod m20260207_000001_create_whereabouts;
+mod m20260208_000001_create_search_history;
+mod m20260209_000001_create_mirror_state;
+mod m20260210_000001_add_attachment_content_hash;
+mod m20260211_000001_create_app_meta;
+mod m20260212_000001_create_drafts;
+mod m20260213_000001_create_inbox_items;
+mod m20260214_000001_add_campaign_lock;
+mod m20260215_000001_create_strength_changes;
+mod m20260216_000001_add_entity_appearance;
+mod m20260217_000001_create_recall_cards;
+mod m20260218_000001_create_progress_clocks;
+mod m20260219_000001_create_encounter_tables;
+mod m20260220_000001_create_price_list;
+mod m20260221_000001_create_organization_goals;
+mod m20260222_000001_add_character_roleplay;
+mod m20260223_000001_create_content_warnings;
+mod m20260224_000001_create_annotations;
+mod m20260225_000001_add_authorship;
+mod m20260226_000001_backfill_attachment_hashes;
+mod m20260227_000001_add_entity_tag_sync;
+mod m20260228_000001_create_entity_embeddings;
 
 pub struct Migrator;
 
@@ -43,6 +91,54 @@ impl MigratorTrait for Migrator {
             Box::new(m20251129_000002_create_ai_messages::Migration),
             Box::new(m20251129_000003_add_agent_messages_to_ai_conversations::Migration),
             Box::new(m20251218_000001_drop_detail_level::Migration),
+            Box::new(m20260105_000001_create_tombstones::Migration),
+            Box::new(m20260112_000001_create_sync_state::Migration),
+            Box::new(m20260119_000001_add_character_life_dates::Migration),
+            Box::new(m20260120_000001_add_character_aliases::Migration),
+            Box::new(m20260121_000001_add_conversion_sources::Migration),
+            Box::new(m20260122_000001_create_items::Migration),
+            Box::new(m20260122_000002_create_hero_items::Migration),
+            Box::new(m20260123_000001_add_player_scheduling::Migration),
+            Box::new(m20260123_000002_create_session_absences::Migration),
+            Box::new(m20260124_000001_create_handouts::Migration),
+            Box::new(m20260124_000002_create_handout_deliveries::Migration),
+            Box::new(m20260125_000001_add_session_clock::Migration),
+            Box::new(m20260126_000001_add_session_timeline_event::Migration),
+            Box::new(m20260127_000001_create_timeline_branches::Migration),
+            Box::new(m20260127_000002_add_timeline_event_branch::Migration),
+            Box::new(m20260128_000001_create_snippets::Migration),
+            Box::new(m20260129_000001_create_dispositions::Migration),
+            Box::new(m20260129_000002_add_secret_prerequisites::Migration),
+            Box::new(m20260130_000001_create_srd_cache::Migration),
+            Box::new(m20260131_000001_create_session_transcripts::Migration),
+            Box::new(m20260201_000001_create_prompt_templates::Migration),
+            Box::new(m20260202_000001_create_ai_usage::Migration),
+            Box::new(m20260203_000001_create_attachments::Migration),
+            Box::new(m20260204_000001_add_campaign_archive::Migration),
+            Box::new(m20260205_000001_add_location_settlement_fields::Migration),
+            Box::new(m20260206_000001_create_organization_holdings::Migration),
+            Box::new(m20260207_000001_create_whereabouts::Migration),
+            Box::new(m20260208_000001_create_search_history::Migration),
+            Box::new(m20260209_000001_create_mirror_state::Migration),
+            Box::new(m20260210_000001_add_attachment_content_hash::Migration),
+            Box::new(m20260211_000001_create_app_meta::Migration),
+            Box::new(m20260212_000001_create_drafts::Migration),
+            Box::new(m20260213_000001_create_inbox_items::Migration),
+            Box::new(m20260214_000001_add_campaign_lock::Migration),
+            Box::new(m20260215_000001_create_strength_changes::Migration),
+            Box::new(m20260216_000001_add_entity_appearance::Migration),
+            Box::new(m20260217_000001_create_recall_cards::Migration),
+            Box::new(m20260218_000001_create_progress_clocks::Migration),
+            Box::new(m20260219_000001_create_encounter_tables::Migration),
+            Box::new(m20260220_000001_create_price_list::Migration),
+            Box::new(m20260221_000001_create_organization_goals::Migration),
+            Box::new(m20260222_000001_add_character_roleplay::Migration),
+            Box::new(m20260223_000001_create_content_warnings::Migration),
+            Box::new(m20260224_000001_create_annotations::Migration),
+            Box::new(m20260225_000001_add_authorship::Migration),
+            Box::new(m20260226_000001_backfill_attachment_hashes::Migration),
+            Box::new(m20260227_000001_add_entity_tag_sync::Migration),
+            Box::new(m20260228_000001_create_entity_embeddings::Migration),
         ]
     }
 }
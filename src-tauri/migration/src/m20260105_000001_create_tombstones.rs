@@ -0,0 +1,117 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables that record a tombstone when a row is deleted, paired with the
+/// entity type string used throughout the app
+const TRACKED_TABLES: &[(&str, &str)] = &[
+    ("characters", "character"),
+    ("locations", "location"),
+    ("organizations", "organization"),
+    ("quests", "quest"),
+    ("heroes", "hero"),
+    ("players", "player"),
+    ("sessions", "session"),
+    ("timeline_events", "timeline_event"),
+    ("secrets", "secret"),
+    ("relationships", "relationship"),
+    ("tags", "tag"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // No foreign key to campaigns: cascaded deletes from a campaign still
+        // fire the triggers below, and the tombstones must survive the parent.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tombstones::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Tombstones::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Tombstones::CampaignId).string().not_null())
+                    .col(ColumnDef::new(Tombstones::EntityType).string().not_null())
+                    .col(ColumnDef::new(Tombstones::EntityId).string().not_null())
+                    .col(
+                        ColumnDef::new(Tombstones::DeletedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tombstones_campaign_deleted")
+                    .table(Tombstones::Table)
+                    .col(Tombstones::CampaignId)
+                    .col(Tombstones::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        // Timestamps are written in RFC 3339 form so they compare correctly
+        // against the values sqlx stores for `updated_at`.
+        for (table, entity_type) in TRACKED_TABLES {
+            db.execute_unprepared(&format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {table}_tombstone AFTER DELETE ON {table} BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, '{entity_type}', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#
+            ))
+            .await?;
+        }
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS campaigns_tombstone AFTER DELETE ON campaigns BEGIN
+                INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                VALUES (lower(hex(randomblob(16))), OLD.id, 'campaign', OLD.id,
+                        strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+            END;
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        for (table, _) in TRACKED_TABLES {
+            db.execute_unprepared(&format!("DROP TRIGGER IF EXISTS {table}_tombstone;"))
+                .await?;
+        }
+        db.execute_unprepared("DROP TRIGGER IF EXISTS campaigns_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Tombstones::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Tombstones {
+    Table,
+    Id,
+    CampaignId,
+    EntityType,
+    EntityId,
+    DeletedAt,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per campaign that has been configured for server sync
+        manager
+            .create_table(
+                Table::create()
+                    .table(SyncState::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SyncState::CampaignId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SyncState::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(SyncState::ServerUrl).string())
+                    .col(ColumnDef::new(SyncState::AuthToken).string())
+                    .col(ColumnDef::new(SyncState::LastPulledWatermark).string())
+                    .col(ColumnDef::new(SyncState::LastPushedWatermark).string())
+                    .col(ColumnDef::new(SyncState::LastSyncedAt).timestamp())
+                    .col(
+                        ColumnDef::new(SyncState::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sync_state_campaign")
+                            .from(SyncState::Table, SyncState::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SyncState::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SyncState {
+    Table,
+    CampaignId,
+    Enabled,
+    ServerUrl,
+    AuthToken,
+    LastPulledWatermark,
+    LastPushedWatermark,
+    LastSyncedAt,
+    UpdatedAt,
+}
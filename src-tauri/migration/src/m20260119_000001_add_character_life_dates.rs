@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Columns added to characters. SQLite only allows one change per ALTER.
+const DATE_COLUMNS: [Characters; 6] = [
+    Characters::BirthYear,
+    Characters::BirthMonth,
+    Characters::BirthDay,
+    Characters::DeathYear,
+    Characters::DeathMonth,
+    Characters::DeathDay,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in DATE_COLUMNS {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Characters::Table)
+                        .add_column(ColumnDef::new(column).integer())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // Timeline event created for the character's death, kept in step
+        // with the death date
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Characters::Table)
+                    .add_column(ColumnDef::new(Characters::DeathEventId).string())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let columns = DATE_COLUMNS
+            .into_iter()
+            .chain(std::iter::once(Characters::DeathEventId));
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Characters::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Characters {
+    Table,
+    BirthYear,
+    BirthMonth,
+    BirthDay,
+    DeathYear,
+    DeathMonth,
+    DeathDay,
+    DeathEventId,
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Expression indexed as a character's search content
+fn character_content(with_aliases: bool) -> String {
+    let mut content = "COALESCE(NEW.description, '') || ' ' || \
+                       COALESCE(NEW.personality, '') || ' ' || \
+                       COALESCE(NEW.motivations, '')"
+        .to_string();
+    if with_aliases {
+        content.push_str(
+            " || ' ' || COALESCE((SELECT group_concat(value, ' ') \
+             FROM json_each(CASE WHEN json_valid(NEW.aliases_json) \
+             THEN NEW.aliases_json END)), '')",
+        );
+    }
+    content
+}
+
+async fn create_character_triggers(
+    manager: &SchemaManager<'_>,
+    with_aliases: bool,
+) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    let content = character_content(with_aliases);
+
+    db.execute_unprepared("DROP TRIGGER IF EXISTS characters_ai;")
+        .await?;
+    db.execute_unprepared("DROP TRIGGER IF EXISTS characters_au;")
+        .await?;
+
+    db.execute_unprepared(&format!(
+        r#"
+        CREATE TRIGGER characters_ai AFTER INSERT ON characters BEGIN
+            INSERT INTO search_index(entity_type, entity_id, campaign_id, name, content)
+            VALUES ('character', NEW.id, NEW.campaign_id, NEW.name, {content});
+        END;
+        "#
+    ))
+    .await?;
+
+    db.execute_unprepared(&format!(
+        r#"
+        CREATE TRIGGER characters_au AFTER UPDATE ON characters BEGIN
+            DELETE FROM search_index WHERE entity_type = 'character' AND entity_id = OLD.id;
+            INSERT INTO search_index(entity_type, entity_id, campaign_id, name, content)
+            VALUES ('character', NEW.id, NEW.campaign_id, NEW.name, {content});
+        END;
+        "#
+    ))
+    .await?;
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // JSON array of alternate names: titles, nicknames, false identities
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Characters::Table)
+                    .add_column(ColumnDef::new(Characters::AliasesJson).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Index aliases with the rest of the character so search and
+        // mentions find them
+        create_character_triggers(manager, true).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Triggers reference the column, so restore them before dropping it
+        create_character_triggers(manager, false).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Characters::Table)
+                    .drop_column(Characters::AliasesJson)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Characters {
+    Table,
+    AliasesJson,
+}
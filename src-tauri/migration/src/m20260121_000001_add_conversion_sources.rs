@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Record which character a hero was converted from, and vice versa, so the
+/// original id can still be traced after the source row is removed
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Heroes::Table)
+                    .add_column(ColumnDef::new(Heroes::SourceCharacterId).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Characters::Table)
+                    .add_column(ColumnDef::new(Characters::SourceHeroId).string())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Characters::Table)
+                    .drop_column(Characters::SourceHeroId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Heroes::Table)
+                    .drop_column(Heroes::SourceCharacterId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Heroes {
+    Table,
+    SourceCharacterId,
+}
+
+#[derive(DeriveIden)]
+enum Characters {
+    Table,
+    SourceHeroId,
+}
@@ -0,0 +1,140 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Items::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Items::Id).string().not_null().primary_key())
+                    .col(ColumnDef::new(Items::CampaignId).string().not_null())
+                    .col(ColumnDef::new(Items::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Items::ItemType)
+                            .string()
+                            .not_null()
+                            .default("other"),
+                    )
+                    .col(ColumnDef::new(Items::Rarity).string())
+                    .col(ColumnDef::new(Items::Description).text())
+                    .col(
+                        ColumnDef::new(Items::RequiresAttunement)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Items::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Items::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_items_campaign")
+                            .from(Items::Table, Items::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_items_campaign")
+                    .table(Items::Table)
+                    .col(Items::CampaignId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        // Same search and tombstone triggers as the original entity tables
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS items_ai AFTER INSERT ON items BEGIN
+                INSERT INTO search_index(entity_type, entity_id, campaign_id, name, content)
+                VALUES ('item', NEW.id, NEW.campaign_id, NEW.name, COALESCE(NEW.description, ''));
+            END;
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS items_au AFTER UPDATE ON items BEGIN
+                DELETE FROM search_index WHERE entity_type = 'item' AND entity_id = OLD.id;
+                INSERT INTO search_index(entity_type, entity_id, campaign_id, name, content)
+                VALUES ('item', NEW.id, NEW.campaign_id, NEW.name, COALESCE(NEW.description, ''));
+            END;
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS items_ad AFTER DELETE ON items BEGIN
+                DELETE FROM search_index WHERE entity_type = 'item' AND entity_id = OLD.id;
+            END;
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS items_tombstone AFTER DELETE ON items BEGIN
+                INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'item', OLD.id,
+                        strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+            END;
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for trigger in ["items_ai", "items_au", "items_ad", "items_tombstone"] {
+            db.execute_unprepared(&format!("DROP TRIGGER IF EXISTS {trigger};"))
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Items::Table).to_owned())
+            .await
+    }
+}
+
+/// Item types enum values:
+/// weapon, armor, wondrous, potion, scroll, ring, wand, tool, treasure, quest, other
+#[derive(DeriveIden)]
+pub enum Items {
+    Table,
+    Id,
+    CampaignId,
+    Name,
+    ItemType,
+    Rarity,
+    Description,
+    RequiresAttunement,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,147 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000007_create_heroes::Heroes;
+use super::m20260122_000001_create_items::Items;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Rows carry their own id and campaign so they sync like any other
+        // entity; (hero_id, item_id) is kept unique by the commands rather
+        // than an index so merges of diverged copies can't fail on it.
+        manager
+            .create_table(
+                Table::create()
+                    .table(HeroItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HeroItems::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(HeroItems::CampaignId).string().not_null())
+                    .col(ColumnDef::new(HeroItems::HeroId).string().not_null())
+                    .col(ColumnDef::new(HeroItems::ItemId).string().not_null())
+                    .col(
+                        ColumnDef::new(HeroItems::Quantity)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(
+                        ColumnDef::new(HeroItems::Equipped)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(HeroItems::Attuned)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(HeroItems::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(HeroItems::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_hero_items_campaign")
+                            .from(HeroItems::Table, HeroItems::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_hero_items_hero")
+                            .from(HeroItems::Table, HeroItems::HeroId)
+                            .to(Heroes::Table, Heroes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_hero_items_item")
+                            .from(HeroItems::Table, HeroItems::ItemId)
+                            .to(Items::Table, Items::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hero_items_hero")
+                    .table(HeroItems::Table)
+                    .col(HeroItems::HeroId)
+                    .col(HeroItems::ItemId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // For "who is carrying this item" lookups
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hero_items_item")
+                    .table(HeroItems::Table)
+                    .col(HeroItems::ItemId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS hero_items_tombstone AFTER DELETE ON hero_items BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'hero_item', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS hero_items_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(HeroItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum HeroItems {
+    Table,
+    Id,
+    CampaignId,
+    HeroId,
+    ItemId,
+    Quantity,
+    Equipped,
+    Attuned,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Contact details and recurring availability for scheduling sessions
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Players::Table)
+                    .add_column(ColumnDef::new(Players::ContactEmail).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Players::Table)
+                    .add_column(ColumnDef::new(Players::ContactHandle).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Players::Table)
+                    .add_column(ColumnDef::new(Players::AvailabilityJson).text())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            Players::AvailabilityJson,
+            Players::ContactHandle,
+            Players::ContactEmail,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Players::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Players {
+    Table,
+    ContactEmail,
+    ContactHandle,
+    AvailabilityJson,
+}
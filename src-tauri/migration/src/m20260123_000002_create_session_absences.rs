@@ -0,0 +1,135 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000002_create_players::Players;
+use super::m20251126_000008_create_sessions::Sessions;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SessionAbsences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SessionAbsences::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionAbsences::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionAbsences::SessionId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionAbsences::PlayerId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SessionAbsences::Reason).text())
+                    .col(
+                        ColumnDef::new(SessionAbsences::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SessionAbsences::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_session_absences_campaign")
+                            .from(SessionAbsences::Table, SessionAbsences::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_session_absences_session")
+                            .from(SessionAbsences::Table, SessionAbsences::SessionId)
+                            .to(Sessions::Table, Sessions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_session_absences_player")
+                            .from(SessionAbsences::Table, SessionAbsences::PlayerId)
+                            .to(Players::Table, Players::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_session_absences_session")
+                    .table(SessionAbsences::Table)
+                    .col(SessionAbsences::SessionId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_session_absences_player")
+                    .table(SessionAbsences::Table)
+                    .col(SessionAbsences::PlayerId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS session_absences_tombstone AFTER DELETE ON session_absences BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'session_absence', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS session_absences_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SessionAbsences::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SessionAbsences {
+    Table,
+    Id,
+    CampaignId,
+    SessionId,
+    PlayerId,
+    Reason,
+    CreatedAt,
+    UpdatedAt,
+}
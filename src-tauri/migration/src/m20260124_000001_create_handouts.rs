@@ -0,0 +1,142 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000010_create_secrets::Secrets;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Handouts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Handouts::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Handouts::CampaignId).string().not_null())
+                    .col(ColumnDef::new(Handouts::Title).string().not_null())
+                    .col(ColumnDef::new(Handouts::Content).text())
+                    .col(ColumnDef::new(Handouts::SecretId).string())
+                    .col(
+                        ColumnDef::new(Handouts::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Handouts::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_handouts_campaign")
+                            .from(Handouts::Table, Handouts::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_handouts_secret")
+                            .from(Handouts::Table, Handouts::SecretId)
+                            .to(Secrets::Table, Secrets::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_handouts_campaign")
+                    .table(Handouts::Table)
+                    .col(Handouts::CampaignId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        // Same search and tombstone triggers as the original entity tables
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS handouts_ai AFTER INSERT ON handouts BEGIN
+                INSERT INTO search_index(entity_type, entity_id, campaign_id, name, content)
+                VALUES ('handout', NEW.id, NEW.campaign_id, NEW.title, COALESCE(NEW.content, ''));
+            END;
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS handouts_au AFTER UPDATE ON handouts BEGIN
+                DELETE FROM search_index WHERE entity_type = 'handout' AND entity_id = OLD.id;
+                INSERT INTO search_index(entity_type, entity_id, campaign_id, name, content)
+                VALUES ('handout', NEW.id, NEW.campaign_id, NEW.title, COALESCE(NEW.content, ''));
+            END;
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS handouts_ad AFTER DELETE ON handouts BEGIN
+                DELETE FROM search_index WHERE entity_type = 'handout' AND entity_id = OLD.id;
+            END;
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS handouts_tombstone AFTER DELETE ON handouts BEGIN
+                INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'handout', OLD.id,
+                        strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+            END;
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for trigger in [
+            "handouts_ai",
+            "handouts_au",
+            "handouts_ad",
+            "handouts_tombstone",
+        ] {
+            db.execute_unprepared(&format!("DROP TRIGGER IF EXISTS {trigger};"))
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Handouts::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Handouts {
+    Table,
+    Id,
+    CampaignId,
+    Title,
+    Content,
+    SecretId,
+    CreatedAt,
+    UpdatedAt,
+}
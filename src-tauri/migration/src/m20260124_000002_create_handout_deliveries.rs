@@ -0,0 +1,150 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000002_create_players::Players;
+use super::m20260124_000001_create_handouts::Handouts;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(HandoutDeliveries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HandoutDeliveries::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HandoutDeliveries::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HandoutDeliveries::HandoutId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HandoutDeliveries::PlayerId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(HandoutDeliveries::Status)
+                            .string()
+                            .not_null()
+                            .default("sent"),
+                    )
+                    .col(
+                        ColumnDef::new(HandoutDeliveries::SentAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(HandoutDeliveries::ReadAt).timestamp())
+                    .col(
+                        ColumnDef::new(HandoutDeliveries::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(HandoutDeliveries::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_handout_deliveries_campaign")
+                            .from(HandoutDeliveries::Table, HandoutDeliveries::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_handout_deliveries_handout")
+                            .from(HandoutDeliveries::Table, HandoutDeliveries::HandoutId)
+                            .to(Handouts::Table, Handouts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_handout_deliveries_player")
+                            .from(HandoutDeliveries::Table, HandoutDeliveries::PlayerId)
+                            .to(Players::Table, Players::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_handout_deliveries_handout")
+                    .table(HandoutDeliveries::Table)
+                    .col(HandoutDeliveries::HandoutId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_handout_deliveries_player")
+                    .table(HandoutDeliveries::Table)
+                    .col(HandoutDeliveries::PlayerId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS handout_deliveries_tombstone AFTER DELETE ON handout_deliveries BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'handout_delivery', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS handout_deliveries_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(HandoutDeliveries::Table).to_owned())
+            .await
+    }
+}
+
+/// Delivery status enum values: sent, read
+#[derive(DeriveIden)]
+pub enum HandoutDeliveries {
+    Table,
+    Id,
+    CampaignId,
+    HandoutId,
+    PlayerId,
+    Status,
+    SentAt,
+    ReadAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,106 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Alternate timelines (prophecies, time-travel splits). A branch shares its
+/// parent's events up to `fork_event_id` and adds its own after that; the
+/// main timeline is the implicit root with no row here.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimelineBranches::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TimelineBranches::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TimelineBranches::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TimelineBranches::Name).string().not_null())
+                    .col(ColumnDef::new(TimelineBranches::Description).text())
+                    .col(ColumnDef::new(TimelineBranches::ParentBranchId).string())
+                    .col(ColumnDef::new(TimelineBranches::ForkEventId).string())
+                    .col(
+                        ColumnDef::new(TimelineBranches::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(TimelineBranches::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timeline_branches_campaign")
+                            .from(TimelineBranches::Table, TimelineBranches::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_timeline_branches_campaign")
+                    .table(TimelineBranches::Table)
+                    .col(TimelineBranches::CampaignId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS timeline_branches_tombstone AFTER DELETE ON timeline_branches BEGIN
+                INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'timeline_branch', OLD.id,
+                        strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+            END;
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS timeline_branches_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TimelineBranches::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum TimelineBranches {
+    Table,
+    Id,
+    CampaignId,
+    Name,
+    Description,
+    ParentBranchId,
+    ForkEventId,
+    CreatedAt,
+    UpdatedAt,
+}
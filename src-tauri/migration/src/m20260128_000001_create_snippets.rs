@@ -0,0 +1,106 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Snippets::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Snippets::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Snippets::CampaignId).string().not_null())
+                    .col(ColumnDef::new(Snippets::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Snippets::SnippetType)
+                            .string()
+                            .not_null()
+                            .default("other"),
+                    )
+                    .col(ColumnDef::new(Snippets::Content).text())
+                    .col(
+                        ColumnDef::new(Snippets::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Snippets::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_snippets_campaign")
+                            .from(Snippets::Table, Snippets::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_snippets_campaign")
+                    .table(Snippets::Table)
+                    .col(Snippets::CampaignId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Snippets are boilerplate rather than world content, so they stay
+        // out of the search index and only get a tombstone trigger
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS snippets_tombstone AFTER DELETE ON snippets BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'snippet', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS snippets_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Snippets::Table).to_owned())
+            .await
+    }
+}
+
+/// Snippet types enum values:
+/// read_aloud, shop, description, dialogue, rules, other
+#[derive(DeriveIden)]
+pub enum Snippets {
+    Table,
+    Id,
+    CampaignId,
+    Name,
+    SnippetType,
+    Content,
+    CreatedAt,
+    UpdatedAt,
+}
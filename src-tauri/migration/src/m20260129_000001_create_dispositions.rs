@@ -0,0 +1,119 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000004_create_characters::Characters;
+use super::m20251126_000008_create_sessions::Sessions;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Dispositions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Dispositions::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Dispositions::CampaignId).string().not_null())
+                    .col(
+                        ColumnDef::new(Dispositions::CharacterId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Dispositions::SessionId).string())
+                    .col(ColumnDef::new(Dispositions::Value).integer().not_null())
+                    .col(ColumnDef::new(Dispositions::Reason).text())
+                    .col(
+                        ColumnDef::new(Dispositions::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Dispositions::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dispositions_campaign")
+                            .from(Dispositions::Table, Dispositions::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dispositions_character")
+                            .from(Dispositions::Table, Dispositions::CharacterId)
+                            .to(Characters::Table, Characters::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dispositions_session")
+                            .from(Dispositions::Table, Dispositions::SessionId)
+                            .to(Sessions::Table, Sessions::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dispositions_character")
+                    .table(Dispositions::Table)
+                    .col(Dispositions::CharacterId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS dispositions_tombstone AFTER DELETE ON dispositions BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'disposition', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS dispositions_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Dispositions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Dispositions {
+    Table,
+    Id,
+    CampaignId,
+    CharacterId,
+    SessionId,
+    Value,
+    Reason,
+    CreatedAt,
+    UpdatedAt,
+}
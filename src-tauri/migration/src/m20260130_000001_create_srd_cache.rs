@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Open5e documents fetched for lookup. Shared by every campaign and
+        // never synced or exported, so there is no campaign key or tombstone.
+        manager
+            .create_table(
+                Table::create()
+                    .table(SrdCache::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SrdCache::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SrdCache::Kind).string().not_null())
+                    .col(ColumnDef::new(SrdCache::Slug).string().not_null())
+                    .col(ColumnDef::new(SrdCache::Name).string().not_null())
+                    .col(ColumnDef::new(SrdCache::DataJson).text().not_null())
+                    .col(
+                        ColumnDef::new(SrdCache::FetchedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_srd_cache_kind_name")
+                    .table(SrdCache::Table)
+                    .col(SrdCache::Kind)
+                    .col(SrdCache::Name)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SrdCache::Table).to_owned())
+            .await
+    }
+}
+
+/// Kind enum values:
+/// monster, spell, magic_item
+#[derive(DeriveIden)]
+pub enum SrdCache {
+    Table,
+    /// "{kind}:{slug}"
+    Id,
+    Kind,
+    Slug,
+    Name,
+    DataJson,
+    FetchedAt,
+}
@@ -0,0 +1,110 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PromptTemplates::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PromptTemplates::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PromptTemplates::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PromptTemplates::Name).string().not_null())
+                    .col(ColumnDef::new(PromptTemplates::Template).text().not_null())
+                    .col(
+                        ColumnDef::new(PromptTemplates::ContextType)
+                            .string()
+                            .not_null()
+                            .default("general"),
+                    )
+                    .col(
+                        ColumnDef::new(PromptTemplates::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PromptTemplates::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_prompt_templates_campaign")
+                            .from(PromptTemplates::Table, PromptTemplates::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_prompt_templates_campaign")
+                    .table(PromptTemplates::Table)
+                    .col(PromptTemplates::CampaignId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Templates are tooling rather than world content, so like snippets
+        // they stay out of the search index
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS prompt_templates_tombstone AFTER DELETE ON prompt_templates BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'prompt_template', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS prompt_templates_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PromptTemplates::Table).to_owned())
+            .await
+    }
+}
+
+/// Context types enum values:
+/// general, character, location, organization, quest, hero, item, session
+#[derive(DeriveIden)]
+pub enum PromptTemplates {
+    Table,
+    Id,
+    CampaignId,
+    Name,
+    Template,
+    ContextType,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per agent run, summed per month for budget caps. Like the
+        // AI conversations it belongs to, usage stays on this device and is
+        // never synced or exported.
+        manager
+            .create_table(
+                Table::create()
+                    .table(AiUsage::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AiUsage::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AiUsage::CampaignId).string().not_null())
+                    .col(ColumnDef::new(AiUsage::ConversationId).string())
+                    .col(
+                        ColumnDef::new(AiUsage::InputTokens)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(AiUsage::OutputTokens)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(AiUsage::CacheReadTokens)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(AiUsage::CacheCreationTokens)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(AiUsage::CostMicrousd)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(AiUsage::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ai_usage_campaign")
+                            .from(AiUsage::Table, AiUsage::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_ai_usage_campaign_created")
+                    .table(AiUsage::Table)
+                    .col(AiUsage::CampaignId)
+                    .col(AiUsage::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AiUsage::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum AiUsage {
+    Table,
+    Id,
+    CampaignId,
+    /// Conversation the run belonged to; kept after the conversation is
+    /// cleared so the month's total doesn't drop
+    ConversationId,
+    InputTokens,
+    OutputTokens,
+    CacheReadTokens,
+    CacheCreationTokens,
+    /// Estimated cost in millionths of a US dollar
+    CostMicrousd,
+    CreatedAt,
+}
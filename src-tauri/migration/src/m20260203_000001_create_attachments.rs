@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Files attached to an entity, such as a generated portrait. The bytes
+        // live in the row so the database stays a single file; attachments
+        // stay on this device and are not synced yet.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Attachments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Attachments::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Attachments::CampaignId).string().not_null())
+                    .col(ColumnDef::new(Attachments::EntityType).string().not_null())
+                    .col(ColumnDef::new(Attachments::EntityId).string().not_null())
+                    .col(
+                        ColumnDef::new(Attachments::Role)
+                            .string()
+                            .not_null()
+                            .default("image"),
+                    )
+                    .col(ColumnDef::new(Attachments::FileName).string().not_null())
+                    .col(ColumnDef::new(Attachments::MimeType).string().not_null())
+                    .col(
+                        ColumnDef::new(Attachments::SizeBytes)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(Attachments::Data).blob().not_null())
+                    .col(ColumnDef::new(Attachments::Source).string())
+                    .col(ColumnDef::new(Attachments::Prompt).text())
+                    .col(
+                        ColumnDef::new(Attachments::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_attachments_campaign")
+                            .from(Attachments::Table, Attachments::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_attachments_entity")
+                    .table(Attachments::Table)
+                    .col(Attachments::EntityType)
+                    .col(Attachments::EntityId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Attachments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Attachments {
+    Table,
+    Id,
+    CampaignId,
+    EntityType,
+    EntityId,
+    /// "portrait", "map" or "image"; an entity has at most one portrait and
+    /// one map
+    Role,
+    FileName,
+    MimeType,
+    SizeBytes,
+    Data,
+    /// Where the file came from, e.g. "openai" for a generated image
+    Source,
+    /// Prompt a generated image was made from
+    Prompt,
+    CreatedAt,
+}
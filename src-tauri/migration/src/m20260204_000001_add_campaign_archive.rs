@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Archived campaigns keep only their `campaigns` row, pointing at the
+/// archive file that holds everything else
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Campaigns::Table)
+                    .add_column(ColumnDef::new(Campaigns::ArchivedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Campaigns::Table)
+                    .add_column(ColumnDef::new(Campaigns::ArchivePath).string())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Campaigns::Table)
+                    .drop_column(Campaigns::ArchivePath)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Campaigns::Table)
+                    .drop_column(Campaigns::ArchivedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Campaigns {
+    Table,
+    ArchivedAt,
+    ArchivePath,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Structured settlement data: population, government, the organization
+/// that runs the place and notes on its economy
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Locations::Table)
+                    .add_column(ColumnDef::new(Locations::Population).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Locations::Table)
+                    .add_column(ColumnDef::new(Locations::GovernmentType).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Locations::Table)
+                    .add_column(ColumnDef::new(Locations::DominantOrganizationId).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Locations::Table)
+                    .add_column(ColumnDef::new(Locations::EconomyNotes).text())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            Locations::EconomyNotes,
+            Locations::DominantOrganizationId,
+            Locations::GovernmentType,
+            Locations::Population,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Locations::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Locations {
+    Table,
+    Population,
+    GovernmentType,
+    DominantOrganizationId,
+    EconomyNotes,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000003_create_locations::Locations;
+use super::m20251126_000005_create_organizations::Organizations;
+use super::m20260122_000001_create_items::Items;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Each row holds exactly one of location_id or item_id, checked by
+        // the commands. Like hero_items, duplicates are kept out by the
+        // commands rather than a unique index so merges can't fail on them.
+        manager
+            .create_table(
+                Table::create()
+                    .table(OrganizationHoldings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OrganizationHoldings::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationHoldings::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationHoldings::OrganizationId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(OrganizationHoldings::LocationId).string())
+                    .col(ColumnDef::new(OrganizationHoldings::ItemId).string())
+                    .col(ColumnDef::new(OrganizationHoldings::Notes).text())
+                    .col(
+                        ColumnDef::new(OrganizationHoldings::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationHoldings::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_organization_holdings_campaign")
+                            .from(
+                                OrganizationHoldings::Table,
+                                OrganizationHoldings::CampaignId,
+                            )
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_organization_holdings_organization")
+                            .from(
+                                OrganizationHoldings::Table,
+                                OrganizationHoldings::OrganizationId,
+                            )
+                            .to(Organizations::Table, Organizations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_organization_holdings_location")
+                            .from(
+                                OrganizationHoldings::Table,
+                                OrganizationHoldings::LocationId,
+                            )
+                            .to(Locations::Table, Locations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_organization_holdings_item")
+                            .from(OrganizationHoldings::Table, OrganizationHoldings::ItemId)
+                            .to(Items::Table, Items::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_organization_holdings_organization")
+                    .table(OrganizationHoldings::Table)
+                    .col(OrganizationHoldings::OrganizationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS organization_holdings_tombstone AFTER DELETE ON organization_holdings BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'organization_holding', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS organization_holdings_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(OrganizationHoldings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum OrganizationHoldings {
+    Table,
+    Id,
+    CampaignId,
+    OrganizationId,
+    LocationId,
+    ItemId,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,132 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000003_create_locations::Locations;
+use super::m20251126_000004_create_characters::Characters;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per character, saying where they are now. The commands
+        // keep it to one rather than a unique index so merges can't fail on
+        // it. The since date is split into columns like character life dates.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Whereabouts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Whereabouts::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Whereabouts::CampaignId).string().not_null())
+                    .col(ColumnDef::new(Whereabouts::CharacterId).string().not_null())
+                    .col(ColumnDef::new(Whereabouts::LocationId).string().not_null())
+                    .col(ColumnDef::new(Whereabouts::SinceYear).integer())
+                    .col(ColumnDef::new(Whereabouts::SinceMonth).integer())
+                    .col(ColumnDef::new(Whereabouts::SinceDay).integer())
+                    .col(ColumnDef::new(Whereabouts::Notes).text())
+                    .col(
+                        ColumnDef::new(Whereabouts::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Whereabouts::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_whereabouts_campaign")
+                            .from(Whereabouts::Table, Whereabouts::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_whereabouts_character")
+                            .from(Whereabouts::Table, Whereabouts::CharacterId)
+                            .to(Characters::Table, Characters::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_whereabouts_location")
+                            .from(Whereabouts::Table, Whereabouts::LocationId)
+                            .to(Locations::Table, Locations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_whereabouts_character")
+                    .table(Whereabouts::Table)
+                    .col(Whereabouts::CharacterId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_whereabouts_location")
+                    .table(Whereabouts::Table)
+                    .col(Whereabouts::LocationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS whereabouts_tombstone AFTER DELETE ON whereabouts BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'whereabouts', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS whereabouts_tombstone;")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Whereabouts::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Whereabouts {
+    Table,
+    Id,
+    CampaignId,
+    CharacterId,
+    LocationId,
+    SinceYear,
+    SinceMonth,
+    SinceDay,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Recent searches per campaign, for suggestions. History is personal
+        // to this device, so like AI usage it is never synced or exported.
+        manager
+            .create_table(
+                Table::create()
+                    .table(SearchHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SearchHistory::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SearchHistory::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SearchHistory::Query).text().not_null())
+                    .col(
+                        ColumnDef::new(SearchHistory::UseCount)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(
+                        ColumnDef::new(SearchHistory::LastUsedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SearchHistory::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_search_history_campaign")
+                            .from(SearchHistory::Table, SearchHistory::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_search_history_campaign_used")
+                    .table(SearchHistory::Table)
+                    .col(SearchHistory::CampaignId)
+                    .col(SearchHistory::LastUsedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SearchHistory::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SearchHistory {
+    Table,
+    Id,
+    CampaignId,
+    /// The query as last typed; repeats are matched case-insensitively
+    Query,
+    UseCount,
+    LastUsedAt,
+    CreatedAt,
+}
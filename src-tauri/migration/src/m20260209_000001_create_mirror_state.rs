@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per campaign mirrored to a plaintext folder. The folder is
+        // a path on this device, so like sync state it is never synced.
+        manager
+            .create_table(
+                Table::create()
+                    .table(MirrorState::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MirrorState::CampaignId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(MirrorState::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(MirrorState::Path).string())
+                    .col(
+                        ColumnDef::new(MirrorState::AutoCommit)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(MirrorState::LastWatermark).string())
+                    .col(ColumnDef::new(MirrorState::LastMirroredAt).timestamp())
+                    .col(
+                        ColumnDef::new(MirrorState::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mirror_state_campaign")
+                            .from(MirrorState::Table, MirrorState::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MirrorState::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum MirrorState {
+    Table,
+    CampaignId,
+    Enabled,
+    Path,
+    AutoCommit,
+    LastWatermark,
+    LastMirroredAt,
+    UpdatedAt,
+}
@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Facts about the database file itself, such as the app version that
+        // last opened it. Read before migrations run, so keep it simple.
+        manager
+            .create_table(
+                Table::create()
+                    .table(AppMeta::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AppMeta::Key)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AppMeta::Value).string().not_null())
+                    .col(
+                        ColumnDef::new(AppMeta::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AppMeta::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum AppMeta {
+    Table,
+    Key,
+    Value,
+    UpdatedAt,
+}
@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Unsaved text from the editor, one row per entity field, so a crash
+        // mid-edit can be recovered from. Drafts belong to this device and
+        // are never synced or exported.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Drafts::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Drafts::Id).string().not_null().primary_key())
+                    .col(ColumnDef::new(Drafts::CampaignId).string().not_null())
+                    .col(ColumnDef::new(Drafts::EntityType).string().not_null())
+                    .col(ColumnDef::new(Drafts::EntityId).string().not_null())
+                    .col(ColumnDef::new(Drafts::Field).string().not_null())
+                    .col(ColumnDef::new(Drafts::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(Drafts::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Drafts::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_drafts_campaign")
+                            .from(Drafts::Table, Drafts::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_drafts_entity_field")
+                    .table(Drafts::Table)
+                    .col(Drafts::EntityType)
+                    .col(Drafts::EntityId)
+                    .col(Drafts::Field)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Drafts::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Drafts {
+    Table,
+    Id,
+    CampaignId,
+    EntityType,
+    EntityId,
+    Field,
+    Content,
+    CreatedAt,
+    UpdatedAt,
+}
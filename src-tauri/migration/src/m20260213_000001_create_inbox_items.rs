@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Captured content not yet filed as an entity: quick notes, AI
+        // suggestions and imported stubs. Items leave the inbox when they are
+        // converted or dismissed.
+        manager
+            .create_table(
+                Table::create()
+                    .table(InboxItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(InboxItems::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(InboxItems::CampaignId).string().not_null())
+                    .col(ColumnDef::new(InboxItems::Source).string().not_null())
+                    .col(ColumnDef::new(InboxItems::Title).string().not_null())
+                    .col(ColumnDef::new(InboxItems::Content).text())
+                    .col(ColumnDef::new(InboxItems::SuggestedType).string())
+                    .col(
+                        ColumnDef::new(InboxItems::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(InboxItems::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_inbox_items_campaign")
+                            .from(InboxItems::Table, InboxItems::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_inbox_items_campaign")
+                    .table(InboxItems::Table)
+                    .col(InboxItems::CampaignId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InboxItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum InboxItems {
+    Table,
+    Id,
+    CampaignId,
+    Source,
+    Title,
+    Content,
+    SuggestedType,
+    CreatedAt,
+    UpdatedAt,
+}
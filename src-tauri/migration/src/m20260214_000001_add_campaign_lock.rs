@@ -0,0 +1,147 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables whose rows belong to a campaign through their own `campaign_id`.
+/// AI conversations, usage, search history and sync bookkeeping are left
+/// out: they record activity around a campaign rather than its content.
+const LOCKED_TABLES: &[&str] = &[
+    "players",
+    "locations",
+    "characters",
+    "organizations",
+    "quests",
+    "heroes",
+    "sessions",
+    "timeline_events",
+    "secrets",
+    "relationships",
+    "tags",
+    "items",
+    "hero_items",
+    "session_absences",
+    "handouts",
+    "handout_deliveries",
+    "timeline_branches",
+    "snippets",
+    "dispositions",
+    "session_transcripts",
+    "prompt_templates",
+    "attachments",
+    "organization_holdings",
+    "whereabouts",
+    "drafts",
+    "inbox_items",
+];
+
+/// Message the lock triggers abort with. `AppError` looks for it to turn the
+/// failure into a READ_ONLY error, so keep the two in step.
+const LOCKED_MESSAGE: &str = "READ_ONLY: campaign is locked";
+
+/// Triggers refusing inserts, updates and deletes on `table` while the
+/// campaign named by its `campaign_id` is locked. Migrations adding a
+/// campaign-scoped table should run these for it too.
+pub fn lock_triggers(table: &str) -> String {
+    ["INSERT", "UPDATE", "DELETE"]
+        .iter()
+        .map(|event| {
+            let row = if *event == "DELETE" { "OLD" } else { "NEW" };
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS {table}_lock_{suffix} BEFORE {event} ON {table}
+                 WHEN (SELECT locked_at FROM campaigns WHERE id = {row}.campaign_id) IS NOT NULL
+                 BEGIN SELECT RAISE(ABORT, '{LOCKED_MESSAGE}'); END;",
+                suffix = event.to_lowercase(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn drop_lock_triggers(table: &str) -> String {
+    ["insert", "update", "delete"]
+        .iter()
+        .map(|suffix| format!("DROP TRIGGER IF EXISTS {}_lock_{};", table, suffix))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Locked campaigns refuse every change to their content until unlocked.
+/// The database enforces it with triggers, so no write path can miss it.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Campaigns::Table)
+                    .add_column(ColumnDef::new(Campaigns::LockedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        for table in LOCKED_TABLES {
+            db.execute_unprepared(&lock_triggers(table)).await?;
+        }
+
+        // Entity tags reach their campaign through the tag
+        db.execute_unprepared(&format!(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS entity_tags_lock_insert BEFORE INSERT ON entity_tags
+            WHEN (SELECT c.locked_at FROM campaigns c JOIN tags t ON t.campaign_id = c.id
+                  WHERE t.id = NEW.tag_id) IS NOT NULL
+            BEGIN SELECT RAISE(ABORT, '{LOCKED_MESSAGE}'); END;
+            CREATE TRIGGER IF NOT EXISTS entity_tags_lock_update BEFORE UPDATE ON entity_tags
+            WHEN (SELECT c.locked_at FROM campaigns c JOIN tags t ON t.campaign_id = c.id
+                  WHERE t.id = NEW.tag_id) IS NOT NULL
+            BEGIN SELECT RAISE(ABORT, '{LOCKED_MESSAGE}'); END;
+            CREATE TRIGGER IF NOT EXISTS entity_tags_lock_delete BEFORE DELETE ON entity_tags
+            WHEN (SELECT c.locked_at FROM campaigns c JOIN tags t ON t.campaign_id = c.id
+                  WHERE t.id = OLD.tag_id) IS NOT NULL
+            BEGIN SELECT RAISE(ABORT, '{LOCKED_MESSAGE}'); END;
+            "#
+        ))
+        .await?;
+
+        // The campaign row itself: unlocking is the only change allowed
+        db.execute_unprepared(&format!(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS campaigns_lock_update BEFORE UPDATE ON campaigns
+            WHEN OLD.locked_at IS NOT NULL AND NEW.locked_at IS NOT NULL
+            BEGIN SELECT RAISE(ABORT, '{LOCKED_MESSAGE}'); END;
+            CREATE TRIGGER IF NOT EXISTS campaigns_lock_delete BEFORE DELETE ON campaigns
+            WHEN OLD.locked_at IS NOT NULL
+            BEGIN SELECT RAISE(ABORT, '{LOCKED_MESSAGE}'); END;
+            "#
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for table in LOCKED_TABLES
+            .iter()
+            .chain(["entity_tags", "campaigns"].iter())
+        {
+            db.execute_unprepared(&drop_lock_triggers(table)).await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Campaigns::Table)
+                    .drop_column(Campaigns::LockedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Campaigns {
+    Table,
+    LockedAt,
+}
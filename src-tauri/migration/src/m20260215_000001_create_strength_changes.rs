@@ -0,0 +1,139 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000008_create_sessions::Sessions;
+use super::m20251126_000011_create_relationships::Relationships;
+use super::m20260214_000001_add_campaign_lock::lock_triggers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(StrengthChanges::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(StrengthChanges::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(StrengthChanges::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(StrengthChanges::RelationshipId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(StrengthChanges::SessionId).string())
+                    .col(ColumnDef::new(StrengthChanges::Value).integer().not_null())
+                    .col(ColumnDef::new(StrengthChanges::Change).integer().not_null())
+                    .col(ColumnDef::new(StrengthChanges::Reason).text())
+                    .col(
+                        ColumnDef::new(StrengthChanges::IsDecay)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(StrengthChanges::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(StrengthChanges::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_strength_changes_campaign")
+                            .from(StrengthChanges::Table, StrengthChanges::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_strength_changes_relationship")
+                            .from(StrengthChanges::Table, StrengthChanges::RelationshipId)
+                            .to(Relationships::Table, Relationships::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_strength_changes_session")
+                            .from(StrengthChanges::Table, StrengthChanges::SessionId)
+                            .to(Sessions::Table, Sessions::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_strength_changes_relationship")
+                    .table(StrengthChanges::Table)
+                    .col(StrengthChanges::RelationshipId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS strength_changes_tombstone AFTER DELETE ON strength_changes BEGIN
+                INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'strength_change', OLD.id,
+                        strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+            END;
+            "#,
+        )
+        .await?;
+        db.execute_unprepared(&lock_triggers("strength_changes"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP TRIGGER IF EXISTS strength_changes_tombstone;
+                 DROP TRIGGER IF EXISTS strength_changes_lock_insert;
+                 DROP TRIGGER IF EXISTS strength_changes_lock_update;
+                 DROP TRIGGER IF EXISTS strength_changes_lock_delete;",
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(StrengthChanges::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum StrengthChanges {
+    Table,
+    Id,
+    CampaignId,
+    RelationshipId,
+    SessionId,
+    Value,
+    Change,
+    Reason,
+    IsDecay,
+    CreatedAt,
+    UpdatedAt,
+}
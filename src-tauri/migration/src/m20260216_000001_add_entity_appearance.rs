@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables whose rows can carry a user-chosen color and icon
+const TABLES: &[&str] = &["characters", "locations", "organizations", "quests"];
+
+/// Optional color and icon on the major entities, so graph views, map pins
+/// and lists can draw them the same way everywhere
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            for column in [Appearance::Color, Appearance::Icon] {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new(*table))
+                            .add_column(ColumnDef::new(column).string())
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            for column in [Appearance::Icon, Appearance::Color] {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new(*table))
+                            .drop_column(column)
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Appearance {
+    Color,
+    Icon,
+}
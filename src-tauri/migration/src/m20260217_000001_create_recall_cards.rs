@@ -0,0 +1,141 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20260214_000001_add_campaign_lock::lock_triggers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecallCards::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RecallCards::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(RecallCards::CampaignId).string().not_null())
+                    .col(ColumnDef::new(RecallCards::EntityType).string())
+                    .col(ColumnDef::new(RecallCards::EntityId).string())
+                    .col(ColumnDef::new(RecallCards::SourceField).string())
+                    .col(ColumnDef::new(RecallCards::Front).text().not_null())
+                    .col(ColumnDef::new(RecallCards::Back).text().not_null())
+                    .col(
+                        ColumnDef::new(RecallCards::Ease)
+                            .integer()
+                            .not_null()
+                            .default(2500),
+                    )
+                    .col(
+                        ColumnDef::new(RecallCards::IntervalDays)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(RecallCards::Repetitions)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(RecallCards::DueAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(RecallCards::LastReviewedAt).timestamp())
+                    .col(ColumnDef::new(RecallCards::LastGrade).integer())
+                    .col(
+                        ColumnDef::new(RecallCards::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(RecallCards::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_recall_cards_campaign")
+                            .from(RecallCards::Table, RecallCards::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_recall_cards_due")
+                    .table(RecallCards::Table)
+                    .col(RecallCards::CampaignId)
+                    .col(RecallCards::DueAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS recall_cards_tombstone AFTER DELETE ON recall_cards BEGIN
+                INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'recall_card', OLD.id,
+                        strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+            END;
+            "#,
+        )
+        .await?;
+        db.execute_unprepared(&lock_triggers("recall_cards"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP TRIGGER IF EXISTS recall_cards_tombstone;
+                 DROP TRIGGER IF EXISTS recall_cards_lock_insert;
+                 DROP TRIGGER IF EXISTS recall_cards_lock_update;
+                 DROP TRIGGER IF EXISTS recall_cards_lock_delete;",
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(RecallCards::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum RecallCards {
+    Table,
+    Id,
+    CampaignId,
+    EntityType,
+    EntityId,
+    SourceField,
+    Front,
+    Back,
+    Ease,
+    IntervalDays,
+    Repetitions,
+    DueAt,
+    LastReviewedAt,
+    LastGrade,
+    CreatedAt,
+    UpdatedAt,
+}
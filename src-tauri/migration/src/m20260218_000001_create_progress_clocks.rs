@@ -0,0 +1,124 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20260214_000001_add_campaign_lock::lock_triggers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProgressClocks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProgressClocks::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProgressClocks::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProgressClocks::Name).string().not_null())
+                    .col(ColumnDef::new(ProgressClocks::Description).text())
+                    .col(
+                        ColumnDef::new(ProgressClocks::Segments)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProgressClocks::Filled)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ProgressClocks::EntityType).string())
+                    .col(ColumnDef::new(ProgressClocks::EntityId).string())
+                    .col(
+                        ColumnDef::new(ProgressClocks::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(ProgressClocks::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_progress_clocks_campaign")
+                            .from(ProgressClocks::Table, ProgressClocks::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_progress_clocks_entity")
+                    .table(ProgressClocks::Table)
+                    .col(ProgressClocks::EntityType)
+                    .col(ProgressClocks::EntityId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS progress_clocks_tombstone AFTER DELETE ON progress_clocks BEGIN
+                INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                VALUES (lower(hex(randomblob(16))), OLD.campaign_id, 'progress_clock', OLD.id,
+                        strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+            END;
+            "#,
+        )
+        .await?;
+        db.execute_unprepared(&lock_triggers("progress_clocks"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP TRIGGER IF EXISTS progress_clocks_tombstone;
+                 DROP TRIGGER IF EXISTS progress_clocks_lock_insert;
+                 DROP TRIGGER IF EXISTS progress_clocks_lock_update;
+                 DROP TRIGGER IF EXISTS progress_clocks_lock_delete;",
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ProgressClocks::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum ProgressClocks {
+    Table,
+    Id,
+    CampaignId,
+    Name,
+    Description,
+    Segments,
+    Filled,
+    EntityType,
+    EntityId,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,339 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000003_create_locations::Locations;
+use super::m20251126_000004_create_characters::Characters;
+use super::m20251126_000008_create_sessions::Sessions;
+use super::m20260214_000001_add_campaign_lock::lock_triggers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLES: &[(&str, &str)] = &[
+    ("encounter_tables", "encounter_table"),
+    ("encounter_entries", "encounter_entry"),
+    ("encounter_rolls", "encounter_roll"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(EncounterTables::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EncounterTables::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(EncounterTables::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EncounterTables::LocationId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(EncounterTables::Name).string().not_null())
+                    .col(ColumnDef::new(EncounterTables::Description).text())
+                    .col(
+                        ColumnDef::new(EncounterTables::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(EncounterTables::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_tables_campaign")
+                            .from(EncounterTables::Table, EncounterTables::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_tables_location")
+                            .from(EncounterTables::Table, EncounterTables::LocationId)
+                            .to(Locations::Table, Locations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_encounter_tables_location")
+                    .table(EncounterTables::Table)
+                    .col(EncounterTables::LocationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(EncounterEntries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EncounterEntries::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(EncounterEntries::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EncounterEntries::TableId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(EncounterEntries::Name).string().not_null())
+                    .col(ColumnDef::new(EncounterEntries::CharacterId).string())
+                    .col(ColumnDef::new(EncounterEntries::Quantity).string())
+                    .col(
+                        ColumnDef::new(EncounterEntries::Weight)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(ColumnDef::new(EncounterEntries::TimeOfDay).string())
+                    .col(ColumnDef::new(EncounterEntries::Season).string())
+                    .col(ColumnDef::new(EncounterEntries::Notes).text())
+                    .col(
+                        ColumnDef::new(EncounterEntries::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(EncounterEntries::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_entries_campaign")
+                            .from(EncounterEntries::Table, EncounterEntries::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_entries_table")
+                            .from(EncounterEntries::Table, EncounterEntries::TableId)
+                            .to(EncounterTables::Table, EncounterTables::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_entries_character")
+                            .from(EncounterEntries::Table, EncounterEntries::CharacterId)
+                            .to(Characters::Table, Characters::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_encounter_entries_table")
+                    .table(EncounterEntries::Table)
+                    .col(EncounterEntries::TableId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(EncounterRolls::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EncounterRolls::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(EncounterRolls::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EncounterRolls::SessionId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(EncounterRolls::LocationId).string())
+                    .col(ColumnDef::new(EncounterRolls::TableId).string())
+                    .col(ColumnDef::new(EncounterRolls::EntryId).string())
+                    .col(ColumnDef::new(EncounterRolls::Result).text().not_null())
+                    .col(ColumnDef::new(EncounterRolls::Count).integer())
+                    .col(ColumnDef::new(EncounterRolls::TimeOfDay).string())
+                    .col(ColumnDef::new(EncounterRolls::Season).string())
+                    .col(
+                        ColumnDef::new(EncounterRolls::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(EncounterRolls::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_rolls_campaign")
+                            .from(EncounterRolls::Table, EncounterRolls::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_rolls_session")
+                            .from(EncounterRolls::Table, EncounterRolls::SessionId)
+                            .to(Sessions::Table, Sessions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_rolls_location")
+                            .from(EncounterRolls::Table, EncounterRolls::LocationId)
+                            .to(Locations::Table, Locations::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_rolls_table")
+                            .from(EncounterRolls::Table, EncounterRolls::TableId)
+                            .to(EncounterTables::Table, EncounterTables::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_encounter_rolls_entry")
+                            .from(EncounterRolls::Table, EncounterRolls::EntryId)
+                            .to(EncounterEntries::Table, EncounterEntries::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_encounter_rolls_session")
+                    .table(EncounterRolls::Table)
+                    .col(EncounterRolls::SessionId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        for (table, entity_type) in TABLES {
+            db.execute_unprepared(&format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {table}_tombstone AFTER DELETE ON {table} BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, '{entity_type}', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#
+            ))
+            .await?;
+            db.execute_unprepared(&lock_triggers(table)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for (table, _) in TABLES {
+            db.execute_unprepared(&format!(
+                "DROP TRIGGER IF EXISTS {table}_tombstone;
+                 DROP TRIGGER IF EXISTS {table}_lock_insert;
+                 DROP TRIGGER IF EXISTS {table}_lock_update;
+                 DROP TRIGGER IF EXISTS {table}_lock_delete;"
+            ))
+            .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(EncounterRolls::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(EncounterEntries::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(EncounterTables::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum EncounterTables {
+    Table,
+    Id,
+    CampaignId,
+    LocationId,
+    Name,
+    Description,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum EncounterEntries {
+    Table,
+    Id,
+    CampaignId,
+    TableId,
+    Name,
+    CharacterId,
+    Quantity,
+    Weight,
+    TimeOfDay,
+    Season,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum EncounterRolls {
+    Table,
+    Id,
+    CampaignId,
+    SessionId,
+    LocationId,
+    TableId,
+    EntryId,
+    Result,
+    Count,
+    TimeOfDay,
+    Season,
+    CreatedAt,
+    UpdatedAt,
+}
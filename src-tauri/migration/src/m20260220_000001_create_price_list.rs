@@ -0,0 +1,214 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000003_create_locations::Locations;
+use super::m20260122_000001_create_items::Items;
+use super::m20260214_000001_add_campaign_lock::lock_triggers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLES: &[(&str, &str)] = &[
+    ("price_list", "price"),
+    ("price_modifiers", "price_modifier"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceList::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PriceList::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PriceList::CampaignId).string().not_null())
+                    .col(ColumnDef::new(PriceList::Name).string().not_null())
+                    .col(ColumnDef::new(PriceList::Category).string().not_null())
+                    .col(ColumnDef::new(PriceList::ItemId).string())
+                    .col(ColumnDef::new(PriceList::BasePrice).integer().not_null())
+                    .col(ColumnDef::new(PriceList::Notes).text())
+                    .col(
+                        ColumnDef::new(PriceList::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PriceList::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_price_list_campaign")
+                            .from(PriceList::Table, PriceList::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_price_list_item")
+                            .from(PriceList::Table, PriceList::ItemId)
+                            .to(Items::Table, Items::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_price_list_campaign_category")
+                    .table(PriceList::Table)
+                    .col(PriceList::CampaignId)
+                    .col(PriceList::Category)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceModifiers::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PriceModifiers::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PriceModifiers::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PriceModifiers::LocationId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PriceModifiers::Category).string())
+                    .col(ColumnDef::new(PriceModifiers::PriceId).string())
+                    .col(ColumnDef::new(PriceModifiers::Percent).integer().not_null())
+                    .col(ColumnDef::new(PriceModifiers::Reason).text())
+                    .col(
+                        ColumnDef::new(PriceModifiers::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PriceModifiers::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_price_modifiers_campaign")
+                            .from(PriceModifiers::Table, PriceModifiers::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_price_modifiers_location")
+                            .from(PriceModifiers::Table, PriceModifiers::LocationId)
+                            .to(Locations::Table, Locations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_price_modifiers_price")
+                            .from(PriceModifiers::Table, PriceModifiers::PriceId)
+                            .to(PriceList::Table, PriceList::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_price_modifiers_location")
+                    .table(PriceModifiers::Table)
+                    .col(PriceModifiers::LocationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        for (table, entity_type) in TABLES {
+            db.execute_unprepared(&format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {table}_tombstone AFTER DELETE ON {table} BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, '{entity_type}', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#
+            ))
+            .await?;
+            db.execute_unprepared(&lock_triggers(table)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for (table, _) in TABLES {
+            db.execute_unprepared(&format!(
+                "DROP TRIGGER IF EXISTS {table}_tombstone;
+                 DROP TRIGGER IF EXISTS {table}_lock_insert;
+                 DROP TRIGGER IF EXISTS {table}_lock_update;
+                 DROP TRIGGER IF EXISTS {table}_lock_delete;"
+            ))
+            .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(PriceModifiers::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(PriceList::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum PriceList {
+    Table,
+    Id,
+    CampaignId,
+    Name,
+    Category,
+    ItemId,
+    BasePrice,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum PriceModifiers {
+    Table,
+    Id,
+    CampaignId,
+    LocationId,
+    Category,
+    PriceId,
+    Percent,
+    Reason,
+    CreatedAt,
+    UpdatedAt,
+}
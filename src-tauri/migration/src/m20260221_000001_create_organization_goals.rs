@@ -0,0 +1,226 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20251126_000005_create_organizations::Organizations;
+use super::m20260214_000001_add_campaign_lock::lock_triggers;
+use super::m20260218_000001_create_progress_clocks::ProgressClocks;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLES: &[(&str, &str)] = &[
+    ("organization_goals", "organization_goal"),
+    ("goal_advances", "goal_advance"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OrganizationGoals::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OrganizationGoals::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationGoals::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationGoals::OrganizationId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(OrganizationGoals::Title).string().not_null())
+                    .col(ColumnDef::new(OrganizationGoals::Description).text())
+                    .col(
+                        ColumnDef::new(OrganizationGoals::Status)
+                            .string()
+                            .not_null()
+                            .default("active"),
+                    )
+                    .col(ColumnDef::new(OrganizationGoals::ClockId).string())
+                    .col(
+                        ColumnDef::new(OrganizationGoals::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(OrganizationGoals::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_organization_goals_campaign")
+                            .from(OrganizationGoals::Table, OrganizationGoals::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_organization_goals_organization")
+                            .from(OrganizationGoals::Table, OrganizationGoals::OrganizationId)
+                            .to(Organizations::Table, Organizations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_organization_goals_clock")
+                            .from(OrganizationGoals::Table, OrganizationGoals::ClockId)
+                            .to(ProgressClocks::Table, ProgressClocks::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_organization_goals_organization")
+                    .table(OrganizationGoals::Table)
+                    .col(OrganizationGoals::OrganizationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(GoalAdvances::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GoalAdvances::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GoalAdvances::CampaignId).string().not_null())
+                    .col(ColumnDef::new(GoalAdvances::GoalId).string().not_null())
+                    .col(
+                        ColumnDef::new(GoalAdvances::Ticks)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(GoalAdvances::Filled).integer())
+                    .col(ColumnDef::new(GoalAdvances::Segments).integer())
+                    .col(ColumnDef::new(GoalAdvances::Status).string())
+                    .col(ColumnDef::new(GoalAdvances::Note).text())
+                    .col(
+                        ColumnDef::new(GoalAdvances::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(GoalAdvances::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_goal_advances_campaign")
+                            .from(GoalAdvances::Table, GoalAdvances::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_goal_advances_goal")
+                            .from(GoalAdvances::Table, GoalAdvances::GoalId)
+                            .to(OrganizationGoals::Table, OrganizationGoals::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_goal_advances_goal_created")
+                    .table(GoalAdvances::Table)
+                    .col(GoalAdvances::GoalId)
+                    .col(GoalAdvances::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        for (table, entity_type) in TABLES {
+            db.execute_unprepared(&format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {table}_tombstone AFTER DELETE ON {table} BEGIN
+                    INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                    VALUES (lower(hex(randomblob(16))), OLD.campaign_id, '{entity_type}', OLD.id,
+                            strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+                END;
+                "#
+            ))
+            .await?;
+            db.execute_unprepared(&lock_triggers(table)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for (table, _) in TABLES {
+            db.execute_unprepared(&format!(
+                "DROP TRIGGER IF EXISTS {table}_tombstone;
+                 DROP TRIGGER IF EXISTS {table}_lock_insert;
+                 DROP TRIGGER IF EXISTS {table}_lock_update;
+                 DROP TRIGGER IF EXISTS {table}_lock_delete;"
+            ))
+            .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(GoalAdvances::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(OrganizationGoals::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum OrganizationGoals {
+    Table,
+    Id,
+    CampaignId,
+    OrganizationId,
+    Title,
+    Description,
+    Status,
+    ClockId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum GoalAdvances {
+    Table,
+    Id,
+    CampaignId,
+    GoalId,
+    Ticks,
+    Filled,
+    Segments,
+    Status,
+    Note,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Structured roleplay cues on characters, next to the free-form
+/// `voice_notes`. Mannerisms and catchphrases are JSON string arrays like
+/// `aliases_json`.
+const ROLEPLAY_COLUMNS: [Characters; 4] = [
+    Characters::Accent,
+    Characters::MannerismsJson,
+    Characters::CatchphrasesJson,
+    Characters::SpeechSample,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in ROLEPLAY_COLUMNS {
+            let mut def = ColumnDef::new(column);
+            match column {
+                Characters::Accent => def.string(),
+                _ => def.text(),
+            };
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Characters::Table)
+                        .add_column(def)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in ROLEPLAY_COLUMNS.into_iter().rev() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Characters::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Characters {
+    Table,
+    Accent,
+    MannerismsJson,
+    CatchphrasesJson,
+    SpeechSample,
+}
@@ -0,0 +1,124 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+use super::m20260214_000001_add_campaign_lock::lock_triggers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLE: &str = "annotations";
+const ENTITY_TYPE: &str = "annotation";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Annotations::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Annotations::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Annotations::CampaignId).string().not_null())
+                    .col(ColumnDef::new(Annotations::EntityType).string().not_null())
+                    .col(ColumnDef::new(Annotations::EntityId).string().not_null())
+                    .col(ColumnDef::new(Annotations::Field).string())
+                    .col(ColumnDef::new(Annotations::Body).text().not_null())
+                    .col(ColumnDef::new(Annotations::Author).string())
+                    .col(ColumnDef::new(Annotations::ResolvedAt).timestamp())
+                    .col(
+                        ColumnDef::new(Annotations::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Annotations::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_annotations_campaign")
+                            .from(Annotations::Table, Annotations::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_annotations_entity")
+                    .table(Annotations::Table)
+                    .col(Annotations::EntityType)
+                    .col(Annotations::EntityId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_annotations_campaign_resolved")
+                    .table(Annotations::Table)
+                    .col(Annotations::CampaignId)
+                    .col(Annotations::ResolvedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared(&format!(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS {TABLE}_tombstone AFTER DELETE ON {TABLE} BEGIN
+                INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                VALUES (lower(hex(randomblob(16))), OLD.campaign_id, '{ENTITY_TYPE}', OLD.id,
+                        strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00');
+            END;
+            "#
+        ))
+        .await?;
+        db.execute_unprepared(&lock_triggers(TABLE)).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                "DROP TRIGGER IF EXISTS {TABLE}_tombstone;
+                 DROP TRIGGER IF EXISTS {TABLE}_lock_insert;
+                 DROP TRIGGER IF EXISTS {TABLE}_lock_update;
+                 DROP TRIGGER IF EXISTS {TABLE}_lock_delete;"
+            ))
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Annotations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Annotations {
+    Table,
+    Id,
+    CampaignId,
+    EntityType,
+    EntityId,
+    Field,
+    Body,
+    Author,
+    ResolvedAt,
+    CreatedAt,
+    UpdatedAt,
+}
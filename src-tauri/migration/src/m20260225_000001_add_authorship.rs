@@ -0,0 +1,139 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// The campaign and every table in the entity registry
+const TABLES: &[&str] = &[
+    "campaigns",
+    "characters",
+    "locations",
+    "organizations",
+    "quests",
+    "heroes",
+    "items",
+    "hero_items",
+    "organization_holdings",
+    "whereabouts",
+    "players",
+    "sessions",
+    "session_absences",
+    "session_transcripts",
+    "dispositions",
+    "timeline_branches",
+    "timeline_events",
+    "secrets",
+    "prompt_templates",
+    "handouts",
+    "handout_deliveries",
+    "snippets",
+    "relationships",
+    "strength_changes",
+    "recall_cards",
+    "progress_clocks",
+    "encounter_tables",
+    "encounter_entries",
+    "encounter_rolls",
+    "price_list",
+    "price_modifiers",
+    "organization_goals",
+    "goal_advances",
+    "content_warnings",
+    "annotations",
+    "tags",
+];
+
+/// `app_meta` key naming whoever writes on this device. The app reads and
+/// sets it through `LOCAL_PROFILE_KEY`, so keep the two in step.
+const LOCAL_PROFILE_KEY: &str = "local_profile";
+
+/// Triggers filling in `created_by` and `modified_by` from the local
+/// profile. A row written with its authorship already set, as synced and
+/// merged rows are, keeps it; nothing is stamped while no profile is set.
+///
+/// The stamp is a second write to the row, so it has to get past the lock
+/// triggers too. Rows of other tables only get that far once their campaign
+/// has let the first write through; locking or unlocking a campaign isn't an
+/// edit to it, and stamping it would trip the lock just set.
+fn authorship_triggers(table: &str) -> String {
+    let profile = format!("(SELECT value FROM app_meta WHERE key = '{LOCAL_PROFILE_KEY}')");
+    let not_locking = if table == "campaigns" {
+        " AND NEW.locked_at IS OLD.locked_at"
+    } else {
+        ""
+    };
+    format!(
+        "CREATE TRIGGER IF NOT EXISTS {table}_author_insert AFTER INSERT ON {table}
+         WHEN {profile} IS NOT NULL AND (NEW.created_by IS NULL OR NEW.modified_by IS NULL)
+         BEGIN
+             UPDATE {table} SET created_by = COALESCE(NEW.created_by, {profile}),
+                 modified_by = COALESCE(NEW.modified_by, {profile})
+             WHERE id = NEW.id;
+         END;
+         CREATE TRIGGER IF NOT EXISTS {table}_author_update AFTER UPDATE ON {table}
+         WHEN {profile} IS NOT NULL AND NEW.modified_by IS OLD.modified_by
+             AND NEW.modified_by IS NOT {profile}{not_locking}
+         BEGIN
+             UPDATE {table} SET modified_by = {profile} WHERE id = NEW.id;
+         END;"
+    )
+}
+
+/// Who created and who last changed each entity, for "only what I wrote"
+/// filters and for telling co-authors' work apart once a campaign is shared.
+/// Triggers do the stamping, like the lock triggers, so no write path can
+/// miss it.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            for column in [Authorship::CreatedBy, Authorship::ModifiedBy] {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new(*table))
+                            .add_column(ColumnDef::new(column).string())
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+
+        let db = manager.get_connection();
+        for table in TABLES {
+            db.execute_unprepared(&authorship_triggers(table)).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for table in TABLES {
+            db.execute_unprepared(&format!(
+                "DROP TRIGGER IF EXISTS {table}_author_insert;
+                 DROP TRIGGER IF EXISTS {table}_author_update;"
+            ))
+            .await?;
+        }
+
+        for table in TABLES {
+            for column in [Authorship::ModifiedBy, Authorship::CreatedBy] {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Alias::new(*table))
+                            .drop_column(column)
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Authorship {
+    CreatedBy,
+    ModifiedBy,
+}
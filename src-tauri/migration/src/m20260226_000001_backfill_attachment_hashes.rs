@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+use sha2::{Digest, Sha256};
+
+use super::m20260214_000001_add_campaign_lock::lock_triggers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Hash attachments stored before `content_hash` existed, so exporting a
+/// campaign only ever reads from the database. Hashes match the app's
+/// `content_hash`: lowercase hex SHA-256 of the file contents.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let backend = db.get_database_backend();
+        let rows = db
+            .query_all(Statement::from_string(
+                backend,
+                "SELECT id, data FROM attachments WHERE content_hash IS NULL",
+            ))
+            .await?;
+
+        // A hash isn't an edit, so locked campaigns are filled in too
+        db.execute_unprepared("DROP TRIGGER IF EXISTS attachments_lock_update")
+            .await?;
+        for row in rows {
+            let id: String = row.try_get("", "id")?;
+            let data: Vec<u8> = row.try_get("", "data")?;
+            let hash: String = Sha256::digest(&data)
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            db.execute(Statement::from_sql_and_values(
+                backend,
+                "UPDATE attachments SET content_hash = $1 WHERE id = $2",
+                [hash.into(), id.into()],
+            ))
+            .await?;
+        }
+        db.execute_unprepared(&lock_triggers("attachments")).await?;
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // Backfilled hashes are indistinguishable from ones stored on write
+        Ok(())
+    }
+}
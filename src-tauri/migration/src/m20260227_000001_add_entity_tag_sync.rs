@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Let entity tags take part in sync: a `created_at` for the change delta
+/// and a tombstone when a tag is taken off an entity. Entity tags have no id
+/// of their own, so tombstones name them `tag_id:entity_type:entity_id`.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // SQLite only takes a constant default when adding a column, so
+        // existing rows are stamped with the time of the migration. That
+        // puts them in the next push, like any other row never synced.
+        let now: String = db
+            .query_one(Statement::from_string(
+                db.get_database_backend(),
+                "SELECT strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00' AS now",
+            ))
+            .await?
+            .ok_or_else(|| DbErr::Custom("Failed to read the current time".to_string()))?
+            .try_get("", "now")?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(EntityTags::Table)
+                    .add_column(
+                        ColumnDef::new(EntityTags::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(now),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Entity tags reach their campaign through the tag. When the tag
+        // itself is deleted its own tombstone covers them.
+        db.execute_unprepared(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS entity_tags_tombstone AFTER DELETE ON entity_tags BEGIN
+                INSERT INTO tombstones(id, campaign_id, entity_type, entity_id, deleted_at)
+                SELECT lower(hex(randomblob(16))), t.campaign_id, 'entity_tag',
+                       OLD.tag_id || ':' || OLD.entity_type || ':' || OLD.entity_id,
+                       strftime('%Y-%m-%dT%H:%M:%f', 'now') || '+00:00'
+                FROM tags t WHERE t.id = OLD.tag_id;
+            END;
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TRIGGER IF EXISTS entity_tags_tombstone;")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(EntityTags::Table)
+                    .drop_column(EntityTags::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EntityTags {
+    Table,
+    CreatedAt,
+}
@@ -0,0 +1,86 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20251126_000001_create_campaigns::Campaigns;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Embedding vectors of entity text, kept so only changed entities go
+        // back to the provider. Derived data: never synced, exported or
+        // locked, so there is no tombstone or lock trigger.
+        manager
+            .create_table(
+                Table::create()
+                    .table(EntityEmbeddings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EntityEmbeddings::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(EntityEmbeddings::CampaignId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(EntityEmbeddings::Model).string().not_null())
+                    .col(
+                        ColumnDef::new(EntityEmbeddings::ContentHash)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(EntityEmbeddings::Vector).blob().not_null())
+                    .col(
+                        ColumnDef::new(EntityEmbeddings::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_entity_embeddings_campaign")
+                            .from(EntityEmbeddings::Table, EntityEmbeddings::CampaignId)
+                            .to(Campaigns::Table, Campaigns::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_entity_embeddings_campaign_model")
+                    .table(EntityEmbeddings::Table)
+                    .col(EntityEmbeddings::CampaignId)
+                    .col(EntityEmbeddings::Model)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EntityEmbeddings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum EntityEmbeddings {
+    Table,
+    /// "{model}:{entity_type}:{entity_id}"
+    Id,
+    CampaignId,
+    /// Provider and model the vector came from, e.g. "ollama/nomic-embed-text"
+    Model,
+    /// Hex SHA-256 of the text that was embedded
+    ContentHash,
+    /// Little-endian f32 values
+    Vector,
+    CreatedAt,
+}
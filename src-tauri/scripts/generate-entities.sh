@@ -83,8 +83,10 @@ mod tests {
         crate::relationships::Model::export_all().unwrap();
         crate::secrets::Model::export_all().unwrap();
         crate::sessions::Model::export_all().unwrap();
+        crate::sync_state::Model::export_all().unwrap();
         crate::tags::Model::export_all().unwrap();
         crate::timeline_events::Model::export_all().unwrap();
+        crate::tombstones::Model::export_all().unwrap();
     }
 }
 EXPORT_EOF
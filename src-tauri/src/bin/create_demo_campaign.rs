@@ -0,0 +1,63 @@
+//! Generate a populated demo campaign into a database file
+
+use clap::Parser;
+use loreweaver_lib::demo::{
+    create_demo_campaign, DemoOptions, DEFAULT_DEMO_NAME, DEFAULT_DEMO_SEED, MAX_DEMO_SCALE,
+};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{Database, TransactionTrait};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "create_demo_campaign")]
+#[command(
+    about = "Generate a populated demo campaign for onboarding, screenshots and load testing"
+)]
+struct Args {
+    /// Path to database file, created if missing (or set LOREWEAVER_DB env var)
+    #[arg(long, env = "LOREWEAVER_DB")]
+    db: PathBuf,
+
+    /// Campaign name
+    #[arg(long, default_value = DEFAULT_DEMO_NAME)]
+    name: String,
+
+    /// Content multiplier; 1 gives ~36 NPCs, larger values for performance testing
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..=MAX_DEMO_SCALE as i64))]
+    scale: u32,
+
+    /// Seed for the generated content
+    #[arg(long, default_value_t = DEFAULT_DEMO_SEED)]
+    seed: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let db_url = format!("sqlite:{}?mode=rwc", args.db.display());
+    let db = Database::connect(&db_url).await?;
+    Migrator::up(&db, None).await?;
+
+    let options = DemoOptions {
+        name: args.name,
+        scale: args.scale,
+        seed: args.seed,
+    };
+
+    let txn = db.begin().await?;
+    let bundle = create_demo_campaign(&txn, &options).await?;
+    txn.commit().await?;
+
+    println!(
+        "Created \"{}\" ({}) in {}",
+        bundle.campaign.name,
+        bundle.campaign.id,
+        args.db.display()
+    );
+    for (entity_type, count) in bundle.counts() {
+        println!("  {:<16} {}", entity_type, count);
+    }
+
+    Ok(())
+}
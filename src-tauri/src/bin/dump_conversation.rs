@@ -1,9 +1,23 @@
 //! Debug tool to dump AI conversations from the database
 
-use clap::Parser;
-use sea_orm::{Database, EntityTrait, QueryOrder, QueryFilter, ColumnTrait};
+use clap::{Parser, ValueEnum};
+use entity::ai_conversations::{self, Entity as AiConversation};
+use entity::ai_messages::{self, Entity as AiMessage};
+use sea_orm::{ColumnTrait, Database, EntityTrait, QueryFilter, QueryOrder};
+use serde::Serialize;
+use std::fmt::Write as _;
 use std::path::PathBuf;
 
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// Human-readable console output
+    Text,
+    /// Machine-readable, full content
+    Json,
+    /// Full content, suitable for pasting into a bug report
+    Markdown,
+}
+
 #[derive(Parser)]
 #[command(name = "dump_conversation")]
 #[command(about = "Debug tool to dump AI conversations from the database")]
@@ -16,129 +30,240 @@ struct Args {
     #[arg(long, value_parser = ["sidebar", "fullpage"])]
     context: Option<String>,
 
+    /// Filter by campaign id
+    #[arg(long)]
+    campaign: Option<String>,
+
     /// Show only the most recent conversation
     #[arg(long)]
     last: bool,
 
-    /// Show only message summaries (no content)
+    /// Show only message summaries (no content); ignored for JSON
     #[arg(long)]
     summary: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Write to this file instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+/// A conversation with its messages in order
+#[derive(Serialize)]
+struct ConversationDump {
+    #[serde(flatten)]
+    conversation: ai_conversations::Model,
+    messages: Vec<ai_messages::Model>,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    // Find the database file
-    let db_path = args.db.map(Ok).unwrap_or_else(find_db_path)?;
-    println!("📁 Database: {}\n", db_path.display());
+    // Find the database file. Reported on stderr so stdout stays parseable.
+    let db_path = args.db.clone().map(Ok).unwrap_or_else(find_db_path)?;
+    eprintln!("📁 Database: {}\n", db_path.display());
 
     let db_url = format!("sqlite:{}?mode=ro", db_path.display());
     let db = Database::connect(&db_url).await?;
 
-    // Load all conversations
-    use entity::ai_conversations::{self, Entity as AiConversation};
-    use entity::ai_messages::{self, Entity as AiMessage};
-
-    let mut conversations = AiConversation::find()
-        .order_by_desc(ai_conversations::Column::UpdatedAt)
-        .all(&db)
-        .await?;
-
+    let mut query = AiConversation::find().order_by_desc(ai_conversations::Column::UpdatedAt);
     if let Some(ref ctx) = args.context {
-        conversations.retain(|c| c.context_type == *ctx);
+        query = query.filter(ai_conversations::Column::ContextType.eq(ctx));
+    }
+    if let Some(ref campaign) = args.campaign {
+        query = query.filter(ai_conversations::Column::CampaignId.eq(campaign));
     }
+    let mut conversations = query.all(&db).await?;
 
-    if args.last && !conversations.is_empty() {
+    if args.last {
         conversations.truncate(1);
     }
 
-    if conversations.is_empty() {
-        println!("No conversations found.");
-        return Ok(());
+    let mut dumps = Vec::with_capacity(conversations.len());
+    for conversation in conversations {
+        let messages = AiMessage::find()
+            .filter(ai_messages::Column::ConversationId.eq(&conversation.id))
+            .order_by_asc(ai_messages::Column::MessageOrder)
+            .all(&db)
+            .await?;
+        dumps.push(ConversationDump { conversation, messages });
+    }
+
+    let rendered = match args.format {
+        Format::Json => serde_json::to_string_pretty(&dumps)? + "\n",
+        Format::Markdown => render_markdown(&dumps, args.summary)?,
+        Format::Text => render_text(&dumps, args.summary)?,
+    };
+
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, rendered)?;
+            eprintln!("Wrote {} conversation(s) to {}", dumps.len(), path.display());
+        }
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+fn role_icon(role: &str) -> &'static str {
+    match role {
+        "user" => "👤",
+        "assistant" => "🤖",
+        "tool" => "🔧",
+        "error" => "❌",
+        "proposal" => "📋",
+        _ => "❓",
+    }
+}
+
+/// Cut `s` to at most `max` bytes without splitting a character
+fn truncate(s: &str, max: usize) -> Option<&str> {
+    if s.len() <= max {
+        return None;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    Some(&s[..end])
+}
+
+fn pretty_json(raw: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(raw)
+        .ok()
+        .and_then(|parsed| serde_json::to_string_pretty(&parsed).ok())
+}
+
+fn render_text(dumps: &[ConversationDump], summary: bool) -> Result<String, std::fmt::Error> {
+    let mut out = String::new();
+
+    if dumps.is_empty() {
+        writeln!(out, "No conversations found.")?;
+        return Ok(out);
     }
 
-    for conv in conversations {
-        println!("═══════════════════════════════════════════════════════════════════════════════");
-        println!("📝 Conversation: {} ({})", conv.context_type.to_uppercase(), conv.id);
-        println!("   Campaign: {}", conv.campaign_id);
-        println!("   Tokens: {} in / {} out / {} cache read / {} cache create",
+    for dump in dumps {
+        let conv = &dump.conversation;
+        writeln!(out, "═══════════════════════════════════════════════════════════════════════════════")?;
+        writeln!(out, "📝 Conversation: {} ({})", conv.context_type.to_uppercase(), conv.id)?;
+        writeln!(out, "   Campaign: {}", conv.campaign_id)?;
+        writeln!(out, "   Tokens: {} in / {} out / {} cache read / {} cache create",
             conv.total_input_tokens,
             conv.total_output_tokens,
             conv.total_cache_read_tokens,
             conv.total_cache_creation_tokens
-        );
-        println!("   Updated: {}", conv.updated_at);
-        println!("───────────────────────────────────────────────────────────────────────────────");
-
-        // Load messages for this conversation
-        let messages = AiMessage::find()
-            .filter(ai_messages::Column::ConversationId.eq(&conv.id))
-            .order_by_asc(ai_messages::Column::MessageOrder)
-            .all(&db)
-            .await?;
+        )?;
+        writeln!(out, "   Updated: {}", conv.updated_at)?;
+        writeln!(out, "───────────────────────────────────────────────────────────────────────────────")?;
 
-        if messages.is_empty() {
-            println!("   (no messages)\n");
+        if dump.messages.is_empty() {
+            writeln!(out, "   (no messages)\n")?;
             continue;
         }
 
-        for msg in messages {
-            let role_icon = match msg.role.as_str() {
-                "user" => "👤",
-                "assistant" => "🤖",
-                "tool" => "🔧",
-                "error" => "❌",
-                "proposal" => "📋",
-                _ => "❓",
-            };
-
-            println!("\n{} [{}] {}", role_icon, msg.message_order, msg.role.to_uppercase());
+        for msg in &dump.messages {
+            writeln!(out, "\n{} [{}] {}", role_icon(&msg.role), msg.message_order, msg.role.to_uppercase())?;
 
             if let Some(tool_name) = &msg.tool_name {
-                println!("   Tool: {}", tool_name);
+                writeln!(out, "   Tool: {}", tool_name)?;
             }
 
-            if args.summary {
+            if summary {
                 // Just show length
-                println!("   Content: ({} chars)", msg.content.len());
-            } else {
-                // Print content (truncated if long)
-                let content = &msg.content;
-                if content.len() > 500 {
-                    println!("   Content: {}...", &content[..500]);
-                } else {
-                    println!("   Content: {}", content);
-                }
+                writeln!(out, "   Content: ({} chars)", msg.content.len())?;
+                continue;
+            }
 
-                // Print tool input if present
-                if let Some(tool_input) = &msg.tool_input_json {
-                    if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(tool_input) {
-                        println!("   Tool Input: {}", serde_json::to_string_pretty(&parsed)?);
-                    }
-                }
+            // Print content (truncated if long)
+            match truncate(&msg.content, 500) {
+                Some(head) => writeln!(out, "   Content: {}...", head)?,
+                None => writeln!(out, "   Content: {}", msg.content)?,
+            }
 
-                // Print tool data summary if present
-                if let Some(tool_data) = &msg.tool_data_json {
-                    if tool_data.len() > 200 {
-                        println!("   Tool Data: {}...", &tool_data[..200]);
-                    } else {
-                        println!("   Tool Data: {}", tool_data);
-                    }
-                }
+            // Print tool input if present
+            if let Some(tool_input) = msg.tool_input_json.as_deref().and_then(pretty_json) {
+                writeln!(out, "   Tool Input: {}", tool_input)?;
+            }
 
-                // Print proposal if present
-                if let Some(proposal) = &msg.proposal_json {
-                    if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(proposal) {
-                        println!("   Proposal: {}", serde_json::to_string_pretty(&parsed)?);
-                    }
+            // Print tool data summary if present
+            if let Some(tool_data) = &msg.tool_data_json {
+                match truncate(tool_data, 200) {
+                    Some(head) => writeln!(out, "   Tool Data: {}...", head)?,
+                    None => writeln!(out, "   Tool Data: {}", tool_data)?,
                 }
             }
+
+            // Print proposal if present
+            if let Some(proposal) = msg.proposal_json.as_deref().and_then(pretty_json) {
+                writeln!(out, "   Proposal: {}", proposal)?;
+            }
         }
-        println!("\n");
+        writeln!(out, "\n")?;
     }
 
-    Ok(())
+    Ok(out)
+}
+
+fn render_markdown(dumps: &[ConversationDump], summary: bool) -> Result<String, std::fmt::Error> {
+    let mut out = String::new();
+
+    if dumps.is_empty() {
+        writeln!(out, "_No conversations found._")?;
+        return Ok(out);
+    }
+
+    for dump in dumps {
+        let conv = &dump.conversation;
+        writeln!(out, "# Conversation: {} (`{}`)\n", conv.context_type.to_uppercase(), conv.id)?;
+        writeln!(out, "- **Campaign:** `{}`", conv.campaign_id)?;
+        writeln!(out, "- **Tokens:** {} in / {} out / {} cache read / {} cache create",
+            conv.total_input_tokens,
+            conv.total_output_tokens,
+            conv.total_cache_read_tokens,
+            conv.total_cache_creation_tokens
+        )?;
+        writeln!(out, "- **Updated:** {}\n", conv.updated_at)?;
+
+        if dump.messages.is_empty() {
+            writeln!(out, "_No messages._\n")?;
+            continue;
+        }
+
+        for msg in &dump.messages {
+            write!(out, "## {} {}. {}", role_icon(&msg.role), msg.message_order, msg.role.to_uppercase())?;
+            match &msg.tool_name {
+                Some(tool_name) => writeln!(out, " — `{}`\n", tool_name)?,
+                None => writeln!(out, "\n")?,
+            }
+
+            if summary {
+                writeln!(out, "_{} chars_\n", msg.content.len())?;
+                continue;
+            }
+
+            if !msg.content.is_empty() {
+                writeln!(out, "{}\n", msg.content)?;
+            }
+
+            let blocks = [
+                ("Tool input", msg.tool_input_json.as_deref()),
+                ("Tool data", msg.tool_data_json.as_deref()),
+                ("Proposal", msg.proposal_json.as_deref()),
+            ];
+            for (label, raw) in blocks {
+                let Some(raw) = raw else { continue };
+                let body = pretty_json(raw).unwrap_or_else(|| raw.to_string());
+                writeln!(out, "<details><summary>{}</summary>\n\n```json\n{}\n```\n\n</details>\n", label, body)?;
+            }
+        }
+    }
+
+    Ok(out)
 }
 
 fn find_db_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
@@ -0,0 +1,177 @@
+//! Headless access to a Loreweaver database for scripting and recovery
+
+use clap::{Parser, Subcommand};
+use loreweaver_lib::commands::archive::{
+    export_campaign_archive_impl, import_campaign_archive_impl, merge_campaign_archive_impl,
+};
+use loreweaver_lib::commands::maintenance::{
+    backup_database_impl, check_database_integrity_impl, rebuild_search_index_impl,
+};
+use loreweaver_lib::db::{connect_with_settings, migrate, DatabaseSettings};
+use loreweaver_lib::export::{load_bundle, MergeStrategy};
+use sea_orm::{DatabaseConnection, EntityTrait, QueryOrder};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "loreweaver-cli")]
+#[command(about = "Export, import, back up and inspect a Loreweaver database")]
+struct Args {
+    /// Path to database file (or set LOREWEAVER_DB env var)
+    #[arg(long, env = "LOREWEAVER_DB")]
+    db: PathBuf,
+
+    /// Passphrase for an encrypted database (or set LOREWEAVER_DB_KEY env var)
+    #[arg(long, env = "LOREWEAVER_DB_KEY", hide_env_values = true)]
+    key: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Export a campaign to a .loreweaver archive
+    Export {
+        /// Campaign id
+        campaign: String,
+        /// Archive file to write
+        #[arg(long, short)]
+        out: PathBuf,
+    },
+    /// Import a .loreweaver archive
+    Import {
+        /// Archive file to read
+        archive: PathBuf,
+        /// Merge into an existing copy of the campaign using this strategy
+        #[arg(long, value_parser = ["keep_local", "take_imported", "duplicate"])]
+        merge: Option<String>,
+    },
+    /// Write a consistent copy of the whole database
+    Backup {
+        /// Backup file to create
+        #[arg(long, short)]
+        out: PathBuf,
+    },
+    /// Rebuild the full-text search index from the entity tables
+    RebuildIndex,
+    /// Run integrity checks
+    Check,
+    /// Print entity counts per campaign
+    Stats {
+        /// Only this campaign
+        #[arg(long)]
+        campaign: Option<String>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let db = open(&args).await?;
+
+    match args.command {
+        Command::Export { campaign, out } => {
+            let summary =
+                export_campaign_archive_impl(&db, campaign, out.to_string_lossy().to_string())
+                    .await?;
+            println!("Exported \"{}\" to {}", summary.campaign_name, summary.path);
+            print_counts(&summary.counts);
+        }
+        Command::Import { archive, merge } => {
+            let path = archive.to_string_lossy().to_string();
+            match merge {
+                Some(strategy) => {
+                    let strategy: MergeStrategy =
+                        serde_json::from_value(serde_json::Value::String(strategy))?;
+                    let report = merge_campaign_archive_impl(&db, path, strategy, vec![]).await?;
+                    println!(
+                        "Merged: {} conflicts, {} kept local, {} took imported, {} duplicated",
+                        report.conflicts.len(),
+                        report.kept_local,
+                        report.took_imported,
+                        report.duplicated
+                    );
+                    print_counts(&report.inserted);
+                }
+                None => {
+                    let summary = import_campaign_archive_impl(&db, path, &|_| {}).await?;
+                    println!(
+                        "Imported \"{}\" ({})",
+                        summary.campaign_name, summary.campaign_id
+                    );
+                    print_counts(&summary.counts);
+                    for issue in summary.rows.iter().flat_map(|r| r.failed.iter()) {
+                        eprintln!(
+                            "Failed {} {}: {}",
+                            issue.entity_type, issue.id, issue.reason
+                        );
+                    }
+                }
+            }
+        }
+        Command::Backup { out } => {
+            let report = backup_database_impl(&db, out.to_string_lossy().to_string()).await?;
+            println!("Backed up to {} ({} bytes)", report.path, report.size_bytes);
+        }
+        Command::RebuildIndex => {
+            let report = rebuild_search_index_impl(&db).await?;
+            println!("Search index rebuilt: {} entries", report.indexed);
+        }
+        Command::Check => {
+            let report = check_database_integrity_impl(&db).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            if !report.ok {
+                std::process::exit(1);
+            }
+        }
+        Command::Stats { campaign } => print_stats(&db, campaign).await?,
+    }
+
+    Ok(())
+}
+
+async fn open(args: &Args) -> Result<DatabaseConnection, Box<dyn std::error::Error>> {
+    if !args.db.exists() {
+        return Err(format!("Database not found: {}", args.db.display()).into());
+    }
+
+    let url = format!("sqlite:{}?mode=rwc", args.db.display());
+    let db = connect_with_settings(&url, &DatabaseSettings::default(), args.key.as_deref()).await?;
+    migrate(&db).await?;
+    Ok(db)
+}
+
+async fn print_stats(
+    db: &DatabaseConnection,
+    campaign: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use entity::campaigns::{self, Entity as Campaign};
+
+    let ids: Vec<String> = match campaign {
+        Some(id) => vec![id],
+        None => Campaign::find()
+            .order_by_asc(campaigns::Column::Name)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|c| c.id)
+            .collect(),
+    };
+
+    for id in ids {
+        let Some(bundle) = load_bundle(db, &id).await? else {
+            return Err(format!("Campaign {} not found", id).into());
+        };
+        println!("{} ({})", bundle.campaign.name, bundle.campaign.id);
+        print_counts(&bundle.counts());
+        println!();
+    }
+
+    Ok(())
+}
+
+fn print_counts(counts: &std::collections::BTreeMap<String, usize>) {
+    for (entity_type, count) in counts {
+        println!("  {:<16} {}", entity_type, count);
+    }
+}
@@ -0,0 +1,448 @@
+// Tauri commands grouped by what they can do, for capability scoping.
+//
+// `build.rs` includes this file to declare every command to Tauri and to
+// write the matching permission sets into `permissions/command-groups.toml`,
+// so a window only reaches the commands its capability grants. Each command
+// registered in `lib.rs` belongs to exactly one of the first three groups.
+//
+// Plain comments only: `include!` doesn't accept inner doc comments.
+
+/// Commands that only read campaign data
+pub const READ_ONLY_COMMANDS: &[&str] = &[
+    // Campaigns
+    "get_campaign",
+    "list_campaigns",
+    "get_campaign_settings",
+    "list_content_warnings",
+    "check_content_warnings",
+    "list_annotations",
+    "list_open_annotations",
+    "list_authored_entities",
+    // Characters
+    "get_character",
+    "list_characters",
+    "get_roleplay_card",
+    "find_duplicate_candidates",
+    "get_disposition_history",
+    "list_dispositions",
+    // Locations, organizations, quests, heroes and items
+    "get_location",
+    "list_locations",
+    "get_location_children",
+    "get_character_whereabouts",
+    "list_characters_at_location",
+    "get_organization",
+    "list_organizations",
+    "list_organization_holdings",
+    "get_organization_goal",
+    "list_organization_goals",
+    "list_goal_advances",
+    "get_world_moves_on",
+    "get_quest",
+    "list_quests",
+    "get_quest_board",
+    "get_hero",
+    "list_heroes",
+    "get_item",
+    "list_items",
+    "get_hero_inventory",
+    "find_item_holders",
+    "get_price",
+    "list_prices",
+    "list_price_modifiers",
+    "get_local_price",
+    // Players and sessions
+    "get_player",
+    "list_players",
+    "suggest_session_dates",
+    "get_session",
+    "list_sessions",
+    "list_session_absences",
+    "get_session_clock",
+    "get_play_time_report",
+    "get_gm_screen",
+    "get_progress_clock",
+    "list_progress_clocks",
+    "list_active_progress_clocks",
+    "get_encounter_table",
+    "list_encounter_tables",
+    "list_encounter_rolls",
+    "list_session_transcripts",
+    "get_transcript_chunks",
+    "detect_transcript_mentions",
+    // Timeline
+    "get_timeline_event",
+    "list_timeline_events",
+    "list_timeline_branches",
+    "compare_timeline_branches",
+    // Secrets and handouts
+    "get_secret",
+    "list_secrets",
+    "get_handout",
+    "list_handouts",
+    "list_handout_deliveries",
+    "list_player_handouts",
+    "get_content_stats",
+    "count_entities",
+    "entities_exist",
+    "preview_delete",
+    "get_entity_activity",
+    "list_entities_by_detail",
+    "get_stub_report",
+    "get_worldbuilding_gaps",
+    "player_knowledge_report",
+    // Snippets, prompt templates and the SRD
+    "get_snippet",
+    "list_snippets",
+    "search_snippets",
+    "list_recall_cards",
+    "list_due_recall_cards",
+    "list_inbox_items",
+    "parse_quick_create",
+    "get_prompt_template",
+    "list_prompt_templates",
+    "render_prompt",
+    "search_srd",
+    "get_srd_entry",
+    // Relationships, tags and search
+    "get_relationship",
+    "list_relationships",
+    "get_entity_relationships",
+    "suggest_relationships",
+    "get_relationship_strength_history",
+    "get_family_tree",
+    "get_tag",
+    "list_tags",
+    "get_entity_tags",
+    "get_tags_for_entities",
+    "suggest_tags",
+    "search_entities",
+    "search_in_location",
+    "get_command_palette_items",
+    "get_search_suggestions",
+    // Archives, sharing and sync
+    "preview_campaign_archive_merge",
+    "preview_player_snapshot",
+    "get_changes_since",
+    "get_sync_settings",
+    "get_mirror_settings",
+    // Diagnostics
+    "get_encryption_status",
+    "get_recent_logs",
+    "check_database_integrity",
+    "get_database_settings",
+    "get_local_profile",
+    "get_migration_status",
+    "get_db_info",
+    "validate_stat_block",
+    "render_stat_block",
+    "find_invalid_json_fields",
+    "render_markdown",
+    "check_links",
+    // Attachments
+    "list_entity_attachments",
+    "get_entity_portrait",
+    "get_attachment_data",
+    "get_player_entity_portrait",
+    "get_player_attachment_data",
+    // Drafts
+    "get_draft",
+];
+
+/// Commands that change campaign data, the database or files on disk
+pub const MUTATING_COMMANDS: &[&str] = &[
+    "create_campaign",
+    "update_campaign",
+    "delete_campaign",
+    "lock_campaign",
+    "unlock_campaign",
+    "create_demo_campaign",
+    "bootstrap_campaign",
+    "update_campaign_settings",
+    "create_content_warning",
+    "update_content_warning",
+    "delete_content_warning",
+    "create_annotation",
+    "update_annotation",
+    "resolve_annotation",
+    "unresolve_annotation",
+    "delete_annotation",
+    "create_character",
+    "update_character",
+    "delete_character",
+    "set_character_life_dates",
+    "add_character_alias",
+    "remove_character_alias",
+    "set_character_roleplay",
+    "convert_character_to_hero",
+    "convert_hero_to_character",
+    "merge_entities",
+    "set_entity_appearance",
+    "adjust_disposition",
+    "delete_disposition",
+    "create_location",
+    "update_location",
+    "delete_location",
+    "move_character",
+    "clear_character_whereabouts",
+    "create_organization",
+    "update_organization",
+    "delete_organization",
+    "add_organization_holding",
+    "remove_organization_holding",
+    "create_organization_goal",
+    "update_organization_goal",
+    "advance_organization_goal",
+    "delete_organization_goal",
+    "create_quest",
+    "update_quest",
+    "delete_quest",
+    "create_hero",
+    "update_hero",
+    "delete_hero",
+    "create_item",
+    "update_item",
+    "delete_item",
+    "grant_item",
+    "remove_item",
+    "transfer_item",
+    "update_hero_item",
+    "create_price",
+    "update_price",
+    "delete_price",
+    "create_price_modifier",
+    "update_price_modifier",
+    "delete_price_modifier",
+    "create_player",
+    "update_player",
+    "delete_player",
+    "set_player_availability",
+    "create_session",
+    "update_session",
+    "delete_session",
+    "save_session_summary",
+    "log_session_to_timeline",
+    "record_session_absence",
+    "remove_session_absence",
+    "start_session_clock",
+    "pause_session_clock",
+    "stop_session_clock",
+    "create_progress_clock",
+    "update_progress_clock",
+    "tick_progress_clock",
+    "reset_progress_clock",
+    "delete_progress_clock",
+    "create_encounter_table",
+    "update_encounter_table",
+    "delete_encounter_table",
+    "create_encounter_entry",
+    "update_encounter_entry",
+    "delete_encounter_entry",
+    "roll_encounter",
+    "generate_session_prep",
+    "generate_player_recap",
+    "import_session_transcript",
+    "delete_session_transcript",
+    "create_timeline_event",
+    "update_timeline_event",
+    "delete_timeline_event",
+    "export_timeline_image",
+    "create_timeline_branch",
+    "update_timeline_branch",
+    "delete_timeline_branch",
+    "create_secret",
+    "update_secret",
+    "delete_secret",
+    "add_secret_prerequisite",
+    "remove_secret_prerequisite",
+    "create_handout",
+    "update_handout",
+    "delete_handout",
+    "send_handout",
+    "mark_handout_read",
+    "revoke_handout",
+    "create_snippet",
+    "update_snippet",
+    "delete_snippet",
+    "create_recall_card",
+    "review_recall_card",
+    "generate_recall_cards",
+    "delete_recall_card",
+    "quick_capture",
+    "set_active_campaign",
+    "capture_quick_note",
+    "create_inbox_item",
+    "convert_inbox_item",
+    "dismiss_inbox_item",
+    "create_prompt_template",
+    "update_prompt_template",
+    "delete_prompt_template",
+    "import_srd_entry",
+    "create_relationship",
+    "update_relationship",
+    "delete_relationship",
+    "export_relationships_csv",
+    "adjust_relationship_strength",
+    "apply_relationship_decay",
+    "create_tag",
+    "delete_tag",
+    "add_entity_tag",
+    "remove_entity_tag",
+    "find_and_replace",
+    "record_search_query",
+    "clear_search_history",
+    "export_campaign_archive",
+    "import_campaign_archive",
+    "merge_campaign_archive",
+    "archive_campaign",
+    "unarchive_campaign",
+    "import_markdown_folder",
+    "export_roll20_journal",
+    "export_homebrewery",
+    "export_player_snapshot",
+    "start_player_share",
+    "stop_player_share",
+    "update_sync_settings",
+    "sync_campaign",
+    "export_changes_since",
+    "update_mirror_settings",
+    "mirror_campaign",
+    "unlock_database",
+    "encrypt_database",
+    "forget_database_key",
+    "set_log_level",
+    "optimize_database",
+    "rebuild_search_index",
+    "repair_orphaned_references",
+    "backup_database",
+    "rollback_last_migration",
+    "update_database_settings",
+    "set_local_profile",
+    "delete_attachment",
+    "save_draft",
+    "discard_draft",
+];
+
+/// Commands backing the AI assistant, including its usage records
+pub const AI_COMMANDS: &[&str] = &[
+    "build_ai_system_prompt",
+    "get_or_create_ai_conversation",
+    "load_ai_conversation",
+    "add_ai_message",
+    "update_ai_token_counts",
+    "clear_ai_conversation",
+    "update_ai_message_proposal",
+    "update_ai_agent_messages",
+    "export_ai_conversation",
+    "archive_ai_conversation",
+    "preview_proposal",
+    "estimate_prompt_tokens",
+    "get_ai_budget_usage",
+    "check_ai_budget",
+    "estimate_request_tokens",
+    "generate_entity_image",
+    // Sends entity text to the embedding provider when one is given
+    "get_related_entities",
+];
+
+/// Read-only commands that reveal nothing beyond what players may see, for
+/// the player-view window
+pub const PLAYER_SAFE_COMMANDS: &[&str] = &[
+    "preview_player_snapshot",
+    "list_player_handouts",
+    "get_session_clock",
+    "get_player_entity_portrait",
+    "get_player_attachment_data",
+];
+
+/// Commands for the tray's quick-note window, which may only file notes
+pub const QUICK_NOTE_COMMANDS: &[&str] = &["capture_quick_note"];
+
+/// Permission sets written to `permissions/command-groups.toml`, as
+/// (identifier, description, commands)
+pub const COMMAND_GROUPS: &[(&str, &str, &[&str])] = &[
+    (
+        "read-only",
+        "Commands that only read campaign data",
+        READ_ONLY_COMMANDS,
+    ),
+    (
+        "mutating",
+        "Commands that change campaign data, the database or files on disk",
+        MUTATING_COMMANDS,
+    ),
+    ("ai", "Commands backing the AI assistant", AI_COMMANDS),
+    (
+        "player-safe",
+        "Read-only commands safe for the player-view window",
+        PLAYER_SAFE_COMMANDS,
+    ),
+    (
+        "quick-note",
+        "Commands for the tray's quick-note window",
+        QUICK_NOTE_COMMANDS,
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Command names registered in `lib.rs`'s `generate_handler!`
+    fn registered_commands() -> Vec<&'static str> {
+        let source = include_str!("lib.rs");
+        let start = source.find("generate_handler![").unwrap();
+        let end = start + source[start..].find("])").unwrap();
+        source[start..end]
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("commands::"))
+            .map(|line| line.trim_end_matches(',').rsplit("::").next().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_every_command_in_one_group() {
+        let mut grouped = HashSet::new();
+        for command in READ_ONLY_COMMANDS
+            .iter()
+            .chain(MUTATING_COMMANDS)
+            .chain(AI_COMMANDS)
+        {
+            assert!(grouped.insert(*command), "{} is in two groups", command);
+        }
+
+        let registered = registered_commands();
+        for command in &registered {
+            assert!(grouped.contains(command), "{} has no group", command);
+        }
+        assert_eq!(
+            registered.len(),
+            grouped.len(),
+            "a grouped command isn't registered"
+        );
+    }
+
+    #[test]
+    fn test_player_safe_commands_are_read_only() {
+        for command in PLAYER_SAFE_COMMANDS {
+            assert!(
+                READ_ONLY_COMMANDS.contains(command),
+                "{} is not read-only",
+                command
+            );
+        }
+    }
+
+    #[test]
+    fn test_quick_note_commands_are_grouped() {
+        for command in QUICK_NOTE_COMMANDS {
+            assert!(
+                MUTATING_COMMANDS.contains(command),
+                "{} is not in the mutating group",
+                command
+            );
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! A chronological feed for an entity's detail page, pieced together from
+//! the timestamps already stored: the entity's own rows, relationships to
+//! it, secrets about it and sessions whose notes mention it. There is no
+//! separate audit log, so edits only show up as the latest update.
+
+use crate::commands::references::REFERENCEABLE_TYPES;
+use crate::commands::registry::table_for;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::relationships::{self, Entity as Relationship};
+use ::entity::secrets::{self, Entity as Secret};
+use ::entity::sessions::{self, Entity as Session};
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+use tracing::instrument;
+
+/// Kinds of entry in an activity feed
+pub const ACTIVITY_KINDS: &[&str] = &[
+    "created",
+    "updated",
+    "relationship_added",
+    "relationship_updated",
+    "secret_added",
+    "secret_revealed",
+    "session_appearance",
+];
+
+const DEFAULT_LIMIT: u32 = 100;
+const MAX_LIMIT: u32 = 500;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    /// One of `ACTIVITY_KINDS`
+    pub kind: String,
+    pub occurred_at: String,
+    pub summary: String,
+    /// The other side of the entry: a relationship's other end, a secret or
+    /// a session
+    pub related_type: Option<String>,
+    pub related_id: Option<String>,
+    pub related_name: Option<String>,
+    pub session_number: Option<i32>,
+}
+
+impl ActivityEntry {
+    fn new(kind: &str, occurred_at: DateTime<Utc>, summary: String) -> Self {
+        Self {
+            kind: kind.to_string(),
+            occurred_at: occurred_at.to_rfc3339(),
+            summary,
+            related_type: None,
+            related_id: None,
+            related_name: None,
+            session_number: None,
+        }
+    }
+
+    fn related(mut self, entity_type: &str, id: &str, name: Option<String>) -> Self {
+        self.related_type = Some(entity_type.to_string());
+        self.related_id = Some(id.to_string());
+        self.related_name = name;
+        self
+    }
+}
+
+/// When a session happened: its start, its date, or when it was created
+pub(crate) fn session_time(session: &sessions::Model) -> DateTime<Utc> {
+    session
+        .started_at
+        .or_else(|| {
+            session
+                .date
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc())
+        })
+        .unwrap_or(session.created_at)
+}
+
+fn session_label(session: &sessions::Model) -> String {
+    match &session.title {
+        Some(title) => format!("Session {}: {}", session.session_number, title),
+        None => format!("Session {}", session.session_number),
+    }
+}
+
+/// Display names of entities, looked up once each
+struct NameCache<'a> {
+    db: &'a DatabaseConnection,
+    names: HashMap<(String, String), Option<String>>,
+}
+
+impl<'a> NameCache<'a> {
+    fn new(db: &'a DatabaseConnection) -> Self {
+        Self {
+            db,
+            names: HashMap::new(),
+        }
+    }
+
+    async fn get(&mut self, entity_type: &str, id: &str) -> Result<Option<String>, AppError> {
+        let key = (entity_type.to_string(), id.to_string());
+        if let Some(name) = self.names.get(&key) {
+            return Ok(name.clone());
+        }
+        let name = match table_for(entity_type).and_then(|t| Some((t.table, t.name_column?))) {
+            Some((table, column)) => self
+                .db
+                .query_one(Statement::from_sql_and_values(
+                    self.db.get_database_backend(),
+                    format!("SELECT {column} AS name FROM {table} WHERE id = $1"),
+                    [id.into()],
+                ))
+                .await?
+                .map(|row| row.try_get::<Option<String>>("", "name"))
+                .transpose()?
+                .flatten(),
+            None => None,
+        };
+        self.names.insert(key, name.clone());
+        Ok(name)
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Everything that happened to an entity, newest first
+#[instrument(skip_all, fields(entity_type = %entity_type, id = %id), err)]
+pub async fn get_entity_activity_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    id: String,
+    limit: Option<u32>,
+) -> Result<Vec<ActivityEntry>, AppError> {
+    if !REFERENCEABLE_TYPES.contains(&entity_type.as_str()) {
+        return Err(AppError::Validation(format!(
+            "entity_type: must be one of: {}",
+            REFERENCEABLE_TYPES.join(", ")
+        )));
+    }
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+    let table = table_for(&entity_type).expect("referenceable types are registered");
+
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "SELECT campaign_id, created_at, updated_at FROM {} WHERE id = $1",
+                table.table
+            ),
+            [id.clone().into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} {} not found", entity_type, id)))?;
+    let campaign_id: String = row.try_get("", "campaign_id")?;
+    let created_at: DateTime<Utc> = row.try_get("", "created_at")?;
+    let updated_at: DateTime<Utc> = row.try_get("", "updated_at")?;
+
+    let mut names = NameCache::new(db);
+    let mut feed = vec![ActivityEntry::new(
+        "created",
+        created_at,
+        format!("Created {}", entity_type.replace('_', " ")),
+    )];
+    if updated_at > created_at {
+        feed.push(ActivityEntry::new(
+            "updated",
+            updated_at,
+            "Last edited".to_string(),
+        ));
+    }
+
+    let relationships = Relationship::find()
+        .filter(
+            Condition::any()
+                .add(
+                    relationships::Column::SourceType
+                        .eq(&entity_type)
+                        .and(relationships::Column::SourceId.eq(&id)),
+                )
+                .add(
+                    relationships::Column::TargetType
+                        .eq(&entity_type)
+                        .and(relationships::Column::TargetId.eq(&id)),
+                ),
+        )
+        .all(db)
+        .await?;
+    for r in relationships {
+        let (other_type, other_id) = if r.source_type == entity_type && r.source_id == id {
+            (r.target_type.clone(), r.target_id.clone())
+        } else {
+            (r.source_type.clone(), r.source_id.clone())
+        };
+        let other_name = names.get(&other_type, &other_id).await?;
+        let label = other_name.clone().unwrap_or_else(|| other_type.clone());
+        let relationship_type = r.relationship_type.replace('_', " ");
+
+        feed.push(
+            ActivityEntry::new(
+                "relationship_added",
+                r.created_at,
+                format!("Linked to {} ({})", label, relationship_type),
+            )
+            .related(&other_type, &other_id, other_name.clone()),
+        );
+        if r.updated_at > r.created_at {
+            feed.push(
+                ActivityEntry::new(
+                    "relationship_updated",
+                    r.updated_at,
+                    format!("Relationship with {} changed", label),
+                )
+                .related(&other_type, &other_id, other_name),
+            );
+        }
+    }
+
+    let sessions = Session::find()
+        .filter(sessions::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?;
+    let by_number: HashMap<i32, &sessions::Model> =
+        sessions.iter().map(|s| (s.session_number, s)).collect();
+
+    let secrets = Secret::find()
+        .filter(secrets::Column::RelatedEntityType.eq(&entity_type))
+        .filter(secrets::Column::RelatedEntityId.eq(&id))
+        .all(db)
+        .await?;
+    for s in secrets {
+        feed.push(
+            ActivityEntry::new(
+                "secret_added",
+                s.created_at,
+                format!("Secret added: {}", s.title),
+            )
+            .related("secret", &s.id, Some(s.title.clone())),
+        );
+        if s.revealed {
+            let session = s
+                .revealed_in_session
+                .and_then(|n| by_number.get(&n).copied());
+            let mut entry = ActivityEntry::new(
+                "secret_revealed",
+                session.map(session_time).unwrap_or(s.updated_at),
+                match session {
+                    Some(session) => {
+                        format!("Secret revealed in {}: {}", session_label(session), s.title)
+                    }
+                    None => format!("Secret revealed: {}", s.title),
+                },
+            )
+            .related("secret", &s.id, Some(s.title.clone()));
+            entry.session_number = s.revealed_in_session;
+            feed.push(entry);
+        }
+    }
+
+    // Ids are UUIDs, so a substring match only finds mentions
+    let mentioned_in = |text: &Option<String>| text.as_deref().is_some_and(|t| t.contains(&id));
+    for session in &sessions {
+        if session.id == id {
+            continue;
+        }
+        let appears = [
+            &session.planned_content,
+            &session.notes,
+            &session.summary,
+            &session.highlights,
+        ]
+        .into_iter()
+        .any(mentioned_in);
+        if appears {
+            let label = session_label(session);
+            let mut entry = ActivityEntry::new(
+                "session_appearance",
+                session_time(session),
+                format!("Appeared in {}", label),
+            )
+            .related("session", &session.id, Some(label));
+            entry.session_number = Some(session.session_number);
+            feed.push(entry);
+        }
+    }
+
+    // RFC 3339 strings in UTC sort chronologically
+    feed.sort_by(|a, b| b.occurred_at.cmp(&a.occurred_at));
+    feed.truncate(limit);
+    Ok(feed)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_entity_activity(
+    state: State<'_, AppState>,
+    entity_type: String,
+    id: String,
+    limit: Option<u32>,
+) -> Result<Vec<ActivityEntry>, AppError> {
+    get_entity_activity_impl(&state.db, entity_type, id, limit).await
+}
@@ -0,0 +1,299 @@
+use crate::commands::campaign_settings::get_campaign_settings_impl;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::tokenizer::{count_message_tokens, count_tokens};
+use ::entity::ai_usage::{self, Entity as AiUsage};
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Tokens and estimated cost of one agent run
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunUsage {
+    pub input_tokens: i32,
+    pub output_tokens: i32,
+    pub cache_read_tokens: i32,
+    pub cache_creation_tokens: i32,
+    pub cost_usd: Option<f64>,
+}
+
+impl RunUsage {
+    fn total_tokens(&self) -> u64 {
+        [
+            self.input_tokens,
+            self.output_tokens,
+            self.cache_read_tokens,
+            self.cache_creation_tokens,
+        ]
+        .iter()
+        .map(|t| (*t).max(0) as u64)
+        .sum()
+    }
+}
+
+/// A campaign's AI usage this calendar month against its budgets
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AiBudgetUsage {
+    pub campaign_id: String,
+    /// Month being counted, e.g. "2026-10" (UTC)
+    pub month: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub tokens_used: u64,
+    pub cost_used: f64,
+    pub token_budget: Option<u32>,
+    pub cost_budget: Option<f64>,
+    /// Share of the token budget used, for a progress bar; may exceed 1
+    pub token_fraction: Option<f64>,
+    pub cost_fraction: Option<f64>,
+    pub enforcement: String,
+    pub over_budget: bool,
+}
+
+/// Whether a request may go ahead under the monthly budget
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AiBudgetCheck {
+    pub allowed: bool,
+    /// The request would go over budget and the GM must confirm it
+    pub requires_confirmation: bool,
+    /// Which budget would be exceeded, and by how much
+    pub reason: Option<String>,
+    pub usage: AiBudgetUsage,
+}
+
+const MICROUSD_PER_USD: f64 = 1_000_000.0;
+
+/// First instant of the month containing `now`, and of the month after
+fn month_bounds(now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let (year, month) = (now.year(), now.month());
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    (
+        Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(next_year, next_month, 1, 0, 0, 0)
+            .unwrap(),
+    )
+}
+
+/// Log a finished agent run against its campaign's monthly usage
+pub(crate) async fn record_ai_usage<C: ConnectionTrait>(
+    db: &C,
+    campaign_id: &str,
+    conversation_id: Option<&str>,
+    usage: RunUsage,
+) -> Result<(), AppError> {
+    if usage.total_tokens() == 0 {
+        return Ok(());
+    }
+
+    let cost_microusd = usage
+        .cost_usd
+        .filter(|c| c.is_finite() && *c > 0.0)
+        .map(|c| (c * MICROUSD_PER_USD).round() as i64)
+        .unwrap_or(0);
+
+    ai_usage::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id.to_string()),
+        conversation_id: Set(conversation_id.map(str::to_string)),
+        input_tokens: Set(usage.input_tokens),
+        output_tokens: Set(usage.output_tokens),
+        cache_read_tokens: Set(usage.cache_read_tokens),
+        cache_creation_tokens: Set(usage.cache_creation_tokens),
+        cost_microusd: Set(cost_microusd),
+        created_at: Set(chrono::Utc::now()),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(())
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_ai_budget_usage_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<AiBudgetUsage, AppError> {
+    let settings = get_campaign_settings_impl(db, campaign_id.clone()).await?;
+    let (start, end) = month_bounds(Utc::now());
+
+    let rows = AiUsage::find()
+        .filter(ai_usage::Column::CampaignId.eq(&campaign_id))
+        .filter(ai_usage::Column::CreatedAt.gte(start))
+        .filter(ai_usage::Column::CreatedAt.lt(end))
+        .all(db)
+        .await?;
+
+    let tokens_used: u64 = rows
+        .iter()
+        .map(|r| {
+            RunUsage {
+                input_tokens: r.input_tokens,
+                output_tokens: r.output_tokens,
+                cache_read_tokens: r.cache_read_tokens,
+                cache_creation_tokens: r.cache_creation_tokens,
+                cost_usd: None,
+            }
+            .total_tokens()
+        })
+        .sum();
+    let cost_used = rows.iter().map(|r| r.cost_microusd).sum::<i64>() as f64 / MICROUSD_PER_USD;
+
+    let token_budget = settings.ai.monthly_token_budget;
+    let cost_budget = settings.ai.monthly_cost_budget;
+    let token_fraction = token_budget.map(|b| tokens_used as f64 / b as f64);
+    let cost_fraction = cost_budget.map(|b| cost_used / b);
+
+    Ok(AiBudgetUsage {
+        campaign_id,
+        month: start.format("%Y-%m").to_string(),
+        period_start: start.to_string(),
+        period_end: end.to_string(),
+        tokens_used,
+        cost_used,
+        token_budget,
+        cost_budget,
+        token_fraction,
+        cost_fraction,
+        enforcement: settings
+            .ai
+            .budget_enforcement
+            .unwrap_or_else(|| "confirm".to_string()),
+        over_budget: token_fraction.is_some_and(|f| f >= 1.0)
+            || cost_fraction.is_some_and(|f| f >= 1.0),
+    })
+}
+
+/// Check a request of about `estimated_tokens` against the monthly budgets.
+/// Over budget, "block" campaigns get a validation error and "confirm"
+/// campaigns are allowed only once `confirmed` is set.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn check_ai_budget_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    estimated_tokens: u32,
+    estimated_cost_usd: Option<f64>,
+    confirmed: bool,
+) -> Result<AiBudgetCheck, AppError> {
+    let usage = get_ai_budget_usage_impl(db, campaign_id).await?;
+
+    let projected_tokens = usage.tokens_used + estimated_tokens as u64;
+    let projected_cost = usage.cost_used + estimated_cost_usd.unwrap_or(0.0).max(0.0);
+
+    let reason = match (usage.token_budget, usage.cost_budget) {
+        (Some(budget), _) if projected_tokens > budget as u64 => Some(format!(
+            "this request would bring the month to about {} tokens, over the budget of {}",
+            projected_tokens, budget
+        )),
+        (_, Some(budget)) if projected_cost > budget => Some(format!(
+            "this request would bring the month to about ${:.2}, over the budget of ${:.2}",
+            projected_cost, budget
+        )),
+        _ => None,
+    };
+
+    let Some(reason) = reason else {
+        return Ok(AiBudgetCheck {
+            allowed: true,
+            requires_confirmation: false,
+            reason: None,
+            usage,
+        });
+    };
+
+    if usage.enforcement == "block" {
+        return Err(AppError::Validation(format!(
+            "budget: monthly AI budget reached; {}",
+            reason
+        )));
+    }
+
+    Ok(AiBudgetCheck {
+        allowed: confirmed,
+        requires_confirmation: !confirmed,
+        reason: Some(reason),
+        usage,
+    })
+}
+
+/// Estimated input tokens of a one-off request outside a conversation,
+/// counted the same way `estimate_prompt_tokens` counts one, for sizing a
+/// budget check before the call
+pub fn estimate_request_tokens_impl(system_prompt: Option<&str>, messages: &[String]) -> u32 {
+    let tokens = system_prompt.map(count_tokens).unwrap_or(0)
+        + messages
+            .iter()
+            .map(String::as_str)
+            .map(count_message_tokens)
+            .sum::<usize>();
+    u32::try_from(tokens).unwrap_or(u32::MAX)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_ai_budget_usage(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<AiBudgetUsage, AppError> {
+    get_ai_budget_usage_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_ai_budget(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    estimated_tokens: u32,
+    estimated_cost_usd: Option<f64>,
+    confirmed: Option<bool>,
+) -> Result<AiBudgetCheck, AppError> {
+    check_ai_budget_impl(
+        &state.db,
+        campaign_id,
+        estimated_tokens,
+        estimated_cost_usd,
+        confirmed.unwrap_or(false),
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn estimate_request_tokens(
+    system_prompt: Option<String>,
+    messages: Vec<String>,
+) -> Result<u32, AppError> {
+    Ok(estimate_request_tokens_impl(
+        system_prompt.as_deref(),
+        &messages,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_bounds() {
+        let now = Utc.with_ymd_and_hms(2026, 12, 15, 8, 30, 0).unwrap();
+        let (start, end) = month_bounds(now);
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_estimate_request_tokens() {
+        assert_eq!(estimate_request_tokens_impl(None, &[]), 0);
+        let messages = ["Who rules Waterdeep?".to_string()];
+        assert_eq!(
+            estimate_request_tokens_impl(Some("You are a helpful archivist."), &messages) as usize,
+            count_tokens("You are a helpful archivist.") + count_message_tokens(&messages[0])
+        );
+    }
+}
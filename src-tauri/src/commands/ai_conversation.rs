@@ -1,10 +1,21 @@
+use crate::commands::ai_budget::{record_ai_usage, RunUsage};
+use crate::commands::campaign_settings::get_campaign_settings_impl;
+use crate::commands::json_schema::{PatchType, Proposal, ProposalOperation, ProposalStatus};
+use crate::commands::registry::table_for;
+use crate::commands::snippet::{create_snippet_impl, SnippetResponse};
+use crate::commands::validation::CreateSnippetInput;
 use crate::db::AppState;
 use crate::error::AppError;
+use crate::sync::rows::load_row_json;
+use crate::text::plain_text;
+use crate::tokenizer::{count_message_tokens, count_tokens, CONTEXT_WINDOW_TOKENS};
 use ::entity::ai_conversations::{self, Entity as AiConversation};
 use ::entity::ai_messages::{self, Entity as AiMessage};
+use ::entity::campaigns::Entity as Campaign;
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
 
 // ============ Response Types ============
 
@@ -76,8 +87,266 @@ pub struct ConversationWithMessages {
     pub messages: Vec<AiMessageResponse>,
 }
 
+/// One field of an update or patch proposal next to the stored value
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProposalFieldDiff {
+    pub field: String,
+    /// Stored value; null when the entity has no such field
+    pub current: serde_json::Value,
+    pub proposed: serde_json::Value,
+    /// False when the proposal leaves the field's text unchanged
+    pub changed: bool,
+    /// False when the entity has no column by this name, so applying the
+    /// proposal would ignore it
+    pub known_field: bool,
+}
+
+/// What applying a proposal would change, field by field
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProposalPreview {
+    pub message_id: String,
+    pub proposal_id: String,
+    pub operation: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub entity_name: Option<String>,
+    pub status: ProposalStatus,
+    pub fields: Vec<ProposalFieldDiff>,
+}
+
+/// Estimated size of the next request in a conversation
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PromptTokenEstimate {
+    pub conversation_id: String,
+    pub system_tokens: usize,
+    /// Prior turns the agent will resend
+    pub history_tokens: usize,
+    pub draft_tokens: usize,
+    pub total_tokens: usize,
+    pub context_window: usize,
+    /// Room kept free for the response (the campaign's `max_tokens`)
+    pub reserved_output_tokens: usize,
+    /// The campaign's `token_budget`, if set
+    pub budget: Option<usize>,
+    pub exceeds_context: bool,
+    pub exceeds_budget: bool,
+}
+
+/// A conversation rendered for archiving
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConversationExport {
+    pub conversation_id: String,
+    pub format: String,
+    pub content: String,
+    /// Where the export was written, if a path was given
+    pub path: Option<String>,
+}
+
+/// Message as written to a JSON export, with stored JSON columns parsed
+#[derive(Debug, Serialize)]
+struct ExportedMessage {
+    role: String,
+    content: String,
+    tool_name: Option<String>,
+    tool_input: Option<serde_json::Value>,
+    tool_data: Option<serde_json::Value>,
+    proposal: Option<serde_json::Value>,
+    message_order: i32,
+    created_at: String,
+}
+
+// ============ Export Rendering ============
+
+/// Parse a stored JSON column, keeping the raw text if it isn't valid JSON
+fn parse_stored_json(raw: Option<&str>) -> Option<serde_json::Value> {
+    raw.map(|raw| {
+        serde_json::from_str(raw).unwrap_or_else(|_| serde_json::Value::String(raw.to_string()))
+    })
+}
+
+fn pretty_json(raw: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(raw)
+        .ok()
+        .and_then(|parsed| serde_json::to_string_pretty(&parsed).ok())
+        .unwrap_or_else(|| raw.to_string())
+}
+
+fn role_heading(role: &str) -> &str {
+    match role {
+        "user" => "You",
+        "assistant" => "Assistant",
+        "tool" => "Tool call",
+        "error" => "Error",
+        "proposal" => "Proposal",
+        other => other,
+    }
+}
+
+/// Field list for proposal data, one bullet per field
+fn render_fields(out: &mut String, fields: &serde_json::Map<String, serde_json::Value>) {
+    for (key, value) in fields {
+        match value {
+            serde_json::Value::String(text) if text.contains('\n') => {
+                out.push_str(&format!("- **{}:**\n", key));
+                for line in text.lines() {
+                    out.push_str(&format!("  > {}\n", line));
+                }
+            }
+            serde_json::Value::String(text) => out.push_str(&format!("- **{}:** {}\n", key, text)),
+            serde_json::Value::Null => {}
+            other => out.push_str(&format!("- **{}:** `{}`\n", key, other)),
+        }
+    }
+}
+
+fn render_proposal(out: &mut String, raw: &str) {
+    let Ok(proposal) = Proposal::parse(raw) else {
+        out.push_str(&format!("```json\n{}\n```\n\n", pretty_json(raw)));
+        return;
+    };
+
+    let status = match proposal.status {
+        ProposalStatus::Pending => "pending",
+        ProposalStatus::Accepted => "accepted",
+        ProposalStatus::Rejected => "rejected",
+    };
+    match &proposal.operation {
+        ProposalOperation::Create(create) => {
+            out.push_str(&format!(
+                "**Create {}** ({})\n\n",
+                create.entity_type, status
+            ));
+            render_fields(out, &create.data);
+        }
+        ProposalOperation::Update(update) => {
+            out.push_str(&format!(
+                "**Update {}** `{}` ({})\n\n",
+                update.entity_type, update.entity_id, status
+            ));
+            render_fields(out, &update.changes);
+        }
+        ProposalOperation::Patch(patch) => {
+            out.push_str(&format!(
+                "**Patch {}** `{}` ({})\n\n",
+                patch.entity_type, patch.entity_id, status
+            ));
+            for field in &patch.patches {
+                let lang = match field.patch_type {
+                    PatchType::UnifiedDiff => "diff",
+                    PatchType::JsonPatch => "json",
+                };
+                out.push_str(&format!(
+                    "- **{}:**\n\n```{}\n{}\n```\n",
+                    field.field,
+                    lang,
+                    field.patch.trim_end()
+                ));
+            }
+        }
+        ProposalOperation::Relationship(rel) => {
+            out.push_str(&format!(
+                "**Relationship** ({}): {} `{}` *{}* {} `{}`\n",
+                status,
+                rel.source_type,
+                rel.source_id,
+                rel.relationship_type,
+                rel.target_type,
+                rel.target_id
+            ));
+        }
+    }
+    if let Some(reasoning) = proposal.reasoning.as_deref().filter(|r| !r.is_empty()) {
+        out.push_str(&format!("\n_Reasoning:_ {}\n", reasoning));
+    }
+    out.push('\n');
+}
+
+fn render_markdown(
+    campaign_name: &str,
+    conversation: &ai_conversations::Model,
+    messages: &[ai_messages::Model],
+) -> String {
+    let mut out = format!("# AI conversation: {}\n\n", campaign_name);
+    out.push_str(&format!("- **Context:** {}\n", conversation.context_type));
+    out.push_str(&format!(
+        "- **Started:** {}\n",
+        conversation.created_at.format("%Y-%m-%d %H:%M")
+    ));
+    out.push_str(&format!(
+        "- **Updated:** {}\n",
+        conversation.updated_at.format("%Y-%m-%d %H:%M")
+    ));
+    out.push_str(&format!(
+        "- **Tokens:** {} in / {} out\n\n",
+        conversation.total_input_tokens, conversation.total_output_tokens
+    ));
+    if messages.is_empty() {
+        out.push_str("_No messages._\n");
+        return out;
+    }
+
+    for msg in messages {
+        out.push_str(&format!("## {}", role_heading(&msg.role)));
+        if let Some(tool_name) = &msg.tool_name {
+            out.push_str(&format!(": `{}`", tool_name));
+        }
+        out.push_str("\n\n");
+
+        if !msg.content.trim().is_empty() {
+            out.push_str(msg.content.trim_end());
+            out.push_str("\n\n");
+        }
+        if let Some(input) = &msg.tool_input_json {
+            out.push_str(&format!(
+                "<details><summary>Input</summary>\n\n```json\n{}\n```\n\n</details>\n\n",
+                pretty_json(input)
+            ));
+        }
+        if let Some(data) = &msg.tool_data_json {
+            out.push_str(&format!(
+                "<details><summary>Result</summary>\n\n```json\n{}\n```\n\n</details>\n\n",
+                pretty_json(data)
+            ));
+        }
+        if let Some(proposal) = &msg.proposal_json {
+            render_proposal(&mut out, proposal);
+        }
+    }
+    out
+}
+
+fn render_json(
+    campaign_name: &str,
+    conversation: &ai_conversations::Model,
+    messages: &[ai_messages::Model],
+) -> Result<String, AppError> {
+    let messages: Vec<ExportedMessage> = messages
+        .iter()
+        .map(|msg| ExportedMessage {
+            role: msg.role.clone(),
+            content: msg.content.clone(),
+            tool_name: msg.tool_name.clone(),
+            tool_input: parse_stored_json(msg.tool_input_json.as_deref()),
+            tool_data: parse_stored_json(msg.tool_data_json.as_deref()),
+            proposal: parse_stored_json(msg.proposal_json.as_deref()),
+            message_order: msg.message_order,
+            created_at: msg.created_at.to_string(),
+        })
+        .collect();
+
+    let export = serde_json::json!({
+        "campaign_name": campaign_name,
+        "conversation": AiConversationResponse::from(conversation.clone()),
+        "messages": messages,
+    });
+    serde_json::to_string_pretty(&export)
+        .map(|json| json + "\n")
+        .map_err(|e| AppError::Internal(format!("Failed to serialize conversation: {}", e)))
+}
+
 // ============ Core Implementation Functions ============
 
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn get_or_create_conversation_impl(
     db: &DatabaseConnection,
     campaign_id: String,
@@ -115,6 +384,7 @@ pub async fn get_or_create_conversation_impl(
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn load_conversation_impl(
     db: &DatabaseConnection,
     campaign_id: String,
@@ -144,6 +414,7 @@ pub async fn load_conversation_impl(
 }
 
 #[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(conversation_id = %conversation_id), err)]
 pub async fn add_message_impl(
     db: &DatabaseConnection,
     conversation_id: String,
@@ -154,6 +425,10 @@ pub async fn add_message_impl(
     tool_data_json: Option<String>,
     proposal_json: Option<String>,
 ) -> Result<AiMessageResponse, AppError> {
+    if let Some(proposal) = &proposal_json {
+        Proposal::parse(proposal)?;
+    }
+
     // Get next message order by counting existing messages
     let message_count = AiMessage::find()
         .filter(ai_messages::Column::ConversationId.eq(&conversation_id))
@@ -179,9 +454,16 @@ pub async fn add_message_impl(
     };
 
     let result = model.insert(db).await?;
+    tracing::debug!(
+        role = %result.role,
+        tool_name = ?result.tool_name,
+        message_order = result.message_order,
+        "Stored AI message"
+    );
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(conversation_id = %conversation_id), err)]
 pub async fn update_token_counts_impl(
     db: &DatabaseConnection,
     conversation_id: String,
@@ -189,6 +471,7 @@ pub async fn update_token_counts_impl(
     output_tokens: i32,
     cache_read_tokens: i32,
     cache_creation_tokens: i32,
+    cost_usd: Option<f64>,
 ) -> Result<AiConversationResponse, AppError> {
     let conversation = AiConversation::find_by_id(&conversation_id)
         .one(db)
@@ -199,6 +482,28 @@ pub async fn update_token_counts_impl(
     let new_output = conversation.total_output_tokens + output_tokens;
     let new_cache_read = conversation.total_cache_read_tokens + cache_read_tokens;
     let new_cache_creation = conversation.total_cache_creation_tokens + cache_creation_tokens;
+    tracing::debug!(
+        input_tokens,
+        output_tokens,
+        cache_read_tokens,
+        cache_creation_tokens,
+        "Recording AI token usage"
+    );
+
+    // Also logged per run so monthly budgets survive clearing the conversation
+    record_ai_usage(
+        db,
+        &conversation.campaign_id,
+        Some(&conversation.id),
+        RunUsage {
+            input_tokens,
+            output_tokens,
+            cache_read_tokens,
+            cache_creation_tokens,
+            cost_usd,
+        },
+    )
+    .await?;
 
     let mut active: ai_conversations::ActiveModel = conversation.into();
     active.total_input_tokens = Set(new_input);
@@ -211,6 +516,7 @@ pub async fn update_token_counts_impl(
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(conversation_id = %conversation_id), err)]
 pub async fn clear_conversation_impl(
     db: &DatabaseConnection,
     conversation_id: String,
@@ -240,6 +546,7 @@ pub async fn clear_conversation_impl(
     Ok(result.rows_affected > 0)
 }
 
+#[instrument(skip_all, fields(message_id = %message_id), err)]
 pub async fn update_message_proposal_impl(
     db: &DatabaseConnection,
     message_id: String,
@@ -249,6 +556,7 @@ pub async fn update_message_proposal_impl(
         .one(db)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Message {} not found", message_id)))?;
+    Proposal::parse(&proposal_json)?;
 
     let mut active: ai_messages::ActiveModel = message.into();
     active.proposal_json = Set(Some(proposal_json));
@@ -257,6 +565,7 @@ pub async fn update_message_proposal_impl(
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(conversation_id = %conversation_id), err)]
 pub async fn update_agent_messages_impl(
     db: &DatabaseConnection,
     conversation_id: String,
@@ -275,6 +584,289 @@ pub async fn update_agent_messages_impl(
     Ok(())
 }
 
+/// Render a conversation as Markdown or JSON, optionally writing it to `path`
+#[instrument(skip_all, fields(conversation_id = %conversation_id, format = %format), err)]
+pub async fn export_conversation_impl(
+    db: &DatabaseConnection,
+    conversation_id: String,
+    format: String,
+    path: Option<String>,
+) -> Result<ConversationExport, AppError> {
+    let format = match format.to_lowercase().as_str() {
+        "markdown" | "md" => "markdown",
+        "json" => "json",
+        _ => {
+            return Err(AppError::Validation(
+                "format: must be markdown or json".to_string(),
+            ))
+        }
+    };
+
+    let conversation = AiConversation::find_by_id(&conversation_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Conversation {} not found", conversation_id)))?;
+    let campaign_name = Campaign::find_by_id(&conversation.campaign_id)
+        .one(db)
+        .await?
+        .map(|c| c.name)
+        .unwrap_or_else(|| conversation.campaign_id.clone());
+    let messages = AiMessage::find()
+        .filter(ai_messages::Column::ConversationId.eq(&conversation_id))
+        .order_by_asc(ai_messages::Column::MessageOrder)
+        .all(db)
+        .await?;
+
+    let content = if format == "json" {
+        render_json(&campaign_name, &conversation, &messages)?
+    } else {
+        render_markdown(&campaign_name, &conversation, &messages)
+    };
+
+    if let Some(path) = &path {
+        std::fs::write(path, &content)
+            .map_err(|e| AppError::Internal(format!("Failed to write {}: {}", path, e)))?;
+        tracing::info!(path = %path, messages = messages.len(), "Exported AI conversation");
+    }
+
+    Ok(ConversationExport {
+        conversation_id,
+        format: format.to_string(),
+        content,
+        path,
+    })
+}
+
+/// Save a Markdown copy of a conversation to the campaign's snippet library
+#[instrument(skip_all, fields(conversation_id = %conversation_id), err)]
+pub async fn archive_conversation_impl(
+    db: &DatabaseConnection,
+    conversation_id: String,
+    name: Option<String>,
+) -> Result<SnippetResponse, AppError> {
+    let conversation = AiConversation::find_by_id(&conversation_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Conversation {} not found", conversation_id)))?;
+    let export =
+        export_conversation_impl(db, conversation_id, "markdown".to_string(), None).await?;
+
+    let name = name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| {
+            format!(
+                "AI conversation {}",
+                chrono::Utc::now().format("%Y-%m-%d %H:%M")
+            )
+        });
+    create_snippet_impl(
+        db,
+        CreateSnippetInput {
+            name,
+            campaign_id: conversation.campaign_id,
+            snippet_type: "other".to_string(),
+            content: Some(export.content),
+        },
+    )
+    .await
+}
+
+/// Whether a proposed value differs from the stored one. Rich text is stored
+/// as editor JSON but proposed as markdown, so strings compare by their text.
+fn value_changed(current: &serde_json::Value, proposed: &serde_json::Value) -> bool {
+    match (current, proposed) {
+        (serde_json::Value::String(current), serde_json::Value::String(proposed)) => {
+            let words = |s: &str| {
+                plain_text(s)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            words(current) != words(proposed)
+        }
+        // Clearing an unset field is not a change
+        (serde_json::Value::Null, serde_json::Value::String(proposed)) => {
+            !proposed.trim().is_empty()
+        }
+        _ => current != proposed,
+    }
+}
+
+/// Field-by-field diff between an update or patch proposal and the entity's
+/// stored values. Patch proposals are compared using the preview the
+/// assistant computed when proposing them.
+#[instrument(skip_all, fields(message_id = %message_id), err)]
+pub async fn preview_proposal_impl(
+    db: &DatabaseConnection,
+    message_id: String,
+) -> Result<ProposalPreview, AppError> {
+    let message = AiMessage::find_by_id(&message_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Message {} not found", message_id)))?;
+    let raw = message
+        .proposal_json
+        .as_deref()
+        .ok_or_else(|| AppError::Validation("message_id: message has no proposal".to_string()))?;
+    let proposal = Proposal::parse(raw)?;
+
+    let (operation, entity_type, entity_id, proposed) = match proposal.operation {
+        ProposalOperation::Update(update) => (
+            "update",
+            update.entity_type,
+            update.entity_id,
+            update.changes.into_iter().collect::<Vec<_>>(),
+        ),
+        ProposalOperation::Patch(patch) => {
+            let preview = patch.extra.get("previewData").and_then(|v| v.as_object());
+            let mut proposed = Vec::with_capacity(patch.patches.len());
+            for field_patch in patch.patches {
+                let value = preview
+                    .and_then(|p| p.get(&field_patch.field))
+                    .cloned()
+                    .ok_or_else(|| {
+                        AppError::Validation(format!(
+                            "proposal_json: patch for {} has no preview",
+                            field_patch.field
+                        ))
+                    })?;
+                proposed.push((field_patch.field, value));
+            }
+            ("patch", patch.entity_type, patch.entity_id, proposed)
+        }
+        _ => {
+            return Err(AppError::Validation(
+                "proposal_json: only update and patch proposals can be previewed".to_string(),
+            ))
+        }
+    };
+
+    let row = load_row_json(db, &entity_type, &entity_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} {} not found", entity_type, entity_id)))?;
+    let entity_name = table_for(&entity_type)
+        .and_then(|t| t.name_column)
+        .and_then(|column| row.get(column))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    let fields = proposed
+        .into_iter()
+        .map(|(field, proposed)| {
+            let current = row.get(&field).cloned();
+            let known_field = current.is_some();
+            let current = current.unwrap_or(serde_json::Value::Null);
+            ProposalFieldDiff {
+                changed: value_changed(&current, &proposed),
+                known_field,
+                current,
+                field,
+                proposed,
+            }
+        })
+        .collect();
+
+    Ok(ProposalPreview {
+        message_id,
+        proposal_id: proposal.id,
+        operation: operation.to_string(),
+        entity_type,
+        entity_id,
+        entity_name,
+        status: proposal.status,
+        fields,
+    })
+}
+
+/// Output tokens the agent requests when the campaign doesn't set `max_tokens`
+const DEFAULT_RESPONSE_TOKENS: usize = 8192;
+
+/// Text the model sees for one stored agent message: plain text as-is,
+/// tool calls and anything else as JSON
+fn agent_message_text(message: &serde_json::Value) -> String {
+    let content = &message["content"];
+    if let Some(text) = content.as_str() {
+        return text.to_string();
+    }
+
+    let mut out = String::new();
+    for block in content.as_array().into_iter().flatten() {
+        match block["type"].as_str() {
+            Some("text") => out.push_str(block["text"].as_str().unwrap_or_default()),
+            Some("tool_result") => match &block["content"] {
+                serde_json::Value::String(text) => out.push_str(text),
+                other => out.push_str(&other.to_string()),
+            },
+            _ => out.push_str(&block.to_string()),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Estimate the tokens the next request will use: the system prompt, the
+/// conversation so far and the draft message, checked against the model's
+/// context window and the campaign's token budget
+#[instrument(skip_all, fields(conversation_id = %conversation_id), err)]
+pub async fn estimate_prompt_tokens_impl(
+    db: &DatabaseConnection,
+    conversation_id: String,
+    draft_message: String,
+    system_prompt: Option<String>,
+) -> Result<PromptTokenEstimate, AppError> {
+    let conversation = AiConversation::find_by_id(&conversation_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Conversation {} not found", conversation_id)))?;
+    let settings = get_campaign_settings_impl(db, conversation.campaign_id.clone()).await?;
+
+    // The agent resends its own history when it has one; otherwise fall
+    // back to the visible user and assistant turns
+    let agent_messages: Option<Vec<serde_json::Value>> = conversation
+        .agent_messages_json
+        .as_deref()
+        .and_then(|raw| serde_json::from_str(raw).ok());
+    let history_tokens = match agent_messages {
+        Some(messages) => messages
+            .iter()
+            .map(|m| count_message_tokens(&agent_message_text(m)))
+            .sum(),
+        None => AiMessage::find()
+            .filter(ai_messages::Column::ConversationId.eq(&conversation_id))
+            .filter(ai_messages::Column::Role.is_in(["user", "assistant"]))
+            .all(db)
+            .await?
+            .iter()
+            .map(|m| count_message_tokens(&m.content))
+            .sum(),
+    };
+
+    let system_tokens = system_prompt.as_deref().map(count_tokens).unwrap_or(0);
+    let draft_tokens = count_message_tokens(&draft_message);
+    let total_tokens = system_tokens + history_tokens + draft_tokens;
+
+    let reserved_output_tokens = settings
+        .ai
+        .max_tokens
+        .map(|t| t as usize)
+        .unwrap_or(DEFAULT_RESPONSE_TOKENS);
+    let budget = settings.ai.token_budget.map(|t| t as usize);
+
+    Ok(PromptTokenEstimate {
+        conversation_id,
+        system_tokens,
+        history_tokens,
+        draft_tokens,
+        total_tokens,
+        context_window: CONTEXT_WINDOW_TOKENS,
+        reserved_output_tokens,
+        budget,
+        exceeds_context: total_tokens + reserved_output_tokens > CONTEXT_WINDOW_TOKENS,
+        exceeds_budget: budget.is_some_and(|b| total_tokens > b),
+    })
+}
+
 // ============ Tauri Command Wrappers ============
 
 #[tauri::command(rename_all = "snake_case")]
@@ -327,6 +919,7 @@ pub async fn update_ai_token_counts(
     output_tokens: i32,
     cache_read_tokens: i32,
     cache_creation_tokens: i32,
+    cost_usd: Option<f64>,
 ) -> Result<AiConversationResponse, AppError> {
     update_token_counts_impl(
         &state.db,
@@ -335,6 +928,7 @@ pub async fn update_ai_token_counts(
         output_tokens,
         cache_read_tokens,
         cache_creation_tokens,
+        cost_usd,
     )
     .await
 }
@@ -365,6 +959,43 @@ pub async fn update_ai_agent_messages(
     update_agent_messages_impl(&state.db, conversation_id, agent_messages_json).await
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_ai_conversation(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    format: String,
+    path: Option<String>,
+) -> Result<ConversationExport, AppError> {
+    export_conversation_impl(&state.db, conversation_id, format, path).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn archive_ai_conversation(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    name: Option<String>,
+) -> Result<SnippetResponse, AppError> {
+    archive_conversation_impl(&state.db, conversation_id, name).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn preview_proposal(
+    state: State<'_, AppState>,
+    message_id: String,
+) -> Result<ProposalPreview, AppError> {
+    preview_proposal_impl(&state.db, message_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn estimate_prompt_tokens(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    draft_message: String,
+    system_prompt: Option<String>,
+) -> Result<PromptTokenEstimate, AppError> {
+    estimate_prompt_tokens_impl(&state.db, conversation_id, draft_message, system_prompt).await
+}
+
 // ============ Tests ============
 
 #[cfg(test)]
@@ -397,6 +1028,11 @@ mod tests {
             description: Set(None),
             created_at: Set(now),
             updated_at: Set(now),
+            archived_at: Set(None),
+            archive_path: Set(None),
+            locked_at: Set(None),
+            created_by: Set(None),
+            modified_by: Set(None),
         };
         campaign.insert(db).await.expect("Failed to create campaign");
         id
@@ -658,7 +1294,7 @@ mod tests {
         .await
         .unwrap();
 
-        let proposal_json = r#"{"id": "prop1", "operation": "create", "status": "pending"}"#;
+        let proposal_json = r#"{"id": "prop1", "operation": "create", "status": "pending", "entityType": "character", "data": {"name": "Mira"}}"#;
         let message = add_message_impl(
             &db,
             conversation.id,
@@ -698,6 +1334,7 @@ mod tests {
             50,
             25,
             10,
+            None,
         )
         .await
         .unwrap();
@@ -715,6 +1352,7 @@ mod tests {
             100,
             50,
             20,
+            None,
         )
         .await
         .unwrap();
@@ -736,6 +1374,7 @@ mod tests {
             50,
             25,
             10,
+            None,
         )
         .await;
 
@@ -814,6 +1453,7 @@ mod tests {
             500,
             250,
             100,
+            None,
         )
         .await
         .unwrap();
@@ -864,7 +1504,7 @@ mod tests {
         .unwrap();
 
         // Add a proposal message with pending status
-        let initial_proposal = r#"{"id": "prop1", "status": "pending", "operation": "create"}"#;
+        let initial_proposal = r#"{"id": "prop1", "status": "pending", "operation": "create", "entityType": "character", "data": {"name": "Mira"}}"#;
         let message = add_message_impl(
             &db,
             conversation.id,
@@ -881,7 +1521,7 @@ mod tests {
         assert_eq!(message.proposal_json, Some(initial_proposal.to_string()));
 
         // Update the proposal status to accepted
-        let updated_proposal = r#"{"id": "prop1", "status": "accepted", "operation": "create"}"#;
+        let updated_proposal = r#"{"id": "prop1", "status": "accepted", "operation": "create", "entityType": "character", "data": {"name": "Mira"}}"#;
         let result = update_message_proposal_impl(
             &db,
             message.id.clone(),
@@ -911,4 +1551,362 @@ mod tests {
             _ => panic!("Expected NotFound error"),
         }
     }
+
+    #[tokio::test]
+    async fn test_export_conversation_markdown() {
+        let db = setup_test_db().await;
+        let campaign_id = create_test_campaign(&db).await;
+        let conversation = get_or_create_conversation_impl(&db, campaign_id, "sidebar".to_string())
+            .await
+            .unwrap();
+
+        add_message_impl(
+            &db,
+            conversation.id.clone(),
+            "user".to_string(),
+            "Who runs the docks?".to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        add_message_impl(
+            &db,
+            conversation.id.clone(),
+            "tool".to_string(),
+            String::new(),
+            Some("search_entities".to_string()),
+            Some(r#"{"query":"docks"}"#.to_string()),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let proposal = r#"{"id": "prop1", "status": "accepted", "operation": "create", "entityType": "character", "data": {"name": "Mira", "description": "Harbormaster.\nKeeps ledgers."}, "reasoning": "Nobody runs the docks yet"}"#;
+        add_message_impl(
+            &db,
+            conversation.id.clone(),
+            "proposal".to_string(),
+            "Create character".to_string(),
+            None,
+            None,
+            None,
+            Some(proposal.to_string()),
+        )
+        .await
+        .unwrap();
+
+        let export = export_conversation_impl(&db, conversation.id, "md".to_string(), None)
+            .await
+            .unwrap();
+        let md = export.content;
+
+        assert_eq!(export.format, "markdown");
+        assert!(md.starts_with("# AI conversation: Test Campaign"));
+        assert!(md.contains("## You\n\nWho runs the docks?"));
+        assert!(md.contains("## Tool call: `search_entities`"));
+        assert!(md.contains("\"query\": \"docks\""));
+        assert!(md.contains("**Create character** (accepted)"));
+        assert!(md.contains("- **name:** Mira"));
+        assert!(md.contains("  > Keeps ledgers."));
+        assert!(md.contains("_Reasoning:_ Nobody runs the docks yet"));
+    }
+
+    #[tokio::test]
+    async fn test_export_conversation_json_to_file() {
+        let db = setup_test_db().await;
+        let campaign_id = create_test_campaign(&db).await;
+        let conversation =
+            get_or_create_conversation_impl(&db, campaign_id, "fullpage".to_string())
+                .await
+                .unwrap();
+        add_message_impl(
+            &db,
+            conversation.id.clone(),
+            "tool".to_string(),
+            String::new(),
+            Some("get_entity".to_string()),
+            Some(r#"{"id":"c1"}"#.to_string()),
+            Some("not json".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let path = std::env::temp_dir().join(format!("test-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_string_lossy().into_owned();
+        let export = export_conversation_impl(
+            &db,
+            conversation.id.clone(),
+            "JSON".to_string(),
+            Some(path.clone()),
+        )
+        .await
+        .unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(export.path.as_deref(), Some(path.as_str()));
+        assert_eq!(written, export.content);
+        let parsed: serde_json::Value = serde_json::from_str(&written).unwrap();
+        assert_eq!(parsed["conversation"]["id"], conversation.id);
+        assert_eq!(parsed["messages"][0]["tool_input"]["id"], "c1");
+        assert_eq!(parsed["messages"][0]["tool_data"], "not json");
+    }
+
+    #[tokio::test]
+    async fn test_export_conversation_errors() {
+        let db = setup_test_db().await;
+        let campaign_id = create_test_campaign(&db).await;
+        let conversation = get_or_create_conversation_impl(&db, campaign_id, "sidebar".to_string())
+            .await
+            .unwrap();
+
+        let result = export_conversation_impl(&db, conversation.id, "html".to_string(), None).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let result =
+            export_conversation_impl(&db, "missing".to_string(), "json".to_string(), None).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_archive_conversation_as_snippet() {
+        let db = setup_test_db().await;
+        let campaign_id = create_test_campaign(&db).await;
+        let conversation =
+            get_or_create_conversation_impl(&db, campaign_id.clone(), "sidebar".to_string())
+                .await
+                .unwrap();
+        add_message_impl(
+            &db,
+            conversation.id.clone(),
+            "assistant".to_string(),
+            "The docks belong to the Gull Syndicate.".to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let snippet =
+            archive_conversation_impl(&db, conversation.id, Some("Docks brainstorm".to_string()))
+                .await
+                .unwrap();
+
+        assert_eq!(snippet.campaign_id, campaign_id);
+        assert_eq!(snippet.name, "Docks brainstorm");
+        assert_eq!(snippet.snippet_type, "other");
+        assert!(snippet
+            .content
+            .unwrap()
+            .contains("## Assistant\n\nThe docks belong to the Gull Syndicate."));
+    }
+
+    async fn create_test_character(db: &DatabaseConnection, campaign_id: &str) -> String {
+        use crate::commands::character::create_character_impl;
+        use crate::commands::validation::CreateCharacterInput;
+
+        create_character_impl(
+            db,
+            CreateCharacterInput {
+                name: "Mira".to_string(),
+                campaign_id: campaign_id.to_string(),
+                lineage: None,
+                occupation: Some("Harbormaster".to_string()),
+                description: Some(
+                    r#"{"type":"doc","content":[{"type":"paragraph","content":[{"type":"text","text":"Keeps the ledgers."}]}]}"#
+                        .to_string(),
+                ),
+                personality: None,
+                motivations: None,
+                secrets: None,
+                voice_notes: None,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    async fn add_proposal(
+        db: &DatabaseConnection,
+        conversation_id: &str,
+        proposal: String,
+    ) -> String {
+        add_message_impl(
+            db,
+            conversation_id.to_string(),
+            "proposal".to_string(),
+            "Update character".to_string(),
+            None,
+            None,
+            None,
+            Some(proposal),
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    #[tokio::test]
+    async fn test_preview_update_proposal() {
+        let db = setup_test_db().await;
+        let campaign_id = create_test_campaign(&db).await;
+        let character_id = create_test_character(&db, &campaign_id).await;
+        let conversation = get_or_create_conversation_impl(&db, campaign_id, "sidebar".to_string())
+            .await
+            .unwrap();
+
+        let proposal = serde_json::json!({
+            "id": "prop1",
+            "status": "pending",
+            "operation": "update",
+            "entityType": "character",
+            "entityId": character_id,
+            "changes": {
+                "occupation": "Smuggler",
+                "description": "Keeps the ledgers.",
+                "mood": "wary"
+            }
+        });
+        let message_id = add_proposal(&db, &conversation.id, proposal.to_string()).await;
+
+        let preview = preview_proposal_impl(&db, message_id).await.unwrap();
+        assert_eq!(preview.operation, "update");
+        assert_eq!(preview.entity_name.as_deref(), Some("Mira"));
+        assert_eq!(preview.status, ProposalStatus::Pending);
+
+        let field = |name: &str| preview.fields.iter().find(|f| f.field == name).unwrap();
+        assert_eq!(field("occupation").current, "Harbormaster");
+        assert_eq!(field("occupation").proposed, "Smuggler");
+        assert!(field("occupation").changed);
+        // Same text, stored as editor JSON and proposed as markdown
+        assert!(!field("description").changed);
+        assert!(!field("mood").known_field);
+    }
+
+    #[tokio::test]
+    async fn test_preview_patch_proposal_uses_preview_data() {
+        let db = setup_test_db().await;
+        let campaign_id = create_test_campaign(&db).await;
+        let character_id = create_test_character(&db, &campaign_id).await;
+        let conversation = get_or_create_conversation_impl(&db, campaign_id, "sidebar".to_string())
+            .await
+            .unwrap();
+
+        let mut proposal = serde_json::json!({
+            "id": "prop2",
+            "status": "pending",
+            "operation": "patch",
+            "entityType": "character",
+            "entityId": character_id,
+            "patches": [{
+                "field": "description",
+                "patchType": "unified_diff",
+                "patch": "@@ -1 +1 @@\n-Keeps the ledgers.\n+Burns the ledgers."
+            }]
+        });
+        let message_id = add_proposal(&db, &conversation.id, proposal.to_string()).await;
+        let result = preview_proposal_impl(&db, message_id).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        proposal["previewData"] = serde_json::json!({ "description": "Burns the ledgers." });
+        let message_id = add_proposal(&db, &conversation.id, proposal.to_string()).await;
+        let preview = preview_proposal_impl(&db, message_id).await.unwrap();
+        assert_eq!(preview.operation, "patch");
+        assert_eq!(preview.fields.len(), 1);
+        assert_eq!(preview.fields[0].proposed, "Burns the ledgers.");
+        assert!(preview.fields[0].changed);
+    }
+
+    #[tokio::test]
+    async fn test_preview_proposal_errors() {
+        let db = setup_test_db().await;
+        let campaign_id = create_test_campaign(&db).await;
+        let conversation = get_or_create_conversation_impl(&db, campaign_id, "sidebar".to_string())
+            .await
+            .unwrap();
+
+        let create = r#"{"id": "prop3", "status": "pending", "operation": "create", "entityType": "character", "data": {"name": "Mira"}}"#;
+        let message_id = add_proposal(&db, &conversation.id, create.to_string()).await;
+        let result = preview_proposal_impl(&db, message_id).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let update = r#"{"id": "prop4", "status": "pending", "operation": "update", "entityType": "character", "entityId": "missing", "changes": {"name": "Vey"}}"#;
+        let message_id = add_proposal(&db, &conversation.id, update.to_string()).await;
+        let result = preview_proposal_impl(&db, message_id).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = preview_proposal_impl(&db, "missing".to_string()).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_prompt_tokens() {
+        use crate::commands::campaign_settings::{update_campaign_settings_impl, CampaignSettings};
+
+        let db = setup_test_db().await;
+        let campaign_id = create_test_campaign(&db).await;
+        let conversation =
+            get_or_create_conversation_impl(&db, campaign_id.clone(), "sidebar".to_string())
+                .await
+                .unwrap();
+
+        let empty = estimate_prompt_tokens_impl(
+            &db,
+            conversation.id.clone(),
+            "Who runs the docks?".to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(empty.history_tokens, 0);
+        assert_eq!(
+            empty.draft_tokens,
+            count_message_tokens("Who runs the docks?")
+        );
+        assert!(!empty.exceeds_context);
+        assert!(!empty.exceeds_budget);
+
+        // Stored agent history takes precedence over visible messages
+        let history = r#"[{"role":"user","content":"Find Mira"},{"role":"assistant","content":[{"type":"text","text":"Searching."},{"type":"tool_use","id":"t1","name":"search_entities","input":{"query":"Mira"}}]}]"#;
+        update_agent_messages_impl(&db, conversation.id.clone(), history.to_string())
+            .await
+            .unwrap();
+
+        let mut settings = CampaignSettings::default();
+        settings.ai.token_budget = Some(20);
+        update_campaign_settings_impl(&db, campaign_id, settings)
+            .await
+            .unwrap();
+
+        let estimate = estimate_prompt_tokens_impl(
+            &db,
+            conversation.id,
+            "Who runs the docks?".to_string(),
+            Some("You are a helpful archivist.".to_string()),
+        )
+        .await
+        .unwrap();
+        assert!(estimate.history_tokens > count_message_tokens("Find Mira"));
+        assert_eq!(
+            estimate.system_tokens,
+            count_tokens("You are a helpful archivist.")
+        );
+        assert_eq!(
+            estimate.total_tokens,
+            estimate.system_tokens + estimate.history_tokens + estimate.draft_tokens
+        );
+        assert_eq!(estimate.budget, Some(20));
+        assert!(estimate.exceeds_budget);
+
+        let result =
+            estimate_prompt_tokens_impl(&db, "missing".to_string(), String::new(), None).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
 }
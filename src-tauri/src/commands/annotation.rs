@@ -0,0 +1,373 @@
+//! Comments left on an entity, or on one of its fields, by a co-GM. They sit
+//! beside the canonical text rather than in it, so a second GM can question or
+//! suggest without editing the prep. An annotation stays open until someone
+//! resolves it, and each campaign has a queue of the open ones.
+
+use crate::commands::references::REFERENCEABLE_TYPES;
+use crate::commands::registry::table_for;
+use crate::commands::validation::{CreateAnnotationInput, UpdateAnnotationInput};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::annotations::{self, Entity as Annotation};
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnnotationResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    /// Field commented on; None for the entity as a whole
+    pub field: Option<String>,
+    pub body: String,
+    pub author: Option<String>,
+    /// When the annotation was resolved; None while it is open
+    pub resolved_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<annotations::Model> for AnnotationResponse {
+    fn from(model: annotations::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            entity_type: model.entity_type,
+            entity_id: model.entity_id,
+            field: model.field,
+            body: model.body,
+            author: model.author,
+            resolved_at: model.resolved_at.map(|t| t.to_string()),
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+/// An open annotation with the name of the entity it is on
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAnnotation {
+    #[serde(flatten)]
+    pub annotation: AnnotationResponse,
+    pub entity_name: Option<String>,
+}
+
+fn check_entity_type(entity_type: &str) -> Result<(), AppError> {
+    if REFERENCEABLE_TYPES.contains(&entity_type) {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "entity_type: must be one of: {}",
+            REFERENCEABLE_TYPES.join(", ")
+        )))
+    }
+}
+
+/// Campaign of the entity being annotated
+async fn entity_campaign_id(
+    db: &DatabaseConnection,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<String, AppError> {
+    let table = table_for(entity_type).expect("referenceable types are registered");
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!("SELECT campaign_id FROM {} WHERE id = $1", table.table),
+            [entity_id.into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} {} not found", entity_type, entity_id)))?;
+    Ok(row.try_get("", "campaign_id")?)
+}
+
+async fn find_annotation(
+    db: &DatabaseConnection,
+    id: &str,
+) -> Result<annotations::Model, AppError> {
+    Annotation::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Annotation {} not found", id)))
+}
+
+/// Display names of the given entities of one type, by id
+async fn entity_names(
+    db: &DatabaseConnection,
+    entity_type: &str,
+    ids: &[&str],
+) -> Result<HashMap<String, String>, AppError> {
+    let table = table_for(entity_type).expect("referenceable types are registered");
+    let Some(name_column) = table.name_column else {
+        return Ok(HashMap::new());
+    };
+    let placeholders: Vec<String> = (1..=ids.len()).map(|i| format!("${i}")).collect();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "SELECT id, {name_column} AS name FROM {} WHERE id IN ({})",
+                table.table,
+                placeholders.join(", ")
+            ),
+            ids.iter().map(|id| (*id).into()),
+        ))
+        .await?;
+
+    let mut names = HashMap::new();
+    for row in rows {
+        if let Some(name) = row.try_get::<Option<String>>("", "name")? {
+            names.insert(row.try_get("", "id")?, name);
+        }
+    }
+    Ok(names)
+}
+
+async fn set_resolved(
+    db: &DatabaseConnection,
+    id: &str,
+    resolved: bool,
+) -> Result<AnnotationResponse, AppError> {
+    let annotation = find_annotation(db, id).await?;
+    if annotation.resolved_at.is_some() == resolved {
+        return Ok(annotation.into());
+    }
+
+    let now = chrono::Utc::now();
+    let mut active: annotations::ActiveModel = annotation.into();
+    active.resolved_at = Set(resolved.then_some(now));
+    active.updated_at = Set(now);
+    Ok(active.update(db).await?.into())
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(entity_type = %input.entity_type, entity_id = %input.entity_id), err)]
+pub async fn create_annotation_impl(
+    db: &DatabaseConnection,
+    input: CreateAnnotationInput,
+) -> Result<AnnotationResponse, AppError> {
+    input.validate()?;
+    check_entity_type(&input.entity_type)?;
+    let body = input.body.trim().to_string();
+    if body.is_empty() {
+        return Err(AppError::Validation("body: must not be blank".to_string()));
+    }
+    let field = input.field.filter(|f| !f.is_empty());
+    if let Some(field) = &field {
+        let table = table_for(&input.entity_type).expect("referenceable types are registered");
+        if table.name_column != Some(field.as_str())
+            && !table.text_columns.contains(&field.as_str())
+        {
+            return Err(AppError::Validation(format!(
+                "field: {} has no field {}",
+                input.entity_type, field
+            )));
+        }
+    }
+    let campaign_id = entity_campaign_id(db, &input.entity_type, &input.entity_id).await?;
+
+    let now = chrono::Utc::now();
+    let model = annotations::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id),
+        entity_type: Set(input.entity_type),
+        entity_id: Set(input.entity_id),
+        field: Set(field),
+        body: Set(body),
+        author: Set(input.author.filter(|a| !a.trim().is_empty())),
+        resolved_at: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    Ok(model.insert(db).await?.into())
+}
+
+/// An entity's annotations, oldest first. Resolved ones are left out unless
+/// `include_resolved`.
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn list_annotations_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    entity_id: String,
+    include_resolved: bool,
+) -> Result<Vec<AnnotationResponse>, AppError> {
+    check_entity_type(&entity_type)?;
+
+    let mut query = Annotation::find()
+        .filter(annotations::Column::EntityType.eq(&entity_type))
+        .filter(annotations::Column::EntityId.eq(&entity_id));
+    if !include_resolved {
+        query = query.filter(annotations::Column::ResolvedAt.is_null());
+    }
+    let annotations = query
+        .order_by_asc(annotations::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(annotations.into_iter().map(Into::into).collect())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_annotation_impl(
+    db: &DatabaseConnection,
+    id: String,
+    input: UpdateAnnotationInput,
+) -> Result<AnnotationResponse, AppError> {
+    input.validate()?;
+    let annotation = find_annotation(db, &id).await?;
+
+    let mut active: annotations::ActiveModel = annotation.into();
+    if let Some(body) = input.body {
+        let body = body.trim().to_string();
+        if body.is_empty() {
+            return Err(AppError::Validation("body: must not be blank".to_string()));
+        }
+        active.body = Set(body);
+    }
+    if let Some(author) = input.author {
+        active.author = Set(Some(author).filter(|a| !a.trim().is_empty()));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    Ok(active.update(db).await?.into())
+}
+
+/// Mark an annotation as dealt with. Resolving one twice keeps the first time.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn resolve_annotation_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<AnnotationResponse, AppError> {
+    set_resolved(db, &id, true).await
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn unresolve_annotation_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<AnnotationResponse, AppError> {
+    set_resolved(db, &id, false).await
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_annotation_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
+    let result = Annotation::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Every unresolved annotation in a campaign, oldest first
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_open_annotations_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<OpenAnnotation>, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let annotations = Annotation::find()
+        .filter(annotations::Column::CampaignId.eq(&campaign_id))
+        .filter(annotations::Column::ResolvedAt.is_null())
+        .order_by_asc(annotations::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut ids_by_type: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for annotation in &annotations {
+        ids_by_type
+            .entry(&annotation.entity_type)
+            .or_default()
+            .push(&annotation.entity_id);
+    }
+    let mut names: HashMap<(String, String), String> = HashMap::new();
+    for (entity_type, ids) in ids_by_type {
+        for (id, name) in entity_names(db, entity_type, &ids).await? {
+            names.insert((entity_type.to_string(), id), name);
+        }
+    }
+
+    Ok(annotations
+        .into_iter()
+        .map(|annotation| OpenAnnotation {
+            entity_name: names
+                .get(&(annotation.entity_type.clone(), annotation.entity_id.clone()))
+                .cloned(),
+            annotation: annotation.into(),
+        })
+        .collect())
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_annotation(
+    state: State<'_, AppState>,
+    input: CreateAnnotationInput,
+) -> Result<AnnotationResponse, AppError> {
+    create_annotation_impl(&state.db, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_annotations(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+    include_resolved: Option<bool>,
+) -> Result<Vec<AnnotationResponse>, AppError> {
+    list_annotations_impl(
+        &state.db,
+        entity_type,
+        entity_id,
+        include_resolved.unwrap_or(false),
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_annotation(
+    state: State<'_, AppState>,
+    id: String,
+    input: UpdateAnnotationInput,
+) -> Result<AnnotationResponse, AppError> {
+    update_annotation_impl(&state.db, id, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn resolve_annotation(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<AnnotationResponse, AppError> {
+    resolve_annotation_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn unresolve_annotation(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<AnnotationResponse, AppError> {
+    unresolve_annotation_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_annotation(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    delete_annotation_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_open_annotations(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<OpenAnnotation>, AppError> {
+    list_open_annotations_impl(&state.db, campaign_id).await
+}
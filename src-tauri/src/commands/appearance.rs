@@ -0,0 +1,104 @@
+//! User-chosen color and icon for the major entities. Graph views, map pins
+//! and lists read them from the entity responses; this module only sets them.
+
+use crate::commands::validation::AppearanceInput;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::{characters, locations, organizations, quests};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+/// Entity types that carry a color and icon
+pub const APPEARANCE_TYPES: &[&str] = &["character", "location", "organization", "quest"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityAppearance {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+}
+
+/// `None` keeps the current value, an empty string clears it
+fn change(value: Option<String>) -> Option<Option<String>> {
+    value.map(|v| Some(v).filter(|v| !v.is_empty()))
+}
+
+/// Apply the changes to one row of `$module`, evaluating to its new color
+/// and icon
+macro_rules! update_appearance {
+    ($db:expr, $module:ident, $label:literal, $id:expr, $color:expr, $icon:expr) => {{
+        let row = $module::Entity::find_by_id($id)
+            .one($db)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("{} {} not found", $label, $id)))?;
+        let mut active: $module::ActiveModel = row.into();
+        if let Some(color) = $color {
+            active.color = Set(color);
+        }
+        if let Some(icon) = $icon {
+            active.icon = Set(icon);
+        }
+        active.updated_at = Set(chrono::Utc::now());
+        let row = active.update($db).await?;
+        (row.color, row.icon)
+    }};
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn set_entity_appearance_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    entity_id: String,
+    input: AppearanceInput,
+) -> Result<EntityAppearance, AppError> {
+    input.validate()?;
+    let color = change(input.color).map(|c| c.map(|c| c.to_lowercase()));
+    let icon = change(input.icon);
+
+    let (color, icon) = match entity_type.as_str() {
+        "character" => update_appearance!(db, characters, "Character", &entity_id, color, icon),
+        "location" => update_appearance!(db, locations, "Location", &entity_id, color, icon),
+        "organization" => {
+            update_appearance!(db, organizations, "Organization", &entity_id, color, icon)
+        }
+        "quest" => update_appearance!(db, quests, "Quest", &entity_id, color, icon),
+        _ => {
+            return Err(AppError::Validation(format!(
+                "entity_type: must be one of: {}",
+                APPEARANCE_TYPES.join(", ")
+            )))
+        }
+    };
+
+    Ok(EntityAppearance {
+        entity_type,
+        entity_id,
+        color,
+        icon,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_entity_appearance(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+    color: Option<String>,
+    icon: Option<String>,
+) -> Result<EntityAppearance, AppError> {
+    set_entity_appearance_impl(
+        &state.db,
+        entity_type,
+        entity_id,
+        AppearanceInput { color, icon },
+    )
+    .await
+}
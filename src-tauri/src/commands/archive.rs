@@ -0,0 +1,445 @@
+use crate::commands::authorship::{pause_authorship, resume_authorship};
+use crate::commands::registry::ENTITY_TABLES;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::export::{
+    apply_merge, import_attachments, import_bundle_contents, insert_bundle, insert_bundle_contents,
+    load_attachments, load_bundle, preview_merge, read_archive, strategy_lookup, write_archive,
+    AttachmentReport, ImportProgress, ImportReport, MergeReport, MergeResolution, MergeStrategy,
+    ProgressFn, ARCHIVE_EXTENSION, IMPORT_PROGRESS_EVENT,
+};
+use ::entity::attachments::{self, Entity as Attachment};
+use ::entity::campaigns::{self, Entity as Campaign};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tracing::instrument;
+
+/// Folder in the app data directory that archived campaigns go to by default
+const ARCHIVE_DIR: &str = "archives";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveSummary {
+    pub path: String,
+    pub campaign_id: String,
+    pub campaign_name: String,
+    pub counts: BTreeMap<String, usize>,
+    /// How the archive's attachments were brought in; None when exporting
+    pub attachments: Option<AttachmentReport>,
+    /// Rows created, skipped and failed by an import
+    pub rows: Option<ImportReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergePreview {
+    pub campaign_id: String,
+    pub campaign_name: String,
+    /// False when the campaign isn't present locally and will simply be imported
+    pub local_campaign_exists: bool,
+    pub report: MergeReport,
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn export_campaign_archive_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    path: String,
+) -> Result<ArchiveSummary, AppError> {
+    let bundle = load_bundle(db, &campaign_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let (records, files) = load_attachments(db, &campaign_id).await?;
+    let manifest = write_archive(Path::new(&path), &bundle, &records, &files)?;
+
+    let mut counts = bundle.counts();
+    counts.insert("attachment".to_string(), records.len());
+    Ok(ArchiveSummary {
+        path,
+        campaign_id: manifest.campaign_id,
+        campaign_name: manifest.campaign_name,
+        counts,
+        attachments: None,
+        rows: None,
+    })
+}
+
+/// Import an archive as a new campaign. Rows are inserted one at a time and
+/// reported to `on_progress`; any that fail are listed in the summary
+/// instead of aborting the import.
+#[instrument(skip_all, err)]
+pub async fn import_campaign_archive_impl(
+    db: &DatabaseConnection,
+    path: String,
+    on_progress: ProgressFn<'_>,
+) -> Result<ArchiveSummary, AppError> {
+    let archive = read_archive(Path::new(&path))?;
+    let bundle = archive.bundle;
+
+    if Campaign::find_by_id(&bundle.campaign.id)
+        .one(db)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::Conflict(format!(
+            "Campaign {} already exists; merge the archive instead",
+            bundle.campaign.name
+        )));
+    }
+
+    let mut summary = ArchiveSummary {
+        path,
+        campaign_id: bundle.campaign.id.clone(),
+        campaign_name: bundle.campaign.name.clone(),
+        counts: bundle.counts(),
+        attachments: None,
+        rows: None,
+    };
+
+    let txn = db.begin().await?;
+    Campaign::insert(bundle.campaign.clone().into_active_model().reset_all())
+        .exec_without_returning(&txn)
+        .await?;
+    let rows = import_bundle_contents(&txn, bundle, &summary.path, on_progress).await?;
+    let attachments = import_attachments(
+        &txn,
+        &summary.campaign_id,
+        &archive.manifest.attachment_records,
+        &archive.attachments,
+    )
+    .await?;
+    txn.commit().await?;
+
+    if !rows.failed.is_empty() {
+        tracing::warn!(
+            failed = rows.failed.len(),
+            skipped = rows.skipped.len(),
+            "Imported campaign archive with failed rows"
+        );
+    }
+    summary.attachments = Some(attachments);
+    summary.rows = Some(rows);
+    Ok(summary)
+}
+
+#[instrument(skip_all, err)]
+pub async fn preview_campaign_archive_merge_impl(
+    db: &DatabaseConnection,
+    path: String,
+) -> Result<MergePreview, AppError> {
+    let imported = read_archive(Path::new(&path))?.bundle;
+    let campaign_id = imported.campaign.id.clone();
+    let campaign_name = imported.campaign.name.clone();
+
+    let local = load_bundle(db, &campaign_id).await?;
+    let local_campaign_exists = local.is_some();
+    let report = match local {
+        Some(local) => {
+            let lookup = strategy_lookup(MergeStrategy::KeepLocal, &[]);
+            preview_merge(&local, imported, &lookup)
+        }
+        None => MergeReport {
+            inserted: imported.counts(),
+            ..Default::default()
+        },
+    };
+
+    Ok(MergePreview {
+        campaign_id,
+        campaign_name,
+        local_campaign_exists,
+        report,
+    })
+}
+
+/// Merge an archive into the local copy of its campaign. Matching entities
+/// use `default_strategy` unless a resolution names them explicitly.
+#[instrument(skip_all, err)]
+pub async fn merge_campaign_archive_impl(
+    db: &DatabaseConnection,
+    path: String,
+    default_strategy: MergeStrategy,
+    resolutions: Vec<MergeResolution>,
+) -> Result<MergeReport, AppError> {
+    let archive = read_archive(Path::new(&path))?;
+    let imported = archive.bundle;
+    let campaign_id = imported.campaign.id.clone();
+
+    let txn = db.begin().await?;
+    // Merged rows keep the authorship they were written with
+    let profile = pause_authorship(&txn).await?;
+    let mut report = match load_bundle(&txn, &campaign_id).await? {
+        Some(local) => {
+            let lookup = strategy_lookup(default_strategy, &resolutions);
+            apply_merge(&txn, &local, imported, &lookup).await?
+        }
+        None => {
+            let report = MergeReport {
+                inserted: imported.counts(),
+                ..Default::default()
+            };
+            insert_bundle(&txn, imported).await?;
+            report
+        }
+    };
+    report.attachments = import_attachments(
+        &txn,
+        &campaign_id,
+        &archive.manifest.attachment_records,
+        &archive.attachments,
+    )
+    .await?;
+    resume_authorship(&txn, profile).await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        conflicts = report.conflicts.len(),
+        kept_local = report.kept_local,
+        took_imported = report.took_imported,
+        duplicated = report.duplicated,
+        unresolved_attachments = report.attachments.unresolved.len(),
+        "Merged campaign archive"
+    );
+
+    Ok(report)
+}
+
+/// Move a campaign out of the live database: export it to an archive in
+/// `dir`, read the archive back to check it, then delete the campaign's rows,
+/// keeping only the `campaigns` row as a stub that points at the file.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn archive_campaign_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    dir: &Path,
+) -> Result<ArchiveSummary, AppError> {
+    let campaign = Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    if let Some(path) = &campaign.archive_path {
+        return Err(AppError::Conflict(format!(
+            "Campaign {} is already archived at {}",
+            campaign.name, path
+        )));
+    }
+
+    let bundle = load_bundle(db, &campaign_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    let mut counts = bundle.counts();
+    let (records, files) = load_attachments(db, &campaign_id).await?;
+
+    std::fs::create_dir_all(dir)
+        .map_err(|e| AppError::Internal(format!("Failed to create archive folder: {}", e)))?;
+    let path = dir.join(format!(
+        "{}-{}.{}",
+        campaign_id,
+        chrono::Utc::now().format("%Y%m%d%H%M%S"),
+        ARCHIVE_EXTENSION
+    ));
+    write_archive(&path, &bundle, &records, &files)?;
+
+    // Nothing is deleted unless the file reads back with the same contents
+    let verified = read_archive(&path)
+        .map(|a| {
+            a.manifest.campaign_id == campaign_id
+                && a.manifest.attachment_records == records
+                && a.attachments.len() == files.len()
+                && a.bundle.counts() == counts
+        })
+        .unwrap_or(false);
+    if !verified {
+        let _ = std::fs::remove_file(&path);
+        return Err(AppError::Internal(
+            "Archive failed verification; the campaign was left in place".to_string(),
+        ));
+    }
+    let path_str = path.to_string_lossy().to_string();
+    counts.insert("attachment".to_string(), records.len());
+
+    let txn = db.begin().await?;
+    let backend = txn.get_database_backend();
+
+    // Deleting fires the sync tombstone triggers; archiving isn't a deletion
+    // other devices should copy, so those tombstones are dropped again
+    let watermark: i64 = txn
+        .query_one(Statement::from_string(
+            backend,
+            "SELECT COALESCE(MAX(rowid), 0) AS watermark FROM tombstones",
+        ))
+        .await?
+        .map(|row| row.try_get("", "watermark"))
+        .transpose()?
+        .unwrap_or(0);
+
+    // Foreign keys between these tables cascade or set null, so any order works
+    for table in ENTITY_TABLES {
+        txn.execute(Statement::from_sql_and_values(
+            backend,
+            format!("DELETE FROM {} WHERE campaign_id = $1", table.table),
+            [campaign_id.clone().into()],
+        ))
+        .await?;
+    }
+    Attachment::delete_many()
+        .filter(attachments::Column::CampaignId.eq(&campaign_id))
+        .exec(&txn)
+        .await?;
+    txn.execute(Statement::from_sql_and_values(
+        backend,
+        "DELETE FROM tombstones WHERE rowid > $1 AND campaign_id = $2",
+        [watermark.into(), campaign_id.clone().into()],
+    ))
+    .await?;
+
+    let now = chrono::Utc::now();
+    let mut active: campaigns::ActiveModel = campaign.into();
+    active.archived_at = Set(Some(now));
+    active.archive_path = Set(Some(path_str.clone()));
+    active.updated_at = Set(now);
+    let campaign = active.update(&txn).await?;
+    txn.commit().await?;
+
+    tracing::info!(path = %path_str, counts = ?counts, "Archived campaign");
+    Ok(ArchiveSummary {
+        path: path_str,
+        campaign_id,
+        campaign_name: campaign.name,
+        counts,
+        attachments: None,
+        rows: None,
+    })
+}
+
+/// Restore an archived campaign from its archive file. The campaign must be
+/// an archived stub or missing altogether.
+#[instrument(skip_all, err)]
+pub async fn unarchive_campaign_impl(
+    db: &DatabaseConnection,
+    path: String,
+) -> Result<ArchiveSummary, AppError> {
+    let archive = read_archive(Path::new(&path))?;
+    let mut bundle = archive.bundle;
+    bundle.campaign.archived_at = None;
+    bundle.campaign.archive_path = None;
+
+    let mut summary = ArchiveSummary {
+        path,
+        campaign_id: bundle.campaign.id.clone(),
+        campaign_name: bundle.campaign.name.clone(),
+        counts: bundle.counts(),
+        attachments: None,
+        rows: None,
+    };
+
+    let txn = db.begin().await?;
+    match Campaign::find_by_id(&bundle.campaign.id).one(&txn).await? {
+        Some(stub) if stub.archived_at.is_some() => {
+            // The stub may have been renamed since; the archive's copy wins
+            let campaign = bundle.campaign.clone().into_active_model().reset_all();
+            campaign.update(&txn).await?;
+            insert_bundle_contents(&txn, bundle).await?;
+        }
+        Some(_) => {
+            return Err(AppError::Conflict(format!(
+                "Campaign {} is not archived; merge the archive instead",
+                summary.campaign_name
+            )));
+        }
+        None => insert_bundle(&txn, bundle).await?,
+    }
+    let report = import_attachments(
+        &txn,
+        &summary.campaign_id,
+        &archive.manifest.attachment_records,
+        &archive.attachments,
+    )
+    .await?;
+    txn.commit().await?;
+
+    tracing::info!(counts = ?summary.counts, "Unarchived campaign");
+    summary.attachments = Some(report);
+    Ok(summary)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_campaign_archive(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    path: String,
+) -> Result<ArchiveSummary, AppError> {
+    export_campaign_archive_impl(&state.db, campaign_id, path).await
+}
+
+/// Emits an `import-progress` event per row while importing
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_campaign_archive(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<ArchiveSummary, AppError> {
+    let on_progress = |progress: &ImportProgress| {
+        if let Err(e) = app.emit(IMPORT_PROGRESS_EVENT, progress) {
+            tracing::warn!("Failed to emit import progress: {}", e);
+        }
+    };
+    import_campaign_archive_impl(&state.db, path, &on_progress).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn preview_campaign_archive_merge(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<MergePreview, AppError> {
+    preview_campaign_archive_merge_impl(&state.db, path).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn merge_campaign_archive(
+    state: State<'_, AppState>,
+    path: String,
+    default_strategy: MergeStrategy,
+    resolutions: Option<Vec<MergeResolution>>,
+) -> Result<MergeReport, AppError> {
+    merge_campaign_archive_impl(
+        &state.db,
+        path,
+        default_strategy,
+        resolutions.unwrap_or_default(),
+    )
+    .await
+}
+
+/// Archive a campaign into `dir`, or the app's archive folder by default
+#[tauri::command(rename_all = "snake_case")]
+pub async fn archive_campaign(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+    dir: Option<String>,
+) -> Result<ArchiveSummary, AppError> {
+    let dir = match dir {
+        Some(dir) => PathBuf::from(dir),
+        None => app
+            .path()
+            .app_data_dir()
+            .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {}", e)))?
+            .join(ARCHIVE_DIR),
+    };
+    archive_campaign_impl(&state.db, id, &dir).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn unarchive_campaign(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<ArchiveSummary, AppError> {
+    unarchive_campaign_impl(&state.db, path).await
+}
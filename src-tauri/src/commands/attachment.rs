@@ -0,0 +1,330 @@
+//! Files attached to entities. For now these are generated images: a
+//! portrait for a character or hero, a map for a location.
+
+use crate::commands::registry::table_for;
+use crate::commands::share::player_visible_entities;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::export::content_hash;
+use crate::image_gen::{ConfiguredProvider, GeneratedImage, ImageProvider, ImageProviderConfig};
+use ::entity::attachments::{self, Entity as Attachment};
+use sea_orm::*;
+use tauri::State;
+use tracing::instrument;
+
+/// Entity types that can have attachments
+pub const ATTACHMENT_ENTITY_TYPES: &[&str] = &[
+    "character",
+    "location",
+    "organization",
+    "quest",
+    "hero",
+    "item",
+];
+
+/// Roles an attachment can play; an entity has at most one of each except
+/// "image"
+pub const ATTACHMENT_ROLES: &[&str] = &["portrait", "map", "image"];
+
+const MAX_PROMPT_CHARS: usize = 4000;
+
+/// Campaign an attachable entity belongs to
+async fn entity_campaign_id(
+    db: &DatabaseConnection,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<String, AppError> {
+    if !ATTACHMENT_ENTITY_TYPES.contains(&entity_type) {
+        return Err(AppError::Validation(format!(
+            "entity_type: must be one of: {}",
+            ATTACHMENT_ENTITY_TYPES.join(", ")
+        )));
+    }
+    let table = table_for(entity_type).expect("attachment types are registered");
+
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!("SELECT campaign_id FROM {} WHERE id = $1", table.table),
+            [entity_id.into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} {} not found", entity_type, entity_id)))?;
+    Ok(row.try_get("", "campaign_id")?)
+}
+
+/// Store an image against an entity. A new portrait or map replaces the
+/// entity's current one, which is kept as a plain image.
+#[allow(clippy::too_many_arguments)]
+async fn store_image(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    entity_type: String,
+    entity_id: String,
+    role: String,
+    image: GeneratedImage,
+    source: &str,
+    prompt: Option<String>,
+) -> Result<attachments::Model, AppError> {
+    let txn = db.begin().await?;
+
+    if role != "image" {
+        Attachment::update_many()
+            .col_expr(attachments::Column::Role, "image".into())
+            .filter(attachments::Column::EntityType.eq(&entity_type))
+            .filter(attachments::Column::EntityId.eq(&entity_id))
+            .filter(attachments::Column::Role.eq(&role))
+            .exec(&txn)
+            .await?;
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let file_name = format!("{}-{}.{}", role, &id[..8], image.extension());
+    let attachment = attachments::ActiveModel {
+        id: Set(id),
+        campaign_id: Set(campaign_id),
+        entity_type: Set(entity_type),
+        entity_id: Set(entity_id),
+        role: Set(role),
+        file_name: Set(file_name),
+        mime_type: Set(image.mime_type),
+        size_bytes: Set(image.bytes.len() as i64),
+        content_hash: Set(Some(content_hash(&image.bytes))),
+        data: Set(image.bytes),
+        source: Set(Some(source.to_string())),
+        prompt: Set(prompt),
+        created_at: Set(chrono::Utc::now()),
+    }
+    .insert(&txn)
+    .await?;
+
+    txn.commit().await?;
+    Ok(attachment)
+}
+
+/// Whether the player view shows the entity an attachment belongs to
+async fn player_can_see(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<bool, AppError> {
+    Ok(player_visible_entities(db, campaign_id, Some(entity_id))
+        .await?
+        .iter()
+        .any(|e| e.entity_type == entity_type))
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Generate an image from `prompt` and attach it to the entity, as its
+/// portrait unless another role is given
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id, provider = provider.name()), err)]
+pub async fn generate_entity_image_impl<P: ImageProvider>(
+    db: &DatabaseConnection,
+    provider: &P,
+    entity_type: String,
+    entity_id: String,
+    prompt: String,
+    role: Option<String>,
+) -> Result<attachments::Model, AppError> {
+    let prompt = prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err(AppError::Validation(
+            "prompt: must not be empty".to_string(),
+        ));
+    }
+    if prompt.chars().count() > MAX_PROMPT_CHARS {
+        return Err(AppError::Validation(format!(
+            "prompt: must be at most {} characters",
+            MAX_PROMPT_CHARS
+        )));
+    }
+    let role = role.unwrap_or_else(|| "portrait".to_string());
+    if !ATTACHMENT_ROLES.contains(&role.as_str()) {
+        return Err(AppError::Validation(format!(
+            "role: must be one of: {}",
+            ATTACHMENT_ROLES.join(", ")
+        )));
+    }
+    let campaign_id = entity_campaign_id(db, &entity_type, &entity_id).await?;
+
+    let image = provider.generate(&prompt).await?;
+    if image.bytes.is_empty() {
+        return Err(AppError::AiProvider(format!(
+            "{} returned an empty image",
+            provider.name()
+        )));
+    }
+
+    let attachment = store_image(
+        db,
+        campaign_id,
+        entity_type,
+        entity_id,
+        role,
+        image,
+        provider.name(),
+        Some(prompt),
+    )
+    .await?;
+    tracing::info!(attachment_id = %attachment.id, "Generated entity image");
+    Ok(attachment)
+}
+
+/// An entity's attachments, newest first
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn list_entity_attachments_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    entity_id: String,
+) -> Result<Vec<attachments::Model>, AppError> {
+    Ok(Attachment::find()
+        .filter(attachments::Column::EntityType.eq(entity_type))
+        .filter(attachments::Column::EntityId.eq(entity_id))
+        .order_by_desc(attachments::Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// The entity's current portrait, if it has one
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn get_entity_portrait_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    entity_id: String,
+) -> Result<Option<attachments::Model>, AppError> {
+    Ok(Attachment::find()
+        .filter(attachments::Column::EntityType.eq(entity_type))
+        .filter(attachments::Column::EntityId.eq(entity_id))
+        .filter(attachments::Column::Role.eq("portrait"))
+        .one(db)
+        .await?)
+}
+
+/// The file contents of an attachment
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_attachment_data_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<Vec<u8>, AppError> {
+    Attachment::find_by_id(&id)
+        .one(db)
+        .await?
+        .map(|a| a.data)
+        .ok_or_else(|| AppError::NotFound(format!("Attachment {} not found", id)))
+}
+
+/// `get_entity_portrait_impl` for the player view: entities players can't
+/// see are reported as not found
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn get_player_entity_portrait_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    entity_id: String,
+) -> Result<Option<attachments::Model>, AppError> {
+    let campaign_id = entity_campaign_id(db, &entity_type, &entity_id).await?;
+    if !player_can_see(db, &campaign_id, &entity_type, &entity_id).await? {
+        return Err(AppError::NotFound(format!(
+            "{} {} not found",
+            entity_type, entity_id
+        )));
+    }
+    get_entity_portrait_impl(db, entity_type, entity_id).await
+}
+
+/// `get_attachment_data_impl` for the player view: attachments of entities
+/// players can't see are reported as not found
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_player_attachment_data_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<Vec<u8>, AppError> {
+    let attachment = Attachment::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Attachment {} not found", id)))?;
+    let visible = player_can_see(
+        db,
+        &attachment.campaign_id,
+        &attachment.entity_type,
+        &attachment.entity_id,
+    )
+    .await?;
+    if !visible {
+        return Err(AppError::NotFound(format!("Attachment {} not found", id)));
+    }
+    Ok(attachment.data)
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_attachment_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
+    let result = Attachment::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn generate_entity_image(
+    state: State<'_, AppState>,
+    entity_type: String,
+    id: String,
+    prompt: String,
+    provider: ImageProviderConfig,
+    role: Option<String>,
+) -> Result<attachments::Model, AppError> {
+    let provider = ConfiguredProvider::from_config(&provider)?;
+    generate_entity_image_impl(&state.db, &provider, entity_type, id, prompt, role).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_entity_attachments(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+) -> Result<Vec<attachments::Model>, AppError> {
+    list_entity_attachments_impl(&state.db, entity_type, entity_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_entity_portrait(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+) -> Result<Option<attachments::Model>, AppError> {
+    get_entity_portrait_impl(&state.db, entity_type, entity_id).await
+}
+
+/// Raw bytes rather than a JSON number array, so images load quickly
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_attachment_data(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<tauri::ipc::Response, AppError> {
+    let data = get_attachment_data_impl(&state.db, id).await?;
+    Ok(tauri::ipc::Response::new(data))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_player_entity_portrait(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+) -> Result<Option<attachments::Model>, AppError> {
+    get_player_entity_portrait_impl(&state.db, entity_type, entity_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_player_attachment_data(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<tauri::ipc::Response, AppError> {
+    let data = get_player_attachment_data_impl(&state.db, id).await?;
+    Ok(tauri::ipc::Response::new(data))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_attachment(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    delete_attachment_impl(&state.db, id).await
+}
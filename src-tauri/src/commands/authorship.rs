@@ -0,0 +1,231 @@
+//! Who wrote what. Every entity records the profile that created it and the
+//! one that last changed it (`created_by`, `modified_by`). Database triggers
+//! stamp them from the local profile set here, so no write path can miss
+//! them. The profile belongs to this device: it lives in `app_meta`, which is
+//! neither exported nor synced.
+
+use crate::commands::registry::{table_for, EntityTable, ENTITY_TABLES};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::app_meta::{self, Entity as AppMeta};
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// `app_meta` key holding the local profile. The authorship triggers read it
+/// by name, so keep it in step with the migration that created them.
+pub const LOCAL_PROFILE_KEY: &str = "local_profile";
+/// Longest profile name accepted
+pub const MAX_PROFILE_LEN: usize = 100;
+
+/// An entity with who created and last changed it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuthoredEntity {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: Option<String>,
+    pub created_by: Option<String>,
+    pub modified_by: Option<String>,
+    pub modified_at: String,
+}
+
+async fn read_profile<C: ConnectionTrait>(conn: &C) -> Result<Option<String>, DbErr> {
+    Ok(AppMeta::find_by_id(LOCAL_PROFILE_KEY)
+        .one(conn)
+        .await?
+        .map(|meta| meta.value))
+}
+
+/// Store the profile, or remove it with `None`
+async fn write_profile<C: ConnectionTrait>(conn: &C, profile: Option<String>) -> Result<(), DbErr> {
+    let Some(profile) = profile else {
+        AppMeta::delete_by_id(LOCAL_PROFILE_KEY).exec(conn).await?;
+        return Ok(());
+    };
+
+    let now = chrono::Utc::now();
+    match AppMeta::find_by_id(LOCAL_PROFILE_KEY).one(conn).await? {
+        Some(meta) => {
+            let mut active: app_meta::ActiveModel = meta.into();
+            active.value = Set(profile);
+            active.updated_at = Set(now);
+            active.update(conn).await?;
+        }
+        None => {
+            app_meta::ActiveModel {
+                key: Set(LOCAL_PROFILE_KEY.to_string()),
+                value: Set(profile),
+                updated_at: Set(now),
+            }
+            .insert(conn)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Stop stamping authorship for the rest of `txn`, so rows written on someone
+/// else's behalf keep theirs. Hand the result to `resume_authorship` before
+/// committing; other connections never see the profile missing.
+pub(crate) async fn pause_authorship(txn: &DatabaseTransaction) -> Result<Option<String>, DbErr> {
+    let profile = read_profile(txn).await?;
+    if profile.is_some() {
+        write_profile(txn, None).await?;
+    }
+    Ok(profile)
+}
+
+pub(crate) async fn resume_authorship(
+    txn: &DatabaseTransaction,
+    profile: Option<String>,
+) -> Result<(), DbErr> {
+    if profile.is_some() {
+        write_profile(txn, profile).await?;
+    }
+    Ok(())
+}
+
+/// Rows of one table created, or with `include_modified` last changed, by
+/// `author`
+async fn authored_rows(
+    db: &DatabaseConnection,
+    table: &EntityTable,
+    campaign_id: &str,
+    author: &str,
+    include_modified: bool,
+) -> Result<Vec<AuthoredEntity>, AppError> {
+    let modified = table.modified_column();
+    let by = if include_modified {
+        "(created_by = $2 OR modified_by = $2)"
+    } else {
+        "created_by = $2"
+    };
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "SELECT id, {} AS name, created_by, modified_by, \
+                 CAST({modified} AS TEXT) AS modified_at \
+                 FROM {} WHERE campaign_id = $1 AND {by}",
+                table.name_column.unwrap_or("NULL"),
+                table.table,
+            ),
+            [campaign_id.into(), author.into()],
+        ))
+        .await?;
+
+    let mut entities = Vec::with_capacity(rows.len());
+    for row in rows {
+        entities.push(AuthoredEntity {
+            entity_type: table.entity_type.to_string(),
+            entity_id: row.try_get("", "id")?,
+            name: row.try_get("", "name")?,
+            created_by: row.try_get("", "created_by")?,
+            modified_by: row.try_get("", "modified_by")?,
+            modified_at: row.try_get("", "modified_at")?,
+        });
+    }
+    Ok(entities)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Name stamped on entities written on this device, if one is set
+#[instrument(skip_all, err)]
+pub async fn get_local_profile_impl(db: &DatabaseConnection) -> Result<Option<String>, AppError> {
+    Ok(read_profile(db).await?)
+}
+
+/// Set the local profile, or stop stamping authorship with a blank or
+/// missing name. Entities already written keep the name they were stamped
+/// with.
+#[instrument(skip_all, err)]
+pub async fn set_local_profile_impl(
+    db: &DatabaseConnection,
+    name: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if name
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_PROFILE_LEN)
+    {
+        return Err(AppError::Validation(format!(
+            "name: must be at most {} characters",
+            MAX_PROFILE_LEN
+        )));
+    }
+
+    write_profile(db, name.clone()).await?;
+    Ok(name)
+}
+
+/// Entities in a campaign that `author` created, most recently changed
+/// first. `author` defaults to the local profile; `include_modified` adds
+/// entities they last changed; `entity_type` limits it to one type.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_authored_entities_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    author: Option<String>,
+    entity_type: Option<String>,
+    include_modified: bool,
+) -> Result<Vec<AuthoredEntity>, AppError> {
+    let tables: Vec<&EntityTable> = match entity_type.as_deref() {
+        Some(entity_type) => vec![table_for(entity_type).ok_or_else(|| {
+            AppError::Validation(format!("entity_type: unknown type {}", entity_type))
+        })?],
+        None => ENTITY_TABLES.iter().collect(),
+    };
+    let author = match author.filter(|a| !a.trim().is_empty()) {
+        Some(author) => author,
+        None => read_profile(db)
+            .await?
+            .ok_or_else(|| AppError::Validation("author: no local profile is set".to_string()))?,
+    };
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let mut entities = Vec::new();
+    for table in tables {
+        entities.extend(authored_rows(db, table, &campaign_id, &author, include_modified).await?);
+    }
+    entities.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    Ok(entities)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_local_profile(state: State<'_, AppState>) -> Result<Option<String>, AppError> {
+    get_local_profile_impl(&state.db).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_local_profile(
+    state: State<'_, AppState>,
+    name: Option<String>,
+) -> Result<Option<String>, AppError> {
+    set_local_profile_impl(&state.db, name).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_authored_entities(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    author: Option<String>,
+    entity_type: Option<String>,
+    include_modified: Option<bool>,
+) -> Result<Vec<AuthoredEntity>, AppError> {
+    list_authored_entities_impl(
+        &state.db,
+        campaign_id,
+        author,
+        entity_type,
+        include_modified.unwrap_or(false),
+    )
+    .await
+}
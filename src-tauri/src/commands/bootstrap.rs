@@ -0,0 +1,410 @@
+//! Campaign creation wizard ("session zero"): a new campaign with skeleton
+//! content so the GM isn't starting from an empty database.
+
+use crate::commands::campaign::CampaignResponse;
+use crate::commands::campaign_settings::CampaignSettings;
+use crate::commands::validation::ORG_TYPES;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::export::{insert_bundle, CampaignBundle, BUNDLE_FORMAT_VERSION};
+use ::entity::{campaigns, locations, organizations, sessions, tags};
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+use tracing::instrument;
+
+/// A starting location hierarchy. Each entry is (name, location type,
+/// index of its parent in the list); the first entry is the root and takes
+/// the wizard's world name when one is given.
+pub struct LocationTemplate {
+    pub key: &'static str,
+    pub locations: &'static [(&'static str, &'static str, Option<usize>)],
+}
+
+pub const LOCATION_TEMPLATES: &[LocationTemplate] = &[
+    LocationTemplate {
+        key: "kingdom",
+        locations: &[
+            ("The Known World", "world", None),
+            ("The Kingdom", "territory", Some(0)),
+            ("The Capital", "settlement", Some(1)),
+            ("The Royal Palace", "building", Some(2)),
+            ("The Market Ward", "district", Some(2)),
+            ("A Roadside Inn", "building", Some(1)),
+            ("The Borderlands", "region", Some(0)),
+            ("A Frontier Village", "settlement", Some(6)),
+            ("The Old Ruins", "landmark", Some(6)),
+        ],
+    },
+    LocationTemplate {
+        key: "city",
+        locations: &[
+            ("The City", "settlement", None),
+            ("The Docks", "district", Some(0)),
+            ("The Noble Quarter", "district", Some(0)),
+            ("The Temple District", "district", Some(0)),
+            ("The Slums", "district", Some(0)),
+            ("The Tavern", "building", Some(1)),
+            ("The Sewers", "landmark", Some(0)),
+        ],
+    },
+    LocationTemplate {
+        key: "frontier",
+        locations: &[
+            ("The Frontier", "region", None),
+            ("The Starting Village", "settlement", Some(0)),
+            ("The Village Tavern", "building", Some(1)),
+            ("The Deep Woods", "wilderness", Some(0)),
+            ("The Abandoned Mine", "landmark", Some(0)),
+        ],
+    },
+    LocationTemplate {
+        key: "none",
+        locations: &[],
+    },
+];
+
+pub const DEFAULT_LOCATION_TEMPLATE: &str = "kingdom";
+
+/// Tags every new campaign starts with unless the wizard lists its own
+pub const DEFAULT_TAGS: &[(&str, &str)] = &[
+    ("Ally", "#22c55e"),
+    ("Enemy", "#ef4444"),
+    ("Mystery", "#8b5cf6"),
+    ("Plot Hook", "#f59e0b"),
+    ("Rumor", "#64748b"),
+    ("Unresolved", "#0ea5e9"),
+];
+
+const SESSION_ZERO_PLAN: &str = "Session zero\n\n\
+- Pitch the campaign and its tone\n\
+- Agree on lines, veils and safety tools\n\
+- Build characters and how they know each other\n\
+- Settle scheduling and table rules";
+
+/// A faction to create with the campaign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactionSeed {
+    pub name: String,
+    /// One of `ORG_TYPES`; "other" when unset
+    #[serde(default)]
+    pub org_type: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Everything the campaign creation wizard collects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapConfig {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub system: Option<String>,
+    /// Key of one of `LOCATION_TEMPLATES`; `DEFAULT_LOCATION_TEMPLATE` when unset
+    #[serde(default)]
+    pub location_template: Option<String>,
+    /// Name for the template's top location
+    #[serde(default)]
+    pub world_name: Option<String>,
+    #[serde(default)]
+    pub factions: Vec<FactionSeed>,
+    /// Tag names to create; `DEFAULT_TAGS` when unset, none when empty
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Add a session 0 entry; true when unset
+    #[serde(default)]
+    pub session_zero: Option<bool>,
+    #[serde(default)]
+    pub session_zero_date: Option<NaiveDate>,
+    /// Calendar, defaults and other settings to start with
+    #[serde(default)]
+    pub settings: Option<CampaignSettings>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BootstrapReport {
+    pub campaign: CampaignResponse,
+    /// Rows created, keyed by entity type
+    pub counts: BTreeMap<String, usize>,
+    pub session_zero_id: Option<String>,
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Turn the wizard's answers into a bundle ready to insert
+fn build_bundle(config: BootstrapConfig, now: DateTime<Utc>) -> Result<CampaignBundle, AppError> {
+    let name = config.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::Validation("name: must not be empty".to_string()));
+    }
+
+    let template_key = config
+        .location_template
+        .as_deref()
+        .unwrap_or(DEFAULT_LOCATION_TEMPLATE);
+    let template = LOCATION_TEMPLATES
+        .iter()
+        .find(|t| t.key == template_key)
+        .ok_or_else(|| {
+            let keys: Vec<&str> = LOCATION_TEMPLATES.iter().map(|t| t.key).collect();
+            AppError::Validation(format!(
+                "location_template: must be one of: {}",
+                keys.join(", ")
+            ))
+        })?;
+
+    let settings_json = match &config.settings {
+        Some(settings) => {
+            settings.validate()?;
+            Some(settings.to_json()?)
+        }
+        None => None,
+    };
+
+    let campaign_id = new_id();
+    let campaign = campaigns::Model {
+        id: campaign_id.clone(),
+        name,
+        description: non_empty(config.description),
+        system: non_empty(config.system),
+        settings_json,
+        created_at: now,
+        updated_at: now,
+        archived_at: None,
+        archive_path: None,
+        locked_at: None,
+        created_by: None,
+        modified_by: None,
+    };
+
+    // Parents always come before their children in a template
+    let world_name = non_empty(config.world_name);
+    let mut location_models: Vec<locations::Model> = Vec::new();
+    for (i, &(default_name, location_type, parent)) in template.locations.iter().enumerate() {
+        let name = match (&world_name, i) {
+            (Some(world), 0) => world.clone(),
+            _ => default_name.to_string(),
+        };
+        location_models.push(locations::Model {
+            id: new_id(),
+            campaign_id: campaign_id.clone(),
+            parent_id: parent.map(|p| location_models[p].id.clone()),
+            name,
+            location_type: location_type.to_string(),
+            description: None,
+            gm_notes: None,
+            color: None,
+            icon: None,
+            created_at: now,
+            updated_at: now,
+            population: None,
+            government_type: None,
+            dominant_organization_id: None,
+            economy_notes: None,
+            created_by: None,
+            modified_by: None,
+        });
+    }
+
+    let mut organization_models = Vec::with_capacity(config.factions.len());
+    for (i, faction) in config.factions.into_iter().enumerate() {
+        let name = faction.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::Validation(format!(
+                "factions[{}].name: must not be empty",
+                i
+            )));
+        }
+        let org_type = faction.org_type.unwrap_or_else(|| "other".to_string());
+        if !ORG_TYPES.contains(&org_type.as_str()) {
+            return Err(AppError::Validation(format!(
+                "factions[{}].org_type: must be one of: {}",
+                i,
+                ORG_TYPES.join(", ")
+            )));
+        }
+        organization_models.push(organizations::Model {
+            id: new_id(),
+            campaign_id: campaign_id.clone(),
+            name,
+            org_type,
+            description: non_empty(faction.description),
+            goals: None,
+            resources: None,
+            reputation: None,
+            secrets: None,
+            is_active: true,
+            color: None,
+            icon: None,
+            created_at: now,
+            updated_at: now,
+            created_by: None,
+            modified_by: None,
+        });
+    }
+
+    let tag_names: Vec<(String, Option<String>)> = match config.tags {
+        Some(names) => {
+            let mut unique: Vec<(String, Option<String>)> = Vec::new();
+            for name in names.into_iter().filter_map(|n| non_empty(Some(n))) {
+                if !unique.iter().any(|(u, _)| u.eq_ignore_ascii_case(&name)) {
+                    unique.push((name, None));
+                }
+            }
+            unique
+        }
+        None => DEFAULT_TAGS
+            .iter()
+            .map(|&(name, color)| (name.to_string(), Some(color.to_string())))
+            .collect(),
+    };
+    let tag_models = tag_names
+        .into_iter()
+        .map(|(name, color)| tags::Model {
+            id: new_id(),
+            campaign_id: campaign_id.clone(),
+            name,
+            color,
+            created_at: now,
+            created_by: None,
+            modified_by: None,
+        })
+        .collect();
+
+    let session_models = if config.session_zero.unwrap_or(true) {
+        vec![sessions::Model {
+            id: new_id(),
+            campaign_id: campaign_id.clone(),
+            session_number: 0,
+            date: config.session_zero_date,
+            title: Some("Session Zero".to_string()),
+            planned_content: Some(SESSION_ZERO_PLAN.to_string()),
+            notes: None,
+            summary: None,
+            highlights: None,
+            started_at: None,
+            ended_at: None,
+            breaks_json: None,
+            timeline_event_id: None,
+            created_at: now,
+            updated_at: now,
+            created_by: None,
+            modified_by: None,
+        }]
+    } else {
+        Vec::new()
+    };
+
+    Ok(CampaignBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        campaign,
+        players: Vec::new(),
+        locations: location_models,
+        characters: Vec::new(),
+        organizations: organization_models,
+        quests: Vec::new(),
+        heroes: Vec::new(),
+        items: Vec::new(),
+        hero_items: Vec::new(),
+        organization_holdings: Vec::new(),
+        whereabouts: Vec::new(),
+        sessions: session_models,
+        session_absences: Vec::new(),
+        session_transcripts: Vec::new(),
+        dispositions: Vec::new(),
+        timeline_branches: Vec::new(),
+        timeline_events: Vec::new(),
+        secrets: Vec::new(),
+        handouts: Vec::new(),
+        handout_deliveries: Vec::new(),
+        snippets: Vec::new(),
+        prompt_templates: Vec::new(),
+        relationships: Vec::new(),
+        strength_changes: Vec::new(),
+        recall_cards: Vec::new(),
+        progress_clocks: Vec::new(),
+        encounter_tables: Vec::new(),
+        encounter_entries: Vec::new(),
+        encounter_rolls: Vec::new(),
+        price_list: Vec::new(),
+        price_modifiers: Vec::new(),
+        organization_goals: Vec::new(),
+        goal_advances: Vec::new(),
+        content_warnings: Vec::new(),
+        annotations: Vec::new(),
+        tags: tag_models,
+        entity_tags: Vec::new(),
+    })
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Create a campaign and its starting content in one transaction
+#[instrument(skip_all, err)]
+pub async fn bootstrap_campaign_impl(
+    db: &DatabaseConnection,
+    config: BootstrapConfig,
+) -> Result<BootstrapReport, AppError> {
+    let bundle = build_bundle(config, Utc::now())?;
+    let counts = bundle.counts();
+    let session_zero_id = bundle.sessions.first().map(|s| s.id.clone());
+    let campaign = bundle.campaign.clone();
+
+    let txn = db.begin().await?;
+    insert_bundle(&txn, bundle).await?;
+    txn.commit().await?;
+
+    tracing::info!(campaign_id = %campaign.id, counts = ?counts, "Bootstrapped campaign");
+    Ok(BootstrapReport {
+        campaign: campaign.into(),
+        counts,
+        session_zero_id,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn bootstrap_campaign(
+    state: State<'_, AppState>,
+    config: BootstrapConfig,
+) -> Result<BootstrapReport, AppError> {
+    bootstrap_campaign_impl(&state.db, config).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::validation::LOCATION_TYPES;
+
+    #[test]
+    fn test_location_templates_are_well_formed() {
+        for template in LOCATION_TEMPLATES {
+            for (i, (_, location_type, parent)) in template.locations.iter().enumerate() {
+                assert!(
+                    LOCATION_TYPES.contains(location_type),
+                    "{}: unknown type {}",
+                    template.key,
+                    location_type
+                );
+                assert_eq!(parent.is_none(), i == 0, "{}: one root first", template.key);
+                assert!(
+                    parent.is_none_or(|p| p < i),
+                    "{}: parent after child",
+                    template.key
+                );
+            }
+        }
+    }
+}
@@ -1,9 +1,12 @@
+use crate::commands::campaign_settings::CampaignSettings;
 use crate::db::AppState;
+use crate::demo::{self, DemoOptions, MAX_DEMO_SCALE};
 use crate::error::AppError;
 use ::entity::campaigns::{self, Entity as Campaign};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CampaignResponse {
@@ -14,6 +17,11 @@ pub struct CampaignResponse {
     pub settings_json: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Set once the campaign's content has been moved out to an archive file
+    pub archived_at: Option<String>,
+    pub archive_path: Option<String>,
+    /// Set while the campaign is locked against edits
+    pub locked_at: Option<String>,
 }
 
 impl From<campaigns::Model> for CampaignResponse {
@@ -26,12 +34,16 @@ impl From<campaigns::Model> for CampaignResponse {
             settings_json: model.settings_json,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
+            archived_at: model.archived_at.map(|t| t.to_string()),
+            archive_path: model.archive_path,
+            locked_at: model.locked_at.map(|t| t.to_string()),
         }
     }
 }
 
 // ============ Core implementation functions (testable) ============
 
+#[instrument(skip_all, err)]
 pub async fn create_campaign_impl(
     db: &DatabaseConnection,
     name: String,
@@ -49,12 +61,18 @@ pub async fn create_campaign_impl(
         settings_json: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        archived_at: Set(None),
+        archive_path: Set(None),
+        locked_at: Set(None),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     let result = model.insert(db).await?;
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_campaign_impl(
     db: &DatabaseConnection,
     id: String,
@@ -67,6 +85,7 @@ pub async fn get_campaign_impl(
     Ok(campaign.into())
 }
 
+#[instrument(skip_all, err)]
 pub async fn list_campaigns_impl(
     db: &DatabaseConnection,
 ) -> Result<Vec<CampaignResponse>, AppError> {
@@ -78,6 +97,7 @@ pub async fn list_campaigns_impl(
     Ok(campaigns.into_iter().map(|c| c.into()).collect())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn update_campaign_impl(
     db: &DatabaseConnection,
     id: String,
@@ -103,6 +123,10 @@ pub async fn update_campaign_impl(
         active.system = Set(Some(s));
     }
     if let Some(sj) = settings_json {
+        // Reject JSON that doesn't fit the settings shape instead of storing it blindly
+        let settings: CampaignSettings = serde_json::from_str(&sj)
+            .map_err(|e| AppError::Validation(format!("settings_json: {}", e)))?;
+        settings.validate()?;
         active.settings_json = Set(Some(sj));
     }
     active.updated_at = Set(chrono::Utc::now());
@@ -111,11 +135,79 @@ pub async fn update_campaign_impl(
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_campaign_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
     let result = Campaign::delete_by_id(&id).exec(db).await?;
     Ok(result.rows_affected > 0)
 }
 
+/// Lock a campaign against edits. Until it's unlocked, anything that would
+/// change its content fails with a READ_ONLY error; reads and search still
+/// work. Locking an already locked campaign leaves it as it is.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn lock_campaign_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<CampaignResponse, AppError> {
+    let campaign = Campaign::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", id)))?;
+    if campaign.locked_at.is_some() {
+        return Ok(campaign.into());
+    }
+
+    let mut active: campaigns::ActiveModel = campaign.into();
+    active.locked_at = Set(Some(chrono::Utc::now()));
+    Ok(active.update(db).await?.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn unlock_campaign_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<CampaignResponse, AppError> {
+    let campaign = Campaign::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", id)))?;
+
+    let mut active: campaigns::ActiveModel = campaign.into();
+    active.locked_at = Set(None);
+    Ok(active.update(db).await?.into())
+}
+
+/// Generate and insert a populated example campaign
+#[instrument(skip_all, err)]
+pub async fn create_demo_campaign_impl(
+    db: &DatabaseConnection,
+    name: Option<String>,
+    scale: Option<u32>,
+) -> Result<CampaignResponse, AppError> {
+    let scale = scale.unwrap_or(1);
+    if !(1..=MAX_DEMO_SCALE).contains(&scale) {
+        return Err(AppError::Validation(format!(
+            "scale: must be between 1 and {}",
+            MAX_DEMO_SCALE
+        )));
+    }
+
+    let mut options = DemoOptions {
+        scale,
+        ..Default::default()
+    };
+    if let Some(name) = name {
+        options.name = name;
+    }
+
+    let txn = db.begin().await?;
+    let bundle = demo::create_demo_campaign(&txn, &options).await?;
+    txn.commit().await?;
+
+    tracing::info!(counts = ?bundle.counts(), "Created demo campaign");
+    Ok(bundle.campaign.into())
+}
+
 // ============ Tauri command wrappers ============
 
 #[tauri::command(rename_all = "snake_case")]
@@ -157,3 +249,28 @@ pub async fn update_campaign(
 pub async fn delete_campaign(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
     delete_campaign_impl(&state.db, id).await
 }
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn lock_campaign(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<CampaignResponse, AppError> {
+    lock_campaign_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn unlock_campaign(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<CampaignResponse, AppError> {
+    unlock_campaign_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_demo_campaign(
+    state: State<'_, AppState>,
+    name: Option<String>,
+    scale: Option<u32>,
+) -> Result<CampaignResponse, AppError> {
+    create_demo_campaign_impl(&state.db, name, scale).await
+}
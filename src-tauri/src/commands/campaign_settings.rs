@@ -0,0 +1,745 @@
+//! Typed view of `campaigns.settings_json`.
+//!
+//! Every struct defaults missing fields and the top level keeps unknown keys,
+//! so settings written by a newer version survive a round trip through an
+//! older one.
+
+use crate::commands::validation::{LOCATION_TYPES, ORG_TYPES, PLOT_TYPES, QUEST_STATUS};
+use crate::db::AppState;
+use crate::error::{AppError, FieldError};
+use crate::share::SHAREABLE_TYPES;
+use ::entity::campaigns::{self, Entity as Campaign};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tauri::State;
+use tracing::instrument;
+use ts_rs::TS;
+
+/// Bump when a field changes meaning; `CampaignSettings::upgrade` migrates
+/// older layouts
+pub const CAMPAIGN_SETTINGS_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct CampaignSettings {
+    pub version: u32,
+    pub ai: AiSettings,
+    pub autosave: AutosaveSettings,
+    pub calendar: CalendarSettings,
+    pub defaults: EntityDefaults,
+    pub player_view: PlayerViewSettings,
+    pub timeline: TimelineSettings,
+    /// Keys this version doesn't know about, preserved as-is
+    #[serde(flatten)]
+    #[ts(skip)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl Default for CampaignSettings {
+    fn default() -> Self {
+        Self {
+            version: CAMPAIGN_SETTINGS_VERSION,
+            ai: AiSettings::default(),
+            autosave: AutosaveSettings::default(),
+            calendar: CalendarSettings::default(),
+            defaults: EntityDefaults::default(),
+            player_view: PlayerViewSettings::default(),
+            timeline: TimelineSettings::default(),
+            extra: BTreeMap::new(),
+        }
+    }
+}
+
+/// Per-campaign overrides for the AI assistant
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct AiSettings {
+    pub model: Option<String>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    /// Voice and role the assistant takes on for this campaign
+    pub persona: Option<String>,
+    /// Subjects the assistant must not raise or write about
+    pub banned_topics: Vec<String>,
+    /// One of `AI_RESPONSE_STYLES`; the assistant's default when unset
+    pub response_style: Option<String>,
+    /// Prompt tokens a single request may use before the UI warns
+    pub token_budget: Option<u32>,
+    /// Tokens the campaign may use per calendar month
+    pub monthly_token_budget: Option<u32>,
+    /// Estimated spend allowed per calendar month, in US dollars
+    pub monthly_cost_budget: Option<f64>,
+    /// One of `AI_BUDGET_ENFORCEMENTS`; "confirm" when unset
+    pub budget_enforcement: Option<String>,
+}
+
+/// Response styles and the instruction each adds to the system prompt
+pub const AI_RESPONSE_STYLES: &[(&str, &str)] = &[
+    (
+        "concise",
+        "Keep answers short: a few sentences or a tight list. Skip preamble and recaps.",
+    ),
+    (
+        "balanced",
+        "Match the length of your answer to the question. Use a short answer for a quick \
+         lookup and a fuller one for worldbuilding.",
+    ),
+    (
+        "detailed",
+        "Give thorough answers with sensory detail, hooks and connections to existing lore.",
+    ),
+];
+
+/// What happens when a request would go over a monthly budget: ask the GM
+/// first, or refuse outright
+pub const AI_BUDGET_ENFORCEMENTS: &[&str] = &["confirm", "block"];
+
+/// Longest persona text accepted
+const MAX_PERSONA_CHARS: usize = 4000;
+/// Most banned topics accepted
+const MAX_BANNED_TOPICS: usize = 50;
+
+/// How often the editor saves drafts of long text fields
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct AutosaveSettings {
+    /// Seconds between draft saves while a field is being edited;
+    /// 5 (`DEFAULT_AUTOSAVE_SECONDS`) when unset, and 0 turns autosave off
+    pub interval_seconds: Option<u32>,
+}
+
+pub const DEFAULT_AUTOSAVE_SECONDS: u32 = 5;
+/// Longest autosave interval accepted
+const MAX_AUTOSAVE_SECONDS: u32 = 300;
+
+/// In-world calendar used for timeline dates
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct CalendarSettings {
+    pub name: Option<String>,
+    pub months: Vec<CalendarMonth>,
+    pub weekdays: Vec<String>,
+    /// Era suffix shown after years, e.g. "DR"
+    pub era: Option<String>,
+    /// Current in-world date as displayed, e.g. "3 Hammer 1492 DR"
+    pub current_date: Option<String>,
+    /// Current in-world date, used to work out character ages
+    pub today: Option<InWorldDate>,
+}
+
+/// A date on the campaign calendar. Months and days are 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct InWorldDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct CalendarMonth {
+    pub name: String,
+    pub days: u32,
+}
+
+/// Values pre-filled when creating entities
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct EntityDefaults {
+    pub location_type: Option<String>,
+    pub org_type: Option<String>,
+    pub quest_status: Option<String>,
+    pub plot_type: Option<String>,
+}
+
+/// What the player companion view is allowed to show
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct PlayerViewSettings {
+    /// Entity types shared when no explicit selection is made; empty means all
+    pub shared_entity_types: Vec<String>,
+    pub hide_dead_characters: bool,
+}
+
+/// How play is mirrored onto the world timeline
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct TimelineSettings {
+    /// Log a timeline event whenever a session summary is saved
+    pub log_sessions: bool,
+    /// Significance given to logged session events; "local" when unset
+    pub session_significance: Option<String>,
+}
+
+/// Month layout assumed when a campaign hasn't defined its own calendar
+const DEFAULT_MONTHS: u32 = 12;
+const DEFAULT_MONTH_DAYS: u32 = 30;
+
+impl CalendarSettings {
+    fn month_count(&self) -> u32 {
+        if self.months.is_empty() {
+            DEFAULT_MONTHS
+        } else {
+            self.months.len() as u32
+        }
+    }
+
+    fn month_days(&self, month: u32) -> u32 {
+        match self.months.get(month.wrapping_sub(1) as usize) {
+            Some(m) => m.days,
+            None => DEFAULT_MONTH_DAYS,
+        }
+    }
+
+    /// Check that `date` exists on this calendar
+    pub fn check_date(&self, date: &InWorldDate, field: &str) -> Option<FieldError> {
+        let months = self.month_count();
+        if !(1..=months).contains(&date.month) {
+            return Some(FieldError {
+                field: format!("{}.month", field),
+                code: "range".to_string(),
+                message: format!("must be between 1 and {}", months),
+            });
+        }
+        let days = self.month_days(date.month);
+        if !(1..=days).contains(&date.day) {
+            return Some(FieldError {
+                field: format!("{}.day", field),
+                code: "range".to_string(),
+                message: format!("must be between 1 and {}", days),
+            });
+        }
+        None
+    }
+
+    /// Render a date the way the timeline shows it, e.g. "3 Hammer 1492 DR"
+    pub fn format_date(&self, date: &InWorldDate) -> String {
+        let month = match self.months.get(date.month.wrapping_sub(1) as usize) {
+            Some(m) => m.name.clone(),
+            None => format!("Month {}", date.month),
+        };
+        match &self.era {
+            Some(era) => format!("{} {} {} {}", date.day, month, date.year, era),
+            None => format!("{} {} {}", date.day, month, date.year),
+        }
+    }
+
+    /// Days since day 1 of year 0; suitable as a timeline sort order
+    pub fn day_number(&self, date: &InWorldDate) -> i64 {
+        let days_per_year: i64 = (1..=self.month_count())
+            .map(|m| self.month_days(m) as i64)
+            .sum();
+        let days_before_month: i64 = (1..date.month).map(|m| self.month_days(m) as i64).sum();
+        date.year as i64 * days_per_year + days_before_month + date.day as i64 - 1
+    }
+}
+
+impl AiSettings {
+    /// The campaign's system prompt: `base` followed by the persona, response
+    /// style and banned topics, each under its own heading
+    pub fn system_prompt(&self, base: &str) -> String {
+        let mut prompt = base.trim_end().to_string();
+
+        if let Some(persona) = self.persona.as_deref().map(str::trim) {
+            if !persona.is_empty() {
+                prompt.push_str("\n\n## Campaign Persona\n\n");
+                prompt.push_str(persona);
+            }
+        }
+
+        let style = self.response_style.as_deref().and_then(|style| {
+            AI_RESPONSE_STYLES
+                .iter()
+                .find(|(name, _)| *name == style)
+                .map(|(_, instruction)| *instruction)
+        });
+        if let Some(instruction) = style {
+            prompt.push_str("\n\n## Response Style\n\n");
+            prompt.push_str(instruction);
+        }
+
+        let banned: Vec<&str> = self
+            .banned_topics
+            .iter()
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .collect();
+        if !banned.is_empty() {
+            prompt.push_str("\n\n## Off-Limits Topics\n\n");
+            prompt.push_str(
+                "The GM has ruled these topics out for this campaign. Never introduce them \
+                 in answers or generated content. If asked about one directly, say it is \
+                 off-limits for this campaign.\n",
+            );
+            for topic in banned {
+                prompt.push_str("\n- ");
+                prompt.push_str(topic);
+            }
+        }
+
+        prompt
+    }
+}
+
+impl InWorldDate {
+    /// Whole years from `self` until `later`, counting a year only once its
+    /// anniversary has passed
+    pub fn years_until(&self, later: &InWorldDate) -> i32 {
+        let mut years = later.year - self.year;
+        if (later.month, later.day) < (self.month, self.day) {
+            years -= 1;
+        }
+        years
+    }
+}
+
+impl CampaignSettings {
+    /// Parse stored settings, falling back to defaults for missing or
+    /// unreadable values rather than failing the caller
+    pub fn from_json(raw: Option<&str>) -> Self {
+        let Some(raw) = raw.filter(|r| !r.trim().is_empty()) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str::<Self>(raw) {
+            Ok(settings) => settings.upgrade(),
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable campaign settings: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, AppError> {
+        serde_json::to_string(self)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {}", e)))
+    }
+
+    /// Bring an older layout up to the current version. Newer versions are
+    /// left untouched so their fields aren't clobbered.
+    fn upgrade(mut self) -> Self {
+        if self.version < CAMPAIGN_SETTINGS_VERSION {
+            self.version = CAMPAIGN_SETTINGS_VERSION;
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        let mut errors = Vec::new();
+        let mut fail = |field: &str, code: &str, message: String| {
+            errors.push(FieldError {
+                field: field.to_string(),
+                code: code.to_string(),
+                message,
+            })
+        };
+
+        if let Some(t) = self.ai.temperature {
+            if !(0.0..=2.0).contains(&t) {
+                fail("ai.temperature", "range", "must be between 0 and 2".into());
+            }
+        }
+        if self.ai.max_tokens == Some(0) {
+            fail("ai.max_tokens", "range", "must be greater than 0".into());
+        }
+        if self.ai.token_budget == Some(0) {
+            fail("ai.token_budget", "range", "must be greater than 0".into());
+        }
+        if self.ai.monthly_token_budget == Some(0) {
+            fail(
+                "ai.monthly_token_budget",
+                "range",
+                "must be greater than 0".into(),
+            );
+        }
+        if let Some(cost) = self.ai.monthly_cost_budget {
+            if !(cost.is_finite() && cost > 0.0) {
+                fail(
+                    "ai.monthly_cost_budget",
+                    "range",
+                    "must be greater than 0".into(),
+                );
+            }
+        }
+        if let Some(enforcement) = &self.ai.budget_enforcement {
+            if !AI_BUDGET_ENFORCEMENTS.contains(&enforcement.as_str()) {
+                fail(
+                    "ai.budget_enforcement",
+                    "invalid_option",
+                    format!("must be one of: {}", AI_BUDGET_ENFORCEMENTS.join(", ")),
+                );
+            }
+        }
+        if let Some(persona) = &self.ai.persona {
+            if persona.chars().count() > MAX_PERSONA_CHARS {
+                fail(
+                    "ai.persona",
+                    "length",
+                    format!("must be at most {} characters", MAX_PERSONA_CHARS),
+                );
+            }
+        }
+        if self.ai.banned_topics.len() > MAX_BANNED_TOPICS {
+            fail(
+                "ai.banned_topics",
+                "length",
+                format!("must have at most {} topics", MAX_BANNED_TOPICS),
+            );
+        }
+        for (i, topic) in self.ai.banned_topics.iter().enumerate() {
+            if !(1..=100).contains(&topic.trim().chars().count()) {
+                fail(
+                    &format!("ai.banned_topics[{}]", i),
+                    "length",
+                    "must be 1-100 characters".into(),
+                );
+            }
+        }
+        if let Some(style) = &self.ai.response_style {
+            if !AI_RESPONSE_STYLES.iter().any(|(name, _)| name == style) {
+                let names: Vec<&str> = AI_RESPONSE_STYLES.iter().map(|(name, _)| *name).collect();
+                fail(
+                    "ai.response_style",
+                    "invalid_option",
+                    format!("must be one of: {}", names.join(", ")),
+                );
+            }
+        }
+
+        if let Some(seconds) = self.autosave.interval_seconds {
+            if seconds > MAX_AUTOSAVE_SECONDS {
+                fail(
+                    "autosave.interval_seconds",
+                    "range",
+                    format!("must be between 0 and {}", MAX_AUTOSAVE_SECONDS),
+                );
+            }
+        }
+
+        for (i, month) in self.calendar.months.iter().enumerate() {
+            if month.name.trim().is_empty() {
+                fail(
+                    &format!("calendar.months[{}].name", i),
+                    "required",
+                    "must not be empty".into(),
+                );
+            }
+            if !(1..=1000).contains(&month.days) {
+                fail(
+                    &format!("calendar.months[{}].days", i),
+                    "range",
+                    "must be between 1 and 1000".into(),
+                );
+            }
+        }
+
+        if let Some(today) = &self.calendar.today {
+            if let Some(e) = self.calendar.check_date(today, "calendar.today") {
+                fail(&e.field, &e.code, e.message);
+            }
+        }
+
+        if let Some(significance) = &self.timeline.session_significance {
+            if !(1..=50).contains(&significance.trim().len()) {
+                fail(
+                    "timeline.session_significance",
+                    "length",
+                    "must be 1-50 characters".into(),
+                );
+            }
+        }
+
+        let allowed = [
+            (
+                "defaults.location_type",
+                &self.defaults.location_type,
+                LOCATION_TYPES,
+            ),
+            ("defaults.org_type", &self.defaults.org_type, ORG_TYPES),
+            (
+                "defaults.quest_status",
+                &self.defaults.quest_status,
+                QUEST_STATUS,
+            ),
+            ("defaults.plot_type", &self.defaults.plot_type, PLOT_TYPES),
+        ];
+        for (field, value, options) in allowed {
+            if let Some(value) = value {
+                if !options.contains(&value.as_str()) {
+                    fail(
+                        field,
+                        "invalid_option",
+                        format!("must be one of: {}", options.join(", ")),
+                    );
+                }
+            }
+        }
+
+        for entity_type in &self.player_view.shared_entity_types {
+            if !SHAREABLE_TYPES.contains(&entity_type.as_str()) {
+                fail(
+                    "player_view.shared_entity_types",
+                    "invalid_option",
+                    format!("must be one of: {}", SHAREABLE_TYPES.join(", ")),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::InvalidFields(errors))
+        }
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_campaign_settings_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<CampaignSettings, AppError> {
+    let campaign = Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    Ok(CampaignSettings::from_json(
+        campaign.settings_json.as_deref(),
+    ))
+}
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn update_campaign_settings_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    settings: CampaignSettings,
+) -> Result<CampaignSettings, AppError> {
+    settings.validate()?;
+    let settings = settings.upgrade();
+
+    let campaign = Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let mut active: campaigns::ActiveModel = campaign.into();
+    active.settings_json = Set(Some(settings.to_json()?));
+    active.updated_at = Set(chrono::Utc::now());
+    active.update(db).await?;
+
+    Ok(settings)
+}
+
+/// The assistant's system prompt for a campaign, built from `base_prompt`
+/// and the campaign's AI settings
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn build_ai_system_prompt_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    base_prompt: String,
+) -> Result<String, AppError> {
+    let settings = get_campaign_settings_impl(db, campaign_id).await?;
+    Ok(settings.ai.system_prompt(&base_prompt))
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_campaign_settings(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<CampaignSettings, AppError> {
+    get_campaign_settings_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_campaign_settings(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    settings: CampaignSettings,
+) -> Result<CampaignSettings, AppError> {
+    update_campaign_settings_impl(&state.db, campaign_id, settings).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn build_ai_system_prompt(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    base_prompt: String,
+) -> Result<String, AppError> {
+    build_ai_system_prompt_impl(&state.db, campaign_id, base_prompt).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_bindings() {
+        CampaignSettings::export_all().unwrap();
+    }
+
+    #[test]
+    fn test_missing_or_invalid_json_gives_defaults() {
+        assert_eq!(
+            CampaignSettings::from_json(None),
+            CampaignSettings::default()
+        );
+        assert_eq!(
+            CampaignSettings::from_json(Some("not json")),
+            CampaignSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_survive_round_trip() {
+        let raw = r#"{"version":1,"ai":{"model":"opus","future_knob":3},"dice":{"theme":"bone"}}"#;
+        let settings = CampaignSettings::from_json(Some(raw));
+
+        assert_eq!(settings.ai.model.as_deref(), Some("opus"));
+        assert!(settings.extra.contains_key("dice"));
+
+        let written: Value = serde_json::from_str(&settings.to_json().unwrap()).unwrap();
+        assert_eq!(written["dice"]["theme"], "bone");
+    }
+
+    #[test]
+    fn test_newer_version_is_preserved() {
+        let settings = CampaignSettings::from_json(Some(r#"{"version":99}"#));
+        assert_eq!(settings.version, 99);
+    }
+
+    fn harptos() -> CalendarSettings {
+        CalendarSettings {
+            months: vec![
+                CalendarMonth {
+                    name: "Hammer".to_string(),
+                    days: 30,
+                },
+                CalendarMonth {
+                    name: "Midwinter".to_string(),
+                    days: 1,
+                },
+            ],
+            era: Some("DR".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_calendar_dates() {
+        let calendar = harptos();
+        let date = InWorldDate {
+            year: 1492,
+            month: 2,
+            day: 1,
+        };
+
+        assert_eq!(calendar.format_date(&date), "1 Midwinter 1492 DR");
+        assert_eq!(calendar.day_number(&date), 1492 * 31 + 30);
+        assert!(calendar.check_date(&date, "date").is_none());
+
+        let bad_day = InWorldDate { day: 2, ..date };
+        let error = calendar.check_date(&bad_day, "date").unwrap();
+        assert_eq!(error.field, "date.day");
+    }
+
+    #[test]
+    fn test_years_until_counts_anniversaries() {
+        let birth = InWorldDate {
+            year: 1450,
+            month: 6,
+            day: 15,
+        };
+        let before = InWorldDate {
+            year: 1492,
+            month: 6,
+            day: 14,
+        };
+        let on = InWorldDate { day: 15, ..before };
+
+        assert_eq!(birth.years_until(&before), 41);
+        assert_eq!(birth.years_until(&on), 42);
+    }
+
+    #[test]
+    fn test_validate_reports_each_bad_field() {
+        let settings = CampaignSettings {
+            ai: AiSettings {
+                temperature: Some(5.0),
+                ..Default::default()
+            },
+            autosave: AutosaveSettings {
+                interval_seconds: Some(3600),
+            },
+            defaults: EntityDefaults {
+                location_type: Some("spaceship".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let Err(AppError::InvalidFields(errors)) = settings.validate() else {
+            panic!("expected field errors");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "ai.temperature",
+                "autosave.interval_seconds",
+                "defaults.location_type"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ai_system_prompt_sections() {
+        let ai = AiSettings {
+            persona: Some("You are Old Quill, the campaign's archivist.".to_string()),
+            banned_topics: vec!["spiders".to_string(), "  ".to_string()],
+            response_style: Some("concise".to_string()),
+            ..Default::default()
+        };
+        let prompt = ai.system_prompt("Base prompt.\n");
+
+        assert!(prompt.starts_with("Base prompt.\n\n## Campaign Persona\n\nYou are Old Quill"));
+        assert!(prompt.contains("## Response Style\n\nKeep answers short"));
+        assert!(prompt.ends_with("off-limits for this campaign.\n\n- spiders"));
+
+        assert_eq!(
+            AiSettings::default().system_prompt("Base prompt."),
+            "Base prompt."
+        );
+    }
+
+    #[test]
+    fn test_validate_ai_persona_settings() {
+        let settings = CampaignSettings {
+            ai: AiSettings {
+                banned_topics: vec![String::new()],
+                response_style: Some("rambling".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let Err(AppError::InvalidFields(errors)) = settings.validate() else {
+            panic!("expected field errors");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["ai.banned_topics[0]", "ai.response_style"]);
+    }
+}
@@ -1,10 +1,17 @@
-use crate::commands::validation::CreateCharacterInput;
+use crate::commands::campaign_settings::{
+    get_campaign_settings_impl, CalendarSettings, InWorldDate,
+};
+use crate::commands::json_schema::validate_stat_block_impl;
+use crate::commands::references::remove_references;
+use crate::commands::validation::{CreateCharacterInput, RoleplayInput};
 use crate::db::AppState;
-use crate::error::AppError;
+use crate::error::{AppError, FieldError};
 use ::entity::characters::{self, Entity as Character};
+use ::entity::timeline_events::{self, Entity as TimelineEvent};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,7 +27,25 @@ pub struct CharacterResponse {
     pub motivations: Option<String>,
     pub secrets: Option<String>,
     pub voice_notes: Option<String>,
+    /// How the character sounds, e.g. "clipped northern burr"
+    pub accent: Option<String>,
+    pub mannerisms: Vec<String>,
+    pub catchphrases: Vec<String>,
+    /// A line or two in the character's voice
+    pub speech_sample: Option<String>,
     pub stat_block_json: Option<String>,
+    /// Titles, nicknames and false names the character also goes by
+    pub aliases: Vec<String>,
+    pub birth_date: Option<InWorldDate>,
+    pub death_date: Option<InWorldDate>,
+    /// Timeline event recording the death, created with the death date
+    pub death_event_id: Option<String>,
+    /// Years lived as of the campaign's current date, or at death
+    pub age: Option<i32>,
+    /// Hero this character was converted from, if any
+    pub source_hero_id: Option<String>,
+    pub color: Option<String>,
+    pub icon: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -39,15 +64,87 @@ impl From<characters::Model> for CharacterResponse {
             motivations: model.motivations,
             secrets: model.secrets,
             voice_notes: model.voice_notes,
+            accent: model.accent,
+            mannerisms: parse_string_list(model.mannerisms_json.as_deref()),
+            catchphrases: parse_string_list(model.catchphrases_json.as_deref()),
+            speech_sample: model.speech_sample,
             stat_block_json: model.stat_block_json,
+            aliases: parse_string_list(model.aliases_json.as_deref()),
+            birth_date: date_from_columns(model.birth_year, model.birth_month, model.birth_day),
+            death_date: date_from_columns(model.death_year, model.death_month, model.death_day),
+            death_event_id: model.death_event_id,
+            age: None,
+            source_hero_id: model.source_hero_id,
+            color: model.color,
+            icon: model.icon,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
     }
 }
 
+impl CharacterResponse {
+    fn with_age(mut self, calendar: &CalendarSettings) -> Self {
+        let until = self.death_date.or(calendar.today);
+        self.age = match (self.birth_date, until) {
+            (Some(birth), Some(until)) => Some(birth.years_until(&until)),
+            _ => None,
+        };
+        self
+    }
+}
+
+/// Maximum length of a single alias, matching the name limit
+const MAX_ALIAS_LEN: usize = 200;
+
+/// A JSON string array column such as `aliases_json`
+pub(crate) fn parse_string_list(raw: Option<&str>) -> Vec<String> {
+    raw.and_then(|r| serde_json::from_str(r).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn string_list_to_json(values: &[String]) -> Result<Option<String>, AppError> {
+    if values.is_empty() {
+        return Ok(None);
+    }
+    serde_json::to_string(values)
+        .map(Some)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize list: {}", e)))
+}
+
+pub(crate) fn date_from_columns(
+    year: Option<i32>,
+    month: Option<i32>,
+    day: Option<i32>,
+) -> Option<InWorldDate> {
+    Some(InWorldDate {
+        year: year?,
+        month: u32::try_from(month?).ok()?,
+        day: u32::try_from(day?).ok()?,
+    })
+}
+
+pub(crate) fn date_to_columns(
+    date: Option<InWorldDate>,
+) -> (Option<i32>, Option<i32>, Option<i32>) {
+    match date {
+        Some(d) => (Some(d.year), Some(d.month as i32), Some(d.day as i32)),
+        None => (None, None, None),
+    }
+}
+
+async fn campaign_calendar(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+) -> Result<CalendarSettings, AppError> {
+    Ok(get_campaign_settings_impl(db, campaign_id.to_string())
+        .await?
+        .calendar)
+}
+
 // ============ Core implementation functions (testable) ============
 
+#[instrument(skip_all, err)]
 pub async fn create_character_impl(
     db: &DatabaseConnection,
     input: CreateCharacterInput,
@@ -71,14 +168,32 @@ pub async fn create_character_impl(
         secrets: Set(input.secrets),
         voice_notes: Set(input.voice_notes),
         stat_block_json: Set(None),
+        birth_year: Set(None),
+        birth_month: Set(None),
+        birth_day: Set(None),
+        death_year: Set(None),
+        death_month: Set(None),
+        death_day: Set(None),
+        death_event_id: Set(None),
+        aliases_json: Set(None),
+        source_hero_id: Set(None),
+        color: Set(None),
+        icon: Set(None),
+        accent: Set(None),
+        mannerisms_json: Set(None),
+        catchphrases_json: Set(None),
+        speech_sample: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     let result = model.insert(db).await?;
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_character_impl(
     db: &DatabaseConnection,
     id: String,
@@ -88,9 +203,11 @@ pub async fn get_character_impl(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Character {} not found", id)))?;
 
-    Ok(character.into())
+    let calendar = campaign_calendar(db, &character.campaign_id).await?;
+    Ok(CharacterResponse::from(character).with_age(&calendar))
 }
 
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn list_characters_impl(
     db: &DatabaseConnection,
     campaign_id: String,
@@ -101,10 +218,15 @@ pub async fn list_characters_impl(
         .all(db)
         .await?;
 
-    Ok(characters.into_iter().map(|c| c.into()).collect())
+    let calendar = campaign_calendar(db, &campaign_id).await?;
+    Ok(characters
+        .into_iter()
+        .map(|c| CharacterResponse::from(c).with_age(&calendar))
+        .collect())
 }
 
 #[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn update_character_impl(
     db: &DatabaseConnection,
     id: String,
@@ -119,6 +241,10 @@ pub async fn update_character_impl(
     voice_notes: Option<String>,
     stat_block_json: Option<String>,
 ) -> Result<CharacterResponse, AppError> {
+    if let Some(sb) = &stat_block_json {
+        validate_stat_block_impl(sb)?;
+    }
+
     let character = Character::find_by_id(&id)
         .one(db)
         .await?
@@ -159,11 +285,226 @@ pub async fn update_character_impl(
     active.updated_at = Set(chrono::Utc::now());
 
     let result = active.update(db).await?;
-    Ok(result.into())
+    let calendar = campaign_calendar(db, &result.campaign_id).await?;
+    Ok(CharacterResponse::from(result).with_age(&calendar))
 }
 
+/// Set or clear a character's birth and death dates. A death date marks the
+/// character dead and creates (or moves) a timeline event for the death;
+/// clearing it removes that event.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn set_character_life_dates_impl(
+    db: &DatabaseConnection,
+    id: String,
+    birth_date: Option<InWorldDate>,
+    death_date: Option<InWorldDate>,
+) -> Result<CharacterResponse, AppError> {
+    let character = Character::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", id)))?;
+    let calendar = campaign_calendar(db, &character.campaign_id).await?;
+
+    let mut errors = Vec::new();
+    if let Some(birth) = &birth_date {
+        errors.extend(calendar.check_date(birth, "birth_date"));
+    }
+    if let Some(death) = &death_date {
+        errors.extend(calendar.check_date(death, "death_date"));
+    }
+    if let (Some(birth), Some(death)) = (&birth_date, &death_date) {
+        if calendar.day_number(death) < calendar.day_number(birth) {
+            errors.push(FieldError {
+                field: "death_date".to_string(),
+                code: "range".to_string(),
+                message: "must not be before birth_date".to_string(),
+            });
+        }
+    }
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    let txn = db.begin().await?;
+    let now = chrono::Utc::now();
+
+    let existing_event = match &character.death_event_id {
+        Some(event_id) => TimelineEvent::find_by_id(event_id).one(&txn).await?,
+        None => None,
+    };
+
+    let death_event_id = match (death_date, existing_event) {
+        (Some(death), Some(event)) => {
+            let mut active: timeline_events::ActiveModel = event.into();
+            active.title = Set(format!("Death of {}", character.name));
+            active.date_display = Set(calendar.format_date(&death));
+            active.sort_order = Set(calendar.day_number(&death));
+            active.updated_at = Set(now);
+            Some(active.update(&txn).await?.id)
+        }
+        (Some(death), None) => {
+            let event = timeline_events::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                campaign_id: Set(character.campaign_id.clone()),
+                date_display: Set(calendar.format_date(&death)),
+                sort_order: Set(calendar.day_number(&death)),
+                title: Set(format!("Death of {}", character.name)),
+                description: Set(None),
+                significance: Set("local".to_string()),
+                is_public: Set(true),
+                branch_id: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+                created_by: Set(None),
+                modified_by: Set(None),
+            };
+            Some(event.insert(&txn).await?.id)
+        }
+        (None, Some(event)) => {
+            TimelineEvent::delete_by_id(&event.id).exec(&txn).await?;
+            None
+        }
+        (None, None) => None,
+    };
+
+    let is_alive = death_date.is_none() && character.is_alive;
+    let (birth_year, birth_month, birth_day) = date_to_columns(birth_date);
+    let (death_year, death_month, death_day) = date_to_columns(death_date);
+
+    let mut active: characters::ActiveModel = character.into();
+    active.birth_year = Set(birth_year);
+    active.birth_month = Set(birth_month);
+    active.birth_day = Set(birth_day);
+    active.death_year = Set(death_year);
+    active.death_month = Set(death_month);
+    active.death_day = Set(death_day);
+    active.death_event_id = Set(death_event_id);
+    active.is_alive = Set(is_alive);
+    active.updated_at = Set(now);
+
+    let result = active.update(&txn).await?;
+    txn.commit().await?;
+
+    Ok(CharacterResponse::from(result).with_age(&calendar))
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn add_character_alias_impl(
+    db: &DatabaseConnection,
+    id: String,
+    alias: String,
+) -> Result<CharacterResponse, AppError> {
+    let alias = alias.trim().to_string();
+    if alias.is_empty() || alias.chars().count() > MAX_ALIAS_LEN {
+        return Err(AppError::Validation(format!(
+            "alias: must be 1-{} characters",
+            MAX_ALIAS_LEN
+        )));
+    }
+
+    let character = Character::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", id)))?;
+
+    let mut aliases = parse_string_list(character.aliases_json.as_deref());
+    let lower = alias.to_lowercase();
+    if character.name.to_lowercase() == lower || aliases.iter().any(|a| a.to_lowercase() == lower) {
+        return Err(AppError::Conflict(format!(
+            "{} is already known as {}",
+            character.name, alias
+        )));
+    }
+    aliases.push(alias);
+
+    let mut active: characters::ActiveModel = character.into();
+    active.aliases_json = Set(string_list_to_json(&aliases)?);
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    let calendar = campaign_calendar(db, &result.campaign_id).await?;
+    Ok(CharacterResponse::from(result).with_age(&calendar))
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn remove_character_alias_impl(
+    db: &DatabaseConnection,
+    id: String,
+    alias: String,
+) -> Result<CharacterResponse, AppError> {
+    let character = Character::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", id)))?;
+
+    let mut aliases = parse_string_list(character.aliases_json.as_deref());
+    let lower = alias.trim().to_lowercase();
+    let before = aliases.len();
+    aliases.retain(|a| a.to_lowercase() != lower);
+    if aliases.len() == before {
+        return Err(AppError::NotFound(format!(
+            "Alias {} not found on character {}",
+            alias, id
+        )));
+    }
+
+    let mut active: characters::ActiveModel = character.into();
+    active.aliases_json = Set(string_list_to_json(&aliases)?);
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    let calendar = campaign_calendar(db, &result.campaign_id).await?;
+    Ok(CharacterResponse::from(result).with_age(&calendar))
+}
+
+/// Set a character's accent, mannerisms, catchphrases and speech sample.
+/// Blank mannerisms and catchphrases are dropped.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn set_character_roleplay_impl(
+    db: &DatabaseConnection,
+    id: String,
+    input: RoleplayInput,
+) -> Result<CharacterResponse, AppError> {
+    input.validate()?;
+
+    let character = Character::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", id)))?;
+
+    let cues = |values: Vec<String>| -> Vec<String> {
+        values
+            .into_iter()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect()
+    };
+    let mut active: characters::ActiveModel = character.into();
+    if let Some(accent) = input.accent {
+        active.accent = Set(Some(accent).filter(|a| !a.trim().is_empty()));
+    }
+    if let Some(mannerisms) = input.mannerisms {
+        active.mannerisms_json = Set(string_list_to_json(&cues(mannerisms))?);
+    }
+    if let Some(catchphrases) = input.catchphrases {
+        active.catchphrases_json = Set(string_list_to_json(&cues(catchphrases))?);
+    }
+    if let Some(sample) = input.speech_sample {
+        active.speech_sample = Set(Some(sample).filter(|s| !s.trim().is_empty()));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    let calendar = campaign_calendar(db, &result.campaign_id).await?;
+    Ok(CharacterResponse::from(result).with_age(&calendar))
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_character_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
-    let result = Character::delete_by_id(&id).exec(db).await?;
+    let txn = db.begin().await?;
+    remove_references(&txn, "character", &id).await?;
+    let result = Character::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
     Ok(result.rows_affected > 0)
 }
 
@@ -248,3 +589,40 @@ pub async fn update_character(
 pub async fn delete_character(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
     delete_character_impl(&state.db, id).await
 }
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_character_life_dates(
+    state: State<'_, AppState>,
+    id: String,
+    birth_date: Option<InWorldDate>,
+    death_date: Option<InWorldDate>,
+) -> Result<CharacterResponse, AppError> {
+    set_character_life_dates_impl(&state.db, id, birth_date, death_date).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn add_character_alias(
+    state: State<'_, AppState>,
+    id: String,
+    alias: String,
+) -> Result<CharacterResponse, AppError> {
+    add_character_alias_impl(&state.db, id, alias).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remove_character_alias(
+    state: State<'_, AppState>,
+    id: String,
+    alias: String,
+) -> Result<CharacterResponse, AppError> {
+    remove_character_alias_impl(&state.db, id, alias).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_character_roleplay(
+    state: State<'_, AppState>,
+    id: String,
+    input: RoleplayInput,
+) -> Result<CharacterResponse, AppError> {
+    set_character_roleplay_impl(&state.db, id, input).await
+}
@@ -0,0 +1,262 @@
+//! Data for the keyboard command palette. One call blends entities matching
+//! the query, recently edited entities and app actions into a single ranked
+//! list, so the palette doesn't merge several searches itself.
+
+use crate::commands::registry::table_for;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::State;
+use tracing::instrument;
+
+/// Entity types the palette can switch to
+const PALETTE_TYPES: &[&str] = &[
+    "character",
+    "location",
+    "organization",
+    "quest",
+    "hero",
+    "item",
+    "player",
+    "session",
+    "timeline_event",
+    "secret",
+    "handout",
+];
+
+/// App actions as (id, label, extra words they match)
+const PALETTE_ACTIONS: &[(&str, &str, &str)] = &[
+    ("new_session", "New session", "create add plan"),
+    ("new_character", "New character", "create add npc"),
+    ("new_location", "New location", "create add place"),
+    ("new_quest", "New quest", "create add hook"),
+    ("quick_note", "Quick note", "capture jot idea"),
+    ("open_inbox", "Open inbox", "triage unfiled"),
+    ("start_backup", "Start backup", "database copy save"),
+    ("search", "Search", "find full text"),
+    ("settings", "Settings", "preferences options"),
+];
+
+/// Items returned per call
+const PALETTE_LIMIT: usize = 20;
+/// Recently edited entities listed, and boosted when they match
+const RECENT_ITEMS: u64 = 8;
+/// Matching entities read before ranking
+const MAX_CANDIDATES: u64 = 200;
+/// Score added to matches that were edited recently
+const RECENT_BOOST: f64 = 0.1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaletteItem {
+    /// `entity`, `recent` for a recently edited entity, or `action`
+    pub kind: String,
+    /// The entity's id, or the action's
+    pub id: String,
+    pub label: String,
+    /// None for actions
+    pub entity_type: Option<String>,
+    /// Higher ranks first
+    pub score: f64,
+}
+
+/// How well `label` matches `query`, ignoring case: exact, prefix, word
+/// prefix, substring, then letters in order. None when it doesn't match.
+fn match_score(label: &str, query: &str) -> Option<f64> {
+    let label = label.to_lowercase();
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Some(0.0);
+    }
+    if label == query {
+        Some(1.0)
+    } else if label.starts_with(&query) {
+        Some(0.9)
+    } else if label
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(&query))
+    {
+        Some(0.8)
+    } else if label.contains(&query) {
+        Some(0.6)
+    } else {
+        let mut letters = label.chars();
+        query
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .all(|q| letters.any(|c| c == q))
+            .then_some(0.3)
+    }
+}
+
+/// Entities of the palette's types, most recently edited first, optionally
+/// only those whose name contains `query`
+async fn palette_entities(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    query: Option<&str>,
+    limit: u64,
+) -> Result<Vec<(String, String, String)>, AppError> {
+    let selects: Vec<String> = PALETTE_TYPES
+        .iter()
+        .filter_map(|t| table_for(t))
+        .filter_map(|table| {
+            let name_column = table.name_column?;
+            let filter = if query.is_some() {
+                format!(" AND {} LIKE $2 ESCAPE '\\'", name_column)
+            } else {
+                String::new()
+            };
+            Some(format!(
+                "SELECT '{}' AS entity_type, id, {} AS name, {} AS modified \
+                 FROM {} WHERE campaign_id = $1{}",
+                table.entity_type,
+                name_column,
+                table.modified_column(),
+                table.table,
+                filter
+            ))
+        })
+        .collect();
+
+    let mut values: Vec<Value> = vec![campaign_id.into()];
+    if let Some(query) = query {
+        let escaped = query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        values.push(format!("%{}%", escaped).into());
+    }
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "{} ORDER BY modified DESC LIMIT {}",
+                selects.join(" UNION ALL "),
+                limit
+            ),
+            values,
+        ))
+        .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok((
+                row.try_get("", "entity_type")?,
+                row.try_get("", "id")?,
+                row.try_get::<Option<String>>("", "name")?
+                    .unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Everything the command palette lists for `query`: with no query, recent
+/// entities then actions; otherwise matching entities and actions, ranked by
+/// how well they match with recently edited entities nudged up
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_command_palette_items_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    query: String,
+) -> Result<Vec<PaletteItem>, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    let query = query.trim();
+
+    let recent = palette_entities(db, &campaign_id, None, RECENT_ITEMS).await?;
+    let mut items: Vec<PaletteItem> = Vec::new();
+    if query.is_empty() {
+        // Keep recent items in edit order, ahead of the actions
+        let count = recent.len() as f64;
+        items.extend(
+            recent
+                .into_iter()
+                .enumerate()
+                .map(|(i, (entity_type, id, name))| PaletteItem {
+                    kind: "recent".to_string(),
+                    id,
+                    label: name,
+                    entity_type: Some(entity_type),
+                    score: 1.0 + (count - i as f64) / count,
+                }),
+        );
+    } else {
+        let recent_ids: HashSet<String> = recent.into_iter().map(|(_, id, _)| id).collect();
+        let matches = palette_entities(db, &campaign_id, Some(query), MAX_CANDIDATES).await?;
+        items.extend(matches.into_iter().filter_map(|(entity_type, id, name)| {
+            let score = match_score(&name, query)?;
+            let is_recent = recent_ids.contains(&id);
+            Some(PaletteItem {
+                kind: if is_recent { "recent" } else { "entity" }.to_string(),
+                id,
+                label: name,
+                entity_type: Some(entity_type),
+                score: if is_recent {
+                    score + RECENT_BOOST
+                } else {
+                    score
+                },
+            })
+        }));
+    }
+
+    items.extend(PALETTE_ACTIONS.iter().filter_map(|(id, label, words)| {
+        let score =
+            match_score(label, query).or_else(|| match_score(words, query).map(|s| s / 2.0))?;
+        Some(PaletteItem {
+            kind: "action".to_string(),
+            id: (*id).to_string(),
+            label: (*label).to_string(),
+            entity_type: None,
+            score,
+        })
+    }));
+
+    // Stable, so equal scores keep entities ahead of actions
+    items.sort_by(|a, b| b.score.total_cmp(&a.score));
+    items.truncate(PALETTE_LIMIT);
+    Ok(items)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_command_palette_items(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    query: String,
+) -> Result<Vec<PaletteItem>, AppError> {
+    get_command_palette_items_impl(&state.db, campaign_id, query).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_score_ranks_closer_matches_higher() {
+        let scores: Vec<_> = [
+            "Bree",
+            "Breeland",
+            "The Bree Gate",
+            "Ambree",
+            "Barrow East Gate",
+        ]
+        .iter()
+        .map(|label| match_score(label, "bree"))
+        .collect();
+        assert_eq!(
+            scores,
+            vec![Some(1.0), Some(0.9), Some(0.8), Some(0.6), Some(0.3)]
+        );
+        assert_eq!(match_score("Rivendell", "bree"), None);
+        assert_eq!(match_score("Rivendell", "  "), Some(0.0));
+    }
+}
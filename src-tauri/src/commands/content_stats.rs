@@ -0,0 +1,153 @@
+//! Word counts over a campaign's written content, for tracking how much
+//! worldbuilding has been done. Counting happens here so the text itself
+//! never has to cross to the frontend.
+
+use crate::commands::registry::ENTITY_TABLES;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::text::plain_text;
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityWordCount {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: Option<String>,
+    pub word_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeWordCount {
+    pub entity_type: String,
+    pub entity_count: u64,
+    pub word_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentStats {
+    pub campaign_id: String,
+    pub total_words: u64,
+    /// One entry per entity type with free text, most words first
+    pub by_type: Vec<TypeWordCount>,
+    /// Every entity with free text, most words first
+    pub entities: Vec<EntityWordCount>,
+}
+
+/// Words in a free-text field, rich text or plain. Tokens without a letter
+/// or digit (dashes, bullets) don't count.
+pub(crate) fn word_count(text: &str) -> u64 {
+    plain_text(text)
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .count() as u64
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Word counts for every entity in a campaign, with totals per type
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_content_stats_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<ContentStats, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let backend = db.get_database_backend();
+    let mut by_type = Vec::new();
+    let mut entities = Vec::new();
+
+    for table in ENTITY_TABLES.iter().filter(|t| !t.text_columns.is_empty()) {
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                backend,
+                format!(
+                    "SELECT id, {} AS display_name, {} FROM {} WHERE campaign_id = $1",
+                    table.name_column.unwrap_or("NULL"),
+                    table.text_columns.join(", "),
+                    table.table
+                ),
+                [campaign_id.clone().into()],
+            ))
+            .await?;
+
+        let mut type_count = TypeWordCount {
+            entity_type: table.entity_type.to_string(),
+            entity_count: 0,
+            word_count: 0,
+        };
+        for row in rows {
+            let mut words = 0;
+            for column in table.text_columns {
+                if let Some(text) = row.try_get::<Option<String>>("", column)? {
+                    words += word_count(&text);
+                }
+            }
+            type_count.entity_count += 1;
+            type_count.word_count += words;
+            entities.push(EntityWordCount {
+                entity_type: table.entity_type.to_string(),
+                entity_id: row.try_get("", "id")?,
+                name: row.try_get("", "display_name")?,
+                word_count: words,
+            });
+        }
+        if type_count.entity_count > 0 {
+            by_type.push(type_count);
+        }
+    }
+
+    by_type.sort_by(|a, b| {
+        b.word_count
+            .cmp(&a.word_count)
+            .then_with(|| a.entity_type.cmp(&b.entity_type))
+    });
+    entities.sort_by(|a, b| {
+        b.word_count
+            .cmp(&a.word_count)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(ContentStats {
+        campaign_id,
+        total_words: by_type.iter().map(|t| t.word_count).sum(),
+        by_type,
+        entities,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_content_stats(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<ContentStats, AppError> {
+    get_content_stats_impl(&state.db, campaign_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_count() {
+        // Whitespace-separated tokens with a letter or digit: the lone dash
+        // is dropped, "abandoned." keeps its period and still counts
+        assert_eq!(word_count("The old lighthouse - long abandoned."), 5);
+        assert_eq!(word_count("   "), 0);
+
+        let doc = r#"{"type":"doc","content":[
+            {"type":"paragraph","content":[{"type":"text","text":"Ruled by "},
+                {"type":"mention","attrs":{"label":"Queen Ysolde"}}]},
+            {"type":"paragraph","content":[{"type":"text","text":"since the war"}]}
+        ]}"#;
+        assert_eq!(word_count(doc), 7);
+    }
+}
@@ -0,0 +1,425 @@
+//! Lines and veils agreed at session zero. A line is content that stays out
+//! of the game entirely; a veil may happen, but off-screen. Each warning has
+//! a topic and optional keywords, and the checker scans the campaign's
+//! written content for them so the GM can find prep that crosses one.
+
+use crate::commands::character::{parse_string_list, string_list_to_json};
+use crate::commands::registry::{table_for, EntityTable, ENTITY_TABLES};
+use crate::commands::validation::{
+    CreateContentWarningInput, UpdateContentWarningInput, CONTENT_WARNING_SEVERITIES,
+};
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::text::plain_text;
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::content_warnings::{self, Entity as ContentWarning};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+/// Characters of context kept either side of a match
+const EXCERPT_CONTEXT: usize = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentWarningResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub topic: String,
+    /// One of `CONTENT_WARNING_SEVERITIES`
+    pub severity: String,
+    pub keywords: Vec<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<content_warnings::Model> for ContentWarningResponse {
+    fn from(model: content_warnings::Model) -> Self {
+        Self {
+            keywords: parse_string_list(model.keywords_json.as_deref()),
+            id: model.id,
+            campaign_id: model.campaign_id,
+            topic: model.topic,
+            severity: model.severity,
+            notes: model.notes,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+/// A field that touches a line or veil
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentFlag {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub entity_name: Option<String>,
+    pub field: String,
+    pub warning_id: String,
+    pub topic: String,
+    pub severity: String,
+    /// The term found: the topic or one of its keywords
+    pub matched: String,
+    /// Plain text around the match
+    pub excerpt: String,
+}
+
+/// Trimmed keywords without blanks or repeats
+fn clean_keywords(keywords: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for keyword in keywords {
+        let keyword = keyword.trim();
+        if !keyword.is_empty() && !cleaned.iter().any(|k| k.eq_ignore_ascii_case(keyword)) {
+            cleaned.push(keyword.to_string());
+        }
+    }
+    cleaned
+}
+
+/// Lowercase, one char for one char, so indexes line up with the original
+fn fold(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// Char index of the first whole-word match of `term` in `text`, both
+/// folded. A plural ending still counts, so "spider" finds "spiders".
+fn find_term(text: &[char], term: &[char]) -> Option<usize> {
+    if term.is_empty() || term.len() > text.len() {
+        return None;
+    }
+    (0..=text.len() - term.len()).find(|&at| {
+        if text[at..at + term.len()] != *term || (at > 0 && text[at - 1].is_alphanumeric()) {
+            return false;
+        }
+        let tail: String = text[at + term.len()..]
+            .iter()
+            .take_while(|c| c.is_alphanumeric())
+            .collect();
+        matches!(tail.as_str(), "" | "s" | "es")
+    })
+}
+
+/// Up to `EXCERPT_CONTEXT` chars either side of `len` chars at `at`
+fn excerpt(text: &[char], at: usize, len: usize) -> String {
+    let start = at.saturating_sub(EXCERPT_CONTEXT);
+    let end = (at + len + EXCERPT_CONTEXT).min(text.len());
+    let middle: String = text[start..end].iter().collect();
+    let middle = middle.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        middle,
+        if end < text.len() { "…" } else { "" }
+    )
+}
+
+/// A warning's topic and keywords, ready to match
+struct WarningTerms {
+    warning: content_warnings::Model,
+    terms: Vec<(String, Vec<char>)>,
+}
+
+impl WarningTerms {
+    fn new(warning: content_warnings::Model) -> Self {
+        let mut words = vec![warning.topic.clone()];
+        words.extend(parse_string_list(warning.keywords_json.as_deref()));
+        let terms = clean_keywords(words)
+            .into_iter()
+            .map(|word| {
+                let folded = fold(&word);
+                (word, folded)
+            })
+            .collect();
+        Self { warning, terms }
+    }
+}
+
+/// Flags for one table: a field is flagged once per warning it touches
+async fn check_table(
+    db: &DatabaseConnection,
+    table: &EntityTable,
+    campaign_id: &str,
+    warnings: &[WarningTerms],
+) -> Result<Vec<ContentFlag>, AppError> {
+    let mut columns: Vec<&str> = table.name_column.into_iter().collect();
+    columns.extend(table.text_columns);
+    if columns.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "SELECT id, {} AS display_name, {} FROM {} WHERE campaign_id = $1",
+                table.name_column.unwrap_or("NULL"),
+                columns.join(", "),
+                table.table
+            ),
+            [campaign_id.into()],
+        ))
+        .await?;
+
+    let mut flags = Vec::new();
+    for row in rows {
+        let id: String = row.try_get("", "id")?;
+        let name: Option<String> = row.try_get("", "display_name")?;
+        for &column in &columns {
+            let Some(text) = row.try_get::<Option<String>>("", column)? else {
+                continue;
+            };
+            let text = plain_text(&text);
+            let folded = fold(&text);
+            let text: Vec<char> = text.chars().collect();
+            for WarningTerms { warning, terms } in warnings {
+                let found = terms
+                    .iter()
+                    .find_map(|(word, term)| find_term(&folded, term).map(|at| (word, at, term)));
+                if let Some((word, at, term)) = found {
+                    flags.push(ContentFlag {
+                        entity_type: table.entity_type.to_string(),
+                        entity_id: id.clone(),
+                        entity_name: name.clone(),
+                        field: column.to_string(),
+                        warning_id: warning.id.clone(),
+                        topic: warning.topic.clone(),
+                        severity: warning.severity.clone(),
+                        matched: word.clone(),
+                        excerpt: excerpt(&text, at, term.len()),
+                    });
+                }
+            }
+        }
+    }
+    Ok(flags)
+}
+
+async fn find_warning(
+    db: &DatabaseConnection,
+    id: &str,
+) -> Result<content_warnings::Model, AppError> {
+    ContentWarning::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Content warning {} not found", id)))
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %input.campaign_id), err)]
+pub async fn create_content_warning_impl(
+    db: &DatabaseConnection,
+    input: CreateContentWarningInput,
+) -> Result<ContentWarningResponse, AppError> {
+    input.validate()?;
+    let topic = input.topic.trim().to_string();
+    if topic.is_empty() {
+        return Err(AppError::Validation("topic: must not be blank".to_string()));
+    }
+
+    let now = chrono::Utc::now();
+    let model = content_warnings::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(input.campaign_id),
+        topic: Set(topic),
+        severity: Set(input.severity),
+        keywords_json: Set(string_list_to_json(&clean_keywords(input.keywords))?),
+        notes: Set(input.notes.filter(|n| !n.is_empty())),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    Ok(model.insert(db).await?.into())
+}
+
+/// A campaign's lines then veils, each by topic
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_content_warnings_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<ContentWarningResponse>, AppError> {
+    let mut warnings = ContentWarning::find()
+        .filter(content_warnings::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(content_warnings::Column::Topic)
+        .all(db)
+        .await?;
+    warnings.sort_by_key(|w| {
+        CONTENT_WARNING_SEVERITIES
+            .iter()
+            .position(|s| *s == w.severity)
+    });
+
+    Ok(warnings.into_iter().map(Into::into).collect())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_content_warning_impl(
+    db: &DatabaseConnection,
+    id: String,
+    input: UpdateContentWarningInput,
+) -> Result<ContentWarningResponse, AppError> {
+    input.validate()?;
+    let warning = find_warning(db, &id).await?;
+
+    let mut active: content_warnings::ActiveModel = warning.into();
+    if let Some(topic) = input.topic {
+        let topic = topic.trim().to_string();
+        if topic.is_empty() {
+            return Err(AppError::Validation("topic: must not be blank".to_string()));
+        }
+        active.topic = Set(topic);
+    }
+    if let Some(severity) = input.severity {
+        active.severity = Set(severity);
+    }
+    if let Some(keywords) = input.keywords {
+        active.keywords_json = Set(string_list_to_json(&clean_keywords(keywords))?);
+    }
+    if let Some(notes) = input.notes {
+        active.notes = Set(Some(notes).filter(|n| !n.is_empty()));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    Ok(active.update(db).await?.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_content_warning_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = ContentWarning::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Fields anywhere in the campaign, or in one entity type, that mention a
+/// line or veil by topic or keyword. Lines come first.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn check_content_warnings_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    entity_type: Option<String>,
+) -> Result<Vec<ContentFlag>, AppError> {
+    let tables: Vec<&EntityTable> = match entity_type.as_deref() {
+        Some(entity_type) => vec![table_for(entity_type).ok_or_else(|| {
+            AppError::Validation(format!("entity_type: unknown type {}", entity_type))
+        })?],
+        None => ENTITY_TABLES.iter().collect(),
+    };
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let warnings: Vec<WarningTerms> = ContentWarning::find()
+        .filter(content_warnings::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(WarningTerms::new)
+        .collect();
+    if warnings.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut flags = Vec::new();
+    // The warnings themselves name every topic, and annotations are comments
+    // on content rather than content
+    for table in tables
+        .into_iter()
+        .filter(|t| !matches!(t.entity_type, "content_warning" | "annotation"))
+    {
+        flags.extend(check_table(db, table, &campaign_id, &warnings).await?);
+    }
+    flags.sort_by(|a, b| {
+        let rank = |f: &ContentFlag| {
+            CONTENT_WARNING_SEVERITIES
+                .iter()
+                .position(|s| *s == f.severity)
+        };
+        rank(a)
+            .cmp(&rank(b))
+            .then_with(|| a.entity_type.cmp(&b.entity_type))
+            .then_with(|| a.entity_name.cmp(&b.entity_name))
+            .then_with(|| a.entity_id.cmp(&b.entity_id))
+    });
+    Ok(flags)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_content_warning(
+    state: State<'_, AppState>,
+    input: CreateContentWarningInput,
+) -> Result<ContentWarningResponse, AppError> {
+    create_content_warning_impl(&state.db, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_content_warnings(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<ContentWarningResponse>, AppError> {
+    list_content_warnings_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_content_warning(
+    state: State<'_, AppState>,
+    id: String,
+    input: UpdateContentWarningInput,
+) -> Result<ContentWarningResponse, AppError> {
+    update_content_warning_impl(&state.db, id, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_content_warning(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    delete_content_warning_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_content_warnings(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    entity_type: Option<String>,
+) -> Result<Vec<ContentFlag>, AppError> {
+    check_content_warnings_impl(&state.db, campaign_id, entity_type).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(text: &str, term: &str) -> Option<usize> {
+        find_term(&fold(text), &fold(term))
+    }
+
+    #[test]
+    fn test_find_term_whole_words() {
+        assert_eq!(find("Giant Spiders nest here", "spider"), Some(6));
+        assert_eq!(find("The torches flicker", "torch"), Some(4));
+        assert_eq!(find("He'd rather not", "rat"), None);
+        assert_eq!(find("A spidery scrawl", "spider"), None);
+        assert_eq!(find("Body horror ahead", "body horror"), Some(0));
+    }
+
+    #[test]
+    fn test_excerpt_trims_long_text() {
+        let text: Vec<char> = format!("{} spiders {}", "a ".repeat(50), "b ".repeat(50))
+            .chars()
+            .collect();
+        let at = find_term(&text, &fold("spiders")).expect("no match");
+        let snippet = excerpt(&text, at, 7);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("spiders"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Converting NPCs into player characters and back. The new row takes over
+//! everything that points at the source; the source is then deleted, or kept
+//! as an archived copy.
+
+use crate::commands::character::{get_character_impl, CharacterResponse};
+use crate::commands::hero::HeroResponse;
+use crate::commands::references::remove_references;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::annotations::{self, Entity as Annotation};
+use ::entity::attachments::{self, Entity as Attachment};
+use ::entity::characters::{self, Entity as Character};
+use ::entity::entity_tags::{self, Entity as EntityTag};
+use ::entity::heroes::{self, Entity as Hero};
+use ::entity::progress_clocks::{self, Entity as ProgressClock};
+use ::entity::recall_cards::{self, Entity as RecallCard};
+use ::entity::relationships::{self, Entity as Relationship};
+use ::entity::secrets::{self, Entity as Secret};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use tauri::State;
+use tracing::instrument;
+
+/// Point every relationship, tag, attachment, recall card, annotation, secret
+/// and progress clock at `(from_type, from_id)` to `(to_type, to_id)` instead
+pub(crate) async fn move_references<C: ConnectionTrait>(
+    conn: &C,
+    from_type: &str,
+    from_id: &str,
+    to_type: &str,
+    to_id: &str,
+) -> Result<(), AppError> {
+    let now = chrono::Utc::now();
+
+    Relationship::update_many()
+        .col_expr(relationships::Column::SourceType, Expr::value(to_type))
+        .col_expr(relationships::Column::SourceId, Expr::value(to_id))
+        .col_expr(relationships::Column::UpdatedAt, Expr::value(now))
+        .filter(relationships::Column::SourceType.eq(from_type))
+        .filter(relationships::Column::SourceId.eq(from_id))
+        .exec(conn)
+        .await?;
+    Relationship::update_many()
+        .col_expr(relationships::Column::TargetType, Expr::value(to_type))
+        .col_expr(relationships::Column::TargetId, Expr::value(to_id))
+        .col_expr(relationships::Column::UpdatedAt, Expr::value(now))
+        .filter(relationships::Column::TargetType.eq(from_type))
+        .filter(relationships::Column::TargetId.eq(from_id))
+        .exec(conn)
+        .await?;
+
+    // Sync keys entity tags by the entity, so a moved tag goes out as new
+    EntityTag::update_many()
+        .col_expr(entity_tags::Column::EntityType, Expr::value(to_type))
+        .col_expr(entity_tags::Column::EntityId, Expr::value(to_id))
+        .col_expr(entity_tags::Column::CreatedAt, Expr::value(now))
+        .filter(entity_tags::Column::EntityType.eq(from_type))
+        .filter(entity_tags::Column::EntityId.eq(from_id))
+        .exec(conn)
+        .await?;
+
+    Secret::update_many()
+        .col_expr(secrets::Column::RelatedEntityType, Expr::value(to_type))
+        .col_expr(secrets::Column::RelatedEntityId, Expr::value(to_id))
+        .col_expr(secrets::Column::UpdatedAt, Expr::value(now))
+        .filter(secrets::Column::RelatedEntityType.eq(from_type))
+        .filter(secrets::Column::RelatedEntityId.eq(from_id))
+        .exec(conn)
+        .await?;
+
+    Attachment::update_many()
+        .col_expr(attachments::Column::EntityType, Expr::value(to_type))
+        .col_expr(attachments::Column::EntityId, Expr::value(to_id))
+        .filter(attachments::Column::EntityType.eq(from_type))
+        .filter(attachments::Column::EntityId.eq(from_id))
+        .exec(conn)
+        .await?;
+
+    RecallCard::update_many()
+        .col_expr(recall_cards::Column::EntityType, Expr::value(to_type))
+        .col_expr(recall_cards::Column::EntityId, Expr::value(to_id))
+        .col_expr(recall_cards::Column::UpdatedAt, Expr::value(now))
+        .filter(recall_cards::Column::EntityType.eq(from_type))
+        .filter(recall_cards::Column::EntityId.eq(from_id))
+        .exec(conn)
+        .await?;
+
+    Annotation::update_many()
+        .col_expr(annotations::Column::EntityType, Expr::value(to_type))
+        .col_expr(annotations::Column::EntityId, Expr::value(to_id))
+        .col_expr(annotations::Column::UpdatedAt, Expr::value(now))
+        .filter(annotations::Column::EntityType.eq(from_type))
+        .filter(annotations::Column::EntityId.eq(from_id))
+        .exec(conn)
+        .await?;
+
+    ProgressClock::update_many()
+        .col_expr(progress_clocks::Column::EntityType, Expr::value(to_type))
+        .col_expr(progress_clocks::Column::EntityId, Expr::value(to_id))
+        .col_expr(progress_clocks::Column::UpdatedAt, Expr::value(now))
+        .filter(progress_clocks::Column::EntityType.eq(from_type))
+        .filter(progress_clocks::Column::EntityId.eq(from_id))
+        .exec(conn)
+        .await?;
+
+    Ok(())
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Turn an NPC into a hero. With `keep_source` the character stays as an
+/// archived copy; otherwise it is deleted.
+#[instrument(skip_all, fields(character_id = %character_id), err)]
+pub async fn convert_character_to_hero_impl(
+    db: &DatabaseConnection,
+    character_id: String,
+    player_id: Option<String>,
+    keep_source: bool,
+) -> Result<HeroResponse, AppError> {
+    let character = Character::find_by_id(&character_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", character_id)))?;
+
+    let txn = db.begin().await?;
+    let now = chrono::Utc::now();
+
+    let hero = heroes::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(character.campaign_id.clone()),
+        player_id: Set(player_id),
+        name: Set(character.name.clone()),
+        lineage: Set(character.lineage.clone()),
+        classes: Set(None),
+        description: Set(character.description.clone()),
+        backstory: Set(None),
+        goals: Set(character.motivations.clone()),
+        bonds: Set(None),
+        is_active: Set(true),
+        source_character_id: Set(Some(character.id.clone())),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(&txn)
+    .await?;
+
+    move_references(&txn, "character", &character.id, "hero", &hero.id).await?;
+
+    if !keep_source {
+        remove_references(&txn, "character", &character.id).await?;
+        Character::delete_by_id(&character.id).exec(&txn).await?;
+    }
+
+    txn.commit().await?;
+    Ok(hero.into())
+}
+
+/// Turn a hero into an NPC, e.g. when a player leaves the table. With
+/// `keep_source` the hero is kept but marked inactive; otherwise it is
+/// deleted.
+#[instrument(skip_all, fields(hero_id = %hero_id), err)]
+pub async fn convert_hero_to_character_impl(
+    db: &DatabaseConnection,
+    hero_id: String,
+    keep_source: bool,
+) -> Result<CharacterResponse, AppError> {
+    let hero = Hero::find_by_id(&hero_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Hero {} not found", hero_id)))?;
+
+    let txn = db.begin().await?;
+    let now = chrono::Utc::now();
+
+    let character = characters::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(hero.campaign_id.clone()),
+        name: Set(hero.name.clone()),
+        lineage: Set(hero.lineage.clone()),
+        occupation: Set(None),
+        is_alive: Set(true),
+        description: Set(hero.description.clone()),
+        personality: Set(None),
+        motivations: Set(hero.goals.clone()),
+        secrets: Set(None),
+        voice_notes: Set(None),
+        stat_block_json: Set(None),
+        birth_year: Set(None),
+        birth_month: Set(None),
+        birth_day: Set(None),
+        death_year: Set(None),
+        death_month: Set(None),
+        death_day: Set(None),
+        death_event_id: Set(None),
+        aliases_json: Set(None),
+        source_hero_id: Set(Some(hero.id.clone())),
+        color: Set(None),
+        icon: Set(None),
+        accent: Set(None),
+        mannerisms_json: Set(None),
+        catchphrases_json: Set(None),
+        speech_sample: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(&txn)
+    .await?;
+
+    move_references(&txn, "hero", &hero.id, "character", &character.id).await?;
+
+    if keep_source {
+        let mut active: heroes::ActiveModel = hero.into();
+        active.is_active = Set(false);
+        active.updated_at = Set(now);
+        active.update(&txn).await?;
+    } else {
+        remove_references(&txn, "hero", &hero.id).await?;
+        Hero::delete_by_id(&hero.id).exec(&txn).await?;
+    }
+
+    txn.commit().await?;
+    // Re-read so the response carries the campaign calendar's age
+    get_character_impl(db, character.id).await
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn convert_character_to_hero(
+    state: State<'_, AppState>,
+    character_id: String,
+    player_id: Option<String>,
+    keep_source: Option<bool>,
+) -> Result<HeroResponse, AppError> {
+    convert_character_to_hero_impl(
+        &state.db,
+        character_id,
+        player_id,
+        keep_source.unwrap_or(false),
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn convert_hero_to_character(
+    state: State<'_, AppState>,
+    hero_id: String,
+    keep_source: Option<bool>,
+) -> Result<CharacterResponse, AppError> {
+    convert_hero_to_character_impl(&state.db, hero_id, keep_source.unwrap_or(false)).await
+}
@@ -0,0 +1,542 @@
+//! What a delete would take with it, so the UI can say so before the user
+//! confirms. Nothing here writes; the report mirrors the foreign keys in the
+//! migrations and the references `remove_references` clears, plus mentions
+//! in text, which deletes leave pointing at nothing.
+
+use crate::commands::entity_refs::count_entities_impl;
+use crate::commands::registry::{table_for, ENTITY_TABLES};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+use tracing::instrument;
+
+/// What happens to a row whose foreign key points at the deleted entity
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OnDelete {
+    Cascade,
+    SetNull,
+}
+
+/// A table holding a foreign key to another entity type
+struct Dependent {
+    parent: &'static str,
+    child: &'static str,
+    table: &'static str,
+    column: &'static str,
+    on_delete: OnDelete,
+}
+
+const DEPENDENTS: &[Dependent] = &[
+    Dependent {
+        parent: "location",
+        child: "location",
+        table: "locations",
+        column: "parent_id",
+        on_delete: OnDelete::SetNull,
+    },
+    Dependent {
+        parent: "organization",
+        child: "location",
+        table: "locations",
+        column: "dominant_organization_id",
+        on_delete: OnDelete::SetNull,
+    },
+    Dependent {
+        parent: "character",
+        child: "disposition",
+        table: "dispositions",
+        column: "character_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "hero",
+        child: "hero_item",
+        table: "hero_items",
+        column: "hero_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "item",
+        child: "hero_item",
+        table: "hero_items",
+        column: "item_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "organization",
+        child: "organization_holding",
+        table: "organization_holdings",
+        column: "organization_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "organization",
+        child: "organization_goal",
+        table: "organization_goals",
+        column: "organization_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "location",
+        child: "organization_holding",
+        table: "organization_holdings",
+        column: "location_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "item",
+        child: "organization_holding",
+        table: "organization_holdings",
+        column: "item_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "character",
+        child: "whereabouts",
+        table: "whereabouts",
+        column: "character_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "location",
+        child: "whereabouts",
+        table: "whereabouts",
+        column: "location_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "location",
+        child: "encounter_table",
+        table: "encounter_tables",
+        column: "location_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "location",
+        child: "encounter_roll",
+        table: "encounter_rolls",
+        column: "location_id",
+        on_delete: OnDelete::SetNull,
+    },
+    Dependent {
+        parent: "location",
+        child: "price_modifier",
+        table: "price_modifiers",
+        column: "location_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "item",
+        child: "price",
+        table: "price_list",
+        column: "item_id",
+        on_delete: OnDelete::SetNull,
+    },
+    Dependent {
+        parent: "character",
+        child: "encounter_entry",
+        table: "encounter_entries",
+        column: "character_id",
+        on_delete: OnDelete::SetNull,
+    },
+    Dependent {
+        parent: "player",
+        child: "hero",
+        table: "heroes",
+        column: "player_id",
+        on_delete: OnDelete::SetNull,
+    },
+    Dependent {
+        parent: "player",
+        child: "session_absence",
+        table: "session_absences",
+        column: "player_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "player",
+        child: "handout_delivery",
+        table: "handout_deliveries",
+        column: "player_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "session",
+        child: "session_absence",
+        table: "session_absences",
+        column: "session_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "session",
+        child: "session_transcript",
+        table: "session_transcripts",
+        column: "session_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "session",
+        child: "disposition",
+        table: "dispositions",
+        column: "session_id",
+        on_delete: OnDelete::SetNull,
+    },
+    Dependent {
+        parent: "session",
+        child: "strength_change",
+        table: "strength_changes",
+        column: "session_id",
+        on_delete: OnDelete::SetNull,
+    },
+    Dependent {
+        parent: "session",
+        child: "encounter_roll",
+        table: "encounter_rolls",
+        column: "session_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "secret",
+        child: "handout",
+        table: "handouts",
+        column: "secret_id",
+        on_delete: OnDelete::SetNull,
+    },
+    Dependent {
+        parent: "handout",
+        child: "handout_delivery",
+        table: "handout_deliveries",
+        column: "handout_id",
+        on_delete: OnDelete::Cascade,
+    },
+    Dependent {
+        parent: "tag",
+        child: "entity_tag",
+        table: "entity_tags",
+        column: "tag_id",
+        on_delete: OnDelete::Cascade,
+    },
+];
+
+/// Local tables that go with a campaign but aren't entity types
+const CAMPAIGN_LOCAL_TABLES: &[(&str, &str)] = &[
+    ("ai_conversation", "ai_conversations"),
+    ("attachment", "attachments"),
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AffectedEntity {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeletePreview {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: Option<String>,
+    /// Rows deleted along with it, by type: a campaign's whole contents, a
+    /// hero's inventory, a session's transcripts
+    pub cascaded: BTreeMap<String, u64>,
+    /// Rows that stay but lose their link to it, such as child locations
+    /// moving to the top level
+    pub detached: Vec<AffectedEntity>,
+    /// Relationships from or to it, deleted with it
+    pub relationships: u64,
+    /// Tags applied to it, removed with it
+    pub tags: u64,
+    /// Secrets about it, which are kept but unlinked
+    pub secrets: u64,
+    /// Entities whose text mentions it; those links will point nowhere
+    pub mentions: Vec<AffectedEntity>,
+    /// Full-text search rows removed with it
+    pub search_rows: u64,
+}
+
+async fn count<C: ConnectionTrait>(
+    conn: &C,
+    sql: &str,
+    values: Vec<Value>,
+) -> Result<u64, AppError> {
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            conn.get_database_backend(),
+            sql,
+            values,
+        ))
+        .await?
+        .ok_or_else(|| AppError::Internal("COUNT returned no row".to_string()))?;
+    let count: i64 = row.try_get("", "count")?;
+    Ok(count as u64)
+}
+
+async fn preview_campaign_delete(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<DeletePreview, AppError> {
+    let campaign = Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let mut cascaded: BTreeMap<String, u64> = count_entities_impl(db, campaign_id.clone())
+        .await?
+        .counts
+        .into_iter()
+        .filter(|(_, n)| *n > 0)
+        .collect();
+    for &(entity_type, table) in CAMPAIGN_LOCAL_TABLES {
+        let n = count(
+            db,
+            &format!("SELECT COUNT(*) AS count FROM {table} WHERE campaign_id = $1"),
+            vec![campaign_id.clone().into()],
+        )
+        .await?;
+        if n > 0 {
+            cascaded.insert(entity_type.to_string(), n);
+        }
+    }
+
+    let tags = count(
+        db,
+        "SELECT COUNT(*) AS count FROM entity_tags \
+         WHERE tag_id IN (SELECT id FROM tags WHERE campaign_id = $1)",
+        vec![campaign_id.clone().into()],
+    )
+    .await?;
+    let search_rows = count(
+        db,
+        "SELECT COUNT(*) AS count FROM search_index WHERE campaign_id = $1",
+        vec![campaign_id.clone().into()],
+    )
+    .await?;
+
+    Ok(DeletePreview {
+        entity_type: "campaign".to_string(),
+        entity_id: campaign_id,
+        name: Some(campaign.name),
+        relationships: cascaded.get("relationship").copied().unwrap_or(0),
+        secrets: cascaded.get("secret").copied().unwrap_or(0),
+        tags,
+        cascaded,
+        detached: Vec::new(),
+        mentions: Vec::new(),
+        search_rows,
+    })
+}
+
+/// Entities in the campaign whose free text contains `entity_id`. Ids are
+/// UUIDs, so a substring match only finds mentions.
+async fn find_mentions(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    entity_id: &str,
+) -> Result<Vec<AffectedEntity>, AppError> {
+    let mut mentions = Vec::new();
+    for table in ENTITY_TABLES {
+        let mut columns: Vec<&str> = table.text_columns.to_vec();
+        // Secrets also list who knows them by id
+        if table.table == "secrets" {
+            columns.push("known_by");
+        }
+        if columns.is_empty() {
+            continue;
+        }
+        let condition = columns
+            .iter()
+            .map(|c| format!("instr({c}, $2) > 0"))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        let name = table.name_column.unwrap_or("NULL");
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!(
+                    "SELECT id, {name} AS name FROM {} \
+                     WHERE campaign_id = $1 AND id != $2 AND ({condition})",
+                    table.table
+                ),
+                [campaign_id.into(), entity_id.into()],
+            ))
+            .await?;
+        for row in rows {
+            mentions.push(AffectedEntity {
+                entity_type: table.entity_type.to_string(),
+                entity_id: row.try_get("", "id")?,
+                name: row.try_get("", "name")?,
+            });
+        }
+    }
+    Ok(mentions)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Everything deleting one entity (or a whole campaign, with
+/// `entity_type` "campaign") would remove or leave dangling
+#[instrument(skip_all, fields(entity_type = %entity_type, id = %id), err)]
+pub async fn preview_delete_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    id: String,
+) -> Result<DeletePreview, AppError> {
+    if entity_type == "campaign" {
+        return preview_campaign_delete(db, id).await;
+    }
+    let table = table_for(&entity_type).ok_or_else(|| {
+        AppError::Validation(format!("entity_type: unknown entity type {}", entity_type))
+    })?;
+
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "SELECT campaign_id, {} AS name FROM {} WHERE id = $1",
+                table.name_column.unwrap_or("NULL"),
+                table.table
+            ),
+            [id.clone().into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} {} not found", entity_type, id)))?;
+    let campaign_id: String = row.try_get("", "campaign_id")?;
+    let name: Option<String> = row.try_get("", "name")?;
+
+    let mut cascaded = BTreeMap::new();
+    let mut detached = Vec::new();
+    for dependent in DEPENDENTS.iter().filter(|d| d.parent == entity_type) {
+        let (child_table, column) = (dependent.table, dependent.column);
+        if dependent.on_delete == OnDelete::Cascade {
+            let n = count(
+                db,
+                &format!("SELECT COUNT(*) AS count FROM {child_table} WHERE {column} = $1"),
+                vec![id.clone().into()],
+            )
+            .await?;
+            if n > 0 {
+                *cascaded.entry(dependent.child.to_string()).or_default() += n;
+            }
+            continue;
+        }
+
+        let name_column = table_for(dependent.child)
+            .and_then(|t| t.name_column)
+            .unwrap_or("NULL");
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!(
+                    "SELECT id, {name_column} AS name FROM {child_table} \
+                     WHERE {column} = $1 ORDER BY name, id"
+                ),
+                [id.clone().into()],
+            ))
+            .await?;
+        for row in rows {
+            detached.push(AffectedEntity {
+                entity_type: dependent.child.to_string(),
+                entity_id: row.try_get("", "id")?,
+                name: row.try_get("", "name")?,
+            });
+        }
+    }
+
+    // Clocks tracking it stay, unattached
+    let clocks = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "SELECT id, name FROM progress_clocks \
+             WHERE entity_type = $1 AND entity_id = $2 ORDER BY name, id",
+            [entity_type.clone().into(), id.clone().into()],
+        ))
+        .await?;
+    for row in clocks {
+        detached.push(AffectedEntity {
+            entity_type: "progress_clock".to_string(),
+            entity_id: row.try_get("", "id")?,
+            name: row.try_get("", "name")?,
+        });
+    }
+
+    // Recall cards and annotations on it go too, though they only point at it
+    // by (type, id)
+    let recall_cards = count(
+        db,
+        "SELECT COUNT(*) AS count FROM recall_cards WHERE entity_type = $1 AND entity_id = $2",
+        vec![entity_type.clone().into(), id.clone().into()],
+    )
+    .await?;
+    if recall_cards > 0 {
+        *cascaded.entry("recall_card".to_string()).or_default() += recall_cards;
+    }
+    let annotations = count(
+        db,
+        "SELECT COUNT(*) AS count FROM annotations WHERE entity_type = $1 AND entity_id = $2",
+        vec![entity_type.clone().into(), id.clone().into()],
+    )
+    .await?;
+    if annotations > 0 {
+        *cascaded.entry("annotation".to_string()).or_default() += annotations;
+    }
+
+    let relationships = count(
+        db,
+        "SELECT COUNT(*) AS count FROM relationships \
+         WHERE (source_type = $1 AND source_id = $2) OR (target_type = $1 AND target_id = $2)",
+        vec![entity_type.clone().into(), id.clone().into()],
+    )
+    .await?;
+    let tags = count(
+        db,
+        "SELECT COUNT(*) AS count FROM entity_tags WHERE entity_type = $1 AND entity_id = $2",
+        vec![entity_type.clone().into(), id.clone().into()],
+    )
+    .await?;
+    let secrets = count(
+        db,
+        "SELECT COUNT(*) AS count FROM secrets \
+         WHERE related_entity_type = $1 AND related_entity_id = $2",
+        vec![entity_type.clone().into(), id.clone().into()],
+    )
+    .await?;
+    let search_rows = if table.searchable {
+        count(
+            db,
+            "SELECT COUNT(*) AS count FROM search_index WHERE entity_type = $1 AND entity_id = $2",
+            vec![entity_type.clone().into(), id.clone().into()],
+        )
+        .await?
+    } else {
+        0
+    };
+    let mentions = find_mentions(db, &campaign_id, &id).await?;
+
+    Ok(DeletePreview {
+        entity_type,
+        entity_id: id,
+        name,
+        cascaded,
+        detached,
+        relationships,
+        tags,
+        secrets,
+        mentions,
+        search_rows,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn preview_delete(
+    state: State<'_, AppState>,
+    entity_type: String,
+    id: String,
+) -> Result<DeletePreview, AppError> {
+    preview_delete_impl(&state.db, entity_type, id).await
+}
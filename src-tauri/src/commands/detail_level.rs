@@ -0,0 +1,345 @@
+//! How fleshed out an entity is, from 0 (a stub with just a name) to 5
+//! (fully detailed). Locations once stored this as `detail_level`, but
+//! nothing kept it up to date and the column was dropped; it is now derived
+//! from the entity's written content, so it can't drift.
+//!
+//! | Level | Label          | Words in free-text fields                     |
+//! |-------|----------------|-----------------------------------------------|
+//! | 0     | stub           | none                                          |
+//! | 1     | sketched       | under 25                                      |
+//! | 2     | outlined       | under 75                                      |
+//! | 3     | described      | under 200                                     |
+//! | 4     | developed      | 200 or more                                   |
+//! | 5     | fully detailed | 500 or more, with at least half the fields    |
+
+use crate::commands::content_stats::word_count;
+use crate::commands::registry::{table_for, EntityTable, ENTITY_TABLES};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::relationships::{self, Entity as Relationship};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+use tracing::instrument;
+
+/// Entity types that get a detail level
+pub const DETAIL_LEVEL_TYPES: &[&str] = &["character", "location", "organization"];
+
+/// Label for each level, indexed by level
+pub const DETAIL_LEVEL_LABELS: &[&str] = &[
+    "stub",
+    "sketched",
+    "outlined",
+    "described",
+    "developed",
+    "fully detailed",
+];
+
+pub const MAX_DETAIL_LEVEL: u8 = 5;
+
+/// Stub report defaults: entities at level 1 or below, linked from at
+/// least two others
+const DEFAULT_STUB_MAX_LEVEL: u8 = 1;
+const DEFAULT_MIN_REFERENCES: u32 = 2;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityDetail {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: Option<String>,
+    /// 0 (stub) to 5 (fully detailed)
+    pub detail_level: u8,
+    /// One of `DETAIL_LEVEL_LABELS`
+    pub detail_label: String,
+    pub word_count: u64,
+    /// Free-text fields with anything in them, out of `total_fields`
+    pub filled_fields: u32,
+    pub total_fields: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StubEntry {
+    #[serde(flatten)]
+    pub detail: EntityDetail,
+    /// Distinct entities that link to this one by relationship or mention
+    pub reference_count: u32,
+}
+
+/// Level for an entity with `words` words across `filled` of its `total`
+/// free-text fields
+pub fn detail_level(words: u64, filled: u32, total: u32) -> u8 {
+    match words {
+        0 => 0,
+        1..=24 => 1,
+        25..=74 => 2,
+        75..=199 => 3,
+        200..=499 => 4,
+        _ if filled * 2 >= total => MAX_DETAIL_LEVEL,
+        _ => 4,
+    }
+}
+
+fn detail_table(entity_type: &str) -> Result<&'static EntityTable, AppError> {
+    if !DETAIL_LEVEL_TYPES.contains(&entity_type) {
+        return Err(AppError::Validation(format!(
+            "entity_type: must be one of: {}",
+            DETAIL_LEVEL_TYPES.join(", ")
+        )));
+    }
+    Ok(table_for(entity_type).expect("detail level types are registered"))
+}
+
+async fn ensure_campaign(db: &DatabaseConnection, campaign_id: &str) -> Result<(), AppError> {
+    Campaign::find_by_id(campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    Ok(())
+}
+
+/// Detail of every entity of one type in a campaign, unsorted
+async fn table_details(
+    db: &DatabaseConnection,
+    table: &EntityTable,
+    campaign_id: &str,
+) -> Result<Vec<EntityDetail>, AppError> {
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "SELECT id, {} AS display_name, {} FROM {} WHERE campaign_id = $1",
+                table.name_column.unwrap_or("NULL"),
+                table.text_columns.join(", "),
+                table.table
+            ),
+            [campaign_id.into()],
+        ))
+        .await?;
+
+    let total_fields = table.text_columns.len() as u32;
+    let mut details = Vec::with_capacity(rows.len());
+    for row in rows {
+        let mut words = 0;
+        let mut filled_fields = 0;
+        for column in table.text_columns {
+            if let Some(text) = row.try_get::<Option<String>>("", column)? {
+                let count = word_count(&text);
+                if count > 0 {
+                    words += count;
+                    filled_fields += 1;
+                }
+            }
+        }
+        let level = detail_level(words, filled_fields, total_fields);
+        details.push(EntityDetail {
+            entity_type: table.entity_type.to_string(),
+            entity_id: row.try_get("", "id")?,
+            name: row.try_get("", "display_name")?,
+            detail_level: level,
+            detail_label: DETAIL_LEVEL_LABELS[level as usize].to_string(),
+            word_count: words,
+            filled_fields,
+            total_fields,
+        });
+    }
+    Ok(details)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Entities of one type with their detail level, optionally limited to a
+/// range of levels. Least detailed first unless `descending`.
+#[instrument(skip_all, fields(campaign_id = %campaign_id, entity_type = %entity_type), err)]
+pub async fn list_entities_by_detail_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    entity_type: String,
+    min_level: Option<u8>,
+    max_level: Option<u8>,
+    descending: bool,
+) -> Result<Vec<EntityDetail>, AppError> {
+    let table = detail_table(&entity_type)?;
+    let min_level = min_level.unwrap_or(0);
+    let max_level = max_level.unwrap_or(MAX_DETAIL_LEVEL);
+    if max_level > MAX_DETAIL_LEVEL || min_level > max_level {
+        return Err(AppError::Validation(format!(
+            "max_level: levels must satisfy 0 <= min_level <= max_level <= {}",
+            MAX_DETAIL_LEVEL
+        )));
+    }
+    ensure_campaign(db, &campaign_id).await?;
+
+    let mut details: Vec<EntityDetail> = table_details(db, table, &campaign_id)
+        .await?
+        .into_iter()
+        .filter(|d| (min_level..=max_level).contains(&d.detail_level))
+        .collect();
+    details.sort_by(|a, b| {
+        let order = a
+            .detail_level
+            .cmp(&b.detail_level)
+            .then_with(|| a.word_count.cmp(&b.word_count));
+        let order = if descending { order.reverse() } else { order };
+        order.then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(details)
+}
+
+/// Under-detailed entities that many others point at, most referenced
+/// first: the ones most worth writing up next
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_stub_report_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    max_level: Option<u8>,
+    min_references: Option<u32>,
+) -> Result<Vec<StubEntry>, AppError> {
+    let max_level = max_level.unwrap_or(DEFAULT_STUB_MAX_LEVEL);
+    if max_level > MAX_DETAIL_LEVEL {
+        return Err(AppError::Validation(format!(
+            "max_level: must be at most {}",
+            MAX_DETAIL_LEVEL
+        )));
+    }
+    let min_references = min_references.unwrap_or(DEFAULT_MIN_REFERENCES);
+    ensure_campaign(db, &campaign_id).await?;
+
+    let mut stubs = Vec::new();
+    for entity_type in DETAIL_LEVEL_TYPES {
+        let table = table_for(entity_type).expect("detail level types are registered");
+        stubs.extend(
+            table_details(db, table, &campaign_id)
+                .await?
+                .into_iter()
+                .filter(|d| d.detail_level <= max_level),
+        );
+    }
+    if stubs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    type EntityKey = (String, String);
+    let mut referrers: HashMap<EntityKey, HashSet<EntityKey>> = HashMap::new();
+
+    for r in Relationship::find()
+        .filter(relationships::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+    {
+        let source = (r.source_type, r.source_id);
+        let target = (r.target_type, r.target_id);
+        referrers
+            .entry(target.clone())
+            .or_default()
+            .insert(source.clone());
+        referrers.entry(source).or_default().insert(target);
+    }
+
+    // Ids are UUIDs, so a substring match only finds mentions
+    let stub_ids: Vec<EntityKey> = stubs
+        .iter()
+        .map(|s| (s.entity_type.clone(), s.entity_id.clone()))
+        .collect();
+    for table in ENTITY_TABLES.iter().filter(|t| !t.text_columns.is_empty()) {
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!(
+                    "SELECT id, {} FROM {} WHERE campaign_id = $1",
+                    table.text_columns.join(", "),
+                    table.table
+                ),
+                [campaign_id.clone().into()],
+            ))
+            .await?;
+        for row in rows {
+            let id: String = row.try_get("", "id")?;
+            let mut text = String::new();
+            for column in table.text_columns {
+                if let Some(value) = row.try_get::<Option<String>>("", column)? {
+                    text.push_str(&value);
+                    text.push('\n');
+                }
+            }
+            for stub in stub_ids.iter().filter(|s| s.1 != id) {
+                if text.contains(&stub.1) {
+                    referrers
+                        .entry(stub.clone())
+                        .or_default()
+                        .insert((table.entity_type.to_string(), id.clone()));
+                }
+            }
+        }
+    }
+
+    let mut report: Vec<StubEntry> = stubs
+        .into_iter()
+        .filter_map(|detail| {
+            let key = (detail.entity_type.clone(), detail.entity_id.clone());
+            let reference_count = referrers.get(&key).map_or(0, |r| r.len() as u32);
+            (reference_count >= min_references).then_some(StubEntry {
+                detail,
+                reference_count,
+            })
+        })
+        .collect();
+    report.sort_by(|a, b| {
+        b.reference_count
+            .cmp(&a.reference_count)
+            .then_with(|| a.detail.detail_level.cmp(&b.detail.detail_level))
+            .then_with(|| a.detail.name.cmp(&b.detail.name))
+    });
+    Ok(report)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_entities_by_detail(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    entity_type: String,
+    min_level: Option<u8>,
+    max_level: Option<u8>,
+    descending: Option<bool>,
+) -> Result<Vec<EntityDetail>, AppError> {
+    list_entities_by_detail_impl(
+        &state.db,
+        campaign_id,
+        entity_type,
+        min_level,
+        max_level,
+        descending.unwrap_or(false),
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_stub_report(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    max_level: Option<u8>,
+    min_references: Option<u32>,
+) -> Result<Vec<StubEntry>, AppError> {
+    get_stub_report_impl(&state.db, campaign_id, max_level, min_references).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detail_level() {
+        assert_eq!(detail_level(0, 0, 5), 0);
+        assert_eq!(detail_level(10, 1, 5), 1);
+        assert_eq!(detail_level(60, 2, 5), 2);
+        assert_eq!(detail_level(150, 2, 5), 3);
+        assert_eq!(detail_level(300, 2, 5), 4);
+        // Long but lopsided entries stop short of fully detailed
+        assert_eq!(detail_level(800, 1, 5), 4);
+        assert_eq!(detail_level(800, 3, 5), 5);
+        assert_eq!(DETAIL_LEVEL_LABELS.len(), MAX_DETAIL_LEVEL as usize + 1);
+    }
+}
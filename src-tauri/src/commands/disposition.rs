@@ -0,0 +1,255 @@
+//! How characters feel about the party, and how that changed over time.
+//! Every adjustment is kept as its own row so the history can be replayed;
+//! the newest row is the current attitude. Unlike relationship strength this
+//! is always toward the party as a whole, never between two NPCs.
+
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::characters::{self, Entity as Character};
+use ::entity::dispositions::{self, Entity as Disposition};
+use ::entity::sessions::Entity as Session;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+use tracing::instrument;
+
+/// Most hostile a character can be toward the party
+pub const MIN_DISPOSITION: i32 = -100;
+/// Friendliest a character can be toward the party
+pub const MAX_DISPOSITION: i32 = 100;
+
+/// One entry in a character's disposition history
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DispositionEntry {
+    pub id: String,
+    pub character_id: String,
+    pub session_id: Option<String>,
+    /// Attitude after this adjustment
+    pub value: i32,
+    /// Difference from the entry before it
+    pub change: i32,
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DispositionHistory {
+    pub character_id: String,
+    /// Current attitude; 0 when nothing has been recorded
+    pub current: i32,
+    /// Oldest first
+    pub entries: Vec<DispositionEntry>,
+}
+
+/// A character's current attitude, for campaign-wide overviews
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CharacterDisposition {
+    pub character_id: String,
+    pub character_name: String,
+    pub value: i32,
+    pub updated_at: String,
+}
+
+async fn history_rows(
+    db: &DatabaseConnection,
+    character_id: &str,
+) -> Result<Vec<dispositions::Model>, AppError> {
+    Ok(Disposition::find()
+        .filter(dispositions::Column::CharacterId.eq(character_id))
+        .order_by_asc(dispositions::Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Shift a character's attitude by `change`, clamped to
+/// [`MIN_DISPOSITION`]..=[`MAX_DISPOSITION`], and record why
+#[instrument(skip_all, fields(character_id = %character_id, change), err)]
+pub async fn adjust_disposition_impl(
+    db: &DatabaseConnection,
+    character_id: String,
+    change: i32,
+    reason: Option<String>,
+    session_id: Option<String>,
+) -> Result<DispositionEntry, AppError> {
+    if change == 0 {
+        return Err(AppError::Validation("change: must not be zero".to_string()));
+    }
+
+    let character = Character::find_by_id(&character_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", character_id)))?;
+    if let Some(session_id) = &session_id {
+        let session = Session::find_by_id(session_id)
+            .one(db)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Session {} not found", session_id)))?;
+        if session.campaign_id != character.campaign_id {
+            return Err(AppError::Validation(
+                "session_id: session belongs to a different campaign".to_string(),
+            ));
+        }
+    }
+
+    let previous = history_rows(db, &character_id)
+        .await?
+        .last()
+        .map(|d| d.value)
+        .unwrap_or(0);
+    let value = previous
+        .saturating_add(change)
+        .clamp(MIN_DISPOSITION, MAX_DISPOSITION);
+
+    let now = chrono::Utc::now();
+    let entry = dispositions::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(character.campaign_id),
+        character_id: Set(character_id),
+        session_id: Set(session_id),
+        value: Set(value),
+        reason: Set(reason),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(DispositionEntry {
+        id: entry.id,
+        character_id: entry.character_id,
+        session_id: entry.session_id,
+        value: entry.value,
+        change: entry.value - previous,
+        reason: entry.reason,
+        created_at: entry.created_at.to_string(),
+    })
+}
+
+#[instrument(skip_all, fields(character_id = %character_id), err)]
+pub async fn get_disposition_history_impl(
+    db: &DatabaseConnection,
+    character_id: String,
+) -> Result<DispositionHistory, AppError> {
+    Character::find_by_id(&character_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", character_id)))?;
+
+    let mut previous = 0;
+    let entries: Vec<DispositionEntry> = history_rows(db, &character_id)
+        .await?
+        .into_iter()
+        .map(|d| {
+            let change = d.value - previous;
+            previous = d.value;
+            DispositionEntry {
+                id: d.id,
+                character_id: d.character_id,
+                session_id: d.session_id,
+                value: d.value,
+                change,
+                reason: d.reason,
+                created_at: d.created_at.to_string(),
+            }
+        })
+        .collect();
+
+    Ok(DispositionHistory {
+        character_id,
+        current: previous,
+        entries,
+    })
+}
+
+/// Current attitude of every character that has any history, friendliest first
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_dispositions_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<CharacterDisposition>, AppError> {
+    let rows = Disposition::find()
+        .filter(dispositions::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(dispositions::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    // Later rows overwrite earlier ones, leaving the newest per character
+    let latest: HashMap<String, dispositions::Model> = rows
+        .into_iter()
+        .map(|d| (d.character_id.clone(), d))
+        .collect();
+    let names: HashMap<String, String> = Character::find()
+        .filter(characters::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|c| (c.id, c.name))
+        .collect();
+
+    let mut result: Vec<CharacterDisposition> = latest
+        .into_values()
+        .filter_map(|d| {
+            Some(CharacterDisposition {
+                character_name: names.get(&d.character_id)?.clone(),
+                character_id: d.character_id,
+                value: d.value,
+                updated_at: d.created_at.to_string(),
+            })
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        b.value
+            .cmp(&a.value)
+            .then_with(|| a.character_name.cmp(&b.character_name))
+    });
+    Ok(result)
+}
+
+/// Remove a mistaken entry. Later entries keep their recorded values.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_disposition_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = Disposition::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn adjust_disposition(
+    state: State<'_, AppState>,
+    character_id: String,
+    change: i32,
+    reason: Option<String>,
+    session_id: Option<String>,
+) -> Result<DispositionEntry, AppError> {
+    adjust_disposition_impl(&state.db, character_id, change, reason, session_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_disposition_history(
+    state: State<'_, AppState>,
+    character_id: String,
+) -> Result<DispositionHistory, AppError> {
+    get_disposition_history_impl(&state.db, character_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_dispositions(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<CharacterDisposition>, AppError> {
+    list_dispositions_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_disposition(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    delete_disposition_impl(&state.db, id).await
+}
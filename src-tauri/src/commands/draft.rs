@@ -0,0 +1,221 @@
+//! Autosaved drafts of long text fields. While a field is being edited the
+//! frontend saves its text every few seconds (`autosave` in the campaign
+//! settings) and discards the draft once the entity itself is saved, so a
+//! draft still around when the entity is reopened is work a crash would
+//! otherwise have lost. Drafts stay on this device: they are never synced or
+//! exported.
+
+use crate::commands::registry::{table_for, EntityTable};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::drafts::{self, Entity as Draft};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Longest draft accepted, in characters
+const MAX_DRAFT_CHARS: usize = 200_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DraftResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub field: String,
+    pub content: String,
+    pub updated_at: String,
+    /// True when the entity was saved after the draft was last written, so
+    /// the draft may no longer match what is stored
+    pub stale: bool,
+}
+
+impl DraftResponse {
+    fn new(draft: drafts::Model, stale: bool) -> Self {
+        Self {
+            id: draft.id,
+            campaign_id: draft.campaign_id,
+            entity_type: draft.entity_type,
+            entity_id: draft.entity_id,
+            field: draft.field,
+            content: draft.content,
+            updated_at: draft.updated_at.to_rfc3339(),
+            stale,
+        }
+    }
+}
+
+/// Check that `field` is one of the entity type's text fields and return the
+/// table along with the campaign the entity belongs to
+async fn draft_target(
+    db: &DatabaseConnection,
+    entity_type: &str,
+    entity_id: &str,
+    field: &str,
+) -> Result<(&'static EntityTable, String), AppError> {
+    let table = table_for(entity_type).ok_or_else(|| {
+        AppError::Validation(format!("entity_type: unknown entity type {}", entity_type))
+    })?;
+    if !table.text_columns.contains(&field) {
+        return Err(AppError::Validation(format!(
+            "field: {} has no text field {}",
+            entity_type, field
+        )));
+    }
+
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!("SELECT campaign_id FROM {} WHERE id = $1", table.table),
+            [entity_id.into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} {} not found", entity_type, entity_id)))?;
+    Ok((table, row.try_get("", "campaign_id")?))
+}
+
+async fn find_draft(
+    db: &DatabaseConnection,
+    entity_type: &str,
+    entity_id: &str,
+    field: &str,
+) -> Result<Option<drafts::Model>, AppError> {
+    Ok(Draft::find()
+        .filter(drafts::Column::EntityType.eq(entity_type))
+        .filter(drafts::Column::EntityId.eq(entity_id))
+        .filter(drafts::Column::Field.eq(field))
+        .one(db)
+        .await?)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Save the in-progress text of an entity's field, replacing any earlier
+/// draft of the same field
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn save_draft_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    entity_id: String,
+    field: String,
+    content: String,
+) -> Result<DraftResponse, AppError> {
+    let (_, campaign_id) = draft_target(db, &entity_type, &entity_id, &field).await?;
+    if content.chars().count() > MAX_DRAFT_CHARS {
+        return Err(AppError::Validation(format!(
+            "content: must be at most {} characters",
+            MAX_DRAFT_CHARS
+        )));
+    }
+
+    let now = chrono::Utc::now();
+    let draft = match find_draft(db, &entity_type, &entity_id, &field).await? {
+        Some(existing) => {
+            let mut active: drafts::ActiveModel = existing.into();
+            active.content = Set(content);
+            active.updated_at = Set(now);
+            active.update(db).await?
+        }
+        None => {
+            drafts::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                campaign_id: Set(campaign_id),
+                entity_type: Set(entity_type),
+                entity_id: Set(entity_id),
+                field: Set(field),
+                content: Set(content),
+                created_at: Set(now),
+                updated_at: Set(now),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+
+    Ok(DraftResponse::new(draft, false))
+}
+
+/// The saved draft of an entity's field, if there is one
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn get_draft_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    entity_id: String,
+    field: String,
+) -> Result<Option<DraftResponse>, AppError> {
+    let (table, _) = draft_target(db, &entity_type, &entity_id, &field).await?;
+    let Some(draft) = find_draft(db, &entity_type, &entity_id, &field).await? else {
+        return Ok(None);
+    };
+
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "SELECT {} > $1 AS stale FROM {} WHERE id = $2",
+                table.modified_column(),
+                table.table
+            ),
+            [draft.updated_at.into(), entity_id.into()],
+        ))
+        .await?;
+    let stale = match row {
+        Some(row) => row.try_get("", "stale")?,
+        None => false,
+    };
+
+    Ok(Some(DraftResponse::new(draft, stale)))
+}
+
+/// Drop the draft of an entity's field, typically once the field is saved.
+/// Returns whether there was one. Works for entities that have since been
+/// deleted, so their leftover drafts can be cleared.
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn discard_draft_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    entity_id: String,
+    field: String,
+) -> Result<bool, AppError> {
+    let result = Draft::delete_many()
+        .filter(drafts::Column::EntityType.eq(&entity_type))
+        .filter(drafts::Column::EntityId.eq(&entity_id))
+        .filter(drafts::Column::Field.eq(&field))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn save_draft(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+    field: String,
+    content: String,
+) -> Result<DraftResponse, AppError> {
+    save_draft_impl(&state.db, entity_type, entity_id, field, content).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_draft(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+    field: String,
+) -> Result<Option<DraftResponse>, AppError> {
+    get_draft_impl(&state.db, entity_type, entity_id, field).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn discard_draft(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+    field: String,
+) -> Result<bool, AppError> {
+    discard_draft_impl(&state.db, entity_type, entity_id, field).await
+}
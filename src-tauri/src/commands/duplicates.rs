@@ -0,0 +1,582 @@
+//! Spotting entities that were entered twice under slightly different names,
+//! and folding one into the other. The survivor takes over everything that
+//! points at the duplicate, and its mentions, before the duplicate is deleted.
+
+use crate::commands::conversion::move_references;
+use crate::commands::organization_holding::merge_organization_holdings;
+use crate::commands::references::remove_references;
+use crate::commands::registry::{table_for, EntityTable, ENTITY_TABLES};
+use crate::commands::whereabouts::merge_whereabouts;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::characters::{self, Entity as Character};
+use ::entity::entity_tags::{self, Entity as EntityTag};
+use ::entity::hero_items::{self, Entity as HeroItem};
+use ::entity::heroes::{self, Entity as Hero};
+use ::entity::locations::{self, Entity as Location};
+use ::entity::organization_holdings;
+use ::entity::relationships::{self, Entity as Relationship};
+use ::entity::whereabouts::{self, Entity as Whereabouts};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Names scoring at least this are reported when no threshold is given
+pub const DEFAULT_MIN_SIMILARITY: f64 = 0.85;
+
+/// Entity types that can be checked for duplicates and merged
+pub const MERGEABLE_TYPES: &[&str] = &[
+    "character",
+    "location",
+    "organization",
+    "quest",
+    "hero",
+    "item",
+];
+
+/// Two entities whose names look like the same thing
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateCandidate {
+    pub entity_type: String,
+    pub first_id: String,
+    pub first_name: String,
+    pub second_id: String,
+    pub second_name: String,
+    /// 0.0-1.0, where 1.0 means the normalized names are identical
+    pub similarity: f64,
+    /// Whether the names only differ in case, punctuation or a leading article
+    pub exact: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityMergeReport {
+    pub entity_type: String,
+    /// The entity that was kept
+    pub target_id: String,
+    /// The entity that was folded in and deleted
+    pub source_id: String,
+    /// Text fields rewritten to mention the target instead of the source
+    pub mentions_updated: u64,
+}
+
+/// Normalized forms of a name used for comparison
+struct NameKey {
+    normalized: Vec<char>,
+    /// Words sorted, so "Vance, Mira" matches "Mira Vance"
+    sorted: Vec<char>,
+}
+
+impl NameKey {
+    fn new(name: &str) -> Self {
+        let normalized = normalize_name(name);
+        let mut words: Vec<&str> = normalized.split(' ').collect();
+        words.sort_unstable();
+        Self {
+            normalized: normalized.chars().collect(),
+            sorted: words.join(" ").chars().collect(),
+        }
+    }
+}
+
+/// Lowercase, drop punctuation and a leading article, and collapse
+/// whitespace, so "The Rusty Anchor" and "rusty anchor." compare equal
+fn normalize_name(name: &str) -> String {
+    let cleaned: String = name
+        .to_lowercase()
+        .chars()
+        .filter(|c| *c != '\'' && *c != '\u{2019}')
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let mut words: Vec<&str> = cleaned.split_whitespace().collect();
+    if words.len() > 1 && matches!(words[0], "the" | "a" | "an") {
+        words.remove(0);
+    }
+    words.join(" ")
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+fn ratio(a: &[char], b: &[char]) -> f64 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / longest as f64
+}
+
+/// Edit-distance similarity of two names, ignoring word order
+fn similarity(a: &NameKey, b: &NameKey) -> f64 {
+    ratio(&a.normalized, &b.normalized).max(ratio(&a.sorted, &b.sorted))
+}
+
+/// Similarity of two names as the duplicate finder scores them
+pub(crate) fn name_similarity(a: &str, b: &str) -> f64 {
+    similarity(&NameKey::new(a), &NameKey::new(b))
+}
+
+fn mergeable_table(entity_type: &str) -> Result<&'static EntityTable, AppError> {
+    if !MERGEABLE_TYPES.contains(&entity_type) {
+        return Err(AppError::Validation(format!(
+            "entity_type: must be one of {}",
+            MERGEABLE_TYPES.join(", ")
+        )));
+    }
+    table_for(entity_type)
+        .ok_or_else(|| AppError::Internal(format!("No table registered for {}", entity_type)))
+}
+
+async fn campaign_of<C: ConnectionTrait>(
+    conn: &C,
+    table: &EntityTable,
+    id: &str,
+) -> Result<String, AppError> {
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            conn.get_database_backend(),
+            format!("SELECT campaign_id FROM {} WHERE id = $1", table.table),
+            [id.into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} {} not found", table.entity_type, id)))?;
+    Ok(row.try_get("", "campaign_id")?)
+}
+
+/// Remove relationships the merge turned into self-links or exact copies of
+/// one the survivor already had
+async fn drop_redundant_relationships<C: ConnectionTrait>(
+    conn: &C,
+    entity_type: &str,
+    id: &str,
+) -> Result<(), AppError> {
+    Relationship::delete_many()
+        .filter(relationships::Column::SourceType.eq(entity_type))
+        .filter(relationships::Column::SourceId.eq(id))
+        .filter(relationships::Column::TargetType.eq(entity_type))
+        .filter(relationships::Column::TargetId.eq(id))
+        .exec(conn)
+        .await?;
+
+    conn.execute(Statement::from_sql_and_values(
+        conn.get_database_backend(),
+        r#"
+        DELETE FROM relationships WHERE id IN (
+            SELECT r.id FROM relationships r
+            JOIN relationships o
+                ON o.source_type = r.source_type AND o.source_id = r.source_id
+                AND o.target_type = r.target_type AND o.target_id = r.target_id
+                AND o.relationship_type = r.relationship_type
+                AND o.id < r.id
+            WHERE (r.source_type = $1 AND r.source_id = $2)
+               OR (r.target_type = $1 AND r.target_id = $2)
+        )
+        "#,
+        [entity_type.into(), id.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Repoint inventory rows from `source_id` to `target_id` on `column`,
+/// stacking onto a holding the target already has for the same `other`
+async fn merge_holdings<C: ConnectionTrait>(
+    conn: &C,
+    column: hero_items::Column,
+    other: hero_items::Column,
+    source_id: &str,
+    target_id: &str,
+) -> Result<(), AppError> {
+    let now = chrono::Utc::now();
+
+    for holding in HeroItem::find()
+        .filter(column.eq(source_id))
+        .all(conn)
+        .await?
+    {
+        let existing = HeroItem::find()
+            .filter(column.eq(target_id))
+            .filter(other.eq(holding.get(other)))
+            .one(conn)
+            .await?;
+
+        match existing {
+            Some(existing) => {
+                let quantity = existing.quantity + holding.quantity;
+                let equipped = existing.equipped || holding.equipped;
+                let attuned = existing.attuned || holding.attuned;
+                let mut active: hero_items::ActiveModel = existing.into();
+                active.quantity = Set(quantity);
+                active.equipped = Set(equipped);
+                active.attuned = Set(attuned);
+                active.updated_at = Set(now);
+                active.update(conn).await?;
+                HeroItem::delete_by_id(holding.id).exec(conn).await?;
+            }
+            None => {
+                let mut active: hero_items::ActiveModel = holding.into();
+                active.set(column, target_id.into());
+                active.updated_at = Set(now);
+                active.update(conn).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Move the references only one entity type has (parent locations,
+/// governing organizations, holdings, inventory, whereabouts, conversion
+/// links)
+async fn move_typed_references<C: ConnectionTrait>(
+    conn: &C,
+    entity_type: &str,
+    source_id: &str,
+    target_id: &str,
+) -> Result<(), AppError> {
+    let now = chrono::Utc::now();
+
+    match entity_type {
+        "location" => {
+            let source = Location::find_by_id(source_id)
+                .one(conn)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Location {} not found", source_id)))?;
+            let target = Location::find_by_id(target_id)
+                .one(conn)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Location {} not found", target_id)))?;
+
+            // A target nested in the source moves up to the source's parent
+            if target.parent_id.as_deref() == Some(source_id) {
+                let parent = source.parent_id.filter(|p| p != target_id);
+                let mut active: locations::ActiveModel = target.into();
+                active.parent_id = Set(parent);
+                active.updated_at = Set(now);
+                active.update(conn).await?;
+            }
+
+            Location::update_many()
+                .col_expr(locations::Column::ParentId, Expr::value(target_id))
+                .col_expr(locations::Column::UpdatedAt, Expr::value(now))
+                .filter(locations::Column::ParentId.eq(source_id))
+                .filter(locations::Column::Id.ne(target_id))
+                .exec(conn)
+                .await?;
+            merge_organization_holdings(
+                conn,
+                organization_holdings::Column::LocationId,
+                source_id,
+                target_id,
+            )
+            .await?;
+            Whereabouts::update_many()
+                .col_expr(whereabouts::Column::LocationId, Expr::value(target_id))
+                .col_expr(whereabouts::Column::UpdatedAt, Expr::value(now))
+                .filter(whereabouts::Column::LocationId.eq(source_id))
+                .exec(conn)
+                .await?;
+        }
+        "organization" => {
+            Location::update_many()
+                .col_expr(
+                    locations::Column::DominantOrganizationId,
+                    Expr::value(target_id),
+                )
+                .col_expr(locations::Column::UpdatedAt, Expr::value(now))
+                .filter(locations::Column::DominantOrganizationId.eq(source_id))
+                .exec(conn)
+                .await?;
+            merge_organization_holdings(
+                conn,
+                organization_holdings::Column::OrganizationId,
+                source_id,
+                target_id,
+            )
+            .await?;
+        }
+        "item" => {
+            merge_holdings(
+                conn,
+                hero_items::Column::ItemId,
+                hero_items::Column::HeroId,
+                source_id,
+                target_id,
+            )
+            .await?;
+            merge_organization_holdings(
+                conn,
+                organization_holdings::Column::ItemId,
+                source_id,
+                target_id,
+            )
+            .await?;
+        }
+        "hero" => {
+            merge_holdings(
+                conn,
+                hero_items::Column::HeroId,
+                hero_items::Column::ItemId,
+                source_id,
+                target_id,
+            )
+            .await?;
+            Character::update_many()
+                .col_expr(characters::Column::SourceHeroId, Expr::value(target_id))
+                .col_expr(characters::Column::UpdatedAt, Expr::value(now))
+                .filter(characters::Column::SourceHeroId.eq(source_id))
+                .exec(conn)
+                .await?;
+        }
+        "character" => {
+            Hero::update_many()
+                .col_expr(heroes::Column::SourceCharacterId, Expr::value(target_id))
+                .col_expr(heroes::Column::UpdatedAt, Expr::value(now))
+                .filter(heroes::Column::SourceCharacterId.eq(source_id))
+                .exec(conn)
+                .await?;
+            merge_whereabouts(conn, source_id, target_id).await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace the source id with the target id wherever campaign text mentions
+/// it. Ids are UUIDs, so a plain substring replace only hits mentions.
+async fn rewrite_mentions<C: ConnectionTrait>(
+    conn: &C,
+    campaign_id: &str,
+    source_id: &str,
+    target_id: &str,
+) -> Result<u64, AppError> {
+    let backend = conn.get_database_backend();
+    let now = chrono::Utc::now();
+    let mut updated = 0;
+
+    let columns: Vec<(&str, &str)> = ENTITY_TABLES
+        .iter()
+        .flat_map(|t| t.text_columns.iter().map(move |c| (t.table, *c)))
+        // Secrets also list who knows them by id
+        .chain(std::iter::once(("secrets", "known_by")))
+        .collect();
+
+    for (table, column) in columns {
+        let result = conn
+            .execute(Statement::from_sql_and_values(
+                backend,
+                format!(
+                    "UPDATE {table} SET {column} = REPLACE({column}, $1, $2), updated_at = $3 \
+                     WHERE campaign_id = $4 AND instr({column}, $1) > 0"
+                ),
+                [
+                    source_id.into(),
+                    target_id.into(),
+                    now.into(),
+                    campaign_id.into(),
+                ],
+            ))
+            .await?;
+        updated += result.rows_affected();
+    }
+    Ok(updated)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Pairs of entities of one type whose names are identical or nearly so,
+/// most similar first
+#[instrument(skip_all, fields(campaign_id = %campaign_id, entity_type = %entity_type), err)]
+pub async fn find_duplicate_candidates_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    entity_type: String,
+    min_similarity: Option<f64>,
+) -> Result<Vec<DuplicateCandidate>, AppError> {
+    let table = mergeable_table(&entity_type)?;
+    let min_similarity = min_similarity.unwrap_or(DEFAULT_MIN_SIMILARITY);
+    if !(0.0..=1.0).contains(&min_similarity) {
+        return Err(AppError::Validation(
+            "min_similarity: must be between 0 and 1".to_string(),
+        ));
+    }
+    let name_column = table
+        .name_column
+        .ok_or_else(|| AppError::Internal(format!("{} has no name column", table.table)))?;
+
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "SELECT id, {name_column} AS name FROM {} WHERE campaign_id = $1 ORDER BY name, id",
+                table.table
+            ),
+            [campaign_id.into()],
+        ))
+        .await?;
+
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        let id: String = row.try_get("", "id")?;
+        let name: String = row.try_get("", "name")?;
+        let key = NameKey::new(&name);
+        entries.push((id, name, key));
+    }
+
+    let mut candidates = Vec::new();
+    for (i, (first_id, first_name, first_key)) in entries.iter().enumerate() {
+        for (second_id, second_name, second_key) in &entries[i + 1..] {
+            // Edit distance is at least the length difference, so skip pairs
+            // that can't reach the threshold
+            let (a, b) = (first_key.normalized.len(), second_key.normalized.len());
+            let longest = a.max(b).max(1);
+            if 1.0 - a.abs_diff(b) as f64 / (longest as f64) < min_similarity {
+                continue;
+            }
+
+            let exact = first_key.normalized == second_key.normalized;
+            let score = if exact {
+                1.0
+            } else {
+                similarity(first_key, second_key)
+            };
+            if score >= min_similarity {
+                candidates.push(DuplicateCandidate {
+                    entity_type: entity_type.clone(),
+                    first_id: first_id.clone(),
+                    first_name: first_name.clone(),
+                    second_id: second_id.clone(),
+                    second_name: second_name.clone(),
+                    similarity: score,
+                    exact,
+                });
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        b.similarity
+            .partial_cmp(&a.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.first_name.cmp(&b.first_name))
+    });
+    Ok(candidates)
+}
+
+/// Fold `source_id` into `target_id`: everything pointing at the source is
+/// moved to the target, then the source is deleted
+#[instrument(skip_all, fields(entity_type = %entity_type, source_id = %source_id, target_id = %target_id), err)]
+pub async fn merge_entities_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    source_id: String,
+    target_id: String,
+) -> Result<EntityMergeReport, AppError> {
+    let table = mergeable_table(&entity_type)?;
+    if source_id == target_id {
+        return Err(AppError::Validation(
+            "target_id: cannot merge an entity into itself".to_string(),
+        ));
+    }
+
+    let campaign_id = campaign_of(db, table, &source_id).await?;
+    if campaign_of(db, table, &target_id).await? != campaign_id {
+        return Err(AppError::Validation(
+            "target_id: must belong to the same campaign".to_string(),
+        ));
+    }
+
+    let txn = db.begin().await?;
+
+    // Tags both already carry would collide once moved
+    let target_tags: Vec<String> = EntityTag::find()
+        .filter(entity_tags::Column::EntityType.eq(&entity_type))
+        .filter(entity_tags::Column::EntityId.eq(&target_id))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|t| t.tag_id)
+        .collect();
+    EntityTag::delete_many()
+        .filter(entity_tags::Column::EntityType.eq(&entity_type))
+        .filter(entity_tags::Column::EntityId.eq(&source_id))
+        .filter(entity_tags::Column::TagId.is_in(target_tags))
+        .exec(&txn)
+        .await?;
+
+    move_references(&txn, &entity_type, &source_id, &entity_type, &target_id).await?;
+    drop_redundant_relationships(&txn, &entity_type, &target_id).await?;
+    move_typed_references(&txn, &entity_type, &source_id, &target_id).await?;
+    let mentions_updated = rewrite_mentions(&txn, &campaign_id, &source_id, &target_id).await?;
+
+    remove_references(&txn, &entity_type, &source_id).await?;
+    txn.execute(Statement::from_sql_and_values(
+        txn.get_database_backend(),
+        format!("DELETE FROM {} WHERE id = $1", table.table),
+        [source_id.clone().into()],
+    ))
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(EntityMergeReport {
+        entity_type,
+        target_id,
+        source_id,
+        mentions_updated,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn find_duplicate_candidates(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    entity_type: String,
+    min_similarity: Option<f64>,
+) -> Result<Vec<DuplicateCandidate>, AppError> {
+    find_duplicate_candidates_impl(&state.db, campaign_id, entity_type, min_similarity).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn merge_entities(
+    state: State<'_, AppState>,
+    entity_type: String,
+    source_id: String,
+    target_id: String,
+) -> Result<EntityMergeReport, AppError> {
+    merge_entities_impl(&state.db, entity_type, source_id, target_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_ignores_case_punctuation_and_articles() {
+        assert_eq!(normalize_name("The Rusty Anchor"), "rusty anchor");
+        assert_eq!(normalize_name("  rusty   anchor. "), "rusty anchor");
+        assert_eq!(normalize_name("Mira's Rest"), "miras rest");
+        assert_eq!(normalize_name("The"), "the");
+    }
+
+    #[test]
+    fn similarity_ignores_word_order() {
+        let a = NameKey::new("Mira Vance");
+        let b = NameKey::new("Vance, Mira");
+        assert_eq!(similarity(&a, &b), 1.0);
+        assert_eq!(levenshtein(&['a', 'b', 'c'], &['a', 'x', 'c', 'd']), 2);
+    }
+}
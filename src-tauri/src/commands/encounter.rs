@@ -0,0 +1,775 @@
+//! Random encounter tables for regions. A table hangs off a location and
+//! holds weighted entries, each a creature or event that can be limited to
+//! day or night and to a season. Rolling at a location uses the tables of
+//! the nearest place up its parent chain with anything that fits the moment,
+//! so a tavern falls back to its city and the city to the wider region.
+//! Rolls made during play are logged to the session.
+
+use crate::commands::validation::{
+    CreateEncounterEntryInput, UpdateEncounterEntryInput, SEASONS, TIMES_OF_DAY,
+};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::characters::{self, Entity as Character};
+use ::entity::encounter_entries::{self, Entity as EncounterEntry};
+use ::entity::encounter_rolls::{self, Entity as EncounterRoll};
+use ::entity::encounter_tables::{self, Entity as EncounterTable};
+use ::entity::locations::{self, Entity as Location};
+use ::entity::sessions::Entity as Session;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+/// Weight of an entry created without one
+pub const DEFAULT_WEIGHT: i32 = 1;
+/// Most dice a quantity may roll
+const MAX_DICE: u32 = 100;
+/// Most sides a quantity die may have
+const MAX_SIDES: u32 = 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EncounterEntryResponse {
+    pub id: String,
+    pub table_id: String,
+    pub name: String,
+    /// Character statted for this creature or NPC, if any
+    pub character_id: Option<String>,
+    /// How many turn up, as dice (`2d6`) or a plain count
+    pub quantity: Option<String>,
+    pub weight: i32,
+    /// `day` or `night`; None fits either
+    pub time_of_day: Option<String>,
+    /// None fits every season
+    pub season: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl From<encounter_entries::Model> for EncounterEntryResponse {
+    fn from(model: encounter_entries::Model) -> Self {
+        Self {
+            id: model.id,
+            table_id: model.table_id,
+            name: model.name,
+            character_id: model.character_id,
+            quantity: model.quantity,
+            weight: model.weight,
+            time_of_day: model.time_of_day,
+            season: model.season,
+            notes: model.notes,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EncounterTableResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub location_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Heaviest first
+    pub entries: Vec<EncounterEntryResponse>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// When the party is rolling for. Conditions left out match every entry.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EncounterContext {
+    pub time_of_day: Option<String>,
+    pub season: Option<String>,
+    /// Session to log the roll to
+    pub session_id: Option<String>,
+}
+
+/// A logged roll
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EncounterRollResponse {
+    pub id: String,
+    pub session_id: String,
+    pub location_id: Option<String>,
+    pub table_id: Option<String>,
+    pub entry_id: Option<String>,
+    pub result: String,
+    pub count: Option<i32>,
+    pub time_of_day: Option<String>,
+    pub season: Option<String>,
+    pub created_at: String,
+}
+
+impl From<encounter_rolls::Model> for EncounterRollResponse {
+    fn from(model: encounter_rolls::Model) -> Self {
+        Self {
+            id: model.id,
+            session_id: model.session_id,
+            location_id: model.location_id,
+            table_id: model.table_id,
+            entry_id: model.entry_id,
+            result: model.result,
+            count: model.count,
+            time_of_day: model.time_of_day,
+            season: model.season,
+            created_at: model.created_at.to_string(),
+        }
+    }
+}
+
+/// What `roll_encounter` came up with
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EncounterResult {
+    /// The place whose tables were used, which may be a parent of the one asked about
+    pub location_id: String,
+    pub location_name: String,
+    pub table_id: String,
+    pub table_name: String,
+    pub entry: EncounterEntryResponse,
+    /// How many turned up, if the entry has a quantity
+    pub count: Option<i32>,
+    /// Readable summary, e.g. `Wolves (5)`
+    pub result: String,
+    /// The log entry, when the roll was made for a session
+    pub roll: Option<EncounterRollResponse>,
+}
+
+/// A quantity: `count` dice with `sides` sides plus `bonus`. A plain number
+/// has no dice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Dice {
+    count: u32,
+    sides: u32,
+    bonus: i32,
+}
+
+/// Parse `NdM`, `dM`, either with `+K` or `-K`, or a plain `K`
+fn parse_dice(expr: &str) -> Option<Dice> {
+    let expr: String = expr.chars().filter(|c| !c.is_whitespace()).collect();
+    let expr = expr.to_lowercase();
+    let Some((count, rest)) = expr.split_once('d') else {
+        let bonus = expr.parse::<i32>().ok().filter(|n| *n >= 0)?;
+        return Some(Dice {
+            count: 0,
+            sides: 0,
+            bonus,
+        });
+    };
+    let count = if count.is_empty() {
+        1
+    } else {
+        count.parse::<u32>().ok()?
+    };
+    let (sides, bonus) = match rest.find(['+', '-']) {
+        Some(i) => (&rest[..i], rest[i..].parse::<i32>().ok()?),
+        None => (rest, 0),
+    };
+    let sides = sides.parse::<u32>().ok()?;
+    if !(1..=MAX_DICE).contains(&count) || !(1..=MAX_SIDES).contains(&sides) {
+        return None;
+    }
+    Some(Dice {
+        count,
+        sides,
+        bonus,
+    })
+}
+
+fn validate_quantity(quantity: Option<&str>) -> Result<(), AppError> {
+    match quantity {
+        Some(q) if !q.is_empty() && parse_dice(q).is_none() => Err(AppError::Validation(
+            "quantity: must be dice like 2d6 or 1d4+1, or a number".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Roll the dice, never coming up below zero. `below(n)` returns a number in
+/// `0..n`.
+fn roll_dice(dice: Dice, below: &mut impl FnMut(u64) -> u64) -> i32 {
+    let rolled: i64 = (0..dice.count)
+        .map(|_| below(u64::from(dice.sides)) as i64 + 1)
+        .sum();
+    (rolled + i64::from(dice.bonus)).max(0) as i32
+}
+
+/// Pick an entry with probability proportional to its weight
+fn pick_weighted<'a, T>(
+    entries: &'a [(T, i32)],
+    below: &mut impl FnMut(u64) -> u64,
+) -> Option<&'a T> {
+    let total: u64 = entries.iter().map(|(_, w)| (*w).max(0) as u64).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut point = below(total);
+    for (entry, weight) in entries {
+        let weight = (*weight).max(0) as u64;
+        if point < weight {
+            return Some(entry);
+        }
+        point -= weight;
+    }
+    None
+}
+
+/// Uniform-enough random number in `0..n`, from a v4 UUID's random bits
+fn random_below(n: u64) -> u64 {
+    (uuid::Uuid::new_v4().as_u128() % u128::from(n)) as u64
+}
+
+/// Whether an entry's conditions allow it right now
+fn fits(entry: &encounter_entries::Model, context: &EncounterContext) -> bool {
+    let allows = |condition: &Option<String>, now: &Option<String>| match (condition, now) {
+        (Some(condition), Some(now)) => condition == now,
+        _ => true,
+    };
+    allows(&entry.time_of_day, &context.time_of_day) && allows(&entry.season, &context.season)
+}
+
+fn validate_name(name: &str) -> Result<(), AppError> {
+    if name.trim().is_empty() || name.chars().count() > 200 {
+        return Err(AppError::Validation(
+            "name: must be 1-200 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_context(context: &EncounterContext) -> Result<(), AppError> {
+    if let Some(time) = &context.time_of_day {
+        if !TIMES_OF_DAY.contains(&time.as_str()) {
+            return Err(AppError::Validation(format!(
+                "time_of_day: must be one of: {}",
+                TIMES_OF_DAY.join(", ")
+            )));
+        }
+    }
+    if let Some(season) = &context.season {
+        if !SEASONS.contains(&season.as_str()) {
+            return Err(AppError::Validation(format!(
+                "season: must be one of: {}",
+                SEASONS.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Empty strings clear optional fields
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.is_empty())
+}
+
+async fn find_table(
+    db: &DatabaseConnection,
+    id: &str,
+) -> Result<encounter_tables::Model, AppError> {
+    EncounterTable::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Encounter table {} not found", id)))
+}
+
+async fn find_location(db: &DatabaseConnection, id: &str) -> Result<locations::Model, AppError> {
+    Location::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Location {} not found", id)))
+}
+
+/// The character must be in the table's campaign
+async fn check_character(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    character_id: &str,
+) -> Result<(), AppError> {
+    Character::find_by_id(character_id)
+        .filter(characters::Column::CampaignId.eq(campaign_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Character {} not found in campaign", character_id))
+        })?;
+    Ok(())
+}
+
+async fn entries_for(
+    db: &DatabaseConnection,
+    table_ids: Vec<String>,
+) -> Result<Vec<encounter_entries::Model>, AppError> {
+    Ok(EncounterEntry::find()
+        .filter(encounter_entries::Column::TableId.is_in(table_ids))
+        .order_by_desc(encounter_entries::Column::Weight)
+        .order_by_asc(encounter_entries::Column::Name)
+        .all(db)
+        .await?)
+}
+
+async fn with_entries(
+    db: &DatabaseConnection,
+    tables: Vec<encounter_tables::Model>,
+) -> Result<Vec<EncounterTableResponse>, AppError> {
+    let mut by_table: HashMap<String, Vec<EncounterEntryResponse>> = HashMap::new();
+    for entry in entries_for(db, tables.iter().map(|t| t.id.clone()).collect()).await? {
+        by_table
+            .entry(entry.table_id.clone())
+            .or_default()
+            .push(entry.into());
+    }
+    Ok(tables
+        .into_iter()
+        .map(|table| EncounterTableResponse {
+            entries: by_table.remove(&table.id).unwrap_or_default(),
+            id: table.id,
+            campaign_id: table.campaign_id,
+            location_id: table.location_id,
+            name: table.name,
+            description: table.description,
+            created_at: table.created_at.to_string(),
+            updated_at: table.updated_at.to_string(),
+        })
+        .collect())
+}
+
+async fn table_response(
+    db: &DatabaseConnection,
+    table: encounter_tables::Model,
+) -> Result<EncounterTableResponse, AppError> {
+    let mut responses = with_entries(db, vec![table]).await?;
+    Ok(responses.remove(0))
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Create an empty table for a location, in the location's campaign
+#[instrument(skip_all, fields(location_id = %location_id), err)]
+pub async fn create_encounter_table_impl(
+    db: &DatabaseConnection,
+    location_id: String,
+    name: String,
+    description: Option<String>,
+) -> Result<EncounterTableResponse, AppError> {
+    validate_name(&name)?;
+    let location = find_location(db, &location_id).await?;
+
+    let now = chrono::Utc::now();
+    let model = encounter_tables::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(location.campaign_id),
+        location_id: Set(location_id),
+        name: Set(name),
+        description: Set(description),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    let result = model.insert(db).await?;
+    table_response(db, result).await
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_encounter_table_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<EncounterTableResponse, AppError> {
+    let table = find_table(db, &id).await?;
+    table_response(db, table).await
+}
+
+/// Every table in the campaign by name, or only those for one location
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_encounter_tables_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    location_id: Option<String>,
+) -> Result<Vec<EncounterTableResponse>, AppError> {
+    let mut query =
+        EncounterTable::find().filter(encounter_tables::Column::CampaignId.eq(&campaign_id));
+    if let Some(location_id) = location_id {
+        query = query.filter(encounter_tables::Column::LocationId.eq(location_id));
+    }
+    let tables = query
+        .order_by_asc(encounter_tables::Column::Name)
+        .all(db)
+        .await?;
+
+    with_entries(db, tables).await
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_encounter_table_impl(
+    db: &DatabaseConnection,
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+) -> Result<EncounterTableResponse, AppError> {
+    if let Some(name) = &name {
+        validate_name(name)?;
+    }
+
+    let table = find_table(db, &id).await?;
+    let mut active: encounter_tables::ActiveModel = table.into();
+    if let Some(name) = name {
+        active.name = Set(name);
+    }
+    if let Some(description) = description {
+        active.description = Set(Some(description));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    table_response(db, result).await
+}
+
+/// Delete a table and its entries. Logged rolls keep their result text.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_encounter_table_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = EncounterTable::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+#[instrument(skip_all, fields(table_id = %input.table_id), err)]
+pub async fn create_encounter_entry_impl(
+    db: &DatabaseConnection,
+    input: CreateEncounterEntryInput,
+) -> Result<EncounterEntryResponse, AppError> {
+    input.validate()?;
+    validate_quantity(input.quantity.as_deref())?;
+    let table = find_table(db, &input.table_id).await?;
+    let character_id = non_empty(input.character_id);
+    if let Some(character_id) = &character_id {
+        check_character(db, &table.campaign_id, character_id).await?;
+    }
+
+    let now = chrono::Utc::now();
+    let model = encounter_entries::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(table.campaign_id),
+        table_id: Set(table.id),
+        name: Set(input.name),
+        character_id: Set(character_id),
+        quantity: Set(non_empty(input.quantity)),
+        weight: Set(input.weight.unwrap_or(DEFAULT_WEIGHT)),
+        time_of_day: Set(non_empty(input.time_of_day)),
+        season: Set(non_empty(input.season)),
+        notes: Set(non_empty(input.notes)),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    Ok(model.insert(db).await?.into())
+}
+
+/// Change an entry. Fields left out are kept; empty strings clear them.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_encounter_entry_impl(
+    db: &DatabaseConnection,
+    id: String,
+    input: UpdateEncounterEntryInput,
+) -> Result<EncounterEntryResponse, AppError> {
+    input.validate()?;
+    validate_quantity(input.quantity.as_deref())?;
+    let entry = EncounterEntry::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Encounter entry {} not found", id)))?;
+    if let Some(character_id) = input.character_id.as_deref().filter(|c| !c.is_empty()) {
+        check_character(db, &entry.campaign_id, character_id).await?;
+    }
+
+    let mut active: encounter_entries::ActiveModel = entry.into();
+    if let Some(name) = input.name {
+        active.name = Set(name);
+    }
+    if let Some(character_id) = input.character_id {
+        active.character_id = Set(non_empty(Some(character_id)));
+    }
+    if let Some(quantity) = input.quantity {
+        active.quantity = Set(non_empty(Some(quantity)));
+    }
+    if let Some(weight) = input.weight {
+        active.weight = Set(weight);
+    }
+    if let Some(time_of_day) = input.time_of_day {
+        active.time_of_day = Set(non_empty(Some(time_of_day)));
+    }
+    if let Some(season) = input.season {
+        active.season = Set(non_empty(Some(season)));
+    }
+    if let Some(notes) = input.notes {
+        active.notes = Set(non_empty(Some(notes)));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    Ok(active.update(db).await?.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_encounter_entry_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = EncounterEntry::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Roll for an encounter at a location. The first place up the parent chain
+/// whose tables have entries fitting the context is rolled on; all its
+/// tables share one weighted pool. The result is logged when the context
+/// names a session.
+#[instrument(skip_all, fields(location_id = %location_id), err)]
+pub async fn roll_encounter_impl(
+    db: &DatabaseConnection,
+    location_id: String,
+    context: EncounterContext,
+) -> Result<EncounterResult, AppError> {
+    validate_context(&context)?;
+    let start = find_location(db, &location_id).await?;
+    if let Some(session_id) = &context.session_id {
+        Session::find_by_id(session_id)
+            .filter(::entity::sessions::Column::CampaignId.eq(&start.campaign_id))
+            .one(db)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Session {} not found in campaign", session_id))
+            })?;
+    }
+
+    let mut visited = HashSet::new();
+    let mut current = Some(start.clone());
+    while let Some(location) = current {
+        if !visited.insert(location.id.clone()) {
+            break;
+        }
+        let tables = EncounterTable::find()
+            .filter(encounter_tables::Column::LocationId.eq(&location.id))
+            .all(db)
+            .await?;
+        let pool: Vec<(encounter_entries::Model, i32)> =
+            entries_for(db, tables.iter().map(|t| t.id.clone()).collect())
+                .await?
+                .into_iter()
+                .filter(|e| fits(e, &context))
+                .map(|e| {
+                    let weight = e.weight;
+                    (e, weight)
+                })
+                .collect();
+
+        if let Some(entry) = pick_weighted(&pool, &mut random_below).cloned() {
+            let table = tables
+                .into_iter()
+                .find(|t| t.id == entry.table_id)
+                .ok_or_else(|| AppError::Internal("Encounter entry lost its table".to_string()))?;
+            let count = entry
+                .quantity
+                .as_deref()
+                .and_then(parse_dice)
+                .map(|dice| roll_dice(dice, &mut random_below));
+            let result = match count {
+                Some(count) => format!("{} ({})", entry.name, count),
+                None => entry.name.clone(),
+            };
+
+            let roll = match &context.session_id {
+                Some(session_id) => {
+                    let now = chrono::Utc::now();
+                    let model = encounter_rolls::ActiveModel {
+                        id: Set(uuid::Uuid::new_v4().to_string()),
+                        campaign_id: Set(start.campaign_id.clone()),
+                        session_id: Set(session_id.clone()),
+                        location_id: Set(Some(location.id.clone())),
+                        table_id: Set(Some(table.id.clone())),
+                        entry_id: Set(Some(entry.id.clone())),
+                        result: Set(result.clone()),
+                        count: Set(count),
+                        time_of_day: Set(context.time_of_day.clone()),
+                        season: Set(context.season.clone()),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                        created_by: Set(None),
+                        modified_by: Set(None),
+                    };
+                    Some(model.insert(db).await?.into())
+                }
+                None => None,
+            };
+
+            return Ok(EncounterResult {
+                location_id: location.id,
+                location_name: location.name,
+                table_id: table.id,
+                table_name: table.name,
+                entry: entry.into(),
+                count,
+                result,
+                roll,
+            });
+        }
+
+        current = match location.parent_id {
+            Some(parent_id) => Location::find_by_id(parent_id).one(db).await?,
+            None => None,
+        };
+    }
+
+    Err(AppError::NotFound(format!(
+        "No encounter table fits at {} or the places around it",
+        start.name
+    )))
+}
+
+/// Rolls logged for a session, oldest first
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn list_encounter_rolls_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+) -> Result<Vec<EncounterRollResponse>, AppError> {
+    let rolls = EncounterRoll::find()
+        .filter(encounter_rolls::Column::SessionId.eq(&session_id))
+        .order_by_asc(encounter_rolls::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(rolls.into_iter().map(Into::into).collect())
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_encounter_table(
+    state: State<'_, AppState>,
+    location_id: String,
+    name: String,
+    description: Option<String>,
+) -> Result<EncounterTableResponse, AppError> {
+    create_encounter_table_impl(&state.db, location_id, name, description).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_encounter_table(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<EncounterTableResponse, AppError> {
+    get_encounter_table_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_encounter_tables(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    location_id: Option<String>,
+) -> Result<Vec<EncounterTableResponse>, AppError> {
+    list_encounter_tables_impl(&state.db, campaign_id, location_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_encounter_table(
+    state: State<'_, AppState>,
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+) -> Result<EncounterTableResponse, AppError> {
+    update_encounter_table_impl(&state.db, id, name, description).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_encounter_table(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    delete_encounter_table_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_encounter_entry(
+    state: State<'_, AppState>,
+    input: CreateEncounterEntryInput,
+) -> Result<EncounterEntryResponse, AppError> {
+    create_encounter_entry_impl(&state.db, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_encounter_entry(
+    state: State<'_, AppState>,
+    id: String,
+    input: UpdateEncounterEntryInput,
+) -> Result<EncounterEntryResponse, AppError> {
+    update_encounter_entry_impl(&state.db, id, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_encounter_entry(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    delete_encounter_entry_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn roll_encounter(
+    state: State<'_, AppState>,
+    location_id: String,
+    context: Option<EncounterContext>,
+) -> Result<EncounterResult, AppError> {
+    roll_encounter_impl(&state.db, location_id, context.unwrap_or_default()).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_encounter_rolls(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<EncounterRollResponse>, AppError> {
+    list_encounter_rolls_impl(&state.db, session_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dice() {
+        let dice = |count, sides, bonus| {
+            Some(Dice {
+                count,
+                sides,
+                bonus,
+            })
+        };
+        assert_eq!(parse_dice("2d6"), dice(2, 6, 0));
+        assert_eq!(parse_dice("1d4+1"), dice(1, 4, 1));
+        assert_eq!(parse_dice(" D8 - 2 "), dice(1, 8, -2));
+        assert_eq!(parse_dice("3"), dice(0, 0, 3));
+        assert_eq!(parse_dice("0d6"), None);
+        assert_eq!(parse_dice("2d"), None);
+        assert_eq!(parse_dice("-3"), None);
+        assert_eq!(parse_dice("wolves"), None);
+    }
+
+    #[test]
+    fn test_roll_dice_bounds() {
+        let dice = parse_dice("2d6+1").unwrap();
+        assert_eq!(roll_dice(dice, &mut |_| 0), 3);
+        assert_eq!(roll_dice(dice, &mut |n| n - 1), 13);
+        // Penalties stop at zero
+        assert_eq!(roll_dice(parse_dice("1d4-5").unwrap(), &mut |_| 0), 0);
+        assert_eq!(roll_dice(parse_dice("4").unwrap(), &mut |_| 0), 4);
+    }
+
+    #[test]
+    fn test_pick_weighted() {
+        let entries = [("wolves", 3), ("bandits", 0), ("bear", 1)];
+        assert_eq!(pick_weighted(&entries, &mut |_| 0), Some(&"wolves"));
+        assert_eq!(pick_weighted(&entries, &mut |_| 2), Some(&"wolves"));
+        assert_eq!(pick_weighted(&entries, &mut |_| 3), Some(&"bear"));
+        assert_eq!(pick_weighted(&[("bandits", 0)], &mut |_| 0), None);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::db::{
+    database_path, delete_key_from_keychain, export_encrypted, is_encrypted,
+    load_key_from_keychain, open_database, pending_path, save_key_to_keychain, AppState,
+    ENCRYPTION_SUPPORTED, MIN_PASSPHRASE_LEN,
+};
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EncryptionStatus {
+    /// Whether this build can encrypt databases at all
+    pub supported: bool,
+    pub encrypted: bool,
+    /// False until the passphrase has been entered for an encrypted database
+    pub unlocked: bool,
+    pub key_in_keychain: bool,
+}
+
+fn encryption_status(app: &AppHandle) -> Result<EncryptionStatus, AppError> {
+    let db_path = database_path(app)?;
+    let encrypted = is_encrypted(&db_path)
+        .map_err(|e| AppError::Internal(format!("Failed to read database file: {}", e)))?;
+
+    Ok(EncryptionStatus {
+        supported: ENCRYPTION_SUPPORTED,
+        encrypted,
+        unlocked: app.try_state::<AppState>().is_some(),
+        key_in_keychain: load_key_from_keychain().is_some(),
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_encryption_status(app: AppHandle) -> Result<EncryptionStatus, AppError> {
+    encryption_status(&app)
+}
+
+/// Open an encrypted database with the user's passphrase, optionally saving
+/// it to the OS keychain so later launches unlock automatically
+#[tauri::command(rename_all = "snake_case")]
+pub async fn unlock_database(
+    app: AppHandle,
+    passphrase: String,
+    remember: bool,
+) -> Result<EncryptionStatus, AppError> {
+    if app.try_state::<AppState>().is_none() {
+        let db = open_database(&app, Some(&passphrase)).await?;
+        if remember {
+            save_key_to_keychain(&passphrase)?;
+        }
+        app.manage(AppState { db });
+        tracing::info!("Database unlocked");
+    }
+
+    encryption_status(&app)
+}
+
+/// Encrypt the current plaintext database. The encrypted copy replaces the
+/// original when the app restarts, which happens immediately on success.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn encrypt_database(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    passphrase: String,
+    remember: bool,
+) -> Result<(), AppError> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(AppError::Validation(format!(
+            "passphrase: must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        )));
+    }
+
+    let db_path = database_path(&app)?;
+    if is_encrypted(&db_path)
+        .map_err(|e| AppError::Internal(format!("Failed to read database file: {}", e)))?
+    {
+        return Err(AppError::Conflict(
+            "Database is already encrypted".to_string(),
+        ));
+    }
+
+    export_encrypted(&state.db, &pending_path(&db_path), &passphrase).await?;
+    if remember {
+        save_key_to_keychain(&passphrase)?;
+    }
+
+    tracing::info!("Encrypted database copy written; restarting to switch over");
+    app.restart()
+}
+
+/// Remove the saved passphrase so the next launch asks for it
+#[tauri::command(rename_all = "snake_case")]
+pub async fn forget_database_key() -> Result<bool, AppError> {
+    delete_key_from_keychain()
+}
@@ -0,0 +1,171 @@
+//! Cheap checks over entity references: how many of each type a campaign
+//! has, and whether a batch of (type, id) pairs point at real rows. Neither
+//! loads more than ids, so importers can validate references up front.
+
+use crate::commands::registry::{table_for, ENTITY_TABLES};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tauri::State;
+use tracing::instrument;
+
+/// Most references `entities_exist` checks in one call
+pub const MAX_ENTITY_REFS: usize = 10_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityCounts {
+    pub campaign_id: String,
+    /// Rows per entity type, including types with none
+    pub counts: BTreeMap<String, u64>,
+    pub total: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityExistence {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub exists: bool,
+    /// Campaign the entity belongs to, when it exists
+    pub campaign_id: Option<String>,
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Number of rows of every entity type in a campaign, in one query
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn count_entities_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<EntityCounts, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let sql = ENTITY_TABLES
+        .iter()
+        .map(|t| {
+            format!(
+                "SELECT '{}' AS entity_type, COUNT(*) AS count FROM {} WHERE campaign_id = $1",
+                t.entity_type, t.table
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" UNION ALL ");
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            [campaign_id.clone().into()],
+        ))
+        .await?;
+
+    let mut counts = BTreeMap::new();
+    for row in rows {
+        let entity_type: String = row.try_get("", "entity_type")?;
+        let count: i64 = row.try_get("", "count")?;
+        counts.insert(entity_type, count as u64);
+    }
+
+    Ok(EntityCounts {
+        campaign_id,
+        total: counts.values().sum(),
+        counts,
+    })
+}
+
+/// Whether each (type, id) reference exists, in the order given. With a
+/// `campaign_id`, entities from other campaigns count as missing.
+#[instrument(skip_all, fields(count = refs.len()), err)]
+pub async fn entities_exist_impl(
+    db: &DatabaseConnection,
+    refs: Vec<(String, String)>,
+    campaign_id: Option<String>,
+) -> Result<Vec<EntityExistence>, AppError> {
+    if refs.len() > MAX_ENTITY_REFS {
+        return Err(AppError::Validation(format!(
+            "refs: at most {} references can be checked at once",
+            MAX_ENTITY_REFS
+        )));
+    }
+
+    let mut ids_by_type: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for (entity_type, entity_id) in &refs {
+        if table_for(entity_type).is_none() {
+            return Err(AppError::Validation(format!(
+                "refs: unknown entity type {}",
+                entity_type
+            )));
+        }
+        ids_by_type
+            .entry(entity_type.as_str())
+            .or_default()
+            .insert(entity_id.as_str());
+    }
+
+    let backend = db.get_database_backend();
+    let mut found: HashMap<(String, String), String> = HashMap::new();
+    for (entity_type, ids) in ids_by_type {
+        let table = table_for(entity_type).expect("checked above");
+        let ids: Vec<&str> = ids.into_iter().collect();
+        // Stay well under SQLite's bound parameter limit
+        for chunk in ids.chunks(500) {
+            let placeholders: Vec<String> = (1..=chunk.len()).map(|i| format!("${}", i)).collect();
+            let rows = db
+                .query_all(Statement::from_sql_and_values(
+                    backend,
+                    format!(
+                        "SELECT id, campaign_id FROM {} WHERE id IN ({})",
+                        table.table,
+                        placeholders.join(", ")
+                    ),
+                    chunk.iter().map(|id| (*id).into()),
+                ))
+                .await?;
+            for row in rows {
+                found.insert(
+                    (entity_type.to_string(), row.try_get("", "id")?),
+                    row.try_get("", "campaign_id")?,
+                );
+            }
+        }
+    }
+
+    Ok(refs
+        .into_iter()
+        .map(|(entity_type, entity_id)| {
+            let owner = found
+                .get(&(entity_type.clone(), entity_id.clone()))
+                .filter(|owner| campaign_id.as_ref().is_none_or(|c| c == *owner))
+                .cloned();
+            EntityExistence {
+                exists: owner.is_some(),
+                campaign_id: owner,
+                entity_type,
+                entity_id,
+            }
+        })
+        .collect())
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn count_entities(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<EntityCounts, AppError> {
+    count_entities_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn entities_exist(
+    state: State<'_, AppState>,
+    refs: Vec<(String, String)>,
+    campaign_id: Option<String>,
+) -> Result<Vec<EntityExistence>, AppError> {
+    entities_exist_impl(&state.db, refs, campaign_id).await
+}
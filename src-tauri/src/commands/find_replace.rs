@@ -0,0 +1,362 @@
+//! Campaign-wide find and replace over free-text fields, e.g. after renaming
+//! a city. Rich text is edited node by node so the document structure and
+//! mention ids are never touched.
+
+use crate::commands::registry::{table_for, EntityTable, ENTITY_TABLES};
+use crate::db::AppState;
+use crate::error::AppError;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+use tracing::instrument;
+
+/// Characters of context kept on each side of a match in previews
+const PREVIEW_CONTEXT: usize = 30;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FindReplaceOptions {
+    pub case_sensitive: bool,
+    /// Skip matches that are part of a longer word
+    pub whole_word: bool,
+    /// Also replace in names and titles
+    pub include_names: bool,
+    /// Limit the search to these entity types; every type when empty
+    pub entity_types: Vec<String>,
+    /// Report what would change without writing anything
+    pub dry_run: bool,
+}
+
+/// One field containing the search text
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FieldMatch {
+    pub field: String,
+    pub occurrences: usize,
+    /// Text around the first match, as it reads now
+    pub before: String,
+    /// The same excerpt with the replacement applied
+    pub after: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityMatch {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: Option<String>,
+    pub fields: Vec<FieldMatch>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FindReplaceReport {
+    /// False for a dry run
+    pub applied: bool,
+    pub total_occurrences: usize,
+    pub entities: Vec<EntityMatch>,
+}
+
+struct Matcher<'a> {
+    find: &'a str,
+    case_sensitive: bool,
+    whole_word: bool,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+impl Matcher<'_> {
+    /// Byte length of the match starting at the beginning of `rest`, if any
+    fn match_at(&self, rest: &str) -> Option<usize> {
+        if self.case_sensitive {
+            return rest.starts_with(self.find).then_some(self.find.len());
+        }
+        let mut chars = rest.char_indices();
+        for f in self.find.chars() {
+            let (_, c) = chars.next()?;
+            if !c.to_lowercase().eq(f.to_lowercase()) {
+                return None;
+            }
+        }
+        Some(chars.next().map_or(rest.len(), |(i, _)| i))
+    }
+
+    /// Byte ranges of every non-overlapping match in `text`
+    fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut ranges = Vec::new();
+        let mut pos = 0;
+        while pos < text.len() {
+            if let Some(len) = self.match_at(&text[pos..]) {
+                let end = pos + len;
+                let bounded = !self.whole_word
+                    || (!text[..pos].chars().next_back().is_some_and(is_word_char)
+                        && !text[end..].chars().next().is_some_and(is_word_char));
+                if bounded {
+                    ranges.push((pos, end));
+                    pos = end;
+                    continue;
+                }
+            }
+            pos += text[pos..].chars().next().map_or(1, char::len_utf8);
+        }
+        ranges
+    }
+}
+
+/// Result of replacing within one field
+struct FieldEdit {
+    value: String,
+    occurrences: usize,
+    before: String,
+    after: String,
+}
+
+/// Replace every match in a plain string, with a before/after excerpt of
+/// the first match
+fn replace_text(text: &str, matcher: &Matcher, replace: &str) -> Option<FieldEdit> {
+    let ranges = matcher.find_all(text);
+    let &(first_start, first_end) = ranges.first()?;
+
+    let mut value = String::with_capacity(text.len());
+    let mut last = 0;
+    for &(start, end) in &ranges {
+        value.push_str(&text[last..start]);
+        value.push_str(replace);
+        last = end;
+    }
+    value.push_str(&text[last..]);
+
+    let context_start = text[..first_start]
+        .char_indices()
+        .rev()
+        .nth(PREVIEW_CONTEXT - 1)
+        .map_or(0, |(i, _)| i);
+    let context_end = text[first_end..]
+        .char_indices()
+        .nth(PREVIEW_CONTEXT)
+        .map_or(text.len(), |(i, _)| first_end + i);
+    let prefix = format!(
+        "{}{}",
+        if context_start > 0 { "..." } else { "" },
+        &text[context_start..first_start]
+    );
+    let suffix = format!(
+        "{}{}",
+        &text[first_end..context_end],
+        if context_end < text.len() { "..." } else { "" }
+    );
+
+    Some(FieldEdit {
+        value,
+        occurrences: ranges.len(),
+        before: format!("{}{}{}", prefix, &text[first_start..first_end], suffix),
+        after: format!("{}{}{}", prefix, replace, suffix),
+    })
+}
+
+/// Replace within the text nodes of a ProseMirror document, accumulating
+/// the count and keeping the first excerpt
+fn replace_in_node(
+    node: &mut Value,
+    matcher: &Matcher,
+    replace: &str,
+    occurrences: &mut usize,
+    excerpt: &mut Option<(String, String)>,
+) {
+    if node.get("type").and_then(Value::as_str) == Some("text") {
+        if let Some(text) = node.get("text").and_then(Value::as_str) {
+            if let Some(edit) = replace_text(text, matcher, replace) {
+                *occurrences += edit.occurrences;
+                excerpt.get_or_insert((edit.before, edit.after));
+                node["text"] = Value::String(edit.value);
+            }
+        }
+    }
+    if let Some(children) = node.get_mut("content").and_then(Value::as_array_mut) {
+        for child in children {
+            replace_in_node(child, matcher, replace, occurrences, excerpt);
+        }
+    }
+}
+
+fn edit_field(text: &str, matcher: &Matcher, replace: &str) -> Option<FieldEdit> {
+    if let Ok(mut doc) = serde_json::from_str::<Value>(text) {
+        if doc.get("type").and_then(Value::as_str) == Some("doc") {
+            let mut occurrences = 0;
+            let mut excerpt = None;
+            replace_in_node(&mut doc, matcher, replace, &mut occurrences, &mut excerpt);
+            let (before, after) = excerpt?;
+            return Some(FieldEdit {
+                value: doc.to_string(),
+                occurrences,
+                before,
+                after,
+            });
+        }
+    }
+
+    replace_text(text, matcher, replace)
+}
+
+/// Tables to search, each with the columns to look at
+fn target_tables(
+    options: &FindReplaceOptions,
+) -> Result<Vec<(&'static EntityTable, Vec<&'static str>)>, AppError> {
+    for entity_type in &options.entity_types {
+        if table_for(entity_type).is_none() {
+            return Err(AppError::Validation(format!(
+                "entity_types: unknown entity type '{}'",
+                entity_type
+            )));
+        }
+    }
+
+    Ok(ENTITY_TABLES
+        .iter()
+        .filter(|t| {
+            options.entity_types.is_empty()
+                || options.entity_types.iter().any(|e| e == t.entity_type)
+        })
+        .map(|t| {
+            let mut columns: Vec<&'static str> = Vec::new();
+            if options.include_names {
+                columns.extend(t.name_column);
+            }
+            columns.extend(t.text_columns);
+            (t, columns)
+        })
+        .filter(|(_, columns)| !columns.is_empty())
+        .collect())
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Replace `find` with `replace` across the campaign's text. With
+/// `options.dry_run` only the report of what would change is returned;
+/// otherwise every edit is written in one transaction.
+#[instrument(skip_all, fields(campaign_id = %campaign_id, dry_run = options.dry_run), err)]
+pub async fn find_and_replace_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    find: String,
+    replace: String,
+    options: FindReplaceOptions,
+) -> Result<FindReplaceReport, AppError> {
+    if find.is_empty() {
+        return Err(AppError::Validation("find: must not be empty".to_string()));
+    }
+    let tables = target_tables(&options)?;
+    let matcher = Matcher {
+        find: &find,
+        case_sensitive: options.case_sensitive,
+        whole_word: options.whole_word,
+    };
+
+    let txn = db.begin().await?;
+    let backend = txn.get_database_backend();
+    let now = chrono::Utc::now();
+    let mut entities = Vec::new();
+    let mut total_occurrences = 0;
+
+    for (table, columns) in tables {
+        let rows = txn
+            .query_all(Statement::from_sql_and_values(
+                backend,
+                format!(
+                    "SELECT id, {} AS display_name, {} FROM {} WHERE campaign_id = $1",
+                    table.name_column.unwrap_or("NULL"),
+                    columns.join(", "),
+                    table.table
+                ),
+                [campaign_id.clone().into()],
+            ))
+            .await?;
+
+        for row in rows {
+            let id: String = row.try_get("", "id")?;
+            let name: Option<String> = row.try_get("", "display_name")?;
+
+            let mut fields = Vec::new();
+            let mut assignments = Vec::new();
+            let mut params: Vec<sea_orm::Value> = Vec::new();
+            for &column in &columns {
+                let Some(text) = row.try_get::<Option<String>>("", column)? else {
+                    continue;
+                };
+                let Some(edit) = edit_field(&text, &matcher, &replace) else {
+                    continue;
+                };
+                total_occurrences += edit.occurrences;
+                assignments.push(format!("{} = ${}", column, assignments.len() + 1));
+                params.push(edit.value.into());
+                fields.push(FieldMatch {
+                    field: column.to_string(),
+                    occurrences: edit.occurrences,
+                    before: edit.before,
+                    after: edit.after,
+                });
+            }
+            if fields.is_empty() {
+                continue;
+            }
+
+            if !options.dry_run {
+                if table.has_updated_at {
+                    assignments.push(format!("updated_at = ${}", params.len() + 1));
+                    params.push(now.into());
+                }
+                params.push(id.clone().into());
+                txn.execute(Statement::from_sql_and_values(
+                    backend,
+                    format!(
+                        "UPDATE {} SET {} WHERE id = ${}",
+                        table.table,
+                        assignments.join(", "),
+                        params.len()
+                    ),
+                    params,
+                ))
+                .await?;
+            }
+
+            entities.push(EntityMatch {
+                entity_type: table.entity_type.to_string(),
+                entity_id: id,
+                name,
+                fields,
+            });
+        }
+    }
+
+    if options.dry_run {
+        txn.rollback().await?;
+    } else {
+        txn.commit().await?;
+    }
+
+    Ok(FindReplaceReport {
+        applied: !options.dry_run,
+        total_occurrences,
+        entities,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn find_and_replace(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    find: String,
+    replace: String,
+    options: Option<FindReplaceOptions>,
+) -> Result<FindReplaceReport, AppError> {
+    find_and_replace_impl(
+        &state.db,
+        campaign_id,
+        find,
+        replace,
+        options.unwrap_or_default(),
+    )
+    .await
+}
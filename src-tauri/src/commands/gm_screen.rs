@@ -0,0 +1,358 @@
+//! The GM screen: everything needed at the table for one session in a
+//! single payload, so the condensed view doesn't fire off a query per panel.
+//! Scenes, pinned entities and reminders all come from the session's plan.
+
+use crate::commands::json_schema::SystemStatBlock;
+use crate::commands::progress_clock::{list_active_progress_clocks_impl, ProgressClockResponse};
+use crate::commands::relationship::entity_names;
+use crate::commands::session::SessionResponse;
+use crate::commands::session_clock::{get_session_clock_impl, SessionClockResponse};
+use crate::commands::session_prep::{collect_mentions, document_nodes, plan_nodes, PrepQuest};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::characters::{self, Entity as Character};
+use ::entity::hero_items::{self, Entity as HeroItem};
+use ::entity::heroes::{self, Entity as Hero};
+use ::entity::players::{self, Entity as Player};
+use ::entity::quests::{self, Entity as Quest};
+use ::entity::session_absences::{self, Entity as SessionAbsence};
+use ::entity::sessions::Entity as Session;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tauri::State;
+use tracing::instrument;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GmEntity {
+    pub entity_type: String,
+    pub id: String,
+    pub name: String,
+}
+
+/// A heading in the plan and the entities mentioned under it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GmScene {
+    pub title: String,
+    pub entities: Vec<GmEntity>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PartyMember {
+    pub hero_id: String,
+    pub name: String,
+    pub player_name: Option<String>,
+    pub lineage: Option<String>,
+    pub classes: Option<String>,
+    /// The hero's player is recorded as absent from this session
+    pub absent: bool,
+    /// Distinct items the hero carries
+    pub item_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InitiativeEntry {
+    /// `hero` or `character`
+    pub entity_type: String,
+    pub id: String,
+    pub name: String,
+    /// From the stat block; None for heroes, whose players roll their own
+    pub modifier: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GmScreen {
+    pub session: SessionResponse,
+    /// Headings of the plan, above any generated prep checklist
+    pub scenes: Vec<GmScene>,
+    /// Everything the plan mentions, by type then name
+    pub pinned: Vec<GmEntity>,
+    pub active_quests: Vec<PrepQuest>,
+    /// Active heroes by name
+    pub party: Vec<PartyMember>,
+    /// Suggested turn order: heroes at the table and pinned characters with
+    /// stat blocks, highest modifier first. Nothing is rolled or saved.
+    pub initiative: Vec<InitiativeEntry>,
+    pub clock: SessionClockResponse,
+    /// The campaign's unfilled progress clocks, closest to complete first
+    pub progress_clocks: Vec<ProgressClockResponse>,
+    /// Unticked `[ ]` items from the plan and its prep checklist
+    pub reminders: Vec<String>,
+}
+
+/// Plain text of a node, with citations and mentions as their labels
+fn inline_text(node: &Value) -> String {
+    if let Some(text) = node["text"].as_str() {
+        return text.to_string();
+    }
+    if let Some(label) = node["attrs"]["label"].as_str() {
+        return label.to_string();
+    }
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(|children| children.iter().map(inline_text).collect())
+        .unwrap_or_default()
+}
+
+/// Unticked checklist items anywhere under `node`, in document order
+fn collect_reminders(node: &Value, found: &mut Vec<String>) {
+    if node["type"] == "paragraph" {
+        let text = inline_text(node);
+        let line = text
+            .trim_start()
+            .trim_start_matches(['-', '*'])
+            .trim_start();
+        if let Some(item) = line.strip_prefix("[ ]") {
+            if !item.trim().is_empty() {
+                found.push(item.trim().to_string());
+            }
+        }
+        return;
+    }
+    if let Some(children) = node.get("content").and_then(Value::as_array) {
+        for child in children {
+            collect_reminders(child, found);
+        }
+    }
+}
+
+/// Split the plan at its headings, keeping the mentions in each section.
+/// Anything before the first heading isn't a scene.
+fn split_scenes(plan: &[Value]) -> Vec<(String, BTreeSet<(String, String)>)> {
+    let mut scenes: Vec<(String, BTreeSet<(String, String)>)> = Vec::new();
+    for node in plan {
+        if node["type"] == "heading" {
+            let title = inline_text(node).trim().to_string();
+            if !title.is_empty() {
+                scenes.push((title, BTreeSet::new()));
+                continue;
+            }
+        }
+        if let Some((_, mentions)) = scenes.last_mut() {
+            collect_mentions(node, mentions);
+        }
+    }
+    scenes
+}
+
+/// Initiative modifier a stat block implies: Dexterity for 5e and generic
+/// blocks, Perception for PF2e
+fn initiative_modifier(block: &SystemStatBlock) -> Option<i32> {
+    let dex_modifier = |score: i32| (score - 10).div_euclid(2);
+    match block {
+        SystemStatBlock::Generic(block) => block.abilities.get("dex").copied().map(dex_modifier),
+        SystemStatBlock::Dnd5e(block) => Some(dex_modifier(
+            block.abilities.get("dex").copied().unwrap_or(10),
+        )),
+        SystemStatBlock::Pf2e(block) => block.perception,
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn get_gm_screen_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+) -> Result<GmScreen, AppError> {
+    let session = Session::find_by_id(&session_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} not found", session_id)))?;
+    let campaign_id = session.campaign_id.clone();
+
+    let document = document_nodes(session.planned_content.as_deref());
+    let mut mentioned = BTreeSet::new();
+    let mut reminders = Vec::new();
+    for node in &document {
+        collect_mentions(node, &mut mentioned);
+        collect_reminders(node, &mut reminders);
+    }
+    let types: BTreeSet<&str> = mentioned.iter().map(|(t, _)| t.as_str()).collect();
+    let names = entity_names(db, &campaign_id, &types).await?;
+    // Mentions of deleted entities are dropped
+    let resolve = |mentions: &BTreeSet<(String, String)>| -> Vec<GmEntity> {
+        let mut entities: Vec<GmEntity> = mentions
+            .iter()
+            .filter_map(|key| {
+                names.get(key).map(|name| GmEntity {
+                    entity_type: key.0.clone(),
+                    id: key.1.clone(),
+                    name: name.clone(),
+                })
+            })
+            .collect();
+        entities.sort_by(|a, b| (&a.entity_type, &a.name).cmp(&(&b.entity_type, &b.name)));
+        entities
+    };
+
+    let plan = plan_nodes(session.planned_content.as_deref());
+    let scenes: Vec<GmScene> = split_scenes(&plan)
+        .into_iter()
+        .map(|(title, mentions)| GmScene {
+            title,
+            entities: resolve(&mentions),
+        })
+        .collect();
+    let pinned = resolve(&mentioned);
+
+    let active_quests: Vec<PrepQuest> = Quest::find()
+        .filter(quests::Column::CampaignId.eq(&campaign_id))
+        .filter(quests::Column::Status.eq("active"))
+        .order_by_asc(quests::Column::Name)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|q| PrepQuest {
+            id: q.id,
+            name: q.name,
+            status: q.status,
+        })
+        .collect();
+
+    let heroes = Hero::find()
+        .filter(heroes::Column::CampaignId.eq(&campaign_id))
+        .filter(heroes::Column::IsActive.eq(true))
+        .order_by_asc(heroes::Column::Name)
+        .all(db)
+        .await?;
+    let player_names: HashMap<String, String> = Player::find()
+        .filter(players::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.name))
+        .collect();
+    let absent_players: HashSet<String> = SessionAbsence::find()
+        .filter(session_absences::Column::SessionId.eq(&session_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|a| a.player_id)
+        .collect();
+    let mut item_counts: HashMap<String, usize> = HashMap::new();
+    for hero_item in HeroItem::find()
+        .filter(hero_items::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+    {
+        *item_counts.entry(hero_item.hero_id).or_default() += 1;
+    }
+
+    let mut initiative = Vec::new();
+    let party: Vec<PartyMember> = heroes
+        .into_iter()
+        .map(|hero| {
+            let absent = hero
+                .player_id
+                .as_ref()
+                .is_some_and(|id| absent_players.contains(id));
+            if !absent {
+                initiative.push(InitiativeEntry {
+                    entity_type: "hero".to_string(),
+                    id: hero.id.clone(),
+                    name: hero.name.clone(),
+                    modifier: None,
+                });
+            }
+            PartyMember {
+                player_name: hero
+                    .player_id
+                    .as_ref()
+                    .and_then(|id| player_names.get(id).cloned()),
+                item_count: item_counts.get(&hero.id).copied().unwrap_or(0),
+                hero_id: hero.id,
+                name: hero.name,
+                lineage: hero.lineage,
+                classes: hero.classes,
+                absent,
+            }
+        })
+        .collect();
+
+    let pinned_characters: Vec<&str> = pinned
+        .iter()
+        .filter(|e| e.entity_type == "character")
+        .map(|e| e.id.as_str())
+        .collect();
+    for character in Character::find()
+        .filter(characters::Column::Id.is_in(pinned_characters))
+        .all(db)
+        .await?
+    {
+        // A block that no longer parses just leaves the character out
+        let Some(block) = character
+            .stat_block_json
+            .as_deref()
+            .and_then(|raw| SystemStatBlock::parse(raw).ok())
+        else {
+            continue;
+        };
+        initiative.push(InitiativeEntry {
+            entity_type: "character".to_string(),
+            id: character.id,
+            name: character.name,
+            modifier: initiative_modifier(&block),
+        });
+    }
+    // Highest modifier first; None sorts below any modifier, so last
+    initiative.sort_by(|a, b| {
+        b.modifier
+            .cmp(&a.modifier)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let clock = get_session_clock_impl(db, session_id).await?;
+    let progress_clocks = list_active_progress_clocks_impl(db, campaign_id).await?;
+
+    Ok(GmScreen {
+        session: session.into(),
+        scenes,
+        pinned,
+        active_quests,
+        party,
+        initiative,
+        clock,
+        progress_clocks,
+        reminders,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_gm_screen(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<GmScreen, AppError> {
+    get_gm_screen_impl(&state.db, session_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_collect_reminders_finds_unticked_items() {
+        let doc = json!({
+            "type": "doc",
+            "content": [
+                { "type": "paragraph", "content": [{ "type": "text", "text": "- [ ] Print the map" }] },
+                { "type": "bulletList", "content": [
+                    { "type": "listItem", "content": [{ "type": "paragraph", "content": [
+                        { "type": "text", "text": "[ ] Review " },
+                        { "type": "citation", "attrs": { "entityType": "location", "entityId": "l1", "label": "Duskhollow" } }
+                    ] }] },
+                    { "type": "listItem", "content": [{ "type": "paragraph", "content": [
+                        { "type": "text", "text": "[x] Done already" }
+                    ] }] }
+                ] }
+            ]
+        });
+        let mut found = Vec::new();
+        collect_reminders(&doc, &mut found);
+        assert_eq!(found, vec!["Print the map", "Review Duskhollow"]);
+    }
+}
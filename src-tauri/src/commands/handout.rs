@@ -0,0 +1,456 @@
+//! Handouts given to players (letters, maps, private notes) and which player
+//! has received and read each one.
+
+use crate::commands::references::remove_references;
+use crate::commands::validation::{CreateHandoutInput, UpdateHandoutInput};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::handout_deliveries::{self, Entity as HandoutDelivery};
+use ::entity::handouts::{self, Entity as Handout};
+use ::entity::players::{self, Entity as Player};
+use ::entity::secrets::Entity as Secret;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HandoutResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub title: String,
+    pub content: Option<String>,
+    pub secret_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<handouts::Model> for HandoutResponse {
+    fn from(model: handouts::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            title: model.title,
+            content: model.content,
+            secret_id: model.secret_id,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+/// One player's copy of a handout
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HandoutDeliveryResponse {
+    pub id: String,
+    pub handout_id: String,
+    pub handout_title: String,
+    pub player_id: String,
+    pub player_name: String,
+    /// "sent" or "read"
+    pub status: String,
+    pub sent_at: String,
+    pub read_at: Option<String>,
+}
+
+impl HandoutDeliveryResponse {
+    fn new(
+        delivery: handout_deliveries::Model,
+        handout: &handouts::Model,
+        player: &players::Model,
+    ) -> Self {
+        Self {
+            id: delivery.id,
+            handout_id: delivery.handout_id,
+            handout_title: handout.title.clone(),
+            player_id: delivery.player_id,
+            player_name: player.name.clone(),
+            status: delivery.status,
+            sent_at: delivery.sent_at.to_string(),
+            read_at: delivery.read_at.map(|t| t.to_string()),
+        }
+    }
+}
+
+async fn find_handout(db: &DatabaseConnection, id: &str) -> Result<handouts::Model, AppError> {
+    Handout::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Handout {} not found", id)))
+}
+
+async fn find_campaign_player(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    player_id: &str,
+) -> Result<players::Model, AppError> {
+    let player = Player::find_by_id(player_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Player {} not found", player_id)))?;
+    if player.campaign_id != campaign_id {
+        return Err(AppError::Validation(
+            "player_id: player belongs to a different campaign".to_string(),
+        ));
+    }
+    Ok(player)
+}
+
+async fn check_secret(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    secret_id: &str,
+) -> Result<(), AppError> {
+    let secret = Secret::find_by_id(secret_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Secret {} not found", secret_id)))?;
+    if secret.campaign_id != campaign_id {
+        return Err(AppError::Validation(
+            "secret_id: secret belongs to a different campaign".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, err)]
+pub async fn create_handout_impl(
+    db: &DatabaseConnection,
+    input: CreateHandoutInput,
+) -> Result<HandoutResponse, AppError> {
+    input.validate()?;
+    if let Some(secret_id) = &input.secret_id {
+        check_secret(db, &input.campaign_id, secret_id).await?;
+    }
+
+    let now = chrono::Utc::now();
+    let model = handouts::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(input.campaign_id),
+        title: Set(input.title),
+        content: Set(input.content),
+        secret_id: Set(input.secret_id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    let result = model.insert(db).await?;
+    Ok(result.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_handout_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<HandoutResponse, AppError> {
+    Ok(find_handout(db, &id).await?.into())
+}
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_handouts_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<HandoutResponse>, AppError> {
+    let handouts = Handout::find()
+        .filter(handouts::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(handouts::Column::Title)
+        .all(db)
+        .await?;
+
+    Ok(handouts.into_iter().map(|h| h.into()).collect())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_handout_impl(
+    db: &DatabaseConnection,
+    id: String,
+    input: UpdateHandoutInput,
+) -> Result<HandoutResponse, AppError> {
+    input.validate()?;
+
+    let handout = find_handout(db, &id).await?;
+    if let Some(secret_id) = &input.secret_id {
+        check_secret(db, &handout.campaign_id, secret_id).await?;
+    }
+
+    let mut active: handouts::ActiveModel = handout.into();
+
+    if let Some(t) = input.title {
+        active.title = Set(t);
+    }
+    if let Some(c) = input.content {
+        active.content = Set(Some(c));
+    }
+    if let Some(s) = input.secret_id {
+        active.secret_id = Set(Some(s));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    Ok(result.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_handout_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
+    let txn = db.begin().await?;
+    remove_references(&txn, "handout", &id).await?;
+    let result = Handout::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Mark a handout as sent to each of the given players. Players who already
+/// have it keep their existing delivery, including its read status.
+#[instrument(skip_all, fields(handout_id = %handout_id), err)]
+pub async fn send_handout_impl(
+    db: &DatabaseConnection,
+    handout_id: String,
+    player_ids: Vec<String>,
+) -> Result<Vec<HandoutDeliveryResponse>, AppError> {
+    let handout = find_handout(db, &handout_id).await?;
+
+    let mut players = Vec::with_capacity(player_ids.len());
+    for player_id in &player_ids {
+        players.push(find_campaign_player(db, &handout.campaign_id, player_id).await?);
+    }
+
+    let txn = db.begin().await?;
+    let now = chrono::Utc::now();
+    let mut deliveries = Vec::with_capacity(players.len());
+    for player in players {
+        let existing = HandoutDelivery::find()
+            .filter(handout_deliveries::Column::HandoutId.eq(&handout.id))
+            .filter(handout_deliveries::Column::PlayerId.eq(&player.id))
+            .one(&txn)
+            .await?;
+
+        let delivery = match existing {
+            Some(existing) => existing,
+            None => {
+                handout_deliveries::ActiveModel {
+                    id: Set(uuid::Uuid::new_v4().to_string()),
+                    campaign_id: Set(handout.campaign_id.clone()),
+                    handout_id: Set(handout.id.clone()),
+                    player_id: Set(player.id.clone()),
+                    status: Set("sent".to_string()),
+                    sent_at: Set(now),
+                    read_at: Set(None),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                    created_by: Set(None),
+                    modified_by: Set(None),
+                }
+                .insert(&txn)
+                .await?
+            }
+        };
+        deliveries.push(HandoutDeliveryResponse::new(delivery, &handout, &player));
+    }
+    txn.commit().await?;
+
+    Ok(deliveries)
+}
+
+/// Mark a delivered handout as read by the player
+#[instrument(skip_all, fields(handout_id = %handout_id, player_id = %player_id), err)]
+pub async fn mark_handout_read_impl(
+    db: &DatabaseConnection,
+    handout_id: String,
+    player_id: String,
+) -> Result<HandoutDeliveryResponse, AppError> {
+    let handout = find_handout(db, &handout_id).await?;
+    let player = find_campaign_player(db, &handout.campaign_id, &player_id).await?;
+
+    let delivery = HandoutDelivery::find()
+        .filter(handout_deliveries::Column::HandoutId.eq(&handout_id))
+        .filter(handout_deliveries::Column::PlayerId.eq(&player_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Handout {} was not sent to player {}",
+                handout_id, player_id
+            ))
+        })?;
+
+    let delivery = if delivery.status == "read" {
+        delivery
+    } else {
+        let now = chrono::Utc::now();
+        let mut active: handout_deliveries::ActiveModel = delivery.into();
+        active.status = Set("read".to_string());
+        active.read_at = Set(Some(now));
+        active.updated_at = Set(now);
+        active.update(db).await?
+    };
+
+    Ok(HandoutDeliveryResponse::new(delivery, &handout, &player))
+}
+
+/// Take a handout back from a player, e.g. when it was sent by mistake
+#[instrument(skip_all, fields(handout_id = %handout_id, player_id = %player_id), err)]
+pub async fn revoke_handout_impl(
+    db: &DatabaseConnection,
+    handout_id: String,
+    player_id: String,
+) -> Result<bool, AppError> {
+    let result = HandoutDelivery::delete_many()
+        .filter(handout_deliveries::Column::HandoutId.eq(&handout_id))
+        .filter(handout_deliveries::Column::PlayerId.eq(&player_id))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Who has received a handout, ordered by player name
+#[instrument(skip_all, fields(handout_id = %handout_id), err)]
+pub async fn list_handout_deliveries_impl(
+    db: &DatabaseConnection,
+    handout_id: String,
+) -> Result<Vec<HandoutDeliveryResponse>, AppError> {
+    let handout = find_handout(db, &handout_id).await?;
+
+    let rows = HandoutDelivery::find()
+        .filter(handout_deliveries::Column::HandoutId.eq(&handout_id))
+        .find_also_related(Player)
+        .order_by_asc(players::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(delivery, player)| {
+            Some(HandoutDeliveryResponse::new(delivery, &handout, &player?))
+        })
+        .collect())
+}
+
+/// Every handout a player has received, newest first
+#[instrument(skip_all, fields(player_id = %player_id), err)]
+pub async fn list_player_handouts_impl(
+    db: &DatabaseConnection,
+    player_id: String,
+) -> Result<Vec<HandoutDeliveryResponse>, AppError> {
+    let player = Player::find_by_id(&player_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Player {} not found", player_id)))?;
+
+    let rows = HandoutDelivery::find()
+        .filter(handout_deliveries::Column::PlayerId.eq(&player_id))
+        .find_also_related(Handout)
+        .order_by_desc(handout_deliveries::Column::SentAt)
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(delivery, handout)| {
+            Some(HandoutDeliveryResponse::new(delivery, &handout?, &player))
+        })
+        .collect())
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_handout(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    title: String,
+    content: Option<String>,
+    secret_id: Option<String>,
+) -> Result<HandoutResponse, AppError> {
+    let input = CreateHandoutInput {
+        campaign_id,
+        title,
+        content,
+        secret_id,
+    };
+    create_handout_impl(&state.db, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_handout(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<HandoutResponse, AppError> {
+    get_handout_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_handouts(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<HandoutResponse>, AppError> {
+    list_handouts_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_handout(
+    state: State<'_, AppState>,
+    id: String,
+    title: Option<String>,
+    content: Option<String>,
+    secret_id: Option<String>,
+) -> Result<HandoutResponse, AppError> {
+    let input = UpdateHandoutInput {
+        title,
+        content,
+        secret_id,
+    };
+    update_handout_impl(&state.db, id, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_handout(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    delete_handout_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn send_handout(
+    state: State<'_, AppState>,
+    handout_id: String,
+    player_ids: Vec<String>,
+) -> Result<Vec<HandoutDeliveryResponse>, AppError> {
+    send_handout_impl(&state.db, handout_id, player_ids).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn mark_handout_read(
+    state: State<'_, AppState>,
+    handout_id: String,
+    player_id: String,
+) -> Result<HandoutDeliveryResponse, AppError> {
+    mark_handout_read_impl(&state.db, handout_id, player_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn revoke_handout(
+    state: State<'_, AppState>,
+    handout_id: String,
+    player_id: String,
+) -> Result<bool, AppError> {
+    revoke_handout_impl(&state.db, handout_id, player_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_handout_deliveries(
+    state: State<'_, AppState>,
+    handout_id: String,
+) -> Result<Vec<HandoutDeliveryResponse>, AppError> {
+    list_handout_deliveries_impl(&state.db, handout_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_player_handouts(
+    state: State<'_, AppState>,
+    player_id: String,
+) -> Result<Vec<HandoutDeliveryResponse>, AppError> {
+    list_player_handouts_impl(&state.db, player_id).await
+}
@@ -1,9 +1,11 @@
+use crate::commands::references::remove_references;
 use crate::db::AppState;
 use crate::error::AppError;
 use ::entity::heroes::{self, Entity as Hero};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HeroResponse {
@@ -18,6 +20,8 @@ pub struct HeroResponse {
     pub goals: Option<String>,
     pub bonds: Option<String>,
     pub is_active: bool,
+    /// Character this hero was converted from, if any
+    pub source_character_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -36,6 +40,7 @@ impl From<heroes::Model> for HeroResponse {
             goals: model.goals,
             bonds: model.bonds,
             is_active: model.is_active,
+            source_character_id: model.source_character_id,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
@@ -43,6 +48,7 @@ impl From<heroes::Model> for HeroResponse {
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn create_hero(
     state: State<'_, AppState>,
     campaign_id: String,
@@ -67,8 +73,11 @@ pub async fn create_hero(
         goals: Set(None),
         bonds: Set(None),
         is_active: Set(true),
+        source_character_id: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     let result = model.insert(&state.db).await?;
@@ -76,6 +85,7 @@ pub async fn create_hero(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_hero(state: State<'_, AppState>, id: String) -> Result<HeroResponse, AppError> {
     let hero = Hero::find_by_id(&id)
         .one(&state.db)
@@ -86,6 +96,7 @@ pub async fn get_hero(state: State<'_, AppState>, id: String) -> Result<HeroResp
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn list_heroes(
     state: State<'_, AppState>,
     campaign_id: String,
@@ -100,6 +111,7 @@ pub async fn list_heroes(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn update_hero(
     state: State<'_, AppState>,
     id: String,
@@ -154,7 +166,11 @@ pub async fn update_hero(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_hero(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
-    let result = Hero::delete_by_id(&id).exec(&state.db).await?;
+    let txn = state.db.begin().await?;
+    remove_references(&txn, "hero", &id).await?;
+    let result = Hero::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
     Ok(result.rows_affected > 0)
 }
@@ -0,0 +1,210 @@
+//! The campaign inbox: captured content that hasn't been filed yet, such as
+//! tray quick notes, AI suggestions and stubs from imports. Items wait here
+//! instead of cluttering the entity lists until the GM converts each into an
+//! entity or dismisses it.
+
+use crate::commands::markdown_import::{NewEntity, MARKDOWN_IMPORT_TYPES};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::inbox_items::{self, Entity as InboxItem};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Where inbox items come from
+pub const INBOX_SOURCES: &[&str] = &["quick_note", "capture", "ai_suggestion", "import"];
+
+/// Longest item title accepted, matching entity names
+const MAX_TITLE_CHARS: usize = 200;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InboxItemResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub source: String,
+    pub title: String,
+    pub content: Option<String>,
+    /// The entity type the item most likely becomes, if known
+    pub suggested_type: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<inbox_items::Model> for InboxItemResponse {
+    fn from(model: inbox_items::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            source: model.source,
+            title: model.title,
+            content: model.content,
+            suggested_type: model.suggested_type,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+/// The entity an inbox item became
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InboxConversion {
+    pub entity_type: String,
+    pub entity_id: String,
+}
+
+fn check_entity_type(field: &str, entity_type: &str) -> Result<(), AppError> {
+    if MARKDOWN_IMPORT_TYPES.contains(&entity_type) {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "{}: must be one of: {}",
+            field,
+            MARKDOWN_IMPORT_TYPES.join(", ")
+        )))
+    }
+}
+
+async fn find_item(db: &DatabaseConnection, id: &str) -> Result<inbox_items::Model, AppError> {
+    InboxItem::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Inbox item {} not found", id)))
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id, source = %source), err)]
+pub async fn create_inbox_item_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    source: String,
+    title: String,
+    content: Option<String>,
+    suggested_type: Option<String>,
+) -> Result<InboxItemResponse, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    if !INBOX_SOURCES.contains(&source.as_str()) {
+        return Err(AppError::Validation(format!(
+            "source: must be one of: {}",
+            INBOX_SOURCES.join(", ")
+        )));
+    }
+    let title = title.trim().to_string();
+    if title.is_empty() || title.chars().count() > MAX_TITLE_CHARS {
+        return Err(AppError::Validation(format!(
+            "title: must be 1 to {} characters",
+            MAX_TITLE_CHARS
+        )));
+    }
+    if let Some(suggested_type) = &suggested_type {
+        check_entity_type("suggested_type", suggested_type)?;
+    }
+
+    let now = chrono::Utc::now();
+    let model = inbox_items::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id),
+        source: Set(source),
+        title: Set(title),
+        content: Set(content.filter(|c| !c.trim().is_empty())),
+        suggested_type: Set(suggested_type),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+    Ok(model.insert(db).await?.into())
+}
+
+/// The campaign's inbox, newest first
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_inbox_items_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<InboxItemResponse>, AppError> {
+    let items = InboxItem::find()
+        .filter(inbox_items::Column::CampaignId.eq(&campaign_id))
+        .order_by_desc(inbox_items::Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(items.into_iter().map(InboxItemResponse::from).collect())
+}
+
+/// File an item as an entity named after its title, with its content as
+/// the description, and take it out of the inbox
+#[instrument(skip_all, fields(id = %id, entity_type = %entity_type), err)]
+pub async fn convert_inbox_item_impl(
+    db: &DatabaseConnection,
+    id: String,
+    entity_type: String,
+) -> Result<InboxConversion, AppError> {
+    let item = find_item(db, &id).await?;
+    check_entity_type("entity_type", &entity_type)?;
+
+    let entity = NewEntity::basic(&entity_type, &item.campaign_id, item.title, item.content);
+    entity.validate()?;
+    let entity_id = entity.create(db).await?;
+    InboxItem::delete_by_id(&id).exec(db).await?;
+
+    Ok(InboxConversion {
+        entity_type,
+        entity_id,
+    })
+}
+
+/// Drop an item from the inbox. Returns whether there was one.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn dismiss_inbox_item_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = InboxItem::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_inbox_item(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    source: String,
+    title: String,
+    content: Option<String>,
+    suggested_type: Option<String>,
+) -> Result<InboxItemResponse, AppError> {
+    create_inbox_item_impl(
+        &state.db,
+        campaign_id,
+        source,
+        title,
+        content,
+        suggested_type,
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_inbox_items(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<InboxItemResponse>, AppError> {
+    list_inbox_items_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn convert_inbox_item(
+    state: State<'_, AppState>,
+    id: String,
+    entity_type: String,
+) -> Result<InboxConversion, AppError> {
+    convert_inbox_item_impl(&state.db, id, entity_type).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn dismiss_inbox_item(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    dismiss_inbox_item_impl(&state.db, id).await
+}
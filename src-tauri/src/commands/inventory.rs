@@ -0,0 +1,394 @@
+//! Hero inventories: which items each hero carries, how many, and whether
+//! they are equipped or attuned.
+
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::hero_items::{self, Entity as HeroItem};
+use ::entity::heroes::{self, Entity as Hero};
+use ::entity::items::{self, Entity as Item};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Most items a hero can be attuned to at once (D&D 5e rule)
+pub const MAX_ATTUNED_ITEMS: u64 = 3;
+
+/// One stack of an item in a hero's inventory
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InventoryEntryResponse {
+    pub id: String,
+    pub hero_id: String,
+    pub item_id: String,
+    pub item_name: String,
+    pub item_type: String,
+    pub rarity: Option<String>,
+    pub requires_attunement: bool,
+    pub quantity: i32,
+    pub equipped: bool,
+    pub attuned: bool,
+    pub updated_at: String,
+}
+
+impl InventoryEntryResponse {
+    fn new(holding: hero_items::Model, item: items::Model) -> Self {
+        Self {
+            id: holding.id,
+            hero_id: holding.hero_id,
+            item_id: holding.item_id,
+            item_name: item.name,
+            item_type: item.item_type,
+            rarity: item.rarity,
+            requires_attunement: item.requires_attunement,
+            quantity: holding.quantity,
+            equipped: holding.equipped,
+            attuned: holding.attuned,
+            updated_at: holding.updated_at.to_string(),
+        }
+    }
+}
+
+/// A hero carrying a given item
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemHolderResponse {
+    pub hero_id: String,
+    pub hero_name: String,
+    pub player_id: Option<String>,
+    /// Inactive heroes are included so retired characters' gear isn't lost
+    pub hero_is_active: bool,
+    pub quantity: i32,
+    pub equipped: bool,
+    pub attuned: bool,
+}
+
+async fn find_hero<C: ConnectionTrait>(conn: &C, id: &str) -> Result<heroes::Model, AppError> {
+    Hero::find_by_id(id)
+        .one(conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Hero {} not found", id)))
+}
+
+async fn find_item<C: ConnectionTrait>(conn: &C, id: &str) -> Result<items::Model, AppError> {
+    Item::find_by_id(id)
+        .one(conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Item {} not found", id)))
+}
+
+async fn find_holding<C: ConnectionTrait>(
+    conn: &C,
+    hero_id: &str,
+    item_id: &str,
+) -> Result<Option<hero_items::Model>, AppError> {
+    Ok(HeroItem::find()
+        .filter(hero_items::Column::HeroId.eq(hero_id))
+        .filter(hero_items::Column::ItemId.eq(item_id))
+        .one(conn)
+        .await?)
+}
+
+fn check_quantity(quantity: i32) -> Result<(), AppError> {
+    if quantity < 1 {
+        return Err(AppError::Validation(
+            "quantity: must be at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Add `quantity` of an item to a hero, stacking onto any they already hold
+async fn add_to_inventory<C: ConnectionTrait>(
+    conn: &C,
+    hero: &heroes::Model,
+    item: &items::Model,
+    quantity: i32,
+) -> Result<hero_items::Model, AppError> {
+    if hero.campaign_id != item.campaign_id {
+        return Err(AppError::Validation(
+            "item_id: item belongs to a different campaign".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now();
+    let holding = match find_holding(conn, &hero.id, &item.id).await? {
+        Some(existing) => {
+            let total = existing.quantity + quantity;
+            let mut active: hero_items::ActiveModel = existing.into();
+            active.quantity = Set(total);
+            active.updated_at = Set(now);
+            active.update(conn).await?
+        }
+        None => {
+            hero_items::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                campaign_id: Set(hero.campaign_id.clone()),
+                hero_id: Set(hero.id.clone()),
+                item_id: Set(item.id.clone()),
+                quantity: Set(quantity),
+                equipped: Set(false),
+                attuned: Set(false),
+                created_at: Set(now),
+                updated_at: Set(now),
+                created_by: Set(None),
+                modified_by: Set(None),
+            }
+            .insert(conn)
+            .await?
+        }
+    };
+    Ok(holding)
+}
+
+/// Take `quantity` (or everything) of an item from a hero. Returns the
+/// remaining stack, or `None` once the hero holds none.
+async fn take_from_inventory<C: ConnectionTrait>(
+    conn: &C,
+    hero_id: &str,
+    item_id: &str,
+    quantity: Option<i32>,
+) -> Result<(i32, Option<hero_items::Model>), AppError> {
+    let holding = find_holding(conn, hero_id, item_id).await?.ok_or_else(|| {
+        AppError::NotFound(format!("Hero {} does not hold item {}", hero_id, item_id))
+    })?;
+
+    let taken = quantity.unwrap_or(holding.quantity);
+    check_quantity(taken)?;
+    if taken > holding.quantity {
+        return Err(AppError::Validation(format!(
+            "quantity: hero only holds {}",
+            holding.quantity
+        )));
+    }
+
+    if taken == holding.quantity {
+        HeroItem::delete_by_id(&holding.id).exec(conn).await?;
+        return Ok((taken, None));
+    }
+
+    let remaining = holding.quantity - taken;
+    let mut active: hero_items::ActiveModel = holding.into();
+    active.quantity = Set(remaining);
+    active.updated_at = Set(chrono::Utc::now());
+    Ok((taken, Some(active.update(conn).await?)))
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(hero_id = %hero_id), err)]
+pub async fn get_hero_inventory_impl(
+    db: &DatabaseConnection,
+    hero_id: String,
+) -> Result<Vec<InventoryEntryResponse>, AppError> {
+    find_hero(db, &hero_id).await?;
+
+    let rows = HeroItem::find()
+        .filter(hero_items::Column::HeroId.eq(&hero_id))
+        .find_also_related(Item)
+        .order_by_asc(items::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(holding, item)| Some(InventoryEntryResponse::new(holding, item?)))
+        .collect())
+}
+
+#[instrument(skip_all, fields(hero_id = %hero_id, item_id = %item_id), err)]
+pub async fn grant_item_impl(
+    db: &DatabaseConnection,
+    hero_id: String,
+    item_id: String,
+    quantity: i32,
+) -> Result<InventoryEntryResponse, AppError> {
+    check_quantity(quantity)?;
+    let hero = find_hero(db, &hero_id).await?;
+    let item = find_item(db, &item_id).await?;
+
+    let holding = add_to_inventory(db, &hero, &item, quantity).await?;
+    Ok(InventoryEntryResponse::new(holding, item))
+}
+
+/// Remove `quantity` of an item from a hero, or the whole stack when `None`.
+/// Returns what the hero still holds.
+#[instrument(skip_all, fields(hero_id = %hero_id, item_id = %item_id), err)]
+pub async fn remove_item_impl(
+    db: &DatabaseConnection,
+    hero_id: String,
+    item_id: String,
+    quantity: Option<i32>,
+) -> Result<Option<InventoryEntryResponse>, AppError> {
+    let item = find_item(db, &item_id).await?;
+    let (_, remaining) = take_from_inventory(db, &hero_id, &item_id, quantity).await?;
+    Ok(remaining.map(|holding| InventoryEntryResponse::new(holding, item)))
+}
+
+/// Hand items from one hero to another. The recipient starts with the item
+/// unequipped and unattuned; the giver keeps their flags on whatever is left.
+#[instrument(
+    skip_all,
+    fields(from_hero_id = %from_hero_id, to_hero_id = %to_hero_id, item_id = %item_id),
+    err
+)]
+pub async fn transfer_item_impl(
+    db: &DatabaseConnection,
+    from_hero_id: String,
+    to_hero_id: String,
+    item_id: String,
+    quantity: Option<i32>,
+) -> Result<InventoryEntryResponse, AppError> {
+    if from_hero_id == to_hero_id {
+        return Err(AppError::Validation(
+            "to_hero_id: cannot transfer an item to the same hero".to_string(),
+        ));
+    }
+    let recipient = find_hero(db, &to_hero_id).await?;
+    let item = find_item(db, &item_id).await?;
+
+    let txn = db.begin().await?;
+    let (taken, _) = take_from_inventory(&txn, &from_hero_id, &item_id, quantity).await?;
+    let holding = add_to_inventory(&txn, &recipient, &item, taken).await?;
+    txn.commit().await?;
+
+    Ok(InventoryEntryResponse::new(holding, item))
+}
+
+/// Equip/unequip or attune/unattune a held item. Attuning requires an item
+/// that needs attunement and a free slot.
+#[instrument(skip_all, fields(hero_id = %hero_id, item_id = %item_id), err)]
+pub async fn update_hero_item_impl(
+    db: &DatabaseConnection,
+    hero_id: String,
+    item_id: String,
+    equipped: Option<bool>,
+    attuned: Option<bool>,
+) -> Result<InventoryEntryResponse, AppError> {
+    let item = find_item(db, &item_id).await?;
+    let holding = find_holding(db, &hero_id, &item_id).await?.ok_or_else(|| {
+        AppError::NotFound(format!("Hero {} does not hold item {}", hero_id, item_id))
+    })?;
+
+    if attuned == Some(true) && !holding.attuned {
+        if !item.requires_attunement {
+            return Err(AppError::Validation(format!(
+                "attuned: {} does not require attunement",
+                item.name
+            )));
+        }
+        let attuned_count = HeroItem::find()
+            .filter(hero_items::Column::HeroId.eq(&hero_id))
+            .filter(hero_items::Column::Attuned.eq(true))
+            .count(db)
+            .await?;
+        if attuned_count >= MAX_ATTUNED_ITEMS {
+            return Err(AppError::Validation(format!(
+                "attuned: hero is already attuned to {} items",
+                MAX_ATTUNED_ITEMS
+            )));
+        }
+    }
+
+    let mut active: hero_items::ActiveModel = holding.into();
+    if let Some(e) = equipped {
+        active.equipped = Set(e);
+    }
+    if let Some(a) = attuned {
+        active.attuned = Set(a);
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    let holding = active.update(db).await?;
+    Ok(InventoryEntryResponse::new(holding, item))
+}
+
+/// Every hero holding an item, for "who has the macguffin?"
+#[instrument(skip_all, fields(item_id = %item_id), err)]
+pub async fn find_item_holders_impl(
+    db: &DatabaseConnection,
+    item_id: String,
+) -> Result<Vec<ItemHolderResponse>, AppError> {
+    find_item(db, &item_id).await?;
+
+    let rows = HeroItem::find()
+        .filter(hero_items::Column::ItemId.eq(&item_id))
+        .find_also_related(Hero)
+        .order_by_asc(heroes::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(holding, hero)| {
+            let hero = hero?;
+            Some(ItemHolderResponse {
+                hero_id: hero.id,
+                hero_name: hero.name,
+                player_id: hero.player_id,
+                hero_is_active: hero.is_active,
+                quantity: holding.quantity,
+                equipped: holding.equipped,
+                attuned: holding.attuned,
+            })
+        })
+        .collect())
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_hero_inventory(
+    state: State<'_, AppState>,
+    hero_id: String,
+) -> Result<Vec<InventoryEntryResponse>, AppError> {
+    get_hero_inventory_impl(&state.db, hero_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn grant_item(
+    state: State<'_, AppState>,
+    hero_id: String,
+    item_id: String,
+    quantity: Option<i32>,
+) -> Result<InventoryEntryResponse, AppError> {
+    grant_item_impl(&state.db, hero_id, item_id, quantity.unwrap_or(1)).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remove_item(
+    state: State<'_, AppState>,
+    hero_id: String,
+    item_id: String,
+    quantity: Option<i32>,
+) -> Result<Option<InventoryEntryResponse>, AppError> {
+    remove_item_impl(&state.db, hero_id, item_id, quantity).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn transfer_item(
+    state: State<'_, AppState>,
+    from_hero_id: String,
+    to_hero_id: String,
+    item_id: String,
+    quantity: Option<i32>,
+) -> Result<InventoryEntryResponse, AppError> {
+    transfer_item_impl(&state.db, from_hero_id, to_hero_id, item_id, quantity).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_hero_item(
+    state: State<'_, AppState>,
+    hero_id: String,
+    item_id: String,
+    equipped: Option<bool>,
+    attuned: Option<bool>,
+) -> Result<InventoryEntryResponse, AppError> {
+    update_hero_item_impl(&state.db, hero_id, item_id, equipped, attuned).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn find_item_holders(
+    state: State<'_, AppState>,
+    item_id: String,
+) -> Result<Vec<ItemHolderResponse>, AppError> {
+    find_item_holders_impl(&state.db, item_id).await
+}
@@ -0,0 +1,197 @@
+use crate::commands::references::remove_references;
+use crate::commands::validation::{CreateItemInput, UpdateItemInput};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::items::{self, Entity as Item};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub name: String,
+    pub item_type: String,
+    pub rarity: Option<String>,
+    pub description: Option<String>,
+    pub requires_attunement: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<items::Model> for ItemResponse {
+    fn from(model: items::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            name: model.name,
+            item_type: model.item_type,
+            rarity: model.rarity,
+            description: model.description,
+            requires_attunement: model.requires_attunement,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, err)]
+pub async fn create_item_impl(
+    db: &DatabaseConnection,
+    input: CreateItemInput,
+) -> Result<ItemResponse, AppError> {
+    input.validate()?;
+
+    let now = chrono::Utc::now();
+    let model = items::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(input.campaign_id),
+        name: Set(input.name),
+        item_type: Set(input.item_type),
+        rarity: Set(input.rarity),
+        description: Set(input.description),
+        requires_attunement: Set(input.requires_attunement),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    let result = model.insert(db).await?;
+    Ok(result.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_item_impl(db: &DatabaseConnection, id: String) -> Result<ItemResponse, AppError> {
+    let item = Item::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Item {} not found", id)))?;
+
+    Ok(item.into())
+}
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_items_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<ItemResponse>, AppError> {
+    let items = Item::find()
+        .filter(items::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(items::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(items.into_iter().map(|i| i.into()).collect())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_item_impl(
+    db: &DatabaseConnection,
+    id: String,
+    input: UpdateItemInput,
+) -> Result<ItemResponse, AppError> {
+    input.validate()?;
+
+    let item = Item::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Item {} not found", id)))?;
+
+    let mut active: items::ActiveModel = item.into();
+
+    if let Some(n) = input.name {
+        active.name = Set(n);
+    }
+    if let Some(t) = input.item_type {
+        active.item_type = Set(t);
+    }
+    if let Some(r) = input.rarity {
+        active.rarity = Set(Some(r));
+    }
+    if let Some(d) = input.description {
+        active.description = Set(Some(d));
+    }
+    if let Some(a) = input.requires_attunement {
+        active.requires_attunement = Set(a);
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    Ok(result.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_item_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
+    let txn = db.begin().await?;
+    remove_references(&txn, "item", &id).await?;
+    let result = Item::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
+    Ok(result.rows_affected > 0)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_item(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    name: String,
+    item_type: Option<String>,
+    rarity: Option<String>,
+    description: Option<String>,
+    requires_attunement: Option<bool>,
+) -> Result<ItemResponse, AppError> {
+    let input = CreateItemInput {
+        campaign_id,
+        name,
+        item_type: item_type.unwrap_or_else(|| "other".to_string()),
+        rarity,
+        description,
+        requires_attunement: requires_attunement.unwrap_or(false),
+    };
+    create_item_impl(&state.db, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_item(state: State<'_, AppState>, id: String) -> Result<ItemResponse, AppError> {
+    get_item_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_items(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<ItemResponse>, AppError> {
+    list_items_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_item(
+    state: State<'_, AppState>,
+    id: String,
+    name: Option<String>,
+    item_type: Option<String>,
+    rarity: Option<String>,
+    description: Option<String>,
+    requires_attunement: Option<bool>,
+) -> Result<ItemResponse, AppError> {
+    let input = UpdateItemInput {
+        name,
+        item_type,
+        rarity,
+        description,
+        requires_attunement,
+    };
+    update_item_impl(&state.db, id, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_item(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    delete_item_impl(&state.db, id).await
+}
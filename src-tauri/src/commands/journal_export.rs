@@ -0,0 +1,164 @@
+//! Exports of hand-picked entities for other tools, so prep written here can
+//! be carried to the table in whatever the group plays with.
+
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::export::homebrewery::render_homebrewery;
+use crate::export::journal::{build_journal, JournalEntry};
+use crate::export::load_bundle;
+use crate::export::roll20::{build_roll20_journal, render_roll20_html};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// A journal rendered for another tool
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalExport {
+    pub format: String,
+    pub content: String,
+    pub entity_count: usize,
+    /// Where the export was written, if a path was given
+    pub path: Option<String>,
+}
+
+/// Campaign name and the selected entities as journal entries
+async fn load_journal(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    entity_ids: &[String],
+) -> Result<(String, Vec<JournalEntry>), AppError> {
+    if entity_ids.is_empty() {
+        return Err(AppError::Validation(
+            "entity_ids: select at least one entity".to_string(),
+        ));
+    }
+    let bundle = load_bundle(db, campaign_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    let entries = build_journal(&bundle, entity_ids)
+        .map_err(|id| AppError::NotFound(format!("Entity {} not found", id)))?;
+    Ok((bundle.campaign.name, entries))
+}
+
+/// Write `content` to `path` when one is given
+pub(crate) fn write_export(path: &Option<String>, content: &str) -> Result<(), AppError> {
+    if let Some(path) = path {
+        std::fs::write(path, content)
+            .map_err(|e| AppError::Internal(format!("Failed to write {}: {}", path, e)))?;
+    }
+    Ok(())
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Selected characters, locations, organizations, quests and items as Roll20
+/// handouts: `json` for an API script to create them, `html` to paste by
+/// hand. GM-only fields go in each handout's GM notes.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn export_roll20_journal_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    entity_ids: Vec<String>,
+    format: String,
+    share_with_players: bool,
+    path: Option<String>,
+) -> Result<JournalExport, AppError> {
+    let format = match format.to_lowercase().as_str() {
+        "json" => "json",
+        "html" => "html",
+        _ => {
+            return Err(AppError::Validation(
+                "format: must be json or html".to_string(),
+            ))
+        }
+    };
+
+    let (campaign_name, entries) = load_journal(db, &campaign_id, &entity_ids).await?;
+    let journal = build_roll20_journal(&campaign_name, &entries, share_with_players);
+    let content = if format == "json" {
+        serde_json::to_string_pretty(&journal)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize journal: {}", e)))?
+    } else {
+        render_roll20_html(&journal)
+    };
+
+    write_export(&path, &content)?;
+    if let Some(path) = &path {
+        tracing::info!(path = %path, handouts = entries.len(), "Exported Roll20 journal");
+    }
+
+    Ok(JournalExport {
+        format: format.to_string(),
+        content,
+        entity_count: entries.len(),
+        path,
+    })
+}
+
+/// Selected entities as a Homebrewery document: description boxes, GM notes
+/// in note blocks and NPC stat blocks in monster frames. Leave out GM notes
+/// for a document to hand to players.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn export_homebrewery_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    entity_ids: Vec<String>,
+    include_gm_notes: bool,
+    path: Option<String>,
+) -> Result<JournalExport, AppError> {
+    let (campaign_name, entries) = load_journal(db, &campaign_id, &entity_ids).await?;
+    let content = render_homebrewery(&campaign_name, &entries, include_gm_notes);
+
+    write_export(&path, &content)?;
+    if let Some(path) = &path {
+        tracing::info!(path = %path, entries = entries.len(), "Exported Homebrewery document");
+    }
+
+    Ok(JournalExport {
+        format: "homebrewery".to_string(),
+        content,
+        entity_count: entries.len(),
+        path,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_roll20_journal(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    entity_ids: Vec<String>,
+    format: String,
+    share_with_players: Option<bool>,
+    path: Option<String>,
+) -> Result<JournalExport, AppError> {
+    export_roll20_journal_impl(
+        &state.db,
+        campaign_id,
+        entity_ids,
+        format,
+        share_with_players.unwrap_or(false),
+        path,
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_homebrewery(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    entity_ids: Vec<String>,
+    include_gm_notes: Option<bool>,
+    path: Option<String>,
+) -> Result<JournalExport, AppError> {
+    export_homebrewery_impl(
+        &state.db,
+        campaign_id,
+        entity_ids,
+        include_gm_notes.unwrap_or(true),
+        path,
+    )
+    .await
+}
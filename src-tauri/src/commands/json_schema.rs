@@ -0,0 +1,970 @@
+//! Typed shapes for the free-form JSON columns `characters.stat_block_json`,
+//! `players.availability_json`, `sessions.breaks_json` and
+//! `ai_messages.proposal_json`.
+//!
+//! Writes are checked against these structs but stored as sent, so key order
+//! and formatting chosen by the frontend are kept. Unknown keys are allowed
+//! and survive a round trip.
+
+use crate::db::AppState;
+use crate::error::{AppError, FieldError};
+use ::entity::ai_messages::{self, Entity as AiMessage};
+use ::entity::characters::{self, Entity as Character};
+use ::entity::players::{self, Entity as Player};
+use ::entity::sessions::{self, Entity as Session};
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use tauri::State;
+use tracing::instrument;
+use ts_rs::TS;
+
+/// Game-system-agnostic creature statistics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct StatBlock {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub armor_class: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_points: Option<i32>,
+    /// Hit dice expression, e.g. "6d8+12"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_dice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<String>,
+    /// Kept as text so fractions like "1/2" read naturally
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge_rating: Option<String>,
+    /// Ability scores keyed by short name, e.g. "str": 16
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub abilities: BTreeMap<String, i32>,
+    /// Skill bonuses keyed by skill name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub skills: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub senses: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub traits: Vec<StatBlockFeature>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<StatBlockFeature>,
+    /// Keys this version doesn't know about, preserved as-is
+    #[serde(flatten)]
+    #[ts(skip)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+/// A named trait, action or reaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct StatBlockFeature {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Field errors collected while checking a JSON value
+#[derive(Default)]
+struct FieldChecks {
+    errors: Vec<FieldError>,
+}
+
+impl FieldChecks {
+    fn fail(&mut self, field: String, code: &str, message: &str) {
+        self.errors.push(FieldError {
+            field,
+            code: code.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    fn not_negative(&mut self, field: &str, value: Option<i32>) {
+        if value.is_some_and(|v| v < 0) {
+            self.fail(field.into(), "range", "must not be negative");
+        }
+    }
+
+    fn in_range(&mut self, field: String, value: i32, min: i32, max: i32) {
+        if value < min || value > max {
+            self.fail(
+                field,
+                "range",
+                &format!("must be between {} and {}", min, max),
+            );
+        }
+    }
+
+    fn named(&mut self, list: &str, names: impl Iterator<Item = impl AsRef<str>>) {
+        for (i, name) in names.enumerate() {
+            if name.as_ref().trim().is_empty() {
+                self.fail(
+                    format!("{}[{}].name", list, i),
+                    "required",
+                    "must not be empty",
+                );
+            }
+        }
+    }
+
+    fn finish(self) -> Result<(), AppError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::InvalidFields(self.errors))
+        }
+    }
+}
+
+impl StatBlock {
+    pub fn parse(raw: &str) -> Result<Self, AppError> {
+        serde_json::from_str(raw)
+            .map_err(|e| AppError::Validation(format!("stat_block_json: {}", e)))
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        let mut checks = FieldChecks::default();
+
+        checks.not_negative("armor_class", self.armor_class);
+        checks.not_negative("hit_points", self.hit_points);
+        for (name, score) in &self.abilities {
+            checks.not_negative(&format!("abilities.{}", name), Some(*score));
+        }
+
+        checks.named("traits", self.traits.iter().map(|f| &f.name));
+        checks.named("actions", self.actions.iter().map(|f| &f.name));
+
+        checks.finish()
+    }
+}
+
+/// Ability score keys shared by the d20 systems, in printed order
+pub const ABILITY_KEYS: &[&str] = &["str", "dex", "con", "int", "wis", "cha"];
+
+/// Challenge ratings a 5e creature can have
+pub const DND5E_CHALLENGE_RATINGS: &[&str] = &[
+    "0", "1/8", "1/4", "1/2", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13",
+    "14", "15", "16", "17", "18", "19", "20", "21", "22", "23", "24", "25", "26", "27", "28", "29",
+    "30",
+];
+
+/// Action costs a PF2e activity can have
+pub const PF2E_ACTION_COSTS: &[&str] = &["1", "2", "3", "free", "reaction"];
+
+/// A stat block tagged with the game system it follows. Blocks saved before
+/// systems existed have no `system` key and read as generic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "system")]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub enum SystemStatBlock {
+    #[serde(rename = "generic")]
+    Generic(StatBlock),
+    #[serde(rename = "5e")]
+    Dnd5e(Dnd5eStatBlock),
+    #[serde(rename = "pf2e")]
+    Pf2e(Pf2eStatBlock),
+}
+
+impl SystemStatBlock {
+    pub fn parse(raw: &str) -> Result<Self, AppError> {
+        let invalid =
+            |e: serde_json::Error| AppError::Validation(format!("stat_block_json: {}", e));
+        let mut value: Value = serde_json::from_str(raw).map_err(invalid)?;
+        if let Value::Object(map) = &mut value {
+            map.entry("system")
+                .or_insert_with(|| Value::String("generic".to_string()));
+        }
+        serde_json::from_value(value).map_err(invalid)
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        match self {
+            Self::Generic(block) => block.validate(),
+            Self::Dnd5e(block) => block.validate(),
+            Self::Pf2e(block) => block.validate(),
+        }
+    }
+}
+
+/// D&D 5th edition monster statistics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct Dnd5eStatBlock {
+    /// e.g. "Medium"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    /// e.g. "humanoid (elf)"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creature_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alignment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub armor_class: Option<i32>,
+    /// Where the armor class comes from, e.g. "natural armor"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub armor_note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_points: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_dice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<String>,
+    /// Ability scores keyed by short name; missing scores count as 10
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub abilities: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub saving_throws: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub skills: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub damage_resistances: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub damage_immunities: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub condition_immunities: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub senses: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge_rating: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub traits: Vec<StatBlockFeature>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<StatBlockFeature>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bonus_actions: Vec<StatBlockFeature>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<StatBlockFeature>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub legendary_actions: Vec<StatBlockFeature>,
+    /// Keys this version doesn't know about, preserved as-is
+    #[serde(flatten)]
+    #[ts(skip)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl Dnd5eStatBlock {
+    pub fn validate(&self) -> Result<(), AppError> {
+        let mut checks = FieldChecks::default();
+
+        checks.not_negative("armor_class", self.armor_class);
+        checks.not_negative("hit_points", self.hit_points);
+        for (name, score) in &self.abilities {
+            let field = format!("abilities.{}", name);
+            if ABILITY_KEYS.contains(&name.as_str()) {
+                checks.in_range(field, *score, 1, 30);
+            } else {
+                checks.fail(
+                    field,
+                    "unknown_ability",
+                    "must be one of str, dex, con, int, wis, cha",
+                );
+            }
+        }
+        if let Some(cr) = &self.challenge_rating {
+            if !DND5E_CHALLENGE_RATINGS.contains(&cr.as_str()) {
+                checks.fail(
+                    "challenge_rating".into(),
+                    "invalid_challenge_rating",
+                    "must be 0, 1/8, 1/4, 1/2 or a whole number up to 30",
+                );
+            }
+        }
+
+        let features = [
+            ("traits", &self.traits),
+            ("actions", &self.actions),
+            ("bonus_actions", &self.bonus_actions),
+            ("reactions", &self.reactions),
+            ("legendary_actions", &self.legendary_actions),
+        ];
+        for (list, features) in features {
+            checks.named(list, features.iter().map(|f| &f.name));
+        }
+
+        checks.finish()
+    }
+
+    /// Ability score by short name, 10 when not given
+    pub fn ability(&self, key: &str) -> i32 {
+        self.abilities.get(key).copied().unwrap_or(10)
+    }
+}
+
+/// Pathfinder 2nd edition creature statistics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct Pf2eStatBlock {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    /// Creature traits such as "humanoid" or "undead"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub traits: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub perception: Option<i32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub senses: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub skills: BTreeMap<String, i32>,
+    /// Attribute modifiers keyed by short name, e.g. "dex": 3
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub armor_class: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fortitude: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reflex: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub will: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_points: Option<i32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub immunities: Vec<String>,
+    /// Damage type to amount, e.g. "fire": 5
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub weaknesses: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub resistances: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<String>,
+    /// Passive abilities
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub abilities: Vec<StatBlockFeature>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<Pf2eAction>,
+    /// Keys this version doesn't know about, preserved as-is
+    #[serde(flatten)]
+    #[ts(skip)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+/// A PF2e activity: a strike, spell or other action with its cost
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct Pf2eAction {
+    pub name: String,
+    /// "1", "2", "3", "free" or "reaction"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub traits: Vec<String>,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl Pf2eStatBlock {
+    pub fn validate(&self) -> Result<(), AppError> {
+        let mut checks = FieldChecks::default();
+
+        if let Some(level) = self.level {
+            checks.in_range("level".into(), level, -1, 25);
+        }
+        checks.not_negative("armor_class", self.armor_class);
+        checks.not_negative("hit_points", self.hit_points);
+        for (name, modifier) in &self.attributes {
+            let field = format!("attributes.{}", name);
+            if ABILITY_KEYS.contains(&name.as_str()) {
+                checks.in_range(field, *modifier, -5, 10);
+            } else {
+                checks.fail(
+                    field,
+                    "unknown_attribute",
+                    "must be one of str, dex, con, int, wis, cha",
+                );
+            }
+        }
+        let amounts = [
+            ("weaknesses", &self.weaknesses),
+            ("resistances", &self.resistances),
+        ];
+        for (list, amounts) in amounts {
+            for (name, amount) in amounts {
+                checks.not_negative(&format!("{}.{}", list, name), Some(*amount));
+            }
+        }
+
+        checks.named("abilities", self.abilities.iter().map(|f| &f.name));
+        checks.named("actions", self.actions.iter().map(|a| &a.name));
+        for (i, action) in self.actions.iter().enumerate() {
+            if let Some(cost) = &action.cost {
+                if !PF2E_ACTION_COSTS.contains(&cost.as_str()) {
+                    checks.fail(
+                        format!("actions[{}].cost", i),
+                        "invalid_action_cost",
+                        "must be one of: 1, 2, 3, free, reaction",
+                    );
+                }
+            }
+        }
+
+        checks.finish()
+    }
+}
+
+/// When a player can usually make a session
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct PlayerAvailability {
+    /// Weekdays the player is usually free, e.g. "fri". Empty means no
+    /// recurring schedule is known and every day counts as free.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub weekdays: Vec<String>,
+    /// One-off dates the player is free outside their usual weekdays
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub available_dates: Vec<NaiveDate>,
+    /// One-off dates the player can't make, overriding everything else
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unavailable_dates: Vec<NaiveDate>,
+    /// Keys this version doesn't know about, preserved as-is
+    #[serde(flatten)]
+    #[ts(skip)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl PlayerAvailability {
+    pub fn parse(raw: &str) -> Result<Self, AppError> {
+        serde_json::from_str(raw)
+            .map_err(|e| AppError::Validation(format!("availability_json: {}", e)))
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        let errors: Vec<FieldError> = self
+            .weekdays
+            .iter()
+            .enumerate()
+            .filter(|(_, day)| day.parse::<Weekday>().is_err())
+            .map(|(i, _)| FieldError {
+                field: format!("weekdays[{}]", i),
+                code: "invalid".to_string(),
+                message: "must be a weekday such as \"mon\"".to_string(),
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::InvalidFields(errors))
+        }
+    }
+
+    pub fn is_free_on(&self, date: NaiveDate) -> bool {
+        if self.unavailable_dates.contains(&date) {
+            return false;
+        }
+        if self.available_dates.contains(&date) || self.weekdays.is_empty() {
+            return true;
+        }
+        self.weekdays
+            .iter()
+            .filter_map(|day| day.parse::<Weekday>().ok())
+            .any(|day| day == date.weekday())
+    }
+}
+
+/// A pause in play recorded by the session clock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct SessionBreak {
+    pub started_at: DateTime<Utc>,
+    /// Unset while the break is still going
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Keys this version doesn't know about, preserved as-is
+    #[serde(flatten)]
+    #[ts(skip)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl SessionBreak {
+    pub fn new(started_at: DateTime<Utc>) -> Self {
+        Self {
+            started_at,
+            ended_at: None,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    /// Parse the list stored in `sessions.breaks_json`
+    pub fn parse_list(raw: &str) -> Result<Vec<Self>, AppError> {
+        serde_json::from_str(raw).map_err(|e| AppError::Validation(format!("breaks_json: {}", e)))
+    }
+
+    pub fn validate_list(breaks: &[Self]) -> Result<(), AppError> {
+        let errors: Vec<FieldError> = breaks
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.ended_at.is_some_and(|end| end < b.started_at))
+            .map(|(i, _)| FieldError {
+                field: format!("breaks[{}].ended_at", i),
+                code: "invalid".to_string(),
+                message: "must not be before started_at".to_string(),
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::InvalidFields(errors))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+/// An entity proposal as produced by the AI assistant. Mirrors
+/// `EntityProposal` in `src/ai/tools/entity-proposals/types.ts`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Proposal {
+    pub id: String,
+    pub status: ProposalStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(flatten)]
+    pub operation: ProposalOperation,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum ProposalOperation {
+    Create(CreateProposal),
+    Update(UpdateProposal),
+    Patch(PatchProposal),
+    Relationship(RelationshipProposal),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateProposal {
+    pub entity_type: String,
+    pub data: Map<String, Value>,
+    /// Display-only and optional fields, preserved as-is
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProposal {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub changes: Map<String, Value>,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchProposal {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub patches: Vec<FieldPatch>,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldPatch {
+    pub field: String,
+    pub patch_type: PatchType,
+    pub patch: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchType {
+    UnifiedDiff,
+    JsonPatch,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelationshipProposal {
+    pub source_type: String,
+    pub source_id: String,
+    pub target_type: String,
+    pub target_id: String,
+    pub relationship_type: String,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl Proposal {
+    pub fn parse(raw: &str) -> Result<Self, AppError> {
+        let proposal: Self = serde_json::from_str(raw)
+            .map_err(|e| AppError::Validation(format!("proposal_json: {}", e)))?;
+
+        if proposal.id.trim().is_empty() {
+            return Err(AppError::Validation(
+                "proposal_json: id must not be empty".to_string(),
+            ));
+        }
+        Ok(proposal)
+    }
+}
+
+/// A stored JSON value that no longer matches its schema
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InvalidJsonField {
+    pub table: String,
+    pub column: String,
+    pub row_id: String,
+    pub message: String,
+}
+
+fn check_stat_block(raw: &str) -> Result<(), AppError> {
+    SystemStatBlock::parse(raw)?.validate()
+}
+
+fn check_availability(raw: &str) -> Result<(), AppError> {
+    PlayerAvailability::parse(raw)?.validate()
+}
+
+fn check_session_breaks(raw: &str) -> Result<(), AppError> {
+    SessionBreak::validate_list(&SessionBreak::parse_list(raw)?)
+}
+
+fn check_proposal(raw: &str) -> Result<(), AppError> {
+    Proposal::parse(raw).map(|_| ())
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Parse and check a stat block against the model for its `system`,
+/// returning it in normalized form
+pub fn validate_stat_block_impl(stat_block_json: &str) -> Result<SystemStatBlock, AppError> {
+    let stat_block = SystemStatBlock::parse(stat_block_json)?;
+    stat_block.validate()?;
+    Ok(stat_block)
+}
+
+/// Scan existing rows for JSON columns that fail their schema. Rows written
+/// before validation existed may need repair; nothing is modified here.
+#[instrument(skip_all, err)]
+pub async fn find_invalid_json_fields_impl(
+    db: &DatabaseConnection,
+) -> Result<Vec<InvalidJsonField>, AppError> {
+    let mut invalid = Vec::new();
+
+    let stat_blocks: Vec<(String, Option<String>)> = Character::find()
+        .select_only()
+        .column(characters::Column::Id)
+        .column(characters::Column::StatBlockJson)
+        .filter(characters::Column::StatBlockJson.is_not_null())
+        .into_tuple()
+        .all(db)
+        .await?;
+    collect_invalid(
+        &mut invalid,
+        "characters",
+        "stat_block_json",
+        stat_blocks,
+        check_stat_block,
+    );
+
+    let availability: Vec<(String, Option<String>)> = Player::find()
+        .select_only()
+        .column(players::Column::Id)
+        .column(players::Column::AvailabilityJson)
+        .filter(players::Column::AvailabilityJson.is_not_null())
+        .into_tuple()
+        .all(db)
+        .await?;
+    collect_invalid(
+        &mut invalid,
+        "players",
+        "availability_json",
+        availability,
+        check_availability,
+    );
+
+    let breaks: Vec<(String, Option<String>)> = Session::find()
+        .select_only()
+        .column(sessions::Column::Id)
+        .column(sessions::Column::BreaksJson)
+        .filter(sessions::Column::BreaksJson.is_not_null())
+        .into_tuple()
+        .all(db)
+        .await?;
+    collect_invalid(
+        &mut invalid,
+        "sessions",
+        "breaks_json",
+        breaks,
+        check_session_breaks,
+    );
+
+    let proposals: Vec<(String, Option<String>)> = AiMessage::find()
+        .select_only()
+        .column(ai_messages::Column::Id)
+        .column(ai_messages::Column::ProposalJson)
+        .filter(ai_messages::Column::ProposalJson.is_not_null())
+        .into_tuple()
+        .all(db)
+        .await?;
+    collect_invalid(
+        &mut invalid,
+        "ai_messages",
+        "proposal_json",
+        proposals,
+        check_proposal,
+    );
+
+    Ok(invalid)
+}
+
+fn collect_invalid(
+    out: &mut Vec<InvalidJsonField>,
+    table: &str,
+    column: &str,
+    rows: Vec<(String, Option<String>)>,
+    check: fn(&str) -> Result<(), AppError>,
+) {
+    for (row_id, raw) in rows {
+        let Some(raw) = raw else { continue };
+        if let Err(e) = check(&raw) {
+            out.push(InvalidJsonField {
+                table: table.to_string(),
+                column: column.to_string(),
+                row_id,
+                message: e.to_string(),
+            });
+        }
+    }
+}
+
+/// Log rows that need repair; called once after migrations run
+pub async fn report_invalid_json_fields(db: &DatabaseConnection) {
+    match find_invalid_json_fields_impl(db).await {
+        Ok(invalid) if invalid.is_empty() => {}
+        Ok(invalid) => {
+            for field in &invalid {
+                tracing::warn!(
+                    table = %field.table,
+                    column = %field.column,
+                    row_id = %field.row_id,
+                    "Stored JSON needs repair: {}",
+                    field.message
+                );
+            }
+            tracing::warn!(count = invalid.len(), "Found stored JSON that needs repair");
+        }
+        Err(e) => tracing::warn!("Failed to scan stored JSON: {}", e),
+    }
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn validate_stat_block(stat_block_json: String) -> Result<SystemStatBlock, AppError> {
+    validate_stat_block_impl(&stat_block_json)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn find_invalid_json_fields(
+    state: State<'_, AppState>,
+) -> Result<Vec<InvalidJsonField>, AppError> {
+    find_invalid_json_fields_impl(&state.db).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_bindings() {
+        StatBlock::export_all().unwrap();
+        SystemStatBlock::export_all().unwrap();
+        PlayerAvailability::export_all().unwrap();
+        SessionBreak::export_all().unwrap();
+    }
+
+    #[test]
+    fn test_stat_block_round_trip_keeps_unknown_keys() {
+        let raw = r#"{"armor_class":15,"abilities":{"str":16},"hp":45,"legendary":{"uses":3}}"#;
+        let stat_block = StatBlock::parse(raw).unwrap();
+
+        assert_eq!(stat_block.armor_class, Some(15));
+        assert_eq!(stat_block.abilities.get("str"), Some(&16));
+
+        let original: Value = serde_json::from_str(raw).unwrap();
+        let written = serde_json::to_value(&stat_block).unwrap();
+        assert_eq!(written, original);
+    }
+
+    #[test]
+    fn test_stat_block_rejects_wrong_types() {
+        assert!(StatBlock::parse("not json").is_err());
+        assert!(StatBlock::parse("[1, 2]").is_err());
+        assert!(StatBlock::parse(r#"{"armor_class": "high"}"#).is_err());
+    }
+
+    #[test]
+    fn test_stat_block_validate_reports_each_bad_field() {
+        let raw = r#"{"hit_points": -3, "abilities": {"dex": -1}, "actions": [{"name": " "}]}"#;
+        let Err(AppError::InvalidFields(errors)) = validate_stat_block_impl(raw) else {
+            panic!("expected field errors");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["hit_points", "abilities.dex", "actions[0].name"]
+        );
+    }
+
+    #[test]
+    fn test_system_stat_block_dispatches_on_system() {
+        let generic = SystemStatBlock::parse(r#"{"armor_class": 12}"#).unwrap();
+        assert!(matches!(generic, SystemStatBlock::Generic(ref b) if b.armor_class == Some(12)));
+
+        let dnd = SystemStatBlock::parse(r#"{"system": "5e", "abilities": {"str": 18}}"#).unwrap();
+        let SystemStatBlock::Dnd5e(dnd) = dnd else {
+            panic!("expected a 5e stat block");
+        };
+        assert_eq!(dnd.ability("str"), 18);
+        assert_eq!(dnd.ability("wis"), 10);
+
+        let pf2e =
+            SystemStatBlock::parse(r#"{"system": "pf2e", "level": 3, "homebrew": true}"#).unwrap();
+        let SystemStatBlock::Pf2e(pf2e) = pf2e else {
+            panic!("expected a PF2e stat block");
+        };
+        assert_eq!(pf2e.level, Some(3));
+        assert!(pf2e.extra.contains_key("homebrew"));
+
+        assert!(SystemStatBlock::parse(r#"{"system": "gurps"}"#).is_err());
+    }
+
+    #[test]
+    fn test_dnd5e_stat_block_validation() {
+        let raw = r#"{"system": "5e", "abilities": {"str": 31, "luck": 12}, "challenge_rating": "1/3", "reactions": [{"name": ""}]}"#;
+        let Err(AppError::InvalidFields(errors)) = validate_stat_block_impl(raw) else {
+            panic!("expected field errors");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "abilities.luck",
+                "abilities.str",
+                "challenge_rating",
+                "reactions[0].name"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pf2e_stat_block_validation() {
+        let raw = r#"{"system": "pf2e", "level": 30, "attributes": {"dex": 12}, "weaknesses": {"fire": -5}, "actions": [{"name": "Strike", "cost": "4"}]}"#;
+        let Err(AppError::InvalidFields(errors)) = validate_stat_block_impl(raw) else {
+            panic!("expected field errors");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "level",
+                "attributes.dex",
+                "weaknesses.fire",
+                "actions[0].cost"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_player_availability_overrides() {
+        let availability = PlayerAvailability::parse(
+            r#"{"weekdays":["fri","Saturday"],"available_dates":["2026-02-04"],"unavailable_dates":["2026-02-07"]}"#,
+        )
+        .unwrap();
+        availability.validate().unwrap();
+
+        let date = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+        assert!(availability.is_free_on(date("2026-02-06"))); // Friday
+        assert!(!availability.is_free_on(date("2026-02-07"))); // Saturday, but blocked
+        assert!(availability.is_free_on(date("2026-02-04"))); // Wednesday, one-off
+        assert!(!availability.is_free_on(date("2026-02-05"))); // Thursday
+
+        assert!(PlayerAvailability::default().is_free_on(date("2026-02-05")));
+        assert!(PlayerAvailability::parse(r#"{"weekdays":["someday"]}"#)
+            .unwrap()
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_session_breaks_reject_negative_length() {
+        let breaks = SessionBreak::parse_list(
+            r#"[{"started_at":"2026-02-06T20:00:00Z","ended_at":"2026-02-06T20:15:00Z"},{"started_at":"2026-02-06T22:00:00Z"}]"#,
+        )
+        .unwrap();
+        SessionBreak::validate_list(&breaks).unwrap();
+        assert!(breaks[1].ended_at.is_none());
+
+        let backwards = SessionBreak::parse_list(
+            r#"[{"started_at":"2026-02-06T20:00:00Z","ended_at":"2026-02-06T19:00:00Z"}]"#,
+        )
+        .unwrap();
+        assert!(SessionBreak::validate_list(&backwards).is_err());
+        assert!(SessionBreak::parse_list(r#"{"started_at":"2026-02-06T20:00:00Z"}"#).is_err());
+    }
+
+    #[test]
+    fn test_proposal_round_trip_keeps_unknown_keys() {
+        let raw = r#"{
+            "id": "prop1",
+            "operation": "patch",
+            "status": "pending",
+            "createdAt": "2026-01-01T00:00:00.000Z",
+            "entityType": "character",
+            "entityId": "c1",
+            "patches": [{"field": "description", "patchType": "unified_diff", "patch": "@@"}],
+            "previewData": {"name": "Mira"}
+        }"#;
+        let proposal = Proposal::parse(raw).unwrap();
+
+        let ProposalOperation::Patch(patch) = &proposal.operation else {
+            panic!("expected a patch proposal");
+        };
+        assert_eq!(patch.patches[0].patch_type, PatchType::UnifiedDiff);
+        assert!(patch.extra.contains_key("previewData"));
+
+        let original: Value = serde_json::from_str(raw).unwrap();
+        let written = serde_json::to_value(&proposal).unwrap();
+        assert_eq!(written, original);
+    }
+
+    #[test]
+    fn test_proposal_rejects_bad_shapes() {
+        // Unknown operation
+        assert!(Proposal::parse(r#"{"id":"p","operation":"merge","status":"pending"}"#).is_err());
+        // Unknown status
+        assert!(Proposal::parse(
+            r#"{"id":"p","operation":"create","status":"maybe","entityType":"character","data":{}}"#
+        )
+        .is_err());
+        // Missing the fields a create needs
+        assert!(Proposal::parse(r#"{"id":"p","operation":"create","status":"pending"}"#).is_err());
+    }
+}
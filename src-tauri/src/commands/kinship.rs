@@ -0,0 +1,351 @@
+//! Family relationships between characters and heroes. Kinship is stored as
+//! ordinary relationships; `parent_of`/`child_of` are kept in pairs and the
+//! symmetric kinds are always bidirectional, so either end reads correctly.
+
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::characters::{self, Entity as Character};
+use ::entity::heroes::{self, Entity as Hero};
+use ::entity::relationships::{self, Entity as Relationship};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+use tracing::instrument;
+
+pub const KINSHIP_TYPES: &[&str] = &["parent_of", "child_of", "sibling_of", "married_to"];
+
+/// Entity types that can hold kinship relationships
+pub const KIN_ENTITY_TYPES: &[&str] = &["character", "hero"];
+
+pub const DEFAULT_FAMILY_TREE_DEPTH: u32 = 3;
+pub const MAX_FAMILY_TREE_DEPTH: u32 = 10;
+
+/// The relationship type a kinship edge implies in the other direction
+pub fn kinship_inverse(relationship_type: &str) -> Option<&'static str> {
+    match relationship_type {
+        "parent_of" => Some("child_of"),
+        "child_of" => Some("parent_of"),
+        "sibling_of" => Some("sibling_of"),
+        "married_to" => Some("married_to"),
+        _ => None,
+    }
+}
+
+/// Whether a relationship of this type between these ends is kinship
+pub fn is_kinship(source_type: &str, target_type: &str, relationship_type: &str) -> bool {
+    KIN_ENTITY_TYPES.contains(&source_type)
+        && KIN_ENTITY_TYPES.contains(&target_type)
+        && kinship_inverse(relationship_type).is_some()
+}
+
+/// Kinship kinds that read the same from both ends
+pub fn is_symmetric_kinship(relationship_type: &str) -> bool {
+    kinship_inverse(relationship_type) == Some(relationship_type)
+}
+
+fn inverse_filter(rel: &relationships::Model, inverse: &str) -> Condition {
+    Condition::all()
+        .add(relationships::Column::CampaignId.eq(&rel.campaign_id))
+        .add(relationships::Column::SourceType.eq(&rel.target_type))
+        .add(relationships::Column::SourceId.eq(&rel.target_id))
+        .add(relationships::Column::TargetType.eq(&rel.source_type))
+        .add(relationships::Column::TargetId.eq(&rel.source_id))
+        .add(relationships::Column::RelationshipType.eq(inverse))
+}
+
+/// Insert the `child_of` row for a `parent_of` (and vice versa) unless it
+/// already exists. Symmetric kinds need no second row.
+pub(crate) async fn add_kinship_inverse<C: ConnectionTrait>(
+    conn: &C,
+    rel: &relationships::Model,
+) -> Result<(), AppError> {
+    if !is_kinship(&rel.source_type, &rel.target_type, &rel.relationship_type)
+        || is_symmetric_kinship(&rel.relationship_type)
+    {
+        return Ok(());
+    }
+    let Some(inverse) = kinship_inverse(&rel.relationship_type) else {
+        return Ok(());
+    };
+
+    let exists = Relationship::find()
+        .filter(inverse_filter(rel, inverse))
+        .one(conn)
+        .await?
+        .is_some();
+    if exists {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now();
+    relationships::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(rel.campaign_id.clone()),
+        source_type: Set(rel.target_type.clone()),
+        source_id: Set(rel.target_id.clone()),
+        target_type: Set(rel.source_type.clone()),
+        target_id: Set(rel.source_id.clone()),
+        relationship_type: Set(inverse.to_string()),
+        description: Set(rel.description.clone()),
+        is_bidirectional: Set(false),
+        strength: Set(rel.strength),
+        is_public: Set(rel.is_public),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(conn)
+    .await?;
+    Ok(())
+}
+
+/// Delete the paired row of a `parent_of`/`child_of` relationship
+pub(crate) async fn remove_kinship_inverse<C: ConnectionTrait>(
+    conn: &C,
+    rel: &relationships::Model,
+) -> Result<(), AppError> {
+    if !is_kinship(&rel.source_type, &rel.target_type, &rel.relationship_type)
+        || is_symmetric_kinship(&rel.relationship_type)
+    {
+        return Ok(());
+    }
+    let Some(inverse) = kinship_inverse(&rel.relationship_type) else {
+        return Ok(());
+    };
+
+    Relationship::delete_many()
+        .filter(inverse_filter(rel, inverse))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+/// A person in a family tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FamilyMember {
+    pub entity_type: String,
+    pub id: String,
+    pub name: String,
+    /// Unknown for heroes
+    pub is_alive: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FamilyTreeNode {
+    pub member: FamilyMember,
+    pub spouses: Vec<FamilyMember>,
+    /// The next generation away from the root: parents when walking
+    /// ancestors, children when walking descendants
+    pub relatives: Vec<FamilyTreeNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FamilyTree {
+    pub root: FamilyMember,
+    pub depth: u32,
+    pub spouses: Vec<FamilyMember>,
+    /// Explicit siblings and anyone sharing a parent with the root
+    pub siblings: Vec<FamilyMember>,
+    pub ancestors: Vec<FamilyTreeNode>,
+    pub descendants: Vec<FamilyTreeNode>,
+}
+
+type Person = (String, String);
+
+/// Kinship edges of a campaign, normalized to parent -> child and
+/// undirected sibling/spouse pairs
+#[derive(Default)]
+struct KinGraph {
+    members: HashMap<Person, FamilyMember>,
+    parents: HashMap<Person, Vec<Person>>,
+    children: HashMap<Person, Vec<Person>>,
+    siblings: HashMap<Person, Vec<Person>>,
+    spouses: HashMap<Person, Vec<Person>>,
+}
+
+fn link(map: &mut HashMap<Person, Vec<Person>>, from: &Person, to: &Person) {
+    let list = map.entry(from.clone()).or_default();
+    if !list.contains(to) {
+        list.push(to.clone());
+    }
+}
+
+impl KinGraph {
+    async fn load(db: &DatabaseConnection, campaign_id: &str) -> Result<Self, AppError> {
+        let mut graph = KinGraph::default();
+
+        for c in Character::find()
+            .filter(characters::Column::CampaignId.eq(campaign_id))
+            .all(db)
+            .await?
+        {
+            graph.members.insert(
+                ("character".to_string(), c.id.clone()),
+                FamilyMember {
+                    entity_type: "character".to_string(),
+                    id: c.id,
+                    name: c.name,
+                    is_alive: Some(c.is_alive),
+                },
+            );
+        }
+        for h in Hero::find()
+            .filter(heroes::Column::CampaignId.eq(campaign_id))
+            .all(db)
+            .await?
+        {
+            graph.members.insert(
+                ("hero".to_string(), h.id.clone()),
+                FamilyMember {
+                    entity_type: "hero".to_string(),
+                    id: h.id,
+                    name: h.name,
+                    is_alive: None,
+                },
+            );
+        }
+
+        let rels = Relationship::find()
+            .filter(relationships::Column::CampaignId.eq(campaign_id))
+            .filter(relationships::Column::RelationshipType.is_in(KINSHIP_TYPES.iter().copied()))
+            .filter(relationships::Column::SourceType.is_in(KIN_ENTITY_TYPES.iter().copied()))
+            .filter(relationships::Column::TargetType.is_in(KIN_ENTITY_TYPES.iter().copied()))
+            .all(db)
+            .await?;
+
+        for rel in rels {
+            let source = (rel.source_type, rel.source_id);
+            let target = (rel.target_type, rel.target_id);
+            if !graph.members.contains_key(&source) || !graph.members.contains_key(&target) {
+                continue;
+            }
+            match rel.relationship_type.as_str() {
+                "parent_of" => graph.add_parent(&source, &target),
+                "child_of" => graph.add_parent(&target, &source),
+                "sibling_of" => {
+                    link(&mut graph.siblings, &source, &target);
+                    link(&mut graph.siblings, &target, &source);
+                }
+                "married_to" => {
+                    link(&mut graph.spouses, &source, &target);
+                    link(&mut graph.spouses, &target, &source);
+                }
+                _ => {}
+            }
+        }
+        Ok(graph)
+    }
+
+    fn add_parent(&mut self, parent: &Person, child: &Person) {
+        link(&mut self.parents, child, parent);
+        link(&mut self.children, parent, child);
+    }
+
+    fn members_of(&self, people: impl IntoIterator<Item = Person>) -> Vec<FamilyMember> {
+        let mut members: Vec<FamilyMember> = people
+            .into_iter()
+            .filter_map(|p| self.members.get(&p).cloned())
+            .collect();
+        members.sort_by(|a, b| a.name.cmp(&b.name));
+        members
+    }
+
+    fn related(&self, map: &HashMap<Person, Vec<Person>>, person: &Person) -> Vec<Person> {
+        map.get(person).cloned().unwrap_or_default()
+    }
+
+    /// Walk `map` (parents or children) out to `depth` generations. People
+    /// already on the current path are skipped so bad data can't loop.
+    fn walk(
+        &self,
+        map: &HashMap<Person, Vec<Person>>,
+        person: &Person,
+        depth: u32,
+        path: &mut HashSet<Person>,
+    ) -> Vec<FamilyTreeNode> {
+        if depth == 0 {
+            return Vec::new();
+        }
+        let mut nodes = Vec::new();
+        for next in self.related(map, person) {
+            if !path.insert(next.clone()) {
+                continue;
+            }
+            if let Some(member) = self.members.get(&next) {
+                nodes.push(FamilyTreeNode {
+                    member: member.clone(),
+                    spouses: self.members_of(self.related(&self.spouses, &next)),
+                    relatives: self.walk(map, &next, depth - 1, path),
+                });
+            }
+            path.remove(&next);
+        }
+        nodes.sort_by(|a, b| a.member.name.cmp(&b.member.name));
+        nodes
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// A character's family: spouses and siblings, plus ancestors and
+/// descendants out to `depth` generations
+#[instrument(skip_all, fields(character_id = %character_id), err)]
+pub async fn get_family_tree_impl(
+    db: &DatabaseConnection,
+    character_id: String,
+    depth: Option<u32>,
+) -> Result<FamilyTree, AppError> {
+    let depth = depth.unwrap_or(DEFAULT_FAMILY_TREE_DEPTH);
+    if !(1..=MAX_FAMILY_TREE_DEPTH).contains(&depth) {
+        return Err(AppError::Validation(format!(
+            "depth: must be between 1 and {}",
+            MAX_FAMILY_TREE_DEPTH
+        )));
+    }
+
+    let character = Character::find_by_id(&character_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", character_id)))?;
+
+    let graph = KinGraph::load(db, &character.campaign_id).await?;
+    let root: Person = ("character".to_string(), character.id.clone());
+    let member = graph
+        .members
+        .get(&root)
+        .cloned()
+        .ok_or_else(|| AppError::Internal(format!("Character {} not loaded", character.id)))?;
+
+    // Half-siblings count too: anyone else who shares a parent
+    let mut siblings: HashSet<Person> = graph.related(&graph.siblings, &root).into_iter().collect();
+    for parent in graph.related(&graph.parents, &root) {
+        siblings.extend(graph.related(&graph.children, &parent));
+    }
+    siblings.remove(&root);
+
+    let mut path = HashSet::from([root.clone()]);
+    let ancestors = graph.walk(&graph.parents, &root, depth, &mut path);
+    let descendants = graph.walk(&graph.children, &root, depth, &mut path);
+
+    Ok(FamilyTree {
+        root: member,
+        depth,
+        spouses: graph.members_of(graph.related(&graph.spouses, &root)),
+        siblings: graph.members_of(siblings),
+        ancestors,
+        descendants,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_family_tree(
+    state: State<'_, AppState>,
+    character_id: String,
+    depth: Option<u32>,
+) -> Result<FamilyTree, AppError> {
+    get_family_tree_impl(&state.db, character_id, depth).await
+}
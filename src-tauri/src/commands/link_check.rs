@@ -0,0 +1,381 @@
+//! Finding links in entity text that no longer lead anywhere, so renamed or
+//! deleted entities don't leave dead links behind without anyone noticing.
+//! Every free-text field is scanned for `[[links]]`, rich text mentions and
+//! bare entity ids; each dead one is reported with similarly named entities
+//! it may have meant.
+
+use crate::commands::duplicates::name_similarity;
+use crate::commands::markdown::gm_targets;
+use crate::commands::registry::{table_for, ENTITY_TABLES};
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::markdown::{resolve, wiki_links, LinkTarget};
+use ::entity::tombstones::{self, Entity as Tombstone};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tauri::State;
+use tracing::instrument;
+
+/// Names scoring below this aren't suggested
+const MIN_SUGGESTION_SIMILARITY: f64 = 0.6;
+/// Most suggestions given for one dead link
+const MAX_SUGGESTIONS: usize = 3;
+/// Length of a hyphenated UUID, the shape of every entity id
+const ID_LEN: usize = 36;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    /// `[[Name]]` or `[[type:id]]` in markdown
+    WikiLink,
+    /// A mention or citation node in rich text
+    Mention,
+    /// An entity id written out in the text
+    RawId,
+}
+
+/// An entity a dead link may have meant
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkSuggestion {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: String,
+    /// 0.0-1.0, scored like duplicate names
+    pub similarity: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLink {
+    /// The entity whose text holds the link
+    pub entity_type: String,
+    pub entity_id: String,
+    pub entity_name: Option<String>,
+    pub field: String,
+    pub kind: LinkKind,
+    /// The link as written: the text between the brackets, or the id
+    pub link: String,
+    /// Type of the entity the link pointed at, when its deletion is on record
+    pub deleted_type: Option<String>,
+    /// Best match first
+    pub suggestions: Vec<LinkSuggestion>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkReport {
+    pub campaign_id: String,
+    pub links_checked: usize,
+    pub dead_links: Vec<DeadLink>,
+}
+
+/// A link found in a field, before it's checked
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct FoundLink {
+    kind: LinkKind,
+    link: String,
+    /// Type named by the link, if any
+    entity_type: Option<String>,
+    /// Id named by the link, if any
+    id: Option<String>,
+    /// Name to suggest replacements for
+    label: Option<String>,
+}
+
+fn is_id_char(c: u8) -> bool {
+    c.is_ascii_hexdigit() || c == b'-'
+}
+
+/// Every UUID-shaped token in `text`
+fn find_ids(text: &str) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut ids = Vec::new();
+    let mut i = 0;
+    while i + ID_LEN <= bytes.len() {
+        let candidate = &bytes[i..i + ID_LEN];
+        let shaped = candidate.iter().enumerate().all(|(n, &c)| match n {
+            8 | 13 | 18 | 23 => c == b'-',
+            _ => c.is_ascii_hexdigit(),
+        });
+        let bounded = (i == 0 || !is_id_char(bytes[i - 1]))
+            && bytes.get(i + ID_LEN).is_none_or(|&c| !is_id_char(c));
+        if shaped && bounded {
+            // Only ASCII was matched, so these are char boundaries
+            ids.push(&text[i..i + ID_LEN]);
+            i += ID_LEN;
+        } else {
+            i += 1;
+        }
+    }
+    ids
+}
+
+/// `[[links]]` and bare ids in a run of markdown. Ids inside a link are
+/// checked as part of it.
+fn text_links(text: &str, found: &mut BTreeSet<FoundLink>) {
+    let links = wiki_links(text);
+    for id in find_ids(text) {
+        if !links.iter().any(|l| l.contains(id)) {
+            found.insert(FoundLink {
+                kind: LinkKind::RawId,
+                link: id.to_string(),
+                entity_type: None,
+                id: Some(id.to_string()),
+                label: None,
+            });
+        }
+    }
+    for link in links {
+        // Names may contain colons, so `type:id` needs a known entity type
+        let mut parts = link.splitn(3, ':');
+        let (entity_type, id, label) = match (parts.next(), parts.next()) {
+            (Some(entity_type), Some(id)) if table_for(entity_type).is_some() => (
+                Some(entity_type.to_string()),
+                Some(id.to_string()),
+                parts.next().map(str::to_string),
+            ),
+            _ => (None, None, Some(link.trim().to_string())),
+        };
+        found.insert(FoundLink {
+            kind: LinkKind::WikiLink,
+            link,
+            entity_type,
+            id,
+            label,
+        });
+    }
+}
+
+/// Mentions in editor JSON, plus links typed into its text nodes
+fn json_links(node: &Value, found: &mut BTreeSet<FoundLink>) {
+    match node {
+        Value::Object(map) => {
+            let attrs = &node["attrs"];
+            if let Some(id) = attrs["entityId"].as_str() {
+                found.insert(FoundLink {
+                    kind: LinkKind::Mention,
+                    link: id.to_string(),
+                    entity_type: attrs["entityType"].as_str().map(str::to_string),
+                    id: Some(id.to_string()),
+                    label: attrs["label"].as_str().map(str::to_string),
+                });
+            }
+            if let Some(text) = node["text"].as_str() {
+                text_links(text, found);
+            }
+            for (key, value) in map {
+                if key != "attrs" && key != "text" {
+                    json_links(value, found);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                json_links(item, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Every link in a field, whether it holds editor JSON or markdown
+fn field_links(text: &str) -> BTreeSet<FoundLink> {
+    let mut found = BTreeSet::new();
+    match serde_json::from_str::<Value>(text) {
+        Ok(doc) if doc.is_object() || doc.is_array() => json_links(&doc, &mut found),
+        _ => text_links(text, &mut found),
+    }
+    found
+}
+
+/// Whether the link still leads somewhere
+fn resolves(link: &FoundLink, ids: &HashSet<String>, targets: &[LinkTarget]) -> bool {
+    match (&link.kind, &link.id) {
+        (LinkKind::WikiLink, None) => resolve(targets, &link.link).0.is_some(),
+        (_, Some(id)) => ids.contains(id),
+        (_, None) => true,
+    }
+}
+
+/// Entities named like the link's label, limited to the type it named
+fn suggestions(link: &FoundLink, targets: &[LinkTarget]) -> Vec<LinkSuggestion> {
+    let Some(label) = link.label.as_deref().filter(|l| !l.trim().is_empty()) else {
+        return Vec::new();
+    };
+    let mut scored: Vec<LinkSuggestion> = targets
+        .iter()
+        .filter(|t| {
+            link.entity_type
+                .as_ref()
+                .is_none_or(|et| *et == t.entity_type)
+        })
+        .map(|t| LinkSuggestion {
+            entity_type: t.entity_type.clone(),
+            entity_id: t.id.clone(),
+            name: t.name.clone(),
+            similarity: name_similarity(label, &t.name),
+        })
+        .filter(|s| s.similarity >= MIN_SUGGESTION_SIMILARITY)
+        .collect();
+    scored.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    scored.truncate(MAX_SUGGESTIONS);
+    scored
+}
+
+/// Ids of every row in the campaign that text may point at
+async fn campaign_ids(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+) -> Result<HashSet<String>, AppError> {
+    let mut ids = HashSet::from([campaign_id.to_string()]);
+    let tables = ENTITY_TABLES
+        .iter()
+        .map(|t| t.table)
+        // Rich text embeds attachments by id too
+        .chain(["attachments"]);
+    for table in tables {
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!("SELECT id FROM {} WHERE campaign_id = $1", table),
+                [campaign_id.into()],
+            ))
+            .await?;
+        for row in rows {
+            ids.insert(row.try_get("", "id")?);
+        }
+    }
+    Ok(ids)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Scan every free-text field in the campaign for links that no longer
+/// resolve
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn check_links_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<LinkReport, AppError> {
+    // Also checks the campaign exists
+    let targets = gm_targets(db, &campaign_id).await?;
+    let ids = campaign_ids(db, &campaign_id).await?;
+    let deleted: HashMap<String, String> = Tombstone::find()
+        .filter(tombstones::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| (t.entity_id, t.entity_type))
+        .collect();
+
+    let mut links_checked = 0;
+    let mut dead_links = Vec::new();
+    for table in ENTITY_TABLES {
+        if table.text_columns.is_empty() {
+            continue;
+        }
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!(
+                    "SELECT id, {} AS display_name, {} FROM {} WHERE campaign_id = $1",
+                    table.name_column.unwrap_or("NULL"),
+                    table.text_columns.join(", "),
+                    table.table
+                ),
+                [campaign_id.clone().into()],
+            ))
+            .await?;
+
+        for row in rows {
+            let id: String = row.try_get("", "id")?;
+            let name: Option<String> = row.try_get("", "display_name")?;
+            for &column in table.text_columns {
+                let Some(text) = row.try_get::<Option<String>>("", column)? else {
+                    continue;
+                };
+                for link in field_links(&text) {
+                    links_checked += 1;
+                    if resolves(&link, &ids, &targets) {
+                        continue;
+                    }
+                    dead_links.push(DeadLink {
+                        entity_type: table.entity_type.to_string(),
+                        entity_id: id.clone(),
+                        entity_name: name.clone(),
+                        field: column.to_string(),
+                        deleted_type: link.id.as_ref().and_then(|id| deleted.get(id).cloned()),
+                        suggestions: suggestions(&link, &targets),
+                        kind: link.kind,
+                        link: link.link,
+                    });
+                }
+            }
+        }
+    }
+
+    tracing::info!(
+        checked = links_checked,
+        dead = dead_links.len(),
+        "Checked campaign links"
+    );
+    Ok(LinkReport {
+        campaign_id,
+        links_checked,
+        dead_links,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_links(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<LinkReport, AppError> {
+    check_links_impl(&state.db, campaign_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_ids_needs_whole_uuids() {
+        let id = "0b5d6a3e-1c2f-4e8a-9b7d-3f6e2a1c4d5e";
+        let text = format!("see {id}, not f{id} or {}", &id[..30]);
+        assert_eq!(find_ids(&text), vec![id]);
+    }
+
+    #[test]
+    fn test_field_links_reads_markdown_and_editor_json() {
+        let id = "0b5d6a3e-1c2f-4e8a-9b7d-3f6e2a1c4d5e";
+        let links = field_links(&format!("Meet [[Mira]] and [[character:{id}:Bob]]."));
+        let kinds: Vec<(LinkKind, &str)> =
+            links.iter().map(|l| (l.kind, l.link.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (LinkKind::WikiLink, "Mira"),
+                (LinkKind::WikiLink, &format!("character:{id}:Bob")),
+            ]
+        );
+
+        let doc = serde_json::json!({
+            "type": "doc",
+            "content": [{"type": "paragraph", "content": [
+                {"type": "mention", "attrs": {"entityType": "location", "entityId": id, "label": "Keep"}},
+                {"type": "text", "text": "and [[Old Mill]]"}
+            ]}]
+        });
+        let links = field_links(&doc.to_string());
+        assert_eq!(links.len(), 2);
+        let mention = links.iter().find(|l| l.kind == LinkKind::Mention).unwrap();
+        assert_eq!(mention.entity_type.as_deref(), Some("location"));
+        assert_eq!(mention.label.as_deref(), Some("Keep"));
+    }
+}
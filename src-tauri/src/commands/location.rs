@@ -1,10 +1,13 @@
-use crate::commands::validation::CreateLocationInput;
+use crate::commands::references::remove_references;
+use crate::commands::validation::{CreateLocationInput, UpdateLocationInput};
 use crate::db::AppState;
 use crate::error::AppError;
 use ::entity::locations::{self, Entity as Location};
+use ::entity::organizations::Entity as Organization;
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,6 +19,12 @@ pub struct LocationResponse {
     pub location_type: String,
     pub description: Option<String>,
     pub gm_notes: Option<String>,
+    pub population: Option<i64>,
+    pub government_type: Option<String>,
+    pub dominant_organization_id: Option<String>,
+    pub economy_notes: Option<String>,
+    pub color: Option<String>,
+    pub icon: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -30,20 +39,47 @@ impl From<locations::Model> for LocationResponse {
             location_type: model.location_type,
             description: model.description,
             gm_notes: model.gm_notes,
+            population: model.population,
+            government_type: model.government_type,
+            dominant_organization_id: model.dominant_organization_id,
+            economy_notes: model.economy_notes,
+            color: model.color,
+            icon: model.icon,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
     }
 }
 
+async fn check_organization(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    organization_id: &str,
+) -> Result<(), AppError> {
+    let organization = Organization::find_by_id(organization_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Organization {} not found", organization_id)))?;
+    if organization.campaign_id != campaign_id {
+        return Err(AppError::Validation(
+            "dominant_organization_id: organization belongs to a different campaign".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 // ============ Core implementation functions (testable) ============
 
+#[instrument(skip_all, err)]
 pub async fn create_location_impl(
     db: &DatabaseConnection,
     input: CreateLocationInput,
 ) -> Result<LocationResponse, AppError> {
     // Validate input
     input.validate()?;
+    if let Some(organization_id) = &input.dominant_organization_id {
+        check_organization(db, &input.campaign_id, organization_id).await?;
+    }
 
     let id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now();
@@ -56,14 +92,23 @@ pub async fn create_location_impl(
         location_type: Set(input.location_type),
         description: Set(input.description),
         gm_notes: Set(None),
+        color: Set(None),
+        icon: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        population: Set(input.population),
+        government_type: Set(input.government_type),
+        dominant_organization_id: Set(input.dominant_organization_id),
+        economy_notes: Set(input.economy_notes),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     let result = model.insert(db).await?;
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_location_impl(
     db: &DatabaseConnection,
     id: String,
@@ -76,6 +121,7 @@ pub async fn get_location_impl(
     Ok(location.into())
 }
 
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn list_locations_impl(
     db: &DatabaseConnection,
     campaign_id: String,
@@ -89,6 +135,7 @@ pub async fn list_locations_impl(
     Ok(locations.into_iter().map(|l| l.into()).collect())
 }
 
+#[instrument(skip_all, fields(parent_id = %parent_id), err)]
 pub async fn get_location_children_impl(
     db: &DatabaseConnection,
     parent_id: String,
@@ -102,50 +149,75 @@ pub async fn get_location_children_impl(
     Ok(locations.into_iter().map(|l| l.into()).collect())
 }
 
+/// Fields left as `None` are unchanged; an empty `dominant_organization_id`
+/// clears it
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn update_location_impl(
     db: &DatabaseConnection,
     id: String,
-    name: Option<String>,
-    location_type: Option<String>,
-    parent_id: Option<String>,
-    description: Option<String>,
-    gm_notes: Option<String>,
+    input: UpdateLocationInput,
 ) -> Result<LocationResponse, AppError> {
+    input.validate()?;
+    input.validate_location_type()?;
+
     let location = Location::find_by_id(&id)
         .one(db)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Location {} not found", id)))?;
+    let dominant_organization_id = input
+        .dominant_organization_id
+        .map(|org| Some(org).filter(|org| !org.is_empty()));
+    if let Some(Some(organization_id)) = &dominant_organization_id {
+        check_organization(db, &location.campaign_id, organization_id).await?;
+    }
 
     let mut active: locations::ActiveModel = location.into();
 
-    if let Some(n) = name {
+    if let Some(n) = input.name {
         active.name = Set(n);
     }
-    if let Some(lt) = location_type {
+    if let Some(lt) = input.location_type {
         active.location_type = Set(lt);
     }
-    if let Some(pid) = parent_id {
+    if let Some(pid) = input.parent_id {
         active.parent_id = Set(Some(pid));
     }
-    if let Some(d) = description {
+    if let Some(d) = input.description {
         active.description = Set(Some(d));
     }
-    if let Some(gm) = gm_notes {
+    if let Some(gm) = input.gm_notes {
         active.gm_notes = Set(Some(gm));
     }
+    if let Some(p) = input.population {
+        active.population = Set(Some(p));
+    }
+    if let Some(g) = input.government_type {
+        active.government_type = Set(Some(g));
+    }
+    if let Some(org) = dominant_organization_id {
+        active.dominant_organization_id = Set(org);
+    }
+    if let Some(e) = input.economy_notes {
+        active.economy_notes = Set(Some(e));
+    }
     active.updated_at = Set(chrono::Utc::now());
 
     let result = active.update(db).await?;
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_location_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
-    let result = Location::delete_by_id(&id).exec(db).await?;
+    let txn = db.begin().await?;
+    remove_references(&txn, "location", &id).await?;
+    let result = Location::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
     Ok(result.rows_affected > 0)
 }
 
 // ============ Tauri command wrappers ============
 
+#[allow(clippy::too_many_arguments)]
 #[tauri::command(rename_all = "snake_case")]
 pub async fn create_location(
     state: State<'_, AppState>,
@@ -154,6 +226,10 @@ pub async fn create_location(
     location_type: Option<String>,
     parent_id: Option<String>,
     description: Option<String>,
+    population: Option<i64>,
+    government_type: Option<String>,
+    dominant_organization_id: Option<String>,
+    economy_notes: Option<String>,
 ) -> Result<LocationResponse, AppError> {
     let input = CreateLocationInput {
         campaign_id,
@@ -161,6 +237,10 @@ pub async fn create_location(
         location_type: location_type.unwrap_or_else(|| "settlement".to_string()),
         parent_id,
         description,
+        population,
+        government_type,
+        dominant_organization_id,
+        economy_notes,
     };
     create_location_impl(&state.db, input).await
 }
@@ -189,6 +269,7 @@ pub async fn get_location_children(
     get_location_children_impl(&state.db, parent_id).await
 }
 
+#[allow(clippy::too_many_arguments)]
 #[tauri::command(rename_all = "snake_case")]
 pub async fn update_location(
     state: State<'_, AppState>,
@@ -198,9 +279,23 @@ pub async fn update_location(
     parent_id: Option<String>,
     description: Option<String>,
     gm_notes: Option<String>,
+    population: Option<i64>,
+    government_type: Option<String>,
+    dominant_organization_id: Option<String>,
+    economy_notes: Option<String>,
 ) -> Result<LocationResponse, AppError> {
-    update_location_impl(&state.db, id, name, location_type, parent_id, description, gm_notes)
-        .await
+    let input = UpdateLocationInput {
+        name,
+        location_type,
+        parent_id,
+        description,
+        gm_notes,
+        population,
+        government_type,
+        dominant_organization_id,
+        economy_notes,
+    };
+    update_location_impl(&state.db, id, input).await
 }
 
 #[tauri::command(rename_all = "snake_case")]
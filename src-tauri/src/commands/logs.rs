@@ -0,0 +1,32 @@
+use crate::error::AppError;
+use crate::logging::{self, LogState, LOG_LEVELS};
+use tauri::State;
+
+/// Number of lines returned when the caller doesn't ask for a specific amount
+const DEFAULT_LOG_LINES: usize = 200;
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_recent_logs(
+    state: State<'_, LogState>,
+    lines: Option<usize>,
+) -> Result<Vec<String>, AppError> {
+    logging::read_recent_lines(&state.log_dir, lines.unwrap_or(DEFAULT_LOG_LINES))
+        .map_err(|e| AppError::Internal(format!("Failed to read log file: {}", e)))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_log_level(state: State<'_, LogState>, level: String) -> Result<(), AppError> {
+    let level = level.to_lowercase();
+    if !LOG_LEVELS.contains(&level.as_str()) {
+        return Err(AppError::Validation(format!(
+            "level: must be one of: {}",
+            LOG_LEVELS.join(", ")
+        )));
+    }
+
+    state.set_filter(&level).map_err(AppError::Internal)?;
+    tracing::info!(level = %level, "Log level changed");
+    Ok(())
+}
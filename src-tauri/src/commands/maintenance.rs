@@ -0,0 +1,540 @@
+use crate::commands::json_schema::{find_invalid_json_fields_impl, InvalidJsonField};
+use crate::commands::registry::{searchable_tables, ENTITY_TABLES};
+use crate::db::{
+    app_version, check_schema_compatibility, database_path, load_database_settings,
+    recorded_app_version, save_database_settings, AppState, DatabaseSettings, RefusedDatabase,
+};
+use crate::error::AppError;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::{AppHandle, Manager, State};
+use tracing::instrument;
+
+/// Folder in the app data directory that automatic backups go to
+const BACKUP_DIR: &str = "backups";
+
+/// Search index drift for a single entity type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchIndexIssue {
+    pub entity_type: String,
+    /// Rows present in the source table but absent from the index
+    pub missing: i64,
+    /// Index entries whose source row no longer exists
+    pub orphaned: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub ok: bool,
+    /// Raw output of `PRAGMA integrity_check` ("ok" when healthy)
+    pub integrity_check: Vec<String>,
+    pub foreign_key_violations: i64,
+    pub search_index_issues: Vec<SearchIndexIssue>,
+    /// Stored JSON columns that no longer match their schema
+    pub invalid_json_fields: Vec<InvalidJsonField>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OptimizeReport {
+    pub size_before_bytes: i64,
+    pub size_after_bytes: i64,
+    pub duration_ms: u64,
+}
+
+/// Pragmas as reported by the live connection
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActivePragmas {
+    pub journal_mode: String,
+    pub synchronous: String,
+    pub busy_timeout_ms: i64,
+    pub foreign_keys: bool,
+}
+
+/// What the search triggers index for each searchable table, as
+/// `(table, entity_type, name, content)` SQL over the table's own columns.
+/// Keep these in step with the `{table}_ai` and `{table}_au` triggers, which
+/// index new and changed rows the same way.
+const SEARCH_INDEX_SOURCES: &[(&str, &str, &str, &str)] = &[
+    (
+        "characters",
+        "character",
+        "name",
+        "COALESCE(description, '') || ' ' || COALESCE(personality, '') || ' ' || \
+         COALESCE(motivations, '') || ' ' || COALESCE((SELECT group_concat(value, ' ') \
+         FROM json_each(CASE WHEN json_valid(aliases_json) THEN aliases_json END)), '')",
+    ),
+    ("locations", "location", "name", "COALESCE(description, '')"),
+    (
+        "organizations",
+        "organization",
+        "name",
+        "COALESCE(description, '') || ' ' || COALESCE(goals, '')",
+    ),
+    (
+        "quests",
+        "quest",
+        "name",
+        "COALESCE(description, '') || ' ' || COALESCE(hook, '') || ' ' || \
+         COALESCE(objectives, '')",
+    ),
+    (
+        "heroes",
+        "hero",
+        "name",
+        "COALESCE(description, '') || ' ' || COALESCE(backstory, '')",
+    ),
+    ("items", "item", "name", "COALESCE(description, '')"),
+    (
+        "sessions",
+        "session",
+        "COALESCE(title, 'Session ' || session_number)",
+        "COALESCE(notes, '') || ' ' || COALESCE(summary, '')",
+    ),
+    ("handouts", "handout", "title", "COALESCE(content, '')"),
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RebuildIndexReport {
+    /// Rows in the search index after the rebuild
+    pub indexed: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupReport {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Schema migrations by name, oldest first
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MigrationStatus {
+    pub applied: Vec<String>,
+    /// Known to this build but not yet run; normally empty once the app is up
+    pub pending: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RollbackReport {
+    pub rolled_back: String,
+    /// Copy of the database taken just before rolling back
+    pub backup: BackupReport,
+    pub status: MigrationStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DbInfo {
+    pub path: String,
+    pub size_bytes: i64,
+    /// Version of the running app
+    pub app_version: String,
+    /// Version recorded as the last to open the database
+    pub written_by: Option<String>,
+    /// Name of the latest applied migration
+    pub schema_version: Option<String>,
+    pub migrations_applied: usize,
+    /// Rows per entity type across all campaigns, plus campaigns themselves
+    pub entity_counts: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatabaseSettingsResponse {
+    /// Saved settings, applied when the app next starts
+    pub saved: DatabaseSettings,
+    pub active: ActivePragmas,
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, err)]
+pub async fn check_database_integrity_impl(
+    db: &DatabaseConnection,
+) -> Result<IntegrityReport, AppError> {
+    let backend = db.get_database_backend();
+
+    let integrity_check: Vec<String> = db
+        .query_all(Statement::from_string(backend, "PRAGMA integrity_check"))
+        .await?
+        .into_iter()
+        .filter_map(|row| row.try_get_by_index::<String>(0).ok())
+        .collect();
+
+    let foreign_key_violations = db
+        .query_all(Statement::from_string(backend, "PRAGMA foreign_key_check"))
+        .await?
+        .len() as i64;
+
+    let mut search_index_issues = Vec::new();
+    for table in searchable_tables() {
+        let row = db
+            .query_one(Statement::from_sql_and_values(
+                backend,
+                format!(
+                    r#"
+                    SELECT
+                        (SELECT COUNT(*) FROM {table} t
+                         WHERE NOT EXISTS (
+                             SELECT 1 FROM search_index s
+                             WHERE s.entity_type = $1 AND s.entity_id = t.id
+                         )) as missing,
+                        (SELECT COUNT(*) FROM search_index s
+                         WHERE s.entity_type = $1
+                         AND NOT EXISTS (SELECT 1 FROM {table} t WHERE t.id = s.entity_id)
+                        ) as orphaned
+                    "#,
+                    table = table.table,
+                ),
+                [table.entity_type.into()],
+            ))
+            .await?
+            .ok_or_else(|| AppError::Internal("Search index check returned no rows".into()))?;
+
+        let missing: i64 = row.try_get("", "missing")?;
+        let orphaned: i64 = row.try_get("", "orphaned")?;
+        if missing > 0 || orphaned > 0 {
+            search_index_issues.push(SearchIndexIssue {
+                entity_type: table.entity_type.to_string(),
+                missing,
+                orphaned,
+            });
+        }
+    }
+
+    let invalid_json_fields = find_invalid_json_fields_impl(db).await?;
+
+    let ok = integrity_check == ["ok"]
+        && foreign_key_violations == 0
+        && search_index_issues.is_empty()
+        && invalid_json_fields.is_empty();
+
+    if !ok {
+        tracing::warn!(
+            foreign_key_violations,
+            search_index_issues = search_index_issues.len(),
+            invalid_json_fields = invalid_json_fields.len(),
+            "Database integrity check found problems"
+        );
+    }
+
+    Ok(IntegrityReport {
+        ok,
+        integrity_check,
+        foreign_key_violations,
+        search_index_issues,
+        invalid_json_fields,
+    })
+}
+
+#[instrument(skip_all, err)]
+pub async fn optimize_database_impl(db: &DatabaseConnection) -> Result<OptimizeReport, AppError> {
+    let started = std::time::Instant::now();
+    let size_before_bytes = database_size(db).await?;
+
+    // Merge FTS segments, refresh planner statistics, then reclaim free pages
+    db.execute_unprepared("INSERT INTO search_index(search_index) VALUES('optimize')")
+        .await?;
+    db.execute_unprepared("ANALYZE").await?;
+    db.execute_unprepared("VACUUM").await?;
+
+    let size_after_bytes = database_size(db).await?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    tracing::info!(
+        size_before_bytes,
+        size_after_bytes,
+        duration_ms,
+        "Database optimized"
+    );
+
+    Ok(OptimizeReport {
+        size_before_bytes,
+        size_after_bytes,
+        duration_ms,
+    })
+}
+
+/// Drop and repopulate the search index from the source tables. Only the
+/// index is written, so locked campaigns and row authorship are left alone.
+#[instrument(skip_all, err)]
+pub async fn rebuild_search_index_impl(
+    db: &DatabaseConnection,
+) -> Result<RebuildIndexReport, AppError> {
+    let txn = db.begin().await?;
+    txn.execute_unprepared("DELETE FROM search_index").await?;
+    for (table, entity_type, name, content) in SEARCH_INDEX_SOURCES {
+        txn.execute_unprepared(&format!(
+            "INSERT INTO search_index(entity_type, entity_id, campaign_id, name, content) \
+             SELECT '{entity_type}', id, campaign_id, {name}, {content} FROM {table}"
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+
+    let row = db
+        .query_one(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT COUNT(*) as indexed FROM search_index",
+        ))
+        .await?
+        .ok_or_else(|| AppError::Internal("Search index count returned no rows".into()))?;
+    let indexed: i64 = row.try_get("", "indexed")?;
+
+    tracing::info!(indexed, "Search index rebuilt");
+    Ok(RebuildIndexReport { indexed })
+}
+
+/// Write a consistent copy of the database to `path`, which must not exist
+#[instrument(skip_all, err)]
+pub async fn backup_database_impl(
+    db: &DatabaseConnection,
+    path: String,
+) -> Result<BackupReport, AppError> {
+    if std::path::Path::new(&path).exists() {
+        return Err(AppError::Conflict(format!("{} already exists", path)));
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "VACUUM INTO $1",
+        [path.clone().into()],
+    ))
+    .await?;
+
+    let size_bytes = std::fs::metadata(&path)
+        .map(|m| m.len())
+        .map_err(|e| AppError::Internal(format!("Backup was not written: {}", e)))?;
+
+    tracing::info!(%path, size_bytes, "Database backed up");
+    Ok(BackupReport { path, size_bytes })
+}
+
+/// Fails with `IncompatibleDatabase` for a database from a newer app, whose
+/// migrations this build can't list
+#[instrument(skip_all, err)]
+pub async fn get_migration_status_impl(
+    db: &DatabaseConnection,
+) -> Result<MigrationStatus, AppError> {
+    check_schema_compatibility(db).await?;
+    let applied = Migrator::get_applied_migrations(db)
+        .await?
+        .iter()
+        .map(|m| m.name().to_string())
+        .collect();
+    let pending = Migrator::get_pending_migrations(db)
+        .await?
+        .iter()
+        .map(|m| m.name().to_string())
+        .collect();
+    Ok(MigrationStatus { applied, pending })
+}
+
+/// Undo the most recently applied migration, after backing the database up
+/// to `backup_path`. `expected` must name that migration, so a stale screen
+/// can't roll back one the user didn't see. The app re-applies pending
+/// migrations when it next starts, so this is for recovering from a failed
+/// upgrade rather than for staying on an older schema.
+#[instrument(skip_all, fields(expected = %expected), err)]
+pub async fn rollback_last_migration_impl(
+    db: &DatabaseConnection,
+    expected: String,
+    backup_path: String,
+) -> Result<RollbackReport, AppError> {
+    let last = get_migration_status_impl(db)
+        .await?
+        .applied
+        .pop()
+        .ok_or_else(|| AppError::Validation("No migrations have been applied".to_string()))?;
+    if last != expected {
+        return Err(AppError::Conflict(format!(
+            "The last applied migration is {}, not {}",
+            last, expected
+        )));
+    }
+
+    let backup = backup_database_impl(db, backup_path).await?;
+    Migrator::down(db, Some(1)).await?;
+    let status = get_migration_status_impl(db).await?;
+
+    tracing::warn!(migration = %last, backup = %backup.path, "Rolled back migration");
+    Ok(RollbackReport {
+        rolled_back: last,
+        backup,
+        status,
+    })
+}
+
+#[instrument(skip_all, err)]
+pub async fn get_db_info_impl(db: &DatabaseConnection, path: String) -> Result<DbInfo, AppError> {
+    let backend = db.get_database_backend();
+    let mut entity_counts = BTreeMap::new();
+    let tables = std::iter::once(("campaign", "campaigns"))
+        .chain(ENTITY_TABLES.iter().map(|t| (t.entity_type, t.table)));
+    for (entity_type, table) in tables {
+        let row = db
+            .query_one(Statement::from_string(
+                backend,
+                format!("SELECT COUNT(*) AS count FROM {}", table),
+            ))
+            .await?
+            .ok_or_else(|| AppError::Internal(format!("Counting {} returned no rows", table)))?;
+        entity_counts.insert(entity_type.to_string(), row.try_get("", "count")?);
+    }
+
+    let applied = get_migration_status_impl(db).await?.applied;
+    Ok(DbInfo {
+        path,
+        size_bytes: database_size(db).await?,
+        app_version: app_version().to_string(),
+        written_by: recorded_app_version(db).await?,
+        schema_version: applied.last().cloned(),
+        migrations_applied: applied.len(),
+        entity_counts,
+    })
+}
+
+#[instrument(skip_all, err)]
+pub async fn get_active_pragmas_impl(db: &DatabaseConnection) -> Result<ActivePragmas, AppError> {
+    let journal_mode: String = pragma_value(db, "journal_mode").await?;
+    let synchronous: i64 = pragma_value(db, "synchronous").await?;
+    let busy_timeout_ms: i64 = pragma_value(db, "busy_timeout").await?;
+    let foreign_keys: i64 = pragma_value(db, "foreign_keys").await?;
+
+    let synchronous = match synchronous {
+        0 => "off",
+        1 => "normal",
+        2 => "full",
+        3 => "extra",
+        _ => "unknown",
+    };
+
+    Ok(ActivePragmas {
+        journal_mode: journal_mode.to_lowercase(),
+        synchronous: synchronous.to_string(),
+        busy_timeout_ms,
+        foreign_keys: foreign_keys == 1,
+    })
+}
+
+async fn pragma_value<T: TryGetable>(db: &DatabaseConnection, pragma: &str) -> Result<T, AppError> {
+    let row = db
+        .query_one(Statement::from_string(
+            db.get_database_backend(),
+            format!("PRAGMA {}", pragma),
+        ))
+        .await?
+        .ok_or_else(|| AppError::Internal(format!("PRAGMA {} returned no rows", pragma)))?;
+
+    Ok(row.try_get_by_index(0)?)
+}
+
+async fn database_size(db: &DatabaseConnection) -> Result<i64, AppError> {
+    let row = db
+        .query_one(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT page_count * page_size as size FROM pragma_page_count(), pragma_page_size()",
+        ))
+        .await?
+        .ok_or_else(|| AppError::Internal("Could not read database size".into()))?;
+
+    Ok(row.try_get("", "size")?)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_database_integrity(
+    state: State<'_, AppState>,
+) -> Result<IntegrityReport, AppError> {
+    check_database_integrity_impl(&state.db).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn optimize_database(state: State<'_, AppState>) -> Result<OptimizeReport, AppError> {
+    optimize_database_impl(&state.db).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn rebuild_search_index(
+    state: State<'_, AppState>,
+) -> Result<RebuildIndexReport, AppError> {
+    rebuild_search_index_impl(&state.db).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn backup_database(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<BackupReport, AppError> {
+    backup_database_impl(&state.db, path).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_migration_status(state: State<'_, AppState>) -> Result<MigrationStatus, AppError> {
+    get_migration_status_impl(&state.db).await
+}
+
+/// Works before the database is open, so the frontend can ask it why: a
+/// database from a newer app fails with `INCOMPATIBLE_DATABASE`
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_db_info(app: AppHandle) -> Result<DbInfo, AppError> {
+    if let Some(refused) = app.try_state::<RefusedDatabase>() {
+        return Err(AppError::IncompatibleDatabase(refused.0.clone()));
+    }
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| AppError::Conflict("Database is locked".to_string()))?;
+    let path = database_path(&app)?.to_string_lossy().to_string();
+    get_db_info_impl(&state.db, path).await
+}
+
+/// Roll back the last migration, backing up to the app's backup folder first
+#[tauri::command(rename_all = "snake_case")]
+pub async fn rollback_last_migration(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    expected: String,
+) -> Result<RollbackReport, AppError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {}", e)))?
+        .join(BACKUP_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::Internal(format!("Failed to create backup folder: {}", e)))?;
+    let backup_path = dir.join(format!(
+        "before-rollback-{}.db",
+        chrono::Utc::now().format("%Y%m%d%H%M%S")
+    ));
+    rollback_last_migration_impl(
+        &state.db,
+        expected,
+        backup_path.to_string_lossy().to_string(),
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_database_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<DatabaseSettingsResponse, AppError> {
+    Ok(DatabaseSettingsResponse {
+        saved: load_database_settings(&app),
+        active: get_active_pragmas_impl(&state.db).await?,
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_database_settings(
+    app: AppHandle,
+    settings: DatabaseSettings,
+) -> Result<DatabaseSettings, AppError> {
+    settings.validate()?;
+    save_database_settings(&app, &settings)?;
+    tracing::info!(
+        ?settings,
+        "Database settings saved; they apply on next launch"
+    );
+    Ok(settings)
+}
@@ -0,0 +1,81 @@
+//! Rendering entity text for display. Links are resolved against the
+//! campaign: every named entity for the GM, only what the player view shows
+//! for players.
+
+use crate::commands::registry::ENTITY_TABLES;
+use crate::commands::share::player_visible_entities;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::markdown::{LinkTarget, RenderMode, RenderedMarkdown};
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use tauri::State;
+use tracing::instrument;
+
+/// Every named entity in the campaign
+pub(crate) async fn gm_targets(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+) -> Result<Vec<LinkTarget>, AppError> {
+    Campaign::find_by_id(campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let mut targets = Vec::new();
+    for table in ENTITY_TABLES {
+        let Some(name_column) = table.name_column else {
+            continue;
+        };
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!(
+                    "SELECT id, {} AS name FROM {} WHERE campaign_id = $1",
+                    name_column, table.table
+                ),
+                [campaign_id.into()],
+            ))
+            .await?;
+        for row in rows {
+            targets.push(LinkTarget {
+                entity_type: table.entity_type.to_string(),
+                id: row.try_get("", "id")?,
+                name: row
+                    .try_get::<Option<String>>("", "name")?
+                    .unwrap_or_default(),
+            });
+        }
+    }
+    Ok(targets)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Render markdown to sanitized HTML, with `[[links]]` resolved to deep
+/// links into the campaign
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn render_markdown_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    text: String,
+    mode: RenderMode,
+) -> Result<RenderedMarkdown, AppError> {
+    let targets = match mode {
+        RenderMode::Gm => gm_targets(db, &campaign_id).await?,
+        RenderMode::Player => player_visible_entities(db, &campaign_id, None).await?,
+    };
+    Ok(crate::markdown::render_markdown(&text, mode, &targets))
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn render_markdown(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    text: String,
+    mode: RenderMode,
+) -> Result<RenderedMarkdown, AppError> {
+    render_markdown_impl(&state.db, campaign_id, text, mode).await
+}
@@ -0,0 +1,805 @@
+//! Import a folder of markdown notes, such as an Obsidian vault, as campaign
+//! entities. Each `.md` file becomes one entity: frontmatter gives its type,
+//! tags and relationships, and the rest of the note its description.
+//!
+//! Only the frontmatter notes tend to use is understood: `key: value` lines,
+//! inline lists (`tags: [port, trade]`) and block lists of `- item` lines.
+//! Relationships are listed as `- ally: "[[Gandalf]]"`, or just the target
+//! for a plain `related` link.
+
+use crate::commands::character::create_character_impl;
+use crate::commands::item::create_item_impl;
+use crate::commands::location::create_location_impl;
+use crate::commands::organization::create_organization_impl;
+use crate::commands::quest::create_quest_impl;
+use crate::commands::registry::table_for;
+use crate::commands::relationship::create_relationship_impl;
+use crate::commands::tag::{add_entity_tag_impl, create_tag_impl};
+use crate::commands::validation::{
+    CreateCharacterInput, CreateItemInput, CreateLocationInput, CreateOrganizationInput,
+    CreateQuestInput, ITEM_RARITIES, ITEM_TYPES, LOCATION_TYPES, ORG_TYPES, PLOT_TYPES,
+    QUEST_STATUS,
+};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::tags::{self, Entity as Tag};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+/// Entity types a note can become
+pub const MARKDOWN_IMPORT_TYPES: &[&str] =
+    &["character", "location", "organization", "quest", "item"];
+
+/// Relationship type for targets listed without one
+const DEFAULT_RELATIONSHIP_TYPE: &str = "related";
+
+/// One note in the folder and what became of it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkdownImportEntry {
+    /// Path relative to the imported folder
+    pub path: String,
+    pub entity_type: String,
+    pub name: String,
+    /// `create` in a dry run, `created`, or `exists` when the campaign
+    /// already has an entity of that type and name, which is left alone
+    pub status: String,
+    /// None for entities a dry run would create
+    pub entity_id: Option<String>,
+}
+
+/// Something in a note that was skipped or guessed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkdownImportWarning {
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkdownImportReport {
+    /// False for a dry run
+    pub applied: bool,
+    pub files_scanned: usize,
+    pub entities: Vec<MarkdownImportEntry>,
+    /// Relationships created, or that would be
+    pub relationships: usize,
+    /// Tags put on entities, or that would be
+    pub tags: usize,
+    pub warnings: Vec<MarkdownImportWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FieldValue {
+    Text(String),
+    List(Vec<String>),
+}
+
+impl FieldValue {
+    fn items(&self) -> Vec<String> {
+        match self {
+            FieldValue::Text(text) => vec![text.clone()],
+            FieldValue::List(items) => items.clone(),
+        }
+    }
+}
+
+/// Split a note into its frontmatter block, if it has one, and the body
+fn split_frontmatter(text: &str) -> (Option<&str>, &str) {
+    let Some(rest) = text.strip_prefix("---\n") else {
+        return (None, text);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if matches!(line.trim_end(), "---" | "...") {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, text)
+}
+
+/// Read frontmatter fields, keyed by lowercased name. Nested maps are
+/// skipped; everything else is kept as raw text for the caller to clean.
+fn parse_frontmatter(block: &str) -> HashMap<String, FieldValue> {
+    let mut fields = HashMap::new();
+    // Key whose block list the following `- item` lines belong to
+    let mut list_key: Option<String> = None;
+
+    for line in block.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-') {
+            if let Some(FieldValue::List(items)) = list_key.as_ref().and_then(|k| fields.get_mut(k))
+            {
+                let item = item.trim();
+                if !item.is_empty() {
+                    items.push(item.to_string());
+                }
+            }
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+
+        list_key = None;
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+        let inline_list = value
+            .strip_prefix('[')
+            .and_then(|v| v.strip_suffix(']'))
+            .filter(|_| !value.starts_with("[["));
+        if value.is_empty() {
+            fields.insert(key.clone(), FieldValue::List(Vec::new()));
+            list_key = Some(key);
+        } else if let Some(inner) = inline_list {
+            let items = inner
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect();
+            fields.insert(key, FieldValue::List(items));
+        } else {
+            fields.insert(key, FieldValue::Text(value.to_string()));
+        }
+    }
+    fields
+}
+
+/// Strip quotes and `[[link]]` brackets, keeping a link's target rather
+/// than its `|alias`
+fn clean_value(raw: &str) -> String {
+    let value = raw.trim();
+    let value = ['"', '\'']
+        .iter()
+        .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)))
+        .unwrap_or(value)
+        .trim();
+    let value = value
+        .strip_prefix("[[")
+        .and_then(|v| v.strip_suffix("]]"))
+        .map(|link| link.split('|').next().unwrap_or(link))
+        .unwrap_or(value);
+    value.trim().to_string()
+}
+
+/// The entity type a word names, allowing plurals and a few common folder
+/// names, e.g. `NPCs` or `Factions`
+fn entity_type_for(word: &str) -> Option<&'static str> {
+    let word = word.trim().to_lowercase();
+    let singular = word.strip_suffix('s').unwrap_or(&word);
+    match singular {
+        "npc" | "people" => Some("character"),
+        "faction" => Some("organization"),
+        _ => MARKDOWN_IMPORT_TYPES
+            .iter()
+            .find(|t| **t == word || **t == singular)
+            .copied(),
+    }
+}
+
+/// A relationship list item: `type: target`, or a bare target
+fn parse_relationship(item: &str) -> (String, String) {
+    let item = clean_value(item);
+    match item.split_once(':') {
+        Some((kind, target)) if !kind.contains('[') => (
+            kind.trim().to_lowercase().replace([' ', '-'], "_"),
+            clean_value(target),
+        ),
+        _ => (DEFAULT_RELATIONSHIP_TYPE.to_string(), clean_value(&item)),
+    }
+}
+
+/// Input to create the entity a note describes
+pub(crate) enum NewEntity {
+    Character(CreateCharacterInput),
+    Location(CreateLocationInput),
+    Organization(CreateOrganizationInput),
+    Quest(CreateQuestInput),
+    Item(CreateItemInput),
+}
+
+impl NewEntity {
+    /// An entity of one of `MARKDOWN_IMPORT_TYPES` with only a name and
+    /// description, its other fields left at their defaults
+    pub(crate) fn basic(
+        entity_type: &str,
+        campaign_id: &str,
+        name: String,
+        description: Option<String>,
+    ) -> Self {
+        let mut warnings = Vec::new();
+        NoteFields {
+            path: "",
+            fields: HashMap::new(),
+            warnings: &mut warnings,
+        }
+        .entity(entity_type, campaign_id, name, description)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), AppError> {
+        match self {
+            NewEntity::Character(input) => input.validate(),
+            NewEntity::Location(input) => input.validate(),
+            NewEntity::Organization(input) => input.validate(),
+            NewEntity::Quest(input) => input.validate(),
+            NewEntity::Item(input) => input.validate(),
+        }
+        .map_err(AppError::from)
+    }
+
+    pub(crate) async fn create(self, db: &DatabaseConnection) -> Result<String, AppError> {
+        Ok(match self {
+            NewEntity::Character(input) => create_character_impl(db, input).await?.id,
+            NewEntity::Location(input) => create_location_impl(db, input).await?.id,
+            NewEntity::Organization(input) => create_organization_impl(db, input).await?.id,
+            NewEntity::Quest(input) => create_quest_impl(db, input).await?.id,
+            NewEntity::Item(input) => create_item_impl(db, input).await?.id,
+        })
+    }
+}
+
+/// A note read and checked, ready to create
+struct Note {
+    path: String,
+    entity_type: &'static str,
+    name: String,
+    /// Name of the parent location, for locations
+    parent: Option<String>,
+    tags: Vec<String>,
+    relationships: Vec<(String, String)>,
+}
+
+/// Builds one note's entity input, noting fields it had to ignore
+struct NoteFields<'a> {
+    path: &'a str,
+    fields: HashMap<String, FieldValue>,
+    warnings: &'a mut Vec<MarkdownImportWarning>,
+}
+
+impl NoteFields<'_> {
+    fn text(&self, key: &str) -> Option<String> {
+        match self.fields.get(key)? {
+            FieldValue::Text(text) => Some(clean_value(text)).filter(|t| !t.is_empty()),
+            FieldValue::List(_) => None,
+        }
+    }
+
+    fn list(&self, key: &str) -> Vec<String> {
+        self.fields
+            .get(key)
+            .map(FieldValue::items)
+            .unwrap_or_default()
+    }
+
+    /// A field limited to `allowed` values, falling back to `default`
+    fn choice(&mut self, key: &str, allowed: &[&str], default: &str) -> String {
+        match self.text(key) {
+            Some(value) if allowed.contains(&value.to_lowercase().as_str()) => value.to_lowercase(),
+            Some(value) => {
+                self.warnings.push(MarkdownImportWarning {
+                    path: self.path.to_string(),
+                    message: format!(
+                        "{} \"{}\" is not one of: {}; using {}",
+                        key,
+                        value,
+                        allowed.join(", "),
+                        default
+                    ),
+                });
+                default.to_string()
+            }
+            None => default.to_string(),
+        }
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        self.text(key)
+            .is_some_and(|v| matches!(v.to_lowercase().as_str(), "true" | "yes"))
+    }
+
+    fn entity(
+        &mut self,
+        entity_type: &str,
+        campaign_id: &str,
+        name: String,
+        description: Option<String>,
+    ) -> NewEntity {
+        let campaign_id = campaign_id.to_string();
+        match entity_type {
+            "character" => NewEntity::Character(CreateCharacterInput {
+                name,
+                campaign_id,
+                lineage: self.text("lineage"),
+                occupation: self.text("occupation"),
+                description,
+                personality: None,
+                motivations: None,
+                secrets: None,
+                voice_notes: None,
+            }),
+            "location" => NewEntity::Location(CreateLocationInput {
+                name,
+                campaign_id,
+                location_type: self.choice("location_type", LOCATION_TYPES, "settlement"),
+                parent_id: None,
+                description,
+                population: self.text("population").and_then(|p| p.parse().ok()),
+                government_type: self.text("government_type"),
+                dominant_organization_id: None,
+                economy_notes: None,
+            }),
+            "organization" => NewEntity::Organization(CreateOrganizationInput {
+                name,
+                campaign_id,
+                org_type: self.choice("org_type", ORG_TYPES, "other"),
+                description,
+                goals: None,
+                resources: None,
+            }),
+            "quest" => NewEntity::Quest(CreateQuestInput {
+                name,
+                campaign_id,
+                plot_type: self.choice("plot_type", PLOT_TYPES, "side"),
+                status: self.choice("status", QUEST_STATUS, "planned"),
+                description,
+                hook: self.text("hook"),
+                objectives: None,
+            }),
+            _ => NewEntity::Item(CreateItemInput {
+                name,
+                campaign_id,
+                item_type: self.choice("item_type", ITEM_TYPES, "other"),
+                rarity: self
+                    .text("rarity")
+                    .map(|_| self.choice("rarity", ITEM_RARITIES, "common")),
+                description,
+                requires_attunement: self.flag("requires_attunement"),
+            }),
+        }
+    }
+}
+
+/// Every `.md` file under `dir`, in path order. Hidden folders such as
+/// `.obsidian` and `.trash` are skipped.
+fn collect_markdown_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            collect_markdown_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("md"))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Read one note. Notes without a known type, from frontmatter or a folder
+/// like `Characters/`, are skipped with a warning.
+fn read_note(
+    root: &Path,
+    file: &Path,
+    campaign_id: &str,
+    warnings: &mut Vec<MarkdownImportWarning>,
+) -> Option<(Note, NewEntity)> {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let path = relative.to_string_lossy().replace('\\', "/");
+    let warn = |warnings: &mut Vec<MarkdownImportWarning>, message: String| {
+        warnings.push(MarkdownImportWarning {
+            path: path.clone(),
+            message,
+        })
+    };
+
+    let text = match std::fs::read_to_string(file) {
+        Ok(text) => text.replace("\r\n", "\n"),
+        Err(e) => {
+            warn(warnings, format!("could not be read: {}", e));
+            return None;
+        }
+    };
+    let (frontmatter, body) = split_frontmatter(text.trim_start_matches('\u{feff}'));
+    let fields = frontmatter.map(parse_frontmatter).unwrap_or_default();
+
+    let declared = match fields.get("type") {
+        Some(FieldValue::Text(t)) => Some(clean_value(t)),
+        _ => None,
+    };
+    let entity_type = match &declared {
+        Some(declared) => entity_type_for(declared),
+        None => relative
+            .parent()
+            .into_iter()
+            .flat_map(Path::ancestors)
+            .filter_map(|dir| dir.file_name())
+            .find_map(|dir| entity_type_for(&dir.to_string_lossy())),
+    };
+    let Some(entity_type) = entity_type else {
+        warn(
+            warnings,
+            match declared {
+                Some(declared) => format!(
+                    "type \"{}\" is not one of: {}; skipped",
+                    declared,
+                    MARKDOWN_IMPORT_TYPES.join(", ")
+                ),
+                None => "no type in frontmatter or folder name; skipped".to_string(),
+            },
+        );
+        return None;
+    };
+
+    // The name comes from frontmatter, a leading `# Heading`, or the file
+    let mut body = body.trim();
+    let heading = body
+        .lines()
+        .next()
+        .and_then(|l| l.strip_prefix("# "))
+        .map(|h| h.trim().to_string());
+    if heading.is_some() {
+        body = body.split_once('\n').map_or("", |(_, rest)| rest).trim();
+    }
+    let mut note_fields = NoteFields {
+        path: &path,
+        fields,
+        warnings,
+    };
+    let name = note_fields
+        .text("name")
+        .or_else(|| note_fields.text("title"))
+        .or(heading)
+        .unwrap_or_else(|| {
+            file.file_stem()
+                .map(|s| s.to_string_lossy().trim().to_string())
+                .unwrap_or_default()
+        });
+    let description = Some(body.to_string()).filter(|b| !b.is_empty());
+
+    let entity = note_fields.entity(entity_type, campaign_id, name.clone(), description);
+    let mut seen_tags = HashSet::new();
+    let tags = note_fields
+        .list("tags")
+        .iter()
+        .map(|t| clean_value(t).trim_start_matches('#').to_string())
+        .filter(|t| !t.is_empty() && seen_tags.insert(t.to_lowercase()))
+        .collect();
+    let relationships = note_fields
+        .list("relationships")
+        .iter()
+        .map(|r| parse_relationship(r))
+        .filter(|(kind, target)| !kind.is_empty() && !target.is_empty())
+        .collect();
+    let parent = note_fields.text("parent");
+
+    if let Err(e) = entity.validate() {
+        warn(note_fields.warnings, format!("{}; skipped", e));
+        return None;
+    }
+    let note = Note {
+        path,
+        entity_type,
+        name,
+        parent: parent.filter(|_| entity_type == "location"),
+        tags,
+        relationships,
+    };
+    Some((note, entity))
+}
+
+/// Ids of the campaign's existing importable entities, by type and
+/// lowercased name
+pub(crate) async fn existing_entities(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+) -> Result<HashMap<(&'static str, String), String>, AppError> {
+    let backend = db.get_database_backend();
+    let mut existing = HashMap::new();
+    for &entity_type in MARKDOWN_IMPORT_TYPES {
+        let Some(table) = table_for(entity_type) else {
+            continue;
+        };
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                backend,
+                format!(
+                    "SELECT id, {} AS name FROM {} WHERE campaign_id = $1",
+                    table.name_column.unwrap_or("id"),
+                    table.table
+                ),
+                [campaign_id.into()],
+            ))
+            .await?;
+        for row in rows {
+            let name: String = row.try_get("", "name")?;
+            existing.insert((entity_type, name.to_lowercase()), row.try_get("", "id")?);
+        }
+    }
+    Ok(existing)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Create an entity for every markdown note under `path`, then their tags
+/// and relationships. With `dry_run` nothing is written and the report
+/// shows what would be. Notes naming an entity the campaign already has are
+/// left out but can still be linked to.
+#[instrument(skip_all, fields(campaign_id = %campaign_id, dry_run = dry_run), err)]
+pub async fn import_markdown_folder_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    path: String,
+    dry_run: bool,
+) -> Result<MarkdownImportReport, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    let root = Path::new(&path);
+    if !root.is_dir() {
+        return Err(AppError::Validation(
+            "path: must be a folder of markdown files".to_string(),
+        ));
+    }
+
+    let mut files = Vec::new();
+    collect_markdown_files(root, &mut files)
+        .map_err(|e| AppError::Internal(format!("Failed to read {}: {}", path, e)))?;
+    let mut warnings = Vec::new();
+    let notes: Vec<(Note, NewEntity)> = files
+        .iter()
+        .filter_map(|file| read_note(root, file, &campaign_id, &mut warnings))
+        .collect();
+
+    // Entity ids by type and lowercased name; None for ones a dry run skips
+    let mut ids: HashMap<(&'static str, String), Option<String>> =
+        existing_entities(db, &campaign_id)
+            .await?
+            .into_iter()
+            .map(|(key, id)| (key, Some(id)))
+            .collect();
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+    let mut pending = Vec::new();
+    for (note, entity) in notes {
+        let key = (note.entity_type, note.name.to_lowercase());
+        if !seen.insert(key.clone()) {
+            warnings.push(MarkdownImportWarning {
+                message: format!(
+                    "another note is already the {} \"{}\"; skipped",
+                    note.entity_type, note.name
+                ),
+                path: note.path,
+            });
+        } else if let Some(id) = ids.get(&key) {
+            entries.push(MarkdownImportEntry {
+                path: note.path,
+                entity_type: note.entity_type.to_string(),
+                name: note.name,
+                status: "exists".to_string(),
+                entity_id: id.clone(),
+            });
+        } else {
+            ids.insert(key, None);
+            pending.push((note, entity));
+        }
+    }
+
+    // Locations wait for their parent, so parents are linked on creation
+    let mut created: Vec<(Note, Option<String>)> = Vec::new();
+    while !pending.is_empty() {
+        let waiting: HashSet<String> = pending
+            .iter()
+            .filter(|(n, _)| n.entity_type == "location")
+            .map(|(n, _)| n.name.to_lowercase())
+            .collect();
+        let (mut batch, rest): (Vec<_>, Vec<_>) = pending.into_iter().partition(|(n, _)| {
+            n.parent
+                .as_ref()
+                .is_none_or(|p| !waiting.contains(&p.to_lowercase()))
+        });
+        pending = rest;
+        if batch.is_empty() {
+            // The parents form a cycle; break it by dropping them
+            for (note, _) in &mut pending {
+                warnings.push(MarkdownImportWarning {
+                    path: note.path.clone(),
+                    message: "parent locations form a cycle; imported without a parent".to_string(),
+                });
+                note.parent = None;
+            }
+            batch = std::mem::take(&mut pending);
+        }
+
+        for (mut note, mut entity) in batch {
+            if let Some(parent) = note.parent.take() {
+                match ids.get(&("location", parent.to_lowercase())) {
+                    Some(parent_id) => {
+                        if let NewEntity::Location(input) = &mut entity {
+                            input.parent_id = parent_id.clone();
+                        }
+                    }
+                    None => warnings.push(MarkdownImportWarning {
+                        path: note.path.clone(),
+                        message: format!(
+                            "parent \"{}\" is not a location; imported without a parent",
+                            parent
+                        ),
+                    }),
+                }
+            }
+            let id = if dry_run {
+                None
+            } else {
+                Some(entity.create(db).await?)
+            };
+            ids.insert((note.entity_type, note.name.to_lowercase()), id.clone());
+            created.push((note, id));
+        }
+    }
+
+    let mut tag_ids: HashMap<String, String> = Tag::find()
+        .filter(tags::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| (t.name.to_lowercase(), t.id))
+        .collect();
+    let mut tag_count = 0;
+    let mut relationship_count = 0;
+
+    for (note, id) in &created {
+        for tag in &note.tags {
+            tag_count += 1;
+            let Some(entity_id) = id else {
+                continue;
+            };
+            let tag_id = match tag_ids.get(&tag.to_lowercase()) {
+                Some(tag_id) => tag_id.clone(),
+                None => {
+                    let created =
+                        create_tag_impl(db, campaign_id.clone(), tag.clone(), None).await?;
+                    tag_ids.insert(tag.to_lowercase(), created.id.clone());
+                    created.id
+                }
+            };
+            add_entity_tag_impl(db, tag_id, note.entity_type.to_string(), entity_id.clone())
+                .await?;
+        }
+
+        for (kind, target) in &note.relationships {
+            let target_key = target.to_lowercase();
+            let matches: Vec<(&str, &Option<String>)> = ids
+                .iter()
+                .filter(|((_, name), _)| *name == target_key)
+                .map(|((entity_type, _), id)| (*entity_type, id))
+                .collect();
+            let [(target_type, target_id)] = matches[..] else {
+                warnings.push(MarkdownImportWarning {
+                    path: note.path.clone(),
+                    message: if matches.is_empty() {
+                        format!("no note or entity named \"{}\" to link to", target)
+                    } else {
+                        format!("\"{}\" names more than one entity; not linked", target)
+                    },
+                });
+                continue;
+            };
+            relationship_count += 1;
+            if let (Some(source_id), Some(target_id)) = (id, target_id) {
+                create_relationship_impl(
+                    db,
+                    campaign_id.clone(),
+                    note.entity_type.to_string(),
+                    source_id.clone(),
+                    target_type.to_string(),
+                    target_id.clone(),
+                    kind.clone(),
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
+            }
+        }
+    }
+
+    entries.extend(created.into_iter().map(|(note, id)| MarkdownImportEntry {
+        path: note.path,
+        entity_type: note.entity_type.to_string(),
+        name: note.name,
+        status: if dry_run { "create" } else { "created" }.to_string(),
+        entity_id: id,
+    }));
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    if !dry_run {
+        tracing::info!(
+            entities = entries.len(),
+            relationships = relationship_count,
+            "Imported markdown folder"
+        );
+    }
+
+    Ok(MarkdownImportReport {
+        applied: !dry_run,
+        files_scanned: files.len(),
+        entities: entries,
+        relationships: relationship_count,
+        tags: tag_count,
+        warnings,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_markdown_folder(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    path: String,
+    dry_run: Option<bool>,
+) -> Result<MarkdownImportReport, AppError> {
+    import_markdown_folder_impl(&state.db, campaign_id, path, dry_run.unwrap_or(false)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_frontmatter_lists_and_links() {
+        let text = "---\ntype: Character\ntags: [ranger, \"dunedain\"]\nrelationships:\n  - ally: \"[[Gandalf|the Grey]]\"\n  - \"[[Bree]]\"\n---\n# Strider\nA ranger.\n";
+        let (frontmatter, body) = split_frontmatter(text);
+        assert_eq!(body, "# Strider\nA ranger.\n");
+        let fields = parse_frontmatter(frontmatter.unwrap());
+
+        assert_eq!(fields["type"], FieldValue::Text("Character".to_string()));
+        assert_eq!(
+            fields["tags"]
+                .items()
+                .iter()
+                .map(|t| clean_value(t))
+                .collect::<Vec<_>>(),
+            vec!["ranger", "dunedain"]
+        );
+        let relationships: Vec<_> = fields["relationships"]
+            .items()
+            .iter()
+            .map(|r| parse_relationship(r))
+            .collect();
+        assert_eq!(
+            relationships,
+            vec![
+                ("ally".to_string(), "Gandalf".to_string()),
+                ("related".to_string(), "Bree".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entity_type_for_folder_names() {
+        assert_eq!(entity_type_for("Characters"), Some("character"));
+        assert_eq!(entity_type_for("NPCs"), Some("character"));
+        assert_eq!(entity_type_for("Factions"), Some("organization"));
+        assert_eq!(entity_type_for("quest"), Some("quest"));
+        assert_eq!(entity_type_for("Sessions"), None);
+    }
+}
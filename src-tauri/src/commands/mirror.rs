@@ -0,0 +1,245 @@
+//! Per-campaign plaintext mirror settings, and the command that refreshes the
+//! mirror. The frontend calls `mirror_campaign` after each batch of edits;
+//! when nothing has changed since the last run it returns without touching
+//! the folder.
+
+use crate::commands::sync::get_changes_since_impl;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::export::load_bundle;
+use crate::mirror::{git, write_mirror};
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::mirror_state::{self, Entity as MirrorState};
+use chrono::Utc;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::State;
+use tracing::instrument;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MirrorSettingsResponse {
+    pub campaign_id: String,
+    pub enabled: bool,
+    pub path: Option<String>,
+    pub auto_commit: bool,
+    pub last_mirrored_at: Option<String>,
+}
+
+impl From<mirror_state::Model> for MirrorSettingsResponse {
+    fn from(state: mirror_state::Model) -> Self {
+        Self {
+            campaign_id: state.campaign_id,
+            enabled: state.enabled,
+            path: state.path,
+            auto_commit: state.auto_commit,
+            last_mirrored_at: state.last_mirrored_at.map(|ts| ts.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MirrorReport {
+    /// True when nothing had changed and the folder was left alone
+    pub up_to_date: bool,
+    /// Files written or removed, relative to the mirror folder
+    pub written: Vec<String>,
+    pub removed: Vec<String>,
+    /// Hash of the commit made, when auto-commit is on and files changed
+    pub commit: Option<String>,
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_mirror_settings_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<MirrorSettingsResponse, AppError> {
+    let state = MirrorState::find_by_id(&campaign_id).one(db).await?;
+    Ok(match state {
+        Some(state) => state.into(),
+        None => MirrorSettingsResponse {
+            campaign_id,
+            enabled: false,
+            path: None,
+            auto_commit: false,
+            last_mirrored_at: None,
+        },
+    })
+}
+
+/// Create or update a campaign's mirror settings. Pointing the mirror at a
+/// new folder makes the next run write everything.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn update_mirror_settings_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    enabled: bool,
+    path: Option<String>,
+    auto_commit: bool,
+) -> Result<MirrorSettingsResponse, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if enabled && path.is_none() {
+        return Err(AppError::Validation(
+            "path: required when the mirror is enabled".to_string(),
+        ));
+    }
+
+    let existing = MirrorState::find_by_id(&campaign_id).one(db).await?;
+    let now = Utc::now();
+
+    let state = match existing {
+        Some(existing) => {
+            let path_changed = existing.path != path;
+            let mut active: mirror_state::ActiveModel = existing.into();
+            active.enabled = Set(enabled);
+            active.path = Set(path);
+            active.auto_commit = Set(auto_commit);
+            if path_changed {
+                active.last_watermark = Set(None);
+                active.last_mirrored_at = Set(None);
+            }
+            active.updated_at = Set(now);
+            active.update(db).await?
+        }
+        None => {
+            let model = mirror_state::ActiveModel {
+                campaign_id: Set(campaign_id),
+                enabled: Set(enabled),
+                path: Set(path),
+                auto_commit: Set(auto_commit),
+                last_watermark: Set(None),
+                last_mirrored_at: Set(None),
+                updated_at: Set(now),
+            };
+            model.insert(db).await?
+        }
+    };
+
+    Ok(state.into())
+}
+
+/// Refresh the campaign's mirror folder if anything changed since the last
+/// run, or regardless when `force` is set, then commit it if auto-commit is
+/// on
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn mirror_campaign_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    force: bool,
+) -> Result<MirrorReport, AppError> {
+    let state = MirrorState::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .filter(|state| state.enabled)
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "Mirror is not enabled for campaign {}",
+                campaign_id
+            ))
+        })?;
+    let dir = state.path.clone().ok_or_else(|| {
+        AppError::Validation("path: required when the mirror is enabled".to_string())
+    })?;
+
+    // Taken before loading so edits made while writing are picked up next run
+    let watermark = match &state.last_watermark {
+        Some(since) => {
+            let changes = get_changes_since_impl(db, campaign_id.clone(), since.clone()).await?;
+            let unchanged = changes.created.is_empty()
+                && changes.updated.is_empty()
+                && changes.deleted.is_empty();
+            if unchanged && !force {
+                return Ok(MirrorReport {
+                    up_to_date: true,
+                    written: Vec::new(),
+                    removed: Vec::new(),
+                    commit: None,
+                });
+            }
+            changes.watermark
+        }
+        None => Utc::now().to_rfc3339(),
+    };
+
+    let bundle = load_bundle(db, &campaign_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    let root = Path::new(&dir);
+    let stats = write_mirror(root, &bundle)
+        .map_err(|e| AppError::Internal(format!("Failed to write mirror to {}: {}", dir, e)))?;
+
+    let commit = if state.auto_commit {
+        let message = format!(
+            "Mirror {} ({} written, {} removed)",
+            bundle.campaign.name,
+            stats.written.len(),
+            stats.removed.len()
+        );
+        git::commit_all(root, &message)?
+    } else {
+        None
+    };
+
+    let mut active: mirror_state::ActiveModel = state.into();
+    active.last_watermark = Set(Some(watermark));
+    active.last_mirrored_at = Set(Some(Utc::now()));
+    active.update(db).await?;
+
+    tracing::info!(
+        written = stats.written.len(),
+        removed = stats.removed.len(),
+        committed = commit.is_some(),
+        "Campaign mirrored"
+    );
+
+    Ok(MirrorReport {
+        up_to_date: false,
+        written: stats.written,
+        removed: stats.removed,
+        commit,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_mirror_settings(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<MirrorSettingsResponse, AppError> {
+    get_mirror_settings_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_mirror_settings(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    enabled: bool,
+    path: Option<String>,
+    auto_commit: Option<bool>,
+) -> Result<MirrorSettingsResponse, AppError> {
+    update_mirror_settings_impl(
+        &state.db,
+        campaign_id,
+        enabled,
+        path,
+        auto_commit.unwrap_or(false),
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn mirror_campaign(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    force: Option<bool>,
+) -> Result<MirrorReport, AppError> {
+    mirror_campaign_impl(&state.db, campaign_id, force.unwrap_or(false)).await
+}
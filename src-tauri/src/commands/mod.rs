@@ -1,19 +1,86 @@
+pub mod activity;
+pub mod ai_budget;
 pub mod ai_conversation;
+pub mod annotation;
+pub mod appearance;
+pub mod archive;
+pub mod attachment;
+pub mod authorship;
+pub mod bootstrap;
 pub mod campaign;
+pub mod campaign_settings;
 pub mod character;
+pub mod command_palette;
+pub mod content_stats;
+pub mod content_warning;
+pub mod conversion;
+pub mod delete_preview;
+pub mod detail_level;
+pub mod disposition;
+pub mod draft;
+pub mod duplicates;
+pub mod encounter;
+pub mod encryption;
+pub mod entity_refs;
+pub mod find_replace;
+pub mod gm_screen;
+pub mod handout;
 pub mod hero;
+pub mod inbox;
+pub mod inventory;
+pub mod item;
+pub mod journal_export;
+pub mod json_schema;
+pub mod kinship;
+pub mod link_check;
 pub mod location;
+pub mod logs;
+pub mod maintenance;
+pub mod markdown;
+pub mod markdown_import;
+pub mod mirror;
 pub mod organization;
+pub mod organization_goal;
+pub mod organization_holding;
 pub mod player;
+pub mod player_knowledge;
+pub mod player_recap;
+pub mod price_list;
+pub mod progress_clock;
+pub mod prompt_template;
 pub mod quest;
+pub mod quick_capture;
+pub mod quick_create;
+pub mod quick_note;
+pub mod recall_card;
+pub mod references;
+pub mod registry;
+pub mod related_entities;
 pub mod relationship;
+pub mod relationship_strength;
+pub mod relationship_suggestion;
+pub mod roleplay_card;
+pub mod scheduling;
 pub mod search;
+pub mod search_history;
 pub mod secret;
 pub mod session;
+pub mod session_clock;
+pub mod session_prep;
+pub mod session_transcript;
+pub mod share;
+pub mod snippet;
+pub mod srd;
+pub mod stat_block;
+pub mod sync;
 pub mod tag;
+pub mod tag_suggestion;
 pub mod timeline;
+pub mod timeline_branch;
 pub mod types;
 pub mod validation;
+pub mod whereabouts;
+pub mod worldbuilding_gaps;
 
 pub use types::*;
 pub use validation::*;
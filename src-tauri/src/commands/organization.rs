@@ -1,10 +1,14 @@
+use crate::commands::references::remove_references;
 use crate::commands::validation::CreateOrganizationInput;
 use crate::db::AppState;
 use crate::error::AppError;
+use ::entity::locations::{self, Entity as Location};
 use ::entity::organizations::{self, Entity as Organization};
+use sea_orm::sea_query::Expr;
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,6 +23,8 @@ pub struct OrganizationResponse {
     pub reputation: Option<String>,
     pub secrets: Option<String>,
     pub is_active: bool,
+    pub color: Option<String>,
+    pub icon: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -36,12 +42,15 @@ impl From<organizations::Model> for OrganizationResponse {
             reputation: model.reputation,
             secrets: model.secrets,
             is_active: model.is_active,
+            color: model.color,
+            icon: model.icon,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
     }
 }
 
+#[instrument(skip_all, err)]
 pub async fn create_organization_impl(
     db: &DatabaseConnection,
     input: CreateOrganizationInput,
@@ -63,8 +72,12 @@ pub async fn create_organization_impl(
         reputation: Set(None),
         secrets: Set(None),
         is_active: Set(true),
+        color: Set(None),
+        icon: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     let result = model.insert(db).await?;
@@ -93,6 +106,7 @@ pub async fn create_organization(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_organization(
     state: State<'_, AppState>,
     id: String,
@@ -106,6 +120,7 @@ pub async fn get_organization(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn list_organizations(
     state: State<'_, AppState>,
     campaign_id: String,
@@ -120,6 +135,7 @@ pub async fn list_organizations(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn update_organization(
     state: State<'_, AppState>,
     id: String,
@@ -170,7 +186,23 @@ pub async fn update_organization(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_organization(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
-    let result = Organization::delete_by_id(&id).exec(&state.db).await?;
+    let txn = state.db.begin().await?;
+    remove_references(&txn, "organization", &id).await?;
+    Location::update_many()
+        .col_expr(
+            locations::Column::DominantOrganizationId,
+            Expr::value(None::<String>),
+        )
+        .col_expr(
+            locations::Column::UpdatedAt,
+            Expr::value(chrono::Utc::now()),
+        )
+        .filter(locations::Column::DominantOrganizationId.eq(&id))
+        .exec(&txn)
+        .await?;
+    let result = Organization::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
     Ok(result.rows_affected > 0)
 }
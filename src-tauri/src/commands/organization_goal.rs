@@ -0,0 +1,597 @@
+//! What organizations are working toward. Each goal has a status and can
+//! carry a progress clock that the GM advances between sessions; every
+//! advance is logged so the "world moves on" report can sum up what the
+//! factions did off-screen since the table last met.
+
+use crate::commands::progress_clock::{
+    create_progress_clock_impl, tick_progress_clock_impl, ProgressClockResponse,
+};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::goal_advances::{self, Entity as GoalAdvance};
+use ::entity::organization_goals::{self, Entity as OrganizationGoal};
+use ::entity::organizations::{self, Entity as Organization};
+use ::entity::progress_clocks::{self, Entity as ProgressClock};
+use ::entity::sessions::{self, Entity as Session};
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::State;
+use tracing::instrument;
+
+/// Where a goal stands
+pub const GOAL_STATUSES: &[&str] = &["active", "achieved", "failed", "abandoned"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrganizationGoalResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub organization_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    /// The clock measuring progress, if the goal has one
+    pub clock: Option<ProgressClockResponse>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// One logged step: clock ticks, a status change, or both
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GoalAdvanceResponse {
+    pub id: String,
+    pub goal_id: String,
+    pub ticks: i32,
+    /// The clock's filled segments after this advance
+    pub filled: Option<i32>,
+    pub segments: Option<i32>,
+    /// The status the goal moved to, if it changed
+    pub status: Option<String>,
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+impl From<goal_advances::Model> for GoalAdvanceResponse {
+    fn from(model: goal_advances::Model) -> Self {
+        Self {
+            id: model.id,
+            goal_id: model.goal_id,
+            ticks: model.ticks,
+            filled: model.filled,
+            segments: model.segments,
+            status: model.status,
+            note: model.note,
+            created_at: model.created_at.to_string(),
+        }
+    }
+}
+
+/// What one goal did in the report's window
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GoalProgress {
+    pub goal_id: String,
+    pub title: String,
+    pub status: String,
+    /// Net ticks; negative after setbacks
+    pub ticks: i32,
+    pub filled: Option<i32>,
+    pub segments: Option<i32>,
+    /// Statuses the goal moved through, oldest first
+    pub status_changes: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FactionProgress {
+    pub organization_id: String,
+    pub organization_name: String,
+    pub goals: Vec<GoalProgress>,
+}
+
+/// Off-screen faction progress since a point in time
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorldMovesOnReport {
+    pub campaign_id: String,
+    /// Start of the window; `None` covers the whole campaign
+    pub since: Option<String>,
+    /// The session whose end opened the window, when it was picked by default
+    pub since_session_number: Option<i32>,
+    pub organizations: Vec<FactionProgress>,
+    /// One readable line per goal, for reading out or pasting into notes
+    pub lines: Vec<String>,
+}
+
+fn validate_title(title: &str) -> Result<(), AppError> {
+    if title.trim().is_empty() || title.chars().count() > 200 {
+        return Err(AppError::Validation(
+            "title: must be 1-200 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_status(status: &str) -> Result<(), AppError> {
+    if !GOAL_STATUSES.contains(&status) {
+        return Err(AppError::Validation(format!(
+            "status: must be one of: {}",
+            GOAL_STATUSES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+async fn find_goal(
+    db: &DatabaseConnection,
+    id: &str,
+) -> Result<organization_goals::Model, AppError> {
+    OrganizationGoal::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Organization goal {} not found", id)))
+}
+
+/// Responses for goals with their clocks loaded in one query
+async fn with_clocks(
+    db: &DatabaseConnection,
+    goals: Vec<organization_goals::Model>,
+) -> Result<Vec<OrganizationGoalResponse>, AppError> {
+    let clock_ids: Vec<&String> = goals.iter().filter_map(|g| g.clock_id.as_ref()).collect();
+    let mut clocks: HashMap<String, progress_clocks::Model> = if clock_ids.is_empty() {
+        HashMap::new()
+    } else {
+        ProgressClock::find()
+            .filter(progress_clocks::Column::Id.is_in(clock_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|c| (c.id.clone(), c))
+            .collect()
+    };
+
+    Ok(goals
+        .into_iter()
+        .map(|goal| OrganizationGoalResponse {
+            clock: goal
+                .clock_id
+                .as_ref()
+                .and_then(|id| clocks.remove(id))
+                .map(ProgressClockResponse::from),
+            id: goal.id,
+            campaign_id: goal.campaign_id,
+            organization_id: goal.organization_id,
+            title: goal.title,
+            description: goal.description,
+            status: goal.status,
+            created_at: goal.created_at.to_string(),
+            updated_at: goal.updated_at.to_string(),
+        })
+        .collect())
+}
+
+async fn response(
+    db: &DatabaseConnection,
+    goal: organization_goals::Model,
+) -> Result<OrganizationGoalResponse, AppError> {
+    let mut responses = with_clocks(db, vec![goal]).await?;
+    Ok(responses.remove(0))
+}
+
+async fn log_advance(
+    db: &DatabaseConnection,
+    goal: &organization_goals::Model,
+    ticks: i32,
+    clock: Option<&ProgressClockResponse>,
+    status: Option<String>,
+    note: Option<String>,
+) -> Result<(), AppError> {
+    let now = Utc::now();
+    goal_advances::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(goal.campaign_id.clone()),
+        goal_id: Set(goal.id.clone()),
+        ticks: Set(ticks),
+        filled: Set(clock.map(|c| c.filled)),
+        segments: Set(clock.map(|c| c.segments)),
+        status: Set(status),
+        note: Set(note.filter(|n| !n.trim().is_empty())),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
+/// Readable summary of one goal's progress, e.g. `Iron Guild: Seize the
+/// docks +2 (5/8)` or `Iron Guild achieved Seize the docks`
+fn report_line(organization: &str, goal: &GoalProgress) -> String {
+    if let Some(status) = goal.status_changes.last().filter(|s| *s != "active") {
+        return format!("{} {} {}", organization, status, goal.title);
+    }
+    let mut line = format!("{}: {} {:+}", organization, goal.title, goal.ticks);
+    if let (Some(filled), Some(segments)) = (goal.filled, goal.segments) {
+        line.push_str(&format!(" ({}/{})", filled, segments));
+    }
+    line
+}
+
+fn parse_since(value: &str) -> Result<DateTime<Utc>, AppError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|ts| ts.with_timezone(&Utc))
+        .map_err(|e| AppError::Validation(format!("since: invalid timestamp '{}': {}", value, e)))
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Add a goal to an organization. With `segments`, a progress clock
+/// attached to the organization is created to measure it.
+#[instrument(skip_all, fields(organization_id = %organization_id), err)]
+pub async fn create_organization_goal_impl(
+    db: &DatabaseConnection,
+    organization_id: String,
+    title: String,
+    description: Option<String>,
+    segments: Option<i32>,
+) -> Result<OrganizationGoalResponse, AppError> {
+    validate_title(&title)?;
+    let organization = Organization::find_by_id(&organization_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Organization {} not found", organization_id)))?;
+
+    let clock_id = match segments {
+        Some(segments) => Some(
+            create_progress_clock_impl(
+                db,
+                organization.campaign_id.clone(),
+                title.clone(),
+                segments,
+                None,
+                Some("organization".to_string()),
+                Some(organization_id.clone()),
+            )
+            .await?
+            .id,
+        ),
+        None => None,
+    };
+
+    let now = Utc::now();
+    let model = organization_goals::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(organization.campaign_id),
+        organization_id: Set(organization_id),
+        title: Set(title),
+        description: Set(description),
+        status: Set("active".to_string()),
+        clock_id: Set(clock_id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    let result = model.insert(db).await?;
+    response(db, result).await
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_organization_goal_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<OrganizationGoalResponse, AppError> {
+    let goal = find_goal(db, &id).await?;
+    response(db, goal).await
+}
+
+/// Goals in the campaign by title, optionally for one organization or in
+/// one status
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_organization_goals_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    organization_id: Option<String>,
+    status: Option<String>,
+) -> Result<Vec<OrganizationGoalResponse>, AppError> {
+    let mut query =
+        OrganizationGoal::find().filter(organization_goals::Column::CampaignId.eq(&campaign_id));
+    if let Some(organization_id) = organization_id {
+        query = query.filter(organization_goals::Column::OrganizationId.eq(organization_id));
+    }
+    if let Some(status) = status {
+        validate_status(&status)?;
+        query = query.filter(organization_goals::Column::Status.eq(status));
+    }
+    let goals = query
+        .order_by_asc(organization_goals::Column::Title)
+        .all(db)
+        .await?;
+
+    with_clocks(db, goals).await
+}
+
+/// Retitle, describe or set the status of a goal. A status change is
+/// logged like an advance so it shows in the report.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_organization_goal_impl(
+    db: &DatabaseConnection,
+    id: String,
+    title: Option<String>,
+    description: Option<String>,
+    status: Option<String>,
+) -> Result<OrganizationGoalResponse, AppError> {
+    if let Some(title) = &title {
+        validate_title(title)?;
+    }
+    if let Some(status) = &status {
+        validate_status(status)?;
+    }
+
+    let goal = find_goal(db, &id).await?;
+    let changed_status = status.filter(|s| *s != goal.status);
+    let mut active: organization_goals::ActiveModel = goal.into();
+    if let Some(title) = title {
+        active.title = Set(title);
+    }
+    if let Some(description) = description {
+        active.description = Set(Some(description).filter(|d| !d.is_empty()));
+    }
+    if let Some(status) = &changed_status {
+        active.status = Set(status.clone());
+    }
+    active.updated_at = Set(Utc::now());
+
+    let result = active.update(db).await?;
+    if changed_status.is_some() {
+        log_advance(db, &result, 0, None, changed_status, None).await?;
+    }
+    response(db, result).await
+}
+
+/// Tick the goal's clock forward, or back with a negative count, and log
+/// it with an optional note. Filling the clock achieves the goal.
+#[instrument(skip_all, fields(id = %id, ticks), err)]
+pub async fn advance_organization_goal_impl(
+    db: &DatabaseConnection,
+    id: String,
+    ticks: i32,
+    note: Option<String>,
+) -> Result<OrganizationGoalResponse, AppError> {
+    let goal = find_goal(db, &id).await?;
+    if goal.status != "active" {
+        return Err(AppError::Validation(format!(
+            "status: goal is {}, only active goals advance",
+            goal.status
+        )));
+    }
+    let clock_id = goal.clock_id.clone().ok_or_else(|| {
+        AppError::Validation("clock_id: goal has no progress clock to advance".to_string())
+    })?;
+
+    let clock = tick_progress_clock_impl(db, clock_id, ticks).await?;
+    let goal = if clock.complete {
+        let mut active: organization_goals::ActiveModel = goal.into();
+        active.status = Set("achieved".to_string());
+        active.updated_at = Set(Utc::now());
+        active.update(db).await?
+    } else {
+        goal
+    };
+    let status = clock.complete.then(|| goal.status.clone());
+    log_advance(db, &goal, ticks, Some(&clock), status, note).await?;
+
+    response(db, goal).await
+}
+
+/// A goal's log, oldest first
+#[instrument(skip_all, fields(goal_id = %goal_id), err)]
+pub async fn list_goal_advances_impl(
+    db: &DatabaseConnection,
+    goal_id: String,
+) -> Result<Vec<GoalAdvanceResponse>, AppError> {
+    let advances = GoalAdvance::find()
+        .filter(goal_advances::Column::GoalId.eq(goal_id))
+        .order_by_asc(goal_advances::Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(advances.into_iter().map(Into::into).collect())
+}
+
+/// Delete a goal, its log and the clock made for it
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_organization_goal_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let Some(goal) = OrganizationGoal::find_by_id(&id).one(db).await? else {
+        return Ok(false);
+    };
+
+    let txn = db.begin().await?;
+    OrganizationGoal::delete_by_id(&goal.id).exec(&txn).await?;
+    if let Some(clock_id) = goal.clock_id {
+        ProgressClock::delete_by_id(clock_id).exec(&txn).await?;
+    }
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// What the factions did off-screen: goal advances and status changes
+/// since `since`, or by default since the last session ended, grouped by
+/// organization
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_world_moves_on_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    since: Option<String>,
+) -> Result<WorldMovesOnReport, AppError> {
+    let (since, since_session_number) = match since {
+        Some(since) => (Some(parse_since(&since)?), None),
+        None => {
+            let last = Session::find()
+                .filter(sessions::Column::CampaignId.eq(&campaign_id))
+                .filter(sessions::Column::EndedAt.is_not_null())
+                .order_by_desc(sessions::Column::EndedAt)
+                .one(db)
+                .await?;
+            match last {
+                Some(session) => (session.ended_at, Some(session.session_number)),
+                None => (None, None),
+            }
+        }
+    };
+
+    let mut query = GoalAdvance::find().filter(goal_advances::Column::CampaignId.eq(&campaign_id));
+    if let Some(since) = since {
+        query = query.filter(goal_advances::Column::CreatedAt.gt(since));
+    }
+    let advances = query
+        .order_by_asc(goal_advances::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut by_goal: BTreeMap<String, Vec<goal_advances::Model>> = BTreeMap::new();
+    for advance in advances {
+        by_goal
+            .entry(advance.goal_id.clone())
+            .or_default()
+            .push(advance);
+    }
+    let goals = OrganizationGoal::find()
+        .filter(organization_goals::Column::Id.is_in(by_goal.keys().cloned()))
+        .order_by_asc(organization_goals::Column::Title)
+        .all(db)
+        .await?;
+    let organizations: HashMap<String, String> = Organization::find()
+        .filter(organizations::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|o| (o.id, o.name))
+        .collect();
+
+    let mut grouped: BTreeMap<(String, String), Vec<GoalProgress>> = BTreeMap::new();
+    for goal in goals {
+        let advances = by_goal.remove(&goal.id).unwrap_or_default();
+        let last_clock = advances.iter().rev().find(|a| a.segments.is_some());
+        let progress = GoalProgress {
+            goal_id: goal.id,
+            title: goal.title,
+            status: goal.status,
+            ticks: advances.iter().map(|a| a.ticks).sum(),
+            filled: last_clock.and_then(|a| a.filled),
+            segments: last_clock.and_then(|a| a.segments),
+            status_changes: advances.iter().filter_map(|a| a.status.clone()).collect(),
+            notes: advances.iter().filter_map(|a| a.note.clone()).collect(),
+        };
+        let name = organizations
+            .get(&goal.organization_id)
+            .cloned()
+            .unwrap_or_default();
+        grouped
+            .entry((name, goal.organization_id))
+            .or_default()
+            .push(progress);
+    }
+
+    let mut lines = Vec::new();
+    let organizations = grouped
+        .into_iter()
+        .map(|((organization_name, organization_id), goals)| {
+            lines.extend(goals.iter().map(|g| report_line(&organization_name, g)));
+            FactionProgress {
+                organization_id,
+                organization_name,
+                goals,
+            }
+        })
+        .collect();
+
+    Ok(WorldMovesOnReport {
+        campaign_id,
+        since: since.map(|s| s.to_rfc3339()),
+        since_session_number,
+        organizations,
+        lines,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_organization_goal(
+    state: State<'_, AppState>,
+    organization_id: String,
+    title: String,
+    description: Option<String>,
+    segments: Option<i32>,
+) -> Result<OrganizationGoalResponse, AppError> {
+    create_organization_goal_impl(&state.db, organization_id, title, description, segments).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_organization_goal(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OrganizationGoalResponse, AppError> {
+    get_organization_goal_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_organization_goals(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    organization_id: Option<String>,
+    status: Option<String>,
+) -> Result<Vec<OrganizationGoalResponse>, AppError> {
+    list_organization_goals_impl(&state.db, campaign_id, organization_id, status).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_organization_goal(
+    state: State<'_, AppState>,
+    id: String,
+    title: Option<String>,
+    description: Option<String>,
+    status: Option<String>,
+) -> Result<OrganizationGoalResponse, AppError> {
+    update_organization_goal_impl(&state.db, id, title, description, status).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn advance_organization_goal(
+    state: State<'_, AppState>,
+    id: String,
+    ticks: i32,
+    note: Option<String>,
+) -> Result<OrganizationGoalResponse, AppError> {
+    advance_organization_goal_impl(&state.db, id, ticks, note).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_goal_advances(
+    state: State<'_, AppState>,
+    goal_id: String,
+) -> Result<Vec<GoalAdvanceResponse>, AppError> {
+    list_goal_advances_impl(&state.db, goal_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_organization_goal(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    delete_organization_goal_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_world_moves_on(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    since: Option<String>,
+) -> Result<WorldMovesOnReport, AppError> {
+    get_world_moves_on_impl(&state.db, campaign_id, since).await
+}
@@ -0,0 +1,296 @@
+//! What organizations control: the locations they hold and the items in
+//! their vaults. Each holding points at exactly one location or item.
+
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::items::{self, Entity as Item};
+use ::entity::locations::{self, Entity as Location};
+use ::entity::organization_holdings::{self, Entity as OrganizationHolding};
+use ::entity::organizations::{self, Entity as Organization};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Entity types an organization can hold
+pub const HOLDING_TYPES: &[&str] = &["location", "item"];
+
+/// A location or item an organization controls
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrganizationHoldingResponse {
+    pub id: String,
+    pub organization_id: String,
+    /// `location` or `item`
+    pub holding_type: String,
+    pub holding_id: String,
+    pub holding_name: String,
+    /// The held location's or item's own type, e.g. `fortress` or `weapon`
+    pub holding_kind: String,
+    pub notes: Option<String>,
+    pub created_at: String,
+}
+
+async fn find_organization<C: ConnectionTrait>(
+    conn: &C,
+    id: &str,
+) -> Result<organizations::Model, AppError> {
+    Organization::find_by_id(id)
+        .one(conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Organization {} not found", id)))
+}
+
+/// Name, kind and campaign of the location or item being held
+async fn find_target<C: ConnectionTrait>(
+    conn: &C,
+    holding_type: &str,
+    holding_id: &str,
+) -> Result<(String, String, String), AppError> {
+    match holding_type {
+        "location" => {
+            let location = Location::find_by_id(holding_id)
+                .one(conn)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Location {} not found", holding_id)))?;
+            Ok((location.name, location.location_type, location.campaign_id))
+        }
+        "item" => {
+            let item = Item::find_by_id(holding_id)
+                .one(conn)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Item {} not found", holding_id)))?;
+            Ok((item.name, item.item_type, item.campaign_id))
+        }
+        other => Err(AppError::Validation(format!(
+            "holding_type: must be one of: {} (got {})",
+            HOLDING_TYPES.join(", "),
+            other
+        ))),
+    }
+}
+
+fn response(
+    holding: organization_holdings::Model,
+    holding_type: &str,
+    holding_id: String,
+    holding_name: String,
+    holding_kind: String,
+) -> OrganizationHoldingResponse {
+    OrganizationHoldingResponse {
+        id: holding.id,
+        organization_id: holding.organization_id,
+        holding_type: holding_type.to_string(),
+        holding_id,
+        holding_name,
+        holding_kind,
+        notes: holding.notes,
+        created_at: holding.created_at.to_string(),
+    }
+}
+
+/// Repoint holdings from `source_id` to `target_id` on `column`, dropping any
+/// the target already has. Used when merging duplicate entities.
+pub(crate) async fn merge_organization_holdings<C: ConnectionTrait>(
+    conn: &C,
+    column: organization_holdings::Column,
+    source_id: &str,
+    target_id: &str,
+) -> Result<(), AppError> {
+    let key = |h: &organization_holdings::Model| {
+        (
+            h.organization_id.clone(),
+            h.location_id.clone(),
+            h.item_id.clone(),
+        )
+    };
+    let existing: Vec<_> = OrganizationHolding::find()
+        .filter(column.eq(target_id))
+        .all(conn)
+        .await?
+        .iter()
+        .map(key)
+        .collect();
+    let now = chrono::Utc::now();
+
+    for holding in OrganizationHolding::find()
+        .filter(column.eq(source_id))
+        .all(conn)
+        .await?
+    {
+        let mut moved = holding.clone();
+        match column {
+            organization_holdings::Column::OrganizationId => {
+                moved.organization_id = target_id.to_string()
+            }
+            organization_holdings::Column::LocationId => {
+                moved.location_id = Some(target_id.to_string())
+            }
+            organization_holdings::Column::ItemId => moved.item_id = Some(target_id.to_string()),
+            _ => {}
+        }
+
+        if existing.contains(&key(&moved)) {
+            OrganizationHolding::delete_by_id(holding.id)
+                .exec(conn)
+                .await?;
+        } else {
+            let mut active: organization_holdings::ActiveModel = holding.into();
+            active.set(column, target_id.into());
+            active.updated_at = Set(now);
+            active.update(conn).await?;
+        }
+    }
+    Ok(())
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Record that an organization holds a location or item. Adding a holding
+/// it already has updates the notes instead of duplicating it.
+#[instrument(
+    skip_all,
+    fields(organization_id = %organization_id, holding_type = %holding_type),
+    err
+)]
+pub async fn add_organization_holding_impl(
+    db: &DatabaseConnection,
+    organization_id: String,
+    holding_type: String,
+    holding_id: String,
+    notes: Option<String>,
+) -> Result<OrganizationHoldingResponse, AppError> {
+    let organization = find_organization(db, &organization_id).await?;
+    let (name, kind, campaign_id) = find_target(db, &holding_type, &holding_id).await?;
+    if campaign_id != organization.campaign_id {
+        return Err(AppError::Validation(format!(
+            "holding_id: {} belongs to a different campaign",
+            holding_type
+        )));
+    }
+
+    let (location_id, item_id) = match holding_type.as_str() {
+        "location" => (Some(holding_id.clone()), None),
+        _ => (None, Some(holding_id.clone())),
+    };
+    let now = chrono::Utc::now();
+
+    let existing = OrganizationHolding::find()
+        .filter(organization_holdings::Column::OrganizationId.eq(&organization_id))
+        .filter(match &location_id {
+            Some(id) => organization_holdings::Column::LocationId.eq(id),
+            None => organization_holdings::Column::ItemId.eq(&holding_id),
+        })
+        .one(db)
+        .await?;
+
+    let holding = match existing {
+        Some(existing) => {
+            let mut active: organization_holdings::ActiveModel = existing.into();
+            if notes.is_some() {
+                active.notes = Set(notes);
+            }
+            active.updated_at = Set(now);
+            active.update(db).await?
+        }
+        None => {
+            organization_holdings::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                campaign_id: Set(organization.campaign_id),
+                organization_id: Set(organization_id),
+                location_id: Set(location_id),
+                item_id: Set(item_id),
+                notes: Set(notes),
+                created_at: Set(now),
+                updated_at: Set(now),
+                created_by: Set(None),
+                modified_by: Set(None),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+
+    Ok(response(holding, &holding_type, holding_id, name, kind))
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn remove_organization_holding_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = OrganizationHolding::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Everything an organization holds, locations first, each sorted by name
+#[instrument(skip_all, fields(organization_id = %organization_id), err)]
+pub async fn list_organization_holdings_impl(
+    db: &DatabaseConnection,
+    organization_id: String,
+) -> Result<Vec<OrganizationHoldingResponse>, AppError> {
+    find_organization(db, &organization_id).await?;
+
+    let location_rows = OrganizationHolding::find()
+        .filter(organization_holdings::Column::OrganizationId.eq(&organization_id))
+        .find_also_related(Location)
+        .order_by_asc(locations::Column::Name)
+        .all(db)
+        .await?;
+    let item_rows = OrganizationHolding::find()
+        .filter(organization_holdings::Column::OrganizationId.eq(&organization_id))
+        .find_also_related(Item)
+        .order_by_asc(items::Column::Name)
+        .all(db)
+        .await?;
+
+    let locations = location_rows.into_iter().filter_map(|(holding, location)| {
+        let location = location?;
+        Some(response(
+            holding,
+            "location",
+            location.id,
+            location.name,
+            location.location_type,
+        ))
+    });
+    let items = item_rows.into_iter().filter_map(|(holding, item)| {
+        let item = item?;
+        Some(response(
+            holding,
+            "item",
+            item.id,
+            item.name,
+            item.item_type,
+        ))
+    });
+    Ok(locations.chain(items).collect())
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn add_organization_holding(
+    state: State<'_, AppState>,
+    organization_id: String,
+    holding_type: String,
+    holding_id: String,
+    notes: Option<String>,
+) -> Result<OrganizationHoldingResponse, AppError> {
+    add_organization_holding_impl(&state.db, organization_id, holding_type, holding_id, notes).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remove_organization_holding(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    remove_organization_holding_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_organization_holdings(
+    state: State<'_, AppState>,
+    organization_id: String,
+) -> Result<Vec<OrganizationHoldingResponse>, AppError> {
+    list_organization_holdings_impl(&state.db, organization_id).await
+}
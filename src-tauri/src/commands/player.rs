@@ -1,9 +1,13 @@
+use crate::commands::json_schema::PlayerAvailability;
+use crate::commands::references::remove_references;
 use crate::db::AppState;
 use crate::error::AppError;
 use ::entity::players::{self, Entity as Player};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
+use validator::ValidateEmail;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerResponse {
@@ -13,6 +17,10 @@ pub struct PlayerResponse {
     pub preferences: Option<String>,
     pub boundaries: Option<String>,
     pub notes: Option<String>,
+    pub contact_email: Option<String>,
+    /// Discord or other chat handle
+    pub contact_handle: Option<String>,
+    pub availability: PlayerAvailability,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -26,20 +34,43 @@ impl From<players::Model> for PlayerResponse {
             preferences: model.preferences,
             boundaries: model.boundaries,
             notes: model.notes,
+            contact_email: model.contact_email,
+            contact_handle: model.contact_handle,
+            // Stored values are checked on write; anything unreadable counts
+            // as "no schedule known"
+            availability: model
+                .availability_json
+                .as_deref()
+                .and_then(|raw| PlayerAvailability::parse(raw).ok())
+                .unwrap_or_default(),
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
     }
 }
 
+fn check_contact_email(email: Option<&str>) -> Result<(), AppError> {
+    match email {
+        Some(e) if !e.validate_email() => Err(AppError::Validation(
+            "contact_email: must be a valid email address".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn create_player(
     state: State<'_, AppState>,
     campaign_id: String,
     name: String,
     preferences: Option<String>,
     boundaries: Option<String>,
+    contact_email: Option<String>,
+    contact_handle: Option<String>,
 ) -> Result<PlayerResponse, AppError> {
+    check_contact_email(contact_email.as_deref())?;
+
     let id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now();
 
@@ -50,8 +81,13 @@ pub async fn create_player(
         preferences: Set(preferences),
         boundaries: Set(boundaries),
         notes: Set(None),
+        contact_email: Set(contact_email),
+        contact_handle: Set(contact_handle),
+        availability_json: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     let result = model.insert(&state.db).await?;
@@ -59,6 +95,7 @@ pub async fn create_player(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_player(
     state: State<'_, AppState>,
     id: String,
@@ -72,6 +109,7 @@ pub async fn get_player(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn list_players(
     state: State<'_, AppState>,
     campaign_id: String,
@@ -86,6 +124,7 @@ pub async fn list_players(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn update_player(
     state: State<'_, AppState>,
     id: String,
@@ -93,7 +132,11 @@ pub async fn update_player(
     preferences: Option<String>,
     boundaries: Option<String>,
     notes: Option<String>,
+    contact_email: Option<String>,
+    contact_handle: Option<String>,
 ) -> Result<PlayerResponse, AppError> {
+    check_contact_email(contact_email.as_deref())?;
+
     let player = Player::find_by_id(&id)
         .one(&state.db)
         .await?
@@ -113,6 +156,12 @@ pub async fn update_player(
     if let Some(no) = notes {
         active.notes = Set(Some(no));
     }
+    if let Some(e) = contact_email {
+        active.contact_email = Set(Some(e));
+    }
+    if let Some(h) = contact_handle {
+        active.contact_handle = Set(Some(h));
+    }
     active.updated_at = Set(chrono::Utc::now());
 
     let result = active.update(&state.db).await?;
@@ -120,7 +169,11 @@ pub async fn update_player(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_player(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
-    let result = Player::delete_by_id(&id).exec(&state.db).await?;
+    let txn = state.db.begin().await?;
+    remove_references(&txn, "player", &id).await?;
+    let result = Player::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
     Ok(result.rows_affected > 0)
 }
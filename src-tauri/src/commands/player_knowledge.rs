@@ -0,0 +1,245 @@
+//! What the players know: everything already revealed or handed to them,
+//! gathered in one place for recaps. Nothing here is hidden from players, so
+//! the digest can be read aloud as-is.
+
+use crate::commands::relationship::entity_names;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::handout_deliveries::{self, Entity as HandoutDelivery};
+use ::entity::handouts::Entity as Handout;
+use ::entity::players::{self, Entity as Player};
+use ::entity::relationships::{self, Entity as Relationship};
+use ::entity::secrets::{self, Entity as Secret};
+use ::entity::timeline_events::{self, Entity as TimelineEvent};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use tauri::State;
+use tracing::instrument;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KnownSecret {
+    pub id: String,
+    pub title: String,
+    pub content: String,
+    pub revealed_in_session: Option<i32>,
+    pub related_entity_type: Option<String>,
+    pub related_entity_id: Option<String>,
+    pub related_entity_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KnownRelationship {
+    pub id: String,
+    pub source_type: String,
+    pub source_id: String,
+    pub source_name: Option<String>,
+    pub relationship_type: String,
+    pub target_type: String,
+    pub target_id: String,
+    pub target_name: Option<String>,
+    pub is_bidirectional: bool,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KnownTimelineEvent {
+    pub id: String,
+    pub date_display: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub significance: String,
+}
+
+/// A handout at least one player has received
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KnownHandout {
+    pub id: String,
+    pub title: String,
+    pub content: Option<String>,
+    /// Names of the players holding it, alphabetical
+    pub recipients: Vec<String>,
+    /// When the first copy went out
+    pub first_sent_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayerKnowledgeReport {
+    pub campaign_id: String,
+    /// Revealed secrets, in the order they came out
+    pub secrets: Vec<KnownSecret>,
+    /// Public relationships, grouped by source name
+    pub relationships: Vec<KnownRelationship>,
+    /// Public events on the main timeline, in timeline order
+    pub timeline_events: Vec<KnownTimelineEvent>,
+    /// Delivered handouts, oldest first
+    pub handouts: Vec<KnownHandout>,
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Everything players have learned so far in a campaign
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn player_knowledge_report_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<PlayerKnowledgeReport, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let revealed = Secret::find()
+        .filter(secrets::Column::CampaignId.eq(&campaign_id))
+        .filter(secrets::Column::Revealed.eq(true))
+        .all(db)
+        .await?;
+    let public_relationships = Relationship::find()
+        .filter(relationships::Column::CampaignId.eq(&campaign_id))
+        .filter(relationships::Column::IsPublic.eq(true))
+        .all(db)
+        .await?;
+
+    let entity_types: BTreeSet<&str> = revealed
+        .iter()
+        .filter_map(|s| s.related_entity_type.as_deref())
+        .chain(
+            public_relationships
+                .iter()
+                .flat_map(|r| [r.source_type.as_str(), r.target_type.as_str()]),
+        )
+        .collect();
+    let names = entity_names(db, &campaign_id, &entity_types).await?;
+    let name_of = |entity_type: &str, id: &str| {
+        names
+            .get(&(entity_type.to_string(), id.to_string()))
+            .cloned()
+    };
+
+    let mut known_secrets: Vec<KnownSecret> = revealed
+        .into_iter()
+        .map(|s| KnownSecret {
+            related_entity_name: s
+                .related_entity_type
+                .as_deref()
+                .zip(s.related_entity_id.as_deref())
+                .and_then(|(entity_type, id)| name_of(entity_type, id)),
+            id: s.id,
+            title: s.title,
+            content: s.content,
+            revealed_in_session: s.revealed_in_session,
+            related_entity_type: s.related_entity_type,
+            related_entity_id: s.related_entity_id,
+        })
+        .collect();
+    // Secrets revealed without a session number go last
+    known_secrets.sort_by(|a, b| {
+        (
+            a.revealed_in_session.is_none(),
+            a.revealed_in_session,
+            &a.title,
+        )
+            .cmp(&(
+                b.revealed_in_session.is_none(),
+                b.revealed_in_session,
+                &b.title,
+            ))
+    });
+
+    let mut known_relationships: Vec<KnownRelationship> = public_relationships
+        .into_iter()
+        .map(|r| KnownRelationship {
+            source_name: name_of(&r.source_type, &r.source_id),
+            target_name: name_of(&r.target_type, &r.target_id),
+            id: r.id,
+            source_type: r.source_type,
+            source_id: r.source_id,
+            relationship_type: r.relationship_type,
+            target_type: r.target_type,
+            target_id: r.target_id,
+            is_bidirectional: r.is_bidirectional,
+            description: r.description,
+        })
+        .collect();
+    known_relationships.sort_by(|a, b| {
+        (&a.source_name, &a.relationship_type, &a.target_name).cmp(&(
+            &b.source_name,
+            &b.relationship_type,
+            &b.target_name,
+        ))
+    });
+
+    // What-if branches never happened, so players can't know about them
+    let timeline_events = TimelineEvent::find()
+        .filter(timeline_events::Column::CampaignId.eq(&campaign_id))
+        .filter(timeline_events::Column::IsPublic.eq(true))
+        .filter(timeline_events::Column::BranchId.is_null())
+        .order_by_asc(timeline_events::Column::SortOrder)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|e| KnownTimelineEvent {
+            id: e.id,
+            date_display: e.date_display,
+            title: e.title,
+            description: e.description,
+            significance: e.significance,
+        })
+        .collect();
+
+    let players: HashMap<String, String> = Player::find()
+        .filter(players::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.name))
+        .collect();
+    let deliveries = HandoutDelivery::find()
+        .filter(handout_deliveries::Column::CampaignId.eq(&campaign_id))
+        .find_also_related(Handout)
+        .order_by_asc(handout_deliveries::Column::SentAt)
+        .all(db)
+        .await?;
+    let mut handouts: Vec<KnownHandout> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (delivery, handout) in deliveries {
+        let Some(handout) = handout else {
+            continue;
+        };
+        let position = *positions.entry(handout.id.clone()).or_insert_with(|| {
+            handouts.push(KnownHandout {
+                id: handout.id,
+                title: handout.title,
+                content: handout.content,
+                recipients: Vec::new(),
+                first_sent_at: delivery.sent_at.to_string(),
+            });
+            handouts.len() - 1
+        });
+        if let Some(name) = players.get(&delivery.player_id) {
+            handouts[position].recipients.push(name.clone());
+        }
+    }
+    for handout in &mut handouts {
+        handout.recipients.sort();
+    }
+
+    Ok(PlayerKnowledgeReport {
+        campaign_id,
+        secrets: known_secrets,
+        relationships: known_relationships,
+        timeline_events,
+        handouts,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn player_knowledge_report(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<PlayerKnowledgeReport, AppError> {
+    player_knowledge_report_impl(&state.db, campaign_id).await
+}
@@ -0,0 +1,216 @@
+//! Player-facing recaps of a session, saved as a handout so they can be sent
+//! to the group. The prose comes from the AI when the frontend has written
+//! some, and from the session summary otherwise; secrets revealed that
+//! session and public timeline events are listed after it either way.
+//! GM-only blocks in any of that text are left out.
+
+use crate::commands::handout::HandoutResponse;
+use crate::commands::journal_export::write_export;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::markdown::strip_gm_blocks;
+use ::entity::handouts::{self, Entity as Handout};
+use ::entity::secrets::{self, Entity as Secret};
+use ::entity::sessions::{self, Entity as Session};
+use ::entity::timeline_events::{self, Entity as TimelineEvent};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayerRecap {
+    /// The handout holding the recap, created on the first run and
+    /// overwritten on later ones
+    pub handout: HandoutResponse,
+    pub markdown: String,
+    /// False when the template wrote the prose
+    pub used_prose: bool,
+    /// Where the markdown was written, if a path was given
+    pub path: Option<String>,
+}
+
+fn recap_title(session: &sessions::Model) -> String {
+    match session.title.as_deref().map(str::trim) {
+        Some(title) if !title.is_empty() => {
+            format!("Recap: Session {} - {}", session.session_number, title)
+        }
+        _ => format!("Recap: Session {}", session.session_number),
+    }
+}
+
+/// Prose used when no AI recap was supplied
+fn template_prose(session: &sessions::Model) -> String {
+    let summary = session.summary.as_deref().map(strip_gm_blocks);
+    match summary.as_deref().map(str::trim) {
+        Some(summary) if !summary.is_empty() => summary.to_string(),
+        _ => format!(
+            "Session {} has no summary yet; the notes below cover what came to light.",
+            session.session_number
+        ),
+    }
+}
+
+fn render_recap(
+    title: &str,
+    prose: &str,
+    secrets: &[secrets::Model],
+    events: &[timeline_events::Model],
+) -> String {
+    let mut out = format!("# {}\n\n{}\n", title, strip_gm_blocks(prose).trim());
+    if !secrets.is_empty() {
+        out.push_str("\n## Revealed\n\n");
+        for secret in secrets {
+            out.push_str(&format!(
+                "- **{}**: {}\n",
+                secret.title,
+                strip_gm_blocks(&secret.content).trim()
+            ));
+        }
+    }
+    if !events.is_empty() {
+        out.push_str("\n## On the timeline\n\n");
+        for event in events {
+            let description = event.description.as_deref().map(strip_gm_blocks);
+            match description.as_deref().map(str::trim) {
+                Some(description) if !description.is_empty() => out.push_str(&format!(
+                    "- **{}**: {} - {}\n",
+                    event.date_display, event.title, description
+                )),
+                _ => out.push_str(&format!("- **{}**: {}\n", event.date_display, event.title)),
+            }
+        }
+    }
+    out
+}
+
+/// Public, main-line events logged since the previous session up to and
+/// including this one's. Empty when the session isn't on the timeline.
+async fn session_events(
+    db: &DatabaseConnection,
+    session: &sessions::Model,
+) -> Result<Vec<timeline_events::Model>, AppError> {
+    let Some(event_id) = &session.timeline_event_id else {
+        return Ok(Vec::new());
+    };
+    let Some(current) = TimelineEvent::find_by_id(event_id).one(db).await? else {
+        return Ok(Vec::new());
+    };
+
+    let earlier_event_ids: Vec<String> = Session::find()
+        .filter(sessions::Column::CampaignId.eq(&session.campaign_id))
+        .filter(sessions::Column::SessionNumber.lt(session.session_number))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|s| s.timeline_event_id)
+        .collect();
+    let previous = TimelineEvent::find()
+        .filter(timeline_events::Column::Id.is_in(earlier_event_ids))
+        .filter(timeline_events::Column::SortOrder.lt(current.sort_order))
+        .order_by_desc(timeline_events::Column::SortOrder)
+        .one(db)
+        .await?;
+
+    let mut query = TimelineEvent::find()
+        .filter(timeline_events::Column::CampaignId.eq(&session.campaign_id))
+        .filter(timeline_events::Column::IsPublic.eq(true))
+        // What-if branches stay with the GM
+        .filter(timeline_events::Column::BranchId.is_null())
+        .filter(timeline_events::Column::SortOrder.lte(current.sort_order));
+    if let Some(previous) = previous {
+        query = query.filter(timeline_events::Column::SortOrder.gt(previous.sort_order));
+    }
+    Ok(query
+        .order_by_asc(timeline_events::Column::SortOrder)
+        .all(db)
+        .await?)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Build the players' recap of a session and save it as a handout, writing
+/// the markdown to `path` too when given. `prose` is recap text the AI
+/// wrote; without it the session summary is used.
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn generate_player_recap_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+    prose: Option<String>,
+    path: Option<String>,
+) -> Result<PlayerRecap, AppError> {
+    let session = Session::find_by_id(&session_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} not found", session_id)))?;
+
+    let prose = prose.filter(|p| !p.trim().is_empty());
+    let used_prose = prose.is_some();
+    let prose = prose.unwrap_or_else(|| template_prose(&session));
+
+    let secrets = Secret::find()
+        .filter(secrets::Column::CampaignId.eq(&session.campaign_id))
+        .filter(secrets::Column::Revealed.eq(true))
+        .filter(secrets::Column::RevealedInSession.eq(session.session_number))
+        .order_by_asc(secrets::Column::Title)
+        .all(db)
+        .await?;
+    let events = session_events(db, &session).await?;
+
+    let title = recap_title(&session);
+    let markdown = render_recap(&title, &prose, &secrets, &events);
+
+    let now = chrono::Utc::now();
+    let existing = Handout::find()
+        .filter(handouts::Column::CampaignId.eq(&session.campaign_id))
+        .filter(handouts::Column::Title.eq(&title))
+        .one(db)
+        .await?;
+    let handout = match existing {
+        Some(handout) => {
+            let mut active: handouts::ActiveModel = handout.into();
+            active.content = Set(Some(markdown.clone()));
+            active.updated_at = Set(now);
+            active.update(db).await?
+        }
+        None => {
+            handouts::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                campaign_id: Set(session.campaign_id.clone()),
+                title: Set(title),
+                content: Set(Some(markdown.clone())),
+                secret_id: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+                created_by: Set(None),
+                modified_by: Set(None),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+
+    write_export(&path, &markdown)?;
+    if let Some(path) = &path {
+        tracing::info!(path = %path, "Exported player recap");
+    }
+
+    Ok(PlayerRecap {
+        handout: handout.into(),
+        markdown,
+        used_prose,
+        path,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn generate_player_recap(
+    state: State<'_, AppState>,
+    session_id: String,
+    prose: Option<String>,
+    path: Option<String>,
+) -> Result<PlayerRecap, AppError> {
+    generate_player_recap_impl(&state.db, session_id, prose, path).await
+}
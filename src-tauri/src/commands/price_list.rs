@@ -0,0 +1,565 @@
+//! The campaign's price list, so haggling scenes use consistent numbers.
+//! Each price has a category and a base price in the smallest coin. Places
+//! mark prices up or down with percentage modifiers, for everything, for a
+//! category or for one price. A local price applies every modifier from the
+//! place asked about up through its parents, so a port city's cheap imports
+//! and a kingdom-wide war tax both count.
+
+use crate::commands::validation::{CreatePriceInput, UpdatePriceInput};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::items::{self, Entity as Item};
+use ::entity::locations::{self, Entity as Location};
+use ::entity::price_list::{self, Entity as Price};
+use ::entity::price_modifiers::{self, Entity as PriceModifier};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+/// Largest markdown; -100 makes something free
+pub const MIN_PERCENT: i32 = -100;
+/// Largest markup, ten times over
+pub const MAX_PERCENT: i32 = 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub name: String,
+    pub category: String,
+    /// Item this price is for, if it's in the campaign's items
+    pub item_id: Option<String>,
+    /// In the campaign's smallest coin
+    pub base_price: i32,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<price_list::Model> for PriceResponse {
+    fn from(model: price_list::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            name: model.name,
+            category: model.category,
+            item_id: model.item_id,
+            base_price: model.base_price,
+            notes: model.notes,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceModifierResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub location_id: String,
+    /// Category the modifier covers; None with no `price_id` covers everything
+    pub category: Option<String>,
+    /// Single price the modifier covers
+    pub price_id: Option<String>,
+    /// Change in percent: 25 is a quarter dearer, -10 a tenth cheaper
+    pub percent: i32,
+    pub reason: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<price_modifiers::Model> for PriceModifierResponse {
+    fn from(model: price_modifiers::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            location_id: model.location_id,
+            category: model.category,
+            price_id: model.price_id,
+            percent: model.percent,
+            reason: model.reason,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+/// A modifier that went into a local price
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppliedModifier {
+    pub id: String,
+    pub location_id: String,
+    pub location_name: String,
+    pub category: Option<String>,
+    pub price_id: Option<String>,
+    pub percent: i32,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LocalPrice {
+    pub price: PriceResponse,
+    pub location_id: String,
+    pub location_name: String,
+    pub base_price: i32,
+    /// Base price with every modifier applied, rounded to the nearest coin
+    pub local_price: i32,
+    /// Nearest place first
+    pub modifiers: Vec<AppliedModifier>,
+}
+
+/// Categories match regardless of case and surrounding space
+fn normalize_category(category: &str) -> String {
+    category.trim().to_lowercase()
+}
+
+/// Apply percentage changes in turn, rounding once at the end
+fn apply_percents(base: i32, percents: &[i32]) -> i32 {
+    let price = percents.iter().fold(f64::from(base), |price, percent| {
+        price * f64::from(100 + percent) / 100.0
+    });
+    price.round().clamp(0.0, f64::from(i32::MAX)) as i32
+}
+
+fn validate_percent(percent: i32) -> Result<(), AppError> {
+    if !(MIN_PERCENT..=MAX_PERCENT).contains(&percent) {
+        return Err(AppError::Validation(format!(
+            "percent: must be between {} and {}",
+            MIN_PERCENT, MAX_PERCENT
+        )));
+    }
+    Ok(())
+}
+
+async fn find_price(db: &DatabaseConnection, id: &str) -> Result<price_list::Model, AppError> {
+    Price::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Price {} not found", id)))
+}
+
+/// The item must be in the price's campaign
+async fn check_item(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    item_id: &str,
+) -> Result<(), AppError> {
+    Item::find_by_id(item_id)
+        .filter(items::Column::CampaignId.eq(campaign_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Item {} not found in campaign", item_id)))?;
+    Ok(())
+}
+
+/// A location and its parents, nearest first
+async fn location_chain(
+    db: &DatabaseConnection,
+    location_id: &str,
+) -> Result<Vec<locations::Model>, AppError> {
+    let mut chain = Vec::new();
+    let mut seen = HashSet::new();
+    let mut next = Some(location_id.to_string());
+    while let Some(id) = next {
+        if !seen.insert(id.clone()) {
+            break;
+        }
+        let Some(location) = Location::find_by_id(&id).one(db).await? else {
+            break;
+        };
+        next = location.parent_id.clone();
+        chain.push(location);
+    }
+    Ok(chain)
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %input.campaign_id), err)]
+pub async fn create_price_impl(
+    db: &DatabaseConnection,
+    input: CreatePriceInput,
+) -> Result<PriceResponse, AppError> {
+    input.validate()?;
+    let item_id = input.item_id.filter(|id| !id.is_empty());
+    if let Some(item_id) = &item_id {
+        check_item(db, &input.campaign_id, item_id).await?;
+    }
+
+    let now = chrono::Utc::now();
+    let model = price_list::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(input.campaign_id),
+        name: Set(input.name),
+        category: Set(normalize_category(&input.category)),
+        item_id: Set(item_id),
+        base_price: Set(input.base_price),
+        notes: Set(input.notes),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    Ok(model.insert(db).await?.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_price_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<PriceResponse, AppError> {
+    Ok(find_price(db, &id).await?.into())
+}
+
+/// The price list by category then name, or one category of it
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_prices_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    category: Option<String>,
+) -> Result<Vec<PriceResponse>, AppError> {
+    let mut query = Price::find().filter(price_list::Column::CampaignId.eq(&campaign_id));
+    if let Some(category) = category {
+        query = query.filter(price_list::Column::Category.eq(normalize_category(&category)));
+    }
+    let prices = query
+        .order_by_asc(price_list::Column::Category)
+        .order_by_asc(price_list::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(prices.into_iter().map(Into::into).collect())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_price_impl(
+    db: &DatabaseConnection,
+    id: String,
+    input: UpdatePriceInput,
+) -> Result<PriceResponse, AppError> {
+    input.validate()?;
+    let price = find_price(db, &id).await?;
+    if let Some(item_id) = input.item_id.as_deref().filter(|id| !id.is_empty()) {
+        check_item(db, &price.campaign_id, item_id).await?;
+    }
+
+    let mut active: price_list::ActiveModel = price.into();
+    if let Some(name) = input.name {
+        active.name = Set(name);
+    }
+    if let Some(category) = input.category {
+        active.category = Set(normalize_category(&category));
+    }
+    if let Some(base_price) = input.base_price {
+        active.base_price = Set(base_price);
+    }
+    if let Some(item_id) = input.item_id {
+        active.item_id = Set(Some(item_id).filter(|id| !id.is_empty()));
+    }
+    if let Some(notes) = input.notes {
+        active.notes = Set(Some(notes).filter(|n| !n.is_empty()));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    Ok(active.update(db).await?.into())
+}
+
+/// Delete a price along with the modifiers made for it alone
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_price_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
+    let result = Price::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Mark prices at a location up or down. With neither `category` nor
+/// `price_id` the modifier covers everything sold there.
+#[instrument(skip_all, fields(location_id = %location_id, percent), err)]
+pub async fn create_price_modifier_impl(
+    db: &DatabaseConnection,
+    location_id: String,
+    percent: i32,
+    category: Option<String>,
+    price_id: Option<String>,
+    reason: Option<String>,
+) -> Result<PriceModifierResponse, AppError> {
+    validate_percent(percent)?;
+    let category = category
+        .map(|c| normalize_category(&c))
+        .filter(|c| !c.is_empty());
+    if category.is_some() && price_id.is_some() {
+        return Err(AppError::Validation(
+            "price_id: give a category or a price, not both".to_string(),
+        ));
+    }
+    let location = Location::find_by_id(&location_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Location {} not found", location_id)))?;
+    if let Some(price_id) = &price_id {
+        let price = find_price(db, price_id).await?;
+        if price.campaign_id != location.campaign_id {
+            return Err(AppError::NotFound(format!(
+                "Price {} not found in campaign",
+                price_id
+            )));
+        }
+    }
+
+    let now = chrono::Utc::now();
+    let model = price_modifiers::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(location.campaign_id),
+        location_id: Set(location_id),
+        category: Set(category),
+        price_id: Set(price_id),
+        percent: Set(percent),
+        reason: Set(reason),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    Ok(model.insert(db).await?.into())
+}
+
+/// Every modifier in the campaign, or those set at one location
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_price_modifiers_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    location_id: Option<String>,
+) -> Result<Vec<PriceModifierResponse>, AppError> {
+    let mut query =
+        PriceModifier::find().filter(price_modifiers::Column::CampaignId.eq(&campaign_id));
+    if let Some(location_id) = location_id {
+        query = query.filter(price_modifiers::Column::LocationId.eq(location_id));
+    }
+    let modifiers = query
+        .order_by_asc(price_modifiers::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(modifiers.into_iter().map(Into::into).collect())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_price_modifier_impl(
+    db: &DatabaseConnection,
+    id: String,
+    percent: Option<i32>,
+    reason: Option<String>,
+) -> Result<PriceModifierResponse, AppError> {
+    if let Some(percent) = percent {
+        validate_percent(percent)?;
+    }
+
+    let modifier = PriceModifier::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Price modifier {} not found", id)))?;
+    let mut active: price_modifiers::ActiveModel = modifier.into();
+    if let Some(percent) = percent {
+        active.percent = Set(percent);
+    }
+    if let Some(reason) = reason {
+        active.reason = Set(Some(reason));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    Ok(active.update(db).await?.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_price_modifier_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = PriceModifier::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// What something costs at a location. `item` is a price id, or the id of
+/// an item with a price. Modifiers from the location and each of its parents
+/// all apply, one after another.
+#[instrument(skip_all, fields(item = %item, location_id = %location_id), err)]
+pub async fn get_local_price_impl(
+    db: &DatabaseConnection,
+    item: String,
+    location_id: String,
+) -> Result<LocalPrice, AppError> {
+    let price = match Price::find_by_id(&item).one(db).await? {
+        Some(price) => price,
+        None => Price::find()
+            .filter(price_list::Column::ItemId.eq(&item))
+            .order_by_asc(price_list::Column::Name)
+            .one(db)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("No price listed for {}", item)))?,
+    };
+    let chain: Vec<locations::Model> = location_chain(db, &location_id)
+        .await?
+        .into_iter()
+        .filter(|l| l.campaign_id == price.campaign_id)
+        .collect();
+    let Some(location) = chain.first() else {
+        return Err(AppError::NotFound(format!(
+            "Location {} not found in campaign",
+            location_id
+        )));
+    };
+
+    let modifiers = PriceModifier::find()
+        .filter(price_modifiers::Column::LocationId.is_in(chain.iter().map(|l| l.id.clone())))
+        .filter(
+            Condition::any()
+                .add(price_modifiers::Column::PriceId.eq(&price.id))
+                .add(
+                    Condition::all()
+                        .add(price_modifiers::Column::PriceId.is_null())
+                        .add(
+                            Condition::any()
+                                .add(price_modifiers::Column::Category.is_null())
+                                .add(price_modifiers::Column::Category.eq(&price.category)),
+                        ),
+                ),
+        )
+        .order_by_asc(price_modifiers::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut applied = Vec::new();
+    for place in &chain {
+        for modifier in modifiers.iter().filter(|m| m.location_id == place.id) {
+            applied.push(AppliedModifier {
+                id: modifier.id.clone(),
+                location_id: place.id.clone(),
+                location_name: place.name.clone(),
+                category: modifier.category.clone(),
+                price_id: modifier.price_id.clone(),
+                percent: modifier.percent,
+                reason: modifier.reason.clone(),
+            });
+        }
+    }
+    let percents: Vec<i32> = applied.iter().map(|m| m.percent).collect();
+
+    Ok(LocalPrice {
+        location_id: location.id.clone(),
+        location_name: location.name.clone(),
+        base_price: price.base_price,
+        local_price: apply_percents(price.base_price, &percents),
+        modifiers: applied,
+        price: price.into(),
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_price(
+    state: State<'_, AppState>,
+    input: CreatePriceInput,
+) -> Result<PriceResponse, AppError> {
+    create_price_impl(&state.db, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_price(state: State<'_, AppState>, id: String) -> Result<PriceResponse, AppError> {
+    get_price_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_prices(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    category: Option<String>,
+) -> Result<Vec<PriceResponse>, AppError> {
+    list_prices_impl(&state.db, campaign_id, category).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_price(
+    state: State<'_, AppState>,
+    id: String,
+    input: UpdatePriceInput,
+) -> Result<PriceResponse, AppError> {
+    update_price_impl(&state.db, id, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_price(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    delete_price_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_price_modifier(
+    state: State<'_, AppState>,
+    location_id: String,
+    percent: i32,
+    category: Option<String>,
+    price_id: Option<String>,
+    reason: Option<String>,
+) -> Result<PriceModifierResponse, AppError> {
+    create_price_modifier_impl(&state.db, location_id, percent, category, price_id, reason).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_price_modifiers(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    location_id: Option<String>,
+) -> Result<Vec<PriceModifierResponse>, AppError> {
+    list_price_modifiers_impl(&state.db, campaign_id, location_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_price_modifier(
+    state: State<'_, AppState>,
+    id: String,
+    percent: Option<i32>,
+    reason: Option<String>,
+) -> Result<PriceModifierResponse, AppError> {
+    update_price_modifier_impl(&state.db, id, percent, reason).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_price_modifier(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    delete_price_modifier_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_local_price(
+    state: State<'_, AppState>,
+    item: String,
+    location_id: String,
+) -> Result<LocalPrice, AppError> {
+    get_local_price_impl(&state.db, item, location_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_percents_compounds() {
+        assert_eq!(apply_percents(100, &[]), 100);
+        assert_eq!(apply_percents(100, &[50]), 150);
+        // +50% then -20% is 120, not 130
+        assert_eq!(apply_percents(100, &[50, -20]), 120);
+        assert_eq!(apply_percents(15, &[10]), 17);
+        assert_eq!(apply_percents(100, &[-100, 50]), 0);
+    }
+
+    #[test]
+    fn test_normalize_category() {
+        assert_eq!(normalize_category("  Weapons "), "weapons");
+    }
+}
@@ -0,0 +1,406 @@
+//! Progress clocks, as in Blades in the Dark: a named circle of segments
+//! filled in as a threat or project advances. A clock can hang off the
+//! faction or quest it tracks. Unfilled clocks are the campaign's fronts and
+//! show on the GM screen.
+
+use crate::commands::entity_refs::entities_exist_impl;
+use crate::commands::relationship::entity_names;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::progress_clocks::{self, Entity as ProgressClock};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tauri::State;
+use tracing::instrument;
+
+/// Entity types a clock can be attached to
+pub const CLOCK_ENTITY_TYPES: &[&str] = &["organization", "quest"];
+/// Fewest segments a clock can have
+pub const MIN_SEGMENTS: i32 = 2;
+/// Most segments a clock can have
+pub const MAX_SEGMENTS: i32 = 24;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProgressClockResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub segments: i32,
+    pub filled: i32,
+    /// Every segment is filled
+    pub complete: bool,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    /// Name of the attached faction or quest
+    pub entity_name: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<progress_clocks::Model> for ProgressClockResponse {
+    fn from(model: progress_clocks::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            name: model.name,
+            description: model.description,
+            complete: model.filled >= model.segments,
+            segments: model.segments,
+            filled: model.filled,
+            entity_type: model.entity_type,
+            entity_id: model.entity_id,
+            entity_name: None,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+/// Responses for clocks in one campaign, with the names of what they track
+async fn with_entity_names(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    clocks: Vec<progress_clocks::Model>,
+) -> Result<Vec<ProgressClockResponse>, AppError> {
+    let types: BTreeSet<&str> = clocks
+        .iter()
+        .filter_map(|c| c.entity_type.as_deref())
+        .collect();
+    let names = entity_names(db, campaign_id, &types).await?;
+    Ok(clocks
+        .into_iter()
+        .map(|clock| {
+            let name = match (&clock.entity_type, &clock.entity_id) {
+                (Some(t), Some(id)) => names.get(&(t.clone(), id.clone())).cloned(),
+                _ => None,
+            };
+            ProgressClockResponse {
+                entity_name: name,
+                ..clock.into()
+            }
+        })
+        .collect())
+}
+
+async fn response(
+    db: &DatabaseConnection,
+    clock: progress_clocks::Model,
+) -> Result<ProgressClockResponse, AppError> {
+    let campaign_id = clock.campaign_id.clone();
+    let mut responses = with_entity_names(db, &campaign_id, vec![clock]).await?;
+    Ok(responses.remove(0))
+}
+
+async fn find_clock(db: &DatabaseConnection, id: &str) -> Result<progress_clocks::Model, AppError> {
+    ProgressClock::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Progress clock {} not found", id)))
+}
+
+fn validate_segments(segments: i32) -> Result<(), AppError> {
+    if !(MIN_SEGMENTS..=MAX_SEGMENTS).contains(&segments) {
+        return Err(AppError::Validation(format!(
+            "segments: must be between {} and {}",
+            MIN_SEGMENTS, MAX_SEGMENTS
+        )));
+    }
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<(), AppError> {
+    if name.trim().is_empty() || name.chars().count() > 200 {
+        return Err(AppError::Validation(
+            "name: must be 1-200 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Create an empty clock, attached to the faction or quest it tracks if
+/// `entity_type` and `entity_id` are given
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn create_progress_clock_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    name: String,
+    segments: i32,
+    description: Option<String>,
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+) -> Result<ProgressClockResponse, AppError> {
+    validate_name(&name)?;
+    validate_segments(segments)?;
+    match (&entity_type, &entity_id) {
+        (Some(entity_type), Some(entity_id)) => {
+            if !CLOCK_ENTITY_TYPES.contains(&entity_type.as_str()) {
+                return Err(AppError::Validation(format!(
+                    "entity_type: must be one of: {}",
+                    CLOCK_ENTITY_TYPES.join(", ")
+                )));
+            }
+            let found = entities_exist_impl(
+                db,
+                vec![(entity_type.clone(), entity_id.clone())],
+                Some(campaign_id.clone()),
+            )
+            .await?;
+            if !found.iter().all(|e| e.exists) {
+                return Err(AppError::NotFound(format!(
+                    "{} {} not found in campaign",
+                    entity_type, entity_id
+                )));
+            }
+        }
+        (None, None) => {}
+        _ => {
+            return Err(AppError::Validation(
+                "entity_id: entity_type and entity_id must be given together".to_string(),
+            ))
+        }
+    }
+
+    let now = chrono::Utc::now();
+    let model = progress_clocks::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id),
+        name: Set(name),
+        description: Set(description),
+        segments: Set(segments),
+        filled: Set(0),
+        entity_type: Set(entity_type),
+        entity_id: Set(entity_id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    let result = model.insert(db).await?;
+    response(db, result).await
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_progress_clock_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<ProgressClockResponse, AppError> {
+    let clock = find_clock(db, &id).await?;
+    response(db, clock).await
+}
+
+/// Every clock in the campaign by name, or only those on one entity
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_progress_clocks_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+) -> Result<Vec<ProgressClockResponse>, AppError> {
+    let mut query =
+        ProgressClock::find().filter(progress_clocks::Column::CampaignId.eq(&campaign_id));
+    if let Some(entity_type) = entity_type {
+        query = query.filter(progress_clocks::Column::EntityType.eq(entity_type));
+    }
+    if let Some(entity_id) = entity_id {
+        query = query.filter(progress_clocks::Column::EntityId.eq(entity_id));
+    }
+    let clocks = query
+        .order_by_asc(progress_clocks::Column::Name)
+        .all(db)
+        .await?;
+
+    with_entity_names(db, &campaign_id, clocks).await
+}
+
+/// Clocks still filling, closest to complete first
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_active_progress_clocks_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<ProgressClockResponse>, AppError> {
+    let mut clocks = ProgressClock::find()
+        .filter(progress_clocks::Column::CampaignId.eq(&campaign_id))
+        .filter(
+            Expr::col(progress_clocks::Column::Filled)
+                .lt(Expr::col(progress_clocks::Column::Segments)),
+        )
+        .order_by_asc(progress_clocks::Column::Name)
+        .all(db)
+        .await?;
+    clocks.sort_by_key(|c| c.segments - c.filled);
+
+    with_entity_names(db, &campaign_id, clocks).await
+}
+
+/// Rename, describe or resize a clock. Shrinking it below its filled
+/// segments leaves it complete.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_progress_clock_impl(
+    db: &DatabaseConnection,
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+    segments: Option<i32>,
+) -> Result<ProgressClockResponse, AppError> {
+    if let Some(name) = &name {
+        validate_name(name)?;
+    }
+    if let Some(segments) = segments {
+        validate_segments(segments)?;
+    }
+
+    let clock = find_clock(db, &id).await?;
+    let filled = clock.filled;
+    let mut active: progress_clocks::ActiveModel = clock.into();
+    if let Some(name) = name {
+        active.name = Set(name);
+    }
+    if let Some(description) = description {
+        active.description = Set(Some(description));
+    }
+    if let Some(segments) = segments {
+        active.segments = Set(segments);
+        active.filled = Set(filled.min(segments));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    response(db, result).await
+}
+
+/// Fill `ticks` more segments, or clear some with a negative count. The
+/// clock stops at empty and full.
+#[instrument(skip_all, fields(id = %id, ticks), err)]
+pub async fn tick_progress_clock_impl(
+    db: &DatabaseConnection,
+    id: String,
+    ticks: i32,
+) -> Result<ProgressClockResponse, AppError> {
+    if ticks == 0 {
+        return Err(AppError::Validation("ticks: must not be zero".to_string()));
+    }
+
+    let clock = find_clock(db, &id).await?;
+    let filled = clock.filled.saturating_add(ticks).clamp(0, clock.segments);
+    let mut active: progress_clocks::ActiveModel = clock.into();
+    active.filled = Set(filled);
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    response(db, result).await
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn reset_progress_clock_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<ProgressClockResponse, AppError> {
+    let clock = find_clock(db, &id).await?;
+    let mut active: progress_clocks::ActiveModel = clock.into();
+    active.filled = Set(0);
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    response(db, result).await
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_progress_clock_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = ProgressClock::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_progress_clock(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    name: String,
+    segments: i32,
+    description: Option<String>,
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+) -> Result<ProgressClockResponse, AppError> {
+    create_progress_clock_impl(
+        &state.db,
+        campaign_id,
+        name,
+        segments,
+        description,
+        entity_type,
+        entity_id,
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_progress_clock(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<ProgressClockResponse, AppError> {
+    get_progress_clock_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_progress_clocks(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+) -> Result<Vec<ProgressClockResponse>, AppError> {
+    list_progress_clocks_impl(&state.db, campaign_id, entity_type, entity_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_active_progress_clocks(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<ProgressClockResponse>, AppError> {
+    list_active_progress_clocks_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_progress_clock(
+    state: State<'_, AppState>,
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+    segments: Option<i32>,
+) -> Result<ProgressClockResponse, AppError> {
+    update_progress_clock_impl(&state.db, id, name, description, segments).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn tick_progress_clock(
+    state: State<'_, AppState>,
+    id: String,
+    ticks: i32,
+) -> Result<ProgressClockResponse, AppError> {
+    tick_progress_clock_impl(&state.db, id, ticks).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn reset_progress_clock(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<ProgressClockResponse, AppError> {
+    reset_progress_clock_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_progress_clock(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    delete_progress_clock_impl(&state.db, id).await
+}
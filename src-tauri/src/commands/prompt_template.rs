@@ -0,0 +1,343 @@
+use crate::commands::validation::{CreatePromptTemplateInput, UpdatePromptTemplateInput};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::prompt_templates::{self, Entity as PromptTemplate};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PromptTemplateResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub name: String,
+    pub template: String,
+    pub context_type: String,
+    /// Placeholder names in order of first use
+    pub variables: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<prompt_templates::Model> for PromptTemplateResponse {
+    fn from(model: prompt_templates::Model) -> Self {
+        Self {
+            variables: template_variables(&model.template),
+            id: model.id,
+            campaign_id: model.campaign_id,
+            name: model.name,
+            template: model.template,
+            context_type: model.context_type,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+/// A template with its placeholders filled in, ready to send to the model
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RenderedPrompt {
+    pub template_id: String,
+    pub name: String,
+    pub context_type: String,
+    pub prompt: String,
+}
+
+/// `{{name}}` placeholders in `template` with their byte ranges. Names are
+/// letters, digits and underscores; anything else between braces is left
+/// as literal text.
+fn placeholders(template: &str) -> Vec<(Range<usize>, &str)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = template[offset..].find("{{") {
+        let start = offset + open;
+        let Some(close) = template[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let name = template[start + 2..end - 2].trim();
+        if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            found.push((start..end, name));
+            offset = end;
+        } else {
+            offset = start + 2;
+        }
+    }
+    found
+}
+
+fn template_variables(template: &str) -> Vec<String> {
+    let mut variables: Vec<String> = Vec::new();
+    for (_, name) in placeholders(template) {
+        if !variables.iter().any(|v| v == name) {
+            variables.push(name.to_string());
+        }
+    }
+    variables
+}
+
+/// Fill in every placeholder, or list the names with no binding
+fn render_template(
+    template: &str,
+    bindings: &HashMap<String, String>,
+) -> Result<String, Vec<String>> {
+    let mut out = String::with_capacity(template.len());
+    let mut missing: Vec<String> = Vec::new();
+    let mut last = 0;
+    for (range, name) in placeholders(template) {
+        out.push_str(&template[last..range.start]);
+        match bindings.get(name) {
+            Some(value) => out.push_str(value),
+            None if !missing.iter().any(|m| m == name) => missing.push(name.to_string()),
+            None => {}
+        }
+        last = range.end;
+    }
+    out.push_str(&template[last..]);
+
+    if missing.is_empty() {
+        Ok(out)
+    } else {
+        Err(missing)
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, err)]
+pub async fn create_prompt_template_impl(
+    db: &DatabaseConnection,
+    input: CreatePromptTemplateInput,
+) -> Result<PromptTemplateResponse, AppError> {
+    input.validate()?;
+
+    let now = chrono::Utc::now();
+    let model = prompt_templates::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(input.campaign_id),
+        name: Set(input.name),
+        template: Set(input.template),
+        context_type: Set(input.context_type),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    let result = model.insert(db).await?;
+    Ok(result.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_prompt_template_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<PromptTemplateResponse, AppError> {
+    let template = PromptTemplate::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Prompt template {} not found", id)))?;
+
+    Ok(template.into())
+}
+
+/// Templates for a campaign. With a `context_type`, only templates for that
+/// context plus the general ones.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_prompt_templates_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    context_type: Option<String>,
+) -> Result<Vec<PromptTemplateResponse>, AppError> {
+    let mut select =
+        PromptTemplate::find().filter(prompt_templates::Column::CampaignId.eq(&campaign_id));
+    if let Some(context_type) = &context_type {
+        select = select.filter(
+            prompt_templates::Column::ContextType.is_in([context_type.as_str(), "general"]),
+        );
+    }
+    let templates = select
+        .order_by_asc(prompt_templates::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(templates.into_iter().map(|t| t.into()).collect())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_prompt_template_impl(
+    db: &DatabaseConnection,
+    id: String,
+    input: UpdatePromptTemplateInput,
+) -> Result<PromptTemplateResponse, AppError> {
+    input.validate()?;
+
+    let template = PromptTemplate::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Prompt template {} not found", id)))?;
+
+    let mut active: prompt_templates::ActiveModel = template.into();
+
+    if let Some(n) = input.name {
+        active.name = Set(n);
+    }
+    if let Some(t) = input.template {
+        active.template = Set(t);
+    }
+    if let Some(c) = input.context_type {
+        active.context_type = Set(c);
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    Ok(result.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_prompt_template_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = PromptTemplate::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Substitute `bindings` into a template. Every placeholder needs a value;
+/// extra bindings are ignored.
+#[instrument(skip_all, fields(template_id = %template_id), err)]
+pub async fn render_prompt_impl(
+    db: &DatabaseConnection,
+    template_id: String,
+    bindings: HashMap<String, String>,
+) -> Result<RenderedPrompt, AppError> {
+    let template = PromptTemplate::find_by_id(&template_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Prompt template {} not found", template_id)))?;
+
+    let prompt = render_template(&template.template, &bindings).map_err(|missing| {
+        AppError::Validation(format!(
+            "bindings: missing values for {}",
+            missing.join(", ")
+        ))
+    })?;
+
+    Ok(RenderedPrompt {
+        template_id,
+        name: template.name,
+        context_type: template.context_type,
+        prompt,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_prompt_template(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    name: String,
+    template: String,
+    context_type: Option<String>,
+) -> Result<PromptTemplateResponse, AppError> {
+    let input = CreatePromptTemplateInput {
+        campaign_id,
+        name,
+        template,
+        context_type: context_type.unwrap_or_else(|| "general".to_string()),
+    };
+    create_prompt_template_impl(&state.db, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_prompt_template(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<PromptTemplateResponse, AppError> {
+    get_prompt_template_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_prompt_templates(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    context_type: Option<String>,
+) -> Result<Vec<PromptTemplateResponse>, AppError> {
+    list_prompt_templates_impl(&state.db, campaign_id, context_type).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_prompt_template(
+    state: State<'_, AppState>,
+    id: String,
+    name: Option<String>,
+    template: Option<String>,
+    context_type: Option<String>,
+) -> Result<PromptTemplateResponse, AppError> {
+    let input = UpdatePromptTemplateInput {
+        name,
+        template,
+        context_type,
+    };
+    update_prompt_template_impl(&state.db, id, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_prompt_template(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    delete_prompt_template_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn render_prompt(
+    state: State<'_, AppState>,
+    template_id: String,
+    bindings: HashMap<String, String>,
+) -> Result<RenderedPrompt, AppError> {
+    render_prompt_impl(&state.db, template_id, bindings).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_variables() {
+        let template = "Describe {{ character }} arriving at {{location}}. {{character}} is \
+                        {{mood}}; ignore {{not a name}} and {{}}.";
+        assert_eq!(
+            template_variables(template),
+            vec!["character", "location", "mood"]
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let template = "Write a rumor about {{target}} heard in {{place}}.";
+        let mut bindings = HashMap::new();
+        bindings.insert("target".to_string(), "Mira".to_string());
+
+        assert_eq!(
+            render_template(template, &bindings),
+            Err(vec!["place".to_string()])
+        );
+
+        bindings.insert("place".to_string(), "the Salt Docks".to_string());
+        bindings.insert("unused".to_string(), "ignored".to_string());
+        assert_eq!(
+            render_template(template, &bindings).as_deref(),
+            Ok("Write a rumor about Mira heard in the Salt Docks.")
+        );
+        assert_eq!(
+            render_template("{{ unclosed", &bindings).as_deref(),
+            Ok("{{ unclosed")
+        );
+    }
+}
@@ -1,10 +1,12 @@
-use crate::commands::validation::CreateQuestInput;
+use crate::commands::references::remove_references;
+use crate::commands::validation::{CreateQuestInput, QUEST_STATUS};
 use crate::db::AppState;
 use crate::error::AppError;
 use ::entity::quests::{self, Entity as Quest};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,6 +22,8 @@ pub struct QuestResponse {
     pub complications: Option<String>,
     pub resolution: Option<String>,
     pub reward: Option<String>,
+    pub color: Option<String>,
+    pub icon: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -38,12 +42,106 @@ impl From<quests::Model> for QuestResponse {
             complications: model.complications,
             resolution: model.resolution,
             reward: model.reward,
+            color: model.color,
+            icon: model.icon,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
     }
 }
 
+/// Relationship type linking a quest to the character or organization that
+/// offers it
+pub const QUEST_GIVER_RELATIONSHIP: &str = "quest_giver";
+
+/// Quests grouped into one column per status, for the kanban view
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestBoard {
+    pub columns: Vec<QuestBoardColumn>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestBoardColumn {
+    pub status: String,
+    pub quests: Vec<QuestBoardCard>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestBoardCard {
+    pub id: String,
+    pub name: String,
+    pub plot_type: String,
+    /// Checklist items (`- [ ]` / `- [x]`) found in the objectives
+    pub objectives_total: u32,
+    pub objectives_completed: u32,
+    /// `None` when the objectives have no checklist items
+    pub completion_percent: Option<u32>,
+    pub giver_names: Vec<String>,
+    pub last_session: Option<QuestBoardSession>,
+}
+
+/// Most recent session that links to or mentions the quest
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestBoardSession {
+    pub id: String,
+    pub session_number: i32,
+    pub title: Option<String>,
+}
+
+/// Count checklist items in quest objectives, returning (completed, total).
+/// Objectives are usually editor JSON, where a typed `- [x]` becomes a list
+/// item starting with `[x]`; plain markdown is handled the same way.
+fn count_objectives(objectives: &str) -> (u32, u32) {
+    let mut items = Vec::new();
+    match serde_json::from_str::<serde_json::Value>(objectives) {
+        Ok(doc) if doc.is_object() => collect_checklist_items(&doc, &mut items),
+        _ => {
+            for line in objectives.lines() {
+                let line = line.trim_start();
+                if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+                    items.extend(checkbox_state(rest));
+                }
+            }
+        }
+    }
+
+    let completed = items.iter().filter(|done| **done).count() as u32;
+    (completed, items.len() as u32)
+}
+
+fn checkbox_state(text: &str) -> Option<bool> {
+    if text.starts_with("[ ]") {
+        Some(false)
+    } else if text.starts_with("[x]") || text.starts_with("[X]") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+fn collect_checklist_items(node: &serde_json::Value, items: &mut Vec<bool>) {
+    match node.get("type").and_then(|t| t.as_str()) {
+        Some("taskItem") => {
+            let checked = node["attrs"]["checked"].as_bool().unwrap_or(false);
+            items.push(checked);
+        }
+        Some("listItem") => {
+            let first_text = node["content"][0]["content"][0]["text"].as_str();
+            items.extend(first_text.and_then(checkbox_state));
+        }
+        _ => {}
+    }
+
+    if let Some(children) = node.get("content").and_then(|c| c.as_array()) {
+        for child in children {
+            collect_checklist_items(child, items);
+        }
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, err)]
 pub async fn create_quest_impl(
     db: &DatabaseConnection,
     input: CreateQuestInput,
@@ -66,14 +164,138 @@ pub async fn create_quest_impl(
         complications: Set(None),
         resolution: Set(None),
         reward: Set(None),
+        color: Set(None),
+        icon: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     let result = model.insert(db).await?;
     Ok(result.into())
 }
 
+/// Giver names and the last session are resolved in the same query: givers
+/// through `quest_giver` relationships in either direction, sessions through
+/// any relationship to the quest or a mention of its name in the notes,
+/// summary or highlights.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_quest_board_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<QuestBoard, AppError> {
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            r#"
+            WITH quest_links AS (
+                SELECT source_id AS quest_id, target_type AS other_type,
+                       target_id AS other_id, relationship_type
+                FROM relationships WHERE campaign_id = $1 AND source_type = 'quest'
+                UNION ALL
+                SELECT target_id, source_type, source_id, relationship_type
+                FROM relationships WHERE campaign_id = $1 AND target_type = 'quest'
+            ),
+            givers AS (
+                SELECT l.quest_id, group_concat(COALESCE(c.name, o.name), char(31)) AS names
+                FROM quest_links l
+                LEFT JOIN characters c ON l.other_type = 'character' AND c.id = l.other_id
+                LEFT JOIN organizations o ON l.other_type = 'organization' AND o.id = l.other_id
+                WHERE l.relationship_type = $2 AND COALESCE(c.name, o.name) IS NOT NULL
+                GROUP BY l.quest_id
+            ),
+            touched AS (
+                SELECT q.id AS quest_id, s.id AS session_id, s.session_number, s.title,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY q.id ORDER BY s.session_number DESC
+                       ) AS rn
+                FROM quests q
+                JOIN sessions s ON s.campaign_id = q.campaign_id
+                WHERE q.campaign_id = $1
+                AND (
+                    EXISTS (
+                        SELECT 1 FROM quest_links l
+                        WHERE l.quest_id = q.id AND l.other_type = 'session'
+                        AND l.other_id = s.id
+                    )
+                    OR instr(
+                        lower(COALESCE(s.notes, '') || ' ' || COALESCE(s.summary, '')
+                              || ' ' || COALESCE(s.highlights, '')),
+                        lower(q.name)
+                    ) > 0
+                )
+            )
+            SELECT q.id, q.name, q.status, q.plot_type, q.objectives,
+                   g.names AS giver_names,
+                   t.session_id, t.session_number, t.title AS session_title
+            FROM quests q
+            LEFT JOIN givers g ON g.quest_id = q.id
+            LEFT JOIN touched t ON t.quest_id = q.id AND t.rn = 1
+            WHERE q.campaign_id = $1
+            ORDER BY q.name
+            "#,
+            [campaign_id.into(), QUEST_GIVER_RELATIONSHIP.into()],
+        ))
+        .await?;
+
+    let mut columns: Vec<QuestBoardColumn> = QUEST_STATUS
+        .iter()
+        .map(|&status| QuestBoardColumn {
+            status: status.to_string(),
+            quests: Vec::new(),
+        })
+        .collect();
+
+    for row in rows {
+        let status: String = row.try_get("", "status")?;
+        let objectives: Option<String> = row.try_get("", "objectives")?;
+        let (objectives_completed, objectives_total) =
+            count_objectives(objectives.as_deref().unwrap_or_default());
+
+        let mut giver_names: Vec<String> = row
+            .try_get::<Option<String>>("", "giver_names")?
+            .map(|names| names.split('\u{1f}').map(str::to_string).collect())
+            .unwrap_or_default();
+        giver_names.sort();
+        giver_names.dedup();
+
+        let last_session = match row.try_get::<Option<String>>("", "session_id")? {
+            Some(id) => Some(QuestBoardSession {
+                id,
+                session_number: row.try_get("", "session_number")?,
+                title: row.try_get("", "session_title")?,
+            }),
+            None => None,
+        };
+
+        let card = QuestBoardCard {
+            id: row.try_get("", "id")?,
+            name: row.try_get("", "name")?,
+            plot_type: row.try_get("", "plot_type")?,
+            objectives_total,
+            objectives_completed,
+            completion_percent: (objectives_total > 0)
+                .then(|| objectives_completed * 100 / objectives_total),
+            giver_names,
+            last_session,
+        };
+
+        // Statuses are validated on write, but keep anything unexpected visible
+        match columns.iter_mut().find(|c| c.status == status) {
+            Some(column) => column.quests.push(card),
+            None => columns.push(QuestBoardColumn {
+                status,
+                quests: vec![card],
+            }),
+        }
+    }
+
+    Ok(QuestBoard { columns })
+}
+
+// ============ Tauri command wrappers ============
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn create_quest(
     state: State<'_, AppState>,
@@ -98,6 +320,7 @@ pub async fn create_quest(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_quest(state: State<'_, AppState>, id: String) -> Result<QuestResponse, AppError> {
     let quest = Quest::find_by_id(&id)
         .one(&state.db)
@@ -108,6 +331,7 @@ pub async fn get_quest(state: State<'_, AppState>, id: String) -> Result<QuestRe
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn list_quests(
     state: State<'_, AppState>,
     campaign_id: String,
@@ -122,6 +346,7 @@ pub async fn list_quests(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn update_quest(
     state: State<'_, AppState>,
     id: String,
@@ -176,7 +401,54 @@ pub async fn update_quest(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_quest(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
-    let result = Quest::delete_by_id(&id).exec(&state.db).await?;
+    let txn = state.db.begin().await?;
+    remove_references(&txn, "quest", &id).await?;
+    let result = Quest::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
     Ok(result.rows_affected > 0)
 }
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_quest_board(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<QuestBoard, AppError> {
+    get_quest_board_impl(&state.db, campaign_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_objectives() {
+        let objectives = "Find the map\n- [x] Reach the pass\n  * [X] Bribe the guard\n- [ ] Open the vault\n- plain bullet";
+        assert_eq!(count_objectives(objectives), (2, 3));
+        assert_eq!(count_objectives("No checklist here"), (0, 0));
+    }
+
+    #[test]
+    fn test_count_objectives_in_editor_json() {
+        let item = |text: &str| {
+            serde_json::json!({
+                "type": "listItem",
+                "content": [{"type": "paragraph", "content": [{"type": "text", "text": text}]}]
+            })
+        };
+        let doc = serde_json::json!({
+            "type": "doc",
+            "content": [
+                {"type": "paragraph", "content": [{"type": "text", "text": "Goals"}]},
+                {"type": "bulletList", "content": [
+                    item("[x] Reach the pass"),
+                    item("[ ] Open the vault"),
+                    item("Note to self"),
+                ]},
+                {"type": "taskItem", "attrs": {"checked": true}},
+            ]
+        });
+        assert_eq!(count_objectives(&doc.to_string()), (2, 3));
+    }
+}
@@ -0,0 +1,275 @@
+//! Capture a pasted blob mid-session without stopping to file it. Lines that
+//! name entities, like `NPC: Mira - smuggler` or bullets under a "Locations"
+//! heading, come back as proposals for the GM to confirm; text that names
+//! nothing is saved as a note so the idea isn't lost.
+
+use crate::commands::markdown_import::existing_entities;
+use crate::commands::snippet::{create_snippet_impl, SnippetResponse};
+use crate::commands::validation::CreateSnippetInput;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Words that mark a line or heading as naming entities of a type. Headings
+/// may use the plural.
+const TYPE_WORDS: &[(&str, &str)] = &[
+    ("npc", "character"),
+    ("character", "character"),
+    ("people", "character"),
+    ("location", "location"),
+    ("place", "location"),
+    ("org", "organization"),
+    ("organization", "organization"),
+    ("faction", "organization"),
+    ("quest", "quest"),
+    ("hook", "quest"),
+    ("item", "item"),
+    ("loot", "item"),
+];
+
+/// Separators between a name and its description, e.g. "Mira - smuggler"
+const NAME_SEPARATORS: &[&str] = &[" - ", " — ", " – ", ": ", ", "];
+
+/// Longest capture accepted, matching the snippet content limit
+const MAX_CAPTURE_CHARS: usize = 50_000;
+/// Longest note name taken from the text
+const MAX_TITLE_CHARS: usize = 60;
+
+/// An entity the captured text seems to describe
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureProposal {
+    pub entity_type: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// The campaign's entity of this type and name, when there already is one
+    pub existing_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuickCaptureResult {
+    /// The note saved when the text named no entities
+    pub note: Option<SnippetResponse>,
+    pub proposals: Vec<CaptureProposal>,
+}
+
+/// The entity type a word like "NPC" or "locations" names
+pub(crate) fn entity_type_for(word: &str) -> Option<&'static str> {
+    let word = word.trim().to_lowercase();
+    TYPE_WORDS
+        .iter()
+        .find(|(w, _)| word == *w || word.strip_suffix('s') == Some(*w))
+        .map(|(_, entity_type)| *entity_type)
+}
+
+/// Split "Mira - smuggler" into a name and description at the first
+/// separator. Markdown emphasis and link brackets around the name go.
+fn split_entry(text: &str) -> Option<(String, Option<String>)> {
+    let text = text.trim();
+    let (name, description) = NAME_SEPARATORS
+        .iter()
+        .filter_map(|sep| text.split_once(sep))
+        .min_by_key(|(name, _)| name.len())
+        .unwrap_or((text, ""));
+    let name = name
+        .trim_matches(|c: char| matches!(c, '*' | '_' | '[' | ']') || c.is_whitespace())
+        .to_string();
+    if name.is_empty() || name.chars().count() > 200 {
+        return None;
+    }
+    let description = Some(description.trim().to_string()).filter(|d| !d.is_empty());
+    Some((name, description))
+}
+
+/// A list item's text, for `-`, `*`, `+` and `1.` bullets
+fn bullet_text(line: &str) -> Option<&str> {
+    if let Some(item) = ["- ", "* ", "+ "].iter().find_map(|m| line.strip_prefix(m)) {
+        return Some(item);
+    }
+    let (number, item) = line.split_once(". ")?;
+    number.chars().all(|c| c.is_ascii_digit()).then_some(item)
+}
+
+/// A note name from its title, shortened to `MAX_TITLE_CHARS`
+pub(crate) fn note_name(title: &str) -> String {
+    match title.char_indices().nth(MAX_TITLE_CHARS) {
+        Some((end, _)) => format!("{}…", title[..end].trim_end()),
+        None => title.to_string(),
+    }
+}
+
+/// An entity named in the text: its type, name and description
+type CaptureEntry = (&'static str, String, Option<String>);
+
+/// Read the text's title (its first heading or line) and the entities it
+/// names, merging repeats of the same type and name
+fn parse_capture(text: &str) -> (Option<String>, Vec<CaptureEntry>) {
+    let mut title = None;
+    let mut section = None;
+    let mut entries: Vec<CaptureEntry> = Vec::new();
+
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if line.starts_with('#') {
+            let heading = line.trim_start_matches('#').trim();
+            section = entity_type_for(heading);
+            if section.is_none() && title.is_none() && !heading.is_empty() {
+                title = Some(heading.to_string());
+            }
+            continue;
+        }
+
+        let bullet = bullet_text(line);
+        let body = bullet.unwrap_or(line);
+        let entry = match body.split_once(':') {
+            Some((word, rest)) if entity_type_for(word).is_some() => {
+                entity_type_for(word).zip(split_entry(rest))
+            }
+            _ => section.zip(bullet.and_then(split_entry)),
+        };
+        let Some((entity_type, (name, description))) = entry else {
+            if title.is_none() {
+                title = Some(line.to_string());
+            }
+            continue;
+        };
+
+        let key = name.to_lowercase();
+        match entries
+            .iter_mut()
+            .find(|(t, n, _)| *t == entity_type && n.to_lowercase() == key)
+        {
+            Some((_, _, existing)) => {
+                if existing.is_none() {
+                    *existing = description;
+                }
+            }
+            None => entries.push((entity_type, name, description)),
+        }
+    }
+
+    (title, entries)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Turn pasted text into entity proposals, or save it as a note when it
+/// names no entities. Proposals aren't created; the frontend confirms them
+/// through the usual create commands.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn quick_capture_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    text: String,
+) -> Result<QuickCaptureResult, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    if text.trim().is_empty() {
+        return Err(AppError::Validation("text: must not be empty".to_string()));
+    }
+    if text.chars().count() > MAX_CAPTURE_CHARS {
+        return Err(AppError::Validation(format!(
+            "text: must be at most {} characters",
+            MAX_CAPTURE_CHARS
+        )));
+    }
+
+    let (title, entries) = parse_capture(&text);
+    if !entries.is_empty() {
+        let existing = existing_entities(db, &campaign_id).await?;
+        let proposals = entries
+            .into_iter()
+            .map(|(entity_type, name, description)| CaptureProposal {
+                existing_id: existing.get(&(entity_type, name.to_lowercase())).cloned(),
+                entity_type: entity_type.to_string(),
+                name,
+                description,
+            })
+            .collect();
+        return Ok(QuickCaptureResult {
+            note: None,
+            proposals,
+        });
+    }
+
+    let note = create_snippet_impl(
+        db,
+        CreateSnippetInput {
+            name: note_name(&title.unwrap_or_default()),
+            campaign_id,
+            snippet_type: "other".to_string(),
+            content: Some(text),
+        },
+    )
+    .await?;
+
+    Ok(QuickCaptureResult {
+        note: Some(note),
+        proposals: Vec::new(),
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn quick_capture(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    text: String,
+) -> Result<QuickCaptureResult, AppError> {
+    quick_capture_impl(&state.db, campaign_id, text).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_prefixed_lines_and_sections() {
+        let text = "# Session 12 ideas\n\
+                    NPC: Mira - smuggler who owes the guild\n\
+                    - Location: The Drowned Lantern, a dockside tavern\n\
+                    Remember to foreshadow the storm.\n\
+                    ## Factions\n\
+                    - **Tidecallers**: cult of the deep\n\
+                    - Harbor Watch\n\
+                    ## Notes\n\
+                    - not an entity\n\
+                    npc: mira\n";
+        let (title, entries) = parse_capture(text);
+
+        assert_eq!(title.as_deref(), Some("Session 12 ideas"));
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "character",
+                    "Mira".to_string(),
+                    Some("smuggler who owes the guild".to_string())
+                ),
+                (
+                    "location",
+                    "The Drowned Lantern".to_string(),
+                    Some("a dockside tavern".to_string())
+                ),
+                (
+                    "organization",
+                    "Tidecallers".to_string(),
+                    Some("cult of the deep".to_string())
+                ),
+                ("organization", "Harbor Watch".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_text_names_nothing() {
+        let (title, entries) = parse_capture("The party should hear thunder.\nLater: rain.");
+        assert_eq!(title.as_deref(), Some("The party should hear thunder."));
+        assert!(entries.is_empty());
+    }
+}
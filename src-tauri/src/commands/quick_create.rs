@@ -0,0 +1,477 @@
+//! One-line entity creation, e.g. "npc Elara, elf herbalist in Hobbiton,
+//! ally of Frodo". The line is parsed by rules rather than an AI provider,
+//! so it works offline, and comes back as a plan of entities and
+//! relationships for the GM to confirm; nothing is written here.
+//!
+//! Entries are separated by `;` or line breaks. Each starts with an optional
+//! type word and the name, followed by comma-separated clauses: a
+//! description ("elf herbalist"), a place ("in Hobbiton") or a relation
+//! ("ally of Frodo and Sam").
+
+use crate::commands::kinship::is_symmetric_kinship;
+use crate::commands::markdown_import::{existing_entities, MARKDOWN_IMPORT_TYPES};
+use crate::commands::quick_capture::entity_type_for;
+use crate::commands::validation::LOCATION_TYPES;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::State;
+use tracing::instrument;
+
+/// Longest input accepted, in characters
+const MAX_INPUT_CHARS: usize = 2_000;
+
+/// Lineages recognised at the start of a character's description
+const LINEAGES: &[&str] = &[
+    "human",
+    "elf",
+    "half-elf",
+    "dwarf",
+    "halfling",
+    "hobbit",
+    "gnome",
+    "orc",
+    "half-orc",
+    "goblin",
+    "tiefling",
+    "dragonborn",
+];
+
+/// Words for a settlement that aren't location types themselves
+const SETTLEMENT_WORDS: &[&str] = &["hamlet", "village", "town", "city", "outpost"];
+
+/// Phrases relating the entity to others: the phrase, the relationship type
+/// and the type of the other entity when it doesn't exist yet
+const RELATION_PHRASES: &[(&str, &str, &str)] = &[
+    ("ally of", "ally", "character"),
+    ("friend of", "friend", "character"),
+    ("rival of", "rival", "character"),
+    ("enemy of", "enemy", "character"),
+    ("mentor of", "mentor", "character"),
+    ("mentor to", "mentor", "character"),
+    ("lover of", "lover", "character"),
+    ("employer of", "employer", "character"),
+    ("member of", "member", "organization"),
+    ("leader of", "leader", "organization"),
+    ("parent of", "parent_of", "character"),
+    ("mother of", "parent_of", "character"),
+    ("father of", "parent_of", "character"),
+    ("child of", "child_of", "character"),
+    ("son of", "child_of", "character"),
+    ("daughter of", "child_of", "character"),
+    ("sibling of", "sibling_of", "character"),
+    ("brother of", "sibling_of", "character"),
+    ("sister of", "sibling_of", "character"),
+    ("married to", "married_to", "character"),
+    ("spouse of", "married_to", "character"),
+];
+
+/// Relationship types that read the same from both ends
+const SYMMETRIC_TYPES: &[&str] = &["ally", "friend", "rival", "enemy"];
+
+/// Words introducing the place an entity is in
+const PLACE_WORDS: &[&str] = &["in", "at", "from"];
+
+/// An entity the input names, either new or one the campaign already has
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposedEntity {
+    pub entity_type: String,
+    pub name: String,
+    /// Fields to set, such as `lineage`, `occupation` or `location_type`
+    pub fields: BTreeMap<String, String>,
+    pub description: Option<String>,
+    /// Name of the parent location, for locations
+    pub parent: Option<String>,
+    /// The campaign's entity of this type and name, when there already is
+    /// one; the fields are then changes to it
+    pub existing_id: Option<String>,
+}
+
+/// A relationship between two entities of the plan, by type and name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposedRelationship {
+    pub source_type: String,
+    pub source_name: String,
+    pub relationship_type: String,
+    pub target_type: String,
+    pub target_name: String,
+    pub is_bidirectional: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct QuickCreatePlan {
+    /// Every entity the relationships refer to, new or existing
+    pub entities: Vec<ProposedEntity>,
+    pub relationships: Vec<ProposedRelationship>,
+}
+
+/// One entry of the input, before names are looked up
+#[derive(Debug, Default, PartialEq)]
+struct ParsedEntry {
+    entity_type: Option<&'static str>,
+    name: String,
+    descriptions: Vec<String>,
+    place: Option<String>,
+    /// Relationship type, type of a new target, and the target's name
+    relations: Vec<(&'static str, &'static str, String)>,
+}
+
+/// Drop a leading "a", "an" or "the" from a description
+fn strip_article(text: &str) -> &str {
+    let text = text.trim();
+    ["a ", "an ", "the "]
+        .iter()
+        .find_map(|article| {
+            text.get(..article.len())
+                .filter(|head| head.eq_ignore_ascii_case(article))
+                .map(|_| text[article.len()..].trim_start())
+        })
+        .unwrap_or(text)
+}
+
+/// Split "elf herbalist in Hobbiton" into its description and place
+fn split_place(clause: &str) -> (&str, Option<&str>) {
+    let lower = clause.to_ascii_lowercase();
+    for word in PLACE_WORDS {
+        if lower.starts_with(&format!("{} ", word)) {
+            return ("", Some(clause[word.len()..].trim()));
+        }
+        if let Some(at) = lower.find(&format!(" {} ", word)) {
+            return (
+                clause[..at].trim(),
+                Some(clause[at + word.len() + 2..].trim()),
+            );
+        }
+    }
+    (clause, None)
+}
+
+fn parse_entry(entry: &str) -> Option<ParsedEntry> {
+    let mut clauses = entry.split(',').map(str::trim);
+    let head = clauses.next()?;
+
+    // "npc Elara" or "NPC: Elara"
+    let (entity_type, name) = match head.split_once(':') {
+        Some((word, rest)) if entity_type_for(word).is_some() => (entity_type_for(word), rest),
+        _ => match head.split_once(char::is_whitespace) {
+            Some((word, rest)) if entity_type_for(word).is_some() => (entity_type_for(word), rest),
+            _ => (None, head),
+        },
+    };
+    // A type word on its own names nothing
+    let name = name.trim();
+    if name.is_empty() || (entity_type.is_none() && entity_type_for(name).is_some()) {
+        return None;
+    }
+
+    let mut parsed = ParsedEntry {
+        entity_type,
+        name: name.to_string(),
+        ..Default::default()
+    };
+    for clause in clauses {
+        let clause = clause.strip_prefix("and ").unwrap_or(clause).trim();
+        if clause.is_empty() {
+            continue;
+        }
+
+        let lower = clause.to_ascii_lowercase();
+        let relation = RELATION_PHRASES
+            .iter()
+            .find(|(phrase, _, _)| lower.starts_with(&format!("{} ", phrase)));
+        if let Some((phrase, relationship_type, target_type)) = relation {
+            for target in clause[phrase.len()..].split(" and ") {
+                let target = target.trim();
+                if !target.is_empty() {
+                    parsed
+                        .relations
+                        .push((relationship_type, target_type, target.to_string()));
+                }
+            }
+            continue;
+        }
+
+        let (description, place) = split_place(clause);
+        let description = strip_article(description);
+        if !description.is_empty() {
+            parsed.descriptions.push(description.to_string());
+        }
+        if let Some(place) = place.filter(|p| !p.is_empty()) {
+            parsed.place = Some(place.to_string());
+        }
+    }
+    Some(parsed)
+}
+
+/// Read a character's lineage and occupation, or a location's type, out of
+/// its first description. Whatever is left becomes the description.
+fn describe(entity: &mut ProposedEntity, descriptions: &[String]) {
+    let mut rest: Vec<&str> = descriptions.iter().map(String::as_str).collect();
+    if let Some(first) = rest.first().copied() {
+        match entity.entity_type.as_str() {
+            "character" => {
+                let (word, occupation) = first.split_once(' ').unwrap_or((first, ""));
+                let occupation = if LINEAGES.contains(&word.to_lowercase().as_str()) {
+                    entity
+                        .fields
+                        .insert("lineage".to_string(), word.to_string());
+                    occupation.trim()
+                } else {
+                    first
+                };
+                if !occupation.is_empty() {
+                    entity
+                        .fields
+                        .insert("occupation".to_string(), occupation.to_string());
+                }
+                rest.remove(0);
+            }
+            "location" => {
+                let location_type = first.split_whitespace().find_map(|word| {
+                    let word = word.to_lowercase();
+                    if SETTLEMENT_WORDS.contains(&word.as_str()) {
+                        Some("settlement")
+                    } else {
+                        LOCATION_TYPES.iter().copied().find(|t| *t == word)
+                    }
+                });
+                if let Some(location_type) = location_type {
+                    entity
+                        .fields
+                        .insert("location_type".to_string(), location_type.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    if !rest.is_empty() {
+        entity.description = Some(rest.join(", "));
+    }
+}
+
+/// Builds the plan, looking names up among the campaign's entities
+struct Planner {
+    existing: HashMap<(&'static str, String), String>,
+    plan: QuickCreatePlan,
+}
+
+impl Planner {
+    /// The type of the entity called `name`: an existing or already planned
+    /// one of that name, preferring `default`, else `default`
+    fn type_of(&self, name: &str, default: &'static str) -> String {
+        let key = name.to_lowercase();
+        if self.existing.contains_key(&(default, key.clone())) {
+            return default.to_string();
+        }
+        MARKDOWN_IMPORT_TYPES
+            .iter()
+            .find(|t| self.existing.contains_key(&(**t, key.clone())))
+            .map(|t| (*t).to_string())
+            .or_else(|| {
+                self.plan
+                    .entities
+                    .iter()
+                    .find(|e| e.name.to_lowercase() == key)
+                    .map(|e| e.entity_type.clone())
+            })
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    /// The planned entity of this type and name, adding it if needed
+    fn entity(&mut self, entity_type: &str, name: &str) -> &mut ProposedEntity {
+        let key = name.to_lowercase();
+        let index = match self
+            .plan
+            .entities
+            .iter()
+            .position(|e| e.entity_type == entity_type && e.name.to_lowercase() == key)
+        {
+            Some(index) => index,
+            None => {
+                let existing_id = MARKDOWN_IMPORT_TYPES
+                    .iter()
+                    .find(|t| **t == entity_type)
+                    .and_then(|t| self.existing.get(&(*t, key)))
+                    .cloned();
+                self.plan.entities.push(ProposedEntity {
+                    entity_type: entity_type.to_string(),
+                    name: name.to_string(),
+                    fields: BTreeMap::new(),
+                    description: None,
+                    parent: None,
+                    existing_id,
+                });
+                self.plan.entities.len() - 1
+            }
+        };
+        &mut self.plan.entities[index]
+    }
+
+    fn relate(
+        &mut self,
+        source: (&str, &str),
+        relationship_type: &str,
+        target_type: &'static str,
+        target_name: &str,
+    ) {
+        let target_type = self.type_of(target_name, target_type);
+        let target_name = self.entity(&target_type, target_name).name.clone();
+        let relationship = ProposedRelationship {
+            source_type: source.0.to_string(),
+            source_name: source.1.to_string(),
+            relationship_type: relationship_type.to_string(),
+            target_type,
+            target_name,
+            is_bidirectional: SYMMETRIC_TYPES.contains(&relationship_type)
+                || is_symmetric_kinship(relationship_type),
+        };
+        if !self.plan.relationships.contains(&relationship) {
+            self.plan.relationships.push(relationship);
+        }
+    }
+
+    fn add(&mut self, entry: ParsedEntry) {
+        let entity_type = match entry.entity_type {
+            Some(entity_type) => entity_type.to_string(),
+            None => self.type_of(&entry.name, "character"),
+        };
+        let entity = self.entity(&entity_type, &entry.name);
+        describe(entity, &entry.descriptions);
+        let name = entity.name.clone();
+
+        if let Some(place) = &entry.place {
+            if entity_type == "location" {
+                self.entity(&entity_type, &name).parent = Some(place.clone());
+                self.entity("location", place);
+            } else {
+                let relationship_type = match entity_type.as_str() {
+                    "character" => "resides_in",
+                    "organization" => "headquartered_in",
+                    _ => "located_in",
+                };
+                self.relate((&entity_type, &name), relationship_type, "location", place);
+            }
+        }
+        for (relationship_type, target_type, target) in &entry.relations {
+            self.relate(
+                (&entity_type, &name),
+                relationship_type,
+                target_type,
+                target,
+            );
+        }
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Parse a line like "npc Elara, elf herbalist in Hobbiton, ally of Frodo"
+/// into the entities and relationships it describes. Names are matched
+/// against the campaign's entities, so the plan says which already exist.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn parse_quick_create_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    input: String,
+) -> Result<QuickCreatePlan, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    if input.chars().count() > MAX_INPUT_CHARS {
+        return Err(AppError::Validation(format!(
+            "input: must be at most {} characters",
+            MAX_INPUT_CHARS
+        )));
+    }
+    let entries: Vec<ParsedEntry> = input.split([';', '\n']).filter_map(parse_entry).collect();
+    if entries.is_empty() {
+        return Err(AppError::Validation(
+            "input: must name at least one entity".to_string(),
+        ));
+    }
+
+    let mut planner = Planner {
+        existing: existing_entities(db, &campaign_id).await?,
+        plan: QuickCreatePlan::default(),
+    };
+    for entry in entries {
+        planner.add(entry);
+    }
+    Ok(planner.plan)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn parse_quick_create(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    input: String,
+) -> Result<QuickCreatePlan, AppError> {
+    parse_quick_create_impl(&state.db, campaign_id, input).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entry_clauses() {
+        let parsed = parse_entry("npc Elara, an elf herbalist in Hobbiton, ally of Frodo and Sam")
+            .expect("entry parses");
+        assert_eq!(
+            parsed,
+            ParsedEntry {
+                entity_type: Some("character"),
+                name: "Elara".to_string(),
+                descriptions: vec!["elf herbalist".to_string()],
+                place: Some("Hobbiton".to_string()),
+                relations: vec![
+                    ("ally", "character", "Frodo".to_string()),
+                    ("ally", "character", "Sam".to_string()),
+                ],
+            }
+        );
+
+        let parsed = parse_entry("Location: Bree, a village, at the Shire").expect("entry parses");
+        assert_eq!(parsed.entity_type, Some("location"));
+        assert_eq!(parsed.name, "Bree");
+        assert_eq!(parsed.descriptions, vec!["village"]);
+        assert_eq!(parsed.place.as_deref(), Some("the Shire"));
+
+        assert_eq!(parse_entry("npc ,"), None);
+    }
+
+    #[test]
+    fn test_describe_character_and_location() {
+        let mut elara = ProposedEntity {
+            entity_type: "character".to_string(),
+            name: "Elara".to_string(),
+            fields: BTreeMap::new(),
+            description: None,
+            parent: None,
+            existing_id: None,
+        };
+        describe(
+            &mut elara,
+            &["Elf herbalist".to_string(), "shy".to_string()],
+        );
+        assert_eq!(elara.fields["lineage"], "Elf");
+        assert_eq!(elara.fields["occupation"], "herbalist");
+        assert_eq!(elara.description.as_deref(), Some("shy"));
+
+        let mut bree = ProposedEntity {
+            entity_type: "location".to_string(),
+            ..elara.clone()
+        };
+        bree.fields.clear();
+        bree.description = None;
+        describe(&mut bree, &["walled village".to_string()]);
+        assert_eq!(bree.fields["location_type"], "settlement");
+        assert_eq!(bree.description.as_deref(), Some("walled village"));
+    }
+}
@@ -0,0 +1,81 @@
+//! Notes captured from the tray's quick-note window. The main window tells
+//! the backend which campaign is open, so the quick-note window, which has
+//! no campaign of its own, can file notes into that campaign's inbox.
+
+use crate::commands::inbox::{create_inbox_item_impl, InboxItemResponse};
+use crate::commands::quick_capture::note_name;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::tray::TrayState;
+use ::entity::campaigns::{self, Entity as Campaign};
+use sea_orm::*;
+use tauri::State;
+use tracing::instrument;
+
+// ============ Core implementation functions (testable) ============
+
+/// Save a quick note to the inbox of `campaign_id`, or of the most recently
+/// updated campaign when that's unset or has since been deleted
+#[instrument(skip_all, err)]
+pub async fn capture_quick_note_impl(
+    db: &DatabaseConnection,
+    campaign_id: Option<String>,
+    text: String,
+) -> Result<InboxItemResponse, AppError> {
+    let text = text.trim().to_string();
+    let Some(title) = text.lines().map(str::trim).find(|l| !l.is_empty()) else {
+        return Err(AppError::Validation("text: must not be empty".to_string()));
+    };
+
+    let active = match campaign_id {
+        Some(id) => Campaign::find_by_id(id).one(db).await?,
+        None => None,
+    };
+    let campaign = match active {
+        Some(campaign) => campaign,
+        None => Campaign::find()
+            .order_by_desc(campaigns::Column::UpdatedAt)
+            .one(db)
+            .await?
+            .ok_or_else(|| AppError::NotFound("No campaign to file the note in".to_string()))?,
+    };
+
+    create_inbox_item_impl(
+        db,
+        campaign.id,
+        "quick_note".to_string(),
+        note_name(title),
+        Some(text),
+        None,
+    )
+    .await
+}
+
+// ============ Tauri command wrappers ============
+
+/// Record the campaign open in the main window, or None when it has none
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_active_campaign(
+    tray: State<'_, TrayState>,
+    campaign_id: Option<String>,
+) -> Result<(), AppError> {
+    *tray
+        .active_campaign
+        .lock()
+        .map_err(|_| AppError::Internal("Tray state lock poisoned".into()))? = campaign_id;
+    Ok(())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn capture_quick_note(
+    state: State<'_, AppState>,
+    tray: State<'_, TrayState>,
+    text: String,
+) -> Result<InboxItemResponse, AppError> {
+    let campaign_id = tray
+        .active_campaign
+        .lock()
+        .map_err(|_| AppError::Internal("Tray state lock poisoned".into()))?
+        .clone();
+    capture_quick_note_impl(&state.db, campaign_id, text).await
+}
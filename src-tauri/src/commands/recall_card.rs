@@ -0,0 +1,556 @@
+//! Flashcards for remembering the campaign between sessions. Key facts, such
+//! as what a character is hiding, what drives them or what a place is like,
+//! become question-and-answer cards. Cards are reviewed on the SM-2 schedule,
+//! so the ones the GM keeps forgetting come back sooner than the ones they know.
+
+use crate::commands::registry::table_for;
+use crate::commands::validation::CreateRecallCardInput;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::text::plain_text;
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::characters::{self, Entity as Character};
+use ::entity::locations::{self, Entity as Location};
+use ::entity::recall_cards::{self, Entity as RecallCard};
+use ::entity::secrets::{self, Entity as Secret};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+/// Ease of a new card, in thousandths (2.5)
+pub const DEFAULT_EASE: i32 = 2500;
+/// SM-2 never lets ease drop below 1.3
+pub const MIN_EASE: i32 = 1300;
+/// Best review grade; 0 is a complete blank
+pub const MAX_GRADE: u8 = 5;
+/// Grades below this count as forgotten and restart the card
+pub const PASSING_GRADE: u8 = 3;
+/// Longest gap between reviews, so intervals can't grow without bound
+const MAX_INTERVAL_DAYS: i32 = 3650;
+/// Longest answer a generated card copies from its source
+const MAX_GENERATED_BACK: usize = 5000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecallCardResponse {
+    pub id: String,
+    pub campaign_id: String,
+    /// The entity the card is about, if any
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    /// Field a generated card was made from; None for cards written by hand
+    pub source_field: Option<String>,
+    pub front: String,
+    pub back: String,
+    pub ease_factor: f64,
+    pub interval_days: i32,
+    pub repetitions: i32,
+    pub due_at: String,
+    pub last_reviewed_at: Option<String>,
+    pub last_grade: Option<i32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<recall_cards::Model> for RecallCardResponse {
+    fn from(model: recall_cards::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            entity_type: model.entity_type,
+            entity_id: model.entity_id,
+            source_field: model.source_field,
+            front: model.front,
+            back: model.back,
+            ease_factor: f64::from(model.ease) / 1000.0,
+            interval_days: model.interval_days,
+            repetitions: model.repetitions,
+            due_at: model.due_at.to_string(),
+            last_reviewed_at: model.last_reviewed_at.map(|t| t.to_string()),
+            last_grade: model.last_grade,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RecallGenerateReport {
+    /// Cards made for facts that had none
+    pub created: usize,
+    /// Generated cards whose answer changed because their source was edited;
+    /// their review schedule is kept
+    pub refreshed: usize,
+}
+
+/// A card's place in the SM-2 schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Schedule {
+    ease: i32,
+    interval_days: i32,
+    repetitions: i32,
+}
+
+/// The schedule after a review graded `grade`. A pass lengthens the interval
+/// (1 day, then 6, then by the ease each time) and adjusts the ease by how
+/// hard the answer was; a fail starts the card over with its ease unchanged.
+fn next_schedule(current: Schedule, grade: u8) -> Schedule {
+    if grade < PASSING_GRADE {
+        return Schedule {
+            ease: current.ease,
+            interval_days: 1,
+            repetitions: 0,
+        };
+    }
+
+    let miss = i32::from(MAX_GRADE - grade);
+    let ease = (current.ease + 100 - miss * (80 + miss * 20)).max(MIN_EASE);
+    let repetitions = current.repetitions + 1;
+    let interval_days = match repetitions {
+        1 => 1,
+        2 => 6,
+        _ => {
+            let grown = (i64::from(current.interval_days) * i64::from(ease) + 500) / 1000;
+            i32::try_from(grown).unwrap_or(MAX_INTERVAL_DAYS)
+        }
+    };
+    Schedule {
+        ease,
+        interval_days: interval_days.clamp(1, MAX_INTERVAL_DAYS),
+        repetitions,
+    }
+}
+
+/// A fact worth a card, read from an entity field
+struct Fact {
+    entity_type: &'static str,
+    entity_id: String,
+    field: &'static str,
+    front: String,
+    back: String,
+}
+
+impl Fact {
+    /// None when the field is empty
+    fn new(
+        entity_type: &'static str,
+        entity_id: &str,
+        field: &'static str,
+        front: String,
+        text: Option<&str>,
+    ) -> Option<Self> {
+        let back = plain_text(text?).trim().to_string();
+        if back.is_empty() {
+            return None;
+        }
+        let back = match back.char_indices().nth(MAX_GENERATED_BACK) {
+            Some((end, _)) => format!("{}…", &back[..end]),
+            None => back,
+        };
+        Some(Self {
+            entity_type,
+            entity_id: entity_id.to_string(),
+            field,
+            front,
+            back,
+        })
+    }
+}
+
+/// The facts a campaign's cards are generated from: characters' secrets and
+/// motivations, what each location is like, and the secrets themselves
+async fn campaign_facts(db: &DatabaseConnection, campaign_id: &str) -> Result<Vec<Fact>, AppError> {
+    let mut facts = Vec::new();
+
+    let characters = Character::find()
+        .filter(characters::Column::CampaignId.eq(campaign_id))
+        .order_by_asc(characters::Column::Name)
+        .all(db)
+        .await?;
+    for c in &characters {
+        facts.extend(Fact::new(
+            "character",
+            &c.id,
+            "secrets",
+            format!("What is {} hiding?", c.name),
+            c.secrets.as_deref(),
+        ));
+        facts.extend(Fact::new(
+            "character",
+            &c.id,
+            "motivations",
+            format!("What drives {}?", c.name),
+            c.motivations.as_deref(),
+        ));
+    }
+
+    let locations = Location::find()
+        .filter(locations::Column::CampaignId.eq(campaign_id))
+        .order_by_asc(locations::Column::Name)
+        .all(db)
+        .await?;
+    for l in &locations {
+        facts.extend(Fact::new(
+            "location",
+            &l.id,
+            "description",
+            format!("What is {} like?", l.name),
+            l.description.as_deref(),
+        ));
+    }
+
+    let secrets = Secret::find()
+        .filter(secrets::Column::CampaignId.eq(campaign_id))
+        .order_by_asc(secrets::Column::Title)
+        .all(db)
+        .await?;
+    for s in &secrets {
+        facts.extend(Fact::new(
+            "secret",
+            &s.id,
+            "content",
+            format!("What is the truth of \"{}\"?", s.title),
+            Some(s.content.as_str()),
+        ));
+    }
+
+    Ok(facts)
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %input.campaign_id), err)]
+pub async fn create_recall_card_impl(
+    db: &DatabaseConnection,
+    input: CreateRecallCardInput,
+) -> Result<RecallCardResponse, AppError> {
+    input.validate()?;
+    match (&input.entity_type, &input.entity_id) {
+        (Some(entity_type), Some(_)) => {
+            if table_for(entity_type).is_none() {
+                return Err(AppError::Validation(format!(
+                    "entity_type: unknown entity type {}",
+                    entity_type
+                )));
+            }
+        }
+        (None, None) => {}
+        _ => {
+            return Err(AppError::Validation(
+                "entity_id: entity_type and entity_id must be given together".to_string(),
+            ))
+        }
+    }
+
+    let now = chrono::Utc::now();
+    let model = recall_cards::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(input.campaign_id),
+        entity_type: Set(input.entity_type),
+        entity_id: Set(input.entity_id),
+        source_field: Set(None),
+        front: Set(input.front),
+        back: Set(input.back),
+        ease: Set(DEFAULT_EASE),
+        interval_days: Set(0),
+        repetitions: Set(0),
+        due_at: Set(now),
+        last_reviewed_at: Set(None),
+        last_grade: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    let result = model.insert(db).await?;
+    Ok(result.into())
+}
+
+/// Every card in the campaign, soonest due first
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_recall_cards_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<RecallCardResponse>, AppError> {
+    let cards = RecallCard::find()
+        .filter(recall_cards::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(recall_cards::Column::DueAt)
+        .order_by_asc(recall_cards::Column::Front)
+        .all(db)
+        .await?;
+
+    Ok(cards.into_iter().map(|c| c.into()).collect())
+}
+
+/// Cards due for review now, most overdue first
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_due_recall_cards_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    limit: Option<u64>,
+) -> Result<Vec<RecallCardResponse>, AppError> {
+    let cards = RecallCard::find()
+        .filter(recall_cards::Column::CampaignId.eq(&campaign_id))
+        .filter(recall_cards::Column::DueAt.lte(chrono::Utc::now()))
+        .order_by_asc(recall_cards::Column::DueAt)
+        .order_by_asc(recall_cards::Column::Front)
+        .limit(limit)
+        .all(db)
+        .await?;
+
+    Ok(cards.into_iter().map(|c| c.into()).collect())
+}
+
+/// Grade a review from 0 (blank) to [`MAX_GRADE`] (perfect recall) and
+/// schedule the card's next one
+#[instrument(skip_all, fields(id = %id, grade), err)]
+pub async fn review_recall_card_impl(
+    db: &DatabaseConnection,
+    id: String,
+    grade: u8,
+) -> Result<RecallCardResponse, AppError> {
+    if grade > MAX_GRADE {
+        return Err(AppError::Validation(format!(
+            "grade: must be between 0 and {}",
+            MAX_GRADE
+        )));
+    }
+
+    let card = RecallCard::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Recall card {} not found", id)))?;
+
+    let next = next_schedule(
+        Schedule {
+            ease: card.ease,
+            interval_days: card.interval_days,
+            repetitions: card.repetitions,
+        },
+        grade,
+    );
+    let now = chrono::Utc::now();
+    let mut active: recall_cards::ActiveModel = card.into();
+    active.ease = Set(next.ease);
+    active.interval_days = Set(next.interval_days);
+    active.repetitions = Set(next.repetitions);
+    active.due_at = Set(now + chrono::Duration::days(i64::from(next.interval_days)));
+    active.last_reviewed_at = Set(Some(now));
+    active.last_grade = Set(Some(i32::from(grade)));
+    active.updated_at = Set(now);
+
+    let result = active.update(db).await?;
+    Ok(result.into())
+}
+
+/// Make a card for every fact in the campaign that doesn't have one yet.
+/// Running it again only picks up new facts and refreshes cards whose
+/// source text changed.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn generate_recall_cards_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<RecallGenerateReport, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let facts = campaign_facts(db, &campaign_id).await?;
+    let mut existing: HashMap<(String, String, String), recall_cards::Model> = RecallCard::find()
+        .filter(recall_cards::Column::CampaignId.eq(&campaign_id))
+        .filter(recall_cards::Column::SourceField.is_not_null())
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|c| {
+            let key = (
+                c.entity_type.clone()?,
+                c.entity_id.clone()?,
+                c.source_field.clone()?,
+            );
+            Some((key, c))
+        })
+        .collect();
+
+    let now = chrono::Utc::now();
+    let mut report = RecallGenerateReport::default();
+    let txn = db.begin().await?;
+    for fact in facts {
+        let key = (
+            fact.entity_type.to_string(),
+            fact.entity_id.clone(),
+            fact.field.to_string(),
+        );
+        match existing.remove(&key) {
+            Some(card) if card.front == fact.front && card.back == fact.back => {}
+            Some(card) => {
+                let mut active: recall_cards::ActiveModel = card.into();
+                active.front = Set(fact.front);
+                active.back = Set(fact.back);
+                active.updated_at = Set(now);
+                active.update(&txn).await?;
+                report.refreshed += 1;
+            }
+            None => {
+                recall_cards::ActiveModel {
+                    id: Set(uuid::Uuid::new_v4().to_string()),
+                    campaign_id: Set(campaign_id.clone()),
+                    entity_type: Set(Some(fact.entity_type.to_string())),
+                    entity_id: Set(Some(fact.entity_id)),
+                    source_field: Set(Some(fact.field.to_string())),
+                    front: Set(fact.front),
+                    back: Set(fact.back),
+                    ease: Set(DEFAULT_EASE),
+                    interval_days: Set(0),
+                    repetitions: Set(0),
+                    due_at: Set(now),
+                    last_reviewed_at: Set(None),
+                    last_grade: Set(None),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                    created_by: Set(None),
+                    modified_by: Set(None),
+                }
+                .insert(&txn)
+                .await?;
+                report.created += 1;
+            }
+        }
+    }
+    txn.commit().await?;
+
+    tracing::info!(
+        created = report.created,
+        refreshed = report.refreshed,
+        "Generated recall cards"
+    );
+    Ok(report)
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_recall_card_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = RecallCard::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_recall_card(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    front: String,
+    back: String,
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+) -> Result<RecallCardResponse, AppError> {
+    let input = CreateRecallCardInput {
+        campaign_id,
+        front,
+        back,
+        entity_type,
+        entity_id,
+    };
+    create_recall_card_impl(&state.db, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_recall_cards(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<RecallCardResponse>, AppError> {
+    list_recall_cards_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_due_recall_cards(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    limit: Option<u64>,
+) -> Result<Vec<RecallCardResponse>, AppError> {
+    list_due_recall_cards_impl(&state.db, campaign_id, limit).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn review_recall_card(
+    state: State<'_, AppState>,
+    id: String,
+    grade: u8,
+) -> Result<RecallCardResponse, AppError> {
+    review_recall_card_impl(&state.db, id, grade).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn generate_recall_cards(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<RecallGenerateReport, AppError> {
+    generate_recall_cards_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_recall_card(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    delete_recall_card_impl(&state.db, id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fresh() -> Schedule {
+        Schedule {
+            ease: DEFAULT_EASE,
+            interval_days: 0,
+            repetitions: 0,
+        }
+    }
+
+    #[test]
+    fn test_next_schedule_follows_sm2() {
+        let first = next_schedule(fresh(), 4);
+        assert_eq!(first.interval_days, 1);
+        assert_eq!(first.ease, DEFAULT_EASE);
+
+        let second = next_schedule(first, 5);
+        assert_eq!(second.interval_days, 6);
+        assert_eq!(second.ease, 2600);
+
+        // 6 days at ease 2.46
+        let third = next_schedule(second, 3);
+        assert_eq!(third.ease, 2460);
+        assert_eq!(third.interval_days, 15);
+        assert_eq!(third.repetitions, 3);
+
+        let forgot = next_schedule(third, 1);
+        assert_eq!(
+            forgot,
+            Schedule {
+                ease: 2460,
+                interval_days: 1,
+                repetitions: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_next_schedule_bounds() {
+        let mut hard = fresh();
+        for _ in 0..20 {
+            hard = next_schedule(hard, 3);
+        }
+        assert_eq!(hard.ease, MIN_EASE);
+
+        let mut easy = fresh();
+        for _ in 0..40 {
+            easy = next_schedule(easy, 5);
+        }
+        assert_eq!(easy.interval_days, MAX_INTERVAL_DAYS);
+    }
+}
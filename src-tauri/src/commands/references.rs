@@ -0,0 +1,260 @@
+//! Relationships, tags, secrets, attachments, recall cards, progress clocks
+//! and annotations point at entities by (type, id), with no foreign key to
+//! remove them when the entity goes. Delete commands clear them in the same
+//! transaction as the delete, and `repair_orphaned_references` cleans up any
+//! left behind earlier.
+
+use crate::commands::registry::table_for;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::annotations::{self, Entity as Annotation};
+use ::entity::attachments::{self, Entity as Attachment};
+use ::entity::entity_tags::{self, Entity as EntityTag};
+use ::entity::progress_clocks::{self, Entity as ProgressClock};
+use ::entity::recall_cards::{self, Entity as RecallCard};
+use ::entity::relationships::{self, Entity as Relationship};
+use ::entity::secrets::{self, Entity as Secret};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Entity types that polymorphic references can point at
+pub const REFERENCEABLE_TYPES: &[&str] = &[
+    "character",
+    "location",
+    "organization",
+    "quest",
+    "hero",
+    "item",
+    "player",
+    "session",
+    "timeline_event",
+    "secret",
+    "handout",
+];
+
+/// References to entities that no longer exist
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OrphanedReferences {
+    pub relationships: u64,
+    pub entity_tags: u64,
+    /// Secrets whose related entity is gone; the secret itself is kept
+    pub secrets: u64,
+    pub attachments: u64,
+    pub recall_cards: u64,
+    pub annotations: u64,
+    /// Clocks whose faction or quest is gone; the clock itself is kept
+    pub progress_clocks: u64,
+    /// Whether the references were removed or only counted
+    pub repaired: bool,
+}
+
+/// Remove the relationships, tags, attachments, recall cards and annotations
+/// of an entity about to be deleted, and unlink secrets and clocks about it.
+/// Run inside the delete's transaction.
+pub(crate) async fn remove_references<C: ConnectionTrait>(
+    conn: &C,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<(), AppError> {
+    Relationship::delete_many()
+        .filter(
+            Condition::any()
+                .add(
+                    relationships::Column::SourceType
+                        .eq(entity_type)
+                        .and(relationships::Column::SourceId.eq(entity_id)),
+                )
+                .add(
+                    relationships::Column::TargetType
+                        .eq(entity_type)
+                        .and(relationships::Column::TargetId.eq(entity_id)),
+                ),
+        )
+        .exec(conn)
+        .await?;
+
+    EntityTag::delete_many()
+        .filter(entity_tags::Column::EntityType.eq(entity_type))
+        .filter(entity_tags::Column::EntityId.eq(entity_id))
+        .exec(conn)
+        .await?;
+
+    Attachment::delete_many()
+        .filter(attachments::Column::EntityType.eq(entity_type))
+        .filter(attachments::Column::EntityId.eq(entity_id))
+        .exec(conn)
+        .await?;
+
+    RecallCard::delete_many()
+        .filter(recall_cards::Column::EntityType.eq(entity_type))
+        .filter(recall_cards::Column::EntityId.eq(entity_id))
+        .exec(conn)
+        .await?;
+
+    Annotation::delete_many()
+        .filter(annotations::Column::EntityType.eq(entity_type))
+        .filter(annotations::Column::EntityId.eq(entity_id))
+        .exec(conn)
+        .await?;
+
+    Secret::update_many()
+        .col_expr(
+            secrets::Column::RelatedEntityType,
+            Expr::value(None::<String>),
+        )
+        .col_expr(
+            secrets::Column::RelatedEntityId,
+            Expr::value(None::<String>),
+        )
+        .col_expr(secrets::Column::UpdatedAt, Expr::value(chrono::Utc::now()))
+        .filter(secrets::Column::RelatedEntityType.eq(entity_type))
+        .filter(secrets::Column::RelatedEntityId.eq(entity_id))
+        .exec(conn)
+        .await?;
+
+    ProgressClock::update_many()
+        .col_expr(
+            progress_clocks::Column::EntityType,
+            Expr::value(None::<String>),
+        )
+        .col_expr(
+            progress_clocks::Column::EntityId,
+            Expr::value(None::<String>),
+        )
+        .col_expr(
+            progress_clocks::Column::UpdatedAt,
+            Expr::value(chrono::Utc::now()),
+        )
+        .filter(progress_clocks::Column::EntityType.eq(entity_type))
+        .filter(progress_clocks::Column::EntityId.eq(entity_id))
+        .exec(conn)
+        .await?;
+
+    Ok(())
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Find references to deleted entities and, unless `dry_run`, remove them
+#[instrument(skip_all, fields(dry_run), err)]
+pub async fn repair_orphaned_references_impl(
+    db: &DatabaseConnection,
+    dry_run: bool,
+) -> Result<OrphanedReferences, AppError> {
+    let backend = db.get_database_backend();
+    let txn = db.begin().await?;
+    let now = chrono::Utc::now();
+    let mut report = OrphanedReferences {
+        repaired: !dry_run,
+        ..Default::default()
+    };
+
+    for entity_type in REFERENCEABLE_TYPES {
+        let table = table_for(entity_type)
+            .expect("referenceable types are registered")
+            .table;
+
+        // (table, type column, id column) of each kind of reference
+        let references = [
+            ("relationships", "source_type", "source_id"),
+            ("relationships", "target_type", "target_id"),
+            ("entity_tags", "entity_type", "entity_id"),
+            ("attachments", "entity_type", "entity_id"),
+            ("recall_cards", "entity_type", "entity_id"),
+            ("annotations", "entity_type", "entity_id"),
+            ("secrets", "related_entity_type", "related_entity_id"),
+            ("progress_clocks", "entity_type", "entity_id"),
+        ];
+        for (reference_table, type_column, id_column) in references {
+            let orphaned = format!(
+                "{reference_table} WHERE {type_column} = $1 \
+                 AND {id_column} NOT IN (SELECT id FROM {table})"
+            );
+            let count: i64 = txn
+                .query_one(Statement::from_sql_and_values(
+                    backend,
+                    format!("SELECT COUNT(*) AS count FROM {orphaned}"),
+                    [(*entity_type).into()],
+                ))
+                .await?
+                .ok_or_else(|| AppError::Internal("COUNT returned no row".to_string()))?
+                .try_get("", "count")?;
+            if count == 0 {
+                continue;
+            }
+
+            let slot = match reference_table {
+                "relationships" => &mut report.relationships,
+                "entity_tags" => &mut report.entity_tags,
+                "attachments" => &mut report.attachments,
+                "recall_cards" => &mut report.recall_cards,
+                "annotations" => &mut report.annotations,
+                "progress_clocks" => &mut report.progress_clocks,
+                _ => &mut report.secrets,
+            };
+            *slot += count as u64;
+
+            if dry_run {
+                continue;
+            }
+            if matches!(reference_table, "secrets" | "progress_clocks") {
+                txn.execute(Statement::from_sql_and_values(
+                    backend,
+                    format!(
+                        "UPDATE {reference_table} SET {type_column} = NULL, {id_column} = NULL, \
+                         updated_at = $2 WHERE {type_column} = $1 \
+                         AND {id_column} NOT IN (SELECT id FROM {table})"
+                    ),
+                    [(*entity_type).into(), now.into()],
+                ))
+                .await?;
+            } else {
+                txn.execute(Statement::from_sql_and_values(
+                    backend,
+                    format!("DELETE FROM {orphaned}"),
+                    [(*entity_type).into()],
+                ))
+                .await?;
+            }
+        }
+    }
+
+    txn.commit().await?;
+
+    if report.relationships
+        + report.entity_tags
+        + report.secrets
+        + report.attachments
+        + report.recall_cards
+        + report.annotations
+        + report.progress_clocks
+        > 0
+    {
+        tracing::info!(
+            relationships = report.relationships,
+            entity_tags = report.entity_tags,
+            secrets = report.secrets,
+            attachments = report.attachments,
+            recall_cards = report.recall_cards,
+            annotations = report.annotations,
+            progress_clocks = report.progress_clocks,
+            dry_run,
+            "Found orphaned references"
+        );
+    }
+
+    Ok(report)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn repair_orphaned_references(
+    state: State<'_, AppState>,
+    dry_run: Option<bool>,
+) -> Result<OrphanedReferences, AppError> {
+    repair_orphaned_references_impl(&state.db, dry_run.unwrap_or(false)).await
+}
@@ -0,0 +1,336 @@
+//! Table metadata for the campaign-scoped entity types, used by commands that
+//! operate across every table (sync, maintenance, exports).
+
+/// Describes how an entity type maps onto its database table
+#[derive(Debug, Clone, Copy)]
+pub struct EntityTable {
+    /// Entity type string used by search, tags and relationships
+    pub entity_type: &'static str,
+    /// Table name in the database
+    pub table: &'static str,
+    /// Column holding the display name, if the table has one
+    pub name_column: Option<&'static str>,
+    /// Free-text columns, which may hold rich text JSON and mentions
+    pub text_columns: &'static [&'static str],
+    /// Whether the table tracks `updated_at` (tags only have `created_at`)
+    pub has_updated_at: bool,
+    /// Whether rows are mirrored into the `search_index` FTS table by triggers
+    pub searchable: bool,
+}
+
+impl EntityTable {
+    /// Column to use as the modification watermark
+    pub fn modified_column(&self) -> &'static str {
+        if self.has_updated_at {
+            "updated_at"
+        } else {
+            "created_at"
+        }
+    }
+}
+
+pub const ENTITY_TABLES: &[EntityTable] = &[
+    EntityTable {
+        entity_type: "character",
+        table: "characters",
+        name_column: Some("name"),
+        text_columns: &[
+            "description",
+            "personality",
+            "motivations",
+            "secrets",
+            "voice_notes",
+        ],
+        has_updated_at: true,
+        searchable: true,
+    },
+    EntityTable {
+        entity_type: "location",
+        table: "locations",
+        name_column: Some("name"),
+        text_columns: &["description", "gm_notes"],
+        has_updated_at: true,
+        searchable: true,
+    },
+    EntityTable {
+        entity_type: "organization",
+        table: "organizations",
+        name_column: Some("name"),
+        text_columns: &["description", "goals", "resources", "reputation", "secrets"],
+        has_updated_at: true,
+        searchable: true,
+    },
+    EntityTable {
+        entity_type: "quest",
+        table: "quests",
+        name_column: Some("name"),
+        text_columns: &[
+            "description",
+            "hook",
+            "objectives",
+            "complications",
+            "resolution",
+            "reward",
+        ],
+        has_updated_at: true,
+        searchable: true,
+    },
+    EntityTable {
+        entity_type: "hero",
+        table: "heroes",
+        name_column: Some("name"),
+        text_columns: &["description", "backstory", "goals", "bonds"],
+        has_updated_at: true,
+        searchable: true,
+    },
+    EntityTable {
+        entity_type: "item",
+        table: "items",
+        name_column: Some("name"),
+        text_columns: &["description"],
+        has_updated_at: true,
+        searchable: true,
+    },
+    EntityTable {
+        entity_type: "hero_item",
+        table: "hero_items",
+        name_column: None,
+        text_columns: &[],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "organization_holding",
+        table: "organization_holdings",
+        name_column: None,
+        text_columns: &["notes"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "whereabouts",
+        table: "whereabouts",
+        name_column: None,
+        text_columns: &["notes"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "player",
+        table: "players",
+        name_column: Some("name"),
+        text_columns: &["preferences", "boundaries", "notes"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "session",
+        table: "sessions",
+        name_column: Some("title"),
+        text_columns: &["planned_content", "notes", "summary", "highlights"],
+        has_updated_at: true,
+        searchable: true,
+    },
+    EntityTable {
+        entity_type: "session_absence",
+        table: "session_absences",
+        name_column: None,
+        text_columns: &[],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "session_transcript",
+        table: "session_transcripts",
+        name_column: Some("file_name"),
+        text_columns: &[],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "disposition",
+        table: "dispositions",
+        name_column: None,
+        text_columns: &["reason"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "timeline_branch",
+        table: "timeline_branches",
+        name_column: Some("name"),
+        text_columns: &["description"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "timeline_event",
+        table: "timeline_events",
+        name_column: Some("title"),
+        text_columns: &["description"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "secret",
+        table: "secrets",
+        name_column: Some("title"),
+        text_columns: &["content"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "prompt_template",
+        table: "prompt_templates",
+        name_column: Some("name"),
+        text_columns: &[],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "handout",
+        table: "handouts",
+        name_column: Some("title"),
+        text_columns: &["content"],
+        has_updated_at: true,
+        searchable: true,
+    },
+    EntityTable {
+        entity_type: "handout_delivery",
+        table: "handout_deliveries",
+        name_column: None,
+        text_columns: &[],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "snippet",
+        table: "snippets",
+        name_column: Some("name"),
+        text_columns: &["content"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "relationship",
+        table: "relationships",
+        name_column: Some("relationship_type"),
+        text_columns: &["description"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "strength_change",
+        table: "strength_changes",
+        name_column: None,
+        text_columns: &["reason"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "recall_card",
+        table: "recall_cards",
+        name_column: Some("front"),
+        text_columns: &["front", "back"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "progress_clock",
+        table: "progress_clocks",
+        name_column: Some("name"),
+        text_columns: &["description"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "encounter_table",
+        table: "encounter_tables",
+        name_column: Some("name"),
+        text_columns: &["description"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "encounter_entry",
+        table: "encounter_entries",
+        name_column: Some("name"),
+        text_columns: &["notes"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "encounter_roll",
+        table: "encounter_rolls",
+        name_column: None,
+        text_columns: &["result"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "price",
+        table: "price_list",
+        name_column: Some("name"),
+        text_columns: &["notes"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "price_modifier",
+        table: "price_modifiers",
+        name_column: None,
+        text_columns: &["reason"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "organization_goal",
+        table: "organization_goals",
+        name_column: Some("title"),
+        text_columns: &["description"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "goal_advance",
+        table: "goal_advances",
+        name_column: None,
+        text_columns: &["note"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "content_warning",
+        table: "content_warnings",
+        name_column: Some("topic"),
+        text_columns: &["notes"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "annotation",
+        table: "annotations",
+        name_column: None,
+        text_columns: &["body"],
+        has_updated_at: true,
+        searchable: false,
+    },
+    EntityTable {
+        entity_type: "tag",
+        table: "tags",
+        name_column: Some("name"),
+        text_columns: &[],
+        has_updated_at: false,
+        searchable: false,
+    },
+];
+
+/// Entity types kept in the full-text search index
+pub fn searchable_tables() -> impl Iterator<Item = &'static EntityTable> {
+    ENTITY_TABLES.iter().filter(|t| t.searchable)
+}
+
+/// Look up the table metadata for an entity type string
+pub fn table_for(entity_type: &str) -> Option<&'static EntityTable> {
+    ENTITY_TABLES.iter().find(|t| t.entity_type == entity_type)
+}
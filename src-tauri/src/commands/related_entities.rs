@@ -0,0 +1,367 @@
+//! "Related content" for an entity's sidebar: other entities ranked by how
+//! close their text sits in embedding space, how many tags they share and
+//! how close they sit in the relationship graph.
+//!
+//! Embeddings come from the provider the frontend passes in and are cached
+//! in `entity_embeddings` by content hash, so only new or edited entities go
+//! back to it. Without a provider the text signal is left out and entities
+//! are ranked by tags and relationships alone.
+
+use crate::commands::registry::table_for;
+use crate::db::AppState;
+use crate::embeddings::{
+    cosine, vector_from_bytes, vector_to_bytes, ConfiguredEmbeddings, EmbeddingProvider,
+    EmbeddingProviderConfig,
+};
+use crate::error::AppError;
+use crate::export::attachments::content_hash;
+use crate::text::plain_text;
+use ::entity::entity_embeddings::{self, Entity as EntityEmbedding};
+use ::entity::entity_tags::{self, Entity as EntityTag};
+use ::entity::relationships::{self, Entity as Relationship};
+use ::entity::tags::{self, Entity as Tag};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use tauri::State;
+use tracing::instrument;
+
+/// Entity types that can appear in the panel
+const RELATED_TYPES: &[&str] = &[
+    "character",
+    "location",
+    "organization",
+    "quest",
+    "hero",
+    "item",
+];
+
+/// Weight of each signal in the combined score
+const TEXT_WEIGHT: f64 = 0.5;
+const TAG_WEIGHT: f64 = 0.3;
+const GRAPH_WEIGHT: f64 = 0.2;
+
+/// Relationship hops searched; further entities get no graph score
+const MAX_HOPS: u32 = 2;
+
+const DEFAULT_K: u32 = 10;
+const MAX_K: u32 = 50;
+
+/// Texts sent to the provider per request
+const EMBED_BATCH: usize = 64;
+
+/// Text embedded per entity, comfortably inside common models' input limits
+const MAX_EMBED_CHARS: usize = 8000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedEntity {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: String,
+    /// Combined score, 0 to 1
+    pub score: f64,
+    /// Cosine similarity of the two entities' embeddings, 0 to 1; None when
+    /// no embedding provider was given
+    pub text_similarity: Option<f64>,
+    /// Names of tags both entities carry
+    pub shared_tags: Vec<String>,
+    /// Relationship hops between the two, if within `MAX_HOPS`
+    pub relationship_distance: Option<u32>,
+}
+
+type EntityKey = (String, String);
+
+/// Embedding of each document: from the cache when its text is unchanged,
+/// from `provider` otherwise. Cached vectors of entities no longer in the
+/// campaign are dropped along the way.
+async fn text_vectors<P: EmbeddingProvider>(
+    db: &DatabaseConnection,
+    provider: &P,
+    campaign_id: &str,
+    documents: &HashMap<EntityKey, String>,
+) -> Result<HashMap<EntityKey, Vec<f32>>, AppError> {
+    let model = format!("{}/{}", provider.name(), provider.model());
+    let cache_id =
+        |(entity_type, entity_id): &EntityKey| format!("{model}:{entity_type}:{entity_id}");
+
+    let mut cached: HashMap<String, entity_embeddings::Model> = EntityEmbedding::find()
+        .filter(entity_embeddings::Column::CampaignId.eq(campaign_id))
+        .filter(entity_embeddings::Column::Model.eq(&model))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|row| (row.id.clone(), row))
+        .collect();
+
+    let mut vectors = HashMap::new();
+    let mut missing = Vec::new();
+    for (key, text) in documents {
+        let hash = content_hash(text.as_bytes());
+        match cached.remove(&cache_id(key)) {
+            Some(row) if row.content_hash == hash => {
+                vectors.insert(key.clone(), vector_from_bytes(&row.vector));
+            }
+            _ => missing.push((key, text.clone(), hash)),
+        }
+    }
+
+    if !cached.is_empty() {
+        EntityEmbedding::delete_many()
+            .filter(entity_embeddings::Column::Id.is_in(cached.into_keys()))
+            .exec(db)
+            .await?;
+    }
+
+    let now = chrono::Utc::now();
+    for batch in missing.chunks(EMBED_BATCH) {
+        let texts: Vec<String> = batch.iter().map(|(_, text, _)| text.clone()).collect();
+        let embedded = provider.embed(&texts).await?;
+        for ((key, _, hash), vector) in batch.iter().zip(embedded) {
+            let row = entity_embeddings::ActiveModel {
+                id: Set(cache_id(*key)),
+                campaign_id: Set(campaign_id.to_string()),
+                model: Set(model.clone()),
+                content_hash: Set(hash.clone()),
+                vector: Set(vector_to_bytes(&vector)),
+                created_at: Set(now),
+            };
+            EntityEmbedding::insert(row)
+                .on_conflict(
+                    OnConflict::column(entity_embeddings::Column::Id)
+                        .update_columns([
+                            entity_embeddings::Column::ContentHash,
+                            entity_embeddings::Column::Vector,
+                            entity_embeddings::Column::CreatedAt,
+                        ])
+                        .to_owned(),
+                )
+                .exec(db)
+                .await?;
+            vectors.insert((*key).clone(), vector);
+        }
+    }
+
+    tracing::debug!(
+        model = %model,
+        embedded = missing.len(),
+        cached = documents.len() - missing.len(),
+        "Entity embeddings ready"
+    );
+    Ok(vectors)
+}
+
+/// Hops from `start` to every entity within `MAX_HOPS`
+fn graph_distances(
+    edges: &HashMap<EntityKey, Vec<EntityKey>>,
+    start: &EntityKey,
+) -> HashMap<EntityKey, u32> {
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start.clone()]);
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        if distance == MAX_HOPS {
+            continue;
+        }
+        for next in edges.get(&node).into_iter().flatten() {
+            if !distances.contains_key(next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next.clone());
+            }
+        }
+    }
+    distances
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// The `k` entities most related to one entity, best first. `embeddings`
+/// supplies the text signal; without it only tags and relationships count.
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn get_related_entities_impl<P: EmbeddingProvider>(
+    db: &DatabaseConnection,
+    embeddings: Option<&P>,
+    entity_type: String,
+    entity_id: String,
+    k: Option<u32>,
+) -> Result<Vec<RelatedEntity>, AppError> {
+    if !RELATED_TYPES.contains(&entity_type.as_str()) {
+        return Err(AppError::Validation(format!(
+            "entity_type: must be one of: {}",
+            RELATED_TYPES.join(", ")
+        )));
+    }
+    let k = k.unwrap_or(DEFAULT_K).clamp(1, MAX_K) as usize;
+
+    let table = table_for(&entity_type).expect("related types are registered");
+    let campaign_id: String = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!("SELECT campaign_id FROM {} WHERE id = $1", table.table),
+            [entity_id.clone().into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} {} not found", entity_type, entity_id)))?
+        .try_get("", "campaign_id")?;
+
+    // Name and free text of every candidate in the campaign
+    let mut names: HashMap<EntityKey, String> = HashMap::new();
+    let mut documents: HashMap<EntityKey, String> = HashMap::new();
+    for table in RELATED_TYPES.iter().filter_map(|t| table_for(t)) {
+        let Some(name_column) = table.name_column else {
+            continue;
+        };
+        let mut columns = vec![format!("{} AS display_name", name_column)];
+        columns.extend(table.text_columns.iter().map(|&c| c.to_string()));
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!(
+                    "SELECT id, {} FROM {} WHERE campaign_id = $1",
+                    columns.join(", "),
+                    table.table
+                ),
+                [campaign_id.clone().into()],
+            ))
+            .await?;
+
+        for row in rows {
+            let key: EntityKey = (table.entity_type.to_string(), row.try_get("", "id")?);
+            let name: String = row
+                .try_get::<Option<String>>("", "display_name")?
+                .unwrap_or_default();
+            let mut text = name.clone();
+            for column in table.text_columns {
+                if let Some(value) = row.try_get::<Option<String>>("", column)? {
+                    text.push('\n');
+                    text.push_str(&plain_text(&value));
+                }
+            }
+            if text.chars().count() > MAX_EMBED_CHARS {
+                text = text.chars().take(MAX_EMBED_CHARS).collect();
+            }
+            names.insert(key.clone(), name);
+            documents.insert(key, text);
+        }
+    }
+
+    let target: EntityKey = (entity_type, entity_id);
+    let vectors = match embeddings {
+        Some(provider) => Some(text_vectors(db, provider, &campaign_id, &documents).await?),
+        None => None,
+    };
+
+    // Tags on each entity, by tag id
+    let tag_names: HashMap<String, String> = Tag::find()
+        .filter(tags::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| (t.id, t.name))
+        .collect();
+    let mut entity_tags: HashMap<EntityKey, HashSet<String>> = HashMap::new();
+    for et in EntityTag::find()
+        .filter(entity_tags::Column::TagId.is_in(tag_names.keys().cloned()))
+        .all(db)
+        .await?
+    {
+        entity_tags
+            .entry((et.entity_type, et.entity_id))
+            .or_default()
+            .insert(et.tag_id);
+    }
+    let no_tags = HashSet::new();
+    let target_tags = entity_tags.get(&target).unwrap_or(&no_tags);
+
+    let mut edges: HashMap<EntityKey, Vec<EntityKey>> = HashMap::new();
+    for r in Relationship::find()
+        .filter(relationships::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+    {
+        let source = (r.source_type, r.source_id);
+        let target = (r.target_type, r.target_id);
+        edges
+            .entry(source.clone())
+            .or_default()
+            .push(target.clone());
+        edges.entry(target).or_default().push(source);
+    }
+    let distances = graph_distances(&edges, &target);
+
+    let mut related = Vec::new();
+    for (key, name) in &names {
+        if *key == target {
+            continue;
+        }
+        let text_similarity =
+            vectors
+                .as_ref()
+                .map(|vectors| match (vectors.get(&target), vectors.get(key)) {
+                    (Some(a), Some(b)) => cosine(a, b).max(0.0),
+                    _ => 0.0,
+                });
+
+        let tags = entity_tags.get(key).unwrap_or(&no_tags);
+        let shared: Vec<&String> = target_tags.intersection(tags).collect();
+        let union = target_tags.union(tags).count();
+        let tag_score = if union == 0 {
+            0.0
+        } else {
+            shared.len() as f64 / union as f64
+        };
+
+        let distance = distances.get(key).copied();
+        let graph_score = distance.map(|d| 1.0 / d as f64).unwrap_or(0.0);
+
+        let score = match text_similarity {
+            Some(similarity) => {
+                TEXT_WEIGHT * similarity + TAG_WEIGHT * tag_score + GRAPH_WEIGHT * graph_score
+            }
+            // Rescaled so scores still run from 0 to 1
+            None => {
+                (TAG_WEIGHT * tag_score + GRAPH_WEIGHT * graph_score) / (TAG_WEIGHT + GRAPH_WEIGHT)
+            }
+        };
+        if score <= 0.0 {
+            continue;
+        }
+
+        let mut shared_tags: Vec<String> = shared.iter().map(|id| tag_names[*id].clone()).collect();
+        shared_tags.sort();
+        related.push(RelatedEntity {
+            entity_type: key.0.clone(),
+            entity_id: key.1.clone(),
+            name: name.clone(),
+            score,
+            text_similarity,
+            shared_tags,
+            relationship_distance: distance,
+        });
+    }
+
+    related.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    related.truncate(k);
+    Ok(related)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_related_entities(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+    k: Option<u32>,
+    provider: Option<EmbeddingProviderConfig>,
+) -> Result<Vec<RelatedEntity>, AppError> {
+    let provider = provider
+        .as_ref()
+        .map(ConfiguredEmbeddings::from_config)
+        .transpose()?;
+    get_related_entities_impl(&state.db, provider.as_ref(), entity_type, entity_id, k).await
+}
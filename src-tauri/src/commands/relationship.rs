@@ -1,9 +1,32 @@
+use crate::commands::kinship::{
+    add_kinship_inverse, is_kinship, is_symmetric_kinship, remove_kinship_inverse,
+};
+use crate::commands::registry::table_for;
+use crate::commands::relationship_strength::record_strength_change;
 use crate::db::AppState;
 use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
 use ::entity::relationships::{self, Entity as Relationship};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use tauri::State;
+use tracing::instrument;
+
+/// Columns of the relationship CSV export
+const RELATIONSHIP_CSV_HEADER: &[&str] = &[
+    "source_type",
+    "source_id",
+    "source_name",
+    "relationship_type",
+    "target_type",
+    "target_id",
+    "target_name",
+    "is_bidirectional",
+    "strength",
+    "is_public",
+    "description",
+];
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RelationshipResponse {
@@ -42,9 +65,71 @@ impl From<relationships::Model> for RelationshipResponse {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CsvExportSummary {
+    pub path: String,
+    /// Data rows written, excluding the header
+    pub rows: usize,
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut row = fields
+        .iter()
+        .map(|f| csv_field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+/// Display names for every entity of the given types in a campaign
+pub(crate) async fn entity_names(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    entity_types: &BTreeSet<&str>,
+) -> Result<HashMap<(String, String), String>, AppError> {
+    let mut names = HashMap::new();
+    for &entity_type in entity_types {
+        let Some(table) = table_for(entity_type) else {
+            continue;
+        };
+        let Some(name_column) = table.name_column else {
+            continue;
+        };
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!(
+                    "SELECT id, {} AS name FROM {} WHERE campaign_id = $1",
+                    name_column, table.table
+                ),
+                [campaign_id.into()],
+            ))
+            .await?;
+        for row in rows {
+            let id: String = row.try_get("", "id")?;
+            let name: Option<String> = row.try_get("", "name")?;
+            if let Some(name) = name {
+                names.insert((entity_type.to_string(), id), name);
+            }
+        }
+    }
+    Ok(names)
+}
+
 // ============ Core implementation functions (testable) ============
 
 #[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn create_relationship_impl(
     db: &DatabaseConnection,
     campaign_id: String,
@@ -59,7 +144,12 @@ pub async fn create_relationship_impl(
 ) -> Result<RelationshipResponse, AppError> {
     let id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now();
+    // Siblings and spouses read the same from both ends
+    let is_bidirectional = is_bidirectional.unwrap_or(false)
+        || (is_kinship(&source_type, &target_type, &relationship_type)
+            && is_symmetric_kinship(&relationship_type));
 
+    let txn = db.begin().await?;
     let model = relationships::ActiveModel {
         id: Set(id),
         campaign_id: Set(campaign_id),
@@ -69,17 +159,22 @@ pub async fn create_relationship_impl(
         target_id: Set(target_id),
         relationship_type: Set(relationship_type),
         description: Set(description),
-        is_bidirectional: Set(is_bidirectional.unwrap_or(false)),
+        is_bidirectional: Set(is_bidirectional),
         strength: Set(strength),
         is_public: Set(true),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
-    let result = model.insert(db).await?;
+    let result = model.insert(&txn).await?;
+    add_kinship_inverse(&txn, &result).await?;
+    txn.commit().await?;
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_relationship_impl(
     db: &DatabaseConnection,
     id: String,
@@ -92,6 +187,7 @@ pub async fn get_relationship_impl(
     Ok(rel.into())
 }
 
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn list_relationships_impl(
     db: &DatabaseConnection,
     campaign_id: String,
@@ -105,6 +201,7 @@ pub async fn list_relationships_impl(
     Ok(rels.into_iter().map(|r| r.into()).collect())
 }
 
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
 pub async fn get_entity_relationships_impl(
     db: &DatabaseConnection,
     entity_type: String,
@@ -131,6 +228,7 @@ pub async fn get_entity_relationships_impl(
 }
 
 #[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn update_relationship_impl(
     db: &DatabaseConnection,
     id: String,
@@ -145,6 +243,9 @@ pub async fn update_relationship_impl(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Relationship {} not found", id)))?;
 
+    let previous_strength = rel.strength;
+    let txn = db.begin().await?;
+    remove_kinship_inverse(&txn, &rel).await?;
     let mut active: relationships::ActiveModel = rel.into();
 
     if let Some(rt) = relationship_type {
@@ -164,18 +265,117 @@ pub async fn update_relationship_impl(
     }
     active.updated_at = Set(chrono::Utc::now());
 
-    let result = active.update(db).await?;
+    let mut result = active.update(&txn).await?;
+    // Edits to strength show up in its history like adjustments do
+    if result.strength != previous_strength {
+        record_strength_change(
+            &txn,
+            &result,
+            previous_strength.unwrap_or(0),
+            None,
+            None,
+            false,
+        )
+        .await?;
+    }
+    if is_kinship(
+        &result.source_type,
+        &result.target_type,
+        &result.relationship_type,
+    ) && is_symmetric_kinship(&result.relationship_type)
+        && !result.is_bidirectional
+    {
+        let mut active: relationships::ActiveModel = result.into();
+        active.is_bidirectional = Set(true);
+        result = active.update(&txn).await?;
+    }
+    // Re-pair from the updated row so the inverse carries the same details
+    add_kinship_inverse(&txn, &result).await?;
+    txn.commit().await?;
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_relationship_impl(
     db: &DatabaseConnection,
     id: String,
 ) -> Result<bool, AppError> {
-    let result = Relationship::delete_by_id(&id).exec(db).await?;
+    let Some(rel) = Relationship::find_by_id(&id).one(db).await? else {
+        return Ok(false);
+    };
+
+    let txn = db.begin().await?;
+    let result = Relationship::delete_by_id(&id).exec(&txn).await?;
+    remove_kinship_inverse(&txn, &rel).await?;
+    txn.commit().await?;
     Ok(result.rows_affected > 0)
 }
 
+/// Write the campaign's relationships to `path` as a CSV adjacency list,
+/// one row per relationship with both ends' names resolved
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn export_relationships_csv_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    path: String,
+) -> Result<CsvExportSummary, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let rels = Relationship::find()
+        .filter(relationships::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?;
+
+    let entity_types: BTreeSet<&str> = rels
+        .iter()
+        .flat_map(|r| [r.source_type.as_str(), r.target_type.as_str()])
+        .collect();
+    let names = entity_names(db, &campaign_id, &entity_types).await?;
+    let name_of = |entity_type: &str, id: &str| {
+        names
+            .get(&(entity_type.to_string(), id.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    // Same order as RELATIONSHIP_CSV_HEADER
+    let mut rows: Vec<[String; 11]> = rels
+        .iter()
+        .map(|r| {
+            [
+                r.source_type.clone(),
+                r.source_id.clone(),
+                name_of(&r.source_type, &r.source_id),
+                r.relationship_type.clone(),
+                r.target_type.clone(),
+                r.target_id.clone(),
+                name_of(&r.target_type, &r.target_id),
+                r.is_bidirectional.to_string(),
+                r.strength.map(|s| s.to_string()).unwrap_or_default(),
+                r.is_public.to_string(),
+                r.description.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    // Group by source name, then relationship type and target name
+    rows.sort_by(|a, b| (&a[2], &a[3], &a[6]).cmp(&(&b[2], &b[3], &b[6])));
+
+    let mut csv = csv_row(RELATIONSHIP_CSV_HEADER);
+    for row in &rows {
+        csv.push_str(&csv_row(row));
+    }
+    std::fs::write(&path, csv)
+        .map_err(|e| AppError::Internal(format!("Failed to write {}: {}", path, e)))?;
+
+    Ok(CsvExportSummary {
+        path,
+        rows: rows.len(),
+    })
+}
+
 // ============ Tauri command wrappers ============
 
 #[tauri::command(rename_all = "snake_case")]
@@ -257,3 +457,12 @@ pub async fn update_relationship(
 pub async fn delete_relationship(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
     delete_relationship_impl(&state.db, id).await
 }
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_relationships_csv(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    path: String,
+) -> Result<CsvExportSummary, AppError> {
+    export_relationships_csv_impl(&state.db, campaign_id, path).await
+}
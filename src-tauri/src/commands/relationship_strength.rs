@@ -0,0 +1,282 @@
+//! How strong a relationship is, and how that changed over time. The current
+//! value lives on the relationship itself; every change to it is also kept
+//! as a row here with the reason and session, so the history can be shown.
+//! Relationships nobody reinforces can be made to drift back toward 0 by a
+//! decay rule, applied whenever the GM asks rather than on a timer.
+
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::relationships::{self, Entity as Relationship};
+use ::entity::sessions::Entity as Session;
+use ::entity::strength_changes::{self, Entity as StrengthChange};
+use sea_orm::prelude::DateTimeUtc;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+use tracing::instrument;
+
+/// Weakest a relationship can be adjusted to
+pub const MIN_STRENGTH: i32 = -100;
+/// Strongest a relationship can be adjusted to
+pub const MAX_STRENGTH: i32 = 100;
+
+/// One entry in a relationship's strength history
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StrengthEntry {
+    pub id: String,
+    pub relationship_id: String,
+    pub session_id: Option<String>,
+    /// Strength after this change
+    pub value: i32,
+    pub change: i32,
+    pub reason: Option<String>,
+    /// True when a decay rule made the change rather than the GM
+    pub is_decay: bool,
+    pub created_at: String,
+}
+
+impl From<strength_changes::Model> for StrengthEntry {
+    fn from(model: strength_changes::Model) -> Self {
+        Self {
+            id: model.id,
+            relationship_id: model.relationship_id,
+            session_id: model.session_id,
+            value: model.value,
+            change: model.change,
+            reason: model.reason,
+            is_decay: model.is_decay,
+            created_at: model.created_at.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StrengthHistory {
+    pub relationship_id: String,
+    /// Current strength; None when it was never set
+    pub current: Option<i32>,
+    /// Oldest first
+    pub entries: Vec<StrengthEntry>,
+}
+
+/// "Unreinforced relationships drift toward 0": once a relationship's
+/// strength has gone `after_days` without changing, it moves `step` closer
+/// to 0 for every such period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecayRule {
+    pub after_days: u32,
+    pub step: i32,
+}
+
+/// Record a change to `rel`'s strength, which already holds the new value
+pub(crate) async fn record_strength_change<C: ConnectionTrait>(
+    conn: &C,
+    rel: &relationships::Model,
+    previous: i32,
+    reason: Option<String>,
+    session_id: Option<String>,
+    is_decay: bool,
+) -> Result<strength_changes::Model, AppError> {
+    let value = rel.strength.unwrap_or(0);
+    let now = chrono::Utc::now();
+    Ok(strength_changes::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(rel.campaign_id.clone()),
+        relationship_id: Set(rel.id.clone()),
+        session_id: Set(session_id),
+        value: Set(value),
+        change: Set(value - previous),
+        reason: Set(reason),
+        is_decay: Set(is_decay),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(conn)
+    .await?)
+}
+
+/// Move `value` toward 0 by `amount` without crossing it
+fn decay_toward_zero(value: i32, amount: i32) -> i32 {
+    if value > 0 {
+        value.saturating_sub(amount).max(0)
+    } else {
+        value.saturating_add(amount).min(0)
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Shift a relationship's strength by `change`, clamped to
+/// [`MIN_STRENGTH`]..=[`MAX_STRENGTH`], and record why
+#[instrument(skip_all, fields(relationship_id = %relationship_id, change), err)]
+pub async fn adjust_relationship_strength_impl(
+    db: &DatabaseConnection,
+    relationship_id: String,
+    change: i32,
+    reason: Option<String>,
+    session_id: Option<String>,
+) -> Result<StrengthEntry, AppError> {
+    if change == 0 {
+        return Err(AppError::Validation("change: must not be zero".to_string()));
+    }
+
+    let rel = Relationship::find_by_id(&relationship_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Relationship {} not found", relationship_id)))?;
+    if let Some(session_id) = &session_id {
+        let session = Session::find_by_id(session_id)
+            .one(db)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Session {} not found", session_id)))?;
+        if session.campaign_id != rel.campaign_id {
+            return Err(AppError::Validation(
+                "session_id: session belongs to a different campaign".to_string(),
+            ));
+        }
+    }
+
+    let previous = rel.strength.unwrap_or(0);
+    let value = previous
+        .saturating_add(change)
+        .clamp(MIN_STRENGTH, MAX_STRENGTH);
+
+    let txn = db.begin().await?;
+    let mut active: relationships::ActiveModel = rel.into();
+    active.strength = Set(Some(value));
+    active.updated_at = Set(chrono::Utc::now());
+    let rel = active.update(&txn).await?;
+    let entry = record_strength_change(&txn, &rel, previous, reason, session_id, false).await?;
+    txn.commit().await?;
+
+    Ok(entry.into())
+}
+
+#[instrument(skip_all, fields(relationship_id = %relationship_id), err)]
+pub async fn get_relationship_strength_history_impl(
+    db: &DatabaseConnection,
+    relationship_id: String,
+) -> Result<StrengthHistory, AppError> {
+    let rel = Relationship::find_by_id(&relationship_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Relationship {} not found", relationship_id)))?;
+
+    let entries = StrengthChange::find()
+        .filter(strength_changes::Column::RelationshipId.eq(&relationship_id))
+        .order_by_asc(strength_changes::Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(StrengthEntry::from)
+        .collect();
+
+    Ok(StrengthHistory {
+        relationship_id,
+        current: rel.strength,
+        entries,
+    })
+}
+
+/// Apply `rule` to every relationship in the campaign, returning the
+/// changes it made. A relationship's clock starts at its last strength
+/// change, or its creation when there is none, and the decay itself counts
+/// as a change, so running this again straight away does nothing.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn apply_relationship_decay_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    rule: DecayRule,
+) -> Result<Vec<StrengthEntry>, AppError> {
+    if rule.after_days == 0 {
+        return Err(AppError::Validation(
+            "after_days: must be at least 1".to_string(),
+        ));
+    }
+    if rule.step <= 0 {
+        return Err(AppError::Validation("step: must be positive".to_string()));
+    }
+
+    let relationships = Relationship::find()
+        .filter(relationships::Column::CampaignId.eq(&campaign_id))
+        .filter(relationships::Column::Strength.is_not_null())
+        .filter(relationships::Column::Strength.ne(0))
+        .all(db)
+        .await?;
+    if relationships.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Later rows overwrite earlier ones, leaving the newest per relationship
+    let last_changed: HashMap<String, DateTimeUtc> = StrengthChange::find()
+        .filter(strength_changes::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(strength_changes::Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|c| (c.relationship_id, c.created_at))
+        .collect();
+
+    let now = chrono::Utc::now();
+    let txn = db.begin().await?;
+    let mut entries = Vec::new();
+    for rel in relationships {
+        let since = last_changed.get(&rel.id).copied().unwrap_or(rel.created_at);
+        let idle_days = (now - since).num_days();
+        let periods = idle_days / i64::from(rule.after_days);
+        if periods <= 0 {
+            continue;
+        }
+
+        let previous = rel.strength.unwrap_or(0);
+        let amount = i32::try_from(periods)
+            .unwrap_or(i32::MAX)
+            .saturating_mul(rule.step);
+        let value = decay_toward_zero(previous, amount);
+        let reason = format!("Not reinforced for {} days", idle_days);
+
+        let mut active: relationships::ActiveModel = rel.into();
+        active.strength = Set(Some(value));
+        active.updated_at = Set(now);
+        let rel = active.update(&txn).await?;
+        let entry = record_strength_change(&txn, &rel, previous, Some(reason), None, true).await?;
+        entries.push(entry.into());
+    }
+    txn.commit().await?;
+
+    tracing::info!(changed = entries.len(), "Applied relationship decay");
+    Ok(entries)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn adjust_relationship_strength(
+    state: State<'_, AppState>,
+    relationship_id: String,
+    change: i32,
+    reason: Option<String>,
+    session_id: Option<String>,
+) -> Result<StrengthEntry, AppError> {
+    adjust_relationship_strength_impl(&state.db, relationship_id, change, reason, session_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_relationship_strength_history(
+    state: State<'_, AppState>,
+    relationship_id: String,
+) -> Result<StrengthHistory, AppError> {
+    get_relationship_strength_history_impl(&state.db, relationship_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn apply_relationship_decay(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    rule: DecayRule,
+) -> Result<Vec<StrengthEntry>, AppError> {
+    apply_relationship_decay_impl(&state.db, campaign_id, rule).await
+}
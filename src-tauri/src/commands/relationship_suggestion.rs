@@ -0,0 +1,175 @@
+//! Relationship suggestions from mentions: entities whose text keeps
+//! referring to each other but that have no relationship yet. Nothing is
+//! created here; accepted proposals go through `create_relationship`.
+
+use crate::commands::registry::table_for;
+use crate::commands::relationship::entity_names;
+use crate::commands::session_prep::{collect_mentions, collect_text_mentions};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::relationships::{self, Entity as Relationship};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tauri::State;
+use tracing::instrument;
+
+/// Entity types that take part in relationships
+const RELATABLE_TYPES: &[&str] = &[
+    "character",
+    "location",
+    "organization",
+    "quest",
+    "hero",
+    "item",
+];
+
+/// Relationship type proposed for every suggestion; the GM picks a real one
+/// when accepting
+const SUGGESTED_RELATIONSHIP_TYPE: &str = "associated_with";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelationshipSuggestion {
+    /// The side that mentions the other more often
+    pub source_type: String,
+    pub source_id: String,
+    pub source_name: String,
+    pub target_type: String,
+    pub target_id: String,
+    pub target_name: String,
+    /// Text fields of the source that mention the target
+    pub source_mentions: u32,
+    /// Text fields of the target that mention the source
+    pub target_mentions: u32,
+    pub relationship_type: String,
+    /// Both sides mention each other
+    pub is_bidirectional: bool,
+}
+
+/// Entity references in a free-text field, rich text or plain
+fn mentions_in(text: &str) -> BTreeSet<(String, String)> {
+    let mut found = BTreeSet::new();
+    match serde_json::from_str::<Value>(text) {
+        Ok(doc) if doc.is_object() => collect_mentions(&doc, &mut found),
+        _ => collect_text_mentions(text, &mut found),
+    }
+    found
+}
+
+type EntityKey = (String, String);
+
+// ============ Core implementation functions (testable) ============
+
+/// Pairs of entities that mention each other at least `min_mentions` times
+/// in total (counting each text field once) and aren't related yet, most
+/// mentioned first
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn suggest_relationships_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    min_mentions: Option<u32>,
+) -> Result<Vec<RelationshipSuggestion>, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    let min_mentions = min_mentions.unwrap_or(1).max(1);
+
+    // (mentioner, mentioned) -> number of the mentioner's fields
+    let mut counts: HashMap<(EntityKey, EntityKey), u32> = HashMap::new();
+    for table in RELATABLE_TYPES.iter().filter_map(|t| table_for(t)) {
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!(
+                    "SELECT id, {} FROM {} WHERE campaign_id = $1",
+                    table.text_columns.join(", "),
+                    table.table
+                ),
+                [campaign_id.clone().into()],
+            ))
+            .await?;
+
+        for row in rows {
+            let source: EntityKey = (table.entity_type.to_string(), row.try_get("", "id")?);
+            for column in table.text_columns {
+                let Some(text) = row.try_get::<Option<String>>("", column)? else {
+                    continue;
+                };
+                for target in mentions_in(&text) {
+                    if target != source && RELATABLE_TYPES.contains(&target.0.as_str()) {
+                        *counts.entry((source.clone(), target)).or_default() += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let related: HashSet<(EntityKey, EntityKey)> = Relationship::find()
+        .filter(relationships::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .flat_map(|r| {
+            let source = (r.source_type, r.source_id);
+            let target = (r.target_type, r.target_id);
+            [(source.clone(), target.clone()), (target, source)]
+        })
+        .collect();
+
+    let names = entity_names(db, &campaign_id, &RELATABLE_TYPES.iter().copied().collect()).await?;
+
+    let mut suggestions = Vec::new();
+    for ((source, target), &forward) in &counts {
+        let backward = counts
+            .get(&(target.clone(), source.clone()))
+            .copied()
+            .unwrap_or(0);
+        // Each pair once, from the side that mentions more
+        if (backward, source) > (forward, target) {
+            continue;
+        }
+        if forward + backward < min_mentions || related.contains(&(source.clone(), target.clone()))
+        {
+            continue;
+        }
+        // Mentions of deleted entities are left for the editor to clean up
+        let (Some(source_name), Some(target_name)) = (names.get(source), names.get(target)) else {
+            continue;
+        };
+
+        suggestions.push(RelationshipSuggestion {
+            source_type: source.0.clone(),
+            source_id: source.1.clone(),
+            source_name: source_name.clone(),
+            target_type: target.0.clone(),
+            target_id: target.1.clone(),
+            target_name: target_name.clone(),
+            source_mentions: forward,
+            target_mentions: backward,
+            relationship_type: SUGGESTED_RELATIONSHIP_TYPE.to_string(),
+            is_bidirectional: backward > 0,
+        });
+    }
+
+    suggestions.sort_by(|a, b| {
+        (b.source_mentions + b.target_mentions)
+            .cmp(&(a.source_mentions + a.target_mentions))
+            .then_with(|| a.source_name.cmp(&b.source_name))
+            .then_with(|| a.target_name.cmp(&b.target_name))
+    });
+    Ok(suggestions)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn suggest_relationships(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    min_mentions: Option<u32>,
+) -> Result<Vec<RelationshipSuggestion>, AppError> {
+    suggest_relationships_impl(&state.db, campaign_id, min_mentions).await
+}
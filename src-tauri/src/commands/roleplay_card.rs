@@ -0,0 +1,239 @@
+//! Cue cards for voicing an NPC at the table: the character's roleplay
+//! fields on one compact card, plus the last few sessions they turned up in
+//! so the GM remembers what was said. A session counts when its notes
+//! mention the character, as in the activity feed, or when the character's
+//! disposition changed during it.
+
+use crate::commands::activity::session_time;
+use crate::commands::character::get_character_impl;
+use crate::commands::disposition::get_disposition_history_impl;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::export::rich_text::to_markdown;
+use ::entity::sessions::{self, Entity as Session};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tauri::State;
+use tracing::instrument;
+
+/// Sessions shown on a card
+pub const RECENT_INTERACTIONS: usize = 5;
+/// Longest excerpt quoted from session notes
+const MAX_EXCERPT_LEN: usize = 300;
+/// Excerpts quoted per session
+const MAX_EXCERPTS: usize = 3;
+
+/// A session the character appeared in
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleplayInteraction {
+    pub session_id: String,
+    pub session_number: i32,
+    pub session_title: Option<String>,
+    pub occurred_at: String,
+    /// Passages of the session's notes, summary and highlights that mention
+    /// the character, as markdown
+    pub excerpts: Vec<String>,
+    /// Net disposition change recorded during the session
+    pub disposition_change: i32,
+    /// Reasons given for those changes
+    pub disposition_reasons: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleplayCard {
+    pub character_id: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub lineage: Option<String>,
+    pub occupation: Option<String>,
+    pub is_alive: bool,
+    pub accent: Option<String>,
+    pub mannerisms: Vec<String>,
+    pub catchphrases: Vec<String>,
+    pub speech_sample: Option<String>,
+    /// Free-form voice notes, as markdown
+    pub voice_notes: Option<String>,
+    /// Personality and motivations, cut short to fit the card
+    pub personality: Option<String>,
+    pub motivations: Option<String>,
+    /// Current attitude toward the party
+    pub disposition: i32,
+    /// Newest first
+    pub recent_interactions: Vec<RoleplayInteraction>,
+}
+
+/// A field as markdown, or `None` when it's blank
+fn markdown(field: Option<String>) -> Option<String> {
+    field
+        .map(|f| to_markdown(&f, 0))
+        .filter(|f| !f.trim().is_empty())
+}
+
+/// Cut `text` to about `MAX_EXCERPT_LEN` characters, at a word break
+fn shorten(text: &str) -> String {
+    if text.chars().count() <= MAX_EXCERPT_LEN {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(MAX_EXCERPT_LEN).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+    format!("{}…", cut.trim_end())
+}
+
+/// Paragraphs of `text` that mention the character `id`, as markdown.
+/// Editor JSON is split into its top-level blocks; plain text into lines.
+fn mentioning_passages(text: &str, id: &str, name: &str) -> Vec<String> {
+    let blocks: Vec<String> = match serde_json::from_str::<Value>(text) {
+        Ok(doc) if doc.get("type").and_then(Value::as_str) == Some("doc") => doc
+            .get("content")
+            .and_then(Value::as_array)
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .map(|b| serde_json::json!({ "type": "doc", "content": [b] }).to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => text.lines().map(str::to_string).collect(),
+    };
+    // Bare `[[character:id]]` links would otherwise come out as the id
+    let bare_link = format!("[[character:{}]]", id);
+    let labelled_link = format!("[[character:{}:{}]]", id, name);
+    blocks
+        .into_iter()
+        .filter(|block| block.contains(id))
+        .map(|block| to_markdown(&block.replace(&bare_link, &labelled_link), 0))
+        .map(|passage| shorten(passage.trim()))
+        .filter(|passage| !passage.is_empty())
+        .collect()
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// The character's roleplay card, with up to `RECENT_INTERACTIONS` of the
+/// latest sessions they appeared in
+#[instrument(skip_all, fields(character_id = %character_id), err)]
+pub async fn get_roleplay_card_impl(
+    db: &DatabaseConnection,
+    character_id: String,
+) -> Result<RoleplayCard, AppError> {
+    let character = get_character_impl(db, character_id.clone()).await?;
+    let history = get_disposition_history_impl(db, character_id.clone()).await?;
+
+    let mut changes: HashMap<String, (i32, Vec<String>)> = HashMap::new();
+    for entry in history.entries {
+        if let Some(session_id) = entry.session_id {
+            let (change, reasons) = changes.entry(session_id).or_default();
+            *change += entry.change;
+            reasons.extend(entry.reason.filter(|r| !r.trim().is_empty()));
+        }
+    }
+
+    let mut sessions = Session::find()
+        .filter(sessions::Column::CampaignId.eq(&character.campaign_id))
+        .all(db)
+        .await?;
+    sessions.sort_by_key(|s| std::cmp::Reverse((session_time(s), s.session_number)));
+
+    let mut recent_interactions = Vec::new();
+    for session in sessions {
+        if recent_interactions.len() == RECENT_INTERACTIONS {
+            break;
+        }
+        let texts: Vec<&String> = [
+            &session.summary,
+            &session.highlights,
+            &session.notes,
+            &session.planned_content,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let mentioned = texts.iter().any(|text| text.contains(&character.id));
+        if !mentioned && !changes.contains_key(&session.id) {
+            continue;
+        }
+        let excerpts: Vec<String> = texts
+            .into_iter()
+            .flat_map(|text| mentioning_passages(text, &character.id, &character.name))
+            .take(MAX_EXCERPTS)
+            .collect();
+        let (disposition_change, disposition_reasons) =
+            changes.remove(&session.id).unwrap_or_default();
+        recent_interactions.push(RoleplayInteraction {
+            occurred_at: session_time(&session).to_rfc3339(),
+            session_id: session.id,
+            session_number: session.session_number,
+            session_title: session.title,
+            excerpts,
+            disposition_change,
+            disposition_reasons,
+        });
+    }
+
+    Ok(RoleplayCard {
+        character_id: character.id,
+        name: character.name,
+        aliases: character.aliases,
+        lineage: character.lineage,
+        occupation: character.occupation,
+        is_alive: character.is_alive,
+        accent: character.accent,
+        mannerisms: character.mannerisms,
+        catchphrases: character.catchphrases,
+        speech_sample: character.speech_sample,
+        voice_notes: markdown(character.voice_notes),
+        personality: markdown(character.personality).map(|p| shorten(&p)),
+        motivations: markdown(character.motivations).map(|m| shorten(&m)),
+        disposition: history.current,
+        recent_interactions,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_roleplay_card(
+    state: State<'_, AppState>,
+    character_id: String,
+) -> Result<RoleplayCard, AppError> {
+    get_roleplay_card_impl(&state.db, character_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passages_from_plain_text() {
+        let notes = "Met the harbormaster.\n[[character:c1]] refused the bribe.\nLeft town.";
+        assert_eq!(
+            mentioning_passages(notes, "c1", "Mara"),
+            vec!["Mara refused the bribe."]
+        );
+    }
+
+    #[test]
+    fn test_passages_from_editor_json() {
+        let doc = r#"{"type":"doc","content":[
+            {"type":"paragraph","content":[{"type":"text","text":"Rain all day."}]},
+            {"type":"paragraph","content":[
+                {"type":"mention","attrs":{"entityType":"character","entityId":"c1","label":"Mara"}},
+                {"type":"text","text":" sold them a map"}
+            ]}
+        ]}"#;
+        assert_eq!(
+            mentioning_passages(doc, "c1", "Mara"),
+            vec!["Mara sold them a map"]
+        );
+    }
+
+    #[test]
+    fn test_shorten_at_word_break() {
+        let long = "word ".repeat(100);
+        let short = shorten(long.trim());
+        assert!(short.ends_with("word…"));
+        assert!(short.chars().count() <= MAX_EXCERPT_LEN + 1);
+    }
+}
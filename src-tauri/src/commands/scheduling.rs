@@ -0,0 +1,305 @@
+//! Real-world scheduling: player availability, suggested session dates and
+//! who missed which session.
+
+use crate::commands::json_schema::PlayerAvailability;
+use crate::commands::player::PlayerResponse;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::players::{self, Entity as Player};
+use ::entity::session_absences::{self, Entity as SessionAbsence};
+use ::entity::sessions::{self, Entity as Session};
+use chrono::{Datelike, NaiveDate};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::State;
+use tracing::instrument;
+
+/// Longest range `suggest_session_dates` will scan
+pub const MAX_SUGGESTION_RANGE_DAYS: i64 = 366;
+
+/// A candidate date and who could attend
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionDateSuggestion {
+    /// ISO date, e.g. "2026-02-06"
+    pub date: String,
+    /// Short weekday name, e.g. "Fri"
+    pub weekday: String,
+    pub available_player_ids: Vec<String>,
+    pub unavailable_player_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionAbsenceResponse {
+    pub id: String,
+    pub session_id: String,
+    pub player_id: String,
+    pub player_name: String,
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+fn parse_date(value: &str, field: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| AppError::Validation(format!("{}: must be a date like 2026-01-31", field)))
+}
+
+fn availability_of(player: &players::Model) -> PlayerAvailability {
+    player
+        .availability_json
+        .as_deref()
+        .and_then(|raw| PlayerAvailability::parse(raw).ok())
+        .unwrap_or_default()
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(player_id = %player_id), err)]
+pub async fn set_player_availability_impl(
+    db: &DatabaseConnection,
+    player_id: String,
+    availability: PlayerAvailability,
+) -> Result<PlayerResponse, AppError> {
+    availability.validate()?;
+
+    let player = Player::find_by_id(&player_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Player {} not found", player_id)))?;
+
+    let availability_json = serde_json::to_string(&availability)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize availability: {}", e)))?;
+
+    let mut active: players::ActiveModel = player.into();
+    active.availability_json = Set(Some(availability_json));
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    Ok(result.into())
+}
+
+/// Dates between `start_date` and `end_date` (inclusive) when at least
+/// `min_available` players are free, defaulting to the whole group. Dates
+/// that already have a session are skipped.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn suggest_session_dates_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    start_date: String,
+    end_date: String,
+    min_available: Option<usize>,
+) -> Result<Vec<SessionDateSuggestion>, AppError> {
+    let start = parse_date(&start_date, "start_date")?;
+    let end = parse_date(&end_date, "end_date")?;
+    if end < start {
+        return Err(AppError::Validation(
+            "end_date: must not be before start_date".to_string(),
+        ));
+    }
+    if (end - start).num_days() >= MAX_SUGGESTION_RANGE_DAYS {
+        return Err(AppError::Validation(format!(
+            "end_date: range must be shorter than {} days",
+            MAX_SUGGESTION_RANGE_DAYS
+        )));
+    }
+
+    let players = Player::find()
+        .filter(players::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(players::Column::Name)
+        .all(db)
+        .await?;
+    let min_available = min_available.unwrap_or(players.len());
+    if min_available > players.len() {
+        return Err(AppError::Validation(format!(
+            "min_available: campaign only has {} players",
+            players.len()
+        )));
+    }
+
+    let booked: HashSet<NaiveDate> = Session::find()
+        .filter(sessions::Column::CampaignId.eq(&campaign_id))
+        .filter(sessions::Column::Date.between(start, end))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|s| s.date)
+        .collect();
+
+    let schedules: Vec<(&str, PlayerAvailability)> = players
+        .iter()
+        .map(|p| (p.id.as_str(), availability_of(p)))
+        .collect();
+
+    let suggestions = start
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .filter(|date| !booked.contains(date))
+        .filter_map(|date| {
+            let (free, busy): (Vec<_>, Vec<_>) = schedules
+                .iter()
+                .partition(|(_, availability)| availability.is_free_on(date));
+            (free.len() >= min_available).then(|| SessionDateSuggestion {
+                date: date.to_string(),
+                weekday: date.weekday().to_string(),
+                available_player_ids: free.iter().map(|&&(id, _)| id.to_string()).collect(),
+                unavailable_player_ids: busy.iter().map(|&&(id, _)| id.to_string()).collect(),
+            })
+        })
+        .collect();
+
+    Ok(suggestions)
+}
+
+/// Record that a player missed a session. Recording the same player twice
+/// updates the reason.
+#[instrument(skip_all, fields(session_id = %session_id, player_id = %player_id), err)]
+pub async fn record_session_absence_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+    player_id: String,
+    reason: Option<String>,
+) -> Result<SessionAbsenceResponse, AppError> {
+    let session = Session::find_by_id(&session_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} not found", session_id)))?;
+    let player = Player::find_by_id(&player_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Player {} not found", player_id)))?;
+    if player.campaign_id != session.campaign_id {
+        return Err(AppError::Validation(
+            "player_id: player belongs to a different campaign".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now();
+    let existing = SessionAbsence::find()
+        .filter(session_absences::Column::SessionId.eq(&session_id))
+        .filter(session_absences::Column::PlayerId.eq(&player_id))
+        .one(db)
+        .await?;
+
+    let absence = match existing {
+        Some(existing) => {
+            let mut active: session_absences::ActiveModel = existing.into();
+            active.reason = Set(reason);
+            active.updated_at = Set(now);
+            active.update(db).await?
+        }
+        None => {
+            session_absences::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                campaign_id: Set(session.campaign_id),
+                session_id: Set(session_id),
+                player_id: Set(player_id),
+                reason: Set(reason),
+                created_at: Set(now),
+                updated_at: Set(now),
+                created_by: Set(None),
+                modified_by: Set(None),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+
+    Ok(SessionAbsenceResponse {
+        id: absence.id,
+        session_id: absence.session_id,
+        player_id: absence.player_id,
+        player_name: player.name,
+        reason: absence.reason,
+        created_at: absence.created_at.to_string(),
+    })
+}
+
+#[instrument(skip_all, fields(session_id = %session_id, player_id = %player_id), err)]
+pub async fn remove_session_absence_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+    player_id: String,
+) -> Result<bool, AppError> {
+    let result = SessionAbsence::delete_many()
+        .filter(session_absences::Column::SessionId.eq(&session_id))
+        .filter(session_absences::Column::PlayerId.eq(&player_id))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn list_session_absences_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+) -> Result<Vec<SessionAbsenceResponse>, AppError> {
+    let rows = SessionAbsence::find()
+        .filter(session_absences::Column::SessionId.eq(&session_id))
+        .find_also_related(Player)
+        .order_by_asc(players::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(absence, player)| {
+            Some(SessionAbsenceResponse {
+                id: absence.id,
+                session_id: absence.session_id,
+                player_id: absence.player_id,
+                player_name: player?.name,
+                reason: absence.reason,
+                created_at: absence.created_at.to_string(),
+            })
+        })
+        .collect())
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_player_availability(
+    state: State<'_, AppState>,
+    player_id: String,
+    availability: PlayerAvailability,
+) -> Result<PlayerResponse, AppError> {
+    set_player_availability_impl(&state.db, player_id, availability).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn suggest_session_dates(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    start_date: String,
+    end_date: String,
+    min_available: Option<usize>,
+) -> Result<Vec<SessionDateSuggestion>, AppError> {
+    suggest_session_dates_impl(&state.db, campaign_id, start_date, end_date, min_available).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn record_session_absence(
+    state: State<'_, AppState>,
+    session_id: String,
+    player_id: String,
+    reason: Option<String>,
+) -> Result<SessionAbsenceResponse, AppError> {
+    record_session_absence_impl(&state.db, session_id, player_id, reason).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remove_session_absence(
+    state: State<'_, AppState>,
+    session_id: String,
+    player_id: String,
+) -> Result<bool, AppError> {
+    remove_session_absence_impl(&state.db, session_id, player_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_session_absences(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<SessionAbsenceResponse>, AppError> {
+    list_session_absences_impl(&state.db, session_id).await
+}
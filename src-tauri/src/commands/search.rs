@@ -1,8 +1,90 @@
 use crate::db::AppState;
 use crate::error::AppError;
+use ::entity::locations::{self, Entity as Location};
+use ::entity::relationships::{self, Entity as Relationship};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use tauri::State;
+use tracing::instrument;
+
+/// Columns of the `search_index` FTS5 table a snippet can be cut from
+const SNIPPET_COLUMNS: &[&str] = &["auto", "name", "content"];
+
+/// FTS5 refuses snippets longer than this many tokens
+const MAX_SNIPPET_TOKENS: u32 = 64;
+
+/// How search result snippets are cut and highlighted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnippetOptions {
+    /// `name`, `content`, or `auto` to let FTS5 pick the best-matching column
+    pub column: String,
+    /// Tokens of context in the snippet, at most 64
+    pub context_tokens: u32,
+    pub open_marker: String,
+    pub close_marker: String,
+    /// Shown where the snippet cuts the text short
+    pub ellipsis: String,
+}
+
+impl Default for SnippetOptions {
+    fn default() -> Self {
+        Self {
+            column: "auto".to_string(),
+            context_tokens: 32,
+            open_marker: "<mark>".to_string(),
+            close_marker: "</mark>".to_string(),
+            ellipsis: "...".to_string(),
+        }
+    }
+}
+
+impl SnippetOptions {
+    /// The FTS5 column index to pass to `snippet()`; -1 picks automatically
+    fn column_index(&self) -> Result<i64, AppError> {
+        match self.column.as_str() {
+            "auto" => Ok(-1),
+            "name" => Ok(3),
+            "content" => Ok(4),
+            other => Err(AppError::Validation(format!(
+                "snippet.column: must be one of: {} (got {})",
+                SNIPPET_COLUMNS.join(", "),
+                other
+            ))),
+        }
+    }
+
+    fn validate(&self) -> Result<(), AppError> {
+        self.column_index()?;
+        if !(1..=MAX_SNIPPET_TOKENS).contains(&self.context_tokens) {
+            return Err(AppError::Validation(format!(
+                "snippet.context_tokens: must be between 1 and {}",
+                MAX_SNIPPET_TOKENS
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How many results of each entity type a grouped search keeps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TypeLimits {
+    /// Results kept for types without an override
+    pub per_type: u64,
+    /// Limits for particular entity types, e.g. `{"quest": 5}`
+    pub overrides: HashMap<String, u64>,
+}
+
+impl Default for TypeLimits {
+    fn default() -> Self {
+        Self {
+            per_type: 3,
+            overrides: HashMap::new(),
+        }
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -10,17 +92,142 @@ pub struct SearchResult {
     pub entity_id: String,
     pub name: String,
     pub snippet: Option<String>,
+    /// Which field the query hit, `name` or `content`; a hit on both counts
+    /// as `name`. None when only the entity type matched.
+    pub matched_field: Option<String>,
     pub rank: f64,
 }
 
+fn search_result(row: QueryResult) -> Option<SearchResult> {
+    let name_matched: bool = row.try_get("", "name_matched").unwrap_or(false);
+    let content_matched: bool = row.try_get("", "content_matched").unwrap_or(false);
+    let matched_field = if name_matched {
+        Some("name".to_string())
+    } else if content_matched {
+        Some("content".to_string())
+    } else {
+        None
+    };
+    Some(SearchResult {
+        entity_type: row.try_get("", "entity_type").ok()?,
+        entity_id: row.try_get("", "entity_id").ok()?,
+        name: row.try_get("", "name").ok()?,
+        snippet: row.try_get("", "snippet").ok(),
+        matched_field,
+        rank: row.try_get("", "rank").ok()?,
+    })
+}
+
+/// Result columns for a full-text match. `highlight()` marks every hit in a
+/// column, so wrapping hits in a control character that can't appear in
+/// entity text tells which columns matched.
+const MATCH_COLUMNS: &str = r#"
+    entity_type,
+    entity_id,
+    name,
+    snippet(search_index, $3, $4, $5, $6, $7) as snippet,
+    instr(highlight(search_index, 3, char(1), ''), char(1)) > 0 as name_matched,
+    instr(highlight(search_index, 4, char(1), ''), char(1)) > 0 as content_matched,
+    rank
+"#;
+
+/// Bind values for the snippet placeholders in [`MATCH_COLUMNS`], which
+/// follow the query ($1) and campaign ($2)
+fn snippet_values(options: &SnippetOptions) -> Result<Vec<Value>, AppError> {
+    options.validate()?;
+    Ok(vec![
+        options.column_index()?.into(),
+        options.open_marker.clone().into(),
+        options.close_marker.clone().into(),
+        options.ellipsis.clone().into(),
+        i64::from(options.context_tokens).into(),
+    ])
+}
+
+/// Keep the best few results of each type, one type after another. Results
+/// arrive best first, so each type's group sits where its top hit ranked.
+fn group_by_type(results: Vec<SearchResult>, limits: &TypeLimits) -> Vec<SearchResult> {
+    let mut groups: Vec<(String, Vec<SearchResult>)> = Vec::new();
+    for result in results {
+        let limit = limits
+            .overrides
+            .get(&result.entity_type)
+            .copied()
+            .unwrap_or(limits.per_type);
+        match groups.iter_mut().find(|(t, _)| *t == result.entity_type) {
+            Some((_, group)) if (group.len() as u64) < limit => group.push(result),
+            None if limit > 0 => groups.push((result.entity_type.clone(), vec![result])),
+            _ => {}
+        }
+    }
+    groups.into_iter().flat_map(|(_, group)| group).collect()
+}
+
+/// Entities a location search covers: the location, every location beneath
+/// it, and anything linked to one of those by a relationship
+async fn location_scope(
+    db: &DatabaseConnection,
+    location: &locations::Model,
+) -> Result<HashSet<(String, String)>, AppError> {
+    let all = Location::find()
+        .filter(locations::Column::CampaignId.eq(&location.campaign_id))
+        .all(db)
+        .await?;
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for l in &all {
+        if let Some(parent_id) = &l.parent_id {
+            children.entry(parent_id.as_str()).or_default().push(&l.id);
+        }
+    }
+
+    // The visited check also stops on a parent cycle
+    let mut subtree: HashSet<&str> = HashSet::new();
+    let mut stack = vec![location.id.as_str()];
+    while let Some(id) = stack.pop() {
+        if subtree.insert(id) {
+            stack.extend(children.get(id).into_iter().flatten());
+        }
+    }
+
+    let mut scope: HashSet<(String, String)> = subtree
+        .iter()
+        .map(|&id| ("location".to_string(), id.to_string()))
+        .collect();
+    let in_subtree =
+        |entity_type: &str, id: &str| entity_type == "location" && subtree.contains(id);
+    let rels = Relationship::find()
+        .filter(relationships::Column::CampaignId.eq(&location.campaign_id))
+        .filter(
+            Condition::any()
+                .add(relationships::Column::SourceType.eq("location"))
+                .add(relationships::Column::TargetType.eq("location")),
+        )
+        .all(db)
+        .await?;
+    for r in rels {
+        if in_subtree(&r.source_type, &r.source_id) {
+            scope.insert((r.target_type.clone(), r.target_id.clone()));
+        }
+        if in_subtree(&r.target_type, &r.target_id) {
+            scope.insert((r.source_type, r.source_id));
+        }
+    }
+    Ok(scope)
+}
+
 // ============ Core implementation functions (testable) ============
 
+/// Full-text search across a campaign. With `grouping`, results come back
+/// grouped by entity type, each type cut to its limit before `limit` applies.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn search_entities_impl(
     db: &DatabaseConnection,
     campaign_id: String,
     query: String,
     entity_types: Option<Vec<String>>,
     limit: Option<u64>,
+    snippet: SnippetOptions,
+    grouping: Option<TypeLimits>,
 ) -> Result<Vec<SearchResult>, AppError> {
     let limit = limit.unwrap_or(50);
     let _ = entity_types; // TODO: Implement entity type filtering
@@ -29,39 +236,97 @@ pub async fn search_entities_impl(
     let fts_query = build_fts_query(&query);
 
     let backend = db.get_database_backend();
+    let mut values: Vec<Value> = vec![fts_query.into(), campaign_id.into()];
+    values.extend(snippet_values(&snippet)?);
+    // Grouping needs every match to pick from; SQLite reads LIMIT -1 as none
+    let sql_limit = if grouping.is_some() { -1 } else { limit as i64 };
+    values.push(sql_limit.into());
 
     let results: Vec<SearchResult> = db
         .query_all(Statement::from_sql_and_values(
             backend,
-            r#"
-            SELECT
-                entity_type,
-                entity_id,
-                name,
-                snippet(search_index, 3, '<mark>', '</mark>', '...', 32) as snippet,
-                rank
-            FROM search_index
-            WHERE search_index MATCH $1
-            AND campaign_id = $2
-            ORDER BY rank
-            LIMIT $3
-            "#,
-            [fts_query.into(), campaign_id.into(), (limit as i64).into()],
+            format!(
+                r#"
+                SELECT {MATCH_COLUMNS}
+                FROM search_index
+                WHERE search_index MATCH $1
+                AND campaign_id = $2
+                ORDER BY rank
+                LIMIT $8
+                "#
+            ),
+            values,
         ))
         .await?
         .into_iter()
-        .filter_map(|row| {
-            Some(SearchResult {
-                entity_type: row.try_get("", "entity_type").ok()?,
-                entity_id: row.try_get("", "entity_id").ok()?,
-                name: row.try_get("", "name").ok()?,
-                snippet: row.try_get("", "snippet").ok(),
-                rank: row.try_get("", "rank").ok()?,
-            })
-        })
+        .filter_map(search_result)
         .collect();
 
-    Ok(results)
+    Ok(match grouping {
+        Some(limits) => group_by_type(results, &limits)
+            .into_iter()
+            .take(limit as usize)
+            .collect(),
+        None => results,
+    })
+}
+
+/// Search only the entities within a location's subtree or related to it,
+/// e.g. everything about one district. An empty query lists all of them.
+#[instrument(skip_all, fields(location_id = %location_id), err)]
+pub async fn search_in_location_impl(
+    db: &DatabaseConnection,
+    location_id: String,
+    query: String,
+    limit: Option<u64>,
+) -> Result<Vec<SearchResult>, AppError> {
+    let limit = limit.unwrap_or(50);
+    let location = Location::find_by_id(&location_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Location {} not found", location_id)))?;
+    let scope = location_scope(db, &location).await?;
+
+    let fts_query = build_fts_query(&query);
+    let backend = db.get_database_backend();
+    // The scope is applied after matching, so no LIMIT here
+    let statement = if fts_query.is_empty() {
+        Statement::from_sql_and_values(
+            backend,
+            r#"
+            SELECT entity_type, entity_id, name, NULL as snippet, 0.0 as rank
+            FROM search_index
+            WHERE campaign_id = $1
+            ORDER BY name
+            "#,
+            [location.campaign_id.into()],
+        )
+    } else {
+        let mut values: Vec<Value> = vec![fts_query.into(), location.campaign_id.into()];
+        values.extend(snippet_values(&SnippetOptions::default())?);
+        Statement::from_sql_and_values(
+            backend,
+            format!(
+                r#"
+                SELECT {MATCH_COLUMNS}
+                FROM search_index
+                WHERE search_index MATCH $1
+                AND campaign_id = $2
+                ORDER BY rank
+                "#
+            ),
+            values,
+        )
+    };
+
+    Ok(db
+        .query_all(statement)
+        .await?
+        .into_iter()
+        .filter_map(search_result)
+        .filter(|r| scope.contains(&(r.entity_type.clone(), r.entity_id.clone())))
+        .take(limit as usize)
+        .collect())
 }
 
 // ============ Tauri command wrappers ============
@@ -73,8 +338,33 @@ pub async fn search_entities(
     query: String,
     entity_types: Option<Vec<String>>,
     limit: Option<u64>,
+    snippet: Option<SnippetOptions>,
+    group_by_type: Option<bool>,
+    type_limits: Option<TypeLimits>,
 ) -> Result<Vec<SearchResult>, AppError> {
-    search_entities_impl(&state.db, campaign_id, query, entity_types, limit).await
+    let grouping = group_by_type
+        .unwrap_or(false)
+        .then(|| type_limits.unwrap_or_default());
+    search_entities_impl(
+        &state.db,
+        campaign_id,
+        query,
+        entity_types,
+        limit,
+        snippet.unwrap_or_default(),
+        grouping,
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn search_in_location(
+    state: State<'_, AppState>,
+    location_id: String,
+    query: String,
+    limit: Option<u64>,
+) -> Result<Vec<SearchResult>, AppError> {
+    search_in_location_impl(&state.db, location_id, query, limit).await
 }
 
 /// Build FTS5 query string from user input
@@ -129,4 +419,45 @@ mod tests {
         let result = build_fts_query("dragon");
         assert_eq!(result, "dragon*");
     }
+
+    fn result(entity_type: &str, name: &str) -> SearchResult {
+        SearchResult {
+            entity_type: entity_type.to_string(),
+            entity_id: name.to_lowercase(),
+            name: name.to_string(),
+            snippet: None,
+            matched_field: Some("name".to_string()),
+            rank: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_group_by_type_orders_groups_by_best_hit() {
+        let results = vec![
+            result("location", "Dragon's Lair"),
+            result("character", "Dragon Knight"),
+            result("location", "Dragon Peak"),
+            result("location", "Dragonspire"),
+            result("character", "Dragon Slayer"),
+            result("quest", "Slay the Dragon"),
+        ];
+        let limits = TypeLimits {
+            per_type: 2,
+            overrides: HashMap::from([("quest".to_string(), 0)]),
+        };
+
+        let names: Vec<_> = group_by_type(results, &limits)
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "Dragon's Lair",
+                "Dragon Peak",
+                "Dragon Knight",
+                "Dragon Slayer"
+            ]
+        );
+    }
 }
@@ -0,0 +1,219 @@
+//! Recent searches per campaign, and the suggestions built from them for the
+//! search box. History stays on this device: it is never synced or exported.
+
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::search_history::{self, Entity as SearchHistory};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Queries remembered per campaign; older ones are dropped
+const MAX_HISTORY: usize = 50;
+
+/// A suggestion for the search box, from history or an entity's name
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchSuggestion {
+    /// `history` or `entity`
+    pub source: String,
+    pub text: String,
+    /// Set for entity suggestions
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+}
+
+/// Trim and collapse runs of whitespace, so "  old  mill " repeats "old mill"
+fn normalize_query(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+async fn ensure_campaign(db: &DatabaseConnection, campaign_id: &str) -> Result<(), AppError> {
+    Campaign::find_by_id(campaign_id)
+        .one(db)
+        .await?
+        .map(|_| ())
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))
+}
+
+/// A campaign's history, most recently used first
+async fn recent_queries(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+) -> Result<Vec<search_history::Model>, AppError> {
+    Ok(SearchHistory::find()
+        .filter(search_history::Column::CampaignId.eq(campaign_id))
+        .order_by_desc(search_history::Column::LastUsedAt)
+        .all(db)
+        .await?)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Remember a search. Repeating a query, in any case, moves it to the top
+/// of the history instead of adding it twice. Blank queries are ignored.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn record_search_query_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    query: String,
+) -> Result<(), AppError> {
+    ensure_campaign(db, &campaign_id).await?;
+    let query = normalize_query(&query);
+    if query.is_empty() {
+        return Ok(());
+    }
+
+    let history = recent_queries(db, &campaign_id).await?;
+    let now = chrono::Utc::now();
+    let key = query.to_lowercase();
+    match history.iter().find(|h| h.query.to_lowercase() == key) {
+        Some(existing) => {
+            let mut active: search_history::ActiveModel = existing.clone().into();
+            active.query = Set(query);
+            active.use_count = Set(existing.use_count + 1);
+            active.last_used_at = Set(now);
+            active.update(db).await?;
+        }
+        None => {
+            search_history::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                campaign_id: Set(campaign_id),
+                query: Set(query),
+                use_count: Set(1),
+                last_used_at: Set(now),
+                created_at: Set(now),
+            }
+            .insert(db)
+            .await?;
+
+            // The new row is the most recent, so the oldest past the cap go
+            let stale: Vec<String> = history
+                .into_iter()
+                .skip(MAX_HISTORY - 1)
+                .map(|h| h.id)
+                .collect();
+            if !stale.is_empty() {
+                SearchHistory::delete_many()
+                    .filter(search_history::Column::Id.is_in(stale))
+                    .exec(db)
+                    .await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Suggestions for a partly typed query: past searches starting with the
+/// prefix, most recent first, then entities whose name starts with it. An
+/// empty prefix gives just the recent searches.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_search_suggestions_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    prefix: String,
+    limit: Option<u64>,
+) -> Result<Vec<SearchSuggestion>, AppError> {
+    ensure_campaign(db, &campaign_id).await?;
+    let limit = limit.unwrap_or(10) as usize;
+    let prefix = normalize_query(&prefix);
+    let key = prefix.to_lowercase();
+
+    let mut suggestions: Vec<SearchSuggestion> = recent_queries(db, &campaign_id)
+        .await?
+        .into_iter()
+        .filter(|h| h.query.to_lowercase().starts_with(&key))
+        .take(limit)
+        .map(|h| SearchSuggestion {
+            source: "history".to_string(),
+            text: h.query,
+            entity_type: None,
+            entity_id: None,
+        })
+        .collect();
+
+    if prefix.is_empty() || suggestions.len() >= limit {
+        return Ok(suggestions);
+    }
+
+    // Comparing a cut of the name avoids escaping LIKE wildcards in the prefix
+    let backend = db.get_database_backend();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            backend,
+            r#"
+            SELECT entity_type, entity_id, name
+            FROM search_index
+            WHERE campaign_id = $1
+            AND lower(substr(name, 1, length($2))) = lower($2)
+            ORDER BY length(name), name
+            LIMIT $3
+            "#,
+            [campaign_id.into(), prefix.into(), (limit as i64).into()],
+        ))
+        .await?;
+
+    for row in rows {
+        if suggestions.len() >= limit {
+            break;
+        }
+        let name: String = row.try_get("", "name")?;
+        let name_key = name.to_lowercase();
+        if suggestions
+            .iter()
+            .any(|s| s.text.to_lowercase() == name_key)
+        {
+            continue;
+        }
+        suggestions.push(SearchSuggestion {
+            source: "entity".to_string(),
+            text: name,
+            entity_type: Some(row.try_get("", "entity_type")?),
+            entity_id: Some(row.try_get("", "entity_id")?),
+        });
+    }
+    Ok(suggestions)
+}
+
+/// Forget every remembered search in a campaign, returning how many
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn clear_search_history_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<u64, AppError> {
+    let result = SearchHistory::delete_many()
+        .filter(search_history::Column::CampaignId.eq(&campaign_id))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn record_search_query(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    query: String,
+) -> Result<(), AppError> {
+    record_search_query_impl(&state.db, campaign_id, query).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_search_suggestions(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    prefix: String,
+    limit: Option<u64>,
+) -> Result<Vec<SearchSuggestion>, AppError> {
+    get_search_suggestions_impl(&state.db, campaign_id, prefix, limit).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn clear_search_history(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<u64, AppError> {
+    clear_search_history_impl(&state.db, campaign_id).await
+}
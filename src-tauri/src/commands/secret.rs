@@ -1,9 +1,12 @@
+use crate::commands::references::remove_references;
 use crate::db::AppState;
 use crate::error::AppError;
 use ::entity::secrets::{self, Entity as Secret};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use tauri::State;
+use tracing::instrument;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SecretResponse {
@@ -16,12 +19,21 @@ pub struct SecretResponse {
     pub known_by: Option<String>,
     pub revealed: bool,
     pub revealed_in_session: Option<i32>,
+    /// Secrets that have to be revealed before this one makes sense
+    pub prerequisite_ids: Vec<String>,
+    /// Unrevealed, with every prerequisite already revealed
+    pub revealable: bool,
     pub created_at: String,
     pub updated_at: String,
 }
 
-impl From<secrets::Model> for SecretResponse {
-    fn from(model: secrets::Model) -> Self {
+impl SecretResponse {
+    /// `unrevealed` holds the ids of the campaign's unrevealed secrets.
+    /// Prerequisites that no longer exist don't block anything.
+    fn new(model: secrets::Model, unrevealed: &HashSet<String>) -> Self {
+        let prerequisite_ids = parse_prerequisites(model.prerequisites_json.as_deref());
+        let revealable =
+            !model.revealed && !prerequisite_ids.iter().any(|id| unrevealed.contains(id));
         Self {
             id: model.id,
             campaign_id: model.campaign_id,
@@ -32,15 +44,95 @@ impl From<secrets::Model> for SecretResponse {
             known_by: model.known_by,
             revealed: model.revealed,
             revealed_in_session: model.revealed_in_session,
+            prerequisite_ids,
+            revealable,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
     }
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub async fn create_secret(
-    state: State<'_, AppState>,
+fn parse_prerequisites(raw: Option<&str>) -> Vec<String> {
+    raw.and_then(|r| serde_json::from_str(r).ok())
+        .unwrap_or_default()
+}
+
+fn prerequisites_to_json(ids: &[String]) -> Result<Option<String>, AppError> {
+    if ids.is_empty() {
+        return Ok(None);
+    }
+    serde_json::to_string(ids)
+        .map(Some)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize prerequisites: {}", e)))
+}
+
+async fn find_secret(db: &DatabaseConnection, id: &str) -> Result<secrets::Model, AppError> {
+    Secret::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Secret {} not found", id)))
+}
+
+async fn campaign_secrets(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+) -> Result<Vec<secrets::Model>, AppError> {
+    Ok(Secret::find()
+        .filter(secrets::Column::CampaignId.eq(campaign_id))
+        .order_by_desc(secrets::Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+fn unrevealed_ids(secrets: &[secrets::Model]) -> HashSet<String> {
+    secrets
+        .iter()
+        .filter(|s| !s.revealed)
+        .map(|s| s.id.clone())
+        .collect()
+}
+
+/// Build the response for a single secret, checking its prerequisites
+async fn respond(
+    db: &DatabaseConnection,
+    model: secrets::Model,
+) -> Result<SecretResponse, AppError> {
+    let prerequisites = parse_prerequisites(model.prerequisites_json.as_deref());
+    let unrevealed = if prerequisites.is_empty() {
+        HashSet::new()
+    } else {
+        Secret::find()
+            .filter(secrets::Column::Id.is_in(prerequisites))
+            .filter(secrets::Column::Revealed.eq(false))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|s| s.id)
+            .collect()
+    };
+    Ok(SecretResponse::new(model, &unrevealed))
+}
+
+/// Whether `from` can reach `to` by following prerequisite links
+fn depends_on(graph: &HashMap<String, Vec<String>>, from: &str, to: &str) -> bool {
+    let mut seen = HashSet::new();
+    let mut pending = vec![from];
+    while let Some(id) = pending.pop() {
+        if id == to {
+            return true;
+        }
+        if seen.insert(id) {
+            pending.extend(graph.get(id).into_iter().flatten().map(String::as_str));
+        }
+    }
+    false
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn create_secret_impl(
+    db: &DatabaseConnection,
     campaign_id: String,
     title: String,
     content: String,
@@ -60,44 +152,46 @@ pub async fn create_secret(
         known_by: Set(None),
         revealed: Set(false),
         revealed_in_session: Set(None),
+        prerequisites_json: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
-    let result = model.insert(&state.db).await?;
-    Ok(result.into())
+    let result = model.insert(db).await?;
+    Ok(SecretResponse::new(result, &HashSet::new()))
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub async fn get_secret(
-    state: State<'_, AppState>,
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_secret_impl(
+    db: &DatabaseConnection,
     id: String,
 ) -> Result<SecretResponse, AppError> {
-    let secret = Secret::find_by_id(&id)
-        .one(&state.db)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Secret {} not found", id)))?;
-
-    Ok(secret.into())
+    let secret = find_secret(db, &id).await?;
+    respond(db, secret).await
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub async fn list_secrets(
-    state: State<'_, AppState>,
+/// All of a campaign's secrets, newest first, with `revealable` set on the
+/// ones whose prerequisites have all been revealed
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_secrets_impl(
+    db: &DatabaseConnection,
     campaign_id: String,
 ) -> Result<Vec<SecretResponse>, AppError> {
-    let secrets = Secret::find()
-        .filter(secrets::Column::CampaignId.eq(&campaign_id))
-        .order_by_desc(secrets::Column::CreatedAt)
-        .all(&state.db)
-        .await?;
+    let secrets = campaign_secrets(db, &campaign_id).await?;
+    let unrevealed = unrevealed_ids(&secrets);
 
-    Ok(secrets.into_iter().map(|s| s.into()).collect())
+    Ok(secrets
+        .into_iter()
+        .map(|s| SecretResponse::new(s, &unrevealed))
+        .collect())
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub async fn update_secret(
-    state: State<'_, AppState>,
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_secret_impl(
+    db: &DatabaseConnection,
     id: String,
     title: Option<String>,
     content: Option<String>,
@@ -107,10 +201,7 @@ pub async fn update_secret(
     revealed: Option<bool>,
     revealed_in_session: Option<i32>,
 ) -> Result<SecretResponse, AppError> {
-    let secret = Secret::find_by_id(&id)
-        .one(&state.db)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Secret {} not found", id)))?;
+    let secret = find_secret(db, &id).await?;
 
     let mut active: secrets::ActiveModel = secret.into();
 
@@ -137,12 +228,200 @@ pub async fn update_secret(
     }
     active.updated_at = Set(chrono::Utc::now());
 
-    let result = active.update(&state.db).await?;
-    Ok(result.into())
+    let result = active.update(db).await?;
+    respond(db, result).await
+}
+
+/// Delete a secret, drop it from the prerequisites of any others and remove
+/// its relationships and tags
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_secret_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
+    let Some(secret) = Secret::find_by_id(&id).one(db).await? else {
+        return Ok(false);
+    };
+
+    let txn = db.begin().await?;
+    let now = chrono::Utc::now();
+    for other in Secret::find()
+        .filter(secrets::Column::CampaignId.eq(&secret.campaign_id))
+        .filter(secrets::Column::PrerequisitesJson.contains(&id))
+        .all(&txn)
+        .await?
+    {
+        let mut prerequisites = parse_prerequisites(other.prerequisites_json.as_deref());
+        prerequisites.retain(|p| *p != id);
+        let mut active: secrets::ActiveModel = other.into();
+        active.prerequisites_json = Set(prerequisites_to_json(&prerequisites)?);
+        active.updated_at = Set(now);
+        active.update(&txn).await?;
+    }
+    remove_references(&txn, "secret", &id).await?;
+    let result = Secret::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
+
+    Ok(result.rows_affected > 0)
+}
+
+/// Require `prerequisite_id` to be revealed before `secret_id`. Links that
+/// would make a secret depend on itself, directly or through others, are
+/// rejected.
+#[instrument(skip_all, fields(secret_id = %secret_id, prerequisite_id = %prerequisite_id), err)]
+pub async fn add_secret_prerequisite_impl(
+    db: &DatabaseConnection,
+    secret_id: String,
+    prerequisite_id: String,
+) -> Result<SecretResponse, AppError> {
+    if secret_id == prerequisite_id {
+        return Err(AppError::Validation(
+            "prerequisite_id: a secret can't be its own prerequisite".to_string(),
+        ));
+    }
+    let secret = find_secret(db, &secret_id).await?;
+    let prerequisite = find_secret(db, &prerequisite_id).await?;
+    if prerequisite.campaign_id != secret.campaign_id {
+        return Err(AppError::Validation(
+            "prerequisite_id: secret belongs to a different campaign".to_string(),
+        ));
+    }
+
+    let mut prerequisites = parse_prerequisites(secret.prerequisites_json.as_deref());
+    if prerequisites.contains(&prerequisite_id) {
+        return respond(db, secret).await;
+    }
+
+    let graph: HashMap<String, Vec<String>> = campaign_secrets(db, &secret.campaign_id)
+        .await?
+        .into_iter()
+        .map(|s| {
+            let ids = parse_prerequisites(s.prerequisites_json.as_deref());
+            (s.id, ids)
+        })
+        .collect();
+    if depends_on(&graph, &prerequisite_id, &secret_id) {
+        return Err(AppError::Validation(format!(
+            "prerequisite_id: \"{}\" already depends on \"{}\"",
+            prerequisite.title, secret.title
+        )));
+    }
+
+    prerequisites.push(prerequisite_id);
+    let mut active: secrets::ActiveModel = secret.into();
+    active.prerequisites_json = Set(prerequisites_to_json(&prerequisites)?);
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    respond(db, result).await
+}
+
+#[instrument(skip_all, fields(secret_id = %secret_id, prerequisite_id = %prerequisite_id), err)]
+pub async fn remove_secret_prerequisite_impl(
+    db: &DatabaseConnection,
+    secret_id: String,
+    prerequisite_id: String,
+) -> Result<SecretResponse, AppError> {
+    let secret = find_secret(db, &secret_id).await?;
+
+    let mut prerequisites = parse_prerequisites(secret.prerequisites_json.as_deref());
+    let before = prerequisites.len();
+    prerequisites.retain(|p| *p != prerequisite_id);
+    if prerequisites.len() == before {
+        return Err(AppError::NotFound(format!(
+            "Secret {} is not a prerequisite of {}",
+            prerequisite_id, secret_id
+        )));
+    }
+
+    let mut active: secrets::ActiveModel = secret.into();
+    active.prerequisites_json = Set(prerequisites_to_json(&prerequisites)?);
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    respond(db, result).await
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_secret(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    title: String,
+    content: String,
+    related_entity_type: Option<String>,
+    related_entity_id: Option<String>,
+) -> Result<SecretResponse, AppError> {
+    create_secret_impl(
+        &state.db,
+        campaign_id,
+        title,
+        content,
+        related_entity_type,
+        related_entity_id,
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_secret(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<SecretResponse, AppError> {
+    get_secret_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_secrets(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<SecretResponse>, AppError> {
+    list_secrets_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_secret(
+    state: State<'_, AppState>,
+    id: String,
+    title: Option<String>,
+    content: Option<String>,
+    related_entity_type: Option<String>,
+    related_entity_id: Option<String>,
+    known_by: Option<String>,
+    revealed: Option<bool>,
+    revealed_in_session: Option<i32>,
+) -> Result<SecretResponse, AppError> {
+    update_secret_impl(
+        &state.db,
+        id,
+        title,
+        content,
+        related_entity_type,
+        related_entity_id,
+        known_by,
+        revealed,
+        revealed_in_session,
+    )
+    .await
 }
 
 #[tauri::command(rename_all = "snake_case")]
 pub async fn delete_secret(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
-    let result = Secret::delete_by_id(&id).exec(&state.db).await?;
-    Ok(result.rows_affected > 0)
+    delete_secret_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn add_secret_prerequisite(
+    state: State<'_, AppState>,
+    secret_id: String,
+    prerequisite_id: String,
+) -> Result<SecretResponse, AppError> {
+    add_secret_prerequisite_impl(&state.db, secret_id, prerequisite_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remove_secret_prerequisite(
+    state: State<'_, AppState>,
+    secret_id: String,
+    prerequisite_id: String,
+) -> Result<SecretResponse, AppError> {
+    remove_secret_prerequisite_impl(&state.db, secret_id, prerequisite_id).await
 }
@@ -1,9 +1,15 @@
+use crate::commands::campaign_settings::{
+    get_campaign_settings_impl, CampaignSettings, InWorldDate,
+};
+use crate::commands::references::remove_references;
 use crate::db::AppState;
 use crate::error::AppError;
 use ::entity::sessions::{self, Entity as Session};
+use ::entity::timeline_events::{self, Entity as TimelineEvent};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SessionResponse {
@@ -16,6 +22,10 @@ pub struct SessionResponse {
     pub notes: Option<String>,
     pub summary: Option<String>,
     pub highlights: Option<String>,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    /// Timeline event mirroring this session's summary
+    pub timeline_event_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -32,13 +42,193 @@ impl From<sessions::Model> for SessionResponse {
             notes: model.notes,
             summary: model.summary,
             highlights: model.highlights,
+            started_at: model.started_at.map(|t| t.to_string()),
+            ended_at: model.ended_at.map(|t| t.to_string()),
+            timeline_event_id: model.timeline_event_id,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
     }
 }
 
+fn session_event_title(session: &sessions::Model) -> String {
+    match &session.title {
+        Some(title) => format!("Session {}: {}", session.session_number, title),
+        None => format!("Session {}", session.session_number),
+    }
+}
+
+/// Create or refresh the timeline event mirroring a session and return its
+/// id. `date` moves the event; without one, a new event lands on the
+/// campaign's current in-world date, or after every other event when the
+/// calendar doesn't track one.
+async fn upsert_session_event<C: ConnectionTrait>(
+    conn: &C,
+    session: &sessions::Model,
+    settings: &CampaignSettings,
+    date: Option<InWorldDate>,
+    significance: Option<String>,
+) -> Result<String, AppError> {
+    let now = chrono::Utc::now();
+    let calendar = &settings.calendar;
+    let title = session_event_title(session);
+
+    let existing = match &session.timeline_event_id {
+        Some(event_id) => TimelineEvent::find_by_id(event_id).one(conn).await?,
+        None => None,
+    };
+
+    if let Some(event) = existing {
+        let mut active: timeline_events::ActiveModel = event.into();
+        active.title = Set(title);
+        active.description = Set(session.summary.clone());
+        if let Some(significance) = significance {
+            active.significance = Set(significance);
+        }
+        if let Some(date) = date {
+            active.date_display = Set(calendar.format_date(&date));
+            active.sort_order = Set(calendar.day_number(&date));
+        }
+        active.updated_at = Set(now);
+        return Ok(active.update(conn).await?.id);
+    }
+
+    let (date_display, sort_order) = match date.or(calendar.today) {
+        Some(date) => (calendar.format_date(&date), calendar.day_number(&date)),
+        None => {
+            let last = TimelineEvent::find()
+                .filter(timeline_events::Column::CampaignId.eq(&session.campaign_id))
+                .filter(timeline_events::Column::BranchId.is_null())
+                .order_by_desc(timeline_events::Column::SortOrder)
+                .one(conn)
+                .await?;
+            (
+                calendar
+                    .current_date
+                    .clone()
+                    .unwrap_or_else(|| title.clone()),
+                last.map_or(0, |e| e.sort_order + 1),
+            )
+        }
+    };
+
+    let significance = significance
+        .or_else(|| settings.timeline.session_significance.clone())
+        .unwrap_or_else(|| "local".to_string());
+
+    let event = timeline_events::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(session.campaign_id.clone()),
+        date_display: Set(date_display),
+        sort_order: Set(sort_order),
+        title: Set(title),
+        description: Set(session.summary.clone()),
+        significance: Set(significance),
+        is_public: Set(true),
+        branch_id: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+    Ok(event.insert(conn).await?.id)
+}
+
+/// Mirror a freshly saved summary onto the timeline. Sessions already on the
+/// timeline keep their event up to date; others get one when
+/// `log_to_timeline` (or the campaign's `timeline.log_sessions`) asks for it.
+async fn mirror_summary(
+    db: &DatabaseConnection,
+    session: sessions::Model,
+    log_to_timeline: Option<bool>,
+) -> Result<sessions::Model, AppError> {
+    let settings = get_campaign_settings_impl(db, session.campaign_id.clone()).await?;
+    let log = log_to_timeline
+        .unwrap_or(settings.timeline.log_sessions || session.timeline_event_id.is_some());
+    if !log {
+        return Ok(session);
+    }
+
+    let txn = db.begin().await?;
+    let event_id = upsert_session_event(&txn, &session, &settings, None, None).await?;
+    let session = if session.timeline_event_id.as_deref() == Some(event_id.as_str()) {
+        session
+    } else {
+        let mut active: sessions::ActiveModel = session.into();
+        active.timeline_event_id = Set(Some(event_id));
+        active.update(&txn).await?
+    };
+    txn.commit().await?;
+
+    Ok(session)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Save a session summary, logging it to the timeline as configured
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn save_session_summary_impl(
+    db: &DatabaseConnection,
+    id: String,
+    summary: String,
+    log_to_timeline: Option<bool>,
+) -> Result<SessionResponse, AppError> {
+    let session = Session::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} not found", id)))?;
+
+    let mut active: sessions::ActiveModel = session.into();
+    active.summary = Set(Some(summary));
+    active.updated_at = Set(chrono::Utc::now());
+    let session = active.update(db).await?;
+
+    Ok(mirror_summary(db, session, log_to_timeline).await?.into())
+}
+
+/// Put a session on the timeline now, or move and re-rate its existing
+/// event. `in_world_date` defaults to the campaign's current date.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn log_session_to_timeline_impl(
+    db: &DatabaseConnection,
+    id: String,
+    in_world_date: Option<InWorldDate>,
+    significance: Option<String>,
+) -> Result<SessionResponse, AppError> {
+    if let Some(s) = &significance {
+        if !(1..=50).contains(&s.trim().len()) {
+            return Err(AppError::Validation(
+                "significance: must be 1-50 characters".to_string(),
+            ));
+        }
+    }
+
+    let session = Session::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} not found", id)))?;
+    let settings = get_campaign_settings_impl(db, session.campaign_id.clone()).await?;
+    if let Some(date) = &in_world_date {
+        if let Some(error) = settings.calendar.check_date(date, "in_world_date") {
+            return Err(AppError::InvalidFields(vec![error]));
+        }
+    }
+
+    let txn = db.begin().await?;
+    let event_id =
+        upsert_session_event(&txn, &session, &settings, in_world_date, significance).await?;
+    let mut active: sessions::ActiveModel = session.into();
+    active.timeline_event_id = Set(Some(event_id));
+    let result = active.update(&txn).await?;
+    txn.commit().await?;
+
+    Ok(result.into())
+}
+
+// ============ Tauri command wrappers ============
+
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn create_session(
     state: State<'_, AppState>,
     campaign_id: String,
@@ -61,8 +251,14 @@ pub async fn create_session(
         notes: Set(None),
         summary: Set(None),
         highlights: Set(None),
+        started_at: Set(None),
+        ended_at: Set(None),
+        breaks_json: Set(None),
+        timeline_event_id: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     let result = model.insert(&state.db).await?;
@@ -70,6 +266,7 @@ pub async fn create_session(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_session(
     state: State<'_, AppState>,
     id: String,
@@ -83,6 +280,7 @@ pub async fn get_session(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn list_sessions(
     state: State<'_, AppState>,
     campaign_id: String,
@@ -97,6 +295,7 @@ pub async fn list_sessions(
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn update_session(
     state: State<'_, AppState>,
     id: String,
@@ -107,6 +306,7 @@ pub async fn update_session(
     notes: Option<String>,
     summary: Option<String>,
     highlights: Option<String>,
+    log_to_timeline: Option<bool>,
 ) -> Result<SessionResponse, AppError> {
     let session = Session::find_by_id(&id)
         .one(&state.db)
@@ -131,6 +331,7 @@ pub async fn update_session(
     if let Some(n) = notes {
         active.notes = Set(Some(n));
     }
+    let summary_saved = summary.is_some();
     if let Some(s) = summary {
         active.summary = Set(Some(s));
     }
@@ -140,11 +341,40 @@ pub async fn update_session(
     active.updated_at = Set(chrono::Utc::now());
 
     let result = active.update(&state.db).await?;
+    if summary_saved {
+        return Ok(mirror_summary(&state.db, result, log_to_timeline)
+            .await?
+            .into());
+    }
     Ok(result.into())
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_session(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
-    let result = Session::delete_by_id(&id).exec(&state.db).await?;
+    let txn = state.db.begin().await?;
+    remove_references(&txn, "session", &id).await?;
+    let result = Session::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
     Ok(result.rows_affected > 0)
 }
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn save_session_summary(
+    state: State<'_, AppState>,
+    id: String,
+    summary: String,
+    log_to_timeline: Option<bool>,
+) -> Result<SessionResponse, AppError> {
+    save_session_summary_impl(&state.db, id, summary, log_to_timeline).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn log_session_to_timeline(
+    state: State<'_, AppState>,
+    id: String,
+    in_world_date: Option<InWorldDate>,
+    significance: Option<String>,
+) -> Result<SessionResponse, AppError> {
+    log_session_to_timeline_impl(&state.db, id, in_world_date, significance).await
+}
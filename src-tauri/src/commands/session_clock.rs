@@ -0,0 +1,312 @@
+//! Real-time play clock for sessions: when play started and stopped, the
+//! breaks in between, and campaign-wide play time totals.
+
+use crate::commands::json_schema::SessionBreak;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::sessions::{self, Entity as Session};
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockState {
+    NotStarted,
+    Running,
+    Paused,
+    Stopped,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionClockResponse {
+    pub session_id: String,
+    pub session_number: i32,
+    pub title: Option<String>,
+    pub state: ClockState,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    pub breaks: Vec<SessionBreak>,
+    /// Time at the table, excluding breaks. Counts up to now while running.
+    pub played_minutes: i64,
+    pub break_minutes: i64,
+}
+
+/// Play time across every session whose clock has been stopped
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayTimeReport {
+    pub campaign_id: String,
+    pub clocked_sessions: usize,
+    pub total_minutes: i64,
+    pub total_hours: f64,
+    pub average_minutes: Option<i64>,
+    pub longest_minutes: Option<i64>,
+    pub shortest_minutes: Option<i64>,
+    /// Stopped sessions in session order
+    pub sessions: Vec<SessionClockResponse>,
+}
+
+struct Clock {
+    session: sessions::Model,
+    breaks: Vec<SessionBreak>,
+}
+
+impl Clock {
+    fn load(session: sessions::Model) -> Result<Self, AppError> {
+        let breaks = match session.breaks_json.as_deref() {
+            Some(raw) => SessionBreak::parse_list(raw)?,
+            None => Vec::new(),
+        };
+        Ok(Self { session, breaks })
+    }
+
+    fn state(&self) -> ClockState {
+        match (self.session.started_at, self.session.ended_at) {
+            (None, _) => ClockState::NotStarted,
+            (Some(_), Some(_)) => ClockState::Stopped,
+            (Some(_), None) if self.open_break().is_some() => ClockState::Paused,
+            (Some(_), None) => ClockState::Running,
+        }
+    }
+
+    fn open_break(&self) -> Option<&SessionBreak> {
+        self.breaks.iter().find(|b| b.ended_at.is_none())
+    }
+
+    fn close_open_break(&mut self, now: DateTime<Utc>) {
+        for b in self.breaks.iter_mut().filter(|b| b.ended_at.is_none()) {
+            b.ended_at = Some(now);
+        }
+    }
+
+    /// Played and break seconds, measuring anything still open up to `now`
+    fn durations(&self, now: DateTime<Utc>) -> (i64, i64) {
+        let Some(started_at) = self.session.started_at else {
+            return (0, 0);
+        };
+        let end = self.session.ended_at.unwrap_or(now);
+        let total = (end - started_at).num_seconds().max(0);
+        let breaks: i64 = self
+            .breaks
+            .iter()
+            .map(|b| {
+                (b.ended_at.unwrap_or(end).min(end) - b.started_at)
+                    .num_seconds()
+                    .max(0)
+            })
+            .sum();
+        let breaks = breaks.min(total);
+        (total - breaks, breaks)
+    }
+
+    fn response(self, now: DateTime<Utc>) -> SessionClockResponse {
+        let state = self.state();
+        let (played, breaks) = self.durations(now);
+        SessionClockResponse {
+            session_id: self.session.id,
+            session_number: self.session.session_number,
+            title: self.session.title,
+            state,
+            started_at: self.session.started_at.map(|t| t.to_string()),
+            ended_at: self.session.ended_at.map(|t| t.to_string()),
+            breaks: self.breaks,
+            played_minutes: played / 60,
+            break_minutes: breaks / 60,
+        }
+    }
+
+    /// Write the clock columns back to the session
+    async fn save(self, db: &DatabaseConnection, now: DateTime<Utc>) -> Result<Self, AppError> {
+        let breaks_json = if self.breaks.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&self.breaks).map_err(|e| {
+                AppError::Internal(format!("Failed to serialize session breaks: {}", e))
+            })?)
+        };
+
+        let started_at = self.session.started_at;
+        let ended_at = self.session.ended_at;
+        let mut active: sessions::ActiveModel = self.session.into();
+        active.started_at = Set(started_at);
+        active.ended_at = Set(ended_at);
+        active.breaks_json = Set(breaks_json);
+        active.updated_at = Set(now);
+        let session = active.update(db).await?;
+        Ok(Self {
+            session,
+            breaks: self.breaks,
+        })
+    }
+}
+
+async fn load_clock(db: &DatabaseConnection, session_id: &str) -> Result<Clock, AppError> {
+    let session = Session::find_by_id(session_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} not found", session_id)))?;
+    Clock::load(session)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Start the clock, or resume it after a break
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn start_session_clock_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+) -> Result<SessionClockResponse, AppError> {
+    let mut clock = load_clock(db, &session_id).await?;
+    let now = Utc::now();
+
+    match clock.state() {
+        ClockState::Running => return Ok(clock.response(now)),
+        ClockState::Stopped => {
+            return Err(AppError::Conflict(format!(
+                "Session {} has already been stopped",
+                clock.session.session_number
+            )))
+        }
+        ClockState::NotStarted => clock.session.started_at = Some(now),
+        ClockState::Paused => clock.close_open_break(now),
+    }
+
+    Ok(clock.save(db, now).await?.response(now))
+}
+
+/// Begin a break. Play resumes with `start_session_clock`.
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn pause_session_clock_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+) -> Result<SessionClockResponse, AppError> {
+    let mut clock = load_clock(db, &session_id).await?;
+    let now = Utc::now();
+
+    match clock.state() {
+        ClockState::Paused => return Ok(clock.response(now)),
+        ClockState::Running => clock.breaks.push(SessionBreak::new(now)),
+        ClockState::NotStarted | ClockState::Stopped => {
+            return Err(AppError::Conflict(format!(
+                "Session {} is not running",
+                clock.session.session_number
+            )))
+        }
+    }
+
+    Ok(clock.save(db, now).await?.response(now))
+}
+
+/// Stop the clock, ending any break in progress
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn stop_session_clock_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+) -> Result<SessionClockResponse, AppError> {
+    let mut clock = load_clock(db, &session_id).await?;
+    let now = Utc::now();
+
+    match clock.state() {
+        ClockState::Stopped => return Ok(clock.response(now)),
+        ClockState::NotStarted => {
+            return Err(AppError::Conflict(format!(
+                "Session {} has not been started",
+                clock.session.session_number
+            )))
+        }
+        ClockState::Running | ClockState::Paused => {
+            clock.close_open_break(now);
+            clock.session.ended_at = Some(now);
+        }
+    }
+
+    Ok(clock.save(db, now).await?.response(now))
+}
+
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn get_session_clock_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+) -> Result<SessionClockResponse, AppError> {
+    Ok(load_clock(db, &session_id).await?.response(Utc::now()))
+}
+
+/// Totals over every stopped session in the campaign. Sessions still
+/// running or never clocked are left out so they don't skew the averages.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_play_time_report_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<PlayTimeReport, AppError> {
+    let sessions = Session::find()
+        .filter(sessions::Column::CampaignId.eq(&campaign_id))
+        .filter(sessions::Column::StartedAt.is_not_null())
+        .filter(sessions::Column::EndedAt.is_not_null())
+        .order_by_asc(sessions::Column::SessionNumber)
+        .all(db)
+        .await?;
+
+    let now = Utc::now();
+    let clocked = sessions
+        .into_iter()
+        .map(|s| Clock::load(s).map(|c| c.response(now)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let minutes: Vec<i64> = clocked.iter().map(|s| s.played_minutes).collect();
+    let total_minutes: i64 = minutes.iter().sum();
+
+    Ok(PlayTimeReport {
+        campaign_id,
+        clocked_sessions: clocked.len(),
+        total_minutes,
+        total_hours: (total_minutes as f64 / 60.0 * 10.0).round() / 10.0,
+        average_minutes: (!minutes.is_empty()).then(|| total_minutes / minutes.len() as i64),
+        longest_minutes: minutes.iter().copied().max(),
+        shortest_minutes: minutes.iter().copied().min(),
+        sessions: clocked,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn start_session_clock(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<SessionClockResponse, AppError> {
+    start_session_clock_impl(&state.db, session_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn pause_session_clock(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<SessionClockResponse, AppError> {
+    pause_session_clock_impl(&state.db, session_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn stop_session_clock(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<SessionClockResponse, AppError> {
+    stop_session_clock_impl(&state.db, session_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_session_clock(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<SessionClockResponse, AppError> {
+    get_session_clock_impl(&state.db, session_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_play_time_report(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<PlayTimeReport, AppError> {
+    get_play_time_report_impl(&state.db, campaign_id).await
+}
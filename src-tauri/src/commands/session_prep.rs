@@ -0,0 +1,359 @@
+//! Prep checklists for upcoming sessions. Locations and other entities
+//! mentioned in a session's planned content decide what's relevant; the
+//! checklist is appended to the plan so it can be ticked off in the editor.
+
+use crate::commands::session::SessionResponse;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::locations::{self, Entity as Location};
+use ::entity::quests::{self, Entity as Quest};
+use ::entity::secrets::{self, Entity as Secret};
+use ::entity::sessions::{self, Entity as Session};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use tauri::State;
+use tracing::instrument;
+
+/// Heading that starts the generated checklist; everything from it to the
+/// end of the plan is replaced on regeneration
+pub const PREP_HEADING: &str = "Prep checklist";
+
+/// Quest statuses still in play
+const OPEN_QUEST_STATUSES: &[&str] = &["available", "active"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrepLocation {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrepQuest {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrepSecret {
+    pub id: String,
+    pub title: String,
+    pub related_entity_type: Option<String>,
+    pub related_entity_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionPrep {
+    /// Locations mentioned in the plan, plus everything inside them
+    pub expected_locations: Vec<PrepLocation>,
+    pub open_quests: Vec<PrepQuest>,
+    /// Unrevealed secrets tied to an expected location or to anything else
+    /// the plan mentions
+    pub secrets: Vec<PrepSecret>,
+    pub session: SessionResponse,
+}
+
+/// `[[type:id:label]]` references in plain text
+pub(crate) fn collect_text_mentions(text: &str, found: &mut BTreeSet<(String, String)>) {
+    for chunk in text.split("[[").skip(1) {
+        let Some((inner, _)) = chunk.split_once("]]") else {
+            continue;
+        };
+        let mut parts = inner.splitn(3, ':');
+        if let (Some(entity_type), Some(id)) = (parts.next(), parts.next()) {
+            found.insert((entity_type.to_string(), id.to_string()));
+        }
+    }
+}
+
+/// Entity references in editor JSON: citation and mention nodes, plus any
+/// markdown references typed into text nodes
+pub(crate) fn collect_mentions(node: &Value, found: &mut BTreeSet<(String, String)>) {
+    let attrs = &node["attrs"];
+    if let (Some(entity_type), Some(id)) =
+        (attrs["entityType"].as_str(), attrs["entityId"].as_str())
+    {
+        found.insert((entity_type.to_string(), id.to_string()));
+    }
+    if let Some(text) = node["text"].as_str() {
+        collect_text_mentions(text, found);
+    }
+    if let Some(children) = node.get("content").and_then(Value::as_array) {
+        for child in children {
+            collect_mentions(child, found);
+        }
+    }
+}
+
+/// Top-level nodes of a session's planned content, including any generated
+/// checklist. Plain text becomes one paragraph per line.
+pub(crate) fn document_nodes(content: Option<&str>) -> Vec<Value> {
+    let Some(content) = content.filter(|c| !c.trim().is_empty()) else {
+        return Vec::new();
+    };
+
+    match serde_json::from_str::<Value>(content) {
+        Ok(doc) if doc.is_object() => doc["content"].as_array().cloned().unwrap_or_default(),
+        _ => content
+            .lines()
+            .map(|line| match line.trim() {
+                "" => json!({ "type": "paragraph" }),
+                _ => json!({ "type": "paragraph", "content": [text(line)] }),
+            })
+            .collect(),
+    }
+}
+
+/// Whether `node` is the heading that starts a generated checklist
+pub(crate) fn is_prep_heading(node: &Value) -> bool {
+    node["type"] == "heading" && node["content"][0]["text"].as_str() == Some(PREP_HEADING)
+}
+
+/// Top-level nodes of the existing plan, without a previously generated
+/// checklist
+pub(crate) fn plan_nodes(content: Option<&str>) -> Vec<Value> {
+    let nodes = document_nodes(content);
+    match nodes.iter().position(is_prep_heading) {
+        Some(pos) => nodes[..pos].to_vec(),
+        None => nodes,
+    }
+}
+
+fn text(value: &str) -> Value {
+    json!({ "type": "text", "text": value })
+}
+
+fn citation(entity_type: &str, id: &str, label: &str) -> Value {
+    json!({
+        "type": "citation",
+        "attrs": { "entityType": entity_type, "entityId": id, "label": label }
+    })
+}
+
+fn heading(level: u8, value: &str) -> Value {
+    json!({ "type": "heading", "attrs": { "level": level }, "content": [text(value)] })
+}
+
+/// A section of unchecked `[ ]` items, the same checklist form quest
+/// objectives use
+fn checklist_section(title: &str, items: Vec<Vec<Value>>) -> Vec<Value> {
+    if items.is_empty() {
+        return vec![
+            heading(3, title),
+            json!({ "type": "paragraph", "content": [text("Nothing to prep.")] }),
+        ];
+    }
+    let items: Vec<Value> = items
+        .into_iter()
+        .map(|mut inline| {
+            inline.insert(0, text("[ ] "));
+            json!({
+                "type": "listItem",
+                "content": [{ "type": "paragraph", "content": inline }]
+            })
+        })
+        .collect();
+    vec![
+        heading(3, title),
+        json!({ "type": "bulletList", "content": items }),
+    ]
+}
+
+fn prep_document(
+    plan: Vec<Value>,
+    locations: &[PrepLocation],
+    quests: &[PrepQuest],
+    secrets: &[PrepSecret],
+) -> Value {
+    let mut content = plan;
+    content.push(heading(2, PREP_HEADING));
+    content.extend(checklist_section(
+        "Locations",
+        locations
+            .iter()
+            .map(|l| vec![text("Review "), citation("location", &l.id, &l.name)])
+            .collect(),
+    ));
+    content.extend(checklist_section(
+        "Open quests",
+        quests
+            .iter()
+            .map(|q| {
+                vec![
+                    citation("quest", &q.id, &q.name),
+                    text(&format!(" ({})", q.status)),
+                ]
+            })
+            .collect(),
+    ));
+    content.extend(checklist_section(
+        "Secrets that could come up",
+        secrets
+            .iter()
+            .map(|s| vec![citation("secret", &s.id, &s.title)])
+            .collect(),
+    ));
+    json!({ "type": "doc", "content": content })
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Assemble the prep for a session and save it into the session's planned
+/// content, replacing any checklist generated earlier
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn generate_session_prep_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+) -> Result<SessionPrep, AppError> {
+    let session = Session::find_by_id(&session_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} not found", session_id)))?;
+    let campaign_id = session.campaign_id.clone();
+
+    // Mentions inside an old checklist don't feed the new one
+    let plan = plan_nodes(session.planned_content.as_deref());
+    let mut mentioned = BTreeSet::new();
+    for node in &plan {
+        collect_mentions(node, &mut mentioned);
+    }
+
+    // Mentioned locations and everything nested under them
+    let all_locations = Location::find()
+        .filter(locations::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(locations::Column::Name)
+        .all(db)
+        .await?;
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for l in &all_locations {
+        if let Some(parent) = &l.parent_id {
+            children.entry(parent.as_str()).or_default().push(&l.id);
+        }
+    }
+    let mut expected: BTreeSet<&str> = BTreeSet::new();
+    let mut pending: Vec<&str> = mentioned
+        .iter()
+        .filter(|(t, _)| t == "location")
+        .map(|(_, id)| id.as_str())
+        .collect();
+    while let Some(id) = pending.pop() {
+        if expected.insert(id) {
+            pending.extend(children.get(id).into_iter().flatten());
+        }
+    }
+    let expected_locations: Vec<PrepLocation> = all_locations
+        .iter()
+        .filter(|l| expected.contains(l.id.as_str()))
+        .map(|l| PrepLocation {
+            id: l.id.clone(),
+            name: l.name.clone(),
+        })
+        .collect();
+
+    let open_quests: Vec<PrepQuest> = Quest::find()
+        .filter(quests::Column::CampaignId.eq(&campaign_id))
+        .filter(quests::Column::Status.is_in(OPEN_QUEST_STATUSES.iter().copied()))
+        .order_by_asc(quests::Column::Status)
+        .order_by_asc(quests::Column::Name)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|q| PrepQuest {
+            id: q.id,
+            name: q.name,
+            status: q.status,
+        })
+        .collect();
+
+    let secrets: Vec<PrepSecret> = Secret::find()
+        .filter(secrets::Column::CampaignId.eq(&campaign_id))
+        .filter(secrets::Column::Revealed.eq(false))
+        .order_by_asc(secrets::Column::Title)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|s| match (&s.related_entity_type, &s.related_entity_id) {
+            (Some(t), Some(id)) => {
+                (t == "location" && expected.contains(id.as_str()))
+                    || mentioned.contains(&(t.clone(), id.clone()))
+            }
+            _ => false,
+        })
+        .map(|s| PrepSecret {
+            id: s.id,
+            title: s.title,
+            related_entity_type: s.related_entity_type,
+            related_entity_id: s.related_entity_id,
+        })
+        .collect();
+
+    let document = prep_document(plan, &expected_locations, &open_quests, &secrets);
+    let mut active: sessions::ActiveModel = session.into();
+    active.planned_content = Set(Some(document.to_string()));
+    active.updated_at = Set(chrono::Utc::now());
+    let session = active.update(db).await?;
+
+    Ok(SessionPrep {
+        expected_locations,
+        open_quests,
+        secrets,
+        session: session.into(),
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn generate_session_prep(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<SessionPrep, AppError> {
+    generate_session_prep_impl(&state.db, session_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_mentions_reads_nodes_and_markdown() {
+        let doc = json!({
+            "type": "doc",
+            "content": [{
+                "type": "paragraph",
+                "content": [
+                    text("Meet [[character:c2:Mira]] at "),
+                    citation("location", "l1", "Duskhollow"),
+                    { "type": "mention", "attrs": { "entityType": "character", "entityId": "c1" } }
+                ]
+            }]
+        });
+        let mut found = BTreeSet::new();
+        collect_mentions(&doc, &mut found);
+        assert_eq!(found.len(), 3);
+        assert!(found.contains(&("location".to_string(), "l1".to_string())));
+        assert!(found.contains(&("character".to_string(), "c1".to_string())));
+        assert!(found.contains(&("character".to_string(), "c2".to_string())));
+    }
+
+    #[test]
+    fn test_plan_nodes_drops_previous_checklist() {
+        let doc = json!({
+            "type": "doc",
+            "content": [
+                { "type": "paragraph", "content": [text("Open on the docks")] },
+                heading(2, PREP_HEADING),
+                heading(3, "Locations")
+            ]
+        });
+        let nodes = plan_nodes(Some(&doc.to_string()));
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0]["content"][0]["text"], "Open on the docks");
+
+        let nodes = plan_nodes(Some("Line one\n\nLine two"));
+        assert_eq!(nodes.len(), 3);
+        assert!(plan_nodes(None).is_empty());
+    }
+}
@@ -0,0 +1,515 @@
+//! Session transcripts: plain text or WebVTT files attached to a session,
+//! split into chunks small enough for the AI summarizer and scanned for
+//! names of known entities. Summaries themselves are written by the
+//! frontend AI layer into the session's summary and highlights.
+
+use crate::commands::character::parse_string_list;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::text::words;
+use ::entity::characters::{self, Entity as Character};
+use ::entity::heroes::{self, Entity as Hero};
+use ::entity::items::{self, Entity as Item};
+use ::entity::locations::{self, Entity as Location};
+use ::entity::organizations::{self, Entity as Organization};
+use ::entity::quests::{self, Entity as Quest};
+use ::entity::session_transcripts::{self, Entity as SessionTranscript};
+use ::entity::sessions::Entity as Session;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::State;
+use tracing::instrument;
+
+/// Chunk size used when none is given, roughly 2k tokens
+pub const DEFAULT_CHUNK_CHARS: usize = 8000;
+/// Smallest chunk size accepted
+const MIN_CHUNK_CHARS: usize = 500;
+/// Names shorter than this are too ambiguous to look for
+const MIN_MENTION_NAME_CHARS: usize = 3;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub session_id: String,
+    pub file_name: String,
+    /// "text" or "vtt"
+    pub format: String,
+    /// Normalized text; VTT cues become "[hh:mm:ss] Speaker: line"
+    pub content: String,
+    pub word_count: usize,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<session_transcripts::Model> for TranscriptResponse {
+    fn from(model: session_transcripts::Model) -> Self {
+        Self {
+            word_count: model.content.split_whitespace().count(),
+            id: model.id,
+            campaign_id: model.campaign_id,
+            session_id: model.session_id,
+            file_name: model.file_name,
+            format: model.format,
+            content: model.content,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptChunk {
+    pub index: usize,
+    /// Timestamp of the first timed line, for VTT transcripts
+    pub start: Option<String>,
+    /// Timestamp of the last timed line
+    pub end: Option<String>,
+    pub text: String,
+}
+
+/// A known entity named in a transcript
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptMention {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: String,
+    pub occurrences: usize,
+}
+
+/// "hh:mm:ss" from a VTT timestamp like "00:01:02.500" or "01:02.500"
+fn short_timestamp(timestamp: &str) -> String {
+    let whole = timestamp.split('.').next().unwrap_or(timestamp);
+    if whole.matches(':').count() == 1 {
+        format!("00:{}", whole)
+    } else {
+        whole.to_string()
+    }
+}
+
+/// Text of a cue line without markup. A `<v Speaker>` voice tag becomes a
+/// "Speaker: " prefix.
+fn cue_text(line: &str) -> String {
+    let mut speaker = None;
+    let mut text = String::new();
+    let mut rest = line;
+    while let Some(open) = rest.find('<') {
+        text.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('>') else {
+            rest = &rest[open..];
+            break;
+        };
+        let tag = &rest[open + 1..open + close];
+        // "<v.loud Mira>" carries classes before the name
+        let name = tag
+            .strip_prefix("v ")
+            .or_else(|| tag.strip_prefix("v.")?.split_once(' ').map(|(_, n)| n));
+        if let Some(name) = name.map(str::trim).filter(|n| !n.is_empty()) {
+            speaker.get_or_insert_with(|| name.to_string());
+        }
+        rest = &rest[open + close + 1..];
+    }
+    text.push_str(rest);
+    let text = text
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ");
+    let text = text.trim();
+    match speaker {
+        Some(speaker) => format!("{}: {}", speaker, text),
+        None => text.to_string(),
+    }
+}
+
+/// Flatten a WebVTT file into one line per cue
+fn parse_vtt(raw: &str) -> String {
+    let mut lines = Vec::new();
+    for block in raw.replace("\r\n", "\n").split("\n\n") {
+        let mut block_lines = block.lines().map(str::trim).filter(|l| !l.is_empty());
+        let Some(mut first) = block_lines.next() else {
+            continue;
+        };
+        if first.starts_with("WEBVTT")
+            || first.starts_with("NOTE")
+            || first == "STYLE"
+            || first == "REGION"
+        {
+            continue;
+        }
+        // Cue identifiers sit above the timing line
+        if !first.contains("-->") {
+            let Some(timing) = block_lines.next() else {
+                continue;
+            };
+            first = timing;
+        }
+        let Some((start, _)) = first.split_once("-->") else {
+            continue;
+        };
+
+        let text: Vec<String> = block_lines
+            .map(cue_text)
+            .filter(|l| !l.is_empty())
+            .collect();
+        if !text.is_empty() {
+            lines.push(format!(
+                "[{}] {}",
+                short_timestamp(start.trim()),
+                text.join(" ")
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Timestamp at the start of a normalized VTT line
+fn line_timestamp(line: &str) -> Option<&str> {
+    line.strip_prefix('[')?.split_once(']').map(|(t, _)| t)
+}
+
+/// Split a transcript into chunks of at most `max_chars`, breaking between
+/// lines where possible and between words otherwise
+fn chunk_transcript(content: &str, max_chars: usize) -> Vec<TranscriptChunk> {
+    let mut pieces: Vec<String> = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        if line.chars().count() <= max_chars {
+            pieces.push(line.to_string());
+            continue;
+        }
+        let mut piece = String::new();
+        for word in line.split_whitespace() {
+            if !piece.is_empty() && piece.chars().count() + 1 + word.chars().count() > max_chars {
+                pieces.push(std::mem::take(&mut piece));
+            }
+            if !piece.is_empty() {
+                piece.push(' ');
+            }
+            piece.push_str(word);
+        }
+        if !piece.is_empty() {
+            pieces.push(piece);
+        }
+    }
+
+    fn flush(current: &mut Vec<String>, chunks: &mut Vec<TranscriptChunk>) {
+        if current.is_empty() {
+            return;
+        }
+        let start = current.iter().find_map(|l| line_timestamp(l));
+        let end = current.iter().rev().find_map(|l| line_timestamp(l));
+        chunks.push(TranscriptChunk {
+            index: chunks.len(),
+            start: start.map(str::to_string),
+            end: end.map(str::to_string),
+            text: current.join("\n"),
+        });
+        current.clear();
+    }
+
+    let mut chunks: Vec<TranscriptChunk> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut length = 0;
+    for piece in pieces {
+        let piece_length = piece.chars().count();
+        if !current.is_empty() && length + 1 + piece_length > max_chars {
+            flush(&mut current, &mut chunks);
+            length = 0;
+        }
+        length += piece_length + usize::from(!current.is_empty());
+        current.push(piece);
+    }
+    flush(&mut current, &mut chunks);
+    chunks
+}
+
+/// Times `name` appears in `text` as whole words
+fn count_name(text: &[String], name: &str) -> usize {
+    let name = words(name);
+    if name.is_empty() || name.len() > text.len() {
+        return 0;
+    }
+    text.windows(name.len())
+        .filter(|w| *w == name.as_slice())
+        .count()
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Read a .txt or .vtt transcript from disk and attach it to a session
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn import_session_transcript_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+    path: String,
+) -> Result<TranscriptResponse, AppError> {
+    let session = Session::find_by_id(&session_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} not found", session_id)))?;
+
+    let file = Path::new(&path);
+    let extension = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    if !matches!(extension.as_str(), "txt" | "text" | "md" | "vtt") {
+        return Err(AppError::Validation(
+            "path: transcripts must be .txt, .md or .vtt files".to_string(),
+        ));
+    }
+    let raw = std::fs::read_to_string(file)
+        .map_err(|e| AppError::Internal(format!("Failed to read {}: {}", path, e)))?;
+    let raw = raw.trim_start_matches('\u{feff}');
+
+    let (format, content) = if extension == "vtt" || raw.starts_with("WEBVTT") {
+        ("vtt", parse_vtt(raw))
+    } else {
+        ("text", raw.replace("\r\n", "\n").trim().to_string())
+    };
+    if content.trim().is_empty() {
+        return Err(AppError::Validation(
+            "path: transcript has no text".to_string(),
+        ));
+    }
+
+    let now = chrono::Utc::now();
+    let model = session_transcripts::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(session.campaign_id),
+        session_id: Set(session_id),
+        file_name: Set(file
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.clone())),
+        format: Set(format.to_string()),
+        content: Set(content),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+    let transcript = model.insert(db).await?;
+    tracing::info!(transcript_id = %transcript.id, format, "Imported session transcript");
+    Ok(transcript.into())
+}
+
+#[instrument(skip_all, fields(session_id = %session_id), err)]
+pub async fn list_session_transcripts_impl(
+    db: &DatabaseConnection,
+    session_id: String,
+) -> Result<Vec<TranscriptResponse>, AppError> {
+    let transcripts = SessionTranscript::find()
+        .filter(session_transcripts::Column::SessionId.eq(&session_id))
+        .order_by_asc(session_transcripts::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(transcripts.into_iter().map(|t| t.into()).collect())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_session_transcript_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let result = SessionTranscript::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+async fn find_transcript(
+    db: &DatabaseConnection,
+    id: &str,
+) -> Result<session_transcripts::Model, AppError> {
+    SessionTranscript::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Transcript {} not found", id)))
+}
+
+/// The transcript split into pieces for summarizing one at a time
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_transcript_chunks_impl(
+    db: &DatabaseConnection,
+    id: String,
+    max_chars: Option<usize>,
+) -> Result<Vec<TranscriptChunk>, AppError> {
+    let max_chars = max_chars.unwrap_or(DEFAULT_CHUNK_CHARS);
+    if max_chars < MIN_CHUNK_CHARS {
+        return Err(AppError::Validation(format!(
+            "max_chars: must be at least {}",
+            MIN_CHUNK_CHARS
+        )));
+    }
+    let transcript = find_transcript(db, &id).await?;
+    Ok(chunk_transcript(&transcript.content, max_chars))
+}
+
+/// Characters, locations, organizations, quests, heroes and items named in
+/// the transcript, most mentioned first. Character aliases count too.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn detect_transcript_mentions_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<Vec<TranscriptMention>, AppError> {
+    let transcript = find_transcript(db, &id).await?;
+    let campaign_id = &transcript.campaign_id;
+    let text = words(&transcript.content);
+
+    // (entity_type, id, name, other names)
+    let mut candidates: Vec<(&str, String, String, Vec<String>)> = Vec::new();
+    for c in Character::find()
+        .filter(characters::Column::CampaignId.eq(campaign_id))
+        .all(db)
+        .await?
+    {
+        let aliases = parse_string_list(c.aliases_json.as_deref());
+        candidates.push(("character", c.id, c.name, aliases));
+    }
+    for l in Location::find()
+        .filter(locations::Column::CampaignId.eq(campaign_id))
+        .all(db)
+        .await?
+    {
+        candidates.push(("location", l.id, l.name, Vec::new()));
+    }
+    for o in Organization::find()
+        .filter(organizations::Column::CampaignId.eq(campaign_id))
+        .all(db)
+        .await?
+    {
+        candidates.push(("organization", o.id, o.name, Vec::new()));
+    }
+    for q in Quest::find()
+        .filter(quests::Column::CampaignId.eq(campaign_id))
+        .all(db)
+        .await?
+    {
+        candidates.push(("quest", q.id, q.name, Vec::new()));
+    }
+    for h in Hero::find()
+        .filter(heroes::Column::CampaignId.eq(campaign_id))
+        .all(db)
+        .await?
+    {
+        candidates.push(("hero", h.id, h.name, Vec::new()));
+    }
+    for i in Item::find()
+        .filter(items::Column::CampaignId.eq(campaign_id))
+        .all(db)
+        .await?
+    {
+        candidates.push(("item", i.id, i.name, Vec::new()));
+    }
+
+    let mut mentions: Vec<TranscriptMention> = candidates
+        .into_iter()
+        .filter_map(|(entity_type, entity_id, name, aliases)| {
+            let occurrences: usize = std::iter::once(&name)
+                .chain(aliases.iter())
+                .filter(|n| n.trim().chars().count() >= MIN_MENTION_NAME_CHARS)
+                .map(|n| count_name(&text, n))
+                .sum();
+            (occurrences > 0).then(|| TranscriptMention {
+                entity_type: entity_type.to_string(),
+                entity_id,
+                name,
+                occurrences,
+            })
+        })
+        .collect();
+    mentions.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(mentions)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_session_transcript(
+    state: State<'_, AppState>,
+    session_id: String,
+    path: String,
+) -> Result<TranscriptResponse, AppError> {
+    import_session_transcript_impl(&state.db, session_id, path).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_session_transcripts(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<TranscriptResponse>, AppError> {
+    list_session_transcripts_impl(&state.db, session_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_session_transcript(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    delete_session_transcript_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_transcript_chunks(
+    state: State<'_, AppState>,
+    id: String,
+    max_chars: Option<usize>,
+) -> Result<Vec<TranscriptChunk>, AppError> {
+    get_transcript_chunks_impl(&state.db, id, max_chars).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn detect_transcript_mentions(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Vec<TranscriptMention>, AppError> {
+    detect_transcript_mentions_impl(&state.db, id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vtt() {
+        let raw = "WEBVTT\n\n\
+            NOTE recorded at the table\n\n\
+            1\n00:00:01.000 --> 00:00:04.000\n<v Sam Reyes>We head for the <i>docks</i>.</v>\n\n\
+            01:05.500 --> 01:07.000 align:start\n<v.gm Dana>Roll for it.\nThe fog rolls in.\n";
+        assert_eq!(
+            parse_vtt(raw),
+            "[00:00:01] Sam Reyes: We head for the docks.\n\
+             [00:01:05] Dana: Roll for it. The fog rolls in."
+        );
+    }
+
+    #[test]
+    fn test_chunk_transcript() {
+        let content =
+            "[00:00:01] Sam: one two three\n[00:00:05] Dana: four five six\n[00:00:09] Sam: seven";
+        let chunks = chunk_transcript(content, 60);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].start.as_deref(), Some("00:00:01"));
+        assert_eq!(chunks[0].end.as_deref(), Some("00:00:05"));
+        assert_eq!(chunks[1].index, 1);
+        assert_eq!(chunks[1].text, "[00:00:09] Sam: seven");
+
+        // A single long line is split between words
+        let long = "word ".repeat(30);
+        let chunks = chunk_transcript(&long, 20);
+        assert!(chunks.iter().all(|c| c.text.chars().count() <= 20));
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|c| c.text.split(' ').count())
+                .sum::<usize>(),
+            30
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use crate::commands::campaign_settings::CampaignSettings;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::export::load_bundle;
+use crate::markdown::LinkTarget;
+use crate::share::snapshot::PLAYER_VISIBLE_ROWS;
+use crate::share::{
+    build_snapshot, render_snapshot_html, PlayerSnapshot, ShareServer, ShareState,
+    SnapshotSelection,
+};
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::State;
+use tracing::instrument;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotExport {
+    pub path: String,
+    pub entity_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShareInfo {
+    pub url: String,
+    pub port: u16,
+    pub entity_count: usize,
+}
+
+/// Entities the player view shows, as a snapshot with the campaign's
+/// settings would, without building one. With `entity_id`, just that entity
+/// if players can see it.
+pub(crate) async fn player_visible_entities(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    entity_id: Option<&str>,
+) -> Result<Vec<LinkTarget>, AppError> {
+    let campaign = Campaign::find_by_id(campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    let player_view = CampaignSettings::from_json(campaign.settings_json.as_deref()).player_view;
+
+    let mut entities = Vec::new();
+    for (entity_type, table, name, filter) in PLAYER_VISIBLE_ROWS {
+        let shared = &player_view.shared_entity_types;
+        if !shared.is_empty() && !shared.iter().any(|t| t == entity_type) {
+            continue;
+        }
+        let mut sql =
+            format!("SELECT id, {name} AS name FROM {table} WHERE campaign_id = $1 AND {filter}");
+        if *entity_type == "character" && player_view.hide_dead_characters {
+            sql.push_str(" AND is_alive");
+        }
+        let mut values = vec![campaign_id.into()];
+        if let Some(entity_id) = entity_id {
+            sql.push_str(" AND id = $2");
+            values.push(entity_id.into());
+        }
+
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                sql,
+                values,
+            ))
+            .await?;
+        for row in rows {
+            entities.push(LinkTarget {
+                entity_type: entity_type.to_string(),
+                id: row.try_get("", "id")?,
+                name: row.try_get("", "name")?,
+            });
+        }
+    }
+    Ok(entities)
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn build_player_snapshot_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    mut selection: SnapshotSelection,
+) -> Result<PlayerSnapshot, AppError> {
+    let mut bundle = load_bundle(db, &campaign_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    // Campaign player-view settings fill in what the caller leaves open
+    let player_view =
+        CampaignSettings::from_json(bundle.campaign.settings_json.as_deref()).player_view;
+    if selection.entity_types.is_empty() {
+        selection.entity_types = player_view.shared_entity_types;
+    }
+    if player_view.hide_dead_characters {
+        bundle.characters.retain(|c| c.is_alive);
+    }
+
+    Ok(build_snapshot(&bundle, &selection))
+}
+
+/// Write `index.html` and `snapshot.json` into `dir`, ready to upload to any
+/// static host
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn export_player_snapshot_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    selection: SnapshotSelection,
+    dir: String,
+) -> Result<SnapshotExport, AppError> {
+    let snapshot = build_player_snapshot_impl(db, campaign_id, selection).await?;
+    let json = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize snapshot: {}", e)))?;
+
+    let write = |dir: &Path| -> std::io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("index.html"), render_snapshot_html(&snapshot))?;
+        fs::write(dir.join("snapshot.json"), json)
+    };
+    write(Path::new(&dir))
+        .map_err(|e| AppError::Internal(format!("Failed to write snapshot to {}: {}", dir, e)))?;
+
+    Ok(SnapshotExport {
+        path: dir,
+        entity_count: snapshot.entities.len(),
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn preview_player_snapshot(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    selection: Option<SnapshotSelection>,
+) -> Result<PlayerSnapshot, AppError> {
+    build_player_snapshot_impl(&state.db, campaign_id, selection.unwrap_or_default()).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_player_snapshot(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    selection: Option<SnapshotSelection>,
+    dir: String,
+) -> Result<SnapshotExport, AppError> {
+    export_player_snapshot_impl(&state.db, campaign_id, selection.unwrap_or_default(), dir).await
+}
+
+/// Serve a snapshot on the LAN, replacing any share already running
+#[tauri::command(rename_all = "snake_case")]
+pub async fn start_player_share(
+    state: State<'_, AppState>,
+    share: State<'_, ShareState>,
+    campaign_id: String,
+    selection: Option<SnapshotSelection>,
+    port: Option<u16>,
+) -> Result<ShareInfo, AppError> {
+    let snapshot =
+        build_player_snapshot_impl(&state.db, campaign_id, selection.unwrap_or_default()).await?;
+
+    let mut running = share
+        .server
+        .lock()
+        .map_err(|_| AppError::Internal("Share state lock poisoned".into()))?;
+    if let Some(previous) = running.take() {
+        previous.stop();
+    }
+
+    let server = ShareServer::start(&snapshot, port.unwrap_or(0))?;
+    let info = ShareInfo {
+        url: server.url.clone(),
+        port: server.port,
+        entity_count: snapshot.entities.len(),
+    };
+    *running = Some(server);
+
+    Ok(info)
+}
+
+/// Stop the LAN share. Returns false if nothing was running.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn stop_player_share(share: State<'_, ShareState>) -> Result<bool, AppError> {
+    let server = share
+        .server
+        .lock()
+        .map_err(|_| AppError::Internal("Share state lock poisoned".into()))?
+        .take();
+
+    Ok(match server {
+        Some(server) => {
+            server.stop();
+            true
+        }
+        None => false,
+    })
+}
@@ -0,0 +1,278 @@
+use crate::commands::validation::{CreateSnippetInput, UpdateSnippetInput};
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::text::plain_text;
+use ::entity::entity_tags::{self, Entity as EntityTag};
+use ::entity::snippets::{self, Entity as Snippet};
+use ::entity::tags::{self, Entity as Tag};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+use tracing::instrument;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnippetResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub name: String,
+    pub snippet_type: String,
+    pub content: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<snippets::Model> for SnippetResponse {
+    fn from(model: snippets::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            name: model.name,
+            snippet_type: model.snippet_type,
+            content: model.content,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+/// Search rank of a snippet: name prefix, then name, then content or tags.
+/// None when some query word doesn't appear anywhere.
+fn rank(snippet: &snippets::Model, tags: &[String], words: &[String]) -> Option<u8> {
+    let name = snippet.name.to_lowercase();
+    let body = snippet
+        .content
+        .as_deref()
+        .map(plain_text)
+        .unwrap_or_default()
+        .to_lowercase();
+    let tags = tags.join(" ").to_lowercase();
+
+    for word in words {
+        if !name.contains(word) && !body.contains(word) && !tags.contains(word) {
+            return None;
+        }
+    }
+    let phrase = words.join(" ");
+    Some(if name.starts_with(&phrase) {
+        0
+    } else if name.contains(&phrase) {
+        1
+    } else {
+        2
+    })
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, err)]
+pub async fn create_snippet_impl(
+    db: &DatabaseConnection,
+    input: CreateSnippetInput,
+) -> Result<SnippetResponse, AppError> {
+    input.validate()?;
+
+    let now = chrono::Utc::now();
+    let model = snippets::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(input.campaign_id),
+        name: Set(input.name),
+        snippet_type: Set(input.snippet_type),
+        content: Set(input.content),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    let result = model.insert(db).await?;
+    Ok(result.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_snippet_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<SnippetResponse, AppError> {
+    let snippet = Snippet::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Snippet {} not found", id)))?;
+
+    Ok(snippet.into())
+}
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_snippets_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<SnippetResponse>, AppError> {
+    let snippets = Snippet::find()
+        .filter(snippets::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(snippets::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(snippets.into_iter().map(|s| s.into()).collect())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_snippet_impl(
+    db: &DatabaseConnection,
+    id: String,
+    input: UpdateSnippetInput,
+) -> Result<SnippetResponse, AppError> {
+    input.validate()?;
+
+    let snippet = Snippet::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Snippet {} not found", id)))?;
+
+    let mut active: snippets::ActiveModel = snippet.into();
+
+    if let Some(n) = input.name {
+        active.name = Set(n);
+    }
+    if let Some(t) = input.snippet_type {
+        active.snippet_type = Set(t);
+    }
+    if let Some(c) = input.content {
+        active.content = Set(Some(c));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    Ok(result.into())
+}
+
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_snippet_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
+    let result = Snippet::delete_by_id(&id).exec(db).await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Quick-insert lookup. Every word of `query` must appear in the name, the
+/// text or a tag; name matches sort first. An empty query lists everything.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn search_snippets_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    query: String,
+    snippet_type: Option<String>,
+    limit: Option<u64>,
+) -> Result<Vec<SnippetResponse>, AppError> {
+    let limit = limit.unwrap_or(20) as usize;
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+
+    let mut select = Snippet::find().filter(snippets::Column::CampaignId.eq(&campaign_id));
+    if let Some(snippet_type) = &snippet_type {
+        select = select.filter(snippets::Column::SnippetType.eq(snippet_type));
+    }
+    let snippets = select.all(db).await?;
+
+    let tag_names: HashMap<String, String> = Tag::find()
+        .filter(tags::Column::CampaignId.eq(&campaign_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| (t.id, t.name))
+        .collect();
+    let mut tags_by_snippet: HashMap<String, Vec<String>> = HashMap::new();
+    for entity_tag in EntityTag::find()
+        .filter(entity_tags::Column::EntityType.eq("snippet"))
+        .filter(entity_tags::Column::TagId.is_in(tag_names.keys().cloned()))
+        .all(db)
+        .await?
+    {
+        if let Some(name) = tag_names.get(&entity_tag.tag_id) {
+            tags_by_snippet
+                .entry(entity_tag.entity_id)
+                .or_default()
+                .push(name.clone());
+        }
+    }
+
+    let mut ranked: Vec<(u8, snippets::Model)> = snippets
+        .into_iter()
+        .filter_map(|s| {
+            let tags = tags_by_snippet.get(&s.id).map(Vec::as_slice).unwrap_or(&[]);
+            rank(&s, tags, &words).map(|r| (r, s))
+        })
+        .collect();
+    ranked.sort_by(|(ra, a), (rb, b)| ra.cmp(rb).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, s)| s.into())
+        .collect())
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_snippet(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    name: String,
+    snippet_type: Option<String>,
+    content: Option<String>,
+) -> Result<SnippetResponse, AppError> {
+    let input = CreateSnippetInput {
+        campaign_id,
+        name,
+        snippet_type: snippet_type.unwrap_or_else(|| "other".to_string()),
+        content,
+    };
+    create_snippet_impl(&state.db, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_snippet(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<SnippetResponse, AppError> {
+    get_snippet_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_snippets(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<SnippetResponse>, AppError> {
+    list_snippets_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_snippet(
+    state: State<'_, AppState>,
+    id: String,
+    name: Option<String>,
+    snippet_type: Option<String>,
+    content: Option<String>,
+) -> Result<SnippetResponse, AppError> {
+    let input = UpdateSnippetInput {
+        name,
+        snippet_type,
+        content,
+    };
+    update_snippet_impl(&state.db, id, input).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_snippet(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    delete_snippet_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn search_snippets(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    query: String,
+    snippet_type: Option<String>,
+    limit: Option<u64>,
+) -> Result<Vec<SnippetResponse>, AppError> {
+    search_snippets_impl(&state.db, campaign_id, query, snippet_type, limit).await
+}
@@ -0,0 +1,327 @@
+//! Searching the 5e SRD through Open5e and importing results into a campaign.
+//! Monsters become characters with a 5e stat block; magic items become items
+//! and spells become spell scrolls.
+
+use crate::commands::item::create_item_impl;
+use crate::commands::json_schema::SystemStatBlock;
+use crate::commands::validation::CreateItemInput;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::srd::client::{Open5eClient, DEFAULT_BASE_URL};
+use crate::srd::{self, SrdKind};
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::characters;
+use ::entity::srd_cache::{self, Entity as SrdCache};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+use tracing::instrument;
+
+/// Results returned when no limit is given
+const DEFAULT_SEARCH_LIMIT: u64 = 20;
+/// Largest page Open5e is asked for
+const MAX_SEARCH_LIMIT: u64 = 50;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SrdEntry {
+    pub kind: String,
+    pub slug: String,
+    pub name: String,
+    /// e.g. "Small humanoid, CR 1/4"
+    pub summary: Option<String>,
+    /// The Open5e document as returned by the API
+    pub data: Value,
+}
+
+impl TryFrom<srd_cache::Model> for SrdEntry {
+    type Error = AppError;
+
+    fn try_from(model: srd_cache::Model) -> Result<Self, Self::Error> {
+        let kind = SrdKind::parse(&model.kind)?;
+        let data: Value = serde_json::from_str(&model.data_json)
+            .map_err(|e| AppError::Internal(format!("Corrupt SRD cache entry: {}", e)))?;
+        Ok(Self {
+            kind: model.kind,
+            slug: model.slug,
+            name: model.name,
+            summary: srd::summary(kind, &data),
+            data,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SrdSearchResponse {
+    pub results: Vec<SrdEntry>,
+    /// Open5e couldn't be reached and the results came from the local cache
+    pub offline: bool,
+}
+
+/// What an SRD entry was imported as
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SrdImportResponse {
+    /// "character" or "item"
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: String,
+}
+
+fn cache_id(kind: SrdKind, slug: &str) -> String {
+    format!("{}:{}", kind.as_str(), slug)
+}
+
+/// Store Open5e documents in the cache, replacing older copies. Documents
+/// without a slug or name are skipped.
+pub async fn cache_srd_documents(
+    db: &DatabaseConnection,
+    kind: SrdKind,
+    documents: Vec<Value>,
+) -> Result<Vec<SrdEntry>, AppError> {
+    let now = chrono::Utc::now();
+    let mut entries = Vec::with_capacity(documents.len());
+    for data in documents {
+        let (Some(slug), Some(name)) = (srd::text(&data, "slug"), srd::text(&data, "name")) else {
+            continue;
+        };
+        let model = srd_cache::ActiveModel {
+            id: Set(cache_id(kind, &slug)),
+            kind: Set(kind.as_str().to_string()),
+            slug: Set(slug.clone()),
+            name: Set(name.clone()),
+            data_json: Set(data.to_string()),
+            fetched_at: Set(now),
+        };
+        SrdCache::insert(model)
+            .on_conflict(
+                OnConflict::column(srd_cache::Column::Id)
+                    .update_columns([
+                        srd_cache::Column::Name,
+                        srd_cache::Column::DataJson,
+                        srd_cache::Column::FetchedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db)
+            .await?;
+
+        entries.push(SrdEntry {
+            kind: kind.as_str().to_string(),
+            slug,
+            name,
+            summary: srd::summary(kind, &data),
+            data,
+        });
+    }
+    Ok(entries)
+}
+
+async fn search_cache(
+    db: &DatabaseConnection,
+    kind: SrdKind,
+    query: &str,
+    limit: u64,
+) -> Result<Vec<SrdEntry>, AppError> {
+    SrdCache::find()
+        .filter(srd_cache::Column::Kind.eq(kind.as_str()))
+        .filter(srd_cache::Column::Name.contains(query))
+        .order_by_asc(srd_cache::Column::Name)
+        .limit(limit)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(SrdEntry::try_from)
+        .collect()
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Search Open5e, falling back to previously cached results when it can't be
+/// reached
+#[instrument(skip_all, fields(kind = %kind, query = %query), err)]
+pub async fn search_srd_impl(
+    db: &DatabaseConnection,
+    client: &Open5eClient,
+    kind: String,
+    query: String,
+    limit: Option<u64>,
+) -> Result<SrdSearchResponse, AppError> {
+    let kind = SrdKind::parse(&kind)?;
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::Validation("query: must not be empty".to_string()));
+    }
+    let limit = limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+
+    match client.search(kind, query, limit).await {
+        Ok(documents) => Ok(SrdSearchResponse {
+            results: cache_srd_documents(db, kind, documents).await?,
+            offline: false,
+        }),
+        Err(e) => {
+            let cached = search_cache(db, kind, query, limit).await?;
+            if cached.is_empty() {
+                return Err(e);
+            }
+            tracing::warn!("Open5e search failed, using cached results: {}", e);
+            Ok(SrdSearchResponse {
+                results: cached,
+                offline: true,
+            })
+        }
+    }
+}
+
+/// A single SRD entry, from the cache when it has been seen before
+#[instrument(skip_all, fields(kind = %kind, slug = %slug), err)]
+pub async fn get_srd_entry_impl(
+    db: &DatabaseConnection,
+    client: &Open5eClient,
+    kind: String,
+    slug: String,
+) -> Result<SrdEntry, AppError> {
+    let kind = SrdKind::parse(&kind)?;
+    if let Some(cached) = SrdCache::find_by_id(cache_id(kind, &slug)).one(db).await? {
+        return cached.try_into();
+    }
+
+    let data = client.fetch(kind, &slug).await?;
+    cache_srd_documents(db, kind, vec![data])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound(format!("SRD entry {} not found", slug)))
+}
+
+/// Create a character or item in a campaign from an SRD entry
+#[instrument(skip_all, fields(campaign_id = %campaign_id, kind = %kind, slug = %slug), err)]
+pub async fn import_srd_entry_impl(
+    db: &DatabaseConnection,
+    client: &Open5eClient,
+    campaign_id: String,
+    kind: String,
+    slug: String,
+) -> Result<SrdImportResponse, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let entry = get_srd_entry_impl(db, client, kind, slug).await?;
+    let imported = match SrdKind::parse(&entry.kind)? {
+        SrdKind::Monster => {
+            let block = SystemStatBlock::Dnd5e(srd::monster_stat_block(&entry.data));
+            block.validate()?;
+            let stat_block_json = serde_json::to_string(&block)
+                .map_err(|e| AppError::Internal(format!("Failed to encode stat block: {}", e)))?;
+
+            let now = chrono::Utc::now();
+            let model = characters::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                campaign_id: Set(campaign_id),
+                name: Set(entry.name),
+                lineage: Set(srd::text(&entry.data, "type")),
+                occupation: Set(None),
+                is_alive: Set(true),
+                description: Set(srd::text(&entry.data, "desc")),
+                personality: Set(None),
+                motivations: Set(None),
+                secrets: Set(None),
+                voice_notes: Set(None),
+                stat_block_json: Set(Some(stat_block_json)),
+                birth_year: Set(None),
+                birth_month: Set(None),
+                birth_day: Set(None),
+                death_year: Set(None),
+                death_month: Set(None),
+                death_day: Set(None),
+                death_event_id: Set(None),
+                aliases_json: Set(None),
+                source_hero_id: Set(None),
+                color: Set(None),
+                icon: Set(None),
+                accent: Set(None),
+                mannerisms_json: Set(None),
+                catchphrases_json: Set(None),
+                speech_sample: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+                created_by: Set(None),
+                modified_by: Set(None),
+            };
+            let character = model.insert(db).await?;
+            SrdImportResponse {
+                entity_type: "character".to_string(),
+                entity_id: character.id,
+                name: character.name,
+            }
+        }
+        kind @ (SrdKind::Spell | SrdKind::MagicItem) => {
+            let item = if kind == SrdKind::Spell {
+                srd::spell_scroll(&entry.data)
+            } else {
+                srd::magic_item(&entry.data)
+            };
+            let item = create_item_impl(
+                db,
+                CreateItemInput {
+                    name: item.name,
+                    campaign_id,
+                    item_type: item.item_type,
+                    rarity: item.rarity,
+                    description: item.description,
+                    requires_attunement: item.requires_attunement,
+                },
+            )
+            .await?;
+            SrdImportResponse {
+                entity_type: "item".to_string(),
+                entity_id: item.id,
+                name: item.name,
+            }
+        }
+    };
+
+    tracing::info!(
+        entity_type = %imported.entity_type,
+        entity_id = %imported.entity_id,
+        "Imported SRD entry"
+    );
+    Ok(imported)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn search_srd(
+    state: State<'_, AppState>,
+    kind: String,
+    query: String,
+    limit: Option<u64>,
+) -> Result<SrdSearchResponse, AppError> {
+    let client = Open5eClient::new(DEFAULT_BASE_URL)?;
+    search_srd_impl(&state.db, &client, kind, query, limit).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_srd_entry(
+    state: State<'_, AppState>,
+    kind: String,
+    slug: String,
+) -> Result<SrdEntry, AppError> {
+    let client = Open5eClient::new(DEFAULT_BASE_URL)?;
+    get_srd_entry_impl(&state.db, &client, kind, slug).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_srd_entry(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    kind: String,
+    slug: String,
+) -> Result<SrdImportResponse, AppError> {
+    let client = Open5eClient::new(DEFAULT_BASE_URL)?;
+    import_srd_entry_impl(&state.db, &client, campaign_id, kind, slug).await
+}
@@ -0,0 +1,496 @@
+//! Printable stat blocks for session screens. Each game system lays out its
+//! own block; the layout is then written as plain text or markdown.
+
+use crate::commands::json_schema::{
+    Dnd5eStatBlock, Pf2eStatBlock, StatBlock, StatBlockFeature, SystemStatBlock, ABILITY_KEYS,
+};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::characters::Entity as Character;
+use sea_orm::*;
+use std::collections::BTreeMap;
+use tauri::State;
+use tracing::instrument;
+
+/// Output formats `render_stat_block` understands
+pub const RENDER_FORMATS: &[&str] = &["text", "markdown", "homebrewery"];
+
+/// One piece of a stat block layout, independent of output format
+enum Line {
+    Title(String),
+    Subtitle(String),
+    Field(&'static str, String),
+    /// Short labels over values, e.g. ability scores
+    Row(Vec<(String, String)>),
+    Rule,
+    Section(&'static str),
+    Feature(String, String),
+}
+
+fn signed(value: i32) -> String {
+    format!("{:+}", value)
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// "Stealth +4, Perception +2" from a map of bonuses
+fn bonuses(map: &BTreeMap<String, i32>) -> String {
+    map.iter()
+        .map(|(name, bonus)| format!("{} {}", capitalize(name), signed(*bonus)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Known ability keys first in printed order, then anything else
+fn ability_order<'a>(keys: impl Iterator<Item = &'a String>) -> Vec<&'a str> {
+    let keys: Vec<&str> = keys.map(String::as_str).collect();
+    let mut ordered: Vec<&str> = ABILITY_KEYS
+        .iter()
+        .copied()
+        .filter(|k| keys.contains(k))
+        .collect();
+    ordered.extend(keys.iter().copied().filter(|k| !ABILITY_KEYS.contains(k)));
+    ordered
+}
+
+fn modifier(score: i32) -> i32 {
+    (score - 10).div_euclid(2)
+}
+
+fn push_field(lines: &mut Vec<Line>, label: &'static str, value: Option<String>) {
+    if let Some(value) = value.filter(|v| !v.is_empty()) {
+        lines.push(Line::Field(label, value));
+    }
+}
+
+fn push_list(lines: &mut Vec<Line>, label: &'static str, values: &[String]) {
+    push_field(lines, label, Some(values.join(", ")));
+}
+
+fn push_features(
+    lines: &mut Vec<Line>,
+    section: Option<&'static str>,
+    features: &[StatBlockFeature],
+) {
+    if features.is_empty() {
+        return;
+    }
+    if let Some(section) = section {
+        lines.push(Line::Section(section));
+    }
+    for feature in features {
+        lines.push(Line::Feature(
+            feature.name.clone(),
+            feature.description.clone(),
+        ));
+    }
+}
+
+fn with_detail(value: Option<i32>, detail: Option<&str>) -> Option<String> {
+    value.map(|v| match detail {
+        Some(detail) if !detail.is_empty() => format!("{} ({})", v, detail),
+        _ => v.to_string(),
+    })
+}
+
+fn generic_lines(name: &str, block: &StatBlock) -> Vec<Line> {
+    let mut lines = vec![Line::Title(name.to_string()), Line::Rule];
+    push_field(
+        &mut lines,
+        "Armor Class",
+        block.armor_class.map(|ac| ac.to_string()),
+    );
+    push_field(
+        &mut lines,
+        "Hit Points",
+        with_detail(block.hit_points, block.hit_dice.as_deref()),
+    );
+    push_field(&mut lines, "Speed", block.speed.clone());
+    if !block.abilities.is_empty() {
+        lines.push(Line::Rule);
+        lines.push(Line::Row(
+            ability_order(block.abilities.keys())
+                .into_iter()
+                .map(|key| {
+                    let score = block.abilities[key];
+                    (
+                        key.to_uppercase(),
+                        format!("{} ({})", score, signed(modifier(score))),
+                    )
+                })
+                .collect(),
+        ));
+    }
+    lines.push(Line::Rule);
+    push_field(&mut lines, "Skills", Some(bonuses(&block.skills)));
+    push_list(&mut lines, "Senses", &block.senses);
+    push_list(&mut lines, "Languages", &block.languages);
+    push_field(&mut lines, "Challenge", block.challenge_rating.clone());
+    push_features(&mut lines, None, &block.traits);
+    push_features(&mut lines, Some("Actions"), &block.actions);
+    lines
+}
+
+fn dnd5e_lines(name: &str, block: &Dnd5eStatBlock) -> Vec<Line> {
+    let mut lines = vec![Line::Title(name.to_string())];
+    let kind = [block.size.as_deref(), block.creature_type.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let subtitle = [Some(kind.as_str()), block.alignment.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if !subtitle.is_empty() {
+        lines.push(Line::Subtitle(subtitle));
+    }
+
+    lines.push(Line::Rule);
+    push_field(
+        &mut lines,
+        "Armor Class",
+        with_detail(block.armor_class, block.armor_note.as_deref()),
+    );
+    push_field(
+        &mut lines,
+        "Hit Points",
+        with_detail(block.hit_points, block.hit_dice.as_deref()),
+    );
+    push_field(&mut lines, "Speed", block.speed.clone());
+    lines.push(Line::Rule);
+    lines.push(Line::Row(
+        ABILITY_KEYS
+            .iter()
+            .map(|key| {
+                let score = block.ability(key);
+                (
+                    key.to_uppercase(),
+                    format!("{} ({})", score, signed(modifier(score))),
+                )
+            })
+            .collect(),
+    ));
+    lines.push(Line::Rule);
+    push_field(
+        &mut lines,
+        "Saving Throws",
+        Some(bonuses(&block.saving_throws)),
+    );
+    push_field(&mut lines, "Skills", Some(bonuses(&block.skills)));
+    push_list(&mut lines, "Damage Resistances", &block.damage_resistances);
+    push_list(&mut lines, "Damage Immunities", &block.damage_immunities);
+    push_list(
+        &mut lines,
+        "Condition Immunities",
+        &block.condition_immunities,
+    );
+    push_list(&mut lines, "Senses", &block.senses);
+    push_list(&mut lines, "Languages", &block.languages);
+    push_field(&mut lines, "Challenge", block.challenge_rating.clone());
+    push_features(&mut lines, None, &block.traits);
+    push_features(&mut lines, Some("Actions"), &block.actions);
+    push_features(&mut lines, Some("Bonus Actions"), &block.bonus_actions);
+    push_features(&mut lines, Some("Reactions"), &block.reactions);
+    push_features(
+        &mut lines,
+        Some("Legendary Actions"),
+        &block.legendary_actions,
+    );
+    lines
+}
+
+fn pf2e_lines(name: &str, block: &Pf2eStatBlock) -> Vec<Line> {
+    let title = match block.level {
+        Some(level) => format!("{} (Creature {})", name, level),
+        None => name.to_string(),
+    };
+    let mut lines = vec![Line::Title(title)];
+    let traits: Vec<String> = block
+        .size
+        .iter()
+        .chain(&block.traits)
+        .map(|t| capitalize(t))
+        .collect();
+    if !traits.is_empty() {
+        lines.push(Line::Subtitle(traits.join(", ")));
+    }
+
+    lines.push(Line::Rule);
+    let perception = block.perception.map(|p| {
+        std::iter::once(signed(p))
+            .chain(block.senses.iter().cloned())
+            .collect::<Vec<_>>()
+            .join("; ")
+    });
+    push_field(&mut lines, "Perception", perception);
+    push_list(&mut lines, "Languages", &block.languages);
+    push_field(&mut lines, "Skills", Some(bonuses(&block.skills)));
+    let attributes = ability_order(block.attributes.keys())
+        .into_iter()
+        .map(|key| format!("{} {}", capitalize(key), signed(block.attributes[key])))
+        .collect::<Vec<_>>()
+        .join(", ");
+    push_field(&mut lines, "Attributes", Some(attributes));
+
+    lines.push(Line::Rule);
+    let saves = [
+        ("Fort", block.fortitude),
+        ("Ref", block.reflex),
+        ("Will", block.will),
+    ]
+    .into_iter()
+    .filter_map(|(label, save)| save.map(|s| format!("{} {}", label, signed(s))))
+    .collect::<Vec<_>>()
+    .join(", ");
+    let defenses = [block.armor_class.map(|ac| ac.to_string()), Some(saves)]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("; ");
+    push_field(&mut lines, "AC", Some(defenses));
+    push_field(&mut lines, "HP", block.hit_points.map(|hp| hp.to_string()));
+    push_list(&mut lines, "Immunities", &block.immunities);
+    let amounts = |map: &BTreeMap<String, i32>| {
+        map.iter()
+            .map(|(kind, amount)| format!("{} {}", kind, amount))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    push_field(&mut lines, "Weaknesses", Some(amounts(&block.weaknesses)));
+    push_field(&mut lines, "Resistances", Some(amounts(&block.resistances)));
+    push_field(&mut lines, "Speed", block.speed.clone());
+    push_features(&mut lines, None, &block.abilities);
+
+    if !block.actions.is_empty() {
+        lines.push(Line::Section("Actions"));
+        for action in &block.actions {
+            let name = match &action.cost {
+                Some(cost) => format!("{} [{}]", action.name, cost),
+                None => action.name.clone(),
+            };
+            let description = if action.traits.is_empty() {
+                action.description.clone()
+            } else {
+                format!("({}) {}", action.traits.join(", "), action.description)
+            };
+            lines.push(Line::Feature(name, description.trim_end().to_string()));
+        }
+    }
+    lines
+}
+
+fn to_text(lines: &[Line]) -> String {
+    let mut out = Vec::new();
+    for line in lines {
+        match line {
+            Line::Title(title) => out.push(title.to_uppercase()),
+            Line::Subtitle(subtitle) => out.push(subtitle.clone()),
+            Line::Field(label, value) => out.push(format!("{}: {}", label, value)),
+            Line::Row(cells) => out.push(
+                cells
+                    .iter()
+                    .map(|(label, value)| format!("{} {}", label, value))
+                    .collect::<Vec<_>>()
+                    .join("  "),
+            ),
+            Line::Rule => out.push("-".repeat(40)),
+            Line::Section(section) => {
+                out.push(String::new());
+                out.push(section.to_uppercase());
+            }
+            Line::Feature(name, description) => out.push(format!("{}. {}", name, description)),
+        }
+    }
+    out.join("\n").trim_end().to_string()
+}
+
+fn to_markdown(lines: &[Line]) -> String {
+    let mut out = Vec::new();
+    for line in lines {
+        match line {
+            Line::Title(title) => out.push(format!("### {}\n", title)),
+            Line::Subtitle(subtitle) => out.push(format!("*{}*\n", subtitle)),
+            Line::Field(label, value) => out.push(format!("**{}** {}  ", label, value)),
+            Line::Row(cells) => {
+                let labels: Vec<&str> = cells.iter().map(|(l, _)| l.as_str()).collect();
+                let values: Vec<&str> = cells.iter().map(|(_, v)| v.as_str()).collect();
+                out.push(format!("| {} |", labels.join(" | ")));
+                out.push(format!("|{}", ":---:|".repeat(cells.len())));
+                out.push(format!("| {} |\n", values.join(" | ")));
+            }
+            Line::Rule => out.push("\n---\n".to_string()),
+            Line::Section(section) => out.push(format!("\n#### {}\n", section)),
+            Line::Feature(name, description) => {
+                out.push(format!("***{}.*** {}\n", name, description))
+            }
+        }
+    }
+    out.join("\n").trim().to_string()
+}
+
+/// Markdown in Homebrewery's monster block, which GM Binder also reads
+fn to_homebrewery(lines: &[Line]) -> String {
+    let mut out = vec!["{{monster,frame".to_string()];
+    for line in lines {
+        match line {
+            Line::Title(title) => out.push(format!("## {}", title)),
+            Line::Subtitle(subtitle) => out.push(format!("*{}*", subtitle)),
+            Line::Field(label, value) => out.push(format!("**{}** :: {}", label, value)),
+            Line::Row(cells) => {
+                let labels: Vec<&str> = cells.iter().map(|(l, _)| l.as_str()).collect();
+                let values: Vec<&str> = cells.iter().map(|(_, v)| v.as_str()).collect();
+                out.push(format!("|{}|", labels.join("|")));
+                out.push(format!("|{}", ":---:|".repeat(cells.len())));
+                out.push(format!("|{}|", values.join("|")));
+            }
+            Line::Rule => out.push("___".to_string()),
+            Line::Section(section) => out.push(format!("### {}", section)),
+            Line::Feature(name, description) => {
+                out.push(format!("***{}.*** {}", name, description));
+                out.push(":".to_string());
+            }
+        }
+    }
+    out.push("}}".to_string());
+    out.join("\n")
+}
+
+/// Render a parsed stat block in `format`, "text", "markdown" or
+/// "homebrewery"
+pub fn render(name: &str, block: &SystemStatBlock, format: &str) -> Result<String, AppError> {
+    let lines = match block {
+        SystemStatBlock::Generic(block) => generic_lines(name, block),
+        SystemStatBlock::Dnd5e(block) => dnd5e_lines(name, block),
+        SystemStatBlock::Pf2e(block) => pf2e_lines(name, block),
+    };
+    match format {
+        "text" => Ok(to_text(&lines)),
+        "markdown" => Ok(to_markdown(&lines)),
+        "homebrewery" => Ok(to_homebrewery(&lines)),
+        _ => Err(AppError::Validation(format!(
+            "format: must be one of: {}",
+            RENDER_FORMATS.join(", ")
+        ))),
+    }
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Format a character's stat block for display at the table
+#[instrument(skip_all, fields(id = %id, format = %format), err)]
+pub async fn render_stat_block_impl(
+    db: &DatabaseConnection,
+    id: String,
+    format: String,
+) -> Result<String, AppError> {
+    let character = Character::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", id)))?;
+    let raw = character
+        .stat_block_json
+        .as_deref()
+        .ok_or_else(|| AppError::NotFound(format!("Character {} has no stat block", id)))?;
+
+    let block = SystemStatBlock::parse(raw)?;
+    render(&character.name, &block, &format)
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn render_stat_block(
+    state: State<'_, AppState>,
+    id: String,
+    format: String,
+) -> Result<String, AppError> {
+    render_stat_block_impl(&state.db, id, format).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modifier_rounds_down() {
+        assert_eq!(modifier(10), 0);
+        assert_eq!(modifier(11), 0);
+        assert_eq!(modifier(9), -1);
+        assert_eq!(modifier(1), -5);
+        assert_eq!(modifier(30), 10);
+    }
+
+    #[test]
+    fn test_render_dnd5e_text() {
+        let block = SystemStatBlock::parse(
+            r#"{"system": "5e", "size": "Medium", "creature_type": "humanoid", "alignment": "neutral evil",
+                "armor_class": 15, "armor_note": "leather armor", "hit_points": 27, "hit_dice": "5d8+5",
+                "abilities": {"dex": 16}, "skills": {"stealth": 5},
+                "actions": [{"name": "Dagger", "description": "+5 to hit, 1d4+3 piercing."}]}"#,
+        )
+        .unwrap();
+        let text = render("Cutthroat", &block, "text").unwrap();
+
+        assert!(text.starts_with("CUTTHROAT\nMedium humanoid, neutral evil\n"));
+        assert!(text.contains("Armor Class: 15 (leather armor)"));
+        assert!(text.contains("Hit Points: 27 (5d8+5)"));
+        assert!(text.contains("STR 10 (+0)  DEX 16 (+3)"));
+        assert!(text.contains("Skills: Stealth +5"));
+        assert!(text.contains("ACTIONS\nDagger. +5 to hit, 1d4+3 piercing."));
+        assert!(!text.contains("Saving Throws"));
+    }
+
+    #[test]
+    fn test_render_pf2e_markdown() {
+        let block = SystemStatBlock::parse(
+            r#"{"system": "pf2e", "level": -1, "size": "small", "traits": ["goblin", "humanoid"],
+                "perception": 2, "senses": ["darkvision"], "armor_class": 16, "fortitude": 5,
+                "reflex": 7, "will": 3, "hit_points": 6, "weaknesses": {"fire": 2},
+                "actions": [{"name": "Dogslicer", "cost": "1", "traits": ["agile"], "description": "+8 to hit"}]}"#,
+        )
+        .unwrap();
+        let markdown = render("Goblin Warrior", &block, "markdown").unwrap();
+
+        assert!(markdown.starts_with("### Goblin Warrior (Creature -1)"));
+        assert!(markdown.contains("*Small, Goblin, Humanoid*"));
+        assert!(markdown.contains("**Perception** +2; darkvision"));
+        assert!(markdown.contains("**AC** 16; Fort +5, Ref +7, Will +3"));
+        assert!(markdown.contains("**Weaknesses** fire 2"));
+        assert!(markdown.contains("***Dogslicer [1].*** (agile) +8 to hit"));
+    }
+
+    #[test]
+    fn test_render_homebrewery_monster_block() {
+        let block = SystemStatBlock::parse(
+            r#"{"system": "5e", "size": "Small", "creature_type": "humanoid",
+                "armor_class": 15, "hit_points": 7, "abilities": {"dex": 14},
+                "actions": [{"name": "Scimitar", "description": "+4 to hit, 1d6+2 slashing."}]}"#,
+        )
+        .unwrap();
+        let markdown = render("Goblin", &block, "homebrewery").unwrap();
+
+        assert!(markdown.starts_with("{{monster,frame\n## Goblin\n*Small humanoid*\n___\n"));
+        assert!(markdown.contains("**Armor Class** :: 15\n**Hit Points** :: 7\n"));
+        assert!(markdown.contains("|STR|DEX|CON|INT|WIS|CHA|\n|:---:|"));
+        assert!(markdown.contains("|10 (+0)|14 (+2)|"));
+        assert!(
+            markdown.ends_with("### Actions\n***Scimitar.*** +4 to hit, 1d6+2 slashing.\n:\n}}")
+        );
+    }
+
+    #[test]
+    fn test_render_rejects_unknown_format() {
+        let block = SystemStatBlock::parse("{}").unwrap();
+        assert!(render("Anyone", &block, "html").is_err());
+    }
+}
@@ -0,0 +1,509 @@
+use crate::commands::registry::{EntityTable, ENTITY_TABLES};
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::sync::client::SyncClient;
+use crate::sync::protocol::{ChangeOperation, ChangeRecord};
+use crate::sync::rows::ENTITY_TAG;
+use crate::sync::{apply_remote_changes, collect_local_changes, SyncConflict, INITIAL_WATERMARK};
+use ::entity::campaigns::Entity as Campaign;
+use ::entity::sync_state::{self, Entity as SyncState};
+use ::entity::tombstones::{self, Entity as Tombstone};
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// Reference to an entity that changed after the requested watermark
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityChange {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: Option<String>,
+    pub changed_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeSetResponse {
+    pub campaign_id: String,
+    pub since: String,
+    /// Pass this back as `since` on the next call
+    pub watermark: String,
+    pub created: Vec<EntityChange>,
+    pub updated: Vec<EntityChange>,
+    pub deleted: Vec<EntityChange>,
+}
+
+/// Per-campaign sync configuration; the auth token itself is never returned
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncSettingsResponse {
+    pub campaign_id: String,
+    pub enabled: bool,
+    pub server_url: Option<String>,
+    pub has_auth_token: bool,
+    pub last_synced_at: Option<String>,
+}
+
+impl SyncSettingsResponse {
+    fn disabled(campaign_id: String) -> Self {
+        Self {
+            campaign_id,
+            enabled: false,
+            server_url: None,
+            has_auth_token: false,
+            last_synced_at: None,
+        }
+    }
+}
+
+impl From<sync_state::Model> for SyncSettingsResponse {
+    fn from(state: sync_state::Model) -> Self {
+        Self {
+            campaign_id: state.campaign_id,
+            enabled: state.enabled,
+            server_url: state.server_url,
+            has_auth_token: state.auth_token.is_some(),
+            last_synced_at: state.last_synced_at.map(|ts| ts.to_rfc3339()),
+        }
+    }
+}
+
+/// Format tag at the top of an incremental export file
+pub const CHANGE_EXPORT_FORMAT: &str = "loreweaver-changes";
+
+/// Everything that changed in a campaign since a watermark, as written by
+/// `export_changes_since`: full rows for upserts, ids for deletes. Changes
+/// are sorted by type and id so consecutive exports diff cleanly.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeExport {
+    pub format: String,
+    pub campaign_id: String,
+    pub since: String,
+    /// Pass this as `since` for the next export
+    pub watermark: String,
+    pub changes: Vec<ChangeRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeExportSummary {
+    pub path: String,
+    pub since: String,
+    pub watermark: String,
+    pub upserted: usize,
+    pub deleted: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub pulled: usize,
+    pub applied: usize,
+    pub deleted: usize,
+    /// Pulled changes skipped because the local row was newer
+    pub conflicts: Vec<SyncConflict>,
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_changes_since_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    since: String,
+) -> Result<ChangeSetResponse, AppError> {
+    let since_ts = parse_timestamp(&since)?;
+    // Taken before querying so writes racing with this call show up next time
+    let watermark = Utc::now();
+
+    let campaign = Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let mut created = Vec::new();
+    let mut updated = Vec::new();
+
+    if campaign.updated_at > since_ts {
+        let change = EntityChange {
+            entity_type: "campaign".to_string(),
+            entity_id: campaign.id.clone(),
+            name: Some(campaign.name.clone()),
+            changed_at: campaign.updated_at.to_rfc3339(),
+        };
+        if campaign.created_at > since_ts {
+            created.push(change);
+        } else {
+            updated.push(change);
+        }
+    }
+
+    for table in ENTITY_TABLES {
+        for (is_new, change) in changed_rows(db, table, &campaign_id, since_ts).await? {
+            if is_new {
+                created.push(change);
+            } else {
+                updated.push(change);
+            }
+        }
+    }
+
+    // Entity tags are never edited in place, only added and removed
+    created.extend(changed_entity_tags(db, &campaign_id, since_ts).await?);
+
+    // Tombstone triggers only record milliseconds, so a delete in the same
+    // millisecond as `since` would otherwise sort before it and be missed.
+    // Reporting it twice is harmless; deletes apply idempotently.
+    let tombstones_since = since_ts
+        .duration_trunc(TimeDelta::milliseconds(1))
+        .unwrap_or(since_ts);
+    let deleted = Tombstone::find()
+        .filter(tombstones::Column::CampaignId.eq(&campaign_id))
+        .filter(tombstones::Column::DeletedAt.gte(tombstones_since))
+        .order_by_asc(tombstones::Column::DeletedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| EntityChange {
+            entity_type: t.entity_type,
+            entity_id: t.entity_id,
+            name: None,
+            changed_at: t.deleted_at.to_rfc3339(),
+        })
+        .collect();
+
+    Ok(ChangeSetResponse {
+        campaign_id,
+        since,
+        watermark: watermark.to_rfc3339(),
+        created,
+        updated,
+        deleted,
+    })
+}
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_sync_settings_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<SyncSettingsResponse, AppError> {
+    let state = SyncState::find_by_id(&campaign_id).one(db).await?;
+    Ok(match state {
+        Some(state) => state.into(),
+        None => SyncSettingsResponse::disabled(campaign_id),
+    })
+}
+
+/// Create or update a campaign's sync settings. `auth_token` is only
+/// replaced when provided; pass an empty string to clear it.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn update_sync_settings_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    enabled: bool,
+    server_url: Option<String>,
+    auth_token: Option<String>,
+) -> Result<SyncSettingsResponse, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let server_url = server_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    if let Some(url) = &server_url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(AppError::Validation(
+                "server_url: must start with http:// or https://".to_string(),
+            ));
+        }
+    }
+    if enabled && server_url.is_none() {
+        return Err(AppError::Validation(
+            "server_url: required when sync is enabled".to_string(),
+        ));
+    }
+
+    let existing = SyncState::find_by_id(&campaign_id).one(db).await?;
+    let now = Utc::now();
+
+    let state = match existing {
+        Some(existing) => {
+            // A different server has none of our history, so start over
+            let server_changed = existing.server_url != server_url;
+            let mut active: sync_state::ActiveModel = existing.into();
+            active.enabled = Set(enabled);
+            active.server_url = Set(server_url);
+            if let Some(token) = auth_token {
+                active.auth_token = Set(Some(token).filter(|t| !t.is_empty()));
+            }
+            if server_changed {
+                active.last_pulled_watermark = Set(None);
+                active.last_pushed_watermark = Set(None);
+                active.last_synced_at = Set(None);
+            }
+            active.updated_at = Set(now);
+            active.update(db).await?
+        }
+        None => {
+            let model = sync_state::ActiveModel {
+                campaign_id: Set(campaign_id),
+                enabled: Set(enabled),
+                server_url: Set(server_url),
+                auth_token: Set(auth_token.filter(|t| !t.is_empty())),
+                last_pulled_watermark: Set(None),
+                last_pushed_watermark: Set(None),
+                last_synced_at: Set(None),
+                updated_at: Set(now),
+            };
+            model.insert(db).await?
+        }
+    };
+
+    Ok(state.into())
+}
+
+/// Push local changes to the campaign's sync server, then pull and apply
+/// remote ones
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn sync_campaign_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<SyncReport, AppError> {
+    let state = SyncState::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .filter(|state| state.enabled)
+        .ok_or_else(|| {
+            AppError::Validation(format!("Sync is not enabled for campaign {}", campaign_id))
+        })?;
+    let server_url = state.server_url.clone().ok_or_else(|| {
+        AppError::Validation("server_url: required when sync is enabled".to_string())
+    })?;
+    let client = SyncClient::new(&server_url, state.auth_token.clone())?;
+
+    let (local_changes, push_watermark) =
+        collect_local_changes(db, &campaign_id, state.last_pushed_watermark.as_deref()).await?;
+    let pushed = if local_changes.is_empty() {
+        0
+    } else {
+        client.push(&campaign_id, local_changes).await?.accepted
+    };
+
+    let pull = client
+        .pull(&campaign_id, state.last_pulled_watermark.as_deref())
+        .await?;
+    let pulled = pull.changes.len();
+    let outcome = apply_remote_changes(db, &campaign_id, pull.changes).await?;
+
+    // The push watermark was taken before collecting, so local edits made
+    // while syncing are picked up next time. Rows just applied from the
+    // server may be echoed back once, which the server treats as a no-op.
+    let mut active: sync_state::ActiveModel = state.into();
+    active.last_pushed_watermark = Set(Some(push_watermark));
+    active.last_pulled_watermark = Set(Some(pull.watermark));
+    active.last_synced_at = Set(Some(Utc::now()));
+    active.update(db).await?;
+
+    tracing::info!(
+        pushed,
+        pulled,
+        conflicts = outcome.conflicts.len(),
+        "Campaign synced"
+    );
+
+    Ok(SyncReport {
+        pushed,
+        pulled,
+        applied: outcome.upserted,
+        deleted: outcome.deleted,
+        conflicts: outcome.conflicts,
+    })
+}
+
+/// Write the entities changed since `since` to a JSON file at `path`, or
+/// the whole campaign when `since` is omitted. Chaining each export's
+/// watermark into the next gives a series of small nightly diffs.
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn export_changes_since_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    since: Option<String>,
+    path: String,
+) -> Result<ChangeExportSummary, AppError> {
+    let since = since.unwrap_or_else(|| INITIAL_WATERMARK.to_string());
+    let (mut changes, watermark) = collect_local_changes(db, &campaign_id, Some(&since)).await?;
+    changes.sort_by(|a, b| (&a.entity_type, &a.entity_id).cmp(&(&b.entity_type, &b.entity_id)));
+    let deleted = changes
+        .iter()
+        .filter(|c| c.operation == ChangeOperation::Delete)
+        .count();
+    let upserted = changes.len() - deleted;
+
+    let export = ChangeExport {
+        format: CHANGE_EXPORT_FORMAT.to_string(),
+        campaign_id,
+        since: since.clone(),
+        watermark: watermark.clone(),
+        changes,
+    };
+    let json = serde_json::to_string_pretty(&export)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize changes: {}", e)))?;
+    std::fs::write(&path, json + "\n")
+        .map_err(|e| AppError::Internal(format!("Failed to write {}: {}", path, e)))?;
+    tracing::info!(path = %path, upserted, deleted, "Exported campaign changes");
+
+    Ok(ChangeExportSummary {
+        path,
+        since,
+        watermark,
+        upserted,
+        deleted,
+    })
+}
+
+/// Rows of one table modified after `since`, flagged with whether they were
+/// also created after it
+async fn changed_rows(
+    db: &DatabaseConnection,
+    table: &EntityTable,
+    campaign_id: &str,
+    since: DateTime<Utc>,
+) -> Result<Vec<(bool, EntityChange)>, AppError> {
+    let backend = db.get_database_backend();
+    let name_expr = table.name_column.unwrap_or("NULL");
+    let modified = table.modified_column();
+
+    let sql = format!(
+        r#"
+        SELECT
+            id,
+            {name_expr} as name,
+            CAST({modified} AS TEXT) as changed_at,
+            created_at > $1 as is_new
+        FROM {table}
+        WHERE campaign_id = $2
+        AND {modified} > $1
+        ORDER BY {modified}
+        "#,
+        table = table.table,
+    );
+
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            backend,
+            &sql,
+            [since.into(), campaign_id.into()],
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|row| {
+            let is_new: bool = row.try_get("", "is_new").ok()?;
+            Some((
+                is_new,
+                EntityChange {
+                    entity_type: table.entity_type.to_string(),
+                    entity_id: row.try_get("", "id").ok()?,
+                    name: row.try_get("", "name").ok()?,
+                    changed_at: row.try_get("", "changed_at").ok()?,
+                },
+            ))
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+/// Entity tags added after `since`. They have no `campaign_id` of their own
+/// and reach it through the tag; ids take the form the sync rows expect.
+async fn changed_entity_tags(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    since: DateTime<Utc>,
+) -> Result<Vec<EntityChange>, AppError> {
+    let sql = r#"
+        SELECT
+            et.tag_id || ':' || et.entity_type || ':' || et.entity_id as id,
+            t.name as name,
+            CAST(et.created_at AS TEXT) as changed_at
+        FROM entity_tags et
+        JOIN tags t ON t.id = et.tag_id
+        WHERE t.campaign_id = $2
+        AND et.created_at > $1
+        ORDER BY et.created_at
+    "#;
+
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            sql,
+            [since.into(), campaign_id.into()],
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|row| {
+            Some(EntityChange {
+                entity_type: ENTITY_TAG.to_string(),
+                entity_id: row.try_get("", "id").ok()?,
+                name: row.try_get("", "name").ok()?,
+                changed_at: row.try_get("", "changed_at").ok()?,
+            })
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, AppError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|ts| ts.with_timezone(&Utc))
+        .map_err(|e| AppError::Validation(format!("Invalid timestamp '{}': {}", value, e)))
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_changes_since(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    since: String,
+) -> Result<ChangeSetResponse, AppError> {
+    get_changes_since_impl(&state.db, campaign_id, since).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_changes_since(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    since: Option<String>,
+    path: String,
+) -> Result<ChangeExportSummary, AppError> {
+    export_changes_since_impl(&state.db, campaign_id, since, path).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_sync_settings(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<SyncSettingsResponse, AppError> {
+    get_sync_settings_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_sync_settings(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    enabled: bool,
+    server_url: Option<String>,
+    auth_token: Option<String>,
+) -> Result<SyncSettingsResponse, AppError> {
+    update_sync_settings_impl(&state.db, campaign_id, enabled, server_url, auth_token).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn sync_campaign(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<SyncReport, AppError> {
+    sync_campaign_impl(&state.db, campaign_id).await
+}
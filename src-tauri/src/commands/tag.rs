@@ -4,9 +4,11 @@ use ::entity::entity_tags::{self, Entity as EntityTag};
 use ::entity::tags::{self, Entity as Tag};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tauri::State;
+use tracing::instrument;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TagResponse {
     pub id: String,
     pub campaign_id: String,
@@ -27,8 +29,17 @@ impl From<tags::Model> for TagResponse {
     }
 }
 
+/// Tags attached to one entity, as returned by `get_tags_for_entities`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityTagsResponse {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub tags: Vec<TagResponse>,
+}
+
 // ============ Core implementation functions (testable) ============
 
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn create_tag_impl(
     db: &DatabaseConnection,
     campaign_id: String,
@@ -44,12 +55,15 @@ pub async fn create_tag_impl(
         name: Set(name),
         color: Set(color),
         created_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     let result = model.insert(db).await?;
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn get_tag_impl(db: &DatabaseConnection, id: String) -> Result<TagResponse, AppError> {
     let tag = Tag::find_by_id(&id)
         .one(db)
@@ -59,6 +73,7 @@ pub async fn get_tag_impl(db: &DatabaseConnection, id: String) -> Result<TagResp
     Ok(tag.into())
 }
 
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
 pub async fn list_tags_impl(
     db: &DatabaseConnection,
     campaign_id: String,
@@ -72,11 +87,17 @@ pub async fn list_tags_impl(
     Ok(tags.into_iter().map(|t| t.into()).collect())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
 pub async fn delete_tag_impl(db: &DatabaseConnection, id: String) -> Result<bool, AppError> {
     let result = Tag::delete_by_id(&id).exec(db).await?;
     Ok(result.rows_affected > 0)
 }
 
+#[instrument(
+    skip_all,
+    fields(tag_id = %tag_id, entity_type = %entity_type, entity_id = %entity_id),
+    err
+)]
 pub async fn add_entity_tag_impl(
     db: &DatabaseConnection,
     tag_id: String,
@@ -87,12 +108,18 @@ pub async fn add_entity_tag_impl(
         tag_id: Set(tag_id),
         entity_type: Set(entity_type),
         entity_id: Set(entity_id),
+        created_at: Set(chrono::Utc::now()),
     };
 
     model.insert(db).await?;
     Ok(true)
 }
 
+#[instrument(
+    skip_all,
+    fields(tag_id = %tag_id, entity_type = %entity_type, entity_id = %entity_id),
+    err
+)]
 pub async fn remove_entity_tag_impl(
     db: &DatabaseConnection,
     tag_id: String,
@@ -109,33 +136,72 @@ pub async fn remove_entity_tag_impl(
     Ok(result.rows_affected > 0)
 }
 
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
 pub async fn get_entity_tags_impl(
     db: &DatabaseConnection,
     entity_type: String,
     entity_id: String,
 ) -> Result<Vec<TagResponse>, AppError> {
-    let entity_tag_records = EntityTag::find()
+    let tags = Tag::find()
+        .inner_join(EntityTag)
         .filter(entity_tags::Column::EntityType.eq(&entity_type))
         .filter(entity_tags::Column::EntityId.eq(&entity_id))
+        .order_by_asc(tags::Column::Name)
         .all(db)
         .await?;
 
-    let tag_ids: Vec<String> = entity_tag_records
-        .iter()
-        .map(|et| et.tag_id.clone())
-        .collect();
+    Ok(tags.into_iter().map(|t| t.into()).collect())
+}
 
-    if tag_ids.is_empty() {
-        return Ok(vec![]);
+/// Tags for many entities at once, in the order requested. Entities with no
+/// tags get an empty list.
+#[instrument(skip_all, fields(count = entities.len()), err)]
+pub async fn get_tags_for_entities_impl(
+    db: &DatabaseConnection,
+    entities: Vec<(String, String)>,
+) -> Result<Vec<EntityTagsResponse>, AppError> {
+    let mut ids_by_type: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (entity_type, entity_id) in &entities {
+        ids_by_type
+            .entry(entity_type.as_str())
+            .or_default()
+            .push(entity_id.as_str());
     }
 
-    let tags = Tag::find()
-        .filter(tags::Column::Id.is_in(tag_ids))
-        .order_by_asc(tags::Column::Name)
-        .all(db)
-        .await?;
+    let mut tags_by_entity: HashMap<(String, String), Vec<TagResponse>> = HashMap::new();
+    for (entity_type, ids) in ids_by_type {
+        // Stay well under SQLite's bound parameter limit
+        for chunk in ids.chunks(500) {
+            let rows = EntityTag::find()
+                .find_also_related(Tag)
+                .filter(entity_tags::Column::EntityType.eq(entity_type))
+                .filter(entity_tags::Column::EntityId.is_in(chunk.iter().copied()))
+                .order_by_asc(tags::Column::Name)
+                .all(db)
+                .await?;
 
-    Ok(tags.into_iter().map(|t| t.into()).collect())
+            for (entity_tag, tag) in rows {
+                if let Some(tag) = tag {
+                    tags_by_entity
+                        .entry((entity_tag.entity_type, entity_tag.entity_id))
+                        .or_default()
+                        .push(tag.into());
+                }
+            }
+        }
+    }
+
+    Ok(entities
+        .into_iter()
+        .map(|key| {
+            let tags = tags_by_entity.get(&key).cloned().unwrap_or_default();
+            EntityTagsResponse {
+                entity_type: key.0,
+                entity_id: key.1,
+                tags,
+            }
+        })
+        .collect())
 }
 
 // ============ Tauri command wrappers ============
@@ -196,3 +262,11 @@ pub async fn get_entity_tags(
 ) -> Result<Vec<TagResponse>, AppError> {
     get_entity_tags_impl(&state.db, entity_type, entity_id).await
 }
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_tags_for_entities(
+    state: State<'_, AppState>,
+    entities: Vec<(String, String)>,
+) -> Result<Vec<EntityTagsResponse>, AppError> {
+    get_tags_for_entities_impl(&state.db, entities).await
+}
@@ -0,0 +1,223 @@
+//! Tag suggestions for an entity from its text. Keyword rules run here; the
+//! analyzed text is returned too so the frontend can add an optional AI pass.
+//! Nothing is tagged until the user confirms a suggestion.
+
+use crate::commands::registry::table_for;
+use crate::commands::tag::TagResponse;
+use crate::db::AppState;
+use crate::error::AppError;
+use crate::text::{plain_text, words};
+use ::entity::entity_tags::{self, Entity as EntityTag};
+use ::entity::tags::{self, Entity as Tag};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::State;
+use tracing::instrument;
+
+/// Tags proposed when the text contains one of their keywords
+const KEYWORD_RULES: &[(&str, &str)] = &[
+    (
+        "undead",
+        "undead zombie skeleton vampire lich ghoul wraith necromancer",
+    ),
+    (
+        "noble",
+        "noble duke duchess baron baroness countess king queen prince princess",
+    ),
+    ("criminal", "thief smuggler assassin bandit pirate cutpurse"),
+    (
+        "religious",
+        "temple priest priestess cleric shrine cult deity",
+    ),
+    ("arcane", "wizard sorcerer warlock arcane mage enchanted"),
+    ("merchant", "merchant trader shopkeeper market caravan"),
+    ("military", "soldier army knight garrison fortress legion"),
+    ("dragon", "dragon wyrm drake wyvern"),
+    ("fey", "fey fairy faerie pixie feywild"),
+];
+
+/// A campaign tag the entity's text matches
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExistingTagSuggestion {
+    pub tag: TagResponse,
+    /// The word or phrase in the text that matched
+    pub matched: String,
+}
+
+/// A tag the campaign doesn't have yet
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewTagSuggestion {
+    pub name: String,
+    pub matched: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagSuggestions {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub campaign_id: String,
+    /// Matching campaign tags not yet on the entity, alphabetical
+    pub existing: Vec<ExistingTagSuggestion>,
+    /// Keyword rule tags missing from the campaign, alphabetical
+    pub new_tags: Vec<NewTagSuggestion>,
+    /// The readable text that was analyzed, name first
+    pub text: String,
+}
+
+/// Whether `phrase` appears in `text` as whole words; a trailing plural "s"
+/// on the last word also matches
+fn contains_phrase(text: &[String], phrase: &[String]) -> bool {
+    let Some((last, head)) = phrase.split_last() else {
+        return false;
+    };
+    text.windows(phrase.len()).any(|window| {
+        let (window_last, window_head) = window.split_last().unwrap();
+        window_head == head
+            && (window_last == last
+                || window_last.strip_suffix('s') == Some(last.as_str())
+                || window_last.strip_suffix("es") == Some(last.as_str()))
+    })
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Suggest tags for an entity from its name and free text
+#[instrument(skip_all, fields(entity_type = %entity_type, entity_id = %entity_id), err)]
+pub async fn suggest_tags_impl(
+    db: &DatabaseConnection,
+    entity_type: String,
+    entity_id: String,
+) -> Result<TagSuggestions, AppError> {
+    let table = table_for(&entity_type)
+        .filter(|t| !t.text_columns.is_empty())
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "entity_type: '{}' has no text to analyze",
+                entity_type
+            ))
+        })?;
+
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            format!(
+                "SELECT campaign_id, {} AS display_name, {} FROM {} WHERE id = $1",
+                table.name_column.unwrap_or("NULL"),
+                table.text_columns.join(", "),
+                table.table
+            ),
+            [entity_id.clone().into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} {} not found", entity_type, entity_id)))?;
+    let campaign_id: String = row.try_get("", "campaign_id")?;
+
+    let mut parts: Vec<String> = Vec::new();
+    if let Some(name) = row.try_get::<Option<String>>("", "display_name")? {
+        parts.push(name);
+    }
+    for column in table.text_columns {
+        if let Some(text) = row.try_get::<Option<String>>("", column)? {
+            let text = plain_text(&text);
+            if !text.trim().is_empty() {
+                parts.push(text.trim().to_string());
+            }
+        }
+    }
+    let text = parts.join("\n");
+    let text_words = words(&text);
+
+    let applied: HashSet<String> = EntityTag::find()
+        .filter(entity_tags::Column::EntityType.eq(&entity_type))
+        .filter(entity_tags::Column::EntityId.eq(&entity_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|et| et.tag_id)
+        .collect();
+    let campaign_tags = Tag::find()
+        .filter(tags::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(tags::Column::Name)
+        .all(db)
+        .await?;
+
+    // A tag matches on its own name or on one of its rule's keywords
+    let rule_match = |name: &str| {
+        let name = name.to_lowercase();
+        KEYWORD_RULES
+            .iter()
+            .filter(|(tag, _)| *tag == name)
+            .flat_map(|(_, keywords)| keywords.split_whitespace())
+            .find(|&keyword| contains_phrase(&text_words, &[keyword.to_string()]))
+            .map(|keyword| keyword.to_string())
+    };
+
+    let mut existing = Vec::new();
+    let mut taken: HashSet<String> = HashSet::new();
+    for tag in campaign_tags {
+        taken.insert(tag.name.to_lowercase());
+        if applied.contains(&tag.id) {
+            continue;
+        }
+        let matched = if contains_phrase(&text_words, &words(&tag.name)) {
+            Some(tag.name.clone())
+        } else {
+            rule_match(&tag.name)
+        };
+        if let Some(matched) = matched {
+            existing.push(ExistingTagSuggestion {
+                tag: tag.into(),
+                matched,
+            });
+        }
+    }
+
+    let mut new_tags: Vec<NewTagSuggestion> = KEYWORD_RULES
+        .iter()
+        .filter(|(tag, _)| !taken.contains(*tag))
+        .filter_map(|&(tag, _)| {
+            rule_match(tag).map(|matched| NewTagSuggestion {
+                name: tag.to_string(),
+                matched,
+            })
+        })
+        .collect();
+    new_tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(TagSuggestions {
+        entity_type,
+        entity_id,
+        campaign_id,
+        existing,
+        new_tags,
+        text,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn suggest_tags(
+    state: State<'_, AppState>,
+    entity_type: String,
+    entity_id: String,
+) -> Result<TagSuggestions, AppError> {
+    suggest_tags_impl(&state.db, entity_type, entity_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains_phrase_whole_words() {
+        let text = words("The Thieves' Guild hires two smugglers near the docks.");
+        assert!(contains_phrase(&text, &words("thieves' guild")));
+        assert!(contains_phrase(&text, &words("smuggler")));
+        assert!(contains_phrase(&text, &words("Dock")));
+        assert!(!contains_phrase(&text, &words("guilds")));
+        assert!(!contains_phrase(&text, &words("smug")));
+        assert!(!contains_phrase(&text, &[]));
+    }
+}
@@ -1,9 +1,15 @@
+use crate::commands::journal_export::write_export;
+use crate::commands::references::remove_references;
+use crate::commands::timeline_branch::{find_branch, visible_events};
 use crate::db::AppState;
 use crate::error::AppError;
+use crate::export::timeline_svg::{render_timeline_svg, ChartEvent, TimelineEra};
+use ::entity::campaigns::Entity as Campaign;
 use ::entity::timeline_events::{self, Entity as TimelineEvent};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use tracing::instrument;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TimelineEventResponse {
@@ -15,6 +21,8 @@ pub struct TimelineEventResponse {
     pub description: Option<String>,
     pub significance: String,
     pub is_public: bool,
+    /// None for events on the main timeline
+    pub branch_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -30,22 +38,55 @@ impl From<timeline_events::Model> for TimelineEventResponse {
             description: model.description,
             significance: model.significance,
             is_public: model.is_public,
+            branch_id: model.branch_id,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
     }
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub async fn create_timeline_event(
-    state: State<'_, AppState>,
+/// How to draw a timeline chart. Everything is optional.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimelineImageOptions {
+    /// Chart a what-if branch instead of the main timeline
+    pub branch_id: Option<String>,
+    /// Leave out events players haven't been told about
+    pub public_only: bool,
+    /// Width in pixels
+    pub width: Option<u32>,
+    /// Spans drawn as bands behind the events, in sort order units
+    pub eras: Vec<TimelineEra>,
+    /// Also write the SVG here
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimelineImage {
+    pub svg: String,
+    pub event_count: usize,
+    /// Where the SVG was written, if a path was given
+    pub path: Option<String>,
+}
+
+// ============ Core implementation functions (testable) ============
+
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn create_timeline_event_impl(
+    db: &DatabaseConnection,
     campaign_id: String,
     title: String,
     date_display: String,
     sort_order: Option<i64>,
     description: Option<String>,
     significance: Option<String>,
+    branch_id: Option<String>,
 ) -> Result<TimelineEventResponse, AppError> {
+    if let Some(branch_id) = &branch_id {
+        find_branch(db, &campaign_id, branch_id).await?;
+    }
+
     let id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now();
 
@@ -58,44 +99,46 @@ pub async fn create_timeline_event(
         description: Set(description),
         significance: Set(significance.unwrap_or_else(|| "local".to_string())),
         is_public: Set(true),
+        branch_id: Set(branch_id),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
-    let result = model.insert(&state.db).await?;
+    let result = model.insert(db).await?;
     Ok(result.into())
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub async fn get_timeline_event(
-    state: State<'_, AppState>,
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn get_timeline_event_impl(
+    db: &DatabaseConnection,
     id: String,
 ) -> Result<TimelineEventResponse, AppError> {
     let event = TimelineEvent::find_by_id(&id)
-        .one(&state.db)
+        .one(db)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Timeline event {} not found", id)))?;
 
     Ok(event.into())
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub async fn list_timeline_events(
-    state: State<'_, AppState>,
+/// Events as they read on one branch, or on the main timeline when
+/// `branch_id` is None
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_timeline_events_impl(
+    db: &DatabaseConnection,
     campaign_id: String,
+    branch_id: Option<String>,
 ) -> Result<Vec<TimelineEventResponse>, AppError> {
-    let events = TimelineEvent::find()
-        .filter(timeline_events::Column::CampaignId.eq(&campaign_id))
-        .order_by_asc(timeline_events::Column::SortOrder)
-        .all(&state.db)
-        .await?;
-
+    let events = visible_events(db, &campaign_id, branch_id.as_deref()).await?;
     Ok(events.into_iter().map(|e| e.into()).collect())
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub async fn update_timeline_event(
-    state: State<'_, AppState>,
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_timeline_event_impl(
+    db: &DatabaseConnection,
     id: String,
     title: Option<String>,
     date_display: Option<String>,
@@ -105,7 +148,7 @@ pub async fn update_timeline_event(
     is_public: Option<bool>,
 ) -> Result<TimelineEventResponse, AppError> {
     let event = TimelineEvent::find_by_id(&id)
-        .one(&state.db)
+        .one(db)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Timeline event {} not found", id)))?;
 
@@ -131,15 +174,145 @@ pub async fn update_timeline_event(
     }
     active.updated_at = Set(chrono::Utc::now());
 
-    let result = active.update(&state.db).await?;
+    let result = active.update(db).await?;
     Ok(result.into())
 }
 
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_timeline_event_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let txn = db.begin().await?;
+    remove_references(&txn, "timeline_event", &id).await?;
+    let result = TimelineEvent::delete_by_id(&id).exec(&txn).await?;
+    txn.commit().await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Draw the campaign's timeline as an SVG chart, with a lane per
+/// significance
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn export_timeline_image_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    options: TimelineImageOptions,
+) -> Result<TimelineImage, AppError> {
+    let campaign = Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+    if options.eras.iter().any(|era| era.name.trim().is_empty()) {
+        return Err(AppError::Validation(
+            "eras: every era needs a name".to_string(),
+        ));
+    }
+
+    let events: Vec<ChartEvent> = visible_events(db, &campaign_id, options.branch_id.as_deref())
+        .await?
+        .into_iter()
+        .filter(|e| e.is_public || !options.public_only)
+        .map(|e| ChartEvent {
+            title: e.title,
+            date_display: e.date_display,
+            sort_order: e.sort_order,
+            significance: e.significance,
+        })
+        .collect();
+    let svg = render_timeline_svg(&campaign.name, &events, &options.eras, options.width);
+
+    write_export(&options.path, &svg)?;
+    if let Some(path) = &options.path {
+        tracing::info!(path = %path, events = events.len(), "Exported timeline image");
+    }
+
+    Ok(TimelineImage {
+        svg,
+        event_count: events.len(),
+        path: options.path,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_timeline_event(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    title: String,
+    date_display: String,
+    sort_order: Option<i64>,
+    description: Option<String>,
+    significance: Option<String>,
+    branch_id: Option<String>,
+) -> Result<TimelineEventResponse, AppError> {
+    create_timeline_event_impl(
+        &state.db,
+        campaign_id,
+        title,
+        date_display,
+        sort_order,
+        description,
+        significance,
+        branch_id,
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_timeline_event(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<TimelineEventResponse, AppError> {
+    get_timeline_event_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_timeline_events(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    branch_id: Option<String>,
+) -> Result<Vec<TimelineEventResponse>, AppError> {
+    list_timeline_events_impl(&state.db, campaign_id, branch_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_timeline_event(
+    state: State<'_, AppState>,
+    id: String,
+    title: Option<String>,
+    date_display: Option<String>,
+    sort_order: Option<i64>,
+    description: Option<String>,
+    significance: Option<String>,
+    is_public: Option<bool>,
+) -> Result<TimelineEventResponse, AppError> {
+    update_timeline_event_impl(
+        &state.db,
+        id,
+        title,
+        date_display,
+        sort_order,
+        description,
+        significance,
+        is_public,
+    )
+    .await
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn delete_timeline_event(
     state: State<'_, AppState>,
     id: String,
 ) -> Result<bool, AppError> {
-    let result = TimelineEvent::delete_by_id(&id).exec(&state.db).await?;
-    Ok(result.rows_affected > 0)
+    delete_timeline_event_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_timeline_image(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    options: Option<TimelineImageOptions>,
+) -> Result<TimelineImage, AppError> {
+    export_timeline_image_impl(&state.db, campaign_id, options.unwrap_or_default()).await
 }
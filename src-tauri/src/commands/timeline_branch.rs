@@ -0,0 +1,368 @@
+//! Alternate timelines such as prophesied futures or time-travel splits. A
+//! branch forks from the main timeline (or another branch) after one of its
+//! events; reading a branch shows the parent line up to that event followed
+//! by the branch's own events.
+
+use crate::commands::timeline::TimelineEventResponse;
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::timeline_branches::{self, Entity as TimelineBranch};
+use ::entity::timeline_events::{self, Entity as TimelineEvent};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+use tracing::instrument;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimelineBranchResponse {
+    pub id: String,
+    pub campaign_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// None when the branch forks from the main timeline
+    pub parent_branch_id: Option<String>,
+    /// Last parent event the branch shares; None shares all of them
+    pub fork_event_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<timeline_branches::Model> for TimelineBranchResponse {
+    fn from(model: timeline_branches::Model) -> Self {
+        Self {
+            id: model.id,
+            campaign_id: model.campaign_id,
+            name: model.name,
+            description: model.description,
+            parent_branch_id: model.parent_branch_id,
+            fork_event_id: model.fork_event_id,
+            created_at: model.created_at.to_string(),
+            updated_at: model.updated_at.to_string(),
+        }
+    }
+}
+
+/// Two timelines side by side. A None branch id is the main timeline.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimelineComparison {
+    pub left_branch_id: Option<String>,
+    pub right_branch_id: Option<String>,
+    /// Events on both timelines
+    pub shared: Vec<TimelineEventResponse>,
+    pub only_left: Vec<TimelineEventResponse>,
+    pub only_right: Vec<TimelineEventResponse>,
+}
+
+/// Load a branch and check it belongs to `campaign_id`
+pub(crate) async fn find_branch<C: ConnectionTrait>(
+    conn: &C,
+    campaign_id: &str,
+    branch_id: &str,
+) -> Result<timeline_branches::Model, AppError> {
+    let branch = TimelineBranch::find_by_id(branch_id)
+        .one(conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Timeline branch {} not found", branch_id)))?;
+    if branch.campaign_id != campaign_id {
+        return Err(AppError::Validation(
+            "branch_id: belongs to a different campaign".to_string(),
+        ));
+    }
+    Ok(branch)
+}
+
+/// The events of one line, following parent branches back to the main
+/// timeline. A fork event that has since been deleted no longer cuts the
+/// parent line short.
+fn line_events(
+    events: &[timeline_events::Model],
+    branches: &HashMap<String, timeline_branches::Model>,
+    branch_id: Option<&str>,
+    seen: &mut HashSet<String>,
+) -> Vec<timeline_events::Model> {
+    let own = events
+        .iter()
+        .filter(|e| e.branch_id.as_deref() == branch_id)
+        .cloned();
+
+    let Some(branch) = branch_id.and_then(|id| branches.get(id)) else {
+        return own.collect();
+    };
+    // Parents are fixed at creation so cycles shouldn't exist; guard anyway
+    if !seen.insert(branch.id.clone()) {
+        return own.collect();
+    }
+
+    let mut line = line_events(events, branches, branch.parent_branch_id.as_deref(), seen);
+    if let Some(fork) = &branch.fork_event_id {
+        if let Some(pos) = line.iter().position(|e| &e.id == fork) {
+            line.truncate(pos + 1);
+        }
+    }
+    line.extend(own);
+    line.sort_by(|a, b| {
+        a.sort_order
+            .cmp(&b.sort_order)
+            .then(a.created_at.cmp(&b.created_at))
+    });
+    line
+}
+
+/// Events visible on a branch, or on the main timeline when `branch_id` is
+/// None, in timeline order
+pub(crate) async fn visible_events<C: ConnectionTrait>(
+    conn: &C,
+    campaign_id: &str,
+    branch_id: Option<&str>,
+) -> Result<Vec<timeline_events::Model>, AppError> {
+    if let Some(branch_id) = branch_id {
+        find_branch(conn, campaign_id, branch_id).await?;
+    }
+
+    let events = TimelineEvent::find()
+        .filter(timeline_events::Column::CampaignId.eq(campaign_id))
+        .order_by_asc(timeline_events::Column::SortOrder)
+        .order_by_asc(timeline_events::Column::CreatedAt)
+        .all(conn)
+        .await?;
+    let branches: HashMap<String, timeline_branches::Model> = TimelineBranch::find()
+        .filter(timeline_branches::Column::CampaignId.eq(campaign_id))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|b| (b.id.clone(), b))
+        .collect();
+
+    Ok(line_events(
+        &events,
+        &branches,
+        branch_id,
+        &mut HashSet::new(),
+    ))
+}
+
+fn validate_name(name: &str) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::Validation("name: must not be empty".to_string()));
+    }
+    Ok(())
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Fork a new branch from `parent_branch_id` (the main timeline when None)
+/// after `fork_event_id`, which must be on the parent line
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn create_timeline_branch_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    name: String,
+    description: Option<String>,
+    parent_branch_id: Option<String>,
+    fork_event_id: Option<String>,
+) -> Result<TimelineBranchResponse, AppError> {
+    validate_name(&name)?;
+
+    if let Some(fork_event_id) = &fork_event_id {
+        let parent_line = visible_events(db, &campaign_id, parent_branch_id.as_deref()).await?;
+        if !parent_line.iter().any(|e| &e.id == fork_event_id) {
+            return Err(AppError::Validation(
+                "fork_event_id: not an event on the parent timeline".to_string(),
+            ));
+        }
+    } else if let Some(parent_branch_id) = &parent_branch_id {
+        find_branch(db, &campaign_id, parent_branch_id).await?;
+    }
+
+    let now = chrono::Utc::now();
+    let model = timeline_branches::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id),
+        name: Set(name.trim().to_string()),
+        description: Set(description),
+        parent_branch_id: Set(parent_branch_id),
+        fork_event_id: Set(fork_event_id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    let result = model.insert(db).await?;
+    Ok(result.into())
+}
+
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn list_timeline_branches_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<Vec<TimelineBranchResponse>, AppError> {
+    let branches = TimelineBranch::find()
+        .filter(timeline_branches::Column::CampaignId.eq(&campaign_id))
+        .order_by_asc(timeline_branches::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(branches.into_iter().map(|b| b.into()).collect())
+}
+
+/// Rename or re-describe a branch. Where it forks from can't change.
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn update_timeline_branch_impl(
+    db: &DatabaseConnection,
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+) -> Result<TimelineBranchResponse, AppError> {
+    let branch = TimelineBranch::find_by_id(&id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Timeline branch {} not found", id)))?;
+
+    let mut active: timeline_branches::ActiveModel = branch.into();
+
+    if let Some(n) = name {
+        validate_name(&n)?;
+        active.name = Set(n.trim().to_string());
+    }
+    if let Some(d) = description {
+        active.description = Set(Some(d));
+    }
+    active.updated_at = Set(chrono::Utc::now());
+
+    let result = active.update(db).await?;
+    Ok(result.into())
+}
+
+/// Delete a branch along with its events and every branch forked from it
+#[instrument(skip_all, fields(id = %id), err)]
+pub async fn delete_timeline_branch_impl(
+    db: &DatabaseConnection,
+    id: String,
+) -> Result<bool, AppError> {
+    let Some(branch) = TimelineBranch::find_by_id(&id).one(db).await? else {
+        return Ok(false);
+    };
+
+    let txn = db.begin().await?;
+    let branches = TimelineBranch::find()
+        .filter(timeline_branches::Column::CampaignId.eq(&branch.campaign_id))
+        .all(&txn)
+        .await?;
+
+    let mut doomed = Vec::new();
+    let mut pending = vec![branch.id];
+    while let Some(parent) = pending.pop() {
+        pending.extend(
+            branches
+                .iter()
+                .filter(|b| b.parent_branch_id.as_deref() == Some(parent.as_str()))
+                .map(|b| b.id.clone()),
+        );
+        doomed.push(parent);
+    }
+
+    TimelineEvent::delete_many()
+        .filter(timeline_events::Column::BranchId.is_in(doomed.clone()))
+        .exec(&txn)
+        .await?;
+    TimelineBranch::delete_many()
+        .filter(timeline_branches::Column::Id.is_in(doomed))
+        .exec(&txn)
+        .await?;
+    txn.commit().await?;
+
+    Ok(true)
+}
+
+/// Compare two timelines event by event; None selects the main timeline
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn compare_timeline_branches_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+    left_branch_id: Option<String>,
+    right_branch_id: Option<String>,
+) -> Result<TimelineComparison, AppError> {
+    let left = visible_events(db, &campaign_id, left_branch_id.as_deref()).await?;
+    let right = visible_events(db, &campaign_id, right_branch_id.as_deref()).await?;
+
+    let left_ids: HashSet<&str> = left.iter().map(|e| e.id.as_str()).collect();
+    let right_ids: HashSet<&str> = right.iter().map(|e| e.id.as_str()).collect();
+
+    let only_right = right
+        .iter()
+        .filter(|e| !left_ids.contains(e.id.as_str()))
+        .cloned()
+        .map(Into::into)
+        .collect();
+    let (shared, only_left): (Vec<_>, Vec<_>) = left
+        .into_iter()
+        .partition(|e| right_ids.contains(e.id.as_str()));
+
+    Ok(TimelineComparison {
+        left_branch_id,
+        right_branch_id,
+        shared: shared.into_iter().map(Into::into).collect(),
+        only_left: only_left.into_iter().map(Into::into).collect(),
+        only_right,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_timeline_branch(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    name: String,
+    description: Option<String>,
+    parent_branch_id: Option<String>,
+    fork_event_id: Option<String>,
+) -> Result<TimelineBranchResponse, AppError> {
+    create_timeline_branch_impl(
+        &state.db,
+        campaign_id,
+        name,
+        description,
+        parent_branch_id,
+        fork_event_id,
+    )
+    .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_timeline_branches(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<TimelineBranchResponse>, AppError> {
+    list_timeline_branches_impl(&state.db, campaign_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_timeline_branch(
+    state: State<'_, AppState>,
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+) -> Result<TimelineBranchResponse, AppError> {
+    update_timeline_branch_impl(&state.db, id, name, description).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_timeline_branch(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, AppError> {
+    delete_timeline_branch_impl(&state.db, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn compare_timeline_branches(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    left_branch_id: Option<String>,
+    right_branch_id: Option<String>,
+) -> Result<TimelineComparison, AppError> {
+    compare_timeline_branches_impl(&state.db, campaign_id, left_branch_id, right_branch_id).await
+}
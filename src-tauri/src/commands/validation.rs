@@ -45,6 +45,57 @@ pub const QUEST_STATUS: &[&str] = &[
 
 pub const PLOT_TYPES: &[&str] = &["main", "secondary", "side", "background"];
 
+pub const ITEM_TYPES: &[&str] = &[
+    "weapon", "armor", "wondrous", "potion", "scroll", "ring", "wand", "tool", "treasure", "quest",
+    "other",
+];
+
+pub const ITEM_RARITIES: &[&str] = &[
+    "common",
+    "uncommon",
+    "rare",
+    "very_rare",
+    "legendary",
+    "artifact",
+];
+
+pub const SNIPPET_TYPES: &[&str] = &[
+    "read_aloud",
+    "shop",
+    "description",
+    "dialogue",
+    "rules",
+    "other",
+];
+
+pub const PROMPT_CONTEXT_TYPES: &[&str] = &[
+    "general",
+    "character",
+    "location",
+    "organization",
+    "quest",
+    "hero",
+    "item",
+    "session",
+];
+
+pub const TIMES_OF_DAY: &[&str] = &["day", "night"];
+
+pub const SEASONS: &[&str] = &["spring", "summer", "autumn", "winter"];
+
+/// Most mannerisms or catchphrases a character can have
+pub const MAX_ROLEPLAY_CUES: usize = 20;
+/// Longest single mannerism or catchphrase
+pub const MAX_ROLEPLAY_CUE_LEN: usize = 500;
+
+/// Lines are never in the game; veils happen off-screen
+pub const CONTENT_WARNING_SEVERITIES: &[&str] = &["line", "veil"];
+
+/// Most extra keywords a content warning can have
+pub const MAX_WARNING_KEYWORDS: usize = 30;
+/// Longest single keyword
+pub const MAX_WARNING_KEYWORD_LEN: usize = 100;
+
 // ============ Custom Validators ============
 
 fn validate_location_type(value: &str) -> Result<(), ValidationError> {
@@ -87,6 +138,163 @@ fn validate_plot_type(value: &str) -> Result<(), ValidationError> {
     }
 }
 
+fn validate_item_type(value: &str) -> Result<(), ValidationError> {
+    if ITEM_TYPES.contains(&value) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_item_type");
+        error.message = Some(format!("must be one of: {}", ITEM_TYPES.join(", ")).into());
+        Err(error)
+    }
+}
+
+fn validate_item_rarity(value: &str) -> Result<(), ValidationError> {
+    if ITEM_RARITIES.contains(&value) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_item_rarity");
+        error.message = Some(format!("must be one of: {}", ITEM_RARITIES.join(", ")).into());
+        Err(error)
+    }
+}
+
+fn validate_snippet_type(value: &str) -> Result<(), ValidationError> {
+    if SNIPPET_TYPES.contains(&value) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_snippet_type");
+        error.message = Some(format!("must be one of: {}", SNIPPET_TYPES.join(", ")).into());
+        Err(error)
+    }
+}
+
+fn validate_prompt_context_type(value: &str) -> Result<(), ValidationError> {
+    if PROMPT_CONTEXT_TYPES.contains(&value) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_prompt_context_type");
+        error.message = Some(format!("must be one of: {}", PROMPT_CONTEXT_TYPES.join(", ")).into());
+        Err(error)
+    }
+}
+
+/// Empty clears the condition on an encounter entry
+fn validate_time_of_day(value: &str) -> Result<(), ValidationError> {
+    if value.is_empty() || TIMES_OF_DAY.contains(&value) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_time_of_day");
+        error.message = Some(format!("must be one of: {}", TIMES_OF_DAY.join(", ")).into());
+        Err(error)
+    }
+}
+
+/// Empty clears the condition on an encounter entry
+fn validate_season(value: &str) -> Result<(), ValidationError> {
+    if value.is_empty() || SEASONS.contains(&value) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_season");
+        error.message = Some(format!("must be one of: {}", SEASONS.join(", ")).into());
+        Err(error)
+    }
+}
+
+/// Mannerisms or catchphrases: a short list of short lines
+fn validate_roleplay_cues(values: &[String]) -> Result<(), ValidationError> {
+    let valid = values.len() <= MAX_ROLEPLAY_CUES
+        && values
+            .iter()
+            .all(|v| v.chars().count() <= MAX_ROLEPLAY_CUE_LEN);
+    if valid {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_roleplay_cues");
+        error.message = Some(
+            format!(
+                "at most {} entries of up to {} characters each",
+                MAX_ROLEPLAY_CUES, MAX_ROLEPLAY_CUE_LEN
+            )
+            .into(),
+        );
+        Err(error)
+    }
+}
+
+fn validate_content_warning_severity(value: &str) -> Result<(), ValidationError> {
+    if CONTENT_WARNING_SEVERITIES.contains(&value) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_content_warning_severity");
+        error.message =
+            Some(format!("must be one of: {}", CONTENT_WARNING_SEVERITIES.join(", ")).into());
+        Err(error)
+    }
+}
+
+/// Keywords a content warning is scanned for, besides its topic
+fn validate_warning_keywords(values: &[String]) -> Result<(), ValidationError> {
+    let valid = values.len() <= MAX_WARNING_KEYWORDS
+        && values
+            .iter()
+            .all(|v| v.chars().count() <= MAX_WARNING_KEYWORD_LEN);
+    if valid {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_warning_keywords");
+        error.message = Some(
+            format!(
+                "at most {} keywords of up to {} characters each",
+                MAX_WARNING_KEYWORDS, MAX_WARNING_KEYWORD_LEN
+            )
+            .into(),
+        );
+        Err(error)
+    }
+}
+
+/// Longest icon name accepted
+pub const MAX_ICON_LEN: usize = 64;
+
+/// A hex color, `#rgb` or `#rrggbb`. Empty clears the color.
+fn validate_color(value: &str) -> Result<(), ValidationError> {
+    let digits = value.strip_prefix('#').unwrap_or("");
+    if value.is_empty()
+        || (matches!(digits.len(), 3 | 6) && digits.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_color");
+        error.message = Some("must be a hex color like #a0c4ff".into());
+        Err(error)
+    }
+}
+
+/// An icon name such as `map-pin`: lowercase letters, digits and single
+/// hyphens. Empty clears the icon.
+fn validate_icon(value: &str) -> Result<(), ValidationError> {
+    let valid = value.len() <= MAX_ICON_LEN
+        && !value.starts_with('-')
+        && !value.ends_with('-')
+        && !value.contains("--")
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_icon");
+        error.message = Some(
+            format!(
+                "must be lowercase letters, digits and hyphens (max {} chars)",
+                MAX_ICON_LEN
+            )
+            .into(),
+        );
+        Err(error)
+    }
+}
+
 // ============ Input Structs ============
 
 /// Input for creating a character
@@ -134,6 +342,18 @@ pub struct CreateLocationInput {
 
     #[validate(length(max = 50000, message = "description too long"))]
     pub description: Option<String>,
+
+    #[validate(range(min = 0, message = "population cannot be negative"))]
+    pub population: Option<i64>,
+
+    #[validate(length(max = 100, message = "government_type too long"))]
+    pub government_type: Option<String>,
+
+    /// Organization that runs the place
+    pub dominant_organization_id: Option<String>,
+
+    #[validate(length(max = 50000, message = "economy_notes too long"))]
+    pub economy_notes: Option<String>,
 }
 
 /// Input for creating an organization
@@ -181,6 +401,170 @@ pub struct CreateQuestInput {
     pub objectives: Option<String>,
 }
 
+/// Input for creating an item
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateItemInput {
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: String,
+
+    pub campaign_id: String,
+
+    #[validate(custom(function = "validate_item_type"))]
+    pub item_type: String,
+
+    #[validate(custom(function = "validate_item_rarity"))]
+    pub rarity: Option<String>,
+
+    #[validate(length(max = 50000, message = "description too long"))]
+    pub description: Option<String>,
+
+    pub requires_attunement: bool,
+}
+
+/// Input for creating a snippet
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateSnippetInput {
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: String,
+
+    pub campaign_id: String,
+
+    #[validate(custom(function = "validate_snippet_type"))]
+    pub snippet_type: String,
+
+    #[validate(length(max = 50000, message = "content too long"))]
+    pub content: Option<String>,
+}
+
+/// Input for creating a prompt template
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePromptTemplateInput {
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: String,
+
+    pub campaign_id: String,
+
+    #[validate(length(min = 1, max = 20000, message = "template must be 1-20000 characters"))]
+    pub template: String,
+
+    #[validate(custom(function = "validate_prompt_context_type"))]
+    pub context_type: String,
+}
+
+/// Input for creating a handout
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateHandoutInput {
+    #[validate(length(min = 1, max = 200, message = "title must be 1-200 characters"))]
+    pub title: String,
+
+    pub campaign_id: String,
+
+    #[validate(length(max = 50000, message = "content too long"))]
+    pub content: Option<String>,
+
+    pub secret_id: Option<String>,
+}
+
+/// Input for creating a recall card
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateRecallCardInput {
+    pub campaign_id: String,
+
+    #[validate(length(min = 1, max = 500, message = "front must be 1-500 characters"))]
+    pub front: String,
+
+    #[validate(length(min = 1, max = 5000, message = "back must be 1-5000 characters"))]
+    pub back: String,
+
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+}
+
+/// Input for adding an entry to an encounter table
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateEncounterEntryInput {
+    pub table_id: String,
+
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: String,
+
+    pub character_id: Option<String>,
+
+    /// Dice expression such as `2d6` or `1d4+1`, or a plain count
+    #[validate(length(max = 20, message = "quantity must be at most 20 characters"))]
+    pub quantity: Option<String>,
+
+    #[validate(range(min = 1, max = 1000, message = "weight must be 1-1000"))]
+    pub weight: Option<i32>,
+
+    #[validate(custom(function = "validate_time_of_day"))]
+    pub time_of_day: Option<String>,
+
+    #[validate(custom(function = "validate_season"))]
+    pub season: Option<String>,
+
+    #[validate(length(max = 5000, message = "notes too long"))]
+    pub notes: Option<String>,
+}
+
+/// Input for adding a price to the campaign's price list
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePriceInput {
+    pub campaign_id: String,
+
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 100, message = "category must be 1-100 characters"))]
+    pub category: String,
+
+    /// In the campaign's smallest coin, e.g. copper pieces
+    #[validate(range(min = 0, message = "base_price must not be negative"))]
+    pub base_price: i32,
+
+    pub item_id: Option<String>,
+
+    #[validate(length(max = 5000, message = "notes too long"))]
+    pub notes: Option<String>,
+}
+
+/// Input for adding a line or veil to a campaign
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateContentWarningInput {
+    pub campaign_id: String,
+
+    #[validate(length(min = 1, max = 200, message = "topic must be 1-200 characters"))]
+    pub topic: String,
+
+    /// One of `CONTENT_WARNING_SEVERITIES`
+    #[validate(custom(function = "validate_content_warning_severity"))]
+    pub severity: String,
+
+    /// Other words that signal the topic, e.g. "arachnid" for spiders
+    #[serde(default)]
+    #[validate(custom(function = "validate_warning_keywords"))]
+    pub keywords: Vec<String>,
+
+    #[validate(length(max = 5000, message = "notes too long"))]
+    pub notes: Option<String>,
+}
+
+/// Input for leaving a comment on an entity or one of its fields
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateAnnotationInput {
+    pub entity_type: String,
+    pub entity_id: String,
+
+    /// Name or text column commented on; none for the entity as a whole
+    pub field: Option<String>,
+
+    #[validate(length(min = 1, max = 5000, message = "body must be 1-5000 characters"))]
+    pub body: String,
+
+    #[validate(length(max = 100, message = "author too long (max 100 chars)"))]
+    pub author: Option<String>,
+}
+
 // ============ Update Input Structs ============
 
 /// Input for updating a character (all fields optional)
@@ -212,7 +596,7 @@ pub struct UpdateCharacterInput {
 }
 
 /// Input for updating a location
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Default, Deserialize, Validate)]
 pub struct UpdateLocationInput {
     #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
     pub name: Option<String>,
@@ -226,6 +610,18 @@ pub struct UpdateLocationInput {
 
     #[validate(length(max = 50000, message = "gm_notes too long"))]
     pub gm_notes: Option<String>,
+
+    #[validate(range(min = 0, message = "population cannot be negative"))]
+    pub population: Option<i64>,
+
+    #[validate(length(max = 100, message = "government_type too long"))]
+    pub government_type: Option<String>,
+
+    /// Organization that runs the place
+    pub dominant_organization_id: Option<String>,
+
+    #[validate(length(max = 50000, message = "economy_notes too long"))]
+    pub economy_notes: Option<String>,
 }
 
 impl UpdateLocationInput {
@@ -333,6 +729,162 @@ impl UpdateQuestInput {
     }
 }
 
+/// Input for updating an item
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateItemInput {
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: Option<String>,
+
+    #[validate(custom(function = "validate_item_type"))]
+    pub item_type: Option<String>,
+
+    #[validate(custom(function = "validate_item_rarity"))]
+    pub rarity: Option<String>,
+
+    #[validate(length(max = 50000, message = "description too long"))]
+    pub description: Option<String>,
+
+    pub requires_attunement: Option<bool>,
+}
+
+/// Input for updating a snippet
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateSnippetInput {
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: Option<String>,
+
+    #[validate(custom(function = "validate_snippet_type"))]
+    pub snippet_type: Option<String>,
+
+    #[validate(length(max = 50000, message = "content too long"))]
+    pub content: Option<String>,
+}
+
+/// Input for updating a prompt template
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePromptTemplateInput {
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: Option<String>,
+
+    #[validate(length(min = 1, max = 20000, message = "template must be 1-20000 characters"))]
+    pub template: Option<String>,
+
+    #[validate(custom(function = "validate_prompt_context_type"))]
+    pub context_type: Option<String>,
+}
+
+/// Input for updating a handout
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateHandoutInput {
+    #[validate(length(min = 1, max = 200, message = "title must be 1-200 characters"))]
+    pub title: Option<String>,
+
+    #[validate(length(max = 50000, message = "content too long"))]
+    pub content: Option<String>,
+
+    pub secret_id: Option<String>,
+}
+
+/// Input for updating an encounter entry. Fields left out are kept; empty
+/// strings clear the optional ones.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdateEncounterEntryInput {
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: Option<String>,
+
+    pub character_id: Option<String>,
+
+    #[validate(length(max = 20, message = "quantity must be at most 20 characters"))]
+    pub quantity: Option<String>,
+
+    #[validate(range(min = 1, max = 1000, message = "weight must be 1-1000"))]
+    pub weight: Option<i32>,
+
+    #[validate(custom(function = "validate_time_of_day"))]
+    pub time_of_day: Option<String>,
+
+    #[validate(custom(function = "validate_season"))]
+    pub season: Option<String>,
+
+    #[validate(length(max = 5000, message = "notes too long"))]
+    pub notes: Option<String>,
+}
+
+/// Input for updating a price. Fields left out are kept; empty strings
+/// clear `item_id` and `notes`.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdatePriceInput {
+    #[validate(length(min = 1, max = 200, message = "name must be 1-200 characters"))]
+    pub name: Option<String>,
+
+    #[validate(length(min = 1, max = 100, message = "category must be 1-100 characters"))]
+    pub category: Option<String>,
+
+    #[validate(range(min = 0, message = "base_price must not be negative"))]
+    pub base_price: Option<i32>,
+
+    pub item_id: Option<String>,
+
+    #[validate(length(max = 5000, message = "notes too long"))]
+    pub notes: Option<String>,
+}
+
+/// Input for updating a line or veil. Fields left out are kept; empty
+/// notes or keywords clear them.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdateContentWarningInput {
+    #[validate(length(min = 1, max = 200, message = "topic must be 1-200 characters"))]
+    pub topic: Option<String>,
+
+    #[validate(custom(function = "validate_content_warning_severity"))]
+    pub severity: Option<String>,
+
+    #[validate(custom(function = "validate_warning_keywords"))]
+    pub keywords: Option<Vec<String>>,
+
+    #[validate(length(max = 5000, message = "notes too long"))]
+    pub notes: Option<String>,
+}
+
+/// Input for editing an annotation. Fields left out are kept; an empty
+/// author clears it.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdateAnnotationInput {
+    #[validate(length(min = 1, max = 5000, message = "body must be 1-5000 characters"))]
+    pub body: Option<String>,
+
+    #[validate(length(max = 100, message = "author too long (max 100 chars)"))]
+    pub author: Option<String>,
+}
+
+/// Input for setting a character's roleplay cues. Fields left out are kept;
+/// empty strings and lists clear them.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct RoleplayInput {
+    #[validate(length(max = 200, message = "accent too long (max 200 chars)"))]
+    pub accent: Option<String>,
+
+    #[validate(custom(function = "validate_roleplay_cues"))]
+    pub mannerisms: Option<Vec<String>>,
+
+    #[validate(custom(function = "validate_roleplay_cues"))]
+    pub catchphrases: Option<Vec<String>>,
+
+    #[validate(length(max = 5000, message = "speech_sample too long"))]
+    pub speech_sample: Option<String>,
+}
+
+/// Input for setting an entity's color and icon. Fields left out are kept;
+/// empty strings clear them.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct AppearanceInput {
+    #[validate(custom(function = "validate_color"))]
+    pub color: Option<String>,
+
+    #[validate(custom(function = "validate_icon"))]
+    pub icon: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -377,6 +929,10 @@ mod tests {
             location_type: "settlement".to_string(),
             parent_id: None,
             description: None,
+            population: None,
+            government_type: None,
+            dominant_organization_id: None,
+            economy_notes: None,
         };
         assert!(input.validate().is_ok());
     }
@@ -389,6 +945,10 @@ mod tests {
             location_type: "invalid_type".to_string(),
             parent_id: None,
             description: None,
+            population: None,
+            government_type: None,
+            dominant_organization_id: None,
+            economy_notes: None,
         };
         assert!(input.validate().is_err());
     }
@@ -433,4 +993,41 @@ mod tests {
         };
         assert!(input.validate().is_err());
     }
+
+    #[test]
+    fn test_create_item_invalid_rarity() {
+        let input = CreateItemInput {
+            name: "Vorpal Sword".to_string(),
+            campaign_id: "test-campaign".to_string(),
+            item_type: "weapon".to_string(),
+            rarity: Some("mythic".to_string()),
+            description: None,
+            requires_attunement: true,
+        };
+        assert!(input.validate().is_err());
+    }
+
+    #[test]
+    fn test_appearance_validation() {
+        for (color, icon) in [("#a0c4ff", "map-pin"), ("#FFF", "crown"), ("", "")] {
+            let input = AppearanceInput {
+                color: Some(color.to_string()),
+                icon: Some(icon.to_string()),
+            };
+            assert!(input.validate().is_ok(), "{} {}", color, icon);
+        }
+        for (color, icon) in [
+            ("red", None),
+            ("#12345", None),
+            ("#a0c4ff", Some("Map Pin")),
+        ] {
+            let input = AppearanceInput {
+                color: Some(color.to_string()),
+                icon: icon.map(str::to_string),
+            };
+            assert!(input.validate().is_err(), "{} {:?}", color, icon);
+        }
+        assert!(validate_icon("-pin").is_err());
+        assert!(validate_icon(&"a".repeat(MAX_ICON_LEN + 1)).is_err());
+    }
 }
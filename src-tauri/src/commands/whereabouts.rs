@@ -0,0 +1,283 @@
+//! Where characters are right now. Each character has at most one
+//! whereabouts row naming the location they're at, since when in-world,
+//! and anything worth remembering about the visit.
+
+use crate::commands::campaign_settings::{
+    get_campaign_settings_impl, CalendarSettings, InWorldDate,
+};
+use crate::commands::character::{date_from_columns, date_to_columns};
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::characters::{self, Entity as Character};
+use ::entity::locations::{self, Entity as Location};
+use ::entity::whereabouts::{self, Entity as Whereabouts};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::instrument;
+
+/// A character's current location
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WhereaboutsResponse {
+    pub id: String,
+    pub character_id: String,
+    pub character_name: String,
+    pub location_id: String,
+    pub location_name: String,
+    pub since: Option<InWorldDate>,
+    /// `since` formatted with the campaign calendar
+    pub since_display: Option<String>,
+    pub notes: Option<String>,
+    pub updated_at: String,
+}
+
+async fn find_character<C: ConnectionTrait>(
+    conn: &C,
+    id: &str,
+) -> Result<characters::Model, AppError> {
+    Character::find_by_id(id)
+        .one(conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", id)))
+}
+
+async fn find_location<C: ConnectionTrait>(
+    conn: &C,
+    id: &str,
+) -> Result<locations::Model, AppError> {
+    Location::find_by_id(id)
+        .one(conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Location {} not found", id)))
+}
+
+fn response(
+    row: whereabouts::Model,
+    character_name: String,
+    location_name: String,
+    calendar: &CalendarSettings,
+) -> WhereaboutsResponse {
+    let since = date_from_columns(row.since_year, row.since_month, row.since_day);
+    WhereaboutsResponse {
+        id: row.id,
+        character_id: row.character_id,
+        character_name,
+        location_id: row.location_id,
+        location_name,
+        since_display: since.map(|date| calendar.format_date(&date)),
+        since,
+        notes: row.notes,
+        updated_at: row.updated_at.to_string(),
+    }
+}
+
+async fn campaign_calendar(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+) -> Result<CalendarSettings, AppError> {
+    Ok(get_campaign_settings_impl(db, campaign_id.to_string())
+        .await?
+        .calendar)
+}
+
+/// Move whereabouts from one character to another when merging duplicates.
+/// A target that already has whereabouts keeps its own.
+pub(crate) async fn merge_whereabouts<C: ConnectionTrait>(
+    conn: &C,
+    source_id: &str,
+    target_id: &str,
+) -> Result<(), AppError> {
+    let target_has_whereabouts = Whereabouts::find()
+        .filter(whereabouts::Column::CharacterId.eq(target_id))
+        .one(conn)
+        .await?
+        .is_some();
+
+    for row in Whereabouts::find()
+        .filter(whereabouts::Column::CharacterId.eq(source_id))
+        .all(conn)
+        .await?
+    {
+        if target_has_whereabouts {
+            Whereabouts::delete_by_id(row.id).exec(conn).await?;
+        } else {
+            let mut active: whereabouts::ActiveModel = row.into();
+            active.character_id = Set(target_id.to_string());
+            active.updated_at = Set(chrono::Utc::now());
+            active.update(conn).await?;
+        }
+    }
+    Ok(())
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Put a character at a location, replacing wherever they were before.
+/// `since` defaults to the campaign's current in-world date.
+#[instrument(skip_all, fields(character_id = %character_id, location_id = %location_id), err)]
+pub async fn move_character_impl(
+    db: &DatabaseConnection,
+    character_id: String,
+    location_id: String,
+    since: Option<InWorldDate>,
+    notes: Option<String>,
+) -> Result<WhereaboutsResponse, AppError> {
+    let character = find_character(db, &character_id).await?;
+    let location = find_location(db, &location_id).await?;
+    if location.campaign_id != character.campaign_id {
+        return Err(AppError::Validation(
+            "location_id: location belongs to a different campaign".to_string(),
+        ));
+    }
+
+    let calendar = campaign_calendar(db, &character.campaign_id).await?;
+    if let Some(date) = &since {
+        if let Some(error) = calendar.check_date(date, "since") {
+            return Err(AppError::InvalidFields(vec![error]));
+        }
+    }
+    let (since_year, since_month, since_day) = date_to_columns(since.or(calendar.today));
+    let now = chrono::Utc::now();
+
+    let existing = Whereabouts::find()
+        .filter(whereabouts::Column::CharacterId.eq(&character_id))
+        .one(db)
+        .await?;
+    let row = match existing {
+        Some(existing) => {
+            let mut active: whereabouts::ActiveModel = existing.into();
+            active.location_id = Set(location_id);
+            active.since_year = Set(since_year);
+            active.since_month = Set(since_month);
+            active.since_day = Set(since_day);
+            active.notes = Set(notes);
+            active.updated_at = Set(now);
+            active.update(db).await?
+        }
+        None => {
+            whereabouts::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                campaign_id: Set(character.campaign_id),
+                character_id: Set(character_id),
+                location_id: Set(location_id),
+                since_year: Set(since_year),
+                since_month: Set(since_month),
+                since_day: Set(since_day),
+                notes: Set(notes),
+                created_at: Set(now),
+                updated_at: Set(now),
+                created_by: Set(None),
+                modified_by: Set(None),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+
+    Ok(response(row, character.name, location.name, &calendar))
+}
+
+/// Where a character is, or None when nobody has said
+#[instrument(skip_all, fields(character_id = %character_id), err)]
+pub async fn get_character_whereabouts_impl(
+    db: &DatabaseConnection,
+    character_id: String,
+) -> Result<Option<WhereaboutsResponse>, AppError> {
+    let character = find_character(db, &character_id).await?;
+    let found = Whereabouts::find()
+        .filter(whereabouts::Column::CharacterId.eq(&character_id))
+        .find_also_related(Location)
+        .one(db)
+        .await?;
+
+    match found {
+        Some((row, Some(location))) => {
+            let calendar = campaign_calendar(db, &character.campaign_id).await?;
+            Ok(Some(response(
+                row,
+                character.name,
+                location.name,
+                &calendar,
+            )))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Forget where a character is
+#[instrument(skip_all, fields(character_id = %character_id), err)]
+pub async fn clear_character_whereabouts_impl(
+    db: &DatabaseConnection,
+    character_id: String,
+) -> Result<bool, AppError> {
+    let result = Whereabouts::delete_many()
+        .filter(whereabouts::Column::CharacterId.eq(&character_id))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Who is at a location right now, sorted by name
+#[instrument(skip_all, fields(location_id = %location_id), err)]
+pub async fn list_characters_at_location_impl(
+    db: &DatabaseConnection,
+    location_id: String,
+) -> Result<Vec<WhereaboutsResponse>, AppError> {
+    let location = find_location(db, &location_id).await?;
+    let rows = Whereabouts::find()
+        .filter(whereabouts::Column::LocationId.eq(&location_id))
+        .find_also_related(Character)
+        .order_by_asc(characters::Column::Name)
+        .all(db)
+        .await?;
+
+    let calendar = campaign_calendar(db, &location.campaign_id).await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(row, character)| {
+            Some(response(
+                row,
+                character?.name,
+                location.name.clone(),
+                &calendar,
+            ))
+        })
+        .collect())
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn move_character(
+    state: State<'_, AppState>,
+    character_id: String,
+    location_id: String,
+    since: Option<InWorldDate>,
+    notes: Option<String>,
+) -> Result<WhereaboutsResponse, AppError> {
+    move_character_impl(&state.db, character_id, location_id, since, notes).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_character_whereabouts(
+    state: State<'_, AppState>,
+    character_id: String,
+) -> Result<Option<WhereaboutsResponse>, AppError> {
+    get_character_whereabouts_impl(&state.db, character_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn clear_character_whereabouts(
+    state: State<'_, AppState>,
+    character_id: String,
+) -> Result<bool, AppError> {
+    clear_character_whereabouts_impl(&state.db, character_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_characters_at_location(
+    state: State<'_, AppState>,
+    location_id: String,
+) -> Result<Vec<WhereaboutsResponse>, AppError> {
+    list_characters_at_location_impl(&state.db, location_id).await
+}
@@ -0,0 +1,213 @@
+//! A to-do list for worldbuilding: entities nothing connects to, untagged or
+//! undescribed entities, locations floating outside the map hierarchy and
+//! quests with no objectives. Each check is a column of one SQL query per
+//! entity type.
+
+use crate::db::AppState;
+use crate::error::AppError;
+use ::entity::campaigns::Entity as Campaign;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+use tracing::instrument;
+
+/// Gap kinds and how much each adds to an entity's priority
+pub const GAP_WEIGHTS: &[(&str, u32)] = &[
+    ("no_relationships", 3),
+    ("empty_description", 3),
+    ("no_objectives", 3),
+    ("no_tags", 1),
+    ("no_parent", 1),
+];
+
+/// A gap check, evaluated against the row aliased `t`
+enum Check {
+    /// The text column has nothing written in it
+    Empty(&'static str),
+    /// Any other SQL condition
+    Sql(&'static str),
+}
+
+struct GapTable {
+    entity_type: &'static str,
+    table: &'static str,
+    checks: &'static [(&'static str, Check)],
+}
+
+const GAP_TABLES: &[GapTable] = &[
+    GapTable {
+        entity_type: "character",
+        table: "characters",
+        checks: &[("empty_description", Check::Empty("t.description"))],
+    },
+    GapTable {
+        entity_type: "location",
+        table: "locations",
+        checks: &[
+            ("empty_description", Check::Empty("t.description")),
+            // A top-level location with children is a region, not a stray
+            (
+                "no_parent",
+                Check::Sql(
+                    "t.parent_id IS NULL \
+                     AND NOT EXISTS (SELECT 1 FROM locations c WHERE c.parent_id = t.id)",
+                ),
+            ),
+        ],
+    },
+    GapTable {
+        entity_type: "organization",
+        table: "organizations",
+        checks: &[("empty_description", Check::Empty("t.description"))],
+    },
+    GapTable {
+        entity_type: "quest",
+        table: "quests",
+        checks: &[
+            ("empty_description", Check::Empty("t.description")),
+            ("no_objectives", Check::Empty("t.objectives")),
+        ],
+    },
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GapEntry {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub name: String,
+    /// Gap kinds from `GAP_WEIGHTS`
+    pub gaps: Vec<String>,
+    /// Sum of the gaps' weights; higher needs attention sooner
+    pub priority: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorldbuildingGaps {
+    pub campaign_id: String,
+    /// Entities with each gap kind
+    pub counts: BTreeMap<String, u64>,
+    /// Entities with at least one gap, highest priority first
+    pub entities: Vec<GapEntry>,
+}
+
+impl Check {
+    fn sql(&self) -> String {
+        match self {
+            // Rich text JSON with no text nodes is an empty editor
+            Check::Empty(column) => format!(
+                "({column} IS NULL OR TRIM({column}) = '' \
+                 OR ({column} LIKE '{{%' AND instr({column}, '\"text\"') = 0))"
+            ),
+            Check::Sql(condition) => format!("({condition})"),
+        }
+    }
+}
+
+fn weight(kind: &str) -> u32 {
+    GAP_WEIGHTS
+        .iter()
+        .find(|(k, _)| *k == kind)
+        .map_or(0, |(_, w)| *w)
+}
+
+// ============ Core implementation functions (testable) ============
+
+/// Every character, location, organization and quest with a gap, most
+/// urgent first
+#[instrument(skip_all, fields(campaign_id = %campaign_id), err)]
+pub async fn get_worldbuilding_gaps_impl(
+    db: &DatabaseConnection,
+    campaign_id: String,
+) -> Result<WorldbuildingGaps, AppError> {
+    Campaign::find_by_id(&campaign_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+    let mut entities = Vec::new();
+
+    for gap_table in GAP_TABLES {
+        let mut columns = vec![
+            (
+                "no_relationships",
+                "NOT EXISTS (SELECT 1 FROM relationships r \
+                 WHERE (r.source_type = $2 AND r.source_id = t.id) \
+                 OR (r.target_type = $2 AND r.target_id = t.id))"
+                    .to_string(),
+            ),
+            (
+                "no_tags",
+                "NOT EXISTS (SELECT 1 FROM entity_tags et \
+                 WHERE et.entity_type = $2 AND et.entity_id = t.id)"
+                    .to_string(),
+            ),
+        ];
+        columns.extend(
+            gap_table
+                .checks
+                .iter()
+                .map(|(kind, check)| (*kind, check.sql())),
+        );
+
+        let select = columns
+            .iter()
+            .map(|(kind, condition)| format!("{condition} AS {kind}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                format!(
+                    "SELECT t.id, t.name, {select} FROM {} t WHERE t.campaign_id = $1",
+                    gap_table.table
+                ),
+                [campaign_id.clone().into(), gap_table.entity_type.into()],
+            ))
+            .await?;
+
+        for row in rows {
+            let mut gaps = Vec::new();
+            for &(kind, _) in &columns {
+                if row.try_get::<bool>("", kind)? {
+                    gaps.push(kind.to_string());
+                    *counts.entry(kind.to_string()).or_default() += 1;
+                }
+            }
+            if gaps.is_empty() {
+                continue;
+            }
+            entities.push(GapEntry {
+                entity_type: gap_table.entity_type.to_string(),
+                entity_id: row.try_get("", "id")?,
+                name: row.try_get("", "name")?,
+                priority: gaps.iter().map(|g| weight(g)).sum(),
+                gaps,
+            });
+        }
+    }
+
+    entities.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.entity_type.cmp(&b.entity_type))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(WorldbuildingGaps {
+        campaign_id,
+        counts,
+        entities,
+    })
+}
+
+// ============ Tauri command wrappers ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_worldbuilding_gaps(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<WorldbuildingGaps, AppError> {
+    get_worldbuilding_gaps_impl(&state.db, campaign_id).await
+}
@@ -0,0 +1,106 @@
+//! Guarding against databases written by a newer app. Migrations are only
+//! ever added, so a database with a migration this build doesn't know came
+//! from a newer version; opening it anyway would fail later on whichever
+//! command first touches the changed schema.
+
+use crate::error::{AppError, SchemaMismatch};
+use ::entity::app_meta::{self, Entity as AppMeta};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::*;
+use std::collections::HashSet;
+
+/// `app_meta` key holding the version of the app that last opened the database
+pub const APP_VERSION_KEY: &str = "app_version";
+
+pub fn app_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+async fn table_exists<C: ConnectionTrait>(db: &C, table: &str) -> Result<bool, DbErr> {
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "SELECT 1 AS found FROM sqlite_master WHERE type = 'table' AND name = $1",
+            [table.into()],
+        ))
+        .await?;
+    Ok(row.is_some())
+}
+
+/// Version of the app that last opened the database, if it recorded one.
+/// Safe to call before migrations have run.
+pub async fn recorded_app_version<C: ConnectionTrait>(db: &C) -> Result<Option<String>, DbErr> {
+    if !table_exists(db, "app_meta").await? {
+        return Ok(None);
+    }
+    Ok(AppMeta::find_by_id(APP_VERSION_KEY)
+        .one(db)
+        .await?
+        .map(|meta| meta.value))
+}
+
+/// Fail with `IncompatibleDatabase` when the database has migrations this
+/// build doesn't know. A new, empty database is always compatible.
+pub async fn check_schema_compatibility<C: ConnectionTrait>(db: &C) -> Result<(), AppError> {
+    if !table_exists(db, "seaql_migrations").await? {
+        return Ok(());
+    }
+
+    let known: HashSet<String> = Migrator::migrations()
+        .iter()
+        .map(|m| m.name().to_string())
+        .collect();
+    let unknown_migrations: Vec<String> = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT version FROM seaql_migrations ORDER BY version",
+        ))
+        .await?
+        .into_iter()
+        .map(|row| row.try_get::<String>("", "version"))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|version| !known.contains(version))
+        .collect();
+    if unknown_migrations.is_empty() {
+        return Ok(());
+    }
+
+    Err(AppError::IncompatibleDatabase(SchemaMismatch {
+        app_version: app_version().to_string(),
+        written_by: recorded_app_version(db).await?,
+        unknown_migrations,
+    }))
+}
+
+/// Stamp the database with this build's version
+pub async fn record_app_version<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    match AppMeta::find_by_id(APP_VERSION_KEY).one(db).await? {
+        Some(meta) => {
+            let mut active: app_meta::ActiveModel = meta.into();
+            active.value = Set(app_version().to_string());
+            active.updated_at = Set(now);
+            active.update(db).await?;
+        }
+        None => {
+            app_meta::ActiveModel {
+                key: Set(APP_VERSION_KEY.to_string()),
+                value: Set(app_version().to_string()),
+                updated_at: Set(now),
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Refuse databases from a newer app, bring older ones up to date, and
+/// record this build as the last to open it
+pub async fn migrate(db: &DatabaseConnection) -> Result<(), AppError> {
+    check_schema_compatibility(db).await?;
+    Migrator::up(db, None).await?;
+    record_app_version(db).await?;
+    Ok(())
+}
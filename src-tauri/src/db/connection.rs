@@ -1,36 +1,115 @@
-use migration::{Migrator, MigratorTrait};
-use sea_orm::{Database, DatabaseConnection};
+use super::compat::migrate;
+use super::encryption::{finish_pending_encryption, is_encrypted, load_key_from_keychain};
+use super::settings::{connect_with_settings, load_database_settings};
+use crate::commands::json_schema::report_invalid_json_fields;
+use crate::error::{AppError, SchemaMismatch};
+use sea_orm::{ConnectionTrait, DatabaseConnection};
 use std::fs;
-use tauri::Manager;
+use std::path::PathBuf;
+use tauri::{Manager, Runtime};
 
 /// Application state holding the database connection
 pub struct AppState {
     pub db: DatabaseConnection,
 }
 
-/// Initialize the database connection and run migrations
+/// Managed instead of `AppState` when the database was written by a newer
+/// app, so commands can explain why nothing is open
+pub struct RefusedDatabase(pub SchemaMismatch);
+
+/// Location of the campaign database inside the app data directory
+pub fn database_path<R: Runtime, M: Manager<R>>(manager: &M) -> Result<PathBuf, AppError> {
+    let app_dir = manager
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Internal(format!("Failed to resolve app data dir: {}", e)))?;
+
+    // Create the directory if it doesn't exist
+    fs::create_dir_all(&app_dir)
+        .map_err(|e| AppError::Internal(format!("Failed to create app data dir: {}", e)))?;
+
+    Ok(app_dir.join("campaigns.db"))
+}
+
+/// Initialize the database connection and run migrations. Returns `None`
+/// when the database is encrypted and no working key is in the keychain;
+/// the frontend then prompts for the passphrase and calls `unlock_database`.
+/// Also returns `None`, leaving the file untouched, when the database came
+/// from a newer version of the app.
 pub async fn init_database(
     app: &tauri::App,
-) -> Result<DatabaseConnection, Box<dyn std::error::Error>> {
-    // Get app data directory from Tauri
-    let app_dir = app.path().app_data_dir()?;
+) -> Result<Option<DatabaseConnection>, Box<dyn std::error::Error>> {
+    let db_path = database_path(app)?;
+    tracing::info!("Initializing database at: {}", db_path.display());
 
-    // Create the directory if it doesn't exist
-    fs::create_dir_all(&app_dir)?;
+    if finish_pending_encryption(&db_path)? {
+        tracing::info!("Switched to the encrypted copy of the database");
+    }
+
+    let key = if is_encrypted(&db_path)? {
+        match load_key_from_keychain() {
+            Some(key) => Some(key),
+            None => {
+                tracing::info!("Database is encrypted; waiting for passphrase");
+                return Ok(None);
+            }
+        }
+    } else {
+        None
+    };
 
-    // Construct the database path
-    let db_path = app_dir.join("campaigns.db");
+    match open_database(app, key.as_deref()).await {
+        Ok(db) => Ok(Some(db)),
+        Err(AppError::IncompatibleDatabase(mismatch)) => {
+            tracing::error!(?mismatch, "Refusing database from a newer app version");
+            app.manage(RefusedDatabase(mismatch));
+            Ok(None)
+        }
+        // A stale keychain entry shouldn't stop the app, just ask again
+        Err(e) if key.is_some() => {
+            tracing::warn!("Saved database key was rejected: {}", e);
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Connect with the configured pragmas (and key, if any), confirm the
+/// database is readable and not from a newer app, and run migrations
+pub async fn open_database<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    key: Option<&str>,
+) -> Result<DatabaseConnection, AppError> {
+    let db_path = database_path(manager)?;
     let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
 
-    log::info!("Initializing database at: {}", db_path.display());
+    // Connect to the database with the configured pragmas
+    let settings = load_database_settings(manager);
+    tracing::info!(?settings, "Applying database settings");
+    let db = connect_with_settings(&db_url, &settings, key).await?;
 
-    // Connect to the database
-    let db = Database::connect(&db_url).await?;
+    // A wrong key only surfaces on the first real read
+    if db
+        .execute_unprepared("SELECT count(*) FROM sqlite_master")
+        .await
+        .is_err()
+    {
+        let _ = db.close().await;
+        return Err(AppError::Validation(
+            "passphrase: incorrect passphrase".to_string(),
+        ));
+    }
 
     // Run migrations
-    log::info!("Running database migrations...");
-    Migrator::up(&db, None).await?;
-    log::info!("Database migrations complete");
+    tracing::info!("Running database migrations...");
+    if let Err(e) = migrate(&db).await {
+        let _ = db.close().await;
+        return Err(e);
+    }
+    tracing::info!("Database migrations complete");
+
+    // Rows written before JSON columns were validated may need repair
+    report_invalid_json_fields(&db).await;
 
     Ok(db)
 }
@@ -0,0 +1,187 @@
+use crate::error::AppError;
+use sea_orm::sqlx;
+use sea_orm::DatabaseConnection;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Keychain entry holding the database passphrase when the user opts in
+const KEYRING_SERVICE: &str = "loreweaver";
+const KEYRING_ACCOUNT: &str = "database-key";
+
+/// Every plaintext SQLite file starts with this header; SQLCipher files don't
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Passphrases shorter than this are rejected when encrypting
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Whether this build links SQLCipher
+pub const ENCRYPTION_SUPPORTED: bool = cfg!(feature = "sqlcipher");
+
+/// True if the file exists and is not a plaintext SQLite database
+pub fn is_encrypted(path: &Path) -> io::Result<bool> {
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    let mut header = Vec::with_capacity(SQLITE_HEADER.len());
+    fs::File::open(path)?
+        .take(SQLITE_HEADER.len() as u64)
+        .read_to_end(&mut header)?;
+    // An empty file is a database that hasn't been written yet
+    Ok(!header.is_empty() && header != SQLITE_HEADER)
+}
+
+/// Quote a passphrase for use as a `PRAGMA key` value
+pub fn quote_key(passphrase: &str) -> String {
+    format!("'{}'", passphrase.replace('\'', "''"))
+}
+
+/// Where `encrypt_database` writes the encrypted copy until the next launch
+pub fn pending_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push(".encrypted-pending");
+    PathBuf::from(name)
+}
+
+/// Swap in an encrypted copy left by `encrypt_database`, before any
+/// connection to the plaintext file is opened. Returns true if it did.
+pub fn finish_pending_encryption(db_path: &Path) -> io::Result<bool> {
+    let pending = pending_path(db_path);
+    if !pending.exists() {
+        return Ok(false);
+    }
+
+    fs::rename(&pending, db_path)?;
+    // The WAL and shared-memory files belong to the old plaintext database
+    for suffix in ["-wal", "-shm"] {
+        let mut side = db_path.as_os_str().to_owned();
+        side.push(suffix);
+        let side = PathBuf::from(side);
+        if side.exists() {
+            fs::remove_file(side)?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Write an encrypted copy of the open database to `dest`
+pub async fn export_encrypted(
+    db: &DatabaseConnection,
+    dest: &Path,
+    passphrase: &str,
+) -> Result<(), AppError> {
+    if !ENCRYPTION_SUPPORTED {
+        return Err(AppError::Validation(
+            "This build does not include database encryption support".to_string(),
+        ));
+    }
+
+    if dest.exists() {
+        fs::remove_file(dest).map_err(|e| {
+            AppError::Internal(format!("Failed to clear {}: {}", dest.display(), e))
+        })?;
+    }
+
+    // ATTACH can't run inside a transaction and must share a connection with
+    // the export, so borrow one from the pool directly
+    let sql_err = |e: sqlx::Error| AppError::Internal(format!("Encryption failed: {}", e));
+    let mut conn = db
+        .get_sqlite_connection_pool()
+        .acquire()
+        .await
+        .map_err(sql_err)?;
+
+    sqlx::query("ATTACH DATABASE ?1 AS encrypted KEY ?2")
+        .bind(dest.to_string_lossy().to_string())
+        .bind(passphrase)
+        .execute(&mut *conn)
+        .await
+        .map_err(sql_err)?;
+    let exported = sqlx::query("SELECT sqlcipher_export('encrypted')")
+        .execute(&mut *conn)
+        .await
+        .map_err(sql_err);
+    sqlx::query("DETACH DATABASE encrypted")
+        .execute(&mut *conn)
+        .await
+        .map_err(sql_err)?;
+    exported?;
+
+    Ok(())
+}
+
+fn keyring_entry() -> Result<keyring::Entry, AppError> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT)
+        .map_err(|e| AppError::Internal(format!("Keychain unavailable: {}", e)))
+}
+
+/// Passphrase saved in the OS keychain, if any
+pub fn load_key_from_keychain() -> Option<String> {
+    keyring_entry().ok()?.get_password().ok()
+}
+
+pub fn save_key_to_keychain(passphrase: &str) -> Result<(), AppError> {
+    keyring_entry()?
+        .set_password(passphrase)
+        .map_err(|e| AppError::Internal(format!("Failed to save key to keychain: {}", e)))
+}
+
+/// Remove the saved passphrase. Returns false if none was stored.
+pub fn delete_key_from_keychain() -> Result<bool, AppError> {
+    match keyring_entry()?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(AppError::Internal(format!(
+            "Failed to remove key from keychain: {}",
+            e
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir().join(format!("loreweaver-{}.db", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_quote_key_escapes_quotes() {
+        assert_eq!(quote_key("it's"), "'it''s'");
+    }
+
+    #[test]
+    fn test_detects_plaintext_and_encrypted_files() {
+        let path = temp_path();
+        assert!(!is_encrypted(&path).unwrap());
+
+        fs::write(&path, b"SQLite format 3\0rest of the page").unwrap();
+        assert!(!is_encrypted(&path).unwrap());
+
+        fs::write(&path, [0x8a_u8; 64]).unwrap();
+        assert!(is_encrypted(&path).unwrap());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_finish_pending_encryption_replaces_database() {
+        let path = temp_path();
+        fs::write(&path, b"plaintext").unwrap();
+        fs::write(pending_path(&path), b"ciphertext").unwrap();
+        let mut wal = path.as_os_str().to_owned();
+        wal.push("-wal");
+        fs::write(&wal, b"old wal").unwrap();
+
+        assert!(finish_pending_encryption(&path).unwrap());
+        assert_eq!(fs::read(&path).unwrap(), b"ciphertext");
+        assert!(!pending_path(&path).exists());
+        assert!(!PathBuf::from(wal).exists());
+        assert!(!finish_pending_encryption(&path).unwrap());
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -1,3 +1,9 @@
+mod compat;
 mod connection;
+mod encryption;
+mod settings;
 
+pub use compat::*;
 pub use connection::*;
+pub use encryption::*;
+pub use settings::*;
@@ -0,0 +1,180 @@
+use super::encryption::quote_key;
+use crate::error::AppError;
+use sea_orm::sqlx::sqlite::{SqliteJournalMode, SqliteSynchronous};
+use sea_orm::{ConnectOptions, Database, DatabaseConnection, DbErr};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+use tauri::Runtime;
+use tauri_plugin_store::StoreExt;
+
+/// Store file holding user overrides for the connection pragmas
+pub const DATABASE_SETTINGS_STORE: &str = "database-settings.json";
+const PRAGMAS_KEY: &str = "pragmas";
+
+pub const JOURNAL_MODES: &[&str] = &["wal", "delete", "truncate", "persist", "memory", "off"];
+pub const SYNCHRONOUS_MODES: &[&str] = &["off", "normal", "full", "extra"];
+
+/// SQLite pragmas applied to every pooled connection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseSettings {
+    pub journal_mode: String,
+    pub synchronous: String,
+    pub busy_timeout_ms: u64,
+    pub foreign_keys: bool,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        // WAL lets the UI keep reading while long writes (imports, AI batches) run
+        Self {
+            journal_mode: "wal".to_string(),
+            synchronous: "normal".to_string(),
+            busy_timeout_ms: 5000,
+            foreign_keys: true,
+        }
+    }
+}
+
+impl DatabaseSettings {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !JOURNAL_MODES.contains(&self.journal_mode.as_str()) {
+            return Err(AppError::Validation(format!(
+                "journal_mode: must be one of: {}",
+                JOURNAL_MODES.join(", ")
+            )));
+        }
+        if !SYNCHRONOUS_MODES.contains(&self.synchronous.as_str()) {
+            return Err(AppError::Validation(format!(
+                "synchronous: must be one of: {}",
+                SYNCHRONOUS_MODES.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    fn journal_mode(&self) -> SqliteJournalMode {
+        SqliteJournalMode::from_str(&self.journal_mode).unwrap_or(SqliteJournalMode::Wal)
+    }
+
+    fn synchronous(&self) -> SqliteSynchronous {
+        SqliteSynchronous::from_str(&self.synchronous).unwrap_or(SqliteSynchronous::Normal)
+    }
+}
+
+/// Open a connection pool with the given pragmas applied to each connection.
+/// `key` unlocks an SQLCipher database and is applied before anything else.
+pub async fn connect_with_settings(
+    url: &str,
+    settings: &DatabaseSettings,
+    key: Option<&str>,
+) -> Result<DatabaseConnection, DbErr> {
+    let journal_mode = settings.journal_mode();
+    let synchronous = settings.synchronous();
+    let busy_timeout = Duration::from_millis(settings.busy_timeout_ms);
+    let foreign_keys = settings.foreign_keys;
+    let key = key.map(quote_key);
+
+    let mut options = ConnectOptions::new(url);
+    options.map_sqlx_sqlite_opts(move |opts| {
+        let opts = match &key {
+            Some(key) => opts.pragma("key", key.clone()),
+            None => opts,
+        };
+        opts.journal_mode(journal_mode)
+            .synchronous(synchronous)
+            .busy_timeout(busy_timeout)
+            .foreign_keys(foreign_keys)
+    });
+
+    Database::connect(options).await
+}
+
+/// Load saved settings, falling back to defaults if missing or invalid
+pub fn load_database_settings<R: Runtime, M: tauri::Manager<R>>(manager: &M) -> DatabaseSettings {
+    let saved = manager
+        .store(DATABASE_SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get(PRAGMAS_KEY))
+        .and_then(|value| serde_json::from_value::<DatabaseSettings>(value).ok());
+
+    match saved {
+        Some(settings) if settings.validate().is_ok() => settings,
+        Some(_) => {
+            tracing::warn!("Ignoring invalid saved database settings");
+            DatabaseSettings::default()
+        }
+        None => DatabaseSettings::default(),
+    }
+}
+
+pub fn save_database_settings<R: Runtime, M: tauri::Manager<R>>(
+    manager: &M,
+    settings: &DatabaseSettings,
+) -> Result<(), AppError> {
+    let store = manager
+        .store(DATABASE_SETTINGS_STORE)
+        .map_err(|e| AppError::Internal(format!("Failed to open settings store: {}", e)))?;
+    let value = serde_json::to_value(settings)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {}", e)))?;
+
+    store.set(PRAGMAS_KEY, value);
+    store
+        .save()
+        .map_err(|e| AppError::Internal(format!("Failed to save settings: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::maintenance::get_active_pragmas_impl;
+
+    #[test]
+    fn test_defaults_are_valid() {
+        assert!(DatabaseSettings::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_rejects_unknown_journal_mode() {
+        let settings = DatabaseSettings {
+            journal_mode: "sideways".to_string(),
+            ..Default::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_partial_settings_fill_defaults() {
+        let settings: DatabaseSettings =
+            serde_json::from_value(serde_json::json!({ "busy_timeout_ms": 100 })).unwrap();
+        assert_eq!(settings.busy_timeout_ms, 100);
+        assert_eq!(settings.journal_mode, "wal");
+        assert!(settings.foreign_keys);
+    }
+
+    #[tokio::test]
+    async fn test_connect_applies_pragmas() {
+        let path = std::env::temp_dir().join(format!("loreweaver-{}.db", uuid::Uuid::new_v4()));
+        let url = format!("sqlite:{}?mode=rwc", path.display());
+        let settings = DatabaseSettings {
+            busy_timeout_ms: 1234,
+            ..Default::default()
+        };
+
+        let db = connect_with_settings(&url, &settings, None)
+            .await
+            .expect("Failed to connect");
+        let active = get_active_pragmas_impl(&db)
+            .await
+            .expect("Failed to read pragmas");
+
+        assert_eq!(active.journal_mode, "wal");
+        assert_eq!(active.synchronous, "normal");
+        assert_eq!(active.busy_timeout_ms, 1234);
+        assert!(active.foreign_keys);
+
+        db.close().await.expect("Failed to close database");
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,1040 @@
+//! Generates a populated example campaign for onboarding, screenshots and
+//! performance testing. Output is deterministic for a given seed apart from
+//! ids and timestamps.
+
+use crate::export::{insert_bundle, CampaignBundle, BUNDLE_FORMAT_VERSION};
+use ::entity::{
+    campaigns, characters, entity_tags, hero_items, heroes, items, locations, organizations,
+    players, quests, relationships, secrets, sessions, tags, timeline_events,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sea_orm::{ConnectionTrait, DbErr};
+
+pub const DEFAULT_DEMO_NAME: &str = "The Shattered Crown (Demo)";
+pub const DEFAULT_DEMO_SEED: u64 = 0x10AD_5EED;
+/// Upper bound on `scale`, which multiplies the number of generated NPCs,
+/// locations, quests and so on
+pub const MAX_DEMO_SCALE: u32 = 50;
+
+#[derive(Debug, Clone)]
+pub struct DemoOptions {
+    pub name: String,
+    pub scale: u32,
+    pub seed: u64,
+}
+
+impl Default for DemoOptions {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_DEMO_NAME.to_string(),
+            scale: 1,
+            seed: DEFAULT_DEMO_SEED,
+        }
+    }
+}
+
+/// Generate the demo campaign and insert it in one pass
+pub async fn create_demo_campaign<C: ConnectionTrait>(
+    conn: &C,
+    options: &DemoOptions,
+) -> Result<CampaignBundle, DbErr> {
+    let bundle = generate_demo_bundle(options);
+    insert_bundle(conn, bundle.clone()).await?;
+    Ok(bundle)
+}
+
+// ============ Name pools ============
+
+const FIRST_NAMES: &[&str] = &[
+    "Aldric", "Brenna", "Cassian", "Dagny", "Elowen", "Fenwick", "Greta", "Halvard", "Isolde",
+    "Jorund", "Kaelin", "Lysandra", "Maelis", "Nedric", "Orla", "Perrin", "Quilla", "Rowan",
+    "Sabine", "Tobin", "Ulric", "Vesna", "Wendell", "Yara", "Zoran", "Ansel", "Bryony", "Corvin",
+    "Delphine", "Emrys",
+];
+
+const SURNAMES: &[&str] = &[
+    "Ashdown",
+    "Blackthorn",
+    "Coldwater",
+    "Dunmore",
+    "Emberlyn",
+    "Fairweather",
+    "Greyhallow",
+    "Holloway",
+    "Ironwood",
+    "Kestrel",
+    "Larkspur",
+    "Marrow",
+    "Northcott",
+    "Oakheart",
+    "Pell",
+    "Ravenscar",
+    "Stormvale",
+    "Thistledown",
+    "Underhill",
+    "Whitlock",
+];
+
+const LINEAGES: &[&str] = &[
+    "Human",
+    "Human",
+    "Human",
+    "Elf",
+    "Dwarf",
+    "Halfling",
+    "Half-Orc",
+    "Gnome",
+    "Tiefling",
+    "Dragonborn",
+];
+
+const OCCUPATIONS: &[&str] = &[
+    "Blacksmith",
+    "Innkeeper",
+    "Priest",
+    "Guard Captain",
+    "Merchant",
+    "Scholar",
+    "Smuggler",
+    "Noble",
+    "Herbalist",
+    "Bard",
+    "Mercenary",
+    "Fence",
+    "Cartographer",
+    "Alchemist",
+    "Harbormaster",
+];
+
+const PERSONALITIES: &[&str] = &[
+    "Warm but guarded; laughs easily and forgives slowly.",
+    "Blunt to the point of rudeness, fiercely loyal.",
+    "Soft-spoken and watchful, always counting exits.",
+    "Boastful storyteller who embellishes everything.",
+    "Meticulous and anxious about small details.",
+    "Charming, generous, and impossible to pin down.",
+    "Pious and stern, quick to judge outsiders.",
+    "Curious about everything, especially forbidden things.",
+];
+
+const MOTIVATIONS: &[&str] = &[
+    "Restore the family name after a generation of disgrace.",
+    "Pay off a debt owed to dangerous people.",
+    "Protect a younger sibling from the city's gangs.",
+    "Find the relic that was stolen from their temple.",
+    "Earn a seat on the merchant council.",
+    "Avenge a mentor murdered years ago.",
+    "Escape the town before the past catches up.",
+    "Prove the old prophecy is a lie.",
+];
+
+const CHARACTER_SECRETS: &[&str] = &[
+    "Is secretly in the pay of a rival house.",
+    "Witnessed the murder at the old mill and said nothing.",
+    "Carries a cursed coin that whispers at night.",
+    "Is the illegitimate child of a local lord.",
+    "Forged the documents that exiled an innocent family.",
+    "Is slowly turning into something not quite human.",
+];
+
+const VOICES: &[&str] = &[
+    "Slow drawl, ends sentences with a question.",
+    "Clipped military cadence.",
+    "Whispers, leans in close.",
+    "Sing-song, hums between sentences.",
+    "Gravelly, clears throat constantly.",
+];
+
+const REGION_NAMES: &[&str] = &[
+    "The Ashen Marches",
+    "Vale of Thorns",
+    "Saltreach Coast",
+    "The Hollow Downs",
+    "Frostmere",
+    "The Sunken Weald",
+];
+
+const SETTLEMENT_PREFIXES: &[&str] = &[
+    "Brae", "Cinder", "Drift", "Elm", "Gull", "Hearth", "Iron", "Marsh", "Raven", "Stone",
+];
+
+const SETTLEMENT_SUFFIXES: &[&str] = &[
+    "ford", "holm", "wick", "haven", "gate", "moor", "stead", "watch",
+];
+
+const BUILDINGS: &[(&str, &str)] = &[
+    ("The Gilded Goose", "building"),
+    ("Temple of the Dawn", "building"),
+    ("Old Mill", "building"),
+    ("Harbor District", "district"),
+    ("Market Square", "district"),
+    ("Lord's Keep", "building"),
+    ("The Drowned Lantern", "building"),
+    ("Guildhall", "building"),
+];
+
+const WILDS: &[(&str, &str)] = &[
+    ("Whispering Barrow", "landmark"),
+    ("Widow's Peak", "landmark"),
+    ("Blackroot Forest", "wilderness"),
+    ("The Grey Fens", "wilderness"),
+    ("Standing Stones of Orm", "landmark"),
+];
+
+const ORGANIZATIONS: &[(&str, &str)] = &[
+    ("The Crimson Ledger", "mercantile"),
+    ("Order of the Silver Flame", "religion"),
+    ("The Hollow Hand", "criminal"),
+    ("Wardens of the March", "military"),
+    ("House Stormvale", "family"),
+    ("The Quiet Library", "academic"),
+    ("Council of Lanterns", "government"),
+    ("The Veiled Circle", "secret_society"),
+    ("Guild of Tidewrights", "guild"),
+];
+
+const QUEST_HOOKS: &[&str] = &[
+    "Recover the shards of the shattered crown",
+    "Investigate disappearances near the old mill",
+    "Escort a caravan through the fens",
+    "Expose the traitor on the council",
+    "Clear the barrow of restless dead",
+    "Negotiate a truce between rival guilds",
+    "Find the missing heir",
+    "Stop the smuggling ring at the harbor",
+    "Decode the Quiet Library's forbidden tome",
+    "Survive the night of the blood moon",
+    "Retrieve a stolen relic from the temple vault",
+    "Break the curse on the standing stones",
+];
+
+const RELATIONSHIP_TYPES: &[&str] = &[
+    "ally", "rival", "family", "mentor", "enemy", "friend", "employer", "lover",
+];
+
+const TAGS: &[(&str, &str)] = &[
+    ("Villain", "#c0392b"),
+    ("Ally", "#27ae60"),
+    ("Mystery", "#8e44ad"),
+    ("Main Plot", "#d35400"),
+    ("Noble", "#f1c40f"),
+    ("Undead", "#7f8c8d"),
+    ("Merchant", "#16a085"),
+    ("Ancient", "#2c3e50"),
+];
+
+const PLAYERS: &[(&str, &str, &str, &str)] = &[
+    ("Sam", "Thessaly Brightwater", "Half-Elf", "Bard 3"),
+    ("Alex", "Grund Ironfoot", "Dwarf", "Fighter 3"),
+    ("Jordan", "Nyx", "Tiefling", "Warlock 3"),
+    ("Riley", "Pip Underbough", "Halfling", "Rogue 3"),
+];
+
+// ============ Generator ============
+
+/// Small deterministic PRNG (SplitMix64) so demos are reproducible without
+/// pulling in a dependency
+struct DemoRng(u64);
+
+impl DemoRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n.max(1) as u64) as usize
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Build a full demo campaign without touching the database
+pub fn generate_demo_bundle(options: &DemoOptions) -> CampaignBundle {
+    let scale = options.scale.clamp(1, MAX_DEMO_SCALE) as usize;
+    let mut rng = DemoRng(options.seed);
+    let now = Utc::now();
+    let campaign_id = new_id();
+
+    let campaign = campaigns::Model {
+        id: campaign_id.clone(),
+        name: options.name.clone(),
+        description: Some(
+            "The crown of the old kings was broken into seven shards. Factions across the \
+             marches race to reunite it, and the party stands in the middle."
+                .to_string(),
+        ),
+        system: Some("D&D 5e".to_string()),
+        settings_json: None,
+        created_at: now,
+        updated_at: now,
+        archived_at: None,
+        archive_path: None,
+        locked_at: None,
+        created_by: None,
+        modified_by: None,
+    };
+
+    let locations = generate_locations(&mut rng, &campaign_id, scale, now);
+    let characters = generate_characters(&mut rng, &campaign_id, scale, &locations, now);
+    let organizations = generate_organizations(&mut rng, &campaign_id, scale, now);
+    let quests = generate_quests(&mut rng, &campaign_id, scale, now);
+    let (players, heroes) = generate_party(&campaign_id, now);
+    let items = generate_items(&campaign_id, now);
+    let hero_items = generate_inventory(&campaign_id, &heroes, &items, now);
+    let sessions = generate_sessions(&mut rng, &campaign_id, scale, &characters, now);
+    let timeline_events = generate_timeline(&mut rng, &campaign_id, scale, now);
+    let secrets = generate_secrets(
+        &mut rng,
+        &campaign_id,
+        &characters,
+        &organizations,
+        &locations,
+        sessions.len() as i32,
+        now,
+    );
+    let relationships = generate_relationships(
+        &mut rng,
+        &campaign_id,
+        &characters,
+        &organizations,
+        &locations,
+        now,
+    );
+    let (tags, entity_tags) = generate_tags(&mut rng, &campaign_id, &characters, &quests, now);
+
+    CampaignBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        campaign,
+        players,
+        locations,
+        characters,
+        organizations,
+        quests,
+        heroes,
+        items,
+        hero_items,
+        organization_holdings: Vec::new(),
+        whereabouts: Vec::new(),
+        sessions,
+        session_absences: Vec::new(),
+        session_transcripts: Vec::new(),
+        dispositions: Vec::new(),
+        timeline_branches: Vec::new(),
+        timeline_events,
+        secrets,
+        handouts: Vec::new(),
+        handout_deliveries: Vec::new(),
+        snippets: Vec::new(),
+        prompt_templates: Vec::new(),
+        relationships,
+        strength_changes: Vec::new(),
+        recall_cards: Vec::new(),
+        progress_clocks: Vec::new(),
+        encounter_tables: Vec::new(),
+        encounter_entries: Vec::new(),
+        encounter_rolls: Vec::new(),
+        price_list: Vec::new(),
+        price_modifiers: Vec::new(),
+        organization_goals: Vec::new(),
+        goal_advances: Vec::new(),
+        content_warnings: Vec::new(),
+        annotations: Vec::new(),
+        tags,
+        entity_tags,
+    }
+}
+
+fn location(
+    campaign_id: &str,
+    parent_id: Option<&str>,
+    name: String,
+    location_type: &str,
+    description: String,
+    now: DateTime<Utc>,
+) -> locations::Model {
+    locations::Model {
+        id: new_id(),
+        campaign_id: campaign_id.to_string(),
+        parent_id: parent_id.map(str::to_string),
+        name,
+        location_type: location_type.to_string(),
+        description: Some(description),
+        gm_notes: None,
+        color: None,
+        icon: None,
+        created_at: now,
+        updated_at: now,
+        population: None,
+        government_type: None,
+        dominant_organization_id: None,
+        economy_notes: None,
+        created_by: None,
+        modified_by: None,
+    }
+}
+
+fn generate_locations(
+    rng: &mut DemoRng,
+    campaign_id: &str,
+    scale: usize,
+    now: DateTime<Utc>,
+) -> Vec<locations::Model> {
+    let world = location(
+        campaign_id,
+        None,
+        "Aerendal".to_string(),
+        "world",
+        "A weary world of broken kingdoms and long memories.".to_string(),
+        now,
+    );
+    let world_id = world.id.clone();
+    let mut out = vec![world];
+
+    for r in 0..2 * scale {
+        let region_name = if r < REGION_NAMES.len() {
+            REGION_NAMES[r].to_string()
+        } else {
+            format!(
+                "{} {}",
+                REGION_NAMES[r % REGION_NAMES.len()],
+                r / REGION_NAMES.len() + 1
+            )
+        };
+        let region = location(
+            campaign_id,
+            Some(&world_id),
+            region_name.clone(),
+            "region",
+            format!(
+                "{} is contested ground, rich in ruins and rumor.",
+                region_name
+            ),
+            now,
+        );
+        let region_id = region.id.clone();
+        out.push(region);
+
+        for _ in 0..3 {
+            let name = format!(
+                "{}{}",
+                rng.pick(SETTLEMENT_PREFIXES),
+                rng.pick(SETTLEMENT_SUFFIXES)
+            );
+            let settlement = location(
+                campaign_id,
+                Some(&region_id),
+                name.clone(),
+                "settlement",
+                format!(
+                    "A settlement in {} known for its stubborn folk.",
+                    region_name
+                ),
+                now,
+            );
+            let settlement_id = settlement.id.clone();
+            out.push(settlement);
+
+            for _ in 0..2 {
+                let (building, kind) = rng.pick(BUILDINGS);
+                out.push(location(
+                    campaign_id,
+                    Some(&settlement_id),
+                    format!("{} of {}", building, name),
+                    kind,
+                    format!("A well-known spot in {}.", name),
+                    now,
+                ));
+            }
+        }
+
+        let (wild, kind) = rng.pick(WILDS);
+        out.push(location(
+            campaign_id,
+            Some(&region_id),
+            format!("{} ({})", wild, region_name),
+            kind,
+            "Locals avoid it after dark.".to_string(),
+            now,
+        ));
+    }
+
+    out
+}
+
+fn generate_characters(
+    rng: &mut DemoRng,
+    campaign_id: &str,
+    scale: usize,
+    locations: &[locations::Model],
+    now: DateTime<Utc>,
+) -> Vec<characters::Model> {
+    let settlements: Vec<&locations::Model> = locations
+        .iter()
+        .filter(|l| l.location_type == "settlement")
+        .collect();
+
+    (0..36 * scale)
+        .map(|i| {
+            let first = FIRST_NAMES[i % FIRST_NAMES.len()];
+            let last = rng.pick(SURNAMES);
+            let occupation = rng.pick(OCCUPATIONS);
+            let home = rng.pick(&settlements);
+            characters::Model {
+                id: new_id(),
+                campaign_id: campaign_id.to_string(),
+                name: format!("{} {}", first, last),
+                lineage: Some(rng.pick(LINEAGES).to_string()),
+                occupation: Some(occupation.to_string()),
+                is_alive: !rng.chance(8),
+                description: Some(format!(
+                    "A {} of {}, well known around town.",
+                    occupation.to_lowercase(),
+                    home.name
+                )),
+                personality: Some(rng.pick(PERSONALITIES).to_string()),
+                motivations: Some(rng.pick(MOTIVATIONS).to_string()),
+                secrets: rng
+                    .chance(40)
+                    .then(|| rng.pick(CHARACTER_SECRETS).to_string()),
+                voice_notes: Some(rng.pick(VOICES).to_string()),
+                stat_block_json: None,
+                birth_year: None,
+                birth_month: None,
+                birth_day: None,
+                death_year: None,
+                death_month: None,
+                death_day: None,
+                death_event_id: None,
+                aliases_json: None,
+                source_hero_id: None,
+                color: None,
+                icon: None,
+                accent: None,
+                mannerisms_json: None,
+                catchphrases_json: None,
+                speech_sample: None,
+                created_at: now,
+                updated_at: now,
+                created_by: None,
+                modified_by: None,
+            }
+        })
+        .collect()
+}
+
+fn generate_organizations(
+    rng: &mut DemoRng,
+    campaign_id: &str,
+    scale: usize,
+    now: DateTime<Utc>,
+) -> Vec<organizations::Model> {
+    (0..ORGANIZATIONS.len() * scale)
+        .map(|i| {
+            let (name, org_type) = ORGANIZATIONS[i % ORGANIZATIONS.len()];
+            let name = if i < ORGANIZATIONS.len() {
+                name.to_string()
+            } else {
+                format!("{} (Chapter {})", name, i / ORGANIZATIONS.len() + 1)
+            };
+            organizations::Model {
+                id: new_id(),
+                campaign_id: campaign_id.to_string(),
+                name,
+                org_type: org_type.to_string(),
+                description: Some("An influential faction with a long reach.".to_string()),
+                goals: Some(rng.pick(MOTIVATIONS).to_string()),
+                resources: Some("Coin, informants, and a few loyal blades.".to_string()),
+                reputation: Some(
+                    rng.pick(&["Respected", "Feared", "Mistrusted", "Beloved", "Unknown"])
+                        .to_string(),
+                ),
+                secrets: rng
+                    .chance(50)
+                    .then(|| "Secretly seeks a shard of the crown for its own ends.".to_string()),
+                is_active: !rng.chance(10),
+                color: None,
+                icon: None,
+                created_at: now,
+                updated_at: now,
+                created_by: None,
+                modified_by: None,
+            }
+        })
+        .collect()
+}
+
+fn generate_quests(
+    rng: &mut DemoRng,
+    campaign_id: &str,
+    scale: usize,
+    now: DateTime<Utc>,
+) -> Vec<quests::Model> {
+    const STATUSES: &[&str] = &["planned", "available", "active", "completed", "failed"];
+
+    (0..QUEST_HOOKS.len() * scale)
+        .map(|i| {
+            let hook = QUEST_HOOKS[i % QUEST_HOOKS.len()];
+            let status = if i == 0 {
+                "active"
+            } else {
+                *rng.pick(STATUSES)
+            };
+            let plot_type = if i == 0 {
+                "main"
+            } else {
+                *rng.pick(&["secondary", "side", "background"])
+            };
+            quests::Model {
+                id: new_id(),
+                campaign_id: campaign_id.to_string(),
+                name: if i < QUEST_HOOKS.len() {
+                    hook.to_string()
+                } else {
+                    format!("{} ({})", hook, i / QUEST_HOOKS.len() + 1)
+                },
+                status: status.to_string(),
+                plot_type: plot_type.to_string(),
+                description: Some(format!("{}, before someone else does.", hook)),
+                hook: Some("A desperate stranger approaches the party at the inn.".to_string()),
+                objectives: Some("Find the lead\nConfront the culprit\nReport back".to_string()),
+                complications: Some("A rival faction wants the same prize.".to_string()),
+                resolution: (status == "completed")
+                    .then(|| "The party succeeded, though not without cost.".to_string()),
+                reward: Some(format!("{} gold and a favor owed", 50 + rng.below(10) * 25)),
+                color: None,
+                icon: None,
+                created_at: now,
+                updated_at: now,
+                created_by: None,
+                modified_by: None,
+            }
+        })
+        .collect()
+}
+
+fn generate_party(
+    campaign_id: &str,
+    now: DateTime<Utc>,
+) -> (Vec<players::Model>, Vec<heroes::Model>) {
+    PLAYERS
+        .iter()
+        .map(|(player, hero, lineage, classes)| {
+            let player = players::Model {
+                id: new_id(),
+                campaign_id: campaign_id.to_string(),
+                name: player.to_string(),
+                preferences: Some("Enjoys roleplay and puzzles.".to_string()),
+                boundaries: None,
+                notes: None,
+                contact_email: None,
+                contact_handle: None,
+                availability_json: Some(r#"{"weekdays":["fri","sat"]}"#.to_string()),
+                created_at: now,
+                updated_at: now,
+                created_by: None,
+                modified_by: None,
+            };
+            let hero = heroes::Model {
+                id: new_id(),
+                campaign_id: campaign_id.to_string(),
+                player_id: Some(player.id.clone()),
+                name: hero.to_string(),
+                lineage: Some(lineage.to_string()),
+                classes: Some(classes.to_string()),
+                description: Some("A member of the party.".to_string()),
+                backstory: Some("Left home chasing rumors of the shattered crown.".to_string()),
+                goals: None,
+                bonds: None,
+                is_active: true,
+                source_character_id: None,
+                created_at: now,
+                updated_at: now,
+                created_by: None,
+                modified_by: None,
+            };
+            (player, hero)
+        })
+        .unzip()
+}
+
+fn generate_items(campaign_id: &str, now: DateTime<Utc>) -> Vec<items::Model> {
+    const GEAR: &[(&str, &str, &str)] = &[
+        ("Potion of Healing", "potion", "common"),
+        ("Bag of Holding", "wondrous", "uncommon"),
+        ("Cloak of Elvenkind", "wondrous", "uncommon"),
+    ];
+
+    let shards = (1..=7).map(|i| items::Model {
+        id: new_id(),
+        campaign_id: campaign_id.to_string(),
+        name: format!("Shard of the Crown ({} of 7)", i),
+        item_type: "quest".to_string(),
+        rarity: Some("artifact".to_string()),
+        description: Some("A jagged sliver of gold that hums near the other shards.".to_string()),
+        requires_attunement: true,
+        created_at: now,
+        updated_at: now,
+        created_by: None,
+        modified_by: None,
+    });
+    let gear = GEAR.iter().map(|(name, item_type, rarity)| items::Model {
+        id: new_id(),
+        campaign_id: campaign_id.to_string(),
+        name: name.to_string(),
+        item_type: item_type.to_string(),
+        rarity: Some(rarity.to_string()),
+        description: None,
+        requires_attunement: *name == "Cloak of Elvenkind",
+        created_at: now,
+        updated_at: now,
+        created_by: None,
+        modified_by: None,
+    });
+
+    shards.chain(gear).collect()
+}
+
+/// The party starts out holding the first shard and a little gear
+fn generate_inventory(
+    campaign_id: &str,
+    heroes: &[heroes::Model],
+    items: &[items::Model],
+    now: DateTime<Utc>,
+) -> Vec<hero_items::Model> {
+    let holding =
+        |hero: &heroes::Model, item: &items::Model, quantity, attuned| hero_items::Model {
+            id: new_id(),
+            campaign_id: campaign_id.to_string(),
+            hero_id: hero.id.clone(),
+            item_id: item.id.clone(),
+            quantity,
+            equipped: attuned,
+            attuned,
+            created_at: now,
+            updated_at: now,
+            created_by: None,
+            modified_by: None,
+        };
+    let potion = items.iter().find(|i| i.item_type == "potion");
+
+    heroes
+        .iter()
+        .enumerate()
+        .flat_map(|(i, hero)| {
+            let shard = (i == 0).then(|| holding(hero, &items[0], 1, true));
+            let potions = potion.map(|potion| holding(hero, potion, 2, false));
+            shard.into_iter().chain(potions)
+        })
+        .collect()
+}
+
+fn generate_sessions(
+    rng: &mut DemoRng,
+    campaign_id: &str,
+    scale: usize,
+    characters: &[characters::Model],
+    now: DateTime<Utc>,
+) -> Vec<sessions::Model> {
+    let first_date = NaiveDate::from_ymd_opt(2025, 1, 4).expect("valid date");
+
+    (0..6 * scale)
+        .map(|i| {
+            let npc = &rng.pick(characters).name;
+            let date = first_date + Duration::weeks(i as i64);
+            let started_at = date.and_hms_opt(19, 0, 0).expect("valid time").and_utc();
+            let length = Duration::minutes(180 + 15 * rng.below(5) as i64);
+            sessions::Model {
+                id: new_id(),
+                campaign_id: campaign_id.to_string(),
+                session_number: i as i32 + 1,
+                date: Some(date),
+                title: Some(format!("Chapter {}", i + 1)),
+                planned_content: None,
+                notes: Some(format!("Introduced {}.", npc)),
+                summary: Some(format!(
+                    "The party followed a lead that brought them to {}.",
+                    npc
+                )),
+                highlights: Some("A critical hit at exactly the right moment.".to_string()),
+                started_at: Some(started_at),
+                ended_at: Some(started_at + length),
+                breaks_json: None,
+                timeline_event_id: None,
+                created_at: now,
+                updated_at: now,
+                created_by: None,
+                modified_by: None,
+            }
+        })
+        .collect()
+}
+
+fn generate_timeline(
+    rng: &mut DemoRng,
+    campaign_id: &str,
+    scale: usize,
+    now: DateTime<Utc>,
+) -> Vec<timeline_events::Model> {
+    const EVENTS: &[&str] = &[
+        "The crown is shattered",
+        "A comet is seen over the marches",
+        "The plague years begin",
+        "The council of lanterns is founded",
+        "House Stormvale falls from favor",
+        "The harbor burns",
+        "The barrow is sealed",
+        "A treaty is signed at Elmford",
+        "The great flood",
+        "The last king dies without an heir",
+    ];
+    const SEASONS: &[&str] = &["Spring", "Summer", "Autumn", "Winter"];
+
+    (0..20 * scale)
+        .map(|i| {
+            let year = 900 + i * 7;
+            timeline_events::Model {
+                id: new_id(),
+                campaign_id: campaign_id.to_string(),
+                date_display: format!("Year {}, {}", year, rng.pick(SEASONS)),
+                sort_order: i as i64,
+                title: EVENTS[i % EVENTS.len()].to_string(),
+                description: Some("Still spoken of in taverns across the land.".to_string()),
+                significance: rng.pick(&["minor", "moderate", "major"]).to_string(),
+                is_public: rng.chance(70),
+                branch_id: None,
+                created_at: now,
+                updated_at: now,
+                created_by: None,
+                modified_by: None,
+            }
+        })
+        .collect()
+}
+
+fn generate_secrets(
+    rng: &mut DemoRng,
+    campaign_id: &str,
+    characters: &[characters::Model],
+    organizations: &[organizations::Model],
+    locations: &[locations::Model],
+    session_count: i32,
+    now: DateTime<Utc>,
+) -> Vec<secrets::Model> {
+    let count = (characters.len() + organizations.len()) / 3;
+
+    (0..count)
+        .map(|i| {
+            let (entity_type, entity_id, title) = match i % 3 {
+                0 => {
+                    let c = rng.pick(characters);
+                    (
+                        "character",
+                        c.id.clone(),
+                        format!("The truth about {}", c.name),
+                    )
+                }
+                1 => {
+                    let o = rng.pick(organizations);
+                    (
+                        "organization",
+                        o.id.clone(),
+                        format!("What {} hides", o.name),
+                    )
+                }
+                _ => {
+                    let l = rng.pick(locations);
+                    ("location", l.id.clone(), format!("Beneath {}", l.name))
+                }
+            };
+            let revealed = rng.chance(25);
+            secrets::Model {
+                id: new_id(),
+                campaign_id: campaign_id.to_string(),
+                title,
+                content: rng.pick(CHARACTER_SECRETS).to_string(),
+                related_entity_type: Some(entity_type.to_string()),
+                related_entity_id: Some(entity_id),
+                known_by: Some(rng.pick(characters).name.clone()),
+                revealed,
+                revealed_in_session: revealed
+                    .then(|| 1 + rng.below(session_count.max(1) as usize) as i32),
+                prerequisites_json: None,
+                created_at: now,
+                updated_at: now,
+                created_by: None,
+                modified_by: None,
+            }
+        })
+        .collect()
+}
+
+fn generate_relationships(
+    rng: &mut DemoRng,
+    campaign_id: &str,
+    characters: &[characters::Model],
+    organizations: &[organizations::Model],
+    locations: &[locations::Model],
+    now: DateTime<Utc>,
+) -> Vec<relationships::Model> {
+    let relationship = |source_type: &str,
+                        source_id: &str,
+                        target_type: &str,
+                        target_id: &str,
+                        relationship_type: &str,
+                        strength: i32| relationships::Model {
+        id: new_id(),
+        campaign_id: campaign_id.to_string(),
+        source_type: source_type.to_string(),
+        source_id: source_id.to_string(),
+        target_type: target_type.to_string(),
+        target_id: target_id.to_string(),
+        relationship_type: relationship_type.to_string(),
+        description: None,
+        is_bidirectional: matches!(relationship_type, "ally" | "rival" | "family" | "friend"),
+        strength: Some(strength),
+        is_public: strength > 2,
+        created_at: now,
+        updated_at: now,
+        created_by: None,
+        modified_by: None,
+    };
+
+    let settlements: Vec<&locations::Model> = locations
+        .iter()
+        .filter(|l| l.location_type == "settlement")
+        .collect();
+    let mut out = Vec::new();
+
+    for (i, character) in characters.iter().enumerate() {
+        // Each NPC knows a couple of others, belongs somewhere, and may serve a faction
+        for _ in 0..2 {
+            let other = &characters[rng.below(characters.len())];
+            if other.id != character.id {
+                let kind = *rng.pick(RELATIONSHIP_TYPES);
+                out.push(relationship(
+                    "character",
+                    &character.id,
+                    "character",
+                    &other.id,
+                    kind,
+                    1 + rng.below(5) as i32,
+                ));
+            }
+        }
+        out.push(relationship(
+            "character",
+            &character.id,
+            "location",
+            &rng.pick(&settlements).id,
+            "resides_in",
+            3,
+        ));
+        if i % 2 == 0 {
+            out.push(relationship(
+                "character",
+                &character.id,
+                "organization",
+                &rng.pick(organizations).id,
+                if rng.chance(15) { "leader" } else { "member" },
+                1 + rng.below(5) as i32,
+            ));
+        }
+    }
+
+    for organization in organizations {
+        out.push(relationship(
+            "organization",
+            &organization.id,
+            "location",
+            &rng.pick(&settlements).id,
+            "headquartered_in",
+            4,
+        ));
+    }
+
+    out
+}
+
+fn generate_tags(
+    rng: &mut DemoRng,
+    campaign_id: &str,
+    characters: &[characters::Model],
+    quests: &[quests::Model],
+    now: DateTime<Utc>,
+) -> (Vec<tags::Model>, Vec<entity_tags::Model>) {
+    let tags: Vec<tags::Model> = TAGS
+        .iter()
+        .map(|(name, color)| tags::Model {
+            id: new_id(),
+            campaign_id: campaign_id.to_string(),
+            name: name.to_string(),
+            color: Some(color.to_string()),
+            created_at: now,
+            created_by: None,
+            modified_by: None,
+        })
+        .collect();
+
+    let targets = characters
+        .iter()
+        .map(|c| ("character", &c.id))
+        .chain(quests.iter().map(|q| ("quest", &q.id)));
+    let mut entity_tags = Vec::new();
+    for (entity_type, entity_id) in targets {
+        if rng.chance(30) {
+            entity_tags.push(entity_tags::Model {
+                tag_id: rng.pick(&tags).id.clone(),
+                entity_type: entity_type.to_string(),
+                entity_id: entity_id.clone(),
+                created_at: now,
+            });
+        }
+    }
+
+    (tags, entity_tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo_is_deterministic_per_seed() {
+        let options = DemoOptions::default();
+        let a = generate_demo_bundle(&options);
+        let b = generate_demo_bundle(&options);
+
+        let names = |bundle: &CampaignBundle| -> Vec<String> {
+            bundle.characters.iter().map(|c| c.name.clone()).collect()
+        };
+        assert_eq!(names(&a), names(&b));
+        assert_eq!(a.counts(), b.counts());
+    }
+
+    #[test]
+    fn test_demo_scale_multiplies_content() {
+        let small = generate_demo_bundle(&DemoOptions::default());
+        let large = generate_demo_bundle(&DemoOptions {
+            scale: 3,
+            ..Default::default()
+        });
+
+        assert_eq!(small.characters.len(), 36);
+        assert_eq!(large.characters.len(), 108);
+        assert!(large.locations.len() > small.locations.len());
+    }
+}
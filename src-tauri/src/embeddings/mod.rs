@@ -0,0 +1,202 @@
+//! Text embeddings for comparing what entities are about.
+//!
+//! Each backend implements `EmbeddingProvider`; `EmbeddingProviderConfig`
+//! picks one from what the frontend sends, as `image_gen` does, since API
+//! keys live in the frontend's store. Providers only turn text into vectors;
+//! caching them is up to the caller.
+
+pub mod ollama;
+pub mod openai;
+
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+/// Providers that can be named in `EmbeddingProviderConfig::provider`
+pub const EMBEDDING_PROVIDERS: &[&str] = &["openai", "ollama"];
+
+/// A local model embedding a large batch can take a while
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A backend that turns text into vectors
+pub trait EmbeddingProvider {
+    /// Short name, e.g. "openai"
+    fn name(&self) -> &'static str;
+
+    /// Model the vectors come from; vectors from different models are never
+    /// compared
+    fn model(&self) -> &str;
+
+    /// One vector per text, in the order given
+    fn embed(
+        &self,
+        texts: &[String],
+    ) -> impl Future<Output = Result<Vec<Vec<f32>>, AppError>> + Send;
+}
+
+/// Which provider to use and how to reach it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmbeddingProviderConfig {
+    /// One of `EMBEDDING_PROVIDERS`
+    pub provider: String,
+    /// Required for "openai"
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Server address; each provider has its own default
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// Any configured provider, so commands can pick one at runtime
+pub enum ConfiguredEmbeddings {
+    OpenAi(openai::OpenAiEmbeddings),
+    Ollama(ollama::OllamaEmbeddings),
+}
+
+impl ConfiguredEmbeddings {
+    pub fn from_config(config: &EmbeddingProviderConfig) -> Result<Self, AppError> {
+        match config.provider.as_str() {
+            "openai" => {
+                let api_key = config
+                    .api_key
+                    .as_deref()
+                    .filter(|k| !k.trim().is_empty())
+                    .ok_or_else(|| {
+                        AppError::Validation("api_key: required for OpenAI embeddings".to_string())
+                    })?;
+                Ok(Self::OpenAi(openai::OpenAiEmbeddings::new(
+                    api_key,
+                    config.base_url.as_deref(),
+                    config.model.as_deref(),
+                )?))
+            }
+            "ollama" => Ok(Self::Ollama(ollama::OllamaEmbeddings::new(
+                config.base_url.as_deref(),
+                config.model.as_deref(),
+            )?)),
+            _ => Err(AppError::Validation(format!(
+                "provider: must be one of: {}",
+                EMBEDDING_PROVIDERS.join(", ")
+            ))),
+        }
+    }
+}
+
+impl EmbeddingProvider for ConfiguredEmbeddings {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::OpenAi(p) => p.name(),
+            Self::Ollama(p) => p.name(),
+        }
+    }
+
+    fn model(&self) -> &str {
+        match self {
+            Self::OpenAi(p) => p.model(),
+            Self::Ollama(p) => p.model(),
+        }
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, AppError> {
+        match self {
+            Self::OpenAi(p) => p.embed(texts).await,
+            Self::Ollama(p) => p.embed(texts).await,
+        }
+    }
+}
+
+/// Cosine similarity of two vectors, 0 when either is empty or their
+/// lengths differ
+pub fn cosine(a: &[f32], b: &[f32]) -> f64 {
+    if a.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (*x as f64, *y as f64);
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a.sqrt() * norm_b.sqrt())
+    }
+}
+
+/// A vector as stored in `entity_embeddings.vector`
+pub fn vector_to_bytes(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+pub fn vector_from_bytes(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn http_client() -> Result<reqwest::Client, AppError> {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::Internal(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// Fail unless a provider sent back exactly one vector per text
+fn check_count(provider: &str, vectors: &[Vec<f32>], texts: &[String]) -> Result<(), AppError> {
+    if vectors.len() == texts.len() {
+        Ok(())
+    } else {
+        Err(AppError::AiProvider(format!(
+            "{} returned {} embeddings for {} texts",
+            provider,
+            vectors.len(),
+            texts.len()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine() {
+        assert!((cosine(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-9);
+        assert_eq!(cosine(&[1.0, 0.0], &[0.0, 3.0]), 0.0);
+        assert_eq!(cosine(&[1.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn test_vector_bytes_round_trip() {
+        let vector = [0.25, -1.5, 3.0];
+        assert_eq!(vector_from_bytes(&vector_to_bytes(&vector)), vector);
+    }
+
+    #[test]
+    fn test_from_config_requires_openai_key() {
+        let config = EmbeddingProviderConfig {
+            provider: "openai".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ConfiguredEmbeddings::from_config(&config),
+            Err(AppError::Validation(_))
+        ));
+
+        let config = EmbeddingProviderConfig {
+            provider: "word2vec".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ConfiguredEmbeddings::from_config(&config),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+//! A local Ollama server's embed endpoint.
+
+use super::{check_count, http_client, EmbeddingProvider};
+use crate::error::AppError;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Where Ollama listens by default
+pub const DEFAULT_BASE_URL: &str = "http://127.0.0.1:11434";
+pub const DEFAULT_MODEL: &str = "nomic-embed-text";
+
+#[derive(Debug, Deserialize)]
+struct EmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+pub struct OllamaEmbeddings {
+    http: reqwest::Client,
+    base_url: String,
+    model: String,
+}
+
+impl OllamaEmbeddings {
+    pub fn new(base_url: Option<&str>, model: Option<&str>) -> Result<Self, AppError> {
+        Ok(Self {
+            http: http_client()?,
+            base_url: base_url
+                .unwrap_or(DEFAULT_BASE_URL)
+                .trim_end_matches('/')
+                .to_string(),
+            model: model.unwrap_or(DEFAULT_MODEL).to_string(),
+        })
+    }
+}
+
+impl EmbeddingProvider for OllamaEmbeddings {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, AppError> {
+        let response = self
+            .http
+            .post(format!("{}/api/embed", self.base_url))
+            .json(&json!({
+                "model": self.model,
+                "input": texts,
+            }))
+            .send()
+            .await
+            .map_err(|e| AppError::AiProvider(format!("Ollama unreachable: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::AiProvider(format!(
+                "Ollama returned {}: {}",
+                status, body
+            )));
+        }
+
+        let embedded: EmbedResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::AiProvider(format!("Invalid Ollama response: {}", e)))?;
+        check_count("Ollama", &embedded.embeddings, texts)?;
+        Ok(embedded.embeddings)
+    }
+}
@@ -0,0 +1,89 @@
+//! OpenAI's Embeddings API, which OpenAI-compatible servers such as LM
+//! Studio and llama.cpp also implement.
+
+use super::{check_count, http_client, EmbeddingProvider};
+use crate::error::AppError;
+use serde::Deserialize;
+use serde_json::json;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+pub const DEFAULT_MODEL: &str = "text-embedding-3-small";
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+pub struct OpenAiEmbeddings {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAiEmbeddings {
+    pub fn new(
+        api_key: &str,
+        base_url: Option<&str>,
+        model: Option<&str>,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            http: http_client()?,
+            base_url: base_url
+                .unwrap_or(DEFAULT_BASE_URL)
+                .trim_end_matches('/')
+                .to_string(),
+            api_key: api_key.to_string(),
+            model: model.unwrap_or(DEFAULT_MODEL).to_string(),
+        })
+    }
+}
+
+impl EmbeddingProvider for OpenAiEmbeddings {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, AppError> {
+        let response = self
+            .http
+            .post(format!("{}/embeddings", self.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&json!({
+                "model": self.model,
+                "input": texts,
+            }))
+            .send()
+            .await
+            .map_err(|e| AppError::AiProvider(format!("OpenAI unreachable: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::AiProvider(format!(
+                "OpenAI returned {}: {}",
+                status, body
+            )));
+        }
+
+        let mut embeddings: EmbeddingsResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::AiProvider(format!("Invalid OpenAI response: {}", e)))?;
+        // The API documents no ordering, only an index per input
+        embeddings.data.sort_by_key(|d| d.index);
+        let vectors: Vec<Vec<f32>> = embeddings.data.into_iter().map(|d| d.embedding).collect();
+        check_count("OpenAI", &vectors, texts)?;
+        Ok(vectors)
+    }
+}
@@ -1,11 +1,13 @@
+use sea_orm::{DbErr, SqlErr};
 use serde::Serialize;
+use ts_rs::TS;
 use validator::ValidationErrors;
 
 /// Application error types for Tauri commands
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
     #[error("Database error: {0}")]
-    Database(#[from] sea_orm::DbErr),
+    Database(DbErr),
 
     #[error("Not found: {0}")]
     NotFound(String),
@@ -13,26 +15,175 @@ pub enum AppError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Validation error: {}", join_field_errors(.0))]
+    InvalidFields(Vec<FieldError>),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("AI provider error: {0}")]
+    AiProvider(String),
+
+    #[error("Read-only: {0}")]
+    ReadOnly(String),
+
+    #[error("Incompatible database: {0}")]
+    IncompatibleDatabase(SchemaMismatch),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
+/// Stable error codes the frontend can branch on instead of matching messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub enum ErrorCode {
+    NotFound,
+    Validation,
+    Conflict,
+    Db,
+    AiProvider,
+    /// The campaign is locked against edits
+    ReadOnly,
+    IncompatibleDatabase,
+    Internal,
+}
+
+/// Shape of every error returned from a Tauri command
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct ErrorPayload {
+    pub code: ErrorCode,
+    pub message: String,
+    pub details: Option<serde_json::Value>,
+}
+
+/// A single failed validation rule on an input field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../../src/types/bindings/")]
+pub struct FieldError {
+    pub field: String,
+    /// Validator rule code, e.g. `length` or `invalid_location_type`
+    pub code: String,
+    pub message: String,
+}
+
+/// Why a database was refused: it has migrations this build doesn't know,
+/// so it was written by a newer version of the app
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaMismatch {
+    /// Version of the app that refused it
+    pub app_version: String,
+    /// Version that last opened the database, when it recorded one
+    pub written_by: Option<String>,
+    pub unknown_migrations: Vec<String>,
+}
+
+impl std::fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.written_by {
+            Some(version) => write!(
+                f,
+                "the database was last opened by Loreweaver {}, which is newer than this version ({}); update the app to open it",
+                version, self.app_version
+            ),
+            None => write!(
+                f,
+                "the database was written by a newer version of Loreweaver than this one ({}); update the app to open it",
+                self.app_version
+            ),
+        }
+    }
+}
+
+fn join_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl AppError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::Database(err) if is_unique_violation(err) => ErrorCode::Conflict,
+            AppError::Database(_) => ErrorCode::Db,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::Validation(_) | AppError::InvalidFields(_) => ErrorCode::Validation,
+            AppError::Conflict(_) => ErrorCode::Conflict,
+            AppError::AiProvider(_) => ErrorCode::AiProvider,
+            AppError::ReadOnly(_) => ErrorCode::ReadOnly,
+            AppError::IncompatibleDatabase(_) => ErrorCode::IncompatibleDatabase,
+            AppError::Internal(_) => ErrorCode::Internal,
+        }
+    }
+
+    /// Extra machine-readable context for the error, if any
+    pub fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            AppError::Database(err) => match err.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(constraint)) => {
+                    Some(serde_json::json!({ "constraint": constraint }))
+                }
+                _ => None,
+            },
+            AppError::InvalidFields(fields) => Some(serde_json::json!({ "fields": fields })),
+            AppError::IncompatibleDatabase(mismatch) => serde_json::to_value(mismatch).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn to_payload(&self) -> ErrorPayload {
+        ErrorPayload {
+            code: self.code(),
+            message: self.to_string(),
+            details: self.details(),
+        }
+    }
+}
+
+fn is_unique_violation(err: &DbErr) -> bool {
+    matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_)))
+}
+
+/// Start of the message the campaign lock triggers abort with
+const CAMPAIGN_LOCKED: &str = "READ_ONLY: campaign is locked";
+
+impl From<DbErr> for AppError {
+    fn from(err: DbErr) -> Self {
+        // Writes to a locked campaign are stopped by triggers, so the lock
+        // surfaces as a database error; give it its own code
+        if err.to_string().contains(CAMPAIGN_LOCKED) {
+            AppError::ReadOnly("campaign is locked; unlock it to make changes".to_string())
+        } else {
+            AppError::Database(err)
+        }
+    }
+}
+
 impl From<ValidationErrors> for AppError {
     fn from(errors: ValidationErrors) -> Self {
-        // Format validation errors into a readable message
-        let messages: Vec<String> = errors
+        // Keep per-field errors so the UI can highlight the offending inputs
+        let mut fields: Vec<FieldError> = errors
             .field_errors()
             .iter()
             .flat_map(|(field, errs)| {
-                errs.iter().map(move |e| {
-                    e.message
+                errs.iter().map(move |e| FieldError {
+                    field: field.to_string(),
+                    code: e.code.to_string(),
+                    message: e
+                        .message
                         .as_ref()
-                        .map(|m| format!("{}: {}", field, m))
-                        .unwrap_or_else(|| format!("{}: invalid", field))
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "invalid".to_string()),
                 })
             })
             .collect();
-        AppError::Validation(messages.join(", "))
+        // field_errors() is a HashMap, so sort for a stable order
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        AppError::InvalidFields(fields)
     }
 }
 
@@ -42,6 +193,106 @@ impl Serialize for AppError {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        self.to_payload().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_bindings() {
+        ErrorCode::export_all().unwrap();
+        ErrorPayload::export_all().unwrap();
+        FieldError::export_all().unwrap();
+    }
+
+    #[test]
+    fn test_validation_errors_keep_field_details() {
+        let mut errors = ValidationErrors::new();
+        let mut err = validator::ValidationError::new("length");
+        err.message = Some("Name must be 1-200 characters".into());
+        errors.add("name", err);
+        errors.add(
+            "location_type",
+            validator::ValidationError::new("location_type"),
+        );
+
+        let app_err: AppError = errors.into();
+        let json = serde_json::to_value(&app_err).unwrap();
+
+        assert_eq!(json["code"], "VALIDATION");
+        let fields = json["details"]["fields"].as_array().unwrap();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0]["field"], "location_type");
+        assert_eq!(fields[0]["code"], "location_type");
+        assert_eq!(fields[0]["message"], "invalid");
+        assert_eq!(fields[1]["field"], "name");
+        assert_eq!(fields[1]["code"], "length");
+        assert_eq!(fields[1]["message"], "Name must be 1-200 characters");
+    }
+
+    #[test]
+    fn test_serializes_code_and_message() {
+        let err = AppError::NotFound("Character abc not found".to_string());
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "NOT_FOUND");
+        assert_eq!(json["message"], "Not found: Character abc not found");
+        assert!(json["details"].is_null());
+    }
+
+    #[test]
+    fn test_error_codes_are_stable() {
+        assert_eq!(
+            AppError::Validation("x".to_string()).code(),
+            ErrorCode::Validation
+        );
+        assert_eq!(
+            AppError::Conflict("x".to_string()).code(),
+            ErrorCode::Conflict
+        );
+        assert_eq!(
+            AppError::AiProvider("x".to_string()).code(),
+            ErrorCode::AiProvider
+        );
+        assert_eq!(
+            AppError::Database(DbErr::Custom("boom".to_string())).code(),
+            ErrorCode::Db
+        );
+    }
+
+    #[test]
+    fn test_campaign_lock_failures_are_read_only() {
+        let err: AppError = DbErr::Custom(
+            "error returned from database: (code: 1811) READ_ONLY: campaign is locked".to_string(),
+        )
+        .into();
+        assert_eq!(err.code(), ErrorCode::ReadOnly);
+
+        let err: AppError = DbErr::Custom("boom".to_string()).into();
+        assert_eq!(err.code(), ErrorCode::Db);
+    }
+
+    #[test]
+    fn test_incompatible_database_carries_details() {
+        let err = AppError::IncompatibleDatabase(SchemaMismatch {
+            app_version: "0.1.0".to_string(),
+            written_by: Some("0.2.0".to_string()),
+            unknown_migrations: vec!["m20990101_000001_future".to_string()],
+        });
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "INCOMPATIBLE_DATABASE");
+        assert_eq!(json["details"]["written_by"], "0.2.0");
+        assert_eq!(
+            json["details"]["unknown_migrations"][0],
+            "m20990101_000001_future"
+        );
+        assert!(json["message"]
+            .as_str()
+            .unwrap()
+            .contains("last opened by Loreweaver 0.2.0"));
     }
 }
@@ -0,0 +1,153 @@
+//! `.loreweaver` archive format: a zip file holding a manifest, the campaign
+//! bundle as JSON, and any attachment files under `attachments/`.
+
+use super::attachments::AttachmentRecord;
+use super::bundle::{CampaignBundle, BUNDLE_FORMAT_VERSION};
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+pub const ARCHIVE_EXTENSION: &str = "loreweaver";
+pub const ARCHIVE_FORMAT: &str = "loreweaver-campaign";
+
+const MANIFEST_PATH: &str = "manifest.json";
+const CAMPAIGN_PATH: &str = "campaign.json";
+const ATTACHMENTS_DIR: &str = "attachments/";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    #[error("Invalid JSON in archive: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Not a Loreweaver campaign archive")]
+    WrongFormat,
+
+    #[error("Archive format version {0} is newer than this app supports")]
+    UnsupportedVersion(u32),
+}
+
+impl From<ArchiveError> for AppError {
+    fn from(err: ArchiveError) -> Self {
+        match err {
+            ArchiveError::Io(_) | ArchiveError::Zip(_) => AppError::Internal(err.to_string()),
+            _ => AppError::Validation(err.to_string()),
+        }
+    }
+}
+
+/// Top-level description of an archive, readable without parsing the bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format: String,
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: String,
+    pub campaign_id: String,
+    pub campaign_name: String,
+    /// Paths of attachment files relative to `attachments/`
+    #[serde(default)]
+    pub attachments: Vec<String>,
+    /// Attachment rows, each naming its file by content hash. Older archives
+    /// have none.
+    #[serde(default)]
+    pub attachment_records: Vec<AttachmentRecord>,
+}
+
+/// A file stored alongside the campaign data
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Contents of an archive once read back
+#[derive(Debug)]
+pub struct CampaignArchive {
+    pub manifest: ArchiveManifest,
+    pub bundle: CampaignBundle,
+    pub attachments: Vec<Attachment>,
+}
+
+pub fn write_archive(
+    path: &Path,
+    bundle: &CampaignBundle,
+    attachment_records: &[AttachmentRecord],
+    attachments: &[Attachment],
+) -> Result<ArchiveManifest, ArchiveError> {
+    let manifest = ArchiveManifest {
+        format: ARCHIVE_FORMAT.to_string(),
+        format_version: BUNDLE_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        campaign_id: bundle.campaign.id.clone(),
+        campaign_name: bundle.campaign.name.clone(),
+        attachments: attachments.iter().map(|a| a.name.clone()).collect(),
+        attachment_records: attachment_records.to_vec(),
+    };
+
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file(MANIFEST_PATH, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+
+    zip.start_file(CAMPAIGN_PATH, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(bundle)?)?;
+
+    for attachment in attachments {
+        zip.start_file(format!("{}{}", ATTACHMENTS_DIR, attachment.name), options)?;
+        zip.write_all(&attachment.bytes)?;
+    }
+
+    zip.finish()?;
+    Ok(manifest)
+}
+
+pub fn read_archive(path: &Path) -> Result<CampaignArchive, ArchiveError> {
+    let mut zip = ZipArchive::new(File::open(path)?)?;
+
+    let manifest: ArchiveManifest = serde_json::from_slice(&read_entry(&mut zip, MANIFEST_PATH)?)?;
+    if manifest.format != ARCHIVE_FORMAT {
+        return Err(ArchiveError::WrongFormat);
+    }
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(ArchiveError::UnsupportedVersion(manifest.format_version));
+    }
+
+    let bundle: CampaignBundle = serde_json::from_slice(&read_entry(&mut zip, CAMPAIGN_PATH)?)?;
+
+    let mut attachments = Vec::with_capacity(manifest.attachments.len());
+    for name in &manifest.attachments {
+        let bytes = read_entry(&mut zip, &format!("{}{}", ATTACHMENTS_DIR, name))?;
+        attachments.push(Attachment {
+            name: name.clone(),
+            bytes,
+        });
+    }
+
+    Ok(CampaignArchive {
+        manifest,
+        bundle,
+        attachments,
+    })
+}
+
+fn read_entry(zip: &mut ZipArchive<File>, name: &str) -> Result<Vec<u8>, ArchiveError> {
+    let mut file = zip.by_name(name).map_err(|e| match e {
+        zip::result::ZipError::FileNotFound => ArchiveError::WrongFormat,
+        other => other.into(),
+    })?;
+    let mut bytes = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
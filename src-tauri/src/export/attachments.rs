@@ -0,0 +1,262 @@
+//! Attachment files in archives. Each file is stored once under its content
+//! hash, with a manifest record per attachment row pointing at it, so an
+//! image attached to three entities costs one copy. Importing matches files
+//! by hash: files already present are skipped, rows whose data went missing
+//! are repaired, and anything that can't be placed is reported rather than
+//! failing the import.
+
+use super::archive::Attachment;
+use crate::commands::registry::table_for;
+use ::entity::attachments::{self, Entity as AttachmentRow};
+use sea_orm::prelude::DateTimeUtc;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+
+/// Hex SHA-256 of an attachment's contents
+pub fn content_hash(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// One attachment row as listed in an archive manifest; the file itself is
+/// `attachments/{content_hash}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttachmentRecord {
+    pub id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub role: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub content_hash: String,
+    pub source: Option<String>,
+    pub prompt: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnresolvedReason {
+    /// The entity the attachment belongs to isn't in the campaign
+    MissingEntity,
+    /// The archive has no file with the recorded hash
+    MissingFile,
+    /// A local attachment with the same id holds different contents; the
+    /// local file was kept
+    Conflict,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedAttachment {
+    pub id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub file_name: String,
+    pub reason: UnresolvedReason,
+}
+
+/// Outcome of importing an archive's attachments
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AttachmentReport {
+    pub imported: usize,
+    /// Already present locally, under the same id or on the same entity
+    pub deduplicated: usize,
+    /// Local rows whose data was missing or damaged, restored from the archive
+    pub repaired: usize,
+    pub unresolved: Vec<UnresolvedAttachment>,
+}
+
+/// Load a campaign's attachments for an archive: one record per row and one
+/// file per distinct hash. Only reads: a row without a stored hash is
+/// listed under its computed one.
+pub async fn load_attachments<C: ConnectionTrait>(
+    conn: &C,
+    campaign_id: &str,
+) -> Result<(Vec<AttachmentRecord>, Vec<Attachment>), DbErr> {
+    let rows = AttachmentRow::find()
+        .filter(attachments::Column::CampaignId.eq(campaign_id))
+        .order_by_asc(attachments::Column::CreatedAt)
+        .order_by_asc(attachments::Column::Id)
+        .all(conn)
+        .await?;
+
+    let mut records = Vec::with_capacity(rows.len());
+    let mut files = BTreeMap::new();
+    for row in rows {
+        let actual = content_hash(&row.data);
+        let hash = row.content_hash.unwrap_or_else(|| actual.clone());
+        records.push(AttachmentRecord {
+            id: row.id,
+            entity_type: row.entity_type,
+            entity_id: row.entity_id,
+            role: row.role,
+            file_name: row.file_name,
+            mime_type: row.mime_type,
+            size_bytes: row.size_bytes,
+            content_hash: hash.clone(),
+            source: row.source,
+            prompt: row.prompt,
+            created_at: row.created_at,
+        });
+        // An empty or damaged row has no file to carry; the record stays so
+        // an import elsewhere can still repair it from its own copy
+        if !row.data.is_empty() && actual == hash {
+            files.entry(hash).or_insert(row.data);
+        }
+    }
+
+    let files = files
+        .into_iter()
+        .map(|(name, bytes)| Attachment { name, bytes })
+        .collect();
+    Ok((records, files))
+}
+
+async fn entity_exists<C: ConnectionTrait>(
+    conn: &C,
+    campaign_id: &str,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<bool, DbErr> {
+    let Some(table) = table_for(entity_type) else {
+        return Ok(false);
+    };
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            conn.get_database_backend(),
+            format!(
+                "SELECT 1 AS found FROM {} WHERE id = $1 AND campaign_id = $2",
+                table.table
+            ),
+            [entity_id.into(), campaign_id.into()],
+        ))
+        .await?;
+    Ok(row.is_some())
+}
+
+/// Bring an archive's attachments into `campaign_id`, whose entities must
+/// already be in place. Files are only trusted when their bytes match the
+/// hash they are stored under.
+pub async fn import_attachments<C: ConnectionTrait>(
+    conn: &C,
+    campaign_id: &str,
+    records: &[AttachmentRecord],
+    files: &[Attachment],
+) -> Result<AttachmentReport, DbErr> {
+    let files: BTreeMap<&str, &[u8]> = files
+        .iter()
+        .filter(|file| content_hash(&file.bytes) == file.name)
+        .map(|file| (file.name.as_str(), file.bytes.as_slice()))
+        .collect();
+
+    let mut report = AttachmentReport::default();
+    let unresolved = |record: &AttachmentRecord, reason| UnresolvedAttachment {
+        id: record.id.clone(),
+        entity_type: record.entity_type.clone(),
+        entity_id: record.entity_id.clone(),
+        file_name: record.file_name.clone(),
+        reason,
+    };
+
+    for record in records {
+        let bytes = files.get(record.content_hash.as_str()).copied();
+
+        if let Some(local) = AttachmentRow::find_by_id(&record.id).one(conn).await? {
+            let local_hash = content_hash(&local.data);
+            if local_hash == record.content_hash {
+                if local.content_hash.as_deref() != Some(local_hash.as_str()) {
+                    let mut active: attachments::ActiveModel = local.into();
+                    active.content_hash = Set(Some(local_hash));
+                    active.update(conn).await?;
+                }
+                report.deduplicated += 1;
+                continue;
+            }
+
+            // The row lost its data, or no longer matches the hash it was
+            // stored with; either way the archive's copy is the real file
+            let damaged = local.data.is_empty()
+                || local
+                    .content_hash
+                    .as_deref()
+                    .is_some_and(|stored| stored != local_hash);
+            if !damaged {
+                report
+                    .unresolved
+                    .push(unresolved(record, UnresolvedReason::Conflict));
+                continue;
+            }
+            let Some(bytes) = bytes else {
+                report
+                    .unresolved
+                    .push(unresolved(record, UnresolvedReason::MissingFile));
+                continue;
+            };
+            let mut active: attachments::ActiveModel = local.into();
+            active.data = Set(bytes.to_vec());
+            active.size_bytes = Set(bytes.len() as i64);
+            active.content_hash = Set(Some(record.content_hash.clone()));
+            active.update(conn).await?;
+            report.repaired += 1;
+            continue;
+        }
+
+        if !entity_exists(conn, campaign_id, &record.entity_type, &record.entity_id).await? {
+            report
+                .unresolved
+                .push(unresolved(record, UnresolvedReason::MissingEntity));
+            continue;
+        }
+
+        let siblings = AttachmentRow::find()
+            .filter(attachments::Column::EntityType.eq(&record.entity_type))
+            .filter(attachments::Column::EntityId.eq(&record.entity_id))
+            .all(conn)
+            .await?;
+        let hashes: HashSet<String> = siblings.iter().map(|s| content_hash(&s.data)).collect();
+        if hashes.contains(&record.content_hash) {
+            report.deduplicated += 1;
+            continue;
+        }
+
+        let Some(bytes) = bytes else {
+            report
+                .unresolved
+                .push(unresolved(record, UnresolvedReason::MissingFile));
+            continue;
+        };
+
+        // The entity keeps its own portrait or map; the imported one joins
+        // it as a plain image
+        let role = if record.role != "image" && siblings.iter().any(|s| s.role == record.role) {
+            "image".to_string()
+        } else {
+            record.role.clone()
+        };
+        attachments::ActiveModel {
+            id: Set(record.id.clone()),
+            campaign_id: Set(campaign_id.to_string()),
+            entity_type: Set(record.entity_type.clone()),
+            entity_id: Set(record.entity_id.clone()),
+            role: Set(role),
+            file_name: Set(record.file_name.clone()),
+            mime_type: Set(record.mime_type.clone()),
+            size_bytes: Set(bytes.len() as i64),
+            data: Set(bytes.to_vec()),
+            content_hash: Set(Some(record.content_hash.clone())),
+            source: Set(record.source.clone()),
+            prompt: Set(record.prompt.clone()),
+            created_at: Set(record.created_at),
+        }
+        .insert(conn)
+        .await?;
+        report.imported += 1;
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,559 @@
+//! In-memory representation of a whole campaign, shared by the archive
+//! format and anything else that moves campaigns between databases.
+
+use super::merge::BundleRow;
+use super::progress::{ImportReport, ImportTracker, ProgressFn, RowIssue};
+use ::entity::{
+    annotations, campaigns, characters, content_warnings, dispositions, encounter_entries,
+    encounter_rolls, encounter_tables, entity_tags, goal_advances, handout_deliveries, handouts,
+    hero_items, heroes, items, locations, organization_goals, organization_holdings, organizations,
+    players, price_list, price_modifiers, progress_clocks, prompt_templates, quests, recall_cards,
+    relationships, secrets, session_absences, session_transcripts, sessions, snippets,
+    strength_changes, tags, timeline_branches, timeline_events, whereabouts,
+};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Bump when the bundle layout changes in a way older readers can't handle
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Every row belonging to one campaign. Lists default to empty so archives
+/// written before a table existed still load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignBundle {
+    pub format_version: u32,
+    pub campaign: campaigns::Model,
+    #[serde(default)]
+    pub players: Vec<players::Model>,
+    #[serde(default)]
+    pub locations: Vec<locations::Model>,
+    #[serde(default)]
+    pub characters: Vec<characters::Model>,
+    #[serde(default)]
+    pub organizations: Vec<organizations::Model>,
+    #[serde(default)]
+    pub quests: Vec<quests::Model>,
+    #[serde(default)]
+    pub heroes: Vec<heroes::Model>,
+    #[serde(default)]
+    pub items: Vec<items::Model>,
+    #[serde(default)]
+    pub hero_items: Vec<hero_items::Model>,
+    #[serde(default)]
+    pub organization_holdings: Vec<organization_holdings::Model>,
+    #[serde(default)]
+    pub whereabouts: Vec<whereabouts::Model>,
+    #[serde(default)]
+    pub sessions: Vec<sessions::Model>,
+    #[serde(default)]
+    pub session_absences: Vec<session_absences::Model>,
+    #[serde(default)]
+    pub session_transcripts: Vec<session_transcripts::Model>,
+    #[serde(default)]
+    pub dispositions: Vec<dispositions::Model>,
+    #[serde(default)]
+    pub timeline_branches: Vec<timeline_branches::Model>,
+    #[serde(default)]
+    pub timeline_events: Vec<timeline_events::Model>,
+    #[serde(default)]
+    pub secrets: Vec<secrets::Model>,
+    #[serde(default)]
+    pub handouts: Vec<handouts::Model>,
+    #[serde(default)]
+    pub handout_deliveries: Vec<handout_deliveries::Model>,
+    #[serde(default)]
+    pub snippets: Vec<snippets::Model>,
+    #[serde(default)]
+    pub prompt_templates: Vec<prompt_templates::Model>,
+    #[serde(default)]
+    pub relationships: Vec<relationships::Model>,
+    #[serde(default)]
+    pub strength_changes: Vec<strength_changes::Model>,
+    #[serde(default)]
+    pub recall_cards: Vec<recall_cards::Model>,
+    #[serde(default)]
+    pub progress_clocks: Vec<progress_clocks::Model>,
+    #[serde(default)]
+    pub encounter_tables: Vec<encounter_tables::Model>,
+    #[serde(default)]
+    pub encounter_entries: Vec<encounter_entries::Model>,
+    #[serde(default)]
+    pub encounter_rolls: Vec<encounter_rolls::Model>,
+    #[serde(default)]
+    pub price_list: Vec<price_list::Model>,
+    #[serde(default)]
+    pub price_modifiers: Vec<price_modifiers::Model>,
+    #[serde(default)]
+    pub organization_goals: Vec<organization_goals::Model>,
+    #[serde(default)]
+    pub goal_advances: Vec<goal_advances::Model>,
+    #[serde(default)]
+    pub content_warnings: Vec<content_warnings::Model>,
+    #[serde(default)]
+    pub annotations: Vec<annotations::Model>,
+    #[serde(default)]
+    pub tags: Vec<tags::Model>,
+    #[serde(default)]
+    pub entity_tags: Vec<entity_tags::Model>,
+}
+
+impl CampaignBundle {
+    /// Row counts keyed by entity type, for summaries shown to the user
+    pub fn counts(&self) -> BTreeMap<String, usize> {
+        BTreeMap::from([
+            ("player".to_string(), self.players.len()),
+            ("location".to_string(), self.locations.len()),
+            ("character".to_string(), self.characters.len()),
+            ("organization".to_string(), self.organizations.len()),
+            ("quest".to_string(), self.quests.len()),
+            ("hero".to_string(), self.heroes.len()),
+            ("item".to_string(), self.items.len()),
+            ("hero_item".to_string(), self.hero_items.len()),
+            (
+                "organization_holding".to_string(),
+                self.organization_holdings.len(),
+            ),
+            ("whereabouts".to_string(), self.whereabouts.len()),
+            ("session".to_string(), self.sessions.len()),
+            ("session_absence".to_string(), self.session_absences.len()),
+            (
+                "session_transcript".to_string(),
+                self.session_transcripts.len(),
+            ),
+            ("disposition".to_string(), self.dispositions.len()),
+            ("timeline_branch".to_string(), self.timeline_branches.len()),
+            ("timeline_event".to_string(), self.timeline_events.len()),
+            ("secret".to_string(), self.secrets.len()),
+            ("handout".to_string(), self.handouts.len()),
+            (
+                "handout_delivery".to_string(),
+                self.handout_deliveries.len(),
+            ),
+            ("snippet".to_string(), self.snippets.len()),
+            ("prompt_template".to_string(), self.prompt_templates.len()),
+            ("relationship".to_string(), self.relationships.len()),
+            ("strength_change".to_string(), self.strength_changes.len()),
+            ("recall_card".to_string(), self.recall_cards.len()),
+            ("progress_clock".to_string(), self.progress_clocks.len()),
+            ("encounter_table".to_string(), self.encounter_tables.len()),
+            ("encounter_entry".to_string(), self.encounter_entries.len()),
+            ("encounter_roll".to_string(), self.encounter_rolls.len()),
+            ("price".to_string(), self.price_list.len()),
+            ("price_modifier".to_string(), self.price_modifiers.len()),
+            (
+                "organization_goal".to_string(),
+                self.organization_goals.len(),
+            ),
+            ("goal_advance".to_string(), self.goal_advances.len()),
+            ("content_warning".to_string(), self.content_warnings.len()),
+            ("annotation".to_string(), self.annotations.len()),
+            ("tag".to_string(), self.tags.len()),
+        ])
+    }
+}
+
+/// Read a campaign and all of its rows
+pub async fn load_bundle<C: ConnectionTrait>(
+    conn: &C,
+    campaign_id: &str,
+) -> Result<Option<CampaignBundle>, DbErr> {
+    let Some(mut campaign) = campaigns::Entity::find_by_id(campaign_id).one(conn).await? else {
+        return Ok(None);
+    };
+    // Locks guard this database only; a restored or imported copy starts
+    // unlocked so its rows can be written
+    campaign.locked_at = None;
+
+    let tags = tags::Entity::find()
+        .filter(tags::Column::CampaignId.eq(campaign_id))
+        .all(conn)
+        .await?;
+    let tag_ids: Vec<String> = tags.iter().map(|t| t.id.clone()).collect();
+    let entity_tags = entity_tags::Entity::find()
+        .filter(entity_tags::Column::TagId.is_in(tag_ids))
+        .all(conn)
+        .await?;
+
+    Ok(Some(CampaignBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        players: players::Entity::find()
+            .filter(players::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        locations: locations::Entity::find()
+            .filter(locations::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        characters: characters::Entity::find()
+            .filter(characters::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        organizations: organizations::Entity::find()
+            .filter(organizations::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        quests: quests::Entity::find()
+            .filter(quests::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        heroes: heroes::Entity::find()
+            .filter(heroes::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        items: items::Entity::find()
+            .filter(items::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        hero_items: hero_items::Entity::find()
+            .filter(hero_items::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        organization_holdings: organization_holdings::Entity::find()
+            .filter(organization_holdings::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        whereabouts: whereabouts::Entity::find()
+            .filter(whereabouts::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        sessions: sessions::Entity::find()
+            .filter(sessions::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        session_absences: session_absences::Entity::find()
+            .filter(session_absences::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        session_transcripts: session_transcripts::Entity::find()
+            .filter(session_transcripts::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        dispositions: dispositions::Entity::find()
+            .filter(dispositions::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        timeline_branches: timeline_branches::Entity::find()
+            .filter(timeline_branches::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        timeline_events: timeline_events::Entity::find()
+            .filter(timeline_events::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        secrets: secrets::Entity::find()
+            .filter(secrets::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        handouts: handouts::Entity::find()
+            .filter(handouts::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        handout_deliveries: handout_deliveries::Entity::find()
+            .filter(handout_deliveries::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        snippets: snippets::Entity::find()
+            .filter(snippets::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        prompt_templates: prompt_templates::Entity::find()
+            .filter(prompt_templates::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        relationships: relationships::Entity::find()
+            .filter(relationships::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        strength_changes: strength_changes::Entity::find()
+            .filter(strength_changes::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        recall_cards: recall_cards::Entity::find()
+            .filter(recall_cards::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        progress_clocks: progress_clocks::Entity::find()
+            .filter(progress_clocks::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        encounter_tables: encounter_tables::Entity::find()
+            .filter(encounter_tables::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        encounter_entries: encounter_entries::Entity::find()
+            .filter(encounter_entries::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        encounter_rolls: encounter_rolls::Entity::find()
+            .filter(encounter_rolls::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        price_list: price_list::Entity::find()
+            .filter(price_list::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        price_modifiers: price_modifiers::Entity::find()
+            .filter(price_modifiers::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        organization_goals: organization_goals::Entity::find()
+            .filter(organization_goals::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        goal_advances: goal_advances::Entity::find()
+            .filter(goal_advances::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        content_warnings: content_warnings::Entity::find()
+            .filter(content_warnings::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        annotations: annotations::Entity::find()
+            .filter(annotations::Column::CampaignId.eq(campaign_id))
+            .all(conn)
+            .await?,
+        tags,
+        entity_tags,
+        campaign,
+    }))
+}
+
+/// Insert every row of the bundle, ordered so foreign keys resolve
+pub async fn insert_bundle<C: ConnectionTrait>(
+    conn: &C,
+    bundle: CampaignBundle,
+) -> Result<(), DbErr> {
+    insert_models::<_, campaigns::ActiveModel>(conn, vec![bundle.campaign.clone()]).await?;
+    insert_bundle_contents(conn, bundle).await
+}
+
+/// Insert everything in the bundle except the campaign row, which must
+/// already exist
+pub async fn insert_bundle_contents<C: ConnectionTrait>(
+    conn: &C,
+    bundle: CampaignBundle,
+) -> Result<(), DbErr> {
+    insert_models::<_, players::ActiveModel>(conn, bundle.players).await?;
+    insert_models::<_, locations::ActiveModel>(conn, parents_first(bundle.locations)).await?;
+    insert_models::<_, characters::ActiveModel>(conn, bundle.characters).await?;
+    insert_models::<_, organizations::ActiveModel>(conn, bundle.organizations).await?;
+    insert_models::<_, quests::ActiveModel>(conn, bundle.quests).await?;
+    insert_models::<_, heroes::ActiveModel>(conn, bundle.heroes).await?;
+    insert_models::<_, items::ActiveModel>(conn, bundle.items).await?;
+    insert_models::<_, hero_items::ActiveModel>(conn, bundle.hero_items).await?;
+    insert_models::<_, organization_holdings::ActiveModel>(conn, bundle.organization_holdings)
+        .await?;
+    insert_models::<_, whereabouts::ActiveModel>(conn, bundle.whereabouts).await?;
+    insert_models::<_, sessions::ActiveModel>(conn, bundle.sessions).await?;
+    insert_models::<_, session_absences::ActiveModel>(conn, bundle.session_absences).await?;
+    insert_models::<_, session_transcripts::ActiveModel>(conn, bundle.session_transcripts).await?;
+    insert_models::<_, dispositions::ActiveModel>(conn, bundle.dispositions).await?;
+    insert_models::<_, timeline_branches::ActiveModel>(conn, bundle.timeline_branches).await?;
+    insert_models::<_, timeline_events::ActiveModel>(conn, bundle.timeline_events).await?;
+    insert_models::<_, secrets::ActiveModel>(conn, bundle.secrets).await?;
+    insert_models::<_, handouts::ActiveModel>(conn, bundle.handouts).await?;
+    insert_models::<_, handout_deliveries::ActiveModel>(conn, bundle.handout_deliveries).await?;
+    insert_models::<_, snippets::ActiveModel>(conn, bundle.snippets).await?;
+    insert_models::<_, prompt_templates::ActiveModel>(conn, bundle.prompt_templates).await?;
+    insert_models::<_, relationships::ActiveModel>(conn, bundle.relationships).await?;
+    insert_models::<_, strength_changes::ActiveModel>(conn, bundle.strength_changes).await?;
+    insert_models::<_, recall_cards::ActiveModel>(conn, bundle.recall_cards).await?;
+    insert_models::<_, progress_clocks::ActiveModel>(conn, bundle.progress_clocks).await?;
+    insert_models::<_, encounter_tables::ActiveModel>(conn, bundle.encounter_tables).await?;
+    insert_models::<_, encounter_entries::ActiveModel>(conn, bundle.encounter_entries).await?;
+    insert_models::<_, encounter_rolls::ActiveModel>(conn, bundle.encounter_rolls).await?;
+    insert_models::<_, price_list::ActiveModel>(conn, bundle.price_list).await?;
+    insert_models::<_, price_modifiers::ActiveModel>(conn, bundle.price_modifiers).await?;
+    insert_models::<_, organization_goals::ActiveModel>(conn, bundle.organization_goals).await?;
+    insert_models::<_, goal_advances::ActiveModel>(conn, bundle.goal_advances).await?;
+    insert_models::<_, content_warnings::ActiveModel>(conn, bundle.content_warnings).await?;
+    insert_models::<_, annotations::ActiveModel>(conn, bundle.annotations).await?;
+    insert_models::<_, tags::ActiveModel>(conn, bundle.tags).await?;
+    insert_models::<_, entity_tags::ActiveModel>(conn, bundle.entity_tags).await?;
+    Ok(())
+}
+
+/// Like `insert_bundle_contents`, but each row goes in under its own
+/// savepoint, so a row that fails is rolled back alone and the rest carry
+/// on. Rows clashing with an existing one are counted as skipped, other
+/// failures as failed, each with the database's reason.
+pub async fn import_bundle_contents(
+    txn: &DatabaseTransaction,
+    bundle: CampaignBundle,
+    source: &str,
+    on_progress: ProgressFn<'_>,
+) -> Result<ImportReport, DbErr> {
+    let total = bundle.counts().values().sum::<usize>() + bundle.entity_tags.len();
+    let mut tracker = ImportTracker::new(source, total, on_progress);
+    let t = &mut tracker;
+
+    import_rows(txn, bundle.players, t).await?;
+    import_rows(txn, parents_first(bundle.locations), t).await?;
+    import_rows(txn, bundle.characters, t).await?;
+    import_rows(txn, bundle.organizations, t).await?;
+    import_rows(txn, bundle.quests, t).await?;
+    import_rows(txn, bundle.heroes, t).await?;
+    import_rows(txn, bundle.items, t).await?;
+    import_rows(txn, bundle.hero_items, t).await?;
+    import_rows(txn, bundle.organization_holdings, t).await?;
+    import_rows(txn, bundle.whereabouts, t).await?;
+    import_rows(txn, bundle.sessions, t).await?;
+    import_rows(txn, bundle.session_absences, t).await?;
+    import_rows(txn, bundle.session_transcripts, t).await?;
+    import_rows(txn, bundle.dispositions, t).await?;
+    import_rows(txn, bundle.timeline_branches, t).await?;
+    import_rows(txn, bundle.timeline_events, t).await?;
+    import_rows(txn, bundle.secrets, t).await?;
+    import_rows(txn, bundle.handouts, t).await?;
+    import_rows(txn, bundle.handout_deliveries, t).await?;
+    import_rows(txn, bundle.snippets, t).await?;
+    import_rows(txn, bundle.prompt_templates, t).await?;
+    import_rows(txn, bundle.relationships, t).await?;
+    import_rows(txn, bundle.strength_changes, t).await?;
+    import_rows(txn, bundle.recall_cards, t).await?;
+    import_rows(txn, bundle.progress_clocks, t).await?;
+    import_rows(txn, bundle.encounter_tables, t).await?;
+    import_rows(txn, bundle.encounter_entries, t).await?;
+    import_rows(txn, bundle.encounter_rolls, t).await?;
+    import_rows(txn, bundle.price_list, t).await?;
+    import_rows(txn, bundle.price_modifiers, t).await?;
+    import_rows(txn, bundle.organization_goals, t).await?;
+    import_rows(txn, bundle.goal_advances, t).await?;
+    import_rows(txn, bundle.content_warnings, t).await?;
+    import_rows(txn, bundle.annotations, t).await?;
+    import_rows(txn, bundle.tags, t).await?;
+
+    for entity_tag in bundle.entity_tags {
+        let id = format!(
+            "{}:{}:{}",
+            entity_tag.tag_id, entity_tag.entity_type, entity_tag.entity_id
+        );
+        let result = insert_row(txn, entity_tag.into_active_model().reset_all()).await?;
+        track_row(t, "entity_tag", id, None, result);
+    }
+
+    Ok(tracker.finish())
+}
+
+async fn import_rows<R>(
+    txn: &DatabaseTransaction,
+    rows: Vec<R>,
+    tracker: &mut ImportTracker<'_>,
+) -> Result<(), DbErr>
+where
+    R: BundleRow + IntoActiveModel<R::Active>,
+    <<R::Active as ActiveModelTrait>::Entity as EntityTrait>::Model: IntoActiveModel<R::Active>,
+{
+    for row in rows {
+        let id = row.id().to_string();
+        let name = row.name().map(str::to_string);
+        let result = insert_row(txn, row.into_active_model().reset_all()).await?;
+        track_row(tracker, R::ENTITY_TYPE, id, name, result);
+    }
+    Ok(())
+}
+
+/// Insert one row under a savepoint. The outer error is the transaction
+/// itself failing; the inner one is the row's own.
+async fn insert_row<A>(txn: &DatabaseTransaction, active: A) -> Result<Result<(), DbErr>, DbErr>
+where
+    A: ActiveModelTrait + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    let savepoint = txn.begin().await?;
+    match A::Entity::insert(active)
+        .exec_without_returning(&savepoint)
+        .await
+    {
+        Ok(_) => {
+            savepoint.commit().await?;
+            Ok(Ok(()))
+        }
+        Err(err) => {
+            savepoint.rollback().await?;
+            Ok(Err(err))
+        }
+    }
+}
+
+fn track_row(
+    tracker: &mut ImportTracker<'_>,
+    entity_type: &str,
+    id: String,
+    name: Option<String>,
+    result: Result<(), DbErr>,
+) {
+    let Err(err) = result else {
+        tracker.created(entity_type);
+        return;
+    };
+    let issue = RowIssue {
+        entity_type: entity_type.to_string(),
+        id,
+        name,
+        reason: err.to_string(),
+    };
+    if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) {
+        tracker.skipped(issue);
+    } else {
+        tracker.failed(issue);
+    }
+}
+
+pub(crate) async fn insert_models<C, A>(
+    conn: &C,
+    models: Vec<<A::Entity as EntityTrait>::Model>,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+    A: ActiveModelTrait + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    for model in models {
+        let active = model.into_active_model().reset_all();
+        A::Entity::insert(active)
+            .exec_without_returning(conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Order locations so each parent is inserted before its children. Parents
+/// missing from the bundle are detached rather than failing the import.
+pub(crate) fn parents_first(locations: Vec<locations::Model>) -> Vec<locations::Model> {
+    let ids: HashSet<String> = locations.iter().map(|l| l.id.clone()).collect();
+    let mut remaining: Vec<locations::Model> = locations
+        .into_iter()
+        .map(|mut l| {
+            if l.parent_id.as_ref().is_some_and(|p| !ids.contains(p)) {
+                l.parent_id = None;
+            }
+            l
+        })
+        .collect();
+
+    let mut placed: HashSet<String> = HashSet::new();
+    let mut ordered = Vec::with_capacity(remaining.len());
+
+    while !remaining.is_empty() {
+        let (ready, blocked): (Vec<_>, Vec<_>) = remaining
+            .into_iter()
+            .partition(|l| l.parent_id.as_ref().is_none_or(|p| placed.contains(p)));
+
+        if ready.is_empty() {
+            // A parent cycle; break it by detaching the rest
+            ordered.extend(blocked.into_iter().map(|mut l| {
+                l.parent_id = None;
+                l
+            }));
+            break;
+        }
+
+        placed.extend(ready.iter().map(|l| l.id.clone()));
+        ordered.extend(ready);
+        remaining = blocked;
+    }
+
+    ordered
+}
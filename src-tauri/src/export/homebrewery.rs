@@ -0,0 +1,64 @@
+//! Homebrewery documents, which GM Binder reads too: markdown plus their
+//! `{{...}}` blocks, ready to paste into either editor and print to PDF.
+
+use super::journal::{JournalEntry, JournalSection};
+use super::rich_text::to_markdown;
+use crate::commands::json_schema::SystemStatBlock;
+use crate::commands::stat_block;
+
+/// Headings inside a field sit below the `####` section headings
+const FIELD_HEADING_OFFSET: u64 = 4;
+
+fn push_section(out: &mut Vec<String>, section: &JournalSection, heading: &str) {
+    let body = to_markdown(&section.content, FIELD_HEADING_OFFSET);
+    match section.label {
+        Some(label) => {
+            out.push(format!("{} {}", heading, label));
+            out.push(body);
+        }
+        // An entity's main description goes in a read-aloud box
+        None => out.push(format!("{{{{descriptive\n{}\n}}}}", body)),
+    }
+}
+
+fn render_entry(entry: &JournalEntry, include_gm_notes: bool) -> String {
+    let mut out = vec![format!("## {}", entry.name)];
+    if let Some(subtitle) = &entry.subtitle {
+        out.push(format!("*{}*", subtitle));
+    }
+    for section in entry.player_sections() {
+        push_section(&mut out, section, "####");
+    }
+
+    if include_gm_notes {
+        let mut notes = vec!["##### GM notes".to_string()];
+        for section in entry.gm_sections() {
+            push_section(&mut notes, section, "######");
+        }
+        if notes.len() > 1 {
+            out.push(format!("{{{{note\n{}\n}}}}", notes.join("\n\n")));
+        }
+
+        // A stat block that no longer parses is left out rather than failing
+        // the whole document
+        let block = entry
+            .stat_block_json
+            .as_deref()
+            .and_then(|raw| SystemStatBlock::parse(raw).ok())
+            .and_then(|block| stat_block::render(&entry.name, &block, "homebrewery").ok());
+        out.extend(block);
+    }
+    out.join("\n\n")
+}
+
+/// One document with an entry per entity, in the order given. GM notes and
+/// stat blocks are left out of a document meant for players.
+pub fn render_homebrewery(
+    campaign: &str,
+    entries: &[JournalEntry],
+    include_gm_notes: bool,
+) -> String {
+    let mut out = vec![format!("# {}", campaign)];
+    out.extend(entries.iter().map(|e| render_entry(e, include_gm_notes)));
+    out.join("\n\n") + "\n"
+}
@@ -0,0 +1,170 @@
+//! Selected entities laid out as titled sections, the shape shared by the
+//! exporters for other tools. Sections a GM keeps to themselves are flagged
+//! so each format can put them somewhere players won't read them.
+
+use super::CampaignBundle;
+use crate::share::snapshot::humanize;
+
+#[derive(Debug, Clone)]
+pub struct JournalSection {
+    /// None for the entity's main description
+    pub label: Option<&'static str>,
+    /// Editor JSON or plain text, as stored
+    pub content: String,
+    pub gm_only: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    pub entity_type: &'static str,
+    pub id: String,
+    pub name: String,
+    pub subtitle: Option<String>,
+    pub sections: Vec<JournalSection>,
+    /// Characters' stat block JSON, as stored
+    pub stat_block_json: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn player_sections(&self) -> impl Iterator<Item = &JournalSection> {
+        self.sections.iter().filter(|s| !s.gm_only)
+    }
+
+    pub fn gm_sections(&self) -> impl Iterator<Item = &JournalSection> {
+        self.sections.iter().filter(|s| s.gm_only)
+    }
+}
+
+fn sections(fields: &[(Option<&'static str>, &Option<String>, bool)]) -> Vec<JournalSection> {
+    fields
+        .iter()
+        .filter_map(|(label, content, gm_only)| {
+            let content = content
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())?;
+            Some(JournalSection {
+                label: *label,
+                content: content.to_string(),
+                gm_only: *gm_only,
+            })
+        })
+        .collect()
+}
+
+fn join_present(parts: &[Option<String>]) -> Option<String> {
+    let parts: Vec<&str> = parts
+        .iter()
+        .flatten()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+fn entry(bundle: &CampaignBundle, id: &str) -> Option<JournalEntry> {
+    if let Some(c) = bundle.characters.iter().find(|c| c.id == id) {
+        return Some(JournalEntry {
+            entity_type: "character",
+            id: c.id.clone(),
+            name: c.name.clone(),
+            subtitle: join_present(&[
+                c.lineage.clone(),
+                c.occupation.clone(),
+                (!c.is_alive).then(|| "Dead".to_string()),
+            ]),
+            sections: sections(&[
+                (None, &c.description, false),
+                (Some("Personality"), &c.personality, true),
+                (Some("Motivations"), &c.motivations, true),
+                (Some("Secrets"), &c.secrets, true),
+                (Some("Voice"), &c.voice_notes, true),
+            ]),
+            stat_block_json: c.stat_block_json.clone(),
+        });
+    }
+
+    if let Some(l) = bundle.locations.iter().find(|l| l.id == id) {
+        let parent = l
+            .parent_id
+            .as_ref()
+            .and_then(|p| bundle.locations.iter().find(|other| &other.id == p))
+            .map(|p| format!("in {}", p.name));
+        return Some(JournalEntry {
+            entity_type: "location",
+            id: l.id.clone(),
+            name: l.name.clone(),
+            subtitle: join_present(&[Some(humanize(&l.location_type)), parent]),
+            sections: sections(&[
+                (None, &l.description, false),
+                (Some("GM notes"), &l.gm_notes, true),
+                (Some("Economy"), &l.economy_notes, true),
+            ]),
+            stat_block_json: None,
+        });
+    }
+
+    if let Some(o) = bundle.organizations.iter().find(|o| o.id == id) {
+        return Some(JournalEntry {
+            entity_type: "organization",
+            id: o.id.clone(),
+            name: o.name.clone(),
+            subtitle: join_present(&[
+                Some(humanize(&o.org_type)),
+                (!o.is_active).then(|| "Inactive".to_string()),
+            ]),
+            sections: sections(&[
+                (None, &o.description, false),
+                (Some("Reputation"), &o.reputation, false),
+                (Some("Goals"), &o.goals, true),
+                (Some("Resources"), &o.resources, true),
+                (Some("Secrets"), &o.secrets, true),
+            ]),
+            stat_block_json: None,
+        });
+    }
+
+    if let Some(q) = bundle.quests.iter().find(|q| q.id == id) {
+        return Some(JournalEntry {
+            entity_type: "quest",
+            id: q.id.clone(),
+            name: q.name.clone(),
+            subtitle: join_present(&[Some(humanize(&q.plot_type)), Some(humanize(&q.status))]),
+            sections: sections(&[
+                (Some("Hook"), &q.hook, false),
+                (None, &q.description, false),
+                (Some("Objectives"), &q.objectives, false),
+                (Some("Complications"), &q.complications, true),
+                (Some("Reward"), &q.reward, true),
+                (Some("Resolution"), &q.resolution, true),
+            ]),
+            stat_block_json: None,
+        });
+    }
+
+    if let Some(i) = bundle.items.iter().find(|i| i.id == id) {
+        return Some(JournalEntry {
+            entity_type: "item",
+            id: i.id.clone(),
+            name: i.name.clone(),
+            subtitle: join_present(&[
+                Some(humanize(&i.item_type)),
+                i.rarity.as_deref().map(humanize),
+                i.requires_attunement
+                    .then(|| "requires attunement".to_string()),
+            ]),
+            sections: sections(&[(None, &i.description, false)]),
+            stat_block_json: None,
+        });
+    }
+
+    None
+}
+
+/// Journal entries for the given ids, in the order given. Returns the first
+/// id that isn't an exportable entity of this campaign as the error.
+pub fn build_journal(bundle: &CampaignBundle, ids: &[String]) -> Result<Vec<JournalEntry>, String> {
+    ids.iter()
+        .map(|id| entry(bundle, id).ok_or_else(|| id.clone()))
+        .collect()
+}
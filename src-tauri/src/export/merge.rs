@@ -0,0 +1,878 @@
+//! Merging an imported bundle into an existing copy of the same campaign.
+//!
+//! Imported rows are matched against local rows by id, then by name. Each
+//! match is resolved with a [`MergeStrategy`]; unmatched rows are inserted.
+//! Ids are remapped afterwards so references (parents, relationships, tags)
+//! point at whichever row survived.
+
+use super::attachments::AttachmentReport;
+use super::bundle::{parents_first, CampaignBundle};
+use ::entity::{
+    annotations, campaigns, characters, content_warnings, dispositions, encounter_entries,
+    encounter_rolls, encounter_tables, entity_tags, goal_advances, handout_deliveries, handouts,
+    hero_items, heroes, items, locations, organization_goals, organization_holdings, organizations,
+    players, price_list, price_modifiers, progress_clocks, prompt_templates, quests, recall_cards,
+    relationships, secrets, session_absences, session_transcripts, sessions, snippets,
+    strength_changes, tags, timeline_branches, timeline_events, whereabouts,
+};
+use sea_orm::prelude::DateTimeUtc;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Maps imported ids to the id the row ends up with locally
+pub type IdMap = HashMap<String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    KeepLocal,
+    TakeImported,
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    Id,
+    Name,
+}
+
+/// An imported row that matches a local row with different contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeConflict {
+    pub entity_type: String,
+    pub imported_id: String,
+    pub local_id: String,
+    pub name: Option<String>,
+    pub match_kind: MatchKind,
+    pub local_updated_at: Option<String>,
+    pub imported_updated_at: Option<String>,
+}
+
+/// Per-entity override of the default strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeResolution {
+    pub entity_type: String,
+    pub imported_id: String,
+    pub strategy: MergeStrategy,
+}
+
+/// Outcome of planning (and optionally applying) a merge
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MergeReport {
+    pub conflicts: Vec<MergeConflict>,
+    /// New rows per entity type
+    pub inserted: BTreeMap<String, usize>,
+    /// Matching rows with identical contents
+    pub unchanged: usize,
+    pub kept_local: usize,
+    pub took_imported: usize,
+    pub duplicated: usize,
+    /// Filled in once the archive's attachments have been brought in
+    #[serde(default)]
+    pub attachments: AttachmentReport,
+}
+
+/// Common accessors over the entity models so merging can be written once
+pub trait BundleRow: Clone + PartialEq + Send + Sized {
+    type Active: ActiveModelTrait + ActiveModelBehavior + Send;
+
+    const ENTITY_TYPE: &'static str;
+    /// Names must be unique per campaign (enforced by an index)
+    const UNIQUE_NAME: bool = false;
+
+    fn id(&self) -> &str;
+    fn set_id(&mut self, id: String);
+    fn set_campaign_id(&mut self, campaign_id: &str);
+    fn name(&self) -> Option<&str>;
+    fn set_name(&mut self, name: String);
+    fn updated_at(&self) -> DateTimeUtc;
+    /// Rewrite ids of other entities this row points at
+    fn remap_refs(&mut self, map: &IdMap);
+}
+
+macro_rules! impl_bundle_row {
+    (
+        $module:ident, $entity_type:literal, $name:ident: $name_kind:ident,
+        updated_at: $updated:ident
+        $(, unique_name: $unique:literal)?
+        $(, remap: |$row:ident, $map:ident| $remap:block)?
+    ) => {
+        impl BundleRow for $module::Model {
+            type Active = $module::ActiveModel;
+
+            const ENTITY_TYPE: &'static str = $entity_type;
+            $(const UNIQUE_NAME: bool = $unique;)?
+
+            fn id(&self) -> &str {
+                &self.id
+            }
+
+            fn set_id(&mut self, id: String) {
+                self.id = id;
+            }
+
+            fn set_campaign_id(&mut self, campaign_id: &str) {
+                self.campaign_id = campaign_id.to_string();
+            }
+
+            fn name(&self) -> Option<&str> {
+                impl_bundle_row!(@get $name_kind, self.$name)
+            }
+
+            fn set_name(&mut self, name: String) {
+                impl_bundle_row!(@set $name_kind, self.$name, name)
+            }
+
+            fn updated_at(&self) -> DateTimeUtc {
+                self.$updated
+            }
+
+            #[allow(unused_variables)]
+            fn remap_refs(&mut self, map: &IdMap) {
+                $(
+                    let $row = self;
+                    let $map = map;
+                    $remap
+                )?
+            }
+        }
+    };
+    (@get required, $field:expr) => { Some($field.as_str()) };
+    (@get optional, $field:expr) => { $field.as_deref() };
+    (@get none, $field:expr) => { None };
+    (@set required, $field:expr, $value:ident) => { $field = $value };
+    (@set optional, $field:expr, $value:ident) => { $field = Some($value) };
+    (@set none, $field:expr, $value:ident) => {{ let _ = $value; }};
+}
+
+impl_bundle_row!(players, "player", name: required, updated_at: updated_at);
+impl_bundle_row!(locations, "location", name: required, updated_at: updated_at,
+remap: |row, map| {
+    remap_opt(&mut row.parent_id, map);
+    remap_opt(&mut row.dominant_organization_id, map);
+});
+impl_bundle_row!(characters, "character", name: required, updated_at: updated_at,
+remap: |row, map| {
+    remap_opt(&mut row.death_event_id, map);
+    remap_opt(&mut row.source_hero_id, map);
+});
+impl_bundle_row!(organizations, "organization", name: required, updated_at: updated_at);
+impl_bundle_row!(quests, "quest", name: required, updated_at: updated_at);
+impl_bundle_row!(heroes, "hero", name: required, updated_at: updated_at,
+remap: |row, map| {
+    remap_opt(&mut row.player_id, map);
+    remap_opt(&mut row.source_character_id, map);
+});
+impl_bundle_row!(items, "item", name: required, updated_at: updated_at);
+// Inventory rows only match by id, like relationships
+impl_bundle_row!(hero_items, "hero_item", hero_id: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.hero_id, map);
+    remap_id(&mut row.item_id, map);
+});
+impl_bundle_row!(organization_holdings, "organization_holding", notes: none,
+updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.organization_id, map);
+    remap_opt(&mut row.location_id, map);
+    remap_opt(&mut row.item_id, map);
+});
+impl_bundle_row!(whereabouts, "whereabouts", notes: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.character_id, map);
+    remap_id(&mut row.location_id, map);
+});
+impl_bundle_row!(sessions, "session", title: optional, updated_at: updated_at,
+    remap: |row, map| { remap_opt(&mut row.timeline_event_id, map); });
+impl_bundle_row!(session_absences, "session_absence", reason: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.session_id, map);
+    remap_id(&mut row.player_id, map);
+});
+impl_bundle_row!(session_transcripts, "session_transcript", file_name: required,
+    updated_at: updated_at,
+    remap: |row, map| { remap_id(&mut row.session_id, map); });
+// Disposition history only matches by id
+impl_bundle_row!(dispositions, "disposition", reason: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.character_id, map);
+    remap_opt(&mut row.session_id, map);
+});
+impl_bundle_row!(timeline_branches, "timeline_branch", name: required, updated_at: updated_at,
+remap: |row, map| {
+    remap_opt(&mut row.parent_branch_id, map);
+    remap_opt(&mut row.fork_event_id, map);
+});
+impl_bundle_row!(timeline_events, "timeline_event", title: required, updated_at: updated_at,
+    remap: |row, map| { remap_opt(&mut row.branch_id, map); });
+impl_bundle_row!(secrets, "secret", title: required, updated_at: updated_at,
+remap: |row, map| {
+    remap_opt(&mut row.related_entity_id, map);
+    remap_id_list(&mut row.prerequisites_json, map);
+});
+impl_bundle_row!(handouts, "handout", title: required, updated_at: updated_at,
+    remap: |row, map| { remap_opt(&mut row.secret_id, map); });
+impl_bundle_row!(handout_deliveries, "handout_delivery", status: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.handout_id, map);
+    remap_id(&mut row.player_id, map);
+});
+impl_bundle_row!(snippets, "snippet", name: required, updated_at: updated_at);
+impl_bundle_row!(prompt_templates, "prompt_template", name: required, updated_at: updated_at);
+// Relationships have no name of their own, so they only match by id
+impl_bundle_row!(relationships, "relationship", relationship_type: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.source_id, map);
+    remap_id(&mut row.target_id, map);
+});
+// Strength history follows its relationship and only matches by id
+impl_bundle_row!(strength_changes, "strength_change", reason: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.relationship_id, map);
+    remap_opt(&mut row.session_id, map);
+});
+impl_bundle_row!(recall_cards, "recall_card", front: required, updated_at: updated_at,
+    remap: |row, map| { remap_opt(&mut row.entity_id, map); });
+impl_bundle_row!(progress_clocks, "progress_clock", name: required, updated_at: updated_at,
+    remap: |row, map| { remap_opt(&mut row.entity_id, map); });
+impl_bundle_row!(encounter_tables, "encounter_table", name: required, updated_at: updated_at,
+    remap: |row, map| { remap_id(&mut row.location_id, map); });
+// Encounter entries and the roll log only match by id
+impl_bundle_row!(encounter_entries, "encounter_entry", name: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.table_id, map);
+    remap_opt(&mut row.character_id, map);
+});
+impl_bundle_row!(encounter_rolls, "encounter_roll", result: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.session_id, map);
+    remap_opt(&mut row.location_id, map);
+    remap_opt(&mut row.table_id, map);
+    remap_opt(&mut row.entry_id, map);
+});
+impl_bundle_row!(price_list, "price", name: required, updated_at: updated_at,
+    remap: |row, map| { remap_opt(&mut row.item_id, map); });
+impl_bundle_row!(price_modifiers, "price_modifier", reason: none, updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.location_id, map);
+    remap_opt(&mut row.price_id, map);
+});
+impl_bundle_row!(organization_goals, "organization_goal", title: required,
+updated_at: updated_at,
+remap: |row, map| {
+    remap_id(&mut row.organization_id, map);
+    remap_opt(&mut row.clock_id, map);
+});
+// Goal progress only matches by id
+impl_bundle_row!(goal_advances, "goal_advance", note: none, updated_at: updated_at,
+    remap: |row, map| { remap_id(&mut row.goal_id, map); });
+impl_bundle_row!(content_warnings, "content_warning", topic: required, updated_at: updated_at);
+impl_bundle_row!(annotations, "annotation", body: none, updated_at: updated_at,
+    remap: |row, map| { remap_id(&mut row.entity_id, map); });
+impl_bundle_row!(tags, "tag", name: required, updated_at: created_at, unique_name: true);
+
+fn remap_id(id: &mut String, map: &IdMap) {
+    if let Some(mapped) = map.get(id) {
+        *id = mapped.clone();
+    }
+}
+
+fn remap_opt(id: &mut Option<String>, map: &IdMap) {
+    if let Some(id) = id {
+        remap_id(id, map);
+    }
+}
+
+/// Remap every id in a JSON array column
+fn remap_id_list(json: &mut Option<String>, map: &IdMap) {
+    let Some(mut ids) = json
+        .as_deref()
+        .and_then(|raw| serde_json::from_str::<Vec<String>>(raw).ok())
+    else {
+        return;
+    };
+    for id in &mut ids {
+        remap_id(id, map);
+    }
+    if let Ok(remapped) = serde_json::to_string(&ids) {
+        *json = Some(remapped);
+    }
+}
+
+/// What to do with one imported row once references are remapped
+enum Action {
+    Insert,
+    Update,
+    Skip,
+}
+
+struct Planned<R> {
+    row: R,
+    action: Action,
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Decide the action for each imported row and record id remappings
+fn plan_rows<R: BundleRow>(
+    local: &[R],
+    imported: Vec<R>,
+    strategy_for: &(dyn Fn(&str, &str) -> MergeStrategy + Sync),
+    id_map: &mut IdMap,
+    report: &mut MergeReport,
+) -> Vec<Planned<R>> {
+    let local_by_id: HashMap<&str, &R> = local.iter().map(|r| (r.id(), r)).collect();
+
+    // Only unambiguous names are used for matching
+    let mut name_counts: HashMap<String, usize> = HashMap::new();
+    for name in local.iter().filter_map(|r| r.name()) {
+        *name_counts.entry(normalize(name)).or_default() += 1;
+    }
+    let local_by_name: HashMap<String, &R> = local
+        .iter()
+        .filter_map(|r| r.name().map(|n| (normalize(n), r)))
+        .filter(|(n, _)| name_counts.get(n) == Some(&1))
+        .collect();
+    let mut claimed: HashSet<String> = HashSet::new();
+
+    let mut planned = Vec::with_capacity(imported.len());
+    for mut row in imported {
+        let imported_id = row.id().to_string();
+        let matched = match local_by_id.get(imported_id.as_str()) {
+            Some(local) => Some((*local, MatchKind::Id)),
+            None => row
+                .name()
+                .and_then(|n| local_by_name.get(&normalize(n)))
+                .filter(|local| !claimed.contains(local.id()))
+                .map(|local| (*local, MatchKind::Name)),
+        };
+
+        let Some((local_row, match_kind)) = matched else {
+            id_map.insert(imported_id, row.id().to_string());
+            *report
+                .inserted
+                .entry(R::ENTITY_TYPE.to_string())
+                .or_default() += 1;
+            planned.push(Planned {
+                row,
+                action: Action::Insert,
+            });
+            continue;
+        };
+        claimed.insert(local_row.id().to_string());
+
+        if match_kind == MatchKind::Id && *local_row == row {
+            report.unchanged += 1;
+            planned.push(Planned {
+                row,
+                action: Action::Skip,
+            });
+            continue;
+        }
+
+        report.conflicts.push(MergeConflict {
+            entity_type: R::ENTITY_TYPE.to_string(),
+            imported_id: imported_id.clone(),
+            local_id: local_row.id().to_string(),
+            name: row.name().map(|n| n.to_string()),
+            match_kind,
+            local_updated_at: Some(local_row.updated_at().to_rfc3339()),
+            imported_updated_at: Some(row.updated_at().to_rfc3339()),
+        });
+
+        let action = match strategy_for(R::ENTITY_TYPE, &imported_id) {
+            MergeStrategy::KeepLocal => {
+                report.kept_local += 1;
+                id_map.insert(imported_id, local_row.id().to_string());
+                Action::Skip
+            }
+            MergeStrategy::TakeImported => {
+                report.took_imported += 1;
+                id_map.insert(imported_id, local_row.id().to_string());
+                row.set_id(local_row.id().to_string());
+                Action::Update
+            }
+            MergeStrategy::Duplicate => {
+                report.duplicated += 1;
+                let new_id = uuid::Uuid::new_v4().to_string();
+                id_map.insert(imported_id, new_id.clone());
+                row.set_id(new_id);
+                if R::UNIQUE_NAME {
+                    if let Some(name) = row.name().map(|n| format!("{} (imported)", n)) {
+                        row.set_name(name);
+                    }
+                }
+                Action::Insert
+            }
+        };
+        planned.push(Planned { row, action });
+    }
+
+    planned
+}
+
+async fn apply_rows<C: ConnectionTrait, R>(
+    conn: &C,
+    planned: Vec<Planned<R>>,
+    campaign_id: &str,
+    id_map: &IdMap,
+) -> Result<(), DbErr>
+where
+    R: BundleRow + IntoActiveModel<R::Active>,
+    <<R::Active as ActiveModelTrait>::Entity as EntityTrait>::Model: IntoActiveModel<R::Active>,
+{
+    for Planned { mut row, action } in planned {
+        row.remap_refs(id_map);
+        row.set_campaign_id(campaign_id);
+        let active = row.into_active_model().reset_all();
+        match action {
+            Action::Insert => {
+                <R::Active as ActiveModelTrait>::Entity::insert(active)
+                    .exec_without_returning(conn)
+                    .await?;
+            }
+            Action::Update => {
+                active.update(conn).await?;
+            }
+            Action::Skip => {}
+        }
+    }
+    Ok(())
+}
+
+/// All rows of a bundle, planned against the local copy
+struct MergePlan {
+    campaign: Option<campaigns::Model>,
+    players: Vec<Planned<players::Model>>,
+    locations: Vec<Planned<locations::Model>>,
+    characters: Vec<Planned<characters::Model>>,
+    organizations: Vec<Planned<organizations::Model>>,
+    quests: Vec<Planned<quests::Model>>,
+    heroes: Vec<Planned<heroes::Model>>,
+    items: Vec<Planned<items::Model>>,
+    hero_items: Vec<Planned<hero_items::Model>>,
+    organization_holdings: Vec<Planned<organization_holdings::Model>>,
+    whereabouts: Vec<Planned<whereabouts::Model>>,
+    sessions: Vec<Planned<sessions::Model>>,
+    session_absences: Vec<Planned<session_absences::Model>>,
+    session_transcripts: Vec<Planned<session_transcripts::Model>>,
+    dispositions: Vec<Planned<dispositions::Model>>,
+    timeline_branches: Vec<Planned<timeline_branches::Model>>,
+    timeline_events: Vec<Planned<timeline_events::Model>>,
+    secrets: Vec<Planned<secrets::Model>>,
+    handouts: Vec<Planned<handouts::Model>>,
+    handout_deliveries: Vec<Planned<handout_deliveries::Model>>,
+    snippets: Vec<Planned<snippets::Model>>,
+    prompt_templates: Vec<Planned<prompt_templates::Model>>,
+    relationships: Vec<Planned<relationships::Model>>,
+    strength_changes: Vec<Planned<strength_changes::Model>>,
+    recall_cards: Vec<Planned<recall_cards::Model>>,
+    progress_clocks: Vec<Planned<progress_clocks::Model>>,
+    encounter_tables: Vec<Planned<encounter_tables::Model>>,
+    encounter_entries: Vec<Planned<encounter_entries::Model>>,
+    encounter_rolls: Vec<Planned<encounter_rolls::Model>>,
+    price_list: Vec<Planned<price_list::Model>>,
+    price_modifiers: Vec<Planned<price_modifiers::Model>>,
+    organization_goals: Vec<Planned<organization_goals::Model>>,
+    goal_advances: Vec<Planned<goal_advances::Model>>,
+    content_warnings: Vec<Planned<content_warnings::Model>>,
+    annotations: Vec<Planned<annotations::Model>>,
+    tags: Vec<Planned<tags::Model>>,
+    entity_tags: Vec<entity_tags::Model>,
+    id_map: IdMap,
+}
+
+fn plan_merge(
+    local: &CampaignBundle,
+    imported: CampaignBundle,
+    strategy_for: &(dyn Fn(&str, &str) -> MergeStrategy + Sync),
+    report: &mut MergeReport,
+) -> MergePlan {
+    let mut id_map = IdMap::new();
+
+    // The campaign row itself only ever matches by id
+    let campaign = if local.campaign == imported.campaign {
+        None
+    } else {
+        report.conflicts.push(MergeConflict {
+            entity_type: "campaign".to_string(),
+            imported_id: imported.campaign.id.clone(),
+            local_id: local.campaign.id.clone(),
+            name: Some(imported.campaign.name.clone()),
+            match_kind: MatchKind::Id,
+            local_updated_at: Some(local.campaign.updated_at.to_rfc3339()),
+            imported_updated_at: Some(imported.campaign.updated_at.to_rfc3339()),
+        });
+        match strategy_for("campaign", &imported.campaign.id) {
+            MergeStrategy::TakeImported => {
+                report.took_imported += 1;
+                Some(imported.campaign)
+            }
+            _ => {
+                report.kept_local += 1;
+                None
+            }
+        }
+    };
+
+    MergePlan {
+        campaign,
+        players: plan_rows(
+            &local.players,
+            imported.players,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        locations: plan_rows(
+            &local.locations,
+            parents_first(imported.locations),
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        characters: plan_rows(
+            &local.characters,
+            imported.characters,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        organizations: plan_rows(
+            &local.organizations,
+            imported.organizations,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        quests: plan_rows(
+            &local.quests,
+            imported.quests,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        heroes: plan_rows(
+            &local.heroes,
+            imported.heroes,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        items: plan_rows(
+            &local.items,
+            imported.items,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        hero_items: plan_rows(
+            &local.hero_items,
+            imported.hero_items,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        organization_holdings: plan_rows(
+            &local.organization_holdings,
+            imported.organization_holdings,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        whereabouts: plan_rows(
+            &local.whereabouts,
+            imported.whereabouts,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        sessions: plan_rows(
+            &local.sessions,
+            imported.sessions,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        session_absences: plan_rows(
+            &local.session_absences,
+            imported.session_absences,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        session_transcripts: plan_rows(
+            &local.session_transcripts,
+            imported.session_transcripts,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        dispositions: plan_rows(
+            &local.dispositions,
+            imported.dispositions,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        timeline_branches: plan_rows(
+            &local.timeline_branches,
+            imported.timeline_branches,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        timeline_events: plan_rows(
+            &local.timeline_events,
+            imported.timeline_events,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        secrets: plan_rows(
+            &local.secrets,
+            imported.secrets,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        handouts: plan_rows(
+            &local.handouts,
+            imported.handouts,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        handout_deliveries: plan_rows(
+            &local.handout_deliveries,
+            imported.handout_deliveries,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        snippets: plan_rows(
+            &local.snippets,
+            imported.snippets,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        prompt_templates: plan_rows(
+            &local.prompt_templates,
+            imported.prompt_templates,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        relationships: plan_rows(
+            &local.relationships,
+            imported.relationships,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        strength_changes: plan_rows(
+            &local.strength_changes,
+            imported.strength_changes,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        recall_cards: plan_rows(
+            &local.recall_cards,
+            imported.recall_cards,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        progress_clocks: plan_rows(
+            &local.progress_clocks,
+            imported.progress_clocks,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        encounter_tables: plan_rows(
+            &local.encounter_tables,
+            imported.encounter_tables,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        encounter_entries: plan_rows(
+            &local.encounter_entries,
+            imported.encounter_entries,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        encounter_rolls: plan_rows(
+            &local.encounter_rolls,
+            imported.encounter_rolls,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        price_list: plan_rows(
+            &local.price_list,
+            imported.price_list,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        price_modifiers: plan_rows(
+            &local.price_modifiers,
+            imported.price_modifiers,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        organization_goals: plan_rows(
+            &local.organization_goals,
+            imported.organization_goals,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        goal_advances: plan_rows(
+            &local.goal_advances,
+            imported.goal_advances,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        content_warnings: plan_rows(
+            &local.content_warnings,
+            imported.content_warnings,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        annotations: plan_rows(
+            &local.annotations,
+            imported.annotations,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        tags: plan_rows(
+            &local.tags,
+            imported.tags,
+            strategy_for,
+            &mut id_map,
+            report,
+        ),
+        entity_tags: imported.entity_tags,
+        id_map,
+    }
+}
+
+/// Build a strategy lookup from a default plus per-entity overrides
+pub fn strategy_lookup(
+    default: MergeStrategy,
+    resolutions: &[MergeResolution],
+) -> impl Fn(&str, &str) -> MergeStrategy {
+    let overrides: HashMap<(String, String), MergeStrategy> = resolutions
+        .iter()
+        .map(|r| ((r.entity_type.clone(), r.imported_id.clone()), r.strategy))
+        .collect();
+
+    move |entity_type: &str, imported_id: &str| {
+        overrides
+            .get(&(entity_type.to_string(), imported_id.to_string()))
+            .copied()
+            .unwrap_or(default)
+    }
+}
+
+/// Report what a merge would do without writing anything
+pub fn preview_merge(
+    local: &CampaignBundle,
+    imported: CampaignBundle,
+    strategy_for: &(dyn Fn(&str, &str) -> MergeStrategy + Sync),
+) -> MergeReport {
+    let mut report = MergeReport::default();
+    plan_merge(local, imported, strategy_for, &mut report);
+    report
+}
+
+/// Merge `imported` into the local copy of the campaign
+pub async fn apply_merge<C: ConnectionTrait>(
+    conn: &C,
+    local: &CampaignBundle,
+    imported: CampaignBundle,
+    strategy_for: &(dyn Fn(&str, &str) -> MergeStrategy + Sync),
+) -> Result<MergeReport, DbErr> {
+    let mut report = MergeReport::default();
+    let plan = plan_merge(local, imported, strategy_for, &mut report);
+    let campaign_id = local.campaign.id.as_str();
+    let map = &plan.id_map;
+
+    if let Some(campaign) = plan.campaign {
+        campaign
+            .into_active_model()
+            .reset_all()
+            .update(conn)
+            .await?;
+    }
+
+    apply_rows(conn, plan.players, campaign_id, map).await?;
+    apply_rows(conn, plan.locations, campaign_id, map).await?;
+    apply_rows(conn, plan.characters, campaign_id, map).await?;
+    apply_rows(conn, plan.organizations, campaign_id, map).await?;
+    apply_rows(conn, plan.quests, campaign_id, map).await?;
+    apply_rows(conn, plan.heroes, campaign_id, map).await?;
+    apply_rows(conn, plan.items, campaign_id, map).await?;
+    apply_rows(conn, plan.hero_items, campaign_id, map).await?;
+    apply_rows(conn, plan.organization_holdings, campaign_id, map).await?;
+    apply_rows(conn, plan.whereabouts, campaign_id, map).await?;
+    apply_rows(conn, plan.sessions, campaign_id, map).await?;
+    apply_rows(conn, plan.session_absences, campaign_id, map).await?;
+    apply_rows(conn, plan.session_transcripts, campaign_id, map).await?;
+    apply_rows(conn, plan.dispositions, campaign_id, map).await?;
+    apply_rows(conn, plan.timeline_branches, campaign_id, map).await?;
+    apply_rows(conn, plan.timeline_events, campaign_id, map).await?;
+    apply_rows(conn, plan.secrets, campaign_id, map).await?;
+    apply_rows(conn, plan.handouts, campaign_id, map).await?;
+    apply_rows(conn, plan.handout_deliveries, campaign_id, map).await?;
+    apply_rows(conn, plan.snippets, campaign_id, map).await?;
+    apply_rows(conn, plan.prompt_templates, campaign_id, map).await?;
+    apply_rows(conn, plan.relationships, campaign_id, map).await?;
+    apply_rows(conn, plan.strength_changes, campaign_id, map).await?;
+    apply_rows(conn, plan.recall_cards, campaign_id, map).await?;
+    apply_rows(conn, plan.progress_clocks, campaign_id, map).await?;
+    apply_rows(conn, plan.encounter_tables, campaign_id, map).await?;
+    apply_rows(conn, plan.encounter_entries, campaign_id, map).await?;
+    apply_rows(conn, plan.encounter_rolls, campaign_id, map).await?;
+    apply_rows(conn, plan.price_list, campaign_id, map).await?;
+    apply_rows(conn, plan.price_modifiers, campaign_id, map).await?;
+    apply_rows(conn, plan.organization_goals, campaign_id, map).await?;
+    apply_rows(conn, plan.goal_advances, campaign_id, map).await?;
+    apply_rows(conn, plan.content_warnings, campaign_id, map).await?;
+    apply_rows(conn, plan.annotations, campaign_id, map).await?;
+    apply_rows(conn, plan.tags, campaign_id, map).await?;
+
+    for mut entity_tag in plan.entity_tags {
+        remap_id(&mut entity_tag.tag_id, map);
+        remap_id(&mut entity_tag.entity_id, map);
+        let exists = entity_tags::Entity::find_by_id((
+            entity_tag.tag_id.clone(),
+            entity_tag.entity_type.clone(),
+            entity_tag.entity_id.clone(),
+        ))
+        .one(conn)
+        .await?
+        .is_some();
+        if !exists {
+            entity_tags::Entity::insert(entity_tag.into_active_model().reset_all())
+                .exec_without_returning(conn)
+                .await?;
+        }
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,16 @@
+pub mod archive;
+pub mod attachments;
+pub mod bundle;
+pub mod homebrewery;
+pub mod journal;
+pub mod merge;
+pub mod progress;
+pub mod rich_text;
+pub mod roll20;
+pub mod timeline_svg;
+
+pub use archive::*;
+pub use attachments::*;
+pub use bundle::*;
+pub use merge::*;
+pub use progress::*;
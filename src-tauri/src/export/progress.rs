@@ -0,0 +1,142 @@
+//! Row-by-row outcomes for bulk imports. Each row is passed to a progress
+//! callback as it is handled, which the command wrappers forward to the
+//! frontend as `import-progress` events, and collected into a report so a
+//! bad row is listed rather than failing the whole import.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Tauri event carrying an `ImportProgress` for each imported row
+pub const IMPORT_PROGRESS_EVENT: &str = "import-progress";
+
+/// Callback handed each row's progress as the import runs
+pub type ProgressFn<'a> = &'a (dyn Fn(&ImportProgress) + Send + Sync);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowOutcome {
+    Created,
+    /// Left out because an equivalent row already exists
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportProgress {
+    /// What is being imported, such as the archive's path
+    pub source: String,
+    pub processed: usize,
+    pub total: usize,
+    pub entity_type: String,
+    pub outcome: RowOutcome,
+}
+
+/// A row that was skipped or failed, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowIssue {
+    pub entity_type: String,
+    pub id: String,
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub total: usize,
+    /// Rows created, by entity type
+    pub created: BTreeMap<String, usize>,
+    pub skipped: Vec<RowIssue>,
+    pub failed: Vec<RowIssue>,
+}
+
+/// Counts rows as they're imported, reporting each to the callback
+pub struct ImportTracker<'a> {
+    source: String,
+    processed: usize,
+    report: ImportReport,
+    on_progress: ProgressFn<'a>,
+}
+
+impl<'a> ImportTracker<'a> {
+    pub fn new(source: impl Into<String>, total: usize, on_progress: ProgressFn<'a>) -> Self {
+        Self {
+            source: source.into(),
+            processed: 0,
+            report: ImportReport {
+                total,
+                ..Default::default()
+            },
+            on_progress,
+        }
+    }
+
+    fn advance(&mut self, entity_type: &str, outcome: RowOutcome) {
+        self.processed += 1;
+        (self.on_progress)(&ImportProgress {
+            source: self.source.clone(),
+            processed: self.processed,
+            total: self.report.total,
+            entity_type: entity_type.to_string(),
+            outcome,
+        });
+    }
+
+    pub fn created(&mut self, entity_type: &str) {
+        *self
+            .report
+            .created
+            .entry(entity_type.to_string())
+            .or_default() += 1;
+        self.advance(entity_type, RowOutcome::Created);
+    }
+
+    pub fn skipped(&mut self, issue: RowIssue) {
+        self.advance(&issue.entity_type, RowOutcome::Skipped);
+        self.report.skipped.push(issue);
+    }
+
+    pub fn failed(&mut self, issue: RowIssue) {
+        self.advance(&issue.entity_type, RowOutcome::Failed);
+        self.report.failed.push(issue);
+    }
+
+    pub fn finish(self) -> ImportReport {
+        self.report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_tracker_reports_each_row() {
+        let seen = Mutex::new(Vec::new());
+        let on_progress = |p: &ImportProgress| seen.lock().unwrap().push((p.processed, p.outcome));
+        let mut tracker = ImportTracker::new("test", 3, &on_progress);
+
+        tracker.created("character");
+        tracker.failed(RowIssue {
+            entity_type: "location".to_string(),
+            id: "l1".to_string(),
+            name: Some("Bree".to_string()),
+            reason: "boom".to_string(),
+        });
+        tracker.created("character");
+        let report = tracker.finish();
+
+        assert_eq!(report.total, 3);
+        assert_eq!(report.created["character"], 2);
+        assert_eq!(report.failed.len(), 1);
+        assert!(report.skipped.is_empty());
+        assert_eq!(
+            seen.into_inner().unwrap(),
+            vec![
+                (1, RowOutcome::Created),
+                (2, RowOutcome::Failed),
+                (3, RowOutcome::Created)
+            ]
+        );
+    }
+}
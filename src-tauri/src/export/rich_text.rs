@@ -0,0 +1,311 @@
+//! Entity text fields for other tools. Fields hold editor JSON or plain
+//! text; either way entity references come out as their label.
+
+use serde_json::Value;
+
+/// Replace `[[type:id:label]]` references typed into plain text with their
+/// label, or the id when there is none
+fn resolve_text_references(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("[[") {
+        let Some(len) = rest[start..].find("]]") else {
+            break;
+        };
+        let inner = &rest[start + 2..start + len];
+        let mut parts = inner.splitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(_), Some(_), Some(label)) => {
+                out.push_str(&rest[..start]);
+                out.push_str(label);
+            }
+            (Some(_), Some(id), None) => {
+                out.push_str(&rest[..start]);
+                out.push_str(id);
+            }
+            _ => out.push_str(&rest[..start + len + 2]),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Parse editor JSON, or None when the field is plain text
+fn editor_doc(content: &str) -> Option<Value> {
+    serde_json::from_str::<Value>(content)
+        .ok()
+        .filter(Value::is_object)
+}
+
+fn children(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Text of an entity reference node (mention or citation)
+fn reference_label(node: &Value) -> Option<&str> {
+    node["attrs"]["label"]
+        .as_str()
+        .or_else(|| node["attrs"]["entityId"].as_str())
+}
+
+fn html_text(node: &Value, out: &mut String) {
+    let mut html = escape_html(&resolve_text_references(
+        node["text"].as_str().unwrap_or_default(),
+    ));
+    for mark in node["marks"].as_array().into_iter().flatten() {
+        let (open, close) = match mark["type"].as_str() {
+            Some("bold") => ("<strong>".to_string(), "</strong>"),
+            Some("italic") => ("<em>".to_string(), "</em>"),
+            Some("strike") => ("<s>".to_string(), "</s>"),
+            Some("underline") => ("<u>".to_string(), "</u>"),
+            Some("code") => ("<code>".to_string(), "</code>"),
+            Some("link") => match mark["attrs"]["href"].as_str() {
+                Some(href) => (format!("<a href=\"{}\">", escape_html(href)), "</a>"),
+                None => continue,
+            },
+            _ => continue,
+        };
+        html = format!("{}{}{}", open, html, close);
+    }
+    out.push_str(&html);
+}
+
+fn html_node(node: &Value, out: &mut String) {
+    let wrap = |out: &mut String, tag: &str| {
+        out.push_str(&format!("<{}>", tag));
+        for child in children(node) {
+            html_node(child, out);
+        }
+        out.push_str(&format!("</{}>", tag));
+    };
+    match node["type"].as_str().unwrap_or_default() {
+        "text" => html_text(node, out),
+        "mention" | "citation" => out.push_str(&escape_html(reference_label(node).unwrap_or(""))),
+        "hardBreak" => out.push_str("<br>"),
+        "horizontalRule" => out.push_str("<hr>"),
+        "paragraph" => wrap(out, "p"),
+        "heading" => {
+            let level = node["attrs"]["level"].as_u64().unwrap_or(2).clamp(1, 6);
+            wrap(out, &format!("h{}", level));
+        }
+        "bulletList" | "taskList" => wrap(out, "ul"),
+        "orderedList" => wrap(out, "ol"),
+        "listItem" | "taskItem" => wrap(out, "li"),
+        "blockquote" => wrap(out, "blockquote"),
+        "codeBlock" => {
+            out.push_str("<pre>");
+            wrap(out, "code");
+            out.push_str("</pre>");
+        }
+        _ => {
+            for child in children(node) {
+                html_node(child, out);
+            }
+        }
+    }
+}
+
+/// A text field as HTML. Plain text becomes one paragraph per blank-line
+/// separated block.
+pub fn to_html(content: &str) -> String {
+    match editor_doc(content) {
+        Some(doc) => {
+            let mut out = String::new();
+            html_node(&doc, &mut out);
+            out
+        }
+        None => resolve_text_references(content)
+            .replace("\r\n", "\n")
+            .split("\n\n")
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| format!("<p>{}</p>", escape_html(p).replace('\n', "<br>")))
+            .collect(),
+    }
+}
+
+fn markdown_text(node: &Value) -> String {
+    let raw = resolve_text_references(node["text"].as_str().unwrap_or_default());
+    // Markdown emphasis can't start or end on a space, so keep the spaces outside
+    let core = raw.trim();
+    if core.is_empty() {
+        return raw;
+    }
+    let start = raw.len() - raw.trim_start().len();
+    let (lead, trail) = (&raw[..start], &raw[start + core.len()..]);
+    let mut text = core.to_string();
+    for mark in node["marks"].as_array().into_iter().flatten() {
+        text = match mark["type"].as_str() {
+            Some("bold") => format!("**{}**", text),
+            Some("italic") => format!("*{}*", text),
+            Some("strike") => format!("~~{}~~", text),
+            Some("code") => format!("`{}`", text),
+            Some("link") => match mark["attrs"]["href"].as_str() {
+                Some(href) => format!("[{}]({})", text, href),
+                None => text,
+            },
+            _ => text,
+        };
+    }
+    format!("{}{}{}", lead, text, trail)
+}
+
+/// Inline content of a node: text, references and line breaks
+fn markdown_inline(node: &Value) -> String {
+    children(node)
+        .iter()
+        .map(|child| match child["type"].as_str().unwrap_or_default() {
+            "text" => markdown_text(child),
+            "mention" | "citation" => reference_label(child).unwrap_or("").to_string(),
+            "hardBreak" => "\\\n".to_string(),
+            _ => markdown_inline(child),
+        })
+        .collect()
+}
+
+/// Markdown blocks for a node, each without a trailing newline
+fn markdown_blocks(node: &Value, heading_offset: u64) -> Vec<String> {
+    let blocks_of = |node: &Value| -> Vec<String> {
+        children(node)
+            .iter()
+            .flat_map(|child| markdown_blocks(child, heading_offset))
+            .collect()
+    };
+    let prefix_lines = |blocks: Vec<String>, first: &str, rest: &str| -> String {
+        blocks
+            .join("\n\n")
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let prefix = if i == 0 { first } else { rest };
+                format!("{}{}", prefix, line).trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    match node["type"].as_str().unwrap_or_default() {
+        "paragraph" => {
+            let text = markdown_inline(node);
+            if text.trim().is_empty() {
+                Vec::new()
+            } else {
+                vec![text]
+            }
+        }
+        "heading" => {
+            let level = node["attrs"]["level"].as_u64().unwrap_or(2) + heading_offset;
+            vec![format!(
+                "{} {}",
+                "#".repeat(level.clamp(1, 6) as usize),
+                markdown_inline(node)
+            )]
+        }
+        "bulletList" | "taskList" | "orderedList" => {
+            let ordered = node["type"] == "orderedList";
+            let items = children(node)
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let marker = if ordered {
+                        format!("{}. ", i + 1)
+                    } else {
+                        "- ".to_string()
+                    };
+                    let indent = " ".repeat(marker.len());
+                    // Tight list: an item's own paragraphs stay on one line each
+                    prefix_lines(blocks_of(item), &marker, &indent).replace("\n\n", "\n")
+                })
+                .collect::<Vec<_>>();
+            vec![items.join("\n")]
+        }
+        "blockquote" => vec![prefix_lines(blocks_of(node), "> ", "> ")],
+        "codeBlock" => vec![format!("```\n{}\n```", markdown_inline(node))],
+        "horizontalRule" => vec!["---".to_string()],
+        "text" | "mention" | "citation" | "hardBreak" => {
+            vec![markdown_inline(&serde_json::json!({ "content": [node] }))]
+        }
+        _ => blocks_of(node),
+    }
+}
+
+/// A text field as markdown, with the field's own headings pushed down
+/// `heading_offset` levels to sit under the document's. Plain text is
+/// assumed to be markdown already.
+pub fn to_markdown(content: &str, heading_offset: u64) -> String {
+    match editor_doc(content) {
+        Some(doc) => markdown_blocks(&doc, heading_offset).join("\n\n"),
+        None => resolve_text_references(content.trim()).replace("\r\n", "\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_json_to_html() {
+        let doc = r#"{"type":"doc","content":[
+            {"type":"heading","attrs":{"level":2},"content":[{"type":"text","text":"Bree"}]},
+            {"type":"paragraph","content":[
+                {"type":"text","text":"Run by "},
+                {"type":"mention","attrs":{"entityType":"character","entityId":"c1","label":"Butterbur"}},
+                {"type":"text","text":" & co","marks":[{"type":"bold"}]}
+            ]},
+            {"type":"bulletList","content":[{"type":"listItem","content":[
+                {"type":"paragraph","content":[{"type":"text","text":"Inn"}]}
+            ]}]}
+        ]}"#;
+        assert_eq!(
+            to_html(doc),
+            "<h2>Bree</h2><p>Run by Butterbur<strong> &amp; co</strong></p><ul><li><p>Inn</p></li></ul>"
+        );
+    }
+
+    #[test]
+    fn test_editor_json_to_markdown() {
+        let doc = r#"{"type":"doc","content":[
+            {"type":"heading","attrs":{"level":1},"content":[{"type":"text","text":"Bree"}]},
+            {"type":"paragraph","content":[
+                {"type":"text","text":"Run by "},
+                {"type":"citation","attrs":{"entityType":"character","entityId":"c1","label":"Butterbur"}},
+                {"type":"text","text":" of the inn","marks":[{"type":"italic"}]}
+            ]},
+            {"type":"orderedList","content":[
+                {"type":"listItem","content":[{"type":"paragraph","content":[{"type":"text","text":"Rooms"}]}]},
+                {"type":"listItem","content":[{"type":"paragraph","content":[{"type":"text","text":"Ale"}]}]}
+            ]},
+            {"type":"blockquote","content":[{"type":"paragraph","content":[{"type":"text","text":"Welcome!"}]}]}
+        ]}"#;
+        assert_eq!(
+            to_markdown(doc, 2),
+            "### Bree\n\nRun by Butterbur *of the inn*\n\n1. Rooms\n2. Ale\n\n> Welcome!"
+        );
+    }
+
+    #[test]
+    fn test_plain_text_to_html() {
+        assert_eq!(
+            to_html("Ask [[character:c1:Butterbur]] <quietly>.\nThen leave.\n\nLater."),
+            "<p>Ask Butterbur &lt;quietly&gt;.<br>Then leave.</p><p>Later.</p>"
+        );
+    }
+}
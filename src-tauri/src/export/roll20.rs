@@ -0,0 +1,94 @@
+//! Roll20 journal handouts. Roll20 has no journal import of its own, so the
+//! JSON carries the handout properties an API script passes to
+//! `createObj("handout", ...)`, and the HTML page is for pasting handouts in
+//! by hand.
+
+use super::journal::{JournalEntry, JournalSection};
+use super::rich_text::{escape_html, to_html};
+use serde::{Deserialize, Serialize};
+
+/// A handout as Roll20 stores it: `notes` is shown to whoever can see the
+/// handout, `gmnotes` only to GMs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Roll20Handout {
+    pub name: String,
+    pub notes: String,
+    pub gmnotes: String,
+    /// `"all"` to show the handout in every player's journal, or empty
+    pub inplayerjournals: String,
+    pub archived: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Roll20Journal {
+    pub campaign: String,
+    pub handouts: Vec<Roll20Handout>,
+}
+
+fn render_sections<'a>(sections: impl Iterator<Item = &'a JournalSection>) -> String {
+    let mut html = String::new();
+    for section in sections {
+        if let Some(label) = section.label {
+            html.push_str(&format!("<h3>{}</h3>", label));
+        }
+        html.push_str(&to_html(&section.content));
+    }
+    html
+}
+
+pub fn build_handout(entry: &JournalEntry, share_with_players: bool) -> Roll20Handout {
+    let mut notes = String::new();
+    if let Some(subtitle) = &entry.subtitle {
+        notes.push_str(&format!("<p><em>{}</em></p>", escape_html(subtitle)));
+    }
+    notes.push_str(&render_sections(entry.player_sections()));
+
+    Roll20Handout {
+        name: entry.name.clone(),
+        notes,
+        gmnotes: render_sections(entry.gm_sections()),
+        inplayerjournals: if share_with_players { "all" } else { "" }.to_string(),
+        archived: false,
+    }
+}
+
+pub fn build_roll20_journal(
+    campaign: &str,
+    entries: &[JournalEntry],
+    share_with_players: bool,
+) -> Roll20Journal {
+    Roll20Journal {
+        campaign: campaign.to_string(),
+        handouts: entries
+            .iter()
+            .map(|e| build_handout(e, share_with_players))
+            .collect(),
+    }
+}
+
+/// One page with every handout, GM notes set apart under each
+pub fn render_roll20_html(journal: &Roll20Journal) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape_html(&journal.campaign),
+        escape_html(&journal.campaign)
+    ));
+    for handout in &journal.handouts {
+        html.push_str(&format!(
+            "<section>\n<h2>{}</h2>\n{}\n",
+            escape_html(&handout.name),
+            handout.notes
+        ));
+        if !handout.gmnotes.is_empty() {
+            html.push_str(&format!(
+                "<aside>\n<h2>GM notes</h2>\n{}\n</aside>\n",
+                handout.gmnotes
+            ));
+        }
+        html.push_str("</section>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
@@ -0,0 +1,257 @@
+//! Timeline charts as standalone SVG. Events sit on a horizontal axis by
+//! sort order, one lane per significance with the most significant on top,
+//! and optional eras are drawn as labelled bands behind the lanes.
+
+use super::rich_text::escape_html;
+use serde::{Deserialize, Serialize};
+
+/// Significance levels from most to least significant. Others get lanes
+/// below these, alphabetically.
+pub const SIGNIFICANCE_ORDER: &[&str] = &["world", "major", "moderate", "local", "minor"];
+
+const DEFAULT_WIDTH: u32 = 1200;
+const MIN_WIDTH: u32 = 400;
+const MAX_WIDTH: u32 = 8000;
+const LANE_HEIGHT: f64 = 70.0;
+const LABEL_WIDTH: f64 = 110.0;
+const MARGIN: f64 = 20.0;
+const ERA_BAND_HEIGHT: f64 = 24.0;
+const AXIS_HEIGHT: f64 = 30.0;
+/// Longest event title drawn before it's cut short
+const MAX_TITLE_CHARS: usize = 28;
+
+/// A named span of sort order values, e.g. an age or a campaign arc
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEra {
+    pub name: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// What the renderer needs from an event
+#[derive(Debug, Clone)]
+pub struct ChartEvent {
+    pub title: String,
+    pub date_display: String,
+    pub sort_order: i64,
+    pub significance: String,
+}
+
+/// Lane order for the significances present, most significant first
+fn lanes(events: &[ChartEvent]) -> Vec<String> {
+    let mut lanes: Vec<String> = SIGNIFICANCE_ORDER
+        .iter()
+        .filter(|s| events.iter().any(|e| e.significance == **s))
+        .map(|s| s.to_string())
+        .collect();
+    let mut others: Vec<String> = events
+        .iter()
+        .map(|e| e.significance.clone())
+        .filter(|s| !SIGNIFICANCE_ORDER.contains(&s.as_str()))
+        .collect();
+    others.sort();
+    others.dedup();
+    lanes.extend(others);
+    lanes
+}
+
+fn truncate(title: &str) -> String {
+    if title.chars().count() <= MAX_TITLE_CHARS {
+        title.to_string()
+    } else {
+        let cut: String = title.chars().take(MAX_TITLE_CHARS - 1).collect();
+        format!("{}…", cut.trim_end())
+    }
+}
+
+/// Render the chart. `width` is clamped to a sensible range.
+pub fn render_timeline_svg(
+    campaign_name: &str,
+    events: &[ChartEvent],
+    eras: &[TimelineEra],
+    width: Option<u32>,
+) -> String {
+    let width = f64::from(width.unwrap_or(DEFAULT_WIDTH).clamp(MIN_WIDTH, MAX_WIDTH));
+    let lanes = lanes(events);
+    let lane_count = lanes.len().max(1) as f64;
+    let era_height = if eras.is_empty() {
+        0.0
+    } else {
+        ERA_BAND_HEIGHT
+    };
+    let top = MARGIN + 30.0 + era_height;
+    let height = top + lane_count * LANE_HEIGHT + AXIS_HEIGHT + MARGIN;
+
+    let (min, max) = events
+        .iter()
+        .map(|e| e.sort_order)
+        .chain(eras.iter().flat_map(|e| [e.start, e.end]))
+        .fold(None, |range: Option<(i64, i64)>, v| match range {
+            Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+            None => Some((v, v)),
+        })
+        .unwrap_or((0, 0));
+    let left = MARGIN + LABEL_WIDTH;
+    let right = width - MARGIN;
+    let x_of = |value: i64| -> f64 {
+        if max == min {
+            (left + right) / 2.0
+        } else {
+            left + (value - min) as f64 / (max - min) as f64 * (right - left)
+        }
+    };
+
+    let mut out = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif">"#,
+            w = width,
+            h = height
+        ),
+        format!(
+            r##"<rect width="{}" height="{}" fill="#ffffff"/>"##,
+            width, height
+        ),
+        format!(
+            r##"<text x="{}" y="{}" font-size="18" font-weight="bold" fill="#222222">{}</text>"##,
+            MARGIN,
+            MARGIN + 16.0,
+            escape_html(campaign_name)
+        ),
+    ];
+
+    let lanes_bottom = top + lane_count * LANE_HEIGHT;
+    for (i, era) in eras.iter().enumerate() {
+        let x1 = x_of(era.start.min(era.end));
+        let x2 = x_of(era.start.max(era.end));
+        let fill = if i % 2 == 0 { "#eef2f7" } else { "#f7f2ea" };
+        out.push(format!(
+            r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"/>"##,
+            x1,
+            top - era_height,
+            (x2 - x1).max(1.0),
+            lanes_bottom - top + era_height,
+            fill
+        ));
+        out.push(format!(
+            r##"<text x="{:.1}" y="{:.1}" font-size="12" fill="#555555">{}</text>"##,
+            x1 + 4.0,
+            top - era_height + 16.0,
+            escape_html(&era.name)
+        ));
+    }
+
+    for (i, lane) in lanes.iter().enumerate() {
+        let y = top + i as f64 * LANE_HEIGHT + LANE_HEIGHT / 2.0;
+        out.push(format!(
+            r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#cccccc" stroke-dasharray="4 4"/>"##,
+            left, y, right, y
+        ));
+        out.push(format!(
+            r##"<text x="{}" y="{:.1}" font-size="13" fill="#333333">{}</text>"##,
+            MARGIN,
+            y + 4.0,
+            escape_html(lane)
+        ));
+    }
+
+    // Bigger dots for more significant lanes
+    for (n, event) in events.iter().enumerate() {
+        let lane = lanes
+            .iter()
+            .position(|l| *l == event.significance)
+            .unwrap_or(0);
+        let x = x_of(event.sort_order);
+        let y = top + lane as f64 * LANE_HEIGHT + LANE_HEIGHT / 2.0;
+        let radius = (8.0 - lane as f64).max(4.0);
+        // Alternate labels above and below the line so neighbours overlap less
+        let label_y = if n % 2 == 0 { y - 12.0 } else { y + 20.0 };
+        out.push(format!(
+            r##"<g><title>{} ({})</title><circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="#3b6ea5"/><text x="{:.1}" y="{:.1}" font-size="11" text-anchor="middle" fill="#222222">{}</text></g>"##,
+            escape_html(&event.title),
+            escape_html(&event.date_display),
+            x,
+            y,
+            radius,
+            x,
+            label_y,
+            escape_html(&truncate(&event.title))
+        ));
+    }
+
+    let axis_y = lanes_bottom + 8.0;
+    out.push(format!(
+        r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#888888"/>"##,
+        left, axis_y, right, axis_y
+    ));
+    if let (Some(first), Some(last)) = (events.first(), events.last()) {
+        out.push(format!(
+            r##"<text x="{:.1}" y="{:.1}" font-size="11" fill="#555555">{}</text>"##,
+            left,
+            axis_y + 16.0,
+            escape_html(&first.date_display)
+        ));
+        if events.len() > 1 {
+            out.push(format!(
+                r##"<text x="{:.1}" y="{:.1}" font-size="11" text-anchor="end" fill="#555555">{}</text>"##,
+                right,
+                axis_y + 16.0,
+                escape_html(&last.date_display)
+            ));
+        }
+    } else {
+        out.push(format!(
+            r##"<text x="{:.1}" y="{:.1}" font-size="13" fill="#888888">No events</text>"##,
+            left,
+            top + LANE_HEIGHT / 2.0
+        ));
+    }
+
+    out.push("</svg>".to_string());
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(title: &str, sort_order: i64, significance: &str) -> ChartEvent {
+        ChartEvent {
+            title: title.to_string(),
+            date_display: format!("Day {}", sort_order),
+            sort_order,
+            significance: significance.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_lanes_put_known_significance_first() {
+        let events = vec![
+            event("a", 1, "minor"),
+            event("b", 2, "world"),
+            event("c", 3, "battle"),
+            event("d", 4, "minor"),
+        ];
+        assert_eq!(lanes(&events), vec!["world", "minor", "battle"]);
+    }
+
+    #[test]
+    fn test_render_escapes_and_draws_eras() {
+        let events = vec![event("Fall of <Myth Drannor>", 10, "world")];
+        let eras = vec![TimelineEra {
+            name: "Age of Humanity".to_string(),
+            start: 0,
+            end: 20,
+        }];
+        let svg = render_timeline_svg("Sword & Sorcery", &events, &eras, None);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains("Sword &amp; Sorcery"));
+        assert!(svg.contains("Fall of &lt;Myth Drannor&gt;"));
+        assert!(svg.contains("Age of Humanity"));
+        assert!(!svg.contains("No events"));
+
+        let empty = render_timeline_svg("Empty", &[], &[], Some(10));
+        assert!(empty.contains(r#"width="400""#));
+        assert!(empty.contains("No events"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Image generation for entity portraits and maps.
+//!
+//! Each backend implements `ImageProvider`; `ImageProviderConfig` picks one
+//! from what the frontend sends, since API keys live in the frontend's store
+//! rather than the campaign database. Providers return the finished file;
+//! storing it as an attachment is up to the caller.
+
+pub mod openai;
+pub mod stable_diffusion;
+
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+/// Providers that can be named in `ImageProviderConfig::provider`
+pub const IMAGE_PROVIDERS: &[&str] = &["openai", "stable_diffusion"];
+
+/// Square images unless the caller asks otherwise
+pub const DEFAULT_IMAGE_SIZE: &str = "1024x1024";
+
+/// Generation is slow, local models especially
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// A generated image file
+#[derive(Debug, Clone)]
+pub struct GeneratedImage {
+    pub bytes: Vec<u8>,
+    pub mime_type: String,
+}
+
+impl GeneratedImage {
+    /// File extension matching the image's MIME type
+    pub fn extension(&self) -> &'static str {
+        match self.mime_type.as_str() {
+            "image/jpeg" => "jpg",
+            "image/webp" => "webp",
+            _ => "png",
+        }
+    }
+}
+
+/// A backend that turns a text prompt into an image
+pub trait ImageProvider {
+    /// Short name recorded as the attachment's source, e.g. "openai"
+    fn name(&self) -> &'static str;
+
+    fn generate(
+        &self,
+        prompt: &str,
+    ) -> impl Future<Output = Result<GeneratedImage, AppError>> + Send;
+}
+
+/// Which provider to use and how to reach it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImageProviderConfig {
+    /// One of `IMAGE_PROVIDERS`
+    pub provider: String,
+    /// Required for "openai"
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Server address; each provider has its own default
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// "WIDTHxHEIGHT", `DEFAULT_IMAGE_SIZE` when unset
+    #[serde(default)]
+    pub size: Option<String>,
+}
+
+/// Any configured provider, so commands can pick one at runtime
+pub enum ConfiguredProvider {
+    OpenAi(openai::OpenAiImages),
+    StableDiffusion(stable_diffusion::StableDiffusionImages),
+}
+
+impl ConfiguredProvider {
+    pub fn from_config(config: &ImageProviderConfig) -> Result<Self, AppError> {
+        let size = config.size.as_deref().unwrap_or(DEFAULT_IMAGE_SIZE);
+        let (width, height) = parse_size(size)?;
+
+        match config.provider.as_str() {
+            "openai" => {
+                let api_key = config
+                    .api_key
+                    .as_deref()
+                    .filter(|k| !k.trim().is_empty())
+                    .ok_or_else(|| {
+                        AppError::Validation("api_key: required for OpenAI images".to_string())
+                    })?;
+                Ok(Self::OpenAi(openai::OpenAiImages::new(
+                    api_key,
+                    config.base_url.as_deref(),
+                    config.model.as_deref(),
+                    size,
+                )?))
+            }
+            "stable_diffusion" => Ok(Self::StableDiffusion(
+                stable_diffusion::StableDiffusionImages::new(
+                    config.base_url.as_deref(),
+                    width,
+                    height,
+                )?,
+            )),
+            _ => Err(AppError::Validation(format!(
+                "provider: must be one of: {}",
+                IMAGE_PROVIDERS.join(", ")
+            ))),
+        }
+    }
+}
+
+impl ImageProvider for ConfiguredProvider {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::OpenAi(p) => p.name(),
+            Self::StableDiffusion(p) => p.name(),
+        }
+    }
+
+    async fn generate(&self, prompt: &str) -> Result<GeneratedImage, AppError> {
+        match self {
+            Self::OpenAi(p) => p.generate(prompt).await,
+            Self::StableDiffusion(p) => p.generate(prompt).await,
+        }
+    }
+}
+
+fn http_client() -> Result<reqwest::Client, AppError> {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::Internal(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// "1024x768" as (1024, 768)
+fn parse_size(size: &str) -> Result<(u32, u32), AppError> {
+    size.split_once('x')
+        .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)))
+        .filter(|&(w, h): &(u32, u32)| (64..=4096).contains(&w) && (64..=4096).contains(&h))
+        .ok_or_else(|| {
+            AppError::Validation(
+                "size: must look like 1024x1024, between 64 and 4096 pixels".to_string(),
+            )
+        })
+}
+
+/// Decode standard base64, which both providers use for image data
+pub(crate) fn decode_base64(input: &str) -> Result<Vec<u8>, AppError> {
+    fn value(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+            b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    // Local servers sometimes send a data URL rather than bare base64
+    let input = input.split_once(";base64,").map_or(input, |(_, data)| data);
+    let invalid = || AppError::AiProvider("image data is not valid base64".to_string());
+
+    let mut bytes = Vec::with_capacity(input.len() / 4 * 3);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in input.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        if c == b'=' {
+            break;
+        }
+        buffer = (buffer << 6) | value(c).ok_or_else(invalid)?;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
+
+/// MIME type from a file's leading bytes; generated images are PNG otherwise
+pub(crate) fn sniff_mime_type(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else {
+        "image/png"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64("TG9yZQ==").unwrap(), b"Lore");
+        assert_eq!(decode_base64("d2Vh\ndmVy").unwrap(), b"weaver");
+        assert_eq!(
+            decode_base64("data:image/png;base64,iVBORw==").unwrap(),
+            [0x89, b'P', b'N', b'G']
+        );
+        assert!(decode_base64("not*base64").is_err());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024x768").unwrap(), (1024, 768));
+        assert!(parse_size("huge").is_err());
+        assert!(parse_size("10x10").is_err());
+    }
+
+    #[test]
+    fn test_from_config_requires_openai_key() {
+        let config = ImageProviderConfig {
+            provider: "openai".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ConfiguredProvider::from_config(&config),
+            Err(AppError::Validation(_))
+        ));
+
+        let config = ImageProviderConfig {
+            provider: "midjourney".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ConfiguredProvider::from_config(&config),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,100 @@
+//! OpenAI's Images API.
+
+use super::{decode_base64, http_client, sniff_mime_type, GeneratedImage, ImageProvider};
+use crate::error::AppError;
+use serde::Deserialize;
+use serde_json::json;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+pub const DEFAULT_MODEL: &str = "gpt-image-1";
+
+#[derive(Debug, Deserialize)]
+struct ImagesResponse {
+    data: Vec<ImageData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImageData {
+    b64_json: Option<String>,
+}
+
+pub struct OpenAiImages {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+    size: String,
+}
+
+impl OpenAiImages {
+    pub fn new(
+        api_key: &str,
+        base_url: Option<&str>,
+        model: Option<&str>,
+        size: &str,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            http: http_client()?,
+            base_url: base_url
+                .unwrap_or(DEFAULT_BASE_URL)
+                .trim_end_matches('/')
+                .to_string(),
+            api_key: api_key.to_string(),
+            model: model.unwrap_or(DEFAULT_MODEL).to_string(),
+            size: size.to_string(),
+        })
+    }
+}
+
+impl ImageProvider for OpenAiImages {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    async fn generate(&self, prompt: &str) -> Result<GeneratedImage, AppError> {
+        let mut body = json!({
+            "model": self.model,
+            "prompt": prompt,
+            "size": self.size,
+            "n": 1,
+        });
+        // DALL-E models return URLs unless asked; newer models always send base64
+        if self.model.starts_with("dall-e") {
+            body["response_format"] = json!("b64_json");
+        }
+
+        let response = self
+            .http
+            .post(format!("{}/images/generations", self.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AppError::AiProvider(format!("OpenAI unreachable: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::AiProvider(format!(
+                "OpenAI returned {}: {}",
+                status, body
+            )));
+        }
+
+        let images: ImagesResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::AiProvider(format!("Invalid OpenAI response: {}", e)))?;
+        let encoded = images
+            .data
+            .into_iter()
+            .find_map(|d| d.b64_json)
+            .ok_or_else(|| AppError::AiProvider("OpenAI returned no image".to_string()))?;
+
+        let bytes = decode_base64(&encoded)?;
+        Ok(GeneratedImage {
+            mime_type: sniff_mime_type(&bytes).to_string(),
+            bytes,
+        })
+    }
+}
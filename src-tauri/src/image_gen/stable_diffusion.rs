@@ -0,0 +1,86 @@
+//! A local Stable Diffusion server speaking the AUTOMATIC1111 web UI API,
+//! which Forge, SD.Next and others also implement.
+
+use super::{decode_base64, http_client, sniff_mime_type, GeneratedImage, ImageProvider};
+use crate::error::AppError;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Where the web UI listens when started with `--api`
+pub const DEFAULT_BASE_URL: &str = "http://127.0.0.1:7860";
+
+const STEPS: u32 = 30;
+
+#[derive(Debug, Deserialize)]
+struct Txt2ImgResponse {
+    images: Vec<String>,
+}
+
+pub struct StableDiffusionImages {
+    http: reqwest::Client,
+    base_url: String,
+    width: u32,
+    height: u32,
+}
+
+impl StableDiffusionImages {
+    pub fn new(base_url: Option<&str>, width: u32, height: u32) -> Result<Self, AppError> {
+        Ok(Self {
+            http: http_client()?,
+            base_url: base_url
+                .unwrap_or(DEFAULT_BASE_URL)
+                .trim_end_matches('/')
+                .to_string(),
+            width,
+            height,
+        })
+    }
+}
+
+impl ImageProvider for StableDiffusionImages {
+    fn name(&self) -> &'static str {
+        "stable_diffusion"
+    }
+
+    async fn generate(&self, prompt: &str) -> Result<GeneratedImage, AppError> {
+        let body = json!({
+            "prompt": prompt,
+            "width": self.width,
+            "height": self.height,
+            "steps": STEPS,
+            "batch_size": 1,
+        });
+
+        let response = self
+            .http
+            .post(format!("{}/sdapi/v1/txt2img", self.base_url))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::AiProvider(format!("Stable Diffusion server unreachable: {}", e))
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::AiProvider(format!(
+                "Stable Diffusion server returned {}: {}",
+                status, body
+            )));
+        }
+
+        let result: Txt2ImgResponse = response.json().await.map_err(|e| {
+            AppError::AiProvider(format!("Invalid Stable Diffusion response: {}", e))
+        })?;
+        let encoded = result.images.into_iter().next().ok_or_else(|| {
+            AppError::AiProvider("Stable Diffusion server returned no image".to_string())
+        })?;
+
+        let bytes = decode_base64(&encoded)?;
+        Ok(GeneratedImage {
+            mime_type: sniff_mime_type(&bytes).to_string(),
+            bytes,
+        })
+    }
+}
@@ -1,12 +1,26 @@
+pub mod command_groups;
 pub mod commands;
-mod db;
+pub mod db;
+pub mod demo;
+pub mod embeddings;
 mod error;
+pub mod export;
+pub mod image_gen;
+mod logging;
+pub mod markdown;
+pub mod mirror;
+pub mod share;
+pub mod srd;
+pub mod sync;
+pub mod text;
+pub mod tokenizer;
+pub mod tray;
 
 use db::{init_database, AppState};
 use tauri::Manager;
 
 // Re-export for use in commands
-pub use error::AppError;
+pub use error::{AppError, ErrorCode, SchemaMismatch};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -14,12 +28,22 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .setup(|app| {
-            // Initialize database on startup
+            // Start logging first so database initialization is captured
+            let log_state = logging::init_logging(&app.path().app_log_dir()?)?;
+            app.manage(log_state);
+            app.manage(share::ShareState::default());
+            app.manage(tray::TrayState::default());
+            tray::init_tray(app)?;
+
+            // Initialize database on startup. An encrypted database without a
+            // saved key stays locked until `unlock_database` is called.
             tauri::async_runtime::block_on(async {
                 let db = init_database(app)
                     .await
                     .expect("Failed to initialize database");
-                app.manage(AppState { db });
+                if let Some(db) = db {
+                    app.manage(AppState { db });
+                }
             });
             Ok(())
         })
@@ -30,12 +54,34 @@ pub fn run() {
             commands::campaign::list_campaigns,
             commands::campaign::update_campaign,
             commands::campaign::delete_campaign,
+            commands::campaign::lock_campaign,
+            commands::campaign::unlock_campaign,
+            commands::campaign::create_demo_campaign,
+            commands::bootstrap::bootstrap_campaign,
+            commands::campaign_settings::get_campaign_settings,
+            commands::campaign_settings::update_campaign_settings,
+            commands::campaign_settings::build_ai_system_prompt,
             // Character commands
             commands::character::create_character,
             commands::character::get_character,
             commands::character::list_characters,
             commands::character::update_character,
             commands::character::delete_character,
+            commands::character::set_character_life_dates,
+            commands::character::add_character_alias,
+            commands::character::remove_character_alias,
+            commands::character::set_character_roleplay,
+            commands::roleplay_card::get_roleplay_card,
+            commands::conversion::convert_character_to_hero,
+            commands::conversion::convert_hero_to_character,
+            commands::duplicates::find_duplicate_candidates,
+            commands::duplicates::merge_entities,
+            commands::appearance::set_entity_appearance,
+            // Disposition commands
+            commands::disposition::adjust_disposition,
+            commands::disposition::get_disposition_history,
+            commands::disposition::list_dispositions,
+            commands::disposition::delete_disposition,
             // Location commands
             commands::location::create_location,
             commands::location::get_location,
@@ -43,48 +89,215 @@ pub fn run() {
             commands::location::get_location_children,
             commands::location::update_location,
             commands::location::delete_location,
+            commands::whereabouts::move_character,
+            commands::whereabouts::get_character_whereabouts,
+            commands::whereabouts::clear_character_whereabouts,
+            commands::whereabouts::list_characters_at_location,
             // Organization commands
             commands::organization::create_organization,
             commands::organization::get_organization,
             commands::organization::list_organizations,
             commands::organization::update_organization,
             commands::organization::delete_organization,
+            commands::organization_holding::add_organization_holding,
+            commands::organization_holding::remove_organization_holding,
+            commands::organization_holding::list_organization_holdings,
+            commands::organization_goal::create_organization_goal,
+            commands::organization_goal::get_organization_goal,
+            commands::organization_goal::list_organization_goals,
+            commands::organization_goal::update_organization_goal,
+            commands::organization_goal::advance_organization_goal,
+            commands::organization_goal::list_goal_advances,
+            commands::organization_goal::delete_organization_goal,
+            commands::organization_goal::get_world_moves_on,
             // Quest commands
             commands::quest::create_quest,
             commands::quest::get_quest,
             commands::quest::list_quests,
             commands::quest::update_quest,
             commands::quest::delete_quest,
+            commands::quest::get_quest_board,
             // Hero commands
             commands::hero::create_hero,
             commands::hero::get_hero,
             commands::hero::list_heroes,
             commands::hero::update_hero,
             commands::hero::delete_hero,
+            // Item and inventory commands
+            commands::item::create_item,
+            commands::item::get_item,
+            commands::item::list_items,
+            commands::item::update_item,
+            commands::item::delete_item,
+            commands::inventory::get_hero_inventory,
+            commands::inventory::grant_item,
+            commands::inventory::remove_item,
+            commands::inventory::transfer_item,
+            commands::inventory::update_hero_item,
+            commands::inventory::find_item_holders,
             // Player commands
             commands::player::create_player,
             commands::player::get_player,
             commands::player::list_players,
             commands::player::update_player,
             commands::player::delete_player,
+            commands::scheduling::set_player_availability,
+            commands::scheduling::suggest_session_dates,
             // Session commands
             commands::session::create_session,
             commands::session::get_session,
             commands::session::list_sessions,
             commands::session::update_session,
             commands::session::delete_session,
+            commands::session::save_session_summary,
+            commands::session::log_session_to_timeline,
+            commands::scheduling::record_session_absence,
+            commands::scheduling::remove_session_absence,
+            commands::scheduling::list_session_absences,
+            commands::session_clock::start_session_clock,
+            commands::session_clock::pause_session_clock,
+            commands::session_clock::stop_session_clock,
+            commands::session_clock::get_session_clock,
+            commands::session_clock::get_play_time_report,
+            commands::session_prep::generate_session_prep,
+            commands::gm_screen::get_gm_screen,
+            commands::player_recap::generate_player_recap,
+            commands::session_transcript::import_session_transcript,
+            commands::session_transcript::list_session_transcripts,
+            commands::session_transcript::delete_session_transcript,
+            commands::session_transcript::get_transcript_chunks,
+            commands::session_transcript::detect_transcript_mentions,
+            // Progress clock commands
+            commands::progress_clock::create_progress_clock,
+            commands::progress_clock::get_progress_clock,
+            commands::progress_clock::list_progress_clocks,
+            commands::progress_clock::list_active_progress_clocks,
+            commands::progress_clock::update_progress_clock,
+            commands::progress_clock::tick_progress_clock,
+            commands::progress_clock::reset_progress_clock,
+            commands::progress_clock::delete_progress_clock,
+            // Encounter table commands
+            commands::encounter::create_encounter_table,
+            commands::encounter::get_encounter_table,
+            commands::encounter::list_encounter_tables,
+            commands::encounter::update_encounter_table,
+            commands::encounter::delete_encounter_table,
+            commands::encounter::create_encounter_entry,
+            commands::encounter::update_encounter_entry,
+            commands::encounter::delete_encounter_entry,
+            commands::encounter::roll_encounter,
+            commands::encounter::list_encounter_rolls,
+            // Price list commands
+            commands::price_list::create_price,
+            commands::price_list::get_price,
+            commands::price_list::list_prices,
+            commands::price_list::update_price,
+            commands::price_list::delete_price,
+            commands::price_list::create_price_modifier,
+            commands::price_list::list_price_modifiers,
+            commands::price_list::update_price_modifier,
+            commands::price_list::delete_price_modifier,
+            commands::price_list::get_local_price,
             // Timeline event commands
             commands::timeline::create_timeline_event,
             commands::timeline::get_timeline_event,
             commands::timeline::list_timeline_events,
             commands::timeline::update_timeline_event,
             commands::timeline::delete_timeline_event,
+            commands::timeline::export_timeline_image,
+            // Timeline branch commands
+            commands::timeline_branch::create_timeline_branch,
+            commands::timeline_branch::list_timeline_branches,
+            commands::timeline_branch::update_timeline_branch,
+            commands::timeline_branch::delete_timeline_branch,
+            commands::timeline_branch::compare_timeline_branches,
             // Secret commands
             commands::secret::create_secret,
             commands::secret::get_secret,
             commands::secret::list_secrets,
             commands::secret::update_secret,
             commands::secret::delete_secret,
+            commands::secret::add_secret_prerequisite,
+            commands::secret::remove_secret_prerequisite,
+            // Handout commands
+            commands::handout::create_handout,
+            commands::handout::get_handout,
+            commands::handout::list_handouts,
+            commands::handout::update_handout,
+            commands::handout::delete_handout,
+            commands::handout::send_handout,
+            commands::handout::mark_handout_read,
+            commands::handout::revoke_handout,
+            commands::handout::list_handout_deliveries,
+            commands::handout::list_player_handouts,
+            // Content stats commands
+            commands::content_stats::get_content_stats,
+            // Entity reference commands
+            commands::entity_refs::count_entities,
+            commands::entity_refs::entities_exist,
+            // Delete preview commands
+            commands::delete_preview::preview_delete,
+            // Activity feed commands
+            commands::activity::get_entity_activity,
+            // Detail level commands
+            commands::detail_level::list_entities_by_detail,
+            commands::detail_level::get_stub_report,
+            // Worldbuilding gap commands
+            commands::worldbuilding_gaps::get_worldbuilding_gaps,
+            // Player knowledge commands
+            commands::player_knowledge::player_knowledge_report,
+            // Content warning commands
+            commands::content_warning::create_content_warning,
+            commands::content_warning::list_content_warnings,
+            commands::content_warning::update_content_warning,
+            commands::content_warning::delete_content_warning,
+            commands::content_warning::check_content_warnings,
+            // Annotation commands
+            commands::annotation::create_annotation,
+            commands::annotation::list_annotations,
+            commands::annotation::update_annotation,
+            commands::annotation::resolve_annotation,
+            commands::annotation::unresolve_annotation,
+            commands::annotation::delete_annotation,
+            commands::annotation::list_open_annotations,
+            // Authorship commands
+            commands::authorship::get_local_profile,
+            commands::authorship::set_local_profile,
+            commands::authorship::list_authored_entities,
+            // Snippet commands
+            commands::snippet::create_snippet,
+            commands::snippet::get_snippet,
+            commands::snippet::list_snippets,
+            commands::snippet::update_snippet,
+            commands::snippet::delete_snippet,
+            commands::snippet::search_snippets,
+            // Recall card commands
+            commands::recall_card::create_recall_card,
+            commands::recall_card::list_recall_cards,
+            commands::recall_card::list_due_recall_cards,
+            commands::recall_card::review_recall_card,
+            commands::recall_card::generate_recall_cards,
+            commands::recall_card::delete_recall_card,
+            commands::quick_capture::quick_capture,
+            commands::quick_create::parse_quick_create,
+            commands::quick_note::set_active_campaign,
+            commands::quick_note::capture_quick_note,
+            // Inbox commands
+            commands::inbox::create_inbox_item,
+            commands::inbox::list_inbox_items,
+            commands::inbox::convert_inbox_item,
+            commands::inbox::dismiss_inbox_item,
+            // Prompt template commands
+            commands::prompt_template::create_prompt_template,
+            commands::prompt_template::get_prompt_template,
+            commands::prompt_template::list_prompt_templates,
+            commands::prompt_template::update_prompt_template,
+            commands::prompt_template::delete_prompt_template,
+            commands::prompt_template::render_prompt,
+            // SRD lookup commands
+            commands::srd::search_srd,
+            commands::srd::get_srd_entry,
+            commands::srd::import_srd_entry,
             // Relationship commands
             commands::relationship::create_relationship,
             commands::relationship::get_relationship,
@@ -92,6 +305,13 @@ pub fn run() {
             commands::relationship::get_entity_relationships,
             commands::relationship::update_relationship,
             commands::relationship::delete_relationship,
+            commands::relationship::export_relationships_csv,
+            commands::relationship_strength::adjust_relationship_strength,
+            commands::relationship_strength::get_relationship_strength_history,
+            commands::relationship_strength::apply_relationship_decay,
+            commands::relationship_suggestion::suggest_relationships,
+            commands::related_entities::get_related_entities,
+            commands::kinship::get_family_tree,
             // Tag commands
             commands::tag::create_tag,
             commands::tag::get_tag,
@@ -100,8 +320,64 @@ pub fn run() {
             commands::tag::add_entity_tag,
             commands::tag::remove_entity_tag,
             commands::tag::get_entity_tags,
+            commands::tag::get_tags_for_entities,
+            commands::tag_suggestion::suggest_tags,
             // Search commands
             commands::search::search_entities,
+            commands::search::search_in_location,
+            commands::command_palette::get_command_palette_items,
+            commands::search_history::record_search_query,
+            commands::search_history::get_search_suggestions,
+            commands::search_history::clear_search_history,
+            commands::find_replace::find_and_replace,
+            // Archive commands
+            commands::archive::export_campaign_archive,
+            commands::archive::import_campaign_archive,
+            commands::archive::preview_campaign_archive_merge,
+            commands::archive::merge_campaign_archive,
+            commands::archive::archive_campaign,
+            commands::archive::unarchive_campaign,
+            commands::markdown_import::import_markdown_folder,
+            commands::markdown::render_markdown,
+            commands::link_check::check_links,
+            commands::journal_export::export_roll20_journal,
+            commands::journal_export::export_homebrewery,
+            // Player share commands
+            commands::share::preview_player_snapshot,
+            commands::share::export_player_snapshot,
+            commands::share::start_player_share,
+            commands::share::stop_player_share,
+            // Sync commands
+            commands::sync::get_changes_since,
+            commands::sync::get_sync_settings,
+            commands::sync::update_sync_settings,
+            commands::sync::sync_campaign,
+            commands::sync::export_changes_since,
+            // Mirror commands
+            commands::mirror::get_mirror_settings,
+            commands::mirror::update_mirror_settings,
+            commands::mirror::mirror_campaign,
+            // Encryption commands
+            commands::encryption::get_encryption_status,
+            commands::encryption::unlock_database,
+            commands::encryption::encrypt_database,
+            commands::encryption::forget_database_key,
+            // Diagnostics commands
+            commands::logs::get_recent_logs,
+            commands::logs::set_log_level,
+            commands::maintenance::check_database_integrity,
+            commands::maintenance::optimize_database,
+            commands::maintenance::rebuild_search_index,
+            commands::references::repair_orphaned_references,
+            commands::maintenance::backup_database,
+            commands::maintenance::get_migration_status,
+            commands::maintenance::get_db_info,
+            commands::maintenance::rollback_last_migration,
+            commands::maintenance::get_database_settings,
+            commands::maintenance::update_database_settings,
+            commands::json_schema::validate_stat_block,
+            commands::stat_block::render_stat_block,
+            commands::json_schema::find_invalid_json_fields,
             // AI Conversation commands
             commands::ai_conversation::get_or_create_ai_conversation,
             commands::ai_conversation::load_ai_conversation,
@@ -110,6 +386,26 @@ pub fn run() {
             commands::ai_conversation::clear_ai_conversation,
             commands::ai_conversation::update_ai_message_proposal,
             commands::ai_conversation::update_ai_agent_messages,
+            commands::ai_conversation::export_ai_conversation,
+            commands::ai_conversation::archive_ai_conversation,
+            commands::ai_conversation::preview_proposal,
+            commands::ai_conversation::estimate_prompt_tokens,
+            // AI budget commands
+            commands::ai_budget::get_ai_budget_usage,
+            commands::ai_budget::check_ai_budget,
+            commands::ai_budget::estimate_request_tokens,
+            // Attachment commands
+            commands::attachment::generate_entity_image,
+            commands::attachment::list_entity_attachments,
+            commands::attachment::get_entity_portrait,
+            commands::attachment::get_attachment_data,
+            commands::attachment::get_player_entity_portrait,
+            commands::attachment::get_player_attachment_data,
+            commands::attachment::delete_attachment,
+            // Draft commands
+            commands::draft::save_draft,
+            commands::draft::get_draft,
+            commands::draft::discard_draft,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
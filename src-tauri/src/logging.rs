@@ -0,0 +1,127 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// Prefix for the daily-rotated log files (e.g. `loreweaver.log.2025-12-01`)
+pub const LOG_FILE_PREFIX: &str = "loreweaver.log";
+
+/// Days of log files kept; older ones are deleted as the log rotates
+pub const MAX_LOG_FILES: usize = 14;
+
+/// Filter used when neither RUST_LOG nor a runtime override is set
+pub const DEFAULT_LOG_FILTER: &str = "info,sqlx=warn,sea_orm=warn";
+
+/// Log levels accepted by `set_log_level`
+pub const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
+
+/// Managed state for the logging subsystem
+pub struct LogState {
+    pub log_dir: PathBuf,
+    filter: reload::Handle<EnvFilter, Registry>,
+    // Flushes buffered lines to disk when dropped, so it lives as long as the app
+    _guard: WorkerGuard,
+}
+
+impl LogState {
+    /// Replace the active filter, e.g. "debug" or "info,loreweaver_lib=trace"
+    pub fn set_filter(&self, directives: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+        self.filter.reload(filter).map_err(|e| e.to_string())
+    }
+}
+
+/// Install the global subscriber, writing to stdout and a daily-rotated file
+pub fn init_logging(log_dir: &Path) -> Result<LogState, Box<dyn std::error::Error>> {
+    fs::create_dir_all(log_dir)?;
+
+    let file_appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir)?;
+    let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
+
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .with(fmt::layer().with_ansi(false).with_writer(file_writer))
+        .try_init()?;
+
+    Ok(LogState {
+        log_dir: log_dir.to_path_buf(),
+        filter: handle,
+        _guard: guard,
+    })
+}
+
+/// Read the last `count` lines from the most recent log file in `log_dir`
+pub fn read_recent_lines(log_dir: &Path, count: usize) -> io::Result<Vec<String>> {
+    let Some(path) = latest_log_file(log_dir)? else {
+        return Ok(Vec::new());
+    };
+
+    let contents = fs::read_to_string(path)?;
+    let lines: Vec<&str> = contents.lines().collect();
+    let start = lines.len().saturating_sub(count);
+
+    Ok(lines[start..].iter().map(|l| l.to_string()).collect())
+}
+
+/// Rotated files carry a date suffix, so the lexically greatest name is newest
+fn latest_log_file(log_dir: &Path) -> io::Result<Option<PathBuf>> {
+    if !log_dir.exists() {
+        return Ok(None);
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(log_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(LOG_FILE_PREFIX))
+        })
+        .collect();
+    files.sort();
+
+    Ok(files.pop())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("loreweaver-logs-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_read_recent_lines_from_newest_file() {
+        let dir = temp_log_dir();
+        fs::write(dir.join("loreweaver.log.2025-01-01"), "old\n").unwrap();
+        fs::write(dir.join("loreweaver.log.2025-01-02"), "a\nb\nc\n").unwrap();
+        fs::write(dir.join("unrelated.txt"), "ignored\n").unwrap();
+
+        let lines = read_recent_lines(&dir, 2).unwrap();
+        assert_eq!(lines, vec!["b", "c"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_recent_lines_missing_dir() {
+        let dir = std::env::temp_dir().join("loreweaver-logs-does-not-exist");
+        assert!(read_recent_lines(&dir, 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,354 @@
+//! Markdown to sanitized HTML, so every window renders entity text the same
+//! way. `[[links]]` become deep links to the entities they name, raw HTML is
+//! cleaned down to a safe subset, and in player mode GM-only blocks and
+//! links to entities players can't see are left out.
+//!
+//! Links take the forms `[[type:id]]`, `[[type:id:label]]`, `[[Name]]` and
+//! `[[Name|label]]`. GM-only text goes in a fenced block tagged `gm`.
+
+use crate::commands::registry::table_for;
+use crate::export::rich_text::escape_html;
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, LinkType, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+
+/// URL scheme of entity deep links: `loreweaver://{entity_type}/{id}`
+pub const DEEP_LINK_SCHEME: &str = "loreweaver";
+
+/// Info string marking a fenced block as GM-only
+const GM_BLOCK: &str = "gm";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderMode {
+    Gm,
+    /// Safe to show players: GM-only blocks are dropped and only entities
+    /// in the player view are linked
+    Player,
+}
+
+/// An entity a `[[link]]` may point at
+#[derive(Debug, Clone)]
+pub struct LinkTarget {
+    pub entity_type: String,
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RenderedMarkdown {
+    pub html: String,
+    /// Links that matched no entity, as written. Always empty in player
+    /// mode, where a miss may just be an entity players can't see.
+    pub unresolved_links: Vec<String>,
+}
+
+pub fn deep_link(entity_type: &str, id: &str) -> String {
+    format!("{}://{}/{}", DEEP_LINK_SCHEME, entity_type, id)
+}
+
+/// Find the entity a link points at, returning it along with the label the
+/// link gives, if any
+pub(crate) fn resolve<'a, 'd>(
+    targets: &'a [LinkTarget],
+    dest: &'d str,
+) -> (Option<&'a LinkTarget>, Option<&'d str>) {
+    // Names may contain colons, so `type:id` needs a known entity type
+    let mut parts = dest.splitn(3, ':');
+    if let (Some(entity_type), Some(id)) = (parts.next(), parts.next()) {
+        if table_for(entity_type).is_some() {
+            let target = targets
+                .iter()
+                .find(|t| t.entity_type == entity_type && t.id == id);
+            return (target, parts.next());
+        }
+    }
+
+    let name = dest.trim();
+    let target = targets.iter().find(|t| t.name.eq_ignore_ascii_case(name));
+    (target, None)
+}
+
+struct Renderer<'a> {
+    mode: RenderMode,
+    targets: &'a [LinkTarget],
+    unresolved: Vec<String>,
+}
+
+impl Renderer<'_> {
+    fn render(&mut self, text: &str) -> String {
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_WIKILINKS;
+        let mut events = Parser::new_ext(text, options);
+        let mut out = Vec::new();
+
+        while let Some(event) = events.next() {
+            match event {
+                Event::Start(Tag::Link {
+                    link_type: LinkType::WikiLink { has_pothole },
+                    dest_url,
+                    ..
+                }) => {
+                    let inner: Vec<Event> = events
+                        .by_ref()
+                        .take_while(|e| !matches!(e, Event::End(TagEnd::Link)))
+                        .collect();
+                    self.push_link(&mut out, &dest_url, has_pothole, inner);
+                }
+                Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info)))
+                    if info.trim() == GM_BLOCK =>
+                {
+                    let body: String = events
+                        .by_ref()
+                        .take_while(|e| !matches!(e, Event::End(TagEnd::CodeBlock)))
+                        .filter_map(|e| match e {
+                            Event::Text(text) => Some(text.into_string()),
+                            _ => None,
+                        })
+                        .collect();
+                    if self.mode == RenderMode::Gm {
+                        let inner = self.render(&body);
+                        out.push(Event::Html(
+                            format!("<aside class=\"gm-only\">{}</aside>\n", inner).into(),
+                        ));
+                    }
+                }
+                event => out.push(event),
+            }
+        }
+
+        let mut html_out = String::new();
+        html::push_html(&mut html_out, out.into_iter());
+        html_out
+    }
+
+    fn push_link<'e>(
+        &mut self,
+        out: &mut Vec<Event<'e>>,
+        dest: &str,
+        has_pothole: bool,
+        inner: Vec<Event<'e>>,
+    ) {
+        let (target, label) = resolve(self.targets, dest);
+        // A piped link's text is already the label; otherwise use the one in
+        // the link, then the entity's name
+        let content = if has_pothole {
+            inner
+        } else {
+            let label = label
+                .or(target.map(|t| t.name.as_str()))
+                .unwrap_or(dest)
+                .to_string();
+            vec![Event::Text(CowStr::from(label))]
+        };
+
+        let (open, close) = match (target, self.mode) {
+            (Some(target), _) => (
+                format!(
+                    "<a href=\"{}\" data-entity-type=\"{}\" data-entity-id=\"{}\">",
+                    escape_html(&deep_link(&target.entity_type, &target.id)),
+                    escape_html(&target.entity_type),
+                    escape_html(&target.id)
+                ),
+                "</a>",
+            ),
+            (None, RenderMode::Gm) => {
+                self.unresolved.push(dest.to_string());
+                ("<span class=\"broken-link\">".to_string(), "</span>")
+            }
+            (None, RenderMode::Player) => {
+                out.extend(content);
+                return;
+            }
+        };
+        out.push(Event::InlineHtml(open.into()));
+        out.extend(content);
+        out.push(Event::InlineHtml(close.into()));
+    }
+}
+
+/// Strip everything but a safe subset of HTML, keeping entity deep links
+fn sanitize(html: &str) -> String {
+    ammonia::Builder::default()
+        .add_url_schemes([DEEP_LINK_SCHEME])
+        .add_tag_attributes("a", ["data-entity-type", "data-entity-id"])
+        .add_allowed_classes("span", ["broken-link"])
+        .add_allowed_classes("aside", ["gm-only"])
+        .clean(html)
+        .to_string()
+}
+
+/// Every `[[link]]` in `text`, GM-only blocks included, as written between
+/// the brackets (without a piped label)
+pub fn wiki_links(text: &str) -> Vec<String> {
+    let mut events = Parser::new_ext(text, Options::ENABLE_WIKILINKS);
+    let mut links = Vec::new();
+    while let Some(event) = events.next() {
+        match event {
+            Event::Start(Tag::Link {
+                link_type: LinkType::WikiLink { .. },
+                dest_url,
+                ..
+            }) => links.push(dest_url.into_string()),
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info)))
+                if info.trim() == GM_BLOCK =>
+            {
+                let body: String = events
+                    .by_ref()
+                    .take_while(|e| !matches!(e, Event::End(TagEnd::CodeBlock)))
+                    .filter_map(|e| match e {
+                        Event::Text(text) => Some(text.into_string()),
+                        _ => None,
+                    })
+                    .collect();
+                links.extend(wiki_links(&body));
+            }
+            _ => {}
+        }
+    }
+    links
+}
+
+/// `text` with its GM-only blocks cut out and everything else left as
+/// written, for player-facing text that isn't rendered here
+pub fn strip_gm_blocks(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut kept = 0;
+    for (event, range) in Parser::new_ext(text, Options::ENABLE_WIKILINKS).into_offset_iter() {
+        if let Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) = event {
+            if info.trim() == GM_BLOCK && range.start >= kept {
+                stripped.push_str(&text[kept..range.start]);
+                // The range stops short of the closing fence's newline
+                kept = range.end + usize::from(text[range.end..].starts_with('\n'));
+            }
+        }
+    }
+    stripped.push_str(&text[kept..]);
+    stripped
+}
+
+/// Render markdown for display. `targets` are the entities links may
+/// resolve to, already limited to what `mode` may show.
+pub fn render_markdown(text: &str, mode: RenderMode, targets: &[LinkTarget]) -> RenderedMarkdown {
+    let mut renderer = Renderer {
+        mode,
+        targets,
+        unresolved: Vec::new(),
+    };
+    let html = renderer.render(text);
+    RenderedMarkdown {
+        html: sanitize(&html),
+        unresolved_links: renderer.unresolved,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets() -> Vec<LinkTarget> {
+        vec![
+            LinkTarget {
+                entity_type: "character".to_string(),
+                id: "c1".to_string(),
+                name: "Butterbur".to_string(),
+            },
+            LinkTarget {
+                entity_type: "location".to_string(),
+                id: "l1".to_string(),
+                name: "Bree".to_string(),
+            },
+        ]
+    }
+
+    fn render(text: &str, mode: RenderMode) -> RenderedMarkdown {
+        render_markdown(text, mode, &targets())
+    }
+
+    #[test]
+    fn test_links_resolve_by_id_and_name() {
+        let rendered = render(
+            "Ask [[character:c1:Barliman]] in [[bree]], or [[Bree|the town]].",
+            RenderMode::Gm,
+        );
+        assert_eq!(
+            rendered.html,
+            "<p>Ask <a href=\"loreweaver://character/c1\" data-entity-type=\"character\" \
+             data-entity-id=\"c1\" rel=\"noopener noreferrer\">Barliman</a> in \
+             <a href=\"loreweaver://location/l1\" data-entity-type=\"location\" \
+             data-entity-id=\"l1\" rel=\"noopener noreferrer\">Bree</a>, or \
+             <a href=\"loreweaver://location/l1\" data-entity-type=\"location\" \
+             data-entity-id=\"l1\" rel=\"noopener noreferrer\">the town</a>.</p>\n"
+        );
+        assert!(rendered.unresolved_links.is_empty());
+    }
+
+    #[test]
+    fn test_unresolved_links() {
+        let gm = render(
+            "Seek [[Weathertop]] and [[character:c9:Strider]].",
+            RenderMode::Gm,
+        );
+        assert_eq!(
+            gm.html,
+            "<p>Seek <span class=\"broken-link\">Weathertop</span> and \
+             <span class=\"broken-link\">Strider</span>.</p>\n"
+        );
+        assert_eq!(
+            gm.unresolved_links,
+            vec!["Weathertop", "character:c9:Strider"]
+        );
+
+        let player = render("Seek [[Weathertop]].", RenderMode::Player);
+        assert_eq!(player.html, "<p>Seek Weathertop.</p>\n");
+        assert!(player.unresolved_links.is_empty());
+    }
+
+    #[test]
+    fn test_wiki_links() {
+        let text = "Ask [[Frodo|the ring-bearer]] about [[location:l1]].\n\n\
+                    ```gm\n[[character:c9:Strider]] follows.\n```\n\n`[[not a link]]`";
+        assert_eq!(
+            wiki_links(text),
+            vec!["Frodo", "location:l1", "character:c9:Strider"]
+        );
+    }
+
+    #[test]
+    fn test_html_is_sanitized() {
+        let rendered = render(
+            "<script>alert(1)</script>\n\n<b onclick=\"x()\">bold</b> [x](javascript:alert(1))",
+            RenderMode::Gm,
+        );
+        assert!(!rendered.html.contains("script"));
+        assert!(!rendered.html.contains("onclick"));
+        assert!(!rendered.html.contains("javascript"));
+        assert!(rendered.html.contains("<b>bold</b>"));
+    }
+
+    #[test]
+    fn test_gm_blocks_only_in_gm_mode() {
+        let text = "The inn is quiet.\n\n```gm\nThe cellar hides a **Nazgûl**.\n```\n";
+        assert_eq!(
+            render(text, RenderMode::Gm).html,
+            "<p>The inn is quiet.</p>\n<aside class=\"gm-only\"><p>The cellar hides a \
+             <strong>Nazgûl</strong>.</p>\n</aside>\n"
+        );
+        assert_eq!(
+            render(text, RenderMode::Player).html,
+            "<p>The inn is quiet.</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_strip_gm_blocks() {
+        let text = "The inn is *quiet*.\n\n```gm\nThe cellar hides a Nazgûl.\n```\n\n\
+                    ```\nnot gm\n```\nAsk [[Butterbur]].";
+        assert_eq!(
+            strip_gm_blocks(text),
+            "The inn is *quiet*.\n\n\n```\nnot gm\n```\nAsk [[Butterbur]]."
+        );
+        assert_eq!(strip_gm_blocks("No secrets here."), "No secrets here.");
+    }
+}
@@ -0,0 +1,57 @@
+//! Committing the mirror folder with the system `git`. The folder becomes a
+//! repository on first use; remotes and pushing are left to the user.
+
+use crate::error::AppError;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Identity used when the user has none configured, so a fresh machine can
+/// still commit
+const FALLBACK_NAME: &str = "Loreweaver";
+const FALLBACK_EMAIL: &str = "loreweaver@localhost";
+
+fn git(dir: &Path, args: &[&str]) -> Result<Output, AppError> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| AppError::Internal(format!("Failed to run git: {}", e)))
+}
+
+/// Run git and return its stdout, failing with its stderr
+fn git_ok(dir: &Path, args: &[&str]) -> Result<String, AppError> {
+    let output = git(dir, args)?;
+    if !output.status.success() {
+        return Err(AppError::Internal(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Stage everything in `dir` and commit it. Returns the new commit's hash,
+/// or None when there was nothing to commit.
+pub fn commit_all(dir: &Path, message: &str) -> Result<Option<String>, AppError> {
+    if !dir.join(".git").exists() {
+        git_ok(dir, &["init", "--quiet"])?;
+    }
+    git_ok(dir, &["add", "--all"])?;
+    if git_ok(dir, &["status", "--porcelain"])?.is_empty() {
+        return Ok(None);
+    }
+
+    let has_identity = git(dir, &["config", "user.email"])?.status.success();
+    let name = format!("user.name={}", FALLBACK_NAME);
+    let email = format!("user.email={}", FALLBACK_EMAIL);
+    let mut args = Vec::new();
+    if !has_identity {
+        args.extend(["-c", name.as_str(), "-c", email.as_str()]);
+    }
+    args.extend(["commit", "--quiet", "-m", message]);
+    git_ok(dir, &args)?;
+
+    git_ok(dir, &["rev-parse", "HEAD"]).map(Some)
+}
@@ -0,0 +1,129 @@
+//! Optional plaintext mirror of a campaign for version control.
+//!
+//! The campaign bundle is written as one pretty-printed JSON file per row,
+//! `{collection}/{id}.json`, with keys sorted so an unchanged row always
+//! produces the same bytes. Files are only rewritten when their content
+//! changes and files for deleted rows are removed, so `git diff` on the
+//! folder shows exactly what changed since the last mirror. Anything else in
+//! the folder, such as `.git` or a README, is left alone.
+
+pub mod git;
+
+use crate::export::CampaignBundle;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const CAMPAIGN_FILE: &str = "campaign.json";
+
+/// Files touched by one mirror run, as paths relative to the mirror folder
+#[derive(Debug, Default)]
+pub struct MirrorStats {
+    pub written: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+}
+
+fn to_file_contents(value: &Value) -> io::Result<String> {
+    Ok(serde_json::to_string_pretty(value)? + "\n")
+}
+
+/// Write `contents` unless the file already holds exactly that
+fn write_if_changed(
+    root: &Path,
+    relative: &str,
+    contents: &str,
+    stats: &mut MirrorStats,
+) -> io::Result<()> {
+    let path = root.join(relative);
+    if fs::read_to_string(&path).is_ok_and(|existing| existing == contents) {
+        stats.unchanged += 1;
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, contents)?;
+    stats.written.push(relative.to_string());
+    Ok(())
+}
+
+/// Remove `.json` files in a collection's folder that aren't in `keep`,
+/// then the folder itself if that left it empty
+fn remove_stale(
+    root: &Path,
+    collection: &str,
+    keep: &BTreeSet<String>,
+    stats: &mut MirrorStats,
+) -> io::Result<()> {
+    let dir = root.join(collection);
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let relative = format!("{}/{}", collection, name);
+        if name.ends_with(".json") && !keep.contains(&relative) {
+            fs::remove_file(&path)?;
+            stats.removed.push(relative);
+        }
+    }
+    if fs::read_dir(&dir)?.next().is_none() {
+        fs::remove_dir(&dir)?;
+    }
+    Ok(())
+}
+
+/// Bring the mirror folder at `root` in line with `bundle`
+pub fn write_mirror(root: &Path, bundle: &CampaignBundle) -> io::Result<MirrorStats> {
+    let mut stats = MirrorStats::default();
+    let Value::Object(collections) = serde_json::to_value(bundle)? else {
+        return Ok(stats);
+    };
+    fs::create_dir_all(root)?;
+
+    for (collection, value) in &collections {
+        match value {
+            Value::Array(rows) => {
+                let mut keep = BTreeSet::new();
+                // Link rows without an id of their own, like entity tags,
+                // share one file sorted by content
+                let mut unkeyed = Vec::new();
+                for row in rows {
+                    match row.get("id").and_then(Value::as_str) {
+                        Some(id) => {
+                            let relative = format!("{}/{}.json", collection, id);
+                            write_if_changed(root, &relative, &to_file_contents(row)?, &mut stats)?;
+                            keep.insert(relative);
+                        }
+                        None => unkeyed.push(row.clone()),
+                    }
+                }
+                if !unkeyed.is_empty() {
+                    unkeyed.sort_by_cached_key(Value::to_string);
+                    let relative = format!("{}/rows.json", collection);
+                    write_if_changed(
+                        root,
+                        &relative,
+                        &to_file_contents(&Value::Array(unkeyed))?,
+                        &mut stats,
+                    )?;
+                    keep.insert(relative);
+                }
+                remove_stale(root, collection, &keep, &mut stats)?;
+            }
+            Value::Object(_) if collection == "campaign" => {
+                write_if_changed(root, CAMPAIGN_FILE, &to_file_contents(value)?, &mut stats)?;
+            }
+            // Bundle metadata such as the format version isn't campaign data
+            _ => {}
+        }
+    }
+
+    Ok(stats)
+}
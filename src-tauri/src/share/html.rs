@@ -0,0 +1,139 @@
+//! Self-contained HTML page for a player snapshot, sized for phones.
+
+use super::snapshot::{PlayerSnapshot, PublicEntity};
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 0; padding: 1rem; background: #faf7f2; color: #2b2620; line-height: 1.5; }
+h1 { margin: 0 0 .25rem; font-size: 1.6rem; }
+h2 { margin: 1.5rem 0 .5rem; font-size: 1.2rem; border-bottom: 1px solid #d8cfc2; }
+details { background: #fff; border: 1px solid #e4dccf; border-radius: 8px; margin: .5rem 0; padding: .5rem .75rem; }
+summary { cursor: pointer; font-weight: 600; }
+.subtitle { color: #7a6f60; font-weight: 400; font-size: .9rem; margin-left: .35rem; }
+.field { margin: .25rem 0; }
+.field b { color: #5a5046; }
+.meta { color: #7a6f60; font-size: .8rem; }
+p { white-space: pre-wrap; }
+"#;
+
+/// Section order and headings, keyed by entity type
+const SECTIONS: &[(&str, &str)] = &[
+    ("hero", "Party"),
+    ("session", "Sessions"),
+    ("quest", "Quests"),
+    ("character", "People"),
+    ("location", "Places"),
+    ("organization", "Organizations"),
+    ("timeline_event", "Timeline"),
+    ("secret", "Revealed Secrets"),
+];
+
+pub fn render_snapshot_html(snapshot: &PlayerSnapshot) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html.push_str(&format!(
+        "<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n",
+        escape(&snapshot.campaign_name),
+        STYLE
+    ));
+    html.push_str(&format!("<h1>{}</h1>\n", escape(&snapshot.campaign_name)));
+    if let Some(description) = &snapshot.campaign_description {
+        html.push_str(&format!("<p>{}</p>\n", escape(description)));
+    }
+
+    for (entity_type, heading) in SECTIONS {
+        let entities: Vec<&PublicEntity> = snapshot
+            .entities
+            .iter()
+            .filter(|e| e.entity_type == *entity_type)
+            .collect();
+        if entities.is_empty() {
+            continue;
+        }
+
+        html.push_str(&format!("<h2>{}</h2>\n", heading));
+        for entity in entities {
+            render_entity(&mut html, entity);
+        }
+    }
+
+    html.push_str(&format!(
+        "<p class=\"meta\">Updated {}</p>\n</body>\n</html>\n",
+        escape(&snapshot.generated_at)
+    ));
+    html
+}
+
+fn render_entity(html: &mut String, entity: &PublicEntity) {
+    html.push_str(&format!(
+        "<details id=\"{}\">\n<summary>{}",
+        escape(&entity.id),
+        escape(&entity.name)
+    ));
+    if let Some(subtitle) = &entity.subtitle {
+        html.push_str(&format!(
+            "<span class=\"subtitle\">{}</span>",
+            escape(subtitle)
+        ));
+    }
+    html.push_str("</summary>\n");
+
+    if let Some(description) = &entity.description {
+        html.push_str(&format!("<p>{}</p>\n", escape(description)));
+    }
+    for field in &entity.fields {
+        html.push_str(&format!(
+            "<div class=\"field\"><b>{}:</b> {}</div>\n",
+            escape(&field.label),
+            escape(&field.value)
+        ));
+    }
+    html.push_str("</details>\n");
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::share::snapshot::PublicField;
+
+    #[test]
+    fn test_escapes_user_content() {
+        let snapshot = PlayerSnapshot {
+            campaign_name: "<script>alert(1)</script>".to_string(),
+            campaign_description: None,
+            generated_at: "now".to_string(),
+            entities: vec![PublicEntity {
+                entity_type: "character".to_string(),
+                id: "c1".to_string(),
+                name: "Tom & Jerry".to_string(),
+                subtitle: None,
+                description: None,
+                fields: vec![PublicField {
+                    label: "Status".to_string(),
+                    value: "\"Alive\"".to_string(),
+                }],
+            }],
+        };
+
+        let html = render_snapshot_html(&snapshot);
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("Tom &amp; Jerry"));
+        assert!(html.contains("&quot;Alive&quot;"));
+        assert!(html.contains("<h2>People</h2>"));
+    }
+}
@@ -0,0 +1,9 @@
+//! Read-only, spoiler-stripped campaign snapshots for players.
+
+pub mod html;
+pub mod server;
+pub mod snapshot;
+
+pub use html::render_snapshot_html;
+pub use server::{ShareServer, ShareState};
+pub use snapshot::*;
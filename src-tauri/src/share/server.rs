@@ -0,0 +1,100 @@
+//! Minimal LAN HTTP server for a player snapshot. It serves a fixed page and
+//! its JSON, nothing else, and stops when asked or when the app exits.
+
+use super::html::render_snapshot_html;
+use super::snapshot::PlayerSnapshot;
+use crate::error::AppError;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use tiny_http::{Header, Response, Server};
+
+/// A running share server
+pub struct ShareServer {
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+    pub url: String,
+    pub port: u16,
+}
+
+impl ShareServer {
+    /// Serve `snapshot` on all interfaces. Port 0 picks a free port.
+    pub fn start(snapshot: &PlayerSnapshot, port: u16) -> Result<Self, AppError> {
+        let html = render_snapshot_html(snapshot);
+        let json = serde_json::to_string(snapshot)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize snapshot: {}", e)))?;
+
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), port);
+        let server = Arc::new(
+            Server::http(addr)
+                .map_err(|e| AppError::Internal(format!("Failed to start share server: {}", e)))?,
+        );
+        let port = server
+            .server_addr()
+            .to_ip()
+            .map(|addr| addr.port())
+            .unwrap_or(port);
+
+        let worker = Arc::clone(&server);
+        let thread = std::thread::spawn(move || {
+            // Ends once `unblock` is called from `stop`
+            for request in worker.incoming_requests() {
+                let (body, content_type) = match request.url() {
+                    "/snapshot.json" => (json.clone(), "application/json"),
+                    _ => (html.clone(), "text/html; charset=utf-8"),
+                };
+                let header = Header::from_bytes("Content-Type", content_type)
+                    .expect("static header is valid");
+                if let Err(e) = request.respond(Response::from_string(body).with_header(header)) {
+                    tracing::debug!("Share server failed to respond: {}", e);
+                }
+            }
+        });
+
+        let url = format!("http://{}:{}/", lan_address(), port);
+        tracing::info!(%url, "Player share server started");
+
+        Ok(Self {
+            server,
+            thread: Some(thread),
+            url,
+            port,
+        })
+    }
+
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+            tracing::info!("Player share server stopped");
+        }
+    }
+}
+
+impl Drop for ShareServer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Managed state holding the (at most one) running share server
+#[derive(Default)]
+pub struct ShareState {
+    pub server: Mutex<Option<ShareServer>>,
+}
+
+/// Address other devices on the LAN can reach us at. Connecting a UDP socket
+/// sends nothing; it only asks the OS which interface would route outward.
+fn lan_address() -> IpAddr {
+    UdpSocket::bind("0.0.0.0:0")
+        .and_then(|socket| {
+            socket.connect("192.0.2.1:80")?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+}
@@ -0,0 +1,256 @@
+//! Builds the player-facing view of a campaign. Only fields players could
+//! reasonably know are copied; GM notes, secrets, motivations, plans and
+//! unrevealed content never leave this module, and GM-only blocks are cut
+//! from the text that does.
+
+use crate::export::CampaignBundle;
+use crate::markdown::strip_gm_blocks;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Entity types that can appear in a player snapshot
+pub const SHAREABLE_TYPES: &[&str] = &[
+    "character",
+    "location",
+    "organization",
+    "quest",
+    "hero",
+    "session",
+    "timeline_event",
+    "secret",
+];
+
+/// Where each shareable type lives and which of its rows `build_snapshot`
+/// publishes, as (entity_type, table, name, filter) SQL. Lets callers that
+/// only need to know what players can see skip loading the whole campaign;
+/// keep it in step with `build_snapshot`. Hidden dead characters are left to
+/// the caller, as they depend on campaign settings.
+pub(crate) const PLAYER_VISIBLE_ROWS: &[(&str, &str, &str, &str)] = &[
+    ("character", "characters", "name", "1"),
+    ("location", "locations", "name", "1"),
+    ("organization", "organizations", "name", "1"),
+    ("quest", "quests", "name", "status != 'planned'"),
+    ("hero", "heroes", "name", "is_active"),
+    (
+        "session",
+        "sessions",
+        "COALESCE(title, 'Session ' || session_number)",
+        "1",
+    ),
+    (
+        "timeline_event",
+        "timeline_events",
+        "title",
+        "is_public AND branch_id IS NULL",
+    ),
+    ("secret", "secrets", "title", "revealed"),
+];
+
+/// Which entities to publish. Empty lists mean "everything shareable".
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotSelection {
+    pub entity_types: Vec<String>,
+    pub entity_ids: Vec<String>,
+}
+
+impl SnapshotSelection {
+    fn includes(&self, entity_type: &str, id: &str) -> bool {
+        (self.entity_types.is_empty() || self.entity_types.iter().any(|t| t == entity_type))
+            && (self.entity_ids.is_empty() || self.entity_ids.iter().any(|i| i == id))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicField {
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicEntity {
+    pub entity_type: String,
+    pub id: String,
+    pub name: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub fields: Vec<PublicField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub campaign_name: String,
+    pub campaign_description: Option<String>,
+    pub generated_at: String,
+    pub entities: Vec<PublicEntity>,
+}
+
+/// Strip a campaign bundle down to what players may see
+pub fn build_snapshot(bundle: &CampaignBundle, selection: &SnapshotSelection) -> PlayerSnapshot {
+    let mut entities = Vec::new();
+    let mut push = |entity: PublicEntity| {
+        if selection.includes(&entity.entity_type, &entity.id) {
+            entities.push(entity);
+        }
+    };
+
+    for c in &bundle.characters {
+        push(PublicEntity {
+            entity_type: "character".to_string(),
+            id: c.id.clone(),
+            name: c.name.clone(),
+            subtitle: join_present(&[c.lineage.as_deref(), c.occupation.as_deref()]),
+            description: player_text(c.description.as_deref()),
+            fields: fields(&[("Status", Some(if c.is_alive { "Alive" } else { "Dead" }))]),
+        });
+    }
+
+    for l in &bundle.locations {
+        let parent = l
+            .parent_id
+            .as_ref()
+            .and_then(|pid| bundle.locations.iter().find(|p| &p.id == pid))
+            .map(|p| p.name.as_str());
+        push(PublicEntity {
+            entity_type: "location".to_string(),
+            id: l.id.clone(),
+            name: l.name.clone(),
+            subtitle: Some(humanize(&l.location_type)),
+            description: player_text(l.description.as_deref()),
+            fields: fields(&[("Part of", parent)]),
+        });
+    }
+
+    for o in bundle.organizations.iter() {
+        push(PublicEntity {
+            entity_type: "organization".to_string(),
+            id: o.id.clone(),
+            name: o.name.clone(),
+            subtitle: Some(humanize(&o.org_type)),
+            description: player_text(o.description.as_deref()),
+            fields: fields(&[("Reputation", o.reputation.as_deref())]),
+        });
+    }
+
+    // Planned quests haven't been offered to the party yet
+    for q in bundle.quests.iter().filter(|q| q.status != "planned") {
+        let completed = q.status == "completed";
+        push(PublicEntity {
+            entity_type: "quest".to_string(),
+            id: q.id.clone(),
+            name: q.name.clone(),
+            subtitle: Some(humanize(&q.status)),
+            description: player_text(q.description.as_deref()),
+            fields: fields(&[
+                ("Hook", q.hook.as_deref()),
+                ("Reward", q.reward.as_deref().filter(|_| completed)),
+                ("Resolution", q.resolution.as_deref().filter(|_| completed)),
+            ]),
+        });
+    }
+
+    for h in bundle.heroes.iter().filter(|h| h.is_active) {
+        push(PublicEntity {
+            entity_type: "hero".to_string(),
+            id: h.id.clone(),
+            name: h.name.clone(),
+            subtitle: join_present(&[h.lineage.as_deref(), h.classes.as_deref()]),
+            description: player_text(h.description.as_deref()),
+            fields: Vec::new(),
+        });
+    }
+
+    for s in &bundle.sessions {
+        let date = s.date.map(|d| d.to_string());
+        push(PublicEntity {
+            entity_type: "session".to_string(),
+            id: s.id.clone(),
+            name: s
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("Session {}", s.session_number)),
+            subtitle: Some(format!("Session {}", s.session_number)),
+            description: player_text(s.summary.as_deref()),
+            fields: fields(&[
+                ("Date", date.as_deref()),
+                ("Highlights", s.highlights.as_deref()),
+            ]),
+        });
+    }
+
+    let mut events: Vec<_> = bundle
+        .timeline_events
+        .iter()
+        // What-if branches stay with the GM
+        .filter(|e| e.is_public && e.branch_id.is_none())
+        .collect();
+    events.sort_by_key(|e| e.sort_order);
+    for e in events {
+        push(PublicEntity {
+            entity_type: "timeline_event".to_string(),
+            id: e.id.clone(),
+            name: e.title.clone(),
+            subtitle: Some(e.date_display.clone()),
+            description: player_text(e.description.as_deref()),
+            fields: Vec::new(),
+        });
+    }
+
+    for s in bundle.secrets.iter().filter(|s| s.revealed) {
+        let session = s.revealed_in_session.map(|n| format!("Session {}", n));
+        push(PublicEntity {
+            entity_type: "secret".to_string(),
+            id: s.id.clone(),
+            name: s.title.clone(),
+            subtitle: Some("Revealed".to_string()),
+            description: player_text(Some(&s.content)),
+            fields: fields(&[("Revealed in", session.as_deref())]),
+        });
+    }
+
+    PlayerSnapshot {
+        campaign_name: bundle.campaign.name.clone(),
+        campaign_description: player_text(bundle.campaign.description.as_deref()),
+        generated_at: Utc::now().to_rfc3339(),
+        entities,
+    }
+}
+
+fn fields(pairs: &[(&str, Option<&str>)]) -> Vec<PublicField> {
+    pairs
+        .iter()
+        .filter_map(|(label, value)| {
+            Some(PublicField {
+                label: label.to_string(),
+                value: player_text(*value)?,
+            })
+        })
+        .collect()
+}
+
+/// Text with its GM-only blocks removed, or none if nothing is left
+fn player_text(text: Option<&str>) -> Option<String> {
+    let text = strip_gm_blocks(text?);
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn join_present(parts: &[Option<&str>]) -> Option<String> {
+    let parts: Vec<&str> = parts
+        .iter()
+        .flatten()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+/// "trade_guild" -> "Trade guild"
+pub(crate) fn humanize(value: &str) -> String {
+    let spaced = value.replace('_', " ");
+    let mut chars = spaced.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => spaced,
+    }
+}
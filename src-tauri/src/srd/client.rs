@@ -0,0 +1,93 @@
+use super::SrdKind;
+use crate::error::AppError;
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Public Open5e API
+pub const DEFAULT_BASE_URL: &str = "https://api.open5e.com/v1";
+
+/// Only documents from the 5e System Reference Document are looked up
+const SRD_DOCUMENT: &str = "wotc-srd";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// One page of an Open5e list endpoint
+#[derive(Debug, Deserialize)]
+struct Page {
+    results: Vec<Value>,
+}
+
+/// HTTP client for the Open5e API
+pub struct Open5eClient {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl Open5eClient {
+    pub fn new(base_url: &str) -> Result<Self, AppError> {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::Internal(format!("Failed to build HTTP client: {}", e)))?;
+
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn kind_url(&self, kind: SrdKind) -> String {
+        format!("{}/{}/", self.base_url, kind.endpoint())
+    }
+
+    /// Documents of `kind` whose name or text matches `query`
+    pub async fn search(
+        &self,
+        kind: SrdKind,
+        query: &str,
+        limit: u64,
+    ) -> Result<Vec<Value>, AppError> {
+        let limit = limit.to_string();
+        let request = self.http.get(self.kind_url(kind)).query(&[
+            ("search", query),
+            ("document__slug", SRD_DOCUMENT),
+            ("limit", limit.as_str()),
+        ]);
+
+        let page: Page = send_json(request).await?;
+        Ok(page.results)
+    }
+
+    /// A single document by slug
+    pub async fn fetch(&self, kind: SrdKind, slug: &str) -> Result<Value, AppError> {
+        let url = format!("{}{}/", self.kind_url(kind), slug);
+        send_json(self.http.get(url)).await
+    }
+}
+
+async fn send_json<T: serde::de::DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+) -> Result<T, AppError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(format!("Open5e unreachable: {}", e)))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(AppError::NotFound("SRD entry not found".to_string()));
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(AppError::Internal(format!(
+            "Open5e returned {}: {}",
+            status, body
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::Internal(format!("Invalid Open5e response: {}", e)))
+}
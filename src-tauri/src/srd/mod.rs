@@ -0,0 +1,415 @@
+//! SRD content lookup through the Open5e API.
+//!
+//! Documents are kept verbatim in `srd_cache` the first time they are seen so
+//! lookups keep working offline; SRD text doesn't change, so cached rows never
+//! expire. The functions here turn an Open5e document into the fields of a
+//! campaign character or item.
+
+pub mod client;
+
+use crate::commands::json_schema::{Dnd5eStatBlock, StatBlockFeature};
+use crate::commands::validation::{ITEM_RARITIES, ITEM_TYPES};
+use crate::error::AppError;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Kinds of SRD content that can be searched and imported
+pub const SRD_KINDS: &[&str] = &["monster", "spell", "magic_item"];
+
+/// Open5e ability fields and the stat block keys they map to
+const ABILITY_FIELDS: &[(&str, &str)] = &[
+    ("strength", "str"),
+    ("dexterity", "dex"),
+    ("constitution", "con"),
+    ("intelligence", "int"),
+    ("wisdom", "wis"),
+    ("charisma", "cha"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SrdKind {
+    Monster,
+    Spell,
+    MagicItem,
+}
+
+impl SrdKind {
+    pub fn parse(kind: &str) -> Result<Self, AppError> {
+        match kind {
+            "monster" => Ok(Self::Monster),
+            "spell" => Ok(Self::Spell),
+            "magic_item" => Ok(Self::MagicItem),
+            _ => Err(AppError::Validation(format!(
+                "kind: must be one of: {}",
+                SRD_KINDS.join(", ")
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Monster => "monster",
+            Self::Spell => "spell",
+            Self::MagicItem => "magic_item",
+        }
+    }
+
+    /// Open5e list endpoint for this kind
+    pub fn endpoint(self) -> &'static str {
+        match self {
+            Self::Monster => "monsters",
+            Self::Spell => "spells",
+            Self::MagicItem => "magicitems",
+        }
+    }
+}
+
+/// Fields for a campaign item built from an SRD document
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedItem {
+    pub name: String,
+    pub item_type: String,
+    pub rarity: Option<String>,
+    pub requires_attunement: bool,
+    pub description: Option<String>,
+}
+
+/// Non-empty string field
+pub fn text(data: &Value, key: &str) -> Option<String> {
+    data.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn int(data: &Value, key: &str) -> Option<i32> {
+    data.get(key)
+        .and_then(Value::as_i64)
+        .and_then(|v| i32::try_from(v).ok())
+}
+
+fn split_list(value: Option<String>, separator: char) -> Vec<String> {
+    value
+        .map(|v| {
+            v.split(separator)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn features(data: &Value, key: &str) -> Vec<StatBlockFeature> {
+    data.get(key)
+        .and_then(Value::as_array)
+        .map(|list| {
+            list.iter()
+                .filter_map(|f| {
+                    Some(StatBlockFeature {
+                        name: text(f, "name")?,
+                        description: text(f, "desc").unwrap_or_default(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// "30 ft., climb 30 ft., hover" from Open5e's speed object
+fn speed(data: &Value) -> Option<String> {
+    let speeds = data.get("speed")?.as_object()?;
+    let mut parts = Vec::new();
+    if let Some(walk) = speeds.get("walk").and_then(Value::as_i64) {
+        parts.push(format!("{} ft.", walk));
+    }
+    for (mode, value) in speeds.iter().filter(|(mode, _)| *mode != "walk") {
+        match value {
+            Value::Number(distance) => parts.push(format!("{} {} ft.", mode, distance)),
+            Value::Bool(true) => parts.push(mode.clone()),
+            _ => {}
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// Short line shown with a search result, e.g. "Small humanoid, CR 1/4"
+pub fn summary(kind: SrdKind, data: &Value) -> Option<String> {
+    let parts: Vec<String> = match kind {
+        SrdKind::Monster => vec![
+            [text(data, "size"), text(data, "type")]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" "),
+            text(data, "challenge_rating")
+                .map(|cr| format!("CR {}", cr))
+                .unwrap_or_default(),
+        ],
+        SrdKind::Spell => vec![[text(data, "level"), text(data, "school")]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")],
+        SrdKind::MagicItem => vec![
+            text(data, "type").unwrap_or_default(),
+            text(data, "rarity").unwrap_or_default(),
+        ],
+    };
+    let summary = parts
+        .into_iter()
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+    (!summary.is_empty()).then_some(summary)
+}
+
+/// 5e stat block for an Open5e monster
+pub fn monster_stat_block(data: &Value) -> Dnd5eStatBlock {
+    let creature_type = text(data, "type").map(|kind| match text(data, "subtype") {
+        Some(subtype) => format!("{} ({})", kind, subtype),
+        None => kind,
+    });
+
+    let mut abilities = BTreeMap::new();
+    let mut saving_throws = BTreeMap::new();
+    for (field, key) in ABILITY_FIELDS {
+        if let Some(score) = int(data, field) {
+            abilities.insert(key.to_string(), score);
+        }
+        if let Some(save) = int(data, &format!("{}_save", field)) {
+            saving_throws.insert(key.to_string(), save);
+        }
+    }
+    let skills = data
+        .get("skills")
+        .and_then(Value::as_object)
+        .map(|skills| {
+            skills
+                .iter()
+                .filter_map(|(name, bonus)| {
+                    Some((name.clone(), i32::try_from(bonus.as_i64()?).ok()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Dnd5eStatBlock {
+        size: text(data, "size"),
+        creature_type,
+        alignment: text(data, "alignment"),
+        armor_class: int(data, "armor_class"),
+        armor_note: text(data, "armor_desc"),
+        hit_points: int(data, "hit_points"),
+        hit_dice: text(data, "hit_dice"),
+        speed: speed(data),
+        abilities,
+        saving_throws,
+        skills,
+        damage_resistances: split_list(text(data, "damage_resistances"), ';'),
+        damage_immunities: split_list(text(data, "damage_immunities"), ';'),
+        condition_immunities: split_list(text(data, "condition_immunities"), ','),
+        senses: split_list(text(data, "senses"), ','),
+        languages: split_list(text(data, "languages"), ','),
+        challenge_rating: text(data, "challenge_rating"),
+        traits: features(data, "special_abilities"),
+        actions: features(data, "actions"),
+        bonus_actions: features(data, "bonus_actions"),
+        reactions: features(data, "reactions"),
+        legendary_actions: features(data, "legendary_actions"),
+        extra: BTreeMap::new(),
+    }
+}
+
+/// Closest campaign rarity for an Open5e rarity such as "very rare" or
+/// "rare (+1), very rare (+2)"; "varies" has none
+fn rarity(raw: Option<String>) -> Option<String> {
+    let raw = raw?.to_lowercase().replace(' ', "_");
+    ITEM_RARITIES
+        .iter()
+        .find(|r| raw.starts_with(*r))
+        .map(|r| r.to_string())
+}
+
+/// Campaign item for an Open5e magic item
+pub fn magic_item(data: &Value) -> ImportedItem {
+    let kind = text(data, "type").unwrap_or_default().to_lowercase();
+    let item_type = ITEM_TYPES
+        .iter()
+        .find(|t| kind.starts_with(*t))
+        .copied()
+        .unwrap_or("other");
+    // "requires attunement" or "requires attunement by a cleric"
+    let requires_attunement = match data.get("requires_attunement") {
+        Some(Value::Bool(required)) => *required,
+        Some(Value::String(required)) => !required.trim().is_empty(),
+        _ => false,
+    };
+
+    ImportedItem {
+        name: text(data, "name").unwrap_or_default(),
+        item_type: item_type.to_string(),
+        rarity: rarity(text(data, "rarity")),
+        requires_attunement,
+        description: text(data, "desc"),
+    }
+}
+
+/// Spell scroll for an Open5e spell, priced by the DMG's scroll rarity table
+pub fn spell_scroll(data: &Value) -> ImportedItem {
+    let level = int(data, "level_int").unwrap_or(0);
+    let rarity = match level {
+        0 | 1 => "common",
+        2 | 3 => "uncommon",
+        4 | 5 => "rare",
+        6..=8 => "very_rare",
+        _ => "legendary",
+    };
+
+    let details = [
+        ("Casting Time", "casting_time"),
+        ("Range", "range"),
+        ("Components", "components"),
+        ("Duration", "duration"),
+    ]
+    .into_iter()
+    .filter_map(|(label, key)| Some(format!("{}: {}", label, text(data, key)?)));
+    let mut sections: Vec<String> = summary(SrdKind::Spell, data).into_iter().collect();
+    sections.push(details.collect::<Vec<_>>().join("\n"));
+    sections.extend(text(data, "desc"));
+    sections.extend(text(data, "higher_level").map(|h| format!("At Higher Levels. {}", h)));
+
+    ImportedItem {
+        name: format!("Spell Scroll ({})", text(data, "name").unwrap_or_default()),
+        item_type: "scroll".to_string(),
+        rarity: Some(rarity.to_string()),
+        requires_attunement: false,
+        description: Some(
+            sections
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_monster_stat_block() {
+        let goblin = json!({
+            "slug": "goblin",
+            "name": "Goblin",
+            "size": "Small",
+            "type": "humanoid",
+            "subtype": "goblinoid",
+            "alignment": "neutral evil",
+            "armor_class": 15,
+            "armor_desc": "leather armor, shield",
+            "hit_points": 7,
+            "hit_dice": "2d6",
+            "speed": {"walk": 30, "climb": 20, "hover": false},
+            "strength": 8,
+            "dexterity": 14,
+            "constitution": 10,
+            "intelligence": 10,
+            "wisdom": 8,
+            "charisma": 8,
+            "dexterity_save": null,
+            "skills": {"stealth": 6},
+            "damage_resistances": "",
+            "senses": "darkvision 60 ft., passive Perception 9",
+            "languages": "Common, Goblin",
+            "challenge_rating": "1/4",
+            "special_abilities": [{"name": "Nimble Escape", "desc": "Disengage or Hide as a bonus action."}],
+            "actions": [{"name": "Scimitar", "desc": "+4 to hit."}],
+            "reactions": ""
+        });
+
+        let block = monster_stat_block(&goblin);
+        assert_eq!(block.creature_type.as_deref(), Some("humanoid (goblinoid)"));
+        assert_eq!(block.speed.as_deref(), Some("30 ft., climb 20 ft."));
+        assert_eq!(block.ability("dex"), 14);
+        assert!(block.saving_throws.is_empty());
+        assert_eq!(block.skills.get("stealth"), Some(&6));
+        assert!(block.damage_resistances.is_empty());
+        assert_eq!(
+            block.senses,
+            vec!["darkvision 60 ft.", "passive Perception 9"]
+        );
+        assert_eq!(block.traits[0].name, "Nimble Escape");
+        assert!(block.reactions.is_empty());
+        assert!(block.validate().is_ok());
+        assert_eq!(
+            summary(SrdKind::Monster, &goblin).as_deref(),
+            Some("Small humanoid, CR 1/4")
+        );
+    }
+
+    #[test]
+    fn test_magic_item() {
+        let item = magic_item(&json!({
+            "name": "Flame Tongue",
+            "type": "Weapon (any sword)",
+            "rarity": "rare",
+            "requires_attunement": "requires attunement",
+            "desc": "Speak the command word to set the blade ablaze."
+        }));
+        assert_eq!(item.item_type, "weapon");
+        assert_eq!(item.rarity.as_deref(), Some("rare"));
+        assert!(item.requires_attunement);
+
+        let item = magic_item(&json!({
+            "name": "Figurine of Wondrous Power",
+            "type": "Wondrous item",
+            "rarity": "rarity by figurine",
+            "requires_attunement": ""
+        }));
+        assert_eq!(item.item_type, "wondrous");
+        assert_eq!(item.rarity, None);
+        assert!(!item.requires_attunement);
+
+        let item =
+            magic_item(&json!({"name": "Rod of Rulership", "type": "Rod", "rarity": "very rare"}));
+        assert_eq!(item.item_type, "other");
+        assert_eq!(item.rarity.as_deref(), Some("very_rare"));
+    }
+
+    #[test]
+    fn test_spell_scroll() {
+        let scroll = spell_scroll(&json!({
+            "name": "Fireball",
+            "level": "3rd-level",
+            "level_int": 3,
+            "school": "evocation",
+            "casting_time": "1 action",
+            "range": "150 feet",
+            "desc": "A bright streak flashes.",
+            "higher_level": "The damage increases by 1d6."
+        }));
+        assert_eq!(scroll.name, "Spell Scroll (Fireball)");
+        assert_eq!(scroll.item_type, "scroll");
+        assert_eq!(scroll.rarity.as_deref(), Some("uncommon"));
+        assert_eq!(
+            scroll.description.as_deref(),
+            Some(
+                "3rd-level evocation\n\nCasting Time: 1 action\nRange: 150 feet\n\n\
+                 A bright streak flashes.\n\nAt Higher Levels. The damage increases by 1d6."
+            )
+        );
+    }
+
+    #[test]
+    fn test_unknown_kind() {
+        assert!(SrdKind::parse("vehicle").is_err());
+        assert_eq!(
+            SrdKind::parse("magic_item").unwrap().endpoint(),
+            "magicitems"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use super::protocol::{ChangeRecord, PullResponse, PushRequest, PushResponse};
+use crate::error::AppError;
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// HTTP client for one sync server
+pub struct SyncClient {
+    http: reqwest::Client,
+    base_url: String,
+    auth_token: Option<String>,
+}
+
+impl SyncClient {
+    pub fn new(base_url: &str, auth_token: Option<String>) -> Result<Self, AppError> {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::Internal(format!("Failed to build HTTP client: {}", e)))?;
+
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth_token,
+        })
+    }
+
+    fn changes_url(&self, campaign_id: &str) -> String {
+        format!("{}/api/v1/campaigns/{}/changes", self.base_url, campaign_id)
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.auth_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    pub async fn push(
+        &self,
+        campaign_id: &str,
+        changes: Vec<ChangeRecord>,
+    ) -> Result<PushResponse, AppError> {
+        let request = self
+            .http
+            .post(self.changes_url(campaign_id))
+            .json(&PushRequest { changes });
+
+        send_json(self.authorize(request)).await
+    }
+
+    pub async fn pull(
+        &self,
+        campaign_id: &str,
+        since: Option<&str>,
+    ) -> Result<PullResponse, AppError> {
+        let mut request = self.http.get(self.changes_url(campaign_id));
+        if let Some(since) = since {
+            request = request.query(&[("since", since)]);
+        }
+
+        send_json(self.authorize(request)).await
+    }
+}
+
+async fn send_json<T: serde::de::DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+) -> Result<T, AppError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(format!("Sync server unreachable: {}", e)))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(AppError::Internal(format!(
+            "Sync server returned {}: {}",
+            status, body
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::Internal(format!("Invalid sync server response: {}", e)))
+}
@@ -0,0 +1,222 @@
+//! Optional campaign sync with a self-hosted server.
+//!
+//! Local changes are found with the same `updated_at` + tombstone delta used
+//! by `get_changes_since`, pushed as full rows, and remote changes are pulled
+//! and applied with last-writer-wins: a pulled change older than the local
+//! row is reported as a conflict and the local row is kept (it will reach the
+//! server on the next push).
+
+pub mod client;
+pub mod protocol;
+pub mod rows;
+
+use crate::commands::authorship::{pause_authorship, resume_authorship};
+use crate::commands::sync::get_changes_since_impl;
+use crate::error::AppError;
+use ::entity::tags;
+use chrono::{DateTime, Utc};
+use protocol::{ChangeOperation, ChangeRecord};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+
+/// Watermark used the first time a campaign is pushed
+pub const INITIAL_WATERMARK: &str = "1970-01-01T00:00:00+00:00";
+
+/// Order in which pulled entity types are applied so parents land first
+const APPLY_ORDER: &[&str] = &[
+    "campaign",
+    "player",
+    "location",
+    "character",
+    "organization",
+    "quest",
+    "hero",
+    "item",
+    "hero_item",
+    "organization_holding",
+    "whereabouts",
+    "session",
+    "session_absence",
+    "session_transcript",
+    "disposition",
+    "timeline_branch",
+    "timeline_event",
+    "secret",
+    "handout",
+    "handout_delivery",
+    "snippet",
+    "prompt_template",
+    "relationship",
+    "strength_change",
+    "recall_card",
+    "progress_clock",
+    "encounter_table",
+    "encounter_entry",
+    "encounter_roll",
+    "price",
+    "price_modifier",
+    "organization_goal",
+    "goal_advance",
+    "content_warning",
+    "annotation",
+    "tag",
+    rows::ENTITY_TAG,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConflict {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub operation: ChangeOperation,
+    pub local_modified_at: String,
+    pub remote_modified_at: String,
+}
+
+/// Result of applying pulled changes
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ApplyOutcome {
+    pub upserted: usize,
+    pub deleted: usize,
+    /// Changes for other campaigns or unknown types
+    pub skipped: usize,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// Local changes since `since`, as records ready to push, plus the watermark
+/// to store once they have been accepted
+pub async fn collect_local_changes(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    since: Option<&str>,
+) -> Result<(Vec<ChangeRecord>, String), AppError> {
+    let changes = get_changes_since_impl(
+        db,
+        campaign_id.to_string(),
+        since.unwrap_or(INITIAL_WATERMARK).to_string(),
+    )
+    .await?;
+
+    let mut records = Vec::new();
+    for change in changes.created.into_iter().chain(changes.updated) {
+        // Rows deleted between the delta query and now are covered by tombstones
+        let Some(data) = rows::load_row_json(db, &change.entity_type, &change.entity_id).await?
+        else {
+            continue;
+        };
+        records.push(ChangeRecord {
+            entity_type: change.entity_type,
+            entity_id: change.entity_id,
+            operation: ChangeOperation::Upsert,
+            modified_at: change.changed_at,
+            data: Some(data),
+        });
+    }
+    for change in changes.deleted {
+        records.push(ChangeRecord {
+            entity_type: change.entity_type,
+            entity_id: change.entity_id,
+            operation: ChangeOperation::Delete,
+            modified_at: change.changed_at,
+            data: None,
+        });
+    }
+
+    Ok((records, changes.watermark))
+}
+
+/// Apply pulled changes in one transaction using last-writer-wins
+pub async fn apply_remote_changes(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    mut records: Vec<ChangeRecord>,
+) -> Result<ApplyOutcome, AppError> {
+    records.sort_by_key(|r| {
+        APPLY_ORDER
+            .iter()
+            .position(|t| *t == r.entity_type)
+            .unwrap_or(APPLY_ORDER.len())
+    });
+
+    let mut outcome = ApplyOutcome::default();
+    let txn = db.begin().await?;
+    // Rows may arrive before the rows they reference; check FKs at commit
+    txn.execute_unprepared("PRAGMA defer_foreign_keys = ON")
+        .await?;
+    // Pulled rows keep the authorship they were written with
+    let profile = pause_authorship(&txn).await?;
+
+    for record in records {
+        let local = rows::load_row_json(&txn, &record.entity_type, &record.entity_id).await?;
+        let owned = match record.data.as_ref().or(local.as_ref()) {
+            Some(row) => belongs_to_campaign(&txn, &record.entity_type, row, campaign_id).await?,
+            None => false,
+        };
+        if !owned {
+            outcome.skipped += 1;
+            continue;
+        }
+        let Some(remote_modified) = parse_timestamp(&record.modified_at) else {
+            outcome.skipped += 1;
+            continue;
+        };
+
+        let local_modified = local.as_ref().and_then(rows::modified_at);
+        if let Some(local_modified) = local_modified.filter(|local| *local > remote_modified) {
+            outcome.conflicts.push(SyncConflict {
+                entity_type: record.entity_type,
+                entity_id: record.entity_id,
+                operation: record.operation,
+                local_modified_at: local_modified.to_rfc3339(),
+                remote_modified_at: record.modified_at,
+            });
+            continue;
+        }
+
+        match (record.operation, record.data) {
+            (ChangeOperation::Upsert, Some(data)) => {
+                rows::upsert_row_json(&txn, &record.entity_type, data).await?;
+                outcome.upserted += 1;
+            }
+            (ChangeOperation::Delete, _) => {
+                if rows::delete_row(&txn, &record.entity_type, &record.entity_id).await? {
+                    outcome.deleted += 1;
+                }
+            }
+            (ChangeOperation::Upsert, None) => outcome.skipped += 1,
+        }
+    }
+
+    resume_authorship(&txn, profile).await?;
+    txn.commit().await?;
+    Ok(outcome)
+}
+
+/// Entity tags reach their campaign through the tag, which is applied
+/// before them
+async fn belongs_to_campaign<C: ConnectionTrait>(
+    conn: &C,
+    entity_type: &str,
+    row: &serde_json::Value,
+    campaign_id: &str,
+) -> Result<bool, AppError> {
+    if entity_type == rows::ENTITY_TAG {
+        let Some(tag_id) = row.get("tag_id").and_then(|v| v.as_str()) else {
+            return Ok(false);
+        };
+        let tag = tags::Entity::find_by_id(tag_id).one(conn).await?;
+        return Ok(tag.is_some_and(|tag| tag.campaign_id == campaign_id));
+    }
+
+    let key = if entity_type == "campaign" {
+        "id"
+    } else {
+        "campaign_id"
+    };
+    Ok(row.get(key).and_then(|v| v.as_str()) == Some(campaign_id))
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|ts| ts.with_timezone(&Utc))
+}
@@ -0,0 +1,50 @@
+//! Wire format spoken with a self-hosted sync server.
+//!
+//! ```text
+//! GET  {server}/api/v1/campaigns/{campaign_id}/changes?since={watermark}
+//!      -> PullResponse
+//! POST {server}/api/v1/campaigns/{campaign_id}/changes
+//!      PushRequest -> PushResponse
+//! ```
+//!
+//! Watermarks returned by the server are opaque to the client; it only
+//! stores them and sends them back on the next pull.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOperation {
+    Upsert,
+    Delete,
+}
+
+/// A single entity change exchanged with the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeRecord {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub operation: ChangeOperation,
+    /// RFC 3339 time of the change on the device that made it
+    pub modified_at: String,
+    /// Full row for upserts, absent for deletes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushRequest {
+    pub changes: Vec<ChangeRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushResponse {
+    pub accepted: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PullResponse {
+    pub changes: Vec<ChangeRecord>,
+    pub watermark: String,
+}
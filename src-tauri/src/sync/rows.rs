@@ -0,0 +1,274 @@
+//! Type-erased access to entity rows as JSON, keyed by entity type string.
+
+use crate::error::AppError;
+use ::entity::entity_tags;
+use sea_orm::*;
+use serde_json::Value;
+
+/// Run `$body` with `$m` bound to the entity module for `$entity_type`
+macro_rules! with_entity_module {
+    ($entity_type:expr, $m:ident => $body:block) => {
+        match $entity_type {
+            "campaign" => {
+                use ::entity::campaigns as $m;
+                $body
+            }
+            "character" => {
+                use ::entity::characters as $m;
+                $body
+            }
+            "location" => {
+                use ::entity::locations as $m;
+                $body
+            }
+            "organization" => {
+                use ::entity::organizations as $m;
+                $body
+            }
+            "quest" => {
+                use ::entity::quests as $m;
+                $body
+            }
+            "hero" => {
+                use ::entity::heroes as $m;
+                $body
+            }
+            "item" => {
+                use ::entity::items as $m;
+                $body
+            }
+            "hero_item" => {
+                use ::entity::hero_items as $m;
+                $body
+            }
+            "organization_holding" => {
+                use ::entity::organization_holdings as $m;
+                $body
+            }
+            "whereabouts" => {
+                use ::entity::whereabouts as $m;
+                $body
+            }
+            "player" => {
+                use ::entity::players as $m;
+                $body
+            }
+            "session" => {
+                use ::entity::sessions as $m;
+                $body
+            }
+            "session_absence" => {
+                use ::entity::session_absences as $m;
+                $body
+            }
+            "session_transcript" => {
+                use ::entity::session_transcripts as $m;
+                $body
+            }
+            "disposition" => {
+                use ::entity::dispositions as $m;
+                $body
+            }
+            "timeline_branch" => {
+                use ::entity::timeline_branches as $m;
+                $body
+            }
+            "timeline_event" => {
+                use ::entity::timeline_events as $m;
+                $body
+            }
+            "secret" => {
+                use ::entity::secrets as $m;
+                $body
+            }
+            "handout" => {
+                use ::entity::handouts as $m;
+                $body
+            }
+            "handout_delivery" => {
+                use ::entity::handout_deliveries as $m;
+                $body
+            }
+            "snippet" => {
+                use ::entity::snippets as $m;
+                $body
+            }
+            "prompt_template" => {
+                use ::entity::prompt_templates as $m;
+                $body
+            }
+            "relationship" => {
+                use ::entity::relationships as $m;
+                $body
+            }
+            "strength_change" => {
+                use ::entity::strength_changes as $m;
+                $body
+            }
+            "recall_card" => {
+                use ::entity::recall_cards as $m;
+                $body
+            }
+            "progress_clock" => {
+                use ::entity::progress_clocks as $m;
+                $body
+            }
+            "encounter_table" => {
+                use ::entity::encounter_tables as $m;
+                $body
+            }
+            "encounter_entry" => {
+                use ::entity::encounter_entries as $m;
+                $body
+            }
+            "encounter_roll" => {
+                use ::entity::encounter_rolls as $m;
+                $body
+            }
+            "price" => {
+                use ::entity::price_list as $m;
+                $body
+            }
+            "price_modifier" => {
+                use ::entity::price_modifiers as $m;
+                $body
+            }
+            "organization_goal" => {
+                use ::entity::organization_goals as $m;
+                $body
+            }
+            "goal_advance" => {
+                use ::entity::goal_advances as $m;
+                $body
+            }
+            "content_warning" => {
+                use ::entity::content_warnings as $m;
+                $body
+            }
+            "annotation" => {
+                use ::entity::annotations as $m;
+                $body
+            }
+            "tag" => {
+                use ::entity::tags as $m;
+                $body
+            }
+            other => Err(AppError::Validation(format!(
+                "Unknown entity type: {}",
+                other
+            ))),
+        }
+    };
+}
+
+/// Entity type of the rows linking tags to entities
+pub const ENTITY_TAG: &str = "entity_tag";
+
+/// Entity tags have no id of their own; sync names them
+/// `tag_id:entity_type:entity_id`, as their tombstone trigger does
+fn entity_tag_key(id: &str) -> Option<(String, String, String)> {
+    let mut parts = id.splitn(3, ':');
+    Some((
+        parts.next()?.to_string(),
+        parts.next()?.to_string(),
+        parts.next()?.to_string(),
+    ))
+}
+
+/// Load a row as JSON, or `None` if it doesn't exist
+pub async fn load_row_json<C: ConnectionTrait>(
+    conn: &C,
+    entity_type: &str,
+    id: &str,
+) -> Result<Option<Value>, AppError> {
+    if entity_type == ENTITY_TAG {
+        let Some(key) = entity_tag_key(id) else {
+            return Ok(None);
+        };
+        let model = entity_tags::Entity::find_by_id(key).one(conn).await?;
+        return model.map(serde_json::to_value).transpose().map_err(|e| {
+            AppError::Internal(format!("Failed to serialize {}: {}", entity_type, e))
+        });
+    }
+
+    with_entity_module!(entity_type, m => {
+        let model = m::Entity::find_by_id(id.to_string()).one(conn).await?;
+        model
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| AppError::Internal(format!("Failed to serialize {}: {}", entity_type, e)))
+    })
+}
+
+/// Insert the row, or overwrite it if a row with the same id exists
+pub async fn upsert_row_json<C: ConnectionTrait>(
+    conn: &C,
+    entity_type: &str,
+    data: Value,
+) -> Result<(), AppError> {
+    if entity_type == ENTITY_TAG {
+        let model: entity_tags::Model = serde_json::from_value(data)
+            .map_err(|e| AppError::Validation(format!("Invalid {} data: {}", entity_type, e)))?;
+        let key = (
+            model.tag_id.clone(),
+            model.entity_type.clone(),
+            model.entity_id.clone(),
+        );
+        let exists = entity_tags::Entity::find_by_id(key)
+            .one(conn)
+            .await?
+            .is_some();
+        let active = model.into_active_model().reset_all();
+        if exists {
+            active.update(conn).await?;
+        } else {
+            entity_tags::Entity::insert(active)
+                .exec_without_returning(conn)
+                .await?;
+        }
+        return Ok(());
+    }
+
+    with_entity_module!(entity_type, m => {
+        let model: m::Model = serde_json::from_value(data).map_err(|e| {
+            AppError::Validation(format!("Invalid {} data: {}", entity_type, e))
+        })?;
+        let exists = m::Entity::find_by_id(model.id.clone()).one(conn).await?.is_some();
+        let active = model.into_active_model().reset_all();
+        if exists {
+            active.update(conn).await?;
+        } else {
+            m::Entity::insert(active).exec_without_returning(conn).await?;
+        }
+        Ok(())
+    })
+}
+
+pub async fn delete_row<C: ConnectionTrait>(
+    conn: &C,
+    entity_type: &str,
+    id: &str,
+) -> Result<bool, AppError> {
+    if entity_type == ENTITY_TAG {
+        let Some(key) = entity_tag_key(id) else {
+            return Ok(false);
+        };
+        let result = entity_tags::Entity::delete_by_id(key).exec(conn).await?;
+        return Ok(result.rows_affected > 0);
+    }
+
+    with_entity_module!(entity_type, m => {
+        let result = m::Entity::delete_by_id(id.to_string()).exec(conn).await?;
+        Ok(result.rows_affected > 0)
+    })
+}
+
+/// Last modification time of a row, read from `updated_at` (or `created_at`
+/// for tables without one)
+pub fn modified_at(row: &Value) -> Option<chrono::DateTime<chrono::Utc>> {
+    row.get("updated_at")
+        .or_else(|| row.get("created_at"))
+        .and_then(|v| v.as_str())
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|ts| ts.with_timezone(&chrono::Utc))
+}
@@ -0,0 +1,62 @@
+//! Plain-text helpers shared by commands that analyze what entities say:
+//! pulling the readable text out of editor JSON and splitting it into words.
+
+use serde_json::Value;
+
+/// The readable text of a stored field, without editor JSON structure.
+/// Text that isn't editor JSON comes back as it is.
+pub fn plain_text(content: &str) -> String {
+    fn collect(node: &Value, out: &mut String) {
+        if let Some(text) = node["text"].as_str() {
+            out.push_str(text);
+        }
+        if let Some(label) = node["attrs"]["label"].as_str() {
+            out.push_str(label);
+        }
+        if let Some(children) = node.get("content").and_then(Value::as_array) {
+            for child in children {
+                collect(child, out);
+            }
+            out.push(' ');
+        }
+    }
+
+    match serde_json::from_str::<Value>(content) {
+        Ok(doc) if doc.is_object() => {
+            let mut out = String::new();
+            collect(&doc, &mut out);
+            out
+        }
+        _ => content.to_string(),
+    }
+}
+
+/// Lowercase words of `text`, with punctuation treated as spaces
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|w| w.trim_matches('\'').to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text() {
+        let doc = r#"{"type":"doc","content":[{"type":"paragraph","content":[
+            {"type":"text","text":"Ask "},
+            {"type":"mention","attrs":{"label":"Mira"}}]}]}"#;
+        assert_eq!(plain_text(doc).trim(), "Ask Mira");
+        assert_eq!(plain_text("Just markdown"), "Just markdown");
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            words("The Thieves' Guild, 'twas said..."),
+            vec!["the", "thieves", "guild", "twas", "said"]
+        );
+    }
+}
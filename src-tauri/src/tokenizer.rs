@@ -0,0 +1,121 @@
+//! Offline token estimates for AI prompts.
+//!
+//! Splits text the way BPE tokenizers pre-tokenize it (words with their
+//! leading space, short digit groups, punctuation runs, line breaks) and
+//! estimates how many vocabulary pieces each chunk becomes. There is no
+//! vocabulary here, so counts are approximate; they lean slightly high so a
+//! warning fires before the provider rejects a request rather than after.
+
+/// Tokens added per message for role markers and separators
+pub const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Context window of the Claude models the assistant uses
+pub const CONTEXT_WINDOW_TOKENS: usize = 200_000;
+
+/// Longest letter run that usually maps to a single token
+const WHOLE_WORD_CHARS: usize = 6;
+/// Characters per extra token in words longer than `WHOLE_WORD_CHARS`
+const CHARS_PER_WORD_PIECE: usize = 4;
+/// Digits are split into groups of at most this many
+const DIGITS_PER_TOKEN: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Letter,
+    Digit,
+    Space,
+    Newline,
+    Punct,
+    /// Non-Latin scripts and symbols, which rarely merge across characters
+    Wide,
+}
+
+fn classify(c: char) -> CharClass {
+    if c == '\n' || c == '\r' {
+        CharClass::Newline
+    } else if c.is_whitespace() {
+        CharClass::Space
+    } else if c.is_ascii_digit() {
+        CharClass::Digit
+    } else if c.is_ascii_alphabetic() || (c.is_alphabetic() && (c as u32) < 0x0250) {
+        // ASCII and Latin-1/Extended-A letters, so accented words stay whole
+        CharClass::Letter
+    } else if c.is_ascii() {
+        CharClass::Punct
+    } else {
+        CharClass::Wide
+    }
+}
+
+/// Tokens for one run of same-class characters
+fn run_tokens(class: CharClass, len: usize) -> usize {
+    match class {
+        CharClass::Letter if len <= WHOLE_WORD_CHARS => 1,
+        CharClass::Letter => 1 + (len - WHOLE_WORD_CHARS).div_ceil(CHARS_PER_WORD_PIECE),
+        CharClass::Digit => len.div_ceil(DIGITS_PER_TOKEN),
+        // Common pairs like ", " or ".." merge; longer runs mostly don't
+        CharClass::Punct => len.div_ceil(2),
+        CharClass::Newline => 1,
+        CharClass::Space => 1,
+        CharClass::Wide => len,
+    }
+}
+
+/// Estimated token count for `text`
+pub fn count_tokens(text: &str) -> usize {
+    let mut total = 0;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let class = classify(c);
+        let mut len = 1;
+        while chars.peek().is_some_and(|&next| classify(next) == class) {
+            chars.next();
+            len += 1;
+        }
+
+        // A single space is folded into the word that follows it
+        if class == CharClass::Space
+            && len == 1
+            && chars
+                .peek()
+                .is_some_and(|&next| classify(next) != CharClass::Newline)
+        {
+            continue;
+        }
+        total += run_tokens(class, len);
+    }
+
+    total
+}
+
+/// Estimated tokens for a message, including its role overhead
+pub fn count_message_tokens(text: &str) -> usize {
+    count_tokens(text) + MESSAGE_OVERHEAD_TOKENS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_tokens() {
+        assert_eq!(count_tokens(""), 0);
+        // Each short word is one token with its leading space
+        assert_eq!(count_tokens("The party rests at the inn"), 6);
+        // Long words split into pieces, digits into groups of three
+        assert_eq!(count_tokens("Extraordinarily"), 4);
+        assert_eq!(count_tokens("1492"), 2);
+        assert_eq!(count_tokens("Hello, world!\n"), 5);
+        // Wide characters count one each
+        assert_eq!(count_tokens("竜の巣"), 3);
+    }
+
+    #[test]
+    fn test_count_message_tokens() {
+        assert_eq!(
+            count_message_tokens("Who rules Waterdeep?"),
+            count_tokens("Who rules Waterdeep?") + MESSAGE_OVERHEAD_TOKENS
+        );
+    }
+}
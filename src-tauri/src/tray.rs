@@ -0,0 +1,60 @@
+//! The system tray icon. Its "Quick note" item opens a small always-on-top
+//! window for jotting an idea down between sessions; the note lands in the
+//! inbox of the campaign open in the main window.
+
+use std::sync::Mutex;
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{App, AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// Label of the quick-note window, which its capability is scoped to
+pub const QUICK_NOTE_WINDOW: &str = "quick-note";
+
+const QUICK_NOTE_ITEM: &str = "quick-note";
+const QUIT_ITEM: &str = "quit";
+
+/// Managed state holding the campaign open in the main window, which quick
+/// notes are filed under
+#[derive(Default)]
+pub struct TrayState {
+    pub active_campaign: Mutex<Option<String>>,
+}
+
+/// Show the quick-note window, creating it the first time
+fn open_quick_note(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(QUICK_NOTE_WINDOW) {
+        window.show()?;
+        return window.set_focus();
+    }
+    WebviewWindowBuilder::new(app, QUICK_NOTE_WINDOW, WebviewUrl::App("quick-note".into()))
+        .title("Quick note")
+        .inner_size(420.0, 240.0)
+        .resizable(false)
+        .always_on_top(true)
+        .build()?;
+    Ok(())
+}
+
+pub fn init_tray(app: &App) -> tauri::Result<()> {
+    let quick_note = MenuItem::with_id(app, QUICK_NOTE_ITEM, "Quick note", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, QUIT_ITEM, "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&quick_note, &quit])?;
+
+    let mut tray = TrayIconBuilder::new()
+        .tooltip("Loreweaver")
+        .menu(&menu)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            QUICK_NOTE_ITEM => {
+                if let Err(e) = open_quick_note(app) {
+                    tracing::error!(error = %e, "Failed to open the quick-note window");
+                }
+            }
+            QUIT_ITEM => app.exit(0),
+            _ => {}
+        });
+    if let Some(icon) = app.default_window_icon() {
+        tray = tray.icon(icon.clone());
+    }
+    tray.build(app)?;
+    Ok(())
+}
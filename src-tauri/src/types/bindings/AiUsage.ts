@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AiUsage = { id: string, campaign_id: string, conversation_id: string | null, input_tokens: number, output_tokens: number, cache_read_tokens: number, cache_creation_tokens: number, cost_microusd: bigint, created_at: string, };
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Campaigns = { id: string, name: string, description: string | null, system: string | null, settings_json: string | null, created_at: string, updated_at: string, };
+export type Campaigns = { id: string, name: string, description: string | null, system: string | null, settings_json: string | null, created_at: string, updated_at: string, archived_at: string | null, archive_path: string | null, locked_at: string | null, created_by: string | null, modified_by: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type EncounterRolls = { id: string, campaign_id: string, session_id: string, location_id: string | null, table_id: string | null, entry_id: string | null, result: string, count: number | null, time_of_day: string | null, season: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InboxItems = { id: string, campaign_id: string, source: string, title: string, content: string | null, suggested_type: string | null, created_at: string, updated_at: string, };
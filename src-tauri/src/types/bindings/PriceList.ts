@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PriceList = { id: string, campaign_id: string, name: string, category: string, item_id: string | null, base_price: number, notes: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PromptTemplates = { id: string, campaign_id: string, name: string, template: string, context_type: string, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
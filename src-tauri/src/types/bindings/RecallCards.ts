@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type RecallCards = { id: string, campaign_id: string, entity_type: string | null, entity_id: string | null, source_field: string | null, front: string, back: string, ease: number, interval_days: number, repetitions: number, due_at: string, last_reviewed_at: string | null, last_grade: number | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
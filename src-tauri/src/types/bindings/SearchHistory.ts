@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SearchHistory = { id: string, campaign_id: string, query: string, use_count: number, last_used_at: string, created_at: string, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SessionTranscripts = { id: string, campaign_id: string, session_id: string, file_name: string, format: string, content: string, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
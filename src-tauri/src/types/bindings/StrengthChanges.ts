@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type StrengthChanges = { id: string, campaign_id: string, relationship_id: string, session_id: string | null, value: number, change: number, reason: string | null, is_decay: boolean, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -0,0 +1,130 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_session, setup_test_db};
+use entity::sessions;
+use loreweaver_lib::commands::activity::get_entity_activity_impl;
+use loreweaver_lib::commands::relationship::create_relationship_impl;
+use loreweaver_lib::commands::secret::{create_secret_impl, update_secret_impl};
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, Set};
+
+#[tokio::test]
+async fn test_entity_activity_feed() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let tobin = create_test_character(&db, &campaign.id, "Tobin")
+        .await
+        .expect("Failed to create character");
+
+    let date = chrono::NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
+    let session = create_test_session(&db, &campaign.id, 3, Some(date))
+        .await
+        .expect("Failed to create session");
+    let mut active: sessions::ActiveModel = session.into();
+    active.notes = Set(Some(format!(
+        "The party met [[character:{}:Mira]]",
+        mira.id
+    )));
+    active.update(&db).await.expect("Failed to update session");
+
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        tobin.id.clone(),
+        "character".to_string(),
+        mira.id.clone(),
+        "rival".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    let secret = create_secret_impl(
+        &db,
+        campaign.id.clone(),
+        "Double agent".to_string(),
+        "Mira reports to the duke".to_string(),
+        Some("character".to_string()),
+        Some(mira.id.clone()),
+    )
+    .await
+    .expect("Failed to create secret");
+    update_secret_impl(
+        &db,
+        secret.id,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(true),
+        Some(3),
+    )
+    .await
+    .expect("Failed to reveal secret");
+
+    let feed = get_entity_activity_impl(&db, "character".to_string(), mira.id.clone(), None)
+        .await
+        .expect("Failed to get activity");
+    let kinds: Vec<&str> = feed.iter().map(|e| e.kind.as_str()).collect();
+    for kind in [
+        "created",
+        "relationship_added",
+        "secret_added",
+        "secret_revealed",
+        "session_appearance",
+    ] {
+        assert!(kinds.contains(&kind), "missing {kind} in {kinds:?}");
+    }
+    assert!(feed
+        .windows(2)
+        .all(|pair| pair[0].occurred_at >= pair[1].occurred_at));
+
+    let link = feed
+        .iter()
+        .find(|e| e.kind == "relationship_added")
+        .unwrap();
+    assert_eq!(link.related_id.as_deref(), Some(tobin.id.as_str()));
+    assert_eq!(link.related_name.as_deref(), Some("Tobin"));
+
+    // Reveals and appearances are dated by the session they happened in
+    let reveal = feed.iter().find(|e| e.kind == "secret_revealed").unwrap();
+    assert_eq!(reveal.session_number, Some(3));
+    assert!(reveal.occurred_at.starts_with("2026-01-10"));
+    let appearance = feed
+        .iter()
+        .find(|e| e.kind == "session_appearance")
+        .unwrap();
+    assert_eq!(appearance.session_number, Some(3));
+
+    let limited = get_entity_activity_impl(&db, "character".to_string(), mira.id, Some(2))
+        .await
+        .expect("Failed to get activity");
+    assert_eq!(limited.len(), 2);
+}
+
+#[tokio::test]
+async fn test_entity_activity_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = get_entity_activity_impl(&db, "tag".to_string(), "x".to_string(), None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = get_entity_activity_impl(&db, "character".to_string(), "missing".to_string(), None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{create_test_campaign, setup_test_db};
+use loreweaver_lib::commands::ai_budget::{check_ai_budget_impl, get_ai_budget_usage_impl};
+use loreweaver_lib::commands::ai_conversation::{
+    clear_conversation_impl, get_or_create_conversation_impl, update_token_counts_impl,
+};
+use loreweaver_lib::commands::campaign_settings::{
+    update_campaign_settings_impl, CampaignSettings,
+};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_budget_usage_counts_runs() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let mut settings = CampaignSettings::default();
+    settings.ai.monthly_token_budget = Some(1000);
+    settings.ai.monthly_cost_budget = Some(2.0);
+    update_campaign_settings_impl(&db, campaign.id.clone(), settings)
+        .await
+        .expect("Failed to update settings");
+
+    let conversation =
+        get_or_create_conversation_impl(&db, campaign.id.clone(), "sidebar".to_string())
+            .await
+            .expect("Failed to create conversation");
+    update_token_counts_impl(&db, conversation.id.clone(), 300, 100, 50, 50, Some(0.5))
+        .await
+        .expect("Failed to record usage");
+    update_token_counts_impl(&db, conversation.id.clone(), 0, 0, 0, 0, None)
+        .await
+        .expect("Failed to record usage");
+
+    // Clearing the conversation resets its totals but not the month's usage
+    clear_conversation_impl(&db, conversation.id)
+        .await
+        .expect("Failed to clear conversation");
+
+    let usage = get_ai_budget_usage_impl(&db, campaign.id)
+        .await
+        .expect("Failed to load usage");
+    assert_eq!(usage.tokens_used, 500);
+    assert!((usage.cost_used - 0.5).abs() < 1e-9);
+    assert_eq!(usage.token_fraction, Some(0.5));
+    assert_eq!(usage.cost_fraction, Some(0.25));
+    assert_eq!(usage.enforcement, "confirm");
+    assert!(!usage.over_budget);
+}
+
+#[tokio::test]
+async fn test_budget_check_confirm_and_block() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    // No budget set: everything is allowed
+    let check = check_ai_budget_impl(&db, campaign.id.clone(), 1_000_000, None, false)
+        .await
+        .expect("Failed to check budget");
+    assert!(check.allowed);
+
+    let mut settings = CampaignSettings::default();
+    settings.ai.monthly_token_budget = Some(1000);
+    update_campaign_settings_impl(&db, campaign.id.clone(), settings.clone())
+        .await
+        .expect("Failed to update settings");
+
+    let check = check_ai_budget_impl(&db, campaign.id.clone(), 500, None, false)
+        .await
+        .expect("Failed to check budget");
+    assert!(check.allowed);
+    assert!(check.reason.is_none());
+
+    let check = check_ai_budget_impl(&db, campaign.id.clone(), 1500, None, false)
+        .await
+        .expect("Failed to check budget");
+    assert!(!check.allowed);
+    assert!(check.requires_confirmation);
+    assert!(check.reason.is_some());
+
+    let check = check_ai_budget_impl(&db, campaign.id.clone(), 1500, None, true)
+        .await
+        .expect("Failed to check budget");
+    assert!(check.allowed);
+
+    settings.ai.budget_enforcement = Some("block".to_string());
+    update_campaign_settings_impl(&db, campaign.id.clone(), settings)
+        .await
+        .expect("Failed to update settings");
+    let err = check_ai_budget_impl(&db, campaign.id, 1500, None, true)
+        .await
+        .expect_err("Blocked budgets should refuse");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::annotation::{
+    create_annotation_impl, delete_annotation_impl, list_annotations_impl,
+    list_open_annotations_impl, resolve_annotation_impl, unresolve_annotation_impl,
+    update_annotation_impl,
+};
+use loreweaver_lib::commands::character::delete_character_impl;
+use loreweaver_lib::commands::validation::{CreateAnnotationInput, UpdateAnnotationInput};
+use loreweaver_lib::ErrorCode;
+
+fn annotation_input(entity_type: &str, entity_id: &str, body: &str) -> CreateAnnotationInput {
+    CreateAnnotationInput {
+        entity_type: entity_type.to_string(),
+        entity_id: entity_id.to_string(),
+        field: None,
+        body: body.to_string(),
+        author: None,
+    }
+}
+
+#[tokio::test]
+async fn test_annotation_lifecycle() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    let mut input = annotation_input("character", &character.id, " Is she still alive? ");
+    input.field = Some("motivations".to_string());
+    input.author = Some("Sam".to_string());
+    let note = create_annotation_impl(&db, input)
+        .await
+        .expect("Failed to create annotation");
+    assert_eq!(note.campaign_id, campaign.id);
+    assert_eq!(note.body, "Is she still alive?");
+    assert_eq!(note.field.as_deref(), Some("motivations"));
+    assert!(note.resolved_at.is_none());
+    create_annotation_impl(&db, annotation_input("character", &character.id, "Love it"))
+        .await
+        .expect("Failed to create annotation");
+
+    let note = update_annotation_impl(
+        &db,
+        note.id.clone(),
+        UpdateAnnotationInput {
+            body: Some("Is she dead after session 4?".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update annotation");
+    assert_eq!(note.body, "Is she dead after session 4?");
+    assert_eq!(note.author.as_deref(), Some("Sam"));
+
+    let resolved = resolve_annotation_impl(&db, note.id.clone())
+        .await
+        .expect("Failed to resolve annotation");
+    assert!(resolved.resolved_at.is_some());
+    let again = resolve_annotation_impl(&db, note.id.clone())
+        .await
+        .expect("Failed to resolve annotation");
+    assert_eq!(again.resolved_at, resolved.resolved_at);
+
+    let open = list_annotations_impl(&db, "character".to_string(), character.id.clone(), false)
+        .await
+        .expect("Failed to list annotations");
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].body, "Love it");
+    let all = list_annotations_impl(&db, "character".to_string(), character.id.clone(), true)
+        .await
+        .expect("Failed to list annotations");
+    assert_eq!(all.len(), 2);
+
+    let reopened = unresolve_annotation_impl(&db, note.id.clone())
+        .await
+        .expect("Failed to unresolve annotation");
+    assert!(reopened.resolved_at.is_none());
+
+    assert!(delete_annotation_impl(&db, note.id.clone())
+        .await
+        .expect("Failed to delete annotation"));
+    assert!(!delete_annotation_impl(&db, note.id.clone())
+        .await
+        .expect("Failed to delete annotation"));
+}
+
+#[tokio::test]
+async fn test_open_annotations_and_entity_delete() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let location = create_test_location(&db, &campaign.id, "Greywater", None)
+        .await
+        .expect("Failed to create location");
+
+    let first = create_annotation_impl(&db, annotation_input("location", &location.id, "Map?"))
+        .await
+        .expect("Failed to create annotation");
+    create_annotation_impl(&db, annotation_input("character", &character.id, "Age?"))
+        .await
+        .expect("Failed to create annotation");
+    let resolved = create_annotation_impl(&db, annotation_input("location", &location.id, "Ok"))
+        .await
+        .expect("Failed to create annotation");
+    resolve_annotation_impl(&db, resolved.id.clone())
+        .await
+        .expect("Failed to resolve annotation");
+
+    let open = list_open_annotations_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list open annotations");
+    assert_eq!(open.len(), 2);
+    assert_eq!(open[0].annotation.id, first.id);
+    assert_eq!(open[0].entity_name.as_deref(), Some("Greywater"));
+    assert_eq!(open[1].entity_name.as_deref(), Some("Mira"));
+
+    // Annotations go with the entity they are on
+    delete_character_impl(&db, character.id.clone())
+        .await
+        .expect("Failed to delete character");
+    let open = list_open_annotations_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list open annotations");
+    assert_eq!(open.len(), 1);
+}
+
+#[tokio::test]
+async fn test_annotation_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    let err = create_annotation_impl(&db, annotation_input("dragon", &character.id, "Hm"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = create_annotation_impl(&db, annotation_input("character", &character.id, "  "))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let mut input = annotation_input("character", &character.id, "Hm");
+    input.field = Some("campaign_id".to_string());
+    let err = create_annotation_impl(&db, input).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = create_annotation_impl(&db, annotation_input("character", "nope", "Hm"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = resolve_annotation_impl(&db, "nope".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    let err = list_open_annotations_impl(&db, "nope".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_location, create_test_organization,
+    setup_test_db,
+};
+use loreweaver_lib::commands::appearance::set_entity_appearance_impl;
+use loreweaver_lib::commands::character::get_character_impl;
+use loreweaver_lib::commands::location::get_location_impl;
+use loreweaver_lib::commands::validation::AppearanceInput;
+use loreweaver_lib::ErrorCode;
+
+fn appearance(color: Option<&str>, icon: Option<&str>) -> AppearanceInput {
+    AppearanceInput {
+        color: color.map(str::to_string),
+        icon: icon.map(str::to_string),
+    }
+}
+
+#[tokio::test]
+async fn test_set_entity_appearance() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let keep = create_test_location(&db, &campaign.id, "The Keep", None)
+        .await
+        .expect("Failed to create location");
+    let guild = create_test_organization(&db, &campaign.id, "Thieves Guild")
+        .await
+        .expect("Failed to create organization");
+
+    let set = set_entity_appearance_impl(
+        &db,
+        "character".to_string(),
+        mira.id.clone(),
+        appearance(Some("#A0C4FF"), Some("crown")),
+    )
+    .await
+    .expect("Failed to set appearance");
+    assert_eq!(set.color.as_deref(), Some("#a0c4ff"));
+    assert_eq!(set.icon.as_deref(), Some("crown"));
+    let character = get_character_impl(&db, mira.id.clone())
+        .await
+        .expect("Failed to get character");
+    assert_eq!(character.color.as_deref(), Some("#a0c4ff"));
+    assert_eq!(character.icon.as_deref(), Some("crown"));
+
+    // Leaving a field out keeps it; an empty string clears it
+    let set = set_entity_appearance_impl(
+        &db,
+        "character".to_string(),
+        mira.id.clone(),
+        appearance(Some(""), None),
+    )
+    .await
+    .expect("Failed to clear color");
+    assert_eq!(set.color, None);
+    assert_eq!(set.icon.as_deref(), Some("crown"));
+
+    set_entity_appearance_impl(
+        &db,
+        "location".to_string(),
+        keep.id.clone(),
+        appearance(Some("#333"), Some("castle")),
+    )
+    .await
+    .expect("Failed to set appearance");
+    let location = get_location_impl(&db, keep.id.clone())
+        .await
+        .expect("Failed to get location");
+    assert_eq!(location.color.as_deref(), Some("#333"));
+    assert_eq!(location.icon.as_deref(), Some("castle"));
+
+    let set = set_entity_appearance_impl(
+        &db,
+        "organization".to_string(),
+        guild.id.clone(),
+        appearance(None, Some("drama")),
+    )
+    .await
+    .expect("Failed to set appearance");
+    assert_eq!(set.icon.as_deref(), Some("drama"));
+}
+
+#[tokio::test]
+async fn test_set_entity_appearance_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    let err = set_entity_appearance_impl(
+        &db,
+        "character".to_string(),
+        mira.id.clone(),
+        appearance(Some("blue"), None),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = set_entity_appearance_impl(
+        &db,
+        "character".to_string(),
+        mira.id.clone(),
+        appearance(None, Some("Not An Icon")),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = set_entity_appearance_impl(
+        &db,
+        "session".to_string(),
+        mira.id.clone(),
+        appearance(Some("#fff"), None),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = set_entity_appearance_impl(
+        &db,
+        "quest".to_string(),
+        "nope".to_string(),
+        appearance(Some("#fff"), None),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
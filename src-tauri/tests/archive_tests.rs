@@ -0,0 +1,871 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_location, create_test_tag,
+    setup_test_db,
+};
+use entity::{attachments, characters};
+use loreweaver_lib::commands::archive::{
+    archive_campaign_impl, export_campaign_archive_impl, import_campaign_archive_impl,
+    merge_campaign_archive_impl, preview_campaign_archive_merge_impl, unarchive_campaign_impl,
+};
+use loreweaver_lib::commands::attachment::{
+    delete_attachment_impl, get_attachment_data_impl, list_entity_attachments_impl,
+};
+use loreweaver_lib::commands::campaign::{
+    delete_campaign_impl, get_campaign_impl, lock_campaign_impl,
+};
+use loreweaver_lib::commands::character::{
+    delete_character_impl, get_character_impl, list_characters_impl, update_character_impl,
+};
+use loreweaver_lib::commands::location::{get_location_impl, list_locations_impl};
+use loreweaver_lib::commands::search::{search_entities_impl, SnippetOptions};
+use loreweaver_lib::commands::sync::get_changes_since_impl;
+use loreweaver_lib::commands::tag::{add_entity_tag_impl, get_entity_tags_impl};
+use loreweaver_lib::export::{
+    content_hash, read_archive, ImportProgress, MatchKind, MergeResolution, MergeStrategy,
+    RowOutcome, UnresolvedReason,
+};
+use loreweaver_lib::ErrorCode;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use std::path::PathBuf;
+
+fn temp_archive_path() -> PathBuf {
+    std::env::temp_dir().join(format!("test-{}.loreweaver", uuid::Uuid::new_v4()))
+}
+
+/// Insert an attachment directly, without a content hash, as rows stored
+/// before hashing existed were
+async fn attach(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    entity_type: &str,
+    entity_id: &str,
+    role: &str,
+    data: &[u8],
+) -> attachments::Model {
+    let id = uuid::Uuid::new_v4().to_string();
+    attachments::ActiveModel {
+        id: Set(id.clone()),
+        campaign_id: Set(campaign_id.to_string()),
+        entity_type: Set(entity_type.to_string()),
+        entity_id: Set(entity_id.to_string()),
+        role: Set(role.to_string()),
+        file_name: Set(format!("{}.png", role)),
+        mime_type: Set("image/png".to_string()),
+        size_bytes: Set(data.len() as i64),
+        data: Set(data.to_vec()),
+        content_hash: Set(None),
+        source: Set(None),
+        prompt: Set(None),
+        created_at: Set(chrono::Utc::now()),
+    }
+    .insert(db)
+    .await
+    .expect("Failed to insert attachment")
+}
+
+#[tokio::test]
+async fn test_archive_round_trip() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let region = create_test_location(&db, &campaign.id, "Eriador", None)
+        .await
+        .expect("Failed to create region");
+    let town = create_test_location(&db, &campaign.id, "Bree", Some(&region.id))
+        .await
+        .expect("Failed to create town");
+    let tag = create_test_tag(&db, &campaign.id, "Wizard")
+        .await
+        .expect("Failed to create tag");
+    add_entity_tag_impl(
+        &db,
+        tag.id.clone(),
+        "character".to_string(),
+        character.id.clone(),
+    )
+    .await
+    .expect("Failed to tag character");
+
+    let path = temp_archive_path();
+    let exported =
+        export_campaign_archive_impl(&db, campaign.id.clone(), path.to_string_lossy().to_string())
+            .await
+            .expect("Failed to export archive");
+    assert_eq!(exported.counts["character"], 1);
+    assert_eq!(exported.counts["location"], 2);
+
+    delete_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to delete campaign");
+
+    let imported = import_campaign_archive_impl(&db, path.to_string_lossy().to_string(), &|_| {})
+        .await
+        .expect("Failed to import archive");
+    assert_eq!(imported.campaign_id, campaign.id);
+
+    let characters = list_characters_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list characters");
+    assert_eq!(characters.len(), 1);
+    assert_eq!(characters[0].id, character.id);
+
+    let locations = list_locations_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list locations");
+    assert_eq!(locations.len(), 2);
+    let town = get_location_impl(&db, town.id.clone())
+        .await
+        .expect("Failed to get town");
+    assert_eq!(town.parent_id, Some(region.id.clone()));
+
+    let tags = get_entity_tags_impl(&db, "character".to_string(), character.id.clone())
+        .await
+        .expect("Failed to get tags");
+    assert_eq!(tags.len(), 1);
+
+    // Search index is rebuilt by the insert triggers
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Gandalf".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Failed to search");
+    assert_eq!(results.len(), 1);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_export_hashes_attachments_without_writing() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let portrait = attach(
+        &db,
+        &campaign.id,
+        "character",
+        &character.id,
+        "portrait",
+        b"grey",
+    )
+    .await;
+    lock_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to lock campaign");
+
+    let path = temp_archive_path();
+    export_campaign_archive_impl(&db, campaign.id.clone(), path.to_string_lossy().to_string())
+        .await
+        .expect("Failed to export archive");
+    let archive = read_archive(&path).expect("Failed to read");
+    assert_eq!(
+        archive.manifest.attachment_records[0].content_hash,
+        content_hash(b"grey")
+    );
+    let stored = attachments::Entity::find_by_id(&portrait.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.content_hash, None);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_migration_backfills_attachment_hashes() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    Migrator::down(&db, Some(1))
+        .await
+        .expect("Failed to roll back backfill");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let portrait = attach(
+        &db,
+        &campaign.id,
+        "character",
+        &character.id,
+        "portrait",
+        b"grey",
+    )
+    .await;
+    lock_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to lock campaign");
+
+    Migrator::up(&db, None)
+        .await
+        .expect("Failed to run backfill");
+    let stored = attachments::Entity::find_by_id(&portrait.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.content_hash, Some(content_hash(b"grey")));
+
+    // The campaign is still locked afterwards
+    let err = delete_attachment_impl(&db, portrait.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::ReadOnly);
+}
+
+#[tokio::test]
+async fn test_import_existing_campaign_is_conflict() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let path = temp_archive_path();
+    export_campaign_archive_impl(&db, campaign.id.clone(), path.to_string_lossy().to_string())
+        .await
+        .expect("Failed to export archive");
+
+    let err = import_campaign_archive_impl(&db, path.to_string_lossy().to_string(), &|_| {})
+        .await
+        .expect_err("Importing over an existing campaign should fail");
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_import_rejects_non_archive() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let path = temp_archive_path();
+    std::fs::write(&path, b"not a zip file").expect("Failed to write file");
+
+    let result =
+        import_campaign_archive_impl(&db, path.to_string_lossy().to_string(), &|_| {}).await;
+    assert!(result.is_err());
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_export_missing_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let path = temp_archive_path();
+    let err = export_campaign_archive_impl(
+        &db,
+        "missing".to_string(),
+        path.to_string_lossy().to_string(),
+    )
+    .await
+    .expect_err("Exporting a missing campaign should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+async fn rename_character(db: &sea_orm::DatabaseConnection, id: &str, name: &str) {
+    update_character_impl(
+        db,
+        id.to_string(),
+        Some(name.to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update character");
+}
+
+#[tokio::test]
+async fn test_merge_preview_reports_conflicts() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    create_test_character(&db, &campaign.id, "Frodo")
+        .await
+        .expect("Failed to create character");
+
+    let path = temp_archive_path();
+    export_campaign_archive_impl(&db, campaign.id.clone(), path.to_string_lossy().to_string())
+        .await
+        .expect("Failed to export archive");
+
+    rename_character(&db, &character.id, "Gandalf the White").await;
+
+    let preview = preview_campaign_archive_merge_impl(&db, path.to_string_lossy().to_string())
+        .await
+        .expect("Failed to preview merge");
+
+    assert!(preview.local_campaign_exists);
+    assert_eq!(preview.report.conflicts.len(), 1);
+    assert_eq!(preview.report.conflicts[0].local_id, character.id);
+    assert_eq!(preview.report.conflicts[0].match_kind, MatchKind::Id);
+    assert_eq!(preview.report.unchanged, 1);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_merge_keep_local_and_take_imported() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let gandalf = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let frodo = create_test_character(&db, &campaign.id, "Frodo")
+        .await
+        .expect("Failed to create character");
+
+    let path = temp_archive_path();
+    let path_str = path.to_string_lossy().to_string();
+    export_campaign_archive_impl(&db, campaign.id.clone(), path_str.clone())
+        .await
+        .expect("Failed to export archive");
+
+    rename_character(&db, &gandalf.id, "Gandalf the White").await;
+    rename_character(&db, &frodo.id, "Frodo Baggins").await;
+
+    // Keep local by default, but take the imported Frodo
+    let report = merge_campaign_archive_impl(
+        &db,
+        path_str,
+        MergeStrategy::KeepLocal,
+        vec![MergeResolution {
+            entity_type: "character".to_string(),
+            imported_id: frodo.id.clone(),
+            strategy: MergeStrategy::TakeImported,
+        }],
+    )
+    .await
+    .expect("Failed to merge archive");
+
+    assert_eq!(report.kept_local, 1);
+    assert_eq!(report.took_imported, 1);
+
+    let gandalf = get_character_impl(&db, gandalf.id.clone())
+        .await
+        .expect("Failed to get character");
+    assert_eq!(gandalf.name, "Gandalf the White");
+    let frodo = get_character_impl(&db, frodo.id.clone())
+        .await
+        .expect("Failed to get character");
+    assert_eq!(frodo.name, "Frodo");
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_merge_matches_by_name_and_duplicates() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let original = create_test_character(&db, &campaign.id, "Aragorn")
+        .await
+        .expect("Failed to create character");
+
+    let path = temp_archive_path();
+    let path_str = path.to_string_lossy().to_string();
+    export_campaign_archive_impl(&db, campaign.id.clone(), path_str.clone())
+        .await
+        .expect("Failed to export archive");
+
+    // Recreate the character locally under a new id
+    delete_character_impl(&db, original.id.clone())
+        .await
+        .expect("Failed to delete character");
+    let recreated = create_test_character(&db, &campaign.id, "aragorn")
+        .await
+        .expect("Failed to create character");
+
+    let preview = preview_campaign_archive_merge_impl(&db, path_str.clone())
+        .await
+        .expect("Failed to preview merge");
+    assert_eq!(preview.report.conflicts.len(), 1);
+    assert_eq!(preview.report.conflicts[0].match_kind, MatchKind::Name);
+    assert_eq!(preview.report.conflicts[0].local_id, recreated.id);
+
+    let report = merge_campaign_archive_impl(&db, path_str, MergeStrategy::Duplicate, vec![])
+        .await
+        .expect("Failed to merge archive");
+    assert_eq!(report.duplicated, 1);
+
+    let characters = list_characters_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list characters");
+    assert_eq!(characters.len(), 2);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_merge_inserts_missing_entities() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Samwise")
+        .await
+        .expect("Failed to create character");
+
+    let path = temp_archive_path();
+    let path_str = path.to_string_lossy().to_string();
+    export_campaign_archive_impl(&db, campaign.id.clone(), path_str.clone())
+        .await
+        .expect("Failed to export archive");
+
+    delete_character_impl(&db, character.id.clone())
+        .await
+        .expect("Failed to delete character");
+
+    let report = merge_campaign_archive_impl(&db, path_str, MergeStrategy::KeepLocal, vec![])
+        .await
+        .expect("Failed to merge archive");
+    assert_eq!(report.inserted.get("character"), Some(&1));
+
+    let restored = get_character_impl(&db, character.id.clone())
+        .await
+        .expect("Character should be restored");
+    assert_eq!(restored.name, "Samwise");
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_archive_and_unarchive_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let region = create_test_location(&db, &campaign.id, "Eriador", None)
+        .await
+        .expect("Failed to create region");
+    create_test_location(&db, &campaign.id, "Bree", Some(&region.id))
+        .await
+        .expect("Failed to create town");
+
+    let since = chrono::Utc::now().to_rfc3339();
+    let dir = std::env::temp_dir().join(format!("archives-{}", uuid::Uuid::new_v4()));
+    let archived = archive_campaign_impl(&db, campaign.id.clone(), &dir)
+        .await
+        .expect("Failed to archive campaign");
+    assert_eq!(archived.counts["character"], 1);
+    assert_eq!(archived.counts["location"], 2);
+    assert!(std::path::Path::new(&archived.path).exists());
+
+    // Only the stub campaign row is left behind
+    let stub = get_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Stub campaign should remain");
+    assert!(stub.archived_at.is_some());
+    assert_eq!(stub.archive_path.as_deref(), Some(archived.path.as_str()));
+    let characters = list_characters_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list characters");
+    assert!(characters.is_empty());
+
+    // Archiving isn't reported to sync as deleting the campaign's content
+    let changes = get_changes_since_impl(&db, campaign.id.clone(), since)
+        .await
+        .expect("Failed to get changes");
+    assert!(changes.deleted.is_empty());
+
+    let err = archive_campaign_impl(&db, campaign.id.clone(), &dir)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    let restored = unarchive_campaign_impl(&db, archived.path.clone())
+        .await
+        .expect("Failed to unarchive campaign");
+    assert_eq!(restored.campaign_id, campaign.id);
+
+    let campaign = get_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to get campaign");
+    assert!(campaign.archived_at.is_none());
+    assert!(campaign.archive_path.is_none());
+    let restored_character = get_character_impl(&db, character.id.clone())
+        .await
+        .expect("Character should be restored");
+    assert_eq!(restored_character.name, "Gandalf");
+    let locations = list_locations_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list locations");
+    assert_eq!(locations.len(), 2);
+
+    // A live campaign can't be unarchived over
+    let err = unarchive_campaign_impl(&db, archived.path.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_archive_stores_attachments_once_per_file() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let town = create_test_location(&db, &campaign.id, "Bree", None)
+        .await
+        .expect("Failed to create town");
+    let portrait = attach(
+        &db,
+        &campaign.id,
+        "character",
+        &character.id,
+        "portrait",
+        b"grey",
+    )
+    .await;
+    attach(
+        &db,
+        &campaign.id,
+        "character",
+        &character.id,
+        "image",
+        b"white",
+    )
+    .await;
+    // The same picture on another entity shares the archived file
+    attach(&db, &campaign.id, "location", &town.id, "map", b"grey").await;
+
+    let dir = std::env::temp_dir().join(format!("archives-{}", uuid::Uuid::new_v4()));
+    let archived = archive_campaign_impl(&db, campaign.id.clone(), &dir)
+        .await
+        .expect("Failed to archive campaign");
+    assert_eq!(archived.counts["attachment"], 3);
+
+    let archive = read_archive(std::path::Path::new(&archived.path)).expect("Failed to read");
+    assert_eq!(archive.manifest.attachment_records.len(), 3);
+    assert_eq!(archive.attachments.len(), 2);
+    assert!(archive
+        .manifest
+        .attachment_records
+        .iter()
+        .all(|r| r.content_hash.len() == 64));
+
+    // Archiving moves the files out of the database along with the rows
+    assert!(attachments::Entity::find_by_id(&portrait.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .is_none());
+
+    let restored = unarchive_campaign_impl(&db, archived.path.clone())
+        .await
+        .expect("Failed to unarchive campaign");
+    let report = restored
+        .attachments
+        .expect("Unarchiving reports attachments");
+    assert_eq!(report.imported, 3);
+    assert!(report.unresolved.is_empty());
+
+    let listed = list_entity_attachments_impl(&db, "character".to_string(), character.id.clone())
+        .await
+        .expect("Failed to list attachments");
+    assert_eq!(listed.len(), 2);
+    let data = get_attachment_data_impl(&db, portrait.id.clone())
+        .await
+        .expect("Failed to get data");
+    assert_eq!(data, b"grey");
+    let restored_portrait = attachments::Entity::find_by_id(&portrait.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored_portrait.content_hash, Some(content_hash(b"grey")));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_merge_repairs_and_deduplicates_attachments() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let damaged = attach(
+        &db,
+        &campaign.id,
+        "character",
+        &character.id,
+        "portrait",
+        b"grey",
+    )
+    .await;
+    let replaced = attach(
+        &db,
+        &campaign.id,
+        "character",
+        &character.id,
+        "image",
+        b"white",
+    )
+    .await;
+    let edited = attach(
+        &db,
+        &campaign.id,
+        "character",
+        &character.id,
+        "image",
+        b"staff",
+    )
+    .await;
+
+    let path = temp_archive_path();
+    export_campaign_archive_impl(&db, campaign.id.clone(), path.to_string_lossy().to_string())
+        .await
+        .expect("Failed to export archive");
+
+    // The portrait loses its data, the second image is re-added under a new
+    // id, and the third is swapped for a different picture
+    let mut active: attachments::ActiveModel = attachments::Entity::find_by_id(&damaged.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    active.data = Set(Vec::new());
+    active.update(&db).await.unwrap();
+    delete_attachment_impl(&db, replaced.id.clone())
+        .await
+        .expect("Failed to delete attachment");
+    attach(
+        &db,
+        &campaign.id,
+        "character",
+        &character.id,
+        "image",
+        b"white",
+    )
+    .await;
+    let mut active: attachments::ActiveModel = edited.clone().into();
+    active.data = Set(b"pipe".to_vec());
+    active.content_hash = Set(Some(content_hash(b"pipe")));
+    active.update(&db).await.unwrap();
+
+    let report = merge_campaign_archive_impl(
+        &db,
+        path.to_string_lossy().to_string(),
+        MergeStrategy::KeepLocal,
+        Vec::new(),
+    )
+    .await
+    .expect("Failed to merge archive");
+    assert_eq!(report.attachments.repaired, 1);
+    assert_eq!(report.attachments.deduplicated, 1);
+    assert_eq!(report.attachments.imported, 0);
+    assert_eq!(report.attachments.unresolved.len(), 1);
+    assert_eq!(report.attachments.unresolved[0].id, edited.id);
+    assert_eq!(
+        report.attachments.unresolved[0].reason,
+        UnresolvedReason::Conflict
+    );
+
+    let data = get_attachment_data_impl(&db, damaged.id.clone())
+        .await
+        .expect("Failed to get data");
+    assert_eq!(data, b"grey");
+    let data = get_attachment_data_impl(&db, edited.id.clone())
+        .await
+        .expect("Failed to get data");
+    assert_eq!(data, b"pipe");
+    let listed = list_entity_attachments_impl(&db, "character".to_string(), character.id.clone())
+        .await
+        .expect("Failed to list attachments");
+    assert_eq!(listed.len(), 3);
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn test_import_reports_unresolved_attachments() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let orphan = attach(
+        &db,
+        &campaign.id,
+        "character",
+        "deleted-id",
+        "portrait",
+        b"grey",
+    )
+    .await;
+    let empty = attach(
+        &db,
+        &campaign.id,
+        "character",
+        &character.id,
+        "portrait",
+        b"",
+    )
+    .await;
+
+    let path = temp_archive_path();
+    export_campaign_archive_impl(&db, campaign.id.clone(), path.to_string_lossy().to_string())
+        .await
+        .expect("Failed to export archive");
+    delete_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to delete campaign");
+
+    let imported = import_campaign_archive_impl(&db, path.to_string_lossy().to_string(), &|_| {})
+        .await
+        .expect("Failed to import archive");
+    let report = imported.attachments.expect("Importing reports attachments");
+    assert_eq!(report.imported, 0);
+    let reasons: Vec<_> = report
+        .unresolved
+        .iter()
+        .map(|u| (u.id.clone(), u.reason))
+        .collect();
+    assert_eq!(
+        reasons,
+        vec![
+            (orphan.id, UnresolvedReason::MissingEntity),
+            (empty.id, UnresolvedReason::MissingFile),
+        ]
+    );
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn test_import_skips_rows_that_already_exist() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let gandalf = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    create_test_character(&db, &campaign.id, "Frodo")
+        .await
+        .expect("Failed to create character");
+
+    let path = temp_archive_path();
+    export_campaign_archive_impl(&db, campaign.id.clone(), path.to_string_lossy().to_string())
+        .await
+        .expect("Failed to export archive");
+    delete_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to delete campaign");
+
+    // Another campaign now holds a character under Gandalf's id
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let squatter = create_test_character(&db, &other.id, "Saruman")
+        .await
+        .expect("Failed to create character");
+    characters::Entity::update_many()
+        .col_expr(characters::Column::Id, gandalf.id.clone().into())
+        .filter(characters::Column::Id.eq(&squatter.id))
+        .exec(&db)
+        .await
+        .expect("Failed to reassign id");
+
+    let events = std::sync::Mutex::new(Vec::new());
+    let on_progress = |p: &ImportProgress| events.lock().unwrap().push(p.clone());
+    let imported =
+        import_campaign_archive_impl(&db, path.to_string_lossy().to_string(), &on_progress)
+            .await
+            .expect("Import should succeed despite the skipped row");
+
+    let report = imported.rows.expect("Importing reports rows");
+    assert_eq!(report.total, 2);
+    assert_eq!(report.created["character"], 1);
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].id, gandalf.id);
+    assert_eq!(report.skipped[0].name.as_deref(), Some("Gandalf"));
+    assert!(report.failed.is_empty());
+
+    let events = events.into_inner().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events.last().map(|p| p.processed), Some(2));
+    assert!(events.iter().any(|p| p.outcome == RowOutcome::Skipped));
+
+    let characters = list_characters_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list characters");
+    assert_eq!(characters.len(), 1);
+    assert_eq!(characters[0].name, "Frodo");
+
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,269 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, setup_test_db};
+use entity::attachments;
+use loreweaver_lib::commands::attachment::{
+    delete_attachment_impl, generate_entity_image_impl, get_attachment_data_impl,
+    get_entity_portrait_impl, get_player_attachment_data_impl, get_player_entity_portrait_impl,
+    list_entity_attachments_impl,
+};
+use loreweaver_lib::commands::campaign_settings::{
+    update_campaign_settings_impl, CampaignSettings, PlayerViewSettings,
+};
+use loreweaver_lib::commands::secret::create_secret_impl;
+use loreweaver_lib::image_gen::{GeneratedImage, ImageProvider};
+use loreweaver_lib::{AppError, ErrorCode};
+use sea_orm::{ActiveModelTrait, ConnectionTrait, Set};
+
+/// Returns a fixed PNG header instead of calling a real service
+struct FakeProvider;
+
+impl ImageProvider for FakeProvider {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    async fn generate(&self, _prompt: &str) -> Result<GeneratedImage, AppError> {
+        Ok(GeneratedImage {
+            bytes: vec![0x89, b'P', b'N', b'G'],
+            mime_type: "image/png".to_string(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_generate_entity_portrait() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    let first = generate_entity_image_impl(
+        &db,
+        &FakeProvider,
+        "character".to_string(),
+        character.id.clone(),
+        "  A half-elf ranger with a scarred cheek  ".to_string(),
+        None,
+    )
+    .await
+    .expect("Failed to generate image");
+    assert_eq!(first.campaign_id, campaign.id);
+    assert_eq!(first.role, "portrait");
+    assert_eq!(first.mime_type, "image/png");
+    assert_eq!(first.size_bytes, 4);
+    assert_eq!(first.source.as_deref(), Some("fake"));
+    assert_eq!(
+        first.prompt.as_deref(),
+        Some("A half-elf ranger with a scarred cheek")
+    );
+    assert!(first.file_name.ends_with(".png"));
+
+    // A new portrait replaces the old one, which stays as a plain image
+    let second = generate_entity_image_impl(
+        &db,
+        &FakeProvider,
+        "character".to_string(),
+        character.id.clone(),
+        "Mira in winter furs".to_string(),
+        None,
+    )
+    .await
+    .expect("Failed to generate image");
+
+    let portrait = get_entity_portrait_impl(&db, "character".to_string(), character.id.clone())
+        .await
+        .expect("Failed to get portrait")
+        .expect("Character should have a portrait");
+    assert_eq!(portrait.id, second.id);
+
+    let attachments =
+        list_entity_attachments_impl(&db, "character".to_string(), character.id.clone())
+            .await
+            .expect("Failed to list attachments");
+    assert_eq!(attachments.len(), 2);
+    let old = attachments.iter().find(|a| a.id == first.id).unwrap();
+    assert_eq!(old.role, "image");
+
+    let data = get_attachment_data_impl(&db, second.id.clone())
+        .await
+        .expect("Failed to get data");
+    assert_eq!(data, vec![0x89, b'P', b'N', b'G']);
+
+    assert!(delete_attachment_impl(&db, second.id.clone())
+        .await
+        .expect("Failed to delete attachment"));
+    let portrait = get_entity_portrait_impl(&db, "character".to_string(), character.id)
+        .await
+        .expect("Failed to get portrait");
+    assert!(portrait.is_none());
+}
+
+#[tokio::test]
+async fn test_generate_entity_image_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    let cases = [
+        ("character", character.id.as_str(), "   ", None),
+        ("character", character.id.as_str(), "Mira", Some("banner")),
+        ("session", character.id.as_str(), "Mira", None),
+    ];
+    for (entity_type, id, prompt, role) in cases {
+        let err = generate_entity_image_impl(
+            &db,
+            &FakeProvider,
+            entity_type.to_string(),
+            id.to_string(),
+            prompt.to_string(),
+            role.map(str::to_string),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Validation);
+    }
+
+    let err = generate_entity_image_impl(
+        &db,
+        &FakeProvider,
+        "character".to_string(),
+        "missing".to_string(),
+        "Mira".to_string(),
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = get_attachment_data_impl(&db, "missing".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_player_attachments_hide_gm_only_entities() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let ghost = create_test_character(&db, &campaign.id, "Old Tom")
+        .await
+        .expect("Failed to create character");
+    let mut portraits = Vec::new();
+    for character in [&mira, &ghost] {
+        portraits.push(
+            generate_entity_image_impl(
+                &db,
+                &FakeProvider,
+                "character".to_string(),
+                character.id.clone(),
+                character.name.clone(),
+                None,
+            )
+            .await
+            .expect("Failed to generate image"),
+        );
+    }
+
+    // An archive can carry a picture of an unrevealed secret
+    let secret = create_secret_impl(
+        &db,
+        campaign.id.clone(),
+        "The mayor is a doppelganger".to_string(),
+        "Replaced last winter".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create secret");
+    let clue = attachments::ActiveModel {
+        id: Set("clue".to_string()),
+        campaign_id: Set(campaign.id.clone()),
+        entity_type: Set("secret".to_string()),
+        entity_id: Set(secret.id.clone()),
+        role: Set("image".to_string()),
+        file_name: Set("clue.png".to_string()),
+        mime_type: Set("image/png".to_string()),
+        size_bytes: Set(1),
+        data: Set(vec![1]),
+        content_hash: Set(None),
+        source: Set(None),
+        prompt: Set(None),
+        created_at: Set(chrono::Utc::now()),
+    }
+    .insert(&db)
+    .await
+    .expect("Failed to insert attachment");
+
+    db.execute_unprepared(&format!(
+        "UPDATE characters SET is_alive = 0 WHERE id = '{}'",
+        ghost.id
+    ))
+    .await
+    .expect("Failed to kill character");
+    update_campaign_settings_impl(
+        &db,
+        campaign.id.clone(),
+        CampaignSettings {
+            player_view: PlayerViewSettings {
+                hide_dead_characters: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update settings");
+
+    let portrait = get_player_entity_portrait_impl(&db, "character".to_string(), mira.id.clone())
+        .await
+        .expect("Failed to get portrait")
+        .expect("Character should have a portrait");
+    assert_eq!(portrait.id, portraits[0].id);
+    let data = get_player_attachment_data_impl(&db, portraits[0].id.clone())
+        .await
+        .expect("Failed to get data");
+    assert_eq!(data, vec![0x89, b'P', b'N', b'G']);
+
+    let err = get_player_attachment_data_impl(&db, clue.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    let err = get_player_attachment_data_impl(&db, portraits[1].id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    let err = get_player_entity_portrait_impl(&db, "character".to_string(), ghost.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    // Revealing the secret shares its pictures
+    db.execute_unprepared(&format!(
+        "UPDATE secrets SET revealed = 1 WHERE id = '{}'",
+        secret.id
+    ))
+    .await
+    .expect("Failed to reveal secret");
+    get_player_attachment_data_impl(&db, clue.id)
+        .await
+        .expect("Failed to get data");
+}
@@ -0,0 +1,282 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use entity::campaigns::Entity as Campaign;
+use entity::locations::{self, Entity as Location};
+use loreweaver_lib::commands::authorship::{
+    get_local_profile_impl, list_authored_entities_impl, set_local_profile_impl,
+};
+use loreweaver_lib::commands::campaign::{lock_campaign_impl, unlock_campaign_impl};
+use loreweaver_lib::commands::location::update_location_impl;
+use loreweaver_lib::commands::maintenance::rebuild_search_index_impl;
+use loreweaver_lib::commands::validation::UpdateLocationInput;
+use loreweaver_lib::sync::apply_remote_changes;
+use loreweaver_lib::sync::protocol::{ChangeOperation, ChangeRecord};
+use loreweaver_lib::ErrorCode;
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+async fn load_location(db: &DatabaseConnection, id: &str) -> locations::Model {
+    Location::find_by_id(id)
+        .one(db)
+        .await
+        .expect("Failed to load location")
+        .expect("Location not found")
+}
+
+async fn rename(db: &DatabaseConnection, id: &str, name: &str) {
+    update_location_impl(
+        db,
+        id.to_string(),
+        UpdateLocationInput {
+            name: Some(name.to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update location");
+}
+
+#[tokio::test]
+async fn test_local_profile_settings() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    assert_eq!(get_local_profile_impl(&db).await.unwrap(), None);
+    let profile = set_local_profile_impl(&db, Some("  Alex ".to_string()))
+        .await
+        .expect("Failed to set profile");
+    assert_eq!(profile.as_deref(), Some("Alex"));
+    assert_eq!(
+        get_local_profile_impl(&db).await.unwrap().as_deref(),
+        Some("Alex")
+    );
+
+    let err = set_local_profile_impl(&db, Some("x".repeat(101)))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    set_local_profile_impl(&db, Some(" ".to_string()))
+        .await
+        .expect("Failed to clear profile");
+    assert_eq!(get_local_profile_impl(&db).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_writes_are_stamped_with_the_local_profile() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    // Nothing is stamped without a profile
+    let old = create_test_location(&db, &campaign.id, "Old Keep", None)
+        .await
+        .expect("Failed to create location");
+    assert_eq!(load_location(&db, &old.id).await.created_by, None);
+
+    set_local_profile_impl(&db, Some("Alex".to_string()))
+        .await
+        .expect("Failed to set profile");
+    let port = create_test_location(&db, &campaign.id, "Greywater", None)
+        .await
+        .expect("Failed to create location");
+    create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let port = load_location(&db, &port.id).await;
+    assert_eq!(port.created_by.as_deref(), Some("Alex"));
+    assert_eq!(port.modified_by.as_deref(), Some("Alex"));
+
+    set_local_profile_impl(&db, Some("Sam".to_string()))
+        .await
+        .expect("Failed to set profile");
+    rename(&db, &port.id, "Greywater Docks").await;
+    rename(&db, &old.id, "Older Keep").await;
+    let port = load_location(&db, &port.id).await;
+    assert_eq!(port.created_by.as_deref(), Some("Alex"));
+    assert_eq!(port.modified_by.as_deref(), Some("Sam"));
+
+    // Defaults to the local profile
+    let sams = list_authored_entities_impl(&db, campaign.id.clone(), None, None, false)
+        .await
+        .expect("Failed to list entities");
+    assert!(sams.is_empty());
+    let sams = list_authored_entities_impl(&db, campaign.id.clone(), None, None, true)
+        .await
+        .expect("Failed to list entities");
+    assert_eq!(sams.len(), 2);
+    assert!(sams.iter().all(|e| e.modified_by.as_deref() == Some("Sam")));
+
+    let alexs = list_authored_entities_impl(
+        &db,
+        campaign.id.clone(),
+        Some("Alex".to_string()),
+        Some("location".to_string()),
+        false,
+    )
+    .await
+    .expect("Failed to list entities");
+    assert_eq!(alexs.len(), 1);
+    assert_eq!(alexs[0].name.as_deref(), Some("Greywater Docks"));
+    assert_eq!(alexs[0].modified_by.as_deref(), Some("Sam"));
+
+    let alexs = list_authored_entities_impl(
+        &db,
+        campaign.id.clone(),
+        Some("Alex".to_string()),
+        None,
+        false,
+    )
+    .await
+    .expect("Failed to list entities");
+    assert_eq!(alexs.len(), 2);
+}
+
+#[tokio::test]
+async fn test_synced_rows_keep_their_authorship() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    set_local_profile_impl(&db, Some("Sam".to_string()))
+        .await
+        .expect("Failed to set profile");
+    let location = create_test_location(&db, &campaign.id, "Greywater", None)
+        .await
+        .expect("Failed to create location");
+
+    // Another device edits a row its own profile wrote
+    let mut remote = load_location(&db, &location.id).await;
+    remote.name = "Greywater Docks".to_string();
+    remote.created_by = Some("Alex".to_string());
+    remote.modified_by = Some("Alex".to_string());
+    remote.updated_at = chrono::Utc::now() + chrono::Duration::seconds(5);
+    let record = ChangeRecord {
+        entity_type: "location".to_string(),
+        entity_id: location.id.clone(),
+        operation: ChangeOperation::Upsert,
+        modified_at: remote.updated_at.to_rfc3339(),
+        data: Some(serde_json::to_value(&remote).unwrap()),
+    };
+    let outcome = apply_remote_changes(&db, &campaign.id, vec![record.clone()])
+        .await
+        .expect("Failed to apply changes");
+    assert_eq!(outcome.upserted, 1);
+    // The second time the write leaves modified_by as it was
+    apply_remote_changes(&db, &campaign.id, vec![record])
+        .await
+        .expect("Failed to apply changes");
+
+    let location = load_location(&db, &location.id).await;
+    assert_eq!(location.name, "Greywater Docks");
+    assert_eq!(location.modified_by.as_deref(), Some("Alex"));
+    assert_eq!(
+        get_local_profile_impl(&db).await.unwrap().as_deref(),
+        Some("Sam")
+    );
+}
+
+#[tokio::test]
+async fn test_locking_with_a_profile_set() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    set_local_profile_impl(&db, Some("Alex".to_string()))
+        .await
+        .expect("Failed to set profile");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_location(&db, &campaign.id, "Greywater", None)
+        .await
+        .expect("Failed to create location");
+
+    set_local_profile_impl(&db, Some("Sam".to_string()))
+        .await
+        .expect("Failed to set profile");
+    let locked = lock_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to lock campaign");
+    assert!(locked.locked_at.is_some());
+    let unlocked = unlock_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to unlock campaign");
+    assert!(unlocked.locked_at.is_none());
+
+    // Locking isn't an edit to the campaign
+    let campaign = Campaign::find_by_id(&campaign.id)
+        .one(&db)
+        .await
+        .expect("Failed to load campaign")
+        .expect("Campaign not found");
+    assert_eq!(campaign.modified_by.as_deref(), Some("Alex"));
+}
+
+#[tokio::test]
+async fn test_search_index_rebuild_keeps_authorship() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    set_local_profile_impl(&db, Some("Alex".to_string()))
+        .await
+        .expect("Failed to set profile");
+    let location = create_test_location(&db, &campaign.id, "Greywater", None)
+        .await
+        .expect("Failed to create location");
+
+    set_local_profile_impl(&db, Some("Sam".to_string()))
+        .await
+        .expect("Failed to set profile");
+    rebuild_search_index_impl(&db)
+        .await
+        .expect("Failed to rebuild index");
+    let location = load_location(&db, &location.id).await;
+    assert_eq!(location.modified_by.as_deref(), Some("Alex"));
+}
+
+#[tokio::test]
+async fn test_list_authored_entities_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    // No author given and no profile to fall back on
+    let err = list_authored_entities_impl(&db, campaign.id.clone(), None, None, false)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = list_authored_entities_impl(
+        &db,
+        campaign.id.clone(),
+        Some("Alex".to_string()),
+        Some("dragon".to_string()),
+        false,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = list_authored_entities_impl(
+        &db,
+        "nope".to_string(),
+        Some("Alex".to_string()),
+        None,
+        false,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
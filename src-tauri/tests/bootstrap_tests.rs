@@ -0,0 +1,134 @@
+mod common;
+
+use common::setup_test_db;
+use loreweaver_lib::commands::bootstrap::{bootstrap_campaign_impl, BootstrapConfig, FactionSeed};
+use loreweaver_lib::commands::campaign::list_campaigns_impl;
+use loreweaver_lib::commands::campaign_settings::{get_campaign_settings_impl, CampaignSettings};
+use loreweaver_lib::commands::location::list_locations_impl;
+use loreweaver_lib::commands::tag::list_tags_impl;
+use loreweaver_lib::ErrorCode;
+
+fn config(name: &str) -> BootstrapConfig {
+    BootstrapConfig {
+        name: name.to_string(),
+        description: None,
+        system: Some("D&D 5e".to_string()),
+        location_template: None,
+        world_name: None,
+        factions: Vec::new(),
+        tags: None,
+        session_zero: None,
+        session_zero_date: None,
+        settings: None,
+    }
+}
+
+#[tokio::test]
+async fn test_bootstrap_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let mut settings = CampaignSettings::default();
+    settings.calendar.era = Some("DR".to_string());
+    let report = bootstrap_campaign_impl(
+        &db,
+        BootstrapConfig {
+            world_name: Some("Faerûn".to_string()),
+            factions: vec![FactionSeed {
+                name: "The Harpers".to_string(),
+                org_type: Some("secret_society".to_string()),
+                description: Some("Spies for the common good.".to_string()),
+            }],
+            settings: Some(settings),
+            ..config("Lost Mine")
+        },
+    )
+    .await
+    .expect("Failed to bootstrap campaign");
+
+    let campaign_id = report.campaign.id.clone();
+    assert_eq!(report.campaign.name, "Lost Mine");
+    assert_eq!(report.counts["location"], 9);
+    assert_eq!(report.counts["organization"], 1);
+    assert_eq!(report.counts["session"], 1);
+    assert_eq!(report.counts["tag"], 6);
+    assert!(report.session_zero_id.is_some());
+
+    let locations = list_locations_impl(&db, campaign_id.clone())
+        .await
+        .expect("Failed to list locations");
+    let roots: Vec<_> = locations.iter().filter(|l| l.parent_id.is_none()).collect();
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].name, "Faerûn");
+
+    let tags = list_tags_impl(&db, campaign_id.clone())
+        .await
+        .expect("Failed to list tags");
+    assert!(tags.iter().any(|t| t.name == "Plot Hook"));
+
+    let settings = get_campaign_settings_impl(&db, campaign_id)
+        .await
+        .expect("Failed to get settings");
+    assert_eq!(settings.calendar.era.as_deref(), Some("DR"));
+}
+
+#[tokio::test]
+async fn test_bootstrap_campaign_options() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let report = bootstrap_campaign_impl(
+        &db,
+        BootstrapConfig {
+            location_template: Some("none".to_string()),
+            tags: Some(vec![
+                "Ally".to_string(),
+                "ally".to_string(),
+                "  ".to_string(),
+            ]),
+            session_zero: Some(false),
+            ..config("Blank Slate")
+        },
+    )
+    .await
+    .expect("Failed to bootstrap campaign");
+    assert_eq!(report.counts["location"], 0);
+    assert_eq!(report.counts["tag"], 1);
+    assert_eq!(report.counts["session"], 0);
+    assert!(report.session_zero_id.is_none());
+}
+
+#[tokio::test]
+async fn test_bootstrap_campaign_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let bad_configs = [
+        config("  "),
+        BootstrapConfig {
+            location_template: Some("underdark".to_string()),
+            ..config("Campaign")
+        },
+        BootstrapConfig {
+            factions: vec![FactionSeed {
+                name: "Guild".to_string(),
+                org_type: Some("cartel".to_string()),
+                description: None,
+            }],
+            ..config("Campaign")
+        },
+    ];
+    for bad in bad_configs {
+        let err = bootstrap_campaign_impl(&db, bad).await.unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Validation);
+    }
+
+    // Nothing is created when the wizard's answers are rejected
+    let campaigns = list_campaigns_impl(&db)
+        .await
+        .expect("Failed to list campaigns");
+    assert!(campaigns.is_empty());
+}
@@ -1,10 +1,18 @@
 mod common;
 
-use common::setup_test_db;
+use common::{create_test_character, create_test_tag, setup_test_db};
 use loreweaver_lib::commands::campaign::{
-    create_campaign_impl, delete_campaign_impl, get_campaign_impl, list_campaigns_impl,
-    update_campaign_impl,
+    create_campaign_impl, create_demo_campaign_impl, delete_campaign_impl, get_campaign_impl,
+    list_campaigns_impl, lock_campaign_impl, unlock_campaign_impl, update_campaign_impl,
 };
+use loreweaver_lib::commands::campaign_settings::{
+    get_campaign_settings_impl, update_campaign_settings_impl, CalendarMonth, CampaignSettings,
+};
+use loreweaver_lib::commands::character::{delete_character_impl, list_characters_impl};
+use loreweaver_lib::commands::inbox::create_inbox_item_impl;
+use loreweaver_lib::commands::search::{search_entities_impl, SnippetOptions};
+use loreweaver_lib::commands::tag::add_entity_tag_impl;
+use loreweaver_lib::{AppError, ErrorCode};
 
 #[tokio::test]
 async fn test_create_campaign() {
@@ -295,3 +303,187 @@ async fn test_campaign_crud_lifecycle() {
         .expect("List after delete failed");
     assert!(list_after.is_empty());
 }
+
+#[tokio::test]
+async fn test_create_demo_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let campaign = create_demo_campaign_impl(&db, None, None)
+        .await
+        .expect("Failed to create demo campaign");
+
+    let characters = list_characters_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list characters");
+    assert_eq!(characters.len(), 36);
+
+    // Demo content goes through the normal triggers, so it is searchable
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "crown".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Failed to search");
+    assert!(!results.is_empty());
+}
+
+#[tokio::test]
+async fn test_create_demo_campaign_rejects_bad_scale() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let result = create_demo_campaign_impl(&db, None, Some(0)).await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+}
+
+#[tokio::test]
+async fn test_campaign_settings_round_trip() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_campaign_impl(&db, "Settings Test".to_string(), None, None)
+        .await
+        .expect("Failed to create campaign");
+
+    let defaults = get_campaign_settings_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to get settings");
+    assert_eq!(defaults, CampaignSettings::default());
+
+    let mut settings = defaults;
+    settings.calendar.months = vec![CalendarMonth {
+        name: "Hammer".to_string(),
+        days: 30,
+    }];
+    settings.defaults.location_type = Some("settlement".to_string());
+    update_campaign_settings_impl(&db, campaign.id.clone(), settings.clone())
+        .await
+        .expect("Failed to update settings");
+
+    let loaded = get_campaign_settings_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to get settings");
+    assert_eq!(loaded, settings);
+}
+
+#[tokio::test]
+async fn test_update_campaign_settings_validates() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_campaign_impl(&db, "Settings Test".to_string(), None, None)
+        .await
+        .expect("Failed to create campaign");
+
+    let mut settings = CampaignSettings::default();
+    settings.defaults.quest_status = Some("imaginary".to_string());
+    let result = update_campaign_settings_impl(&db, campaign.id.clone(), settings).await;
+    assert!(matches!(result, Err(AppError::InvalidFields(_))));
+
+    // Raw settings_json writes go through the same checks
+    let result = update_campaign_impl(
+        &db,
+        campaign.id.clone(),
+        None,
+        None,
+        None,
+        Some(r#"{"ai": "not an object"}"#.to_string()),
+    )
+    .await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+}
+
+#[tokio::test]
+async fn test_locked_campaign_rejects_edits_but_stays_searchable() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_campaign_impl(&db, "Finished Saga".to_string(), None, None)
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let tag = create_test_tag(&db, &campaign.id, "wizard")
+        .await
+        .expect("Failed to create tag");
+
+    let locked = lock_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to lock campaign");
+    assert!(locked.locked_at.is_some());
+    // Locking twice is a no-op rather than an edit
+    let again = lock_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to relock campaign");
+    assert_eq!(again.locked_at, locked.locked_at);
+
+    let err = create_inbox_item_impl(
+        &db,
+        campaign.id.clone(),
+        "capture".to_string(),
+        "Another idea".to_string(),
+        None,
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::ReadOnly);
+    let err = delete_character_impl(&db, character.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::ReadOnly);
+    let err = add_entity_tag_impl(
+        &db,
+        tag.id.clone(),
+        "character".to_string(),
+        character.id.clone(),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::ReadOnly);
+    let err = update_campaign_impl(
+        &db,
+        campaign.id.clone(),
+        Some("Renamed".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::ReadOnly);
+    let err = delete_campaign_impl(&db, campaign.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::ReadOnly);
+
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Gandalf".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
+    assert_eq!(results.len(), 1);
+
+    let unlocked = unlock_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to unlock campaign");
+    assert!(unlocked.locked_at.is_none());
+    assert!(delete_character_impl(&db, character.id)
+        .await
+        .expect("Failed to delete character after unlocking"));
+}
@@ -369,3 +369,78 @@ async fn test_delete_parent_location_orphans_children() {
         "Child location should still exist after parent deleted"
     );
 }
+
+#[tokio::test]
+async fn test_delete_character_removes_polymorphic_references() {
+    use loreweaver_lib::commands::character::delete_character_impl;
+    use loreweaver_lib::commands::secret::{create_secret_impl, get_secret_impl};
+
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let tobin = create_test_character(&db, &campaign.id, "Tobin")
+        .await
+        .expect("Failed to create character");
+    let tag = create_test_tag(&db, &campaign.id, "Suspect")
+        .await
+        .expect("Failed to create tag");
+
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        tobin.id.clone(),
+        "character".to_string(),
+        mira.id.clone(),
+        "ally".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    add_entity_tag_impl(
+        &db,
+        tag.id.clone(),
+        "character".to_string(),
+        mira.id.clone(),
+    )
+    .await
+    .expect("Failed to add tag");
+    let secret = create_secret_impl(
+        &db,
+        campaign.id.clone(),
+        "Double agent".to_string(),
+        "Mira reports to the duke".to_string(),
+        Some("character".to_string()),
+        Some(mira.id.clone()),
+    )
+    .await
+    .expect("Failed to create secret");
+
+    delete_character_impl(&db, mira.id.clone())
+        .await
+        .expect("Failed to delete character");
+
+    let relationships = list_relationships_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list relationships");
+    assert!(relationships.is_empty());
+    let tags = get_entity_tags_impl(&db, "character".to_string(), mira.id.clone())
+        .await
+        .expect("Failed to get tags");
+    assert!(tags.is_empty());
+
+    // The secret is kept but no longer points at the deleted character
+    let secret = get_secret_impl(&db, secret.id)
+        .await
+        .expect("Secret should still exist");
+    assert_eq!(secret.related_entity_type, None);
+    assert_eq!(secret.related_entity_id, None);
+}
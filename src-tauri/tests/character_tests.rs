@@ -1,11 +1,19 @@
 mod common;
 
-use common::{create_test_campaign, setup_test_db};
+use common::{create_test_campaign, create_test_character, setup_test_db};
+use entity::timeline_events;
+use loreweaver_lib::commands::campaign_settings::{
+    update_campaign_settings_impl, CalendarSettings, CampaignSettings, InWorldDate,
+};
 use loreweaver_lib::commands::character::{
-    create_character_impl, delete_character_impl, get_character_impl, list_characters_impl,
-    update_character_impl,
+    add_character_alias_impl, create_character_impl, delete_character_impl, get_character_impl,
+    list_characters_impl, remove_character_alias_impl, set_character_life_dates_impl,
+    set_character_roleplay_impl, update_character_impl,
 };
-use loreweaver_lib::commands::validation::CreateCharacterInput;
+use loreweaver_lib::commands::search::{search_entities_impl, SnippetOptions};
+use loreweaver_lib::commands::validation::{CreateCharacterInput, RoleplayInput};
+use loreweaver_lib::ErrorCode;
+use sea_orm::{DatabaseConnection, EntityTrait};
 
 #[tokio::test]
 async fn test_create_character() {
@@ -472,3 +480,337 @@ async fn test_create_character_validation_empty_name() {
     let err = result.unwrap_err();
     assert!(err.to_string().contains("Validation"));
 }
+
+#[tokio::test]
+async fn test_update_character_rejects_invalid_stat_block() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let invalid = [
+        "not json",
+        r#"["hp", 45]"#,
+        r#"{"armor_class": "high"}"#,
+        r#"{"hit_points": -1}"#,
+    ];
+    for bad in invalid {
+        let result = update_character_impl(
+            &db,
+            character.id.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(bad.to_string()),
+        )
+        .await;
+        assert!(result.is_err(), "accepted {}", bad);
+    }
+
+    let fetched = get_character_impl(&db, character.id)
+        .await
+        .expect("Failed to get character");
+    assert_eq!(fetched.stat_block_json, None);
+}
+
+fn date(year: i32, month: u32, day: u32) -> InWorldDate {
+    InWorldDate { year, month, day }
+}
+
+async fn set_today(db: &DatabaseConnection, campaign_id: &str, today: InWorldDate) {
+    let settings = CampaignSettings {
+        calendar: CalendarSettings {
+            today: Some(today),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    update_campaign_settings_impl(db, campaign_id.to_string(), settings)
+        .await
+        .expect("Failed to update settings");
+}
+
+#[tokio::test]
+async fn test_character_age_from_campaign_date() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Bilbo")
+        .await
+        .expect("Failed to create character");
+
+    // No current date on the calendar yet, so no age
+    let updated =
+        set_character_life_dates_impl(&db, character.id.clone(), Some(date(1290, 9, 22)), None)
+            .await
+            .expect("Failed to set life dates");
+    assert_eq!(updated.birth_date, Some(date(1290, 9, 22)));
+    assert_eq!(updated.age, None);
+
+    set_today(&db, &campaign.id, date(1401, 9, 21)).await;
+    let fetched = get_character_impl(&db, character.id.clone())
+        .await
+        .expect("Failed to get character");
+    assert_eq!(fetched.age, Some(110));
+
+    let listed = list_characters_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list characters");
+    assert_eq!(listed[0].age, Some(110));
+}
+
+#[tokio::test]
+async fn test_death_date_creates_and_removes_timeline_event() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Boromir")
+        .await
+        .expect("Failed to create character");
+    set_today(&db, &campaign.id, date(3100, 1, 1)).await;
+
+    let dead = set_character_life_dates_impl(
+        &db,
+        character.id.clone(),
+        Some(date(2978, 1, 1)),
+        Some(date(3019, 2, 26)),
+    )
+    .await
+    .expect("Failed to set life dates");
+
+    assert!(!dead.is_alive);
+    // Age stops at death
+    assert_eq!(dead.age, Some(41));
+    let event_id = dead.death_event_id.clone().expect("Expected a death event");
+    let event = timeline_events::Entity::find_by_id(&event_id)
+        .one(&db)
+        .await
+        .expect("Failed to query event")
+        .expect("Death event missing");
+    assert_eq!(event.title, "Death of Boromir");
+    assert_eq!(event.date_display, "26 Month 2 3019");
+
+    // Moving the death date moves the same event
+    let moved = set_character_life_dates_impl(
+        &db,
+        character.id.clone(),
+        Some(date(2978, 1, 1)),
+        Some(date(3019, 3, 1)),
+    )
+    .await
+    .expect("Failed to move death date");
+    assert_eq!(moved.death_event_id, Some(event_id.clone()));
+
+    // Clearing the death date removes the event
+    let cleared =
+        set_character_life_dates_impl(&db, character.id.clone(), Some(date(2978, 1, 1)), None)
+            .await
+            .expect("Failed to clear death date");
+    assert_eq!(cleared.death_event_id, None);
+    let event = timeline_events::Entity::find_by_id(&event_id)
+        .one(&db)
+        .await
+        .expect("Failed to query event");
+    assert!(event.is_none());
+}
+
+#[tokio::test]
+async fn test_life_dates_are_validated() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gollum")
+        .await
+        .expect("Failed to create character");
+
+    // Month out of range on the default 12-month calendar
+    let result =
+        set_character_life_dates_impl(&db, character.id.clone(), Some(date(2430, 13, 1)), None)
+            .await;
+    assert!(result.is_err());
+
+    // Death before birth
+    let result = set_character_life_dates_impl(
+        &db,
+        character.id.clone(),
+        Some(date(2430, 1, 1)),
+        Some(date(2400, 1, 1)),
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_character_aliases() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    assert!(character.aliases_json.is_none());
+
+    add_character_alias_impl(&db, character.id.clone(), "Mithrandir".to_string())
+        .await
+        .expect("Failed to add alias");
+    let updated = add_character_alias_impl(&db, character.id.clone(), " Grey Pilgrim ".to_string())
+        .await
+        .expect("Failed to add alias");
+    assert_eq!(updated.aliases, vec!["Mithrandir", "Grey Pilgrim"]);
+
+    // Aliases are searchable, so mentions find the character by them
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "mithr".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Failed to search");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].entity_id, character.id);
+    assert_eq!(results[0].name, "Gandalf");
+
+    let removed = remove_character_alias_impl(&db, character.id.clone(), "mithrandir".to_string())
+        .await
+        .expect("Failed to remove alias");
+    assert_eq!(removed.aliases, vec!["Grey Pilgrim"]);
+
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "mithr".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Failed to search");
+    assert!(results.is_empty());
+}
+
+#[tokio::test]
+async fn test_character_alias_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Strider")
+        .await
+        .expect("Failed to create character");
+
+    add_character_alias_impl(&db, character.id.clone(), "Aragorn".to_string())
+        .await
+        .expect("Failed to add alias");
+
+    let duplicate = add_character_alias_impl(&db, character.id.clone(), "ARAGORN".to_string())
+        .await
+        .expect_err("Duplicate alias should fail");
+    assert_eq!(duplicate.code(), ErrorCode::Conflict);
+
+    let own_name = add_character_alias_impl(&db, character.id.clone(), "strider".to_string())
+        .await
+        .expect_err("Alias matching the name should fail");
+    assert_eq!(own_name.code(), ErrorCode::Conflict);
+
+    let blank = add_character_alias_impl(&db, character.id.clone(), "   ".to_string())
+        .await
+        .expect_err("Blank alias should fail");
+    assert_eq!(blank.code(), ErrorCode::Validation);
+
+    let missing = remove_character_alias_impl(&db, character.id.clone(), "Elessar".to_string())
+        .await
+        .expect_err("Removing an unknown alias should fail");
+    assert_eq!(missing.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_character_roleplay_fields() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Mara")
+        .await
+        .expect("Failed to create character");
+
+    let updated = set_character_roleplay_impl(
+        &db,
+        character.id.clone(),
+        RoleplayInput {
+            accent: Some("Clipped northern burr".to_string()),
+            mannerisms: Some(vec![
+                "Taps her pipe on the table".to_string(),
+                "  ".to_string(),
+            ]),
+            catchphrases: Some(vec!["Coin first, questions never.".to_string()]),
+            speech_sample: Some("You want the map? Then you pay for the map.".to_string()),
+        },
+    )
+    .await
+    .expect("Failed to set roleplay fields");
+    assert_eq!(updated.accent.as_deref(), Some("Clipped northern burr"));
+    assert_eq!(updated.mannerisms, vec!["Taps her pipe on the table"]);
+    assert_eq!(updated.catchphrases, vec!["Coin first, questions never."]);
+
+    // Fields left out are kept; empty values clear
+    let updated = set_character_roleplay_impl(
+        &db,
+        character.id.clone(),
+        RoleplayInput {
+            accent: Some(String::new()),
+            mannerisms: Some(Vec::new()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to set roleplay fields");
+    assert_eq!(updated.accent, None);
+    assert!(updated.mannerisms.is_empty());
+    assert_eq!(updated.catchphrases.len(), 1);
+    assert!(updated.speech_sample.is_some());
+
+    let too_many = RoleplayInput {
+        catchphrases: Some(vec!["Aye".to_string(); 21]),
+        ..Default::default()
+    };
+    let err = set_character_roleplay_impl(&db, character.id.clone(), too_many)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = set_character_roleplay_impl(&db, "nope".to_string(), RoleplayInput::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::command_palette::get_command_palette_items_impl;
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_palette_blends_entities_recent_items_and_actions() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let town = create_test_location(&db, &campaign.id, "Sessrumnir", None)
+        .await
+        .expect("Failed to create location");
+    let seer = create_test_character(&db, &campaign.id, "Old Sess")
+        .await
+        .expect("Failed to create character");
+
+    // No query: recent entities, newest first, then actions
+    let items = get_command_palette_items_impl(&db, campaign.id.clone(), String::new())
+        .await
+        .expect("Failed to list palette items");
+    let ids: Vec<_> = items.iter().take(2).map(|i| i.id.as_str()).collect();
+    assert_eq!(ids, vec![seer.id.as_str(), town.id.as_str()]);
+    assert!(items[..2].iter().all(|i| i.kind == "recent"));
+    assert!(items
+        .iter()
+        .any(|i| i.id == "start_backup" && i.kind == "action"));
+
+    // Prefix matches outrank word matches; the action matches last
+    let items = get_command_palette_items_impl(&db, campaign.id.clone(), "sess".to_string())
+        .await
+        .expect("Failed to search palette items");
+    let ranked: Vec<_> = items.iter().map(|i| i.label.as_str()).collect();
+    assert_eq!(ranked, vec!["Sessrumnir", "Old Sess", "New session"]);
+    assert_eq!(items[0].entity_type.as_deref(), Some("location"));
+    assert_eq!(items[2].kind, "action");
+}
+
+#[tokio::test]
+async fn test_palette_unknown_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = get_command_palette_items_impl(&db, "missing".to_string(), "x".to_string())
+        .await
+        .expect_err("Unknown campaign");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -1,4 +1,6 @@
-use entity::{campaigns, characters, locations, tags};
+use entity::{
+    campaigns, characters, heroes, items, locations, organizations, players, sessions, tags,
+};
 use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, Set};
 
 /// Creates a test campaign with sensible defaults
@@ -17,6 +19,11 @@ pub async fn create_test_campaign(
         settings_json: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        archived_at: Set(None),
+        archive_path: Set(None),
+        locked_at: Set(None),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     model.insert(db).await
@@ -44,8 +51,25 @@ pub async fn create_test_character(
         secrets: Set(None),
         voice_notes: Set(None),
         stat_block_json: Set(None),
+        birth_year: Set(None),
+        birth_month: Set(None),
+        birth_day: Set(None),
+        death_year: Set(None),
+        death_month: Set(None),
+        death_day: Set(None),
+        death_event_id: Set(None),
+        aliases_json: Set(None),
+        source_hero_id: Set(None),
+        color: Set(None),
+        icon: Set(None),
+        accent: Set(None),
+        mannerisms_json: Set(None),
+        catchphrases_json: Set(None),
+        speech_sample: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     model.insert(db).await
@@ -69,8 +93,166 @@ pub async fn create_test_location(
         location_type: Set("settlement".to_string()),
         description: Set(Some("A test location".to_string())),
         gm_notes: Set(None),
+        color: Set(None),
+        icon: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        population: Set(None),
+        government_type: Set(None),
+        dominant_organization_id: Set(None),
+        economy_notes: Set(None),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    model.insert(db).await
+}
+
+/// Creates a test player with no availability recorded
+pub async fn create_test_player(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    name: &str,
+) -> Result<players::Model, DbErr> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now();
+
+    let model = players::ActiveModel {
+        id: Set(id),
+        campaign_id: Set(campaign_id.to_string()),
+        name: Set(name.to_string()),
+        preferences: Set(None),
+        boundaries: Set(None),
+        notes: Set(None),
+        contact_email: Set(None),
+        contact_handle: Set(None),
+        availability_json: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    model.insert(db).await
+}
+
+/// Creates a numbered test session, optionally on a real-world date
+pub async fn create_test_session(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    session_number: i32,
+    date: Option<chrono::NaiveDate>,
+) -> Result<sessions::Model, DbErr> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now();
+
+    let model = sessions::ActiveModel {
+        id: Set(id),
+        campaign_id: Set(campaign_id.to_string()),
+        session_number: Set(session_number),
+        date: Set(date),
+        title: Set(None),
+        planned_content: Set(None),
+        notes: Set(None),
+        summary: Set(None),
+        highlights: Set(None),
+        started_at: Set(None),
+        ended_at: Set(None),
+        breaks_json: Set(None),
+        timeline_event_id: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    model.insert(db).await
+}
+
+/// Creates an active test hero with no player
+pub async fn create_test_hero(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    name: &str,
+) -> Result<heroes::Model, DbErr> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now();
+
+    let model = heroes::ActiveModel {
+        id: Set(id),
+        campaign_id: Set(campaign_id.to_string()),
+        player_id: Set(None),
+        name: Set(name.to_string()),
+        lineage: Set(Some("Human".to_string())),
+        classes: Set(Some("Fighter 1".to_string())),
+        description: Set(None),
+        backstory: Set(None),
+        goals: Set(None),
+        bonds: Set(None),
+        is_active: Set(true),
+        source_character_id: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    model.insert(db).await
+}
+
+/// Creates a test item
+pub async fn create_test_item(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    name: &str,
+    requires_attunement: bool,
+) -> Result<items::Model, DbErr> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now();
+
+    let model = items::ActiveModel {
+        id: Set(id),
+        campaign_id: Set(campaign_id.to_string()),
+        name: Set(name.to_string()),
+        item_type: Set("wondrous".to_string()),
+        rarity: Set(Some("rare".to_string())),
+        description: Set(None),
+        requires_attunement: Set(requires_attunement),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    };
+
+    model.insert(db).await
+}
+
+/// Creates a test organization
+pub async fn create_test_organization(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    name: &str,
+) -> Result<organizations::Model, DbErr> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now();
+
+    let model = organizations::ActiveModel {
+        id: Set(id),
+        campaign_id: Set(campaign_id.to_string()),
+        name: Set(name.to_string()),
+        org_type: Set("guild".to_string()),
+        description: Set(None),
+        goals: Set(None),
+        resources: Set(None),
+        reputation: Set(None),
+        secrets: Set(None),
+        is_active: Set(true),
+        color: Set(None),
+        icon: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     model.insert(db).await
@@ -91,6 +273,8 @@ pub async fn create_test_tag(
         name: Set(name.to_string()),
         color: Set(Some("#FF5733".to_string())),
         created_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
     };
 
     model.insert(db).await
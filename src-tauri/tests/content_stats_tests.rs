@@ -0,0 +1,95 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::content_stats::get_content_stats_impl;
+use loreweaver_lib::commands::secret::create_secret_impl;
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_content_stats_counts_words() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    // "A test character" / "A test location"
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    create_test_character(&db, &campaign.id, "Dorn")
+        .await
+        .expect("Failed to create character");
+    create_test_location(&db, &campaign.id, "Saltmere", None)
+        .await
+        .expect("Failed to create location");
+    create_test_character(&db, &other.id, "Elsewhere")
+        .await
+        .expect("Failed to create character");
+
+    let content = r#"{"type":"doc","content":[{"type":"paragraph","content":[
+        {"type":"text","text":"The harbor master smuggles relics for "},
+        {"type":"mention","attrs":{"id":"x","label":"Mira"}}
+    ]}]}"#;
+    let secret = create_secret_impl(
+        &db,
+        campaign.id.clone(),
+        "Smuggling".to_string(),
+        content.to_string(),
+        Some("character".to_string()),
+        Some(mira.id.clone()),
+    )
+    .await
+    .expect("Failed to create secret");
+
+    let stats = get_content_stats_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to get stats");
+
+    assert_eq!(stats.total_words, 16);
+
+    let by_type: Vec<(&str, u64, u64)> = stats
+        .by_type
+        .iter()
+        .map(|t| (t.entity_type.as_str(), t.entity_count, t.word_count))
+        .collect();
+    assert_eq!(
+        by_type,
+        vec![("secret", 1, 7), ("character", 2, 6), ("location", 1, 3)]
+    );
+
+    assert_eq!(stats.entities.len(), 4);
+    assert_eq!(stats.entities[0].entity_id, secret.id);
+    assert_eq!(stats.entities[0].name.as_deref(), Some("Smuggling"));
+    assert_eq!(stats.entities[0].word_count, 7);
+    assert!(stats
+        .entities
+        .iter()
+        .all(|e| e.name.as_deref() != Some("Elsewhere")));
+}
+
+#[tokio::test]
+async fn test_content_stats_empty_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let stats = get_content_stats_impl(&db, campaign.id)
+        .await
+        .expect("Failed to get stats");
+    assert_eq!(stats.total_words, 0);
+    assert!(stats.by_type.is_empty());
+    assert!(stats.entities.is_empty());
+
+    let err = get_content_stats_impl(&db, "missing".to_string())
+        .await
+        .expect_err("Unknown campaign should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use common::{create_test_campaign, create_test_location, setup_test_db};
+use loreweaver_lib::commands::content_warning::{
+    check_content_warnings_impl, create_content_warning_impl, delete_content_warning_impl,
+    list_content_warnings_impl, update_content_warning_impl,
+};
+use loreweaver_lib::commands::quest::create_quest_impl;
+use loreweaver_lib::commands::validation::{
+    CreateContentWarningInput, CreateQuestInput, UpdateContentWarningInput,
+};
+use loreweaver_lib::ErrorCode;
+
+fn warning_input(campaign_id: &str, topic: &str, severity: &str) -> CreateContentWarningInput {
+    CreateContentWarningInput {
+        campaign_id: campaign_id.to_string(),
+        topic: topic.to_string(),
+        severity: severity.to_string(),
+        keywords: Vec::new(),
+        notes: None,
+    }
+}
+
+#[tokio::test]
+async fn test_content_warning_crud() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let mut input = warning_input(&campaign.id, " Spiders ", "line");
+    input.keywords = vec![
+        "arachnid".to_string(),
+        " ".to_string(),
+        "Arachnid".to_string(),
+    ];
+    let spiders = create_content_warning_impl(&db, input)
+        .await
+        .expect("Failed to create warning");
+    assert_eq!(spiders.topic, "Spiders");
+    assert_eq!(spiders.keywords, vec!["arachnid"]);
+    create_content_warning_impl(&db, warning_input(&campaign.id, "Torture", "veil"))
+        .await
+        .expect("Failed to create warning");
+    create_content_warning_impl(&db, warning_input(&campaign.id, "Gore", "veil"))
+        .await
+        .expect("Failed to create warning");
+
+    let warnings = list_content_warnings_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list warnings");
+    let topics: Vec<&str> = warnings.iter().map(|w| w.topic.as_str()).collect();
+    assert_eq!(topics, vec!["Spiders", "Gore", "Torture"]);
+
+    let spiders = update_content_warning_impl(
+        &db,
+        spiders.id.clone(),
+        UpdateContentWarningInput {
+            severity: Some("veil".to_string()),
+            keywords: Some(Vec::new()),
+            notes: Some("Fine off-screen".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update warning");
+    assert_eq!(spiders.severity, "veil");
+    assert!(spiders.keywords.is_empty());
+    assert_eq!(spiders.notes.as_deref(), Some("Fine off-screen"));
+
+    assert!(delete_content_warning_impl(&db, spiders.id.clone())
+        .await
+        .expect("Failed to delete warning"));
+    let warnings = list_content_warnings_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list warnings");
+    assert_eq!(warnings.len(), 2);
+}
+
+#[tokio::test]
+async fn test_check_flags_entities_and_quests() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_location(&db, &campaign.id, "Arachnid Hollow", None)
+        .await
+        .expect("Failed to create location");
+    create_quest_impl(
+        &db,
+        CreateQuestInput {
+            campaign_id: campaign.id.clone(),
+            name: "The Red Cellar".to_string(),
+            plot_type: "side".to_string(),
+            status: "active".to_string(),
+            description: Some(
+                r#"{"type":"doc","content":[{"type":"paragraph","content":[
+                    {"type":"text","text":"The cult tortures prisoners below the inn."}
+                ]}]}"#
+                    .to_string(),
+            ),
+            hook: Some("Rather quiet, for a tavern.".to_string()),
+            objectives: None,
+        },
+    )
+    .await
+    .expect("Failed to create quest");
+
+    // Nothing to check against yet
+    let flags = check_content_warnings_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to check warnings");
+    assert!(flags.is_empty());
+
+    let mut input = warning_input(&campaign.id, "Spiders", "line");
+    input.keywords = vec!["arachnid".to_string()];
+    create_content_warning_impl(&db, input)
+        .await
+        .expect("Failed to create warning");
+    create_content_warning_impl(&db, warning_input(&campaign.id, "Torture", "veil"))
+        .await
+        .expect("Failed to create warning");
+    create_content_warning_impl(&db, warning_input(&campaign.id, "Rats", "veil"))
+        .await
+        .expect("Failed to create warning");
+
+    let flags = check_content_warnings_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to check warnings");
+    assert_eq!(flags.len(), 2);
+    assert_eq!(flags[0].severity, "line");
+    assert_eq!(flags[0].entity_type, "location");
+    assert_eq!(flags[0].field, "name");
+    assert_eq!(flags[0].matched, "arachnid");
+    assert_eq!(flags[1].entity_name.as_deref(), Some("The Red Cellar"));
+    assert_eq!(flags[1].field, "description");
+    assert_eq!(flags[1].topic, "Torture");
+    assert_eq!(
+        flags[1].excerpt.trim(),
+        "The cult tortures prisoners below the inn."
+    );
+
+    let flags = check_content_warnings_impl(&db, campaign.id.clone(), Some("quest".to_string()))
+        .await
+        .expect("Failed to check warnings");
+    assert_eq!(flags.len(), 1);
+}
+
+#[tokio::test]
+async fn test_content_warning_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let err = create_content_warning_impl(&db, warning_input(&campaign.id, "Gore", "maybe"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = create_content_warning_impl(&db, warning_input(&campaign.id, "  ", "line"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = update_content_warning_impl(&db, "nope".to_string(), Default::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = check_content_warnings_impl(&db, "nope".to_string(), None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    let err = check_content_warnings_impl(&db, campaign.id.clone(), Some("dragon".to_string()))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_tag, setup_test_db};
+use entity::{heroes, secrets};
+use loreweaver_lib::commands::annotation::{create_annotation_impl, list_open_annotations_impl};
+use loreweaver_lib::commands::character::get_character_impl;
+use loreweaver_lib::commands::conversion::{
+    convert_character_to_hero_impl, convert_hero_to_character_impl,
+};
+use loreweaver_lib::commands::recall_card::create_recall_card_impl;
+use loreweaver_lib::commands::references::repair_orphaned_references_impl;
+use loreweaver_lib::commands::relationship::{
+    create_relationship_impl, get_entity_relationships_impl,
+};
+use loreweaver_lib::commands::tag::{add_entity_tag_impl, get_entity_tags_impl};
+use loreweaver_lib::commands::validation::{CreateAnnotationInput, CreateRecallCardInput};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+
+async fn create_secret(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    entity_type: &str,
+    entity_id: &str,
+) -> secrets::Model {
+    let now = chrono::Utc::now();
+    secrets::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id.to_string()),
+        title: Set("Hidden lineage".to_string()),
+        content: Set("Heir to the throne".to_string()),
+        related_entity_type: Set(Some(entity_type.to_string())),
+        related_entity_id: Set(Some(entity_id.to_string())),
+        known_by: Set(None),
+        revealed: Set(false),
+        revealed_in_session: Set(None),
+        prerequisites_json: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(db)
+    .await
+    .expect("Failed to create secret")
+}
+
+#[tokio::test]
+async fn test_convert_character_to_hero_moves_references() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let npc = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let friend = create_test_character(&db, &campaign.id, "Tomas")
+        .await
+        .expect("Failed to create character");
+    let tag = create_test_tag(&db, &campaign.id, "Ally")
+        .await
+        .expect("Failed to create tag");
+
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        friend.id.clone(),
+        "character".to_string(),
+        npc.id.clone(),
+        "friend".to_string(),
+        None,
+        Some(true),
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    add_entity_tag_impl(&db, tag.id.clone(), "character".to_string(), npc.id.clone())
+        .await
+        .expect("Failed to tag character");
+    let secret = create_secret(&db, &campaign.id, "character", &npc.id).await;
+
+    let hero = convert_character_to_hero_impl(&db, npc.id.clone(), None, false)
+        .await
+        .expect("Failed to convert character");
+
+    assert_eq!(hero.name, "Mira");
+    assert_eq!(hero.campaign_id, campaign.id);
+    assert_eq!(hero.source_character_id, Some(npc.id.clone()));
+    assert!(hero.is_active);
+
+    let relationships = get_entity_relationships_impl(&db, "hero".to_string(), hero.id.clone())
+        .await
+        .expect("Failed to get relationships");
+    assert_eq!(relationships.len(), 1);
+    assert_eq!(relationships[0].source_id, friend.id);
+    assert_eq!(relationships[0].target_type, "hero");
+
+    let tags = get_entity_tags_impl(&db, "hero".to_string(), hero.id.clone())
+        .await
+        .expect("Failed to get tags");
+    assert_eq!(tags.len(), 1);
+
+    let secret = secrets::Entity::find_by_id(&secret.id)
+        .one(&db)
+        .await
+        .expect("Failed to query secret")
+        .expect("Secret should still exist");
+    assert_eq!(secret.related_entity_type.as_deref(), Some("hero"));
+    assert_eq!(secret.related_entity_id, Some(hero.id.clone()));
+
+    assert!(get_character_impl(&db, npc.id.clone()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_convert_character_to_hero_keeps_source() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let npc = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    convert_character_to_hero_impl(&db, npc.id.clone(), None, true)
+        .await
+        .expect("Failed to convert character");
+
+    assert!(get_character_impl(&db, npc.id.clone()).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_convert_hero_to_character_round_trip() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let npc = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let tag = create_test_tag(&db, &campaign.id, "Ally")
+        .await
+        .expect("Failed to create tag");
+    add_entity_tag_impl(&db, tag.id.clone(), "character".to_string(), npc.id.clone())
+        .await
+        .expect("Failed to tag character");
+
+    let hero = convert_character_to_hero_impl(&db, npc.id.clone(), None, false)
+        .await
+        .expect("Failed to convert character");
+    let character = convert_hero_to_character_impl(&db, hero.id.clone(), true)
+        .await
+        .expect("Failed to convert hero");
+
+    assert_eq!(character.name, "Mira");
+    assert_eq!(character.source_hero_id, Some(hero.id.clone()));
+    assert!(character.is_alive);
+
+    let tags = get_entity_tags_impl(&db, "character".to_string(), character.id.clone())
+        .await
+        .expect("Failed to get tags");
+    assert_eq!(tags.len(), 1);
+
+    let archived = heroes::Entity::find_by_id(&hero.id)
+        .one(&db)
+        .await
+        .expect("Failed to query hero")
+        .expect("Archived hero should still exist");
+    assert!(!archived.is_active);
+}
+
+#[tokio::test]
+async fn test_convert_character_to_hero_leaves_no_orphans() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let npc = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    create_annotation_impl(
+        &db,
+        CreateAnnotationInput {
+            entity_type: "character".to_string(),
+            entity_id: npc.id.clone(),
+            field: None,
+            body: "Does Mira join the party for good?".to_string(),
+            author: None,
+        },
+    )
+    .await
+    .expect("Failed to create annotation");
+    create_recall_card_impl(
+        &db,
+        CreateRecallCardInput {
+            campaign_id: campaign.id.clone(),
+            front: "Who runs the harbor?".to_string(),
+            back: "Mira".to_string(),
+            entity_type: Some("character".to_string()),
+            entity_id: Some(npc.id.clone()),
+        },
+    )
+    .await
+    .expect("Failed to create recall card");
+
+    let hero = convert_character_to_hero_impl(&db, npc.id.clone(), None, false)
+        .await
+        .expect("Failed to convert character");
+
+    let orphans = repair_orphaned_references_impl(&db, true)
+        .await
+        .expect("Failed to check references");
+    assert_eq!(orphans.annotations, 0);
+    assert_eq!(orphans.recall_cards, 0);
+
+    let annotations = list_open_annotations_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list annotations");
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0].annotation.entity_id, hero.id);
+}
+
+#[tokio::test]
+async fn test_convert_missing_character_fails() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let result = convert_character_to_hero_impl(&db, "missing".to_string(), None, false).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_location, create_test_tag,
+    setup_test_db,
+};
+use loreweaver_lib::commands::delete_preview::preview_delete_impl;
+use loreweaver_lib::commands::relationship::create_relationship_impl;
+use loreweaver_lib::commands::secret::create_secret_impl;
+use loreweaver_lib::commands::tag::add_entity_tag_impl;
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_preview_delete_parent_location() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let city = create_test_location(&db, &campaign.id, "Port Vell", None)
+        .await
+        .expect("Failed to create location");
+    let docks = create_test_location(&db, &campaign.id, "Docks", Some(&city.id))
+        .await
+        .expect("Failed to create location");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let tag = create_test_tag(&db, &campaign.id, "Coastal")
+        .await
+        .expect("Failed to create tag");
+
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        mira.id.clone(),
+        "location".to_string(),
+        city.id.clone(),
+        "lives_in".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    add_entity_tag_impl(&db, tag.id, "location".to_string(), city.id.clone())
+        .await
+        .expect("Failed to tag location");
+    create_secret_impl(
+        &db,
+        campaign.id.clone(),
+        "Smugglers".to_string(),
+        format!("Smugglers meet under [the city](location:{})", city.id),
+        Some("location".to_string()),
+        Some(city.id.clone()),
+    )
+    .await
+    .expect("Failed to create secret");
+
+    let preview = preview_delete_impl(&db, "location".to_string(), city.id.clone())
+        .await
+        .expect("Failed to preview delete");
+    assert_eq!(preview.name.as_deref(), Some("Port Vell"));
+    assert_eq!(preview.detached.len(), 1);
+    assert_eq!(preview.detached[0].entity_id, docks.id);
+    assert_eq!(preview.relationships, 1);
+    assert_eq!(preview.tags, 1);
+    assert_eq!(preview.secrets, 1);
+    assert_eq!(preview.search_rows, 1);
+    assert_eq!(preview.mentions.len(), 1);
+    assert_eq!(preview.mentions[0].entity_type, "secret");
+    assert!(preview.cascaded.is_empty());
+}
+
+#[tokio::test]
+async fn test_preview_delete_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    create_test_location(&db, &campaign.id, "Harbor", None)
+        .await
+        .expect("Failed to create location");
+
+    let preview = preview_delete_impl(&db, "campaign".to_string(), campaign.id.clone())
+        .await
+        .expect("Failed to preview delete");
+    assert_eq!(preview.cascaded["character"], 1);
+    assert_eq!(preview.cascaded["location"], 1);
+    assert!(!preview.cascaded.contains_key("quest"));
+    assert_eq!(preview.search_rows, 2);
+
+    let err = preview_delete_impl(&db, "dragon".to_string(), campaign.id)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = preview_delete_impl(&db, "character".to_string(), "missing".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, setup_test_db};
+use entity::characters;
+use loreweaver_lib::commands::detail_level::{get_stub_report_impl, list_entities_by_detail_impl};
+use loreweaver_lib::commands::relationship::create_relationship_impl;
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+
+async fn set_description(
+    db: &DatabaseConnection,
+    character: characters::Model,
+    text: Option<String>,
+) {
+    let mut active: characters::ActiveModel = character.into();
+    active.description = Set(text);
+    active.update(db).await.expect("Failed to update character");
+}
+
+#[tokio::test]
+async fn test_list_entities_by_detail() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let stub = create_test_character(&db, &campaign.id, "Nameless Guard")
+        .await
+        .expect("Failed to create character");
+    set_description(&db, stub, None).await;
+    let detailed = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    set_description(&db, detailed, Some("word ".repeat(120))).await;
+    create_test_character(&db, &campaign.id, "Tobin")
+        .await
+        .expect("Failed to create character");
+
+    let all = list_entities_by_detail_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        None,
+        None,
+        false,
+    )
+    .await
+    .expect("Failed to list characters");
+    let levels: Vec<(Option<&str>, u8)> = all
+        .iter()
+        .map(|d| (d.name.as_deref(), d.detail_level))
+        .collect();
+    assert_eq!(
+        levels,
+        vec![
+            (Some("Nameless Guard"), 0),
+            (Some("Tobin"), 1),
+            (Some("Mira"), 3)
+        ]
+    );
+    assert_eq!(all[0].detail_label, "stub");
+
+    let detailed = list_entities_by_detail_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        Some(2),
+        None,
+        true,
+    )
+    .await
+    .expect("Failed to list characters");
+    assert_eq!(detailed.len(), 1);
+    assert_eq!(detailed[0].name.as_deref(), Some("Mira"));
+
+    let err = list_entities_by_detail_impl(
+        &db,
+        campaign.id.clone(),
+        "quest".to_string(),
+        None,
+        None,
+        false,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = list_entities_by_detail_impl(
+        &db,
+        campaign.id,
+        "character".to_string(),
+        Some(4),
+        Some(2),
+        false,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
+
+#[tokio::test]
+async fn test_stub_report_ranks_referenced_stubs() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let duke = create_test_character(&db, &campaign.id, "The Duke")
+        .await
+        .expect("Failed to create character");
+    let duke_id = duke.id.clone();
+    set_description(&db, duke, None).await;
+    let lonely = create_test_character(&db, &campaign.id, "Hermit")
+        .await
+        .expect("Failed to create character");
+    set_description(&db, lonely, None).await;
+
+    // One relationship and one mention point at the duke
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        mira.id.clone(),
+        "character".to_string(),
+        duke_id.clone(),
+        "serves".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    let tobin = create_test_character(&db, &campaign.id, "Tobin")
+        .await
+        .expect("Failed to create character");
+    set_description(
+        &db,
+        tobin,
+        Some(format!("Owes a debt to [[character:{}:The Duke]]", duke_id)),
+    )
+    .await;
+
+    let report = get_stub_report_impl(&db, campaign.id.clone(), None, None)
+        .await
+        .expect("Failed to get stub report");
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].detail.entity_id, duke_id);
+    assert_eq!(report[0].reference_count, 2);
+
+    let report = get_stub_report_impl(&db, campaign.id, Some(0), Some(0))
+        .await
+        .expect("Failed to get stub report");
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0].detail.entity_id, duke_id);
+}
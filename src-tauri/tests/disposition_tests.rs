@@ -0,0 +1,162 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_session, setup_test_db};
+use loreweaver_lib::commands::disposition::{
+    adjust_disposition_impl, delete_disposition_impl, get_disposition_history_impl,
+    list_dispositions_impl, MAX_DISPOSITION,
+};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_adjust_disposition_builds_history() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let first = adjust_disposition_impl(
+        &db,
+        mira.id.clone(),
+        20,
+        Some("Party saved her brother".to_string()),
+        Some(session.id.clone()),
+    )
+    .await
+    .expect("Failed to adjust disposition");
+    assert_eq!(first.value, 20);
+    assert_eq!(first.change, 20);
+    assert_eq!(first.session_id, Some(session.id.clone()));
+
+    let second = adjust_disposition_impl(
+        &db,
+        mira.id.clone(),
+        -35,
+        Some("Caught lying".to_string()),
+        None,
+    )
+    .await
+    .expect("Failed to adjust disposition");
+    assert_eq!(second.value, -15);
+    assert_eq!(second.change, -35);
+
+    let history = get_disposition_history_impl(&db, mira.id.clone())
+        .await
+        .expect("Failed to get history");
+    assert_eq!(history.current, -15);
+    let changes: Vec<_> = history.entries.iter().map(|e| e.change).collect();
+    assert_eq!(changes, vec![20, -35]);
+    assert_eq!(history.entries[1].reason.as_deref(), Some("Caught lying"));
+
+    // Deleting a mistaken entry leaves later values alone
+    assert!(delete_disposition_impl(&db, first.id.clone())
+        .await
+        .expect("Failed to delete entry"));
+    let history = get_disposition_history_impl(&db, mira.id.clone())
+        .await
+        .expect("Failed to get history");
+    assert_eq!(history.current, -15);
+    assert_eq!(history.entries.len(), 1);
+    assert_eq!(history.entries[0].change, -15);
+}
+
+#[tokio::test]
+async fn test_disposition_is_clamped() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    adjust_disposition_impl(&db, mira.id.clone(), 90, None, None)
+        .await
+        .expect("Failed to adjust disposition");
+    let capped = adjust_disposition_impl(&db, mira.id.clone(), 50, None, None)
+        .await
+        .expect("Failed to adjust disposition");
+    assert_eq!(capped.value, MAX_DISPOSITION);
+    assert_eq!(capped.change, 10);
+}
+
+#[tokio::test]
+async fn test_list_dispositions() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let borin = create_test_character(&db, &campaign.id, "Borin")
+        .await
+        .expect("Failed to create character");
+    create_test_character(&db, &campaign.id, "Stranger")
+        .await
+        .expect("Failed to create character");
+
+    adjust_disposition_impl(&db, mira.id.clone(), 10, None, None)
+        .await
+        .expect("Failed to adjust disposition");
+    adjust_disposition_impl(&db, borin.id.clone(), 30, None, None)
+        .await
+        .expect("Failed to adjust disposition");
+    adjust_disposition_impl(&db, borin.id.clone(), -50, None, None)
+        .await
+        .expect("Failed to adjust disposition");
+
+    let dispositions = list_dispositions_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list dispositions");
+    let summary: Vec<_> = dispositions
+        .iter()
+        .map(|d| (d.character_name.as_str(), d.value))
+        .collect();
+    assert_eq!(summary, vec![("Mira", 10), ("Borin", -20)]);
+}
+
+#[tokio::test]
+async fn test_adjust_disposition_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other_campaign = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let other_session = create_test_session(&db, &other_campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let err = adjust_disposition_impl(&db, mira.id.clone(), 0, None, None)
+        .await
+        .expect_err("Zero change should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = adjust_disposition_impl(&db, mira.id.clone(), 5, None, Some(other_session.id))
+        .await
+        .expect_err("Session from another campaign should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = adjust_disposition_impl(&db, "missing".to_string(), 5, None, None)
+        .await
+        .expect_err("Unknown character should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, setup_test_db};
+use loreweaver_lib::commands::campaign::delete_campaign_impl;
+use loreweaver_lib::commands::character::update_character_impl;
+use loreweaver_lib::commands::draft::{discard_draft_impl, get_draft_impl, save_draft_impl};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_draft_save_get_discard() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let draft = |content: &str| {
+        save_draft_impl(
+            &db,
+            "character".to_string(),
+            character.id.clone(),
+            "description".to_string(),
+            content.to_string(),
+        )
+    };
+    let get = || {
+        get_draft_impl(
+            &db,
+            "character".to_string(),
+            character.id.clone(),
+            "description".to_string(),
+        )
+    };
+
+    let first = draft("Born in").await.expect("Failed to save draft");
+    assert_eq!(first.campaign_id, campaign.id);
+    let second = draft("Born in the far west")
+        .await
+        .expect("Failed to save draft");
+    assert_eq!(second.id, first.id, "A field keeps a single draft");
+
+    let saved = get()
+        .await
+        .expect("Failed to get draft")
+        .expect("Draft should exist");
+    assert_eq!(saved.content, "Born in the far west");
+    assert!(!saved.stale);
+
+    // Saving the entity afterwards marks the draft as possibly outdated
+    update_character_impl(
+        &db,
+        character.id.clone(),
+        None,
+        None,
+        None,
+        None,
+        Some("Saved elsewhere".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update character");
+    let saved = get().await.expect("Failed to get draft").unwrap();
+    assert!(saved.stale);
+
+    let discard = || {
+        discard_draft_impl(
+            &db,
+            "character".to_string(),
+            character.id.clone(),
+            "description".to_string(),
+        )
+    };
+    assert!(discard().await.expect("Failed to discard draft"));
+    assert!(!discard().await.expect("Failed to discard draft"));
+    assert!(get().await.expect("Failed to get draft").is_none());
+}
+
+#[tokio::test]
+async fn test_draft_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let err = save_draft_impl(
+        &db,
+        "character".to_string(),
+        character.id.clone(),
+        "name".to_string(),
+        "Gandalf the White".to_string(),
+    )
+    .await
+    .expect_err("Only long text fields take drafts");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = save_draft_impl(
+        &db,
+        "character".to_string(),
+        "missing".to_string(),
+        "description".to_string(),
+        "text".to_string(),
+    )
+    .await
+    .expect_err("Drafts need an existing entity");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = save_draft_impl(
+        &db,
+        "character".to_string(),
+        character.id.clone(),
+        "description".to_string(),
+        "x".repeat(200_001),
+    )
+    .await
+    .expect_err("Oversized drafts are rejected");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    // Drafts go with their campaign
+    save_draft_impl(
+        &db,
+        "character".to_string(),
+        character.id.clone(),
+        "secrets".to_string(),
+        "Is a Maia".to_string(),
+    )
+    .await
+    .expect("Failed to save draft");
+    delete_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to delete campaign");
+    let discarded = discard_draft_impl(
+        &db,
+        "character".to_string(),
+        character.id.clone(),
+        "secrets".to_string(),
+    )
+    .await
+    .expect("Failed to discard draft");
+    assert!(!discarded);
+}
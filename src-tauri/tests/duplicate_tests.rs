@@ -0,0 +1,441 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_hero, create_test_item,
+    create_test_location, create_test_tag, setup_test_db,
+};
+use entity::{attachments, characters, hero_items, locations};
+use loreweaver_lib::commands::attachment::list_entity_attachments_impl;
+use loreweaver_lib::commands::duplicates::{find_duplicate_candidates_impl, merge_entities_impl};
+use loreweaver_lib::commands::inventory::{get_hero_inventory_impl, grant_item_impl};
+use loreweaver_lib::commands::recall_card::{create_recall_card_impl, list_recall_cards_impl};
+use loreweaver_lib::commands::relationship::{
+    create_relationship_impl, get_entity_relationships_impl,
+};
+use loreweaver_lib::commands::tag::{add_entity_tag_impl, get_entity_tags_impl};
+use loreweaver_lib::commands::validation::CreateRecallCardInput;
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+#[tokio::test]
+async fn test_find_duplicate_candidates() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let anchor = create_test_location(&db, &campaign.id, "The Rusty Anchor", None)
+        .await
+        .expect("Failed to create location");
+    let anchor_copy = create_test_location(&db, &campaign.id, "rusty anchor.", None)
+        .await
+        .expect("Failed to create location");
+    let keep = create_test_location(&db, &campaign.id, "Stormkeep", None)
+        .await
+        .expect("Failed to create location");
+    let keep_typo = create_test_location(&db, &campaign.id, "Stormkep", None)
+        .await
+        .expect("Failed to create location");
+    create_test_location(&db, &campaign.id, "Whispering Woods", None)
+        .await
+        .expect("Failed to create location");
+
+    let candidates =
+        find_duplicate_candidates_impl(&db, campaign.id.clone(), "location".to_string(), None)
+            .await
+            .expect("Failed to find duplicates");
+
+    assert_eq!(candidates.len(), 2);
+    let exact = &candidates[0];
+    assert!(exact.exact);
+    assert_eq!(exact.similarity, 1.0);
+    let mut ids = [exact.first_id.clone(), exact.second_id.clone()];
+    ids.sort();
+    let mut expected = [anchor.id.clone(), anchor_copy.id.clone()];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    let fuzzy = &candidates[1];
+    assert!(!fuzzy.exact);
+    assert!(fuzzy.similarity >= 0.85 && fuzzy.similarity < 1.0);
+    assert!([&keep.id, &keep_typo.id].contains(&&fuzzy.first_id));
+    assert!([&keep.id, &keep_typo.id].contains(&&fuzzy.second_id));
+
+    // A stricter threshold keeps only the identical names
+    let strict =
+        find_duplicate_candidates_impl(&db, campaign.id.clone(), "location".to_string(), Some(1.0))
+            .await
+            .expect("Failed to find duplicates");
+    assert_eq!(strict.len(), 1);
+}
+
+#[tokio::test]
+async fn test_find_duplicate_candidates_rejects_unknown_type() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let err = find_duplicate_candidates_impl(&db, campaign.id.clone(), "tag".to_string(), None)
+        .await
+        .expect_err("Tags are not mergeable");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = find_duplicate_candidates_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        Some(1.5),
+    )
+    .await
+    .expect_err("Threshold out of range");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
+
+#[tokio::test]
+async fn test_merge_characters_moves_references() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let survivor = create_test_character(&db, &campaign.id, "Mira Vance")
+        .await
+        .expect("Failed to create character");
+    let duplicate = create_test_character(&db, &campaign.id, "Mira Vanse")
+        .await
+        .expect("Failed to create character");
+    let friend = create_test_character(&db, &campaign.id, "Tomas")
+        .await
+        .expect("Failed to create character");
+    let ally = create_test_tag(&db, &campaign.id, "Ally")
+        .await
+        .expect("Failed to create tag");
+    let noble = create_test_tag(&db, &campaign.id, "Noble")
+        .await
+        .expect("Failed to create tag");
+
+    for source in [&survivor, &duplicate] {
+        create_relationship_impl(
+            &db,
+            campaign.id.clone(),
+            "character".to_string(),
+            friend.id.clone(),
+            "character".to_string(),
+            source.id.clone(),
+            "friend".to_string(),
+            None,
+            Some(true),
+            None,
+        )
+        .await
+        .expect("Failed to create relationship");
+    }
+    // Merging turns this into a self-link, which is dropped
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        survivor.id.clone(),
+        "character".to_string(),
+        duplicate.id.clone(),
+        "sibling".to_string(),
+        None,
+        Some(true),
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+
+    add_entity_tag_impl(
+        &db,
+        ally.id.clone(),
+        "character".to_string(),
+        survivor.id.clone(),
+    )
+    .await
+    .expect("Failed to tag character");
+    add_entity_tag_impl(
+        &db,
+        ally.id.clone(),
+        "character".to_string(),
+        duplicate.id.clone(),
+    )
+    .await
+    .expect("Failed to tag character");
+    add_entity_tag_impl(
+        &db,
+        noble.id.clone(),
+        "character".to_string(),
+        duplicate.id.clone(),
+    )
+    .await
+    .expect("Failed to tag character");
+
+    let mut active: characters::ActiveModel = friend.clone().into();
+    active.description = Set(Some(format!(
+        "Owes a debt to [[character:{}:Mira Vanse]]",
+        duplicate.id
+    )));
+    active
+        .update(&db)
+        .await
+        .expect("Failed to update character");
+
+    let report = merge_entities_impl(
+        &db,
+        "character".to_string(),
+        duplicate.id.clone(),
+        survivor.id.clone(),
+    )
+    .await
+    .expect("Failed to merge characters");
+    assert_eq!(report.target_id, survivor.id);
+    assert_eq!(report.mentions_updated, 1);
+
+    assert!(characters::Entity::find_by_id(&duplicate.id)
+        .one(&db)
+        .await
+        .expect("Failed to query character")
+        .is_none());
+
+    let relationships =
+        get_entity_relationships_impl(&db, "character".to_string(), survivor.id.clone())
+            .await
+            .expect("Failed to get relationships");
+    assert_eq!(relationships.len(), 1);
+    assert_eq!(relationships[0].relationship_type, "friend");
+
+    let tags = get_entity_tags_impl(&db, "character".to_string(), survivor.id.clone())
+        .await
+        .expect("Failed to get tags");
+    let mut names: Vec<_> = tags.into_iter().map(|t| t.name).collect();
+    names.sort();
+    assert_eq!(names, vec!["Ally", "Noble"]);
+
+    let friend = characters::Entity::find_by_id(&friend.id)
+        .one(&db)
+        .await
+        .expect("Failed to query character")
+        .expect("Friend should exist");
+    let description = friend.description.expect("Description should be set");
+    assert!(description.contains(&survivor.id));
+    assert!(!description.contains(&duplicate.id));
+}
+
+#[tokio::test]
+async fn test_merge_moves_attachments_and_recall_cards() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let survivor = create_test_character(&db, &campaign.id, "Mira Vance")
+        .await
+        .expect("Failed to create character");
+    let duplicate = create_test_character(&db, &campaign.id, "Mira Vanse")
+        .await
+        .expect("Failed to create character");
+
+    attachments::ActiveModel {
+        id: Set("portrait".to_string()),
+        campaign_id: Set(campaign.id.clone()),
+        entity_type: Set("character".to_string()),
+        entity_id: Set(duplicate.id.clone()),
+        role: Set("portrait".to_string()),
+        file_name: Set("mira.png".to_string()),
+        mime_type: Set("image/png".to_string()),
+        size_bytes: Set(1),
+        data: Set(vec![1]),
+        content_hash: Set(None),
+        source: Set(None),
+        prompt: Set(None),
+        created_at: Set(chrono::Utc::now()),
+    }
+    .insert(&db)
+    .await
+    .expect("Failed to insert attachment");
+    create_recall_card_impl(
+        &db,
+        CreateRecallCardInput {
+            campaign_id: campaign.id.clone(),
+            front: "Who runs the harbor?".to_string(),
+            back: "Mira".to_string(),
+            entity_type: Some("character".to_string()),
+            entity_id: Some(duplicate.id.clone()),
+        },
+    )
+    .await
+    .expect("Failed to create recall card");
+
+    merge_entities_impl(
+        &db,
+        "character".to_string(),
+        duplicate.id.clone(),
+        survivor.id.clone(),
+    )
+    .await
+    .expect("Failed to merge characters");
+
+    let attachments =
+        list_entity_attachments_impl(&db, "character".to_string(), survivor.id.clone())
+            .await
+            .expect("Failed to list attachments");
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].id, "portrait");
+
+    let cards = list_recall_cards_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list recall cards");
+    assert_eq!(cards.len(), 1);
+    assert_eq!(cards[0].entity_id.as_deref(), Some(survivor.id.as_str()));
+}
+
+#[tokio::test]
+async fn test_merge_locations_reparents_children() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let region = create_test_location(&db, &campaign.id, "Northreach", None)
+        .await
+        .expect("Failed to create location");
+    let duplicate = create_test_location(&db, &campaign.id, "Stormkeep", Some(&region.id))
+        .await
+        .expect("Failed to create location");
+    let survivor = create_test_location(&db, &campaign.id, "Storm Keep", Some(&duplicate.id))
+        .await
+        .expect("Failed to create location");
+    let tavern = create_test_location(&db, &campaign.id, "The Gull", Some(&duplicate.id))
+        .await
+        .expect("Failed to create location");
+
+    merge_entities_impl(
+        &db,
+        "location".to_string(),
+        duplicate.id.clone(),
+        survivor.id.clone(),
+    )
+    .await
+    .expect("Failed to merge locations");
+
+    let survivor = locations::Entity::find_by_id(&survivor.id)
+        .one(&db)
+        .await
+        .expect("Failed to query location")
+        .expect("Survivor should exist");
+    assert_eq!(survivor.parent_id, Some(region.id.clone()));
+
+    let tavern = locations::Entity::find_by_id(&tavern.id)
+        .one(&db)
+        .await
+        .expect("Failed to query location")
+        .expect("Tavern should exist");
+    assert_eq!(tavern.parent_id, Some(survivor.id.clone()));
+}
+
+#[tokio::test]
+async fn test_merge_items_stacks_inventory() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let hero = create_test_hero(&db, &campaign.id, "Aria")
+        .await
+        .expect("Failed to create hero");
+    let survivor = create_test_item(&db, &campaign.id, "Healing Potion", false)
+        .await
+        .expect("Failed to create item");
+    let duplicate = create_test_item(&db, &campaign.id, "Potion of Healing", false)
+        .await
+        .expect("Failed to create item");
+
+    grant_item_impl(&db, hero.id.clone(), survivor.id.clone(), 2)
+        .await
+        .expect("Failed to grant item");
+    grant_item_impl(&db, hero.id.clone(), duplicate.id.clone(), 3)
+        .await
+        .expect("Failed to grant item");
+
+    merge_entities_impl(
+        &db,
+        "item".to_string(),
+        duplicate.id.clone(),
+        survivor.id.clone(),
+    )
+    .await
+    .expect("Failed to merge items");
+
+    let inventory = get_hero_inventory_impl(&db, hero.id.clone())
+        .await
+        .expect("Failed to get inventory");
+    assert_eq!(inventory.len(), 1);
+    assert_eq!(inventory[0].item_id, survivor.id);
+    assert_eq!(inventory[0].quantity, 5);
+    assert_eq!(
+        hero_items::Entity::find()
+            .all(&db)
+            .await
+            .expect("Failed to query holdings")
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_merge_entities_rejects_invalid_pairs() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other_campaign = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let other_mira = create_test_character(&db, &other_campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    let err = merge_entities_impl(
+        &db,
+        "character".to_string(),
+        mira.id.clone(),
+        mira.id.clone(),
+    )
+    .await
+    .expect_err("Cannot merge into itself");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = merge_entities_impl(
+        &db,
+        "character".to_string(),
+        other_mira.id.clone(),
+        mira.id.clone(),
+    )
+    .await
+    .expect_err("Cannot merge across campaigns");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = merge_entities_impl(
+        &db,
+        "character".to_string(),
+        "missing".to_string(),
+        mira.id.clone(),
+    )
+    .await
+    .expect_err("Source must exist");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
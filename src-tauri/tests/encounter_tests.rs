@@ -0,0 +1,255 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_location, create_test_session,
+    setup_test_db,
+};
+use loreweaver_lib::commands::encounter::{
+    create_encounter_entry_impl, create_encounter_table_impl, delete_encounter_table_impl,
+    get_encounter_table_impl, list_encounter_rolls_impl, list_encounter_tables_impl,
+    roll_encounter_impl, update_encounter_entry_impl, EncounterContext,
+};
+use loreweaver_lib::commands::validation::{CreateEncounterEntryInput, UpdateEncounterEntryInput};
+use loreweaver_lib::ErrorCode;
+
+fn entry_input(table_id: &str, name: &str) -> CreateEncounterEntryInput {
+    CreateEncounterEntryInput {
+        table_id: table_id.to_string(),
+        name: name.to_string(),
+        character_id: None,
+        quantity: None,
+        weight: None,
+        time_of_day: None,
+        season: None,
+        notes: None,
+    }
+}
+
+fn at(time_of_day: Option<&str>, season: Option<&str>) -> EncounterContext {
+    EncounterContext {
+        time_of_day: time_of_day.map(str::to_string),
+        season: season.map(str::to_string),
+        session_id: None,
+    }
+}
+
+#[tokio::test]
+async fn test_encounter_table_entries() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let forest = create_test_location(&db, &campaign.id, "Darkwood", None)
+        .await
+        .expect("Failed to create location");
+    let wolf = create_test_character(&db, &campaign.id, "Dire Wolf")
+        .await
+        .expect("Failed to create character");
+
+    let table = create_encounter_table_impl(&db, forest.id.clone(), "Forest".to_string(), None)
+        .await
+        .expect("Failed to create table");
+    assert_eq!(table.campaign_id, campaign.id);
+    assert!(table.entries.is_empty());
+
+    let mut input = entry_input(&table.id, "Dire wolves");
+    input.character_id = Some(wolf.id.clone());
+    input.quantity = Some("1d4+1".to_string());
+    input.weight = Some(3);
+    input.time_of_day = Some("night".to_string());
+    let wolves = create_encounter_entry_impl(&db, input)
+        .await
+        .expect("Failed to create entry");
+    assert_eq!(wolves.character_id.as_deref(), Some(wolf.id.as_str()));
+    create_encounter_entry_impl(&db, entry_input(&table.id, "Lost merchant"))
+        .await
+        .expect("Failed to create entry");
+
+    let table = get_encounter_table_impl(&db, table.id.clone())
+        .await
+        .expect("Failed to get table");
+    let names: Vec<&str> = table.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Dire wolves", "Lost merchant"]);
+    assert_eq!(table.entries[1].weight, 1);
+
+    // Empty strings clear optional fields
+    let updated = update_encounter_entry_impl(
+        &db,
+        wolves.id.clone(),
+        UpdateEncounterEntryInput {
+            time_of_day: Some(String::new()),
+            season: Some("winter".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update entry");
+    assert_eq!(updated.time_of_day, None);
+    assert_eq!(updated.season.as_deref(), Some("winter"));
+    assert_eq!(updated.quantity.as_deref(), Some("1d4+1"));
+
+    let tables = list_encounter_tables_impl(&db, campaign.id.clone(), Some(forest.id.clone()))
+        .await
+        .expect("Failed to list tables");
+    assert_eq!(tables.len(), 1);
+
+    assert!(delete_encounter_table_impl(&db, table.id.clone())
+        .await
+        .expect("Failed to delete table"));
+    let tables = list_encounter_tables_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to list tables");
+    assert!(tables.is_empty());
+}
+
+#[tokio::test]
+async fn test_roll_encounter_walks_up_to_region() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    let region = create_test_location(&db, &campaign.id, "The Marches", None)
+        .await
+        .expect("Failed to create location");
+    let town = create_test_location(&db, &campaign.id, "Millbrook", Some(&region.id))
+        .await
+        .expect("Failed to create location");
+
+    let wilds = create_encounter_table_impl(&db, region.id.clone(), "Wilds".to_string(), None)
+        .await
+        .expect("Failed to create table");
+    let mut input = entry_input(&wilds.id, "Goblins");
+    input.quantity = Some("3".to_string());
+    input.time_of_day = Some("night".to_string());
+    create_encounter_entry_impl(&db, input)
+        .await
+        .expect("Failed to create entry");
+
+    let streets = create_encounter_table_impl(&db, town.id.clone(), "Streets".to_string(), None)
+        .await
+        .expect("Failed to create table");
+    let mut input = entry_input(&streets.id, "Town watch");
+    input.time_of_day = Some("day".to_string());
+    create_encounter_entry_impl(&db, input)
+        .await
+        .expect("Failed to create entry");
+
+    // By day the town's own table fits
+    let result = roll_encounter_impl(&db, town.id.clone(), at(Some("day"), None))
+        .await
+        .expect("Failed to roll");
+    assert_eq!(result.location_id, town.id);
+    assert_eq!(result.result, "Town watch");
+    assert_eq!(result.count, None);
+    assert!(result.roll.is_none());
+
+    // At night nothing in town fits, so the region's table is used
+    let context = EncounterContext {
+        session_id: Some(session.id.clone()),
+        ..at(Some("night"), Some("autumn"))
+    };
+    let result = roll_encounter_impl(&db, town.id.clone(), context)
+        .await
+        .expect("Failed to roll");
+    assert_eq!(result.location_name, "The Marches");
+    assert_eq!(result.table_name, "Wilds");
+    assert_eq!(result.count, Some(3));
+    assert_eq!(result.result, "Goblins (3)");
+    let roll = result.roll.expect("Roll not logged");
+    assert_eq!(roll.session_id, session.id);
+    assert_eq!(roll.season.as_deref(), Some("autumn"));
+
+    let rolls = list_encounter_rolls_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to list rolls");
+    assert_eq!(rolls.len(), 1);
+    assert_eq!(rolls[0].entry_id, Some(result.entry.id.clone()));
+
+    // The log keeps the result when the table goes
+    delete_encounter_table_impl(&db, wilds.id.clone())
+        .await
+        .expect("Failed to delete table");
+    let rolls = list_encounter_rolls_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to list rolls");
+    assert_eq!(rolls[0].result, "Goblins (3)");
+    assert_eq!(rolls[0].table_id, None);
+
+    let err = roll_encounter_impl(&db, town.id.clone(), at(Some("night"), None))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_encounter_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let stranger = create_test_character(&db, &other.id, "Stranger")
+        .await
+        .expect("Failed to create character");
+    let elsewhere = create_test_session(&db, &other.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    let cave = create_test_location(&db, &campaign.id, "Cave", None)
+        .await
+        .expect("Failed to create location");
+    let table = create_encounter_table_impl(&db, cave.id.clone(), "Cave".to_string(), None)
+        .await
+        .expect("Failed to create table");
+
+    let mut input = entry_input(&table.id, "Bats");
+    input.quantity = Some("lots".to_string());
+    let err = create_encounter_entry_impl(&db, input).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let mut input = entry_input(&table.id, "Bats");
+    input.season = Some("monsoon".to_string());
+    let err = create_encounter_entry_impl(&db, input).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let mut input = entry_input(&table.id, "Bats");
+    input.weight = Some(0);
+    let err = create_encounter_entry_impl(&db, input).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let mut input = entry_input(&table.id, "Stranger");
+    input.character_id = Some(stranger.id.clone());
+    let err = create_encounter_entry_impl(&db, input).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    create_encounter_entry_impl(&db, entry_input(&table.id, "Bats"))
+        .await
+        .expect("Failed to create entry");
+    let err = roll_encounter_impl(&db, cave.id.clone(), at(Some("dusk"), None))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let context = EncounterContext {
+        session_id: Some(elsewhere.id.clone()),
+        ..Default::default()
+    };
+    let err = roll_encounter_impl(&db, cave.id.clone(), context)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = create_encounter_table_impl(&db, "nope".to_string(), "Table".to_string(), None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::entity_refs::{count_entities_impl, entities_exist_impl};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_count_entities() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    for name in ["Mira", "Tobin"] {
+        create_test_character(&db, &campaign.id, name)
+            .await
+            .expect("Failed to create character");
+    }
+    create_test_location(&db, &campaign.id, "Harbor", None)
+        .await
+        .expect("Failed to create location");
+    create_test_character(&db, &other.id, "Elsewhere")
+        .await
+        .expect("Failed to create character");
+
+    let counts = count_entities_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to count entities");
+    assert_eq!(counts.counts["character"], 2);
+    assert_eq!(counts.counts["location"], 1);
+    assert_eq!(counts.counts["quest"], 0);
+    assert_eq!(counts.total, 3);
+
+    let err = count_entities_impl(&db, "missing".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_entities_exist() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let stranger = create_test_character(&db, &other.id, "Stranger")
+        .await
+        .expect("Failed to create character");
+
+    let refs = vec![
+        ("character".to_string(), mira.id.clone()),
+        ("location".to_string(), mira.id.clone()),
+        ("character".to_string(), "missing".to_string()),
+        ("character".to_string(), stranger.id.clone()),
+    ];
+
+    let results = entities_exist_impl(&db, refs.clone(), None)
+        .await
+        .expect("Failed to check references");
+    let exists: Vec<bool> = results.iter().map(|r| r.exists).collect();
+    assert_eq!(exists, vec![true, false, false, true]);
+    assert_eq!(
+        results[0].campaign_id.as_deref(),
+        Some(campaign.id.as_str())
+    );
+    assert_eq!(results[1].entity_type, "location");
+
+    // Scoped to a campaign, the other campaign's character counts as missing
+    let results = entities_exist_impl(&db, refs, Some(campaign.id.clone()))
+        .await
+        .expect("Failed to check references");
+    let exists: Vec<bool> = results.iter().map(|r| r.exists).collect();
+    assert_eq!(exists, vec![true, false, false, false]);
+
+    let err = entities_exist_impl(&db, vec![("dragon".to_string(), mira.id)], None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use entity::{characters, locations};
+use loreweaver_lib::commands::find_replace::{find_and_replace_impl, FindReplaceOptions};
+use loreweaver_lib::commands::search::{search_entities_impl, SnippetOptions};
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+
+async fn set_character_description(db: &DatabaseConnection, id: &str, description: &str) {
+    let character = characters::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .expect("Failed to query character")
+        .expect("Character should exist");
+    let mut active: characters::ActiveModel = character.into();
+    active.description = Set(Some(description.to_string()));
+    active.update(db).await.expect("Failed to update character");
+}
+
+async fn character_description(db: &DatabaseConnection, id: &str) -> String {
+    characters::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .expect("Failed to query character")
+        .expect("Character should exist")
+        .description
+        .expect("Description should be set")
+}
+
+#[tokio::test]
+async fn test_find_and_replace_dry_run_previews_without_writing() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    create_test_character(&db, &campaign.id, "Tomas")
+        .await
+        .expect("Failed to create character");
+    set_character_description(&db, &mira.id, "Born in Duskhollow, left duskhollow young").await;
+
+    let report = find_and_replace_impl(
+        &db,
+        campaign.id.clone(),
+        "Duskhollow".to_string(),
+        "Emberfall".to_string(),
+        FindReplaceOptions {
+            dry_run: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to preview");
+
+    assert!(!report.applied);
+    assert_eq!(report.total_occurrences, 2);
+    assert_eq!(report.entities.len(), 1);
+    let entity = &report.entities[0];
+    assert_eq!(entity.entity_type, "character");
+    assert_eq!(entity.entity_id, mira.id);
+    assert_eq!(entity.name.as_deref(), Some("Mira"));
+    assert_eq!(entity.fields[0].field, "description");
+    assert_eq!(entity.fields[0].occurrences, 2);
+    assert_eq!(
+        entity.fields[0].after,
+        "Born in Emberfall, left duskhollow young"
+    );
+
+    assert_eq!(
+        character_description(&db, &mira.id).await,
+        "Born in Duskhollow, left duskhollow young"
+    );
+}
+
+#[tokio::test]
+async fn test_find_and_replace_applies_to_plain_and_rich_text() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let city = create_test_location(&db, &campaign.id, "Duskhollow", None)
+        .await
+        .expect("Failed to create location");
+
+    let doc = serde_json::json!({
+        "type": "doc",
+        "content": [{
+            "type": "paragraph",
+            "content": [
+                {"type": "text", "text": "Guards the gates of Duskhollow."},
+                {"type": "citation", "attrs": {"entityType": "location", "entityId": city.id}}
+            ]
+        }]
+    });
+    set_character_description(&db, &mira.id, &doc.to_string()).await;
+
+    let mut active: locations::ActiveModel = city.clone().into();
+    active.gm_notes = Set(Some("Duskhollow's walls are crumbling".to_string()));
+    active.update(&db).await.expect("Failed to update location");
+
+    let report = find_and_replace_impl(
+        &db,
+        campaign.id.clone(),
+        "duskhollow".to_string(),
+        "Emberfall".to_string(),
+        FindReplaceOptions {
+            include_names: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to replace");
+
+    assert!(report.applied);
+    assert_eq!(report.total_occurrences, 3);
+    assert_eq!(report.entities.len(), 2);
+
+    let description: serde_json::Value =
+        serde_json::from_str(&character_description(&db, &mira.id).await)
+            .expect("Description should stay valid JSON");
+    let paragraph = &description["content"][0]["content"];
+    assert_eq!(paragraph[0]["text"], "Guards the gates of Emberfall.");
+    assert_eq!(paragraph[1]["attrs"]["entityId"], city.id.as_str());
+
+    let city = locations::Entity::find_by_id(&city.id)
+        .one(&db)
+        .await
+        .expect("Failed to query location")
+        .expect("Location should exist");
+    assert_eq!(city.name, "Emberfall");
+    assert_eq!(
+        city.gm_notes.as_deref(),
+        Some("Emberfall's walls are crumbling")
+    );
+
+    // The search index follows the rename
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Emberfall".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
+    assert!(results.iter().any(|r| r.entity_id == city.id));
+}
+
+#[tokio::test]
+async fn test_find_and_replace_respects_options() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let city = create_test_location(&db, &campaign.id, "Ash", None)
+        .await
+        .expect("Failed to create location");
+    set_character_description(&db, &mira.id, "Ash falls on Ashford; ash everywhere").await;
+
+    find_and_replace_impl(
+        &db,
+        campaign.id.clone(),
+        "Ash".to_string(),
+        "Soot".to_string(),
+        FindReplaceOptions {
+            case_sensitive: true,
+            whole_word: true,
+            entity_types: vec!["character".to_string()],
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to replace");
+
+    assert_eq!(
+        character_description(&db, &mira.id).await,
+        "Soot falls on Ashford; ash everywhere"
+    );
+    // Names are left alone unless asked for, and locations weren't selected
+    let city = locations::Entity::find_by_id(&city.id)
+        .one(&db)
+        .await
+        .expect("Failed to query location")
+        .expect("Location should exist");
+    assert_eq!(city.name, "Ash");
+}
+
+#[tokio::test]
+async fn test_find_and_replace_validates_input() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let err = find_and_replace_impl(
+        &db,
+        campaign.id.clone(),
+        String::new(),
+        "Emberfall".to_string(),
+        FindReplaceOptions::default(),
+    )
+    .await
+    .expect_err("Empty search text should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = find_and_replace_impl(
+        &db,
+        campaign.id.clone(),
+        "Duskhollow".to_string(),
+        "Emberfall".to_string(),
+        FindReplaceOptions {
+            entity_types: vec!["dragon".to_string()],
+            ..Default::default()
+        },
+    )
+    .await
+    .expect_err("Unknown entity type should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_hero, create_test_location,
+    create_test_player, create_test_session, setup_test_db,
+};
+use entity::{characters, heroes, sessions};
+use loreweaver_lib::commands::gm_screen::get_gm_screen_impl;
+use loreweaver_lib::commands::quest::create_quest_impl;
+use loreweaver_lib::commands::scheduling::record_session_absence_impl;
+use loreweaver_lib::commands::validation::CreateQuestInput;
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, IntoActiveModel, Set};
+
+async fn create_quest(db: &DatabaseConnection, campaign_id: &str, name: &str, status: &str) {
+    create_quest_impl(
+        db,
+        CreateQuestInput {
+            name: name.to_string(),
+            campaign_id: campaign_id.to_string(),
+            plot_type: "main".to_string(),
+            status: status.to_string(),
+            description: None,
+            hook: None,
+            objectives: None,
+        },
+    )
+    .await
+    .expect("Failed to create quest");
+}
+
+#[tokio::test]
+async fn test_get_gm_screen() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let gull = create_test_location(&db, &campaign.id, "The Gull", None)
+        .await
+        .expect("Failed to create location");
+    let ogre = create_test_character(&db, &campaign.id, "Grukk")
+        .await
+        .expect("Failed to create character");
+    let mut active = ogre.into_active_model();
+    active.stat_block_json = Set(Some(
+        r#"{"system": "5e", "abilities": {"dex": 8}}"#.to_string(),
+    ));
+    let ogre: characters::Model = active.update(&db).await.expect("Failed to add stat block");
+    let scout = create_test_character(&db, &campaign.id, "Vess")
+        .await
+        .expect("Failed to create character");
+    let mut active = scout.into_active_model();
+    active.stat_block_json = Set(Some(r#"{"abilities": {"dex": 16}}"#.to_string()));
+    let scout: characters::Model = active.update(&db).await.expect("Failed to add stat block");
+
+    create_quest(&db, &campaign.id, "Find the Heir", "active").await;
+    create_quest(&db, &campaign.id, "Clear the Mine", "available").await;
+
+    let ana = create_test_player(&db, &campaign.id, "Ana")
+        .await
+        .expect("Failed to create player");
+    let ben = create_test_player(&db, &campaign.id, "Ben")
+        .await
+        .expect("Failed to create player");
+    for (hero_name, player) in [("Aria", &ana), ("Brom", &ben)] {
+        let hero = create_test_hero(&db, &campaign.id, hero_name)
+            .await
+            .expect("Failed to create hero");
+        let mut active: heroes::ActiveModel = hero.into();
+        active.player_id = Set(Some(player.id.clone()));
+        active.update(&db).await.expect("Failed to assign player");
+    }
+
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    record_session_absence_impl(&db, session.id.clone(), ben.id.clone(), None)
+        .await
+        .expect("Failed to record absence");
+    let plan = serde_json::json!({
+        "type": "doc",
+        "content": [
+            {"type": "heading", "attrs": {"level": 2}, "content": [{"type": "text", "text": "Ambush at the Gull"}]},
+            {"type": "paragraph", "content": [
+                {"type": "citation", "attrs": {"entityType": "character", "entityId": ogre.id, "label": "Grukk"}},
+                {"type": "text", "text": " waits in "},
+                {"type": "citation", "attrs": {"entityType": "location", "entityId": gull.id, "label": "The Gull"}}
+            ]},
+            {"type": "heading", "attrs": {"level": 2}, "content": [{"type": "text", "text": "Escape"}]},
+            {"type": "paragraph", "content": [
+                {"type": "text", "text": format!("[[character:{}:Vess]] shows the way", scout.id)}
+            ]},
+            {"type": "paragraph", "content": [{"type": "text", "text": "[ ] Bring the tunnel map"}]},
+            {"type": "heading", "attrs": {"level": 2}, "content": [{"type": "text", "text": "Prep checklist"}]},
+            {"type": "paragraph", "content": [{"type": "text", "text": "[x] Review the Gull"}]}
+        ]
+    });
+    let mut active: sessions::ActiveModel = session.into();
+    active.planned_content = Set(Some(plan.to_string()));
+    let session = active.update(&db).await.expect("Failed to save plan");
+
+    let screen = get_gm_screen_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to get GM screen");
+
+    let scenes: Vec<(&str, Vec<&str>)> = screen
+        .scenes
+        .iter()
+        .map(|s| {
+            (
+                s.title.as_str(),
+                s.entities.iter().map(|e| e.name.as_str()).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        scenes,
+        vec![
+            ("Ambush at the Gull", vec!["Grukk", "The Gull"]),
+            ("Escape", vec!["Vess"]),
+        ]
+    );
+    let pinned: Vec<&str> = screen.pinned.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(pinned, vec!["Grukk", "Vess", "The Gull"]);
+
+    let quests: Vec<&str> = screen
+        .active_quests
+        .iter()
+        .map(|q| q.name.as_str())
+        .collect();
+    assert_eq!(quests, vec!["Find the Heir"]);
+
+    assert_eq!(screen.party.len(), 2);
+    assert_eq!(screen.party[0].name, "Aria");
+    assert_eq!(screen.party[0].player_name.as_deref(), Some("Ana"));
+    assert!(!screen.party[0].absent);
+    assert!(screen.party[1].absent);
+
+    // Brom's player is away, so only Aria joins the order, after the NPCs
+    let order: Vec<(&str, Option<i32>)> = screen
+        .initiative
+        .iter()
+        .map(|e| (e.name.as_str(), e.modifier))
+        .collect();
+    assert_eq!(
+        order,
+        vec![("Vess", Some(3)), ("Grukk", Some(-1)), ("Aria", None)]
+    );
+
+    assert_eq!(screen.reminders, vec!["Bring the tunnel map"]);
+    assert_eq!(screen.clock.played_minutes, 0);
+    assert_eq!(screen.session.id, session.id);
+}
+
+#[tokio::test]
+async fn test_get_gm_screen_missing_session() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = get_gm_screen_impl(&db, "nope".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
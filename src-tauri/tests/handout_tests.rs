@@ -0,0 +1,167 @@
+mod common;
+
+use common::{create_test_campaign, create_test_player, setup_test_db};
+use loreweaver_lib::commands::handout::{
+    create_handout_impl, delete_handout_impl, list_handout_deliveries_impl,
+    list_player_handouts_impl, mark_handout_read_impl, revoke_handout_impl, send_handout_impl,
+    HandoutResponse,
+};
+use loreweaver_lib::commands::validation::CreateHandoutInput;
+use loreweaver_lib::ErrorCode;
+use sea_orm::DatabaseConnection;
+
+async fn create_handout(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    title: &str,
+) -> HandoutResponse {
+    create_handout_impl(
+        db,
+        CreateHandoutInput {
+            campaign_id: campaign_id.to_string(),
+            title: title.to_string(),
+            content: Some("Meet me at the old mill at midnight.".to_string()),
+            secret_id: None,
+        },
+    )
+    .await
+    .expect("Failed to create handout")
+}
+
+#[tokio::test]
+async fn test_send_and_read_handout() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let sam = create_test_player(&db, &campaign.id, "Sam")
+        .await
+        .expect("Failed to create player");
+    let alex = create_test_player(&db, &campaign.id, "Alex")
+        .await
+        .expect("Failed to create player");
+    let letter = create_handout(&db, &campaign.id, "Unsigned Letter").await;
+
+    let sent = send_handout_impl(&db, letter.id.clone(), vec![sam.id.clone()])
+        .await
+        .expect("Failed to send handout");
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].status, "sent");
+    assert!(sent[0].read_at.is_none());
+
+    let read = mark_handout_read_impl(&db, letter.id.clone(), sam.id.clone())
+        .await
+        .expect("Failed to mark handout read");
+    assert_eq!(read.status, "read");
+    assert!(read.read_at.is_some());
+
+    // Re-sending keeps Sam's read status and only adds Alex
+    send_handout_impl(
+        &db,
+        letter.id.clone(),
+        vec![sam.id.clone(), alex.id.clone()],
+    )
+    .await
+    .expect("Failed to send handout");
+
+    let deliveries = list_handout_deliveries_impl(&db, letter.id.clone())
+        .await
+        .expect("Failed to list deliveries");
+    let summary: Vec<(&str, &str)> = deliveries
+        .iter()
+        .map(|d| (d.player_name.as_str(), d.status.as_str()))
+        .collect();
+    assert_eq!(summary, vec![("Alex", "sent"), ("Sam", "read")]);
+
+    let sams = list_player_handouts_impl(&db, sam.id.clone())
+        .await
+        .expect("Failed to list player handouts");
+    assert_eq!(sams.len(), 1);
+    assert_eq!(sams[0].handout_title, "Unsigned Letter");
+}
+
+#[tokio::test]
+async fn test_mark_read_requires_delivery() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let sam = create_test_player(&db, &campaign.id, "Sam")
+        .await
+        .expect("Failed to create player");
+    let letter = create_handout(&db, &campaign.id, "Unsigned Letter").await;
+
+    let err = mark_handout_read_impl(&db, letter.id.clone(), sam.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_send_handout_to_player_in_other_campaign_fails() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let sam = create_test_player(&db, &campaign.id, "Sam")
+        .await
+        .expect("Failed to create player");
+    let stranger = create_test_player(&db, &other.id, "Stranger")
+        .await
+        .expect("Failed to create player");
+    let letter = create_handout(&db, &campaign.id, "Unsigned Letter").await;
+
+    let err = send_handout_impl(&db, letter.id.clone(), vec![sam.id.clone(), stranger.id])
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    // Nothing is sent when any recipient is rejected
+    let deliveries = list_handout_deliveries_impl(&db, letter.id.clone())
+        .await
+        .expect("Failed to list deliveries");
+    assert!(deliveries.is_empty());
+}
+
+#[tokio::test]
+async fn test_revoke_and_delete_handout() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let sam = create_test_player(&db, &campaign.id, "Sam")
+        .await
+        .expect("Failed to create player");
+    let letter = create_handout(&db, &campaign.id, "Unsigned Letter").await;
+    let map = create_handout(&db, &campaign.id, "Smuggler's Map").await;
+
+    send_handout_impl(&db, letter.id.clone(), vec![sam.id.clone()])
+        .await
+        .expect("Failed to send handout");
+    send_handout_impl(&db, map.id.clone(), vec![sam.id.clone()])
+        .await
+        .expect("Failed to send handout");
+
+    assert!(revoke_handout_impl(&db, letter.id.clone(), sam.id.clone())
+        .await
+        .expect("Failed to revoke handout"));
+    assert!(delete_handout_impl(&db, map.id.clone())
+        .await
+        .expect("Failed to delete handout"));
+
+    let sams = list_player_handouts_impl(&db, sam.id.clone())
+        .await
+        .expect("Failed to list player handouts");
+    assert!(sams.is_empty());
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::{create_test_campaign, setup_test_db};
+use loreweaver_lib::commands::character::get_character_impl;
+use loreweaver_lib::commands::inbox::{
+    convert_inbox_item_impl, create_inbox_item_impl, dismiss_inbox_item_impl, list_inbox_items_impl,
+};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_inbox_item_converts_to_entity() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let item = create_inbox_item_impl(
+        &db,
+        campaign.id.clone(),
+        "ai_suggestion".to_string(),
+        "  Old Tom  ".to_string(),
+        Some("Ferryman who knows the river".to_string()),
+        Some("character".to_string()),
+    )
+    .await
+    .expect("Failed to create inbox item");
+    assert_eq!(item.title, "Old Tom");
+
+    let conversion = convert_inbox_item_impl(&db, item.id.clone(), "character".to_string())
+        .await
+        .expect("Failed to convert inbox item");
+    assert_eq!(conversion.entity_type, "character");
+
+    let character = get_character_impl(&db, conversion.entity_id)
+        .await
+        .expect("Converted character exists");
+    assert_eq!(character.name, "Old Tom");
+    assert_eq!(
+        character.description.as_deref(),
+        Some("Ferryman who knows the river")
+    );
+
+    let inbox = list_inbox_items_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list inbox");
+    assert!(inbox.is_empty());
+}
+
+#[tokio::test]
+async fn test_inbox_item_dismiss_and_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let item = create_inbox_item_impl(
+        &db,
+        campaign.id.clone(),
+        "import".to_string(),
+        "Stub: Harrowdale".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create inbox item");
+
+    let err = convert_inbox_item_impl(&db, item.id.clone(), "session".to_string())
+        .await
+        .expect_err("Sessions can't be converted to");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    assert!(dismiss_inbox_item_impl(&db, item.id.clone())
+        .await
+        .expect("Failed to dismiss"));
+    assert!(!dismiss_inbox_item_impl(&db, item.id.clone())
+        .await
+        .expect("Failed to dismiss"));
+    let err = convert_inbox_item_impl(&db, item.id, "location".to_string())
+        .await
+        .expect_err("Dismissed items are gone");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = create_inbox_item_impl(
+        &db,
+        campaign.id.clone(),
+        "telepathy".to_string(),
+        "Idea".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect_err("Unknown source");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
@@ -0,0 +1,222 @@
+mod common;
+
+use common::{create_test_campaign, create_test_hero, create_test_item, setup_test_db};
+use loreweaver_lib::commands::inventory::{
+    find_item_holders_impl, get_hero_inventory_impl, grant_item_impl, remove_item_impl,
+    transfer_item_impl, update_hero_item_impl,
+};
+use loreweaver_lib::commands::item::delete_item_impl;
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_grant_item_stacks_quantity() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let hero = create_test_hero(&db, &campaign.id, "Aria")
+        .await
+        .expect("Failed to create hero");
+    let potion = create_test_item(&db, &campaign.id, "Potion of Healing", false)
+        .await
+        .expect("Failed to create item");
+
+    grant_item_impl(&db, hero.id.clone(), potion.id.clone(), 2)
+        .await
+        .expect("Failed to grant item");
+    let entry = grant_item_impl(&db, hero.id.clone(), potion.id.clone(), 3)
+        .await
+        .expect("Failed to grant item");
+
+    assert_eq!(entry.quantity, 5);
+    assert_eq!(entry.item_name, "Potion of Healing");
+
+    let inventory = get_hero_inventory_impl(&db, hero.id.clone())
+        .await
+        .expect("Failed to get inventory");
+    assert_eq!(inventory.len(), 1);
+}
+
+#[tokio::test]
+async fn test_remove_item_partial_and_full() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let hero = create_test_hero(&db, &campaign.id, "Aria")
+        .await
+        .expect("Failed to create hero");
+    let arrows = create_test_item(&db, &campaign.id, "Arrows", false)
+        .await
+        .expect("Failed to create item");
+    grant_item_impl(&db, hero.id.clone(), arrows.id.clone(), 20)
+        .await
+        .expect("Failed to grant item");
+
+    let remaining = remove_item_impl(&db, hero.id.clone(), arrows.id.clone(), Some(5))
+        .await
+        .expect("Failed to remove item");
+    assert_eq!(remaining.map(|e| e.quantity), Some(15));
+
+    let too_many = remove_item_impl(&db, hero.id.clone(), arrows.id.clone(), Some(16)).await;
+    assert_eq!(too_many.unwrap_err().code(), ErrorCode::Validation);
+
+    let remaining = remove_item_impl(&db, hero.id.clone(), arrows.id.clone(), None)
+        .await
+        .expect("Failed to remove item");
+    assert!(remaining.is_none());
+    assert!(get_hero_inventory_impl(&db, hero.id.clone())
+        .await
+        .expect("Failed to get inventory")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_transfer_item_between_heroes() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let aria = create_test_hero(&db, &campaign.id, "Aria")
+        .await
+        .expect("Failed to create hero");
+    let bram = create_test_hero(&db, &campaign.id, "Bram")
+        .await
+        .expect("Failed to create hero");
+    let shard = create_test_item(&db, &campaign.id, "Crown Shard", true)
+        .await
+        .expect("Failed to create item");
+
+    grant_item_impl(&db, aria.id.clone(), shard.id.clone(), 1)
+        .await
+        .expect("Failed to grant item");
+    update_hero_item_impl(
+        &db,
+        aria.id.clone(),
+        shard.id.clone(),
+        Some(true),
+        Some(true),
+    )
+    .await
+    .expect("Failed to attune item");
+
+    let entry = transfer_item_impl(
+        &db,
+        aria.id.clone(),
+        bram.id.clone(),
+        shard.id.clone(),
+        None,
+    )
+    .await
+    .expect("Failed to transfer item");
+    assert_eq!(entry.hero_id, bram.id);
+    assert!(!entry.attuned);
+    assert!(!entry.equipped);
+
+    let holders = find_item_holders_impl(&db, shard.id.clone())
+        .await
+        .expect("Failed to find holders");
+    assert_eq!(holders.len(), 1);
+    assert_eq!(holders[0].hero_name, "Bram");
+}
+
+#[tokio::test]
+async fn test_attunement_rules() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let hero = create_test_hero(&db, &campaign.id, "Aria")
+        .await
+        .expect("Failed to create hero");
+
+    let rope = create_test_item(&db, &campaign.id, "Rope", false)
+        .await
+        .expect("Failed to create item");
+    grant_item_impl(&db, hero.id.clone(), rope.id.clone(), 1)
+        .await
+        .expect("Failed to grant item");
+    let result =
+        update_hero_item_impl(&db, hero.id.clone(), rope.id.clone(), None, Some(true)).await;
+    assert_eq!(result.unwrap_err().code(), ErrorCode::Validation);
+
+    for name in ["Ring", "Cloak", "Amulet"] {
+        let item = create_test_item(&db, &campaign.id, name, true)
+            .await
+            .expect("Failed to create item");
+        grant_item_impl(&db, hero.id.clone(), item.id.clone(), 1)
+            .await
+            .expect("Failed to grant item");
+        update_hero_item_impl(&db, hero.id.clone(), item.id.clone(), None, Some(true))
+            .await
+            .expect("Failed to attune item");
+    }
+
+    let staff = create_test_item(&db, &campaign.id, "Staff", true)
+        .await
+        .expect("Failed to create item");
+    grant_item_impl(&db, hero.id.clone(), staff.id.clone(), 1)
+        .await
+        .expect("Failed to grant item");
+    let result =
+        update_hero_item_impl(&db, hero.id.clone(), staff.id.clone(), None, Some(true)).await;
+    assert_eq!(result.unwrap_err().code(), ErrorCode::Validation);
+}
+
+#[tokio::test]
+async fn test_deleting_item_clears_inventories() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let hero = create_test_hero(&db, &campaign.id, "Aria")
+        .await
+        .expect("Failed to create hero");
+    let shard = create_test_item(&db, &campaign.id, "Crown Shard", true)
+        .await
+        .expect("Failed to create item");
+    grant_item_impl(&db, hero.id.clone(), shard.id.clone(), 1)
+        .await
+        .expect("Failed to grant item");
+
+    delete_item_impl(&db, shard.id.clone())
+        .await
+        .expect("Failed to delete item");
+
+    assert!(get_hero_inventory_impl(&db, hero.id.clone())
+        .await
+        .expect("Failed to get inventory")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_grant_item_from_other_campaign_fails() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let hero = create_test_hero(&db, &campaign.id, "Aria")
+        .await
+        .expect("Failed to create hero");
+    let item = create_test_item(&db, &other.id, "Foreign Coin", false)
+        .await
+        .expect("Failed to create item");
+
+    let result = grant_item_impl(&db, hero.id.clone(), item.id.clone(), 1).await;
+    assert_eq!(result.unwrap_err().code(), ErrorCode::Validation);
+}
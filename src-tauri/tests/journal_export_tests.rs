@@ -0,0 +1,189 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::character::update_character_impl;
+use loreweaver_lib::commands::journal_export::{
+    export_homebrewery_impl, export_roll20_journal_impl,
+};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_export_roll20_journal() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let shire = create_test_location(&db, &campaign.id, "The Shire", None)
+        .await
+        .expect("Failed to create location");
+    let hobbiton = create_test_location(&db, &campaign.id, "Hobbiton", Some(&shire.id))
+        .await
+        .expect("Failed to create location");
+    let character = create_test_character(&db, &campaign.id, "Saruman")
+        .await
+        .expect("Failed to create character");
+    update_character_impl(
+        &db,
+        character.id.clone(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some("Secretly allied with Sauron".to_string()),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update character");
+
+    let export = export_roll20_journal_impl(
+        &db,
+        campaign.id.clone(),
+        vec![character.id.clone(), hobbiton.id.clone()],
+        "JSON".to_string(),
+        true,
+        None,
+    )
+    .await
+    .expect("Failed to export journal");
+    assert_eq!(export.format, "json");
+    assert_eq!(export.entity_count, 2);
+
+    let journal: serde_json::Value = serde_json::from_str(&export.content).unwrap();
+    let handouts = journal["handouts"].as_array().unwrap();
+    assert_eq!(handouts[0]["name"], "Saruman");
+    assert_eq!(handouts[0]["inplayerjournals"], "all");
+    assert_eq!(
+        handouts[0]["notes"],
+        "<p><em>Human · Adventurer</em></p><p>A test character</p>"
+    );
+    assert_eq!(
+        handouts[0]["gmnotes"],
+        "<h3>Secrets</h3><p>Secretly allied with Sauron</p>"
+    );
+    assert_eq!(handouts[1]["name"], "Hobbiton");
+    assert!(handouts[1]["notes"]
+        .as_str()
+        .unwrap()
+        .contains("Settlement · in The Shire"));
+
+    let path = std::env::temp_dir().join(format!("roll20-{}.html", uuid::Uuid::new_v4()));
+    let export = export_roll20_journal_impl(
+        &db,
+        campaign.id.clone(),
+        vec![character.id.clone()],
+        "html".to_string(),
+        false,
+        Some(path.to_string_lossy().to_string()),
+    )
+    .await
+    .expect("Failed to export journal");
+    let written = std::fs::read_to_string(&path).expect("Export not written");
+    assert_eq!(written, export.content);
+    assert!(written.contains("<h2>GM notes</h2>"));
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn test_export_roll20_journal_rejects_bad_input() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Saruman")
+        .await
+        .expect("Failed to create character");
+
+    let export = |ids: Vec<String>, format: &str| {
+        export_roll20_journal_impl(
+            &db,
+            campaign.id.clone(),
+            ids,
+            format.to_string(),
+            false,
+            None,
+        )
+    };
+
+    let err = export(vec![character.id.clone()], "pdf")
+        .await
+        .expect_err("Unknown format should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = export(Vec::new(), "json")
+        .await
+        .expect_err("Empty selection should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = export(vec!["missing".to_string()], "json")
+        .await
+        .expect_err("Unknown entity should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_export_homebrewery() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let bree = create_test_location(&db, &campaign.id, "Bree", None)
+        .await
+        .expect("Failed to create location");
+    let character = create_test_character(&db, &campaign.id, "Bill Ferny")
+        .await
+        .expect("Failed to create character");
+    update_character_impl(
+        &db,
+        character.id.clone(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some("Spies for Saruman".to_string()),
+        None,
+        Some(r#"{"system": "5e", "armor_class": 12, "hit_points": 9}"#.to_string()),
+    )
+    .await
+    .expect("Failed to update character");
+
+    let ids = vec![bree.id.clone(), character.id.clone()];
+    let export = export_homebrewery_impl(&db, campaign.id.clone(), ids.clone(), true, None)
+        .await
+        .expect("Failed to export document");
+    assert_eq!(export.format, "homebrewery");
+    assert_eq!(export.entity_count, 2);
+    let bree_at = export.content.find("## Bree").unwrap();
+    let ferny_at = export.content.find("## Bill Ferny").unwrap();
+    assert!(bree_at < ferny_at);
+    assert!(export
+        .content
+        .contains("{{descriptive\nA test location\n}}"));
+    assert!(export
+        .content
+        .contains("{{note\n##### GM notes\n\n###### Secrets\n\nSpies for Saruman\n}}"));
+    assert!(export.content.contains("{{monster,frame\n## Bill Ferny\n"));
+    assert!(export.content.contains("**Armor Class** :: 12"));
+
+    // A players' copy keeps descriptions but drops notes and stat blocks
+    let export = export_homebrewery_impl(&db, campaign.id.clone(), ids, false, None)
+        .await
+        .expect("Failed to export document");
+    assert!(export
+        .content
+        .contains("{{descriptive\nA test character\n}}"));
+    assert!(!export.content.contains("Saruman"));
+    assert!(!export.content.contains("{{monster"));
+}
@@ -0,0 +1,267 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_hero, create_test_location,
+    setup_test_db,
+};
+use loreweaver_lib::commands::kinship::get_family_tree_impl;
+use loreweaver_lib::commands::relationship::{
+    create_relationship_impl, delete_relationship_impl, list_relationships_impl,
+    update_relationship_impl, RelationshipResponse,
+};
+use loreweaver_lib::ErrorCode;
+use sea_orm::DatabaseConnection;
+
+async fn relate(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    source: (&str, &str),
+    relationship_type: &str,
+    target: (&str, &str),
+) -> RelationshipResponse {
+    create_relationship_impl(
+        db,
+        campaign_id.to_string(),
+        source.0.to_string(),
+        source.1.to_string(),
+        target.0.to_string(),
+        target.1.to_string(),
+        relationship_type.to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship")
+}
+
+#[tokio::test]
+async fn test_parent_of_maintains_inverse() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let queen = create_test_character(&db, &campaign.id, "Queen Alys")
+        .await
+        .expect("Failed to create character");
+    let prince = create_test_character(&db, &campaign.id, "Prince Edric")
+        .await
+        .expect("Failed to create character");
+
+    let rel = relate(
+        &db,
+        &campaign.id,
+        ("character", &queen.id),
+        "parent_of",
+        ("character", &prince.id),
+    )
+    .await;
+
+    let rels = list_relationships_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list relationships");
+    assert_eq!(rels.len(), 2);
+    let inverse = rels
+        .iter()
+        .find(|r| r.id != rel.id)
+        .expect("Inverse should exist");
+    assert_eq!(inverse.relationship_type, "child_of");
+    assert_eq!(inverse.source_id, prince.id);
+    assert_eq!(inverse.target_id, queen.id);
+
+    // Flipping the type flips the pair
+    update_relationship_impl(
+        &db,
+        rel.id.clone(),
+        Some("child_of".to_string()),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update relationship");
+    let rels = list_relationships_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list relationships");
+    assert_eq!(rels.len(), 2);
+    let inverse = rels
+        .iter()
+        .find(|r| r.id != rel.id)
+        .expect("Inverse should exist");
+    assert_eq!(inverse.relationship_type, "parent_of");
+    assert_eq!(inverse.source_id, prince.id);
+
+    assert!(delete_relationship_impl(&db, rel.id.clone())
+        .await
+        .expect("Failed to delete relationship"));
+    let rels = list_relationships_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list relationships");
+    assert!(rels.is_empty());
+}
+
+#[tokio::test]
+async fn test_symmetric_kinship_is_bidirectional() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let edric = create_test_character(&db, &campaign.id, "Edric")
+        .await
+        .expect("Failed to create character");
+    let aria = create_test_hero(&db, &campaign.id, "Aria")
+        .await
+        .expect("Failed to create hero");
+
+    let rel = relate(
+        &db,
+        &campaign.id,
+        ("character", &edric.id),
+        "married_to",
+        ("hero", &aria.id),
+    )
+    .await;
+    assert!(rel.is_bidirectional);
+
+    let rels = list_relationships_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list relationships");
+    assert_eq!(rels.len(), 1);
+}
+
+#[tokio::test]
+async fn test_kinship_ignores_non_people() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let region = create_test_location(&db, &campaign.id, "Northreach", None)
+        .await
+        .expect("Failed to create location");
+    let town = create_test_location(&db, &campaign.id, "Stormkeep", None)
+        .await
+        .expect("Failed to create location");
+
+    relate(
+        &db,
+        &campaign.id,
+        ("location", &region.id),
+        "parent_of",
+        ("location", &town.id),
+    )
+    .await;
+
+    let rels = list_relationships_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list relationships");
+    assert_eq!(rels.len(), 1);
+}
+
+#[tokio::test]
+async fn test_get_family_tree() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mut people = std::collections::HashMap::new();
+    for name in [
+        "Old King",
+        "Queen Alys",
+        "Lord Bram",
+        "Edric",
+        "Wife",
+        "Half Sister",
+        "Son",
+        "Grandson",
+    ] {
+        let character = create_test_character(&db, &campaign.id, name)
+            .await
+            .expect("Failed to create character");
+        people.insert(name, character.id);
+    }
+    let p = |name: &str| ("character", people[name].clone());
+
+    for (parent, child) in [
+        ("Old King", "Queen Alys"),
+        ("Queen Alys", "Edric"),
+        ("Queen Alys", "Half Sister"),
+        ("Edric", "Son"),
+        ("Son", "Grandson"),
+    ] {
+        let (pt, pid) = p(parent);
+        let (ct, cid) = p(child);
+        relate(&db, &campaign.id, (pt, &pid), "parent_of", (ct, &cid)).await;
+    }
+    // Stored child-first; the tree reads it the same way
+    let (et, eid) = p("Edric");
+    let (bt, bid) = p("Lord Bram");
+    relate(&db, &campaign.id, (et, &eid), "child_of", (bt, &bid)).await;
+    let (wt, wid) = p("Wife");
+    relate(&db, &campaign.id, (et, &eid), "married_to", (wt, &wid)).await;
+
+    let tree = get_family_tree_impl(&db, people["Edric"].clone(), Some(2))
+        .await
+        .expect("Failed to get family tree");
+
+    assert_eq!(tree.root.name, "Edric");
+    assert_eq!(tree.depth, 2);
+    assert_eq!(tree.spouses.len(), 1);
+    assert_eq!(tree.spouses[0].name, "Wife");
+    assert_eq!(tree.siblings.len(), 1);
+    assert_eq!(tree.siblings[0].name, "Half Sister");
+
+    let parents: Vec<_> = tree
+        .ancestors
+        .iter()
+        .map(|n| n.member.name.as_str())
+        .collect();
+    assert_eq!(parents, vec!["Lord Bram", "Queen Alys"]);
+    let alys = &tree.ancestors[1];
+    assert_eq!(alys.relatives.len(), 1);
+    assert_eq!(alys.relatives[0].member.name, "Old King");
+
+    // Depth 2 stops at the son's children
+    assert_eq!(tree.descendants.len(), 1);
+    let son = &tree.descendants[0];
+    assert_eq!(son.member.name, "Son");
+    assert_eq!(son.relatives.len(), 1);
+    assert_eq!(son.relatives[0].member.name, "Grandson");
+    assert!(son.relatives[0].relatives.is_empty());
+
+    let shallow = get_family_tree_impl(&db, people["Edric"].clone(), Some(1))
+        .await
+        .expect("Failed to get family tree");
+    assert!(shallow.descendants[0].relatives.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_family_tree_validates_input() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let edric = create_test_character(&db, &campaign.id, "Edric")
+        .await
+        .expect("Failed to create character");
+
+    let err = get_family_tree_impl(&db, edric.id.clone(), Some(0))
+        .await
+        .expect_err("Depth 0 should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = get_family_tree_impl(&db, "missing".to_string(), None)
+        .await
+        .expect_err("Unknown character should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
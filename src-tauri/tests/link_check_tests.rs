@@ -0,0 +1,151 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use entity::{characters, locations};
+use loreweaver_lib::commands::character::delete_character_impl;
+use loreweaver_lib::commands::link_check::{check_links_impl, LinkKind};
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+
+async fn set_description(db: &DatabaseConnection, id: &str, text: String) {
+    let row = locations::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .expect("Failed to load location")
+        .expect("Location missing");
+    let mut active: locations::ActiveModel = row.into();
+    active.description = Set(Some(text));
+    active.update(db).await.expect("Failed to set description");
+}
+
+async fn rename_character(db: &DatabaseConnection, id: &str, name: &str) {
+    let row = characters::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .expect("Failed to load character")
+        .expect("Character missing");
+    let mut active: characters::ActiveModel = row.into();
+    active.name = Set(name.to_string());
+    active.update(db).await.expect("Failed to rename character");
+}
+
+#[tokio::test]
+async fn test_check_links_finds_renamed_and_deleted_targets() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira Stone")
+        .await
+        .expect("Failed to create character");
+    let bob = create_test_character(&db, &campaign.id, "Bob")
+        .await
+        .expect("Failed to create character");
+    let keep = create_test_location(&db, &campaign.id, "The Keep", None)
+        .await
+        .expect("Failed to create location");
+    set_description(
+        &db,
+        &keep.id,
+        format!(
+            "[[Mira Stone]] rules here, [[The Keep]] is hers, and [[character:{}:Bob]] guards it.",
+            bob.id
+        ),
+    )
+    .await;
+
+    let report = check_links_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to check links");
+    assert_eq!(report.links_checked, 3);
+    assert!(report.dead_links.is_empty());
+
+    rename_character(&db, &mira.id, "Mira Stonebrook").await;
+    delete_character_impl(&db, bob.id.clone())
+        .await
+        .expect("Failed to delete character");
+
+    let report = check_links_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to check links");
+    assert_eq!(report.dead_links.len(), 2);
+    assert!(report
+        .dead_links
+        .iter()
+        .all(|d| d.entity_id == keep.id && d.field == "description"));
+
+    let renamed = report
+        .dead_links
+        .iter()
+        .find(|d| d.link == "Mira Stone")
+        .expect("Renamed link not reported");
+    assert_eq!(renamed.kind, LinkKind::WikiLink);
+    assert_eq!(renamed.entity_name.as_deref(), Some("The Keep"));
+    assert_eq!(renamed.deleted_type, None);
+    assert_eq!(renamed.suggestions[0].entity_id, mira.id);
+    assert_eq!(renamed.suggestions[0].name, "Mira Stonebrook");
+
+    let deleted = report
+        .dead_links
+        .iter()
+        .find(|d| d.link.ends_with(":Bob"))
+        .expect("Deleted link not reported");
+    assert_eq!(deleted.deleted_type.as_deref(), Some("character"));
+    assert!(deleted.suggestions.is_empty());
+}
+
+#[tokio::test]
+async fn test_check_links_reads_mentions_and_raw_ids() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let keep = create_test_location(&db, &campaign.id, "The Keep", None)
+        .await
+        .expect("Failed to create location");
+    let gone = uuid::Uuid::new_v4().to_string();
+    let doc = serde_json::json!({
+        "type": "doc",
+        "content": [{"type": "paragraph", "content": [
+            {"type": "mention", "attrs": {"entityType": "character", "entityId": mira.id, "label": "Mira"}},
+            {"type": "mention", "attrs": {"entityType": "character", "entityId": gone, "label": "Mirra"}},
+            {"type": "text", "text": format!(" and see {}", gone)}
+        ]}]
+    });
+    set_description(&db, &keep.id, doc.to_string()).await;
+
+    let report = check_links_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to check links");
+    assert_eq!(report.links_checked, 3);
+    let kinds: Vec<LinkKind> = report.dead_links.iter().map(|d| d.kind).collect();
+    assert_eq!(kinds.len(), 2);
+    assert!(kinds.contains(&LinkKind::Mention));
+    assert!(kinds.contains(&LinkKind::RawId));
+    assert!(report.dead_links.iter().all(|d| d.link == gone));
+
+    let mention = report
+        .dead_links
+        .iter()
+        .find(|d| d.kind == LinkKind::Mention)
+        .unwrap();
+    assert_eq!(mention.suggestions.len(), 1);
+    assert_eq!(mention.suggestions[0].entity_id, mira.id);
+}
+
+#[tokio::test]
+async fn test_check_links_missing_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = check_links_impl(&db, "nope".to_string()).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
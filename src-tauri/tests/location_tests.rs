@@ -5,7 +5,11 @@ use loreweaver_lib::commands::location::{
     create_location_impl, delete_location_impl, get_location_children_impl, get_location_impl,
     list_locations_impl, update_location_impl,
 };
-use loreweaver_lib::commands::validation::CreateLocationInput;
+use loreweaver_lib::commands::organization::create_organization_impl;
+use loreweaver_lib::commands::validation::{
+    CreateLocationInput, CreateOrganizationInput, UpdateLocationInput,
+};
+use loreweaver_lib::AppError;
 
 /// Helper to create a test location
 fn make_location_input(
@@ -21,6 +25,10 @@ fn make_location_input(
         location_type: location_type.to_string(),
         parent_id,
         description: description.map(|s| s.to_string()),
+        population: None,
+        government_type: None,
+        dominant_organization_id: None,
+        economy_notes: None,
     }
 }
 
@@ -312,11 +320,13 @@ async fn test_update_location() {
     let updated = update_location_impl(
         &db,
         created.id.clone(),
-        Some("Updated Name".to_string()),
-        Some("landmark".to_string()),
-        None,
-        Some("A mighty fortress".to_string()),
-        Some("Secret entrance behind waterfall".to_string()),
+        UpdateLocationInput {
+            name: Some("Updated Name".to_string()),
+            location_type: Some("landmark".to_string()),
+            description: Some("A mighty fortress".to_string()),
+            gm_notes: Some("Secret entrance behind waterfall".to_string()),
+            ..Default::default()
+        },
     )
     .await
     .expect("Failed to update location");
@@ -370,11 +380,10 @@ async fn test_update_location_reparent() {
     let reparented = update_location_impl(
         &db,
         child.id.clone(),
-        None,
-        None,
-        Some(parent2.id.clone()),
-        None,
-        None,
+        UpdateLocationInput {
+            parent_id: Some(parent2.id.clone()),
+            ..Default::default()
+        },
     )
     .await
     .expect("Failed to reparent");
@@ -450,11 +459,10 @@ async fn test_location_crud_lifecycle() {
     let updated = update_location_impl(
         &db,
         location.id.clone(),
-        Some("Updated Location".to_string()),
-        None,
-        None,
-        None,
-        None,
+        UpdateLocationInput {
+            name: Some("Updated Location".to_string()),
+            ..Default::default()
+        },
     )
     .await
     .expect("Update failed");
@@ -494,6 +502,10 @@ async fn test_create_location_validation_empty_name() {
         location_type: "settlement".to_string(),
         parent_id: None,
         description: None,
+        population: None,
+        government_type: None,
+        dominant_organization_id: None,
+        economy_notes: None,
     };
     let result = create_location_impl(&db, input).await;
 
@@ -517,6 +529,10 @@ async fn test_create_location_validation_invalid_type() {
         location_type: "invalid_type".to_string(), // Invalid type should fail
         parent_id: None,
         description: None,
+        population: None,
+        government_type: None,
+        dominant_organization_id: None,
+        economy_notes: None,
     };
     let result = create_location_impl(&db, input).await;
 
@@ -524,3 +540,121 @@ async fn test_create_location_validation_invalid_type() {
     let err = result.unwrap_err();
     assert!(err.to_string().contains("Validation"));
 }
+
+#[tokio::test]
+async fn test_create_location_invalid_type_reports_field() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let input = make_location_input(campaign.id.clone(), "Nowhere", "spaceship", None, None);
+    let err = create_location_impl(&db, input)
+        .await
+        .expect_err("Invalid location type should fail");
+
+    match err {
+        AppError::InvalidFields(fields) => {
+            assert_eq!(fields.len(), 1);
+            assert_eq!(fields[0].field, "location_type");
+            assert_eq!(fields[0].code, "invalid_location_type");
+        }
+        other => panic!("Expected field errors, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_location_settlement_fields() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let guild = create_organization_impl(
+        &db,
+        CreateOrganizationInput {
+            campaign_id: campaign.id.clone(),
+            name: "Merchant Guild".to_string(),
+            org_type: "guild".to_string(),
+            description: None,
+            goals: None,
+            resources: None,
+        },
+    )
+    .await
+    .expect("Failed to create organization");
+
+    let mut input = make_location_input(campaign.id.clone(), "Saltmarsh", "settlement", None, None);
+    input.population = Some(4200);
+    input.government_type = Some("Council".to_string());
+    input.dominant_organization_id = Some(guild.id.clone());
+    input.economy_notes = Some("Fishing and smuggling".to_string());
+    let created = create_location_impl(&db, input)
+        .await
+        .expect("Failed to create location");
+
+    assert_eq!(created.population, Some(4200));
+    assert_eq!(created.government_type, Some("Council".to_string()));
+    assert_eq!(created.dominant_organization_id, Some(guild.id.clone()));
+    assert_eq!(
+        created.economy_notes,
+        Some("Fishing and smuggling".to_string())
+    );
+
+    // Unset fields are left alone, an empty organization id clears it
+    let updated = update_location_impl(
+        &db,
+        created.id.clone(),
+        UpdateLocationInput {
+            population: Some(5000),
+            dominant_organization_id: Some(String::new()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update location");
+
+    assert_eq!(updated.population, Some(5000));
+    assert_eq!(updated.government_type, Some("Council".to_string()));
+    assert_eq!(updated.dominant_organization_id, None);
+}
+
+#[tokio::test]
+async fn test_location_rejects_foreign_organization() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let guild = create_organization_impl(
+        &db,
+        CreateOrganizationInput {
+            campaign_id: other.id.clone(),
+            name: "Thieves Guild".to_string(),
+            org_type: "guild".to_string(),
+            description: None,
+            goals: None,
+            resources: None,
+        },
+    )
+    .await
+    .expect("Failed to create organization");
+
+    let mut input = make_location_input(campaign.id.clone(), "Waterdeep", "settlement", None, None);
+    input.dominant_organization_id = Some(guild.id.clone());
+    let err = create_location_impl(&db, input)
+        .await
+        .expect_err("Organization from another campaign should fail");
+    assert!(matches!(err, AppError::Validation(_)));
+
+    let mut input = make_location_input(campaign.id.clone(), "Waterdeep", "settlement", None, None);
+    input.population = Some(-1);
+    assert!(create_location_impl(&db, input).await.is_err());
+}
@@ -0,0 +1,378 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_location, create_test_session,
+    setup_test_db,
+};
+use loreweaver_lib::commands::campaign::lock_campaign_impl;
+use loreweaver_lib::commands::maintenance::{
+    backup_database_impl, check_database_integrity_impl, get_db_info_impl,
+    get_migration_status_impl, optimize_database_impl, rebuild_search_index_impl,
+    rollback_last_migration_impl,
+};
+use loreweaver_lib::db::{app_version, migrate, record_app_version, recorded_app_version};
+use loreweaver_lib::{AppError, ErrorCode};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, Statement};
+
+/// Every search index row, as (entity_type, entity_id, name, content)
+async fn index_rows(db: &DatabaseConnection) -> Vec<(String, String, String, String)> {
+    let rows = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT entity_type, entity_id, name, content FROM search_index \
+             ORDER BY entity_type, entity_id",
+        ))
+        .await
+        .expect("Failed to read search index");
+    rows.iter()
+        .map(|row| {
+            (
+                row.try_get("", "entity_type").unwrap(),
+                row.try_get("", "entity_id").unwrap(),
+                row.try_get("", "name").unwrap(),
+                row.try_get("", "content").unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_integrity_check_healthy_database() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    create_test_location(&db, &campaign.id, "Rivendell", None)
+        .await
+        .expect("Failed to create location");
+
+    let report = check_database_integrity_impl(&db)
+        .await
+        .expect("Failed to check integrity");
+
+    assert!(report.ok);
+    assert_eq!(report.integrity_check, vec!["ok".to_string()]);
+    assert_eq!(report.foreign_key_violations, 0);
+    assert!(report.search_index_issues.is_empty());
+}
+
+#[tokio::test]
+async fn test_integrity_check_detects_search_index_drift() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    // Drop the character's index row and add one for an entity that doesn't exist
+    db.execute_unprepared(&format!(
+        "DELETE FROM search_index WHERE entity_id = '{}'",
+        character.id
+    ))
+    .await
+    .expect("Failed to delete index row");
+    db.execute_unprepared(&format!(
+        "INSERT INTO search_index(entity_type, entity_id, campaign_id, name, content) \
+         VALUES ('character', 'ghost', '{}', 'Ghost', '')",
+        campaign.id
+    ))
+    .await
+    .expect("Failed to insert orphan row");
+
+    let report = check_database_integrity_impl(&db)
+        .await
+        .expect("Failed to check integrity");
+
+    assert!(!report.ok);
+    assert_eq!(report.search_index_issues.len(), 1);
+    let issue = &report.search_index_issues[0];
+    assert_eq!(issue.entity_type, "character");
+    assert_eq!(issue.missing, 1);
+    assert_eq!(issue.orphaned, 1);
+}
+
+#[tokio::test]
+async fn test_integrity_check_reports_invalid_json_fields() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    // Simulate a row written before stat blocks were validated
+    db.execute_unprepared(&format!(
+        "UPDATE characters SET stat_block_json = 'hp: lots' WHERE id = '{}'",
+        character.id
+    ))
+    .await
+    .expect("Failed to write bad stat block");
+
+    let report = check_database_integrity_impl(&db)
+        .await
+        .expect("Failed to check integrity");
+
+    assert!(!report.ok);
+    assert_eq!(report.invalid_json_fields.len(), 1);
+    let field = &report.invalid_json_fields[0];
+    assert_eq!(field.table, "characters");
+    assert_eq!(field.column, "stat_block_json");
+    assert_eq!(field.row_id, character.id);
+}
+
+#[tokio::test]
+async fn test_optimize_database() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let report = optimize_database_impl(&db)
+        .await
+        .expect("Failed to optimize database");
+
+    assert!(report.size_before_bytes > 0);
+    assert!(report.size_after_bytes > 0);
+}
+
+#[tokio::test]
+async fn test_rebuild_search_index_repairs_drift() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    create_test_location(&db, &campaign.id, "Rivendell", None)
+        .await
+        .expect("Failed to create location");
+
+    db.execute_unprepared("DELETE FROM search_index")
+        .await
+        .expect("Failed to clear index");
+    db.execute_unprepared(&format!(
+        "INSERT INTO search_index(entity_type, entity_id, campaign_id, name, content) \
+         VALUES ('character', 'ghost', '{}', 'Ghost', '')",
+        campaign.id
+    ))
+    .await
+    .expect("Failed to insert orphan row");
+
+    let report = rebuild_search_index_impl(&db)
+        .await
+        .expect("Failed to rebuild index");
+    assert_eq!(report.indexed, 2);
+
+    let integrity = check_database_integrity_impl(&db)
+        .await
+        .expect("Failed to check integrity");
+    assert!(integrity.search_index_issues.is_empty());
+}
+
+#[tokio::test]
+async fn test_rebuild_search_index_with_a_locked_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    create_test_location(&db, &campaign.id, "Rivendell", None)
+        .await
+        .expect("Failed to create location");
+    create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    db.execute_unprepared(&format!(
+        "UPDATE characters SET aliases_json = '[\"Mithrandir\"]' WHERE id = '{}'",
+        character.id
+    ))
+    .await
+    .expect("Failed to add alias");
+    let indexed = index_rows(&db).await;
+
+    lock_campaign_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to lock campaign");
+    let report = rebuild_search_index_impl(&db)
+        .await
+        .expect("Failed to rebuild index");
+    assert_eq!(report.indexed, 3);
+    // The rebuild indexes rows the way the search triggers do
+    assert_eq!(index_rows(&db).await, indexed);
+}
+
+#[tokio::test]
+async fn test_backup_database() {
+    // VACUUM INTO from an in-memory database writes another in-memory one
+    let source = std::env::temp_dir().join(format!("source-{}.db", uuid::Uuid::new_v4()));
+    let db = Database::connect(format!("sqlite://{}?mode=rwc", source.display()))
+        .await
+        .expect("Failed to open test database");
+    Migrator::up(&db, None)
+        .await
+        .expect("Failed to run migrations");
+    create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let path = std::env::temp_dir().join(format!("backup-{}.db", uuid::Uuid::new_v4()));
+    let path = path.to_string_lossy().to_string();
+
+    let report = backup_database_impl(&db, path.clone())
+        .await
+        .expect("Failed to back up database");
+    assert!(report.size_bytes > 0);
+
+    // Never overwrite an existing file
+    let err = backup_database_impl(&db, path.clone())
+        .await
+        .expect_err("Backup should refuse to overwrite");
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    std::fs::remove_file(path).unwrap();
+    db.close().await.expect("Failed to close test database");
+    std::fs::remove_file(source).unwrap();
+}
+
+#[tokio::test]
+async fn test_migration_status_and_rollback() {
+    let source = std::env::temp_dir().join(format!("source-{}.db", uuid::Uuid::new_v4()));
+    let db = Database::connect(format!("sqlite://{}?mode=rwc", source.display()))
+        .await
+        .expect("Failed to open test database");
+    Migrator::up(&db, None)
+        .await
+        .expect("Failed to run migrations");
+
+    let status = get_migration_status_impl(&db)
+        .await
+        .expect("Failed to get migration status");
+    assert!(status.pending.is_empty());
+    assert_eq!(status.applied.len(), Migrator::migrations().len());
+    let last = status.applied.last().unwrap().clone();
+
+    let backup = std::env::temp_dir().join(format!("backup-{}.db", uuid::Uuid::new_v4()));
+    let backup = backup.to_string_lossy().to_string();
+
+    // Naming any migration but the last one is refused, and nothing is backed up
+    let err = rollback_last_migration_impl(&db, status.applied[0].clone(), backup.clone())
+        .await
+        .expect_err("Rollback should require the last migration's name");
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    assert!(!std::path::Path::new(&backup).exists());
+
+    let report = rollback_last_migration_impl(&db, last.clone(), backup.clone())
+        .await
+        .expect("Failed to roll back");
+    assert_eq!(report.rolled_back, last);
+    assert!(std::path::Path::new(&report.backup.path).exists());
+    assert_eq!(report.status.pending, vec![last.clone()]);
+    assert_eq!(report.status.applied.len(), status.applied.len() - 1);
+
+    // The next start re-applies it
+    Migrator::up(&db, None)
+        .await
+        .expect("Failed to re-run migrations");
+    let status = get_migration_status_impl(&db)
+        .await
+        .expect("Failed to get migration status");
+    assert!(status.pending.is_empty());
+
+    std::fs::remove_file(backup).unwrap();
+    db.close().await.expect("Failed to close test database");
+    std::fs::remove_file(source).unwrap();
+}
+
+#[tokio::test]
+async fn test_get_db_info() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    migrate(&db).await.expect("Failed to migrate");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let info = get_db_info_impl(&db, "campaigns.db".to_string())
+        .await
+        .expect("Failed to get database info");
+    assert_eq!(info.path, "campaigns.db");
+    assert!(info.size_bytes > 0);
+    assert_eq!(info.written_by.as_deref(), Some(app_version()));
+    assert_eq!(info.migrations_applied, Migrator::migrations().len());
+    assert_eq!(
+        info.schema_version.as_deref(),
+        Migrator::migrations().last().map(|m| m.name())
+    );
+    assert_eq!(info.entity_counts["campaign"], 1);
+    assert_eq!(info.entity_counts["character"], 1);
+    assert_eq!(info.entity_counts["location"], 0);
+}
+
+#[tokio::test]
+async fn test_refuses_database_from_newer_app() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    record_app_version(&db)
+        .await
+        .expect("Failed to record app version");
+    // What a newer app would leave behind
+    db.execute_unprepared(
+        "INSERT INTO seaql_migrations (version, applied_at) VALUES ('m20990101_000001_future', 0)",
+    )
+    .await
+    .unwrap();
+    db.execute_unprepared("UPDATE app_meta SET value = '99.0.0' WHERE key = 'app_version'")
+        .await
+        .unwrap();
+
+    let err = migrate(&db)
+        .await
+        .expect_err("A newer database should be refused");
+    assert_eq!(err.code(), ErrorCode::IncompatibleDatabase);
+    let AppError::IncompatibleDatabase(mismatch) = err else {
+        unreachable!()
+    };
+    assert_eq!(mismatch.written_by.as_deref(), Some("99.0.0"));
+    assert_eq!(mismatch.unknown_migrations, vec!["m20990101_000001_future"]);
+
+    let err = get_db_info_impl(&db, "campaigns.db".to_string())
+        .await
+        .expect_err("Info can't be read from a newer database");
+    assert_eq!(err.code(), ErrorCode::IncompatibleDatabase);
+
+    // Refusing leaves the recorded version alone
+    let recorded = recorded_app_version(&db)
+        .await
+        .expect("Failed to read app version");
+    assert_eq!(recorded.as_deref(), Some("99.0.0"));
+}
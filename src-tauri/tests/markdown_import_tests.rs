@@ -0,0 +1,196 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, setup_test_db};
+use entity::quests;
+use loreweaver_lib::commands::character::list_characters_impl;
+use loreweaver_lib::commands::location::list_locations_impl;
+use loreweaver_lib::commands::markdown_import::import_markdown_folder_impl;
+use loreweaver_lib::commands::relationship::get_entity_relationships_impl;
+use loreweaver_lib::commands::tag::get_entity_tags_impl;
+use loreweaver_lib::ErrorCode;
+use sea_orm::EntityTrait;
+use std::path::{Path, PathBuf};
+
+fn write_note(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    std::fs::create_dir_all(path.parent().unwrap()).expect("Failed to create folder");
+    std::fs::write(path, content).expect("Failed to write note");
+}
+
+/// A small vault: notes typed by frontmatter or by folder, a location
+/// listed before its parent, a note for an entity the campaign already has,
+/// and notes that can't be imported
+fn write_vault() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("vault-{}", uuid::Uuid::new_v4()));
+    write_note(
+        &root,
+        "Gandalf.md",
+        "---\ntype: character\n---\nA wizard, already in the campaign.\n",
+    );
+    write_note(
+        &root,
+        "Locations/Bree.md",
+        "---\nlocation_type: city\nparent: \"[[Middle-earth]]\"\n---\nA village at the crossroads.\n",
+    );
+    write_note(
+        &root,
+        "Locations/Middle-earth.md",
+        "---\nlocation_type: world\n---\n",
+    );
+    write_note(
+        &root,
+        "NPCs/Strider.md",
+        "---\ntags: [ranger, Ranger, \"#dunedain\"]\nrelationships:\n  - ally: \"[[Gandalf]]\"\n  - \"[[Bree]]\"\n  - enemy: \"[[Sauron]]\"\n---\n# Strider\nA ranger of the North.\n",
+    );
+    write_note(
+        &root,
+        "Quests/ring.md",
+        "---\nname: Destroy the Ring\nstatus: active\n---\nTake it to Mordor.\n",
+    );
+    write_note(&root, "Notes/todo.md", "Remember the snacks.\n");
+    write_note(
+        &root,
+        ".obsidian/workspace.md",
+        "---\ntype: character\n---\n",
+    );
+    root
+}
+
+#[tokio::test]
+async fn test_import_markdown_folder() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let gandalf = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let root = write_vault();
+    let path = root.to_string_lossy().to_string();
+
+    // A dry run reports the plan and writes nothing
+    let report = import_markdown_folder_impl(&db, campaign.id.clone(), path.clone(), true)
+        .await
+        .expect("Dry run failed");
+    assert!(!report.applied);
+    assert_eq!(report.files_scanned, 6);
+    let planned: Vec<_> = report
+        .entities
+        .iter()
+        .map(|e| (e.path.as_str(), e.entity_type.as_str(), e.status.as_str()))
+        .collect();
+    assert_eq!(
+        planned,
+        vec![
+            ("Gandalf.md", "character", "exists"),
+            ("Locations/Bree.md", "location", "create"),
+            ("Locations/Middle-earth.md", "location", "create"),
+            ("NPCs/Strider.md", "character", "create"),
+            ("Quests/ring.md", "quest", "create"),
+        ]
+    );
+    assert_eq!(report.tags, 2);
+    assert_eq!(report.relationships, 2);
+    let mut warned: Vec<_> = report.warnings.iter().map(|w| w.path.as_str()).collect();
+    warned.sort();
+    assert_eq!(
+        warned,
+        vec!["Locations/Bree.md", "NPCs/Strider.md", "Notes/todo.md"]
+    );
+    let characters = list_characters_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list characters");
+    assert_eq!(characters.len(), 1);
+
+    let report = import_markdown_folder_impl(&db, campaign.id.clone(), path, false)
+        .await
+        .expect("Import failed");
+    assert!(report.applied);
+    assert!(report
+        .entities
+        .iter()
+        .all(|e| e.entity_id.is_some() && e.status != "create"));
+
+    let locations = list_locations_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list locations");
+    let world = locations.iter().find(|l| l.name == "Middle-earth").unwrap();
+    let bree = locations.iter().find(|l| l.name == "Bree").unwrap();
+    assert_eq!(world.location_type, "world");
+    assert_eq!(bree.location_type, "settlement");
+    assert_eq!(bree.parent_id.as_ref(), Some(&world.id));
+    assert_eq!(
+        bree.description.as_deref(),
+        Some("A village at the crossroads.")
+    );
+
+    let quest_id = report
+        .entities
+        .iter()
+        .find(|e| e.entity_type == "quest")
+        .and_then(|e| e.entity_id.clone())
+        .unwrap();
+    let quest = quests::Entity::find_by_id(quest_id)
+        .one(&db)
+        .await
+        .expect("Failed to get quest")
+        .unwrap();
+    assert_eq!(quest.name, "Destroy the Ring");
+    assert_eq!(quest.status, "active");
+
+    let characters = list_characters_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list characters");
+    let strider = characters.iter().find(|c| c.name == "Strider").unwrap();
+    assert_eq!(
+        strider.description.as_deref(),
+        Some("A ranger of the North.")
+    );
+    let tags = get_entity_tags_impl(&db, "character".to_string(), strider.id.clone())
+        .await
+        .expect("Failed to get tags");
+    let tag_names: Vec<_> = tags.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(tag_names, vec!["dunedain", "ranger"]);
+
+    let relationships =
+        get_entity_relationships_impl(&db, "character".to_string(), strider.id.clone())
+            .await
+            .expect("Failed to get relationships");
+    assert_eq!(relationships.len(), 2);
+    assert!(relationships
+        .iter()
+        .any(|r| r.relationship_type == "ally" && r.target_id == gandalf.id));
+    assert!(relationships
+        .iter()
+        .any(|r| r.relationship_type == "related" && r.target_id == bree.id));
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[tokio::test]
+async fn test_import_markdown_folder_requires_folder() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let missing = std::env::temp_dir().join(format!("vault-{}", uuid::Uuid::new_v4()));
+    let err = import_markdown_folder_impl(
+        &db,
+        campaign.id.clone(),
+        missing.to_string_lossy().to_string(),
+        true,
+    )
+    .await
+    .expect_err("Missing folder should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = import_markdown_folder_impl(&db, "missing".to_string(), String::new(), true)
+        .await
+        .expect_err("Missing campaign should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
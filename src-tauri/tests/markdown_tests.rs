@@ -0,0 +1,128 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::campaign_settings::{
+    update_campaign_settings_impl, CampaignSettings, PlayerViewSettings,
+};
+use loreweaver_lib::commands::markdown::render_markdown_impl;
+use loreweaver_lib::markdown::RenderMode;
+use loreweaver_lib::ErrorCode;
+use sea_orm::ConnectionTrait;
+
+#[tokio::test]
+async fn test_render_markdown_modes() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Butterbur")
+        .await
+        .expect("Failed to create character");
+    let location = create_test_location(&db, &campaign.id, "Bree", None)
+        .await
+        .expect("Failed to create location");
+
+    // Players only see locations
+    update_campaign_settings_impl(
+        &db,
+        campaign.id.clone(),
+        CampaignSettings {
+            player_view: PlayerViewSettings {
+                shared_entity_types: vec!["location".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update settings");
+
+    let text = format!(
+        "[[Butterbur]] runs an inn in [[location:{}:the town]]. [[Weathertop]]\n\n\
+         ```gm\nHe knows more than he says.\n```\n",
+        location.id
+    );
+
+    let gm = render_markdown_impl(&db, campaign.id.clone(), text.clone(), RenderMode::Gm)
+        .await
+        .expect("Failed to render");
+    assert!(gm
+        .html
+        .contains(&format!("href=\"loreweaver://character/{}\"", character.id)));
+    assert!(gm
+        .html
+        .contains(&format!("href=\"loreweaver://location/{}\"", location.id)));
+    assert!(gm.html.contains("He knows more"));
+    assert_eq!(gm.unresolved_links, vec!["Weathertop"]);
+
+    let player = render_markdown_impl(&db, campaign.id.clone(), text, RenderMode::Player)
+        .await
+        .expect("Failed to render");
+    assert!(player.html.starts_with("<p>Butterbur runs an inn in <a "));
+    assert!(!player.html.contains("loreweaver://character"));
+    assert!(!player.html.contains("He knows more"));
+    assert!(player.unresolved_links.is_empty());
+}
+
+#[tokio::test]
+async fn test_render_markdown_player_links() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let alive = create_test_character(&db, &campaign.id, "Butterbur")
+        .await
+        .expect("Failed to create character");
+    let dead = create_test_character(&db, &campaign.id, "Bill Ferny")
+        .await
+        .expect("Failed to create character");
+    db.execute_unprepared(&format!(
+        "UPDATE characters SET is_alive = 0 WHERE id = '{}'",
+        dead.id
+    ))
+    .await
+    .expect("Failed to kill character");
+
+    update_campaign_settings_impl(
+        &db,
+        campaign.id.clone(),
+        CampaignSettings {
+            player_view: PlayerViewSettings {
+                hide_dead_characters: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update settings");
+
+    let player = render_markdown_impl(
+        &db,
+        campaign.id.clone(),
+        "[[Butterbur]] and [[Bill Ferny]]".to_string(),
+        RenderMode::Player,
+    )
+    .await
+    .expect("Failed to render");
+    assert!(player
+        .html
+        .contains(&format!("href=\"loreweaver://character/{}\"", alive.id)));
+    assert!(!player.html.contains(&dead.id));
+}
+
+#[tokio::test]
+async fn test_render_markdown_missing_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = render_markdown_impl(&db, "missing".to_string(), "Hi".to_string(), RenderMode::Gm)
+        .await
+        .expect_err("Rendering needs a campaign");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_tag, setup_test_db};
+use loreweaver_lib::commands::character::delete_character_impl;
+use loreweaver_lib::commands::mirror::{
+    get_mirror_settings_impl, mirror_campaign_impl, update_mirror_settings_impl,
+};
+use loreweaver_lib::commands::tag::add_entity_tag_impl;
+use loreweaver_lib::ErrorCode;
+use std::path::PathBuf;
+
+fn mirror_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("mirror-{}", uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_mirror_campaign_writes_one_file_per_entity() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let gandalf = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let boromir = create_test_character(&db, &campaign.id, "Boromir")
+        .await
+        .expect("Failed to create character");
+    let tag = create_test_tag(&db, &campaign.id, "wizard")
+        .await
+        .expect("Failed to create tag");
+    add_entity_tag_impl(
+        &db,
+        tag.id.clone(),
+        "character".to_string(),
+        gandalf.id.clone(),
+    )
+    .await
+    .expect("Failed to tag character");
+
+    let dir = mirror_dir();
+    update_mirror_settings_impl(
+        &db,
+        campaign.id.clone(),
+        true,
+        Some(dir.to_string_lossy().to_string()),
+        false,
+    )
+    .await
+    .expect("Failed to enable mirror");
+
+    let report = mirror_campaign_impl(&db, campaign.id.clone(), false)
+        .await
+        .expect("Failed to mirror campaign");
+    assert!(!report.up_to_date);
+    assert!(report.commit.is_none());
+    let gandalf_file = format!("characters/{}.json", gandalf.id);
+    let boromir_file = format!("characters/{}.json", boromir.id);
+    for file in [
+        "campaign.json",
+        gandalf_file.as_str(),
+        boromir_file.as_str(),
+        "entity_tags/rows.json",
+    ] {
+        assert!(report.written.iter().any(|w| w == file), "missing {}", file);
+        assert!(dir.join(file).is_file());
+    }
+    let row: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join(&gandalf_file)).unwrap()).unwrap();
+    assert_eq!(row["name"], "Gandalf");
+
+    // Nothing changed, so nothing is touched
+    let report = mirror_campaign_impl(&db, campaign.id.clone(), false)
+        .await
+        .expect("Failed to mirror campaign");
+    assert!(report.up_to_date);
+
+    // A forced run rewrites nothing because every file already matches
+    let report = mirror_campaign_impl(&db, campaign.id.clone(), true)
+        .await
+        .expect("Failed to mirror campaign");
+    assert!(!report.up_to_date);
+    assert!(report.written.is_empty());
+
+    delete_character_impl(&db, boromir.id.clone())
+        .await
+        .expect("Failed to delete character");
+    let report = mirror_campaign_impl(&db, campaign.id.clone(), false)
+        .await
+        .expect("Failed to mirror campaign");
+    assert_eq!(report.removed, vec![boromir_file.clone()]);
+    assert!(!dir.join(&boromir_file).exists());
+    assert!(dir.join(&gandalf_file).exists());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn test_mirror_campaign_auto_commits() {
+    if std::process::Command::new("git")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        return;
+    }
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let dir = mirror_dir();
+    update_mirror_settings_impl(
+        &db,
+        campaign.id.clone(),
+        true,
+        Some(dir.to_string_lossy().to_string()),
+        true,
+    )
+    .await
+    .expect("Failed to enable mirror");
+
+    let report = mirror_campaign_impl(&db, campaign.id.clone(), false)
+        .await
+        .expect("Failed to mirror campaign");
+    let commit = report.commit.expect("Expected a commit");
+    assert!(dir.join(".git").is_dir());
+
+    // A forced run with no file changes has nothing to commit
+    let report = mirror_campaign_impl(&db, campaign.id.clone(), true)
+        .await
+        .expect("Failed to mirror campaign");
+    assert!(report.commit.is_none());
+    assert!(!commit.is_empty());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn test_mirror_settings_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let settings = get_mirror_settings_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to get settings");
+    assert!(!settings.enabled);
+
+    let err = mirror_campaign_impl(&db, campaign.id.clone(), false)
+        .await
+        .expect_err("Disabled mirror should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = update_mirror_settings_impl(&db, campaign.id.clone(), true, Some(" ".into()), false)
+        .await
+        .expect_err("Enabling without a path should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = update_mirror_settings_impl(&db, "missing".to_string(), false, None, false)
+        .await
+        .expect_err("Missing campaign should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,310 @@
+mod common;
+
+use common::{create_test_campaign, create_test_organization, create_test_session, setup_test_db};
+use loreweaver_lib::commands::organization_goal::{
+    advance_organization_goal_impl, create_organization_goal_impl, delete_organization_goal_impl,
+    get_world_moves_on_impl, list_goal_advances_impl, list_organization_goals_impl,
+    update_organization_goal_impl,
+};
+use loreweaver_lib::commands::progress_clock::get_progress_clock_impl;
+use loreweaver_lib::commands::session_clock::{start_session_clock_impl, stop_session_clock_impl};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_organization_goal_crud() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let guild = create_test_organization(&db, &campaign.id, "Iron Guild")
+        .await
+        .expect("Failed to create organization");
+
+    let docks = create_organization_goal_impl(
+        &db,
+        guild.id.clone(),
+        "Seize the docks".to_string(),
+        None,
+        Some(6),
+    )
+    .await
+    .expect("Failed to create goal");
+    assert_eq!(docks.campaign_id, campaign.id);
+    assert_eq!(docks.status, "active");
+    let clock = docks.clock.as_ref().expect("Goal has no clock");
+    assert_eq!(clock.segments, 6);
+    assert_eq!(clock.entity_id.as_deref(), Some(guild.id.as_str()));
+
+    let bribe = create_organization_goal_impl(
+        &db,
+        guild.id.clone(),
+        "Bribe the magistrate".to_string(),
+        Some("Quietly".to_string()),
+        None,
+    )
+    .await
+    .expect("Failed to create goal");
+    assert!(bribe.clock.is_none());
+
+    let goals =
+        list_organization_goals_impl(&db, campaign.id.clone(), Some(guild.id.clone()), None)
+            .await
+            .expect("Failed to list goals");
+    let titles: Vec<&str> = goals.iter().map(|g| g.title.as_str()).collect();
+    assert_eq!(titles, vec!["Bribe the magistrate", "Seize the docks"]);
+
+    let bribe = update_organization_goal_impl(
+        &db,
+        bribe.id.clone(),
+        None,
+        Some(String::new()),
+        Some("abandoned".to_string()),
+    )
+    .await
+    .expect("Failed to update goal");
+    assert_eq!(bribe.status, "abandoned");
+    assert_eq!(bribe.description, None);
+    let advances = list_goal_advances_impl(&db, bribe.id.clone())
+        .await
+        .expect("Failed to list advances");
+    assert_eq!(advances.len(), 1);
+    assert_eq!(advances[0].status.as_deref(), Some("abandoned"));
+
+    let active =
+        list_organization_goals_impl(&db, campaign.id.clone(), None, Some("active".to_string()))
+            .await
+            .expect("Failed to list goals");
+    assert_eq!(active.len(), 1);
+
+    // The goal's clock goes with it
+    let clock_id = docks.clock.expect("Goal has no clock").id;
+    assert!(delete_organization_goal_impl(&db, docks.id.clone())
+        .await
+        .expect("Failed to delete goal"));
+    let err = get_progress_clock_impl(&db, clock_id).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    assert!(!delete_organization_goal_impl(&db, docks.id.clone())
+        .await
+        .expect("Failed to delete goal"));
+}
+
+#[tokio::test]
+async fn test_advance_goal_until_achieved() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let guild = create_test_organization(&db, &campaign.id, "Iron Guild")
+        .await
+        .expect("Failed to create organization");
+    let goal = create_organization_goal_impl(
+        &db,
+        guild.id.clone(),
+        "Seize the docks".to_string(),
+        None,
+        Some(4),
+    )
+    .await
+    .expect("Failed to create goal");
+
+    let goal = advance_organization_goal_impl(
+        &db,
+        goal.id.clone(),
+        3,
+        Some("Bought out the harbormaster".to_string()),
+    )
+    .await
+    .expect("Failed to advance goal");
+    assert_eq!(goal.clock.as_ref().map(|c| c.filled), Some(3));
+    assert_eq!(goal.status, "active");
+
+    let goal = advance_organization_goal_impl(&db, goal.id.clone(), 2, None)
+        .await
+        .expect("Failed to advance goal");
+    assert_eq!(goal.status, "achieved");
+    assert!(goal.clock.as_ref().is_some_and(|c| c.complete));
+
+    let advances = list_goal_advances_impl(&db, goal.id.clone())
+        .await
+        .expect("Failed to list advances");
+    assert_eq!(advances.len(), 2);
+    assert_eq!(
+        advances[0].note.as_deref(),
+        Some("Bought out the harbormaster")
+    );
+    assert_eq!(advances[1].filled, Some(4));
+    assert_eq!(advances[1].status.as_deref(), Some("achieved"));
+
+    let err = advance_organization_goal_impl(&db, goal.id.clone(), 1, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
+
+#[tokio::test]
+async fn test_world_moves_on_since_last_session() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let guild = create_test_organization(&db, &campaign.id, "Iron Guild")
+        .await
+        .expect("Failed to create organization");
+    let cult = create_test_organization(&db, &campaign.id, "Ashen Cult")
+        .await
+        .expect("Failed to create organization");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let docks = create_organization_goal_impl(
+        &db,
+        guild.id.clone(),
+        "Seize the docks".to_string(),
+        None,
+        Some(8),
+    )
+    .await
+    .expect("Failed to create goal");
+    let ritual = create_organization_goal_impl(
+        &db,
+        cult.id.clone(),
+        "Wake the sleeper".to_string(),
+        None,
+        Some(4),
+    )
+    .await
+    .expect("Failed to create goal");
+    let schism = create_organization_goal_impl(
+        &db,
+        cult.id.clone(),
+        "Heal the schism".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create goal");
+
+    // Progress during the session is not off-screen
+    advance_organization_goal_impl(&db, docks.id.clone(), 3, None)
+        .await
+        .expect("Failed to advance goal");
+    start_session_clock_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to start session");
+    stop_session_clock_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to stop session");
+
+    advance_organization_goal_impl(
+        &db,
+        docks.id.clone(),
+        2,
+        Some("Sank a rival barge".to_string()),
+    )
+    .await
+    .expect("Failed to advance goal");
+    advance_organization_goal_impl(&db, ritual.id.clone(), 4, None)
+        .await
+        .expect("Failed to advance goal");
+    update_organization_goal_impl(
+        &db,
+        schism.id.clone(),
+        None,
+        None,
+        Some("failed".to_string()),
+    )
+    .await
+    .expect("Failed to update goal");
+
+    let report = get_world_moves_on_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to get report");
+    assert_eq!(report.since_session_number, Some(1));
+    let names: Vec<&str> = report
+        .organizations
+        .iter()
+        .map(|o| o.organization_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Ashen Cult", "Iron Guild"]);
+
+    let docks_progress = &report.organizations[1].goals[0];
+    assert_eq!(docks_progress.ticks, 2);
+    assert_eq!(docks_progress.filled, Some(5));
+    assert_eq!(docks_progress.notes, vec!["Sank a rival barge"]);
+    assert_eq!(
+        report.lines,
+        vec![
+            "Ashen Cult failed Heal the schism",
+            "Ashen Cult achieved Wake the sleeper",
+            "Iron Guild: Seize the docks +2 (5/8)",
+        ]
+    );
+
+    // From the start of the campaign every advance counts
+    let report = get_world_moves_on_impl(
+        &db,
+        campaign.id.clone(),
+        Some("2000-01-01T00:00:00Z".to_string()),
+    )
+    .await
+    .expect("Failed to get report");
+    assert_eq!(report.since_session_number, None);
+    assert_eq!(report.organizations[1].goals[0].ticks, 5);
+
+    let err = get_world_moves_on_impl(&db, campaign.id.clone(), Some("last week".to_string()))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
+
+#[tokio::test]
+async fn test_organization_goal_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let guild = create_test_organization(&db, &campaign.id, "Iron Guild")
+        .await
+        .expect("Failed to create organization");
+
+    let err =
+        create_organization_goal_impl(&db, "nope".to_string(), "Goal".to_string(), None, None)
+            .await
+            .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    let err = create_organization_goal_impl(&db, guild.id.clone(), " ".to_string(), None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err =
+        create_organization_goal_impl(&db, guild.id.clone(), "Goal".to_string(), None, Some(1))
+            .await
+            .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let goal = create_organization_goal_impl(&db, guild.id.clone(), "Goal".to_string(), None, None)
+        .await
+        .expect("Failed to create goal");
+    let err = advance_organization_goal_impl(&db, goal.id.clone(), 1, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err =
+        update_organization_goal_impl(&db, goal.id.clone(), None, None, Some("won".to_string()))
+            .await
+            .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = update_organization_goal_impl(&db, "nope".to_string(), None, None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
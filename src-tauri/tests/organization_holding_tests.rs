@@ -0,0 +1,145 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_item, create_test_location, create_test_organization,
+    setup_test_db,
+};
+use loreweaver_lib::commands::location::delete_location_impl;
+use loreweaver_lib::commands::organization_holding::{
+    add_organization_holding_impl, list_organization_holdings_impl,
+    remove_organization_holding_impl,
+};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_list_organization_holdings() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let guild = create_test_organization(&db, &campaign.id, "Thieves' Guild")
+        .await
+        .expect("Failed to create organization");
+    let warehouse = create_test_location(&db, &campaign.id, "Dockside Warehouse", None)
+        .await
+        .expect("Failed to create location");
+    let tavern = create_test_location(&db, &campaign.id, "Broken Flagon", None)
+        .await
+        .expect("Failed to create location");
+    let ledger = create_test_item(&db, &campaign.id, "Black Ledger", false)
+        .await
+        .expect("Failed to create item");
+
+    for (holding_type, holding_id) in [
+        ("location", &warehouse.id),
+        ("item", &ledger.id),
+        ("location", &tavern.id),
+    ] {
+        add_organization_holding_impl(
+            &db,
+            guild.id.clone(),
+            holding_type.to_string(),
+            holding_id.clone(),
+            None,
+        )
+        .await
+        .expect("Failed to add holding");
+    }
+
+    // Adding the same holding again only updates its notes
+    let again = add_organization_holding_impl(
+        &db,
+        guild.id.clone(),
+        "location".to_string(),
+        tavern.id.clone(),
+        Some("Front for the fence".to_string()),
+    )
+    .await
+    .expect("Failed to re-add holding");
+    assert_eq!(again.notes.as_deref(), Some("Front for the fence"));
+
+    let holdings = list_organization_holdings_impl(&db, guild.id.clone())
+        .await
+        .expect("Failed to list holdings");
+    let names: Vec<_> = holdings
+        .iter()
+        .map(|h| (h.holding_type.as_str(), h.holding_name.as_str()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("location", "Broken Flagon"),
+            ("location", "Dockside Warehouse"),
+            ("item", "Black Ledger"),
+        ]
+    );
+
+    // Removing a holding, or deleting what was held, drops it from the list
+    assert!(
+        remove_organization_holding_impl(&db, holdings[2].id.clone())
+            .await
+            .expect("Failed to remove holding")
+    );
+    delete_location_impl(&db, warehouse.id.clone())
+        .await
+        .expect("Failed to delete location");
+    let holdings = list_organization_holdings_impl(&db, guild.id.clone())
+        .await
+        .expect("Failed to list holdings");
+    assert_eq!(holdings.len(), 1);
+    assert_eq!(holdings[0].holding_id, tavern.id);
+}
+
+#[tokio::test]
+async fn test_add_organization_holding_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let guild = create_test_organization(&db, &campaign.id, "Thieves' Guild")
+        .await
+        .expect("Failed to create organization");
+    let foreign = create_test_location(&db, &other.id, "Elsewhere", None)
+        .await
+        .expect("Failed to create location");
+
+    let err = add_organization_holding_impl(
+        &db,
+        guild.id.clone(),
+        "location".to_string(),
+        foreign.id.clone(),
+        None,
+    )
+    .await
+    .expect_err("Holding from another campaign should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = add_organization_holding_impl(
+        &db,
+        guild.id.clone(),
+        "quest".to_string(),
+        foreign.id.clone(),
+        None,
+    )
+    .await
+    .expect_err("Unsupported holding type should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = add_organization_holding_impl(
+        &db,
+        guild.id.clone(),
+        "item".to_string(),
+        "missing".to_string(),
+        None,
+    )
+    .await
+    .expect_err("Missing item should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,222 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_player, setup_test_db};
+use loreweaver_lib::commands::handout::{create_handout_impl, send_handout_impl};
+use loreweaver_lib::commands::player_knowledge::player_knowledge_report_impl;
+use loreweaver_lib::commands::relationship::{create_relationship_impl, update_relationship_impl};
+use loreweaver_lib::commands::secret::{create_secret_impl, update_secret_impl};
+use loreweaver_lib::commands::timeline::{create_timeline_event_impl, update_timeline_event_impl};
+use loreweaver_lib::commands::timeline_branch::create_timeline_branch_impl;
+use loreweaver_lib::commands::validation::CreateHandoutInput;
+use loreweaver_lib::ErrorCode;
+use sea_orm::DatabaseConnection;
+
+async fn create_secret(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    title: &str,
+    related_character_id: Option<&str>,
+    revealed_in_session: Option<Option<i32>>,
+) {
+    let secret = create_secret_impl(
+        db,
+        campaign_id.to_string(),
+        title.to_string(),
+        format!("{} details", title),
+        related_character_id.map(|_| "character".to_string()),
+        related_character_id.map(str::to_string),
+    )
+    .await
+    .expect("Failed to create secret");
+    if let Some(session) = revealed_in_session {
+        update_secret_impl(
+            db,
+            secret.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            session,
+        )
+        .await
+        .expect("Failed to reveal secret");
+    }
+}
+
+async fn create_event(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    title: &str,
+    sort_order: i64,
+    branch_id: Option<String>,
+) -> String {
+    create_timeline_event_impl(
+        db,
+        campaign_id.to_string(),
+        title.to_string(),
+        format!("Year {}", sort_order),
+        Some(sort_order),
+        None,
+        None,
+        branch_id,
+    )
+    .await
+    .expect("Failed to create timeline event")
+    .id
+}
+
+#[tokio::test]
+async fn test_player_knowledge_report() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let borin = create_test_character(&db, &campaign.id, "Borin")
+        .await
+        .expect("Failed to create character");
+    let zed = create_test_player(&db, &campaign.id, "Zed")
+        .await
+        .expect("Failed to create player");
+    let ann = create_test_player(&db, &campaign.id, "Ann")
+        .await
+        .expect("Failed to create player");
+
+    // Secrets: only revealed ones, by session, unnumbered last
+    create_secret(
+        &db,
+        &campaign.id,
+        "Hidden Heir",
+        Some(&mira.id),
+        Some(Some(2)),
+    )
+    .await;
+    create_secret(&db, &campaign.id, "Cursed Well", None, Some(Some(1))).await;
+    create_secret(&db, &campaign.id, "Old Rumor", None, Some(None)).await;
+    create_secret(&db, &campaign.id, "True Villain", None, None).await;
+
+    // Relationships: public ones only
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        mira.id.clone(),
+        "character".to_string(),
+        borin.id.clone(),
+        "ally".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    let rivalry = create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        borin.id.clone(),
+        "character".to_string(),
+        mira.id.clone(),
+        "rival".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    update_relationship_impl(&db, rivalry.id, None, None, None, None, Some(false))
+        .await
+        .expect("Failed to hide relationship");
+
+    // Timeline: public main-timeline events only
+    let founding = create_event(&db, &campaign.id, "Founding", 1, None).await;
+    let coup = create_event(&db, &campaign.id, "Secret Coup", 2, None).await;
+    update_timeline_event_impl(&db, coup, None, None, None, None, None, Some(false))
+        .await
+        .expect("Failed to hide event");
+    let branch = create_timeline_branch_impl(
+        &db,
+        campaign.id.clone(),
+        "What If".to_string(),
+        None,
+        None,
+        Some(founding.clone()),
+    )
+    .await
+    .expect("Failed to create branch");
+    create_event(&db, &campaign.id, "Alternate Founding", 3, Some(branch.id)).await;
+
+    // Handouts: delivered ones only
+    let letter = create_handout_impl(
+        &db,
+        CreateHandoutInput {
+            title: "Sealed Letter".to_string(),
+            campaign_id: campaign.id.clone(),
+            content: Some("Meet at dawn.".to_string()),
+            secret_id: None,
+        },
+    )
+    .await
+    .expect("Failed to create handout");
+    create_handout_impl(
+        &db,
+        CreateHandoutInput {
+            title: "Unsent Map".to_string(),
+            campaign_id: campaign.id.clone(),
+            content: None,
+            secret_id: None,
+        },
+    )
+    .await
+    .expect("Failed to create handout");
+    send_handout_impl(&db, letter.id.clone(), vec![zed.id.clone(), ann.id.clone()])
+        .await
+        .expect("Failed to send handout");
+
+    let report = player_knowledge_report_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to build report");
+
+    let secrets: Vec<_> = report.secrets.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(secrets, vec!["Cursed Well", "Hidden Heir", "Old Rumor"]);
+    assert_eq!(
+        report.secrets[1].related_entity_name.as_deref(),
+        Some("Mira")
+    );
+
+    assert_eq!(report.relationships.len(), 1);
+    assert_eq!(report.relationships[0].source_name.as_deref(), Some("Mira"));
+    assert_eq!(
+        report.relationships[0].target_name.as_deref(),
+        Some("Borin")
+    );
+
+    let events: Vec<_> = report
+        .timeline_events
+        .iter()
+        .map(|e| e.title.as_str())
+        .collect();
+    assert_eq!(events, vec!["Founding"]);
+
+    assert_eq!(report.handouts.len(), 1);
+    assert_eq!(report.handouts[0].title, "Sealed Letter");
+    assert_eq!(report.handouts[0].recipients, vec!["Ann", "Zed"]);
+}
+
+#[tokio::test]
+async fn test_player_knowledge_report_unknown_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = player_knowledge_report_impl(&db, "missing".to_string())
+        .await
+        .expect_err("Unknown campaign should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,228 @@
+mod common;
+
+use common::{create_test_campaign, create_test_session, setup_test_db};
+use entity::{secrets, sessions, timeline_events};
+use loreweaver_lib::commands::handout::list_handouts_impl;
+use loreweaver_lib::commands::player_recap::generate_player_recap_impl;
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+
+async fn create_event(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    title: &str,
+    sort_order: i64,
+    is_public: bool,
+) -> timeline_events::Model {
+    let now = chrono::Utc::now();
+    timeline_events::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id.to_string()),
+        date_display: Set(format!("Day {}", sort_order)),
+        sort_order: Set(sort_order),
+        title: Set(title.to_string()),
+        description: Set(None),
+        significance: Set("minor".to_string()),
+        is_public: Set(is_public),
+        branch_id: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(db)
+    .await
+    .expect("Failed to create timeline event")
+}
+
+async fn create_secret(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    title: &str,
+    revealed_in_session: Option<i32>,
+) -> secrets::Model {
+    let now = chrono::Utc::now();
+    secrets::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id.to_string()),
+        title: Set(title.to_string()),
+        content: Set(format!("{} is true", title)),
+        related_entity_type: Set(None),
+        related_entity_id: Set(None),
+        known_by: Set(None),
+        revealed: Set(revealed_in_session.is_some()),
+        revealed_in_session: Set(revealed_in_session),
+        prerequisites_json: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(db)
+    .await
+    .expect("Failed to create secret")
+}
+
+/// A session logged to the timeline with the given event
+async fn create_logged_session(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    number: i32,
+    event: &timeline_events::Model,
+    summary: Option<&str>,
+) -> sessions::Model {
+    let session = create_test_session(db, campaign_id, number, None)
+        .await
+        .expect("Failed to create session");
+    let mut active: sessions::ActiveModel = session.into();
+    active.title = Set(Some(format!("Chapter {}", number)));
+    active.summary = Set(summary.map(str::to_string));
+    active.timeline_event_id = Set(Some(event.id.clone()));
+    active.update(db).await.expect("Failed to update session")
+}
+
+#[tokio::test]
+async fn test_generate_player_recap_from_template() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    create_event(&db, &campaign.id, "Before it all", 5, true).await;
+    let first = create_event(&db, &campaign.id, "Session 1", 10, true).await;
+    create_event(&db, &campaign.id, "The bridge falls", 20, true).await;
+    create_event(&db, &campaign.id, "The duke schemes", 25, false).await;
+    let second = create_event(&db, &campaign.id, "Session 2", 30, true).await;
+    create_logged_session(&db, &campaign.id, 1, &first, None).await;
+    let session = create_logged_session(
+        &db,
+        &campaign.id,
+        2,
+        &second,
+        Some("The party crossed the river."),
+    )
+    .await;
+
+    create_secret(&db, &campaign.id, "The heir lives", Some(2)).await;
+    create_secret(&db, &campaign.id, "Old news", Some(1)).await;
+    create_secret(&db, &campaign.id, "Still hidden", None).await;
+
+    let recap = generate_player_recap_impl(&db, session.id.clone(), None, None)
+        .await
+        .expect("Failed to generate recap");
+
+    assert!(!recap.used_prose);
+    assert_eq!(recap.handout.title, "Recap: Session 2 - Chapter 2");
+    assert_eq!(
+        recap.handout.content.as_deref(),
+        Some(recap.markdown.as_str())
+    );
+    assert!(recap
+        .markdown
+        .starts_with("# Recap: Session 2 - Chapter 2\n\nThe party crossed the river.\n"));
+    assert!(recap
+        .markdown
+        .contains("- **The heir lives**: The heir lives is true"));
+    assert!(!recap.markdown.contains("Old news"));
+    assert!(!recap.markdown.contains("Still hidden"));
+    assert!(recap.markdown.contains("- **Day 20**: The bridge falls"));
+    assert!(recap.markdown.contains("- **Day 30**: Session 2"));
+    assert!(!recap.markdown.contains("The duke schemes"));
+    assert!(!recap.markdown.contains("Before it all"));
+}
+
+#[tokio::test]
+async fn test_generate_player_recap_strips_gm_blocks() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let event = create_event(&db, &campaign.id, "Session 1", 10, true).await;
+    let mut active: timeline_events::ActiveModel = event.into();
+    active.description = Set(Some(
+        "The bridge falls.\n\n```gm\nThe duke had it rigged.\n```\n".to_string(),
+    ));
+    let event = active.update(&db).await.expect("Failed to update event");
+    let session = create_logged_session(
+        &db,
+        &campaign.id,
+        1,
+        &event,
+        Some("The party crossed the river.\n\n```gm\nThey were followed.\n```\n"),
+    )
+    .await;
+    let secret = create_secret(&db, &campaign.id, "The heir lives", Some(1)).await;
+    let mut active: secrets::ActiveModel = secret.into();
+    active.content = Set("In Bree.\n\n```gm\nUnder a false name.\n```\n".to_string());
+    active.update(&db).await.expect("Failed to update secret");
+
+    let recap = generate_player_recap_impl(&db, session.id.clone(), None, None)
+        .await
+        .expect("Failed to generate recap");
+
+    assert!(recap.markdown.contains("The party crossed the river."));
+    assert!(recap.markdown.contains("- **The heir lives**: In Bree.\n"));
+    assert!(recap
+        .markdown
+        .contains("- **Day 10**: Session 1 - The bridge falls.\n"));
+    assert!(!recap.markdown.contains("followed"));
+    assert!(!recap.markdown.contains("false name"));
+    assert!(!recap.markdown.contains("rigged"));
+}
+
+#[tokio::test]
+async fn test_generate_player_recap_with_prose_overwrites_and_exports() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 4, None)
+        .await
+        .expect("Failed to create session");
+
+    let first = generate_player_recap_impl(&db, session.id.clone(), None, None)
+        .await
+        .expect("Failed to generate recap");
+    assert!(first.markdown.contains("Session 4 has no summary yet"));
+
+    let path = std::env::temp_dir()
+        .join(format!("recap-{}.md", uuid::Uuid::new_v4()))
+        .to_string_lossy()
+        .to_string();
+    let second = generate_player_recap_impl(
+        &db,
+        session.id.clone(),
+        Some("Our heroes, weary but alive, reached the keep.".to_string()),
+        Some(path.clone()),
+    )
+    .await
+    .expect("Failed to regenerate recap");
+
+    assert!(second.used_prose);
+    assert_eq!(second.handout.id, first.handout.id);
+    assert_eq!(
+        second.markdown,
+        "# Recap: Session 4\n\nOur heroes, weary but alive, reached the keep.\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("Failed to read export"),
+        second.markdown
+    );
+    let _ = std::fs::remove_file(&path);
+    let handouts = list_handouts_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list handouts");
+    assert_eq!(handouts.len(), 1);
+
+    let err = generate_player_recap_impl(&db, "nope".to_string(), None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use common::{create_test_campaign, create_test_item, create_test_location, setup_test_db};
+use loreweaver_lib::commands::price_list::{
+    create_price_impl, create_price_modifier_impl, delete_price_impl, get_local_price_impl,
+    list_price_modifiers_impl, list_prices_impl, update_price_impl, update_price_modifier_impl,
+};
+use loreweaver_lib::commands::validation::{CreatePriceInput, UpdatePriceInput};
+use loreweaver_lib::ErrorCode;
+
+fn price_input(campaign_id: &str, name: &str, category: &str, base_price: i32) -> CreatePriceInput {
+    CreatePriceInput {
+        campaign_id: campaign_id.to_string(),
+        name: name.to_string(),
+        category: category.to_string(),
+        base_price,
+        item_id: None,
+        notes: None,
+    }
+}
+
+#[tokio::test]
+async fn test_price_list_crud() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let sword = create_price_impl(
+        &db,
+        price_input(&campaign.id, "Longsword", " Weapons ", 1500),
+    )
+    .await
+    .expect("Failed to create price");
+    assert_eq!(sword.category, "weapons");
+    create_price_impl(&db, price_input(&campaign.id, "Ale", "food", 4))
+        .await
+        .expect("Failed to create price");
+    create_price_impl(&db, price_input(&campaign.id, "Dagger", "weapons", 200))
+        .await
+        .expect("Failed to create price");
+
+    let prices = list_prices_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to list prices");
+    let names: Vec<&str> = prices.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["Ale", "Dagger", "Longsword"]);
+    let weapons = list_prices_impl(&db, campaign.id.clone(), Some("WEAPONS".to_string()))
+        .await
+        .expect("Failed to list prices");
+    assert_eq!(weapons.len(), 2);
+
+    let sword = update_price_impl(
+        &db,
+        sword.id.clone(),
+        UpdatePriceInput {
+            base_price: Some(1800),
+            notes: Some("Dwarven steel".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update price");
+    assert_eq!(sword.base_price, 1800);
+    assert_eq!(sword.name, "Longsword");
+    assert_eq!(sword.notes.as_deref(), Some("Dwarven steel"));
+
+    assert!(delete_price_impl(&db, sword.id.clone())
+        .await
+        .expect("Failed to delete price"));
+    let prices = list_prices_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to list prices");
+    assert_eq!(prices.len(), 2);
+}
+
+#[tokio::test]
+async fn test_local_price_applies_regional_modifiers() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let kingdom = create_test_location(&db, &campaign.id, "Kingdom", None)
+        .await
+        .expect("Failed to create location");
+    let port = create_test_location(&db, &campaign.id, "Port Sable", Some(&kingdom.id))
+        .await
+        .expect("Failed to create location");
+    let item = create_test_item(&db, &campaign.id, "Longsword", false)
+        .await
+        .expect("Failed to create item");
+
+    let mut input = price_input(&campaign.id, "Longsword", "weapons", 1000);
+    input.item_id = Some(item.id.clone());
+    let sword = create_price_impl(&db, input)
+        .await
+        .expect("Failed to create price");
+    let ale = create_price_impl(&db, price_input(&campaign.id, "Ale", "food", 4))
+        .await
+        .expect("Failed to create price");
+
+    // War tax on everything in the kingdom, cheap blades at the port, and a
+    // run on ale that only touches ale
+    let tax = create_price_modifier_impl(
+        &db,
+        kingdom.id.clone(),
+        10,
+        None,
+        None,
+        Some("War tax".to_string()),
+    )
+    .await
+    .expect("Failed to create modifier");
+    create_price_modifier_impl(
+        &db,
+        port.id.clone(),
+        -20,
+        Some("Weapons".to_string()),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create modifier");
+    create_price_modifier_impl(&db, port.id.clone(), 50, None, Some(ale.id.clone()), None)
+        .await
+        .expect("Failed to create modifier");
+
+    let local = get_local_price_impl(&db, sword.id.clone(), port.id.clone())
+        .await
+        .expect("Failed to get local price");
+    assert_eq!(local.base_price, 1000);
+    assert_eq!(local.local_price, 880);
+    let percents: Vec<i32> = local.modifiers.iter().map(|m| m.percent).collect();
+    assert_eq!(percents, vec![-20, 10]);
+    assert_eq!(local.modifiers[1].location_name, "Kingdom");
+    assert_eq!(local.modifiers[1].reason.as_deref(), Some("War tax"));
+
+    // Looked up by the item instead of the price
+    let local = get_local_price_impl(&db, item.id.clone(), kingdom.id.clone())
+        .await
+        .expect("Failed to get local price");
+    assert_eq!(local.price.id, sword.id);
+    assert_eq!(local.local_price, 1100);
+
+    let local = get_local_price_impl(&db, ale.id.clone(), port.id.clone())
+        .await
+        .expect("Failed to get local price");
+    assert_eq!(local.local_price, 7);
+
+    update_price_modifier_impl(&db, tax.id.clone(), Some(0), None)
+        .await
+        .expect("Failed to update modifier");
+    let local = get_local_price_impl(&db, sword.id.clone(), port.id.clone())
+        .await
+        .expect("Failed to get local price");
+    assert_eq!(local.local_price, 800);
+
+    let at_port = list_price_modifiers_impl(&db, campaign.id.clone(), Some(port.id.clone()))
+        .await
+        .expect("Failed to list modifiers");
+    assert_eq!(at_port.len(), 2);
+    assert_eq!(at_port[0].category.as_deref(), Some("weapons"));
+}
+
+#[tokio::test]
+async fn test_price_list_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let town = create_test_location(&db, &campaign.id, "Town", None)
+        .await
+        .expect("Failed to create location");
+    let elsewhere = create_test_location(&db, &other.id, "Elsewhere", None)
+        .await
+        .expect("Failed to create location");
+    let foreign_item = create_test_item(&db, &other.id, "Relic", false)
+        .await
+        .expect("Failed to create item");
+
+    let err = create_price_impl(&db, price_input(&campaign.id, "Ale", "food", -1))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let mut input = price_input(&campaign.id, "Relic", "curios", 100);
+    input.item_id = Some(foreign_item.id.clone());
+    let err = create_price_impl(&db, input).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let ale = create_price_impl(&db, price_input(&campaign.id, "Ale", "food", 4))
+        .await
+        .expect("Failed to create price");
+    let err = create_price_modifier_impl(&db, town.id.clone(), -101, None, None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = create_price_modifier_impl(
+        &db,
+        town.id.clone(),
+        10,
+        Some("food".to_string()),
+        Some(ale.id.clone()),
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = create_price_modifier_impl(
+        &db,
+        elsewhere.id.clone(),
+        10,
+        None,
+        Some(ale.id.clone()),
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = get_local_price_impl(&db, ale.id.clone(), elsewhere.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    let err = get_local_price_impl(&db, "nope".to_string(), town.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use common::{create_test_campaign, create_test_organization, create_test_session, setup_test_db};
+use loreweaver_lib::commands::gm_screen::get_gm_screen_impl;
+use loreweaver_lib::commands::progress_clock::{
+    create_progress_clock_impl, delete_progress_clock_impl, get_progress_clock_impl,
+    list_active_progress_clocks_impl, list_progress_clocks_impl, reset_progress_clock_impl,
+    tick_progress_clock_impl, update_progress_clock_impl,
+};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_progress_clock_ticks_and_resets() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let guild = create_test_organization(&db, &campaign.id, "Thieves Guild")
+        .await
+        .expect("Failed to create organization");
+
+    let clock = create_progress_clock_impl(
+        &db,
+        campaign.id.clone(),
+        "Seize the docks".to_string(),
+        4,
+        None,
+        Some("organization".to_string()),
+        Some(guild.id.clone()),
+    )
+    .await
+    .expect("Failed to create clock");
+    assert_eq!(clock.filled, 0);
+    assert!(!clock.complete);
+    assert_eq!(clock.entity_name.as_deref(), Some("Thieves Guild"));
+
+    let clock = tick_progress_clock_impl(&db, clock.id.clone(), 3)
+        .await
+        .expect("Failed to tick clock");
+    assert_eq!(clock.filled, 3);
+    let clock = tick_progress_clock_impl(&db, clock.id.clone(), -1)
+        .await
+        .expect("Failed to untick clock");
+    assert_eq!(clock.filled, 2);
+
+    // Stops at full
+    let clock = tick_progress_clock_impl(&db, clock.id.clone(), 5)
+        .await
+        .expect("Failed to tick clock");
+    assert_eq!(clock.filled, 4);
+    assert!(clock.complete);
+
+    let clock = reset_progress_clock_impl(&db, clock.id.clone())
+        .await
+        .expect("Failed to reset clock");
+    assert_eq!(clock.filled, 0);
+
+    // Shrinking below the filled segments completes it
+    tick_progress_clock_impl(&db, clock.id.clone(), 3)
+        .await
+        .expect("Failed to tick clock");
+    let clock = update_progress_clock_impl(
+        &db,
+        clock.id.clone(),
+        Some("Own the docks".to_string()),
+        None,
+        Some(2),
+    )
+    .await
+    .expect("Failed to update clock");
+    assert_eq!(clock.name, "Own the docks");
+    assert_eq!((clock.segments, clock.filled), (2, 2));
+    assert!(clock.complete);
+
+    let listed = list_progress_clocks_impl(
+        &db,
+        campaign.id.clone(),
+        Some("organization".to_string()),
+        Some(guild.id.clone()),
+    )
+    .await
+    .expect("Failed to list clocks");
+    assert_eq!(listed.len(), 1);
+
+    let clock = get_progress_clock_impl(&db, clock.id.clone())
+        .await
+        .expect("Failed to get clock");
+    assert_eq!(clock.entity_id.as_deref(), Some(guild.id.as_str()));
+
+    assert!(delete_progress_clock_impl(&db, clock.id.clone())
+        .await
+        .expect("Failed to delete clock"));
+}
+
+#[tokio::test]
+async fn test_active_progress_clocks_on_gm_screen() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    for (name, segments, ticks) in [("Ritual", 8, 2), ("Storm", 4, 3), ("Done", 4, 4)] {
+        let clock = create_progress_clock_impl(
+            &db,
+            campaign.id.clone(),
+            name.to_string(),
+            segments,
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("Failed to create clock");
+        tick_progress_clock_impl(&db, clock.id.clone(), ticks)
+            .await
+            .expect("Failed to tick clock");
+    }
+
+    // Complete clocks drop out; the one nearest done comes first
+    let active = list_active_progress_clocks_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list active clocks");
+    let names: Vec<&str> = active.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["Storm", "Ritual"]);
+
+    let screen = get_gm_screen_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to get GM screen");
+    let names: Vec<&str> = screen
+        .progress_clocks
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Storm", "Ritual"]);
+}
+
+#[tokio::test]
+async fn test_progress_clock_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let elsewhere = create_test_organization(&db, &other.id, "Elsewhere")
+        .await
+        .expect("Failed to create organization");
+
+    let create = |segments: i32, entity_type: Option<&str>, entity_id: Option<&str>| {
+        create_progress_clock_impl(
+            &db,
+            campaign.id.clone(),
+            "Clock".to_string(),
+            segments,
+            None,
+            entity_type.map(str::to_string),
+            entity_id.map(str::to_string),
+        )
+    };
+
+    let err = create(1, None, None).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = create(4, Some("character"), Some("x")).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = create(4, Some("organization"), None).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = create(4, Some("organization"), Some(&elsewhere.id))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let clock = create(4, None, None).await.expect("Failed to create clock");
+    let err = tick_progress_clock_impl(&db, clock.id.clone(), 0)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    let err = tick_progress_clock_impl(&db, "nope".to_string(), 1)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use common::{create_test_campaign, setup_test_db};
+use loreweaver_lib::commands::prompt_template::{
+    create_prompt_template_impl, delete_prompt_template_impl, get_prompt_template_impl,
+    list_prompt_templates_impl, render_prompt_impl, update_prompt_template_impl,
+    PromptTemplateResponse,
+};
+use loreweaver_lib::commands::validation::{CreatePromptTemplateInput, UpdatePromptTemplateInput};
+use loreweaver_lib::ErrorCode;
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+
+async fn create_template(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    name: &str,
+    template: &str,
+    context_type: &str,
+) -> PromptTemplateResponse {
+    create_prompt_template_impl(
+        db,
+        CreatePromptTemplateInput {
+            name: name.to_string(),
+            campaign_id: campaign_id.to_string(),
+            template: template.to_string(),
+            context_type: context_type.to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create prompt template")
+}
+
+#[tokio::test]
+async fn test_prompt_template_crud() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let template = create_template(
+        &db,
+        &campaign.id,
+        "Secret motive",
+        "Give {{character}} a secret tied to {{faction}}.",
+        "character",
+    )
+    .await;
+    assert_eq!(template.variables, vec!["character", "faction"]);
+
+    let updated = update_prompt_template_impl(
+        &db,
+        template.id.clone(),
+        UpdatePromptTemplateInput {
+            name: None,
+            template: Some("Give {{character}} a secret.".to_string()),
+            context_type: None,
+        },
+    )
+    .await
+    .expect("Failed to update prompt template");
+    assert_eq!(updated.name, "Secret motive");
+    assert_eq!(updated.variables, vec!["character"]);
+
+    let fetched = get_prompt_template_impl(&db, template.id.clone())
+        .await
+        .expect("Failed to get prompt template");
+    assert_eq!(fetched.template, "Give {{character}} a secret.");
+
+    assert!(delete_prompt_template_impl(&db, template.id.clone())
+        .await
+        .expect("Failed to delete prompt template"));
+    let err = get_prompt_template_impl(&db, template.id)
+        .await
+        .expect_err("Deleted template should be gone");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_list_prompt_templates_by_context() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    create_template(&db, &campaign.id, "Tavern rumor", "A rumor.", "general").await;
+    create_template(&db, &campaign.id, "NPC quirk", "A quirk.", "character").await;
+    create_template(&db, &campaign.id, "Weather", "The weather.", "location").await;
+
+    let all = list_prompt_templates_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to list prompt templates");
+    assert_eq!(all.len(), 3);
+
+    let names: Vec<String> =
+        list_prompt_templates_impl(&db, campaign.id, Some("character".to_string()))
+            .await
+            .expect("Failed to list prompt templates")
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+    assert_eq!(names, vec!["NPC quirk", "Tavern rumor"]);
+}
+
+#[tokio::test]
+async fn test_render_prompt() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let template = create_template(
+        &db,
+        &campaign.id,
+        "Arrival",
+        "Describe {{ character }} arriving at {{location}}.",
+        "general",
+    )
+    .await;
+
+    let mut bindings = HashMap::new();
+    bindings.insert("character".to_string(), "Mira".to_string());
+    let err = render_prompt_impl(&db, template.id.clone(), bindings.clone())
+        .await
+        .expect_err("Missing bindings should be rejected");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    bindings.insert("location".to_string(), "the Salt Docks".to_string());
+    let rendered = render_prompt_impl(&db, template.id.clone(), bindings)
+        .await
+        .expect("Failed to render prompt");
+    assert_eq!(rendered.template_id, template.id);
+    assert_eq!(rendered.prompt, "Describe Mira arriving at the Salt Docks.");
+
+    let err = render_prompt_impl(&db, "missing".to_string(), HashMap::new())
+        .await
+        .expect_err("Unknown template should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_prompt_template_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let err = create_prompt_template_impl(
+        &db,
+        CreatePromptTemplateInput {
+            name: "Bad".to_string(),
+            campaign_id: campaign.id.clone(),
+            template: "Anything".to_string(),
+            context_type: "spaceship".to_string(),
+        },
+    )
+    .await
+    .expect_err("Unknown context type should be rejected");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = create_prompt_template_impl(
+        &db,
+        CreatePromptTemplateInput {
+            name: "Empty".to_string(),
+            campaign_id: campaign.id,
+            template: String::new(),
+            context_type: "general".to_string(),
+        },
+    )
+    .await
+    .expect_err("Empty template should be rejected");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
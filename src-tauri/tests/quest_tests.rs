@@ -0,0 +1,183 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, setup_test_db};
+use entity::sessions;
+use loreweaver_lib::commands::quest::{
+    create_quest_impl, get_quest_board_impl, QUEST_GIVER_RELATIONSHIP,
+};
+use loreweaver_lib::commands::relationship::create_relationship_impl;
+use loreweaver_lib::commands::validation::CreateQuestInput;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+
+fn quest_input(
+    campaign_id: &str,
+    name: &str,
+    status: &str,
+    objectives: Option<&str>,
+) -> CreateQuestInput {
+    CreateQuestInput {
+        campaign_id: campaign_id.to_string(),
+        name: name.to_string(),
+        plot_type: "side".to_string(),
+        status: status.to_string(),
+        description: None,
+        hook: None,
+        objectives: objectives.map(str::to_string),
+    }
+}
+
+async fn create_session(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    session_number: i32,
+    notes: Option<&str>,
+) -> sessions::Model {
+    let now = chrono::Utc::now();
+    sessions::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id.to_string()),
+        session_number: Set(session_number),
+        date: Set(None),
+        title: Set(Some(format!("Session {}", session_number))),
+        planned_content: Set(None),
+        notes: Set(notes.map(str::to_string)),
+        summary: Set(None),
+        highlights: Set(None),
+        started_at: Set(None),
+        ended_at: Set(None),
+        breaks_json: Set(None),
+        timeline_event_id: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(db)
+    .await
+    .expect("Failed to create session")
+}
+
+#[tokio::test]
+async fn test_quest_board_groups_by_status() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    create_quest_impl(
+        &db,
+        quest_input(&campaign.id, "Slay the Dragon", "active", None),
+    )
+    .await
+    .expect("Failed to create quest");
+    create_quest_impl(
+        &db,
+        quest_input(&campaign.id, "Find the Ring", "completed", None),
+    )
+    .await
+    .expect("Failed to create quest");
+
+    let board = get_quest_board_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to get quest board");
+
+    // Every status gets a column, even when empty
+    let statuses: Vec<&str> = board.columns.iter().map(|c| c.status.as_str()).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            "planned",
+            "available",
+            "active",
+            "completed",
+            "failed",
+            "abandoned"
+        ]
+    );
+
+    let active = &board.columns[2];
+    assert_eq!(active.quests.len(), 1);
+    assert_eq!(active.quests[0].name, "Slay the Dragon");
+    assert_eq!(board.columns[3].quests[0].name, "Find the Ring");
+    assert!(board.columns[0].quests.is_empty());
+}
+
+#[tokio::test]
+async fn test_quest_board_progress_givers_and_sessions() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let elrond = create_test_character(&db, &campaign.id, "Elrond")
+        .await
+        .expect("Failed to create character");
+
+    let quest = create_quest_impl(
+        &db,
+        quest_input(
+            &campaign.id,
+            "Destroy the Ring",
+            "active",
+            Some("- [x] Leave the Shire\n- [ ] Cross the mountains\n- [ ] Reach Mount Doom\n- [x] Form the fellowship"),
+        ),
+    )
+    .await
+    .expect("Failed to create quest");
+
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        elrond.id.clone(),
+        "quest".to_string(),
+        quest.id.clone(),
+        QUEST_GIVER_RELATIONSHIP.to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+
+    create_session(
+        &db,
+        &campaign.id,
+        1,
+        Some("The party set out to destroy the ring."),
+    )
+    .await;
+    create_session(&db, &campaign.id, 2, Some("Shopping in Bree.")).await;
+
+    let board = get_quest_board_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to get quest board");
+
+    let card = &board.columns[2].quests[0];
+    assert_eq!(card.objectives_total, 4);
+    assert_eq!(card.objectives_completed, 2);
+    assert_eq!(card.completion_percent, Some(50));
+    assert_eq!(card.giver_names, vec!["Elrond".to_string()]);
+
+    let last_session = card.last_session.as_ref().expect("Expected a session");
+    assert_eq!(last_session.session_number, 1);
+}
+
+#[tokio::test]
+async fn test_quest_board_empty_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let board = get_quest_board_impl(&db, campaign.id)
+        .await
+        .expect("Failed to get quest board");
+
+    assert!(board.columns.iter().all(|c| c.quests.is_empty()));
+}
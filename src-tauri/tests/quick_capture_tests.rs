@@ -0,0 +1,78 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, setup_test_db};
+use loreweaver_lib::commands::quick_capture::quick_capture_impl;
+use loreweaver_lib::commands::snippet::list_snippets_impl;
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_quick_capture_proposes_entities() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    let result = quick_capture_impl(
+        &db,
+        campaign.id.clone(),
+        "NPC: mira - owes the guild\n## Places\n- The Drowned Lantern\n".to_string(),
+    )
+    .await
+    .expect("Failed to capture");
+
+    assert!(result.note.is_none());
+    let proposals: Vec<_> = result
+        .proposals
+        .iter()
+        .map(|p| {
+            (
+                p.entity_type.as_str(),
+                p.name.as_str(),
+                p.existing_id.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        proposals,
+        vec![
+            ("character", "mira", Some(mira.id.clone())),
+            ("location", "The Drowned Lantern", None),
+        ]
+    );
+
+    // Nothing is created until the proposals are confirmed
+    let notes = list_snippets_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list snippets");
+    assert!(notes.is_empty());
+}
+
+#[tokio::test]
+async fn test_quick_capture_saves_plain_text_as_note() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let text = "Thunder rolls when the bell is rung.\nUse it in the finale.";
+    let result = quick_capture_impl(&db, campaign.id.clone(), text.to_string())
+        .await
+        .expect("Failed to capture");
+    let note = result.note.expect("Plain text becomes a note");
+    assert_eq!(note.name, "Thunder rolls when the bell is rung.");
+    assert_eq!(note.snippet_type, "other");
+    assert_eq!(note.content.as_deref(), Some(text));
+    assert!(result.proposals.is_empty());
+
+    let err = quick_capture_impl(&db, campaign.id.clone(), "   ".to_string())
+        .await
+        .expect_err("Empty captures are rejected");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
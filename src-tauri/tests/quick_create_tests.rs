@@ -0,0 +1,94 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, setup_test_db};
+use loreweaver_lib::commands::quick_create::parse_quick_create_impl;
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_parse_quick_create_plans_entities_and_relationships() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let frodo = create_test_character(&db, &campaign.id, "Frodo")
+        .await
+        .expect("Failed to create character");
+
+    let plan = parse_quick_create_impl(
+        &db,
+        campaign.id.clone(),
+        "npc Elara, elf herbalist in Hobbiton, ally of frodo; org Green Hands, healers guild"
+            .to_string(),
+    )
+    .await
+    .expect("Failed to parse");
+
+    let entities: Vec<_> = plan
+        .entities
+        .iter()
+        .map(|e| {
+            (
+                e.entity_type.as_str(),
+                e.name.as_str(),
+                e.existing_id.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entities,
+        vec![
+            ("character", "Elara", None),
+            ("location", "Hobbiton", None),
+            ("character", "frodo", Some(frodo.id.clone())),
+            ("organization", "Green Hands", None),
+        ]
+    );
+    assert_eq!(plan.entities[0].fields["lineage"], "elf");
+    assert_eq!(plan.entities[0].fields["occupation"], "herbalist");
+    assert_eq!(
+        plan.entities[3].description.as_deref(),
+        Some("healers guild")
+    );
+
+    let relationships: Vec<_> = plan
+        .relationships
+        .iter()
+        .map(|r| {
+            (
+                r.source_name.as_str(),
+                r.relationship_type.as_str(),
+                r.target_name.as_str(),
+                r.is_bidirectional,
+            )
+        })
+        .collect();
+    assert_eq!(
+        relationships,
+        vec![
+            ("Elara", "resides_in", "Hobbiton", false),
+            ("Elara", "ally", "frodo", true),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_parse_quick_create_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let err = parse_quick_create_impl(&db, campaign.id.clone(), " ; ".to_string())
+        .await
+        .expect_err("Input naming nothing is rejected");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = parse_quick_create_impl(&db, "missing".to_string(), "npc Elara".to_string())
+        .await
+        .expect_err("Unknown campaign");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{create_test_campaign, setup_test_db};
+use loreweaver_lib::commands::quick_note::capture_quick_note_impl;
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_capture_quick_note_files_into_campaign_inbox() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let active = create_test_campaign(&db, "Active")
+        .await
+        .expect("Failed to create campaign");
+    let recent = create_test_campaign(&db, "Recent")
+        .await
+        .expect("Failed to create campaign");
+
+    let note = capture_quick_note_impl(
+        &db,
+        Some(active.id.clone()),
+        "\n  The mayor is a doppelganger\nReveal at the festival\n".to_string(),
+    )
+    .await
+    .expect("Failed to capture note");
+    assert_eq!(note.campaign_id, active.id);
+    assert_eq!(note.source, "quick_note");
+    assert_eq!(note.title, "The mayor is a doppelganger");
+    assert_eq!(
+        note.content.as_deref(),
+        Some("The mayor is a doppelganger\nReveal at the festival")
+    );
+
+    // Without an open campaign the note goes to the last one worked on
+    let note = capture_quick_note_impl(&db, None, "Bandit ambush".to_string())
+        .await
+        .expect("Failed to capture note");
+    assert_eq!(note.campaign_id, recent.id);
+    let note = capture_quick_note_impl(&db, Some("deleted".to_string()), "Storm".to_string())
+        .await
+        .expect("Failed to capture note");
+    assert_eq!(note.campaign_id, recent.id);
+
+    let err = capture_quick_note_impl(&db, Some(active.id.clone()), " \n ".to_string())
+        .await
+        .expect_err("Empty notes are rejected");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
+
+#[tokio::test]
+async fn test_capture_quick_note_needs_a_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = capture_quick_note_impl(&db, None, "An idea".to_string())
+        .await
+        .expect_err("No campaign to file into");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use entity::characters;
+use loreweaver_lib::commands::character::delete_character_impl;
+use loreweaver_lib::commands::recall_card::{
+    create_recall_card_impl, delete_recall_card_impl, generate_recall_cards_impl,
+    list_due_recall_cards_impl, list_recall_cards_impl, review_recall_card_impl, DEFAULT_EASE,
+};
+use loreweaver_lib::commands::secret::create_secret_impl;
+use loreweaver_lib::commands::validation::CreateRecallCardInput;
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+
+async fn set_character_notes(
+    db: &DatabaseConnection,
+    id: &str,
+    motivations: Option<&str>,
+    secrets: Option<&str>,
+) {
+    let row = characters::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .expect("Failed to load character")
+        .expect("Character missing");
+    let mut active: characters::ActiveModel = row.into();
+    active.motivations = Set(motivations.map(str::to_string));
+    active.secrets = Set(secrets.map(str::to_string));
+    active.update(db).await.expect("Failed to update character");
+}
+
+fn card_input(campaign_id: &str, front: &str, back: &str) -> CreateRecallCardInput {
+    CreateRecallCardInput {
+        campaign_id: campaign_id.to_string(),
+        front: front.to_string(),
+        back: back.to_string(),
+        entity_type: None,
+        entity_id: None,
+    }
+}
+
+#[tokio::test]
+async fn test_generate_recall_cards() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    set_character_notes(
+        &db,
+        &mira.id,
+        Some("Avenge her brother"),
+        Some("She is the masked thief"),
+    )
+    .await;
+    // Nothing to remember about Bob beyond his description
+    create_test_character(&db, &campaign.id, "Bob")
+        .await
+        .expect("Failed to create character");
+    create_test_location(&db, &campaign.id, "The Keep", None)
+        .await
+        .expect("Failed to create location");
+    create_secret_impl(
+        &db,
+        campaign.id.clone(),
+        "The Crown".to_string(),
+        "The crown is a fake".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create secret");
+
+    let report = generate_recall_cards_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to generate cards");
+    assert_eq!(report.created, 4);
+    assert_eq!(report.refreshed, 0);
+
+    let cards = list_recall_cards_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list cards");
+    let hiding = cards
+        .iter()
+        .find(|c| c.front == "What is Mira hiding?")
+        .expect("Secrets card missing");
+    assert_eq!(hiding.back, "She is the masked thief");
+    assert_eq!(hiding.entity_id.as_deref(), Some(mira.id.as_str()));
+    assert_eq!(hiding.source_field.as_deref(), Some("secrets"));
+    assert!(cards.iter().any(|c| c.front == "What is The Keep like?"));
+    assert!(cards
+        .iter()
+        .any(|c| c.front == "What is the truth of \"The Crown\"?"));
+
+    // Running again only picks up edits
+    let report = generate_recall_cards_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to generate cards");
+    assert_eq!((report.created, report.refreshed), (0, 0));
+
+    review_recall_card_impl(&db, hiding.id.clone(), 5)
+        .await
+        .expect("Failed to review card");
+    set_character_notes(
+        &db,
+        &mira.id,
+        Some("Avenge her brother"),
+        Some("She is the masked thief and the queen's daughter"),
+    )
+    .await;
+    let report = generate_recall_cards_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to generate cards");
+    assert_eq!((report.created, report.refreshed), (0, 1));
+    let cards = list_recall_cards_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list cards");
+    let hiding = cards.iter().find(|c| c.id == hiding.id).unwrap();
+    assert_eq!(
+        hiding.back,
+        "She is the masked thief and the queen's daughter"
+    );
+    assert_eq!(hiding.repetitions, 1);
+
+    // Cards go with the entity they're about
+    delete_character_impl(&db, mira.id.clone())
+        .await
+        .expect("Failed to delete character");
+    let cards = list_recall_cards_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list cards");
+    assert_eq!(cards.len(), 2);
+
+    let err = generate_recall_cards_impl(&db, "nope".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_review_recall_card_schedules_next_review() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let card = create_recall_card_impl(
+        &db,
+        card_input(&campaign.id, "Who runs the docks?", "The Harbormaster"),
+    )
+    .await
+    .expect("Failed to create card");
+    let other = create_recall_card_impl(&db, card_input(&campaign.id, "What year is it?", "1042"))
+        .await
+        .expect("Failed to create card");
+    assert_eq!(card.ease_factor, f64::from(DEFAULT_EASE) / 1000.0);
+    assert_eq!(card.repetitions, 0);
+
+    let due = list_due_recall_cards_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to list due cards");
+    assert_eq!(due.len(), 2);
+
+    let reviewed = review_recall_card_impl(&db, card.id.clone(), 4)
+        .await
+        .expect("Failed to review card");
+    assert_eq!(reviewed.interval_days, 1);
+    assert_eq!(reviewed.repetitions, 1);
+    assert_eq!(reviewed.last_grade, Some(4));
+    assert!(reviewed.last_reviewed_at.is_some());
+    let reviewed = review_recall_card_impl(&db, card.id.clone(), 5)
+        .await
+        .expect("Failed to review card");
+    assert_eq!(reviewed.interval_days, 6);
+    assert_eq!(reviewed.ease_factor, 2.6);
+
+    let due = list_due_recall_cards_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to list due cards");
+    let due_ids: Vec<&str> = due.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(due_ids, vec![other.id.as_str()]);
+
+    // Forgetting starts it over
+    let forgot = review_recall_card_impl(&db, card.id.clone(), 0)
+        .await
+        .expect("Failed to review card");
+    assert_eq!(forgot.interval_days, 1);
+    assert_eq!(forgot.repetitions, 0);
+    assert_eq!(forgot.ease_factor, 2.6);
+
+    assert!(delete_recall_card_impl(&db, other.id.clone())
+        .await
+        .expect("Failed to delete card"));
+}
+
+#[tokio::test]
+async fn test_recall_card_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let card = create_recall_card_impl(&db, card_input(&campaign.id, "Front", "Back"))
+        .await
+        .expect("Failed to create card");
+
+    let err = review_recall_card_impl(&db, card.id.clone(), 6)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = review_recall_card_impl(&db, "nope".to_string(), 3)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = create_recall_card_impl(&db, card_input(&campaign.id, "", "Back"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let mut input = card_input(&campaign.id, "Front", "Back");
+    input.entity_type = Some("character".to_string());
+    let err = create_recall_card_impl(&db, input).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let mut input = card_input(&campaign.id, "Front", "Back");
+    input.entity_type = Some("dragon".to_string());
+    input.entity_id = Some(card.id.clone());
+    let err = create_recall_card_impl(&db, input).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
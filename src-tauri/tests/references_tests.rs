@@ -0,0 +1,167 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_organization, create_test_tag,
+    setup_test_db,
+};
+use loreweaver_lib::commands::annotation::{create_annotation_impl, list_open_annotations_impl};
+use loreweaver_lib::commands::progress_clock::{
+    create_progress_clock_impl, get_progress_clock_impl,
+};
+use loreweaver_lib::commands::recall_card::{create_recall_card_impl, list_recall_cards_impl};
+use loreweaver_lib::commands::references::repair_orphaned_references_impl;
+use loreweaver_lib::commands::relationship::{create_relationship_impl, list_relationships_impl};
+use loreweaver_lib::commands::tag::add_entity_tag_impl;
+use loreweaver_lib::commands::validation::{CreateAnnotationInput, CreateRecallCardInput};
+use sea_orm::{ConnectionTrait, Statement};
+
+#[tokio::test]
+async fn test_repair_orphaned_references() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let tobin = create_test_character(&db, &campaign.id, "Tobin")
+        .await
+        .expect("Failed to create character");
+    let guild = create_test_organization(&db, &campaign.id, "Dock Guild")
+        .await
+        .expect("Failed to create organization");
+    let tag = create_test_tag(&db, &campaign.id, "Suspect")
+        .await
+        .expect("Failed to create tag");
+
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        mira.id.clone(),
+        "character".to_string(),
+        tobin.id.clone(),
+        "ally".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    add_entity_tag_impl(
+        &db,
+        tag.id.clone(),
+        "character".to_string(),
+        tobin.id.clone(),
+    )
+    .await
+    .expect("Failed to add tag");
+    create_recall_card_impl(
+        &db,
+        CreateRecallCardInput {
+            campaign_id: campaign.id.clone(),
+            front: "Who sold out the guild?".to_string(),
+            back: "Tobin".to_string(),
+            entity_type: Some("character".to_string()),
+            entity_id: Some(tobin.id.clone()),
+        },
+    )
+    .await
+    .expect("Failed to create recall card");
+    create_annotation_impl(
+        &db,
+        CreateAnnotationInput {
+            entity_type: "character".to_string(),
+            entity_id: tobin.id.clone(),
+            field: None,
+            body: "Does Tobin survive the arc?".to_string(),
+            author: None,
+        },
+    )
+    .await
+    .expect("Failed to create annotation");
+    let clock = create_progress_clock_impl(
+        &db,
+        campaign.id.clone(),
+        "Seize the docks".to_string(),
+        4,
+        None,
+        Some("organization".to_string()),
+        Some(guild.id.clone()),
+    )
+    .await
+    .expect("Failed to create clock");
+
+    // Delete the row directly, the way older versions left references behind
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "DELETE FROM characters WHERE id = $1",
+        [tobin.id.clone().into()],
+    ))
+    .await
+    .expect("Failed to delete character");
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "DELETE FROM organizations WHERE id = $1",
+        [guild.id.clone().into()],
+    ))
+    .await
+    .expect("Failed to delete organization");
+
+    let found = repair_orphaned_references_impl(&db, true)
+        .await
+        .expect("Failed to check references");
+    assert_eq!(found.relationships, 1);
+    assert_eq!(found.entity_tags, 1);
+    assert_eq!(found.recall_cards, 1);
+    assert_eq!(found.annotations, 1);
+    assert_eq!(found.progress_clocks, 1);
+    assert!(!found.repaired);
+    assert_eq!(
+        list_relationships_impl(&db, campaign.id.clone())
+            .await
+            .expect("Failed to list relationships")
+            .len(),
+        1
+    );
+
+    let repaired = repair_orphaned_references_impl(&db, false)
+        .await
+        .expect("Failed to repair references");
+    assert!(repaired.repaired);
+    assert_eq!(repaired.relationships, 1);
+    assert_eq!(repaired.recall_cards, 1);
+    assert_eq!(repaired.annotations, 1);
+    assert_eq!(repaired.progress_clocks, 1);
+    assert!(list_relationships_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list relationships")
+        .is_empty());
+    assert!(list_recall_cards_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list recall cards")
+        .is_empty());
+    assert!(list_open_annotations_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list annotations")
+        .is_empty());
+    let clock = get_progress_clock_impl(&db, clock.id.clone())
+        .await
+        .expect("Repair should keep the clock");
+    assert!(clock.entity_type.is_none());
+    assert!(clock.entity_id.is_none());
+
+    let again = repair_orphaned_references_impl(&db, false)
+        .await
+        .expect("Failed to repair references");
+    assert_eq!(
+        again.relationships
+            + again.entity_tags
+            + again.recall_cards
+            + again.annotations
+            + again.progress_clocks,
+        0
+    );
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_location, create_test_tag,
+    setup_test_db,
+};
+use loreweaver_lib::commands::location::update_location_impl;
+use loreweaver_lib::commands::related_entities::get_related_entities_impl;
+use loreweaver_lib::commands::relationship::create_relationship_impl;
+use loreweaver_lib::commands::tag::add_entity_tag_impl;
+use loreweaver_lib::commands::validation::UpdateLocationInput;
+use loreweaver_lib::embeddings::EmbeddingProvider;
+use loreweaver_lib::{AppError, ErrorCode};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Embeds text as counts of a few topic words instead of calling a real
+/// model, and counts the texts it was asked for
+#[derive(Default)]
+struct FakeEmbeddings {
+    embedded: AtomicUsize,
+}
+
+impl EmbeddingProvider for FakeEmbeddings {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn model(&self) -> &str {
+        "topics"
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, AppError> {
+        self.embedded.fetch_add(texts.len(), Ordering::SeqCst);
+        let topics = [
+            ["vampire", "undead"],
+            ["altar", "priest"],
+            ["bread", "merchant"],
+        ];
+        Ok(texts
+            .iter()
+            .map(|text| {
+                let text = text.to_lowercase();
+                topics
+                    .iter()
+                    .map(|words| {
+                        words.iter().map(|w| text.matches(w).count()).sum::<usize>() as f32
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn test_related_entities_ranking() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let temple = create_test_location(&db, &campaign.id, "Old Temple", None)
+        .await
+        .expect("Failed to create location");
+    let shrine = create_test_location(&db, &campaign.id, "Sunken Shrine", None)
+        .await
+        .expect("Failed to create location");
+    let market = create_test_location(&db, &campaign.id, "Market Square", None)
+        .await
+        .expect("Failed to create location");
+    let priest = create_test_character(&db, &campaign.id, "Brother Ansel")
+        .await
+        .expect("Failed to create character");
+
+    for (id, description) in [
+        (
+            &temple.id,
+            "A drowned temple where vampire priests guard the sunken altar.",
+        ),
+        (
+            &shrine.id,
+            "Vampire priests once prayed at this drowned shrine and its altar.",
+        ),
+        (&market.id, "Merchants sell bread and cloth at dawn."),
+    ] {
+        update_location_impl(
+            &db,
+            id.clone(),
+            UpdateLocationInput {
+                description: Some(description.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to update location");
+    }
+
+    let undead = create_test_tag(&db, &campaign.id, "Undead")
+        .await
+        .expect("Failed to create tag");
+    for id in [&temple.id, &shrine.id] {
+        add_entity_tag_impl(&db, undead.id.clone(), "location".to_string(), id.clone())
+            .await
+            .expect("Failed to tag location");
+    }
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        priest.id.clone(),
+        "location".to_string(),
+        temple.id.clone(),
+        "serves_at".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+
+    let embeddings = FakeEmbeddings::default();
+    let related = get_related_entities_impl(
+        &db,
+        Some(&embeddings),
+        "location".to_string(),
+        temple.id.clone(),
+        None,
+    )
+    .await
+    .expect("Failed to get related entities");
+    let ids: Vec<&str> = related.iter().map(|r| r.entity_id.as_str()).collect();
+    // The market shares no topics, tags or relationships with the temple
+    assert_eq!(ids, vec![shrine.id.as_str(), priest.id.as_str()]);
+
+    assert!(related[0].text_similarity.is_some_and(|s| s > 0.0));
+    assert_eq!(related[0].shared_tags, vec!["Undead".to_string()]);
+    assert_eq!(related[0].relationship_distance, None);
+    assert_eq!(related[1].entity_type, "character");
+    assert_eq!(related[1].relationship_distance, Some(1));
+    assert_eq!(embeddings.embedded.load(Ordering::SeqCst), 4);
+
+    // Unchanged entities are served from the cache
+    let top = get_related_entities_impl(
+        &db,
+        Some(&embeddings),
+        "location".to_string(),
+        temple.id.clone(),
+        Some(1),
+    )
+    .await
+    .expect("Failed to get related entities");
+    assert_eq!(top.len(), 1);
+    assert_eq!(embeddings.embedded.load(Ordering::SeqCst), 4);
+
+    // Without a provider only tags and relationships count
+    let related = get_related_entities_impl(
+        &db,
+        None::<&FakeEmbeddings>,
+        "location".to_string(),
+        temple.id,
+        None,
+    )
+    .await
+    .expect("Failed to get related entities");
+    assert_eq!(related.len(), 2);
+    assert!(related.iter().all(|r| r.text_similarity.is_none()));
+}
+
+#[tokio::test]
+async fn test_related_entities_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = get_related_entities_impl(
+        &db,
+        None::<&FakeEmbeddings>,
+        "tag".to_string(),
+        "x".to_string(),
+        None,
+    )
+    .await
+    .expect_err("Tags have no related panel");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = get_related_entities_impl(
+        &db,
+        None::<&FakeEmbeddings>,
+        "location".to_string(),
+        "missing".to_string(),
+        None,
+    )
+    .await
+    .expect_err("Unknown entity should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_session, setup_test_db};
+use entity::relationships;
+use loreweaver_lib::commands::relationship::{create_relationship_impl, update_relationship_impl};
+use loreweaver_lib::commands::relationship_strength::{
+    adjust_relationship_strength_impl, apply_relationship_decay_impl,
+    get_relationship_strength_history_impl, DecayRule, MAX_STRENGTH,
+};
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+
+async fn create_relationship(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    strength: Option<i32>,
+) -> String {
+    let alice = create_test_character(db, campaign_id, "Alice")
+        .await
+        .expect("Failed to create character");
+    let bob = create_test_character(db, campaign_id, "Bob")
+        .await
+        .expect("Failed to create character");
+    create_relationship_impl(
+        db,
+        campaign_id.to_string(),
+        "character".to_string(),
+        alice.id,
+        "character".to_string(),
+        bob.id,
+        "ally".to_string(),
+        None,
+        None,
+        strength,
+    )
+    .await
+    .expect("Failed to create relationship")
+    .id
+}
+
+/// Pretend the relationship was created `days` ago
+async fn backdate(db: &DatabaseConnection, id: &str, days: i64) {
+    let rel = relationships::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .expect("Failed to load relationship")
+        .expect("Relationship missing");
+    let mut active: relationships::ActiveModel = rel.into();
+    active.created_at = Set(chrono::Utc::now() - chrono::Duration::days(days));
+    active.update(db).await.expect("Failed to backdate");
+}
+
+#[tokio::test]
+async fn test_adjust_relationship_strength_builds_history() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    let rel_id = create_relationship(&db, &campaign.id, Some(50)).await;
+
+    let first = adjust_relationship_strength_impl(
+        &db,
+        rel_id.clone(),
+        30,
+        Some("Fought side by side".to_string()),
+        Some(session.id.clone()),
+    )
+    .await
+    .expect("Failed to adjust strength");
+    assert_eq!(first.value, 80);
+    assert_eq!(first.change, 30);
+    assert_eq!(first.session_id, Some(session.id.clone()));
+    assert!(!first.is_decay);
+
+    // Clamped at the top of the range
+    let second = adjust_relationship_strength_impl(&db, rel_id.clone(), 50, None, None)
+        .await
+        .expect("Failed to adjust strength");
+    assert_eq!(second.value, MAX_STRENGTH);
+    assert_eq!(second.change, MAX_STRENGTH - 80);
+
+    // Plain edits are recorded too
+    update_relationship_impl(&db, rel_id.clone(), None, None, None, Some(40), None)
+        .await
+        .expect("Failed to update relationship");
+    update_relationship_impl(
+        &db,
+        rel_id.clone(),
+        None,
+        Some("Old friends".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update relationship");
+
+    let history = get_relationship_strength_history_impl(&db, rel_id.clone())
+        .await
+        .expect("Failed to get history");
+    assert_eq!(history.current, Some(40));
+    let values: Vec<(i32, i32)> = history
+        .entries
+        .iter()
+        .map(|e| (e.value, e.change))
+        .collect();
+    assert_eq!(values, vec![(80, 30), (100, 20), (40, -60)]);
+    assert_eq!(
+        history.entries[0].reason.as_deref(),
+        Some("Fought side by side")
+    );
+}
+
+#[tokio::test]
+async fn test_adjust_relationship_strength_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let elsewhere = create_test_session(&db, &other.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    let rel_id = create_relationship(&db, &campaign.id, None).await;
+
+    let err = adjust_relationship_strength_impl(&db, rel_id.clone(), 0, None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = adjust_relationship_strength_impl(&db, rel_id.clone(), 5, None, Some(elsewhere.id))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = adjust_relationship_strength_impl(&db, "nope".to_string(), 5, None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_apply_relationship_decay() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let stale = create_relationship(&db, &campaign.id, Some(25)).await;
+    let rival = create_relationship(&db, &campaign.id, Some(-4)).await;
+    let fresh = create_relationship(&db, &campaign.id, Some(60)).await;
+    backdate(&db, &stale, 35).await;
+    backdate(&db, &rival, 70).await;
+    backdate(&db, &fresh, 40).await;
+    // Reinforced just now, so its clock restarts
+    adjust_relationship_strength_impl(&db, fresh.clone(), 5, None, None)
+        .await
+        .expect("Failed to adjust strength");
+
+    let rule = DecayRule {
+        after_days: 7,
+        step: 5,
+    };
+    let changes = apply_relationship_decay_impl(&db, campaign.id.clone(), rule.clone())
+        .await
+        .expect("Failed to apply decay");
+    assert_eq!(changes.len(), 2);
+    assert!(changes.iter().all(|c| c.is_decay));
+
+    // Five idle weeks take 25 off; the rival stops at 0 rather than crossing
+    let history = get_relationship_strength_history_impl(&db, stale.clone())
+        .await
+        .expect("Failed to get history");
+    assert_eq!(history.current, Some(0));
+    assert_eq!(history.entries[0].change, -25);
+    assert_eq!(
+        history.entries[0].reason.as_deref(),
+        Some("Not reinforced for 35 days")
+    );
+    let history = get_relationship_strength_history_impl(&db, rival.clone())
+        .await
+        .expect("Failed to get history");
+    assert_eq!(history.current, Some(0));
+    let history = get_relationship_strength_history_impl(&db, fresh.clone())
+        .await
+        .expect("Failed to get history");
+    assert_eq!(history.current, Some(65));
+
+    let again = apply_relationship_decay_impl(&db, campaign.id.clone(), rule)
+        .await
+        .expect("Failed to apply decay");
+    assert!(again.is_empty());
+
+    let err = apply_relationship_decay_impl(
+        &db,
+        campaign.id.clone(),
+        DecayRule {
+            after_days: 0,
+            step: 5,
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
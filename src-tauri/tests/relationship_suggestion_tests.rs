@@ -0,0 +1,166 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::character::update_character_impl;
+use loreweaver_lib::commands::relationship::create_relationship_impl;
+use loreweaver_lib::commands::relationship_suggestion::suggest_relationships_impl;
+use loreweaver_lib::ErrorCode;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+
+async fn set_text(
+    db: &DatabaseConnection,
+    character_id: &str,
+    description: Option<String>,
+    personality: Option<String>,
+) {
+    update_character_impl(
+        db,
+        character_id.to_string(),
+        None,
+        None,
+        None,
+        None,
+        description,
+        personality,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update character");
+}
+
+#[tokio::test]
+async fn test_suggest_relationships_from_mentions() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let dorn = create_test_character(&db, &campaign.id, "Dorn")
+        .await
+        .expect("Failed to create character");
+    let vess = create_test_character(&db, &campaign.id, "Vess")
+        .await
+        .expect("Failed to create character");
+    let saltmere = create_test_location(&db, &campaign.id, "Saltmere", None)
+        .await
+        .expect("Failed to create location");
+
+    // Mira mentions Dorn in two fields and Saltmere in one
+    let description = json!({
+        "type": "doc",
+        "content": [{
+            "type": "paragraph",
+            "content": [
+                { "type": "text", "text": "Sails with " },
+                { "type": "mention", "attrs": { "entityType": "character", "entityId": dorn.id, "label": "Dorn" } },
+                { "type": "text", "text": format!(" out of [[location:{}:Saltmere]]", saltmere.id) }
+            ]
+        }]
+    });
+    set_text(
+        &db,
+        &mira.id,
+        Some(description.to_string()),
+        Some(format!("Wary of [[character:{}:Dorn]]", dorn.id)),
+    )
+    .await;
+    set_text(
+        &db,
+        &dorn.id,
+        Some(format!("Owes [[character:{}:Mira]] a favor", mira.id)),
+        None,
+    )
+    .await;
+    // Already related, deleted, or not relatable: no suggestions
+    set_text(
+        &db,
+        &vess.id,
+        Some(format!(
+            "Lives in [[location:{}:Saltmere]], hunts [[character:gone:Ghost]] since [[session:s1:Session 1]]",
+            saltmere.id
+        )),
+        None,
+    )
+    .await;
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "location".to_string(),
+        saltmere.id.clone(),
+        "character".to_string(),
+        vess.id.clone(),
+        "located_in".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+
+    let suggestions = suggest_relationships_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to suggest relationships");
+    assert_eq!(suggestions.len(), 2);
+
+    let first = &suggestions[0];
+    assert_eq!(first.source_id, mira.id);
+    assert_eq!(first.target_id, dorn.id);
+    assert_eq!(first.target_name, "Dorn");
+    assert_eq!((first.source_mentions, first.target_mentions), (2, 1));
+    assert!(first.is_bidirectional);
+
+    let second = &suggestions[1];
+    assert_eq!(second.source_id, mira.id);
+    assert_eq!(second.target_type, "location");
+    assert_eq!(second.target_id, saltmere.id);
+    assert_eq!((second.source_mentions, second.target_mentions), (1, 0));
+    assert!(!second.is_bidirectional);
+
+    let frequent = suggest_relationships_impl(&db, campaign.id.clone(), Some(2))
+        .await
+        .expect("Failed to suggest relationships");
+    assert_eq!(frequent.len(), 1);
+    assert_eq!(frequent[0].target_id, dorn.id);
+
+    // Accepting a suggestion removes it
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        first.source_type.clone(),
+        first.source_id.clone(),
+        first.target_type.clone(),
+        first.target_id.clone(),
+        first.relationship_type.clone(),
+        None,
+        Some(first.is_bidirectional),
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    let remaining = suggest_relationships_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to suggest relationships");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].target_id, saltmere.id);
+}
+
+#[tokio::test]
+async fn test_suggest_relationships_unknown_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = suggest_relationships_impl(&db, "missing".to_string(), None)
+        .await
+        .expect_err("Unknown campaign should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
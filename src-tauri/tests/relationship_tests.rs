@@ -2,8 +2,9 @@ mod common;
 
 use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
 use loreweaver_lib::commands::relationship::{
-    create_relationship_impl, delete_relationship_impl, get_entity_relationships_impl,
-    get_relationship_impl, list_relationships_impl, update_relationship_impl,
+    create_relationship_impl, delete_relationship_impl, export_relationships_csv_impl,
+    get_entity_relationships_impl, get_relationship_impl, list_relationships_impl,
+    update_relationship_impl,
 };
 
 #[tokio::test]
@@ -566,3 +567,84 @@ async fn test_relationship_crud_lifecycle() {
         .expect("List after delete failed");
     assert!(list_after.is_empty());
 }
+
+#[tokio::test]
+async fn test_export_relationships_csv() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let alice = create_test_character(&db, &campaign.id, "Alice")
+        .await
+        .expect("Failed to create character");
+    let bob = create_test_character(&db, &campaign.id, "Bob, the Bold")
+        .await
+        .expect("Failed to create character");
+    let town = create_test_location(&db, &campaign.id, "Riverside", None)
+        .await
+        .expect("Failed to create location");
+
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        bob.id.clone(),
+        "location".to_string(),
+        town.id.clone(),
+        "lives_in".to_string(),
+        None,
+        Some(false),
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        alice.id.clone(),
+        "character".to_string(),
+        bob.id.clone(),
+        "rival".to_string(),
+        Some("Says \"never again\"".to_string()),
+        Some(true),
+        Some(-2),
+    )
+    .await
+    .expect("Failed to create relationship");
+
+    let path = std::env::temp_dir().join(format!("test-{}.csv", uuid::Uuid::new_v4()));
+    let summary =
+        export_relationships_csv_impl(&db, campaign.id.clone(), path.to_string_lossy().to_string())
+            .await
+            .expect("Failed to export relationships");
+    assert_eq!(summary.rows, 2);
+
+    let csv = std::fs::read_to_string(&path).expect("Failed to read export");
+    std::fs::remove_file(&path).ok();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[0],
+        "source_type,source_id,source_name,relationship_type,target_type,target_id,\
+         target_name,is_bidirectional,strength,is_public,description"
+    );
+    // Sorted by source name; names and descriptions are quoted where needed
+    assert_eq!(
+        lines[1],
+        format!(
+            "character,{},Alice,rival,character,{},\"Bob, the Bold\",true,-2,true,\
+             \"Says \"\"never again\"\"\"",
+            alice.id, bob.id
+        )
+    );
+    assert_eq!(
+        lines[2],
+        format!(
+            "character,{},\"Bob, the Bold\",lives_in,location,{},Riverside,false,,true,",
+            bob.id, town.id
+        )
+    );
+}
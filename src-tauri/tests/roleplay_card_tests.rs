@@ -0,0 +1,123 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_session, setup_test_db};
+use entity::sessions;
+use loreweaver_lib::commands::character::set_character_roleplay_impl;
+use loreweaver_lib::commands::disposition::adjust_disposition_impl;
+use loreweaver_lib::commands::roleplay_card::{get_roleplay_card_impl, RECENT_INTERACTIONS};
+use loreweaver_lib::commands::validation::RoleplayInput;
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, Set};
+
+#[tokio::test]
+async fn test_roleplay_card() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mara = create_test_character(&db, &campaign.id, "Mara")
+        .await
+        .expect("Failed to create character");
+    set_character_roleplay_impl(
+        &db,
+        mara.id.clone(),
+        RoleplayInput {
+            accent: Some("Clipped northern burr".to_string()),
+            catchphrases: Some(vec!["Coin first, questions never.".to_string()]),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to set roleplay fields");
+
+    let date = |day| chrono::NaiveDate::from_ymd_opt(2025, 3, day);
+    let first = create_test_session(&db, &campaign.id, 1, date(1))
+        .await
+        .expect("Failed to create session");
+    let mut active: sessions::ActiveModel = first.into();
+    active.notes = Set(Some(format!(
+        "Arrived in port.\nBought a map from [[character:{}]].\nSlept.",
+        mara.id
+    )));
+    active.update(&db).await.expect("Failed to update session");
+
+    // No mention, but her attitude changed during the session
+    let second = create_test_session(&db, &campaign.id, 2, date(8))
+        .await
+        .expect("Failed to create session");
+    adjust_disposition_impl(
+        &db,
+        mara.id.clone(),
+        -10,
+        Some("They haggled".to_string()),
+        Some(second.id.clone()),
+    )
+    .await
+    .expect("Failed to adjust disposition");
+
+    create_test_session(&db, &campaign.id, 3, date(15))
+        .await
+        .expect("Failed to create session");
+
+    let card = get_roleplay_card_impl(&db, mara.id.clone())
+        .await
+        .expect("Failed to get roleplay card");
+    assert_eq!(card.name, "Mara");
+    assert_eq!(card.accent.as_deref(), Some("Clipped northern burr"));
+    assert_eq!(card.catchphrases, vec!["Coin first, questions never."]);
+    assert_eq!(card.disposition, -10);
+
+    let numbers: Vec<i32> = card
+        .recent_interactions
+        .iter()
+        .map(|i| i.session_number)
+        .collect();
+    assert_eq!(numbers, vec![2, 1]);
+    assert_eq!(card.recent_interactions[0].disposition_change, -10);
+    assert_eq!(
+        card.recent_interactions[0].disposition_reasons,
+        vec!["They haggled"]
+    );
+    assert!(card.recent_interactions[0].excerpts.is_empty());
+    assert_eq!(
+        card.recent_interactions[1].excerpts,
+        vec!["Bought a map from Mara."]
+    );
+}
+
+#[tokio::test]
+async fn test_roleplay_card_keeps_recent_sessions() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mara = create_test_character(&db, &campaign.id, "Mara")
+        .await
+        .expect("Failed to create character");
+
+    for number in 1..=7 {
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 3, number as u32);
+        let session = create_test_session(&db, &campaign.id, number, date)
+            .await
+            .expect("Failed to create session");
+        let mut active: sessions::ActiveModel = session.into();
+        active.summary = Set(Some(format!("[[character:{}:Mara]] again", mara.id)));
+        active.update(&db).await.expect("Failed to update session");
+    }
+
+    let card = get_roleplay_card_impl(&db, mara.id.clone())
+        .await
+        .expect("Failed to get roleplay card");
+    assert_eq!(card.recent_interactions.len(), RECENT_INTERACTIONS);
+    assert_eq!(card.recent_interactions[0].session_number, 7);
+    assert_eq!(card.recent_interactions[0].excerpts, vec!["Mara again"]);
+
+    let err = get_roleplay_card_impl(&db, "nope".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
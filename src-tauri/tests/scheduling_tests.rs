@@ -0,0 +1,223 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::{create_test_campaign, create_test_player, create_test_session, setup_test_db};
+use loreweaver_lib::commands::json_schema::PlayerAvailability;
+use loreweaver_lib::commands::scheduling::{
+    list_session_absences_impl, record_session_absence_impl, remove_session_absence_impl,
+    set_player_availability_impl, suggest_session_dates_impl,
+};
+use loreweaver_lib::ErrorCode;
+
+fn weekdays(days: &[&str]) -> PlayerAvailability {
+    PlayerAvailability {
+        weekdays: days.iter().map(|d| d.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_suggest_session_dates_when_everyone_is_free() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let sam = create_test_player(&db, &campaign.id, "Sam")
+        .await
+        .expect("Failed to create player");
+    let alex = create_test_player(&db, &campaign.id, "Alex")
+        .await
+        .expect("Failed to create player");
+
+    set_player_availability_impl(&db, sam.id.clone(), weekdays(&["fri", "sat"]))
+        .await
+        .expect("Failed to set availability");
+    let player = set_player_availability_impl(&db, alex.id.clone(), weekdays(&["sat", "sun"]))
+        .await
+        .expect("Failed to set availability");
+    assert_eq!(player.availability.weekdays, vec!["sat", "sun"]);
+
+    // 2026-02-02 is a Monday
+    let suggestions = suggest_session_dates_impl(
+        &db,
+        campaign.id.clone(),
+        "2026-02-02".to_string(),
+        "2026-02-15".to_string(),
+        None,
+    )
+    .await
+    .expect("Failed to suggest dates");
+    let dates: Vec<&str> = suggestions.iter().map(|s| s.date.as_str()).collect();
+    assert_eq!(dates, vec!["2026-02-07", "2026-02-14"]);
+    assert_eq!(suggestions[0].weekday, "Sat");
+
+    let most = suggest_session_dates_impl(
+        &db,
+        campaign.id.clone(),
+        "2026-02-02".to_string(),
+        "2026-02-08".to_string(),
+        Some(1),
+    )
+    .await
+    .expect("Failed to suggest dates");
+    let dates: Vec<&str> = most.iter().map(|s| s.date.as_str()).collect();
+    assert_eq!(dates, vec!["2026-02-06", "2026-02-07", "2026-02-08"]);
+    assert_eq!(most[0].unavailable_player_ids, vec![alex.id.clone()]);
+}
+
+#[tokio::test]
+async fn test_suggest_session_dates_skips_booked_and_blocked_dates() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let sam = create_test_player(&db, &campaign.id, "Sam")
+        .await
+        .expect("Failed to create player");
+
+    let date = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+    set_player_availability_impl(
+        &db,
+        sam.id.clone(),
+        PlayerAvailability {
+            weekdays: vec!["sat".to_string()],
+            unavailable_dates: vec![date("2026-02-14")],
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to set availability");
+    create_test_session(&db, &campaign.id, 1, Some(date("2026-02-07")))
+        .await
+        .expect("Failed to create session");
+
+    let suggestions = suggest_session_dates_impl(
+        &db,
+        campaign.id.clone(),
+        "2026-02-01".to_string(),
+        "2026-02-28".to_string(),
+        None,
+    )
+    .await
+    .expect("Failed to suggest dates");
+    let dates: Vec<&str> = suggestions.iter().map(|s| s.date.as_str()).collect();
+    assert_eq!(dates, vec!["2026-02-21", "2026-02-28"]);
+}
+
+#[tokio::test]
+async fn test_suggest_session_dates_rejects_bad_ranges() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    for (start, end) in [
+        ("2026-02-10", "2026-02-01"),
+        ("2026-01-01", "2027-06-01"),
+        ("next friday", "2026-02-01"),
+    ] {
+        let err = suggest_session_dates_impl(
+            &db,
+            campaign.id.clone(),
+            start.to_string(),
+            end.to_string(),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Validation);
+    }
+}
+
+#[tokio::test]
+async fn test_set_player_availability_rejects_unknown_weekday() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let sam = create_test_player(&db, &campaign.id, "Sam")
+        .await
+        .expect("Failed to create player");
+
+    let err = set_player_availability_impl(&db, sam.id.clone(), weekdays(&["caturday"]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
+
+#[tokio::test]
+async fn test_record_and_remove_session_absence() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let sam = create_test_player(&db, &campaign.id, "Sam")
+        .await
+        .expect("Failed to create player");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    record_session_absence_impl(&db, session.id.clone(), sam.id.clone(), None)
+        .await
+        .expect("Failed to record absence");
+    let absence = record_session_absence_impl(
+        &db,
+        session.id.clone(),
+        sam.id.clone(),
+        Some("Work trip".to_string()),
+    )
+    .await
+    .expect("Failed to record absence");
+    assert_eq!(absence.player_name, "Sam");
+
+    let absences = list_session_absences_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to list absences");
+    assert_eq!(absences.len(), 1);
+    assert_eq!(absences[0].reason.as_deref(), Some("Work trip"));
+
+    assert!(
+        remove_session_absence_impl(&db, session.id.clone(), sam.id.clone())
+            .await
+            .expect("Failed to remove absence")
+    );
+    assert!(list_session_absences_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to list absences")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_record_absence_for_player_in_other_campaign_fails() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let stranger = create_test_player(&db, &other.id, "Stranger")
+        .await
+        .expect("Failed to create player");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let err = record_session_absence_impl(&db, session.id.clone(), stranger.id.clone(), None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
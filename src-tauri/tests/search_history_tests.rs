@@ -0,0 +1,106 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::search_history::{
+    clear_search_history_impl, get_search_suggestions_impl, record_search_query_impl,
+};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_search_history_suggestions() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_character(&db, &campaign.id, "Gandalf the Grey")
+        .await
+        .expect("Failed to create character");
+    create_test_location(&db, &campaign.id, "Gondor", None)
+        .await
+        .expect("Failed to create location");
+
+    for query in ["gondor", "  gandalf   wizard ", "Gondor", "   "] {
+        record_search_query_impl(&db, campaign.id.clone(), query.to_string())
+            .await
+            .expect("Failed to record search");
+    }
+    record_search_query_impl(&db, other.id.clone(), "goblins".to_string())
+        .await
+        .expect("Failed to record search");
+
+    // History first, most recent first, then names not already suggested
+    let suggestions = get_search_suggestions_impl(&db, campaign.id.clone(), "g".to_string(), None)
+        .await
+        .expect("Failed to get suggestions");
+    let texts: Vec<_> = suggestions
+        .iter()
+        .map(|s| (s.source.as_str(), s.text.as_str()))
+        .collect();
+    assert_eq!(
+        texts,
+        vec![
+            ("history", "Gondor"),
+            ("history", "gandalf wizard"),
+            ("entity", "Gandalf the Grey"),
+        ]
+    );
+    assert_eq!(suggestions[2].entity_type.as_deref(), Some("character"));
+
+    let suggestions =
+        get_search_suggestions_impl(&db, campaign.id.clone(), "GAN".to_string(), None)
+            .await
+            .expect("Failed to get suggestions");
+    assert_eq!(suggestions.len(), 2);
+
+    // An empty prefix is just the recent searches
+    let suggestions = get_search_suggestions_impl(&db, campaign.id.clone(), String::new(), Some(1))
+        .await
+        .expect("Failed to get suggestions");
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].text, "Gondor");
+
+    let cleared = clear_search_history_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to clear history");
+    assert_eq!(cleared, 2);
+    let suggestions = get_search_suggestions_impl(&db, campaign.id.clone(), "g".to_string(), None)
+        .await
+        .expect("Failed to get suggestions");
+    assert!(suggestions.iter().all(|s| s.source == "entity"));
+    let suggestions = get_search_suggestions_impl(&db, other.id.clone(), "g".to_string(), None)
+        .await
+        .expect("Failed to get suggestions");
+    assert_eq!(suggestions.len(), 1);
+}
+
+#[tokio::test]
+async fn test_search_history_is_capped() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    for i in 0..55 {
+        record_search_query_impl(&db, campaign.id.clone(), format!("query {}", i))
+            .await
+            .expect("Failed to record search");
+    }
+    let suggestions =
+        get_search_suggestions_impl(&db, campaign.id.clone(), String::new(), Some(100))
+            .await
+            .expect("Failed to get suggestions");
+    assert_eq!(suggestions.len(), 50);
+    assert_eq!(suggestions[0].text, "query 54");
+
+    let err = record_search_query_impl(&db, "missing".to_string(), "anything".to_string())
+        .await
+        .expect_err("Unknown campaign should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -1,7 +1,11 @@
 mod common;
 
 use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
-use loreweaver_lib::commands::search::search_entities_impl;
+use loreweaver_lib::commands::relationship::create_relationship_impl;
+use loreweaver_lib::commands::search::{
+    search_entities_impl, search_in_location_impl, SnippetOptions, TypeLimits,
+};
+use loreweaver_lib::ErrorCode;
 
 #[tokio::test]
 async fn test_search_by_name() {
@@ -20,9 +24,17 @@ async fn test_search_by_name() {
         .await
         .expect("Failed to create character");
 
-    let results = search_entities_impl(&db, campaign.id.clone(), "Gandalf".to_string(), None, None)
-        .await
-        .expect("Search failed");
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Gandalf".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
 
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].name, "Gandalf the Grey");
@@ -49,9 +61,17 @@ async fn test_search_prefix_matching() {
         .expect("Failed to create character");
 
     // Search with prefix "Ga" should match Gandalf and Galadriel
-    let results = search_entities_impl(&db, campaign.id.clone(), "Ga".to_string(), None, None)
-        .await
-        .expect("Search failed");
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Ga".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
 
     assert_eq!(results.len(), 2);
     let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
@@ -85,6 +105,8 @@ async fn test_search_multiple_words() {
         "Gandalf White".to_string(),
         None,
         None,
+        SnippetOptions::default(),
+        None,
     )
     .await
     .expect("Search failed");
@@ -110,9 +132,17 @@ async fn test_search_across_entity_types() {
         .await
         .expect("Failed to create location");
 
-    let results = search_entities_impl(&db, campaign.id.clone(), "Dragon".to_string(), None, None)
-        .await
-        .expect("Search failed");
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Dragon".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
 
     assert_eq!(results.len(), 2);
     let types: Vec<&str> = results.iter().map(|r| r.entity_type.as_str()).collect();
@@ -142,15 +172,29 @@ async fn test_search_campaign_isolation() {
         .expect("Failed to create character in campaign 2");
 
     // Search in campaign 1 should only return that campaign's Gandalf
-    let results1 =
-        search_entities_impl(&db, campaign1.id.clone(), "Gandalf".to_string(), None, None)
-            .await
-            .expect("Search failed");
+    let results1 = search_entities_impl(
+        &db,
+        campaign1.id.clone(),
+        "Gandalf".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
 
-    let results2 =
-        search_entities_impl(&db, campaign2.id.clone(), "Gandalf".to_string(), None, None)
-            .await
-            .expect("Search failed");
+    let results2 = search_entities_impl(
+        &db,
+        campaign2.id.clone(),
+        "Gandalf".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
 
     assert_eq!(results1.len(), 1);
     assert_eq!(results2.len(), 1);
@@ -173,7 +217,16 @@ async fn test_search_empty_query() {
 
     // Empty query causes FTS5 syntax error - this is expected behavior
     // The application should validate queries before sending to FTS5
-    let result = search_entities_impl(&db, campaign.id.clone(), "".to_string(), None, None).await;
+    let result = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await;
 
     assert!(result.is_err());
 }
@@ -197,6 +250,8 @@ async fn test_search_no_matches() {
         "Nonexistent".to_string(),
         None,
         None,
+        SnippetOptions::default(),
+        None,
     )
     .await
     .expect("Search failed");
@@ -226,6 +281,8 @@ async fn test_search_with_limit() {
         "Adventurer".to_string(),
         None,
         Some(3),
+        SnippetOptions::default(),
+        None,
     )
     .await
     .expect("Search failed");
@@ -256,6 +313,8 @@ async fn test_search_default_limit() {
         "Test Character".to_string(),
         None,
         None,
+        SnippetOptions::default(),
+        None,
     )
     .await
     .expect("Search failed");
@@ -276,9 +335,17 @@ async fn test_search_returns_entity_id() {
         .await
         .expect("Failed to create character");
 
-    let results = search_entities_impl(&db, campaign.id.clone(), "Unique".to_string(), None, None)
-        .await
-        .expect("Search failed");
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Unique".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
 
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].entity_id, character.id);
@@ -298,18 +365,39 @@ async fn test_search_case_insensitive() {
         .expect("Failed to create character");
 
     // Search should be case-insensitive
-    let results_lower =
-        search_entities_impl(&db, campaign.id.clone(), "gandalf".to_string(), None, None)
-            .await
-            .expect("Search failed");
-    let results_upper =
-        search_entities_impl(&db, campaign.id.clone(), "GANDALF".to_string(), None, None)
-            .await
-            .expect("Search failed");
-    let results_mixed =
-        search_entities_impl(&db, campaign.id.clone(), "GaNdAlF".to_string(), None, None)
-            .await
-            .expect("Search failed");
+    let results_lower = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "gandalf".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
+    let results_upper = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "GANDALF".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
+    let results_mixed = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "GaNdAlF".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
 
     assert_eq!(results_lower.len(), 1);
     assert_eq!(results_upper.len(), 1);
@@ -336,6 +424,8 @@ async fn test_search_special_characters_in_query() {
         r#""Test" "Character""#.to_string(),
         None,
         None,
+        SnippetOptions::default(),
+        None,
     )
     .await
     .expect("Search failed");
@@ -356,9 +446,17 @@ async fn test_search_returns_snippet() {
         .await
         .expect("Failed to create character");
 
-    let results = search_entities_impl(&db, campaign.id.clone(), "Gandalf".to_string(), None, None)
-        .await
-        .expect("Search failed");
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Gandalf".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
 
     assert_eq!(results.len(), 1);
     // Snippet should be present (may contain highlighted match)
@@ -366,7 +464,7 @@ async fn test_search_returns_snippet() {
 }
 
 #[tokio::test]
-async fn test_search_returns_rank() {
+async fn test_search_snippet_options_and_matched_field() {
     let db = setup_test_db()
         .await
         .expect("Failed to setup test database");
@@ -374,15 +472,235 @@ async fn test_search_returns_rank() {
         .await
         .expect("Failed to create campaign");
 
+    // The fixture describes every character as "A test character"
     create_test_character(&db, &campaign.id, "Gandalf")
         .await
         .expect("Failed to create character");
 
-    let results = search_entities_impl(&db, campaign.id.clone(), "Gandalf".to_string(), None, None)
+    let options = SnippetOptions {
+        column: "content".to_string(),
+        context_tokens: 8,
+        open_marker: "[[".to_string(),
+        close_marker: "]]".to_string(),
+        ..Default::default()
+    };
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "test".to_string(),
+        None,
+        None,
+        options.clone(),
+        None,
+    )
+    .await
+    .expect("Search failed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].matched_field.as_deref(), Some("content"));
+    assert_eq!(
+        results[0].snippet.as_deref().map(str::trim),
+        Some("A [[test]] character")
+    );
+
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "gandalf".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
+    assert_eq!(results[0].matched_field.as_deref(), Some("name"));
+
+    for bad in [
+        SnippetOptions {
+            column: "secrets".to_string(),
+            ..Default::default()
+        },
+        SnippetOptions {
+            context_tokens: 65,
+            ..Default::default()
+        },
+    ] {
+        let err = search_entities_impl(
+            &db,
+            campaign.id.clone(),
+            "gandalf".to_string(),
+            None,
+            None,
+            bad,
+            None,
+        )
+        .await
+        .expect_err("Bad snippet options should fail");
+        assert_eq!(err.code(), ErrorCode::Validation);
+    }
+}
+
+#[tokio::test]
+async fn test_search_returns_rank() {
+    let db = setup_test_db()
         .await
-        .expect("Search failed");
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Gandalf".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        None,
+    )
+    .await
+    .expect("Search failed");
 
     assert_eq!(results.len(), 1);
     // Rank should be a finite number (FTS5 BM25 ranking)
     assert!(results[0].rank.is_finite());
 }
+
+#[tokio::test]
+async fn test_search_in_location_subtree() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let city = create_test_location(&db, &campaign.id, "Saltmere", None)
+        .await
+        .expect("Failed to create location");
+    let docks = create_test_location(&db, &campaign.id, "Docks", Some(&city.id))
+        .await
+        .expect("Failed to create location");
+    let tavern = create_test_location(&db, &campaign.id, "Rusty Anchor", Some(&docks.id))
+        .await
+        .expect("Failed to create location");
+    let market = create_test_location(&db, &campaign.id, "Market", Some(&city.id))
+        .await
+        .expect("Failed to create location");
+
+    let quill = create_test_character(&db, &campaign.id, "Harbormaster Quill")
+        .await
+        .expect("Failed to create character");
+    let vey = create_test_character(&db, &campaign.id, "Captain Vey")
+        .await
+        .expect("Failed to create character");
+    let tobin = create_test_character(&db, &campaign.id, "Merchant Tobin")
+        .await
+        .expect("Failed to create character");
+    // Relationships count in either direction
+    for (source_type, source_id, target_type, target_id) in [
+        ("character", &quill.id, "location", &docks.id),
+        ("location", &tavern.id, "character", &vey.id),
+        ("character", &tobin.id, "location", &market.id),
+    ] {
+        create_relationship_impl(
+            &db,
+            campaign.id.clone(),
+            source_type.to_string(),
+            source_id.clone(),
+            target_type.to_string(),
+            target_id.clone(),
+            "located_at".to_string(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("Failed to create relationship");
+    }
+
+    let results = search_in_location_impl(&db, docks.id.clone(), "test".to_string(), None)
+        .await
+        .expect("Search failed");
+    let mut names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec!["Captain Vey", "Docks", "Harbormaster Quill", "Rusty Anchor"]
+    );
+
+    let results = search_in_location_impl(&db, docks.id.clone(), "Quill".to_string(), None)
+        .await
+        .expect("Search failed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].entity_id, quill.id);
+
+    // An empty query lists everything in scope by name
+    let results = search_in_location_impl(&db, market.id.clone(), String::new(), None)
+        .await
+        .expect("Search failed");
+    let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["Market", "Merchant Tobin"]);
+
+    let results = search_in_location_impl(&db, city.id.clone(), "test".to_string(), Some(3))
+        .await
+        .expect("Search failed");
+    assert_eq!(results.len(), 3);
+}
+
+#[tokio::test]
+async fn test_search_in_unknown_location() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = search_in_location_impl(&db, "missing".to_string(), "docks".to_string(), None)
+        .await
+        .expect_err("Unknown location should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_search_grouped_by_type() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    for i in 1..=5 {
+        create_test_character(&db, &campaign.id, &format!("Dragon Cultist {}", i))
+            .await
+            .expect("Failed to create character");
+    }
+    for name in ["Dragon's Lair", "Dragon Peak"] {
+        create_test_location(&db, &campaign.id, name, None)
+            .await
+            .expect("Failed to create location");
+    }
+
+    let results = search_entities_impl(
+        &db,
+        campaign.id.clone(),
+        "Dragon".to_string(),
+        None,
+        None,
+        SnippetOptions::default(),
+        Some(TypeLimits::default()),
+    )
+    .await
+    .expect("Search failed");
+
+    // Three characters and both locations, each type kept together
+    let types: Vec<&str> = results.iter().map(|r| r.entity_type.as_str()).collect();
+    assert_eq!(types.len(), 5);
+    assert_eq!(types.iter().filter(|t| **t == "character").count(), 3);
+    assert_eq!(types.iter().filter(|t| **t == "location").count(), 2);
+    let type_changes = types.windows(2).filter(|w| w[0] != w[1]).count();
+    assert_eq!(type_changes, 1);
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use common::{create_test_campaign, setup_test_db};
+use loreweaver_lib::commands::secret::{
+    add_secret_prerequisite_impl, create_secret_impl, delete_secret_impl, get_secret_impl,
+    list_secrets_impl, remove_secret_prerequisite_impl, update_secret_impl, SecretResponse,
+};
+use loreweaver_lib::ErrorCode;
+use sea_orm::DatabaseConnection;
+
+async fn create_secret(db: &DatabaseConnection, campaign_id: &str, title: &str) -> SecretResponse {
+    create_secret_impl(
+        db,
+        campaign_id.to_string(),
+        title.to_string(),
+        "Hidden".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create secret")
+}
+
+async fn reveal(db: &DatabaseConnection, id: &str) {
+    update_secret_impl(
+        db,
+        id.to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(true),
+        None,
+    )
+    .await
+    .expect("Failed to reveal secret");
+}
+
+fn revealable(secrets: &[SecretResponse]) -> Vec<&str> {
+    let mut titles: Vec<_> = secrets
+        .iter()
+        .filter(|s| s.revealable)
+        .map(|s| s.title.as_str())
+        .collect();
+    titles.sort();
+    titles
+}
+
+#[tokio::test]
+async fn test_prerequisites_gate_revealable() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let cult = create_secret(&db, &campaign.id, "The cult exists").await;
+    let leader = create_secret(&db, &campaign.id, "The mayor leads the cult").await;
+    let ritual = create_secret(&db, &campaign.id, "The ritual is tonight").await;
+
+    add_secret_prerequisite_impl(&db, leader.id.clone(), cult.id.clone())
+        .await
+        .expect("Failed to add prerequisite");
+    let linked = add_secret_prerequisite_impl(&db, ritual.id.clone(), leader.id.clone())
+        .await
+        .expect("Failed to add prerequisite");
+    assert_eq!(linked.prerequisite_ids, vec![leader.id.clone()]);
+    assert!(!linked.revealable);
+
+    let secrets = list_secrets_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list secrets");
+    assert_eq!(revealable(&secrets), vec!["The cult exists"]);
+
+    reveal(&db, &cult.id).await;
+    let secrets = list_secrets_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list secrets");
+    // Revealed secrets are never flagged
+    assert_eq!(revealable(&secrets), vec!["The mayor leads the cult"]);
+
+    let fetched = get_secret_impl(&db, leader.id.clone())
+        .await
+        .expect("Failed to get secret");
+    assert!(fetched.revealable);
+
+    let unlinked = remove_secret_prerequisite_impl(&db, ritual.id.clone(), leader.id.clone())
+        .await
+        .expect("Failed to remove prerequisite");
+    assert!(unlinked.prerequisite_ids.is_empty());
+    assert!(unlinked.revealable);
+}
+
+#[tokio::test]
+async fn test_prerequisite_cycles_are_rejected() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other_campaign = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let a = create_secret(&db, &campaign.id, "A").await;
+    let b = create_secret(&db, &campaign.id, "B").await;
+    let c = create_secret(&db, &campaign.id, "C").await;
+    let elsewhere = create_secret(&db, &other_campaign.id, "Elsewhere").await;
+
+    add_secret_prerequisite_impl(&db, b.id.clone(), a.id.clone())
+        .await
+        .expect("Failed to add prerequisite");
+    add_secret_prerequisite_impl(&db, c.id.clone(), b.id.clone())
+        .await
+        .expect("Failed to add prerequisite");
+
+    let err = add_secret_prerequisite_impl(&db, a.id.clone(), c.id.clone())
+        .await
+        .expect_err("Indirect cycle should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = add_secret_prerequisite_impl(&db, a.id.clone(), a.id.clone())
+        .await
+        .expect_err("Self prerequisite should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = add_secret_prerequisite_impl(&db, a.id.clone(), elsewhere.id.clone())
+        .await
+        .expect_err("Secret from another campaign should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    // Adding an existing link again is a no-op
+    let again = add_secret_prerequisite_impl(&db, c.id.clone(), b.id.clone())
+        .await
+        .expect("Re-adding a prerequisite should succeed");
+    assert_eq!(again.prerequisite_ids, vec![b.id.clone()]);
+}
+
+#[tokio::test]
+async fn test_delete_secret_clears_prerequisite_links() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let first = create_secret(&db, &campaign.id, "First").await;
+    let second = create_secret(&db, &campaign.id, "Second").await;
+    add_secret_prerequisite_impl(&db, second.id.clone(), first.id.clone())
+        .await
+        .expect("Failed to add prerequisite");
+
+    assert!(delete_secret_impl(&db, first.id.clone())
+        .await
+        .expect("Failed to delete secret"));
+    let second = get_secret_impl(&db, second.id.clone())
+        .await
+        .expect("Failed to get secret");
+    assert!(second.prerequisite_ids.is_empty());
+    assert!(second.revealable);
+
+    assert!(!delete_secret_impl(&db, first.id.clone())
+        .await
+        .expect("Failed to delete secret"));
+}
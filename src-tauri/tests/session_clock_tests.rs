@@ -0,0 +1,174 @@
+mod common;
+
+use chrono::{DateTime, Utc};
+use common::{create_test_campaign, create_test_session, setup_test_db};
+use entity::sessions;
+use loreweaver_lib::commands::session_clock::{
+    get_play_time_report_impl, pause_session_clock_impl, start_session_clock_impl,
+    stop_session_clock_impl, ClockState,
+};
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+
+fn at(raw: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(raw)
+        .expect("valid timestamp")
+        .with_timezone(&Utc)
+}
+
+async fn set_clock(
+    db: &DatabaseConnection,
+    session: sessions::Model,
+    started_at: &str,
+    ended_at: Option<&str>,
+    breaks_json: Option<&str>,
+) {
+    let mut active: sessions::ActiveModel = session.into();
+    active.started_at = Set(Some(at(started_at)));
+    active.ended_at = Set(ended_at.map(at));
+    active.breaks_json = Set(breaks_json.map(str::to_string));
+    active.update(db).await.expect("Failed to set clock");
+}
+
+#[tokio::test]
+async fn test_clock_start_pause_resume_stop() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let clock = start_session_clock_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to start clock");
+    assert_eq!(clock.state, ClockState::Running);
+    assert!(clock.started_at.is_some());
+
+    let clock = pause_session_clock_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to pause clock");
+    assert_eq!(clock.state, ClockState::Paused);
+    assert_eq!(clock.breaks.len(), 1);
+
+    // Pausing twice doesn't open a second break
+    let clock = pause_session_clock_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to pause clock");
+    assert_eq!(clock.breaks.len(), 1);
+
+    let clock = start_session_clock_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to resume clock");
+    assert_eq!(clock.state, ClockState::Running);
+    assert!(clock.breaks[0].ended_at.is_some());
+
+    let clock = stop_session_clock_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to stop clock");
+    assert_eq!(clock.state, ClockState::Stopped);
+    assert!(clock.ended_at.is_some());
+
+    let err = start_session_clock_impl(&db, session.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+}
+
+#[tokio::test]
+async fn test_clock_must_be_started_first() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let err = pause_session_clock_impl(&db, session.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    let err = stop_session_clock_impl(&db, session.id.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+}
+
+#[tokio::test]
+async fn test_play_time_report_totals_stopped_sessions() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let first = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    set_clock(
+        &db,
+        first,
+        "2026-02-06T19:00:00Z",
+        Some("2026-02-06T22:00:00Z"),
+        Some(r#"[{"started_at":"2026-02-06T20:30:00Z","ended_at":"2026-02-06T21:00:00Z"}]"#),
+    )
+    .await;
+
+    let second = create_test_session(&db, &campaign.id, 2, None)
+        .await
+        .expect("Failed to create session");
+    set_clock(
+        &db,
+        second,
+        "2026-02-13T18:00:00Z",
+        Some("2026-02-13T22:00:00Z"),
+        None,
+    )
+    .await;
+
+    // Still running, so it's left out of the totals
+    let third = create_test_session(&db, &campaign.id, 3, None)
+        .await
+        .expect("Failed to create session");
+    set_clock(&db, third, "2026-02-20T18:00:00Z", None, None).await;
+
+    create_test_session(&db, &campaign.id, 4, None)
+        .await
+        .expect("Failed to create session");
+
+    let report = get_play_time_report_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to build report");
+    assert_eq!(report.clocked_sessions, 2);
+    assert_eq!(report.total_minutes, 390);
+    assert_eq!(report.total_hours, 6.5);
+    assert_eq!(report.average_minutes, Some(195));
+    assert_eq!(report.longest_minutes, Some(240));
+    assert_eq!(report.shortest_minutes, Some(150));
+    assert_eq!(report.sessions[0].break_minutes, 30);
+}
+
+#[tokio::test]
+async fn test_play_time_report_empty_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let report = get_play_time_report_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to build report");
+    assert_eq!(report.clocked_sessions, 0);
+    assert_eq!(report.total_minutes, 0);
+    assert_eq!(report.average_minutes, None);
+}
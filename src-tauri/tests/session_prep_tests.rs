@@ -0,0 +1,169 @@
+mod common;
+
+use common::{create_test_campaign, create_test_location, create_test_session, setup_test_db};
+use entity::{secrets, sessions};
+use loreweaver_lib::commands::quest::create_quest_impl;
+use loreweaver_lib::commands::session_prep::{generate_session_prep_impl, PREP_HEADING};
+use loreweaver_lib::commands::validation::CreateQuestInput;
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+
+async fn create_secret(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    title: &str,
+    related: Option<(&str, &str)>,
+    revealed: bool,
+) {
+    let now = chrono::Utc::now();
+    secrets::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        campaign_id: Set(campaign_id.to_string()),
+        title: Set(title.to_string()),
+        content: Set("Hidden".to_string()),
+        related_entity_type: Set(related.map(|(t, _)| t.to_string())),
+        related_entity_id: Set(related.map(|(_, id)| id.to_string())),
+        known_by: Set(None),
+        revealed: Set(revealed),
+        revealed_in_session: Set(None),
+        prerequisites_json: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        created_by: Set(None),
+        modified_by: Set(None),
+    }
+    .insert(db)
+    .await
+    .expect("Failed to create secret");
+}
+
+async fn create_quest(db: &DatabaseConnection, campaign_id: &str, name: &str, status: &str) {
+    create_quest_impl(
+        db,
+        CreateQuestInput {
+            name: name.to_string(),
+            campaign_id: campaign_id.to_string(),
+            plot_type: "main".to_string(),
+            status: status.to_string(),
+            description: None,
+            hook: None,
+            objectives: None,
+        },
+    )
+    .await
+    .expect("Failed to create quest");
+}
+
+#[tokio::test]
+async fn test_generate_session_prep() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let city = create_test_location(&db, &campaign.id, "Duskhollow", None)
+        .await
+        .expect("Failed to create location");
+    let tavern = create_test_location(&db, &campaign.id, "The Gull", Some(&city.id))
+        .await
+        .expect("Failed to create location");
+    let elsewhere = create_test_location(&db, &campaign.id, "Stormkeep", None)
+        .await
+        .expect("Failed to create location");
+
+    create_quest(&db, &campaign.id, "Find the Heir", "active").await;
+    create_quest(&db, &campaign.id, "Clear the Mine", "available").await;
+    create_quest(&db, &campaign.id, "Old Business", "completed").await;
+
+    create_secret(
+        &db,
+        &campaign.id,
+        "Smuggler Tunnels",
+        Some(("location", &tavern.id)),
+        false,
+    )
+    .await;
+    create_secret(
+        &db,
+        &campaign.id,
+        "Keep Traitor",
+        Some(("location", &elsewhere.id)),
+        false,
+    )
+    .await;
+    create_secret(
+        &db,
+        &campaign.id,
+        "Known Already",
+        Some(("location", &city.id)),
+        true,
+    )
+    .await;
+
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    let plan = serde_json::json!({
+        "type": "doc",
+        "content": [{
+            "type": "paragraph",
+            "content": [
+                {"type": "text", "text": "The party arrives in "},
+                {"type": "citation", "attrs": {"entityType": "location", "entityId": city.id, "label": "Duskhollow"}}
+            ]
+        }]
+    });
+    let mut active: sessions::ActiveModel = session.clone().into();
+    active.planned_content = Set(Some(plan.to_string()));
+    active.update(&db).await.expect("Failed to update session");
+
+    let prep = generate_session_prep_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to generate prep");
+
+    let locations: Vec<_> = prep
+        .expected_locations
+        .iter()
+        .map(|l| l.name.as_str())
+        .collect();
+    assert_eq!(locations, vec!["Duskhollow", "The Gull"]);
+    let quests: Vec<_> = prep.open_quests.iter().map(|q| q.name.as_str()).collect();
+    assert_eq!(quests, vec!["Find the Heir", "Clear the Mine"]);
+    assert_eq!(prep.secrets.len(), 1);
+    assert_eq!(prep.secrets[0].title, "Smuggler Tunnels");
+
+    // The plan keeps its own content with the checklist after it
+    let saved = prep
+        .session
+        .planned_content
+        .expect("Planned content should be saved");
+    let doc: serde_json::Value = serde_json::from_str(&saved).expect("Plan should be JSON");
+    let content = doc["content"].as_array().expect("Doc should have content");
+    assert_eq!(content[0]["content"][0]["text"], "The party arrives in ");
+    assert_eq!(content[1]["content"][0]["text"], PREP_HEADING);
+    assert!(saved.contains("[ ] "));
+
+    // Regenerating replaces the old checklist instead of stacking another
+    let again = generate_session_prep_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to regenerate prep");
+    let saved_again = again
+        .session
+        .planned_content
+        .expect("Planned content should be saved");
+    assert_eq!(saved_again.matches(PREP_HEADING).count(), 1);
+    assert_eq!(saved_again, saved);
+}
+
+#[tokio::test]
+async fn test_generate_session_prep_unknown_session() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = generate_session_prep_impl(&db, "missing".to_string())
+        .await
+        .expect_err("Unknown session should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use common::{create_test_campaign, create_test_session, setup_test_db};
+use entity::timeline_events;
+use loreweaver_lib::commands::campaign_settings::{
+    update_campaign_settings_impl, CalendarSettings, CampaignSettings, InWorldDate,
+    TimelineSettings,
+};
+use loreweaver_lib::commands::session::{log_session_to_timeline_impl, save_session_summary_impl};
+use loreweaver_lib::ErrorCode;
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+fn date(year: i32, month: u32, day: u32) -> InWorldDate {
+    InWorldDate { year, month, day }
+}
+
+async fn find_event(db: &DatabaseConnection, id: &str) -> timeline_events::Model {
+    timeline_events::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .expect("Failed to query event")
+        .expect("Timeline event missing")
+}
+
+#[tokio::test]
+async fn test_summary_not_logged_by_default() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let saved = save_session_summary_impl(
+        &db,
+        session.id.clone(),
+        "The party reached Bree.".to_string(),
+        None,
+    )
+    .await
+    .expect("Failed to save summary");
+    assert_eq!(saved.summary.as_deref(), Some("The party reached Bree."));
+    assert_eq!(saved.timeline_event_id, None);
+}
+
+#[tokio::test]
+async fn test_summary_logged_on_current_date_and_kept_in_sync() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    let settings = CampaignSettings {
+        calendar: CalendarSettings {
+            today: Some(date(3018, 9, 29)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    update_campaign_settings_impl(&db, campaign.id.clone(), settings)
+        .await
+        .expect("Failed to update settings");
+
+    let saved = save_session_summary_impl(
+        &db,
+        session.id.clone(),
+        "The party reached Bree.".to_string(),
+        Some(true),
+    )
+    .await
+    .expect("Failed to save summary");
+    let event_id = saved.timeline_event_id.expect("Expected a timeline event");
+    let event = find_event(&db, &event_id).await;
+    assert_eq!(event.title, "Session 1");
+    assert_eq!(event.date_display, "29 Month 9 3018");
+    assert_eq!(event.significance, "local");
+    assert_eq!(
+        event.description.as_deref(),
+        Some("The party reached Bree.")
+    );
+
+    // Later edits update the same event without being asked
+    let resaved = save_session_summary_impl(
+        &db,
+        session.id.clone(),
+        "The party reached Bree and met Strider.".to_string(),
+        None,
+    )
+    .await
+    .expect("Failed to save summary");
+    assert_eq!(
+        resaved.timeline_event_id.as_deref(),
+        Some(event_id.as_str())
+    );
+    let event = find_event(&db, &event_id).await;
+    assert_eq!(
+        event.description.as_deref(),
+        Some("The party reached Bree and met Strider.")
+    );
+    assert_eq!(event.date_display, "29 Month 9 3018");
+}
+
+#[tokio::test]
+async fn test_campaign_setting_logs_sessions_without_calendar_date() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let settings = CampaignSettings {
+        timeline: TimelineSettings {
+            log_sessions: true,
+            session_significance: Some("major".to_string()),
+        },
+        ..Default::default()
+    };
+    update_campaign_settings_impl(&db, campaign.id.clone(), settings)
+        .await
+        .expect("Failed to update settings");
+
+    let first = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+    let second = create_test_session(&db, &campaign.id, 2, None)
+        .await
+        .expect("Failed to create session");
+
+    let first = save_session_summary_impl(&db, first.id.clone(), "Arrival.".to_string(), None)
+        .await
+        .expect("Failed to save summary");
+    let second = save_session_summary_impl(&db, second.id.clone(), "Departure.".to_string(), None)
+        .await
+        .expect("Failed to save summary");
+
+    let first_event = find_event(&db, first.timeline_event_id.as_deref().unwrap()).await;
+    let second_event = find_event(&db, second.timeline_event_id.as_deref().unwrap()).await;
+    assert_eq!(first_event.significance, "major");
+    // Without a current date, later sessions land after earlier ones
+    assert!(second_event.sort_order > first_event.sort_order);
+    assert_eq!(second_event.date_display, "Session 2");
+}
+
+#[tokio::test]
+async fn test_log_session_to_timeline_moves_event() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let err = log_session_to_timeline_impl(&db, session.id.clone(), Some(date(1492, 13, 1)), None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let logged = log_session_to_timeline_impl(
+        &db,
+        session.id.clone(),
+        Some(date(1492, 1, 3)),
+        Some("minor".to_string()),
+    )
+    .await
+    .expect("Failed to log session");
+    let event_id = logged.timeline_event_id.expect("Expected a timeline event");
+
+    let moved = log_session_to_timeline_impl(&db, session.id.clone(), Some(date(1492, 2, 1)), None)
+        .await
+        .expect("Failed to move session event");
+    assert_eq!(moved.timeline_event_id.as_deref(), Some(event_id.as_str()));
+
+    let event = find_event(&db, &event_id).await;
+    assert_eq!(event.date_display, "1 Month 2 1492");
+    assert_eq!(event.significance, "minor");
+}
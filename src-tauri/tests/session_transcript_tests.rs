@@ -0,0 +1,204 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_item, create_test_location,
+    create_test_session, setup_test_db,
+};
+use loreweaver_lib::commands::character::add_character_alias_impl;
+use loreweaver_lib::commands::session_transcript::{
+    delete_session_transcript_impl, detect_transcript_mentions_impl, get_transcript_chunks_impl,
+    import_session_transcript_impl, list_session_transcripts_impl,
+};
+use loreweaver_lib::ErrorCode;
+use std::path::PathBuf;
+
+fn write_transcript(extension: &str, content: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("transcript-{}.{}", uuid::Uuid::new_v4(), extension));
+    std::fs::write(&path, content).expect("Failed to write transcript");
+    path
+}
+
+const VTT: &str = "WEBVTT
+
+1
+00:00:01.000 --> 00:00:04.000
+<v Sam>We row out to Gull Rock.</v>
+
+2
+00:00:05.000 --> 00:00:09.000
+<v Dana>Old Mother Hesk waits on the rock, clutching the Tide Pearl.</v>
+
+3
+00:01:10.000 --> 00:01:12.000
+<v Sam>I ask Hesk about the pearl.</v>
+";
+
+#[tokio::test]
+async fn test_import_vtt_transcript() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let path = write_transcript("vtt", VTT);
+    let transcript = import_session_transcript_impl(
+        &db,
+        session.id.clone(),
+        path.to_string_lossy().into_owned(),
+    )
+    .await
+    .expect("Failed to import transcript");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(transcript.campaign_id, campaign.id);
+    assert_eq!(transcript.format, "vtt");
+    assert!(transcript.file_name.ends_with(".vtt"));
+    assert_eq!(
+        transcript.content.lines().next(),
+        Some("[00:00:01] Sam: We row out to Gull Rock.")
+    );
+    assert_eq!(transcript.content.lines().count(), 3);
+
+    let chunks = get_transcript_chunks_impl(&db, transcript.id.clone(), None)
+        .await
+        .expect("Failed to chunk transcript");
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].start.as_deref(), Some("00:00:01"));
+    assert_eq!(chunks[0].end.as_deref(), Some("00:01:10"));
+
+    let err = get_transcript_chunks_impl(&db, transcript.id.clone(), Some(10))
+        .await
+        .expect_err("Tiny chunks should be rejected");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let listed = list_session_transcripts_impl(&db, session.id.clone())
+        .await
+        .expect("Failed to list transcripts");
+    assert_eq!(listed.len(), 1);
+
+    assert!(delete_session_transcript_impl(&db, transcript.id.clone())
+        .await
+        .expect("Failed to delete transcript"));
+    assert!(list_session_transcripts_impl(&db, session.id)
+        .await
+        .expect("Failed to list transcripts")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_transcript_mentions() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let hesk = create_test_character(&db, &campaign.id, "Old Mother Hesk")
+        .await
+        .expect("Failed to create character");
+    add_character_alias_impl(&db, hesk.id.clone(), "Hesk".to_string())
+        .await
+        .expect("Failed to add alias");
+    let rock = create_test_location(&db, &campaign.id, "Gull Rock", None)
+        .await
+        .expect("Failed to create location");
+    let pearl = create_test_item(&db, &campaign.id, "Tide Pearl", false)
+        .await
+        .expect("Failed to create item");
+    create_test_character(&db, &campaign.id, "Captain Vey")
+        .await
+        .expect("Failed to create character");
+
+    let path = write_transcript("vtt", VTT);
+    let transcript = import_session_transcript_impl(
+        &db,
+        session.id.clone(),
+        path.to_string_lossy().into_owned(),
+    )
+    .await
+    .expect("Failed to import transcript");
+    std::fs::remove_file(&path).ok();
+
+    let mentions = detect_transcript_mentions_impl(&db, transcript.id)
+        .await
+        .expect("Failed to detect mentions");
+    let found: Vec<(&str, usize)> = mentions
+        .iter()
+        .map(|m| (m.entity_id.as_str(), m.occurrences))
+        .collect();
+    // The full name once plus the "Hesk" alias twice, counting the one inside the full name
+    assert_eq!(
+        found,
+        vec![
+            (hesk.id.as_str(), 3),
+            (rock.id.as_str(), 1),
+            (pearl.id.as_str(), 1)
+        ]
+    );
+    assert_eq!(mentions[0].entity_type, "character");
+}
+
+#[tokio::test]
+async fn test_import_plain_text_transcript() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let session = create_test_session(&db, &campaign.id, 1, None)
+        .await
+        .expect("Failed to create session");
+
+    let path = write_transcript(
+        "txt",
+        "\r\nGM: You arrive at dusk.\r\nSam: We make camp.\r\n",
+    );
+    let transcript = import_session_transcript_impl(
+        &db,
+        session.id.clone(),
+        path.to_string_lossy().into_owned(),
+    )
+    .await
+    .expect("Failed to import transcript");
+    std::fs::remove_file(&path).ok();
+    assert_eq!(transcript.format, "text");
+    assert_eq!(
+        transcript.content,
+        "GM: You arrive at dusk.\nSam: We make camp."
+    );
+    assert_eq!(transcript.word_count, 9);
+
+    let path = write_transcript("mp3", "not text");
+    let err = import_session_transcript_impl(
+        &db,
+        session.id.clone(),
+        path.to_string_lossy().into_owned(),
+    )
+    .await
+    .expect_err("Audio files should be rejected");
+    std::fs::remove_file(&path).ok();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let path = write_transcript("txt", "  \n ");
+    let err = import_session_transcript_impl(&db, session.id, path.to_string_lossy().into_owned())
+        .await
+        .expect_err("Empty transcripts should be rejected");
+    std::fs::remove_file(&path).ok();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = import_session_transcript_impl(&db, "missing".to_string(), "x.txt".to_string())
+        .await
+        .expect_err("Unknown session should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
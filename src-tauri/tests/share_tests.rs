@@ -0,0 +1,188 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::character::update_character_impl;
+use loreweaver_lib::commands::share::{build_player_snapshot_impl, export_player_snapshot_impl};
+use loreweaver_lib::share::{render_snapshot_html, SnapshotSelection};
+use loreweaver_lib::AppError;
+
+#[tokio::test]
+async fn test_snapshot_strips_gm_only_fields() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Saruman")
+        .await
+        .expect("Failed to create character");
+    update_character_impl(
+        &db,
+        character.id.clone(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some("Wants the Ring".to_string()),
+        Some("Secretly allied with Sauron".to_string()),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update character");
+
+    let snapshot =
+        build_player_snapshot_impl(&db, campaign.id.clone(), SnapshotSelection::default())
+            .await
+            .expect("Failed to build snapshot");
+
+    let entity = snapshot
+        .entities
+        .iter()
+        .find(|e| e.id == character.id)
+        .expect("Character missing from snapshot");
+    assert_eq!(entity.description.as_deref(), Some("A test character"));
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert!(!json.contains("Secretly allied"));
+    assert!(!json.contains("Wants the Ring"));
+}
+
+#[tokio::test]
+async fn test_snapshot_strips_gm_blocks() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Strider")
+        .await
+        .expect("Failed to create character");
+    update_character_impl(
+        &db,
+        character.id.clone(),
+        None,
+        None,
+        None,
+        None,
+        Some("A ranger in a dark cloak.\n\n```gm\nHeir of Isildur.\n```\n".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update character");
+
+    let snapshot =
+        build_player_snapshot_impl(&db, campaign.id.clone(), SnapshotSelection::default())
+            .await
+            .expect("Failed to build snapshot");
+
+    let entity = snapshot
+        .entities
+        .iter()
+        .find(|e| e.id == character.id)
+        .expect("Character missing from snapshot");
+    assert_eq!(
+        entity.description.as_deref(),
+        Some("A ranger in a dark cloak.")
+    );
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert!(!json.contains("Isildur"));
+    let html = render_snapshot_html(&snapshot);
+    assert!(html.contains("A ranger in a dark cloak."));
+    assert!(!html.contains("Isildur"));
+}
+
+#[tokio::test]
+async fn test_snapshot_respects_selection() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let gandalf = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    create_test_character(&db, &campaign.id, "Frodo")
+        .await
+        .expect("Failed to create character");
+    create_test_location(&db, &campaign.id, "Shire", None)
+        .await
+        .expect("Failed to create location");
+
+    let by_type = build_player_snapshot_impl(
+        &db,
+        campaign.id.clone(),
+        SnapshotSelection {
+            entity_types: vec!["location".to_string()],
+            entity_ids: vec![],
+        },
+    )
+    .await
+    .expect("Failed to build snapshot");
+    assert_eq!(by_type.entities.len(), 1);
+    assert_eq!(by_type.entities[0].name, "Shire");
+
+    let by_id = build_player_snapshot_impl(
+        &db,
+        campaign.id.clone(),
+        SnapshotSelection {
+            entity_types: vec![],
+            entity_ids: vec![gandalf.id.clone()],
+        },
+    )
+    .await
+    .expect("Failed to build snapshot");
+    assert_eq!(by_id.entities.len(), 1);
+    assert_eq!(by_id.entities[0].id, gandalf.id);
+}
+
+#[tokio::test]
+async fn test_export_snapshot_writes_static_site() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let dir = std::env::temp_dir().join(format!("snapshot-{}", uuid::Uuid::new_v4()));
+    let export = export_player_snapshot_impl(
+        &db,
+        campaign.id.clone(),
+        SnapshotSelection::default(),
+        dir.to_string_lossy().to_string(),
+    )
+    .await
+    .expect("Failed to export snapshot");
+
+    assert_eq!(export.entity_count, 1);
+    let html = std::fs::read_to_string(dir.join("index.html")).unwrap();
+    assert!(html.contains("Gandalf"));
+    assert!(dir.join("snapshot.json").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_snapshot_missing_campaign() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let result =
+        build_player_snapshot_impl(&db, "missing".to_string(), SnapshotSelection::default()).await;
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
@@ -0,0 +1,204 @@
+mod common;
+
+use common::{create_test_campaign, create_test_tag, setup_test_db};
+use loreweaver_lib::commands::snippet::{
+    create_snippet_impl, delete_snippet_impl, get_snippet_impl, list_snippets_impl,
+    search_snippets_impl, update_snippet_impl, SnippetResponse,
+};
+use loreweaver_lib::commands::tag::add_entity_tag_impl;
+use loreweaver_lib::commands::validation::{CreateSnippetInput, UpdateSnippetInput};
+use loreweaver_lib::ErrorCode;
+use sea_orm::DatabaseConnection;
+
+async fn create_snippet(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    name: &str,
+    snippet_type: &str,
+    content: &str,
+) -> SnippetResponse {
+    create_snippet_impl(
+        db,
+        CreateSnippetInput {
+            name: name.to_string(),
+            campaign_id: campaign_id.to_string(),
+            snippet_type: snippet_type.to_string(),
+            content: Some(content.to_string()),
+        },
+    )
+    .await
+    .expect("Failed to create snippet")
+}
+
+#[tokio::test]
+async fn test_snippet_crud() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let snippet = create_snippet(
+        &db,
+        &campaign.id,
+        "Tavern Entrance",
+        "read_aloud",
+        "Smoke curls from the hearth.",
+    )
+    .await;
+    assert_eq!(snippet.snippet_type, "read_aloud");
+
+    let updated = update_snippet_impl(
+        &db,
+        snippet.id.clone(),
+        UpdateSnippetInput {
+            name: Some("The Gull's Common Room".to_string()),
+            snippet_type: None,
+            content: None,
+        },
+    )
+    .await
+    .expect("Failed to update snippet");
+    assert_eq!(updated.name, "The Gull's Common Room");
+    assert_eq!(
+        updated.content.as_deref(),
+        Some("Smoke curls from the hearth.")
+    );
+
+    let fetched = get_snippet_impl(&db, snippet.id.clone())
+        .await
+        .expect("Failed to get snippet");
+    assert_eq!(fetched.name, "The Gull's Common Room");
+
+    assert!(delete_snippet_impl(&db, snippet.id.clone())
+        .await
+        .expect("Failed to delete snippet"));
+    let snippets = list_snippets_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list snippets");
+    assert!(snippets.is_empty());
+
+    let err = get_snippet_impl(&db, snippet.id.clone())
+        .await
+        .expect_err("Deleted snippet should be gone");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_create_snippet_rejects_unknown_type() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let err = create_snippet_impl(
+        &db,
+        CreateSnippetInput {
+            name: "Shop Blurb".to_string(),
+            campaign_id: campaign.id.clone(),
+            snippet_type: "poem".to_string(),
+            content: None,
+        },
+    )
+    .await
+    .expect_err("Unknown snippet type should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
+
+#[tokio::test]
+async fn test_search_snippets() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let doc = serde_json::json!({
+        "type": "doc",
+        "content": [{
+            "type": "paragraph",
+            "content": [{"type": "text", "text": "Dusty shelves of potions line the walls."}]
+        }]
+    });
+    let apothecary = create_snippet(
+        &db,
+        &campaign.id,
+        "Apothecary Counter",
+        "shop",
+        &doc.to_string(),
+    )
+    .await;
+    let potion_shop = create_snippet(
+        &db,
+        &campaign.id,
+        "Potion Seller",
+        "shop",
+        "Prices are steep.",
+    )
+    .await;
+    let crypt = create_snippet(
+        &db,
+        &campaign.id,
+        "Crypt Door",
+        "read_aloud",
+        "Cold air seeps out.",
+    )
+    .await;
+
+    let results = search_snippets_impl(&db, campaign.id.clone(), "potion".to_string(), None, None)
+        .await
+        .expect("Failed to search snippets");
+    let ids: Vec<_> = results.iter().map(|s| s.id.as_str()).collect();
+    // Name matches rank ahead of text matches; editor JSON itself never matches
+    assert_eq!(ids, vec![potion_shop.id.as_str(), apothecary.id.as_str()]);
+    let results = search_snippets_impl(
+        &db,
+        campaign.id.clone(),
+        "paragraph".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to search snippets");
+    assert!(results.is_empty());
+
+    // Tags count as text for matching
+    let dungeon = create_test_tag(&db, &campaign.id, "Dungeon")
+        .await
+        .expect("Failed to create tag");
+    add_entity_tag_impl(
+        &db,
+        dungeon.id.clone(),
+        "snippet".to_string(),
+        crypt.id.clone(),
+    )
+    .await
+    .expect("Failed to tag snippet");
+    let results = search_snippets_impl(
+        &db,
+        campaign.id.clone(),
+        "dungeon cold".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to search snippets");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, crypt.id);
+
+    let results = search_snippets_impl(
+        &db,
+        campaign.id.clone(),
+        String::new(),
+        Some("shop".to_string()),
+        Some(1),
+    )
+    .await
+    .expect("Failed to search snippets");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, apothecary.id);
+}
@@ -0,0 +1,252 @@
+mod common;
+
+use common::{create_test_campaign, setup_test_db};
+use loreweaver_lib::commands::character::get_character_impl;
+use loreweaver_lib::commands::item::get_item_impl;
+use loreweaver_lib::commands::srd::{
+    cache_srd_documents, get_srd_entry_impl, import_srd_entry_impl, search_srd_impl,
+};
+use loreweaver_lib::srd::client::Open5eClient;
+use loreweaver_lib::srd::SrdKind;
+use loreweaver_lib::ErrorCode;
+use serde_json::json;
+
+/// Nothing listens on the discard port, so every request fails fast and only
+/// cached entries are available
+fn offline_client() -> Open5eClient {
+    Open5eClient::new("http://127.0.0.1:9").expect("Failed to build client")
+}
+
+#[tokio::test]
+async fn test_import_cached_monster_as_character() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    cache_srd_documents(
+        &db,
+        SrdKind::Monster,
+        vec![json!({
+            "slug": "goblin",
+            "name": "Goblin",
+            "desc": "Small, black-hearted humanoids.",
+            "size": "Small",
+            "type": "humanoid",
+            "subtype": "goblinoid",
+            "armor_class": 15,
+            "hit_points": 7,
+            "dexterity": 14,
+            "challenge_rating": "1/4",
+            "actions": [{"name": "Scimitar", "desc": "+4 to hit."}]
+        })],
+    )
+    .await
+    .expect("Failed to cache document");
+
+    let imported = import_srd_entry_impl(
+        &db,
+        &offline_client(),
+        campaign.id.clone(),
+        "monster".to_string(),
+        "goblin".to_string(),
+    )
+    .await
+    .expect("Failed to import monster");
+    assert_eq!(imported.entity_type, "character");
+    assert_eq!(imported.name, "Goblin");
+
+    let character = get_character_impl(&db, imported.entity_id.clone())
+        .await
+        .expect("Failed to get character");
+    assert_eq!(character.campaign_id, campaign.id);
+    assert_eq!(character.lineage.as_deref(), Some("humanoid"));
+    assert_eq!(
+        character.description.as_deref(),
+        Some("Small, black-hearted humanoids.")
+    );
+    let stat_block: serde_json::Value =
+        serde_json::from_str(character.stat_block_json.as_deref().expect("No stat block"))
+            .expect("Stat block is not JSON");
+    assert_eq!(stat_block["system"], "5e");
+    assert_eq!(stat_block["creature_type"], "humanoid (goblinoid)");
+    assert_eq!(stat_block["abilities"]["dex"], 14);
+    assert_eq!(stat_block["actions"][0]["name"], "Scimitar");
+}
+
+#[tokio::test]
+async fn test_import_cached_spell_and_magic_item_as_items() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    cache_srd_documents(
+        &db,
+        SrdKind::Spell,
+        vec![json!({
+            "slug": "fireball",
+            "name": "Fireball",
+            "level": "3rd-level",
+            "level_int": 3,
+            "school": "evocation",
+            "desc": "A bright streak flashes."
+        })],
+    )
+    .await
+    .expect("Failed to cache document");
+    cache_srd_documents(
+        &db,
+        SrdKind::MagicItem,
+        vec![json!({
+            "slug": "bag-of-holding",
+            "name": "Bag of Holding",
+            "type": "Wondrous item",
+            "rarity": "uncommon",
+            "requires_attunement": "",
+            "desc": "This bag has an interior space considerably larger than its outside."
+        })],
+    )
+    .await
+    .expect("Failed to cache document");
+
+    let scroll = import_srd_entry_impl(
+        &db,
+        &offline_client(),
+        campaign.id.clone(),
+        "spell".to_string(),
+        "fireball".to_string(),
+    )
+    .await
+    .expect("Failed to import spell");
+    let scroll = get_item_impl(&db, scroll.entity_id)
+        .await
+        .expect("Failed to get item");
+    assert_eq!(scroll.name, "Spell Scroll (Fireball)");
+    assert_eq!(scroll.item_type, "scroll");
+    assert_eq!(scroll.rarity.as_deref(), Some("uncommon"));
+
+    let bag = import_srd_entry_impl(
+        &db,
+        &offline_client(),
+        campaign.id.clone(),
+        "magic_item".to_string(),
+        "bag-of-holding".to_string(),
+    )
+    .await
+    .expect("Failed to import magic item");
+    assert_eq!(bag.entity_type, "item");
+    let bag = get_item_impl(&db, bag.entity_id)
+        .await
+        .expect("Failed to get item");
+    assert_eq!(bag.item_type, "wondrous");
+    assert!(!bag.requires_attunement);
+}
+
+#[tokio::test]
+async fn test_search_falls_back_to_cache() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    cache_srd_documents(
+        &db,
+        SrdKind::Monster,
+        vec![
+            json!({"slug": "goblin", "name": "Goblin", "size": "Small", "type": "humanoid", "challenge_rating": "1/4"}),
+            json!({"slug": "hobgoblin", "name": "Hobgoblin"}),
+            json!({"slug": "orc", "name": "Orc"}),
+            // No slug, so it can't be looked up again and isn't cached
+            json!({"name": "Goblin Boss"}),
+        ],
+    )
+    .await
+    .expect("Failed to cache documents");
+
+    let response = search_srd_impl(
+        &db,
+        &offline_client(),
+        "monster".to_string(),
+        "goblin".to_string(),
+        None,
+    )
+    .await
+    .expect("Failed to search cache");
+    assert!(response.offline);
+    let names: Vec<_> = response.results.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Goblin", "Hobgoblin"]);
+    assert_eq!(
+        response.results[0].summary.as_deref(),
+        Some("Small humanoid, CR 1/4")
+    );
+
+    // Nothing cached and nothing reachable
+    let err = search_srd_impl(
+        &db,
+        &offline_client(),
+        "spell".to_string(),
+        "fireball".to_string(),
+        None,
+    )
+    .await
+    .expect_err("Search without network or cache should fail");
+    assert_eq!(err.code(), ErrorCode::Internal);
+}
+
+#[tokio::test]
+async fn test_srd_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = search_srd_impl(
+        &db,
+        &offline_client(),
+        "vehicle".to_string(),
+        "cart".to_string(),
+        None,
+    )
+    .await
+    .expect_err("Unknown kind should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = search_srd_impl(
+        &db,
+        &offline_client(),
+        "monster".to_string(),
+        "  ".to_string(),
+        None,
+    )
+    .await
+    .expect_err("Empty query should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = import_srd_entry_impl(
+        &db,
+        &offline_client(),
+        "missing".to_string(),
+        "monster".to_string(),
+        "goblin".to_string(),
+    )
+    .await
+    .expect_err("Unknown campaign should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    cache_srd_documents(
+        &db,
+        SrdKind::Spell,
+        vec![json!({"slug": "light", "name": "Light"})],
+    )
+    .await
+    .expect("Failed to cache document");
+    let entry = get_srd_entry_impl(
+        &db,
+        &offline_client(),
+        "spell".to_string(),
+        "light".to_string(),
+    )
+    .await
+    .expect("Cached entry should not need the network");
+    assert_eq!(entry.name, "Light");
+}
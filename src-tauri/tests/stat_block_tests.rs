@@ -0,0 +1,166 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, setup_test_db};
+use loreweaver_lib::commands::character::update_character_impl;
+use loreweaver_lib::commands::stat_block::render_stat_block_impl;
+use loreweaver_lib::ErrorCode;
+use sea_orm::DatabaseConnection;
+
+async fn set_stat_block(db: &DatabaseConnection, id: &str, stat_block: serde_json::Value) {
+    update_character_impl(
+        db,
+        id.to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(stat_block.to_string()),
+    )
+    .await
+    .expect("Failed to set stat block");
+}
+
+#[tokio::test]
+async fn test_render_dnd5e_stat_block_as_markdown() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let ogre = create_test_character(&db, &campaign.id, "Ogre")
+        .await
+        .expect("Failed to create character");
+
+    set_stat_block(
+        &db,
+        &ogre.id,
+        serde_json::json!({
+            "system": "5e",
+            "size": "Large",
+            "creature_type": "giant",
+            "alignment": "chaotic evil",
+            "armor_class": 11,
+            "armor_note": "hide armor",
+            "hit_points": 59,
+            "hit_dice": "7d10+21",
+            "abilities": {"str": 19, "dex": 8, "con": 16, "int": 5, "wis": 7, "cha": 7},
+            "challenge_rating": "2",
+            "actions": [{"name": "Greatclub", "description": "+6 to hit, 2d8+4 bludgeoning."}]
+        }),
+    )
+    .await;
+
+    let markdown = render_stat_block_impl(&db, ogre.id.clone(), "markdown".to_string())
+        .await
+        .expect("Failed to render stat block");
+    assert!(markdown.starts_with("### Ogre"));
+    assert!(markdown.contains("*Large giant, chaotic evil*"));
+    assert!(markdown.contains("**Armor Class** 11 (hide armor)"));
+    assert!(markdown.contains("| STR | DEX | CON | INT | WIS | CHA |"));
+    assert!(markdown.contains("| 19 (+4) | 8 (-1) | 16 (+3) | 5 (-3) | 7 (-2) | 7 (-2) |"));
+    assert!(markdown.contains("#### Actions"));
+    assert!(markdown.contains("***Greatclub.*** +6 to hit, 2d8+4 bludgeoning."));
+}
+
+#[tokio::test]
+async fn test_render_pf2e_and_legacy_stat_blocks_as_text() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let skeleton = create_test_character(&db, &campaign.id, "Skeleton Guard")
+        .await
+        .expect("Failed to create character");
+    let bandit = create_test_character(&db, &campaign.id, "Bandit")
+        .await
+        .expect("Failed to create character");
+
+    set_stat_block(
+        &db,
+        &skeleton.id,
+        serde_json::json!({
+            "system": "pf2e",
+            "level": -1,
+            "traits": ["mindless", "undead"],
+            "armor_class": 16,
+            "fortitude": 2,
+            "reflex": 8,
+            "will": 2,
+            "hit_points": 4,
+            "immunities": ["death effects", "poison"],
+            "resistances": {"cold": 5}
+        }),
+    )
+    .await;
+    // Stat blocks saved before systems existed have no `system` key
+    set_stat_block(
+        &db,
+        &bandit.id,
+        serde_json::json!({"armor_class": 12, "hit_points": 11}),
+    )
+    .await;
+
+    let text = render_stat_block_impl(&db, skeleton.id.clone(), "text".to_string())
+        .await
+        .expect("Failed to render stat block");
+    assert!(text.starts_with("SKELETON GUARD (CREATURE -1)\nMindless, Undead"));
+    assert!(text.contains("AC: 16; Fort +2, Ref +8, Will +2"));
+    assert!(text.contains("Immunities: death effects, poison"));
+    assert!(text.contains("Resistances: cold 5"));
+
+    let text = render_stat_block_impl(&db, bandit.id.clone(), "text".to_string())
+        .await
+        .expect("Failed to render stat block");
+    assert!(text.contains("Armor Class: 12"));
+    assert!(text.contains("Hit Points: 11"));
+}
+
+#[tokio::test]
+async fn test_stat_block_validation_on_save_and_render_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+
+    let err = render_stat_block_impl(&db, mira.id.clone(), "text".to_string())
+        .await
+        .expect_err("Character without a stat block should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = update_character_impl(
+        &db,
+        mira.id.clone(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(r#"{"system": "5e", "challenge_rating": "31"}"#.to_string()),
+    )
+    .await
+    .expect_err("Unknown challenge rating should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    set_stat_block(&db, &mira.id, serde_json::json!({"system": "generic"})).await;
+    let err = render_stat_block_impl(&db, mira.id.clone(), "html".to_string())
+        .await
+        .expect_err("Unknown format should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
@@ -0,0 +1,521 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_location, create_test_tag,
+    setup_test_db,
+};
+use loreweaver_lib::commands::character::{delete_character_impl, update_character_impl};
+use loreweaver_lib::commands::sync::{
+    export_changes_since_impl, get_changes_since_impl, get_sync_settings_impl,
+    update_sync_settings_impl, ChangeExport,
+};
+use loreweaver_lib::commands::tag::{
+    add_entity_tag_impl, get_entity_tags_impl, remove_entity_tag_impl,
+};
+use loreweaver_lib::sync::protocol::{ChangeOperation, ChangeRecord};
+use loreweaver_lib::sync::{apply_remote_changes, collect_local_changes};
+use loreweaver_lib::AppError;
+
+#[tokio::test]
+async fn test_changes_since_reports_created_entities() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let since = chrono::Utc::now().to_rfc3339();
+
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let location = create_test_location(&db, &campaign.id, "Rivendell", None)
+        .await
+        .expect("Failed to create location");
+
+    let changes = get_changes_since_impl(&db, campaign.id.clone(), since)
+        .await
+        .expect("Failed to get changes");
+
+    assert_eq!(changes.created.len(), 2);
+    assert!(changes
+        .created
+        .iter()
+        .any(|c| c.entity_type == "character" && c.entity_id == character.id));
+    assert!(changes
+        .created
+        .iter()
+        .any(|c| c.entity_type == "location" && c.entity_id == location.id));
+    assert!(changes.updated.is_empty());
+    assert!(changes.deleted.is_empty());
+}
+
+#[tokio::test]
+async fn test_changes_since_reports_updated_entities() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let since = chrono::Utc::now().to_rfc3339();
+
+    update_character_impl(
+        &db,
+        character.id.clone(),
+        Some("Gandalf the White".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update character");
+
+    let changes = get_changes_since_impl(&db, campaign.id.clone(), since)
+        .await
+        .expect("Failed to get changes");
+
+    assert!(changes.created.is_empty());
+    assert_eq!(changes.updated.len(), 1);
+    assert_eq!(changes.updated[0].entity_id, character.id);
+    assert_eq!(
+        changes.updated[0].name,
+        Some("Gandalf the White".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_changes_since_reports_deleted_entities() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Boromir")
+        .await
+        .expect("Failed to create character");
+
+    let since = chrono::Utc::now().to_rfc3339();
+
+    delete_character_impl(&db, character.id.clone())
+        .await
+        .expect("Failed to delete character");
+
+    let changes = get_changes_since_impl(&db, campaign.id.clone(), since)
+        .await
+        .expect("Failed to get changes");
+
+    assert_eq!(changes.deleted.len(), 1);
+    assert_eq!(changes.deleted[0].entity_type, "character");
+    assert_eq!(changes.deleted[0].entity_id, character.id);
+}
+
+#[tokio::test]
+async fn test_changes_since_watermark_excludes_seen_changes() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    create_test_character(&db, &campaign.id, "Frodo")
+        .await
+        .expect("Failed to create character");
+
+    let first = get_changes_since_impl(&db, campaign.id.clone(), campaign.created_at.to_rfc3339())
+        .await
+        .expect("Failed to get changes");
+    let second = get_changes_since_impl(&db, campaign.id.clone(), first.watermark)
+        .await
+        .expect("Failed to get changes");
+
+    assert!(second.created.is_empty());
+    assert!(second.updated.is_empty());
+    assert!(second.deleted.is_empty());
+}
+
+#[tokio::test]
+async fn test_changes_since_rejects_invalid_timestamp() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let result = get_changes_since_impl(&db, campaign.id, "yesterday".to_string()).await;
+
+    assert!(matches!(result, Err(AppError::Validation(_))));
+}
+
+#[tokio::test]
+async fn test_sync_settings_default_to_disabled() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let settings = get_sync_settings_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to get sync settings");
+
+    assert!(!settings.enabled);
+    assert!(settings.server_url.is_none());
+    assert!(!settings.has_auth_token);
+}
+
+#[tokio::test]
+async fn test_update_sync_settings_upserts() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    update_sync_settings_impl(
+        &db,
+        campaign.id.clone(),
+        true,
+        Some("https://sync.example.com".to_string()),
+        Some("secret-token".to_string()),
+    )
+    .await
+    .expect("Failed to enable sync");
+
+    // Omitting the token keeps the saved one
+    let settings = update_sync_settings_impl(
+        &db,
+        campaign.id.clone(),
+        false,
+        Some("https://sync.example.com".to_string()),
+        None,
+    )
+    .await
+    .expect("Failed to update sync settings");
+
+    assert!(!settings.enabled);
+    assert_eq!(
+        settings.server_url.as_deref(),
+        Some("https://sync.example.com")
+    );
+    assert!(settings.has_auth_token);
+}
+
+#[tokio::test]
+async fn test_enabling_sync_requires_server_url() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let result = update_sync_settings_impl(&db, campaign.id.clone(), true, None, None).await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    let result = update_sync_settings_impl(
+        &db,
+        campaign.id.clone(),
+        true,
+        Some("ftp://example.com".to_string()),
+        None,
+    )
+    .await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+}
+
+#[tokio::test]
+async fn test_collect_local_changes_includes_rows_and_deletes() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let kept = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let removed = create_test_character(&db, &campaign.id, "Boromir")
+        .await
+        .expect("Failed to create character");
+    delete_character_impl(&db, removed.id.clone())
+        .await
+        .expect("Failed to delete character");
+
+    let (records, _) = collect_local_changes(&db, &campaign.id, None)
+        .await
+        .expect("Failed to collect changes");
+
+    let upsert = records
+        .iter()
+        .find(|r| r.entity_id == kept.id)
+        .expect("Missing upsert");
+    assert_eq!(upsert.operation, ChangeOperation::Upsert);
+    assert_eq!(
+        upsert.data.as_ref().and_then(|d| d.get("name")),
+        Some(&serde_json::json!("Gandalf"))
+    );
+
+    let delete = records
+        .iter()
+        .find(|r| r.entity_id == removed.id)
+        .expect("Missing delete");
+    assert_eq!(delete.operation, ChangeOperation::Delete);
+    assert!(delete.data.is_none());
+}
+
+#[tokio::test]
+async fn test_export_changes_since_chains_watermarks() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let gandalf = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let boromir = create_test_character(&db, &campaign.id, "Boromir")
+        .await
+        .expect("Failed to create character");
+    let path = std::env::temp_dir().join(format!("changes-{}.json", uuid::Uuid::new_v4()));
+    let path = path.to_string_lossy().to_string();
+    let read = || -> ChangeExport {
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap()
+    };
+
+    // With no watermark the whole campaign is exported
+    let first = export_changes_since_impl(&db, campaign.id.clone(), None, path.clone())
+        .await
+        .expect("Failed to export changes");
+    assert_eq!(first.upserted, 3);
+    assert_eq!(first.deleted, 0);
+    let export = read();
+    assert_eq!(export.watermark, first.watermark);
+    let mut sorted = export.changes.clone();
+    sorted.sort_by(|a, b| (&a.entity_type, &a.entity_id).cmp(&(&b.entity_type, &b.entity_id)));
+    assert_eq!(
+        export
+            .changes
+            .iter()
+            .map(|c| &c.entity_id)
+            .collect::<Vec<_>>(),
+        sorted.iter().map(|c| &c.entity_id).collect::<Vec<_>>()
+    );
+
+    update_character_impl(
+        &db,
+        gandalf.id.clone(),
+        Some("Gandalf the White".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to update character");
+    delete_character_impl(&db, boromir.id.clone())
+        .await
+        .expect("Failed to delete character");
+
+    let second = export_changes_since_impl(
+        &db,
+        campaign.id.clone(),
+        Some(first.watermark.clone()),
+        path.clone(),
+    )
+    .await
+    .expect("Failed to export changes");
+    assert_eq!(second.since, first.watermark);
+    assert_eq!((second.upserted, second.deleted), (1, 1));
+    let export = read();
+    let ops: Vec<_> = export
+        .changes
+        .iter()
+        .map(|c| (c.entity_id.as_str(), c.operation))
+        .collect();
+    assert!(ops.contains(&(gandalf.id.as_str(), ChangeOperation::Upsert)));
+    assert!(ops.contains(&(boromir.id.as_str(), ChangeOperation::Delete)));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn test_apply_remote_changes_last_writer_wins() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+
+    let (records, _) = collect_local_changes(&db, &campaign.id, None)
+        .await
+        .expect("Failed to collect changes");
+    let mut remote = records
+        .into_iter()
+        .find(|r| r.entity_id == character.id)
+        .expect("Missing character record");
+
+    // An older remote edit loses to the local row
+    let mut stale = remote.clone();
+    stale.modified_at = "2000-01-01T00:00:00+00:00".to_string();
+    stale.data.as_mut().unwrap()["name"] = serde_json::json!("Stale");
+
+    let outcome = apply_remote_changes(&db, &campaign.id, vec![stale])
+        .await
+        .expect("Failed to apply changes");
+    assert_eq!(outcome.upserted, 0);
+    assert_eq!(outcome.conflicts.len(), 1);
+
+    // A newer remote edit replaces it
+    let newer = (chrono::Utc::now() + chrono::Duration::seconds(5)).to_rfc3339();
+    remote.modified_at = newer.clone();
+    let data = remote.data.as_mut().unwrap();
+    data["name"] = serde_json::json!("Gandalf the White");
+    data["updated_at"] = serde_json::json!(newer);
+
+    let outcome = apply_remote_changes(&db, &campaign.id, vec![remote])
+        .await
+        .expect("Failed to apply changes");
+    assert_eq!(outcome.upserted, 1);
+    assert!(outcome.conflicts.is_empty());
+
+    let delete = ChangeRecord {
+        entity_type: "character".to_string(),
+        entity_id: character.id.clone(),
+        operation: ChangeOperation::Delete,
+        modified_at: (chrono::Utc::now() + chrono::Duration::seconds(10)).to_rfc3339(),
+        data: None,
+    };
+    let outcome = apply_remote_changes(&db, &campaign.id, vec![delete])
+        .await
+        .expect("Failed to apply changes");
+    assert_eq!(outcome.deleted, 1);
+}
+
+#[tokio::test]
+async fn test_apply_remote_changes_ignores_other_campaigns() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &other.id, "Saruman")
+        .await
+        .expect("Failed to create character");
+
+    let delete = ChangeRecord {
+        entity_type: "character".to_string(),
+        entity_id: character.id.clone(),
+        operation: ChangeOperation::Delete,
+        modified_at: (chrono::Utc::now() + chrono::Duration::seconds(10)).to_rfc3339(),
+        data: None,
+    };
+    let outcome = apply_remote_changes(&db, &campaign.id, vec![delete])
+        .await
+        .expect("Failed to apply changes");
+
+    assert_eq!(outcome.deleted, 0);
+    assert_eq!(outcome.skipped, 1);
+}
+
+#[tokio::test]
+async fn test_entity_tags_round_trip() {
+    let local = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&local, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&local, &campaign.id, "Gandalf")
+        .await
+        .expect("Failed to create character");
+    let tag = create_test_tag(&local, &campaign.id, "Wizard")
+        .await
+        .expect("Failed to create tag");
+    add_entity_tag_impl(
+        &local,
+        tag.id.clone(),
+        "character".to_string(),
+        character.id.clone(),
+    )
+    .await
+    .expect("Failed to tag character");
+
+    let (records, watermark) = collect_local_changes(&local, &campaign.id, None)
+        .await
+        .expect("Failed to collect changes");
+    let entity_tag_id = format!("{}:character:{}", tag.id, character.id);
+    assert!(records
+        .iter()
+        .any(|r| r.entity_type == "entity_tag" && r.entity_id == entity_tag_id));
+
+    let remote = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let outcome = apply_remote_changes(&remote, &campaign.id, records)
+        .await
+        .expect("Failed to apply changes");
+    assert_eq!(outcome.skipped, 0);
+    let tags = get_entity_tags_impl(&remote, "character".to_string(), character.id.clone())
+        .await
+        .expect("Failed to get entity tags");
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].id, tag.id);
+
+    // Untagging leaves a tombstone that removes the tag on the other side
+    remove_entity_tag_impl(
+        &local,
+        tag.id.clone(),
+        "character".to_string(),
+        character.id.clone(),
+    )
+    .await
+    .expect("Failed to untag character");
+    let (records, _) = collect_local_changes(&local, &campaign.id, Some(&watermark))
+        .await
+        .expect("Failed to collect changes");
+    let delete = records
+        .iter()
+        .find(|r| r.entity_type == "entity_tag")
+        .expect("Missing entity tag delete");
+    assert_eq!(delete.entity_id, entity_tag_id);
+    assert_eq!(delete.operation, ChangeOperation::Delete);
+
+    let outcome = apply_remote_changes(&remote, &campaign.id, records)
+        .await
+        .expect("Failed to apply changes");
+    assert_eq!(outcome.deleted, 1);
+    let tags = get_entity_tags_impl(&remote, "character".to_string(), character.id.clone())
+        .await
+        .expect("Failed to get entity tags");
+    assert!(tags.is_empty());
+}
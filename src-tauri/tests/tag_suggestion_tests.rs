@@ -0,0 +1,105 @@
+mod common;
+
+use common::{create_test_campaign, create_test_location, create_test_tag, setup_test_db};
+use loreweaver_lib::commands::location::update_location_impl;
+use loreweaver_lib::commands::tag::add_entity_tag_impl;
+use loreweaver_lib::commands::tag_suggestion::suggest_tags_impl;
+use loreweaver_lib::commands::validation::UpdateLocationInput;
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_suggest_tags_for_location() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let temple = create_test_location(&db, &campaign.id, "Old Temple", None)
+        .await
+        .expect("Failed to create location");
+
+    let gm_notes = r#"{"type":"doc","content":[{"type":"paragraph","content":[
+        {"type":"text","text":"A vampire sleeps below the altar."}
+    ]}]}"#;
+    update_location_impl(
+        &db,
+        temple.id.clone(),
+        UpdateLocationInput {
+            description: Some(
+                "Smugglers meet here, near the Harbor Ward, where a lich once ruled.".to_string(),
+            ),
+            gm_notes: Some(gm_notes.to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update location");
+
+    let ward = create_test_tag(&db, &campaign.id, "Harbor Ward")
+        .await
+        .expect("Failed to create tag");
+    let undead = create_test_tag(&db, &campaign.id, "Undead")
+        .await
+        .expect("Failed to create tag");
+    let religious = create_test_tag(&db, &campaign.id, "Religious")
+        .await
+        .expect("Failed to create tag");
+    create_test_tag(&db, &campaign.id, "Dragon")
+        .await
+        .expect("Failed to create tag");
+    add_entity_tag_impl(
+        &db,
+        religious.id.clone(),
+        "location".to_string(),
+        temple.id.clone(),
+    )
+    .await
+    .expect("Failed to tag location");
+
+    let suggestions = suggest_tags_impl(&db, "location".to_string(), temple.id.clone())
+        .await
+        .expect("Failed to suggest tags");
+
+    assert_eq!(suggestions.campaign_id, campaign.id);
+    assert!(suggestions.text.starts_with("Old Temple\n"));
+    assert!(suggestions.text.contains("A vampire sleeps"));
+
+    // Tags already on the entity aren't suggested again
+    let existing: Vec<(&str, &str)> = suggestions
+        .existing
+        .iter()
+        .map(|s| (s.tag.id.as_str(), s.matched.as_str()))
+        .collect();
+    assert_eq!(
+        existing,
+        vec![
+            (ward.id.as_str(), "Harbor Ward"),
+            (undead.id.as_str(), "vampire")
+        ]
+    );
+
+    let new_tags: Vec<(&str, &str)> = suggestions
+        .new_tags
+        .iter()
+        .map(|s| (s.name.as_str(), s.matched.as_str()))
+        .collect();
+    assert_eq!(new_tags, vec![("criminal", "smuggler")]);
+}
+
+#[tokio::test]
+async fn test_suggest_tags_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let err = suggest_tags_impl(&db, "location".to_string(), "missing".to_string())
+        .await
+        .expect_err("Unknown entity should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = suggest_tags_impl(&db, "tag".to_string(), "missing".to_string())
+        .await
+        .expect_err("Tags have no text to analyze");
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
@@ -3,8 +3,9 @@ mod common;
 use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
 use loreweaver_lib::commands::tag::{
     add_entity_tag_impl, create_tag_impl, delete_tag_impl, get_entity_tags_impl, get_tag_impl,
-    list_tags_impl, remove_entity_tag_impl,
+    get_tags_for_entities_impl, list_tags_impl, remove_entity_tag_impl,
 };
+use loreweaver_lib::ErrorCode;
 
 #[tokio::test]
 async fn test_create_tag() {
@@ -46,6 +47,26 @@ async fn test_create_tag_without_color() {
     assert_eq!(tag.color, None);
 }
 
+#[tokio::test]
+async fn test_create_duplicate_tag_is_conflict() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    create_tag_impl(&db, campaign.id.clone(), "Villain".to_string(), None)
+        .await
+        .expect("Failed to create tag");
+
+    let err = create_tag_impl(&db, campaign.id.clone(), "Villain".to_string(), None)
+        .await
+        .expect_err("Duplicate tag name should fail");
+
+    assert_eq!(err.code(), ErrorCode::Conflict);
+}
+
 #[tokio::test]
 async fn test_get_tag() {
     let db = setup_test_db()
@@ -505,3 +526,79 @@ async fn test_tag_lifecycle() {
     let result = get_tag_impl(&db, tag.id).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_get_tags_for_entities() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Aragorn")
+        .await
+        .expect("Failed to create character");
+    let untagged = create_test_character(&db, &campaign.id, "Boromir")
+        .await
+        .expect("Failed to create character");
+    let location = create_test_location(&db, &campaign.id, "Rivendell", None)
+        .await
+        .expect("Failed to create location");
+
+    let ranger = create_tag_impl(&db, campaign.id.clone(), "Ranger".to_string(), None)
+        .await
+        .expect("Failed to create tag");
+    let king = create_tag_impl(&db, campaign.id.clone(), "King".to_string(), None)
+        .await
+        .expect("Failed to create tag");
+
+    for (tag_id, entity_type, entity_id) in [
+        (&ranger.id, "character", &character.id),
+        (&king.id, "character", &character.id),
+        (&ranger.id, "location", &location.id),
+    ] {
+        add_entity_tag_impl(
+            &db,
+            tag_id.clone(),
+            entity_type.to_string(),
+            entity_id.clone(),
+        )
+        .await
+        .expect("Failed to add tag");
+    }
+
+    let result = get_tags_for_entities_impl(
+        &db,
+        vec![
+            ("location".to_string(), location.id.clone()),
+            ("character".to_string(), untagged.id.clone()),
+            ("character".to_string(), character.id.clone()),
+        ],
+    )
+    .await
+    .expect("Failed to get tags for entities");
+
+    // One entry per requested entity, in request order
+    assert_eq!(result.len(), 3);
+    assert_eq!(result[0].entity_id, location.id);
+    assert_eq!(result[0].tags.len(), 1);
+    assert_eq!(result[0].tags[0].name, "Ranger");
+    assert_eq!(result[1].entity_id, untagged.id);
+    assert!(result[1].tags.is_empty());
+    assert_eq!(result[2].entity_id, character.id);
+    let names: Vec<&str> = result[2].tags.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["King", "Ranger"]);
+}
+
+#[tokio::test]
+async fn test_get_tags_for_entities_empty() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let result = get_tags_for_entities_impl(&db, vec![])
+        .await
+        .expect("Failed to get tags for entities");
+
+    assert!(result.is_empty());
+}
@@ -0,0 +1,249 @@
+mod common;
+
+use common::{create_test_campaign, setup_test_db};
+use loreweaver_lib::commands::timeline::{
+    create_timeline_event_impl, list_timeline_events_impl, TimelineEventResponse,
+};
+use loreweaver_lib::commands::timeline_branch::{
+    compare_timeline_branches_impl, create_timeline_branch_impl, delete_timeline_branch_impl,
+    list_timeline_branches_impl,
+};
+use loreweaver_lib::ErrorCode;
+use sea_orm::DatabaseConnection;
+
+async fn event(
+    db: &DatabaseConnection,
+    campaign_id: &str,
+    title: &str,
+    sort_order: i64,
+    branch_id: Option<&str>,
+) -> TimelineEventResponse {
+    create_timeline_event_impl(
+        db,
+        campaign_id.to_string(),
+        title.to_string(),
+        format!("Year {}", sort_order),
+        Some(sort_order),
+        None,
+        None,
+        branch_id.map(str::to_string),
+    )
+    .await
+    .expect("Failed to create timeline event")
+}
+
+fn titles(events: &[TimelineEventResponse]) -> Vec<&str> {
+    events.iter().map(|e| e.title.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_branch_reads_parent_line_up_to_fork() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    event(&db, &campaign.id, "Founding", 1, None).await;
+    let coronation = event(&db, &campaign.id, "Coronation", 2, None).await;
+    event(&db, &campaign.id, "Peace Treaty", 3, None).await;
+
+    let prophecy = create_timeline_branch_impl(
+        &db,
+        campaign.id.clone(),
+        "The Dark Prophecy".to_string(),
+        None,
+        None,
+        Some(coronation.id.clone()),
+    )
+    .await
+    .expect("Failed to create branch");
+    event(&db, &campaign.id, "The King Falls", 3, Some(&prophecy.id)).await;
+
+    // The main timeline is the default and never shows branch events
+    let main = list_timeline_events_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to list events");
+    assert_eq!(
+        titles(&main),
+        vec!["Founding", "Coronation", "Peace Treaty"]
+    );
+
+    let branch = list_timeline_events_impl(&db, campaign.id.clone(), Some(prophecy.id.clone()))
+        .await
+        .expect("Failed to list events");
+    assert_eq!(
+        titles(&branch),
+        vec!["Founding", "Coronation", "The King Falls"]
+    );
+
+    // Branches of branches inherit the whole chain
+    let deeper = create_timeline_branch_impl(
+        &db,
+        campaign.id.clone(),
+        "Exile".to_string(),
+        None,
+        Some(prophecy.id.clone()),
+        None,
+    )
+    .await
+    .expect("Failed to create branch");
+    event(&db, &campaign.id, "Flight North", 4, Some(&deeper.id)).await;
+    let nested = list_timeline_events_impl(&db, campaign.id.clone(), Some(deeper.id.clone()))
+        .await
+        .expect("Failed to list events");
+    assert_eq!(
+        titles(&nested),
+        vec!["Founding", "Coronation", "The King Falls", "Flight North"]
+    );
+}
+
+#[tokio::test]
+async fn test_compare_timeline_branches() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let founding = event(&db, &campaign.id, "Founding", 1, None).await;
+    event(&db, &campaign.id, "Plague", 2, None).await;
+    let split = create_timeline_branch_impl(
+        &db,
+        campaign.id.clone(),
+        "Time Travel Split".to_string(),
+        None,
+        None,
+        Some(founding.id.clone()),
+    )
+    .await
+    .expect("Failed to create branch");
+    event(&db, &campaign.id, "Cure Discovered", 2, Some(&split.id)).await;
+
+    let comparison =
+        compare_timeline_branches_impl(&db, campaign.id.clone(), None, Some(split.id.clone()))
+            .await
+            .expect("Failed to compare timelines");
+
+    assert_eq!(comparison.left_branch_id, None);
+    assert_eq!(comparison.right_branch_id, Some(split.id.clone()));
+    assert_eq!(titles(&comparison.shared), vec!["Founding"]);
+    assert_eq!(titles(&comparison.only_left), vec!["Plague"]);
+    assert_eq!(titles(&comparison.only_right), vec!["Cure Discovered"]);
+}
+
+#[tokio::test]
+async fn test_delete_branch_removes_descendants() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    event(&db, &campaign.id, "Founding", 1, None).await;
+    let branch = create_timeline_branch_impl(
+        &db,
+        campaign.id.clone(),
+        "What If".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create branch");
+    let child = create_timeline_branch_impl(
+        &db,
+        campaign.id.clone(),
+        "What If Again".to_string(),
+        None,
+        Some(branch.id.clone()),
+        None,
+    )
+    .await
+    .expect("Failed to create branch");
+    event(&db, &campaign.id, "Alternate", 2, Some(&branch.id)).await;
+    event(&db, &campaign.id, "Alternate Again", 3, Some(&child.id)).await;
+
+    assert!(delete_timeline_branch_impl(&db, branch.id.clone())
+        .await
+        .expect("Failed to delete branch"));
+
+    let branches = list_timeline_branches_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to list branches");
+    assert!(branches.is_empty());
+    let main = list_timeline_events_impl(&db, campaign.id.clone(), None)
+        .await
+        .expect("Failed to list events");
+    assert_eq!(titles(&main), vec!["Founding"]);
+
+    assert!(!delete_timeline_branch_impl(&db, branch.id.clone())
+        .await
+        .expect("Failed to delete branch"));
+}
+
+#[tokio::test]
+async fn test_timeline_branch_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other_campaign = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let err =
+        create_timeline_branch_impl(&db, campaign.id.clone(), "  ".to_string(), None, None, None)
+            .await
+            .expect_err("Blank name should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let branch = create_timeline_branch_impl(
+        &db,
+        campaign.id.clone(),
+        "What If".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create branch");
+    let branch_event = event(&db, &campaign.id, "Alternate", 1, Some(&branch.id)).await;
+
+    // The fork point must be on the parent line, not on some other branch
+    let err = create_timeline_branch_impl(
+        &db,
+        campaign.id.clone(),
+        "Sideways".to_string(),
+        None,
+        None,
+        Some(branch_event.id.clone()),
+    )
+    .await
+    .expect_err("Fork event off the parent line should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = create_timeline_event_impl(
+        &db,
+        other_campaign.id.clone(),
+        "Intruder".to_string(),
+        "Year 1".to_string(),
+        None,
+        None,
+        None,
+        Some(branch.id.clone()),
+    )
+    .await
+    .expect_err("Branch from another campaign should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = list_timeline_events_impl(&db, campaign.id.clone(), Some("missing".to_string()))
+        .await
+        .expect_err("Unknown branch should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use common::{create_test_campaign, setup_test_db};
+use loreweaver_lib::commands::timeline::{
+    create_timeline_event_impl, export_timeline_image_impl, update_timeline_event_impl,
+    TimelineImageOptions,
+};
+use loreweaver_lib::export::timeline_svg::TimelineEra;
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_export_timeline_image() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    for (title, sort_order, significance) in [
+        ("The Sundering", 100, "world"),
+        ("Tavern brawl", 250, "minor"),
+        ("Secret pact", 300, "major"),
+    ] {
+        let event = create_timeline_event_impl(
+            &db,
+            campaign.id.clone(),
+            title.to_string(),
+            format!("Year {}", sort_order),
+            Some(sort_order),
+            None,
+            Some(significance.to_string()),
+            None,
+        )
+        .await
+        .expect("Failed to create event");
+        if title == "Secret pact" {
+            update_timeline_event_impl(&db, event.id, None, None, None, None, None, Some(false))
+                .await
+                .expect("Failed to hide event");
+        }
+    }
+
+    let path = std::env::temp_dir()
+        .join(format!("timeline-{}.svg", uuid::Uuid::new_v4()))
+        .to_string_lossy()
+        .to_string();
+    let image = export_timeline_image_impl(
+        &db,
+        campaign.id.clone(),
+        TimelineImageOptions {
+            eras: vec![TimelineEra {
+                name: "Age of Strife".to_string(),
+                start: 0,
+                end: 200,
+            }],
+            path: Some(path.clone()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to export timeline");
+
+    assert_eq!(image.event_count, 3);
+    assert!(image.svg.starts_with("<svg"));
+    for text in [
+        "The Sundering",
+        "Tavern brawl",
+        "Secret pact",
+        "Age of Strife",
+    ] {
+        assert!(image.svg.contains(text), "missing {}", text);
+    }
+    // Lanes run from most to least significant
+    let world = image.svg.find(">world<").expect("world lane");
+    let major = image.svg.find(">major<").expect("major lane");
+    let minor = image.svg.find(">minor<").expect("minor lane");
+    assert!(world < major && major < minor);
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("Failed to read export"),
+        image.svg
+    );
+    let _ = std::fs::remove_file(&path);
+
+    let public = export_timeline_image_impl(
+        &db,
+        campaign.id.clone(),
+        TimelineImageOptions {
+            public_only: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to export public timeline");
+    assert_eq!(public.event_count, 2);
+    assert!(!public.svg.contains("Secret pact"));
+    assert!(public.path.is_none());
+}
+
+#[tokio::test]
+async fn test_export_timeline_image_errors() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let err = export_timeline_image_impl(
+        &db,
+        campaign.id.clone(),
+        TimelineImageOptions {
+            eras: vec![TimelineEra {
+                name: " ".to_string(),
+                start: 0,
+                end: 1,
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = export_timeline_image_impl(&db, "nope".to_string(), TimelineImageOptions::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
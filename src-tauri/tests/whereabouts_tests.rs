@@ -0,0 +1,168 @@
+mod common;
+
+use common::{create_test_campaign, create_test_character, create_test_location, setup_test_db};
+use loreweaver_lib::commands::campaign_settings::{
+    update_campaign_settings_impl, CalendarSettings, CampaignSettings, InWorldDate,
+};
+use loreweaver_lib::commands::character::delete_character_impl;
+use loreweaver_lib::commands::whereabouts::{
+    clear_character_whereabouts_impl, get_character_whereabouts_impl,
+    list_characters_at_location_impl, move_character_impl,
+};
+use loreweaver_lib::ErrorCode;
+
+#[tokio::test]
+async fn test_move_character_and_list_location() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let inn = create_test_location(&db, &campaign.id, "Prancing Pony", None)
+        .await
+        .expect("Failed to create location");
+    let rivendell = create_test_location(&db, &campaign.id, "Rivendell", None)
+        .await
+        .expect("Failed to create location");
+    let strider = create_test_character(&db, &campaign.id, "Strider")
+        .await
+        .expect("Failed to create character");
+    let butterbur = create_test_character(&db, &campaign.id, "Butterbur")
+        .await
+        .expect("Failed to create character");
+
+    let today = InWorldDate {
+        year: 3018,
+        month: 9,
+        day: 29,
+    };
+    let settings = CampaignSettings {
+        calendar: CalendarSettings {
+            today: Some(today),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    update_campaign_settings_impl(&db, campaign.id.clone(), settings)
+        .await
+        .expect("Failed to update settings");
+
+    assert!(get_character_whereabouts_impl(&db, strider.id.clone())
+        .await
+        .expect("Failed to get whereabouts")
+        .is_none());
+
+    // Without a date, the move happens on the campaign's current date
+    let moved = move_character_impl(
+        &db,
+        strider.id.clone(),
+        inn.id.clone(),
+        None,
+        Some("Brooding in a corner".to_string()),
+    )
+    .await
+    .expect("Failed to move character");
+    assert_eq!(moved.location_name, "Prancing Pony");
+    assert_eq!(moved.since, Some(today));
+    assert_eq!(moved.since_display.as_deref(), Some("29 Month 9 3018"));
+    move_character_impl(&db, butterbur.id.clone(), inn.id.clone(), None, None)
+        .await
+        .expect("Failed to move character");
+
+    let present = list_characters_at_location_impl(&db, inn.id.clone())
+        .await
+        .expect("Failed to list characters");
+    let names: Vec<_> = present.iter().map(|w| w.character_name.as_str()).collect();
+    assert_eq!(names, vec!["Butterbur", "Strider"]);
+
+    // Moving again replaces the old whereabouts
+    let later = InWorldDate {
+        year: 3018,
+        month: 10,
+        day: 20,
+    };
+    let moved = move_character_impl(
+        &db,
+        strider.id.clone(),
+        rivendell.id.clone(),
+        Some(later),
+        None,
+    )
+    .await
+    .expect("Failed to move character");
+    assert_eq!(moved.since, Some(later));
+    assert_eq!(moved.notes, None);
+    let present = list_characters_at_location_impl(&db, inn.id.clone())
+        .await
+        .expect("Failed to list characters");
+    assert_eq!(present.len(), 1);
+    let current = get_character_whereabouts_impl(&db, strider.id.clone())
+        .await
+        .expect("Failed to get whereabouts")
+        .expect("Strider should be somewhere");
+    assert_eq!(current.location_id, rivendell.id);
+
+    // Clearing, or deleting the character, forgets where they were
+    assert!(clear_character_whereabouts_impl(&db, strider.id.clone())
+        .await
+        .expect("Failed to clear whereabouts"));
+    assert!(get_character_whereabouts_impl(&db, strider.id.clone())
+        .await
+        .expect("Failed to get whereabouts")
+        .is_none());
+    delete_character_impl(&db, butterbur.id.clone())
+        .await
+        .expect("Failed to delete character");
+    let present = list_characters_at_location_impl(&db, inn.id.clone())
+        .await
+        .expect("Failed to list characters");
+    assert!(present.is_empty());
+}
+
+#[tokio::test]
+async fn test_move_character_validation() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let other = create_test_campaign(&db, "Other Campaign")
+        .await
+        .expect("Failed to create campaign");
+    let character = create_test_character(&db, &campaign.id, "Wanderer")
+        .await
+        .expect("Failed to create character");
+    let home = create_test_location(&db, &campaign.id, "Home", None)
+        .await
+        .expect("Failed to create location");
+    let foreign = create_test_location(&db, &other.id, "Elsewhere", None)
+        .await
+        .expect("Failed to create location");
+
+    let err = move_character_impl(&db, character.id.clone(), foreign.id.clone(), None, None)
+        .await
+        .expect_err("Location from another campaign should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = move_character_impl(
+        &db,
+        character.id.clone(),
+        home.id.clone(),
+        Some(InWorldDate {
+            year: 1,
+            month: 13,
+            day: 1,
+        }),
+        None,
+    )
+    .await
+    .expect_err("Date off the calendar should fail");
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let err = move_character_impl(&db, character.id.clone(), "missing".to_string(), None, None)
+        .await
+        .expect_err("Missing location should fail");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::{
+    create_test_campaign, create_test_character, create_test_location, create_test_tag,
+    setup_test_db,
+};
+use entity::characters;
+use loreweaver_lib::commands::quest::create_quest_impl;
+use loreweaver_lib::commands::relationship::create_relationship_impl;
+use loreweaver_lib::commands::tag::add_entity_tag_impl;
+use loreweaver_lib::commands::validation::CreateQuestInput;
+use loreweaver_lib::commands::worldbuilding_gaps::get_worldbuilding_gaps_impl;
+use loreweaver_lib::ErrorCode;
+use sea_orm::{ActiveModelTrait, Set};
+
+#[tokio::test]
+async fn test_worldbuilding_gaps() {
+    let db = setup_test_db()
+        .await
+        .expect("Failed to setup test database");
+    let campaign = create_test_campaign(&db, "Test Campaign")
+        .await
+        .expect("Failed to create campaign");
+
+    let region = create_test_location(&db, &campaign.id, "Northreach", None)
+        .await
+        .expect("Failed to create location");
+    let town = create_test_location(&db, &campaign.id, "Harrowgate", Some(&region.id))
+        .await
+        .expect("Failed to create location");
+    let stray = create_test_location(&db, &campaign.id, "Lost Tower", None)
+        .await
+        .expect("Failed to create location");
+
+    let mira = create_test_character(&db, &campaign.id, "Mira")
+        .await
+        .expect("Failed to create character");
+    let tag = create_test_tag(&db, &campaign.id, "Ally")
+        .await
+        .expect("Failed to create tag");
+    add_entity_tag_impl(&db, tag.id, "character".to_string(), mira.id.clone())
+        .await
+        .expect("Failed to tag character");
+    create_relationship_impl(
+        &db,
+        campaign.id.clone(),
+        "character".to_string(),
+        mira.id.clone(),
+        "location".to_string(),
+        town.id.clone(),
+        "lives_in".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to create relationship");
+
+    // An editor document with no text counts as empty
+    let blank = create_test_character(&db, &campaign.id, "Blank")
+        .await
+        .expect("Failed to create character");
+    let mut active: characters::ActiveModel = blank.clone().into();
+    active.description = Set(Some(
+        r#"{"type":"doc","content":[{"type":"paragraph"}]}"#.to_string(),
+    ));
+    active
+        .update(&db)
+        .await
+        .expect("Failed to update character");
+
+    let quest = create_quest_impl(
+        &db,
+        CreateQuestInput {
+            campaign_id: campaign.id.clone(),
+            name: "Find the heir".to_string(),
+            plot_type: "main".to_string(),
+            status: "planned".to_string(),
+            description: Some("The duke's heir is missing".to_string()),
+            hook: None,
+            objectives: None,
+        },
+    )
+    .await
+    .expect("Failed to create quest");
+
+    let report = get_worldbuilding_gaps_impl(&db, campaign.id.clone())
+        .await
+        .expect("Failed to get gaps");
+    let gaps_of = |id: &str| {
+        report
+            .entities
+            .iter()
+            .find(|e| e.entity_id == id)
+            .map(|e| e.gaps.clone())
+            .unwrap_or_default()
+    };
+
+    assert!(gaps_of(&mira.id).is_empty());
+    assert_eq!(gaps_of(&region.id), vec!["no_relationships", "no_tags"]);
+    assert_eq!(gaps_of(&town.id), vec!["no_tags"]);
+    assert_eq!(
+        gaps_of(&stray.id),
+        vec!["no_relationships", "no_tags", "no_parent"]
+    );
+    assert_eq!(
+        gaps_of(&blank.id),
+        vec!["no_relationships", "no_tags", "empty_description"]
+    );
+    assert_eq!(
+        gaps_of(&quest.id),
+        vec!["no_relationships", "no_tags", "no_objectives"]
+    );
+
+    // Blank and the quest both score 7; the stray tower and region trail
+    assert_eq!(report.entities[0].priority, 7);
+    assert_eq!(report.counts["no_parent"], 1);
+    assert_eq!(report.counts["empty_description"], 1);
+
+    let err = get_worldbuilding_gaps_impl(&db, "missing".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -25,6 +25,7 @@ const TimelinePage = lazy(() => import("@/pages/TimelinePage").then(m => ({ defa
 const TimelineEventDetailPage = lazy(() => import("@/pages/TimelineEventDetailPage").then(m => ({ default: m.TimelineEventDetailPage })));
 const SearchPage = lazy(() => import("@/pages/SearchPage").then(m => ({ default: m.SearchPage })));
 const SettingsPage = lazy(() => import("@/pages/SettingsPage").then(m => ({ default: m.SettingsPage })));
+const QuickNotePage = lazy(() => import("@/pages/QuickNotePage").then(m => ({ default: m.QuickNotePage })));
 const PlaceholderPage = lazy(() => import("@/pages/PlaceholderPage").then(m => ({ default: m.PlaceholderPage })));
 const AIFullPageChat = lazy(() => import("@/components/ai/AIFullPageChat").then(m => ({ default: m.AIFullPageChat })));
 
@@ -52,6 +53,9 @@ function App() {
           {/* Campaign selection - standalone page */}
           <Route path="/campaigns" element={<CampaignsPage />} />
 
+          {/* Tray quick-note window */}
+          <Route path="/quick-note" element={<QuickNotePage />} />
+
           {/* All routes within AppShell require active campaign */}
           <Route element={<AppShell />}>
             <Route path="/" element={<DashboardPage />} />
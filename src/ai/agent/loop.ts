@@ -14,6 +14,7 @@ import type {
   Message,
 } from "@anthropic-ai/sdk/resources/messages";
 import { createMessageStream, APIUserAbortError } from "../client";
+import { withRetry, AiProviderError, type RetryAttempt } from "../retry";
 import type { ToolRegistry, ToolResult } from "../tools";
 import { WorkItemTracker } from "./work-items";
 
@@ -37,6 +38,8 @@ export interface AgentConfig {
   onTokenUsage?: (usage: { inputTokens: number; outputTokens: number }) => void;
   /** Optional AbortSignal to cancel the agent run */
   signal?: AbortSignal;
+  /** Callback when a failed API request is about to be retried */
+  onRetry?: (attempt: RetryAttempt) => void;
 }
 
 /** Tool category determines UI behavior */
@@ -96,6 +99,8 @@ export interface AgentResult {
   completed: boolean;
   /** Error if the agent failed */
   error?: string;
+  /** Set when the failure came from the AI provider after retries */
+  providerError?: AiProviderError;
   /** Whether the agent was cancelled by user */
   cancelled?: boolean;
   /** Full message history (for conversation memory) */
@@ -255,7 +260,13 @@ export async function runAgent(
       // Wait for stream to complete and get final message
       let response;
       try {
-        response = await runStreamWithRetry();
+        // Retry rate limits and transient API failures with backoff,
+        // unless text has already been streamed to the user
+        response = await withRetry(() => runStreamWithRetry(), {
+          signal: config.signal,
+          onRetry: config.onRetry,
+          canRetry: () => iterationTextContent === "",
+        });
       } catch (streamError) {
         // Check if this was an abort
         if (
@@ -434,6 +445,7 @@ export async function runAgent(
       workItems: workItemTracker.list(),
       completed: false,
       error: error instanceof Error ? error.message : String(error),
+      providerError: error instanceof AiProviderError ? error : undefined,
       messages,
     };
   }
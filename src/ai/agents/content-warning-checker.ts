@@ -0,0 +1,142 @@
+/**
+ * Content Warning Checker
+ *
+ * Optional AI pass over an entity's text after the keyword scan in
+ * `contentWarnings.check`. Keywords miss content that is implied rather
+ * than named; the model reads the text against the campaign's lines and
+ * veils and reports any it touches. Results are merged into the keyword
+ * flags for the GM to review; nothing is changed.
+ */
+
+import { z } from "zod";
+import { createStructuredMessageStream } from "../client";
+import { AI_CONFIG } from "../config";
+import type { ContentFlag, ContentWarning } from "@/types";
+
+/**
+ * Zod schema for checker output
+ */
+const ContentCheckOutputSchema = z.object({
+  matches: z.array(
+    z.object({
+      topic: z.string(),
+      quote: z.string(),
+    })
+  ),
+});
+
+type ContentCheckOutput = z.infer<typeof ContentCheckOutputSchema>;
+
+/**
+ * Longest text sent to the model
+ */
+const MAX_TEXT_CHARS = 6000;
+
+/**
+ * The entity field being checked
+ */
+export interface ContentCheckTarget {
+  entityType: string;
+  entityId: string;
+  entityName: string | null;
+  field: string;
+  /** Plain text or markdown of the field */
+  text: string;
+}
+
+export interface ContentWarningCheckerResult {
+  success: boolean;
+  /** Keyword flags with the model's findings merged in */
+  flags: ContentFlag[];
+  error?: string;
+}
+
+function buildSystemPrompt(warnings: ContentWarning[]): string {
+  const list = warnings
+    .map((w) => {
+      const keywords = w.keywords.length ? ` (${w.keywords.join(", ")})` : "";
+      return `- ${w.topic}${keywords}: ${w.severity}`;
+    })
+    .join("\n");
+
+  return `You help a tabletop RPG game master keep their prep within the safety tools their table agreed on.
+
+Lines (never in the game) and veils (only off-screen):
+${list}
+
+Read the entry and report each topic above that the text depicts or clearly implies, even if it never names it. Use the topic exactly as written and quote the shortest passage that shows it. Don't report passing, harmless uses of a word. Report nothing if the text is clear.`;
+}
+
+/**
+ * Ask the model which lines and veils a field touches
+ *
+ * @param target - The field to check
+ * @param warnings - Result of `contentWarnings.list`
+ * @param flags - Keyword flags already found for this field
+ * @param signal - Optional abort signal for cancellation
+ */
+export async function checkContentWarningsWithAi(
+  target: ContentCheckTarget,
+  warnings: ContentWarning[],
+  flags: ContentFlag[],
+  signal?: AbortSignal
+): Promise<ContentWarningCheckerResult> {
+  if (!warnings.length || !target.text.trim()) {
+    return { success: true, flags };
+  }
+
+  try {
+    const response = await createStructuredMessageStream({
+      model: AI_CONFIG.models.fast,
+      system: buildSystemPrompt(warnings),
+      messages: [
+        {
+          role: "user",
+          content: `Entry (${target.entityType}, ${target.field}):\n\n${target.text.slice(0, MAX_TEXT_CHARS)}`,
+        },
+      ],
+      schema: ContentCheckOutputSchema,
+      maxTokens: 1024,
+      signal,
+    });
+
+    const data = response.data as ContentCheckOutput;
+    const byTopic = new Map<string, ContentWarning>(
+      warnings.map((w) => [w.topic.toLowerCase(), w])
+    );
+    const merged = [...flags];
+    const seen = new Set(
+      flags
+        .filter(
+          (f) => f.entity_id === target.entityId && f.field === target.field
+        )
+        .map((f) => f.warning_id)
+    );
+
+    for (const { topic, quote } of data.matches) {
+      const warning = byTopic.get(topic.trim().toLowerCase());
+      if (!warning || seen.has(warning.id)) continue;
+      seen.add(warning.id);
+      merged.push({
+        entity_type: target.entityType,
+        entity_id: target.entityId,
+        entity_name: target.entityName,
+        field: target.field,
+        warning_id: warning.id,
+        topic: warning.topic,
+        severity: warning.severity,
+        matched: "(AI)",
+        excerpt: quote,
+      });
+    }
+
+    return { success: true, flags: merged };
+  } catch (error) {
+    // Keyword flags are still usable without the AI pass
+    return {
+      success: false,
+      flags,
+      error: error instanceof Error ? error.message : String(error),
+    };
+  }
+}
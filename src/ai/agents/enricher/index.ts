@@ -0,0 +1,318 @@
+/**
+ * Enricher Agent
+ *
+ * Fills in stub entities in bulk. Each entity is sent to the model with the
+ * campaign summary and its recent activity, one at a time, and the written
+ * fields come back as update proposals. Only blank fields are filled, and
+ * nothing is saved until the user accepts the proposal. The campaign's
+ * monthly AI budget is checked before the batch and before every call.
+ */
+
+import { z } from "zod";
+import { createStructuredMessageStream } from "../../client";
+import { AI_CONFIG } from "../../config";
+import { getCampaignSummary, formatCampaignSummary } from "../../context";
+import { prosemirrorToMarkdown } from "@/ai/utils/content-bridge";
+import type { ProposalTracker } from "@/ai/proposals/tracker";
+import type { UpdateProposal } from "@/ai/tools/entity-proposals/types";
+import {
+  activity,
+  aiBudget,
+  campaigns,
+  characters,
+  errorMessage,
+  locations,
+  organizations,
+} from "@/lib/tauri";
+import {
+  ENRICHABLE_FIELDS,
+  buildEnrichmentSystemPrompt,
+  buildEnrichmentUserPrompt,
+} from "./prompts";
+import type {
+  EnrichableType,
+  EnrichmentOutcome,
+  EnrichmentRequest,
+  EnrichmentResult,
+  EnricherCallbacks,
+} from "./types";
+
+// Re-export types and prompts
+export * from "./types";
+export * from "./prompts";
+
+/**
+ * Zod schema for enrichment output. Fields the entity type doesn't have
+ * are ignored.
+ */
+const EnrichmentOutputSchema = z.object({
+  description: z.string().optional(),
+  personality: z.string().optional(),
+  motivations: z.string().optional(),
+  goals: z.string().optional(),
+  reasoning: z.string(),
+});
+
+type EnrichmentOutput = z.infer<typeof EnrichmentOutputSchema>;
+
+/**
+ * Fields shown to the model as what the entity already says
+ */
+const CONTEXT_FIELDS: Record<EnrichableType, string[]> = {
+  character: [
+    "lineage",
+    "occupation",
+    "description",
+    "personality",
+    "motivations",
+    "voice_notes",
+  ],
+  location: ["location_type", "description", "government_type"],
+  organization: ["org_type", "description", "goals", "resources", "reputation"],
+};
+
+/**
+ * Activity entries sent with each entity
+ */
+const ACTIVITY_LIMIT = 10;
+
+/**
+ * Most tokens one entity's fields may take
+ */
+const MAX_OUTPUT_TOKENS = 2048;
+
+/**
+ * Tokens one call may use, for budget checks ahead of it. Input is counted
+ * by the backend tokenizer, the same one behind the context warning.
+ */
+async function estimateCallTokens(
+  system: string,
+  user: string
+): Promise<number> {
+  const input = await aiBudget.estimateTokens({
+    system_prompt: system,
+    messages: [user],
+  });
+  return input + MAX_OUTPUT_TOKENS;
+}
+
+/**
+ * Check about `estimatedTokens` more against the campaign's monthly AI
+ * budget. Returns why the batch must stop, or null to go on; once the GM
+ * agrees to go over, `budget.confirmed` is set for the rest of the batch.
+ */
+async function checkBudget(
+  campaignId: string,
+  estimatedTokens: number,
+  budget: { confirmed: boolean },
+  confirmOverBudget?: (reason: string) => boolean
+): Promise<string | null> {
+  try {
+    const check = await aiBudget.check({
+      campaign_id: campaignId,
+      estimated_tokens: estimatedTokens,
+      confirmed: budget.confirmed,
+    });
+    if (!check.requires_confirmation) return null;
+    if (confirmOverBudget?.(check.reason ?? "over budget")) {
+      budget.confirmed = true;
+      return null;
+    }
+    return "Stopped: this campaign's monthly AI budget has been reached";
+  } catch (err) {
+    // Budget refusals come back as a VALIDATION error
+    return errorMessage(err);
+  }
+}
+
+function loadEntity(
+  entityType: EnrichableType,
+  entityId: string
+): Promise<Record<string, unknown>> {
+  switch (entityType) {
+    case "character":
+      return characters.get(entityId);
+    case "location":
+      return locations.get(entityId);
+    case "organization":
+      return organizations.get(entityId);
+  }
+}
+
+/**
+ * A field's value as plain markdown, converting editor JSON
+ */
+function fieldText(value: unknown): string {
+  if (typeof value !== "string") return "";
+  if (value.startsWith('{"type":"doc"')) {
+    try {
+      return prosemirrorToMarkdown(JSON.parse(value)).trim();
+    } catch {
+      // Not valid JSON, use as-is
+    }
+  }
+  return value.trim();
+}
+
+/**
+ * Enrich a batch of stub entities
+ *
+ * Entities are processed in order. Each one whose enrichable fields are
+ * not all written yet gets an update proposal in `tracker`; wire the
+ * tracker to the chat store to queue them for review. A failure on one
+ * entity is reported through `onProgress` and the batch carries on; the
+ * monthly AI budget running out stops it.
+ */
+export async function enrichEntities(
+  request: EnrichmentRequest,
+  tracker: ProposalTracker,
+  callbacks?: EnricherCallbacks
+): Promise<EnrichmentResult> {
+  const result: EnrichmentResult = {
+    success: true,
+    proposals: [],
+    skipped: 0,
+    failures: [],
+    usage: { inputTokens: 0, outputTokens: 0 },
+  };
+  const signal = callbacks?.signal;
+  const total = request.targets.length;
+  const model = AI_CONFIG.models.balanced;
+  const budget = { confirmed: false };
+
+  try {
+    // Campaign persona, style and banned topics are layered on by the backend
+    const campaignSummary = await getCampaignSummary(request.campaignId);
+    const systemPrompt = await campaigns.buildAiSystemPrompt(
+      request.campaignId,
+      buildEnrichmentSystemPrompt(
+        request.style,
+        formatCampaignSummary(campaignSummary)
+      )
+    );
+
+    let stop = await checkBudget(
+      request.campaignId,
+      total * (await estimateCallTokens(systemPrompt, "")),
+      budget,
+      callbacks?.confirmOverBudget
+    );
+
+    for (const [index, { entityType, entityId }] of request.targets.entries()) {
+      if (stop) break;
+      if (signal?.aborted) {
+        return { ...result, success: false, error: "Cancelled" };
+      }
+
+      let entityName = entityId;
+      let outcome: EnrichmentOutcome;
+      let error: string | undefined;
+
+      try {
+        const entity = await loadEntity(entityType, entityId);
+        entityName = (entity.name as string) || entityId;
+
+        const known: Record<string, string> = {};
+        for (const field of CONTEXT_FIELDS[entityType]) {
+          const text = fieldText(entity[field]);
+          if (text) known[field] = text;
+        }
+        const fields = Object.keys(ENRICHABLE_FIELDS[entityType]).filter(
+          (field) => !known[field]
+        );
+
+        if (fields.length === 0) {
+          outcome = "skipped";
+          result.skipped++;
+        } else {
+          const recent = await activity.get(
+            entityType,
+            entityId,
+            ACTIVITY_LIMIT
+          );
+          const userPrompt = buildEnrichmentUserPrompt(
+            entityType,
+            entityName,
+            known,
+            fields,
+            recent.map((entry) => entry.summary)
+          );
+          stop = await checkBudget(
+            request.campaignId,
+            await estimateCallTokens(systemPrompt, userPrompt),
+            budget,
+            callbacks?.confirmOverBudget
+          );
+          if (stop) break;
+
+          const response = await createStructuredMessageStream({
+            model,
+            system: systemPrompt,
+            messages: [{ role: "user", content: userPrompt }],
+            schema: EnrichmentOutputSchema,
+            maxTokens: MAX_OUTPUT_TOKENS,
+            signal,
+          });
+          result.usage.inputTokens += response.usage.input_tokens;
+          result.usage.outputTokens += response.usage.output_tokens;
+          await callbacks?.onUsage?.({
+            model,
+            inputTokens: response.usage.input_tokens,
+            outputTokens: response.usage.output_tokens,
+          });
+
+          const data = response.data as EnrichmentOutput;
+          const changes: Record<string, string> = {};
+          for (const field of fields) {
+            const value = data[field as keyof EnrichmentOutput]?.trim();
+            if (value) changes[field] = value;
+          }
+          if (Object.keys(changes).length === 0) {
+            throw new Error("The model returned no content");
+          }
+
+          const proposal: UpdateProposal = tracker.addUpdateProposal(
+            entityType,
+            entityId,
+            changes,
+            {
+              reasoning: data.reasoning,
+              currentData: { name: entityName, ...known },
+            }
+          );
+          result.proposals.push(proposal);
+          outcome = "proposed";
+        }
+      } catch (err) {
+        if (signal?.aborted) {
+          return { ...result, success: false, error: "Cancelled" };
+        }
+        outcome = "failed";
+        error = err instanceof Error ? err.message : String(err);
+        result.failures.push({ entityId, error });
+      }
+
+      callbacks?.onProgress?.({
+        done: index + 1,
+        total,
+        entityType,
+        entityId,
+        entityName,
+        outcome,
+        error,
+      });
+    }
+
+    if (stop) {
+      result.success = false;
+      result.error = stop;
+    }
+  } catch (error) {
+    // Campaign context couldn't be loaded, so nothing was attempted
+    result.success = false;
+    result.error = error instanceof Error ? error.message : String(error);
+  }
+
+  callbacks?.onComplete?.(result);
+  return result;
+}
@@ -0,0 +1,106 @@
+/**
+ * Enricher Agent Prompts
+ *
+ * Prompts for filling in the blank fields of stub entities.
+ */
+
+/**
+ * Writing styles available to users
+ */
+export type EnrichmentStyle = "concise" | "evocative" | "practical";
+
+/**
+ * Human-readable labels for enrichment styles
+ */
+export const ENRICHMENT_STYLE_LABELS: Record<EnrichmentStyle, string> = {
+  concise: "Concise",
+  evocative: "Evocative",
+  practical: "Table-Ready",
+};
+
+/**
+ * Guidance for each enrichment style
+ */
+const STYLE_GUIDANCE: Record<EnrichmentStyle, string> = {
+  concise:
+    "Keep each field to two or three sentences. Favor one memorable detail over a list of ordinary ones.",
+  evocative:
+    "Write a short paragraph per field with sensory detail and atmosphere: how it looks, sounds and feels to be around.",
+  practical:
+    "Write for use at the table: concrete details the GM can say aloud, plus a hook or complication the party could run into.",
+};
+
+/**
+ * Fields the enricher may fill, by entity type, with what each should hold
+ */
+export const ENRICHABLE_FIELDS: Record<string, Record<string, string>> = {
+  character: {
+    description: "appearance and first impression",
+    personality: "temperament, habits and how they treat strangers",
+    motivations: "what they want and what they fear",
+  },
+  location: {
+    description: "what the place looks like and who is found there",
+  },
+  organization: {
+    description: "who they are, how they operate and how others see them",
+    goals: "what the organization is working toward",
+  },
+};
+
+/**
+ * Build the system prompt for enrichment
+ */
+export function buildEnrichmentSystemPrompt(
+  style: EnrichmentStyle,
+  campaignContext: string
+): string {
+  return `You are a worldbuilding assistant for tabletop RPGs, filling in campaign wiki entries that so far have little more than a name.
+
+## Campaign Context
+${campaignContext}
+
+## Your Task
+Write the requested fields for one entry. Stay consistent with what the entry already says and with the campaign above; don't contradict established facts or invent major events. Name only entities that appear in the context.
+
+## Style: ${ENRICHMENT_STYLE_LABELS[style]}
+${STYLE_GUIDANCE[style]}
+
+Write plain markdown without headings. Fill every requested field, and give a one-sentence reason for your choices.`;
+}
+
+/**
+ * Build the user prompt describing the entry and the fields to fill
+ */
+export function buildEnrichmentUserPrompt(
+  entityType: string,
+  entityName: string,
+  known: Record<string, string>,
+  fields: string[],
+  activity: string[]
+): string {
+  const lines = [`Entry: ${entityName} (${entityType})`];
+
+  const knownEntries = Object.entries(known);
+  if (knownEntries.length > 0) {
+    lines.push("", "What the entry already says:");
+    for (const [field, value] of knownEntries) {
+      lines.push(`- ${field}: ${value}`);
+    }
+  }
+
+  if (activity.length > 0) {
+    lines.push("", "Recent campaign activity involving it:");
+    for (const summary of activity) {
+      lines.push(`- ${summary}`);
+    }
+  }
+
+  const guidance = ENRICHABLE_FIELDS[entityType] ?? {};
+  lines.push("", "Fields to write:");
+  for (const field of fields) {
+    lines.push(`- ${field}: ${guidance[field] ?? ""}`);
+  }
+
+  return lines.join("\n");
+}
@@ -0,0 +1,120 @@
+/**
+ * Enricher Agent Types
+ *
+ * Types for the batch enrichment of stub entities.
+ */
+
+import type { UpdateProposal } from "@/ai/tools/entity-proposals/types";
+import type { EnrichmentStyle } from "./prompts";
+
+/**
+ * Entity types the enricher can fill in (those with a detail level)
+ */
+export type EnrichableType = "character" | "location" | "organization";
+
+/**
+ * An entity to enrich, e.g. an entry from `detailLevels.stubReport`
+ */
+export interface EnrichmentTarget {
+  entityType: EnrichableType;
+  entityId: string;
+}
+
+/**
+ * Request to enrich a batch of entities
+ */
+export interface EnrichmentRequest {
+  /** Campaign ID for context */
+  campaignId: string;
+
+  /** Entities to enrich, processed in order */
+  targets: EnrichmentTarget[];
+
+  /** Tone and focus of the written fields */
+  style: EnrichmentStyle;
+}
+
+/**
+ * What happened to one entity
+ */
+export type EnrichmentOutcome = "proposed" | "skipped" | "failed";
+
+/**
+ * Progress after each entity is processed
+ */
+export interface EnrichmentProgress {
+  /** Entities processed so far, including this one */
+  done: number;
+  total: number;
+  entityType: EnrichableType;
+  entityId: string;
+  /** Entity name, or the ID if it couldn't be loaded */
+  entityName: string;
+  outcome: EnrichmentOutcome;
+  /** Why the entity failed */
+  error?: string;
+}
+
+/**
+ * Tokens one model call used
+ */
+export interface EnrichmentCallUsage {
+  model: string;
+  inputTokens: number;
+  outputTokens: number;
+}
+
+/**
+ * Result of a batch enrichment
+ */
+export interface EnrichmentResult {
+  /**
+   * False only when the batch couldn't start, was cancelled or was stopped
+   * by the campaign's monthly AI budget
+   */
+  success: boolean;
+
+  /** Update proposals created, one per enriched entity */
+  proposals: UpdateProposal[];
+
+  /** Entities whose enrichable fields were all written already */
+  skipped: number;
+
+  /** Entities that could not be enriched */
+  failures: { entityId: string; error: string }[];
+
+  /** Error message if the batch failed */
+  error?: string;
+
+  /** Token usage across the batch, for cost tracking */
+  usage: {
+    inputTokens: number;
+    outputTokens: number;
+  };
+}
+
+/**
+ * Callbacks for batch enrichment
+ */
+export interface EnricherCallbacks {
+  /** AbortSignal for cancellation */
+  signal?: AbortSignal;
+
+  /** Called after each entity is processed */
+  onProgress?: (progress: EnrichmentProgress) => void;
+
+  /**
+   * Called after each model call, to record its usage against the
+   * campaign's monthly budget. Awaited, so the next budget check sees it.
+   */
+  onUsage?: (usage: EnrichmentCallUsage) => void | Promise<void>;
+
+  /**
+   * Asked once when the batch would go over a monthly budget that lets
+   * the GM go ahead; without it, or on refusal, the batch stops
+   */
+  confirmOverBudget?: (reason: string) => boolean;
+
+  /** Called when the batch completes */
+  onComplete?: (result: EnrichmentResult) => void;
+}
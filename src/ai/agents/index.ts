@@ -9,4 +9,9 @@ export * from "./generator";
 export * from "./research-agent";
 export * from "./agentic-generator";
 export * from "./expander";
+export * from "./enricher";
 export * from "./consistency-checker";
+export * from "./tag-suggester";
+export * from "./content-warning-checker";
+export * from "./transcript-summarizer";
+export * from "./prompt-runner";
@@ -0,0 +1,76 @@
+/**
+ * Prompt Runner
+ *
+ * Runs a saved prompt template. The backend fills in the `{{placeholders}}`
+ * through `render_prompt`, so a missing binding fails before any tokens are
+ * spent; the rendered prompt is then sent as a single user turn.
+ */
+
+import { createMessageStream } from "../client";
+import { AI_CONFIG } from "../config";
+import { promptTemplates } from "@/lib/tauri";
+
+const DEFAULT_SYSTEM_PROMPT = `You are a creative assistant for a tabletop RPG game master. Follow the request exactly and answer in plain prose unless it asks for another format.`;
+
+export interface PromptRunnerOptions {
+  /** Model to use (defaults to the balanced model) */
+  model?: string;
+  /** System prompt (defaults to a generic GM assistant prompt) */
+  system?: string;
+  maxTokens?: number;
+  /** Called with each text delta as it arrives */
+  onTextDelta?: (delta: string) => void;
+  signal?: AbortSignal;
+}
+
+export interface PromptRunnerResult {
+  success: boolean;
+  /** The prompt as sent, once rendered */
+  prompt?: string;
+  text?: string;
+  error?: string;
+}
+
+/**
+ * Render a prompt template and run it
+ *
+ * @param templateId - Prompt template to run
+ * @param bindings - Values for the template's placeholders
+ * @param options - Model, streaming and cancellation options
+ */
+export async function runPromptTemplate(
+  templateId: string,
+  bindings: Record<string, string>,
+  options: PromptRunnerOptions = {}
+): Promise<PromptRunnerResult> {
+  let prompt: string | undefined;
+  try {
+    prompt = (await promptTemplates.render(templateId, bindings)).prompt;
+
+    const stream = createMessageStream({
+      model: options.model ?? AI_CONFIG.models.balanced,
+      system: options.system ?? DEFAULT_SYSTEM_PROMPT,
+      messages: [{ role: "user", content: prompt }],
+      maxTokens: options.maxTokens,
+    });
+    options.signal?.addEventListener("abort", () => stream.abort(), {
+      once: true,
+    });
+    if (options.onTextDelta) {
+      stream.on("text", options.onTextDelta);
+    }
+
+    const message = await stream.finalMessage();
+    const text = message.content
+      .map((block) => (block.type === "text" ? block.text : ""))
+      .join("");
+
+    return { success: true, prompt, text };
+  } catch (error) {
+    return {
+      success: false,
+      prompt,
+      error: error instanceof Error ? error.message : String(error),
+    };
+  }
+}
@@ -0,0 +1,124 @@
+/**
+ * Tag Suggester
+ *
+ * Optional AI pass over an entity's text after the keyword rules in
+ * `suggest_tags`. The model prefers the campaign's existing tags and only
+ * proposes a handful of new ones. Results are merged into the keyword
+ * suggestions; nothing is applied until the user confirms.
+ */
+
+import { z } from "zod";
+import { createStructuredMessageStream } from "../client";
+import { AI_CONFIG } from "../config";
+import type { Tag, TagSuggestions } from "@/types";
+
+/**
+ * Zod schema for tag suggestion output
+ */
+const TagSuggestionOutputSchema = z.object({
+  tags: z.array(
+    z.object({
+      name: z.string().min(1),
+      reason: z.string(),
+    })
+  ),
+});
+
+type TagSuggestionOutput = z.infer<typeof TagSuggestionOutputSchema>;
+
+/**
+ * Most new tags the model may propose
+ */
+const MAX_NEW_TAGS = 3;
+
+/**
+ * Longest text sent to the model
+ */
+const MAX_TEXT_CHARS = 6000;
+
+export interface TagSuggesterResult {
+  success: boolean;
+  /** Keyword suggestions with the model's picks merged in */
+  suggestions: TagSuggestions;
+  error?: string;
+}
+
+function buildSystemPrompt(availableTags: Tag[]): string {
+  const tagList = availableTags.length
+    ? availableTags.map((t) => `- ${t.name}`).join("\n")
+    : "(none yet)";
+
+  return `You tag entries in a tabletop RPG campaign wiki.
+
+Existing campaign tags:
+${tagList}
+
+Pick the tags that fit the entry. Prefer existing tags, using their exact names. Suggest at most ${MAX_NEW_TAGS} new tags, only for themes no existing tag covers. New tags are short, lowercase and reusable across entries (e.g. "undead", "port town"), never the entry's own name. Give a one-sentence reason for each tag.`;
+}
+
+/**
+ * Ask the model for tags and merge them into the keyword suggestions
+ *
+ * @param suggestions - Result of `tags.suggest` for the entity
+ * @param availableTags - Campaign tags not already on the entity
+ * @param signal - Optional abort signal for cancellation
+ */
+export async function suggestTagsWithAi(
+  suggestions: TagSuggestions,
+  availableTags: Tag[],
+  signal?: AbortSignal
+): Promise<TagSuggesterResult> {
+  try {
+    const response = await createStructuredMessageStream({
+      model: AI_CONFIG.models.fast,
+      system: buildSystemPrompt(availableTags),
+      messages: [
+        {
+          role: "user",
+          content: `Entry (${suggestions.entity_type}):\n\n${suggestions.text.slice(0, MAX_TEXT_CHARS)}`,
+        },
+      ],
+      schema: TagSuggestionOutputSchema,
+      maxTokens: 1024,
+      signal,
+    });
+
+    const data = response.data as TagSuggestionOutput;
+    const byName = new Map<string, Tag>(
+      availableTags.map((t) => [t.name.toLowerCase(), t])
+    );
+    const existing = [...suggestions.existing];
+    const newTags = [...suggestions.new_tags];
+    const seen = new Set([
+      ...existing.map((s) => s.tag.name.toLowerCase()),
+      ...newTags.map((s) => s.name.toLowerCase()),
+    ]);
+
+    let added = 0;
+    for (const { name, reason } of data.tags) {
+      const key = name.trim().toLowerCase();
+      if (!key || seen.has(key)) continue;
+      seen.add(key);
+
+      const tag = byName.get(key);
+      if (tag) {
+        existing.push({ tag, matched: reason });
+      } else if (added < MAX_NEW_TAGS) {
+        newTags.push({ name: name.trim(), matched: reason });
+        added++;
+      }
+    }
+
+    return {
+      success: true,
+      suggestions: { ...suggestions, existing, new_tags: newTags },
+    };
+  } catch (error) {
+    // Keyword suggestions are still usable without the AI pass
+    return {
+      success: false,
+      suggestions,
+      error: error instanceof Error ? error.message : String(error),
+    };
+  }
+}
@@ -0,0 +1,135 @@
+/**
+ * Transcript Summarizer
+ *
+ * Summarizes an imported session transcript. Each chunk from
+ * `sessions.transcriptChunks` is condensed into notes with the fast model,
+ * then a final pass turns the notes into a recap and highlights. Mentions
+ * found by `sessions.transcriptMentions` are passed along so the model can
+ * use canonical names; nothing is saved until the caller applies the result.
+ */
+
+import { z } from "zod";
+import { createStructuredMessageStream } from "../client";
+import { AI_CONFIG } from "../config";
+import type { TranscriptChunk, TranscriptMention } from "@/types";
+
+/**
+ * Zod schema for per-chunk notes
+ */
+const ChunkNotesSchema = z.object({
+  notes: z.array(z.string()),
+});
+
+/**
+ * Zod schema for the final summary
+ */
+const TranscriptSummarySchema = z.object({
+  summary: z.string(),
+  highlights: z.array(z.string()),
+});
+
+type ChunkNotes = z.infer<typeof ChunkNotesSchema>;
+type TranscriptSummary = z.infer<typeof TranscriptSummarySchema>;
+
+export interface TranscriptSummarizerResult {
+  success: boolean;
+  /** Prose recap, suitable for `sessions.saveSummary` */
+  summary?: string;
+  /** One line per memorable moment, suitable for the session's highlights */
+  highlights?: string[];
+  /** Entities named in the transcript, most mentioned first */
+  mentions: TranscriptMention[];
+  error?: string;
+}
+
+/**
+ * Progress callback, called after each chunk is condensed
+ */
+export type TranscriptProgressCallback = (done: number, total: number) => void;
+
+function buildMentionList(mentions: TranscriptMention[]): string {
+  if (!mentions.length) return "(none detected)";
+  return mentions
+    .map((m) => `- ${m.name} (${m.entity_type}, ${m.occurrences}x)`)
+    .join("\n");
+}
+
+const CHUNK_SYSTEM_PROMPT = `You condense part of a tabletop RPG session transcript into notes for the GM.
+
+Write short, factual notes in past tense: decisions the party made, fights and their outcomes, discoveries, NPCs met, loot gained and open threads. Skip rules discussion, dice talk and table chatter. Use character names rather than player names where the transcript makes them clear.`;
+
+function buildSummarySystemPrompt(mentions: TranscriptMention[]): string {
+  return `You write session recaps for a tabletop RPG campaign.
+
+Campaign entities mentioned in this session:
+${buildMentionList(mentions)}
+
+From the GM's notes, write a recap of a few paragraphs in past tense, using the entity names above exactly as written. Then list up to 8 highlights: one sentence each, for the moments players will remember.`;
+}
+
+/**
+ * Summarize a transcript chunk by chunk
+ *
+ * @param chunks - Result of `sessions.transcriptChunks`
+ * @param mentions - Result of `sessions.transcriptMentions`
+ * @param onProgress - Optional callback after each chunk
+ * @param signal - Optional abort signal for cancellation
+ */
+export async function summarizeTranscript(
+  chunks: TranscriptChunk[],
+  mentions: TranscriptMention[],
+  onProgress?: TranscriptProgressCallback,
+  signal?: AbortSignal
+): Promise<TranscriptSummarizerResult> {
+  try {
+    const notes: string[] = [];
+    for (const chunk of chunks) {
+      const range =
+        chunk.start && chunk.end ? ` (${chunk.start} to ${chunk.end})` : "";
+      const response = await createStructuredMessageStream({
+        model: AI_CONFIG.models.fast,
+        system: CHUNK_SYSTEM_PROMPT,
+        messages: [
+          {
+            role: "user",
+            content: `Transcript part ${chunk.index + 1} of ${chunks.length}${range}:\n\n${chunk.text}`,
+          },
+        ],
+        schema: ChunkNotesSchema,
+        maxTokens: 1024,
+        signal,
+      });
+      notes.push(...(response.data as ChunkNotes).notes);
+      onProgress?.(chunk.index + 1, chunks.length);
+    }
+
+    const response = await createStructuredMessageStream({
+      model: AI_CONFIG.models.fast,
+      system: buildSummarySystemPrompt(mentions),
+      messages: [
+        {
+          role: "user",
+          content: `Notes:\n\n${notes.map((n) => `- ${n}`).join("\n")}`,
+        },
+      ],
+      schema: TranscriptSummarySchema,
+      maxTokens: 2048,
+      signal,
+    });
+
+    const data = response.data as TranscriptSummary;
+    return {
+      success: true,
+      summary: data.summary.trim(),
+      highlights: data.highlights.map((h) => h.trim()).filter(Boolean),
+      mentions,
+    };
+  } catch (error) {
+    // Detected mentions are still usable without the AI pass
+    return {
+      success: false,
+      mentions,
+      error: error instanceof Error ? error.message : String(error),
+    };
+  }
+}
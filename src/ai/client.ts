@@ -20,6 +20,7 @@ import type { MessageStream } from "@anthropic-ai/sdk/lib/MessageStream";
 import { z } from "zod";
 import type { ToolDefinition, ToolContext, ToolResult } from "./tools/types";
 import { toAnthropicTool } from "./tools/types";
+import { withRetry, type RetryAttempt } from "./retry";
 
 /** Beta flag for structured outputs */
 const STRUCTURED_OUTPUT_BETA = "structured-outputs-2025-11-13";
@@ -61,6 +62,7 @@ export function initializeClient(apiKey: string): void {
   clientInstance = new Anthropic({
     apiKey,
     dangerouslyAllowBrowser: true, // Required for Tauri webview context
+    maxRetries: 0, // Retries are handled by withRetry so they can be reported
   });
 }
 
@@ -105,13 +107,15 @@ export async function createMessage(options: {
 }> {
   const client = getClient();
 
-  const response = await client.messages.create({
-    model: options.model,
-    system: options.system,
-    messages: options.messages,
-    tools: options.tools,
-    max_tokens: options.maxTokens ?? 4096,
-  });
+  const response = await withRetry(() =>
+    client.messages.create({
+      model: options.model,
+      system: options.system,
+      messages: options.messages,
+      tools: options.tools,
+      max_tokens: options.maxTokens ?? 4096,
+    })
+  );
 
   return {
     content: response.content,
@@ -145,17 +149,19 @@ export async function createStructuredMessage<T>(options: {
   // Zod v4 has built-in toJSONSchema
   const jsonSchema = z.toJSONSchema(options.schema);
 
-  const response = await client.beta.messages.create({
-    model: options.model,
-    system: options.system,
-    messages: options.messages,
-    max_tokens: options.maxTokens ?? 4096,
-    betas: [STRUCTURED_OUTPUT_BETA],
-    output_format: {
-      type: "json_schema",
-      schema: jsonSchema,
-    },
-  });
+  const response = await withRetry(() =>
+    client.beta.messages.create({
+      model: options.model,
+      system: options.system,
+      messages: options.messages,
+      max_tokens: options.maxTokens ?? 4096,
+      betas: [STRUCTURED_OUTPUT_BETA],
+      output_format: {
+        type: "json_schema",
+        schema: jsonSchema,
+      },
+    })
+  );
 
   // Extract text content from response
   const textBlock = response.content.find((b) => b.type === "text");
@@ -345,6 +351,8 @@ export async function createStructuredMessageStream<T>(options: {
   maxToolIterations?: number;
   /** Abort signal for cancellation */
   signal?: AbortSignal;
+  /** Called when a failed request is about to be retried */
+  onRetry?: (attempt: RetryAttempt) => void;
 }): Promise<StructuredStreamResult<T>> {
   const client = getClient();
 
@@ -372,31 +380,41 @@ export async function createStructuredMessageStream<T>(options: {
       throw new APIUserAbortError();
     }
 
-    const stream = client.beta.messages.stream(
-      {
-        model: options.model,
-        system: options.system,
-        messages: conversationMessages,
-        max_tokens: options.maxTokens ?? 4096,
-        betas: [STRUCTURED_OUTPUT_BETA],
-        output_format: {
-          type: "json_schema",
-          schema: jsonSchema,
-        },
-        tools: anthropicTools,
-      },
-      { signal: options.signal }
-    );
-
     // Accumulate text and emit deltas
     let accumulated = "";
-    stream.on("text", (textDelta: string) => {
-      accumulated += textDelta;
-      options.onTextDelta?.(textDelta, accumulated);
-    });
 
-    // Wait for stream to complete
-    const finalMessage = await stream.finalMessage();
+    // Retry transient failures, but only while nothing has been streamed
+    const finalMessage = await withRetry(
+      () => {
+        const stream = client.beta.messages.stream(
+          {
+            model: options.model,
+            system: options.system,
+            messages: conversationMessages,
+            max_tokens: options.maxTokens ?? 4096,
+            betas: [STRUCTURED_OUTPUT_BETA],
+            output_format: {
+              type: "json_schema",
+              schema: jsonSchema,
+            },
+            tools: anthropicTools,
+          },
+          { signal: options.signal }
+        );
+        stream.on("text", (textDelta: string) => {
+          accumulated += textDelta;
+          options.onTextDelta?.(textDelta, accumulated);
+        });
+
+        // Wait for stream to complete
+        return stream.finalMessage();
+      },
+      {
+        signal: options.signal,
+        onRetry: options.onRetry,
+        canRetry: () => accumulated === "",
+      }
+    );
 
     // Accumulate usage
     totalUsage.input_tokens += finalMessage.usage.input_tokens;
@@ -56,6 +56,16 @@ export type {
   MessageStreamEvent,
 } from "./client";
 
+// Retry and provider errors
+export {
+  withRetry,
+  isRetryable,
+  retryAfterMs,
+  AiProviderError,
+  AI_PROVIDER_ERROR_CODE,
+} from "./retry";
+export type { RetryAttempt, RetryOptions } from "./retry";
+
 // Agent
 export {
   runAgent,
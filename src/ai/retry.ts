@@ -0,0 +1,221 @@
+/**
+ * Request Retry
+ *
+ * Retries transient Anthropic API failures with exponential backoff.
+ * Rate-limit responses are retried no sooner than the provider asks
+ * (`retry-after` and the `anthropic-ratelimit-*-reset` headers). Once
+ * retries run out, or on a failure that won't go away by itself, the
+ * error is rethrown as an `AiProviderError` carrying the same
+ * `AI_PROVIDER` code the backend uses.
+ */
+
+import { APIError, APIConnectionError, APIUserAbortError } from "@anthropic-ai/sdk";
+
+/** Error code shared with the backend `AppError::AiProvider` */
+export const AI_PROVIDER_ERROR_CODE = "AI_PROVIDER" as const;
+
+/**
+ * A request to the AI provider that failed for good
+ */
+export class AiProviderError extends Error {
+  readonly code = AI_PROVIDER_ERROR_CODE;
+  /** HTTP status, when the provider answered at all */
+  readonly status?: number;
+  /** Whether the failure was transient (retries ran out) */
+  readonly retryable: boolean;
+  /** Attempts made, including the first */
+  readonly attempts: number;
+  /** The last underlying SDK error */
+  readonly cause?: unknown;
+
+  constructor(
+    message: string,
+    options: { status?: number; retryable: boolean; attempts: number; cause?: unknown }
+  ) {
+    super(message);
+    this.name = "AiProviderError";
+    this.status = options.status;
+    this.retryable = options.retryable;
+    this.attempts = options.attempts;
+    this.cause = options.cause;
+  }
+}
+
+/**
+ * A failed attempt that is about to be retried
+ */
+export interface RetryAttempt {
+  /** The attempt that failed, starting at 1 */
+  attempt: number;
+  maxAttempts: number;
+  status?: number;
+  message: string;
+  /** How long until the next attempt */
+  retryInMs: number;
+}
+
+export interface RetryOptions {
+  /** Total attempts including the first (default 4) */
+  maxAttempts?: number;
+  /** Delay before the first retry, doubled each time (default 1000) */
+  baseDelayMs?: number;
+  /** Upper bound for any single delay (default 60000) */
+  maxDelayMs?: number;
+  signal?: AbortSignal;
+  /** Called before waiting for each retry */
+  onRetry?: (attempt: RetryAttempt) => void;
+  /**
+   * Checked before each retry; return false when the request can no
+   * longer be repeated safely (e.g. output has already been streamed)
+   */
+  canRetry?: () => boolean;
+}
+
+const DEFAULT_MAX_ATTEMPTS = 4;
+const DEFAULT_BASE_DELAY_MS = 1000;
+const DEFAULT_MAX_DELAY_MS = 60_000;
+
+type HeaderSource = Headers | Record<string, string | null | undefined> | undefined;
+
+function header(headers: HeaderSource, name: string): string | null {
+  if (!headers) return null;
+  if (typeof (headers as Headers).get === "function") {
+    return (headers as Headers).get(name);
+  }
+  return (headers as Record<string, string | null | undefined>)[name] ?? null;
+}
+
+/**
+ * How long the provider asked us to wait, from response headers.
+ * Returns null when no usable header is present.
+ */
+export function retryAfterMs(headers: HeaderSource, now = Date.now()): number | null {
+  const ms = Number(header(headers, "retry-after-ms"));
+  if (header(headers, "retry-after-ms") && Number.isFinite(ms) && ms >= 0) {
+    return ms;
+  }
+
+  const retryAfter = header(headers, "retry-after");
+  if (retryAfter) {
+    const seconds = Number(retryAfter);
+    if (Number.isFinite(seconds) && seconds >= 0) return seconds * 1000;
+    const date = Date.parse(retryAfter);
+    if (!Number.isNaN(date)) return Math.max(0, date - now);
+  }
+
+  // Rate-limit reset times are RFC 3339 timestamps; wait for the latest one
+  let reset: number | null = null;
+  for (const name of [
+    "anthropic-ratelimit-requests-reset",
+    "anthropic-ratelimit-tokens-reset",
+    "anthropic-ratelimit-input-tokens-reset",
+    "anthropic-ratelimit-output-tokens-reset",
+  ]) {
+    const value = header(headers, name);
+    const date = value ? Date.parse(value) : NaN;
+    if (!Number.isNaN(date)) {
+      reset = Math.max(reset ?? 0, date - now);
+    }
+  }
+  return reset === null ? null : Math.max(0, reset);
+}
+
+function isAbort(error: unknown): boolean {
+  return (
+    error instanceof APIUserAbortError ||
+    (error instanceof Error && error.name === "AbortError")
+  );
+}
+
+/**
+ * Whether a failure is worth retrying: timeouts, conflicts, rate limits,
+ * server errors (including 529 overloaded) and dropped connections.
+ */
+export function isRetryable(error: unknown): boolean {
+  if (isAbort(error)) return false;
+  if (error instanceof APIConnectionError) return true;
+  if (error instanceof APIError) {
+    const status = error.status;
+    if (status === undefined) return true;
+    return status === 408 || status === 409 || status === 429 || status >= 500;
+  }
+  return false;
+}
+
+function statusOf(error: unknown): number | undefined {
+  return error instanceof APIError ? error.status : undefined;
+}
+
+function messageOf(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+/** Delay before retrying after `attempt` failed */
+function backoffDelay(error: unknown, attempt: number, options: RetryOptions): number {
+  const maxDelay = options.maxDelayMs ?? DEFAULT_MAX_DELAY_MS;
+  const requested =
+    error instanceof APIError ? retryAfterMs(error.headers as HeaderSource) : null;
+  if (requested !== null) {
+    return Math.min(requested, maxDelay);
+  }
+  const base = (options.baseDelayMs ?? DEFAULT_BASE_DELAY_MS) * 2 ** (attempt - 1);
+  // Up to 25% jitter so parallel requests don't retry in lockstep
+  const jitter = base * 0.25 * Math.random();
+  return Math.min(base + jitter, maxDelay);
+}
+
+function sleep(ms: number, signal?: AbortSignal): Promise<void> {
+  return new Promise((resolve, reject) => {
+    if (signal?.aborted) {
+      reject(new APIUserAbortError());
+      return;
+    }
+    const onAbort = () => {
+      clearTimeout(timer);
+      reject(new APIUserAbortError());
+    };
+    const timer = setTimeout(() => {
+      signal?.removeEventListener("abort", onAbort);
+      resolve();
+    }, ms);
+    signal?.addEventListener("abort", onAbort, { once: true });
+  });
+}
+
+/**
+ * Run `request`, retrying transient failures with exponential backoff.
+ * Errors that didn't come from the API are rethrown unchanged; API
+ * failures that can't be retried become an `AiProviderError`.
+ */
+export async function withRetry<T>(
+  request: (attempt: number) => Promise<T>,
+  options: RetryOptions = {}
+): Promise<T> {
+  const maxAttempts = Math.max(1, options.maxAttempts ?? DEFAULT_MAX_ATTEMPTS);
+
+  for (let attempt = 1; ; attempt++) {
+    try {
+      return await request(attempt);
+    } catch (error) {
+      // Aborts and local failures (bad JSON, schema mismatches) pass through
+      if (isAbort(error) || !(error instanceof APIError)) throw error;
+
+      const retryable = isRetryable(error);
+      const status = statusOf(error);
+      const message = messageOf(error);
+
+      if (!retryable || attempt >= maxAttempts || options.canRetry?.() === false) {
+        throw new AiProviderError(message, {
+          status,
+          retryable,
+          attempts: attempt,
+          cause: error,
+        });
+      }
+
+      const retryInMs = Math.round(backoffDelay(error, attempt, options));
+      options.onRetry?.({ attempt, maxAttempts, status, message, retryInMs });
+      await sleep(retryInMs, options.signal);
+    }
+  }
+}
@@ -4,7 +4,7 @@
  * Provides high-level campaign overview and statistics.
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/tauri";
 import type { ToolDefinition, ToolResult, ToolContext } from "../types";
 import type {
   Campaign,
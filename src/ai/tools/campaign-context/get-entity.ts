@@ -5,7 +5,7 @@
  * Formats the entity as markdown for the agent.
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/tauri";
 import { prosemirrorToMarkdown } from "@/ai/utils/content-bridge";
 import { isUUID, createInvalidIdError } from "@/ai/utils/uuid";
 import type { ToolDefinition, ToolResult, ToolContext } from "../types";
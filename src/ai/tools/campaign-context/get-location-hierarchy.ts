@@ -4,7 +4,7 @@
  * Retrieves location hierarchy (parent chain and children).
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/tauri";
 import { isUUID, createInvalidIdError } from "@/ai/utils/uuid";
 import type { ToolDefinition, ToolResult, ToolContext } from "../types";
 import type { Location } from "@/types";
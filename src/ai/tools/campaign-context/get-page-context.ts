@@ -5,7 +5,7 @@
  * This provides richer information than the auto-injected system prompt context.
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/tauri";
 import { prosemirrorToMarkdown } from "@/ai/utils/content-bridge";
 import type { ToolDefinition, ToolResult, ToolContext } from "../types";
 import type {
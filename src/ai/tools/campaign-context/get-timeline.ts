@@ -4,7 +4,7 @@
  * Retrieves timeline events for the campaign.
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/tauri";
 import type { ToolDefinition, ToolResult, ToolContext } from "../types";
 import type { TimelineEvent } from "@/types";
 
@@ -5,7 +5,7 @@
  * Uses FTS5 full-text search in the backend.
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/tauri";
 import type { ToolDefinition, ToolResult, ToolContext } from "../types";
 import type { SearchResult } from "@/types";
 
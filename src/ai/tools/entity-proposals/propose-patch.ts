@@ -6,7 +6,7 @@
  * to large documents without requiring full field replacement.
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/tauri";
 import { prosemirrorToMarkdown } from "@/ai/utils/content-bridge";
 import {
   tryApplyPatches,
@@ -5,7 +5,7 @@
  * The proposal requires user approval before the relationship is created.
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/tauri";
 import type { ToolDefinition, ToolResult, ToolContext } from "../types";
 import type { ProposalTracker } from "@/ai/proposals/tracker";
 import type { ProposeRelationshipInput } from "./types";
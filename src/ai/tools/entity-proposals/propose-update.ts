@@ -5,7 +5,7 @@
  * user approval before the changes are actually applied.
  */
 
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "@/lib/tauri";
 import { prosemirrorToMarkdown } from "@/ai/utils/content-bridge";
 import { RICH_TEXT_FIELDS } from "@/types";
 import type { ToolDefinition, ToolResult, ToolContext } from "../types";
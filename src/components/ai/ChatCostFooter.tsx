@@ -1,10 +1,11 @@
 /**
  * Chat Cost Footer Component
  *
- * Displays token usage and estimated cost for the current chat session.
+ * Displays token usage and estimated cost for the current chat session,
+ * plus the campaign's monthly AI budget when one is set.
  */
 
-import { useMemo } from "react";
+import { useEffect, useMemo, useState } from "react";
 import {
   Tooltip,
   TooltipContent,
@@ -12,14 +13,52 @@ import {
 } from "@/components/ui/tooltip";
 import { calculateCostWithCache, formatCost, formatTokens } from "@/ai/pricing";
 import { selectModel } from "@/ai";
-import { useChatStore } from "@/stores";
+import { useChatStore, useCampaignStore } from "@/stores";
 import { useAIStore } from "@/stores/aiStore";
+import { aiBudget, type AiBudgetUsage } from "@/lib/tauri";
 import { cn } from "@/lib/utils";
 
 export interface ChatCostFooterProps {
   className?: string;
 }
 
+/**
+ * Month-to-date usage against the tighter of the campaign's budgets
+ */
+function MonthlyBudgetBar({ usage }: { usage: AiBudgetUsage }) {
+  const fraction = Math.max(usage.token_fraction ?? 0, usage.cost_fraction ?? 0);
+  const percent = Math.round(fraction * 100);
+  const label =
+    usage.cost_budget !== null &&
+    (usage.cost_fraction ?? 0) >= (usage.token_fraction ?? 0)
+      ? `${formatCost(usage.cost_used)} of ${formatCost(usage.cost_budget)}`
+      : `${formatTokens(usage.tokens_used)} of ${formatTokens(usage.token_budget ?? 0)} tokens`;
+
+  return (
+    <div className="mt-1">
+      <div className="flex justify-between">
+        <span>Monthly budget</span>
+        <span className={cn(usage.over_budget && "text-destructive")}>
+          {label} ({percent}%)
+        </span>
+      </div>
+      <div className="mt-0.5 h-1 w-full overflow-hidden rounded-full bg-muted">
+        <div
+          className={cn(
+            "h-full rounded-full",
+            usage.over_budget
+              ? "bg-destructive"
+              : fraction >= 0.8
+                ? "bg-amber-500"
+                : "bg-primary"
+          )}
+          style={{ width: `${Math.min(percent, 100)}%` }}
+        />
+      </div>
+    </div>
+  );
+}
+
 export function ChatCostFooter({ className }: ChatCostFooterProps) {
   // Use individual selectors to prevent unnecessary re-renders
   const sessionInputTokens = useChatStore((state) => state.sessionInputTokens);
@@ -34,6 +73,28 @@ export function ChatCostFooter({ className }: ChatCostFooterProps) {
   const liveOutputTokens = useChatStore((state) => state.liveOutputTokens);
   const isRunning = useChatStore((state) => state.isRunning);
   const modelPreference = useAIStore((state) => state.modelPreference);
+  const activeCampaignId = useCampaignStore((state) => state.activeCampaignId);
+  const [budgetUsage, setBudgetUsage] = useState<AiBudgetUsage | null>(null);
+
+  // Refresh the monthly budget when the campaign changes and after each run
+  useEffect(() => {
+    if (!activeCampaignId || isRunning) return;
+    let cancelled = false;
+    aiBudget
+      .usage(activeCampaignId)
+      .then((usage) => {
+        if (!cancelled) setBudgetUsage(usage);
+      })
+      .catch((err) => console.error("Failed to load AI budget usage:", err));
+    return () => {
+      cancelled = true;
+    };
+  }, [activeCampaignId, isRunning]);
+
+  const hasBudget =
+    budgetUsage !== null &&
+    budgetUsage.campaign_id === activeCampaignId &&
+    (budgetUsage.token_budget !== null || budgetUsage.cost_budget !== null);
 
   const hasUsage =
     sessionInputTokens > 0 ||
@@ -70,7 +131,7 @@ export function ChatCostFooter({ className }: ChatCostFooterProps) {
     modelPreference,
   ]);
 
-  if (!hasUsage && !hasLiveUsage) {
+  if (!hasUsage && !hasLiveUsage && !hasBudget) {
     return null;
   }
 
@@ -119,6 +180,9 @@ export function ChatCostFooter({ className }: ChatCostFooterProps) {
           </TooltipContent>
         </Tooltip>
       )}
+
+      {/* Monthly budget progress */}
+      {hasBudget && budgetUsage && <MonthlyBudgetBar usage={budgetUsage} />}
     </div>
   );
 }
@@ -2,13 +2,19 @@
  * Shared Chat Input Component
  *
  * Reusable input component for both AIChatPanel and AIFullPageChat.
- * Includes textarea, send/stop button, and campaign selection warning.
+ * Includes textarea, send/stop button, campaign selection warning, and a
+ * warning when the next request would exceed the context or token budget.
  */
 
-import { useRef, useEffect } from "react";
-import { Send, Square } from "lucide-react";
+import { useRef, useEffect, useState } from "react";
+import { AlertTriangle, Send, Square } from "lucide-react";
 import { Button } from "@/components/ui/button";
 import { Textarea } from "@/components/ui/textarea";
+import { useChatStore } from "@/stores";
+import { aiConversations, type PromptTokenEstimate } from "@/lib/tauri";
+
+/** Wait this long after typing stops before re-estimating */
+const ESTIMATE_DEBOUNCE_MS = 500;
 
 export interface ChatInputProps {
   value: string;
@@ -39,6 +45,29 @@ export function ChatInput({
   className,
 }: ChatInputProps) {
   const textareaRef = useRef<HTMLTextAreaElement>(null);
+  const conversationId = useChatStore((state) => state.conversationId);
+  const [estimate, setEstimate] = useState<PromptTokenEstimate | null>(null);
+
+  // Re-estimate the next request's size as the draft changes
+  useEffect(() => {
+    if (!conversationId) {
+      setEstimate(null);
+      return;
+    }
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      aiConversations
+        .estimateTokens({ conversation_id: conversationId, draft_message: value })
+        .then((result) => {
+          if (!cancelled) setEstimate(result);
+        })
+        .catch((err) => console.error("Failed to estimate tokens:", err));
+    }, ESTIMATE_DEBOUNCE_MS);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [conversationId, value, isRunning]);
 
   // Auto-focus when requested
   useEffect(() => {
@@ -99,6 +128,14 @@ export function ChatInput({
           </Button>
         )}
       </div>
+      {estimate && (estimate.exceeds_context || estimate.exceeds_budget) && (
+        <p className="mt-2 flex items-center gap-1 text-xs text-amber-500">
+          <AlertTriangle className="h-3 w-3 shrink-0" />
+          {estimate.exceeds_context
+            ? `~${estimate.total_tokens.toLocaleString()} tokens won't fit the model's context; clear the conversation to continue`
+            : `~${estimate.total_tokens.toLocaleString()} tokens is over this campaign's budget of ${estimate.budget?.toLocaleString()}`}
+        </p>
+      )}
       {!activeCampaignId && (
         <p className="mt-2 text-xs text-muted-foreground">
           Select a campaign to start chatting
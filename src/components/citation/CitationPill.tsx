@@ -18,6 +18,8 @@ import {
   UserCircle,
   Clock,
   Lock,
+  Gem,
+  Mail,
 } from "lucide-react";
 
 export interface CitationPillProps {
@@ -40,9 +42,11 @@ const ENTITY_ICONS: Record<
   quest: Scroll,
   session: Calendar,
   hero: Shield,
+  item: Gem,
   player: UserCircle,
   timeline_event: Clock,
   secret: Lock,
+  handout: Mail,
   campaign: Building2,
 };
 
@@ -52,9 +56,11 @@ const ENTITY_LABELS: Record<EntityType, string> = {
   organization: "Organization",
   quest: "Quest",
   session: "Session",
+  item: "Item",
   hero: "Hero",
   player: "Player",
   timeline_event: "Event",
+  handout: "Handout",
   secret: "Secret",
   campaign: "Campaign",
 };
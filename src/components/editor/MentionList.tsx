@@ -18,6 +18,8 @@ import {
   UserCircle,
   Clock,
   Lock,
+  Gem,
+  Mail,
 } from "lucide-react";
 
 export interface MentionListProps {
@@ -43,9 +45,11 @@ const ENTITY_ICONS: Record<EntityType, React.ComponentType<{ className?: string
   quest: Scroll,
   session: Calendar,
   hero: Shield,
+  item: Gem,
   player: UserCircle,
   timeline_event: Clock,
   secret: Lock,
+  handout: Mail,
   campaign: Building2, // Campaigns typically not mentioned, but included for completeness
 };
 
@@ -55,9 +59,11 @@ const ENTITY_LABELS: Record<EntityType, string> = {
   organization: "Organizations",
   quest: "Quests",
   session: "Sessions",
+  item: "Items",
   hero: "Heroes",
   player: "Players",
   timeline_event: "Timeline Events",
+  handout: "Handouts",
   secret: "Secrets",
   campaign: "Campaigns",
 };
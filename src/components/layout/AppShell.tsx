@@ -6,6 +6,7 @@ import { Sidebar } from "./Sidebar";
 import { CommandPalette } from "./CommandPalette";
 import { WorldNavigator } from "./WorldNavigator";
 import { useCampaignStore, useAIStore } from "@/stores";
+import { quickNotes } from "@/lib/tauri";
 
 // Lazy load AI panel - only loaded when API key is configured
 const AIChatPanel = lazy(() => import("@/components/ai/AIChatPanel").then(m => ({ default: m.AIChatPanel })));
@@ -22,6 +23,11 @@ export function AppShell() {
     }
   }, [activeCampaignId, campaigns.length, isLoading, navigate]);
 
+  // Tray quick notes are filed into the open campaign
+  useEffect(() => {
+    quickNotes.setActiveCampaign(activeCampaignId).catch(() => {});
+  }, [activeCampaignId]);
+
   return (
     <TooltipProvider>
       <div className="flex h-screen flex-col overflow-hidden">
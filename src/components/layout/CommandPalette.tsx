@@ -96,10 +96,12 @@ export function CommandPalette() {
       organization: "/organizations",
       quest: "/quests",
       hero: "/heroes",
+      item: "/items",
       player: "/players",
       session: "/sessions",
       timeline_event: "/timeline",
       secret: "/secrets",
+      handout: "/handouts",
     };
 
     const baseRoute = routeMap[entityType];
@@ -45,6 +45,10 @@ const RELATIONSHIP_TYPES = [
   { value: "rival", label: "Rival" },
   { value: "friend", label: "Friend" },
   { value: "family", label: "Family" },
+  { value: "parent_of", label: "Parent Of" },
+  { value: "child_of", label: "Child Of" },
+  { value: "sibling_of", label: "Sibling Of" },
+  { value: "married_to", label: "Married To" },
   { value: "employer", label: "Employer" },
   { value: "employee", label: "Employee" },
   { value: "mentor", label: "Mentor" },
@@ -59,6 +63,7 @@ const RELATIONSHIP_TYPES = [
   { value: "guards", label: "Guards" },
   { value: "controls", label: "Controls" },
   { value: "worships", label: "Worships" },
+  { value: "quest_giver", label: "Quest Giver" },
   { value: "custom", label: "Custom..." },
 ];
 
@@ -38,10 +38,12 @@ const entityRoutes: Record<EntityType, string> = {
   organization: "/organizations",
   quest: "/quests",
   hero: "/heroes",
+  item: "/items",
   player: "/players",
   session: "/sessions",
   timeline_event: "/timeline",
   secret: "/secrets",
+  handout: "/handouts",
 };
 
 // Format entity type for display
@@ -4,5 +4,6 @@
 
 export { useAgentChat } from "./useAgentChat";
 export { useConsistencyCheck } from "./useConsistencyCheck";
+export { useEnricher } from "./useEnricher";
 export { useGenerator } from "./useGenerator";
 export { usePageContext } from "./usePageContext";
@@ -21,13 +21,56 @@ import {
   selectModel,
 } from "@/ai";
 import { ProposalTracker } from "@/ai/proposals/tracker";
+import { calculateCostWithCache } from "@/ai/pricing";
+import { aiBudget, aiConversations, campaigns } from "@/lib/tauri";
 import type { PageContext } from "@/ai/context/types";
 
+/**
+ * Check the campaign's monthly AI budget before a run. Over budget, the GM
+ * is asked to confirm (or refused, if the campaign blocks); returns the
+ * reason the run must not go ahead, or null when it may.
+ */
+async function checkMonthlyBudget(
+  campaignId: string,
+  content: string,
+  systemPrompt: string
+): Promise<string | null> {
+  const conversationId = useChatStore.getState().conversationId;
+  try {
+    const estimate = conversationId
+      ? await aiConversations.estimateTokens({
+          conversation_id: conversationId,
+          draft_message: content,
+          system_prompt: systemPrompt,
+        })
+      : null;
+    const check = await aiBudget.check({
+      campaign_id: campaignId,
+      estimated_tokens: estimate?.total_tokens ?? 0,
+    });
+    if (
+      check.requires_confirmation &&
+      !confirm(`Monthly AI budget: ${check.reason}. Send anyway?`)
+    ) {
+      return "Not sent: this campaign's monthly AI budget has been reached";
+    }
+    return null;
+  } catch (err) {
+    // Budget refusals come back as a VALIDATION error payload
+    if (err && typeof err === "object" && "message" in err) {
+      return String((err as { message: unknown }).message);
+    }
+    console.error("Failed to check AI budget:", err);
+    return null;
+  }
+}
+
 export function useAgentChat() {
   const {
     addUserMessage,
     addToolResult,
     addError,
+    addRetryNotice,
     setRunning,
     startStreaming,
     appendToStreaming,
@@ -97,7 +140,17 @@ export function useAgentChat() {
 
         // Infer task type and get appropriate prompt with page context
         const taskType = inferTaskType(content);
-        const systemPrompt = getSystemPrompt(taskType, pageContext);
+        // Campaign persona, style and banned topics are layered on by the backend
+        const systemPrompt = await campaigns.buildAiSystemPrompt(
+          campaignId,
+          getSystemPrompt(taskType, pageContext)
+        );
+
+        const budgetError = await checkMonthlyBudget(campaignId, content, systemPrompt);
+        if (budgetError) {
+          addError(budgetError);
+          return;
+        }
 
         // Select model based on preference
         const model = selectModel(modelPreference);
@@ -122,6 +175,10 @@ export function useAgentChat() {
             onTokenUsage: (usage) => {
               addLiveTokens(usage.inputTokens, usage.outputTokens);
             },
+            onRetry: (attempt) => {
+              // Each failed attempt is kept in the conversation as an error message
+              addRetryNotice(attempt);
+            },
             onMessage: (msg) => {
               // Finish current streaming message before adding new messages
               if (isStreamingRef.current) {
@@ -179,7 +236,16 @@ export function useAgentChat() {
 
         // Track token usage including cache metrics (always, even on cancellation/error)
         if (result.usage) {
-          addTokenUsage(result.usage);
+          addTokenUsage({
+            ...result.usage,
+            costUsd: calculateCostWithCache(
+              model,
+              result.usage.inputTokens,
+              result.usage.outputTokens,
+              result.usage.cacheReadTokens,
+              result.usage.cacheCreationTokens
+            ),
+          });
         }
 
         // Store updated conversation history for multi-turn memory
@@ -192,6 +258,17 @@ export function useAgentChat() {
           return;
         }
 
+        // Provider failures that outlasted retries are shown to the user
+        if (result.providerError) {
+          const { code, status, attempts } = result.providerError;
+          addError(`AI provider error: ${result.providerError.message}`, {
+            code,
+            status,
+            attempts,
+          });
+          return;
+        }
+
         // Log errors to console but don't show to user - AI handles gracefully
         if (!result.completed && result.error) {
           console.error("[Agent Error]", result.error);
@@ -221,6 +298,7 @@ export function useAgentChat() {
       addUserMessage,
       addToolResult,
       addError,
+      addRetryNotice,
       setRunning,
       startStreaming,
       appendToStreaming,
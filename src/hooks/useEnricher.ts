@@ -0,0 +1,120 @@
+/**
+ * useEnricher Hook
+ *
+ * Runs batch enrichment of stub entities in the background. Proposals are
+ * added to the chat as they arrive, where the user reviews them like any
+ * other; progress is reported per entity. Each call's tokens are recorded
+ * with the chat's, against the campaign's monthly AI budget.
+ */
+
+import { useState, useCallback, useRef, useEffect } from "react";
+import {
+  enrichEntities,
+  type EnrichmentProgress,
+  type EnrichmentResult,
+  type EnrichmentStyle,
+  type EnrichmentTarget,
+} from "@/ai/agents/enricher";
+import { initializeClient, isClientInitialized } from "@/ai/client";
+import { calculateCost } from "@/ai/pricing";
+import { ProposalTracker } from "@/ai/proposals/tracker";
+import { useChatStore } from "@/stores";
+import { useAIStore } from "@/stores/aiStore";
+
+interface UseEnricherReturn {
+  /** Whether a batch is running */
+  isEnriching: boolean;
+  /** Progress after the latest entity */
+  progress: EnrichmentProgress | null;
+  /** Result of the last batch */
+  result: EnrichmentResult | null;
+  /** Start enriching; any running batch is cancelled */
+  enrich: (targets: EnrichmentTarget[], style: EnrichmentStyle) => void;
+  /** Cancel the running batch; proposals made so far are kept */
+  cancel: () => void;
+}
+
+export function useEnricher(campaignId: string): UseEnricherReturn {
+  const apiKey = useAIStore((state) => state.apiKey);
+  const addProposal = useChatStore((state) => state.addProposal);
+  const addTokenUsage = useChatStore((state) => state.addTokenUsage);
+
+  const [isEnriching, setIsEnriching] = useState(false);
+  const [progress, setProgress] = useState<EnrichmentProgress | null>(null);
+  const [result, setResult] = useState<EnrichmentResult | null>(null);
+
+  // AbortController for cancellation
+  const abortControllerRef = useRef<AbortController | null>(null);
+
+  // Cleanup on unmount
+  useEffect(() => {
+    return () => {
+      abortControllerRef.current?.abort();
+    };
+  }, []);
+
+  const enrich = useCallback(
+    (targets: EnrichmentTarget[], style: EnrichmentStyle) => {
+      if (!apiKey) {
+        setResult({
+          success: false,
+          proposals: [],
+          skipped: 0,
+          failures: [],
+          error: "API key not configured. Please set your Anthropic API key.",
+          usage: { inputTokens: 0, outputTokens: 0 },
+        });
+        return;
+      }
+      if (!isClientInitialized()) {
+        initializeClient(apiKey);
+      }
+
+      abortControllerRef.current?.abort();
+      const abortController = new AbortController();
+      abortControllerRef.current = abortController;
+
+      const tracker = new ProposalTracker();
+      tracker.setOnProposalCreated((proposal) => {
+        addProposal(proposal);
+      });
+
+      setIsEnriching(true);
+      setProgress(null);
+      setResult(null);
+
+      // Not awaited: the batch keeps going while the user works
+      void enrichEntities({ campaignId, targets, style }, tracker, {
+        signal: abortController.signal,
+        onProgress: setProgress,
+        onUsage: (usage) =>
+          addTokenUsage({
+            inputTokens: usage.inputTokens,
+            outputTokens: usage.outputTokens,
+            cacheReadTokens: 0,
+            cacheCreationTokens: 0,
+            costUsd: calculateCost(
+              usage.model,
+              usage.inputTokens,
+              usage.outputTokens
+            ),
+          }),
+        confirmOverBudget: (reason) =>
+          confirm(`Monthly AI budget: ${reason}. Enrich anyway?`),
+      }).then((batchResult) => {
+        // A newer batch has taken over
+        if (abortControllerRef.current !== abortController) return;
+        setResult(batchResult);
+        setIsEnriching(false);
+        abortControllerRef.current = null;
+      });
+    },
+    [campaignId, apiKey, addProposal, addTokenUsage]
+  );
+
+  const cancel = useCallback(() => {
+    abortControllerRef.current?.abort();
+  }, []);
+
+  return { isEnriching, progress, result, enrich, cancel };
+}
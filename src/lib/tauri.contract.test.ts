@@ -14,6 +14,8 @@ import {
   tags,
   search,
   aiConversations,
+  CommandError,
+  errorMessage,
 } from "./tauri";
 
 const mockInvoke = vi.mocked(invoke);
@@ -335,3 +337,37 @@ describe("Tauri Command Contracts - Argument Naming", () => {
     });
   });
 });
+
+describe("Tauri Command Contracts - Errors", () => {
+  beforeEach(() => {
+    mockInvoke.mockClear();
+  });
+
+  it("rejects with a CommandError carrying the payload", async () => {
+    mockInvoke.mockRejectedValue({
+      code: "VALIDATION",
+      message: "Validation error: name: Name is required",
+      details: { fields: [{ field: "name", message: "Name is required" }] },
+    });
+
+    const error = await characters.get("char-1").catch((e: unknown) => e);
+
+    expect(error).toBeInstanceOf(CommandError);
+    expect((error as CommandError).code).toBe("VALIDATION");
+    expect((error as CommandError).details).toEqual({
+      fields: [{ field: "name", message: "Name is required" }],
+    });
+    expect(errorMessage(error)).toBe(
+      "Validation error: name: Name is required"
+    );
+    expect(String(error)).not.toContain("[object Object]");
+  });
+
+  it("errorMessage reads payloads, errors and strings", () => {
+    expect(
+      errorMessage({ code: "NOT_FOUND", message: "Not found", details: null })
+    ).toBe("Not found");
+    expect(errorMessage(new Error("Broken"))).toBe("Broken");
+    expect(errorMessage("plain")).toBe("plain");
+  });
+});
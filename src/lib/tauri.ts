@@ -1,25 +1,178 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke as tauriInvoke } from "@tauri-apps/api/core";
 import type {
   Campaign,
+  CampaignSettings,
+  BootstrapConfig,
+  BootstrapReport,
+  ArchiveSummary,
+  MarkdownImportReport,
+  JournalExport,
+  MirrorSettings,
+  MirrorReport,
   Character,
+  RoleplayCard,
   Location,
   Organization,
+  OrganizationHoldingRecord,
+  WhereaboutsRecord,
   Quest,
   Hero,
+  Item,
+  InventoryEntry,
+  ItemHolder,
   Player,
+  PlayerAvailability,
   Session,
+  SessionDateSuggestion,
+  SessionAbsenceRecord,
+  SessionClock,
+  SessionTranscriptRecord,
+  TranscriptChunk,
+  TranscriptMention,
+  PlayTimeReport,
+  SessionPrep,
+  GmScreen,
+  ProgressClock,
+  EncounterTable,
+  EncounterEntry,
+  EncounterContext,
+  EncounterResult,
+  EncounterRoll,
+  TimeOfDay,
+  Season,
+  PriceEntry,
+  PriceModifier,
+  LocalPrice,
+  GoalStatus,
+  OrganizationGoal,
+  GoalAdvance,
+  WorldMovesOnReport,
+  PlayerRecap,
+  TimelineImageOptions,
+  TimelineImage,
   TimelineEvent,
-  Secret,
+  TimelineBranch,
+  TimelineComparison,
+  SecretRecord,
+  Handout,
+  HandoutDeliveryRecord,
+  DispositionEntry,
+  DispositionHistory,
+  CharacterDisposition,
+  PlayerKnowledgeReport,
+  ContentStats,
+  EntityCounts,
+  EntityExistence,
+  DeletePreview,
+  OrphanedReferences,
+  ActivityEntry,
+  EntityDetail,
+  StubEntry,
+  WorldbuildingGaps,
+  Snippet,
+  RecallCard,
+  RecallGenerateReport,
+  QuickCaptureResult,
+  QuickCreatePlan,
+  PaletteItem,
+  InboxItem,
+  InboxSource,
+  InboxConversion,
+  PromptContextType,
+  PromptTemplateRecord,
+  RenderedPrompt,
+  SrdKind,
+  SrdEntry,
+  SrdSearchResponse,
+  SrdImportResponse,
   Relationship,
+  Attachment,
+  Draft,
   Tag,
   EntityTag,
+  TagSuggestions,
   EntityType,
   SearchResult,
+  SearchSuggestion,
+  RenderMode,
+  RenderedMarkdown,
+  SnippetOptions,
+  TypeLimits,
+  FamilyTree,
+  CsvExportSummary,
+  RelationshipSuggestion,
+  StrengthEntry,
+  StrengthHistory,
+  DecayRule,
+  FindReplaceOptions,
+  FindReplaceReport,
+  DuplicateCandidate,
+  EntityMergeReport,
+  EntityAppearance,
+  LinkReport,
+  RelatedEntity,
   ListByCampaignInput,
   GetChildrenInput,
   EntityScopedInput,
   SearchInput,
+  QuestBoard,
+  InWorldDate,
+  ContentWarning,
+  ContentWarningSeverity,
+  ContentFlag,
+  Annotation,
+  OpenAnnotation,
+  AuthoredEntity,
 } from "@/types";
+import type { ErrorCode } from "@/types/bindings/ErrorCode";
+import type { ErrorPayload } from "@/types/bindings/ErrorPayload";
+import type { JsonValue } from "@/types/bindings/serde_json/JsonValue";
+
+/**
+ * A rejected command, carrying the backend's error code and details
+ */
+export class CommandError extends Error {
+  readonly code: ErrorCode;
+  readonly details: JsonValue | null;
+
+  constructor(payload: ErrorPayload) {
+    super(payload.message);
+    this.name = "CommandError";
+    this.code = payload.code;
+    this.details = payload.details;
+  }
+}
+
+function isErrorPayload(value: unknown): value is ErrorPayload {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    "code" in value &&
+    "message" in value
+  );
+}
+
+/**
+ * Readable message for anything a command call may throw
+ */
+export function errorMessage(error: unknown): string {
+  if (error instanceof Error || isErrorPayload(error)) return error.message;
+  return String(error);
+}
+
+/**
+ * Tauri's `invoke`, rejecting with a `CommandError` instead of the raw
+ * error payload so callers get a message
+ */
+export async function invoke<T>(
+  ...args: Parameters<typeof tauriInvoke>
+): Promise<T> {
+  try {
+    return await tauriInvoke<T>(...args);
+  } catch (error) {
+    throw isErrorPayload(error) ? new CommandError(error) : error;
+  }
+}
 
 // Campaign commands
 export const campaigns = {
@@ -39,6 +192,133 @@ export const campaigns = {
   }) => invoke<Campaign>("update_campaign", data),
 
   delete: (id: string) => invoke<boolean>("delete_campaign", { id }),
+
+  // Locked campaigns reject edits with a READ_ONLY error until unlocked
+  lock: (id: string) => invoke<Campaign>("lock_campaign", { id }),
+
+  unlock: (id: string) => invoke<Campaign>("unlock_campaign", { id }),
+
+  // Campaign plus starting locations, factions, tags and session 0
+  bootstrap: (config: BootstrapConfig) =>
+    invoke<BootstrapReport>("bootstrap_campaign", { config }),
+
+  // Moves the campaign's content to an archive file, leaving a stub behind;
+  // `dir` defaults to the app's archive folder
+  archive: (id: string, dir?: string) =>
+    invoke<ArchiveSummary>("archive_campaign", { id, dir }),
+
+  unarchive: (path: string) =>
+    invoke<ArchiveSummary>("unarchive_campaign", { path }),
+
+  // One entity per .md file under `path`; check the report of a dry run
+  // before importing for real
+  importMarkdown: (campaign_id: string, path: string, dry_run?: boolean) =>
+    invoke<MarkdownImportReport>("import_markdown_folder", {
+      campaign_id,
+      path,
+      dry_run,
+    }),
+
+  getSettings: (campaign_id: string) =>
+    invoke<CampaignSettings>("get_campaign_settings", { campaign_id }),
+
+  updateSettings: (campaign_id: string, settings: CampaignSettings) =>
+    invoke<CampaignSettings>("update_campaign_settings", {
+      campaign_id,
+      settings,
+    }),
+
+  // Appends the campaign's persona, response style and banned topics
+  buildAiSystemPrompt: (campaign_id: string, base_prompt: string) =>
+    invoke<string>("build_ai_system_prompt", { campaign_id, base_prompt }),
+};
+
+// Lines and veils, and a scan of campaign content for them
+export const contentWarnings = {
+  create: (input: {
+    campaign_id: string;
+    topic: string;
+    severity: ContentWarningSeverity;
+    keywords?: string[];
+    notes?: string;
+  }) => invoke<ContentWarning>("create_content_warning", { input }),
+
+  list: (campaign_id: string) =>
+    invoke<ContentWarning[]>("list_content_warnings", { campaign_id }),
+
+  // Fields left out are kept; empty notes or keywords clear them
+  update: (
+    id: string,
+    input: {
+      topic?: string;
+      severity?: ContentWarningSeverity;
+      keywords?: string[];
+      notes?: string;
+    },
+  ) => invoke<ContentWarning>("update_content_warning", { id, input }),
+
+  delete: (id: string) => invoke<boolean>("delete_content_warning", { id }),
+
+  // Lines first; limit to one entity type with entity_type
+  check: (campaign_id: string, entity_type?: string) =>
+    invoke<ContentFlag[]>("check_content_warnings", {
+      campaign_id,
+      entity_type,
+    }),
+};
+
+// Co-GM comments on entities, kept apart from the canonical text
+export const annotations = {
+  // field names a name or text column; leave it out to comment on the entity
+  create: (input: {
+    entity_type: string;
+    entity_id: string;
+    field?: string;
+    body: string;
+    author?: string;
+  }) => invoke<Annotation>("create_annotation", { input }),
+
+  // Oldest first; resolved annotations only with include_resolved
+  list: (entity_type: string, entity_id: string, include_resolved?: boolean) =>
+    invoke<Annotation[]>("list_annotations", {
+      entity_type,
+      entity_id,
+      include_resolved,
+    }),
+
+  // Fields left out are kept; an empty author clears it
+  update: (id: string, input: { body?: string; author?: string }) =>
+    invoke<Annotation>("update_annotation", { id, input }),
+
+  resolve: (id: string) => invoke<Annotation>("resolve_annotation", { id }),
+
+  unresolve: (id: string) =>
+    invoke<Annotation>("unresolve_annotation", { id }),
+
+  delete: (id: string) => invoke<boolean>("delete_annotation", { id }),
+
+  // Every unresolved annotation in the campaign, oldest first
+  listOpen: (campaign_id: string) =>
+    invoke<OpenAnnotation[]>("list_open_annotations", { campaign_id }),
+};
+
+// Who created and last changed each entity
+export const authorship = {
+  // Name stamped on entities written on this device, or null if unset
+  getLocalProfile: () => invoke<string | null>("get_local_profile"),
+
+  // A blank or missing name stops stamping; returns the stored name
+  setLocalProfile: (name?: string) =>
+    invoke<string | null>("set_local_profile", { name }),
+
+  // "Only what I wrote": author defaults to the local profile, and
+  // include_modified adds entities they last changed. Newest first.
+  listAuthored: (input: {
+    campaign_id: string;
+    author?: string;
+    entity_type?: string;
+    include_modified?: boolean;
+  }) => invoke<AuthoredEntity[]>("list_authored_entities", input),
 };
 
 // Character commands
@@ -77,6 +357,68 @@ export const characters = {
   }) => invoke<Character>("update_character", data),
 
   delete: (id: string) => invoke<boolean>("delete_character", { id }),
+
+  renderStatBlock: (id: string, format: "text" | "markdown" | "homebrewery") =>
+    invoke<string>("render_stat_block", { id, format }),
+
+  setLifeDates: (data: {
+    id: string;
+    birth_date: InWorldDate | null;
+    death_date: InWorldDate | null;
+  }) => invoke<Character>("set_character_life_dates", data),
+
+  addAlias: (id: string, alias: string) =>
+    invoke<Character>("add_character_alias", { id, alias }),
+
+  removeAlias: (id: string, alias: string) =>
+    invoke<Character>("remove_character_alias", { id, alias }),
+
+  // Fields left out are kept; empty strings and lists clear them
+  setRoleplay: (
+    id: string,
+    input: {
+      accent?: string;
+      mannerisms?: string[];
+      catchphrases?: string[];
+      speech_sample?: string;
+    },
+  ) => invoke<Character>("set_character_roleplay", { id, input }),
+
+  // Cue card for voicing the character, with their latest sessions
+  roleplayCard: (character_id: string) =>
+    invoke<RoleplayCard>("get_roleplay_card", { character_id }),
+
+  familyTree: (character_id: string, depth?: number) =>
+    invoke<FamilyTree>("get_family_tree", { character_id, depth }),
+
+  convertToHero: (
+    character_id: string,
+    player_id?: string,
+    keep_source?: boolean,
+  ) =>
+    invoke<Hero>("convert_character_to_hero", {
+      character_id,
+      player_id,
+      keep_source,
+    }),
+};
+
+// Disposition commands
+export const dispositions = {
+  adjust: (data: {
+    character_id: string;
+    change: number;
+    reason?: string;
+    session_id?: string;
+  }) => invoke<DispositionEntry>("adjust_disposition", data),
+
+  history: (character_id: string) =>
+    invoke<DispositionHistory>("get_disposition_history", { character_id }),
+
+  list: (input: ListByCampaignInput) =>
+    invoke<CharacterDisposition[]>("list_dispositions", input),
+
+  delete: (id: string) => invoke<boolean>("delete_disposition", { id }),
 };
 
 // Location commands
@@ -87,6 +429,10 @@ export const locations = {
     parent_id?: string;
     location_type?: string;
     description?: string;
+    population?: number;
+    government_type?: string;
+    dominant_organization_id?: string;
+    economy_notes?: string;
   }) => invoke<Location>("create_location", data),
 
   get: (id: string) => invoke<Location>("get_location", { id }),
@@ -104,11 +450,39 @@ export const locations = {
     location_type?: string;
     description?: string;
     gm_notes?: string;
+    population?: number;
+    government_type?: string;
+    // Empty string clears the dominant organization
+    dominant_organization_id?: string;
+    economy_notes?: string;
   }) => invoke<Location>("update_location", data),
 
   delete: (id: string) => invoke<boolean>("delete_location", { id }),
 };
 
+// Whereabouts commands
+export const whereabouts = {
+  move: (data: {
+    character_id: string;
+    location_id: string;
+    since?: InWorldDate;
+    notes?: string;
+  }) => invoke<WhereaboutsRecord>("move_character", data),
+
+  get: (character_id: string) =>
+    invoke<WhereaboutsRecord | null>("get_character_whereabouts", {
+      character_id,
+    }),
+
+  clear: (character_id: string) =>
+    invoke<boolean>("clear_character_whereabouts", { character_id }),
+
+  listAtLocation: (location_id: string) =>
+    invoke<WhereaboutsRecord[]>("list_characters_at_location", {
+      location_id,
+    }),
+};
+
 // Organization commands
 export const organizations = {
   create: (data: {
@@ -145,6 +519,24 @@ export const organizations = {
   delete: (id: string) => invoke<boolean>("delete_organization", { id }),
 };
 
+// Organization holding commands
+export const organizationHoldings = {
+  add: (data: {
+    organization_id: string;
+    holding_type: "location" | "item";
+    holding_id: string;
+    notes?: string;
+  }) => invoke<OrganizationHoldingRecord>("add_organization_holding", data),
+
+  remove: (id: string) =>
+    invoke<boolean>("remove_organization_holding", { id }),
+
+  list: (organization_id: string) =>
+    invoke<OrganizationHoldingRecord[]>("list_organization_holdings", {
+      organization_id,
+    }),
+};
+
 // Quest commands
 export const quests = {
   create: (data: {
@@ -175,6 +567,9 @@ export const quests = {
   }) => invoke<Quest>("update_quest", data),
 
   delete: (id: string) => invoke<boolean>("delete_quest", { id }),
+
+  board: (campaign_id: string) =>
+    invoke<QuestBoard>("get_quest_board", { campaign_id }),
 };
 
 // Hero commands
@@ -207,6 +602,69 @@ export const heroes = {
   }) => invoke<Hero>("update_hero", data),
 
   delete: (id: string) => invoke<boolean>("delete_hero", { id }),
+
+  convertToCharacter: (hero_id: string, keep_source?: boolean) =>
+    invoke<Character>("convert_hero_to_character", { hero_id, keep_source }),
+};
+
+// Item commands
+export const items = {
+  create: (data: {
+    campaign_id: string;
+    name: string;
+    item_type?: string;
+    rarity?: string;
+    description?: string;
+    requires_attunement?: boolean;
+  }) => invoke<Item>("create_item", data),
+
+  get: (id: string) => invoke<Item>("get_item", { id }),
+
+  list: (input: ListByCampaignInput) => invoke<Item[]>("list_items", input),
+
+  update: (data: {
+    id: string;
+    name?: string;
+    item_type?: string;
+    rarity?: string;
+    description?: string;
+    requires_attunement?: boolean;
+  }) => invoke<Item>("update_item", data),
+
+  delete: (id: string) => invoke<boolean>("delete_item", { id }),
+};
+
+// Hero inventory commands
+export const inventory = {
+  get: (hero_id: string) =>
+    invoke<InventoryEntry[]>("get_hero_inventory", { hero_id }),
+
+  grant: (hero_id: string, item_id: string, quantity?: number) =>
+    invoke<InventoryEntry>("grant_item", { hero_id, item_id, quantity }),
+
+  remove: (hero_id: string, item_id: string, quantity?: number) =>
+    invoke<InventoryEntry | null>("remove_item", {
+      hero_id,
+      item_id,
+      quantity,
+    }),
+
+  transfer: (data: {
+    from_hero_id: string;
+    to_hero_id: string;
+    item_id: string;
+    quantity?: number;
+  }) => invoke<InventoryEntry>("transfer_item", data),
+
+  update: (data: {
+    hero_id: string;
+    item_id: string;
+    equipped?: boolean;
+    attuned?: boolean;
+  }) => invoke<InventoryEntry>("update_hero_item", data),
+
+  holders: (item_id: string) =>
+    invoke<ItemHolder[]>("find_item_holders", { item_id }),
 };
 
 // Player commands
@@ -218,6 +676,8 @@ export const players = {
     preferences?: string;
     boundaries?: string;
     notes?: string;
+    contact_email?: string;
+    contact_handle?: string;
   }) => invoke<Player>("create_player", data),
 
   get: (id: string) => invoke<Player>("get_player", { id }),
@@ -231,9 +691,14 @@ export const players = {
     preferences?: string;
     boundaries?: string;
     notes?: string;
+    contact_email?: string;
+    contact_handle?: string;
   }) => invoke<Player>("update_player", data),
 
   delete: (id: string) => invoke<boolean>("delete_player", { id }),
+
+  setAvailability: (player_id: string, availability: PlayerAvailability) =>
+    invoke<Player>("set_player_availability", { player_id, availability }),
 };
 
 // Session commands
@@ -263,11 +728,281 @@ export const sessions = {
     actual_date?: string;
     status?: string;
     summary?: string;
+    highlights?: string;
     notes?: string;
     duration_minutes?: number;
+    log_to_timeline?: boolean;
   }) => invoke<Session>("update_session", data),
 
   delete: (id: string) => invoke<boolean>("delete_session", { id }),
+
+  saveSummary: (data: {
+    id: string;
+    summary: string;
+    log_to_timeline?: boolean;
+  }) => invoke<Session>("save_session_summary", data),
+
+  logToTimeline: (data: {
+    id: string;
+    in_world_date?: InWorldDate;
+    significance?: string;
+  }) => invoke<Session>("log_session_to_timeline", data),
+
+  suggestDates: (data: {
+    campaign_id: string;
+    start_date: string;
+    end_date: string;
+    min_available?: number;
+  }) => invoke<SessionDateSuggestion[]>("suggest_session_dates", data),
+
+  recordAbsence: (data: {
+    session_id: string;
+    player_id: string;
+    reason?: string;
+  }) => invoke<SessionAbsenceRecord>("record_session_absence", data),
+
+  removeAbsence: (session_id: string, player_id: string) =>
+    invoke<boolean>("remove_session_absence", { session_id, player_id }),
+
+  listAbsences: (session_id: string) =>
+    invoke<SessionAbsenceRecord[]>("list_session_absences", { session_id }),
+
+  startClock: (session_id: string) =>
+    invoke<SessionClock>("start_session_clock", { session_id }),
+
+  pauseClock: (session_id: string) =>
+    invoke<SessionClock>("pause_session_clock", { session_id }),
+
+  stopClock: (session_id: string) =>
+    invoke<SessionClock>("stop_session_clock", { session_id }),
+
+  getClock: (session_id: string) =>
+    invoke<SessionClock>("get_session_clock", { session_id }),
+
+  playTimeReport: (campaign_id: string) =>
+    invoke<PlayTimeReport>("get_play_time_report", { campaign_id }),
+
+  generatePrep: (session_id: string) =>
+    invoke<SessionPrep>("generate_session_prep", { session_id }),
+
+  // Scenes, pinned entities, party, initiative, clocks and reminders in one call
+  gmScreen: (session_id: string) =>
+    invoke<GmScreen>("get_gm_screen", { session_id }),
+
+  // Saves the recap as a handout; without prose the summary is used
+  playerRecap: (data: {
+    session_id: string;
+    prose?: string;
+    path?: string;
+  }) => invoke<PlayerRecap>("generate_player_recap", data),
+
+  // Transcripts are .txt, .md or .vtt files read from disk
+  importTranscript: (session_id: string, path: string) =>
+    invoke<SessionTranscriptRecord>("import_session_transcript", {
+      session_id,
+      path,
+    }),
+
+  listTranscripts: (session_id: string) =>
+    invoke<SessionTranscriptRecord[]>("list_session_transcripts", {
+      session_id,
+    }),
+
+  deleteTranscript: (id: string) =>
+    invoke<boolean>("delete_session_transcript", { id }),
+
+  transcriptChunks: (id: string, max_chars?: number) =>
+    invoke<TranscriptChunk[]>("get_transcript_chunks", { id, max_chars }),
+
+  transcriptMentions: (id: string) =>
+    invoke<TranscriptMention[]>("detect_transcript_mentions", { id }),
+};
+
+// Progress clocks; negative ticks clear segments, and filling stops at the
+// clock's size
+export const progressClocks = {
+  create: (data: {
+    campaign_id: string;
+    name: string;
+    segments: number;
+    description?: string;
+    entity_type?: "organization" | "quest";
+    entity_id?: string;
+  }) => invoke<ProgressClock>("create_progress_clock", data),
+
+  get: (id: string) => invoke<ProgressClock>("get_progress_clock", { id }),
+
+  list: (data: {
+    campaign_id: string;
+    entity_type?: "organization" | "quest";
+    entity_id?: string;
+  }) => invoke<ProgressClock[]>("list_progress_clocks", data),
+
+  active: (campaign_id: string) =>
+    invoke<ProgressClock[]>("list_active_progress_clocks", { campaign_id }),
+
+  update: (data: {
+    id: string;
+    name?: string;
+    description?: string;
+    segments?: number;
+  }) => invoke<ProgressClock>("update_progress_clock", data),
+
+  tick: (id: string, ticks: number) =>
+    invoke<ProgressClock>("tick_progress_clock", { id, ticks }),
+
+  reset: (id: string) => invoke<ProgressClock>("reset_progress_clock", { id }),
+
+  delete: (id: string) => invoke<boolean>("delete_progress_clock", { id }),
+};
+
+// Encounter table commands
+export const encounters = {
+  createTable: (data: {
+    location_id: string;
+    name: string;
+    description?: string;
+  }) => invoke<EncounterTable>("create_encounter_table", data),
+
+  getTable: (id: string) =>
+    invoke<EncounterTable>("get_encounter_table", { id }),
+
+  listTables: (data: { campaign_id: string; location_id?: string }) =>
+    invoke<EncounterTable[]>("list_encounter_tables", data),
+
+  updateTable: (data: { id: string; name?: string; description?: string }) =>
+    invoke<EncounterTable>("update_encounter_table", data),
+
+  deleteTable: (id: string) =>
+    invoke<boolean>("delete_encounter_table", { id }),
+
+  createEntry: (input: {
+    table_id: string;
+    name: string;
+    character_id?: string;
+    quantity?: string;
+    weight?: number;
+    time_of_day?: TimeOfDay;
+    season?: Season;
+    notes?: string;
+  }) => invoke<EncounterEntry>("create_encounter_entry", { input }),
+
+  // Fields left out are kept; empty strings clear them
+  updateEntry: (
+    id: string,
+    input: {
+      name?: string;
+      character_id?: string;
+      quantity?: string;
+      weight?: number;
+      time_of_day?: TimeOfDay | "";
+      season?: Season | "";
+      notes?: string;
+    },
+  ) => invoke<EncounterEntry>("update_encounter_entry", { id, input }),
+
+  deleteEntry: (id: string) =>
+    invoke<boolean>("delete_encounter_entry", { id }),
+
+  // Logged to the session when context.session_id is set
+  roll: (location_id: string, context?: EncounterContext) =>
+    invoke<EncounterResult>("roll_encounter", { location_id, context }),
+
+  listRolls: (session_id: string) =>
+    invoke<EncounterRoll[]>("list_encounter_rolls", { session_id }),
+};
+
+// Price list commands. Prices are in the campaign's smallest coin.
+export const prices = {
+  create: (input: {
+    campaign_id: string;
+    name: string;
+    category: string;
+    base_price: number;
+    item_id?: string;
+    notes?: string;
+  }) => invoke<PriceEntry>("create_price", { input }),
+
+  get: (id: string) => invoke<PriceEntry>("get_price", { id }),
+
+  list: (data: { campaign_id: string; category?: string }) =>
+    invoke<PriceEntry[]>("list_prices", data),
+
+  // Fields left out are kept; empty strings clear item_id and notes
+  update: (
+    id: string,
+    input: {
+      name?: string;
+      category?: string;
+      base_price?: number;
+      item_id?: string;
+      notes?: string;
+    },
+  ) => invoke<PriceEntry>("update_price", { id, input }),
+
+  delete: (id: string) => invoke<boolean>("delete_price", { id }),
+
+  // With neither category nor price_id the modifier covers everything
+  createModifier: (data: {
+    location_id: string;
+    percent: number;
+    category?: string;
+    price_id?: string;
+    reason?: string;
+  }) => invoke<PriceModifier>("create_price_modifier", data),
+
+  listModifiers: (data: { campaign_id: string; location_id?: string }) =>
+    invoke<PriceModifier[]>("list_price_modifiers", data),
+
+  updateModifier: (data: { id: string; percent?: number; reason?: string }) =>
+    invoke<PriceModifier>("update_price_modifier", data),
+
+  deleteModifier: (id: string) =>
+    invoke<boolean>("delete_price_modifier", { id }),
+
+  // `item` is a price id or the id of an item with a price
+  local: (item: string, location_id: string) =>
+    invoke<LocalPrice>("get_local_price", { item, location_id }),
+};
+
+// Organization goal commands. Advancing ticks the goal's clock; filling it
+// achieves the goal.
+export const organizationGoals = {
+  // With segments, a progress clock is made to measure the goal
+  create: (data: {
+    organization_id: string;
+    title: string;
+    description?: string;
+    segments?: number;
+  }) => invoke<OrganizationGoal>("create_organization_goal", data),
+
+  get: (id: string) =>
+    invoke<OrganizationGoal>("get_organization_goal", { id }),
+
+  list: (data: {
+    campaign_id: string;
+    organization_id?: string;
+    status?: GoalStatus;
+  }) => invoke<OrganizationGoal[]>("list_organization_goals", data),
+
+  update: (data: {
+    id: string;
+    title?: string;
+    description?: string;
+    status?: GoalStatus;
+  }) => invoke<OrganizationGoal>("update_organization_goal", data),
+
+  advance: (data: { id: string; ticks: number; note?: string }) =>
+    invoke<OrganizationGoal>("advance_organization_goal", data),
+
+  advances: (goal_id: string) =>
+    invoke<GoalAdvance[]>("list_goal_advances", { goal_id }),
+
+  delete: (id: string) => invoke<boolean>("delete_organization_goal", { id }),
+
+  // Off-screen progress since `since`, or since the last session ended
+  worldMovesOn: (campaign_id: string, since?: string) =>
+    invoke<WorldMovesOnReport>("get_world_moves_on", { campaign_id, since }),
 };
 
 // Timeline event commands
@@ -280,11 +1015,13 @@ export const timelineEvents = {
     description?: string;
     significance?: number;
     is_public?: boolean;
+    branch_id?: string;
   }) => invoke<TimelineEvent>("create_timeline_event", data),
 
   get: (id: string) => invoke<TimelineEvent>("get_timeline_event", { id }),
 
-  list: (input: ListByCampaignInput) =>
+  // Main timeline unless a branch_id is given
+  list: (input: ListByCampaignInput & { branch_id?: string }) =>
     invoke<TimelineEvent[]>("list_timeline_events", input),
 
   update: (data: {
@@ -298,6 +1035,35 @@ export const timelineEvents = {
   }) => invoke<TimelineEvent>("update_timeline_event", data),
 
   delete: (id: string) => invoke<boolean>("delete_timeline_event", { id }),
+
+  // SVG chart with a lane per significance; writes it out when given a path
+  exportImage: (campaign_id: string, options?: TimelineImageOptions) =>
+    invoke<TimelineImage>("export_timeline_image", { campaign_id, options }),
+};
+
+// Timeline branch commands (alternate "what if" timelines)
+export const timelineBranches = {
+  create: (data: {
+    campaign_id: string;
+    name: string;
+    description?: string;
+    parent_branch_id?: string;
+    fork_event_id?: string;
+  }) => invoke<TimelineBranch>("create_timeline_branch", data),
+
+  list: (input: ListByCampaignInput) =>
+    invoke<TimelineBranch[]>("list_timeline_branches", input),
+
+  update: (data: { id: string; name?: string; description?: string }) =>
+    invoke<TimelineBranch>("update_timeline_branch", data),
+
+  delete: (id: string) => invoke<boolean>("delete_timeline_branch", { id }),
+
+  compare: (data: {
+    campaign_id: string;
+    left_branch_id?: string;
+    right_branch_id?: string;
+  }) => invoke<TimelineComparison>("compare_timeline_branches", data),
 };
 
 // Secret commands
@@ -313,11 +1079,12 @@ export const secrets = {
     revealed_date?: string;
     reveal_conditions?: string;
     importance?: number;
-  }) => invoke<Secret>("create_secret", data),
+  }) => invoke<SecretRecord>("create_secret", data),
 
-  get: (id: string) => invoke<Secret>("get_secret", { id }),
+  get: (id: string) => invoke<SecretRecord>("get_secret", { id }),
 
-  list: (input: ListByCampaignInput) => invoke<Secret[]>("list_secrets", input),
+  list: (input: ListByCampaignInput) =>
+    invoke<SecretRecord[]>("list_secrets", input),
 
   update: (data: {
     id: string;
@@ -330,9 +1097,279 @@ export const secrets = {
     revealed_date?: string;
     reveal_conditions?: string;
     importance?: number;
-  }) => invoke<Secret>("update_secret", data),
+  }) => invoke<SecretRecord>("update_secret", data),
 
   delete: (id: string) => invoke<boolean>("delete_secret", { id }),
+
+  addPrerequisite: (secret_id: string, prerequisite_id: string) =>
+    invoke<SecretRecord>("add_secret_prerequisite", {
+      secret_id,
+      prerequisite_id,
+    }),
+
+  removePrerequisite: (secret_id: string, prerequisite_id: string) =>
+    invoke<SecretRecord>("remove_secret_prerequisite", {
+      secret_id,
+      prerequisite_id,
+    }),
+};
+
+// Handout commands
+export const handouts = {
+  create: (data: {
+    campaign_id: string;
+    title: string;
+    content?: string;
+    secret_id?: string;
+  }) => invoke<Handout>("create_handout", data),
+
+  get: (id: string) => invoke<Handout>("get_handout", { id }),
+
+  list: (input: ListByCampaignInput) =>
+    invoke<Handout[]>("list_handouts", input),
+
+  update: (data: {
+    id: string;
+    title?: string;
+    content?: string;
+    secret_id?: string;
+  }) => invoke<Handout>("update_handout", data),
+
+  delete: (id: string) => invoke<boolean>("delete_handout", { id }),
+
+  send: (handout_id: string, player_ids: string[]) =>
+    invoke<HandoutDeliveryRecord[]>("send_handout", { handout_id, player_ids }),
+
+  markRead: (handout_id: string, player_id: string) =>
+    invoke<HandoutDeliveryRecord>("mark_handout_read", {
+      handout_id,
+      player_id,
+    }),
+
+  revoke: (handout_id: string, player_id: string) =>
+    invoke<boolean>("revoke_handout", { handout_id, player_id }),
+
+  deliveries: (handout_id: string) =>
+    invoke<HandoutDeliveryRecord[]>("list_handout_deliveries", { handout_id }),
+
+  forPlayer: (player_id: string) =>
+    invoke<HandoutDeliveryRecord[]>("list_player_handouts", { player_id }),
+};
+
+// Content stats commands
+export const contentStats = {
+  get: (campaign_id: string) =>
+    invoke<ContentStats>("get_content_stats", { campaign_id }),
+};
+
+// Entity reference commands
+export const entityRefs = {
+  count: (campaign_id: string) =>
+    invoke<EntityCounts>("count_entities", { campaign_id }),
+
+  exist: (refs: [EntityType, string][], campaign_id?: string) =>
+    invoke<EntityExistence[]>("entities_exist", { refs, campaign_id }),
+};
+
+// Delete preview commands
+export const deletePreview = {
+  get: (entity_type: EntityType | "campaign", id: string) =>
+    invoke<DeletePreview>("preview_delete", { entity_type, id }),
+};
+
+// Orphaned reference commands
+export const references = {
+  repairOrphans: (dry_run?: boolean) =>
+    invoke<OrphanedReferences>("repair_orphaned_references", { dry_run }),
+};
+
+// Activity feed commands
+export const activity = {
+  get: (entity_type: EntityType, id: string, limit?: number) =>
+    invoke<ActivityEntry[]>("get_entity_activity", { entity_type, id, limit }),
+};
+
+// Detail level commands
+export const detailLevels = {
+  list: (data: {
+    campaign_id: string;
+    entity_type: "character" | "location" | "organization";
+    min_level?: number;
+    max_level?: number;
+    descending?: boolean;
+  }) => invoke<EntityDetail[]>("list_entities_by_detail", data),
+
+  stubReport: (
+    campaign_id: string,
+    max_level?: number,
+    min_references?: number,
+  ) =>
+    invoke<StubEntry[]>("get_stub_report", {
+      campaign_id,
+      max_level,
+      min_references,
+    }),
+};
+
+// Worldbuilding gap commands
+export const worldbuildingGaps = {
+  get: (campaign_id: string) =>
+    invoke<WorldbuildingGaps>("get_worldbuilding_gaps", { campaign_id }),
+};
+
+// Player knowledge commands
+export const playerKnowledge = {
+  report: (campaign_id: string) =>
+    invoke<PlayerKnowledgeReport>("player_knowledge_report", { campaign_id }),
+};
+
+// Snippet commands
+export const snippets = {
+  create: (data: {
+    campaign_id: string;
+    name: string;
+    snippet_type?: string;
+    content?: string;
+  }) => invoke<Snippet>("create_snippet", data),
+
+  get: (id: string) => invoke<Snippet>("get_snippet", { id }),
+
+  list: (input: ListByCampaignInput) =>
+    invoke<Snippet[]>("list_snippets", input),
+
+  update: (data: {
+    id: string;
+    name?: string;
+    snippet_type?: string;
+    content?: string;
+  }) => invoke<Snippet>("update_snippet", data),
+
+  delete: (id: string) => invoke<boolean>("delete_snippet", { id }),
+
+  search: (data: {
+    campaign_id: string;
+    query: string;
+    snippet_type?: string;
+    limit?: number;
+  }) => invoke<Snippet[]>("search_snippets", data),
+
+  // Saves plain text as a note; text naming entities returns proposals
+  quickCapture: (campaign_id: string, text: string) =>
+    invoke<QuickCaptureResult>("quick_capture", { campaign_id, text }),
+};
+
+// Recall cards: flashcards of campaign facts. Grades run 0 (blank) to 5
+// (perfect); low grades bring a card back sooner.
+export const recallCards = {
+  create: (data: {
+    campaign_id: string;
+    front: string;
+    back: string;
+    entity_type?: EntityType;
+    entity_id?: string;
+  }) => invoke<RecallCard>("create_recall_card", data),
+
+  list: (campaign_id: string) =>
+    invoke<RecallCard[]>("list_recall_cards", { campaign_id }),
+
+  due: (campaign_id: string, limit?: number) =>
+    invoke<RecallCard[]>("list_due_recall_cards", { campaign_id, limit }),
+
+  review: (id: string, grade: number) =>
+    invoke<RecallCard>("review_recall_card", { id, grade }),
+
+  // Cards for character secrets and motivations, location descriptions and
+  // secrets; reruns only add new facts and refresh edited ones
+  generate: (campaign_id: string) =>
+    invoke<RecallGenerateReport>("generate_recall_cards", { campaign_id }),
+
+  delete: (id: string) => invoke<boolean>("delete_recall_card", { id }),
+};
+
+// Tray quick-note commands
+export const quickNotes = {
+  setActiveCampaign: (campaign_id: string | null) =>
+    invoke<void>("set_active_campaign", { campaign_id }),
+
+  // Files the note into the open campaign's inbox
+  capture: (text: string) => invoke<InboxItem>("capture_quick_note", { text }),
+};
+
+// Inbox commands
+export const inbox = {
+  create: (data: {
+    campaign_id: string;
+    source: InboxSource;
+    title: string;
+    content?: string;
+    suggested_type?: EntityType;
+  }) => invoke<InboxItem>("create_inbox_item", data),
+
+  list: (campaign_id: string) =>
+    invoke<InboxItem[]>("list_inbox_items", { campaign_id }),
+
+  convert: (id: string, entity_type: EntityType) =>
+    invoke<InboxConversion>("convert_inbox_item", { id, entity_type }),
+
+  dismiss: (id: string) => invoke<boolean>("dismiss_inbox_item", { id }),
+};
+
+// Command palette: entities, recent items and actions, ranked together
+export const commandPalette = {
+  items: (campaign_id: string, query: string) =>
+    invoke<PaletteItem[]>("get_command_palette_items", { campaign_id, query }),
+};
+
+// Quick-create commands
+export const quickCreate = {
+  // Parses e.g. "npc Elara, elf herbalist in Hobbiton, ally of Frodo";
+  // nothing is created until the plan is confirmed
+  parse: (campaign_id: string, input: string) =>
+    invoke<QuickCreatePlan>("parse_quick_create", { campaign_id, input }),
+};
+
+// Prompt template commands
+export const promptTemplates = {
+  create: (data: {
+    campaign_id: string;
+    name: string;
+    template: string;
+    context_type?: PromptContextType;
+  }) => invoke<PromptTemplateRecord>("create_prompt_template", data),
+
+  get: (id: string) =>
+    invoke<PromptTemplateRecord>("get_prompt_template", { id }),
+
+  // With a context_type, general templates are included too
+  list: (campaign_id: string, context_type?: PromptContextType) =>
+    invoke<PromptTemplateRecord[]>("list_prompt_templates", {
+      campaign_id,
+      context_type,
+    }),
+
+  update: (data: {
+    id: string;
+    name?: string;
+    template?: string;
+    context_type?: PromptContextType;
+  }) => invoke<PromptTemplateRecord>("update_prompt_template", data),
+
+  delete: (id: string) => invoke<boolean>("delete_prompt_template", { id }),
+
+  render: (template_id: string, bindings: Record<string, string>) =>
+    invoke<RenderedPrompt>("render_prompt", { template_id, bindings }),
+};
+
+// SRD lookup commands
+export const srd = {
+  search: (kind: SrdKind, query: string, limit?: number) =>
+    invoke<SrdSearchResponse>("search_srd", { kind, query, limit }),
+
+  get: (kind: SrdKind, slug: string) =>
+    invoke<SrdEntry>("get_srd_entry", { kind, slug }),
+
+  import: (campaign_id: string, kind: SrdKind, slug: string) =>
+    invoke<SrdImportResponse>("import_srd_entry", { campaign_id, kind, slug }),
 };
 
 // Relationship commands
@@ -366,6 +1403,30 @@ export const relationships = {
   }) => invoke<Relationship>("update_relationship", data),
 
   delete: (id: string) => invoke<boolean>("delete_relationship", { id }),
+
+  exportCsv: (campaign_id: string, path: string) =>
+    invoke<CsvExportSummary>("export_relationships_csv", { campaign_id, path }),
+
+  suggest: (campaign_id: string, min_mentions?: number) =>
+    invoke<RelationshipSuggestion[]>("suggest_relationships", {
+      campaign_id,
+      min_mentions,
+    }),
+
+  adjustStrength: (data: {
+    relationship_id: string;
+    change: number;
+    reason?: string;
+    session_id?: string;
+  }) => invoke<StrengthEntry>("adjust_relationship_strength", data),
+
+  strengthHistory: (relationship_id: string) =>
+    invoke<StrengthHistory>("get_relationship_strength_history", {
+      relationship_id,
+    }),
+
+  applyDecay: (campaign_id: string, rule: DecayRule) =>
+    invoke<StrengthEntry[]>("apply_relationship_decay", { campaign_id, rule }),
 };
 
 // Tag commands
@@ -393,12 +1454,194 @@ export const tags = {
 
   getForEntity: (input: EntityScopedInput) =>
     invoke<Tag[]>("get_entity_tags", input),
+
+  getForEntities: (entities: [EntityType, string][]) =>
+    invoke<{ entity_type: EntityType; entity_id: string; tags: Tag[] }[]>(
+      "get_tags_for_entities",
+      { entities },
+    ),
+
+  suggest: (entity_type: EntityType, entity_id: string) =>
+    invoke<TagSuggestions>("suggest_tags", { entity_type, entity_id }),
 };
 
 // Search commands
 export const search = {
-  entities: (input: SearchInput) =>
-    invoke<SearchResult[]>("search_entities", input),
+  entities: (
+    input: SearchInput & {
+      snippet?: SnippetOptions;
+      group_by_type?: boolean;
+      type_limits?: TypeLimits;
+    },
+  ) => invoke<SearchResult[]>("search_entities", input),
+  inLocation: (location_id: string, query: string, limit?: number) =>
+    invoke<SearchResult[]>("search_in_location", { location_id, query, limit }),
+  findAndReplace: (
+    campaign_id: string,
+    find: string,
+    replace: string,
+    options?: FindReplaceOptions,
+  ) =>
+    invoke<FindReplaceReport>("find_and_replace", {
+      campaign_id,
+      find,
+      replace,
+      options,
+    }),
+  record: (campaign_id: string, query: string) =>
+    invoke<void>("record_search_query", { campaign_id, query }),
+  suggestions: (campaign_id: string, prefix: string, limit?: number) =>
+    invoke<SearchSuggestion[]>("get_search_suggestions", {
+      campaign_id,
+      prefix,
+      limit,
+    }),
+  clearHistory: (campaign_id: string) =>
+    invoke<number>("clear_search_history", { campaign_id }),
+};
+
+// Markdown rendering; links come back as `loreweaver://{type}/{id}` anchors
+// carrying data-entity-type and data-entity-id
+export const markdown = {
+  render: (campaign_id: string, text: string, mode: RenderMode) =>
+    invoke<RenderedMarkdown>("render_markdown", { campaign_id, text, mode }),
+};
+
+// Dead [[links]], mentions and ids across a campaign's text, with similarly
+// named entities to relink them to
+export const links = {
+  check: (campaign_id: string) =>
+    invoke<LinkReport>("check_links", { campaign_id }),
+};
+
+// Duplicate detection commands
+export const duplicates = {
+  find: (
+    campaign_id: string,
+    entity_type: EntityType,
+    min_similarity?: number,
+  ) =>
+    invoke<DuplicateCandidate[]>("find_duplicate_candidates", {
+      campaign_id,
+      entity_type,
+      min_similarity,
+    }),
+  merge: (entity_type: EntityType, source_id: string, target_id: string) =>
+    invoke<EntityMergeReport>("merge_entities", {
+      entity_type,
+      source_id,
+      target_id,
+    }),
+};
+
+// Color and icon for characters, locations, organizations and quests; an
+// empty string clears a field and leaving it out keeps it
+export const appearance = {
+  set: (data: {
+    entity_type: EntityType;
+    entity_id: string;
+    color?: string;
+    icon?: string;
+  }) => invoke<EntityAppearance>("set_entity_appearance", data),
+};
+
+// Embedding provider for related entities; the API key comes from the
+// frontend's store
+export interface EmbeddingProviderConfig {
+  provider: "openai" | "ollama";
+  api_key?: string;
+  base_url?: string;
+  model?: string;
+}
+
+// Related entities commands
+export const relatedEntities = {
+  // Without a provider, entities are ranked by tags and relationships only
+  get: (
+    entity_type: EntityType,
+    entity_id: string,
+    k?: number,
+    provider?: EmbeddingProviderConfig,
+  ) =>
+    invoke<RelatedEntity[]>("get_related_entities", {
+      entity_type,
+      entity_id,
+      k,
+      provider,
+    }),
+};
+
+// Image generation provider; the API key comes from the frontend's store
+export interface ImageProviderConfig {
+  provider: "openai" | "stable_diffusion";
+  api_key?: string;
+  base_url?: string;
+  model?: string;
+  // "WIDTHxHEIGHT", 1024x1024 by default
+  size?: string;
+}
+
+export type AttachmentRole = "portrait" | "map" | "image";
+
+// Attachment commands
+export const attachments = {
+  generateImage: (
+    entity_type: EntityType,
+    id: string,
+    prompt: string,
+    provider: ImageProviderConfig,
+    role?: AttachmentRole,
+  ) =>
+    invoke<Attachment>("generate_entity_image", {
+      entity_type,
+      id,
+      prompt,
+      provider,
+      role,
+    }),
+
+  listForEntity: (entity_type: EntityType, entity_id: string) =>
+    invoke<Attachment[]>("list_entity_attachments", { entity_type, entity_id }),
+
+  getPortrait: (entity_type: EntityType, entity_id: string) =>
+    invoke<Attachment | null>("get_entity_portrait", {
+      entity_type,
+      entity_id,
+    }),
+
+  // Raw file bytes, e.g. for `URL.createObjectURL(new Blob([data]))`
+  getData: (id: string) => invoke<ArrayBuffer>("get_attachment_data", { id }),
+
+  // The player-view window's versions, which refuse entities players can't
+  // see
+  getPlayerPortrait: (entity_type: EntityType, entity_id: string) =>
+    invoke<Attachment | null>("get_player_entity_portrait", {
+      entity_type,
+      entity_id,
+    }),
+
+  getPlayerData: (id: string) =>
+    invoke<ArrayBuffer>("get_player_attachment_data", { id }),
+
+  delete: (id: string) => invoke<boolean>("delete_attachment", { id }),
+};
+
+// Draft commands; save every `autosave.interval_seconds` while editing and
+// discard once the field is saved
+export const drafts = {
+  save: (
+    entity_type: EntityType,
+    entity_id: string,
+    field: string,
+    content: string,
+  ) =>
+    invoke<Draft>("save_draft", { entity_type, entity_id, field, content }),
+
+  get: (entity_type: EntityType, entity_id: string, field: string) =>
+    invoke<Draft | null>("get_draft", { entity_type, entity_id, field }),
+
+  discard: (entity_type: EntityType, entity_id: string, field: string) =>
+    invoke<boolean>("discard_draft", { entity_type, entity_id, field }),
 };
 
 // AI Conversation types (response types from Rust commands)
@@ -433,6 +1676,48 @@ export interface ConversationWithMessages {
   messages: AiMessageResponse[];
 }
 
+export interface ProposalFieldDiff {
+  field: string;
+  current: unknown;
+  proposed: unknown;
+  changed: boolean;
+  /** False when the entity has no such field */
+  known_field: boolean;
+}
+
+export interface ProposalPreview {
+  message_id: string;
+  proposal_id: string;
+  operation: "update" | "patch";
+  entity_type: string;
+  entity_id: string;
+  entity_name: string | null;
+  status: "pending" | "accepted" | "rejected";
+  fields: ProposalFieldDiff[];
+}
+
+export interface PromptTokenEstimate {
+  conversation_id: string;
+  system_tokens: number;
+  history_tokens: number;
+  draft_tokens: number;
+  total_tokens: number;
+  context_window: number;
+  /** Room kept free for the response */
+  reserved_output_tokens: number;
+  /** The campaign's token budget, if set */
+  budget: number | null;
+  exceeds_context: boolean;
+  exceeds_budget: boolean;
+}
+
+export interface ConversationExport {
+  conversation_id: string;
+  format: "markdown" | "json";
+  content: string;
+  path: string | null;
+}
+
 // AI Conversation commands
 export const aiConversations = {
   getOrCreate: (data: { campaign_id: string; context_type: string }) =>
@@ -457,6 +1742,8 @@ export const aiConversations = {
     output_tokens: number;
     cache_read_tokens: number;
     cache_creation_tokens: number;
+    /** Estimated cost of the run, counted against the monthly budget */
+    cost_usd?: number;
   }) => invoke<AiConversationResponse>("update_ai_token_counts", data),
 
   clear: (data: { conversation_id: string }) =>
@@ -467,4 +1754,110 @@ export const aiConversations = {
 
   updateAgentMessages: (data: { conversation_id: string; agent_messages_json: string }) =>
     invoke<void>("update_ai_agent_messages", data),
+
+  // Writes to `path` when given; the rendered text is always returned
+  export: (data: {
+    conversation_id: string;
+    format: "markdown" | "json";
+    path?: string;
+  }) => invoke<ConversationExport>("export_ai_conversation", data),
+
+  // Field-by-field diff of an update or patch proposal against stored values
+  previewProposal: (message_id: string) =>
+    invoke<ProposalPreview>("preview_proposal", { message_id }),
+
+  // Approximate size of the next request, for warning before sending
+  estimateTokens: (data: {
+    conversation_id: string;
+    draft_message: string;
+    system_prompt?: string;
+  }) => invoke<PromptTokenEstimate>("estimate_prompt_tokens", data),
+
+  // Saves a Markdown copy to the snippet library
+  archive: (data: { conversation_id: string; name?: string }) =>
+    invoke<Snippet>("archive_ai_conversation", data),
+};
+
+// AI budget types
+
+export interface AiBudgetUsage {
+  campaign_id: string;
+  /** Month being counted, e.g. "2026-10" (UTC) */
+  month: string;
+  period_start: string;
+  period_end: string;
+  tokens_used: number;
+  cost_used: number;
+  token_budget: number | null;
+  cost_budget: number | null;
+  /** Share of the budget used; may exceed 1 */
+  token_fraction: number | null;
+  cost_fraction: number | null;
+  enforcement: "confirm" | "block";
+  over_budget: boolean;
+}
+
+export interface AiBudgetCheck {
+  allowed: boolean;
+  /** The request would go over budget and needs the GM's go-ahead */
+  requires_confirmation: boolean;
+  reason: string | null;
+  usage: AiBudgetUsage;
+}
+
+// AI budget commands (monthly caps from campaign settings)
+export const aiBudget = {
+  usage: (campaign_id: string) =>
+    invoke<AiBudgetUsage>("get_ai_budget_usage", { campaign_id }),
+
+  // Rejects with a VALIDATION error when the campaign blocks over-budget requests
+  check: (data: {
+    campaign_id: string;
+    estimated_tokens: number;
+    estimated_cost_usd?: number;
+    confirmed?: boolean;
+  }) => invoke<AiBudgetCheck>("check_ai_budget", data),
+
+  // Input tokens of a one-off request, counted like estimate_prompt_tokens
+  estimateTokens: (data: { system_prompt?: string; messages: string[] }) =>
+    invoke<number>("estimate_request_tokens", data),
+};
+
+// Exports of selected entities for other tools
+export const journalExport = {
+  // `json` is handout objects for a Roll20 API script, `html` a page to paste
+  // from; handouts go in players' journals only when `share_with_players`
+  roll20: (data: {
+    campaign_id: string;
+    entity_ids: string[];
+    format: "json" | "html";
+    share_with_players?: boolean;
+    path?: string;
+  }) => invoke<JournalExport>("export_roll20_journal", data),
+
+  // Markdown for Homebrewery or GM Binder; GM notes and stat blocks are
+  // included unless `include_gm_notes` is false
+  homebrewery: (data: {
+    campaign_id: string;
+    entity_ids: string[];
+    include_gm_notes?: boolean;
+    path?: string;
+  }) => invoke<JournalExport>("export_homebrewery", data),
+};
+
+// Plaintext mirror of a campaign, one JSON file per entity, for version control
+export const mirror = {
+  getSettings: (campaign_id: string) =>
+    invoke<MirrorSettings>("get_mirror_settings", { campaign_id }),
+
+  updateSettings: (data: {
+    campaign_id: string;
+    enabled: boolean;
+    path?: string;
+    auto_commit?: boolean;
+  }) => invoke<MirrorSettings>("update_mirror_settings", data),
+
+  // Call after a batch of edits; cheap when nothing changed
+  run: (campaign_id: string, force?: boolean) =>
+    invoke<MirrorReport>("mirror_campaign", { campaign_id, force }),
 };
@@ -0,0 +1,52 @@
+import { useState } from "react";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { Button } from "@/components/ui/button";
+import { Textarea } from "@/components/ui/textarea";
+import { errorMessage, quickNotes } from "@/lib/tauri";
+
+/** Minimal capture window opened from the tray's "Quick note" item */
+export function QuickNotePage() {
+  const [text, setText] = useState("");
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const save = async () => {
+    if (!text.trim()) return;
+    setIsSaving(true);
+    setError(null);
+    try {
+      await quickNotes.capture(text);
+      setText("");
+      await getCurrentWindow().hide();
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <div className="flex h-screen flex-col gap-2 bg-background p-3">
+      <Textarea
+        autoFocus
+        className="flex-1 resize-none"
+        placeholder="Jot down an idea..."
+        value={text}
+        onChange={(e) => setText(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === "Enter" && (e.ctrlKey || e.metaKey)) save();
+          if (e.key === "Escape") getCurrentWindow().hide();
+        }}
+      />
+      {error && <p className="text-sm text-destructive">{error}</p>}
+      <div className="flex items-center justify-between">
+        <span className="text-xs text-muted-foreground">
+          Ctrl+Enter to save to the inbox
+        </span>
+        <Button size="sm" onClick={save} disabled={isSaving || !text.trim()}>
+          Save
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -18,4 +18,5 @@ export { TimelinePage } from "./TimelinePage";
 export { TimelineEventDetailPage } from "./TimelineEventDetailPage";
 export { SearchPage } from "./SearchPage";
 export { SettingsPage } from "./SettingsPage";
+export { QuickNotePage } from "./QuickNotePage";
 export { PlaceholderPage } from "./PlaceholderPage";
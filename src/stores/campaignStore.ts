@@ -1,6 +1,6 @@
 import { create } from "zustand";
 import { persist } from "zustand/middleware";
-import { campaigns } from "@/lib/tauri";
+import { campaigns, errorMessage } from "@/lib/tauri";
 import type { Campaign } from "@/types";
 
 interface CampaignState {
@@ -51,7 +51,7 @@ export const useCampaignStore = create<CampaignState>()(
           const result = await campaigns.list();
           set({ campaigns: result, isLoading: false });
         } catch (e) {
-          set({ error: String(e), isLoading: false });
+          set({ error: errorMessage(e), isLoading: false });
         }
       },
 
@@ -273,6 +273,52 @@ describe("chatStore - Persistence", () => {
     });
   });
 
+  describe("addRetryNotice", () => {
+    it("persists failed attempts without ending the run", async () => {
+      useChatStore.setState({ conversationId: "conv-uuid", isRunning: true });
+
+      mockInvoke.mockResolvedValue({
+        id: "msg-retry-uuid",
+        conversation_id: "conv-uuid",
+        role: "error",
+        content: "Rate limited",
+        tool_name: null,
+        tool_input_json: null,
+        tool_data_json: null,
+        proposal_json: null,
+        message_order: 4,
+        created_at: "2024-01-01T00:00:00Z",
+      });
+
+      const store = useChatStore.getState();
+      await store.addRetryNotice({
+        attempt: 1,
+        maxAttempts: 4,
+        status: 429,
+        message: "Rate limited",
+        retryInMs: 2000,
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith("add_ai_message", {
+        conversation_id: "conv-uuid",
+        role: "error",
+        content: "Rate limited (attempt 1 of 4, retrying in 2s)",
+        tool_data_json: JSON.stringify({
+          code: "AI_PROVIDER",
+          status: 429,
+          attempt: 1,
+          max_attempts: 4,
+          retry_in_ms: 2000,
+        }),
+      });
+
+      const state = useChatStore.getState();
+      expect(state.messages[0].id).toBe("msg-retry-uuid");
+      expect(state.error).toBeNull();
+      expect(state.isRunning).toBe(true);
+    });
+  });
+
   describe("addProposal", () => {
     it("persists proposal with proposal_json", async () => {
       useChatStore.setState({ conversationId: "conv-uuid" });
@@ -9,7 +9,12 @@
 import { create } from "zustand";
 import type { MessageParam } from "@anthropic-ai/sdk/resources/messages";
 import type { EntityProposal } from "@/ai/tools/entity-proposals/types";
-import { aiConversations, type AiMessageResponse } from "@/lib/tauri";
+import type { RetryAttempt } from "@/ai/retry";
+import {
+  aiConversations,
+  errorMessage,
+  type AiMessageResponse,
+} from "@/lib/tauri";
 import type { AiContextType } from "@/types";
 
 /** Tool category determines UI behavior */
@@ -82,7 +87,10 @@ interface ChatState {
   addUserMessage: (content: string) => Promise<string>;
   addAssistantMessage: (content: string, toolName?: string) => string;
   addToolResult: (content: string, toolName: string, toolData?: unknown) => Promise<string>;
-  addError: (error: string) => Promise<string>;
+  /** Add an error message; `details` is stored as its tool data */
+  addError: (error: string, details?: unknown) => Promise<string>;
+  /** Record a failed AI request that is about to be retried (the run keeps going) */
+  addRetryNotice: (attempt: RetryAttempt) => Promise<string>;
   updateMessage: (id: string, content: string) => void;
   setRunning: (running: boolean) => void;
   clearMessages: () => Promise<void>;
@@ -95,6 +103,8 @@ interface ChatState {
     outputTokens: number;
     cacheReadTokens: number;
     cacheCreationTokens: number;
+    /** Estimated cost, recorded against the campaign's monthly budget */
+    costUsd?: number;
   }) => Promise<void>;
   /** Add to live token counts (cumulative during operation) */
   addLiveTokens: (inputTokens: number, outputTokens: number) => void;
@@ -274,7 +284,7 @@ export const useChatStore = create<ChatState>((set, get) => ({
       }
     } catch (err) {
       console.error("Failed to load conversation:", err);
-      set({ isLoading: false, error: String(err) });
+      set({ isLoading: false, error: errorMessage(err) });
     }
   },
 
@@ -375,7 +385,7 @@ export const useChatStore = create<ChatState>((set, get) => ({
     return id;
   },
 
-  addError: async (error: string) => {
+  addError: async (error: string, details?: unknown) => {
     const { conversationId } = get();
     const id = generateId();
 
@@ -386,6 +396,7 @@ export const useChatStore = create<ChatState>((set, get) => ({
           id,
           role: "error",
           content: error,
+          toolData: details,
           timestamp: new Date(),
         },
       ],
@@ -396,7 +407,14 @@ export const useChatStore = create<ChatState>((set, get) => ({
     // Persist to database
     if (conversationId) {
       try {
-        const dbMsg = await persistMessage(conversationId, "error", error);
+        const dbMsg = await persistMessage(
+          conversationId,
+          "error",
+          error,
+          undefined,
+          undefined,
+          details
+        );
         set((state) => ({
           messages: state.messages.map((msg) =>
             msg.id === id ? { ...msg, id: dbMsg.id } : msg
@@ -411,6 +429,57 @@ export const useChatStore = create<ChatState>((set, get) => ({
     return id;
   },
 
+  addRetryNotice: async (attempt: RetryAttempt) => {
+    const { conversationId } = get();
+    const id = generateId();
+    const seconds = Math.max(1, Math.round(attempt.retryInMs / 1000));
+    const content = `${attempt.message} (attempt ${attempt.attempt} of ${attempt.maxAttempts}, retrying in ${seconds}s)`;
+    const details = {
+      code: "AI_PROVIDER",
+      status: attempt.status,
+      attempt: attempt.attempt,
+      max_attempts: attempt.maxAttempts,
+      retry_in_ms: attempt.retryInMs,
+    };
+
+    // Unlike addError, the run is still going
+    set((state) => ({
+      messages: [
+        ...state.messages,
+        {
+          id,
+          role: "error",
+          content,
+          toolData: details,
+          timestamp: new Date(),
+        },
+      ],
+    }));
+
+    if (conversationId) {
+      try {
+        const dbMsg = await persistMessage(
+          conversationId,
+          "error",
+          content,
+          undefined,
+          undefined,
+          details
+        );
+        set((state) => ({
+          messages: state.messages.map((msg) =>
+            msg.id === id ? { ...msg, id: dbMsg.id } : msg
+          ),
+        }));
+        return dbMsg.id;
+      } catch (err) {
+        console.error("Failed to persist retry notice:", err);
+      }
+    }
+
+    return id;
+  },
+
   updateMessage: (id: string, content: string) => {
     set((state) => ({
       messages: state.messages.map((msg) =>
@@ -487,6 +556,7 @@ export const useChatStore = create<ChatState>((set, get) => ({
           output_tokens: usage.outputTokens,
           cache_read_tokens: usage.cacheReadTokens,
           cache_creation_tokens: usage.cacheCreationTokens,
+          cost_usd: usage.costUsd,
         });
       } catch (err) {
         console.error("Failed to update token counts:", err);
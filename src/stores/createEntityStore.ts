@@ -1,5 +1,5 @@
 import { create, type StateCreator } from "zustand";
-import { invoke } from "@tauri-apps/api/core";
+import { errorMessage, invoke } from "@/lib/tauri";
 import type { ListByCampaignInput } from "@/types";
 
 export interface BaseEntity {
@@ -46,7 +46,7 @@ export function createEntityStore<T extends BaseEntity>(
         const entities = await invoke<T[]>(`list_${plural}`, input);
         set({ entities, isLoading: false });
       } catch (e) {
-        set({ error: String(e), isLoading: false });
+        set({ error: errorMessage(e), isLoading: false });
       }
     },
 
@@ -1,4 +1,5 @@
 import { createEntityStore, type BaseEntity } from "./createEntityStore";
+import type { InWorldDate } from "@/types";
 
 // Character entity
 interface CharacterEntity extends BaseEntity {
@@ -12,6 +13,11 @@ interface CharacterEntity extends BaseEntity {
   secrets: string | null;
   voice_notes: string | null;
   stat_block_json: string | null;
+  aliases: string[];
+  birth_date: InWorldDate | null;
+  death_date: InWorldDate | null;
+  death_event_id: string | null;
+  age: number | null;
 }
 
 // Location entity
@@ -19,6 +19,8 @@ const createMockRelationship = (id: string): Relationship => ({
   is_public: true,
   created_at: "2024-01-01T00:00:00Z",
   updated_at: "2024-01-01T00:00:00Z",
+  created_by: null,
+  modified_by: null,
 });
 
 describe("relationshipStore", () => {
@@ -81,7 +83,7 @@ describe("relationshipStore", () => {
       await store.fetchForEntity("quest", "quest-1");
 
       const state = useRelationshipStore.getState();
-      expect(state.error).toBe("Error: Database error");
+      expect(state.error).toBe("Database error");
       expect(state.isLoading).toBe(false);
       expect(state.relationships).toEqual([]);
     });
@@ -113,12 +115,17 @@ describe("relationshipStore", () => {
     });
 
     it("handles errors in campaign fetch", async () => {
-      mockInvoke.mockRejectedValue(new Error("Network error"));
+      // Commands reject with the backend's error payload, not an Error
+      mockInvoke.mockRejectedValue({
+        code: "NOT_FOUND",
+        message: "Not found: Campaign camp-123 not found",
+        details: null,
+      });
 
       await useRelationshipStore.getState().fetchForCampaign("camp-123");
 
       const state = useRelationshipStore.getState();
-      expect(state.error).toBe("Error: Network error");
+      expect(state.error).toBe("Not found: Campaign camp-123 not found");
       expect(state.isLoading).toBe(false);
     });
   });
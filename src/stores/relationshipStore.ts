@@ -1,5 +1,5 @@
 import { create } from "zustand";
-import { relationships } from "@/lib/tauri";
+import { errorMessage, relationships } from "@/lib/tauri";
 import type { Relationship, EntityType } from "@/types";
 
 interface RelationshipState {
@@ -48,7 +48,7 @@ export const useRelationshipStore = create<RelationshipState>((set) => ({
       });
       set({ relationships: result, isLoading: false });
     } catch (e) {
-      set({ error: String(e), isLoading: false });
+      set({ error: errorMessage(e), isLoading: false });
     }
   },
 
@@ -58,7 +58,7 @@ export const useRelationshipStore = create<RelationshipState>((set) => ({
       const result = await relationships.list({ campaign_id: campaignId });
       set({ relationships: result, isLoading: false });
     } catch (e) {
-      set({ error: String(e), isLoading: false });
+      set({ error: errorMessage(e), isLoading: false });
     }
   },
 
@@ -10,6 +10,7 @@ const createMockSearchResult = (id: string, name: string): SearchResult => ({
   entity_id: id,
   name: name,
   snippet: `Description of ${name}`,
+  matched_field: "name",
   rank: 1.0,
 });
 
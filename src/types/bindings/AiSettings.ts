@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Per-campaign overrides for the AI assistant
+ */
+export type AiSettings = { model: string | null, temperature: number | null, max_tokens: number | null, 
+/**
+ * Voice and role the assistant takes on for this campaign
+ */
+persona: string | null, 
+/**
+ * Subjects the assistant must not raise or write about
+ */
+banned_topics: Array<string>, 
+/**
+ * One of `AI_RESPONSE_STYLES`; the assistant's default when unset
+ */
+response_style: string | null, 
+/**
+ * Prompt tokens a single request may use before the UI warns
+ */
+token_budget: number | null, 
+/**
+ * Tokens the campaign may use per calendar month
+ */
+monthly_token_budget: number | null, 
+/**
+ * Estimated spend allowed per calendar month, in US dollars
+ */
+monthly_cost_budget: number | null, 
+/**
+ * One of `AI_BUDGET_ENFORCEMENTS`; "confirm" when unset
+ */
+budget_enforcement: string | null, };
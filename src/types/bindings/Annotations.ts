@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Annotations = { id: string, campaign_id: string, entity_type: string, entity_id: string, field: string | null, body: string, author: string | null, resolved_at: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
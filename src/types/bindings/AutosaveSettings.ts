@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How often the editor saves drafts of long text fields
+ */
+export type AutosaveSettings = { 
+/**
+ * Seconds between draft saves while a field is being edited;
+ * 5 (`DEFAULT_AUTOSAVE_SECONDS`) when unset, and 0 turns autosave off
+ */
+interval_seconds: number | null, };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarMonth } from "./CalendarMonth";
+import type { InWorldDate } from "./InWorldDate";
+
+/**
+ * In-world calendar used for timeline dates
+ */
+export type CalendarSettings = { name: string | null, months: Array<CalendarMonth>, weekdays: Array<string>, 
+/**
+ * Era suffix shown after years, e.g. "DR"
+ */
+era: string | null, 
+/**
+ * Current in-world date as displayed, e.g. "3 Hammer 1492 DR"
+ */
+current_date: string | null, 
+/**
+ * Current in-world date, used to work out character ages
+ */
+today: InWorldDate | null, };
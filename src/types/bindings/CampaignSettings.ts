@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AiSettings } from "./AiSettings";
+import type { AutosaveSettings } from "./AutosaveSettings";
+import type { CalendarSettings } from "./CalendarSettings";
+import type { EntityDefaults } from "./EntityDefaults";
+import type { PlayerViewSettings } from "./PlayerViewSettings";
+import type { TimelineSettings } from "./TimelineSettings";
+
+export type CampaignSettings = { version: number, ai: AiSettings, autosave: AutosaveSettings, calendar: CalendarSettings, defaults: EntityDefaults, player_view: PlayerViewSettings, timeline: TimelineSettings, };
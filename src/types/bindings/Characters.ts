@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Characters = { id: string, campaign_id: string, name: string, lineage: string | null, occupation: string | null, is_alive: boolean, description: string | null, personality: string | null, motivations: string | null, secrets: string | null, voice_notes: string | null, stat_block_json: string | null, created_at: string, updated_at: string, };
+export type Characters = { id: string, campaign_id: string, name: string, lineage: string | null, occupation: string | null, is_alive: boolean, description: string | null, personality: string | null, motivations: string | null, secrets: string | null, voice_notes: string | null, stat_block_json: string | null, birth_year: number | null, birth_month: number | null, birth_day: number | null, death_year: number | null, death_month: number | null, death_day: number | null, death_event_id: string | null, aliases_json: string | null, source_hero_id: string | null, color: string | null, icon: string | null, accent: string | null, mannerisms_json: string | null, catchphrases_json: string | null, speech_sample: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
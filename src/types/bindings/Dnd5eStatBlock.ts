@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StatBlockFeature } from "./StatBlockFeature";
+
+/**
+ * D&D 5th edition monster statistics
+ */
+export type Dnd5eStatBlock = { 
+/**
+ * e.g. "Medium"
+ */
+size?: string, 
+/**
+ * e.g. "humanoid (elf)"
+ */
+creature_type?: string, alignment?: string, armor_class?: number, 
+/**
+ * Where the armor class comes from, e.g. "natural armor"
+ */
+armor_note?: string, hit_points?: number, hit_dice?: string, speed?: string, 
+/**
+ * Ability scores keyed by short name; missing scores count as 10
+ */
+abilities?: { [key in string]?: number }, saving_throws?: { [key in string]?: number }, skills?: { [key in string]?: number }, damage_resistances?: Array<string>, damage_immunities?: Array<string>, condition_immunities?: Array<string>, senses?: Array<string>, languages?: Array<string>, challenge_rating?: string, traits?: Array<StatBlockFeature>, actions?: Array<StatBlockFeature>, bonus_actions?: Array<StatBlockFeature>, reactions?: Array<StatBlockFeature>, legendary_actions?: Array<StatBlockFeature>, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type EncounterEntries = { id: string, campaign_id: string, table_id: string, name: string, character_id: string | null, quantity: string | null, weight: number, time_of_day: string | null, season: string | null, notes: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Values pre-filled when creating entities
+ */
+export type EntityDefaults = { location_type: string | null, org_type: string | null, quest_status: string | null, plot_type: string | null, };
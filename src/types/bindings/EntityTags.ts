@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type EntityTags = { tag_id: string, entity_type: string, entity_id: string, };
+export type EntityTags = { tag_id: string, entity_type: string, entity_id: string, created_at: string, };
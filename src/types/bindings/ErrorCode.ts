@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Stable error codes the frontend can branch on instead of matching messages
+ */
+export type ErrorCode = "NOT_FOUND" | "VALIDATION" | "CONFLICT" | "DB" | "AI_PROVIDER" | "READ_ONLY" | "INCOMPATIBLE_DATABASE" | "INTERNAL";
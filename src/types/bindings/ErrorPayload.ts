@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ErrorCode } from "./ErrorCode";
+import type { JsonValue } from "./serde_json/JsonValue";
+
+/**
+ * Shape of every error returned from a Tauri command
+ */
+export type ErrorPayload = { code: ErrorCode, message: string, details: JsonValue | null, };
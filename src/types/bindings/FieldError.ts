@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A single failed validation rule on an input field
+ */
+export type FieldError = { field: string, 
+/**
+ * Validator rule code, e.g. `length` or `invalid_location_type`
+ */
+code: string, message: string, };
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Heroes = { id: string, campaign_id: string, player_id: string | null, name: string, lineage: string | null, classes: string | null, description: string | null, backstory: string | null, goals: string | null, bonds: string | null, is_active: boolean, created_at: string, updated_at: string, };
+export type Heroes = { id: string, campaign_id: string, player_id: string | null, name: string, lineage: string | null, classes: string | null, description: string | null, backstory: string | null, goals: string | null, bonds: string | null, is_active: boolean, source_character_id: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A date on the campaign calendar. Months and days are 1-based.
+ */
+export type InWorldDate = { year: number, month: number, day: number, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Items = { id: string, campaign_id: string, name: string, item_type: string, rarity: string | null, description: string | null, requires_attunement: boolean, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Locations = { id: string, campaign_id: string, parent_id: string | null, name: string, location_type: string, description: string | null, gm_notes: string | null, created_at: string, updated_at: string, };
+export type Locations = { id: string, campaign_id: string, parent_id: string | null, name: string, location_type: string, description: string | null, gm_notes: string | null, color: string | null, icon: string | null, created_at: string, updated_at: string, population: bigint | null, government_type: string | null, dominant_organization_id: string | null, economy_notes: string | null, created_by: string | null, modified_by: string | null, };
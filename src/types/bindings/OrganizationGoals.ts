@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type OrganizationGoals = { id: string, campaign_id: string, organization_id: string, title: string, description: string | null, status: string, clock_id: string | null, created_at: string, updated_at: string, created_by: string | null, modified_by: string | null, };
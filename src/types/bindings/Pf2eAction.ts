@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A PF2e activity: a strike, spell or other action with its cost
+ */
+export type Pf2eAction = { name: string, 
+/**
+ * "1", "2", "3", "free" or "reaction"
+ */
+cost?: string, traits?: Array<string>, description: string, };
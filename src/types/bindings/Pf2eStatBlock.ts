@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Pf2eAction } from "./Pf2eAction";
+import type { StatBlockFeature } from "./StatBlockFeature";
+
+/**
+ * Pathfinder 2nd edition creature statistics
+ */
+export type Pf2eStatBlock = { level?: number, size?: string, 
+/**
+ * Creature traits such as "humanoid" or "undead"
+ */
+traits?: Array<string>, perception?: number, senses?: Array<string>, languages?: Array<string>, skills?: { [key in string]?: number }, 
+/**
+ * Attribute modifiers keyed by short name, e.g. "dex": 3
+ */
+attributes?: { [key in string]?: number }, armor_class?: number, fortitude?: number, reflex?: number, will?: number, hit_points?: number, immunities?: Array<string>, 
+/**
+ * Damage type to amount, e.g. "fire": 5
+ */
+weaknesses?: { [key in string]?: number }, resistances?: { [key in string]?: number }, speed?: string, 
+/**
+ * Passive abilities
+ */
+abilities?: Array<StatBlockFeature>, actions?: Array<Pf2eAction>, };
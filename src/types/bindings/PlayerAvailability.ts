@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * When a player can usually make a session
+ */
+export type PlayerAvailability = { 
+/**
+ * Weekdays the player is usually free, e.g. "fri". Empty means no
+ * recurring schedule is known and every day counts as free.
+ */
+weekdays?: Array<string>, 
+/**
+ * One-off dates the player is free outside their usual weekdays
+ */
+available_dates?: Array<string>, 
+/**
+ * One-off dates the player can't make, overriding everything else
+ */
+unavailable_dates?: Array<string>, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What the player companion view is allowed to show
+ */
+export type PlayerViewSettings = { 
+/**
+ * Entity types shared when no explicit selection is made; empty means all
+ */
+shared_entity_types: Array<string>, hide_dead_characters: boolean, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A pause in play recorded by the session clock
+ */
+export type SessionBreak = { started_at: string, 
+/**
+ * Unset while the break is still going
+ */
+ended_at?: string, };
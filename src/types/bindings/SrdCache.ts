@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SrdCache = { id: string, kind: string, slug: string, name: string, data_json: string, fetched_at: string, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StatBlockFeature } from "./StatBlockFeature";
+
+/**
+ * Game-system-agnostic creature statistics
+ */
+export type StatBlock = { armor_class?: number, hit_points?: number, 
+/**
+ * Hit dice expression, e.g. "6d8+12"
+ */
+hit_dice?: string, speed?: string, 
+/**
+ * Kept as text so fractions like "1/2" read naturally
+ */
+challenge_rating?: string, 
+/**
+ * Ability scores keyed by short name, e.g. "str": 16
+ */
+abilities?: { [key in string]?: number }, 
+/**
+ * Skill bonuses keyed by skill name
+ */
+skills?: { [key in string]?: number }, senses?: Array<string>, languages?: Array<string>, traits?: Array<StatBlockFeature>, actions?: Array<StatBlockFeature>, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SyncState = { campaign_id: string, enabled: boolean, server_url: string | null, auth_token: string | null, last_pulled_watermark: string | null, last_pushed_watermark: string | null, last_synced_at: string | null, updated_at: string, };
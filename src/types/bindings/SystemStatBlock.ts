@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Dnd5eStatBlock } from "./Dnd5eStatBlock";
+import type { Pf2eStatBlock } from "./Pf2eStatBlock";
+import type { StatBlock } from "./StatBlock";
+
+/**
+ * A stat block tagged with the game system it follows. Blocks saved before
+ * systems existed have no `system` key and read as generic.
+ */
+export type SystemStatBlock = { "system": "generic" } & StatBlock | { "system": "5e" } & Dnd5eStatBlock | { "system": "pf2e" } & Pf2eStatBlock;
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How play is mirrored onto the world timeline
+ */
+export type TimelineSettings = { 
+/**
+ * Log a timeline event whenever a session summary is saved
+ */
+log_sessions: boolean, 
+/**
+ * Significance given to logged session events; "local" when unset
+ */
+session_significance: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Tombstones = { id: string, campaign_id: string, entity_type: string, entity_id: string, deleted_at: string, };
@@ -1,3 +1,14 @@
+import type { CampaignSettings } from "./bindings/CampaignSettings";
+import type { Campaigns } from "./bindings/Campaigns";
+import type { Handouts } from "./bindings/Handouts";
+import type { InWorldDate } from "./bindings/InWorldDate";
+import type { Secrets } from "./bindings/Secrets";
+import type { SessionBreak } from "./bindings/SessionBreak";
+import type { Sessions } from "./bindings/Sessions";
+import type { Snippets } from "./bindings/Snippets";
+import type { Tags } from "./bindings/Tags";
+import type { TimelineEvents } from "./bindings/TimelineEvents";
+
 // Re-export generated types with singular names
 export type { Campaigns as Campaign } from "./bindings/Campaigns";
 export type { Characters as Character } from "./bindings/Characters";
@@ -5,15 +16,33 @@ export type { Locations as Location } from "./bindings/Locations";
 export type { Organizations as Organization } from "./bindings/Organizations";
 export type { Quests as Quest } from "./bindings/Quests";
 export type { Heroes as Hero } from "./bindings/Heroes";
+export type { Items as Item } from "./bindings/Items";
+export type { HeroItems as HeroItem } from "./bindings/HeroItems";
+export type { OrganizationHoldings as OrganizationHolding } from "./bindings/OrganizationHoldings";
+export type { Whereabouts } from "./bindings/Whereabouts";
 export type { Players as Player } from "./bindings/Players";
 export type { Sessions as Session } from "./bindings/Sessions";
+export type { SessionAbsences as SessionAbsence } from "./bindings/SessionAbsences";
+export type { SessionTranscripts as SessionTranscript } from "./bindings/SessionTranscripts";
+export type { Dispositions as Disposition } from "./bindings/Dispositions";
 export type { TimelineEvents as TimelineEvent } from "./bindings/TimelineEvents";
+export type { TimelineBranches as TimelineBranch } from "./bindings/TimelineBranches";
 export type { Secrets as Secret } from "./bindings/Secrets";
+export type { Handouts as Handout } from "./bindings/Handouts";
+export type { HandoutDeliveries as HandoutDelivery } from "./bindings/HandoutDeliveries";
+export type { Snippets as Snippet } from "./bindings/Snippets";
+export type { InboxItems as InboxItem } from "./bindings/InboxItems";
+export type { PromptTemplates as PromptTemplate } from "./bindings/PromptTemplates";
 export type { Relationships as Relationship } from "./bindings/Relationships";
+export type { StrengthChanges as StrengthChange } from "./bindings/StrengthChanges";
 export type { Tags as Tag } from "./bindings/Tags";
 export type { EntityTags as EntityTag } from "./bindings/EntityTags";
 export type { AiConversations as AiConversation } from "./bindings/AiConversations";
 export type { AiMessages as AiMessage } from "./bindings/AiMessages";
+export type { AiUsage } from "./bindings/AiUsage";
+export type { SearchHistory } from "./bindings/SearchHistory";
+export type { MirrorState } from "./bindings/MirrorState";
+export type { Attachments as Attachment } from "./bindings/Attachments";
 
 // AI conversation context type
 export type AiContextType = "sidebar" | "fullpage";
@@ -24,6 +53,23 @@ export type { GetChildrenInput } from "./bindings/GetChildrenInput";
 export type { EntityScopedInput } from "./bindings/EntityScopedInput";
 export type { SearchInput } from "./bindings/SearchInput";
 
+// Typed campaign settings (stored in campaigns.settings_json)
+export type { CampaignSettings } from "./bindings/CampaignSettings";
+export type { InWorldDate } from "./bindings/InWorldDate";
+
+// Typed character stat blocks (stored in characters.stat_block_json)
+export type { StatBlock } from "./bindings/StatBlock";
+export type { SystemStatBlock } from "./bindings/SystemStatBlock";
+export type { Dnd5eStatBlock } from "./bindings/Dnd5eStatBlock";
+export type { Pf2eStatBlock } from "./bindings/Pf2eStatBlock";
+export type { Pf2eAction } from "./bindings/Pf2eAction";
+
+// Typed player schedules (stored in players.availability_json)
+export type { PlayerAvailability } from "./bindings/PlayerAvailability";
+
+// Typed session breaks (stored in sessions.breaks_json)
+export type { SessionBreak } from "./bindings/SessionBreak";
+
 // Entity type union for polymorphic operations
 export type EntityType =
   | "campaign"
@@ -32,10 +78,12 @@ export type EntityType =
   | "organization"
   | "quest"
   | "hero"
+  | "item"
   | "player"
   | "session"
   | "timeline_event"
-  | "secret";
+  | "secret"
+  | "handout";
 
 // Entity with common fields
 export interface BaseEntity {
@@ -50,9 +98,1194 @@ export interface SearchResult {
   entity_id: string;
   name: string;
   snippet: string | null;
+  // Field the query hit; a hit on both counts as "name"
+  matched_field: "name" | "content" | null;
   rank: number;
 }
 
+// Search box suggestion from get_search_suggestions
+export interface SearchSuggestion {
+  source: "history" | "entity";
+  text: string;
+  entity_type: EntityType | null;
+  entity_id: string | null;
+}
+
+// Autosaved text of an entity's long text field
+export interface Draft {
+  id: string;
+  campaign_id: string;
+  entity_type: EntityType;
+  entity_id: string;
+  field: string;
+  content: string;
+  updated_at: string;
+  // The entity was saved after the draft was last written
+  stale: boolean;
+}
+
+// "player" drops ```gm blocks and links only entities in the player view
+export type RenderMode = "gm" | "player";
+
+// An entity quick_capture found in pasted text, not yet created
+export interface CaptureProposal {
+  entity_type: EntityType;
+  name: string;
+  description: string | null;
+  // The campaign's entity of this type and name, if it already exists
+  existing_id: string | null;
+}
+
+// The note saved when the text named no entities, else the proposals
+export interface QuickCaptureResult {
+  note: Snippets | null;
+  proposals: CaptureProposal[];
+}
+
+// An entity parse_quick_create found, new or already in the campaign
+export interface ProposedEntity {
+  entity_type: EntityType;
+  name: string;
+  // Fields to set, such as lineage, occupation or location_type
+  fields: Record<string, string>;
+  description: string | null;
+  // Parent location's name, for locations
+  parent: string | null;
+  existing_id: string | null;
+}
+
+// A relationship between two entities of the plan, by type and name
+export interface ProposedRelationship {
+  source_type: EntityType;
+  source_name: string;
+  relationship_type: string;
+  target_type: EntityType;
+  target_name: string;
+  is_bidirectional: boolean;
+}
+
+// What a quick-create line describes, for the GM to confirm
+export interface QuickCreatePlan {
+  entities: ProposedEntity[];
+  relationships: ProposedRelationship[];
+}
+
+// Where inbox items come from
+export type InboxSource = "quick_note" | "capture" | "ai_suggestion" | "import";
+
+// The entity an inbox item was converted into
+export interface InboxConversion {
+  entity_type: EntityType;
+  entity_id: string;
+}
+
+// One row of the command palette
+export interface PaletteItem {
+  // "recent" is a recently edited entity
+  kind: "entity" | "recent" | "action";
+  // Entity id, or an action id such as "new_session" or "start_backup"
+  id: string;
+  label: string;
+  entity_type: EntityType | null;
+  score: number;
+}
+
+// Sanitized HTML from render_markdown
+export interface RenderedMarkdown {
+  html: string;
+  // Links matching no entity, as written; always empty in player mode
+  unresolved_links: string[];
+}
+
+// How a dead link was written: [[link]], rich text mention, or a bare id
+export type LinkKind = "wiki_link" | "mention" | "raw_id";
+
+// An entity a dead link may have meant
+export interface LinkSuggestion {
+  entity_type: EntityType;
+  entity_id: string;
+  name: string;
+  similarity: number;
+}
+
+// A link in an entity's text that no longer leads anywhere
+export interface DeadLink {
+  entity_type: string;
+  entity_id: string;
+  entity_name: string | null;
+  field: string;
+  kind: LinkKind;
+  // The text between the brackets, or the id
+  link: string;
+  // Set when the target's deletion is on record
+  deleted_type: string | null;
+  suggestions: LinkSuggestion[];
+}
+
+// Result of check_links
+export interface LinkReport {
+  campaign_id: string;
+  links_checked: number;
+  dead_links: DeadLink[];
+}
+
+// How search_entities cuts and highlights snippets
+export interface SnippetOptions {
+  column?: "auto" | "name" | "content";
+  // 1 to 64
+  context_tokens?: number;
+  open_marker?: string;
+  close_marker?: string;
+  ellipsis?: string;
+}
+
+// Per-type result limits for search_entities with group_by_type
+export interface TypeLimits {
+  // Defaults to 3
+  per_type?: number;
+  overrides?: Partial<Record<EntityType, number>>;
+}
+
+// Quest board (kanban) from get_quest_board
+export interface QuestBoardCard {
+  id: string;
+  name: string;
+  plot_type: string;
+  objectives_total: number;
+  objectives_completed: number;
+  completion_percent: number | null;
+  giver_names: string[];
+  last_session: {
+    id: string;
+    session_number: number;
+    title: string | null;
+  } | null;
+}
+
+export interface QuestBoard {
+  columns: { status: string; quests: QuestBoardCard[] }[];
+}
+
+// Hero inventory entry from get_hero_inventory
+export interface InventoryEntry {
+  id: string;
+  hero_id: string;
+  item_id: string;
+  item_name: string;
+  item_type: string;
+  rarity: string | null;
+  requires_attunement: boolean;
+  quantity: number;
+  equipped: boolean;
+  attuned: boolean;
+  updated_at: string;
+}
+
+// Hero carrying an item, from find_item_holders
+export interface ItemHolder {
+  hero_id: string;
+  hero_name: string;
+  player_id: string | null;
+  hero_is_active: boolean;
+  quantity: number;
+  equipped: boolean;
+  attuned: boolean;
+}
+
+// Location or item an organization controls, from list_organization_holdings
+export interface OrganizationHoldingRecord {
+  id: string;
+  organization_id: string;
+  holding_type: "location" | "item";
+  holding_id: string;
+  holding_name: string;
+  holding_kind: string;
+  notes: string | null;
+  created_at: string;
+}
+
+// Where a character is now, from move_character and list_characters_at_location
+export interface WhereaboutsRecord {
+  id: string;
+  character_id: string;
+  character_name: string;
+  location_id: string;
+  location_name: string;
+  since: InWorldDate | null;
+  since_display: string | null;
+  notes: string | null;
+  updated_at: string;
+}
+
+// Candidate session date, from suggest_session_dates
+export interface SessionDateSuggestion {
+  date: string;
+  weekday: string;
+  available_player_ids: string[];
+  unavailable_player_ids: string[];
+}
+
+// Player absence from a session, with the player's name resolved
+export interface SessionAbsenceRecord {
+  id: string;
+  session_id: string;
+  player_id: string;
+  player_name: string;
+  reason: string | null;
+  created_at: string;
+}
+
+// Session play clock, from start/pause/stop_session_clock
+export interface SessionClock {
+  session_id: string;
+  session_number: number;
+  title: string | null;
+  state: "not_started" | "running" | "paused" | "stopped";
+  started_at: string | null;
+  ended_at: string | null;
+  breaks: SessionBreak[];
+  played_minutes: number;
+  break_minutes: number;
+}
+
+// Campaign play time totals over stopped sessions
+export interface PlayTimeReport {
+  campaign_id: string;
+  clocked_sessions: number;
+  total_minutes: number;
+  total_hours: number;
+  average_minutes: number | null;
+  longest_minutes: number | null;
+  shortest_minutes: number | null;
+  sessions: SessionClock[];
+}
+
+// Prep checklist for a session; `session.planned_content` holds the saved copy
+export interface SessionPrep {
+  expected_locations: { id: string; name: string }[];
+  open_quests: { id: string; name: string; status: string }[];
+  secrets: {
+    id: string;
+    title: string;
+    related_entity_type: string | null;
+    related_entity_id: string | null;
+  }[];
+  session: Sessions;
+}
+
+// At-the-table view of a session from get_gm_screen
+export interface GmEntity {
+  entity_type: string;
+  id: string;
+  name: string;
+}
+
+export interface GmScreen {
+  session: Sessions;
+  // Headings of the plan with the entities mentioned under each
+  scenes: { title: string; entities: GmEntity[] }[];
+  pinned: GmEntity[];
+  active_quests: { id: string; name: string; status: string }[];
+  party: {
+    hero_id: string;
+    name: string;
+    player_name: string | null;
+    lineage: string | null;
+    classes: string | null;
+    absent: boolean;
+    item_count: number;
+  }[];
+  // Suggested order only; heroes have no modifier
+  initiative: {
+    entity_type: "hero" | "character";
+    id: string;
+    name: string;
+    modifier: number | null;
+  }[];
+  clock: SessionClock;
+  // Unfilled progress clocks, closest to complete first
+  progress_clocks: ProgressClock[];
+  reminders: string[];
+}
+
+// A Blades-style progress clock, optionally tracking a faction or quest
+export interface ProgressClock {
+  id: string;
+  campaign_id: string;
+  name: string;
+  description: string | null;
+  segments: number;
+  filled: number;
+  complete: boolean;
+  entity_type: "organization" | "quest" | null;
+  entity_id: string | null;
+  entity_name: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export type TimeOfDay = "day" | "night";
+export type Season = "spring" | "summer" | "autumn" | "winter";
+
+// A weighted row on an encounter table. Conditions left null fit any time.
+export interface EncounterEntry {
+  id: string;
+  table_id: string;
+  name: string;
+  character_id: string | null;
+  // Dice such as "2d6" or "1d4+1", or a plain count
+  quantity: string | null;
+  weight: number;
+  time_of_day: TimeOfDay | null;
+  season: Season | null;
+  notes: string | null;
+}
+
+// Random encounters for a region, entries heaviest first
+export interface EncounterTable {
+  id: string;
+  campaign_id: string;
+  location_id: string;
+  name: string;
+  description: string | null;
+  entries: EncounterEntry[];
+  created_at: string;
+  updated_at: string;
+}
+
+export interface EncounterContext {
+  time_of_day?: TimeOfDay;
+  season?: Season;
+  session_id?: string;
+}
+
+// A roll logged to a session
+export interface EncounterRoll {
+  id: string;
+  session_id: string;
+  location_id: string | null;
+  table_id: string | null;
+  entry_id: string | null;
+  result: string;
+  count: number | null;
+  time_of_day: TimeOfDay | null;
+  season: Season | null;
+  created_at: string;
+}
+
+// The location is the one whose tables were used, which may be a parent of
+// the one rolled for
+export interface EncounterResult {
+  location_id: string;
+  location_name: string;
+  table_id: string;
+  table_name: string;
+  entry: EncounterEntry;
+  count: number | null;
+  result: string;
+  roll: EncounterRoll | null;
+}
+
+// An entry on the campaign's price list, in the smallest coin
+export interface PriceEntry {
+  id: string;
+  campaign_id: string;
+  name: string;
+  category: string;
+  item_id: string | null;
+  base_price: number;
+  notes: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+// A markup (positive percent) or discount set at a location
+export interface PriceModifier {
+  id: string;
+  campaign_id: string;
+  location_id: string;
+  category: string | null;
+  price_id: string | null;
+  percent: number;
+  reason: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface AppliedPriceModifier {
+  id: string;
+  location_id: string;
+  location_name: string;
+  category: string | null;
+  price_id: string | null;
+  percent: number;
+  reason: string | null;
+}
+
+// What something costs at a location, with the modifiers that went into it,
+// nearest place first
+export interface LocalPrice {
+  price: PriceEntry;
+  location_id: string;
+  location_name: string;
+  base_price: number;
+  local_price: number;
+  modifiers: AppliedPriceModifier[];
+}
+
+export type GoalStatus = "active" | "achieved" | "failed" | "abandoned";
+
+// Something an organization is working toward
+export interface OrganizationGoal {
+  id: string;
+  campaign_id: string;
+  organization_id: string;
+  title: string;
+  description: string | null;
+  status: GoalStatus;
+  clock: ProgressClock | null;
+  created_at: string;
+  updated_at: string;
+}
+
+// One logged step on a goal: clock ticks, a status change, or both
+export interface GoalAdvance {
+  id: string;
+  goal_id: string;
+  ticks: number;
+  filled: number | null;
+  segments: number | null;
+  status: GoalStatus | null;
+  note: string | null;
+  created_at: string;
+}
+
+export interface GoalProgress {
+  goal_id: string;
+  title: string;
+  status: GoalStatus;
+  ticks: number;
+  filled: number | null;
+  segments: number | null;
+  status_changes: GoalStatus[];
+  notes: string[];
+}
+
+export interface FactionProgress {
+  organization_id: string;
+  organization_name: string;
+  goals: GoalProgress[];
+}
+
+// What the factions did off-screen. `since` is null when the window covers
+// the whole campaign.
+export interface WorldMovesOnReport {
+  campaign_id: string;
+  since: string | null;
+  since_session_number: number | null;
+  organizations: FactionProgress[];
+  lines: string[];
+}
+
+// A session a character appeared in, for their roleplay card
+export interface RoleplayInteraction {
+  session_id: string;
+  session_number: number;
+  session_title: string | null;
+  occurred_at: string;
+  // Passages of the session's notes that mention the character, as markdown
+  excerpts: string[];
+  disposition_change: number;
+  disposition_reasons: string[];
+}
+
+// A compact card for voicing a character at the table
+export interface RoleplayCard {
+  character_id: string;
+  name: string;
+  aliases: string[];
+  lineage: string | null;
+  occupation: string | null;
+  is_alive: boolean;
+  accent: string | null;
+  mannerisms: string[];
+  catchphrases: string[];
+  speech_sample: string | null;
+  voice_notes: string | null;
+  personality: string | null;
+  motivations: string | null;
+  disposition: number;
+  // Newest first
+  recent_interactions: RoleplayInteraction[];
+}
+
+// A line is kept out of the game; a veil happens off-screen
+export type ContentWarningSeverity = "line" | "veil";
+
+export interface ContentWarning {
+  id: string;
+  campaign_id: string;
+  topic: string;
+  severity: ContentWarningSeverity;
+  // Other words that signal the topic
+  keywords: string[];
+  notes: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+// A field that mentions a line or veil's topic or one of its keywords
+export interface ContentFlag {
+  entity_type: string;
+  entity_id: string;
+  entity_name: string | null;
+  field: string;
+  warning_id: string;
+  topic: string;
+  severity: ContentWarningSeverity;
+  matched: string;
+  // Plain text around the match
+  excerpt: string;
+}
+
+// A co-GM's comment on an entity, or on one field of it
+export interface Annotation {
+  id: string;
+  campaign_id: string;
+  entity_type: string;
+  entity_id: string;
+  // Null for a comment on the entity as a whole
+  field: string | null;
+  body: string;
+  author: string | null;
+  // Null while the annotation is open
+  resolved_at: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface OpenAnnotation extends Annotation {
+  entity_name: string | null;
+}
+
+// An entity with the local profiles that created and last changed it
+export interface AuthoredEntity {
+  entity_type: string;
+  entity_id: string;
+  name: string | null;
+  created_by: string | null;
+  modified_by: string | null;
+  modified_at: string;
+}
+
+// Players' recap of a session, saved as a handout. `used_prose` is false
+// when the session summary stood in for AI-written prose.
+export interface PlayerRecap {
+  handout: Handouts;
+  markdown: string;
+  used_prose: boolean;
+  path: string | null;
+}
+
+// Options for export_timeline_image. Era bounds are in sort order units.
+export interface TimelineImageOptions {
+  branch_id?: string;
+  public_only?: boolean;
+  width?: number;
+  eras?: { name: string; start: number; end: number }[];
+  path?: string;
+}
+
+export interface TimelineImage {
+  svg: string;
+  event_count: number;
+  path: string | null;
+}
+
+// Imported transcript; VTT cues are flattened to "[hh:mm:ss] Speaker: text"
+export interface SessionTranscriptRecord {
+  id: string;
+  campaign_id: string;
+  session_id: string;
+  file_name: string;
+  format: "text" | "vtt";
+  content: string;
+  word_count: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface TranscriptChunk {
+  index: number;
+  start: string | null;
+  end: string | null;
+  text: string;
+}
+
+export interface TranscriptMention {
+  entity_type: EntityType;
+  entity_id: string;
+  name: string;
+  occurrences: number;
+}
+
+// One player's copy of a handout, with names resolved
+export interface HandoutDeliveryRecord {
+  id: string;
+  handout_id: string;
+  handout_title: string;
+  player_id: string;
+  player_name: string;
+  status: "sent" | "read";
+  sent_at: string;
+  read_at: string | null;
+}
+
+export interface SecretRecord extends Secrets {
+  prerequisite_ids: string[];
+  /** Unrevealed, with every prerequisite already revealed */
+  revealable: boolean;
+}
+
+export interface DispositionEntry {
+  id: string;
+  character_id: string;
+  session_id: string | null;
+  value: number;
+  change: number;
+  reason: string | null;
+  created_at: string;
+}
+
+export interface DispositionHistory {
+  character_id: string;
+  current: number;
+  entries: DispositionEntry[];
+}
+
+export interface CharacterDisposition {
+  character_id: string;
+  character_name: string;
+  value: number;
+  updated_at: string;
+}
+
+export interface KnownSecret {
+  id: string;
+  title: string;
+  content: string;
+  revealed_in_session: number | null;
+  related_entity_type: EntityType | null;
+  related_entity_id: string | null;
+  related_entity_name: string | null;
+}
+
+export interface KnownRelationship {
+  id: string;
+  source_type: EntityType;
+  source_id: string;
+  source_name: string | null;
+  relationship_type: string;
+  target_type: EntityType;
+  target_id: string;
+  target_name: string | null;
+  is_bidirectional: boolean;
+  description: string | null;
+}
+
+export interface KnownTimelineEvent {
+  id: string;
+  date_display: string;
+  title: string;
+  description: string | null;
+  significance: string;
+}
+
+export interface KnownHandout {
+  id: string;
+  title: string;
+  content: string | null;
+  recipients: string[];
+  first_sent_at: string;
+}
+
+/** Everything the players have learned, for skimming before a recap */
+export interface PlayerKnowledgeReport {
+  campaign_id: string;
+  secrets: KnownSecret[];
+  relationships: KnownRelationship[];
+  timeline_events: KnownTimelineEvent[];
+  handouts: KnownHandout[];
+}
+
+// Word counts from get_content_stats
+export interface EntityWordCount {
+  entity_type: string;
+  entity_id: string;
+  name: string | null;
+  word_count: number;
+}
+
+export interface TypeWordCount {
+  entity_type: string;
+  entity_count: number;
+  word_count: number;
+}
+
+export interface ContentStats {
+  campaign_id: string;
+  total_words: number;
+  by_type: TypeWordCount[];
+  entities: EntityWordCount[];
+}
+
+export interface EntityCounts {
+  campaign_id: string;
+  counts: Record<string, number>;
+  total: number;
+}
+
+export interface EntityExistence {
+  entity_type: EntityType;
+  entity_id: string;
+  exists: boolean;
+  campaign_id: string | null;
+}
+
+export interface AffectedEntity {
+  entity_type: string;
+  entity_id: string;
+  name: string | null;
+}
+
+export interface DeletePreview {
+  entity_type: string;
+  entity_id: string;
+  name: string | null;
+  cascaded: Record<string, number>;
+  detached: AffectedEntity[];
+  relationships: number;
+  tags: number;
+  secrets: number;
+  mentions: AffectedEntity[];
+  search_rows: number;
+}
+
+export interface OrphanedReferences {
+  relationships: number;
+  entity_tags: number;
+  secrets: number;
+  attachments: number;
+  recall_cards: number;
+  annotations: number;
+  progress_clocks: number;
+  repaired: boolean;
+}
+
+export type ActivityKind =
+  | "created"
+  | "updated"
+  | "relationship_added"
+  | "relationship_updated"
+  | "secret_added"
+  | "secret_revealed"
+  | "session_appearance";
+
+export interface ActivityEntry {
+  kind: ActivityKind;
+  occurred_at: string;
+  summary: string;
+  related_type: EntityType | null;
+  related_id: string | null;
+  related_name: string | null;
+  session_number: number | null;
+}
+
+export interface EntityDetail {
+  entity_type: string;
+  entity_id: string;
+  name: string | null;
+  /** 0 (stub) to 5 (fully detailed), derived from the written content */
+  detail_level: number;
+  detail_label: string;
+  word_count: number;
+  filled_fields: number;
+  total_fields: number;
+}
+
+export interface StubEntry extends EntityDetail {
+  reference_count: number;
+}
+
+export type WorldbuildingGapKind =
+  | "no_relationships"
+  | "empty_description"
+  | "no_objectives"
+  | "no_tags"
+  | "no_parent";
+
+export interface GapEntry {
+  entity_type: "character" | "location" | "organization" | "quest";
+  entity_id: string;
+  name: string;
+  gaps: WorldbuildingGapKind[];
+  priority: number;
+}
+
+export interface WorldbuildingGaps {
+  campaign_id: string;
+  counts: Partial<Record<WorldbuildingGapKind, number>>;
+  entities: GapEntry[];
+}
+
+export type SrdKind = "monster" | "spell" | "magic_item";
+
+export interface SrdEntry {
+  kind: SrdKind;
+  slug: string;
+  name: string;
+  summary: string | null;
+  data: Record<string, unknown>;
+}
+
+export interface SrdSearchResponse {
+  results: SrdEntry[];
+  offline: boolean;
+}
+
+export interface SrdImportResponse {
+  entity_type: "character" | "item";
+  entity_id: string;
+  name: string;
+}
+
+export interface CsvExportSummary {
+  path: string;
+  rows: number;
+}
+
+// An archived attachment that couldn't be brought in on import
+export interface UnresolvedAttachment {
+  id: string;
+  entity_type: string;
+  entity_id: string;
+  file_name: string;
+  reason: "missing_entity" | "missing_file" | "conflict";
+}
+
+// How an archive's attachments were matched against local files by hash
+export interface AttachmentReport {
+  imported: number;
+  deduplicated: number;
+  repaired: number;
+  unresolved: UnresolvedAttachment[];
+}
+
+export type RowOutcome = "created" | "skipped" | "failed";
+
+// Payload of the "import-progress" event, sent once per imported row
+export interface ImportProgress {
+  source: string;
+  processed: number;
+  total: number;
+  entity_type: string;
+  outcome: RowOutcome;
+}
+
+export interface RowIssue {
+  entity_type: string;
+  id: string;
+  name: string | null;
+  reason: string;
+}
+
+export interface ImportReport {
+  total: number;
+  // Rows created, by entity type
+  created: Record<string, number>;
+  skipped: RowIssue[];
+  failed: RowIssue[];
+}
+
+// A campaign archive written or read back, with row counts by entity type
+export interface ArchiveSummary {
+  path: string;
+  campaign_id: string;
+  campaign_name: string;
+  counts: Record<string, number>;
+  // Set when the archive was imported or unarchived
+  attachments: AttachmentReport | null;
+  // Set when the archive was imported
+  rows: ImportReport | null;
+}
+
+// Result of import_markdown_folder
+export interface MarkdownImportEntry {
+  // Relative to the imported folder
+  path: string;
+  entity_type: EntityType;
+  name: string;
+  status: "create" | "created" | "exists";
+  // null for entities a dry run would create
+  entity_id: string | null;
+}
+
+export interface MarkdownImportReport {
+  // false for a dry run
+  applied: boolean;
+  files_scanned: number;
+  entities: MarkdownImportEntry[];
+  relationships: number;
+  tags: number;
+  warnings: { path: string; message: string }[];
+}
+
+// Where a campaign's plaintext mirror is written (local to this device)
+export interface MirrorSettings {
+  campaign_id: string;
+  enabled: boolean;
+  path: string | null;
+  auto_commit: boolean;
+  last_mirrored_at: string | null;
+}
+
+export interface MirrorReport {
+  // Nothing changed since the last run, so the folder was left alone
+  up_to_date: boolean;
+  written: string[];
+  removed: string[];
+  // Hash of the commit made when auto-commit is on
+  commit: string | null;
+}
+
+// Selected entities rendered for another tool (Roll20, Homebrewery)
+export interface JournalExport {
+  format: string;
+  content: string;
+  entity_count: number;
+  // Where the export was written, if a path was given
+  path: string | null;
+}
+
+// A person in a family tree (characters and heroes can both hold kinship)
+export interface FamilyMember {
+  entity_type: "character" | "hero";
+  id: string;
+  name: string;
+  is_alive: boolean | null;
+}
+
+export interface FamilyTreeNode {
+  member: FamilyMember;
+  spouses: FamilyMember[];
+  // Parents when walking ancestors, children when walking descendants
+  relatives: FamilyTreeNode[];
+}
+
+export interface FamilyTree {
+  root: FamilyMember;
+  depth: number;
+  spouses: FamilyMember[];
+  siblings: FamilyMember[];
+  ancestors: FamilyTreeNode[];
+  descendants: FamilyTreeNode[];
+}
+
+// Two entities that mention each other but aren't related yet
+export interface RelationshipSuggestion {
+  source_type: EntityType;
+  source_id: string;
+  source_name: string;
+  target_type: EntityType;
+  target_id: string;
+  target_name: string;
+  source_mentions: number;
+  target_mentions: number;
+  relationship_type: string;
+  is_bidirectional: boolean;
+}
+
+export interface StrengthEntry {
+  id: string;
+  relationship_id: string;
+  session_id: string | null;
+  value: number;
+  change: number;
+  reason: string | null;
+  /** Made by a decay rule rather than the GM */
+  is_decay: boolean;
+  created_at: string;
+}
+
+export interface StrengthHistory {
+  relationship_id: string;
+  current: number | null;
+  entries: StrengthEntry[];
+}
+
+// Unreinforced relationships drift `step` toward 0 every `after_days` idle days
+export interface DecayRule {
+  after_days: number;
+  step: number;
+}
+
+// A flashcard reviewed on the SM-2 schedule
+export interface RecallCard {
+  id: string;
+  campaign_id: string;
+  entity_type: EntityType | null;
+  entity_id: string | null;
+  /** Field a generated card was made from; null when written by hand */
+  source_field: string | null;
+  front: string;
+  back: string;
+  ease_factor: number;
+  interval_days: number;
+  repetitions: number;
+  due_at: string;
+  last_reviewed_at: string | null;
+  last_grade: number | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface RecallGenerateReport {
+  created: number;
+  /** Generated cards updated because their source changed */
+  refreshed: number;
+}
+
+// Campaign creation wizard answers for bootstrap_campaign
+export type LocationTemplate = "kingdom" | "city" | "frontier" | "none";
+
+export interface FactionSeed {
+  name: string;
+  org_type?: string;
+  description?: string;
+}
+
+export interface BootstrapConfig {
+  name: string;
+  description?: string;
+  system?: string;
+  // "kingdom" when unset
+  location_template?: LocationTemplate;
+  // Name for the template's top location
+  world_name?: string;
+  factions?: FactionSeed[];
+  // Default tags when unset, none when empty
+  tags?: string[];
+  // Adds a session 0 entry unless false
+  session_zero?: boolean;
+  session_zero_date?: string;
+  settings?: CampaignSettings;
+}
+
+export interface BootstrapReport {
+  campaign: Campaigns;
+  // Rows created, keyed by entity type
+  counts: Record<string, number>;
+  session_zero_id: string | null;
+}
+
+// Tag suggestions from suggest_tags; `matched` is the text that triggered it
+export interface ExistingTagSuggestion {
+  tag: Tags;
+  matched: string;
+}
+
+export interface NewTagSuggestion {
+  name: string;
+  matched: string;
+}
+
+export interface TagSuggestions {
+  entity_type: EntityType;
+  entity_id: string;
+  campaign_id: string;
+  existing: ExistingTagSuggestion[];
+  new_tags: NewTagSuggestion[];
+  // Name and readable text that were analyzed
+  text: string;
+}
+
+// Two entities whose names look like the same thing
+export interface DuplicateCandidate {
+  entity_type: EntityType;
+  first_id: string;
+  first_name: string;
+  second_id: string;
+  second_name: string;
+  similarity: number;
+  exact: boolean;
+}
+
+export interface EntityMergeReport {
+  entity_type: EntityType;
+  target_id: string;
+  source_id: string;
+  mentions_updated: number;
+}
+
+// Color (#rgb or #rrggbb) and icon name chosen for a character, location,
+// organization or quest
+export interface EntityAppearance {
+  entity_type: EntityType;
+  entity_id: string;
+  color: string | null;
+  icon: string | null;
+}
+
+// An entity ranked by get_related_entities for a sidebar panel
+export interface RelatedEntity {
+  entity_type: EntityType;
+  entity_id: string;
+  name: string;
+  // Combined score, 0 to 1
+  score: number;
+  // Embedding similarity, 0 to 1; null when no embedding provider was given
+  text_similarity: number | null;
+  shared_tags: string[];
+  // Relationship hops away, if within two
+  relationship_distance: number | null;
+}
+
+export interface FindReplaceOptions {
+  case_sensitive?: boolean;
+  whole_word?: boolean;
+  include_names?: boolean;
+  entity_types?: EntityType[];
+  dry_run?: boolean;
+}
+
+// One field containing the search text, with a before/after excerpt
+export interface FindReplaceFieldMatch {
+  field: string;
+  occurrences: number;
+  before: string;
+  after: string;
+}
+
+export interface FindReplaceReport {
+  applied: boolean;
+  total_occurrences: number;
+  entities: {
+    entity_type: EntityType;
+    entity_id: string;
+    name: string | null;
+    fields: FindReplaceFieldMatch[];
+  }[];
+}
+
+// Two timelines side by side; a null branch id is the main timeline
+export interface TimelineComparison {
+  left_branch_id: string | null;
+  right_branch_id: string | null;
+  shared: TimelineEvents[];
+  only_left: TimelineEvents[];
+  only_right: TimelineEvents[];
+}
+
 /**
  * Fields that contain ProseMirror/TipTap JSON content.
  * These fields should be converted to/from markdown when interfacing with AI.
@@ -92,3 +1325,32 @@ export const RICH_TEXT_FIELDS = new Set([
   "content",
   "reveal_conditions",
 ]);
+
+export type PromptContextType =
+  | "general"
+  | "character"
+  | "location"
+  | "organization"
+  | "quest"
+  | "hero"
+  | "item"
+  | "session";
+
+// Prompt template with the `{{placeholder}}` names it uses
+export interface PromptTemplateRecord {
+  id: string;
+  campaign_id: string;
+  name: string;
+  template: string;
+  context_type: PromptContextType;
+  variables: string[];
+  created_at: string;
+  updated_at: string;
+}
+
+export interface RenderedPrompt {
+  template_id: string;
+  name: string;
+  context_type: PromptContextType;
+  prompt: string;
+}